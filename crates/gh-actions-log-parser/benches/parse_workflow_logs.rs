@@ -0,0 +1,60 @@
+//! Times parsing a large workflow log archive.
+//!
+//! The job log fixture is repeated until it has at least `LOG_LINES` lines and
+//! packed into a ZIP like the one returned by the GitHub API.
+//!
+//! Run with `cargo bench -p gh-actions-log-parser`.
+
+use gh_actions_log_parser::{job_log_to_tree, parse_workflow_logs};
+use std::hint::black_box;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+const FIXTURE: &str = include_str!("../tests/fixtures/job-logs.txt");
+const LOG_LINES: usize = 100_000;
+const JOBS: usize = 4;
+const ITERATIONS: u32 = 5;
+
+fn build_zip() -> Vec<u8> {
+    let fixture_lines = FIXTURE.lines().count();
+    let repeats = (LOG_LINES / JOBS).div_ceil(fixture_lines);
+    let job_log = FIXTURE.repeat(repeats);
+
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default();
+    for job in 0..JOBS {
+        zip.start_file(format!("{job}_build ({job}).txt"), options)
+            .expect("in-memory zip");
+        zip.write_all(job_log.as_bytes()).expect("in-memory zip");
+    }
+    zip.finish().expect("in-memory zip").into_inner()
+}
+
+fn main() {
+    let archive = build_zip();
+
+    let mut total = Duration::ZERO;
+    let mut fastest = Duration::MAX;
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        let parsed = parse_workflow_logs(&archive).expect("fixture archive parses");
+        black_box(
+            parsed
+                .jobs
+                .into_iter()
+                .map(job_log_to_tree)
+                .collect::<Vec<_>>(),
+        );
+        let elapsed = start.elapsed();
+        total += elapsed;
+        fastest = fastest.min(elapsed);
+    }
+
+    println!(
+        "parse_workflow_logs + job_log_to_tree ({} jobs, ~{} lines): mean {:.2?}, min {:.2?}",
+        JOBS,
+        LOG_LINES,
+        total / ITERATIONS,
+        fastest
+    );
+}
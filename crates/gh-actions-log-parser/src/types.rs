@@ -246,6 +246,51 @@ impl LogLine {
     pub fn should_display(&self) -> bool {
         !self.is_metadata
     }
+
+    /// Approximate memory used by this line in bytes (struct and owned text)
+    pub fn approx_bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.content.capacity()
+            + self.display_content.capacity()
+            + optional_bytes(&self.timestamp)
+            + optional_bytes(&self.group_title)
+            + self
+                .styled_segments
+                .iter()
+                .map(|segment| std::mem::size_of::<StyledSegment>() + segment.text.capacity())
+                .sum::<usize>()
+            + self.command.as_ref().map_or(0, WorkflowCommand::heap_bytes)
+    }
+}
+
+impl WorkflowCommand {
+    /// Bytes of the owned text of the command
+    fn heap_bytes(&self) -> usize {
+        match self {
+            Self::GroupStart { title } => title.capacity(),
+            Self::GroupEnd => 0,
+            Self::Debug { message } => message.capacity(),
+            Self::Error { message, params }
+            | Self::Warning { message, params }
+            | Self::Notice { message, params } => {
+                message.capacity() + optional_bytes(&params.file) + optional_bytes(&params.title)
+            }
+        }
+    }
+}
+
+/// Bytes of an optional owned string
+fn optional_bytes(text: &Option<String>) -> usize {
+    text.as_ref().map_or(0, String::capacity)
+}
+
+impl JobLog {
+    /// Approximate memory used by this job log in bytes
+    pub fn approx_bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.name.capacity()
+            + self.lines.iter().map(LogLine::approx_bytes).sum::<usize>()
+    }
 }
 
 impl StyledSegment {
@@ -320,6 +365,33 @@ impl LogTree {
     }
 }
 
+impl WorkflowNode {
+    /// Approximate memory used by this workflow and all its logs in bytes
+    pub fn approx_bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.name.capacity()
+            + self.jobs.iter().map(JobNode::approx_bytes).sum::<usize>()
+    }
+}
+
+impl JobNode {
+    /// Approximate memory used by this job and all its logs in bytes
+    pub fn approx_bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.name.capacity()
+            + self.steps.iter().map(StepNode::approx_bytes).sum::<usize>()
+    }
+}
+
+impl StepNode {
+    /// Approximate memory used by this step and its lines in bytes
+    pub fn approx_bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.name.capacity()
+            + self.lines.iter().map(LogLine::approx_bytes).sum::<usize>()
+    }
+}
+
 impl Default for LogTree {
     fn default() -> Self {
         Self::new()
@@ -138,3 +138,23 @@ fn test_parse_fixture_file() {
     println!("  - [command] prefixes are removed from display");
     println!("  - Command lines are marked for special styling");
 }
+
+#[test]
+fn test_approx_bytes_covers_log_text() {
+    let content = std::fs::read("tests/fixtures/job-logs.txt").unwrap();
+
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::FileOptions::<()>::default();
+    zip.start_file("test.txt", options).unwrap();
+    zip.write_all(&content).unwrap();
+    let zip_data = zip.finish().unwrap().into_inner();
+
+    let parsed = parse_workflow_logs(&zip_data).unwrap();
+    let job_log = parsed.jobs[0].clone();
+    let job_log_bytes = job_log.approx_bytes();
+    let job_node_bytes = job_log_to_tree(job_log).approx_bytes();
+
+    // Raw and display text are both kept, so the parsed log outweighs the input
+    assert!(job_log_bytes > content.len());
+    assert!(job_node_bytes > content.len());
+}
@@ -3,20 +3,36 @@
 //! Caches API responses to disk to avoid redundant API calls during
 //! frequent app restarts (common during development). Responses are
 //! cached with a 20-minute TTL and support ETags for efficient validation.
+//!
+//! The cache file is written on a background thread, so storing a response
+//! never waits for the disk. Workflow run log archives are cached separately
+//! by [`RunLogCache`].
+
+mod run_logs;
+mod writer;
+
+pub use run_logs::{RunLogCache, RunLogKey, DEFAULT_RUN_LOG_CACHE_BYTES};
 
 use ::log::{debug, warn};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
+use writer::DiskWriter;
 
 /// GitHub API response cache
 #[derive(Debug)]
 pub struct ApiCache {
-    cache_file: PathBuf,
     ttl_seconds: u64,
     entries: HashMap<String, CacheEntry>,
+    /// Lookups answered from the cache since startup
+    hits: AtomicU64,
+    /// Lookups that found no entry since startup
+    misses: AtomicU64,
+    /// Persists snapshots of the entries off the caller's thread
+    writer: DiskWriter<CacheFile>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,6 +62,33 @@ pub struct CacheStats {
     pub fresh_entries: usize,
     pub stale_entries: usize,
     pub ttl_seconds: u64,
+    /// Lookups answered from the cache since startup
+    pub hits: u64,
+    /// Lookups that found no entry since startup
+    pub misses: u64,
+}
+
+/// What is known about a cached response, without its body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheEntryInfo {
+    /// Cache key (`METHOD:{namespace}{url}?{params}`)
+    pub key: String,
+    /// Kind of response (e.g., "pulls", "pulls/comments", "commits/check-runs")
+    pub category: String,
+    /// Size of the cached body in bytes
+    pub size: usize,
+    /// Seconds since the response was stored or revalidated
+    pub age_seconds: u64,
+    /// Whether the entry is within the TTL
+    pub fresh: bool,
+}
+
+impl CacheStats {
+    /// Share of lookups answered from the cache (None before the first lookup)
+    pub fn hit_ratio(&self) -> Option<f64> {
+        let lookups = self.hits + self.misses;
+        (lookups > 0).then(|| self.hits as f64 / lookups as f64)
+    }
 }
 
 impl ApiCache {
@@ -84,12 +127,20 @@ impl ApiCache {
         );
 
         Ok(Self {
-            cache_file,
+            writer: DiskWriter::spawn(cache_file),
             ttl_seconds,
             entries,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
         })
     }
 
+    /// Use a different time to live (20 minutes by default)
+    pub fn with_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.ttl_seconds = ttl.as_secs();
+        self
+    }
+
     /// Get cached response if available and not stale
     ///
     /// Returns cached response with its ETag if entry is fresh (within TTL).
@@ -98,6 +149,7 @@ impl ApiCache {
         let key = self.cache_key(method, url, params);
 
         if let Some(entry) = self.entries.get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
             let age_seconds = self.current_timestamp() - entry.timestamp;
 
             if age_seconds < self.ttl_seconds {
@@ -125,15 +177,71 @@ impl ApiCache {
                 });
             }
         } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
             debug!("Cache MISS: {}", key);
         }
 
         None
     }
 
+    /// Get cached response only if it is fresh (within TTL)
+    ///
+    /// Unlike `get`, a stale entry is no answer and counts as a miss, for
+    /// callers without ETag validation.
+    pub fn get_fresh(
+        &self,
+        method: &str,
+        url: &str,
+        params: &[(&str, &str)],
+    ) -> Option<CachedResponse> {
+        let key = self.cache_key(method, url, params);
+        let fresh = self
+            .entries
+            .get(&key)
+            .filter(|entry| self.current_timestamp() - entry.timestamp < self.ttl_seconds);
+        let Some(entry) = fresh else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            debug!("Cache MISS (fresh): {}", key);
+            return None;
+        };
+
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        debug!("Cache HIT (fresh): {}", key);
+        Some(CachedResponse {
+            body: entry.response_body.clone(),
+            etag: entry.etag.clone(),
+            status_code: entry.status_code,
+        })
+    }
+
+    /// Get cached response and when it was stored, however old it is
+    ///
+    /// Unlike `get`, peeking is not counted as a lookup in the hit ratio,
+    /// e.g. for showing cached data while the fresh data loads.
+    pub fn peek(
+        &self,
+        method: &str,
+        url: &str,
+        params: &[(&str, &str)],
+    ) -> Option<(CachedResponse, SystemTime)> {
+        let key = self.cache_key(method, url, params);
+        let entry = self.entries.get(&key)?;
+        debug!("Cache PEEK: {}", key);
+
+        Some((
+            CachedResponse {
+                body: entry.response_body.clone(),
+                etag: entry.etag.clone(),
+                status_code: entry.status_code,
+            },
+            UNIX_EPOCH + std::time::Duration::from_secs(entry.timestamp),
+        ))
+    }
+
     /// Store response in cache
     ///
-    /// Persists the response body and ETag to disk for future requests.
+    /// Persists the response body and ETag to disk for future requests, in
+    /// the background (see [`ApiCache::flush`]).
     pub fn set(
         &mut self,
         method: &str,
@@ -155,7 +263,7 @@ impl ApiCache {
         debug!("Cache SET: {} (etag: {:?})", key, response.etag);
 
         // Persist to disk
-        self.save_to_disk()?;
+        self.save_to_disk();
 
         Ok(())
     }
@@ -171,7 +279,7 @@ impl ApiCache {
         if let Some(entry) = self.entries.get_mut(&key) {
             entry.timestamp = timestamp;
             debug!("Cache TOUCH: {} (TTL extended)", key);
-            self.save_to_disk()?;
+            self.save_to_disk();
         }
 
         Ok(())
@@ -182,15 +290,28 @@ impl ApiCache {
         let key = self.cache_key(method, url, params);
         if self.entries.remove(&key).is_some() {
             debug!("Cache INVALIDATE: {}", key);
-            let _ = self.save_to_disk();
+            self.save_to_disk();
         }
     }
 
+    /// Invalidate the entry stored under `key`, as listed by [`ApiCache::entries`]
+    ///
+    /// Returns false if there was no such entry.
+    pub fn invalidate_key(&mut self, key: &str) -> bool {
+        if self.entries.remove(key).is_none() {
+            return false;
+        }
+        debug!("Cache INVALIDATE: {}", key);
+        self.save_to_disk();
+        true
+    }
+
     /// Invalidate all entries matching a pattern (e.g., all PRs from a repo)
     ///
     /// Pattern is matched against cache keys using contains().
     /// Example: "/repos/acme/widget" invalidates all entries for that repo.
-    pub fn invalidate_pattern(&mut self, pattern: &str) {
+    /// Returns how many entries were invalidated.
+    pub fn invalidate_pattern(&mut self, pattern: &str) -> usize {
         let keys_to_remove: Vec<_> = self
             .entries
             .keys()
@@ -204,19 +325,25 @@ impl ApiCache {
         }
 
         if !keys_to_remove.is_empty() {
-            let _ = self.save_to_disk();
+            self.save_to_disk();
         }
+        keys_to_remove.len()
     }
 
     /// Clear entire cache
     pub fn clear(&mut self) -> Result<()> {
         let count = self.entries.len();
         self.entries.clear();
-        self.save_to_disk()?;
+        self.save_to_disk();
         debug!("Cache CLEARED ({} entries removed)", count);
         Ok(())
     }
 
+    /// Wait until every change so far is written to disk
+    pub fn flush(&self) {
+        self.writer.flush();
+    }
+
     /// Get cache statistics for debugging
     pub fn stats(&self) -> CacheStats {
         let total_entries = self.entries.len();
@@ -235,9 +362,28 @@ impl ApiCache {
             fresh_entries,
             stale_entries,
             ttl_seconds: self.ttl_seconds,
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
         }
     }
 
+    /// Metadata of every entry, in no particular order
+    ///
+    /// Bodies are only measured, never copied.
+    pub fn entries(&self) -> impl Iterator<Item = CacheEntryInfo> + '_ {
+        let now = self.current_timestamp();
+        self.entries.iter().map(move |(key, entry)| {
+            let age_seconds = now.saturating_sub(entry.timestamp);
+            CacheEntryInfo {
+                key: key.clone(),
+                category: category_of(key),
+                size: entry.response_body.len(),
+                age_seconds,
+                fresh: age_seconds < self.ttl_seconds,
+            }
+        })
+    }
+
     /// Check if cache is enabled via environment variable
     pub fn is_enabled() -> bool {
         std::env::var("DISABLE_API_CACHE")
@@ -285,21 +431,44 @@ impl ApiCache {
         Ok(cache_file.entries)
     }
 
-    fn save_to_disk(&self) -> Result<()> {
-        // Ensure parent directory exists
-        if let Some(parent) = self.cache_file.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-
-        let cache_file = CacheFile {
+    /// Queue a snapshot of the entries for the writer thread
+    fn save_to_disk(&self) {
+        self.writer.save(CacheFile {
             version: 1,
             entries: self.entries.clone(),
-        };
-
-        let content = serde_json::to_string_pretty(&cache_file)?;
-        std::fs::write(&self.cache_file, content)?;
+        });
+    }
+}
 
-        Ok(())
+/// Kind of response cached under `key`
+///
+/// The first path segment below the repository, plus the last one of nested
+/// resources: `GET:/repos/acme/api/pulls/12/comments` is "pulls/comments".
+/// Paths outside a repository use their first segment, the repository itself
+/// is "repo".
+fn category_of(key: &str) -> String {
+    let path = key.split('?').next().unwrap_or(key);
+    let segments: Vec<&str> = match path.find("/repos/") {
+        Some(start) => path[start..]
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .skip(3)
+            .collect(),
+        None => path
+            .find('/')
+            .map(|start| {
+                path[start..]
+                    .split('/')
+                    .filter(|segment| !segment.is_empty())
+                    .take(1)
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+    match segments.as_slice() {
+        [] => "repo".to_string(),
+        [first] | [first, _] => first.to_string(),
+        [first, .., last] => format!("{}/{}", first, last),
     }
 }
 
@@ -308,9 +477,11 @@ impl Default for ApiCache {
         // Fallback to in-memory only cache if no path provided
         warn!("Using default ApiCache with temp path - call new() with proper path instead");
         Self {
-            cache_file: std::env::temp_dir().join("gh-api-cache.json"),
+            writer: DiskWriter::spawn(std::env::temp_dir().join("gh-api-cache.json")),
             ttl_seconds: 20 * 60,
             entries: HashMap::new(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
         }
     }
 }
@@ -391,6 +562,52 @@ mod tests {
         assert_eq!(stale.unwrap().etag, Some("abc".into()));
     }
 
+    #[test]
+    fn test_cache_get_fresh() {
+        let cache_file = std::env::temp_dir().join("gh-api-cache-test-fresh.json");
+        let mut cache = ApiCache::new(cache_file).unwrap();
+        let response = CachedResponse {
+            body: "test".into(),
+            etag: None,
+            status_code: 200,
+        };
+        cache.set("GET", "/test", &[], &response).unwrap();
+
+        assert_eq!(cache.get_fresh("GET", "/test", &[]).unwrap().body, "test");
+        assert!(cache.get_fresh("GET", "/other", &[]).is_none());
+
+        // Stale entries are misses, though `get` still returns them
+        let cache = cache.with_ttl(Duration::ZERO);
+        assert!(cache.get_fresh("GET", "/test", &[]).is_none());
+        assert!(cache.get("GET", "/test", &[]).is_some());
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (2, 2));
+    }
+
+    #[test]
+    fn test_cache_peek() {
+        let cache_file = std::env::temp_dir().join("gh-api-cache-test-peek.json");
+        let mut cache = ApiCache::new(cache_file).unwrap();
+        cache.ttl_seconds = 0; // Every entry is stale
+
+        let response = CachedResponse {
+            body: "test".into(),
+            etag: None,
+            status_code: 200,
+        };
+        let before = SystemTime::now() - Duration::from_secs(1);
+        cache.set("GET", "/test", &[], &response).unwrap();
+
+        let (peeked, cached_at) = cache.peek("GET", "/test", &[]).expect("Cache miss");
+        assert_eq!(peeked.body, "test");
+        assert!(cached_at >= before && cached_at <= SystemTime::now());
+        assert!(cache.peek("GET", "/other", &[]).is_none());
+
+        // Peeking doesn't count as a lookup
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (0, 0));
+    }
+
     #[test]
     fn test_cache_invalidate() {
         let cache_file = std::env::temp_dir().join("gh-api-cache-test-invalidate.json");
@@ -432,13 +649,96 @@ mod tests {
 
         assert_eq!(cache.entries.len(), 3);
 
-        cache.invalidate_pattern("/repos/acme/widget");
+        assert_eq!(cache.invalidate_pattern("/repos/acme/widget"), 2);
 
         // Should have removed 2 entries, leaving 1
         assert_eq!(cache.entries.len(), 1);
         assert!(cache.get("GET", "/repos/acme/other/pulls", &[]).is_some());
     }
 
+    #[test]
+    fn test_cache_invalidate_key() {
+        let cache_file = std::env::temp_dir().join("gh-api-cache-test-invalidate-key.json");
+        let mut cache = ApiCache::new(cache_file).unwrap();
+        let response = CachedResponse {
+            body: "test".into(),
+            etag: None,
+            status_code: 200,
+        };
+        cache
+            .set(
+                "GET",
+                "/repos/acme/api/pulls",
+                &[("state", "open")],
+                &response,
+            )
+            .unwrap();
+
+        assert!(cache.invalidate_key("GET:/repos/acme/api/pulls?state=open"));
+        assert!(!cache.invalidate_key("GET:/repos/acme/api/pulls?state=open"));
+        assert!(cache
+            .get("GET", "/repos/acme/api/pulls", &[("state", "open")])
+            .is_none());
+    }
+
+    #[test]
+    fn test_cache_entries() {
+        let cache_file = std::env::temp_dir().join("gh-api-cache-test-entries.json");
+        let mut cache = ApiCache::new(cache_file).unwrap();
+        cache.ttl_seconds = 60;
+        let response = CachedResponse {
+            body: "[1,2,3]".into(),
+            etag: None,
+            status_code: 200,
+        };
+        cache
+            .set("GET", "/repos/acme/api/pulls/12/comments", &[], &response)
+            .unwrap();
+        cache.entries.insert(
+            "GET:ghe.corp:/repos/acme/api/commits/abc/check-runs".into(),
+            CacheEntry {
+                response_body: "{}".into(),
+                timestamp: cache.current_timestamp() - 100,
+                etag: None,
+                status_code: 200,
+            },
+        );
+
+        let mut entries: Vec<_> = cache.entries().collect();
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+        assert_eq!(
+            entries,
+            vec![
+                CacheEntryInfo {
+                    key: "GET:/repos/acme/api/pulls/12/comments".into(),
+                    category: "pulls/comments".into(),
+                    size: 7,
+                    age_seconds: 0,
+                    fresh: true,
+                },
+                CacheEntryInfo {
+                    key: "GET:ghe.corp:/repos/acme/api/commits/abc/check-runs".into(),
+                    category: "commits/check-runs".into(),
+                    size: 2,
+                    age_seconds: 100,
+                    fresh: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_category_of() {
+        assert_eq!(category_of("GET:/repos/acme/api"), "repo");
+        assert_eq!(category_of("GET:/repos/acme/api/pulls?state=open"), "pulls");
+        assert_eq!(category_of("GET:/repos/acme/api/pulls/12"), "pulls");
+        assert_eq!(
+            category_of("GET:/repos/acme/api/commits/abc/status"),
+            "commits/status"
+        );
+        assert_eq!(category_of("GET:/search/issues?q=is:pr"), "search");
+    }
+
     #[test]
     fn test_cache_touch() {
         let cache_file = std::env::temp_dir().join("gh-api-cache-test-touch.json");
@@ -494,5 +794,31 @@ mod tests {
         assert_eq!(stats.total_entries, 2);
         assert_eq!(stats.fresh_entries, 1);
         assert_eq!(stats.stale_entries, 1);
+        assert_eq!(stats.hit_ratio(), None);
+
+        cache.get("GET", "/fresh", &[]);
+        cache.get("GET", "/stale", &[]);
+        cache.get("GET", "/missing", &[]);
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (2, 1));
+        assert!((stats.hit_ratio().unwrap() - 2.0 / 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_cache_persists_across_restarts() {
+        let cache_file = std::env::temp_dir().join("gh-api-cache-test-restart.json");
+        let _ = std::fs::remove_file(&cache_file);
+        let mut cache = ApiCache::new(cache_file.clone()).unwrap();
+        let response = CachedResponse {
+            body: "test".into(),
+            etag: Some("abc".into()),
+            status_code: 200,
+        };
+        cache.set("GET", "/test", &[], &response).unwrap();
+        cache.flush();
+
+        let reopened = ApiCache::new(cache_file).unwrap();
+        let cached = reopened.get("GET", "/test", &[]).expect("Cache miss");
+        assert_eq!(cached.etag, Some("abc".into()));
     }
 }
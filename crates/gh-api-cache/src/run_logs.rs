@@ -0,0 +1,292 @@
+//! Disk cache of workflow run log archives
+//!
+//! The logs of a run attempt never change once it finished, so their ZIP
+//! archive is stored as is and reused instead of downloaded again. Archives
+//! live at `<dir>/<host>/<org>/<repo>/<run_id>-<attempt>.zip`; a re-run
+//! replaces the archives of earlier attempts, and the least recently used
+//! archives are removed once the cache grows beyond its size cap.
+
+use ::log::{debug, warn};
+use std::fs::{self, File};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Default size cap of the run log cache
+pub const DEFAULT_RUN_LOG_CACHE_BYTES: u64 = 512 * 1024 * 1024;
+
+/// A run attempt whose log archive is cached
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunLogKey<'a> {
+    pub host: &'a str,
+    pub org: &'a str,
+    pub repo: &'a str,
+    pub run_id: u64,
+    pub attempt: u64,
+}
+
+/// Log archives of finished workflow runs, bounded in size
+#[derive(Debug, Clone)]
+pub struct RunLogCache {
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+impl RunLogCache {
+    /// Cache in `dir`, holding up to [`DEFAULT_RUN_LOG_CACHE_BYTES`]
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            max_bytes: DEFAULT_RUN_LOG_CACHE_BYTES,
+        }
+    }
+
+    /// Use a different size cap
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Cached archive of a run attempt, marking it as recently used
+    pub fn get(&self, key: &RunLogKey<'_>) -> Option<Vec<u8>> {
+        let path = self.path(key);
+        let data = fs::read(&path).ok()?;
+        debug!("Run log cache HIT: {}", path.display());
+        // The modification time orders the archives for eviction
+        if let Err(e) = File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(SystemTime::now()))
+        {
+            debug!("Failed to mark {} as used: {}", path.display(), e);
+        }
+        Some(data)
+    }
+
+    /// Store the archive of a run attempt
+    ///
+    /// Drops the archives of other attempts of the run, then the least
+    /// recently used archives beyond the size cap.
+    pub fn insert(&self, key: &RunLogKey<'_>, data: &[u8]) {
+        let path = self.path(key);
+        let stored = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(&path, data));
+        if let Err(e) = stored {
+            warn!("Failed to store run log archive {}: {}", path.display(), e);
+            return;
+        }
+
+        self.remove_other_attempts(key, &path);
+        self.evict();
+    }
+
+    /// Cached archive of a run attempt, else the result of `fetch` (stored)
+    pub async fn get_or_fetch<F, Fut, E>(&self, key: &RunLogKey<'_>, fetch: F) -> Result<Vec<u8>, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Vec<u8>, E>>,
+    {
+        if let Some(data) = self.get(key) {
+            return Ok(data);
+        }
+        let data = fetch().await?;
+        self.insert(key, &data);
+        Ok(data)
+    }
+
+    /// Path of the archive of a run attempt
+    fn path(&self, key: &RunLogKey<'_>) -> PathBuf {
+        self.dir
+            .join(sanitize(key.host))
+            .join(sanitize(key.org))
+            .join(sanitize(key.repo))
+            .join(format!("{}-{}.zip", key.run_id, key.attempt))
+    }
+
+    /// Remove the archives of the run other than `keep` (outdated by a re-run)
+    fn remove_other_attempts(&self, key: &RunLogKey<'_>, keep: &Path) {
+        let Some(dir) = keep.parent() else {
+            return;
+        };
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        let prefix = format!("{}-", key.run_id);
+        for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+            let same_run = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.strip_prefix(&prefix))
+                .is_some_and(|attempt| attempt.parse::<u64>().is_ok());
+            if same_run && path != keep {
+                debug!("Removing outdated run log archive {}", path.display());
+                if let Err(e) = fs::remove_file(&path) {
+                    warn!("Failed to remove {}: {}", path.display(), e);
+                }
+            }
+        }
+    }
+
+    /// Remove the least recently used archives beyond the size cap
+    fn evict(&self) {
+        let mut archives = Vec::new();
+        collect_archives(&self.dir, &mut archives);
+        let mut total: u64 = archives.iter().map(|archive| archive.1).sum();
+        if total <= self.max_bytes {
+            return;
+        }
+
+        archives.sort();
+        for (_, size, path) in archives {
+            if total <= self.max_bytes {
+                break;
+            }
+            debug!("Evicting run log archive {}", path.display());
+            match fs::remove_file(&path) {
+                Ok(()) => total -= size,
+                Err(e) => warn!("Failed to remove {}: {}", path.display(), e),
+            }
+        }
+    }
+}
+
+/// Collect the archives below `dir` as (last use, size, path)
+fn collect_archives(dir: &Path, archives: &mut Vec<(SystemTime, u64, PathBuf)>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            collect_archives(&path, archives);
+        } else if path.extension().is_some_and(|ext| ext == "zip") {
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            archives.push((modified, metadata.len(), path));
+        }
+    }
+}
+
+/// Make a host, org or repo name safe to use as a directory name
+fn sanitize(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    match name.as_str() {
+        "" | "." | ".." => "_".to_string(),
+        _ => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+    use std::time::Duration;
+
+    /// An empty ZIP archive (just the end of central directory record)
+    const FIXTURE_ZIP: [u8; 22] = [
+        0x50, 0x4b, 0x05, 0x06, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+
+    fn cache(name: &str) -> RunLogCache {
+        let dir = std::env::temp_dir().join(format!("gh-api-cache-test-{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        RunLogCache::new(dir)
+    }
+
+    fn key(run_id: u64, attempt: u64) -> RunLogKey<'static> {
+        RunLogKey {
+            host: "github.com",
+            org: "acme",
+            repo: "api",
+            run_id,
+            attempt,
+        }
+    }
+
+    /// Run a future that never waits (the fetches below are ready at once)
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        match future
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()))
+        {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("future is not ready"),
+        }
+    }
+
+    #[test]
+    fn test_second_open_is_served_from_disk() {
+        let cache = cache("hit");
+        let downloads = Cell::new(0);
+        let open = || {
+            block_on(cache.get_or_fetch(&key(7, 1), || async {
+                downloads.set(downloads.get() + 1);
+                Ok::<_, String>(FIXTURE_ZIP.to_vec())
+            }))
+        };
+
+        assert_eq!(open().unwrap(), FIXTURE_ZIP);
+        assert_eq!(open().unwrap(), FIXTURE_ZIP);
+        assert_eq!(downloads.get(), 1);
+        assert!(cache.dir.join("github.com/acme/api/7-1.zip").exists());
+
+        // A failed download stores nothing
+        let failed = block_on(cache.get_or_fetch(&key(8, 1), || async {
+            Err::<Vec<u8>, _>("offline".to_string())
+        }));
+        assert_eq!(failed, Err("offline".to_string()));
+        assert_eq!(cache.get(&key(8, 1)), None);
+    }
+
+    #[test]
+    fn test_rerun_replaces_earlier_attempts() {
+        let cache = cache("rerun");
+        cache.insert(&key(7, 1), &FIXTURE_ZIP);
+        cache.insert(&key(17, 1), &FIXTURE_ZIP);
+
+        cache.insert(&key(7, 2), &FIXTURE_ZIP);
+
+        assert_eq!(cache.get(&key(7, 1)), None);
+        assert!(cache.get(&key(7, 2)).is_some());
+        assert!(cache.get(&key(17, 1)).is_some());
+    }
+
+    #[test]
+    fn test_least_recently_used_archives_are_evicted() {
+        let cache = cache("evict").with_max_bytes(2 * FIXTURE_ZIP.len() as u64);
+        cache.insert(&key(1, 1), &FIXTURE_ZIP);
+        sleep_tick();
+        cache.insert(&key(2, 1), &FIXTURE_ZIP);
+        sleep_tick();
+        // Reading run 1 makes run 2 the least recently used
+        assert!(cache.get(&key(1, 1)).is_some());
+        sleep_tick();
+
+        cache.insert(&key(3, 1), &FIXTURE_ZIP);
+
+        assert!(cache.get(&key(1, 1)).is_some());
+        assert_eq!(cache.get(&key(2, 1)), None);
+        assert!(cache.get(&key(3, 1)).is_some());
+    }
+
+    /// Let file modification times differ
+    fn sleep_tick() {
+        std::thread::sleep(Duration::from_millis(20));
+    }
+}
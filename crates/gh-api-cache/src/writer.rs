@@ -0,0 +1,150 @@
+//! Background persistence of the API cache
+//!
+//! Writing the cache file takes a while once the cache holds a few hundred
+//! responses, and it used to happen on every `set` while the caller held the
+//! cache lock. Snapshots are handed to a dedicated thread instead, which only
+//! writes the latest one when several queue up.
+
+use ::log::{debug, warn};
+use anyhow::Result;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+
+/// Work for the writer thread
+enum Job<T> {
+    /// Write this snapshot
+    Save(T),
+    /// Report back once every snapshot queued before is written
+    Flush(Sender<()>),
+}
+
+/// Writes snapshots of type `T` as JSON to a file, on its own thread
+pub(crate) struct DiskWriter<T: Serialize + Send + 'static> {
+    jobs: Option<Sender<Job<T>>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl<T: Serialize + Send + 'static> DiskWriter<T> {
+    /// Start the writer thread for `path`
+    ///
+    /// Without a thread nothing is persisted, the cache keeps working in memory.
+    pub(crate) fn spawn(path: PathBuf) -> Self {
+        let (jobs, rx) = mpsc::channel();
+        match thread::Builder::new()
+            .name("api-cache-writer".to_string())
+            .spawn(move || run(&path, rx))
+        {
+            Ok(thread) => Self {
+                jobs: Some(jobs),
+                thread: Some(thread),
+            },
+            Err(e) => {
+                warn!(
+                    "Failed to start cache writer, cache is not persisted: {}",
+                    e
+                );
+                Self {
+                    jobs: None,
+                    thread: None,
+                }
+            }
+        }
+    }
+
+    /// Queue a snapshot for writing, returns at once
+    pub(crate) fn save(&self, snapshot: T) {
+        if let Some(jobs) = &self.jobs {
+            let _ = jobs.send(Job::Save(snapshot));
+        }
+    }
+
+    /// Wait until every snapshot queued so far is on disk
+    pub(crate) fn flush(&self) {
+        let Some(jobs) = &self.jobs else {
+            return;
+        };
+        let (done_tx, done_rx) = mpsc::channel();
+        if jobs.send(Job::Flush(done_tx)).is_ok() {
+            let _ = done_rx.recv();
+        }
+    }
+}
+
+impl<T: Serialize + Send + 'static> Drop for DiskWriter<T> {
+    /// Write the last snapshot before the app exits
+    fn drop(&mut self) {
+        self.jobs.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl<T: Serialize + Send + 'static> std::fmt::Debug for DiskWriter<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DiskWriter")
+            .field("running", &self.jobs.is_some())
+            .finish()
+    }
+}
+
+/// Writer thread: write the latest queued snapshot until the sender is gone
+fn run<T: Serialize>(path: &Path, jobs: Receiver<Job<T>>) {
+    while let Ok(job) = jobs.recv() {
+        let mut latest = None;
+        let mut waiting = Vec::new();
+        for job in std::iter::once(job).chain(jobs.try_iter()) {
+            match job {
+                Job::Save(snapshot) => latest = Some(snapshot),
+                Job::Flush(done) => waiting.push(done),
+            }
+        }
+        if let Some(snapshot) = latest {
+            if let Err(e) = write(path, &snapshot) {
+                warn!("Failed to write cache file {}: {}", path.display(), e);
+            }
+        }
+        for done in waiting {
+            let _ = done.send(());
+        }
+    }
+    debug!("Cache writer for {} stopped", path.display());
+}
+
+/// Write `snapshot` next to `path` and move it in place, so a crash mid-write
+/// never leaves a truncated cache file behind
+fn write<T: Serialize>(path: &Path, snapshot: &T) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let content = serde_json::to_string_pretty(snapshot)?;
+    let partial = path.with_extension("json.partial");
+    std::fs::write(&partial, content)?;
+    std::fs::rename(&partial, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flush_waits_for_the_latest_snapshot() {
+        let path = std::env::temp_dir().join("gh-api-cache-test-writer.json");
+        let _ = std::fs::remove_file(&path);
+        let writer = DiskWriter::spawn(path.clone());
+
+        for snapshot in 1..=50 {
+            writer.save(vec![snapshot]);
+        }
+        writer.flush();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[\n  50\n]");
+
+        // Dropping writes what is still queued
+        writer.save(vec![51]);
+        drop(writer);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[\n  51\n]");
+    }
+}
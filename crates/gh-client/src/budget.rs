@@ -0,0 +1,607 @@
+//! Process-wide request budget
+//!
+//! The search limiter and the cache throttle individual features, but the sum
+//! of refreshes, CI checks, stats fetches and watch list checks can still trip
+//! GitHub's secondary rate limits. Every `OctocrabClient` request therefore
+//! acquires a token from a per-host token bucket shared by the whole process
+//! before it is sent.
+//!
+//! Requests run in one of two lanes. Interactive requests (the default, e.g. a
+//! refresh the user asked for) may drain the whole bucket. Background requests
+//! (futures wrapped in [`background`]) leave a reserve for interactive ones and
+//! yield while an interactive request is waiting for a token.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Default number of requests per minute and host
+///
+/// Well below GitHub's secondary limit of 900 REST points per minute, while
+/// still allowing a cold start of a few repositories to finish quickly.
+pub const DEFAULT_REQUESTS_PER_MINUTE: u32 = 300;
+
+/// Requests per hour GitHub allows without a token (per IP address)
+pub const ANONYMOUS_REQUESTS_PER_HOUR: u32 = 60;
+
+/// Fraction of the bucket background requests must leave for interactive ones
+const BACKGROUND_RESERVE: f64 = 0.2;
+
+/// Upper bound for a single sleep while waiting, so waiters re-check priority
+const MAX_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Window of the per-minute metrics
+const METRICS_WINDOW: Duration = Duration::from_secs(60);
+
+/// Scheduling lane of a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestPriority {
+    /// Triggered by the user, preempts background requests
+    Interactive,
+    /// Triggered by timers or prefetching
+    Background,
+}
+
+tokio::task_local! {
+    static PRIORITY: RequestPriority;
+}
+
+/// Run `future` with all its requests in the background lane
+pub async fn background<F: Future>(future: F) -> F::Output {
+    PRIORITY.scope(RequestPriority::Background, future).await
+}
+
+/// Priority of requests issued from the current task
+pub fn current_priority() -> RequestPriority {
+    PRIORITY
+        .try_with(|priority| *priority)
+        .unwrap_or(RequestPriority::Interactive)
+}
+
+/// Kind of API request, used for instrumentation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RequestCategory {
+    PullRequests,
+    Search,
+    Checks,
+    Reviews,
+    Comments,
+    Actions,
+    Mutations,
+}
+
+impl RequestCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PullRequests => "pull requests",
+            Self::Search => "search",
+            Self::Checks => "checks",
+            Self::Reviews => "reviews",
+            Self::Comments => "comments",
+            Self::Actions => "actions",
+            Self::Mutations => "mutations",
+        }
+    }
+}
+
+/// Snapshot of the requests issued through a budget
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientMetrics {
+    /// Configured limit (None = unlimited)
+    pub requests_per_minute: Option<u32>,
+    /// Requests issued in the last minute, per category
+    pub last_minute: BTreeMap<RequestCategory, u64>,
+    /// Requests issued since startup, per category
+    pub total: BTreeMap<RequestCategory, u64>,
+    /// Requests that had to wait for a token
+    pub throttled: u64,
+}
+
+impl ClientMetrics {
+    /// Number of requests issued in the last minute
+    pub fn last_minute_total(&self) -> u64 {
+        self.last_minute.values().sum()
+    }
+
+    /// Number of requests issued since startup
+    pub fn total_requests(&self) -> u64 {
+        self.total.values().sum()
+    }
+}
+
+impl std::fmt::Display for ClientMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let limit = self
+            .requests_per_minute
+            .map(|rpm| rpm.to_string())
+            .unwrap_or_else(|| "unlimited".to_string());
+        write!(
+            f,
+            "{}/{} requests in the last minute, {} total, {} throttled",
+            self.last_minute_total(),
+            limit,
+            self.total_requests(),
+            self.throttled
+        )?;
+        for (category, count) in &self.last_minute {
+            write!(f, "; {}: {}", category.as_str(), count)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+    interactive_waiting: usize,
+}
+
+#[derive(Debug, Default)]
+struct Metrics {
+    recent: VecDeque<(Instant, RequestCategory)>,
+    total: BTreeMap<RequestCategory, u64>,
+    throttled: u64,
+}
+
+/// Size and refill rate of a token bucket
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Rate {
+    capacity: f64,
+    per_second: f64,
+}
+
+impl Rate {
+    fn per_minute(requests: u32) -> Self {
+        Self {
+            capacity: requests as f64,
+            per_second: requests as f64 / 60.0,
+        }
+    }
+
+    fn per_hour(requests: u32) -> Self {
+        Self {
+            capacity: requests as f64,
+            per_second: requests as f64 / 3600.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct BudgetInner {
+    requests_per_minute: Option<u32>,
+    /// Hosts with their own limit (e.g. anonymous clients), taking precedence
+    host_rates: HashMap<String, Rate>,
+    buckets: HashMap<String, Bucket>,
+    metrics: Metrics,
+}
+
+impl BudgetInner {
+    /// Rate of the bucket of `host`, None if unlimited
+    fn rate(&self, host: &str) -> Option<Rate> {
+        self.host_rates
+            .get(host)
+            .copied()
+            .or_else(|| self.requests_per_minute.map(Rate::per_minute))
+    }
+}
+
+/// Async token bucket per host, shared by all clients of the process
+///
+/// Waiting never holds the lock across an await and uses plain timer sleeps,
+/// so dropping a waiting future (e.g. on runtime shutdown) releases
+/// everything it held.
+#[derive(Debug)]
+pub struct RequestBudget {
+    inner: Mutex<BudgetInner>,
+}
+
+impl Default for RequestBudget {
+    fn default() -> Self {
+        Self::new(DEFAULT_REQUESTS_PER_MINUTE)
+    }
+}
+
+impl RequestBudget {
+    /// Create a budget allowing `requests_per_minute` requests per host
+    pub fn new(requests_per_minute: u32) -> Self {
+        Self::with_limit(Some(requests_per_minute))
+    }
+
+    /// Create a budget that never waits but still records metrics
+    pub fn unlimited() -> Self {
+        Self::with_limit(None)
+    }
+
+    fn with_limit(requests_per_minute: Option<u32>) -> Self {
+        Self {
+            inner: Mutex::new(BudgetInner {
+                requests_per_minute: requests_per_minute.filter(|&rpm| rpm > 0),
+                host_rates: HashMap::new(),
+                buckets: HashMap::new(),
+                metrics: Metrics::default(),
+            }),
+        }
+    }
+
+    /// The budget shared by all clients of the process
+    ///
+    /// Unlimited in tests, so unit tests never wait on the clock.
+    pub fn global() -> Arc<RequestBudget> {
+        static GLOBAL: OnceLock<Arc<RequestBudget>> = OnceLock::new();
+        GLOBAL
+            .get_or_init(|| {
+                if cfg!(test) {
+                    Arc::new(Self::unlimited())
+                } else {
+                    Arc::new(Self::default())
+                }
+            })
+            .clone()
+    }
+
+    /// Change the limit (None or 0 = unlimited)
+    pub fn set_requests_per_minute(&self, requests_per_minute: Option<u32>) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.requests_per_minute = requests_per_minute.filter(|&rpm| rpm > 0);
+        inner.buckets.clear();
+    }
+
+    /// Limit `host` to `requests_per_hour`, regardless of the per-minute limit
+    ///
+    /// Used for anonymous clients, which GitHub allows far fewer requests.
+    pub fn set_host_requests_per_hour(&self, host: &str, requests_per_hour: u32) {
+        let mut inner = self.inner.lock().unwrap();
+        inner
+            .host_rates
+            .insert(host.to_string(), Rate::per_hour(requests_per_hour.max(1)));
+        inner.buckets.remove(host);
+    }
+
+    /// Wait for a token for `host` in the lane of the current task
+    pub async fn acquire(&self, host: &str, category: RequestCategory) {
+        let priority = current_priority();
+        let mut waiting: Option<WaitingGuard<'_>> = None;
+
+        loop {
+            let wait = match self.try_acquire_at(host, category, priority, Instant::now()) {
+                Ok(()) => return,
+                Err(wait) => wait,
+            };
+
+            if waiting.is_none() {
+                self.inner.lock().unwrap().metrics.throttled += 1;
+                log::debug!(
+                    "Request budget for {} exhausted, {:?} {} request waits {:?}",
+                    host,
+                    priority,
+                    category.as_str(),
+                    wait
+                );
+                waiting = Some(WaitingGuard::new(self, host, priority));
+            }
+
+            tokio::time::sleep(wait.min(MAX_POLL_INTERVAL)).await;
+        }
+    }
+
+    fn try_acquire_at(
+        &self,
+        host: &str,
+        category: RequestCategory,
+        priority: RequestPriority,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let mut inner = self.inner.lock().unwrap();
+
+        if let Some(Rate {
+            capacity,
+            per_second,
+        }) = inner.rate(host)
+        {
+            let bucket = inner
+                .buckets
+                .entry(host.to_string())
+                .or_insert_with(|| Bucket {
+                    tokens: capacity,
+                    refilled_at: now,
+                    interactive_waiting: 0,
+                });
+
+            let elapsed = now.saturating_duration_since(bucket.refilled_at);
+            bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * per_second).min(capacity);
+            bucket.refilled_at = now;
+
+            let needed = match priority {
+                RequestPriority::Interactive => 1.0,
+                RequestPriority::Background => {
+                    if bucket.interactive_waiting > 0 {
+                        return Err(MAX_POLL_INTERVAL);
+                    }
+                    1.0 + (capacity * BACKGROUND_RESERVE).floor()
+                }
+            };
+
+            if bucket.tokens < needed {
+                let missing = needed - bucket.tokens;
+                return Err(Duration::from_secs_f64(missing / per_second));
+            }
+            bucket.tokens -= 1.0;
+        }
+
+        let metrics = &mut inner.metrics;
+        while metrics
+            .recent
+            .front()
+            .is_some_and(|&(t, _)| now.saturating_duration_since(t) >= METRICS_WINDOW)
+        {
+            metrics.recent.pop_front();
+        }
+        metrics.recent.push_back((now, category));
+        *metrics.total.entry(category).or_default() += 1;
+        Ok(())
+    }
+
+    /// Whether at least half of the bucket for `host` is available
+    ///
+    /// Optional work such as prefetching should only start with headroom.
+    pub fn has_headroom(&self, host: &str) -> bool {
+        self.has_headroom_at(host, Instant::now())
+    }
+
+    fn has_headroom_at(&self, host: &str, now: Instant) -> bool {
+        let inner = self.inner.lock().unwrap();
+        let Some(Rate {
+            capacity,
+            per_second,
+        }) = inner.rate(host)
+        else {
+            return true;
+        };
+        let Some(bucket) = inner.buckets.get(host) else {
+            return true;
+        };
+        let elapsed = now.saturating_duration_since(bucket.refilled_at);
+        let tokens = (bucket.tokens + elapsed.as_secs_f64() * per_second).min(capacity);
+        bucket.interactive_waiting == 0 && tokens >= capacity / 2.0
+    }
+
+    /// Snapshot of the requests issued through this budget
+    pub fn metrics(&self) -> ClientMetrics {
+        let inner = self.inner.lock().unwrap();
+        let now = Instant::now();
+        let mut last_minute = BTreeMap::new();
+        for (at, category) in &inner.metrics.recent {
+            if now.saturating_duration_since(*at) < METRICS_WINDOW {
+                *last_minute.entry(*category).or_default() += 1;
+            }
+        }
+        ClientMetrics {
+            requests_per_minute: inner.requests_per_minute,
+            last_minute,
+            total: inner.metrics.total.clone(),
+            throttled: inner.metrics.throttled,
+        }
+    }
+}
+
+/// Marks an interactive request as waiting, so background requests yield
+///
+/// Released on drop, including when the waiting future is cancelled.
+struct WaitingGuard<'a> {
+    budget: &'a RequestBudget,
+    host: String,
+    interactive: bool,
+}
+
+impl<'a> WaitingGuard<'a> {
+    fn new(budget: &'a RequestBudget, host: &str, priority: RequestPriority) -> Self {
+        let interactive = priority == RequestPriority::Interactive;
+        if interactive {
+            let mut inner = budget.inner.lock().unwrap();
+            if let Some(bucket) = inner.buckets.get_mut(host) {
+                bucket.interactive_waiting += 1;
+            }
+        }
+        Self {
+            budget,
+            host: host.to_string(),
+            interactive,
+        }
+    }
+}
+
+impl Drop for WaitingGuard<'_> {
+    fn drop(&mut self) {
+        if !self.interactive {
+            return;
+        }
+        // Never panic in drop, even if another thread poisoned the lock
+        if let Ok(mut inner) = self.budget.inner.lock() {
+            if let Some(bucket) = inner.buckets.get_mut(&self.host) {
+                bucket.interactive_waiting = bucket.interactive_waiting.saturating_sub(1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOST: &str = "https://api.github.com";
+
+    #[test]
+    fn test_bucket_limits_and_refills() {
+        let budget = RequestBudget::new(60);
+        let start = Instant::now();
+        let acquire = |at| {
+            budget.try_acquire_at(
+                HOST,
+                RequestCategory::PullRequests,
+                RequestPriority::Interactive,
+                at,
+            )
+        };
+
+        for _ in 0..60 {
+            assert!(acquire(start).is_ok());
+        }
+        assert_eq!(acquire(start).unwrap_err(), Duration::from_secs(1));
+
+        // One token per second at 60 requests per minute
+        assert!(acquire(start + Duration::from_secs(1)).is_ok());
+        assert!(acquire(start + Duration::from_secs(1)).is_err());
+    }
+
+    #[test]
+    fn test_hosts_have_separate_buckets() {
+        let budget = RequestBudget::new(1);
+        let now = Instant::now();
+        let priority = RequestPriority::Interactive;
+        let category = RequestCategory::Checks;
+
+        assert!(budget.try_acquire_at(HOST, category, priority, now).is_ok());
+        assert!(budget
+            .try_acquire_at(HOST, category, priority, now)
+            .is_err());
+        assert!(budget
+            .try_acquire_at("https://ghe.example.com/api/v3", category, priority, now)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_background_leaves_reserve_and_yields() {
+        let budget = RequestBudget::new(10);
+        let now = Instant::now();
+        let category = RequestCategory::Checks;
+
+        // Background requests stop while 2 of 10 tokens are left
+        for _ in 0..8 {
+            assert!(budget
+                .try_acquire_at(HOST, category, RequestPriority::Background, now)
+                .is_ok());
+        }
+        assert!(budget
+            .try_acquire_at(HOST, category, RequestPriority::Background, now)
+            .is_err());
+        assert!(budget
+            .try_acquire_at(HOST, category, RequestPriority::Interactive, now)
+            .is_ok());
+
+        // A waiting interactive request blocks background ones entirely
+        let later = now + Duration::from_secs(60);
+        let guard = WaitingGuard::new(&budget, HOST, RequestPriority::Interactive);
+        assert!(budget
+            .try_acquire_at(HOST, category, RequestPriority::Background, later)
+            .is_err());
+        drop(guard);
+        assert!(budget
+            .try_acquire_at(HOST, category, RequestPriority::Background, later)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_headroom() {
+        let budget = RequestBudget::new(10);
+        let now = Instant::now();
+        assert!(budget.has_headroom_at(HOST, now));
+
+        for _ in 0..6 {
+            budget
+                .try_acquire_at(
+                    HOST,
+                    RequestCategory::PullRequests,
+                    RequestPriority::Interactive,
+                    now,
+                )
+                .unwrap();
+        }
+        assert!(!budget.has_headroom_at(HOST, now));
+        assert!(budget.has_headroom_at(HOST, now + Duration::from_secs(6)));
+        assert!(RequestBudget::unlimited().has_headroom_at(HOST, now));
+    }
+
+    #[test]
+    fn test_host_rate_applies_even_when_unlimited() {
+        let budget = RequestBudget::unlimited();
+        budget.set_host_requests_per_hour("anonymous", ANONYMOUS_REQUESTS_PER_HOUR);
+        let now = Instant::now();
+        let acquire = |host, at| {
+            budget.try_acquire_at(
+                host,
+                RequestCategory::PullRequests,
+                RequestPriority::Interactive,
+                at,
+            )
+        };
+
+        for _ in 0..60 {
+            assert!(acquire("anonymous", now).is_ok());
+        }
+        // One request per minute at 60 requests per hour
+        assert_eq!(
+            acquire("anonymous", now).unwrap_err(),
+            Duration::from_secs(60)
+        );
+        assert!(acquire("anonymous", now + Duration::from_secs(60)).is_ok());
+        assert!(acquire(HOST, now).is_ok());
+    }
+
+    #[test]
+    fn test_unlimited_budget_records_metrics() {
+        let budget = RequestBudget::unlimited();
+        let now = Instant::now();
+        for _ in 0..1000 {
+            assert!(budget
+                .try_acquire_at(
+                    HOST,
+                    RequestCategory::Search,
+                    RequestPriority::Background,
+                    now
+                )
+                .is_ok());
+        }
+        budget
+            .try_acquire_at(
+                HOST,
+                RequestCategory::Mutations,
+                RequestPriority::Interactive,
+                now,
+            )
+            .unwrap();
+
+        let metrics = budget.metrics();
+        assert_eq!(metrics.requests_per_minute, None);
+        assert_eq!(metrics.total_requests(), 1001);
+        assert_eq!(metrics.last_minute[&RequestCategory::Search], 1000);
+        assert_eq!(metrics.throttled, 0);
+    }
+
+    #[tokio::test]
+    async fn test_priority_is_task_local() {
+        assert_eq!(current_priority(), RequestPriority::Interactive);
+        let inner = background(async { current_priority() }).await;
+        assert_eq!(inner, RequestPriority::Background);
+        assert_eq!(current_priority(), RequestPriority::Interactive);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_waiter_releases_its_slot() {
+        let budget = RequestBudget::new(1);
+        budget.acquire(HOST, RequestCategory::Reviews).await;
+
+        // The second request has to wait; cancel it via timeout
+        let waited = tokio::time::timeout(
+            Duration::from_millis(20),
+            budget.acquire(HOST, RequestCategory::Reviews),
+        )
+        .await;
+        assert!(waited.is_err());
+
+        let inner = budget.inner.lock().unwrap();
+        assert_eq!(inner.buckets[HOST].interactive_waiting, 0);
+        assert_eq!(inner.metrics.throttled, 1);
+    }
+}
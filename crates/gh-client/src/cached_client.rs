@@ -3,6 +3,7 @@
 //! Wraps any `GitHubClient` implementation to add caching behavior.
 //! The cache mode determines whether to read from cache, write to cache, or both.
 
+use crate::capabilities::HostCapabilities;
 use crate::client::{CacheMode, GitHubClient};
 
 /// URL patterns that return volatile data - should never be read from cache.
@@ -23,14 +24,95 @@ fn is_volatile_endpoint(url: &str) -> bool {
         .any(|pattern| url.contains(pattern))
 }
 use crate::types::{
-    CheckRun, CheckStatus, CiStatus, MergeMethod, MergeResult, PullRequest, ReviewComment,
-    ReviewDecision, ReviewEvent, WorkflowRun,
+    BlameRange, CheckRun, CheckStatus, CiStatus, CommentCounts, CommentLines, Comparison, Label,
+    MergeMethod, MergeResult, PrReviewers, PrStateFilter, PullRequest, RepositorySettings,
+    ReviewComment, ReviewDecision, ReviewEvent, SearchResults, WorkflowRun,
 };
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use gh_api_cache::{ApiCache, CachedResponse};
 use log::debug;
 use std::sync::{Arc, Mutex};
 
+/// Request a `CacheMode::ReadOnly` client could not answer from the cache
+///
+/// Returned instead of reaching the network, e.g. on a cache miss or for a
+/// mutation. Recover it from an `anyhow::Error` with `downcast_ref`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheMiss {
+    /// Method and path of the request (e.g., "GET /repos/o/r/pulls")
+    pub request: String,
+}
+
+impl std::fmt::Display for CacheMiss {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} is not in the cache and the client is read-only",
+            self.request
+        )
+    }
+}
+
+impl std::error::Error for CacheMiss {}
+
+/// PR list as last cached, see `CachedGitHubClient::peek_pull_requests`
+#[derive(Debug, Clone)]
+pub struct CachedPullRequests {
+    pub prs: Vec<PullRequest>,
+    /// When the list was fetched
+    pub cached_at: DateTime<Utc>,
+}
+
+/// Read the cached PR list of an account's cache namespace without any request
+///
+/// For when the account's client isn't available yet, e.g. while its token
+/// is still being resolved at startup.
+pub fn peek_pull_requests(
+    cache: &ApiCache,
+    namespace: &str,
+    owner: &str,
+    repo: &str,
+    base_branch: Option<&str>,
+    state: PrStateFilter,
+) -> Option<CachedPullRequests> {
+    let url = format!("{}/repos/{}/{}/pulls", namespace, owner, repo);
+    let (response, cached_at) =
+        cache.peek("GET", &url, &pull_requests_params(base_branch, state))?;
+    match parse_cached_pull_requests(&response.body) {
+        Ok(prs) => Some(CachedPullRequests {
+            prs,
+            cached_at: cached_at.into(),
+        }),
+        Err(e) => {
+            debug!("Failed to parse cached PRs: {}", e);
+            None
+        }
+    }
+}
+
+/// Cache key parameters of a PR list
+///
+/// Keyed by state so switching filters never serves another filter's list.
+fn pull_requests_params(
+    base_branch: Option<&str>,
+    state: PrStateFilter,
+) -> Vec<(&'static str, &str)> {
+    if let Some(branch) = base_branch {
+        vec![("state", state.as_str()), ("head", branch)]
+    } else {
+        vec![("state", state.as_str())]
+    }
+}
+
+/// Parse a cached PR list, sorted for stable ordering (descending by PR number)
+fn parse_cached_pull_requests(body: &str) -> serde_json::Result<Vec<PullRequest>> {
+    let mut prs = serde_json::from_str::<Vec<PullRequest>>(body)?;
+    prs.sort_by_key(|pr| std::cmp::Reverse(pr.number));
+    prs.dedup_by_key(|pr| pr.number);
+    Ok(prs)
+}
+
 /// Cached GitHub API client using the decorator pattern
 ///
 /// Wraps an inner `GitHubClient` and adds caching behavior based on the configured
@@ -39,11 +121,13 @@ use std::sync::{Arc, Mutex};
 ///
 /// # Cache Modes
 ///
-/// - `CacheMode::None` - Pass through to inner client (no caching)
+/// - `CacheMode::Bypass` - Pass through to inner client (no caching)
 /// - `CacheMode::WriteOnly` - Skip cache reads, but write responses (force refresh)
-/// - `CacheMode::ReadOnly` - Read from cache only, don't update cache
+/// - `CacheMode::ReadOnly` - Read from cache only, never call the inner client
 /// - `CacheMode::ReadWrite` - Full caching (default, most efficient)
 ///
+/// See `CacheMode` for how each mode treats fresh hits, stale hits and misses.
+///
 /// # Example
 ///
 /// ```rust,ignore
@@ -60,9 +144,12 @@ use std::sync::{Arc, Mutex};
 /// ```
 #[derive(Debug, Clone)]
 pub struct CachedGitHubClient<C: GitHubClient + Clone> {
-    inner: C,
+    /// Shared, so switching the mode doesn't clone the inner client
+    inner: Arc<C>,
     cache: Arc<Mutex<ApiCache>>,
     mode: CacheMode,
+    /// Prefix of all cache keys, separating accounts sharing one cache
+    namespace: Arc<str>,
 }
 
 impl<C: GitHubClient + Clone> CachedGitHubClient<C> {
@@ -74,7 +161,32 @@ impl<C: GitHubClient + Clone> CachedGitHubClient<C> {
     /// * `cache` - Shared cache instance
     /// * `mode` - Cache behavior mode
     pub fn new(inner: C, cache: Arc<Mutex<ApiCache>>, mode: CacheMode) -> Self {
-        Self { inner, cache, mode }
+        Self {
+            inner: Arc::new(inner),
+            cache,
+            mode,
+            namespace: Arc::from(""),
+        }
+    }
+
+    /// Use a separate cache namespace (e.g. per account)
+    ///
+    /// Clients with different namespaces never see each other's cached data,
+    /// even when they share the same cache.
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        let namespace: String = namespace.into();
+        self.namespace = Arc::from(namespace);
+        self
+    }
+
+    /// Get the cache namespace (empty for the default account)
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Cache key of a URL within this client's namespace
+    fn cache_url(&self, url: &str) -> String {
+        format!("{}{}", self.namespace, url)
     }
 
     /// Get the current cache mode
@@ -84,16 +196,15 @@ impl<C: GitHubClient + Clone> CachedGitHubClient<C> {
 
     /// Create a new client with a different cache mode
     ///
-    /// This is useful for creating a "force refresh" client without
-    /// constructing a new inner client.
-    pub fn with_mode(&self, mode: CacheMode) -> CachedGitHubClient<C>
-    where
-        C: Clone,
-    {
+    /// This is useful for creating a "force refresh" client for some calls.
+    /// The new client shares the inner client, cache and namespace, so this
+    /// only bumps reference counts.
+    pub fn with_mode(&self, mode: CacheMode) -> CachedGitHubClient<C> {
         CachedGitHubClient {
-            inner: self.inner.clone(),
+            inner: Arc::clone(&self.inner),
             cache: Arc::clone(&self.cache),
             mode,
+            namespace: Arc::clone(&self.namespace),
         }
     }
 
@@ -104,25 +215,56 @@ impl<C: GitHubClient + Clone> CachedGitHubClient<C> {
         &self.inner
     }
 
+    /// Read the cached PR list without any request, however old it is
+    ///
+    /// Ignores the cache mode: the result is only meant to be shown until
+    /// the fresh list from `fetch_pull_requests` arrives.
+    pub fn peek_pull_requests(
+        &self,
+        owner: &str,
+        repo: &str,
+        base_branch: Option<&str>,
+        state: PrStateFilter,
+    ) -> Option<CachedPullRequests> {
+        let cache = self.cache.lock().unwrap();
+        peek_pull_requests(&cache, &self.namespace, owner, repo, base_branch, state)
+    }
+
     /// Try to get data from cache
     ///
     /// Returns `None` if:
     /// - Cache mode doesn't allow reading
-    /// - URL is a volatile endpoint (CI status, reviews, etc.)
+    /// - URL is a volatile endpoint (CI status, reviews, etc.), unless read-only
+    /// - Data is stale, unless read-only
     /// - Data is not in cache
     fn try_cache_get(&self, method: &str, url: &str, params: &[(&str, &str)]) -> Option<String> {
-        if !self.mode.should_read() {
-            return None;
+        let cache = self.cache.lock().unwrap();
+        match self.mode {
+            // The cache is all there is, however old
+            CacheMode::ReadOnly => cache
+                .peek(method, &self.cache_url(url), params)
+                .map(|(r, _)| r.body),
+            // Never read volatile data from cache - it changes too frequently
+            CacheMode::ReadWrite if is_volatile_endpoint(url) => {
+                debug!("Skipping cache read for volatile endpoint: {}", url);
+                None
+            }
+            CacheMode::ReadWrite => cache
+                .get_fresh(method, &self.cache_url(url), params)
+                .map(|r| r.body),
+            CacheMode::WriteOnly | CacheMode::Bypass => None,
         }
+    }
 
-        // Never read volatile data from cache - it changes too frequently
-        if is_volatile_endpoint(url) {
-            debug!("Skipping cache read for volatile endpoint: {}", url);
-            return None;
+    /// Refuse a request the cache couldn't answer if the network is off-limits
+    fn ensure_may_fetch(&self, method: &str, url: &str) -> Result<(), CacheMiss> {
+        if self.mode.should_fetch() {
+            return Ok(());
         }
-
-        let cache = self.cache.lock().unwrap();
-        cache.get(method, url, params).map(|r| r.body)
+        debug!("Read-only cache MISS for {} {}", method, url);
+        Err(CacheMiss {
+            request: format!("{} {}", method, url),
+        })
     }
 
     /// Write data to cache
@@ -138,7 +280,7 @@ impl<C: GitHubClient + Clone> CachedGitHubClient<C> {
         };
 
         let mut cache = self.cache.lock().unwrap();
-        if let Err(e) = cache.set(method, url, params, &response) {
+        if let Err(e) = cache.set(method, &self.cache_url(url), params, &response) {
             debug!("Failed to write to cache: {}", e);
         }
     }
@@ -152,7 +294,7 @@ impl<C: GitHubClient + Clone> CachedGitHubClient<C> {
     fn cache_invalidate_comments(&self, owner: &str, repo: &str) {
         // Pattern with trailing slash matches PR-specific endpoints (comments, reviews, etc.)
         // but not the PR list endpoint (/pulls?state=open)
-        let pattern = format!("/repos/{}/{}/pulls/", owner, repo);
+        let pattern = self.cache_url(&format!("/repos/{}/{}/pulls/", owner, repo));
         debug!(
             "Cache invalidation for comments: invalidating pattern '{}'",
             pattern
@@ -160,6 +302,17 @@ impl<C: GitHubClient + Clone> CachedGitHubClient<C> {
         let mut cache = self.cache.lock().unwrap();
         cache.invalidate_pattern(&pattern);
     }
+
+    /// Invalidate the cached PR lists and details of a repository
+    fn cache_invalidate_pull_requests(&self, owner: &str, repo: &str) {
+        let pattern = self.cache_url(&format!("/repos/{}/{}/pulls", owner, repo));
+        debug!(
+            "Cache invalidation for pull requests: invalidating pattern '{}'",
+            pattern
+        );
+        let mut cache = self.cache.lock().unwrap();
+        cache.invalidate_pattern(&pattern);
+    }
 }
 
 #[async_trait]
@@ -169,21 +322,15 @@ impl<C: GitHubClient + Clone> GitHubClient for CachedGitHubClient<C> {
         owner: &str,
         repo: &str,
         base_branch: Option<&str>,
+        state: PrStateFilter,
     ) -> anyhow::Result<Vec<PullRequest>> {
         let url = format!("/repos/{}/{}/pulls", owner, repo);
-        let params: Vec<(&str, &str)> = if let Some(branch) = base_branch {
-            vec![("state", "open"), ("head", branch)]
-        } else {
-            vec![("state", "open")]
-        };
+        let params = pull_requests_params(base_branch, state);
 
         // Try cache first
         if let Some(cached_body) = self.try_cache_get("GET", &url, &params) {
-            match serde_json::from_str::<Vec<PullRequest>>(&cached_body) {
-                Ok(mut prs) => {
-                    // Always sort for stable ordering (descending by PR number)
-                    prs.sort_by(|a, b| b.number.cmp(&a.number));
-                    prs.dedup_by_key(|pr| pr.number);
+            match parse_cached_pull_requests(&cached_body) {
+                Ok(prs) => {
                     debug!("Cache HIT for {}/{}: {} PRs", owner, repo, prs.len());
                     return Ok(prs);
                 }
@@ -194,10 +341,12 @@ impl<C: GitHubClient + Clone> GitHubClient for CachedGitHubClient<C> {
             }
         }
 
+        self.ensure_may_fetch("GET", &url)?;
+
         // Fetch from API
         let prs = self
             .inner
-            .fetch_pull_requests(owner, repo, base_branch)
+            .fetch_pull_requests(owner, repo, base_branch, state)
             .await?;
 
         // Cache the result
@@ -208,6 +357,26 @@ impl<C: GitHubClient + Clone> GitHubClient for CachedGitHubClient<C> {
         Ok(prs)
     }
 
+    async fn search_pull_requests_in_repo(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &str,
+        page: u32,
+    ) -> anyhow::Result<SearchResults> {
+        self.ensure_may_fetch("GET", "/search/issues")?;
+        // Never cached: searches are explicit and their results change constantly
+        self.inner
+            .search_pull_requests_in_repo(owner, repo, query, page)
+            .await
+    }
+
+    async fn count_search(&self, query: &str) -> anyhow::Result<u64> {
+        self.ensure_may_fetch("GET", "/search/issues")?;
+        // Never cached: callers keep counts as long as they want them
+        self.inner.count_search(query).await
+    }
+
     async fn fetch_pull_request(
         &self,
         owner: &str,
@@ -232,6 +401,8 @@ impl<C: GitHubClient + Clone> GitHubClient for CachedGitHubClient<C> {
 
         // Cache miss - fetch from API
         debug!("Cache MISS for PR #{} in {}/{}", pr_number, owner, repo);
+        self.ensure_may_fetch("GET", &url)?;
+
         let pr = self
             .inner
             .fetch_pull_request(owner, repo, pr_number)
@@ -276,6 +447,8 @@ impl<C: GitHubClient + Clone> GitHubClient for CachedGitHubClient<C> {
             }
         }
 
+        self.ensure_may_fetch("GET", &url)?;
+
         // Fetch from API
         let runs = self.inner.fetch_check_runs(owner, repo, commit_sha).await?;
 
@@ -312,6 +485,8 @@ impl<C: GitHubClient + Clone> GitHubClient for CachedGitHubClient<C> {
             }
         }
 
+        self.ensure_may_fetch("GET", &url)?;
+
         // Fetch from API
         let status = self
             .inner
@@ -337,6 +512,10 @@ impl<C: GitHubClient + Clone> GitHubClient for CachedGitHubClient<C> {
         commit_title: Option<&str>,
         commit_message: Option<&str>,
     ) -> anyhow::Result<MergeResult> {
+        self.ensure_may_fetch(
+            "PUT",
+            &format!("/repos/{}/{}/pulls/{}/merge", owner, repo, pr_number),
+        )?;
         // Mutations are never cached - pass through directly
         self.inner
             .merge_pull_request(
@@ -356,6 +535,13 @@ impl<C: GitHubClient + Clone> GitHubClient for CachedGitHubClient<C> {
         repo: &str,
         pr_number: u64,
     ) -> anyhow::Result<()> {
+        self.ensure_may_fetch(
+            "PUT",
+            &format!(
+                "/repos/{}/{}/pulls/{}/update-branch",
+                owner, repo, pr_number
+            ),
+        )?;
         // Mutations are never cached - pass through directly
         self.inner
             .update_pull_request_branch(owner, repo, pr_number)
@@ -370,6 +556,10 @@ impl<C: GitHubClient + Clone> GitHubClient for CachedGitHubClient<C> {
         event: ReviewEvent,
         body: Option<&str>,
     ) -> anyhow::Result<()> {
+        self.ensure_may_fetch(
+            "POST",
+            &format!("/repos/{}/{}/pulls/{}/reviews", owner, repo, pr_number),
+        )?;
         // Mutations are never cached - pass through directly
         self.inner
             .create_review(owner, repo, pr_number, event, body)
@@ -382,243 +572,942 @@ impl<C: GitHubClient + Clone> GitHubClient for CachedGitHubClient<C> {
         repo: &str,
         pr_number: u64,
     ) -> anyhow::Result<()> {
+        self.ensure_may_fetch(
+            "PATCH",
+            &format!("/repos/{}/{}/pulls/{}", owner, repo, pr_number),
+        )?;
         // Mutations are never cached - pass through directly
         self.inner.close_pull_request(owner, repo, pr_number).await
     }
 
-    async fn rerun_failed_jobs(&self, owner: &str, repo: &str, run_id: u64) -> anyhow::Result<()> {
-        // Mutations are never cached - pass through directly
-        self.inner.rerun_failed_jobs(owner, repo, run_id).await
+    async fn fetch_repository_settings(
+        &self,
+        owner: &str,
+        repo: &str,
+    ) -> anyhow::Result<RepositorySettings> {
+        let url = format!("/repos/{}/{}", owner, repo);
+        let params: &[(&str, &str)] = &[];
+
+        // Settings change rarely, so the cache is always tried first
+        if let Some(cached_body) = self.try_cache_get("GET", &url, params) {
+            match serde_json::from_str::<RepositorySettings>(&cached_body) {
+                Ok(settings) => {
+                    debug!("Cache HIT for {}/{} settings", owner, repo);
+                    return Ok(settings);
+                }
+                Err(e) => {
+                    debug!("Failed to parse cached repository settings: {}", e);
+                }
+            }
+        }
+
+        self.ensure_may_fetch("GET", &url)?;
+
+        let settings = self.inner.fetch_repository_settings(owner, repo).await?;
+
+        if let Ok(json) = serde_json::to_string(&settings) {
+            self.cache_set("GET", &url, params, &json);
+        }
+
+        Ok(settings)
     }
 
-    async fn fetch_workflow_runs(
+    async fn fetch_required_checks(
         &self,
         owner: &str,
         repo: &str,
-        head_sha: &str,
-    ) -> anyhow::Result<Vec<WorkflowRun>> {
-        let url = format!("/repos/{}/{}/actions/runs", owner, repo);
-        let params: &[(&str, &str)] = &[("head_sha", head_sha)];
+        branch: &str,
+    ) -> anyhow::Result<Option<Vec<String>>> {
+        let url = format!(
+            "/repos/{}/{}/branches/{}/protection/required_status_checks",
+            owner, repo, branch
+        );
+        let params: &[(&str, &str)] = &[];
 
-        // Try cache first
+        // Asked for every CI status, and protection changes rarely
         if let Some(cached_body) = self.try_cache_get("GET", &url, params) {
-            match serde_json::from_str::<Vec<WorkflowRun>>(&cached_body) {
-                Ok(runs) => {
+            match serde_json::from_str::<Option<Vec<String>>>(&cached_body) {
+                Ok(required) => {
                     debug!(
-                        "Cache HIT for {}/{} @ {}: {} workflow runs",
-                        owner,
-                        repo,
-                        head_sha,
-                        runs.len()
+                        "Cache HIT for required checks of {}/{}@{}",
+                        owner, repo, branch
                     );
-                    return Ok(runs);
+                    return Ok(required);
                 }
                 Err(e) => {
-                    debug!("Failed to parse cached workflow runs: {}", e);
+                    debug!("Failed to parse cached required checks: {}", e);
                 }
             }
         }
 
-        // Fetch from API
-        let runs = self
+        self.ensure_may_fetch("GET", &url)?;
+
+        let required = self
             .inner
-            .fetch_workflow_runs(owner, repo, head_sha)
+            .fetch_required_checks(owner, repo, branch)
             .await?;
 
-        // Cache the result
-        if let Ok(json) = serde_json::to_string(&runs) {
+        // Unreadable protection too, so it isn't asked for every time
+        if let Ok(json) = serde_json::to_string(&required) {
             self.cache_set("GET", &url, params, &json);
         }
 
-        Ok(runs)
+        Ok(required)
     }
 
-    async fn fetch_ci_status(
-        &self,
-        owner: &str,
-        repo: &str,
-        head_sha: &str,
-    ) -> anyhow::Result<CiStatus> {
-        let url = format!("/repos/{}/{}/commits/{}/check-runs", owner, repo, head_sha);
+    async fn delete_branch(&self, owner: &str, repo: &str, branch: &str) -> anyhow::Result<()> {
+        self.ensure_may_fetch(
+            "DELETE",
+            &format!("/repos/{}/{}/git/refs/heads/{}", owner, repo, branch),
+        )?;
+        // Mutations are never cached - pass through directly
+        self.inner.delete_branch(owner, repo, branch).await
+    }
+
+    async fn fetch_repo_labels(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<Label>> {
+        let url = format!("/repos/{}/{}/labels", owner, repo);
         let params: &[(&str, &str)] = &[];
 
-        // Try cache first
+        // Labels change rarely, so the cache is always tried first
         if let Some(cached_body) = self.try_cache_get("GET", &url, params) {
-            match serde_json::from_str::<CiStatus>(&cached_body) {
-                Ok(status) => {
+            match serde_json::from_str::<Vec<Label>>(&cached_body) {
+                Ok(labels) => {
                     debug!(
-                        "Cache HIT for CI status {}/{} @ {}: {:?}",
-                        owner, repo, head_sha, status.state
+                        "Cache HIT for {}/{} labels: {} labels",
+                        owner,
+                        repo,
+                        labels.len()
                     );
-                    return Ok(status);
+                    return Ok(labels);
                 }
                 Err(e) => {
-                    debug!("Failed to parse cached CI status: {}", e);
+                    debug!("Failed to parse cached labels: {}", e);
                 }
             }
         }
 
+        self.ensure_may_fetch("GET", &url)?;
+
         // Fetch from API
-        let status = self.inner.fetch_ci_status(owner, repo, head_sha).await?;
+        let labels = self.inner.fetch_repo_labels(owner, repo).await?;
 
         // Cache the result
-        if let Ok(json) = serde_json::to_string(&status) {
+        if let Ok(json) = serde_json::to_string(&labels) {
             self.cache_set("GET", &url, params, &json);
         }
 
-        Ok(status)
+        Ok(labels)
     }
 
-    async fn create_review_comment(
+    async fn add_labels(
         &self,
         owner: &str,
         repo: &str,
         pr_number: u64,
-        commit_id: &str,
-        path: &str,
-        line: u32,
-        side: &str,
-        body: &str,
-    ) -> anyhow::Result<u64> {
-        // Execute the create
-        let result = self
-            .inner
-            .create_review_comment(owner, repo, pr_number, commit_id, path, line, side, body)
-            .await;
-
-        // On success, invalidate cached comments for this repo
-        if result.is_ok() {
-            self.cache_invalidate_comments(owner, repo);
-        }
-
-        result
+        labels: &[String],
+    ) -> anyhow::Result<()> {
+        self.ensure_may_fetch(
+            "POST",
+            &format!("/repos/{}/{}/issues/{}/labels", owner, repo, pr_number),
+        )?;
+        // Mutations are never cached, but the cached PR lists now have stale labels
+        self.inner
+            .add_labels(owner, repo, pr_number, labels)
+            .await?;
+        self.cache_invalidate_pull_requests(owner, repo);
+        Ok(())
     }
 
-    async fn delete_review_comment(
+    async fn remove_label(
         &self,
         owner: &str,
         repo: &str,
-        comment_id: u64,
+        pr_number: u64,
+        label: &str,
     ) -> anyhow::Result<()> {
-        // Execute the delete
-        let result = self
-            .inner
-            .delete_review_comment(owner, repo, comment_id)
-            .await;
+        self.ensure_may_fetch(
+            "DELETE",
+            &format!(
+                "/repos/{}/{}/issues/{}/labels/{}",
+                owner, repo, pr_number, label
+            ),
+        )?;
+        // Mutations are never cached, but the cached PR lists now have stale labels
+        self.inner
+            .remove_label(owner, repo, pr_number, label)
+            .await?;
+        self.cache_invalidate_pull_requests(owner, repo);
+        Ok(())
+    }
 
-        // On success, invalidate cached comments for this repo
-        if result.is_ok() {
-            self.cache_invalidate_comments(owner, repo);
+    async fn fetch_collaborators(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<String>> {
+        let url = format!("/repos/{}/{}/collaborators", owner, repo);
+        let params: &[(&str, &str)] = &[];
+
+        // Collaborators change rarely, so the cache is always tried first
+        if let Some(cached_body) = self.try_cache_get("GET", &url, params) {
+            match serde_json::from_str::<Vec<String>>(&cached_body) {
+                Ok(logins) => {
+                    debug!(
+                        "Cache HIT for {}/{} collaborators: {} logins",
+                        owner,
+                        repo,
+                        logins.len()
+                    );
+                    return Ok(logins);
+                }
+                Err(e) => {
+                    debug!("Failed to parse cached collaborators: {}", e);
+                }
+            }
         }
 
-        result
+        self.ensure_may_fetch("GET", &url)?;
+
+        // Fetch from API
+        let logins = self.inner.fetch_collaborators(owner, repo).await?;
+
+        // Cache the result
+        if let Ok(json) = serde_json::to_string(&logins) {
+            self.cache_set("GET", &url, params, &json);
+        }
+
+        Ok(logins)
     }
 
-    async fn fetch_review_comments(
-        &self,
-        owner: &str,
-        repo: &str,
-        pr_number: u64,
-    ) -> anyhow::Result<Vec<ReviewComment>> {
-        let url = format!("/repos/{}/{}/pulls/{}/comments", owner, repo, pr_number);
+    async fn fetch_branches(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<String>> {
+        let url = format!("/repos/{}/{}/branches", owner, repo);
         let params: &[(&str, &str)] = &[];
 
-        // Try cache first
+        // Branches are only listed to pick one, the cache is tried first
         if let Some(cached_body) = self.try_cache_get("GET", &url, params) {
-            match serde_json::from_str::<Vec<ReviewComment>>(&cached_body) {
-                Ok(comments) => {
+            match serde_json::from_str::<Vec<String>>(&cached_body) {
+                Ok(branches) => {
                     debug!(
-                        "Cache HIT for {}/{} PR #{} comments: {} comments",
+                        "Cache HIT for {}/{} branches: {} branches",
                         owner,
                         repo,
-                        pr_number,
-                        comments.len()
+                        branches.len()
                     );
-                    return Ok(comments);
+                    return Ok(branches);
                 }
                 Err(e) => {
-                    debug!("Failed to parse cached comments: {}", e);
-                    // Fall through to fetch fresh data
+                    debug!("Failed to parse cached branches: {}", e);
                 }
             }
         }
 
-        // Fetch from API
-        let comments = self
-            .inner
-            .fetch_review_comments(owner, repo, pr_number)
-            .await?;
+        self.ensure_may_fetch("GET", &url)?;
 
-        // Cache the result
-        if let Ok(json) = serde_json::to_string(&comments) {
+        let branches = self.inner.fetch_branches(owner, repo).await?;
+
+        if let Ok(json) = serde_json::to_string(&branches) {
             self.cache_set("GET", &url, params, &json);
         }
 
-        Ok(comments)
+        Ok(branches)
     }
 
-    async fn fetch_review_decision(
+    async fn request_reviewers(
         &self,
         owner: &str,
         repo: &str,
         pr_number: u64,
-    ) -> anyhow::Result<ReviewDecision> {
-        let url = format!("/repos/{}/{}/pulls/{}/reviews", owner, repo, pr_number);
-        let params: &[(&str, &str)] = &[];
+        reviewers: &[String],
+        team_reviewers: &[String],
+    ) -> anyhow::Result<()> {
+        self.ensure_may_fetch(
+            "POST",
+            &format!(
+                "/repos/{}/{}/pulls/{}/requested_reviewers",
+                owner, repo, pr_number
+            ),
+        )?;
+        // Mutations are never cached, but the cached reviewers are now stale
+        self.inner
+            .request_reviewers(owner, repo, pr_number, reviewers, team_reviewers)
+            .await?;
+        self.cache_invalidate_pull_requests(owner, repo);
+        Ok(())
+    }
+
+    async fn rerun_failed_jobs(&self, owner: &str, repo: &str, run_id: u64) -> anyhow::Result<()> {
+        self.ensure_may_fetch(
+            "POST",
+            &format!(
+                "/repos/{}/{}/actions/runs/{}/rerun-failed-jobs",
+                owner, repo, run_id
+            ),
+        )?;
+        // Mutations are never cached - pass through directly
+        self.inner.rerun_failed_jobs(owner, repo, run_id).await
+    }
+
+    async fn fetch_workflow_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        head_sha: &str,
+    ) -> anyhow::Result<Vec<WorkflowRun>> {
+        let url = format!("/repos/{}/{}/actions/runs", owner, repo);
+        let params: &[(&str, &str)] = &[("head_sha", head_sha)];
 
         // Try cache first
         if let Some(cached_body) = self.try_cache_get("GET", &url, params) {
-            match serde_json::from_str::<ReviewDecision>(&cached_body) {
-                Ok(decision) => {
+            match serde_json::from_str::<Vec<WorkflowRun>>(&cached_body) {
+                Ok(runs) => {
                     debug!(
-                        "Cache HIT for {}/{} PR #{} review decision: {:?}",
-                        owner, repo, pr_number, decision
+                        "Cache HIT for {}/{} @ {}: {} workflow runs",
+                        owner,
+                        repo,
+                        head_sha,
+                        runs.len()
                     );
-                    return Ok(decision);
+                    return Ok(runs);
                 }
                 Err(e) => {
-                    debug!("Failed to parse cached review decision: {}", e);
+                    debug!("Failed to parse cached workflow runs: {}", e);
                 }
             }
         }
 
+        self.ensure_may_fetch("GET", &url)?;
+
         // Fetch from API
-        let decision = self
+        let runs = self
             .inner
-            .fetch_review_decision(owner, repo, pr_number)
+            .fetch_workflow_runs(owner, repo, head_sha)
             .await?;
 
         // Cache the result
-        if let Ok(json) = serde_json::to_string(&decision) {
+        if let Ok(json) = serde_json::to_string(&runs) {
             self.cache_set("GET", &url, params, &json);
         }
 
-        Ok(decision)
+        Ok(runs)
+    }
+
+    async fn fetch_recent_workflow_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        limit: usize,
+    ) -> anyhow::Result<Vec<WorkflowRun>> {
+        let url = format!("/repos/{}/{}/actions/runs", owner, repo);
+        let per_page = limit.to_string();
+        let params: &[(&str, &str)] = &[("branch", branch), ("per_page", &per_page)];
+
+        // Try cache first
+        if let Some(cached_body) = self.try_cache_get("GET", &url, params) {
+            match serde_json::from_str::<Vec<WorkflowRun>>(&cached_body) {
+                Ok(runs) => {
+                    debug!(
+                        "Cache HIT for {}/{} on {}: {} workflow runs",
+                        owner,
+                        repo,
+                        branch,
+                        runs.len()
+                    );
+                    return Ok(runs);
+                }
+                Err(e) => {
+                    debug!("Failed to parse cached workflow runs: {}", e);
+                }
+            }
+        }
+
+        self.ensure_may_fetch("GET", &url)?;
+
+        // Fetch from API
+        let runs = self
+            .inner
+            .fetch_recent_workflow_runs(owner, repo, branch, limit)
+            .await?;
+
+        // Cache the result
+        if let Ok(json) = serde_json::to_string(&runs) {
+            self.cache_set("GET", &url, params, &json);
+        }
+
+        Ok(runs)
+    }
+
+    async fn fetch_branch_head_sha(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> anyhow::Result<Option<String>> {
+        let url = format!("/repos/{}/{}/branches/{}", owner, repo, branch);
+        let params: &[(&str, &str)] = &[];
+
+        // Try cache first
+        if let Some(cached_body) = self.try_cache_get("GET", &url, params) {
+            match serde_json::from_str::<Option<String>>(&cached_body) {
+                Ok(sha) => {
+                    debug!("Cache HIT for head of {}/{}@{}", owner, repo, branch);
+                    return Ok(sha);
+                }
+                Err(e) => {
+                    debug!("Failed to parse cached branch head: {}", e);
+                }
+            }
+        }
+
+        self.ensure_may_fetch("GET", &url)?;
+
+        // Fetch from API
+        let sha = self
+            .inner
+            .fetch_branch_head_sha(owner, repo, branch)
+            .await?;
+
+        // Cache the result (a missing branch too, so it isn't asked for every time)
+        if let Ok(json) = serde_json::to_string(&sha) {
+            self.cache_set("GET", &url, params, &json);
+        }
+
+        Ok(sha)
+    }
+
+    async fn fetch_compare(
+        &self,
+        owner: &str,
+        repo: &str,
+        base: &str,
+        head: &str,
+    ) -> anyhow::Result<Comparison> {
+        self.ensure_may_fetch(
+            "GET",
+            &format!("/repos/{}/{}/compare/{}...{}", owner, repo, base, head),
+        )?;
+        // Branches move with every push, so comparisons are not cached
+        self.inner.fetch_compare(owner, repo, base, head).await
+    }
+
+    async fn fetch_latest_tag(&self, owner: &str, repo: &str) -> anyhow::Result<Option<String>> {
+        let url = format!("/repos/{}/{}/releases/latest", owner, repo);
+        let params: &[(&str, &str)] = &[];
+
+        // Try cache first
+        if let Some(cached_body) = self.try_cache_get("GET", &url, params) {
+            match serde_json::from_str::<Option<String>>(&cached_body) {
+                Ok(tag) => {
+                    debug!("Cache HIT for latest tag of {}/{}", owner, repo);
+                    return Ok(tag);
+                }
+                Err(e) => {
+                    debug!("Failed to parse cached latest tag: {}", e);
+                }
+            }
+        }
+
+        self.ensure_may_fetch("GET", &url)?;
+
+        // Fetch from API
+        let tag = self.inner.fetch_latest_tag(owner, repo).await?;
+
+        // Cache the result (no tags too, so they aren't asked for every time)
+        if let Ok(json) = serde_json::to_string(&tag) {
+            self.cache_set("GET", &url, params, &json);
+        }
+
+        Ok(tag)
+    }
+
+    async fn fetch_ref_date(
+        &self,
+        owner: &str,
+        repo: &str,
+        git_ref: &str,
+    ) -> anyhow::Result<Option<DateTime<Utc>>> {
+        let url = format!("/repos/{}/{}/commits/{}", owner, repo, git_ref);
+        let params: &[(&str, &str)] = &[];
+
+        // Try cache first
+        if let Some(cached_body) = self.try_cache_get("GET", &url, params) {
+            match serde_json::from_str::<Option<DateTime<Utc>>>(&cached_body) {
+                Ok(date) => {
+                    debug!("Cache HIT for date of {}/{}@{}", owner, repo, git_ref);
+                    return Ok(date);
+                }
+                Err(e) => {
+                    debug!("Failed to parse cached ref date: {}", e);
+                }
+            }
+        }
+
+        self.ensure_may_fetch("GET", &url)?;
+
+        // Fetch from API
+        let date = self.inner.fetch_ref_date(owner, repo, git_ref).await?;
+
+        // Cache the result
+        if let Ok(json) = serde_json::to_string(&date) {
+            self.cache_set("GET", &url, params, &json);
+        }
+
+        Ok(date)
+    }
+
+    async fn fetch_pull_request_diff(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<String> {
+        self.ensure_may_fetch(
+            "GET",
+            &format!("/repos/{}/{}/pulls/{}", owner, repo, pr_number),
+        )?;
+        // Diffs are only fetched for the diff viewer, which shows the latest push
+        self.inner
+            .fetch_pull_request_diff(owner, repo, pr_number)
+            .await
+    }
+
+    async fn fetch_ci_status(
+        &self,
+        owner: &str,
+        repo: &str,
+        head_sha: &str,
+    ) -> anyhow::Result<CiStatus> {
+        let url = format!("/repos/{}/{}/commits/{}/check-runs", owner, repo, head_sha);
+        let params: &[(&str, &str)] = &[];
+
+        // Try cache first
+        if let Some(cached_body) = self.try_cache_get("GET", &url, params) {
+            match serde_json::from_str::<CiStatus>(&cached_body) {
+                Ok(status) => {
+                    debug!(
+                        "Cache HIT for CI status {}/{} @ {}: {:?}",
+                        owner, repo, head_sha, status.state
+                    );
+                    return Ok(status);
+                }
+                Err(e) => {
+                    debug!("Failed to parse cached CI status: {}", e);
+                }
+            }
+        }
+
+        self.ensure_may_fetch("GET", &url)?;
+
+        // Fetch from API
+        let status = self.inner.fetch_ci_status(owner, repo, head_sha).await?;
+
+        // Cache the result
+        if let Ok(json) = serde_json::to_string(&status) {
+            self.cache_set("GET", &url, params, &json);
+        }
+
+        Ok(status)
+    }
+
+    async fn create_review_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        commit_id: &str,
+        path: &str,
+        lines: &CommentLines,
+        body: &str,
+    ) -> anyhow::Result<u64> {
+        self.ensure_may_fetch(
+            "POST",
+            &format!("/repos/{}/{}/pulls/{}/comments", owner, repo, pr_number),
+        )?;
+        // Execute the create
+        let result = self
+            .inner
+            .create_review_comment(owner, repo, pr_number, commit_id, path, lines, body)
+            .await;
+
+        // On success, invalidate cached comments for this repo
+        if result.is_ok() {
+            self.cache_invalidate_comments(owner, repo);
+        }
+
+        result
+    }
+
+    async fn delete_review_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        comment_id: u64,
+    ) -> anyhow::Result<()> {
+        self.ensure_may_fetch(
+            "DELETE",
+            &format!("/repos/{}/{}/pulls/comments/{}", owner, repo, comment_id),
+        )?;
+        // Execute the delete
+        let result = self
+            .inner
+            .delete_review_comment(owner, repo, comment_id)
+            .await;
+
+        // On success, invalidate cached comments for this repo
+        if result.is_ok() {
+            self.cache_invalidate_comments(owner, repo);
+        }
+
+        result
+    }
+
+    async fn create_file_review_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        commit_id: &str,
+        path: &str,
+        body: &str,
+    ) -> anyhow::Result<u64> {
+        self.ensure_may_fetch(
+            "POST",
+            &format!("/repos/{}/{}/pulls/{}/comments", owner, repo, pr_number),
+        )?;
+        let result = self
+            .inner
+            .create_file_review_comment(owner, repo, pr_number, commit_id, path, body)
+            .await;
+
+        // On success, invalidate cached comments for this repo
+        if result.is_ok() {
+            self.cache_invalidate_comments(owner, repo);
+        }
+
+        result
+    }
+
+    async fn create_issue_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        body: &str,
+    ) -> anyhow::Result<u64> {
+        self.ensure_may_fetch(
+            "POST",
+            &format!("/repos/{}/{}/issues/{}/comments", owner, repo, pr_number),
+        )?;
+        // Conversation comments are not cached, only their count
+        let result = self
+            .inner
+            .create_issue_comment(owner, repo, pr_number, body)
+            .await;
+
+        if result.is_ok() {
+            self.cache_invalidate_comments(owner, repo);
+        }
+
+        result
+    }
+
+    async fn delete_issue_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        comment_id: u64,
+    ) -> anyhow::Result<()> {
+        self.ensure_may_fetch(
+            "DELETE",
+            &format!("/repos/{}/{}/issues/comments/{}", owner, repo, comment_id),
+        )?;
+        // Conversation comments are not cached, only their count
+        let result = self
+            .inner
+            .delete_issue_comment(owner, repo, comment_id)
+            .await;
+
+        if result.is_ok() {
+            self.cache_invalidate_comments(owner, repo);
+        }
+
+        result
+    }
+
+    async fn fetch_review_comments(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<Vec<ReviewComment>> {
+        let url = format!("/repos/{}/{}/pulls/{}/comments", owner, repo, pr_number);
+        let params: &[(&str, &str)] = &[];
+
+        // Try cache first
+        if let Some(cached_body) = self.try_cache_get("GET", &url, params) {
+            match serde_json::from_str::<Vec<ReviewComment>>(&cached_body) {
+                Ok(comments) => {
+                    debug!(
+                        "Cache HIT for {}/{} PR #{} comments: {} comments",
+                        owner,
+                        repo,
+                        pr_number,
+                        comments.len()
+                    );
+                    return Ok(comments);
+                }
+                Err(e) => {
+                    debug!("Failed to parse cached comments: {}", e);
+                    // Fall through to fetch fresh data
+                }
+            }
+        }
+
+        self.ensure_may_fetch("GET", &url)?;
+
+        // Fetch from API
+        let comments = self
+            .inner
+            .fetch_review_comments(owner, repo, pr_number)
+            .await?;
+
+        // Cache the result
+        if let Ok(json) = serde_json::to_string(&comments) {
+            self.cache_set("GET", &url, params, &json);
+        }
+
+        Ok(comments)
+    }
+
+    async fn fetch_review_decision(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<ReviewDecision> {
+        let url = format!("/repos/{}/{}/pulls/{}/reviews", owner, repo, pr_number);
+        let params: &[(&str, &str)] = &[];
+
+        // Try cache first
+        if let Some(cached_body) = self.try_cache_get("GET", &url, params) {
+            match serde_json::from_str::<ReviewDecision>(&cached_body) {
+                Ok(decision) => {
+                    debug!(
+                        "Cache HIT for {}/{} PR #{} review decision: {:?}",
+                        owner, repo, pr_number, decision
+                    );
+                    return Ok(decision);
+                }
+                Err(e) => {
+                    debug!("Failed to parse cached review decision: {}", e);
+                }
+            }
+        }
+
+        self.ensure_may_fetch("GET", &url)?;
+
+        // Fetch from API
+        let decision = self
+            .inner
+            .fetch_review_decision(owner, repo, pr_number)
+            .await?;
+
+        // Cache the result
+        if let Ok(json) = serde_json::to_string(&decision) {
+            self.cache_set("GET", &url, params, &json);
+        }
+
+        Ok(decision)
+    }
+
+    async fn fetch_comment_counts(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<CommentCounts> {
+        // GraphQL has no URL per resource, key the counts under the PR so
+        // comment mutations invalidate them along with the comments
+        let url = format!(
+            "/repos/{}/{}/pulls/{}/comment-counts",
+            owner, repo, pr_number
+        );
+        let params: &[(&str, &str)] = &[];
+
+        if let Some(cached_body) = self.try_cache_get("GET", &url, params) {
+            match serde_json::from_str::<CommentCounts>(&cached_body) {
+                Ok(counts) => {
+                    debug!(
+                        "Cache HIT for {}/{} PR #{} comment counts",
+                        owner, repo, pr_number
+                    );
+                    return Ok(counts);
+                }
+                Err(e) => {
+                    debug!("Failed to parse cached comment counts: {}", e);
+                }
+            }
+        }
+
+        self.ensure_may_fetch("GET", &url)?;
+
+        let counts = self
+            .inner
+            .fetch_comment_counts(owner, repo, pr_number)
+            .await?;
+
+        if let Ok(json) = serde_json::to_string(&counts) {
+            self.cache_set("GET", &url, params, &json);
+        }
+
+        Ok(counts)
+    }
+
+    async fn fetch_reviewers(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_numbers: &[u64],
+    ) -> anyhow::Result<Vec<PrReviewers>> {
+        // Under a "/reviews" URL, so only read from cache when offline
+        let url = format!("/repos/{}/{}/pulls/reviews", owner, repo);
+        let numbers = pr_numbers
+            .iter()
+            .map(u64::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        let params: &[(&str, &str)] = &[("numbers", &numbers)];
+
+        if let Some(cached_body) = self.try_cache_get("GET", &url, params) {
+            match serde_json::from_str::<Vec<PrReviewers>>(&cached_body) {
+                Ok(reviewers) => {
+                    debug!(
+                        "Cache HIT for {}/{} reviewers of {} PRs",
+                        owner,
+                        repo,
+                        reviewers.len()
+                    );
+                    return Ok(reviewers);
+                }
+                Err(e) => {
+                    debug!("Failed to parse cached reviewers: {}", e);
+                }
+            }
+        }
+
+        self.ensure_may_fetch("GET", &url)?;
+
+        let reviewers = self.inner.fetch_reviewers(owner, repo, pr_numbers).await?;
+
+        if let Ok(json) = serde_json::to_string(&reviewers) {
+            self.cache_set("GET", &url, params, &json);
+        }
+
+        Ok(reviewers)
+    }
+
+    async fn fetch_blame(
+        &self,
+        owner: &str,
+        repo: &str,
+        commit_sha: &str,
+        path: &str,
+    ) -> anyhow::Result<Vec<BlameRange>> {
+        // Blame at a commit never changes; the path goes into the params so
+        // file names can't match a volatile URL pattern
+        let url = format!("/repos/{}/{}/commits/{}/blame", owner, repo, commit_sha);
+        let params: &[(&str, &str)] = &[("path", path)];
+
+        if let Some(cached_body) = self.try_cache_get("GET", &url, params) {
+            match serde_json::from_str::<Vec<BlameRange>>(&cached_body) {
+                Ok(ranges) => {
+                    debug!(
+                        "Cache HIT for {}/{} blame of {} at {}",
+                        owner, repo, path, commit_sha
+                    );
+                    return Ok(ranges);
+                }
+                Err(e) => {
+                    debug!("Failed to parse cached blame: {}", e);
+                }
+            }
+        }
+
+        self.ensure_may_fetch("GET", &url)?;
+
+        let ranges = self
+            .inner
+            .fetch_blame(owner, repo, commit_sha, path)
+            .await?;
+
+        if let Ok(json) = serde_json::to_string(&ranges) {
+            self.cache_set("GET", &url, params, &json);
+        }
+
+        Ok(ranges)
+    }
+
+    async fn fetch_file_content(
+        &self,
+        owner: &str,
+        repo: &str,
+        path: &str,
+        git_ref: Option<&str>,
+    ) -> anyhow::Result<String> {
+        // The path goes into the params so file names can't match a volatile
+        // URL pattern
+        let url = format!("/repos/{}/{}/contents", owner, repo);
+        let params: &[(&str, &str)] = &[("path", path), ("ref", git_ref.unwrap_or(""))];
+
+        if let Some(cached_body) = self.try_cache_get("GET", &url, params) {
+            match serde_json::from_str::<String>(&cached_body) {
+                Ok(content) => {
+                    debug!("Cache HIT for {}/{} file {}", owner, repo, path);
+                    return Ok(content);
+                }
+                Err(e) => {
+                    debug!("Failed to parse cached file content: {}", e);
+                }
+            }
+        }
+
+        self.ensure_may_fetch("GET", &url)?;
+
+        let content = self
+            .inner
+            .fetch_file_content(owner, repo, path, git_ref)
+            .await?;
+
+        if let Ok(json) = serde_json::to_string(&content) {
+            self.cache_set("GET", &url, params, &json);
+        }
+
+        Ok(content)
+    }
+
+    async fn capabilities(&self) -> HostCapabilities {
+        self.inner.capabilities().await
     }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
-    use crate::types::{CheckState, CiState, MergeableState};
+    use crate::types::{CheckState, CiState, MergeableState, PrState};
     use chrono::Utc;
 
     /// Mock client for testing
     #[derive(Debug, Clone)]
-    struct MockClient {
+    pub(crate) struct MockClient {
         prs: Vec<PullRequest>,
         call_count: Arc<Mutex<usize>>,
     }
 
     impl MockClient {
-        fn new(prs: Vec<PullRequest>) -> Self {
+        pub(crate) fn new(prs: Vec<PullRequest>) -> Self {
             Self {
                 prs,
                 call_count: Arc::new(Mutex::new(0)),
             }
         }
 
-        fn call_count(&self) -> usize {
+        pub(crate) fn call_count(&self) -> usize {
             *self.call_count.lock().unwrap()
         }
     }
@@ -630,11 +1519,32 @@ mod tests {
             _owner: &str,
             _repo: &str,
             _base_branch: Option<&str>,
+            _state: PrStateFilter,
         ) -> anyhow::Result<Vec<PullRequest>> {
             *self.call_count.lock().unwrap() += 1;
             Ok(self.prs.clone())
         }
 
+        async fn search_pull_requests_in_repo(
+            &self,
+            _owner: &str,
+            _repo: &str,
+            _query: &str,
+            _page: u32,
+        ) -> anyhow::Result<SearchResults> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(SearchResults {
+                total_count: self.prs.len() as u64,
+                items: self.prs.clone(),
+                incomplete: false,
+            })
+        }
+
+        async fn count_search(&self, _query: &str) -> anyhow::Result<u64> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(self.prs.len() as u64)
+        }
+
         async fn fetch_pull_request(
             &self,
             _owner: &str,
@@ -659,87 +1569,243 @@ mod tests {
             Ok(vec![])
         }
 
-        async fn fetch_commit_status(
+        async fn fetch_commit_status(
+            &self,
+            _owner: &str,
+            _repo: &str,
+            _commit_sha: &str,
+        ) -> anyhow::Result<CheckStatus> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(CheckStatus {
+                state: CheckState::Success,
+                total_count: 0,
+                statuses: vec![],
+            })
+        }
+
+        async fn merge_pull_request(
+            &self,
+            _owner: &str,
+            _repo: &str,
+            _pr_number: u64,
+            _merge_method: MergeMethod,
+            _commit_title: Option<&str>,
+            _commit_message: Option<&str>,
+        ) -> anyhow::Result<MergeResult> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(MergeResult {
+                merged: true,
+                sha: Some("abc123".to_string()),
+                message: "Merged".to_string(),
+                rejection: None,
+            })
+        }
+
+        async fn update_pull_request_branch(
+            &self,
+            _owner: &str,
+            _repo: &str,
+            _pr_number: u64,
+        ) -> anyhow::Result<()> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(())
+        }
+
+        async fn create_review(
+            &self,
+            _owner: &str,
+            _repo: &str,
+            _pr_number: u64,
+            _event: ReviewEvent,
+            _body: Option<&str>,
+        ) -> anyhow::Result<()> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(())
+        }
+
+        async fn close_pull_request(
+            &self,
+            _owner: &str,
+            _repo: &str,
+            _pr_number: u64,
+        ) -> anyhow::Result<()> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(())
+        }
+
+        async fn fetch_repository_settings(
+            &self,
+            _owner: &str,
+            _repo: &str,
+        ) -> anyhow::Result<RepositorySettings> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(RepositorySettings {
+                default_branch: "main".to_string(),
+                delete_branch_on_merge: Some(true),
+            })
+        }
+
+        async fn fetch_required_checks(
+            &self,
+            _owner: &str,
+            _repo: &str,
+            _branch: &str,
+        ) -> anyhow::Result<Option<Vec<String>>> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(None)
+        }
+
+        async fn delete_branch(
+            &self,
+            _owner: &str,
+            _repo: &str,
+            _branch: &str,
+        ) -> anyhow::Result<()> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(())
+        }
+
+        async fn fetch_repo_labels(&self, _owner: &str, _repo: &str) -> anyhow::Result<Vec<Label>> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(vec![Label {
+                name: "skip-changelog".to_string(),
+                color: "ededed".to_string(),
+                description: None,
+            }])
+        }
+
+        async fn add_labels(
+            &self,
+            _owner: &str,
+            _repo: &str,
+            _pr_number: u64,
+            _labels: &[String],
+        ) -> anyhow::Result<()> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(())
+        }
+
+        async fn remove_label(
+            &self,
+            _owner: &str,
+            _repo: &str,
+            _pr_number: u64,
+            _label: &str,
+        ) -> anyhow::Result<()> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(())
+        }
+
+        async fn fetch_collaborators(
+            &self,
+            _owner: &str,
+            _repo: &str,
+        ) -> anyhow::Result<Vec<String>> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(vec!["alice".to_string()])
+        }
+
+        async fn fetch_branches(&self, _owner: &str, _repo: &str) -> anyhow::Result<Vec<String>> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(vec!["main".to_string()])
+        }
+
+        async fn request_reviewers(
+            &self,
+            _owner: &str,
+            _repo: &str,
+            _pr_number: u64,
+            _reviewers: &[String],
+            _team_reviewers: &[String],
+        ) -> anyhow::Result<()> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(())
+        }
+
+        async fn rerun_failed_jobs(
+            &self,
+            _owner: &str,
+            _repo: &str,
+            _run_id: u64,
+        ) -> anyhow::Result<()> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(())
+        }
+
+        async fn fetch_workflow_runs(
             &self,
             _owner: &str,
             _repo: &str,
-            _commit_sha: &str,
-        ) -> anyhow::Result<CheckStatus> {
+            _head_sha: &str,
+        ) -> anyhow::Result<Vec<WorkflowRun>> {
             *self.call_count.lock().unwrap() += 1;
-            Ok(CheckStatus {
-                state: CheckState::Success,
-                total_count: 0,
-                statuses: vec![],
-            })
+            Ok(vec![])
         }
 
-        async fn merge_pull_request(
+        async fn fetch_recent_workflow_runs(
             &self,
             _owner: &str,
             _repo: &str,
-            _pr_number: u64,
-            _merge_method: MergeMethod,
-            _commit_title: Option<&str>,
-            _commit_message: Option<&str>,
-        ) -> anyhow::Result<MergeResult> {
+            _branch: &str,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<WorkflowRun>> {
             *self.call_count.lock().unwrap() += 1;
-            Ok(MergeResult {
-                merged: true,
-                sha: Some("abc123".to_string()),
-                message: "Merged".to_string(),
-            })
+            Ok(vec![])
         }
 
-        async fn update_pull_request_branch(
+        async fn fetch_branch_head_sha(
             &self,
             _owner: &str,
             _repo: &str,
-            _pr_number: u64,
-        ) -> anyhow::Result<()> {
+            branch: &str,
+        ) -> anyhow::Result<Option<String>> {
             *self.call_count.lock().unwrap() += 1;
-            Ok(())
+            Ok((branch == "main").then(|| "abc123".to_string()))
         }
 
-        async fn create_review(
+        async fn fetch_compare(
             &self,
             _owner: &str,
             _repo: &str,
-            _pr_number: u64,
-            _event: ReviewEvent,
-            _body: Option<&str>,
-        ) -> anyhow::Result<()> {
+            _base: &str,
+            _head: &str,
+        ) -> anyhow::Result<Comparison> {
             *self.call_count.lock().unwrap() += 1;
-            Ok(())
+            Ok(Comparison {
+                merge_base_sha: "abc123".to_string(),
+                ahead_by: 1,
+                behind_by: 0,
+                files: vec![],
+            })
         }
 
-        async fn close_pull_request(
+        async fn fetch_latest_tag(
             &self,
             _owner: &str,
             _repo: &str,
-            _pr_number: u64,
-        ) -> anyhow::Result<()> {
+        ) -> anyhow::Result<Option<String>> {
             *self.call_count.lock().unwrap() += 1;
-            Ok(())
+            Ok(Some("v1.0.0".to_string()))
         }
 
-        async fn rerun_failed_jobs(
+        async fn fetch_ref_date(
             &self,
             _owner: &str,
             _repo: &str,
-            _run_id: u64,
-        ) -> anyhow::Result<()> {
+            git_ref: &str,
+        ) -> anyhow::Result<Option<DateTime<Utc>>> {
             *self.call_count.lock().unwrap() += 1;
-            Ok(())
+            Ok((git_ref == "v1.0.0").then(Utc::now))
         }
 
-        async fn fetch_workflow_runs(
+        async fn fetch_pull_request_diff(
             &self,
             _owner: &str,
             _repo: &str,
-            _head_sha: &str,
-        ) -> anyhow::Result<Vec<WorkflowRun>> {
+            _pr_number: u64,
+        ) -> anyhow::Result<String> {
             *self.call_count.lock().unwrap() += 1;
-            Ok(vec![])
+            Ok(String::new())
         }
 
         async fn fetch_ci_status(
@@ -755,6 +1821,13 @@ mod tests {
                 passed: 0,
                 failed: 0,
                 pending: 0,
+                queued: 0,
+                waiting: 0,
+                checks: vec![],
+                required_failed: 0,
+                optional_failed: 0,
+                required_pending: 0,
+                required_known: false,
             })
         }
 
@@ -765,8 +1838,7 @@ mod tests {
             _pr_number: u64,
             _commit_id: &str,
             _path: &str,
-            _line: u32,
-            _side: &str,
+            _lines: &CommentLines,
             _body: &str,
         ) -> anyhow::Result<u64> {
             *self.call_count.lock().unwrap() += 1;
@@ -783,6 +1855,40 @@ mod tests {
             Ok(())
         }
 
+        async fn create_file_review_comment(
+            &self,
+            _owner: &str,
+            _repo: &str,
+            _pr_number: u64,
+            _commit_id: &str,
+            _path: &str,
+            _body: &str,
+        ) -> anyhow::Result<u64> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(12346) // Mock comment ID
+        }
+
+        async fn create_issue_comment(
+            &self,
+            _owner: &str,
+            _repo: &str,
+            _pr_number: u64,
+            _body: &str,
+        ) -> anyhow::Result<u64> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(12347) // Mock comment ID
+        }
+
+        async fn delete_issue_comment(
+            &self,
+            _owner: &str,
+            _repo: &str,
+            _comment_id: u64,
+        ) -> anyhow::Result<()> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(())
+        }
+
         async fn fetch_review_comments(
             &self,
             _owner: &str,
@@ -802,9 +1908,67 @@ mod tests {
             *self.call_count.lock().unwrap() += 1;
             Ok(ReviewDecision::Pending)
         }
+
+        async fn fetch_comment_counts(
+            &self,
+            _owner: &str,
+            _repo: &str,
+            _pr_number: u64,
+        ) -> anyhow::Result<CommentCounts> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(CommentCounts {
+                unresolved_threads: 1,
+                review_comments: 2,
+                issue_comments: 3,
+            })
+        }
+
+        async fn fetch_reviewers(
+            &self,
+            _owner: &str,
+            _repo: &str,
+            pr_numbers: &[u64],
+        ) -> anyhow::Result<Vec<PrReviewers>> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(pr_numbers
+                .iter()
+                .map(|&number| PrReviewers {
+                    number,
+                    ..Default::default()
+                })
+                .collect())
+        }
+
+        async fn fetch_blame(
+            &self,
+            _owner: &str,
+            _repo: &str,
+            commit_sha: &str,
+            _path: &str,
+        ) -> anyhow::Result<Vec<BlameRange>> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(vec![BlameRange {
+                start_line: 1,
+                end_line: 10,
+                commit_sha: commit_sha.to_string(),
+                author: "testuser".to_string(),
+                committed_at: Utc::now(),
+            }])
+        }
+
+        async fn fetch_file_content(
+            &self,
+            _owner: &str,
+            _repo: &str,
+            path: &str,
+            _git_ref: Option<&str>,
+        ) -> anyhow::Result<String> {
+            *self.call_count.lock().unwrap() += 1;
+            Ok(format!("content of {}", path))
+        }
     }
 
-    fn create_test_pr(number: u64) -> PullRequest {
+    pub(crate) fn create_test_pr(number: u64) -> PullRequest {
         use crate::types::MaturityState;
 
         PullRequest {
@@ -818,33 +1982,41 @@ mod tests {
             head_branch: "feature".to_string(),
             mergeable: Some(true),
             mergeable_state: Some(MergeableState::Clean),
+            mergeable_state_raw: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             html_url: "https://github.com/test/repo/pull/1".to_string(),
             additions: 100,
             deletions: 50,
+            changed_files: Some(3),
             maturity: MaturityState::Ready,
             review_decision: ReviewDecision::Pending,
+            state: PrState::Open,
+            labels: Vec::new(),
+            head_repo_owner: Some("test".to_string()),
+            head_repo_name: Some("repo".to_string()),
+            is_from_fork: false,
+            maintainer_can_modify: false,
         }
     }
 
     #[tokio::test]
-    async fn test_cache_mode_none_skips_cache() {
+    async fn test_cache_mode_bypass_skips_cache() {
         let mock = MockClient::new(vec![create_test_pr(1)]);
         let cache = Arc::new(Mutex::new(ApiCache::default()));
-        let client = CachedGitHubClient::new(mock.clone(), cache, CacheMode::None);
+        let client = CachedGitHubClient::new(mock.clone(), cache, CacheMode::Bypass);
 
         // First call
         let prs1 = client
-            .fetch_pull_requests("owner", "repo", None)
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
             .await
             .unwrap();
         assert_eq!(prs1.len(), 1);
         assert_eq!(mock.call_count(), 1);
 
-        // Second call - should NOT use cache (mode is None)
+        // Second call - should NOT use cache (mode is Bypass)
         let prs2 = client
-            .fetch_pull_requests("owner", "repo", None)
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
             .await
             .unwrap();
         assert_eq!(prs2.len(), 1);
@@ -859,7 +2031,7 @@ mod tests {
 
         // First call - cache miss, calls mock
         let prs1 = client
-            .fetch_pull_requests("owner", "repo", None)
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
             .await
             .unwrap();
         assert_eq!(prs1.len(), 1);
@@ -867,7 +2039,7 @@ mod tests {
 
         // Second call - should use cache
         let prs2 = client
-            .fetch_pull_requests("owner", "repo", None)
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
             .await
             .unwrap();
         assert_eq!(prs2.len(), 1);
@@ -882,7 +2054,7 @@ mod tests {
 
         // First call - writes to cache
         let prs1 = client
-            .fetch_pull_requests("owner", "repo", None)
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
             .await
             .unwrap();
         assert_eq!(prs1.len(), 1);
@@ -890,7 +2062,7 @@ mod tests {
 
         // Second call - should NOT read from cache (WriteOnly mode)
         let prs2 = client
-            .fetch_pull_requests("owner", "repo", None)
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
             .await
             .unwrap();
         assert_eq!(prs2.len(), 1);
@@ -899,7 +2071,7 @@ mod tests {
         // But cache should have the data (verify with ReadWrite mode)
         let read_client = CachedGitHubClient::new(mock.clone(), cache, CacheMode::ReadWrite);
         let prs3 = read_client
-            .fetch_pull_requests("owner", "repo", None)
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
             .await
             .unwrap();
         assert_eq!(prs3.len(), 1);
@@ -915,7 +2087,7 @@ mod tests {
         let write_client =
             CachedGitHubClient::new(mock.clone(), cache.clone(), CacheMode::ReadWrite);
         write_client
-            .fetch_pull_requests("owner", "repo", None)
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
             .await
             .unwrap();
         assert_eq!(mock.call_count(), 1);
@@ -926,7 +2098,7 @@ mod tests {
         // ReadOnly client should read from cache
         let read_client = CachedGitHubClient::new(mock2.clone(), cache, CacheMode::ReadOnly);
         let prs = read_client
-            .fetch_pull_requests("owner", "repo", None)
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
             .await
             .unwrap();
 
@@ -936,6 +2108,191 @@ mod tests {
         assert_eq!(mock2.call_count(), 0); // Never called, used cache
     }
 
+    /// Fetch a PR list in `mode` with the API serving PR #2, after PR #1 was
+    /// cached (if `cached`) under `ttl`
+    ///
+    /// Returns the fetched PRs (`None` for a `CacheMiss`), the API calls of
+    /// the fetch and the cached PRs afterwards.
+    async fn fetch_in_mode(
+        mode: CacheMode,
+        ttl: std::time::Duration,
+        cached: bool,
+    ) -> (Option<Vec<u64>>, usize, Option<Vec<u64>>) {
+        let cache = Arc::new(Mutex::new(ApiCache::default().with_ttl(ttl)));
+        if cached {
+            let writer = CachedGitHubClient::new(
+                MockClient::new(vec![create_test_pr(1)]),
+                cache.clone(),
+                CacheMode::ReadWrite,
+            );
+            writer
+                .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
+                .await
+                .unwrap();
+        }
+
+        let mock = MockClient::new(vec![create_test_pr(2)]);
+        let client = CachedGitHubClient::new(mock.clone(), cache, mode);
+        let fetched = match client
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
+            .await
+        {
+            Ok(prs) => Some(prs.iter().map(|pr| pr.number).collect()),
+            Err(e) => {
+                assert!(e.downcast_ref::<CacheMiss>().is_some(), "{}", e);
+                None
+            }
+        };
+        let after = client
+            .peek_pull_requests("owner", "repo", None, PrStateFilter::Open)
+            .map(|cached| cached.prs.iter().map(|pr| pr.number).collect());
+        (fetched, mock.call_count(), after)
+    }
+
+    #[tokio::test]
+    async fn test_cache_mode_matrix() {
+        use std::time::Duration;
+
+        let fresh = Duration::from_secs(60 * 60);
+        let stale = Duration::ZERO;
+        let cached = |n: u64| Some(vec![n]);
+
+        // (mode, fresh hit, stale hit, miss)
+        let cases = [
+            (
+                CacheMode::ReadWrite,
+                (cached(1), 0, cached(1)),
+                (cached(2), 1, cached(2)),
+                (cached(2), 1, cached(2)),
+            ),
+            (
+                CacheMode::ReadOnly,
+                (cached(1), 0, cached(1)),
+                (cached(1), 0, cached(1)),
+                (None, 0, None),
+            ),
+            (
+                CacheMode::WriteOnly,
+                (cached(2), 1, cached(2)),
+                (cached(2), 1, cached(2)),
+                (cached(2), 1, cached(2)),
+            ),
+            (
+                CacheMode::Bypass,
+                (cached(2), 1, cached(1)),
+                (cached(2), 1, cached(1)),
+                (cached(2), 1, None),
+            ),
+        ];
+
+        for (mode, fresh_hit, stale_hit, miss) in cases {
+            assert_eq!(
+                fetch_in_mode(mode, fresh, true).await,
+                fresh_hit,
+                "{:?} fresh hit",
+                mode
+            );
+            assert_eq!(
+                fetch_in_mode(mode, stale, true).await,
+                stale_hit,
+                "{:?} stale hit",
+                mode
+            );
+            assert_eq!(
+                fetch_in_mode(mode, fresh, false).await,
+                miss,
+                "{:?} miss",
+                mode
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_only_serves_volatile_data_and_refuses_mutations() {
+        let mock = MockClient::new(vec![create_test_pr(1)]);
+        let cache = Arc::new(Mutex::new(ApiCache::default()));
+        let client = CachedGitHubClient::new(mock.clone(), cache, CacheMode::ReadWrite);
+        let status = client
+            .fetch_ci_status("owner", "repo", "abc123")
+            .await
+            .unwrap();
+        assert_eq!(mock.call_count(), 1);
+
+        // CI status is volatile, so only a read-only client answers from cache
+        client
+            .fetch_ci_status("owner", "repo", "abc123")
+            .await
+            .unwrap();
+        assert_eq!(mock.call_count(), 2);
+        let offline = client.with_mode(CacheMode::ReadOnly);
+        let cached = offline
+            .fetch_ci_status("owner", "repo", "abc123")
+            .await
+            .unwrap();
+        assert_eq!(cached.state, status.state);
+        assert_eq!(mock.call_count(), 2);
+
+        let err = offline
+            .close_pull_request("owner", "repo", 1)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<CacheMiss>().unwrap().request,
+            "PATCH /repos/owner/repo/pulls/1"
+        );
+        assert_eq!(mock.call_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_peek_pull_requests_never_fetches() {
+        let mock = MockClient::new(vec![create_test_pr(1), create_test_pr(2)]);
+        let cache = Arc::new(Mutex::new(ApiCache::default()));
+        let client = CachedGitHubClient::new(mock.clone(), cache.clone(), CacheMode::WriteOnly)
+            .with_namespace("peek@github.com");
+
+        // Nothing cached yet
+        assert!(client
+            .peek_pull_requests("owner", "peek-repo", None, PrStateFilter::Open)
+            .is_none());
+        assert_eq!(mock.call_count(), 0);
+
+        let before = Utc::now() - chrono::Duration::seconds(1);
+        client
+            .fetch_pull_requests("owner", "peek-repo", None, PrStateFilter::Open)
+            .await
+            .unwrap();
+
+        // Peeking ignores the mode and sorts like a cache hit
+        let cached = client
+            .peek_pull_requests("owner", "peek-repo", None, PrStateFilter::Open)
+            .unwrap();
+        assert_eq!(
+            cached.prs.iter().map(|pr| pr.number).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        assert!(cached.cached_at >= before);
+        assert_eq!(mock.call_count(), 1);
+
+        // Other filters and namespaces have their own lists
+        assert!(client
+            .peek_pull_requests("owner", "peek-repo", None, PrStateFilter::Closed)
+            .is_none());
+        let cache = cache.lock().unwrap();
+        assert!(
+            peek_pull_requests(&cache, "", "owner", "peek-repo", None, PrStateFilter::Open)
+                .is_none()
+        );
+        assert!(peek_pull_requests(
+            &cache,
+            "peek@github.com",
+            "owner",
+            "peek-repo",
+            None,
+            PrStateFilter::Open
+        )
+        .is_some());
+    }
+
     #[tokio::test]
     async fn test_with_mode_creates_new_client() {
         let mock = MockClient::new(vec![create_test_pr(1)]);
@@ -951,6 +2308,214 @@ mod tests {
         assert_eq!(client.cache_mode(), CacheMode::ReadWrite);
     }
 
+    #[tokio::test]
+    async fn test_search_is_never_cached() {
+        let mock = MockClient::new(vec![create_test_pr(1)]);
+        let cache = Arc::new(Mutex::new(ApiCache::default()));
+        let client = CachedGitHubClient::new(mock.clone(), cache, CacheMode::ReadWrite);
+
+        for _ in 0..2 {
+            let results = client
+                .search_pull_requests_in_repo("owner", "repo", "is:merged", 1)
+                .await
+                .unwrap();
+            assert_eq!(results.items.len(), 1);
+        }
+        assert_eq!(mock.call_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cache_is_keyed_by_pr_state() {
+        let mock = MockClient::new(vec![create_test_pr(1)]);
+        let cache = Arc::new(Mutex::new(ApiCache::default()));
+        let client = CachedGitHubClient::new(mock.clone(), cache, CacheMode::ReadWrite);
+
+        client
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
+            .await
+            .unwrap();
+        // Another filter is not served from the open list
+        client
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Merged)
+            .await
+            .unwrap();
+        assert_eq!(mock.call_count(), 2);
+
+        // Each filter hits its own cache entry
+        client
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Merged)
+            .await
+            .unwrap();
+        client
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
+            .await
+            .unwrap();
+        assert_eq!(mock.call_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_namespaces_do_not_share_cached_data() {
+        let cache = Arc::new(Mutex::new(ApiCache::default()));
+        let work_mock = MockClient::new(vec![create_test_pr(1)]);
+        let personal_mock = MockClient::new(vec![create_test_pr(2)]);
+        let work = CachedGitHubClient::new(work_mock.clone(), cache.clone(), CacheMode::ReadWrite)
+            .with_namespace("work@github.com");
+        let personal =
+            CachedGitHubClient::new(personal_mock.clone(), cache.clone(), CacheMode::ReadWrite)
+                .with_namespace("personal@github.com");
+
+        let work_prs = work
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
+            .await
+            .unwrap();
+        assert_eq!(work_prs[0].number, 1);
+
+        // Same repository, other account: fetched, not served from work's cache
+        let personal_prs = personal
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
+            .await
+            .unwrap();
+        assert_eq!(personal_prs[0].number, 2);
+        assert_eq!(personal_mock.call_count(), 1);
+
+        // Each account reads back only its own entry
+        let work_prs = work
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
+            .await
+            .unwrap();
+        let personal_prs = personal
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
+            .await
+            .unwrap();
+        assert_eq!(work_prs[0].number, 1);
+        assert_eq!(personal_prs[0].number, 2);
+        assert_eq!(work_mock.call_count(), 1);
+        assert_eq!(personal_mock.call_count(), 1);
+
+        // The namespace survives a mode switch
+        let refresh = work.with_mode(CacheMode::ReadOnly);
+        assert_eq!(refresh.namespace(), "work@github.com");
+    }
+
+    #[tokio::test]
+    async fn test_labels_are_cached_and_mutations_invalidate_pr_lists() {
+        let mock = MockClient::new(vec![create_test_pr(1)]);
+        let cache = Arc::new(Mutex::new(ApiCache::default()));
+        let client = CachedGitHubClient::new(mock.clone(), cache, CacheMode::ReadWrite);
+
+        let labels = client.fetch_repo_labels("owner", "repo").await.unwrap();
+        assert_eq!(labels[0].name, "skip-changelog");
+        client.fetch_repo_labels("owner", "repo").await.unwrap();
+        assert_eq!(mock.call_count(), 1);
+
+        client
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
+            .await
+            .unwrap();
+        assert_eq!(mock.call_count(), 2);
+
+        // Adding a label makes the cached PR list stale, labels stay cached
+        client
+            .add_labels("owner", "repo", 1, &["skip-changelog".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(mock.call_count(), 3);
+        client
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
+            .await
+            .unwrap();
+        client.fetch_repo_labels("owner", "repo").await.unwrap();
+        assert_eq!(mock.call_count(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_collaborators_are_cached_across_review_requests() {
+        let mock = MockClient::new(vec![]);
+        let cache = Arc::new(Mutex::new(ApiCache::default()));
+        let client = CachedGitHubClient::new(mock.clone(), cache, CacheMode::ReadWrite);
+
+        let logins = client.fetch_collaborators("owner", "repo").await.unwrap();
+        assert_eq!(logins, vec!["alice".to_string()]);
+        client
+            .request_reviewers("owner", "repo", 1, &logins, &[])
+            .await
+            .unwrap();
+        client.fetch_collaborators("owner", "repo").await.unwrap();
+        assert_eq!(mock.call_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_comment_counts_are_cached_until_a_comment_is_posted() {
+        let mock = MockClient::new(vec![]);
+        let cache = Arc::new(Mutex::new(ApiCache::default()));
+        let client = CachedGitHubClient::new(mock.clone(), cache, CacheMode::ReadWrite);
+
+        let counts = client
+            .fetch_comment_counts("owner", "repo", 1)
+            .await
+            .unwrap();
+        assert_eq!(counts.unresolved_threads, 1);
+        client
+            .fetch_comment_counts("owner", "repo", 1)
+            .await
+            .unwrap();
+        assert_eq!(mock.call_count(), 1);
+
+        client
+            .create_issue_comment("owner", "repo", 1, "LGTM")
+            .await
+            .unwrap();
+        client
+            .fetch_comment_counts("owner", "repo", 1)
+            .await
+            .unwrap();
+        assert_eq!(mock.call_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_blame_is_cached_per_file() {
+        let mock = MockClient::new(vec![]);
+        let cache = Arc::new(Mutex::new(ApiCache::default()));
+        let client = CachedGitHubClient::new(mock.clone(), cache, CacheMode::ReadWrite);
+
+        for _ in 0..2 {
+            let ranges = client
+                .fetch_blame("owner", "repo", "abc123", "src/status.rs")
+                .await
+                .unwrap();
+            assert_eq!(ranges[0].end_line, 10);
+        }
+        assert_eq!(mock.call_count(), 1);
+
+        client
+            .fetch_blame("owner", "repo", "abc123", "src/lib.rs")
+            .await
+            .unwrap();
+        assert_eq!(mock.call_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_file_content_is_cached_per_ref() {
+        let mock = MockClient::new(vec![]);
+        let cache = Arc::new(Mutex::new(ApiCache::default()));
+        let client = CachedGitHubClient::new(mock.clone(), cache, CacheMode::ReadWrite);
+
+        for _ in 0..2 {
+            let content = client
+                .fetch_file_content("owner", "repo", ".github/status.md", None)
+                .await
+                .unwrap();
+            assert_eq!(content, "content of .github/status.md");
+        }
+        assert_eq!(mock.call_count(), 1);
+
+        client
+            .fetch_file_content("owner", "repo", ".github/status.md", Some("v1"))
+            .await
+            .unwrap();
+        assert_eq!(mock.call_count(), 2);
+    }
+
     #[test]
     fn test_volatile_endpoint_detection() {
         // Volatile endpoints - should NOT be cached
@@ -967,4 +2532,35 @@ mod tests {
         assert!(!is_volatile_endpoint("/repos/owner/repo/pulls/42"));
         assert!(!is_volatile_endpoint("/repos/owner/repo/pulls/42/comments"));
     }
+
+    #[tokio::test]
+    async fn test_missing_branch_is_cached() {
+        let mock = MockClient::new(vec![]);
+        let cache = Arc::new(Mutex::new(ApiCache::default()));
+        let client = CachedGitHubClient::new(mock.clone(), cache, CacheMode::ReadWrite);
+
+        assert_eq!(
+            client
+                .fetch_branch_head_sha("owner", "repo", "main")
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("abc123")
+        );
+        assert_eq!(
+            client
+                .fetch_branch_head_sha("owner", "repo", "gone")
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            client
+                .fetch_branch_head_sha("owner", "repo", "gone")
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(mock.call_count(), 2);
+    }
 }
@@ -0,0 +1,240 @@
+//! GraphQL capabilities of a GitHub host
+//!
+//! Older GitHub Enterprise Server releases lack some of the GraphQL types and
+//! mutations github.com offers. Each host is probed once with a cheap
+//! introspection query; methods depending on a missing feature then fail
+//! right away with [`Unsupported`] instead of a cryptic GraphQL error.
+//!
+//! Probing failures (e.g. introspection disabled) assume everything is
+//! supported, so the regular runtime error handling still applies.
+
+use std::fmt;
+
+/// A GraphQL feature not every GitHub host supports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Feature {
+    /// `enablePullRequestAutoMerge` mutation
+    AutoMerge,
+    /// `enqueuePullRequest` mutation (merge queue)
+    MergeQueue,
+    /// `PullRequest.reviewDecision` field
+    ReviewDecision,
+    /// `PullRequest.reviewThreads` connection (used for comment counts)
+    ReviewThreads,
+}
+
+impl Feature {
+    /// All features, in probing order
+    pub const ALL: [Feature; 4] = [
+        Feature::AutoMerge,
+        Feature::MergeQueue,
+        Feature::ReviewDecision,
+        Feature::ReviewThreads,
+    ];
+
+    /// Human readable name
+    pub fn label(self) -> &'static str {
+        match self {
+            Feature::AutoMerge => "auto-merge",
+            Feature::MergeQueue => "merge queue",
+            Feature::ReviewDecision => "review decision",
+            Feature::ReviewThreads => "review threads",
+        }
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// GraphQL features supported by a host
+///
+/// The default assumes everything is supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HostCapabilities {
+    pub supports_auto_merge: bool,
+    pub supports_merge_queue: bool,
+    pub supports_review_decision: bool,
+    pub supports_review_threads: bool,
+}
+
+impl Default for HostCapabilities {
+    fn default() -> Self {
+        Self {
+            supports_auto_merge: true,
+            supports_merge_queue: true,
+            supports_review_decision: true,
+            supports_review_threads: true,
+        }
+    }
+}
+
+impl HostCapabilities {
+    /// Whether the host supports the given feature
+    pub fn supports(&self, feature: Feature) -> bool {
+        match feature {
+            Feature::AutoMerge => self.supports_auto_merge,
+            Feature::MergeQueue => self.supports_merge_queue,
+            Feature::ReviewDecision => self.supports_review_decision,
+            Feature::ReviewThreads => self.supports_review_threads,
+        }
+    }
+
+    /// Features the host lacks
+    pub fn unsupported(&self) -> Vec<Feature> {
+        Feature::ALL
+            .into_iter()
+            .filter(|feature| !self.supports(*feature))
+            .collect()
+    }
+
+    /// `Err(Unsupported)` unless the host supports the feature
+    pub fn require(&self, host: &str, feature: Feature) -> anyhow::Result<()> {
+        if self.supports(feature) {
+            Ok(())
+        } else {
+            Err(Unsupported {
+                host: host.to_string(),
+                feature,
+            }
+            .into())
+        }
+    }
+}
+
+/// Introspection query answering which features a host supports
+pub(crate) const PROBE_QUERY: &str = r#"
+    query {
+      mutation: __type(name: "Mutation") { fields { name } }
+      pullRequest: __type(name: "PullRequest") { fields { name } }
+    }"#;
+
+/// Read the capabilities from the answer to `PROBE_QUERY`
+pub(crate) fn parse_capabilities(response: &serde_json::Value) -> anyhow::Result<HostCapabilities> {
+    if let Some(message) = response["errors"][0]["message"].as_str() {
+        anyhow::bail!("GraphQL error: {}", message);
+    }
+    let fields = |type_name: &str| -> anyhow::Result<Vec<&str>> {
+        let fields = response["data"][type_name]["fields"]
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("Missing {} type in the schema", type_name))?;
+        Ok(fields.iter().filter_map(|f| f["name"].as_str()).collect())
+    };
+    let mutations = fields("mutation")?;
+    let pull_request = fields("pullRequest")?;
+
+    Ok(HostCapabilities {
+        supports_auto_merge: mutations.contains(&"enablePullRequestAutoMerge"),
+        supports_merge_queue: mutations.contains(&"enqueuePullRequest"),
+        supports_review_decision: pull_request.contains(&"reviewDecision"),
+        supports_review_threads: pull_request.contains(&"reviewThreads"),
+    })
+}
+
+/// A feature the host does not support
+///
+/// Returned (inside `anyhow::Error`) before any request is made; see
+/// [`unsupported_feature`] to tell it apart from other errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unsupported {
+    pub host: String,
+    pub feature: Feature,
+}
+
+impl fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} does not support {}", self.host, self.feature)
+    }
+}
+
+impl std::error::Error for Unsupported {}
+
+/// The unsupported feature anywhere in the error's chain, if that is the cause
+pub fn unsupported_feature(error: &anyhow::Error) -> Option<&Unsupported> {
+    error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<Unsupported>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema(mutations: &[&str], pull_request: &[&str]) -> serde_json::Value {
+        let fields = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| json!({ "name": name }))
+                .collect::<Vec<_>>()
+        };
+        json!({
+            "data": {
+                "mutation": { "fields": fields(mutations) },
+                "pullRequest": { "fields": fields(pull_request) },
+            }
+        })
+    }
+
+    #[test]
+    fn test_parse_capabilities() {
+        let full = schema(
+            &[
+                "mergePullRequest",
+                "enablePullRequestAutoMerge",
+                "enqueuePullRequest",
+            ],
+            &["title", "reviewDecision", "reviewThreads"],
+        );
+        assert_eq!(
+            parse_capabilities(&full).unwrap(),
+            HostCapabilities::default()
+        );
+
+        // e.g. an older GitHub Enterprise Server
+        let old = schema(&["mergePullRequest"], &["title", "reviewThreads"]);
+        let capabilities = parse_capabilities(&old).unwrap();
+        assert!(capabilities.supports(Feature::ReviewThreads));
+        assert_eq!(
+            capabilities.unsupported(),
+            vec![
+                Feature::AutoMerge,
+                Feature::MergeQueue,
+                Feature::ReviewDecision
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_capabilities_errors() {
+        let error = json!({ "errors": [{ "message": "Introspection is disabled" }] });
+        assert!(parse_capabilities(&error).is_err());
+        assert!(parse_capabilities(&json!({ "data": {} })).is_err());
+    }
+
+    #[test]
+    fn test_unsupported_error() {
+        let capabilities = HostCapabilities {
+            supports_merge_queue: false,
+            ..Default::default()
+        };
+        assert!(capabilities
+            .require("ghe.example.com", Feature::AutoMerge)
+            .is_ok());
+
+        let error = capabilities
+            .require("ghe.example.com", Feature::MergeQueue)
+            .unwrap_err()
+            .context("Failed to enqueue PR #1");
+        let unsupported = unsupported_feature(&error).unwrap();
+        assert_eq!(unsupported.feature, Feature::MergeQueue);
+        assert_eq!(
+            unsupported.to_string(),
+            "ghe.example.com does not support merge queue"
+        );
+
+        assert!(unsupported_feature(&anyhow::anyhow!("Not Found")).is_none());
+    }
+}
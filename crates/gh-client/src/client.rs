@@ -5,31 +5,44 @@
 //! controlling caching behavior.
 
 use crate::types::{
-    CheckRun, CheckStatus, CiStatus, MergeMethod, MergeResult, PullRequest, ReviewEvent,
-    WorkflowRun,
+    CheckRun, CheckStatus, CiStatus, CommentLines, Comparison, Label, MergeMethod, MergeResult,
+    PrStateFilter, PullRequest, RepositorySettings, ReviewEvent, SearchResults, WorkflowRun,
 };
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 /// Cache behavior mode for GitHub API clients
 ///
-/// Controls how the client interacts with the cache layer.
-/// This is set at client construction time, not per-request.
+/// Controls how the client interacts with the cache layer. A client is built
+/// with a mode and `CachedGitHubClient::with_mode` derives a client with
+/// another one for individual calls.
+///
+/// | Mode        | Fresh hit          | Stale hit          | Miss              |
+/// |-------------|--------------------|--------------------|-------------------|
+/// | `ReadWrite` | served from cache  | fetched, cached    | fetched, cached   |
+/// | `ReadOnly`  | served from cache  | served from cache  | `CacheMiss` error |
+/// | `WriteOnly` | fetched, cached    | fetched, cached    | fetched, cached   |
+/// | `Bypass`    | fetched            | fetched            | fetched           |
+///
+/// A hit is stale once it's older than the cache TTL. `ReadWrite` never
+/// serves volatile endpoints (check runs, reviews) from the cache, while
+/// `ReadOnly` never touches the network, so it serves them too and refuses
+/// mutations.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum CacheMode {
     /// No caching - neither read nor write
     /// Use for mutations or when cache would cause issues
-    None,
+    Bypass,
 
     /// Write-only - skip cache reads, but write responses to cache
     /// Use for "force refresh" to get fresh data while populating cache
     WriteOnly,
 
-    /// Read-only - read from cache, but don't update it
+    /// Read-only - answer from cache only, never hit the network
     /// Use for offline mode or when preserving cache state
-    #[allow(dead_code)]
     ReadOnly,
 
-    /// Full caching - read from cache, write to cache
+    /// Full caching - read fresh entries from cache, write to cache
     /// Default behavior for normal operations
     #[default]
     ReadWrite,
@@ -45,6 +58,11 @@ impl CacheMode {
     pub fn should_write(&self) -> bool {
         matches!(self, CacheMode::WriteOnly | CacheMode::ReadWrite)
     }
+
+    /// May we make API calls at all?
+    pub fn should_fetch(&self) -> bool {
+        !matches!(self, CacheMode::ReadOnly)
+    }
 }
 
 /// GitHub API client trait
@@ -61,32 +79,91 @@ impl CacheMode {
 /// # Example
 ///
 /// ```rust,ignore
-/// use gh_client::{GitHubClient, PullRequest};
+/// use gh_client::{GitHubClient, PrStateFilter, PullRequest};
 ///
 /// async fn list_prs(client: &dyn GitHubClient) -> anyhow::Result<Vec<PullRequest>> {
-///     client.fetch_pull_requests("rust-lang", "rust", Some("master")).await
+///     client
+///         .fetch_pull_requests("rust-lang", "rust", Some("master"), PrStateFilter::Open)
+///         .await
 /// }
 /// ```
+/// Results per page of `search_pull_requests_in_repo`
+pub const SEARCH_PAGE_SIZE: u32 = 30;
+
+/// PRs per GraphQL query of `fetch_reviewers`
+pub const REVIEWERS_BATCH_SIZE: usize = 25;
+
+/// Build the full search query for PRs in a repository
+pub fn repo_search_query(owner: &str, repo: &str, query: &str) -> String {
+    let query = query.trim();
+    if query.is_empty() {
+        format!("repo:{}/{} is:pr", owner, repo)
+    } else {
+        format!("repo:{}/{} is:pr {}", owner, repo, query)
+    }
+}
+
 #[async_trait]
 pub trait GitHubClient: Send + Sync {
-    /// Fetch open pull requests for a repository
+    /// Fetch pull requests for a repository
     ///
     /// # Arguments
     ///
     /// * `owner` - Repository owner (user or organization)
     /// * `repo` - Repository name
     /// * `base_branch` - Optional base branch filter (e.g., "main")
+    /// * `state` - Which PRs to list (open, closed, merged or all)
     ///
     /// # Returns
     ///
-    /// A list of open pull requests, or an error if the API call fails.
+    /// A list of pull requests, or an error if the API call fails.
     async fn fetch_pull_requests(
         &self,
         owner: &str,
         repo: &str,
         base_branch: Option<&str>,
+        state: PrStateFilter,
     ) -> anyhow::Result<Vec<PullRequest>>;
 
+    /// Search pull requests of a repository
+    ///
+    /// Uses the search API with GitHub's query syntax; `repo:` and `is:pr`
+    /// qualifiers are added. The search API has a separate, lower rate limit
+    /// than the core API.
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `query` - Search query (e.g. `is:merged "migration"`)
+    /// * `page` - Page to fetch (1-based, `SEARCH_PAGE_SIZE` results per page)
+    ///
+    /// # Returns
+    ///
+    /// One page of matching PRs with the total number of matches.
+    async fn search_pull_requests_in_repo(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &str,
+        page: u32,
+    ) -> anyhow::Result<SearchResults>;
+
+    /// Count the issues and PRs matching a search query
+    ///
+    /// Asks for a single result and reads the total only, so it costs one
+    /// search request whatever the count. Counts towards the search API's
+    /// rate limit like any search.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - Full search query (e.g. `repo:org/repo is:pr merged:>=2024-05-01`)
+    ///
+    /// # Returns
+    ///
+    /// The number of matches.
+    async fn count_search(&self, query: &str) -> anyhow::Result<u64>;
+
     /// Fetch a single pull request by number
     ///
     /// This returns full PR details including additions/deletions
@@ -233,6 +310,159 @@ pub trait GitHubClient: Send + Sync {
         pr_number: u64,
     ) -> anyhow::Result<()>;
 
+    // === Branches ===
+
+    /// Fetch the repository settings that affect merging
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    ///
+    /// # Returns
+    ///
+    /// Default branch and the "delete head branches" setting
+    async fn fetch_repository_settings(
+        &self,
+        owner: &str,
+        repo: &str,
+    ) -> anyhow::Result<RepositorySettings>;
+
+    /// Fetch the status checks branch protection requires on a branch
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `branch` - Protected branch, usually the base branch of PRs
+    ///
+    /// # Returns
+    ///
+    /// Names of the required checks, `None` if the branch is not protected
+    /// or its protection cannot be read (it needs admin access on some
+    /// repositories)
+    async fn fetch_required_checks(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> anyhow::Result<Option<Vec<String>>>;
+
+    /// Delete a branch (via the git refs API)
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `branch` - Branch name, without `refs/heads/`
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) on success, error on failure
+    async fn delete_branch(&self, owner: &str, repo: &str, branch: &str) -> anyhow::Result<()>;
+
+    // === Labels ===
+
+    /// Fetch the labels defined in a repository
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    ///
+    /// # Returns
+    ///
+    /// All labels of the repository, sorted by name
+    async fn fetch_repo_labels(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<Label>>;
+
+    /// Add labels to a pull request
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `pr_number` - Pull request number
+    /// * `labels` - Names of the labels to add (existing labels are kept)
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) on success, error on failure
+    async fn add_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        labels: &[String],
+    ) -> anyhow::Result<()>;
+
+    /// Remove a label from a pull request
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `pr_number` - Pull request number
+    /// * `label` - Name of the label to remove
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) on success, error on failure
+    async fn remove_label(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        label: &str,
+    ) -> anyhow::Result<()>;
+
+    // === Reviewers ===
+
+    /// Fetch the logins of the users with access to a repository
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    ///
+    /// # Returns
+    ///
+    /// Logins of all collaborators, sorted case-insensitively
+    async fn fetch_collaborators(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<String>>;
+
+    /// Fetch the branch names of a repository
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    ///
+    /// # Returns
+    ///
+    /// Names of the branches in GitHub's order, the first thousand at most
+    async fn fetch_branches(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<String>>;
+
+    /// Request reviews on a pull request
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `pr_number` - Pull request number
+    /// * `reviewers` - Logins of the users to request
+    /// * `team_reviewers` - Slugs of the teams to request (without the org)
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) on success, error on failure
+    async fn request_reviewers(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        reviewers: &[String],
+        team_reviewers: &[String],
+    ) -> anyhow::Result<()>;
+
     // === CI Operations ===
 
     /// Rerun failed workflow jobs for a specific run
@@ -266,6 +496,115 @@ pub trait GitHubClient: Send + Sync {
         head_sha: &str,
     ) -> anyhow::Result<Vec<WorkflowRun>>;
 
+    /// Fetch the most recent workflow runs on a branch, newest first
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `branch` - Branch to list runs for (usually the default branch)
+    /// * `limit` - Maximum number of runs to return (at most 100)
+    ///
+    /// # Returns
+    ///
+    /// List of workflow runs on the branch
+    async fn fetch_recent_workflow_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        limit: usize,
+    ) -> anyhow::Result<Vec<WorkflowRun>>;
+
+    /// Fetch the SHA of the latest commit on a branch
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `branch` - Branch name
+    ///
+    /// # Returns
+    ///
+    /// The head commit SHA, or `None` if the branch doesn't exist
+    async fn fetch_branch_head_sha(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> anyhow::Result<Option<String>>;
+
+    /// Compare two commits (`base...head`)
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `base` - Branch or commit SHA to compare against
+    /// * `head` - Branch or commit SHA to compare
+    ///
+    /// # Returns
+    ///
+    /// The merge base and the files changed on `head` since then
+    async fn fetch_compare(
+        &self,
+        owner: &str,
+        repo: &str,
+        base: &str,
+        head: &str,
+    ) -> anyhow::Result<Comparison>;
+
+    /// Fetch the name of the latest tag
+    ///
+    /// The tag of the latest release, or the first tag listed for
+    /// repositories tagging without releases.
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    ///
+    /// # Returns
+    ///
+    /// The tag name (e.g. "v1.4.0"), or `None` if the repository has no tags
+    async fn fetch_latest_tag(&self, owner: &str, repo: &str) -> anyhow::Result<Option<String>>;
+
+    /// Fetch the commit date of a tag, branch or commit SHA
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `git_ref` - Tag, branch or commit SHA
+    ///
+    /// # Returns
+    ///
+    /// When the commit was committed, or `None` if the ref doesn't exist
+    async fn fetch_ref_date(
+        &self,
+        owner: &str,
+        repo: &str,
+        git_ref: &str,
+    ) -> anyhow::Result<Option<DateTime<Utc>>>;
+
+    /// Fetch the unified diff of a pull request
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `pr_number` - Pull request number
+    ///
+    /// # Returns
+    ///
+    /// The diff as returned for the `application/vnd.github.diff` media type
+    async fn fetch_pull_request_diff(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<String>;
+
     /// Fetch aggregated CI status for a commit
     ///
     /// This fetches all check runs for a commit and aggregates them into
@@ -300,8 +639,7 @@ pub trait GitHubClient: Send + Sync {
     /// * `pr_number` - Pull request number
     /// * `commit_id` - The SHA of the commit to comment on (usually head SHA)
     /// * `path` - File path relative to repository root
-    /// * `line` - Line number in the file
-    /// * `side` - Which side of the diff ("LEFT" for deletions, "RIGHT" for additions)
+    /// * `lines` - Line(s) and side(s) of the diff the comment is anchored to
     /// * `body` - Comment body text
     ///
     /// # Returns
@@ -315,8 +653,7 @@ pub trait GitHubClient: Send + Sync {
         pr_number: u64,
         commit_id: &str,
         path: &str,
-        line: u32,
-        side: &str,
+        lines: &CommentLines,
         body: &str,
     ) -> anyhow::Result<u64>;
 
@@ -338,6 +675,70 @@ pub trait GitHubClient: Send + Sync {
         comment_id: u64,
     ) -> anyhow::Result<()>;
 
+    /// Create a review comment on a whole file
+    ///
+    /// Sent with `subject_type: file`, so the comment has no line anchor.
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `pr_number` - Pull request number
+    /// * `commit_id` - The SHA of the commit to comment on (usually head SHA)
+    /// * `path` - File path relative to repository root
+    /// * `body` - Comment body text
+    ///
+    /// # Returns
+    ///
+    /// The GitHub comment ID on success, error on failure
+    async fn create_file_review_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        commit_id: &str,
+        path: &str,
+        body: &str,
+    ) -> anyhow::Result<u64>;
+
+    /// Create a comment on the PR conversation (issue comments endpoint)
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `pr_number` - Pull request number
+    /// * `body` - Comment body text
+    ///
+    /// # Returns
+    ///
+    /// The GitHub comment ID on success, error on failure
+    async fn create_issue_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        body: &str,
+    ) -> anyhow::Result<u64>;
+
+    /// Delete a comment of the PR conversation
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `comment_id` - The GitHub comment ID to delete
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) on success, error on failure
+    async fn delete_issue_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        comment_id: u64,
+    ) -> anyhow::Result<()>;
+
     /// Fetch review comments for a pull request
     ///
     /// Returns all review comments (line comments) on a PR.
@@ -380,12 +781,104 @@ pub trait GitHubClient: Send + Sync {
         repo: &str,
         pr_number: u64,
     ) -> anyhow::Result<crate::types::ReviewDecision>;
+
+    /// Fetch the comment breakdown of a pull request
+    ///
+    /// Counts conversation comments, review line comments and review
+    /// threads not yet resolved. Resolution is only exposed by GraphQL, so
+    /// this issues a single GraphQL query.
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `pr_number` - Pull request number
+    async fn fetch_comment_counts(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<crate::types::CommentCounts>;
+
+    /// Fetch the reviewers of several pull requests at once
+    ///
+    /// Requested reviewers and the latest review per reviewer are only
+    /// batched by GraphQL, so PRs are fetched in one query per
+    /// `REVIEWERS_BATCH_SIZE` PRs. PRs that no longer exist are
+    /// left out of the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `pr_numbers` - Numbers of the pull requests
+    async fn fetch_reviewers(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_numbers: &[u64],
+    ) -> anyhow::Result<Vec<crate::types::PrReviewers>>;
+
+    /// Fetch who last changed each line of a file
+    ///
+    /// Blame is only exposed by GraphQL. The ranges cover the whole file at
+    /// `commit_sha`, so results can be cached for good.
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `commit_sha` - Commit to blame at
+    /// * `path` - File path relative to the repository root
+    async fn fetch_blame(
+        &self,
+        owner: &str,
+        repo: &str,
+        commit_sha: &str,
+        path: &str,
+    ) -> anyhow::Result<Vec<crate::types::BlameRange>>;
+
+    /// Fetch the text content of a file in a repository
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `path` - File path relative to the repository root
+    /// * `git_ref` - Branch, tag or commit (None = the default branch)
+    async fn fetch_file_content(
+        &self,
+        owner: &str,
+        repo: &str,
+        path: &str,
+        git_ref: Option<&str>,
+    ) -> anyhow::Result<String>;
+
+    /// GraphQL features supported by this client's host
+    ///
+    /// Probed once per host; clients that cannot probe assume everything is
+    /// supported.
+    async fn capabilities(&self) -> crate::capabilities::HostCapabilities {
+        crate::capabilities::HostCapabilities::default()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_repo_search_query() {
+        assert_eq!(
+            repo_search_query("org", "repo", "  "),
+            "repo:org/repo is:pr"
+        );
+        assert_eq!(
+            repo_search_query("org", "repo", "is:open \"migration\""),
+            "repo:org/repo is:pr is:open \"migration\""
+        );
+    }
+
     #[test]
     fn test_cache_mode_default() {
         assert_eq!(CacheMode::default(), CacheMode::ReadWrite);
@@ -393,7 +886,7 @@ mod tests {
 
     #[test]
     fn test_cache_mode_should_read() {
-        assert!(!CacheMode::None.should_read());
+        assert!(!CacheMode::Bypass.should_read());
         assert!(!CacheMode::WriteOnly.should_read());
         assert!(CacheMode::ReadOnly.should_read());
         assert!(CacheMode::ReadWrite.should_read());
@@ -401,9 +894,17 @@ mod tests {
 
     #[test]
     fn test_cache_mode_should_write() {
-        assert!(!CacheMode::None.should_write());
+        assert!(!CacheMode::Bypass.should_write());
         assert!(CacheMode::WriteOnly.should_write());
         assert!(!CacheMode::ReadOnly.should_write());
         assert!(CacheMode::ReadWrite.should_write());
     }
+
+    #[test]
+    fn test_cache_mode_should_fetch() {
+        assert!(CacheMode::Bypass.should_fetch());
+        assert!(CacheMode::WriteOnly.should_fetch());
+        assert!(!CacheMode::ReadOnly.should_fetch());
+        assert!(CacheMode::ReadWrite.should_fetch());
+    }
 }
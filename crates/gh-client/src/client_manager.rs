@@ -1,25 +1,48 @@
 //! Multi-host GitHub client manager
 //!
-//! Manages GitHub API clients for different hosts (github.com, GitHub Enterprise).
-//! Clients are lazily initialized and cached per host.
-
-use crate::{ApiCache, CacheMode, CachedGitHubClient, OctocrabClient, DEFAULT_HOST};
+//! Manages GitHub API clients for different hosts (github.com, GitHub Enterprise)
+//! and accounts on the same host (profiles, e.g. "work" and "personal").
+//! Clients are lazily initialized and cached per (host, profile).
+
+use crate::{
+    ApiCache, CacheMode, CachedGitHubClient, GitHubClient, HostCapabilities,
+    InstrumentedGitHubClient, OctocrabClient, TokenStore, DEFAULT_HOST,
+};
 use anyhow::{Context, Result};
-use log::{debug, info};
+use log::{debug, info, warn};
 use octocrab::Octocrab;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+/// Key identifying an account: `host` or `profile@host`
+///
+/// Used for client lookup, the token store and cache namespaces.
+pub fn account_key(host: Option<&str>, profile: Option<&str>) -> String {
+    let host = host.unwrap_or(DEFAULT_HOST);
+    match profile {
+        Some(profile) => format!("{}@{}", profile, host),
+        None => host.to_string(),
+    }
+}
 
-/// Resolves GitHub tokens for different hosts
+/// Resolves GitHub tokens for different hosts and profiles
 ///
 /// Tries multiple sources in order:
 /// 1. Host-specific env var (e.g., `GITHUB_TOKEN_GHE_EXAMPLE_COM`)
-/// 2. `gh auth token --hostname {host}` command
-/// 3. Generic `GITHUB_TOKEN` or `GH_TOKEN` (github.com only)
+/// 2. Token store (keychain or encrypted file), if configured
+/// 3. `gh auth token --hostname {host}` command
+/// 4. Generic `GITHUB_TOKEN` or `GH_TOKEN` (github.com only)
+///
+/// With a profile, the env var gets a `_{PROFILE}` suffix, the store is keyed
+/// by `profile@host`, `gh` is asked for the account `--user {profile}`, and
+/// the generic tokens are never used - so accounts cannot leak into each other.
 #[derive(Debug, Clone)]
 pub struct TokenResolver {
     /// Cached default token from GITHUB_TOKEN/GH_TOKEN
     default_token: Option<String>,
+    /// Stored tokens set from within the app
+    store: Option<Arc<TokenStore>>,
 }
 
 impl Default for TokenResolver {
@@ -35,7 +58,16 @@ impl TokenResolver {
             .or_else(|_| std::env::var("GH_TOKEN"))
             .ok();
 
-        Self { default_token }
+        Self {
+            default_token,
+            store: None,
+        }
+    }
+
+    /// Also resolve tokens from the given token store
+    pub fn with_store(mut self, store: Arc<TokenStore>) -> Self {
+        self.store = Some(store);
+        self
     }
 
     /// Get a token for the given host
@@ -47,58 +79,110 @@ impl TokenResolver {
     /// # Token Resolution Order
     ///
     /// 1. `GITHUB_TOKEN_{HOST}` env var (e.g., `GITHUB_TOKEN_GHE_EXAMPLE_COM`)
-    /// 2. `gh auth token --hostname {host}` command
-    /// 3. `GITHUB_TOKEN` or `GH_TOKEN` (github.com only)
+    /// 2. Token store (keychain or encrypted file)
+    /// 3. `gh auth token --hostname {host}` command
+    /// 4. `GITHUB_TOKEN` or `GH_TOKEN` (github.com only)
     pub async fn get_token(&self, host: Option<&str>) -> Result<String> {
+        self.get_token_for(host, None).await
+    }
+
+    /// Get a token for the given host and profile (None = default account)
+    pub async fn get_token_for(&self, host: Option<&str>, profile: Option<&str>) -> Result<String> {
+        let account = account_key(host, profile);
         let host = host.unwrap_or(DEFAULT_HOST);
+        // Every source tried, reported when no token could be found
+        let mut attempts = Vec::new();
 
-        // Try host-specific env var
-        let env_key = format!(
-            "GITHUB_TOKEN_{}",
-            host.replace(['.', '-'], "_").to_uppercase()
-        );
+        // Try host-specific (and profile-specific) env var
+        let env_key = token_env_key(host, profile);
         if let Ok(token) = std::env::var(&env_key) {
             debug!("Using token from env var {} for host {}", env_key, host);
             return Ok(token);
         }
+        attempts.push(format!("${} is not set", env_key));
 
-        // Try gh CLI with hostname
-        debug!("Trying gh auth token for host {}", host);
-        let output = tokio::process::Command::new("gh")
-            .args(["auth", "token", "--hostname", host])
-            .output()
-            .await
-            .context("Failed to run 'gh auth token'")?;
-
-        if output.status.success() {
-            let token = String::from_utf8(output.stdout)
-                .context("Invalid UTF-8 in gh auth token output")?
-                .trim()
-                .to_string();
-            if !token.is_empty() {
-                debug!("Using token from gh CLI for host {}", host);
+        // Try the token store
+        if let Some(ref store) = self.store {
+            if let Some(token) = store.get(&account) {
+                debug!("Using stored token for {}", account);
                 return Ok(token);
             }
+            attempts.push("no token stored via \"Set GitHub token…\"".to_string());
         }
 
-        // Fallback to default token (for github.com only)
-        if host == DEFAULT_HOST {
+        // Try gh CLI with hostname (and account for profiles)
+        debug!("Trying gh auth token for {}", account);
+        let mut gh = tokio::process::Command::new("gh");
+        gh.args(["auth", "token", "--hostname", host]);
+        if let Some(profile) = profile {
+            gh.args(["--user", profile]);
+        }
+        match gh.output().await {
+            Ok(output) if output.status.success() => {
+                let token = String::from_utf8(output.stdout)
+                    .context("Invalid UTF-8 in gh auth token output")?
+                    .trim()
+                    .to_string();
+                if !token.is_empty() {
+                    debug!("Using token from gh CLI for host {}", host);
+                    return Ok(token);
+                }
+                attempts.push(format!(
+                    "'gh auth token --hostname {}' returned an empty token",
+                    host
+                ));
+            }
+            Ok(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                attempts.push(format!(
+                    "'gh auth token --hostname {}' failed: {}",
+                    host,
+                    stderr.trim()
+                ));
+            }
+            Err(e) => {
+                attempts.push(format!("'gh' CLI could not be run: {}", e));
+            }
+        }
+
+        // Fallback to default token (for the default github.com account only)
+        if host == DEFAULT_HOST && profile.is_none() {
             if let Some(ref token) = self.default_token {
                 debug!("Using default token (GITHUB_TOKEN/GH_TOKEN) for github.com");
                 return Ok(token.clone());
             }
+            attempts.push("$GITHUB_TOKEN and $GH_TOKEN are not set".to_string());
         }
 
         Err(anyhow::anyhow!(
-            "No token found for host '{}'. \
-             Set {} or run 'gh auth login --hostname {}'",
+            "No token found for '{}'. Tried:\n{}\n\
+             Set {}, run 'gh auth login --hostname {}', \
+             or store one with the \"Set GitHub token…\" command",
             host,
+            attempts
+                .iter()
+                .map(|a| format!("  - {}", a))
+                .collect::<Vec<_>>()
+                .join("\n"),
             env_key,
             host
         ))
     }
 }
 
+/// Env var holding the token for a host (and profile)
+fn token_env_key(host: &str, profile: Option<&str>) -> String {
+    let mut key = format!("GITHUB_TOKEN_{}", host);
+    if let Some(profile) = profile {
+        key.push('_');
+        key.push_str(profile);
+    }
+    key.replace(['.', '-', '@'], "_").to_uppercase()
+}
+
+/// Client handed out by the `ClientManager`: cached, with instrumented API calls
+pub type ManagedClient = CachedGitHubClient<InstrumentedGitHubClient<OctocrabClient>>;
+
 /// Manages GitHub API clients for multiple hosts
 ///
 /// Lazily creates and caches clients per host. Each client is configured
@@ -120,14 +204,18 @@ impl TokenResolver {
 /// let ghe_client = manager.get_client(Some("ghe.example.com")).await?;
 /// ```
 pub struct ClientManager {
-    /// Cached clients per host
-    clients: HashMap<String, CachedGitHubClient<OctocrabClient>>,
+    /// Cached clients per account (see `account_key`)
+    clients: HashMap<String, ManagedClient>,
+    /// Logins of the authenticated users per account
+    logins: HashMap<String, String>,
     /// Shared API cache
     cache: Arc<Mutex<ApiCache>>,
     /// Token resolver
     tokens: TokenResolver,
     /// Default cache mode for new clients
     cache_mode: CacheMode,
+    /// GraphQL capabilities per host, probed by the first client that needs them
+    capabilities: Mutex<HashMap<String, Arc<OnceCell<HostCapabilities>>>>,
 }
 
 impl ClientManager {
@@ -135,9 +223,11 @@ impl ClientManager {
     pub fn new(cache: Arc<Mutex<ApiCache>>) -> Self {
         Self {
             clients: HashMap::new(),
+            logins: HashMap::new(),
             cache,
             tokens: TokenResolver::new(),
             cache_mode: CacheMode::ReadWrite,
+            capabilities: Mutex::default(),
         }
     }
 
@@ -145,12 +235,25 @@ impl ClientManager {
     pub fn with_cache_mode(cache: Arc<Mutex<ApiCache>>, cache_mode: CacheMode) -> Self {
         Self {
             clients: HashMap::new(),
+            logins: HashMap::new(),
             cache,
             tokens: TokenResolver::new(),
             cache_mode,
+            capabilities: Mutex::default(),
         }
     }
 
+    /// Resolve tokens from the given token store as well
+    pub fn with_token_store(mut self, store: Arc<TokenStore>) -> Self {
+        self.tokens = self.tokens.with_store(store);
+        self
+    }
+
+    /// The configured token store, if any
+    pub fn token_store(&self) -> Option<Arc<TokenStore>> {
+        self.tokens.store.clone()
+    }
+
     /// Get or create a client for the given host
     ///
     /// # Arguments
@@ -160,61 +263,103 @@ impl ClientManager {
     /// # Returns
     ///
     /// A cached GitHub client for the host
-    pub async fn get_client(
-        &mut self,
-        host: Option<&str>,
-    ) -> Result<&CachedGitHubClient<OctocrabClient>> {
-        let key = host.unwrap_or(DEFAULT_HOST).to_string();
-
-        if !self.clients.contains_key(&key) {
-            let client = self.create_client(host).await?;
-            self.clients.insert(key.clone(), client);
-        }
-
+    pub async fn get_client(&mut self, host: Option<&str>) -> Result<&ManagedClient> {
+        let key = self.ensure_client(host, None).await?;
         Ok(self.clients.get(&key).unwrap())
     }
 
     /// Get a mutable reference to the client for the given host
-    pub async fn get_client_mut(
-        &mut self,
-        host: Option<&str>,
-    ) -> Result<&mut CachedGitHubClient<OctocrabClient>> {
-        let key = host.unwrap_or(DEFAULT_HOST).to_string();
+    pub async fn get_client_mut(&mut self, host: Option<&str>) -> Result<&mut ManagedClient> {
+        let key = self.ensure_client(host, None).await?;
+        Ok(self.clients.get_mut(&key).unwrap())
+    }
 
+    /// Create the client for an account if needed, returning its key
+    async fn ensure_client(&mut self, host: Option<&str>, profile: Option<&str>) -> Result<String> {
+        let key = account_key(host, profile);
         if !self.clients.contains_key(&key) {
-            let client = self.create_client(host).await?;
+            let client = self.create_client(host, profile).await?;
             self.clients.insert(key.clone(), client);
         }
-
-        Ok(self.clients.get_mut(&key).unwrap())
+        Ok(key)
     }
 
     /// Check if a client exists for the given host (without creating one)
     pub fn has_client(&self, host: Option<&str>) -> bool {
-        let key = host.unwrap_or(DEFAULT_HOST);
-        self.clients.contains_key(key)
+        self.clients.contains_key(&account_key(host, None))
     }
 
-    /// Remove a client for the given host
+    /// Remove the client for the given host and profile
     ///
     /// This can be useful for forcing re-authentication after token changes.
-    pub fn remove_client(&mut self, host: Option<&str>) {
-        let key = host.unwrap_or(DEFAULT_HOST);
-        self.clients.remove(key);
+    pub fn remove_client(&mut self, host: Option<&str>, profile: Option<&str>) {
+        let key = account_key(host, profile);
+        self.clients.remove(&key);
+        self.logins.remove(&key);
     }
 
-    /// Get a clone of a client for the given host (for use in async tasks)
+    /// Get a clone of a client for the given host and profile (for use in async tasks)
     ///
     /// Unlike `get_client`, this returns an owned client that can be moved
-    /// into async tasks without borrowing from the manager.
+    /// into async tasks without borrowing from the manager. Each profile has
+    /// its own octocrab instance and cache namespace.
     pub async fn clone_client(
         &mut self,
         host: Option<&str>,
-    ) -> Result<CachedGitHubClient<OctocrabClient>> {
-        // Ensure client exists
-        let _ = self.get_client(host).await?;
-        let key = host.unwrap_or(DEFAULT_HOST);
-        Ok(self.clients.get(key).unwrap().clone())
+        profile: Option<&str>,
+    ) -> Result<ManagedClient> {
+        let key = self.ensure_client(host, profile).await?;
+        Ok(self.clients.get(&key).unwrap().clone())
+    }
+
+    /// Login of the user authenticated for the given host and profile
+    ///
+    /// Fetched from `/user` once per account and remembered.
+    pub async fn login(&mut self, host: Option<&str>, profile: Option<&str>) -> Result<String> {
+        let key = self.ensure_client(host, profile).await?;
+        if let Some(login) = self.logins.get(&key) {
+            return Ok(login.clone());
+        }
+        if self.is_anonymous(host, profile) {
+            anyhow::bail!("No token configured for {}", key);
+        }
+        let user = self.clients[&key]
+            .inner()
+            .inner()
+            .octocrab()
+            .current()
+            .user()
+            .await
+            .with_context(|| format!("Failed to fetch the user for {}", key))?;
+        self.logins.insert(key, user.login.clone());
+        Ok(user.login)
+    }
+
+    /// GraphQL capabilities of the given host
+    ///
+    /// Probed with the account's client on first use and shared by all
+    /// accounts of the host. Probing failures assume everything is supported.
+    pub async fn probe_capabilities(
+        &mut self,
+        host: Option<&str>,
+        profile: Option<&str>,
+    ) -> Result<HostCapabilities> {
+        let key = self.ensure_client(host, profile).await?;
+        Ok(self.clients[&key].capabilities().await)
+    }
+
+    /// Capabilities of the given host, if already probed
+    pub fn host_capabilities(&self, host: Option<&str>) -> Option<HostCapabilities> {
+        let host = host.unwrap_or(DEFAULT_HOST);
+        let capabilities = self.capabilities.lock().unwrap();
+        capabilities.get(host).and_then(|cell| cell.get().copied())
+    }
+
+    /// Capabilities shared by the clients of the given host
+    fn capabilities_cell(&self, host: Option<&str>) -> Arc<OnceCell<HostCapabilities>> {
+        let host = host.unwrap_or(DEFAULT_HOST).to_string();
+        let mut capabilities = self.capabilities.lock().unwrap();
+        Arc::clone(capabilities.entry(host).or_default())
     }
 
     /// Get the default token (github.com) if available
@@ -222,19 +367,111 @@ impl ClientManager {
         self.tokens.default_token.as_deref()
     }
 
-    /// Create a new client for the given host
+    /// Resolve the token for the given host without creating a client
+    pub async fn resolve_token(&self, host: Option<&str>) -> Result<String> {
+        self.tokens.get_token(host).await
+    }
+
+    /// Create and register a client for the given host from an already resolved token
+    ///
+    /// Allows callers to report token resolution and client creation separately.
+    pub fn init_client_with_token(
+        &mut self,
+        host: Option<&str>,
+        profile: Option<&str>,
+        token: String,
+    ) -> Result<()> {
+        let key = account_key(host, profile);
+        let client = self.build_client(host, profile, Some(token))?;
+        self.clients.insert(key, client);
+        Ok(())
+    }
+
+    /// Create and register an unauthenticated client for the given host
+    ///
+    /// The client can only read public repositories, at GitHub's anonymous
+    /// rate limit (see `ANONYMOUS_REQUESTS_PER_HOUR`).
+    pub fn init_anonymous_client(
+        &mut self,
+        host: Option<&str>,
+        profile: Option<&str>,
+    ) -> Result<()> {
+        let key = account_key(host, profile);
+        let client = self.build_client(host, profile, None)?;
+        self.clients.insert(key, client);
+        Ok(())
+    }
+
+    /// Whether the client for the given host and profile has no token
+    pub fn is_anonymous(&self, host: Option<&str>, profile: Option<&str>) -> bool {
+        self.clients
+            .get(&account_key(host, profile))
+            .is_some_and(|client| client.inner().inner().is_anonymous())
+    }
+
+    /// Check a token against the `/user` endpoint of the given host
+    ///
+    /// Returns the login of the authenticated user.
+    pub async fn validate_token(host: Option<&str>, token: String) -> Result<String> {
+        let octocrab = Self::build_octocrab(host, token)?;
+        let user = octocrab
+            .current()
+            .user()
+            .await
+            .context("Token was rejected by GitHub")?;
+        Ok(user.login)
+    }
+
+    /// Build a bare Octocrab instance for the given host
+    fn build_octocrab(host: Option<&str>, token: String) -> Result<Octocrab> {
+        let mut builder = Octocrab::builder().personal_token(token);
+        if let Some(h) = host.filter(|h| *h != DEFAULT_HOST) {
+            builder = builder
+                .base_uri(format!("https://{}/api/v3", h))
+                .context("Failed to set base URI")?;
+        }
+        builder.build().context("Failed to build Octocrab client")
+    }
+
+    /// Create a new client for the given host and profile
+    ///
+    /// Without a token the default account of a host falls back to an
+    /// anonymous client; profiles always need their own token.
     async fn create_client(
         &self,
         host: Option<&str>,
-    ) -> Result<CachedGitHubClient<OctocrabClient>> {
-        let effective_host = host.unwrap_or(DEFAULT_HOST);
-        info!("Creating GitHub client for host: {}", effective_host);
+        profile: Option<&str>,
+    ) -> Result<ManagedClient> {
+        // Get token for this account
+        match self.tokens.get_token_for(host, profile).await {
+            Ok(token) => self.build_client(host, profile, Some(token)),
+            Err(e) if profile.is_none() => {
+                warn!("{:#}", e);
+                warn!(
+                    "Using an anonymous client for {}: public repositories only",
+                    account_key(host, profile)
+                );
+                self.build_client(host, profile, None)
+            }
+            Err(e) => Err(e),
+        }
+    }
 
-        // Get token for this host
-        let token = self.tokens.get_token(host).await?;
+    /// Build a client for the given host and profile (anonymous without token)
+    fn build_client(
+        &self,
+        host: Option<&str>,
+        profile: Option<&str>,
+        token: Option<String>,
+    ) -> Result<ManagedClient> {
+        let effective_host = account_key(host, profile);
+        info!("Creating GitHub client for: {}", effective_host);
 
         // Build octocrab with appropriate base URI
-        let mut builder = Octocrab::builder().personal_token(token);
+        let mut builder = Octocrab::builder();
+        if let Some(ref token) = token {
+            builder = builder.personal_token(token.clone());
+        }
 
         let base_url = if let Some(h) = host {
             if h != DEFAULT_HOST {
@@ -249,15 +486,63 @@ impl ClientManager {
         };
 
         let octocrab = builder.build().context("Failed to build Octocrab client")?;
-        let octocrab_client = OctocrabClient::with_base_url(Arc::new(octocrab), base_url);
-        let cached =
-            CachedGitHubClient::new(octocrab_client, Arc::clone(&self.cache), self.cache_mode);
-
-        info!("GitHub client created for host: {}", effective_host);
+        let mut octocrab_client = OctocrabClient::with_base_url(Arc::new(octocrab), base_url)
+            .with_capabilities(self.capabilities_cell(host));
+        // GitHub Enterprise serves GraphQL at /api/graphql, not below /api/v3
+        if let Some(h) = host.filter(|h| *h != DEFAULT_HOST) {
+            let mut builder = Octocrab::builder()
+                .base_uri(format!("https://{}/api", h))
+                .context("Failed to set GraphQL base URI")?;
+            if let Some(ref token) = token {
+                builder = builder.personal_token(token.clone());
+            }
+            let graphql = builder
+                .build()
+                .context("Failed to build GraphQL Octocrab client")?;
+            octocrab_client = octocrab_client.with_graphql(Arc::new(graphql));
+        }
+        if token.is_none() {
+            octocrab_client = octocrab_client.anonymous();
+        }
+        // Instrumented inside the cache, so the metrics time network calls only
+        let cached = CachedGitHubClient::new(
+            InstrumentedGitHubClient::new(octocrab_client),
+            Arc::clone(&self.cache),
+            self.cache_mode,
+        )
+        .with_namespace(cache_namespace(host, profile));
+
+        info!("GitHub client created for: {}", effective_host);
         Ok(cached)
     }
 }
 
+/// HTTP status of a GitHub API error anywhere in the error's chain
+///
+/// Used to explain 401/404 responses, e.g. private repositories requested
+/// by an anonymous client.
+pub fn github_status(error: &anyhow::Error) -> Option<u16> {
+    error
+        .chain()
+        .find_map(|cause| match cause.downcast_ref::<octocrab::Error>() {
+            Some(octocrab::Error::GitHub { source, .. }) => Some(source.status_code.as_u16()),
+            _ => None,
+        })
+}
+
+/// Cache namespace of an account
+///
+/// The default github.com account keeps the unprefixed keys, so existing
+/// caches stay valid.
+pub fn cache_namespace(host: Option<&str>, profile: Option<&str>) -> String {
+    let host = host.unwrap_or(DEFAULT_HOST);
+    if host == DEFAULT_HOST && profile.is_none() {
+        String::new()
+    } else {
+        account_key(Some(host), profile)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -275,10 +560,7 @@ mod tests {
         ];
 
         for (host, expected_key) in hosts {
-            let env_key = format!(
-                "GITHUB_TOKEN_{}",
-                host.replace(['.', '-'], "_").to_uppercase()
-            );
+            let env_key = token_env_key(host, None);
             assert_eq!(
                 env_key, expected_key,
                 "Host '{}' should produce key '{}'",
@@ -287,6 +569,78 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_token_env_key_with_profile() {
+        assert_eq!(
+            token_env_key("github.com", Some("work")),
+            "GITHUB_TOKEN_GITHUB_COM_WORK"
+        );
+    }
+
+    #[test]
+    fn test_account_key_and_namespace() {
+        assert_eq!(account_key(None, None), "github.com");
+        assert_eq!(account_key(None, Some("work")), "work@github.com");
+        assert_eq!(
+            account_key(Some("ghe.example.com"), Some("me")),
+            "me@ghe.example.com"
+        );
+
+        assert_eq!(cache_namespace(None, None), "");
+        assert_eq!(cache_namespace(Some(DEFAULT_HOST), None), "");
+        assert_eq!(cache_namespace(None, Some("work")), "work@github.com");
+        assert_eq!(
+            cache_namespace(Some("ghe.example.com"), None),
+            "ghe.example.com"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_profiles_get_separate_clients() {
+        let cache = Arc::new(Mutex::new(ApiCache::default()));
+        let mut manager = ClientManager::new(cache);
+        manager
+            .init_client_with_token(None, Some("work"), "work-token".to_string())
+            .unwrap();
+
+        assert!(!manager.has_client(None));
+        assert!(manager.clients.contains_key("work@github.com"));
+
+        manager
+            .init_client_with_token(None, None, "personal-token".to_string())
+            .unwrap();
+        assert!(manager.has_client(None));
+
+        manager.remove_client(None, Some("work"));
+        assert!(!manager.clients.contains_key("work@github.com"));
+        assert!(manager.has_client(None));
+    }
+
+    #[tokio::test]
+    async fn test_anonymous_client() {
+        let cache = Arc::new(Mutex::new(ApiCache::default()));
+        let mut manager = ClientManager::new(cache);
+        manager.init_anonymous_client(None, None).unwrap();
+
+        assert!(manager.has_client(None));
+        assert!(manager.is_anonymous(None, None));
+        assert_eq!(
+            manager.clients["github.com"].inner().inner().budget_host(),
+            "https://api.github.com (anonymous)"
+        );
+        assert!(manager.login(None, None).await.is_err());
+
+        manager
+            .init_client_with_token(None, None, "token".to_string())
+            .unwrap();
+        assert!(!manager.is_anonymous(None, None));
+    }
+
+    #[test]
+    fn test_github_status_of_plain_error() {
+        assert_eq!(github_status(&anyhow::anyhow!("offline")), None);
+    }
+
     #[test]
     fn test_client_manager_new() {
         let cache = Arc::new(Mutex::new(ApiCache::default()));
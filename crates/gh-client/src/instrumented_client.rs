@@ -0,0 +1,931 @@
+//! Instrumented GitHub API client (decorator pattern)
+//!
+//! Wraps any `GitHubClient` implementation and records call counts, errors and
+//! latencies per endpoint into a shared `ApiMetrics`.
+//!
+//! The decorator composes with `CachedGitHubClient` in either order:
+//!
+//! - `CachedGitHubClient<InstrumentedGitHubClient<C>>` times network calls only
+//! - `InstrumentedGitHubClient<CachedGitHubClient<C>>` also times cache hits
+
+use crate::capabilities::HostCapabilities;
+use crate::client::GitHubClient;
+use crate::types::{
+    BlameRange, CheckRun, CheckStatus, CiStatus, CommentCounts, CommentLines, Comparison, Label,
+    MergeMethod, MergeResult, PrReviewers, PrStateFilter, PullRequest, RepositorySettings,
+    ReviewComment, ReviewDecision, ReviewEvent, SearchResults, WorkflowRun,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use gh_api_cache::{ApiCache, CacheStats};
+use std::collections::{BTreeMap, VecDeque};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Latency samples kept per endpoint for the percentiles
+pub const LATENCY_SAMPLES: usize = 256;
+
+/// Calls, errors and latencies of one endpoint since startup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointStats {
+    /// Name of the `GitHubClient` method (e.g. "fetch_pull_requests")
+    pub endpoint: &'static str,
+    pub calls: u64,
+    pub errors: u64,
+    /// Time spent waiting on all calls
+    pub total: Duration,
+    pub mean: Duration,
+    /// Median of the last `LATENCY_SAMPLES` calls
+    pub p50: Duration,
+    /// 95th percentile of the last `LATENCY_SAMPLES` calls
+    pub p95: Duration,
+    pub max: Duration,
+}
+
+/// Snapshot of the recorded API metrics
+#[derive(Debug, Clone, Default)]
+pub struct ApiMetricsSnapshot {
+    /// Endpoints with at least one call, sorted by name
+    pub endpoints: Vec<EndpointStats>,
+    /// Time since the metrics were created
+    pub uptime: Duration,
+    /// Statistics of the attached API cache, if any
+    pub cache: Option<CacheStats>,
+    /// Whether calls are being recorded
+    pub enabled: bool,
+}
+
+impl ApiMetricsSnapshot {
+    /// Number of calls over all endpoints
+    pub fn total_calls(&self) -> u64 {
+        self.endpoints.iter().map(|e| e.calls).sum()
+    }
+
+    /// Number of failed calls over all endpoints
+    pub fn total_errors(&self) -> u64 {
+        self.endpoints.iter().map(|e| e.errors).sum()
+    }
+
+    /// Time spent waiting on calls over all endpoints
+    pub fn total_time(&self) -> Duration {
+        self.endpoints.iter().map(|e| e.total).sum()
+    }
+
+    /// Endpoint with the highest 95th percentile latency
+    pub fn slowest(&self) -> Option<&EndpointStats> {
+        self.endpoints.iter().max_by_key(|e| e.p95)
+    }
+
+    /// Share of cache lookups answered from the cache
+    pub fn cache_hit_ratio(&self) -> Option<f64> {
+        self.cache.as_ref().and_then(CacheStats::hit_ratio)
+    }
+}
+
+impl std::fmt::Display for ApiMetricsSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} calls, {} errors in {}m",
+            self.total_calls(),
+            self.total_errors(),
+            self.uptime.as_secs() / 60
+        )?;
+        if let Some(slowest) = self.slowest() {
+            write!(
+                f,
+                ", slowest {} (p95 {})",
+                slowest.endpoint,
+                format_latency(slowest.p95)
+            )?;
+        }
+        if let Some(ratio) = self.cache_hit_ratio() {
+            write!(f, ", cache hit ratio {:.0}%", ratio * 100.0)?;
+        }
+        Ok(())
+    }
+}
+
+/// Format a latency as milliseconds, or seconds from one second on
+pub fn format_latency(latency: Duration) -> String {
+    if latency < Duration::from_secs(1) {
+        format!("{}ms", latency.as_millis())
+    } else {
+        format!("{:.1}s", latency.as_secs_f64())
+    }
+}
+
+#[derive(Debug, Default)]
+struct EndpointRecord {
+    calls: u64,
+    errors: u64,
+    total: Duration,
+    max: Duration,
+    samples: VecDeque<Duration>,
+}
+
+impl EndpointRecord {
+    fn stats(&self, endpoint: &'static str) -> EndpointStats {
+        let mut sorted: Vec<Duration> = self.samples.iter().copied().collect();
+        sorted.sort();
+        EndpointStats {
+            endpoint,
+            calls: self.calls,
+            errors: self.errors,
+            total: self.total,
+            mean: self.total / self.calls.max(1) as u32,
+            p50: percentile(&sorted, 50),
+            p95: percentile(&sorted, 95),
+            max: self.max,
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted samples
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (sorted.len() * percent).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
+
+/// API call metrics shared by all instrumented clients of the process
+///
+/// Recording can be switched off at runtime; disabled metrics skip the clock
+/// entirely, so the decorator only costs one atomic load per call.
+#[derive(Debug)]
+pub struct ApiMetrics {
+    enabled: AtomicBool,
+    started: Instant,
+    endpoints: Mutex<BTreeMap<&'static str, EndpointRecord>>,
+    cache: Mutex<Option<Arc<Mutex<ApiCache>>>>,
+}
+
+impl Default for ApiMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ApiMetrics {
+    /// Create enabled metrics without an attached cache
+    pub fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(true),
+            started: Instant::now(),
+            endpoints: Mutex::new(BTreeMap::new()),
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// The metrics shared by all clients of the process
+    pub fn global() -> Arc<ApiMetrics> {
+        static GLOBAL: OnceLock<Arc<ApiMetrics>> = OnceLock::new();
+        GLOBAL.get_or_init(|| Arc::new(Self::new())).clone()
+    }
+
+    /// Switch recording on or off (already recorded calls are kept)
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Whether calls are recorded
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Include the statistics of `cache` in snapshots
+    pub fn attach_cache(&self, cache: Arc<Mutex<ApiCache>>) {
+        *self.cache.lock().unwrap() = Some(cache);
+    }
+
+    /// Record one call of `endpoint`
+    pub fn record(&self, endpoint: &'static str, latency: Duration, ok: bool) {
+        let mut endpoints = self.endpoints.lock().unwrap();
+        let record = endpoints.entry(endpoint).or_default();
+        record.calls += 1;
+        if !ok {
+            record.errors += 1;
+        }
+        record.total += latency;
+        record.max = record.max.max(latency);
+        if record.samples.len() == LATENCY_SAMPLES {
+            record.samples.pop_front();
+        }
+        record.samples.push_back(latency);
+    }
+
+    /// Snapshot of all endpoints and the attached cache
+    pub fn snapshot(&self) -> ApiMetricsSnapshot {
+        let endpoints = self
+            .endpoints
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(endpoint, record)| record.stats(endpoint))
+            .collect();
+        // Clone the handle first, never hold both locks at once
+        let cache = self.cache.lock().unwrap().clone();
+        ApiMetricsSnapshot {
+            endpoints,
+            uptime: self.started.elapsed(),
+            cache: cache.map(|cache| cache.lock().unwrap().stats()),
+            enabled: self.is_enabled(),
+        }
+    }
+}
+
+/// Instrumented GitHub API client using the decorator pattern
+///
+/// Times every call of the inner client and records it under the name of the
+/// `GitHubClient` method.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use gh_client::{ApiMetrics, CachedGitHubClient, InstrumentedGitHubClient, OctocrabClient};
+///
+/// let inner = InstrumentedGitHubClient::new(OctocrabClient::new(octocrab));
+/// let client = CachedGitHubClient::new(inner, cache, CacheMode::ReadWrite);
+///
+/// println!("{}", ApiMetrics::global().snapshot());
+/// ```
+#[derive(Debug, Clone)]
+pub struct InstrumentedGitHubClient<C: GitHubClient> {
+    inner: C,
+    metrics: Arc<ApiMetrics>,
+}
+
+impl<C: GitHubClient> InstrumentedGitHubClient<C> {
+    /// Create a client recording into the global metrics
+    pub fn new(inner: C) -> Self {
+        Self::with_metrics(inner, ApiMetrics::global())
+    }
+
+    /// Create a client recording into the given metrics
+    pub fn with_metrics(inner: C, metrics: Arc<ApiMetrics>) -> Self {
+        Self { inner, metrics }
+    }
+
+    /// Get a reference to the inner client
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Get the metrics this client records into
+    pub fn metrics(&self) -> &Arc<ApiMetrics> {
+        &self.metrics
+    }
+
+    /// Await `call`, recording its latency and outcome under `endpoint`
+    async fn timed<T>(
+        &self,
+        endpoint: &'static str,
+        call: impl Future<Output = anyhow::Result<T>>,
+    ) -> anyhow::Result<T> {
+        if !self.metrics.is_enabled() {
+            return call.await;
+        }
+        let start = Instant::now();
+        let result = call.await;
+        self.metrics
+            .record(endpoint, start.elapsed(), result.is_ok());
+        result
+    }
+}
+
+#[async_trait]
+impl<C: GitHubClient> GitHubClient for InstrumentedGitHubClient<C> {
+    async fn fetch_pull_requests(
+        &self,
+        owner: &str,
+        repo: &str,
+        base_branch: Option<&str>,
+        state: PrStateFilter,
+    ) -> anyhow::Result<Vec<PullRequest>> {
+        self.timed(
+            "fetch_pull_requests",
+            self.inner
+                .fetch_pull_requests(owner, repo, base_branch, state),
+        )
+        .await
+    }
+
+    async fn search_pull_requests_in_repo(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &str,
+        page: u32,
+    ) -> anyhow::Result<SearchResults> {
+        self.timed(
+            "search_pull_requests_in_repo",
+            self.inner
+                .search_pull_requests_in_repo(owner, repo, query, page),
+        )
+        .await
+    }
+
+    async fn count_search(&self, query: &str) -> anyhow::Result<u64> {
+        self.timed("count_search", self.inner.count_search(query))
+            .await
+    }
+
+    async fn fetch_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<PullRequest> {
+        self.timed(
+            "fetch_pull_request",
+            self.inner.fetch_pull_request(owner, repo, pr_number),
+        )
+        .await
+    }
+
+    async fn fetch_check_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        commit_sha: &str,
+    ) -> anyhow::Result<Vec<CheckRun>> {
+        self.timed(
+            "fetch_check_runs",
+            self.inner.fetch_check_runs(owner, repo, commit_sha),
+        )
+        .await
+    }
+
+    async fn fetch_commit_status(
+        &self,
+        owner: &str,
+        repo: &str,
+        commit_sha: &str,
+    ) -> anyhow::Result<CheckStatus> {
+        self.timed(
+            "fetch_commit_status",
+            self.inner.fetch_commit_status(owner, repo, commit_sha),
+        )
+        .await
+    }
+
+    async fn merge_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        merge_method: MergeMethod,
+        commit_title: Option<&str>,
+        commit_message: Option<&str>,
+    ) -> anyhow::Result<MergeResult> {
+        self.timed(
+            "merge_pull_request",
+            self.inner.merge_pull_request(
+                owner,
+                repo,
+                pr_number,
+                merge_method,
+                commit_title,
+                commit_message,
+            ),
+        )
+        .await
+    }
+
+    async fn update_pull_request_branch(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<()> {
+        self.timed(
+            "update_pull_request_branch",
+            self.inner
+                .update_pull_request_branch(owner, repo, pr_number),
+        )
+        .await
+    }
+
+    async fn create_review(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        event: ReviewEvent,
+        body: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.timed(
+            "create_review",
+            self.inner
+                .create_review(owner, repo, pr_number, event, body),
+        )
+        .await
+    }
+
+    async fn close_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<()> {
+        self.timed(
+            "close_pull_request",
+            self.inner.close_pull_request(owner, repo, pr_number),
+        )
+        .await
+    }
+
+    async fn fetch_repository_settings(
+        &self,
+        owner: &str,
+        repo: &str,
+    ) -> anyhow::Result<RepositorySettings> {
+        self.timed(
+            "fetch_repository_settings",
+            self.inner.fetch_repository_settings(owner, repo),
+        )
+        .await
+    }
+
+    async fn fetch_required_checks(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> anyhow::Result<Option<Vec<String>>> {
+        self.timed(
+            "fetch_required_checks",
+            self.inner.fetch_required_checks(owner, repo, branch),
+        )
+        .await
+    }
+
+    async fn delete_branch(&self, owner: &str, repo: &str, branch: &str) -> anyhow::Result<()> {
+        self.timed(
+            "delete_branch",
+            self.inner.delete_branch(owner, repo, branch),
+        )
+        .await
+    }
+
+    async fn fetch_repo_labels(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<Label>> {
+        self.timed(
+            "fetch_repo_labels",
+            self.inner.fetch_repo_labels(owner, repo),
+        )
+        .await
+    }
+
+    async fn add_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        labels: &[String],
+    ) -> anyhow::Result<()> {
+        self.timed(
+            "add_labels",
+            self.inner.add_labels(owner, repo, pr_number, labels),
+        )
+        .await
+    }
+
+    async fn remove_label(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        label: &str,
+    ) -> anyhow::Result<()> {
+        self.timed(
+            "remove_label",
+            self.inner.remove_label(owner, repo, pr_number, label),
+        )
+        .await
+    }
+
+    async fn fetch_collaborators(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<String>> {
+        self.timed(
+            "fetch_collaborators",
+            self.inner.fetch_collaborators(owner, repo),
+        )
+        .await
+    }
+
+    async fn fetch_branches(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<String>> {
+        self.timed("fetch_branches", self.inner.fetch_branches(owner, repo))
+            .await
+    }
+
+    async fn request_reviewers(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        reviewers: &[String],
+        team_reviewers: &[String],
+    ) -> anyhow::Result<()> {
+        self.timed(
+            "request_reviewers",
+            self.inner
+                .request_reviewers(owner, repo, pr_number, reviewers, team_reviewers),
+        )
+        .await
+    }
+
+    async fn rerun_failed_jobs(&self, owner: &str, repo: &str, run_id: u64) -> anyhow::Result<()> {
+        self.timed(
+            "rerun_failed_jobs",
+            self.inner.rerun_failed_jobs(owner, repo, run_id),
+        )
+        .await
+    }
+
+    async fn fetch_workflow_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        head_sha: &str,
+    ) -> anyhow::Result<Vec<WorkflowRun>> {
+        self.timed(
+            "fetch_workflow_runs",
+            self.inner.fetch_workflow_runs(owner, repo, head_sha),
+        )
+        .await
+    }
+
+    async fn fetch_recent_workflow_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        limit: usize,
+    ) -> anyhow::Result<Vec<WorkflowRun>> {
+        self.timed(
+            "fetch_recent_workflow_runs",
+            self.inner
+                .fetch_recent_workflow_runs(owner, repo, branch, limit),
+        )
+        .await
+    }
+
+    async fn fetch_branch_head_sha(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> anyhow::Result<Option<String>> {
+        self.timed(
+            "fetch_branch_head_sha",
+            self.inner.fetch_branch_head_sha(owner, repo, branch),
+        )
+        .await
+    }
+
+    async fn fetch_compare(
+        &self,
+        owner: &str,
+        repo: &str,
+        base: &str,
+        head: &str,
+    ) -> anyhow::Result<Comparison> {
+        self.timed(
+            "fetch_compare",
+            self.inner.fetch_compare(owner, repo, base, head),
+        )
+        .await
+    }
+
+    async fn fetch_latest_tag(&self, owner: &str, repo: &str) -> anyhow::Result<Option<String>> {
+        self.timed("fetch_latest_tag", self.inner.fetch_latest_tag(owner, repo))
+            .await
+    }
+
+    async fn fetch_ref_date(
+        &self,
+        owner: &str,
+        repo: &str,
+        git_ref: &str,
+    ) -> anyhow::Result<Option<DateTime<Utc>>> {
+        self.timed(
+            "fetch_ref_date",
+            self.inner.fetch_ref_date(owner, repo, git_ref),
+        )
+        .await
+    }
+
+    async fn fetch_pull_request_diff(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<String> {
+        self.timed(
+            "fetch_pull_request_diff",
+            self.inner.fetch_pull_request_diff(owner, repo, pr_number),
+        )
+        .await
+    }
+
+    async fn fetch_ci_status(
+        &self,
+        owner: &str,
+        repo: &str,
+        head_sha: &str,
+    ) -> anyhow::Result<CiStatus> {
+        self.timed(
+            "fetch_ci_status",
+            self.inner.fetch_ci_status(owner, repo, head_sha),
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn create_review_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        commit_id: &str,
+        path: &str,
+        lines: &CommentLines,
+        body: &str,
+    ) -> anyhow::Result<u64> {
+        self.timed(
+            "create_review_comment",
+            self.inner
+                .create_review_comment(owner, repo, pr_number, commit_id, path, lines, body),
+        )
+        .await
+    }
+
+    async fn delete_review_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        comment_id: u64,
+    ) -> anyhow::Result<()> {
+        self.timed(
+            "delete_review_comment",
+            self.inner.delete_review_comment(owner, repo, comment_id),
+        )
+        .await
+    }
+
+    async fn create_file_review_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        commit_id: &str,
+        path: &str,
+        body: &str,
+    ) -> anyhow::Result<u64> {
+        self.timed(
+            "create_file_review_comment",
+            self.inner
+                .create_file_review_comment(owner, repo, pr_number, commit_id, path, body),
+        )
+        .await
+    }
+
+    async fn create_issue_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        body: &str,
+    ) -> anyhow::Result<u64> {
+        self.timed(
+            "create_issue_comment",
+            self.inner
+                .create_issue_comment(owner, repo, pr_number, body),
+        )
+        .await
+    }
+
+    async fn delete_issue_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        comment_id: u64,
+    ) -> anyhow::Result<()> {
+        self.timed(
+            "delete_issue_comment",
+            self.inner.delete_issue_comment(owner, repo, comment_id),
+        )
+        .await
+    }
+
+    async fn fetch_review_comments(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<Vec<ReviewComment>> {
+        self.timed(
+            "fetch_review_comments",
+            self.inner.fetch_review_comments(owner, repo, pr_number),
+        )
+        .await
+    }
+
+    async fn fetch_review_decision(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<ReviewDecision> {
+        self.timed(
+            "fetch_review_decision",
+            self.inner.fetch_review_decision(owner, repo, pr_number),
+        )
+        .await
+    }
+
+    async fn fetch_comment_counts(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<CommentCounts> {
+        self.timed(
+            "fetch_comment_counts",
+            self.inner.fetch_comment_counts(owner, repo, pr_number),
+        )
+        .await
+    }
+
+    async fn fetch_reviewers(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_numbers: &[u64],
+    ) -> anyhow::Result<Vec<PrReviewers>> {
+        self.timed(
+            "fetch_reviewers",
+            self.inner.fetch_reviewers(owner, repo, pr_numbers),
+        )
+        .await
+    }
+
+    async fn fetch_blame(
+        &self,
+        owner: &str,
+        repo: &str,
+        commit_sha: &str,
+        path: &str,
+    ) -> anyhow::Result<Vec<BlameRange>> {
+        self.timed(
+            "fetch_blame",
+            self.inner.fetch_blame(owner, repo, commit_sha, path),
+        )
+        .await
+    }
+
+    async fn fetch_file_content(
+        &self,
+        owner: &str,
+        repo: &str,
+        path: &str,
+        git_ref: Option<&str>,
+    ) -> anyhow::Result<String> {
+        self.timed(
+            "fetch_file_content",
+            self.inner.fetch_file_content(owner, repo, path, git_ref),
+        )
+        .await
+    }
+
+    async fn capabilities(&self) -> HostCapabilities {
+        self.inner.capabilities().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cached_client::tests::{create_test_pr, MockClient};
+    use crate::{CacheMode, CachedGitHubClient};
+
+    fn endpoint<'a>(snapshot: &'a ApiMetricsSnapshot, name: &str) -> &'a EndpointStats {
+        snapshot
+            .endpoints
+            .iter()
+            .find(|e| e.endpoint == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_percentiles_use_nearest_rank() {
+        let metrics = ApiMetrics::new();
+        for ms in 1..=100 {
+            metrics.record("fetch_ci_status", Duration::from_millis(ms), ms % 10 != 0);
+        }
+
+        let snapshot = metrics.snapshot();
+        let stats = endpoint(&snapshot, "fetch_ci_status");
+        assert_eq!(stats.calls, 100);
+        assert_eq!(stats.errors, 10);
+        assert_eq!(stats.p50, Duration::from_millis(50));
+        assert_eq!(stats.p95, Duration::from_millis(95));
+        assert_eq!(stats.max, Duration::from_millis(100));
+        assert_eq!(percentile(&[], 95), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_calls_are_recorded_through_trait_object() {
+        let metrics = Arc::new(ApiMetrics::new());
+        let client: Box<dyn GitHubClient> = Box::new(InstrumentedGitHubClient::with_metrics(
+            MockClient::new(vec![create_test_pr(1)]),
+            Arc::clone(&metrics),
+        ));
+
+        client
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
+            .await
+            .unwrap();
+        client
+            .fetch_pull_requests("owner", "repo", None, PrStateFilter::Open)
+            .await
+            .unwrap();
+        // The mock only knows PR #1
+        assert!(client.fetch_pull_request("owner", "repo", 2).await.is_err());
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.total_calls(), 3);
+        assert_eq!(snapshot.total_errors(), 1);
+        assert_eq!(endpoint(&snapshot, "fetch_pull_requests").calls, 2);
+        assert_eq!(endpoint(&snapshot, "fetch_pull_request").errors, 1);
+        let stats = endpoint(&snapshot, "fetch_pull_requests");
+        assert!(stats.p50 <= stats.p95 && stats.p95 <= stats.max);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_metrics_record_nothing() {
+        let metrics = Arc::new(ApiMetrics::new());
+        metrics.set_enabled(false);
+        let client =
+            InstrumentedGitHubClient::with_metrics(MockClient::new(vec![]), Arc::clone(&metrics));
+
+        client.fetch_repo_labels("owner", "repo").await.unwrap();
+        let snapshot = metrics.snapshot();
+        assert!(snapshot.endpoints.is_empty());
+        assert!(!snapshot.enabled);
+    }
+
+    #[tokio::test]
+    async fn test_composes_with_cache_in_either_order() {
+        let metrics = Arc::new(ApiMetrics::new());
+        let cache = Arc::new(Mutex::new(ApiCache::default()));
+        metrics.attach_cache(Arc::clone(&cache));
+
+        // Cache outside: only the miss reaches the instrumented client
+        let mock = MockClient::new(vec![create_test_pr(1)]);
+        let client: Box<dyn GitHubClient> = Box::new(CachedGitHubClient::new(
+            InstrumentedGitHubClient::with_metrics(mock.clone(), Arc::clone(&metrics)),
+            Arc::clone(&cache),
+            CacheMode::ReadWrite,
+        ));
+        for _ in 0..2 {
+            client.fetch_pull_request("owner", "repo", 1).await.unwrap();
+        }
+        assert_eq!(mock.call_count(), 1);
+        let snapshot = metrics.snapshot();
+        assert_eq!(endpoint(&snapshot, "fetch_pull_request").calls, 1);
+        assert_eq!(snapshot.cache_hit_ratio(), Some(0.5));
+
+        // Instrumentation outside: cache hits are timed as well
+        let mock = MockClient::new(vec![create_test_pr(1)]);
+        let client: Box<dyn GitHubClient> = Box::new(InstrumentedGitHubClient::with_metrics(
+            CachedGitHubClient::new(mock.clone(), Arc::clone(&cache), CacheMode::ReadWrite)
+                .with_namespace("outer:"),
+            Arc::clone(&metrics),
+        ));
+        for _ in 0..2 {
+            client.fetch_pull_request("owner", "repo", 1).await.unwrap();
+        }
+        assert_eq!(mock.call_count(), 1);
+        let snapshot = metrics.snapshot();
+        assert_eq!(endpoint(&snapshot, "fetch_pull_request").calls, 3);
+        assert_eq!(snapshot.cache_hit_ratio(), Some(0.5));
+    }
+
+    #[test]
+    fn test_summary_line() {
+        let metrics = ApiMetrics::new();
+        assert_eq!(metrics.snapshot().to_string(), "0 calls, 0 errors in 0m");
+
+        metrics.record("fetch_check_runs", Duration::from_millis(120), true);
+        metrics.record("fetch_pull_requests", Duration::from_millis(1500), false);
+        assert_eq!(
+            metrics.snapshot().to_string(),
+            "2 calls, 1 errors in 0m, slowest fetch_pull_requests (p95 1.5s)"
+        );
+        assert_eq!(format_latency(Duration::from_millis(120)), "120ms");
+    }
+}
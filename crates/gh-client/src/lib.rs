@@ -20,12 +20,17 @@
 //! │ OctocrabClient  │         │ CachedGitHubClient  │
 //! │ (direct API)    │◄────────│ (decorator)         │
 //! └─────────────────┘         └─────────────────────┘
+//!          ▲
+//!          │                  ┌──────────────────────────┐
+//!          └──────────────────│ InstrumentedGitHubClient │
+//!                             │ (decorator, ApiMetrics)  │
+//!                             └──────────────────────────┘
 //! ```
 //!
 //! # Example
 //!
 //! ```rust,no_run
-//! use gh_client::{GitHubClient, OctocrabClient, CachedGitHubClient, CacheMode};
+//! use gh_client::{GitHubClient, OctocrabClient, CachedGitHubClient, CacheMode, PrStateFilter};
 //! use gh_api_cache::ApiCache;
 //! use std::sync::{Arc, Mutex};
 //!
@@ -47,31 +52,55 @@
 //! );
 //!
 //! // Both implement the same trait
-//! let prs = cached.fetch_pull_requests("owner", "repo", None).await?;
+//! let prs = cached.fetch_pull_requests("owner", "repo", None, PrStateFilter::Open).await?;
 //! # Ok(())
 //! # }
 //! ```
 
+pub mod budget;
 pub mod cached_client;
+pub mod capabilities;
 pub mod client;
 pub mod client_manager;
+pub mod instrumented_client;
 pub mod octocrab_client;
+pub mod search_limiter;
+pub mod service_status;
+pub mod token_store;
 pub mod types;
 
 /// Default GitHub host (public GitHub)
 pub const DEFAULT_HOST: &str = "github.com";
 
-pub use cached_client::CachedGitHubClient;
-pub use client::{CacheMode, GitHubClient};
-pub use client_manager::{ClientManager, TokenResolver};
+pub use budget::{
+    background, ClientMetrics, RequestBudget, RequestCategory, RequestPriority,
+    ANONYMOUS_REQUESTS_PER_HOUR, DEFAULT_REQUESTS_PER_MINUTE,
+};
+pub use cached_client::{peek_pull_requests, CacheMiss, CachedGitHubClient, CachedPullRequests};
+pub use capabilities::{unsupported_feature, Feature, HostCapabilities, Unsupported};
+pub use client::{
+    repo_search_query, CacheMode, GitHubClient, REVIEWERS_BATCH_SIZE, SEARCH_PAGE_SIZE,
+};
+pub use client_manager::{
+    account_key, cache_namespace, github_status, ClientManager, ManagedClient, TokenResolver,
+};
+pub use instrumented_client::{
+    format_latency, ApiMetrics, ApiMetricsSnapshot, EndpointStats, InstrumentedGitHubClient,
+};
 pub use octocrab_client::OctocrabClient;
+pub use service_status::{Incident, StatusPage, StatusSource, GITHUB_STATUS_URL};
+pub use token_store::{TokenBackend, TokenStore};
 pub use types::{
-    CheckRun, CheckStatus, CiState, CiStatus, MergeMethod, MergeResult, PullRequest, ReviewComment,
-    ReviewEvent, WorkflowRun, WorkflowRunConclusion, WorkflowRunStatus,
+    BlameRange, ChangedFile, CheckResult, CheckRun, CheckStatus, CiState, CiStatus, CommentCounts,
+    CommentLines, Comparison, Label, LatestReview, MergeMethod, MergeRejection, MergeResult,
+    PrReviewers, PrState, PrStateFilter, PullRequest, RepositorySettings, ReviewComment,
+    ReviewEvent, SearchResults, WorkflowRun, WorkflowRunConclusion, WorkflowRunStatus,
 };
 
 // Re-export cache types for convenience
-pub use gh_api_cache::{ApiCache, CacheStats, CachedResponse};
+pub use gh_api_cache::{
+    ApiCache, CacheEntryInfo, CacheStats, CachedResponse, RunLogCache, RunLogKey,
+};
 
 // Re-export octocrab so consumers don't need to depend on it directly
 pub use octocrab;
@@ -3,17 +3,26 @@
 //! Direct implementation of the `GitHubClient` trait using the octocrab library.
 //! This client makes real API calls without any caching.
 
-use crate::client::GitHubClient;
+use crate::budget::{RequestBudget, RequestCategory, ANONYMOUS_REQUESTS_PER_HOUR};
+use crate::capabilities::{self, Feature, HostCapabilities};
+use crate::client::{repo_search_query, GitHubClient, REVIEWERS_BATCH_SIZE, SEARCH_PAGE_SIZE};
+use crate::search_limiter::SearchLimiter;
 use crate::types::{
-    CheckConclusion, CheckRun, CheckRunStatus, CheckState, CheckStatus, CiState, CiStatus,
-    CommitStatus, MaturityState, MergeMethod, MergeResult, MergeableState, PullRequest,
-    ReviewComment, ReviewDecision, ReviewEvent, WorkflowRun, WorkflowRunConclusion,
+    BlameRange, ChangedFile, CheckConclusion, CheckResult, CheckRun, CheckRunStatus, CheckState,
+    CheckStatus, CiStatus, CommentCounts, CommentLines, CommitStatus, Comparison, Label,
+    LatestReview, MaturityState, MergeMethod, MergeRejection, MergeResult, MergeableState,
+    PrReviewers, PrState, PrStateFilter, PullRequest, RepositorySettings, ReviewComment,
+    ReviewDecision, ReviewEvent, SearchResults, WorkflowRun, WorkflowRunConclusion,
     WorkflowRunStatus,
 };
+use crate::DEFAULT_HOST;
+use anyhow::Context;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use log::debug;
 use octocrab::Octocrab;
 use std::sync::Arc;
+use tokio::sync::OnceCell;
 
 /// Direct GitHub API client using octocrab
 ///
@@ -22,27 +31,137 @@ use std::sync::Arc;
 #[derive(Debug, Clone)]
 pub struct OctocrabClient {
     octocrab: Arc<Octocrab>,
+    /// Instance sending GraphQL queries (GitHub Enterprise serves them
+    /// outside the REST base URL, at `/api/graphql`)
+    graphql: Arc<Octocrab>,
     /// API base URL (e.g., "<https://api.github.com>" or "<https://ghe.example.com/api/v3>")
     api_base_url: String,
+    /// Key of this client's bucket in the request budget
+    budget_host: String,
+    /// No token: only public data, at the anonymous rate limit
+    anonymous: bool,
+    /// Guards the search API's separate rate limit
+    search_limiter: SearchLimiter,
+    /// Process-wide request budget acquired before every request
+    budget: Arc<RequestBudget>,
+    /// GraphQL capabilities of the host, shared by all clients of the host
+    capabilities: Arc<OnceCell<HostCapabilities>>,
 }
 
 impl OctocrabClient {
     /// Create a new client with the given octocrab instance (defaults to github.com)
     pub fn new(octocrab: Arc<Octocrab>) -> Self {
         Self {
+            graphql: Arc::clone(&octocrab),
             octocrab,
             api_base_url: "https://api.github.com".to_string(),
+            budget_host: "https://api.github.com".to_string(),
+            anonymous: false,
+            search_limiter: SearchLimiter::default(),
+            budget: RequestBudget::global(),
+            capabilities: Arc::default(),
         }
     }
 
     /// Create a new client with a custom API base URL (for GitHub Enterprise)
     pub fn with_base_url(octocrab: Arc<Octocrab>, api_base_url: impl Into<String>) -> Self {
+        let api_base_url = api_base_url.into();
         Self {
+            graphql: Arc::clone(&octocrab),
             octocrab,
-            api_base_url: api_base_url.into(),
+            budget_host: api_base_url.clone(),
+            api_base_url,
+            anonymous: false,
+            search_limiter: SearchLimiter::default(),
+            budget: RequestBudget::global(),
+            capabilities: Arc::default(),
         }
     }
 
+    /// Send GraphQL queries through a different instance
+    ///
+    /// Needed for GitHub Enterprise, whose GraphQL endpoint is not below
+    /// the REST base URL.
+    pub fn with_graphql(mut self, graphql: Arc<Octocrab>) -> Self {
+        self.graphql = graphql;
+        self
+    }
+
+    /// Share the probed capabilities with the other clients of the host
+    pub fn with_capabilities(mut self, capabilities: Arc<OnceCell<HostCapabilities>>) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Use a different request budget than the process-wide one
+    pub fn with_budget(mut self, budget: Arc<RequestBudget>) -> Self {
+        if self.anonymous {
+            budget.set_host_requests_per_hour(&self.budget_host, ANONYMOUS_REQUESTS_PER_HOUR);
+        }
+        self.budget = budget;
+        self
+    }
+
+    /// Mark this client as unauthenticated
+    ///
+    /// Anonymous requests get their own budget bucket limited to
+    /// `ANONYMOUS_REQUESTS_PER_HOUR`, GitHub's rate limit without a token.
+    pub fn anonymous(mut self) -> Self {
+        self.anonymous = true;
+        self.budget_host = format!("{} (anonymous)", self.api_base_url);
+        self.budget
+            .set_host_requests_per_hour(&self.budget_host, ANONYMOUS_REQUESTS_PER_HOUR);
+        self
+    }
+
+    /// Whether this client has no token
+    pub fn is_anonymous(&self) -> bool {
+        self.anonymous
+    }
+
+    /// Key of this client's bucket in the request budget
+    pub fn budget_host(&self) -> &str {
+        &self.budget_host
+    }
+
+    /// Wait for the request budget of this client's host
+    async fn acquire(&self, category: RequestCategory) {
+        self.budget.acquire(&self.budget_host, category).await;
+    }
+
+    /// Host name of the API base URL (e.g. "github.com" or "ghe.example.com")
+    fn host(&self) -> &str {
+        let host = self
+            .api_base_url
+            .split("://")
+            .last()
+            .unwrap_or_default()
+            .split('/')
+            .next()
+            .unwrap_or_default();
+        if host == "api.github.com" {
+            DEFAULT_HOST
+        } else {
+            host
+        }
+    }
+
+    /// Fail with `Unsupported` if the host lacks the feature
+    async fn require(&self, feature: Feature) -> anyhow::Result<()> {
+        self.capabilities().await.require(self.host(), feature)
+    }
+
+    /// Query which GraphQL features the host supports
+    async fn probe_capabilities(&self) -> anyhow::Result<HostCapabilities> {
+        self.acquire(RequestCategory::PullRequests).await;
+        let response: serde_json::Value = self
+            .graphql
+            .graphql(&serde_json::json!({ "query": capabilities::PROBE_QUERY }))
+            .await
+            .map_err(format_octocrab_error)?;
+        capabilities::parse_capabilities(&response)
+    }
+
     /// Get the API base URL
     pub fn api_base_url(&self) -> &str {
         &self.api_base_url
@@ -68,50 +187,108 @@ impl GitHubClient for OctocrabClient {
         owner: &str,
         repo: &str,
         base_branch: Option<&str>,
+        state: PrStateFilter,
     ) -> anyhow::Result<Vec<PullRequest>> {
-        debug!("Fetching PRs for {}/{}", owner, repo);
+        debug!("Fetching {} PRs for {}/{}", state.as_str(), owner, repo);
+
+        let route = format!("/repos/{}/{}/pulls", owner, repo);
+        let mut prs = collect_pull_requests(state, |page_num| {
+            let route = &route;
+            async move {
+                self.acquire(RequestCategory::PullRequests).await;
+                let mut params = vec![
+                    (
+                        "state",
+                        match state {
+                            PrStateFilter::Open => "open",
+                            // Merged PRs are closed PRs with a merge date
+                            PrStateFilter::Closed | PrStateFilter::Merged => "closed",
+                            PrStateFilter::All => "all",
+                        }
+                        .to_string(),
+                    ),
+                    ("per_page", PR_PAGE_SIZE.to_string()),
+                    ("page", page_num.to_string()),
+                ];
+                if let Some(branch) = base_branch {
+                    // base filters by target branch (where PR merges INTO)
+                    params.push(("base", branch.to_string()));
+                }
 
-        let mut prs = Vec::new();
-        let mut page_num = 1u32;
-        const MAX_PRS: usize = 50;
-        const PER_PAGE: u8 = 30;
+                // Raw JSON, octocrab's model rejects mergeable states it does not know
+                let page: Vec<serde_json::Value> = self.octocrab.get(route, Some(&params)).await?;
+                Ok(page)
+            }
+        })
+        .await?;
 
-        loop {
-            let pulls = self.octocrab.pulls(owner, repo);
-            let mut request = pulls
-                .list()
-                .state(octocrab::params::State::Open)
-                .per_page(PER_PAGE)
-                .page(page_num);
+        // Sort by PR number (descending) for stable ordering
+        prs.sort_by_key(|pr| std::cmp::Reverse(pr.number));
+        prs.dedup_by_key(|pr| pr.number);
 
-            if let Some(branch) = base_branch {
-                // .base() filters by target branch (where PR merges INTO)
-                request = request.base(branch);
-            }
+        debug!("Fetched {} PRs for {}/{}", prs.len(), owner, repo);
+        Ok(prs)
+    }
 
-            let page = request.send().await?;
-            let page_is_empty = page.items.is_empty();
+    async fn search_pull_requests_in_repo(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &str,
+        page: u32,
+    ) -> anyhow::Result<SearchResults> {
+        let query = repo_search_query(owner, repo, query);
+        debug!("Searching PRs: {} (page {})", query, page);
+
+        if let Err(wait) = self.search_limiter.try_acquire() {
+            anyhow::bail!(
+                "Search rate limit reached, try again in {}s",
+                wait.as_secs().max(1)
+            );
+        }
+        self.acquire(RequestCategory::Search).await;
 
-            for pr in page.items {
-                if prs.len() >= MAX_PRS {
-                    break;
-                }
-                prs.push(PullRequest::from(&pr));
-            }
+        let result = self
+            .octocrab
+            .search()
+            .issues_and_pull_requests(&query)
+            .per_page(SEARCH_PAGE_SIZE as u8)
+            .page(page)
+            .send()
+            .await?;
 
-            if prs.len() >= MAX_PRS || page_is_empty {
-                break;
-            }
+        Ok(SearchResults {
+            total_count: result.total_count.unwrap_or(0),
+            incomplete: result.incomplete_results.unwrap_or(false),
+            items: result
+                .items
+                .iter()
+                .filter(|issue| issue.pull_request.is_some())
+                .map(PullRequest::from)
+                .collect(),
+        })
+    }
 
-            page_num += 1;
+    async fn count_search(&self, query: &str) -> anyhow::Result<u64> {
+        debug!("Counting search results: {}", query);
+
+        if let Err(wait) = self.search_limiter.try_acquire() {
+            anyhow::bail!(
+                "Search rate limit reached, try again in {}s",
+                wait.as_secs().max(1)
+            );
         }
+        self.acquire(RequestCategory::Search).await;
 
-        // Sort by PR number (descending) for stable ordering
-        prs.sort_by(|a, b| b.number.cmp(&a.number));
-        prs.dedup_by_key(|pr| pr.number);
+        let result = self
+            .octocrab
+            .search()
+            .issues_and_pull_requests(query)
+            .per_page(1)
+            .send()
+            .await?;
 
-        debug!("Fetched {} PRs for {}/{}", prs.len(), owner, repo);
-        Ok(prs)
+        Ok(result.total_count.unwrap_or(0))
     }
 
     async fn fetch_pull_request(
@@ -121,10 +298,12 @@ impl GitHubClient for OctocrabClient {
         pr_number: u64,
     ) -> anyhow::Result<PullRequest> {
         debug!("Fetching PR #{} for {}/{}", pr_number, owner, repo);
+        self.acquire(RequestCategory::PullRequests).await;
 
-        let pr = self.octocrab.pulls(owner, repo).get(pr_number).await?;
+        let route = format!("/repos/{}/{}/pulls/{}", owner, repo, pr_number);
+        let pr: serde_json::Value = self.octocrab.get(route, None::<&()>).await?;
 
-        Ok(PullRequest::from(&pr))
+        parse_pull_request(pr)
     }
 
     async fn fetch_check_runs(
@@ -137,6 +316,7 @@ impl GitHubClient for OctocrabClient {
             "Fetching check runs for {}/{} @ {}",
             owner, repo, commit_sha
         );
+        self.acquire(RequestCategory::Checks).await;
 
         let checks = self
             .octocrab
@@ -186,6 +366,7 @@ impl GitHubClient for OctocrabClient {
             "Fetching commit status for {}/{} @ {}",
             owner, repo, commit_sha
         );
+        self.acquire(RequestCategory::Checks).await;
 
         // Use raw GET request since octocrab's Reference type doesn't support commit SHAs
         let route = format!("/repos/{}/{}/commits/{}/status", owner, repo, commit_sha);
@@ -224,6 +405,7 @@ impl GitHubClient for OctocrabClient {
             "Merging PR #{} in {}/{} with method {:?}",
             pr_number, owner, repo, merge_method
         );
+        self.acquire(RequestCategory::Mutations).await;
 
         let octocrab_method = match merge_method {
             MergeMethod::Merge => octocrab::params::pulls::MergeMethod::Merge,
@@ -244,12 +426,20 @@ impl GitHubClient for OctocrabClient {
             merge_builder = merge_builder.message(message);
         }
 
-        let response = merge_builder.send().await.map_err(format_octocrab_error)?;
+        let response = match merge_builder.send().await {
+            Ok(response) => response,
+            Err(octocrab::Error::GitHub { source, .. }) if is_merge_refusal(&source) => {
+                let message = github_error_message(&source.message, source.errors.as_deref());
+                return Ok(refused_merge(message));
+            }
+            Err(e) => return Err(format_octocrab_error(e)),
+        };
 
         Ok(MergeResult {
             merged: response.merged,
             sha: response.sha,
             message: response.message.unwrap_or_default(),
+            rejection: None,
         })
     }
 
@@ -263,6 +453,7 @@ impl GitHubClient for OctocrabClient {
             "Updating branch for PR #{} in {}/{}",
             pr_number, owner, repo
         );
+        self.acquire(RequestCategory::Mutations).await;
 
         // Use raw PUT request since octocrab doesn't have a method for this
         let route = format!(
@@ -290,6 +481,7 @@ impl GitHubClient for OctocrabClient {
             "Creating {:?} review for PR #{} in {}/{}",
             event, pr_number, owner, repo
         );
+        self.acquire(RequestCategory::Mutations).await;
 
         // Use raw POST request since octocrab's review API is limited
         let route = format!("/repos/{}/{}/pulls/{}/reviews", owner, repo, pr_number);
@@ -324,6 +516,7 @@ impl GitHubClient for OctocrabClient {
         pr_number: u64,
     ) -> anyhow::Result<()> {
         debug!("Closing PR #{} in {}/{}", pr_number, owner, repo);
+        self.acquire(RequestCategory::Mutations).await;
 
         // Use raw PATCH request since octocrab's State enum doesn't match
         let route = format!("/repos/{}/{}/pulls/{}", owner, repo, pr_number);
@@ -340,243 +533,754 @@ impl GitHubClient for OctocrabClient {
         Ok(())
     }
 
-    async fn rerun_failed_jobs(&self, owner: &str, repo: &str, run_id: u64) -> anyhow::Result<()> {
-        debug!(
-            "Rerunning failed jobs for workflow run {} in {}/{}",
-            run_id, owner, repo
-        );
+    async fn fetch_repository_settings(
+        &self,
+        owner: &str,
+        repo: &str,
+    ) -> anyhow::Result<RepositorySettings> {
+        debug!("Fetching repository settings for {}/{}", owner, repo);
+        self.acquire(RequestCategory::PullRequests).await;
 
-        let route = format!(
-            "/repos/{}/{}/actions/runs/{}/rerun-failed-jobs",
-            owner, repo, run_id
-        );
-        let _response: serde_json::Value = self
+        let repository = self
             .octocrab
-            .post(route, None::<&()>)
+            .repos(owner, repo)
+            .get()
             .await
             .map_err(format_octocrab_error)?;
 
-        Ok(())
+        Ok(RepositorySettings {
+            default_branch: repository
+                .default_branch
+                .unwrap_or_else(|| "main".to_string()),
+            delete_branch_on_merge: repository.delete_branch_on_merge,
+        })
     }
 
-    async fn fetch_workflow_runs(
+    async fn fetch_required_checks(
         &self,
         owner: &str,
         repo: &str,
-        head_sha: &str,
-    ) -> anyhow::Result<Vec<WorkflowRun>> {
-        debug!(
-            "Fetching workflow runs for {}/{} @ {}",
-            owner, repo, head_sha
-        );
-
-        let route = format!(
-            "/repos/{}/{}/actions/runs?head_sha={}",
-            owner, repo, head_sha
-        );
+        branch: &str,
+    ) -> anyhow::Result<Option<Vec<String>>> {
+        debug!("Fetching required checks of {}/{}@{}", owner, repo, branch);
+        self.acquire(RequestCategory::PullRequests).await;
 
         #[derive(serde::Deserialize)]
-        struct WorkflowRunsResponse {
-            workflow_runs: Vec<OctocrabWorkflowRun>,
+        struct RequiredStatusChecks {
+            #[serde(default)]
+            contexts: Vec<String>,
+            #[serde(default)]
+            checks: Vec<RequiredCheck>,
         }
 
         #[derive(serde::Deserialize)]
-        struct OctocrabWorkflowRun {
-            id: u64,
-            name: Option<String>,
-            status: Option<String>,
-            conclusion: Option<String>,
-            head_sha: String,
-            html_url: String,
-            created_at: chrono::DateTime<chrono::Utc>,
-            updated_at: chrono::DateTime<chrono::Utc>,
+        struct RequiredCheck {
+            context: String,
         }
 
-        let response: WorkflowRunsResponse = self.octocrab.get(route, None::<&()>).await?;
-
-        let runs = response
-            .workflow_runs
-            .into_iter()
-            .map(|run| WorkflowRun {
-                id: run.id,
-                name: run.name.unwrap_or_else(|| "Unknown".to_string()),
-                status: convert_workflow_status(run.status.as_deref()),
-                conclusion: run.conclusion.as_deref().map(convert_workflow_conclusion),
-                head_sha: run.head_sha,
-                html_url: run.html_url,
-                created_at: run.created_at,
-                updated_at: run.updated_at,
-            })
-            .collect();
-
-        Ok(runs)
+        let route = format!(
+            "/repos/{}/{}/branches/{}/protection/required_status_checks",
+            owner, repo, branch
+        );
+        match self
+            .octocrab
+            .get::<RequiredStatusChecks, _, _>(route, None::<&()>)
+            .await
+        {
+            Ok(response) => {
+                let mut required = response.contexts;
+                for check in response.checks {
+                    if !required.contains(&check.context) {
+                        required.push(check.context);
+                    }
+                }
+                Ok(Some(required))
+            }
+            // Not protected, or the protection is hidden from this token
+            Err(octocrab::Error::GitHub { source, .. })
+                if matches!(source.status_code.as_u16(), 403 | 404) =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(format_octocrab_error(e)),
+        }
     }
 
-    async fn fetch_ci_status(
-        &self,
-        owner: &str,
-        repo: &str,
-        head_sha: &str,
-    ) -> anyhow::Result<CiStatus> {
-        debug!("Fetching CI status for {}/{} @ {}", owner, repo, head_sha);
-
-        // Use the check-runs API endpoint
-        let route = format!("/repos/{}/{}/commits/{}/check-runs", owner, repo, head_sha);
+    async fn delete_branch(&self, owner: &str, repo: &str, branch: &str) -> anyhow::Result<()> {
+        debug!("Deleting branch {} in {}/{}", branch, owner, repo);
+        self.acquire(RequestCategory::Mutations).await;
 
-        #[derive(serde::Deserialize)]
-        struct CheckRunsResponse {
-            check_runs: Vec<CheckRunItem>,
-        }
+        self.octocrab
+            .repos(owner, repo)
+            .delete_ref(&octocrab::params::repos::Reference::Branch(
+                branch.to_string(),
+            ))
+            .await
+            .map_err(format_octocrab_error)?;
 
-        #[derive(serde::Deserialize)]
-        struct CheckRunItem {
-            status: Option<String>,
-            conclusion: Option<String>,
-        }
+        Ok(())
+    }
 
-        let response: CheckRunsResponse = self.octocrab.get(&route, None::<&()>).await?;
+    async fn fetch_repo_labels(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<Label>> {
+        debug!("Fetching labels for {}/{}", owner, repo);
+        const PER_PAGE: u8 = 100;
 
-        let mut passed = 0;
-        let mut failed = 0;
-        let mut pending = 0;
-
-        for check in &response.check_runs {
-            if let Some(conclusion) = &check.conclusion {
-                match conclusion.as_str() {
-                    "success" | "neutral" | "skipped" => passed += 1,
-                    "failure" | "cancelled" | "timed_out" | "action_required" | "stale"
-                    | "startup_failure" => failed += 1,
-                    _ => pending += 1,
-                }
-            } else if let Some(status) = &check.status {
-                // No conclusion yet - check if in progress or queued
-                if status == "in_progress" || status == "queued" {
-                    pending += 1;
-                }
+        let mut labels = Vec::new();
+        let mut page_num = 1u32;
+        loop {
+            self.acquire(RequestCategory::PullRequests).await;
+            let page = self
+                .octocrab
+                .issues(owner, repo)
+                .list_labels_for_repo()
+                .per_page(PER_PAGE)
+                .page(page_num)
+                .send()
+                .await
+                .map_err(format_octocrab_error)?;
+            let is_last_page = page.items.len() < PER_PAGE as usize;
+
+            labels.extend(page.items.into_iter().map(|label| Label {
+                name: label.name,
+                color: label.color,
+                description: label.description,
+            }));
+
+            if is_last_page {
+                break;
             }
+            page_num += 1;
         }
 
-        let total_checks = response.check_runs.len();
-        let state = if failed > 0 {
-            CiState::Failure
-        } else if pending > 0 {
-            CiState::Pending
-        } else if passed > 0 {
-            CiState::Success
-        } else {
-            CiState::Unknown
-        };
+        labels.sort_by_key(|label| label.name.to_lowercase());
+        debug!("Fetched {} labels for {}/{}", labels.len(), owner, repo);
+        Ok(labels)
+    }
 
+    async fn add_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        labels: &[String],
+    ) -> anyhow::Result<()> {
         debug!(
-            "CI status for {}/{} @ {}: {:?} (passed={}, failed={}, pending={})",
-            owner, repo, head_sha, state, passed, failed, pending
+            "Adding labels {:?} to PR #{} in {}/{}",
+            labels, pr_number, owner, repo
         );
+        self.acquire(RequestCategory::Mutations).await;
 
-        Ok(CiStatus {
-            state,
-            total_checks,
-            passed,
-            failed,
-            pending,
-        })
+        self.octocrab
+            .issues(owner, repo)
+            .add_labels(pr_number, labels)
+            .await
+            .map_err(format_octocrab_error)?;
+
+        Ok(())
     }
 
-    async fn create_review_comment(
+    async fn remove_label(
         &self,
         owner: &str,
         repo: &str,
         pr_number: u64,
-        commit_id: &str,
-        path: &str,
-        line: u32,
-        side: &str,
-        body: &str,
-    ) -> anyhow::Result<u64> {
+        label: &str,
+    ) -> anyhow::Result<()> {
         debug!(
-            "Creating review comment on PR #{} in {}/{} at {}:{}",
-            pr_number, owner, repo, path, line
+            "Removing label {:?} from PR #{} in {}/{}",
+            label, pr_number, owner, repo
         );
+        self.acquire(RequestCategory::Mutations).await;
 
-        let route = format!("/repos/{}/{}/pulls/{}/comments", owner, repo, pr_number);
-
-        let payload = serde_json::json!({
-            "body": body,
-            "commit_id": commit_id,
-            "path": path,
-            "line": line,
-            "side": side,
-        });
-
-        let response: serde_json::Value = self
-            .octocrab
-            .post(route, Some(&payload))
+        self.octocrab
+            .issues(owner, repo)
+            .remove_label(pr_number, label)
             .await
             .map_err(format_octocrab_error)?;
 
-        // Extract comment ID from response
-        let comment_id = response["id"]
-            .as_u64()
-            .ok_or_else(|| anyhow::anyhow!("Missing comment ID in response"))?;
+        Ok(())
+    }
 
-        Ok(comment_id)
+    async fn fetch_collaborators(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<String>> {
+        debug!("Fetching collaborators of {}/{}", owner, repo);
+        const PER_PAGE: usize = 100;
+
+        let mut logins = Vec::new();
+        let mut page_num = 1u32;
+        loop {
+            self.acquire(RequestCategory::PullRequests).await;
+            let route = format!(
+                "/repos/{}/{}/collaborators?per_page={}&page={}",
+                owner, repo, PER_PAGE, page_num
+            );
+            let page: Vec<serde_json::Value> = self
+                .octocrab
+                .get(route, None::<&()>)
+                .await
+                .map_err(format_octocrab_error)?;
+            let is_last_page = page.len() < PER_PAGE;
+
+            logins.extend(
+                page.iter()
+                    .filter_map(|user| user["login"].as_str())
+                    .map(str::to_string),
+            );
+
+            if is_last_page {
+                break;
+            }
+            page_num += 1;
+        }
+
+        logins.sort_by_key(|login| login.to_lowercase());
+        debug!(
+            "Fetched {} collaborators of {}/{}",
+            logins.len(),
+            owner,
+            repo
+        );
+        Ok(logins)
     }
 
-    async fn delete_review_comment(
+    async fn fetch_branches(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<String>> {
+        debug!("Fetching branches of {}/{}", owner, repo);
+        const PER_PAGE: usize = 100;
+        const MAX_PAGES: u32 = 10;
+
+        let mut branches = Vec::new();
+        for page_num in 1..=MAX_PAGES {
+            self.acquire(RequestCategory::PullRequests).await;
+            let route = format!(
+                "/repos/{}/{}/branches?per_page={}&page={}",
+                owner, repo, PER_PAGE, page_num
+            );
+            let page: Vec<serde_json::Value> = self
+                .octocrab
+                .get(route, None::<&()>)
+                .await
+                .map_err(format_octocrab_error)?;
+            let is_last_page = page.len() < PER_PAGE;
+
+            branches.extend(
+                page.iter()
+                    .filter_map(|branch| branch["name"].as_str())
+                    .map(str::to_string),
+            );
+
+            if is_last_page {
+                break;
+            }
+        }
+
+        debug!("Fetched {} branches of {}/{}", branches.len(), owner, repo);
+        Ok(branches)
+    }
+
+    async fn request_reviewers(
         &self,
         owner: &str,
         repo: &str,
-        comment_id: u64,
+        pr_number: u64,
+        reviewers: &[String],
+        team_reviewers: &[String],
     ) -> anyhow::Result<()> {
         debug!(
-            "Deleting review comment {} in {}/{}",
-            comment_id, owner, repo
+            "Requesting reviewers {:?} and teams {:?} on PR #{} in {}/{}",
+            reviewers, team_reviewers, pr_number, owner, repo
         );
+        self.acquire(RequestCategory::Mutations).await;
 
-        // DELETE returns 204 No Content on success - use _delete to get raw response
-        // since the body is empty and can't be parsed as JSON
-        // Note: _delete requires full URL since it bypasses parameterized_uri
-        let url = format!(
-            "{}/repos/{}/{}/pulls/comments/{}",
-            self.api_base_url, owner, repo, comment_id
+        let route = format!(
+            "/repos/{}/{}/pulls/{}/requested_reviewers",
+            owner, repo, pr_number
         );
-
-        let response = self
+        let body = serde_json::json!({
+            "reviewers": reviewers,
+            "team_reviewers": team_reviewers,
+        });
+        let _response: serde_json::Value = self
             .octocrab
-            ._delete(&url, None::<&()>)
+            .post(route, Some(&body))
             .await
             .map_err(format_octocrab_error)?;
 
-        // 204 No Content = successfully deleted
-        // 404 Not Found = already deleted (treat as success)
-        let status = response.status();
-        if status.is_success() || status.as_u16() == 404 {
-            Ok(())
-        } else {
-            Err(anyhow::anyhow!("Failed to delete comment: HTTP {}", status))
-        }
+        Ok(())
     }
 
-    async fn fetch_review_comments(
-        &self,
-        owner: &str,
-        repo: &str,
-        pr_number: u64,
-    ) -> anyhow::Result<Vec<ReviewComment>> {
+    async fn rerun_failed_jobs(&self, owner: &str, repo: &str, run_id: u64) -> anyhow::Result<()> {
         debug!(
-            "Fetching review comments for PR #{} in {}/{}",
-            pr_number, owner, repo
+            "Rerunning failed jobs for workflow run {} in {}/{}",
+            run_id, owner, repo
         );
+        self.acquire(RequestCategory::Mutations).await;
 
-        let route = format!("/repos/{}/{}/pulls/{}/comments", owner, repo, pr_number);
-
-        // Fetch all comments (paginated)
-        let response: Vec<serde_json::Value> = self
+        let route = format!(
+            "/repos/{}/{}/actions/runs/{}/rerun-failed-jobs",
+            owner, repo, run_id
+        );
+        let _response: serde_json::Value = self
             .octocrab
-            .get(route, None::<&()>)
+            .post(route, None::<&()>)
             .await
             .map_err(format_octocrab_error)?;
 
-        let comments = response
+        Ok(())
+    }
+
+    async fn fetch_workflow_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        head_sha: &str,
+    ) -> anyhow::Result<Vec<WorkflowRun>> {
+        debug!(
+            "Fetching workflow runs for {}/{} @ {}",
+            owner, repo, head_sha
+        );
+        self.acquire(RequestCategory::Actions).await;
+
+        let route = format!(
+            "/repos/{}/{}/actions/runs?head_sha={}",
+            owner, repo, head_sha
+        );
+
+        let response: WorkflowRunsResponse = self.octocrab.get(route, None::<&()>).await?;
+        Ok(response.into_runs())
+    }
+
+    async fn fetch_recent_workflow_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        limit: usize,
+    ) -> anyhow::Result<Vec<WorkflowRun>> {
+        debug!(
+            "Fetching recent workflow runs for {}/{} on {}",
+            owner, repo, branch
+        );
+        self.acquire(RequestCategory::Actions).await;
+
+        let route = format!("/repos/{}/{}/actions/runs", owner, repo);
+        let per_page = limit.clamp(1, 100).to_string();
+        let params = [("branch", branch), ("per_page", per_page.as_str())];
+
+        let response: WorkflowRunsResponse = self.octocrab.get(route, Some(&params)).await?;
+        let mut runs = response.into_runs();
+        runs.truncate(limit);
+        Ok(runs)
+    }
+
+    async fn fetch_branch_head_sha(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> anyhow::Result<Option<String>> {
+        debug!("Fetching head of {}/{}@{}", owner, repo, branch);
+        self.acquire(RequestCategory::PullRequests).await;
+
+        #[derive(serde::Deserialize)]
+        struct BranchResponse {
+            commit: BranchCommit,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct BranchCommit {
+            sha: String,
+        }
+
+        let route = format!("/repos/{}/{}/branches/{}", owner, repo, branch);
+        match self
+            .octocrab
+            .get::<BranchResponse, _, _>(route, None::<&()>)
+            .await
+        {
+            Ok(response) => Ok(Some(response.commit.sha)),
+            Err(octocrab::Error::GitHub { source, .. }) if source.status_code.as_u16() == 404 => {
+                Ok(None)
+            }
+            Err(e) => Err(format_octocrab_error(e)),
+        }
+    }
+
+    async fn fetch_compare(
+        &self,
+        owner: &str,
+        repo: &str,
+        base: &str,
+        head: &str,
+    ) -> anyhow::Result<Comparison> {
+        debug!("Comparing {}...{} in {}/{}", base, head, owner, repo);
+        self.acquire(RequestCategory::PullRequests).await;
+
+        #[derive(serde::Deserialize)]
+        struct CompareResponse {
+            merge_base_commit: CompareCommit,
+            ahead_by: u64,
+            behind_by: u64,
+            #[serde(default)]
+            files: Vec<ChangedFile>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct CompareCommit {
+            sha: String,
+        }
+
+        let route = format!("/repos/{}/{}/compare/{}...{}", owner, repo, base, head);
+        let response: CompareResponse = self
+            .octocrab
+            .get(route, None::<&()>)
+            .await
+            .map_err(format_octocrab_error)?;
+
+        Ok(Comparison {
+            merge_base_sha: response.merge_base_commit.sha,
+            ahead_by: response.ahead_by,
+            behind_by: response.behind_by,
+            files: response.files,
+        })
+    }
+
+    async fn fetch_latest_tag(&self, owner: &str, repo: &str) -> anyhow::Result<Option<String>> {
+        debug!("Fetching latest tag of {}/{}", owner, repo);
+        self.acquire(RequestCategory::PullRequests).await;
+
+        #[derive(serde::Deserialize)]
+        struct ReleaseResponse {
+            tag_name: String,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct TagResponse {
+            name: String,
+        }
+
+        let route = format!("/repos/{}/{}/releases/latest", owner, repo);
+        match self
+            .octocrab
+            .get::<ReleaseResponse, _, _>(route, None::<&()>)
+            .await
+        {
+            Ok(release) => return Ok(Some(release.tag_name)),
+            // No releases, maybe tags
+            Err(octocrab::Error::GitHub { source, .. }) if source.status_code.as_u16() == 404 => {}
+            Err(e) => return Err(format_octocrab_error(e)),
+        }
+
+        self.acquire(RequestCategory::PullRequests).await;
+        let route = format!("/repos/{}/{}/tags", owner, repo);
+        let tags: Vec<TagResponse> = self
+            .octocrab
+            .get(route, Some(&[("per_page", "1")]))
+            .await
+            .map_err(format_octocrab_error)?;
+        Ok(tags.into_iter().next().map(|tag| tag.name))
+    }
+
+    async fn fetch_ref_date(
+        &self,
+        owner: &str,
+        repo: &str,
+        git_ref: &str,
+    ) -> anyhow::Result<Option<DateTime<Utc>>> {
+        debug!("Fetching date of {}/{}@{}", owner, repo, git_ref);
+        self.acquire(RequestCategory::PullRequests).await;
+
+        #[derive(serde::Deserialize)]
+        struct CommitResponse {
+            commit: CommitDetails,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct CommitDetails {
+            committer: CommitSignature,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct CommitSignature {
+            date: DateTime<Utc>,
+        }
+
+        let route = format!("/repos/{}/{}/commits/{}", owner, repo, git_ref);
+        match self
+            .octocrab
+            .get::<CommitResponse, _, _>(route, None::<&()>)
+            .await
+        {
+            Ok(response) => Ok(Some(response.commit.committer.date)),
+            // Unknown refs are 422 rather than 404 on this route
+            Err(octocrab::Error::GitHub { source, .. })
+                if matches!(source.status_code.as_u16(), 404 | 422) =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(format_octocrab_error(e)),
+        }
+    }
+
+    async fn fetch_pull_request_diff(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<String> {
+        debug!("Fetching diff of PR #{} in {}/{}", pr_number, owner, repo);
+        self.acquire(RequestCategory::PullRequests).await;
+
+        self.octocrab
+            .pulls(owner, repo)
+            .get_diff(pr_number)
+            .await
+            .map_err(format_octocrab_error)
+    }
+
+    async fn fetch_ci_status(
+        &self,
+        owner: &str,
+        repo: &str,
+        head_sha: &str,
+    ) -> anyhow::Result<CiStatus> {
+        debug!("Fetching CI status for {}/{} @ {}", owner, repo, head_sha);
+        self.acquire(RequestCategory::Checks).await;
+
+        // Use the check-runs API endpoint
+        let route = format!("/repos/{}/{}/commits/{}/check-runs", owner, repo, head_sha);
+
+        #[derive(serde::Deserialize)]
+        struct CheckRunsResponse {
+            check_runs: Vec<CheckRunItem>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct CheckRunItem {
+            #[serde(default)]
+            id: u64,
+            #[serde(default)]
+            name: String,
+            status: Option<String>,
+            conclusion: Option<String>,
+            started_at: Option<chrono::DateTime<chrono::Utc>>,
+            completed_at: Option<chrono::DateTime<chrono::Utc>>,
+        }
+
+        let response: CheckRunsResponse = self.octocrab.get(&route, None::<&()>).await?;
+
+        let status = CiStatus::from_results(
+            response
+                .check_runs
+                .into_iter()
+                .map(|check| CheckResult {
+                    id: check.id,
+                    status: convert_check_run_status(check.status.as_deref()),
+                    conclusion: check.conclusion.as_deref().map(convert_conclusion_string),
+                    name: check.name,
+                    started_at: check.started_at,
+                    completed_at: check.completed_at,
+                    required: true,
+                })
+                .collect(),
+        );
+
+        debug!(
+            "CI status for {}/{} @ {}: {:?} (passed={}, failed={}, pending={}, queued={}, waiting={})",
+            owner,
+            repo,
+            head_sha,
+            status.state,
+            status.passed,
+            status.failed,
+            status.pending,
+            status.queued,
+            status.waiting
+        );
+
+        Ok(status)
+    }
+
+    async fn create_review_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        commit_id: &str,
+        path: &str,
+        lines: &CommentLines,
+        body: &str,
+    ) -> anyhow::Result<u64> {
+        debug!(
+            "Creating review comment on PR #{} in {}/{} at {}:{}",
+            pr_number, owner, repo, path, lines.line
+        );
+        self.acquire(RequestCategory::Mutations).await;
+
+        let route = format!("/repos/{}/{}/pulls/{}/comments", owner, repo, pr_number);
+
+        let payload = review_comment_payload(commit_id, path, lines, body);
+
+        let response: serde_json::Value = self
+            .octocrab
+            .post(route, Some(&payload))
+            .await
+            .map_err(format_octocrab_error)?;
+
+        // Extract comment ID from response
+        let comment_id = response["id"]
+            .as_u64()
+            .ok_or_else(|| anyhow::anyhow!("Missing comment ID in response"))?;
+
+        Ok(comment_id)
+    }
+
+    async fn delete_review_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        comment_id: u64,
+    ) -> anyhow::Result<()> {
+        debug!(
+            "Deleting review comment {} in {}/{}",
+            comment_id, owner, repo
+        );
+        self.acquire(RequestCategory::Mutations).await;
+
+        // DELETE returns 204 No Content on success - use _delete to get raw response
+        // since the body is empty and can't be parsed as JSON
+        // Note: _delete requires full URL since it bypasses parameterized_uri
+        let url = format!(
+            "{}/repos/{}/{}/pulls/comments/{}",
+            self.api_base_url, owner, repo, comment_id
+        );
+
+        let response = self
+            .octocrab
+            ._delete(&url, None::<&()>)
+            .await
+            .map_err(format_octocrab_error)?;
+
+        // 204 No Content = successfully deleted
+        // 404 Not Found = already deleted (treat as success)
+        let status = response.status();
+        if status.is_success() || status.as_u16() == 404 {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("Failed to delete comment: HTTP {}", status))
+        }
+    }
+
+    async fn create_file_review_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        commit_id: &str,
+        path: &str,
+        body: &str,
+    ) -> anyhow::Result<u64> {
+        debug!(
+            "Creating file comment on PR #{} in {}/{} at {}",
+            pr_number, owner, repo, path
+        );
+        self.acquire(RequestCategory::Mutations).await;
+
+        let route = format!("/repos/{}/{}/pulls/{}/comments", owner, repo, pr_number);
+
+        let payload = serde_json::json!({
+            "body": body,
+            "commit_id": commit_id,
+            "path": path,
+            "subject_type": "file",
+        });
+
+        let response: serde_json::Value = self
+            .octocrab
+            .post(route, Some(&payload))
+            .await
+            .map_err(format_octocrab_error)?;
+
+        response["id"]
+            .as_u64()
+            .ok_or_else(|| anyhow::anyhow!("Missing comment ID in response"))
+    }
+
+    async fn create_issue_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        body: &str,
+    ) -> anyhow::Result<u64> {
+        debug!(
+            "Creating conversation comment on PR #{} in {}/{}",
+            pr_number, owner, repo
+        );
+        self.acquire(RequestCategory::Mutations).await;
+
+        let comment = self
+            .octocrab
+            .issues(owner, repo)
+            .create_comment(pr_number, body)
+            .await
+            .map_err(format_octocrab_error)?;
+
+        Ok(comment.id.into_inner())
+    }
+
+    async fn delete_issue_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        comment_id: u64,
+    ) -> anyhow::Result<()> {
+        debug!(
+            "Deleting conversation comment {} in {}/{}",
+            comment_id, owner, repo
+        );
+        self.acquire(RequestCategory::Mutations).await;
+
+        // Same as review comments: 204 on success, full URL for _delete
+        let url = format!(
+            "{}/repos/{}/{}/issues/comments/{}",
+            self.api_base_url, owner, repo, comment_id
+        );
+
+        let response = self
+            .octocrab
+            ._delete(&url, None::<&()>)
+            .await
+            .map_err(format_octocrab_error)?;
+
+        let status = response.status();
+        if status.is_success() || status.as_u16() == 404 {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("Failed to delete comment: HTTP {}", status))
+        }
+    }
+
+    async fn fetch_review_comments(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<Vec<ReviewComment>> {
+        debug!(
+            "Fetching review comments for PR #{} in {}/{}",
+            pr_number, owner, repo
+        );
+        self.acquire(RequestCategory::Comments).await;
+
+        let route = format!("/repos/{}/{}/pulls/{}/comments", owner, repo, pr_number);
+
+        // Fetch all comments (paginated)
+        let response: Vec<serde_json::Value> = self
+            .octocrab
+            .get(route, None::<&()>)
+            .await
+            .map_err(format_octocrab_error)?;
+
+        let comments = response
             .into_iter()
             .filter_map(|c| {
                 let id = c["id"].as_u64()?;
@@ -596,6 +1300,9 @@ impl GitHubClient for OctocrabClient {
                 let line = c["line"].as_u64().map(|l| l as u32);
                 let original_line = c["original_line"].as_u64().map(|l| l as u32);
                 let side = c["side"].as_str().map(|s| s.to_string());
+                let start_line = c["start_line"].as_u64().map(|l| l as u32);
+                let start_side = c["start_side"].as_str().map(|s| s.to_string());
+                let is_file_level = c["subject_type"].as_str() == Some("file");
 
                 Some(ReviewComment {
                     id,
@@ -603,6 +1310,9 @@ impl GitHubClient for OctocrabClient {
                     line,
                     original_line,
                     side,
+                    start_line,
+                    start_side,
+                    is_file_level,
                     body,
                     author,
                     created_at,
@@ -611,91 +1321,595 @@ impl GitHubClient for OctocrabClient {
             })
             .collect();
 
-        Ok(comments)
+        Ok(comments)
+    }
+
+    async fn fetch_review_decision(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<ReviewDecision> {
+        debug!(
+            "Fetching review decision for PR #{} in {}/{}",
+            pr_number, owner, repo
+        );
+        self.acquire(RequestCategory::Reviews).await;
+
+        let route = format!("/repos/{}/{}/pulls/{}/reviews", owner, repo, pr_number);
+
+        #[derive(serde::Deserialize)]
+        struct ReviewItem {
+            state: Option<String>,
+        }
+
+        let reviews: Vec<ReviewItem> = self
+            .octocrab
+            .get(route, None::<&()>)
+            .await
+            .map_err(format_octocrab_error)?;
+
+        // Determine overall review state:
+        // - Any "CHANGES_REQUESTED" → ChangesRequested
+        // - Any "APPROVED" (and no CHANGES_REQUESTED) → Approved
+        // - Otherwise → Pending
+        let mut has_approval = false;
+        let mut has_changes_requested = false;
+
+        for review in &reviews {
+            if let Some(state) = &review.state {
+                match state.to_uppercase().as_str() {
+                    "APPROVED" => has_approval = true,
+                    "CHANGES_REQUESTED" => has_changes_requested = true,
+                    _ => {}
+                }
+            }
+        }
+
+        let decision = if has_changes_requested {
+            ReviewDecision::ChangesRequested
+        } else if has_approval {
+            ReviewDecision::Approved
+        } else {
+            ReviewDecision::Pending
+        };
+
+        debug!(
+            "Review decision for PR #{} in {}/{}: {:?}",
+            pr_number, owner, repo, decision
+        );
+
+        Ok(decision)
+    }
+
+    async fn fetch_comment_counts(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<CommentCounts> {
+        debug!(
+            "Fetching comment counts for PR #{} in {}/{}",
+            pr_number, owner, repo
+        );
+        // GitHub answers GraphQL queries only with a token
+        if self.anonymous {
+            anyhow::bail!("Comment counts need a token");
+        }
+        self.require(Feature::ReviewThreads).await?;
+        self.acquire(RequestCategory::Comments).await;
+
+        // Review comments are counted through their threads, the first 100
+        // threads cover all but the longest reviews
+        let query = r#"
+            query($owner: String!, $repo: String!, $number: Int!) {
+              repository(owner: $owner, name: $repo) {
+                pullRequest(number: $number) {
+                  comments { totalCount }
+                  reviewThreads(first: 100) {
+                    nodes { isResolved comments { totalCount } }
+                  }
+                }
+              }
+            }"#;
+        let response: serde_json::Value = self
+            .graphql
+            .graphql(&serde_json::json!({
+                "query": query,
+                "variables": { "owner": owner, "repo": repo, "number": pr_number },
+            }))
+            .await
+            .map_err(format_octocrab_error)?;
+
+        parse_comment_counts(&response)
+    }
+
+    async fn fetch_reviewers(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_numbers: &[u64],
+    ) -> anyhow::Result<Vec<PrReviewers>> {
+        debug!(
+            "Fetching reviewers of {} PRs in {}/{}",
+            pr_numbers.len(),
+            owner,
+            repo
+        );
+        // GitHub answers GraphQL queries only with a token
+        if self.anonymous {
+            anyhow::bail!("Reviewers need a token");
+        }
+
+        let mut reviewers = Vec::with_capacity(pr_numbers.len());
+        for batch in pr_numbers.chunks(REVIEWERS_BATCH_SIZE) {
+            self.acquire(RequestCategory::Reviews).await;
+            let response: serde_json::Value = self
+                .graphql
+                .graphql(&serde_json::json!({
+                    "query": reviewers_query(batch),
+                    "variables": { "owner": owner, "repo": repo },
+                }))
+                .await
+                .map_err(format_octocrab_error)?;
+            reviewers.extend(parse_reviewers(&response)?);
+        }
+        Ok(reviewers)
+    }
+
+    async fn fetch_blame(
+        &self,
+        owner: &str,
+        repo: &str,
+        commit_sha: &str,
+        path: &str,
+    ) -> anyhow::Result<Vec<BlameRange>> {
+        debug!(
+            "Fetching blame of {} at {} in {}/{}",
+            path, commit_sha, owner, repo
+        );
+        // GitHub answers GraphQL queries only with a token
+        if self.anonymous {
+            anyhow::bail!("Blame needs a token");
+        }
+        self.acquire(RequestCategory::Reviews).await;
+
+        let query = r#"
+            query($owner: String!, $repo: String!, $oid: GitObjectID!, $path: String!) {
+              repository(owner: $owner, name: $repo) {
+                object(oid: $oid) {
+                  ... on Commit {
+                    blame(path: $path) {
+                      ranges {
+                        startingLine
+                        endingLine
+                        commit {
+                          oid
+                          committedDate
+                          author { name user { login } }
+                        }
+                      }
+                    }
+                  }
+                }
+              }
+            }"#;
+        let response: serde_json::Value = self
+            .graphql
+            .graphql(&serde_json::json!({
+                "query": query,
+                "variables": { "owner": owner, "repo": repo, "oid": commit_sha, "path": path },
+            }))
+            .await
+            .map_err(format_octocrab_error)?;
+
+        parse_blame(&response)
     }
 
-    async fn fetch_review_decision(
+    async fn fetch_file_content(
         &self,
         owner: &str,
         repo: &str,
-        pr_number: u64,
-    ) -> anyhow::Result<ReviewDecision> {
+        path: &str,
+        git_ref: Option<&str>,
+    ) -> anyhow::Result<String> {
         debug!(
-            "Fetching review decision for PR #{} in {}/{}",
-            pr_number, owner, repo
+            "Fetching {} at {} in {}/{}",
+            path,
+            git_ref.unwrap_or("default branch"),
+            owner,
+            repo
         );
+        self.acquire(RequestCategory::PullRequests).await;
 
-        let route = format!("/repos/{}/{}/pulls/{}/reviews", owner, repo, pr_number);
-
-        #[derive(serde::Deserialize)]
-        struct ReviewItem {
-            state: Option<String>,
-        }
-
-        let reviews: Vec<ReviewItem> = self
+        let route = format!("/repos/{}/{}/contents/{}", owner, repo, path);
+        let params = git_ref.map(|git_ref| [("ref", git_ref)]);
+        let response: serde_json::Value = self
             .octocrab
-            .get(route, None::<&()>)
+            .get(route, params.as_ref())
             .await
             .map_err(format_octocrab_error)?;
 
-        // Determine overall review state:
-        // - Any "CHANGES_REQUESTED" → ChangesRequested
-        // - Any "APPROVED" (and no CHANGES_REQUESTED) → Approved
-        // - Otherwise → Pending
-        let mut has_approval = false;
-        let mut has_changes_requested = false;
+        parse_file_content(&response)
+            .with_context(|| format!("Failed to read {} in {}/{}", path, owner, repo))
+    }
 
-        for review in &reviews {
-            if let Some(state) = &review.state {
-                match state.to_uppercase().as_str() {
-                    "APPROVED" => has_approval = true,
-                    "CHANGES_REQUESTED" => has_changes_requested = true,
-                    _ => {}
+    async fn capabilities(&self) -> HostCapabilities {
+        // GitHub answers GraphQL queries only with a token
+        if self.anonymous {
+            return HostCapabilities::default();
+        }
+        *self
+            .capabilities
+            .get_or_init(|| async {
+                match self.probe_capabilities().await {
+                    Ok(capabilities) => {
+                        debug!("GraphQL capabilities of {}: {:?}", self.host(), capabilities);
+                        capabilities
+                    }
+                    Err(e) => {
+                        log::warn!(
+                            "Failed to probe the GraphQL capabilities of {}, assuming all are supported: {}",
+                            self.host(),
+                            e
+                        );
+                        HostCapabilities::default()
+                    }
                 }
-            }
+            })
+            .await
+    }
+}
+
+/// Decode the content of a contents API file response
+/// Body of a new review comment, anchored by line and side
+///
+/// `start_line` and `start_side` are only sent for multi-line comments,
+/// GitHub rejects a range whose start is not before its end.
+fn review_comment_payload(
+    commit_id: &str,
+    path: &str,
+    lines: &CommentLines,
+    body: &str,
+) -> serde_json::Value {
+    let mut payload = serde_json::json!({
+        "body": body,
+        "commit_id": commit_id,
+        "path": path,
+        "line": lines.line,
+        "side": lines.side,
+    });
+    if let (Some(start_line), Some(start_side)) = (lines.start_line, &lines.start_side) {
+        payload["start_line"] = start_line.into();
+        payload["start_side"] = start_side.as_str().into();
+    }
+    payload
+}
+
+fn parse_file_content(response: &serde_json::Value) -> anyhow::Result<String> {
+    use base64::engine::general_purpose::STANDARD as BASE64;
+    use base64::Engine;
+
+    if response["type"].as_str() != Some("file") {
+        anyhow::bail!("Not a file");
+    }
+    let content = response["content"].as_str().unwrap_or_default();
+    let bytes = match response["encoding"].as_str() {
+        // GitHub wraps the base64 content every 60 characters
+        Some("base64") => {
+            let content: String = content.split_whitespace().collect();
+            BASE64.decode(content).context("Invalid base64 content")?
+        }
+        Some("none") | None if content.is_empty() => {
+            anyhow::bail!("File too large for the contents API")
         }
+        _ => content.as_bytes().to_vec(),
+    };
+    String::from_utf8(bytes).context("File is not UTF-8 text")
+}
 
-        let decision = if has_changes_requested {
-            ReviewDecision::ChangesRequested
-        } else if has_approval {
-            ReviewDecision::Approved
-        } else {
-            ReviewDecision::Pending
-        };
+/// Extract the blame ranges from the GraphQL response
+fn parse_blame(response: &serde_json::Value) -> anyhow::Result<Vec<BlameRange>> {
+    if let Some(message) = response["errors"][0]["message"].as_str() {
+        anyhow::bail!("GraphQL error: {}", message);
+    }
+    let Some(ranges) = response["data"]["repository"]["object"]["blame"]["ranges"].as_array()
+    else {
+        anyhow::bail!("Commit not found");
+    };
+
+    ranges
+        .iter()
+        .map(|range| {
+            let commit = &range["commit"];
+            let author = &commit["author"];
+            Ok(BlameRange {
+                start_line: range["startingLine"].as_u64().unwrap_or(0) as u32,
+                end_line: range["endingLine"].as_u64().unwrap_or(0) as u32,
+                commit_sha: commit["oid"].as_str().unwrap_or_default().to_string(),
+                author: author["user"]["login"]
+                    .as_str()
+                    .or(author["name"].as_str())
+                    .unwrap_or("unknown")
+                    .to_string(),
+                committed_at: serde_json::from_value(commit["committedDate"].clone())?,
+            })
+        })
+        .collect()
+}
 
-        debug!(
-            "Review decision for PR #{} in {}/{}: {:?}",
-            pr_number, owner, repo, decision
-        );
+/// GraphQL query of the reviewers of `pr_numbers`, one aliased field per PR
+fn reviewers_query(pr_numbers: &[u64]) -> String {
+    let fields: String = pr_numbers
+        .iter()
+        .map(|number| {
+            format!(
+                "pr{0}: pullRequest(number: {0}) {{ ...reviewers }}\n",
+                number
+            )
+        })
+        .collect();
+    format!(
+        r#"
+        query($owner: String!, $repo: String!) {{
+          repository(owner: $owner, name: $repo) {{
+            {}
+          }}
+        }}
+        fragment reviewers on PullRequest {{
+          number
+          reviewDecision
+          reviewRequests(first: 20) {{
+            nodes {{ requestedReviewer {{ ... on User {{ login }} }} }}
+          }}
+          latestReviews(first: 20) {{
+            nodes {{ author {{ login }} state submittedAt }}
+          }}
+        }}"#,
+        fields
+    )
+}
 
-        Ok(decision)
+/// Map a GraphQL review decision or review state
+fn review_decision(state: &serde_json::Value) -> ReviewDecision {
+    match state.as_str() {
+        Some("APPROVED") => ReviewDecision::Approved,
+        Some("CHANGES_REQUESTED") => ReviewDecision::ChangesRequested,
+        Some(_) => ReviewDecision::Pending,
+        None => ReviewDecision::Unknown,
+    }
+}
+
+/// Extract the reviewers of each PR from the GraphQL response
+fn parse_reviewers(response: &serde_json::Value) -> anyhow::Result<Vec<PrReviewers>> {
+    let Some(prs) = response["data"]["repository"].as_object() else {
+        match response["errors"][0]["message"].as_str() {
+            Some(message) => anyhow::bail!("GraphQL error: {}", message),
+            None => anyhow::bail!("Repository not found"),
+        }
+    };
+
+    // A deleted PR fails its field only, the others are still answered
+    prs.values()
+        .filter(|pr| !pr.is_null())
+        .map(|pr| {
+            let requested = pr["reviewRequests"]["nodes"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|request| request["requestedReviewer"]["login"].as_str())
+                .map(String::from)
+                .collect();
+            let latest_reviews = pr["latestReviews"]["nodes"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|review| !review["submittedAt"].is_null())
+                .map(|review| {
+                    Ok(LatestReview {
+                        author: review["author"]["login"]
+                            .as_str()
+                            .unwrap_or("ghost")
+                            .to_string(),
+                        state: match review_decision(&review["state"]) {
+                            ReviewDecision::Unknown => ReviewDecision::Pending,
+                            state => state,
+                        },
+                        submitted_at: serde_json::from_value(review["submittedAt"].clone())?,
+                    })
+                })
+                .collect::<anyhow::Result<_>>()?;
+            Ok(PrReviewers {
+                number: pr["number"].as_u64().unwrap_or_default(),
+                // REVIEW_REQUIRED maps to Pending, null (no review rules) to Unknown
+                review_decision: review_decision(&pr["reviewDecision"]),
+                requested,
+                latest_reviews,
+            })
+        })
+        .collect()
+}
+
+/// Extract the comment breakdown from the GraphQL response
+fn parse_comment_counts(response: &serde_json::Value) -> anyhow::Result<CommentCounts> {
+    if let Some(message) = response["errors"][0]["message"].as_str() {
+        anyhow::bail!("GraphQL error: {}", message);
+    }
+    let pr = &response["data"]["repository"]["pullRequest"];
+    if pr.is_null() {
+        anyhow::bail!("Pull request not found");
+    }
+
+    let mut counts = CommentCounts {
+        issue_comments: pr["comments"]["totalCount"].as_u64().unwrap_or(0) as u32,
+        ..Default::default()
+    };
+    for thread in pr["reviewThreads"]["nodes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+    {
+        counts.review_comments += thread["comments"]["totalCount"].as_u64().unwrap_or(0) as u32;
+        if thread["isResolved"] == false {
+            counts.unresolved_threads += 1;
+        }
+    }
+    Ok(counts)
+}
+
+/// Response of the workflow runs endpoints
+#[derive(serde::Deserialize)]
+struct WorkflowRunsResponse {
+    workflow_runs: Vec<WorkflowRunResponse>,
+}
+
+#[derive(serde::Deserialize)]
+struct WorkflowRunResponse {
+    id: u64,
+    name: Option<String>,
+    status: Option<String>,
+    conclusion: Option<String>,
+    head_sha: String,
+    head_branch: Option<String>,
+    #[serde(default)]
+    event: String,
+    html_url: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+    run_attempt: Option<u64>,
+}
+
+impl WorkflowRunsResponse {
+    fn into_runs(self) -> Vec<WorkflowRun> {
+        self.workflow_runs
+            .into_iter()
+            .map(|run| WorkflowRun {
+                id: run.id,
+                name: run.name.unwrap_or_else(|| "Unknown".to_string()),
+                status: convert_workflow_status(run.status.as_deref()),
+                conclusion: run.conclusion.as_deref().map(convert_workflow_conclusion),
+                head_sha: run.head_sha,
+                head_branch: run.head_branch,
+                event: run.event,
+                html_url: run.html_url,
+                created_at: run.created_at,
+                updated_at: run.updated_at,
+                run_attempt: run.run_attempt.unwrap_or(1),
+            })
+            .collect()
     }
 }
 
 /// Convert workflow run status string to enum
+///
+/// Unknown statuses count as pending, so runs in states GitHub adds later
+/// still show up as not finished.
 fn convert_workflow_status(status: Option<&str>) -> WorkflowRunStatus {
     match status {
-        Some("queued") => WorkflowRunStatus::Queued,
+        Some("queued" | "requested") => WorkflowRunStatus::Queued,
         Some("waiting") => WorkflowRunStatus::Waiting,
         Some("in_progress") => WorkflowRunStatus::InProgress,
         Some("completed") => WorkflowRunStatus::Completed,
         Some("pending") => WorkflowRunStatus::Pending,
-        _ => WorkflowRunStatus::Pending,
+        Some(other) => {
+            debug!("Unknown workflow run status '{}', taken as pending", other);
+            WorkflowRunStatus::Pending
+        }
+        None => {
+            debug!("Workflow run without status, taken as pending");
+            WorkflowRunStatus::Pending
+        }
+    }
+}
+
+/// Parse a pull request from the REST API
+///
+/// octocrab's model fails on mergeable states it does not know, like the
+/// "behind_policy" of GitHub Enterprise. The state is taken out before and
+/// mapped on its own, an unknown one is kept raw for the UI.
+/// Most PRs [`GitHubClient::fetch_pull_requests`] returns
+const MAX_PRS: usize = 50;
+/// PRs per page of [`GitHubClient::fetch_pull_requests`]
+const PR_PAGE_SIZE: u8 = 30;
+/// Most pages [`GitHubClient::fetch_pull_requests`] fetches
+///
+/// Merged PRs are picked from all closed ones: a repository closing many PRs
+/// without merging them would otherwise be paged through to its first PR.
+const MAX_PR_PAGES: u32 = 5;
+
+/// Collect the PRs matching `state` from the pages `fetch_page` returns
+///
+/// Stops at [`MAX_PRS`] PRs, an empty page or after [`MAX_PR_PAGES`] pages.
+async fn collect_pull_requests<F, Fut>(
+    state: PrStateFilter,
+    mut fetch_page: F,
+) -> anyhow::Result<Vec<PullRequest>>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: std::future::Future<Output = anyhow::Result<Vec<serde_json::Value>>>,
+{
+    let mut prs = Vec::new();
+    for page_num in 1..=MAX_PR_PAGES {
+        let page = fetch_page(page_num).await?;
+        let page_is_empty = page.is_empty();
+
+        for pr in page {
+            if prs.len() >= MAX_PRS {
+                break;
+            }
+            let pr = parse_pull_request(pr)?;
+            if state.matches(pr.state) {
+                prs.push(pr);
+            }
+        }
+
+        if prs.len() >= MAX_PRS || page_is_empty {
+            break;
+        }
+    }
+    Ok(prs)
+}
+
+fn parse_pull_request(mut json: serde_json::Value) -> anyhow::Result<PullRequest> {
+    let raw_state = json
+        .get_mut("mergeable_state")
+        .map(serde_json::Value::take)
+        .unwrap_or_default();
+    let pr: octocrab::models::pulls::PullRequest =
+        serde_json::from_value(json).context("Failed to parse pull request")?;
+    let mut pr = PullRequest::from(&pr);
+
+    match serde_json::from_value::<Option<octocrab::models::pulls::MergeableState>>(
+        raw_state.clone(),
+    ) {
+        Ok(state) => pr.mergeable_state = state.as_ref().map(convert_mergeable_state),
+        Err(_) => {
+            let raw = raw_state
+                .as_str()
+                .map_or_else(|| raw_state.to_string(), str::to_string);
+            debug!("PR #{} has unknown mergeable state '{}'", pr.number, raw);
+            pr.mergeable_state = Some(MergeableState::Unknown);
+            pr.mergeable_state_raw = Some(raw);
+        }
     }
+    Ok(pr)
 }
 
 /// Convert workflow run conclusion string to enum
 fn convert_workflow_conclusion(conclusion: &str) -> WorkflowRunConclusion {
     match conclusion {
         "success" => WorkflowRunConclusion::Success,
-        "failure" => WorkflowRunConclusion::Failure,
+        "failure" | "startup_failure" => WorkflowRunConclusion::Failure,
         "neutral" => WorkflowRunConclusion::Neutral,
         "cancelled" => WorkflowRunConclusion::Cancelled,
         "skipped" => WorkflowRunConclusion::Skipped,
         "timed_out" => WorkflowRunConclusion::TimedOut,
         "action_required" => WorkflowRunConclusion::ActionRequired,
         "stale" => WorkflowRunConclusion::Stale,
-        _ => WorkflowRunConclusion::Neutral,
+        other => {
+            debug!("Unknown workflow run conclusion '{}'", other);
+            WorkflowRunConclusion::Unknown
+        }
     }
 }
 
@@ -703,34 +1917,139 @@ impl From<&octocrab::models::pulls::PullRequest> for PullRequest {
     fn from(pr: &octocrab::models::pulls::PullRequest) -> Self {
         Self {
             number: pr.number,
-            title: pr.title.clone().unwrap_or_default(),
+            title: pr.title.clone().unwrap_or_else(|| {
+                debug!("PR #{} has no title", pr.number);
+                "(no title)".to_string()
+            }),
             body: pr.body.clone(),
             author: pr
                 .user
                 .as_ref()
                 .map(|u| u.login.clone())
-                .unwrap_or_else(|| "unknown".to_string()),
+                .unwrap_or_else(|| {
+                    debug!("PR #{} has no author", pr.number);
+                    "unknown".to_string()
+                }),
             comments: pr.comments.unwrap_or(0),
             head_sha: pr.head.sha.clone(),
             base_branch: pr.base.ref_field.clone(),
             head_branch: pr.head.ref_field.clone(),
             mergeable: pr.mergeable,
             mergeable_state: pr.mergeable_state.as_ref().map(convert_mergeable_state),
-            created_at: pr.created_at.unwrap_or_else(chrono::Utc::now),
-            updated_at: pr.updated_at.unwrap_or_else(chrono::Utc::now),
+            mergeable_state_raw: None,
+            created_at: pr.created_at.unwrap_or_else(|| {
+                debug!("PR #{} has no creation date", pr.number);
+                chrono::Utc::now()
+            }),
+            updated_at: pr.updated_at.or(pr.created_at).unwrap_or_else(|| {
+                debug!("PR #{} has no update date", pr.number);
+                chrono::Utc::now()
+            }),
             html_url: pr
                 .html_url
                 .as_ref()
                 .map(|u| u.to_string())
-                .unwrap_or_default(),
+                .unwrap_or_else(|| {
+                    debug!("PR #{} has no URL", pr.number);
+                    String::new()
+                }),
             additions: pr.additions.unwrap_or(0),
             deletions: pr.deletions.unwrap_or(0),
+            changed_files: pr.changed_files,
             maturity: if pr.draft.unwrap_or(false) {
                 MaturityState::Draft
             } else {
                 MaturityState::Ready
             },
             review_decision: ReviewDecision::Unknown, // Fetched separately
+            state: convert_pr_state(pr.number, pr.state.as_ref(), pr.merged_at.is_some()),
+            labels: pr
+                .labels
+                .iter()
+                .flatten()
+                .map(|label| label.name.clone())
+                .collect(),
+            head_repo_owner: pr
+                .head
+                .repo
+                .as_ref()
+                .and_then(|repo| repo.owner.as_ref())
+                .map(|owner| owner.login.clone()),
+            head_repo_name: pr.head.repo.as_ref().map(|repo| repo.name.clone()),
+            // A deleted head repository also counts as a fork
+            is_from_fork: match (&pr.head.repo, &pr.base.repo) {
+                (Some(head), Some(base)) => head.full_name != base.full_name,
+                (None, _) => true,
+                (Some(_), None) => false,
+            },
+            maintainer_can_modify: pr.maintainer_can_modify,
+        }
+    }
+}
+
+/// Convert a search result (issue-shaped) into a partial pull request
+///
+/// Branches, head SHA, mergeability and line stats are not part of search
+/// results; fetch the PR to fill them in. Merged PRs show up as closed.
+impl From<&octocrab::models::issues::Issue> for PullRequest {
+    fn from(issue: &octocrab::models::issues::Issue) -> Self {
+        Self {
+            number: issue.number,
+            title: issue.title.clone(),
+            body: issue.body.clone(),
+            author: issue.user.login.clone(),
+            comments: issue.comments as u64,
+            head_sha: String::new(),
+            base_branch: String::new(),
+            head_branch: String::new(),
+            mergeable: None,
+            mergeable_state: None,
+            mergeable_state_raw: None,
+            created_at: issue.created_at,
+            updated_at: issue.updated_at,
+            html_url: issue.html_url.to_string(),
+            additions: 0,
+            deletions: 0,
+            changed_files: None,
+            maturity: MaturityState::Ready,
+            review_decision: ReviewDecision::Unknown,
+            state: convert_pr_state(issue.number, Some(&issue.state), false),
+            labels: issue
+                .labels
+                .iter()
+                .map(|label| label.name.clone())
+                .collect(),
+            head_repo_owner: None,
+            head_repo_name: None,
+            is_from_fork: false,
+            maintainer_can_modify: false,
+        }
+    }
+}
+
+/// Convert the state of a PR, a merge date wins over it
+///
+/// A missing or unknown state counts as open.
+fn convert_pr_state(
+    number: u64,
+    state: Option<&octocrab::models::IssueState>,
+    merged: bool,
+) -> PrState {
+    use octocrab::models::IssueState;
+    match state {
+        _ if merged => PrState::Merged,
+        Some(IssueState::Open) => PrState::Open,
+        Some(IssueState::Closed) => PrState::Closed,
+        Some(other) => {
+            debug!(
+                "PR #{} has unknown state {:?}, taken as open",
+                number, other
+            );
+            PrState::Open
+        }
+        None => {
+            debug!("PR #{} has no state, taken as open", number);
+            PrState::Open
         }
     }
 }
@@ -739,13 +2058,34 @@ impl From<&octocrab::models::pulls::PullRequest> for PullRequest {
 fn convert_mergeable_state(state: &octocrab::models::pulls::MergeableState) -> MergeableState {
     use octocrab::models::pulls::MergeableState as OMS;
     match state {
-        OMS::Clean => MergeableState::Clean,
+        // Hooks only run on merge, the merge itself is clean
+        OMS::Clean | OMS::HasHooks => MergeableState::Clean,
         OMS::Behind => MergeableState::Behind,
         OMS::Dirty => MergeableState::Dirty,
         OMS::Blocked => MergeableState::Blocked,
         OMS::Unstable => MergeableState::Unstable,
-        OMS::Unknown => MergeableState::Unknown,
-        _ => MergeableState::Unknown,
+        // Drafts show as such through their maturity
+        OMS::Unknown | OMS::Draft => MergeableState::Unknown,
+        other => {
+            debug!("Unknown mergeable state {:?}", other);
+            MergeableState::Unknown
+        }
+    }
+}
+
+/// Convert check run status string from GitHub API to our enum
+///
+/// Unknown statuses count as in progress, a missing one as queued.
+fn convert_check_run_status(status: Option<&str>) -> CheckRunStatus {
+    match status {
+        Some("queued" | "requested") | None => CheckRunStatus::Queued,
+        Some("pending") => CheckRunStatus::Pending,
+        Some("waiting") => CheckRunStatus::Waiting,
+        Some("completed") => CheckRunStatus::Completed,
+        Some(other) => {
+            debug!("Unknown check run status '{}', taken as in progress", other);
+            CheckRunStatus::InProgress
+        }
     }
 }
 
@@ -753,14 +2093,17 @@ fn convert_mergeable_state(state: &octocrab::models::pulls::MergeableState) -> M
 fn convert_conclusion_string(conclusion: &str) -> CheckConclusion {
     match conclusion.to_lowercase().as_str() {
         "success" => CheckConclusion::Success,
-        "failure" => CheckConclusion::Failure,
+        "failure" | "startup_failure" => CheckConclusion::Failure,
         "neutral" => CheckConclusion::Neutral,
         "cancelled" => CheckConclusion::Cancelled,
         "skipped" => CheckConclusion::Skipped,
         "timed_out" => CheckConclusion::TimedOut,
         "action_required" => CheckConclusion::ActionRequired,
         "stale" => CheckConclusion::Stale,
-        _ => CheckConclusion::Neutral,
+        other => {
+            debug!("Unknown check run conclusion '{}'", other);
+            CheckConclusion::Unknown
+        }
     }
 }
 
@@ -771,7 +2114,10 @@ fn convert_status_state(state: &octocrab::models::StatusState) -> CheckState {
         octocrab::models::StatusState::Pending => CheckState::Pending,
         octocrab::models::StatusState::Failure => CheckState::Failure,
         octocrab::models::StatusState::Error => CheckState::Error,
-        _ => CheckState::Pending,
+        other => {
+            debug!("Unknown commit status state {:?}, taken as pending", other);
+            CheckState::Pending
+        }
     }
 }
 
@@ -782,32 +2128,61 @@ fn convert_status_state(state: &octocrab::models::StatusState) -> CheckState {
 fn format_octocrab_error(err: octocrab::Error) -> anyhow::Error {
     match &err {
         octocrab::Error::GitHub { source, .. } => {
-            // Extract the actual error message from GitHubError
-            let msg = &source.message;
-            let details = source
-                .errors
-                .as_ref()
-                .map(|errs| {
-                    errs.iter()
-                        .filter_map(|e| e.as_str().or_else(|| e.get("message")?.as_str()))
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                })
-                .filter(|s| !s.is_empty());
-
-            match details {
-                Some(d) => anyhow::anyhow!("{}: {}", msg, d),
-                None => anyhow::anyhow!("{}", msg),
-            }
+            anyhow::anyhow!(
+                "{}",
+                github_error_message(&source.message, source.errors.as_deref())
+            )
         }
         _ => anyhow::anyhow!("{:?}", err),
     }
 }
 
+/// Message of a GitHub error followed by its detail messages, if any
+fn github_error_message(message: &str, errors: Option<&[serde_json::Value]>) -> String {
+    let details = errors
+        .map(|errs| {
+            errs.iter()
+                .filter_map(|e| e.as_str().or_else(|| e.get("message")?.as_str()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .filter(|s| !s.is_empty());
+
+    match details {
+        Some(d) => format!("{}: {}", message, d),
+        None => message.to_string(),
+    }
+}
+
+/// GitHub refuses merges it does not allow with 405 and stale heads with 409
+fn is_merge_refusal(error: &octocrab::GitHubError) -> bool {
+    matches!(error.status_code.as_u16(), 405 | 409)
+}
+
+/// Unmerged result of a refused merge, with its reason if it is a known one
+fn refused_merge(message: String) -> MergeResult {
+    MergeResult {
+        merged: false,
+        sha: None,
+        rejection: MergeRejection::parse(&message),
+        message,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_host_of_base_url() {
+        let octocrab = Arc::new(Octocrab::builder().build().unwrap());
+        let client = OctocrabClient::new(Arc::clone(&octocrab));
+        assert_eq!(client.host(), "github.com");
+
+        let client = OctocrabClient::with_base_url(octocrab, "https://ghe.example.com/api/v3");
+        assert_eq!(client.host(), "ghe.example.com");
+    }
+
     #[test]
     fn test_convert_conclusion_string() {
         assert_eq!(
@@ -843,9 +2218,376 @@ mod tests {
             CheckConclusion::ActionRequired
         );
         assert_eq!(convert_conclusion_string("stale"), CheckConclusion::Stale);
+        assert_eq!(
+            convert_conclusion_string("startup_failure"),
+            CheckConclusion::Failure
+        );
         assert_eq!(
             convert_conclusion_string("unknown"),
-            CheckConclusion::Neutral
+            CheckConclusion::Unknown
+        );
+    }
+
+    #[test]
+    fn test_convert_check_run_status() {
+        let cases = [
+            (Some("queued"), CheckRunStatus::Queued),
+            (Some("requested"), CheckRunStatus::Queued),
+            (Some("pending"), CheckRunStatus::Pending),
+            (Some("waiting"), CheckRunStatus::Waiting),
+            (Some("in_progress"), CheckRunStatus::InProgress),
+            (Some("completed"), CheckRunStatus::Completed),
+            (Some("brand_new_status"), CheckRunStatus::InProgress),
+            (None, CheckRunStatus::Queued),
+        ];
+        for (status, expected) in cases {
+            assert_eq!(convert_check_run_status(status), expected, "{:?}", status);
+        }
+    }
+
+    #[test]
+    fn test_convert_workflow_status() {
+        let cases = [
+            (Some("queued"), WorkflowRunStatus::Queued),
+            (Some("requested"), WorkflowRunStatus::Queued),
+            (Some("waiting"), WorkflowRunStatus::Waiting),
+            (Some("pending"), WorkflowRunStatus::Pending),
+            (Some("in_progress"), WorkflowRunStatus::InProgress),
+            (Some("completed"), WorkflowRunStatus::Completed),
+            (Some("brand_new_status"), WorkflowRunStatus::Pending),
+            (None, WorkflowRunStatus::Pending),
+        ];
+        for (status, expected) in cases {
+            assert_eq!(convert_workflow_status(status), expected, "{:?}", status);
+        }
+    }
+
+    #[test]
+    fn test_convert_workflow_conclusion() {
+        let cases = [
+            ("success", WorkflowRunConclusion::Success),
+            ("failure", WorkflowRunConclusion::Failure),
+            ("startup_failure", WorkflowRunConclusion::Failure),
+            ("neutral", WorkflowRunConclusion::Neutral),
+            ("cancelled", WorkflowRunConclusion::Cancelled),
+            ("skipped", WorkflowRunConclusion::Skipped),
+            ("timed_out", WorkflowRunConclusion::TimedOut),
+            ("action_required", WorkflowRunConclusion::ActionRequired),
+            ("stale", WorkflowRunConclusion::Stale),
+            ("brand_new_conclusion", WorkflowRunConclusion::Unknown),
+        ];
+        for (conclusion, expected) in cases {
+            assert_eq!(
+                convert_workflow_conclusion(conclusion),
+                expected,
+                "{}",
+                conclusion
+            );
+        }
+    }
+
+    const GHE_PULL_REQUEST: &str = include_str!("../tests/fixtures/ghe-pull-request.json");
+    const GHE_WORKFLOW_RUNS: &str = include_str!("../tests/fixtures/ghe-workflow-runs.json");
+    const MERGE_REJECTIONS: &str = include_str!("../tests/fixtures/merge-rejections.json");
+
+    #[test]
+    fn test_parse_pull_request_from_ghe() {
+        let json: serde_json::Value = serde_json::from_str(GHE_PULL_REQUEST).unwrap();
+        let pr = parse_pull_request(json).unwrap();
+
+        assert_eq!(pr.number, 42);
+        assert_eq!(pr.title, "(no title)");
+        assert_eq!(pr.author, "unknown");
+        assert_eq!(pr.html_url, "");
+        assert_eq!(pr.updated_at, pr.created_at);
+        assert_eq!(pr.state, PrState::Open);
+        assert_eq!(pr.maturity, MaturityState::Ready);
+        assert_eq!((pr.comments, pr.additions, pr.deletions), (0, 0, 0));
+        assert!(pr.labels.is_empty());
+        // A deleted head repository counts as a fork
+        assert!(pr.is_from_fork);
+        assert_eq!(pr.mergeable_state, Some(MergeableState::Unknown));
+        assert_eq!(pr.mergeable_state_raw.as_deref(), Some("behind_policy"));
+    }
+
+    #[tokio::test]
+    async fn test_merged_prs_stop_after_max_pages() {
+        let mut closed: serde_json::Value = serde_json::from_str(GHE_PULL_REQUEST).unwrap();
+        closed["state"] = serde_json::json!("closed");
+        closed["merged_at"] = serde_json::Value::Null;
+        let mut pages = 0;
+
+        // Every page is full of closed PRs that were never merged
+        let prs = collect_pull_requests(PrStateFilter::Merged, |_| {
+            pages += 1;
+            let page = vec![closed.clone(); PR_PAGE_SIZE as usize];
+            async move { Ok(page) }
+        })
+        .await
+        .unwrap();
+
+        assert!(prs.is_empty());
+        assert_eq!(pages, MAX_PR_PAGES);
+    }
+
+    #[test]
+    fn test_parse_pull_request_mergeable_states() {
+        let mut json: serde_json::Value = serde_json::from_str(GHE_PULL_REQUEST).unwrap();
+        let cases = [
+            (serde_json::json!("behind"), Some(MergeableState::Behind)),
+            (serde_json::json!("has_hooks"), Some(MergeableState::Clean)),
+            (serde_json::json!("draft"), Some(MergeableState::Unknown)),
+            (serde_json::Value::Null, None),
+        ];
+        for (state, expected) in cases {
+            json["mergeable_state"] = state.clone();
+            let pr = parse_pull_request(json.clone()).unwrap();
+            assert_eq!(pr.mergeable_state, expected, "{}", state);
+            assert_eq!(pr.mergeable_state_raw, None, "{}", state);
+        }
+
+        json.as_object_mut().unwrap().remove("mergeable_state");
+        assert_eq!(parse_pull_request(json).unwrap().mergeable_state, None);
+    }
+
+    #[test]
+    fn test_parse_workflow_runs_from_ghe() {
+        let response: WorkflowRunsResponse = serde_json::from_str(GHE_WORKFLOW_RUNS).unwrap();
+        let runs = response.into_runs();
+
+        assert_eq!(runs[0].name, "Unknown");
+        assert_eq!(runs[0].status, WorkflowRunStatus::Pending);
+        assert_eq!(runs[0].conclusion, None);
+        assert_eq!(runs[0].run_attempt, 1);
+        assert_eq!(runs[1].status, WorkflowRunStatus::Completed);
+        assert_eq!(runs[1].conclusion, Some(WorkflowRunConclusion::Unknown));
+    }
+
+    #[test]
+    fn test_parse_comment_counts() {
+        let response = serde_json::json!({
+            "data": { "repository": { "pullRequest": {
+                "comments": { "totalCount": 3 },
+                "reviewThreads": { "nodes": [
+                    { "isResolved": false, "comments": { "totalCount": 2 } },
+                    { "isResolved": true, "comments": { "totalCount": 4 } },
+                    { "isResolved": false, "comments": { "totalCount": 1 } },
+                ] },
+            } } }
+        });
+        assert_eq!(
+            parse_comment_counts(&response).unwrap(),
+            CommentCounts {
+                unresolved_threads: 2,
+                review_comments: 7,
+                issue_comments: 3,
+            }
+        );
+
+        let error = serde_json::json!({
+            "data": { "repository": null },
+            "errors": [{ "message": "Could not resolve to a Repository" }],
+        });
+        assert_eq!(
+            parse_comment_counts(&error).unwrap_err().to_string(),
+            "GraphQL error: Could not resolve to a Repository"
+        );
+    }
+
+    #[test]
+    fn test_review_comment_payload() {
+        let single = review_comment_payload(
+            "abc",
+            "src/lib.rs",
+            &CommentLines::single("LEFT", 7),
+            "Why?",
+        );
+        assert_eq!(
+            single,
+            serde_json::json!({
+                "body": "Why?",
+                "commit_id": "abc",
+                "path": "src/lib.rs",
+                "line": 7,
+                "side": "LEFT",
+            })
+        );
+
+        let range = review_comment_payload(
+            "abc",
+            "src/lib.rs",
+            &CommentLines::range("LEFT", 7, "RIGHT", 9),
+            "Why?",
+        );
+        assert_eq!(range["line"], 9);
+        assert_eq!(range["side"], "RIGHT");
+        assert_eq!(range["start_line"], 7);
+        assert_eq!(range["start_side"], "LEFT");
+        assert!(range.get("position").is_none());
+    }
+
+    #[test]
+    fn test_parse_reviewers() {
+        let query = reviewers_query(&[7, 12]);
+        assert!(query.contains("pr7: pullRequest(number: 7) { ...reviewers }"));
+        assert!(query.contains("pr12: pullRequest(number: 12) { ...reviewers }"));
+
+        let response = serde_json::json!({
+            "data": { "repository": {
+                "pr7": {
+                    "number": 7,
+                    "reviewDecision": "CHANGES_REQUESTED",
+                    "reviewRequests": { "nodes": [
+                        { "requestedReviewer": { "login": "alice" } },
+                        { "requestedReviewer": {} },
+                    ] },
+                    "latestReviews": { "nodes": [
+                        { "author": { "login": "bob" }, "state": "CHANGES_REQUESTED", "submittedAt": "2024-03-01T12:00:00Z" },
+                        { "author": { "login": "carol" }, "state": "COMMENTED", "submittedAt": "2024-03-02T12:00:00Z" },
+                        { "author": { "login": "dave" }, "state": "PENDING", "submittedAt": null },
+                    ] },
+                },
+                "pr12": {
+                    "number": 12,
+                    "reviewDecision": null,
+                    "reviewRequests": { "nodes": [] },
+                    "latestReviews": { "nodes": [] },
+                },
+                "pr13": null,
+            } },
+            "errors": [{ "message": "Could not resolve to a PullRequest with the number of 13." }],
+        });
+        let mut reviewers = parse_reviewers(&response).unwrap();
+        reviewers.sort_by_key(|pr| pr.number);
+        assert_eq!(reviewers.len(), 2);
+
+        let pr7 = &reviewers[0];
+        assert_eq!(pr7.review_decision, ReviewDecision::ChangesRequested);
+        // Team requests have no login and are left out
+        assert_eq!(pr7.requested, vec!["alice".to_string()]);
+        assert!(pr7.is_requested_from("Alice"));
+        assert_eq!(pr7.latest_reviews.len(), 2);
+        assert_eq!(
+            pr7.latest_review_of("bob").map(|review| review.state),
+            Some(ReviewDecision::ChangesRequested)
+        );
+        assert_eq!(
+            pr7.latest_review_of("carol").map(|review| review.state),
+            Some(ReviewDecision::Pending)
+        );
+        assert_eq!(reviewers[1].review_decision, ReviewDecision::Unknown);
+
+        let missing = serde_json::json!({
+            "data": { "repository": null },
+            "errors": [{ "message": "Could not resolve to a Repository" }],
+        });
+        assert_eq!(
+            parse_reviewers(&missing).unwrap_err().to_string(),
+            "GraphQL error: Could not resolve to a Repository"
+        );
+    }
+
+    #[test]
+    fn test_parse_blame() {
+        let response = serde_json::json!({
+            "data": { "repository": { "object": { "blame": { "ranges": [
+                {
+                    "startingLine": 1,
+                    "endingLine": 4,
+                    "commit": {
+                        "oid": "abc123",
+                        "committedDate": "2024-03-01T12:00:00Z",
+                        "author": { "name": "Jane Doe", "user": { "login": "jdoe" } },
+                    },
+                },
+                {
+                    "startingLine": 5,
+                    "endingLine": 5,
+                    "commit": {
+                        "oid": "def456",
+                        "committedDate": "2024-05-01T08:30:00Z",
+                        "author": { "name": "Bot", "user": null },
+                    },
+                },
+            ] } } } }
+        });
+        let ranges = parse_blame(&response).unwrap();
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(
+            (
+                ranges[0].start_line,
+                ranges[0].end_line,
+                ranges[0].author.as_str()
+            ),
+            (1, 4, "jdoe")
+        );
+        assert_eq!(ranges[1].author, "Bot");
+        assert_eq!(ranges[1].commit_sha, "def456");
+
+        let missing = serde_json::json!({ "data": { "repository": { "object": null } } });
+        assert_eq!(
+            parse_blame(&missing).unwrap_err().to_string(),
+            "Commit not found"
+        );
+    }
+
+    #[test]
+    fn test_parse_file_content() {
+        let file = serde_json::json!({
+            "type": "file",
+            "encoding": "base64",
+            // "Closing PR {pr_number}.\n", wrapped like GitHub does
+            "content": "Q2xvc2luZyBQUiB7cHJf\nbnVtYmVyfS4K\n",
+        });
+        assert_eq!(
+            parse_file_content(&file).unwrap(),
+            "Closing PR {pr_number}.\n"
+        );
+
+        let dir = serde_json::json!([{ "type": "file", "name": "README.md" }]);
+        assert_eq!(
+            parse_file_content(&dir).unwrap_err().to_string(),
+            "Not a file"
+        );
+
+        let large = serde_json::json!({ "type": "file", "encoding": "none", "content": "" });
+        assert_eq!(
+            parse_file_content(&large).unwrap_err().to_string(),
+            "File too large for the contents API"
+        );
+
+        let binary = serde_json::json!({ "type": "file", "encoding": "base64", "content": "/w==" });
+        assert!(parse_file_content(&binary).is_err());
+    }
+
+    #[test]
+    fn test_refused_merge_from_fixtures() {
+        let fixtures: Vec<serde_json::Value> = serde_json::from_str(MERGE_REJECTIONS).unwrap();
+        for fixture in fixtures {
+            let body = &fixture["body"];
+            let errors = body["errors"].as_array().map(Vec::as_slice);
+            let message = github_error_message(body["message"].as_str().unwrap(), errors);
+            let expected: Option<MergeRejection> =
+                serde_json::from_value(fixture["rejection"].clone()).unwrap();
+
+            let result = refused_merge(message.clone());
+            assert!(!result.merged);
+            assert_eq!(result.rejection, expected, "{}", message);
+            // The raw message stays around for reasons we do not know
+            assert_eq!(result.message, message);
+        }
+    }
+
+    #[test]
+    fn test_github_error_message_joins_details() {
+        let errors = [
+            serde_json::json!({ "message": "Changes must be made through the merge queue" }),
+            serde_json::json!("Branch is protected"),
+        ];
+        assert_eq!(
+            github_error_message("Repository rule violations found", Some(&errors)),
+            "Repository rule violations found: Changes must be made through the merge queue, Branch is protected"
         );
+        assert_eq!(github_error_message("Not Found", Some(&[])), "Not Found");
     }
 }
@@ -0,0 +1,95 @@
+//! Client-side limiter for the search API
+//!
+//! GitHub's search API has its own, much lower rate limit (30 requests per
+//! minute when authenticated) than the core API. The limiter tracks recent
+//! search requests in a sliding window and refuses requests that would
+//! exceed it, so a burst of searches never burns the limit.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Search requests allowed per window for authenticated users
+pub const SEARCH_REQUESTS_PER_MINUTE: usize = 30;
+
+/// Sliding window limiter shared between clones of a client
+#[derive(Debug, Clone)]
+pub struct SearchLimiter {
+    limit: usize,
+    window: Duration,
+    requests: Arc<Mutex<VecDeque<Instant>>>,
+}
+
+impl Default for SearchLimiter {
+    fn default() -> Self {
+        Self::new(SEARCH_REQUESTS_PER_MINUTE, Duration::from_secs(60))
+    }
+}
+
+impl SearchLimiter {
+    /// Create a limiter allowing `limit` requests per `window`
+    pub fn new(limit: usize, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            requests: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    /// Record a request, or return how long to wait if the limit is reached
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        self.try_acquire_at(Instant::now())
+    }
+
+    fn try_acquire_at(&self, now: Instant) -> Result<(), Duration> {
+        let mut requests = self.requests.lock().unwrap();
+        while requests
+            .front()
+            .is_some_and(|&t| now.duration_since(t) >= self.window)
+        {
+            requests.pop_front();
+        }
+
+        if requests.len() >= self.limit {
+            let oldest = *requests.front().unwrap();
+            return Err(self.window - now.duration_since(oldest));
+        }
+
+        requests.push_back(now);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_is_enforced_within_window() {
+        let limiter = SearchLimiter::new(2, Duration::from_secs(60));
+        let start = Instant::now();
+
+        assert!(limiter.try_acquire_at(start).is_ok());
+        assert!(limiter
+            .try_acquire_at(start + Duration::from_secs(1))
+            .is_ok());
+
+        let wait = limiter
+            .try_acquire_at(start + Duration::from_secs(10))
+            .unwrap_err();
+        assert_eq!(wait, Duration::from_secs(50));
+
+        // The oldest request left the window
+        assert!(limiter
+            .try_acquire_at(start + Duration::from_secs(60))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_clones_share_the_window() {
+        let limiter = SearchLimiter::new(1, Duration::from_secs(60));
+        let clone = limiter.clone();
+        assert!(limiter.try_acquire().is_ok());
+        assert!(clone.try_acquire().is_err());
+    }
+}
@@ -0,0 +1,157 @@
+//! Service status of a GitHub host
+//!
+//! When requests keep failing, the host's status page tells whether GitHub
+//! itself has an incident. Status pages speak the Statuspage API: the
+//! `summary.json` document lists components and incidents, while the smaller
+//! `status.json` only has the overall indicator. Both are understood.
+//!
+//! Only incidents of the components the app depends on count, so an outage
+//! of e.g. Codespaces does not mute errors.
+
+use anyhow::Context;
+use async_trait::async_trait;
+use octocrab::Octocrab;
+use serde_json::Value;
+
+/// Status page of github.com
+pub const GITHUB_STATUS_URL: &str = "https://www.githubstatus.com/api/v2/summary.json";
+
+/// Status page components whose incidents affect the app
+pub const RELEVANT_COMPONENTS: [&str; 3] = ["API Requests", "Actions", "Pull Requests"];
+
+/// An active incident of a GitHub host
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Incident {
+    /// What is affected, e.g. "degraded performance: API Requests"
+    pub description: String,
+}
+
+/// Source of the service status of a host
+///
+/// Implemented by [`StatusPage`]; tests fake incidents with their own.
+#[async_trait]
+pub trait StatusSource: Send + Sync {
+    /// The active incident, None while the service is operational
+    async fn incident(&self) -> anyhow::Result<Option<Incident>>;
+}
+
+/// A Statuspage API document fetched over HTTP
+pub struct StatusPage {
+    url: String,
+    octocrab: Octocrab,
+}
+
+impl StatusPage {
+    /// Status page at `url`, e.g. [`GITHUB_STATUS_URL`]
+    pub fn new(url: impl Into<String>) -> anyhow::Result<Self> {
+        Ok(Self {
+            url: url.into(),
+            // Status pages are public, no token is sent along
+            octocrab: Octocrab::builder()
+                .build()
+                .context("Failed to create status page client")?,
+        })
+    }
+}
+
+#[async_trait]
+impl StatusSource for StatusPage {
+    async fn incident(&self) -> anyhow::Result<Option<Incident>> {
+        let document: Value = self
+            .octocrab
+            .get(&self.url, None::<&()>)
+            .await
+            .with_context(|| format!("Failed to fetch status page {}", self.url))?;
+        Ok(parse_incident(&document))
+    }
+}
+
+/// The incident reported by a Statuspage `summary.json` or `status.json`
+pub fn parse_incident(document: &Value) -> Option<Incident> {
+    if let Some(components) = document["components"].as_array() {
+        let affected: Vec<(&str, &str)> = components
+            .iter()
+            .filter_map(|component| {
+                Some((component["name"].as_str()?, component["status"].as_str()?))
+            })
+            .filter(|(name, status)| RELEVANT_COMPONENTS.contains(name) && *status != "operational")
+            .collect();
+        let (_, status) = affected.first()?;
+        let names: Vec<&str> = affected.iter().map(|(name, _)| *name).collect();
+        let description = match document["incidents"][0]["name"].as_str() {
+            Some(incident) => format!("{} ({})", incident, names.join(", ")),
+            None => format!("{}: {}", status.replace('_', " "), names.join(", ")),
+        };
+        return Some(Incident { description });
+    }
+
+    let status = &document["status"];
+    match status["indicator"].as_str() {
+        None | Some("none") => None,
+        Some(indicator) => Some(Incident {
+            description: status["description"]
+                .as_str()
+                .unwrap_or(indicator)
+                .to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_summary_reports_relevant_components_only() {
+        let summary = |api: &str, codespaces: &str, incidents: Value| {
+            json!({
+                "status": { "indicator": "minor", "description": "Minor Service Outage" },
+                "components": [
+                    { "name": "API Requests", "status": api },
+                    { "name": "Codespaces", "status": codespaces },
+                ],
+                "incidents": incidents,
+            })
+        };
+
+        assert_eq!(
+            parse_incident(&summary("operational", "major_outage", json!([]))),
+            None
+        );
+        assert_eq!(
+            parse_incident(&summary("degraded_performance", "operational", json!([]))),
+            Some(Incident {
+                description: "degraded performance: API Requests".to_string()
+            })
+        );
+        assert_eq!(
+            parse_incident(&summary(
+                "partial_outage",
+                "operational",
+                json!([{ "name": "Disruption with some GitHub services" }])
+            )),
+            Some(Incident {
+                description: "Disruption with some GitHub services (API Requests)".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_status_uses_the_overall_indicator() {
+        let status = |indicator: &str| {
+            json!({
+                "status": { "indicator": indicator, "description": "Partially Degraded Service" }
+            })
+        };
+
+        assert_eq!(parse_incident(&status("none")), None);
+        assert_eq!(
+            parse_incident(&status("major")),
+            Some(Incident {
+                description: "Partially Degraded Service".to_string()
+            })
+        );
+        assert_eq!(parse_incident(&json!({})), None);
+    }
+}
@@ -0,0 +1,470 @@
+//! Persistent per-host token storage
+//!
+//! Tokens are stored in the OS keychain, accessed through the platform CLI
+//! (`security` on macOS, `secret-tool` on Linux). Where no keychain is
+//! available, tokens are kept in an AES-256-GCM encrypted file instead.
+//!
+//! The encrypted file fallback keeps its key in a separate file next to it.
+//! It protects tokens from casual disclosure (backups, screen sharing, a
+//! stray `cat`), not from someone with access to the user account.
+//!
+//! The list of hosts with stored tokens is kept in `tokens.json` in the
+//! store directory, so stored credentials can be listed without querying
+//! the keychain.
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use log::{debug, warn};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Keychain service name tokens are stored under
+const KEYCHAIN_SERVICE: &str = "gh-pr-lander";
+/// Index of stored hosts (and sealed tokens for the file fallback)
+const INDEX_FILE: &str = "tokens.json";
+/// Key for the encrypted file fallback
+const KEY_FILE: &str = "tokens.key";
+
+/// Where a token is stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenBackend {
+    /// OS keychain
+    Keychain,
+    /// Encrypted file in the config directory
+    EncryptedFile,
+}
+
+impl TokenBackend {
+    /// Display label
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Keychain => "keychain",
+            Self::EncryptedFile => "encrypted file",
+        }
+    }
+
+    /// Warning shown when a token was stored with this backend (None if secure)
+    pub fn warning(&self) -> Option<&'static str> {
+        match self {
+            Self::Keychain => None,
+            Self::EncryptedFile => Some(
+                "No keychain available - token stored in an encrypted file whose key \
+                 lives next to it; this only protects against casual disclosure",
+            ),
+        }
+    }
+}
+
+/// Access to an OS keychain
+trait Keychain: Send + Sync + std::fmt::Debug {
+    fn get(&self, host: &str) -> Result<Option<String>>;
+    fn set(&self, host: &str, token: &str) -> Result<()>;
+    fn delete(&self, host: &str) -> Result<()>;
+}
+
+/// Keychain accessed through the platform CLI
+#[derive(Debug)]
+struct CliKeychain;
+
+impl Keychain for CliKeychain {
+    fn get(&self, host: &str) -> Result<Option<String>> {
+        let output = if cfg!(target_os = "macos") {
+            Command::new("security")
+                .args(["find-generic-password", "-s", KEYCHAIN_SERVICE, "-a", host])
+                .arg("-w")
+                .output()
+        } else if cfg!(target_os = "linux") {
+            Command::new("secret-tool")
+                .args(["lookup", "service", KEYCHAIN_SERVICE, "host", host])
+                .output()
+        } else {
+            bail!("No keychain available on this platform")
+        }
+        .context("Failed to run keychain tool")?;
+
+        if !output.status.success() {
+            return Ok(None);
+        }
+        let token = String::from_utf8(output.stdout)
+            .context("Invalid UTF-8 in keychain output")?
+            .trim()
+            .to_string();
+        Ok(Some(token).filter(|t| !t.is_empty()))
+    }
+
+    fn set(&self, host: &str, token: &str) -> Result<()> {
+        let output = if cfg!(target_os = "macos") {
+            // `security` only accepts the secret as an argument
+            Command::new("security")
+                .args(["add-generic-password", "-U", "-s", KEYCHAIN_SERVICE])
+                .args(["-a", host, "-w", token])
+                .output()
+        } else if cfg!(target_os = "linux") {
+            let label = format!("{} token for {}", KEYCHAIN_SERVICE, host);
+            let mut child = Command::new("secret-tool")
+                .args(["store", "--label", &label, "service", KEYCHAIN_SERVICE])
+                .args(["host", host])
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::piped())
+                .spawn()
+                .context("Failed to run secret-tool")?;
+            child
+                .stdin
+                .take()
+                .context("secret-tool stdin unavailable")?
+                .write_all(token.as_bytes())?;
+            child.wait_with_output()
+        } else {
+            bail!("No keychain available on this platform")
+        }
+        .context("Failed to run keychain tool")?;
+
+        if !output.status.success() {
+            bail!(
+                "Keychain refused the token: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(())
+    }
+
+    fn delete(&self, host: &str) -> Result<()> {
+        let output = if cfg!(target_os = "macos") {
+            Command::new("security")
+                .args([
+                    "delete-generic-password",
+                    "-s",
+                    KEYCHAIN_SERVICE,
+                    "-a",
+                    host,
+                ])
+                .output()
+        } else if cfg!(target_os = "linux") {
+            Command::new("secret-tool")
+                .args(["clear", "service", KEYCHAIN_SERVICE, "host", host])
+                .output()
+        } else {
+            bail!("No keychain available on this platform")
+        }
+        .context("Failed to run keychain tool")?;
+
+        if !output.status.success() {
+            bail!(
+                "Failed to delete token from keychain: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// A stored host in the index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredHost {
+    backend: TokenBackend,
+    /// Base64 of nonce + ciphertext (encrypted file backend only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sealed: Option<String>,
+}
+
+/// On-disk index of stored hosts
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TokenIndex {
+    #[serde(default)]
+    hosts: BTreeMap<String, StoredHost>,
+}
+
+/// Per-host token storage backed by the OS keychain or an encrypted file
+#[derive(Debug)]
+pub struct TokenStore {
+    dir: PathBuf,
+    keychain: Box<dyn Keychain>,
+}
+
+impl TokenStore {
+    /// Create a store keeping its index (and fallback files) in `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            keychain: Box::new(CliKeychain),
+        }
+    }
+
+    /// Get the stored token for a host
+    pub fn get(&self, host: &str) -> Option<String> {
+        let stored = self.load_index().ok()?.hosts.remove(host)?;
+        let token = match stored.backend {
+            TokenBackend::Keychain => self.keychain.get(host),
+            TokenBackend::EncryptedFile => stored
+                .sealed
+                .context("Missing sealed token")
+                .and_then(|sealed| self.open(host, &sealed))
+                .map(Some),
+        };
+        match token {
+            Ok(token) => token,
+            Err(e) => {
+                warn!("Failed to read stored token for {}: {:#}", host, e);
+                None
+            }
+        }
+    }
+
+    /// Store a token for a host
+    ///
+    /// Tries the keychain first and falls back to the encrypted file.
+    /// Returns the backend that was used.
+    pub fn set(&self, host: &str, token: &str) -> Result<TokenBackend> {
+        let mut index = self.load_index()?;
+        let stored = match self.keychain.set(host, token) {
+            Ok(()) => StoredHost {
+                backend: TokenBackend::Keychain,
+                sealed: None,
+            },
+            Err(e) => {
+                warn!("Keychain unavailable ({:#}), using encrypted file", e);
+                StoredHost {
+                    backend: TokenBackend::EncryptedFile,
+                    sealed: Some(self.seal(host, token)?),
+                }
+            }
+        };
+        let backend = stored.backend;
+        index.hosts.insert(host.to_string(), stored);
+        self.save_index(&index)?;
+        debug!("Stored token for {} in {}", host, backend.label());
+        Ok(backend)
+    }
+
+    /// Delete the stored token for a host (false if none was stored)
+    pub fn delete(&self, host: &str) -> Result<bool> {
+        let mut index = self.load_index()?;
+        let Some(stored) = index.hosts.remove(host) else {
+            return Ok(false);
+        };
+        if stored.backend == TokenBackend::Keychain {
+            self.keychain.delete(host)?;
+        }
+        self.save_index(&index)?;
+        Ok(true)
+    }
+
+    /// Hosts with a stored token and where each is stored
+    pub fn stored_hosts(&self) -> Vec<(String, TokenBackend)> {
+        self.load_index()
+            .map(|index| {
+                index
+                    .hosts
+                    .into_iter()
+                    .map(|(host, stored)| (host, stored.backend))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn load_index(&self) -> Result<TokenIndex> {
+        match fs::read_to_string(self.dir.join(INDEX_FILE)) {
+            Ok(content) => serde_json::from_str(&content).context("Invalid token index"),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(TokenIndex::default()),
+            Err(e) => Err(e).context("Failed to read token index"),
+        }
+    }
+
+    fn save_index(&self, index: &TokenIndex) -> Result<()> {
+        write_private(
+            &self.dir.join(INDEX_FILE),
+            &serde_json::to_vec_pretty(index)?,
+        )
+    }
+
+    /// Load the fallback key, creating it on first use
+    fn key(&self) -> Result<LessSafeKey> {
+        let path = self.dir.join(KEY_FILE);
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                let mut bytes = vec![0u8; AES_256_GCM.key_len()];
+                SystemRandom::new()
+                    .fill(&mut bytes)
+                    .map_err(|_| anyhow!("Failed to generate token key"))?;
+                write_private(&path, &bytes)?;
+                bytes
+            }
+            Err(e) => return Err(e).context("Failed to read token key"),
+        };
+        let key =
+            UnboundKey::new(&AES_256_GCM, &bytes).map_err(|_| anyhow!("Invalid token key"))?;
+        Ok(LessSafeKey::new(key))
+    }
+
+    /// Encrypt a token, bound to its host
+    fn seal(&self, host: &str, token: &str) -> Result<String> {
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| anyhow!("Failed to generate nonce"))?;
+        let mut in_out = token.as_bytes().to_vec();
+        self.key()?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(host.as_bytes()),
+                &mut in_out,
+            )
+            .map_err(|_| anyhow!("Failed to encrypt token"))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend(in_out);
+        Ok(BASE64.encode(sealed))
+    }
+
+    /// Decrypt a token sealed by `seal`
+    fn open(&self, host: &str, sealed: &str) -> Result<String> {
+        let sealed = BASE64.decode(sealed).context("Invalid sealed token")?;
+        if sealed.len() < NONCE_LEN {
+            bail!("Sealed token too short");
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let nonce =
+            Nonce::try_assume_unique_for_key(nonce).map_err(|_| anyhow!("Invalid nonce"))?;
+        let mut in_out = ciphertext.to_vec();
+        let plaintext = self
+            .key()?
+            .open_in_place(nonce, Aad::from(host.as_bytes()), &mut in_out)
+            .map_err(|_| anyhow!("Failed to decrypt token (key changed?)"))?;
+        String::from_utf8(plaintext.to_vec()).context("Invalid UTF-8 in token")
+    }
+}
+
+/// Write a file readable only by the current user (temp file + rename)
+fn write_private(path: &Path, contents: &[u8]) -> Result<()> {
+    let parent = path.parent().unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(parent)
+        .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+
+    let tmp_path = path.with_extension(format!("tmp-{}", std::process::id()));
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(&tmp_path)
+        .with_context(|| format!("Failed to create {:?}", tmp_path))?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path).with_context(|| format!("Failed to write {:?}", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Keychain that is never available
+    #[derive(Debug)]
+    struct NoKeychain;
+
+    impl Keychain for NoKeychain {
+        fn get(&self, _host: &str) -> Result<Option<String>> {
+            bail!("no keychain")
+        }
+        fn set(&self, _host: &str, _token: &str) -> Result<()> {
+            bail!("no keychain")
+        }
+        fn delete(&self, _host: &str) -> Result<()> {
+            bail!("no keychain")
+        }
+    }
+
+    /// In-memory keychain
+    #[derive(Debug, Default)]
+    struct MemoryKeychain(Mutex<BTreeMap<String, String>>);
+
+    impl Keychain for MemoryKeychain {
+        fn get(&self, host: &str) -> Result<Option<String>> {
+            Ok(self.0.lock().unwrap().get(host).cloned())
+        }
+        fn set(&self, host: &str, token: &str) -> Result<()> {
+            self.0
+                .lock()
+                .unwrap()
+                .insert(host.to_string(), token.to_string());
+            Ok(())
+        }
+        fn delete(&self, host: &str) -> Result<()> {
+            self.0.lock().unwrap().remove(host);
+            Ok(())
+        }
+    }
+
+    fn store(name: &str, keychain: Box<dyn Keychain>) -> TokenStore {
+        let dir = std::env::temp_dir()
+            .join(format!("gh-client-token-store-{}", std::process::id()))
+            .join(name);
+        let _ = fs::remove_dir_all(&dir);
+        TokenStore { dir, keychain }
+    }
+
+    #[test]
+    fn test_keychain_backend() {
+        let store = store("keychain", Box::new(MemoryKeychain::default()));
+        assert_eq!(
+            store.set("github.com", "tok").unwrap(),
+            TokenBackend::Keychain
+        );
+        assert_eq!(store.get("github.com").as_deref(), Some("tok"));
+        assert_eq!(
+            store.stored_hosts(),
+            vec![("github.com".to_string(), TokenBackend::Keychain)]
+        );
+
+        // The index never contains the secret
+        let index = fs::read_to_string(store.dir.join(INDEX_FILE)).unwrap();
+        assert!(!index.contains("tok\""));
+    }
+
+    #[test]
+    fn test_encrypted_file_fallback() {
+        let store = store("fallback", Box::new(NoKeychain));
+        let backend = store.set("ghe.example.com", "secret-token").unwrap();
+        assert_eq!(backend, TokenBackend::EncryptedFile);
+        assert!(backend.warning().is_some());
+        assert_eq!(
+            store.get("ghe.example.com").as_deref(),
+            Some("secret-token")
+        );
+
+        let index = fs::read_to_string(store.dir.join(INDEX_FILE)).unwrap();
+        assert!(!index.contains("secret-token"));
+        assert!(store.get("github.com").is_none());
+    }
+
+    #[test]
+    fn test_sealed_token_is_bound_to_host() {
+        let store = store("bound", Box::new(NoKeychain));
+        let sealed = store.seal("a.example.com", "token").unwrap();
+        assert_eq!(store.open("a.example.com", &sealed).unwrap(), "token");
+        assert!(store.open("b.example.com", &sealed).is_err());
+    }
+
+    #[test]
+    fn test_delete() {
+        let store = store("delete", Box::new(NoKeychain));
+        store.set("github.com", "tok").unwrap();
+        assert!(store.delete("github.com").unwrap());
+        assert!(!store.delete("github.com").unwrap());
+        assert!(store.get("github.com").is_none());
+        assert!(store.stored_hosts().is_empty());
+    }
+}
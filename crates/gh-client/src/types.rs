@@ -40,6 +40,12 @@ pub struct PullRequest {
     /// Mergeable state from GitHub
     pub mergeable_state: Option<MergeableState>,
 
+    /// Mergeable state as GitHub sent it, when it is none this client knows
+    /// (e.g. "behind_policy" from GitHub Enterprise, `mergeable_state` is
+    /// then `Unknown`)
+    #[serde(default)]
+    pub mergeable_state_raw: Option<String>,
+
     /// When the PR was created
     pub created_at: DateTime<Utc>,
 
@@ -55,11 +61,166 @@ pub struct PullRequest {
     /// Number of lines deleted
     pub deletions: u64,
 
+    /// Number of changed files (None in PR lists, only sent for a single PR)
+    #[serde(default)]
+    pub changed_files: Option<u64>,
+
     /// Draft vs Ready state for the PR
     pub maturity: MaturityState,
 
     /// Review decision state (summarized from all reviews)
     pub review_decision: ReviewDecision,
+
+    /// Open, closed or merged
+    #[serde(default)]
+    pub state: PrState,
+
+    /// Names of the labels on the PR
+    #[serde(default)]
+    pub labels: Vec<String>,
+
+    /// Owner of the repository holding the head branch (None if it was deleted)
+    #[serde(default)]
+    pub head_repo_owner: Option<String>,
+
+    /// Name of the repository holding the head branch (None if it was deleted)
+    #[serde(default)]
+    pub head_repo_name: Option<String>,
+
+    /// Head branch lives in another repository than the base (or was deleted)
+    #[serde(default)]
+    pub is_from_fork: bool,
+
+    /// Base repository maintainers may push to the head branch of a fork
+    #[serde(default)]
+    pub maintainer_can_modify: bool,
+}
+
+/// Repository settings that affect merging
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepositorySettings {
+    /// Default branch (e.g., "main")
+    pub default_branch: String,
+
+    /// "Automatically delete head branches" setting
+    ///
+    /// None if GitHub did not report it (only visible with admin access).
+    pub delete_branch_on_merge: Option<bool>,
+}
+
+/// A file changed between two commits
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangedFile {
+    /// Path of the file (after a rename)
+    pub filename: String,
+    /// Path before a rename
+    pub previous_filename: Option<String>,
+    /// "added", "removed", "modified", "renamed", ...
+    pub status: String,
+    pub additions: u64,
+    pub deletions: u64,
+}
+
+impl ChangedFile {
+    /// Lines added and removed
+    pub fn churn(&self) -> u64 {
+        self.additions + self.deletions
+    }
+}
+
+/// Comparison of two commits (`base...head`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Comparison {
+    /// Common ancestor of base and head
+    pub merge_base_sha: String,
+    /// Commits on head since the merge base
+    pub ahead_by: u64,
+    /// Commits on base since the merge base
+    pub behind_by: u64,
+    /// Files changed on head since the merge base (GitHub lists up to 300)
+    pub files: Vec<ChangedFile>,
+}
+
+/// A label defined in a repository
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Label {
+    /// Label name (e.g., "skip-changelog")
+    pub name: String,
+    /// Hex color without the leading `#` (e.g., "d73a4a")
+    pub color: String,
+    /// Optional description
+    pub description: Option<String>,
+}
+
+/// One page of PR search results
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchResults {
+    /// Total number of matches across all pages
+    pub total_count: u64,
+    /// PRs on this page
+    ///
+    /// Search returns issue-shaped objects: head/base branch, head SHA,
+    /// mergeability and line stats are empty until the PR is fetched.
+    pub items: Vec<PullRequest>,
+    /// GitHub timed out and the results may be incomplete
+    pub incomplete: bool,
+}
+
+impl SearchResults {
+    /// Whether more pages exist after `page` (1-based) of `per_page` items
+    pub fn has_more(&self, page: u32, per_page: u32) -> bool {
+        (page as u64) * (per_page as u64) < self.total_count
+    }
+}
+
+/// Lifecycle state of a PR
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrState {
+    /// PR is open
+    #[default]
+    Open,
+    /// PR was closed without merging
+    Closed,
+    /// PR was merged
+    Merged,
+}
+
+/// Which PRs to list for a repository
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrStateFilter {
+    /// Only open PRs
+    #[default]
+    Open,
+    /// Closed PRs, merged or not
+    Closed,
+    /// Only merged PRs
+    Merged,
+    /// Open and closed PRs
+    All,
+}
+
+impl PrStateFilter {
+    /// Value used to key cached PR lists
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Closed => "closed",
+            Self::Merged => "merged",
+            Self::All => "all",
+        }
+    }
+
+    /// Check whether a PR in the given state passes this filter
+    pub fn matches(&self, state: PrState) -> bool {
+        match self {
+            Self::Open => state == PrState::Open,
+            Self::Closed => state != PrState::Open,
+            Self::Merged => state == PrState::Merged,
+            Self::All => true,
+        }
+    }
 }
 
 /// Mergeable state as reported by GitHub
@@ -107,6 +268,83 @@ pub enum ReviewDecision {
     ChangesRequested,
 }
 
+/// Breakdown of the comments on a PR
+///
+/// Orders by unresolved threads first, then review and issue comments.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct CommentCounts {
+    /// Review threads not yet resolved
+    pub unresolved_threads: u32,
+    /// Line comments of reviews
+    pub review_comments: u32,
+    /// Conversation comments
+    pub issue_comments: u32,
+}
+
+impl CommentCounts {
+    /// Issue and review comments together
+    pub fn total(&self) -> u32 {
+        self.issue_comments + self.review_comments
+    }
+}
+
+/// Who reviews a PR, to tell whether it waits for the authenticated user
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrReviewers {
+    /// PR number
+    pub number: u64,
+    /// Review decision of the branch protection rules (Unknown without any)
+    pub review_decision: ReviewDecision,
+    /// Logins of the users a review is requested from (teams are left out)
+    pub requested: Vec<String>,
+    /// Latest review of each reviewer
+    pub latest_reviews: Vec<LatestReview>,
+}
+
+impl PrReviewers {
+    /// Whether a review is requested from `login`
+    pub fn is_requested_from(&self, login: &str) -> bool {
+        self.requested
+            .iter()
+            .any(|requested| requested.eq_ignore_ascii_case(login))
+    }
+
+    /// Latest review of `login`
+    pub fn latest_review_of(&self, login: &str) -> Option<&LatestReview> {
+        self.latest_reviews
+            .iter()
+            .find(|review| review.author.eq_ignore_ascii_case(login))
+    }
+}
+
+/// The latest review of a reviewer on a PR
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatestReview {
+    /// Login of the reviewer
+    pub author: String,
+    /// Approved or ChangesRequested; comments count as Pending
+    pub state: ReviewDecision,
+    /// When the review was submitted
+    pub submitted_at: DateTime<Utc>,
+}
+
+/// Consecutive lines of a file last changed by the same commit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlameRange {
+    /// First line of the range (1-indexed)
+    pub start_line: u32,
+    /// Last line of the range (inclusive)
+    pub end_line: u32,
+    /// SHA of the commit that last changed the lines
+    pub commit_sha: String,
+    /// Author login, else the author name of the commit
+    pub author: String,
+    /// When the commit was made
+    pub committed_at: DateTime<Utc>,
+}
+
 /// A CI check run from the GitHub API
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CheckRun {
@@ -136,8 +374,12 @@ pub struct CheckRun {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum CheckRunStatus {
-    /// Check is queued
+    /// Check is queued (or requested) and waits for a runner
     Queued,
+    /// Check is pending, e.g. behind a concurrency group
+    Pending,
+    /// Check waits for a deployment to be approved
+    Waiting,
     /// Check is in progress
     InProgress,
     /// Check has completed
@@ -164,6 +406,8 @@ pub enum CheckConclusion {
     ActionRequired,
     /// Check is stale (superseded by newer run)
     Stale,
+    /// Conclusion this client does not know, counts as neutral
+    Unknown,
 }
 
 /// Combined commit status from the GitHub API
@@ -231,6 +475,68 @@ pub struct MergeResult {
     pub sha: Option<String>,
     /// Message from the merge operation
     pub message: String,
+    /// Why GitHub refused the merge, if the message names a known reason
+    #[serde(default)]
+    pub rejection: Option<MergeRejection>,
+}
+
+/// Reason GitHub refused to merge a PR (405/409 of the merge endpoint)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeRejection {
+    /// Approving (or code owner) reviews are missing
+    ReviewRequired,
+    /// Required checks have not finished (or not started) yet
+    ChecksPending,
+    /// Required checks failed
+    ChecksFailed,
+    /// The head branch must be updated with its base first
+    OutOfDate,
+    /// The branch conflicts with its base
+    Conflicts,
+    /// The base branch only accepts merges through the merge queue
+    MergeQueueRequired,
+    /// Draft PRs cannot be merged
+    Draft,
+}
+
+impl MergeRejection {
+    /// Known reason of a merge error message, matched by GitHub's wording
+    pub fn parse(message: &str) -> Option<Self> {
+        let message = message.to_lowercase();
+        let has = |needle: &str| message.contains(needle);
+        let reason = if has("draft") {
+            Self::Draft
+        } else if has("merge queue") {
+            Self::MergeQueueRequired
+        } else if has("status check") && (has("failing") || has("failed")) {
+            Self::ChecksFailed
+        } else if has("status check") && (has("expected") || has("pending") || has("progress")) {
+            Self::ChecksPending
+        } else if has("review") && (has("required") || has("requested") || has("code owner")) {
+            Self::ReviewRequired
+        } else if has("not up to date") || has("out of date") || has("base branch was modified") {
+            Self::OutOfDate
+        } else if has("conflict") || has("not mergeable") {
+            Self::Conflicts
+        } else {
+            return None;
+        };
+        Some(reason)
+    }
+
+    /// Short explanation, e.g. "required checks are failing"
+    pub fn describe(self) -> &'static str {
+        match self {
+            Self::ReviewRequired => "an approving review is required",
+            Self::ChecksPending => "required checks have not passed yet",
+            Self::ChecksFailed => "required checks are failing",
+            Self::OutOfDate => "the branch is out of date with its base",
+            Self::Conflicts => "the branch has merge conflicts",
+            Self::MergeQueueRequired => "the base branch requires the merge queue",
+            Self::Draft => "the PR is still a draft",
+        }
+    }
 }
 
 /// Review event type for PR reviews
@@ -258,12 +564,25 @@ pub struct WorkflowRun {
     pub conclusion: Option<WorkflowRunConclusion>,
     /// HEAD SHA the workflow ran on
     pub head_sha: String,
+    /// Branch the workflow ran on (missing for e.g. tag pushes)
+    #[serde(default)]
+    pub head_branch: Option<String>,
+    /// Event that triggered the run (e.g. "push", "schedule")
+    #[serde(default)]
+    pub event: String,
     /// URL to view the workflow run
     pub html_url: String,
     /// When the run was created
     pub created_at: DateTime<Utc>,
     /// When the run was last updated
     pub updated_at: DateTime<Utc>,
+    /// Attempt of the run, counting up with every re-run
+    #[serde(default = "first_attempt")]
+    pub run_attempt: u64,
+}
+
+fn first_attempt() -> u64 {
+    1
 }
 
 /// Status of a workflow run
@@ -302,6 +621,8 @@ pub enum WorkflowRunConclusion {
     ActionRequired,
     /// Workflow is stale
     Stale,
+    /// Conclusion this client does not know
+    Unknown,
 }
 
 /// Aggregated CI status from check runs
@@ -318,15 +639,192 @@ pub struct CiStatus {
     pub passed: usize,
     /// Number of failed checks
     pub failed: usize,
-    /// Number of pending/in-progress checks
+    /// Number of pending/in-progress checks (including queued and waiting ones)
     pub pending: usize,
+    /// Number of checks queued for a runner
+    #[serde(default)]
+    pub queued: usize,
+    /// Number of checks waiting for a deployment to be approved
+    #[serde(default)]
+    pub waiting: usize,
+    /// Each check run (empty in cached entries of older versions)
+    #[serde(default)]
+    pub checks: Vec<CheckResult>,
+    /// Failed checks required by branch protection (see [`Self::with_required`])
+    #[serde(default)]
+    pub required_failed: usize,
+    /// Failed checks branch protection does not require
+    #[serde(default)]
+    pub optional_failed: usize,
+    /// Pending checks required by branch protection
+    #[serde(default)]
+    pub required_pending: usize,
+    /// The required checks are known, otherwise every check counts as required
+    #[serde(default)]
+    pub required_known: bool,
+}
+
+impl CiStatus {
+    /// Aggregate the status and conclusion of all check runs of a commit
+    ///
+    /// A set conclusion wins over the status; a check without a conclusion
+    /// counts as running, queued or waiting depending on its status.
+    pub fn from_checks(
+        checks: impl IntoIterator<Item = (CheckRunStatus, Option<CheckConclusion>)>,
+    ) -> Self {
+        let mut status = Self {
+            state: CiState::Unknown,
+            total_checks: 0,
+            passed: 0,
+            failed: 0,
+            pending: 0,
+            queued: 0,
+            waiting: 0,
+            checks: Vec::new(),
+            required_failed: 0,
+            optional_failed: 0,
+            required_pending: 0,
+            required_known: false,
+        };
+        let mut running = 0;
+        for (check_status, conclusion) in checks {
+            status.total_checks += 1;
+            match (check_status, conclusion) {
+                (
+                    _,
+                    Some(
+                        CheckConclusion::Success
+                        | CheckConclusion::Neutral
+                        | CheckConclusion::Skipped
+                        | CheckConclusion::Unknown,
+                    ),
+                ) => status.passed += 1,
+                (_, Some(_)) => status.failed += 1,
+                (CheckRunStatus::Queued | CheckRunStatus::Pending, None) => status.queued += 1,
+                (CheckRunStatus::Waiting, None) => status.waiting += 1,
+                (CheckRunStatus::InProgress | CheckRunStatus::Completed, None) => running += 1,
+            }
+        }
+        status.pending = running + status.queued + status.waiting;
+        status.required_failed = status.failed;
+        status.required_pending = status.pending;
+
+        status.state = if status.failed > 0 {
+            CiState::Failure
+        } else if running > 0 {
+            CiState::Pending
+        } else if status.waiting > 0 {
+            CiState::WaitingApproval
+        } else if status.queued > 0 {
+            CiState::Queued
+        } else if status.passed > 0 {
+            CiState::Success
+        } else {
+            CiState::Unknown
+        };
+        status
+    }
+
+    /// Aggregate check runs, keeping them for details
+    pub fn from_results(checks: Vec<CheckResult>) -> Self {
+        let mut status =
+            Self::from_checks(checks.iter().map(|check| (check.status, check.conclusion)));
+        status.checks = checks;
+        status
+    }
+
+    /// Split the failed and pending checks into required and optional ones
+    ///
+    /// `required` names the checks branch protection requires. Without them
+    /// (the protection cannot be read or requires nothing) every check counts
+    /// as required, as do all checks of a status without check details.
+    pub fn with_required(mut self, required: Option<&[String]>) -> Self {
+        let Some(required) = required.filter(|_| !self.checks.is_empty()) else {
+            return self;
+        };
+        self.required_known = true;
+        self.required_failed = 0;
+        self.optional_failed = 0;
+        self.required_pending = 0;
+        for check in &mut self.checks {
+            check.required = required.contains(&check.name);
+            match (check.required, check.is_failed(), check.is_pending()) {
+                (true, true, _) => self.required_failed += 1,
+                (false, true, _) => self.optional_failed += 1,
+                (true, _, true) => self.required_pending += 1,
+                _ => {}
+            }
+        }
+        self
+    }
+}
+
+/// One check run of a commit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckResult {
+    /// Check run ID
+    pub id: u64,
+    /// Check name (the job name for GitHub Actions)
+    pub name: String,
+    /// Whether the check is queued, running or done
+    pub status: CheckRunStatus,
+    /// Conclusion once completed
+    pub conclusion: Option<CheckConclusion>,
+    /// When the check started running (None while queued)
+    pub started_at: Option<DateTime<Utc>>,
+    /// When the check finished (None while running)
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Required by branch protection (every check while unknown)
+    #[serde(default = "required_by_default")]
+    pub required: bool,
+}
+
+fn required_by_default() -> bool {
+    true
+}
+
+impl CheckResult {
+    /// Whether the check concluded with a failure (including cancelled and
+    /// timed out checks)
+    pub fn is_failed(&self) -> bool {
+        self.conclusion.is_some_and(|conclusion| {
+            !matches!(
+                conclusion,
+                CheckConclusion::Success
+                    | CheckConclusion::Neutral
+                    | CheckConclusion::Skipped
+                    | CheckConclusion::Unknown
+            )
+        })
+    }
+
+    /// Whether the check has not concluded yet
+    pub fn is_pending(&self) -> bool {
+        self.conclusion.is_none()
+    }
+
+    /// Seconds a passed check took, `None` for other checks
+    pub fn passed_seconds(&self) -> Option<u64> {
+        if self.conclusion != Some(CheckConclusion::Success) {
+            return None;
+        }
+        let seconds = (self.completed_at? - self.started_at?).num_seconds();
+        u64::try_from(seconds).ok()
+    }
+
+    /// Whether the check started and did not finish yet
+    pub fn is_running(&self) -> bool {
+        self.started_at.is_some() && self.completed_at.is_none()
+    }
 }
 
 /// Aggregated CI state
 ///
 /// Represents the overall state of CI for a commit:
 /// - Any failure → Failure
-/// - Any pending (and no failure) → Pending
+/// - Any running (and no failure) → Pending
+/// - Any waiting for a deployment approval → WaitingApproval
+/// - Any queued for a runner → Queued
 /// - All success → Success
 /// - No checks → Unknown
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -338,10 +836,21 @@ pub enum CiState {
     Failure,
     /// At least one check is pending/in-progress (no failures)
     Pending,
+    /// Checks are queued for a runner (none running)
+    Queued,
+    /// A deployment waits for approval (none running)
+    WaitingApproval,
     /// No checks found or status unknown
     Unknown,
 }
 
+impl CiState {
+    /// Whether CI has not finished yet
+    pub fn is_in_flight(&self) -> bool {
+        matches!(self, Self::Pending | Self::Queued | Self::WaitingApproval)
+    }
+}
+
 /// A review comment on a pull request
 ///
 /// Represents a comment on a specific line in a PR diff.
@@ -357,6 +866,15 @@ pub struct ReviewComment {
     pub original_line: Option<u32>,
     /// Which side of the diff: "LEFT" (deletions) or "RIGHT" (additions)
     pub side: Option<String>,
+    /// First line of a multi-line comment, `line` being the last
+    #[serde(default)]
+    pub start_line: Option<u32>,
+    /// Side of the first line of a multi-line comment
+    #[serde(default)]
+    pub start_side: Option<String>,
+    /// Comment on the whole file (`subject_type: file`), without a line
+    #[serde(default)]
+    pub is_file_level: bool,
     /// Comment body text
     pub body: String,
     /// Author's GitHub username
@@ -367,6 +885,51 @@ pub struct ReviewComment {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Lines of a diff a new review comment is anchored to
+///
+/// GitHub anchors comments by file line numbers and the side of the diff
+/// ("LEFT" for the old file, "RIGHT" for the new one) rather than by the
+/// deprecated diff `position`. A multi-line comment spans from `start_line`
+/// on `start_side` to `line` on `side`, within one hunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommentLines {
+    /// Last (or only) line commented on
+    pub line: u32,
+    /// Side of `line`
+    pub side: String,
+    /// First line of a multi-line comment
+    pub start_line: Option<u32>,
+    /// Side of `start_line`
+    pub start_side: Option<String>,
+}
+
+impl CommentLines {
+    /// A comment on a single line
+    pub fn single(side: impl Into<String>, line: u32) -> Self {
+        Self {
+            line,
+            side: side.into(),
+            start_line: None,
+            start_side: None,
+        }
+    }
+
+    /// A comment from `start_line` on `start_side` to `line` on `side`
+    pub fn range(
+        start_side: impl Into<String>,
+        start_line: u32,
+        side: impl Into<String>,
+        line: u32,
+    ) -> Self {
+        Self {
+            line,
+            side: side.into(),
+            start_line: Some(start_line),
+            start_side: Some(start_side.into()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -389,13 +952,21 @@ mod tests {
             head_branch: "feature/test".to_string(),
             mergeable: Some(true),
             mergeable_state: Some(MergeableState::Clean),
+            mergeable_state_raw: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             html_url: "https://github.com/owner/repo/pull/42".to_string(),
             additions: 100,
             deletions: 50,
+            changed_files: Some(3),
             maturity: MaturityState::Ready,
             review_decision: ReviewDecision::Approved,
+            state: PrState::Merged,
+            labels: vec!["dependencies".to_string()],
+            head_repo_owner: Some("contributor".to_string()),
+            head_repo_name: Some("repo".to_string()),
+            is_from_fork: true,
+            maintainer_can_modify: true,
         };
 
         let json = serde_json::to_string(&pr).unwrap();
@@ -406,6 +977,160 @@ mod tests {
         assert_eq!(deserialized.author, "testuser");
         assert_eq!(deserialized.maturity, MaturityState::Ready);
         assert_eq!(deserialized.review_decision, ReviewDecision::Approved);
+        assert_eq!(deserialized.state, PrState::Merged);
+        assert_eq!(deserialized.head_repo_owner.as_deref(), Some("contributor"));
+        assert!(deserialized.is_from_fork);
+        assert!(deserialized.maintainer_can_modify);
+    }
+
+    #[test]
+    fn test_ci_status_aggregation() {
+        use CheckConclusion as C;
+        use CheckRunStatus as S;
+        let state = |checks: &[(S, Option<C>)]| CiStatus::from_checks(checks.iter().copied()).state;
+
+        assert_eq!(state(&[]), CiState::Unknown);
+        assert_eq!(
+            state(&[
+                (S::Completed, Some(C::Success)),
+                (S::Completed, Some(C::Skipped))
+            ]),
+            CiState::Success
+        );
+        assert_eq!(
+            state(&[(S::Completed, Some(C::Success)), (S::Queued, None)]),
+            CiState::Queued
+        );
+        assert_eq!(state(&[(S::Pending, None)]), CiState::Queued);
+        assert_eq!(
+            state(&[(S::Queued, None), (S::Waiting, None)]),
+            CiState::WaitingApproval
+        );
+        assert_eq!(
+            state(&[(S::Waiting, None), (S::InProgress, None)]),
+            CiState::Pending
+        );
+        assert_eq!(
+            state(&[(S::InProgress, None), (S::Completed, Some(C::TimedOut))]),
+            CiState::Failure
+        );
+    }
+
+    #[test]
+    fn test_ci_status_counts() {
+        use CheckConclusion as C;
+        use CheckRunStatus as S;
+        let status = CiStatus::from_checks([
+            (S::Completed, Some(C::Neutral)),
+            (S::Completed, Some(C::ActionRequired)),
+            (S::InProgress, None),
+            (S::Queued, None),
+            (S::Waiting, None),
+            (S::Waiting, None),
+        ]);
+        assert_eq!(status.total_checks, 6);
+        assert_eq!(status.passed, 1);
+        assert_eq!(status.failed, 1);
+        assert_eq!(status.pending, 4);
+        assert_eq!(status.queued, 1);
+        assert_eq!(status.waiting, 2);
+    }
+
+    fn check(name: &str, conclusion: Option<CheckConclusion>) -> CheckResult {
+        CheckResult {
+            id: 1,
+            name: name.to_string(),
+            status: match conclusion {
+                Some(_) => CheckRunStatus::Completed,
+                None => CheckRunStatus::InProgress,
+            },
+            conclusion,
+            started_at: None,
+            completed_at: None,
+            required: true,
+        }
+    }
+
+    #[test]
+    fn test_check_timing() {
+        let start = Utc::now();
+        let mut check = check("test", None);
+        check.started_at = Some(start);
+        assert!(check.is_running());
+        assert_eq!(check.passed_seconds(), None);
+
+        check.completed_at = Some(start + chrono::TimeDelta::seconds(840));
+        check.conclusion = Some(CheckConclusion::Failure);
+        assert!(!check.is_running());
+        assert_eq!(check.passed_seconds(), None);
+        check.conclusion = Some(CheckConclusion::Success);
+        assert_eq!(check.passed_seconds(), Some(840));
+    }
+
+    #[test]
+    fn test_required_checks_split_failures() {
+        use CheckConclusion as C;
+        let status = || {
+            CiStatus::from_results(vec![
+                check("test", Some(C::Success)),
+                check("lint", Some(C::Failure)),
+                check("build", None),
+                check("docs", Some(C::TimedOut)),
+            ])
+        };
+        let required = ["test".to_string(), "build".to_string()];
+
+        let split = status().with_required(Some(&required));
+        assert_eq!(split.state, CiState::Failure);
+        assert!(split.required_known);
+        assert_eq!(split.required_failed, 0);
+        assert_eq!(split.optional_failed, 2);
+        assert_eq!(split.required_pending, 1);
+        let required_names: Vec<&str> = split
+            .checks
+            .iter()
+            .filter(|check| check.required)
+            .map(|check| check.name.as_str())
+            .collect();
+        assert_eq!(required_names, vec!["test", "build"]);
+
+        // Unknown protection keeps every check required
+        let unknown = status().with_required(None);
+        assert!(!unknown.required_known);
+        assert_eq!(unknown.required_failed, 2);
+        assert_eq!(unknown.optional_failed, 0);
+        assert_eq!(unknown.required_pending, 1);
+        assert!(unknown.checks.iter().all(|check| check.required));
+    }
+
+    #[test]
+    fn test_ci_state_in_flight() {
+        assert!(CiState::Pending.is_in_flight());
+        assert!(CiState::Queued.is_in_flight());
+        assert!(CiState::WaitingApproval.is_in_flight());
+        assert!(!CiState::Success.is_in_flight());
+        assert!(!CiState::Failure.is_in_flight());
+        assert!(!CiState::Unknown.is_in_flight());
+    }
+
+    #[test]
+    fn test_search_results_has_more() {
+        let results = SearchResults {
+            total_count: 45,
+            ..Default::default()
+        };
+        assert!(results.has_more(1, 30));
+        assert!(!results.has_more(2, 30));
+    }
+
+    #[test]
+    fn test_pr_state_filter_matches() {
+        assert!(PrStateFilter::Open.matches(PrState::Open));
+        assert!(!PrStateFilter::Open.matches(PrState::Merged));
+        assert!(PrStateFilter::Closed.matches(PrState::Closed));
+        assert!(PrStateFilter::Closed.matches(PrState::Merged));
+        assert!(!PrStateFilter::Merged.matches(PrState::Closed));
+        assert!(PrStateFilter::All.matches(PrState::Open));
     }
 
     #[test]
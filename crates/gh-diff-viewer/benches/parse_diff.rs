@@ -0,0 +1,64 @@
+//! Times opening a large diff: parsing plus the initial viewer state.
+//!
+//! Run with `cargo bench -p gh-diff-viewer`.
+
+use gh_diff_viewer::{parse_unified_diff, DiffViewerState};
+use std::fmt::Write as _;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const DIFF_LINES: usize = 50_000;
+const LINES_PER_FILE: usize = 500;
+const LINES_PER_HUNK: usize = 50;
+const ITERATIONS: u32 = 5;
+
+/// A diff of `lines` lines spread over files of `LINES_PER_FILE` lines
+fn generate_diff(lines: usize) -> String {
+    let mut diff = String::new();
+    for file in 0..lines / LINES_PER_FILE {
+        let path = format!("src/module_{}/file_{}.rs", file % 20, file);
+        let _ = write!(
+            diff,
+            "diff --git a/{path} b/{path}\nindex 1111111..2222222 100644\n--- a/{path}\n+++ b/{path}\n"
+        );
+        for hunk in 0..LINES_PER_FILE / LINES_PER_HUNK {
+            let start = hunk * (LINES_PER_HUNK + 10) + 1;
+            // 30 context, 10 added and 10 removed lines per hunk
+            let _ = writeln!(diff, "@@ -{start},40 +{start},40 @@ fn section_{hunk}()");
+            for line in 0..LINES_PER_HUNK {
+                let prefix = match line % 5 {
+                    0..=2 => ' ',
+                    3 => '+',
+                    _ => '-',
+                };
+                let _ = writeln!(
+                    diff,
+                    "{prefix}    let value_{hunk}_{line} = compute({file});"
+                );
+            }
+        }
+    }
+    diff
+}
+
+fn main() {
+    let diff_text = generate_diff(DIFF_LINES);
+
+    let mut total = Duration::ZERO;
+    let mut fastest = Duration::MAX;
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        let diff = parse_unified_diff(&diff_text, "base", "head").expect("generated diff parses");
+        black_box(DiffViewerState::new(diff));
+        let elapsed = start.elapsed();
+        total += elapsed;
+        fastest = fastest.min(elapsed);
+    }
+
+    println!(
+        "parse_unified_diff + DiffViewerState::new ({} lines): mean {:.2?}, min {:.2?}",
+        DIFF_LINES,
+        total / ITERATIONS,
+        fastest
+    );
+}
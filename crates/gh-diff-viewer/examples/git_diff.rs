@@ -0,0 +1,228 @@
+//! Browse a local `git diff` in the diff viewer.
+//!
+//! Run from inside a git repository with
+//! `cargo run -p gh-diff-viewer --example git_diff -- [<rev> | <rev>..<rev>] [--cached] [-- <path>...]`.
+//!
+//! The arguments go to `git diff` as they are. Without a revision it shows the
+//! unstaged changes, with one revision the working tree against it, with a
+//! range the commits in between. Context is expanded from the local
+//! repository, commenting and blame are not available.
+
+use async_trait::async_trait;
+use gh_diff_viewer::event::ExpandDirection;
+use gh_diff_viewer::{
+    parse_unified_diff, ContextError, ContextProvider, DefaultTheme, DiffAction, DiffEvent,
+    DiffHighlighter, DiffViewer, DiffViewerState, FooterHint,
+};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::DefaultTerminal;
+use std::error::Error;
+use std::process::Command;
+
+/// Revision naming the working tree (read from disk)
+const WORKTREE: &str = "";
+/// Revision naming the index (`git show :0:<path>`)
+const INDEX: &str = ":0";
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (base, head) = compared_revisions(&args)?;
+
+    let mut diff_args = vec![
+        "diff",
+        "--no-color",
+        "--no-ext-diff",
+        "--src-prefix=a/",
+        "--dst-prefix=b/",
+    ];
+    diff_args.extend(args.iter().map(String::as_str));
+    let diff = parse_unified_diff(&git(&diff_args)?, base, head)?;
+    if diff.files.is_empty() {
+        println!("No changes");
+        return Ok(());
+    }
+
+    let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+    let mut state = DiffViewerState::new(diff);
+    let mut terminal = ratatui::init();
+    let result = run(
+        &mut terminal,
+        &mut state,
+        |file_path, commit_sha, start, end| {
+            runtime.block_on(LocalContext.fetch_lines(file_path, commit_sha, start, end))
+        },
+    );
+    ratatui::restore();
+    Ok(result?)
+}
+
+/// Draw the viewer and handle keys until it is closed
+fn run(
+    terminal: &mut DefaultTerminal,
+    state: &mut DiffViewerState,
+    fetch_lines: impl Fn(&str, &str, u32, u32) -> Result<Vec<String>, ContextError>,
+) -> std::io::Result<()> {
+    let mut highlighter = DiffHighlighter::new();
+    let hints = vec![
+        FooterHint::new("[ ]", "Expand"),
+        FooterHint::new("n/N", "Hunk"),
+        FooterHint::new("q", "Quit"),
+    ];
+
+    loop {
+        terminal.draw(|frame| {
+            DiffViewer::new(&mut highlighter, &DefaultTheme)
+                .with_footer_hints(hints.clone())
+                .render_with_state(frame.area(), frame.buffer_mut(), state);
+        })?;
+
+        let action = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key_action(key),
+            Event::Resize(width, height) => Some(DiffAction::SetViewport { width, height }),
+            _ => None,
+        };
+        let Some(action) = action else {
+            continue;
+        };
+
+        for event in state.handle_action(action) {
+            match event {
+                DiffEvent::Close => return Ok(()),
+                DiffEvent::RequestContext {
+                    file_path,
+                    commit_sha,
+                    direction,
+                    from_line,
+                    count,
+                } => {
+                    let (start, end) = match direction {
+                        ExpandDirection::Up => {
+                            (from_line.saturating_sub(count).max(1), from_line - 1)
+                        }
+                        ExpandDirection::Down => (from_line + 1, from_line + count),
+                    };
+                    if start > end {
+                        continue;
+                    }
+                    // Nothing to show past the end of the file or for missing files
+                    if let Ok(lines) = fetch_lines(&file_path, &commit_sha, start, end) {
+                        state.insert_expanded_lines(&file_path, direction, from_line, lines);
+                    }
+                }
+                // Comments and blame need a forge, a local diff has none
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Viewer action of a key, comment and review keys are left out
+fn key_action(key: KeyEvent) -> Option<DiffAction> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let action = match key.code {
+        KeyCode::Char('q') | KeyCode::Esc => DiffAction::Close,
+        KeyCode::Char('f') if ctrl => DiffAction::ScrollPageDown,
+        KeyCode::Char('b') if ctrl => DiffAction::ScrollPageUp,
+        KeyCode::Char('d') if ctrl => DiffAction::ScrollHalfDown,
+        KeyCode::Char('u') if ctrl => DiffAction::ScrollHalfUp,
+        KeyCode::Char('j') | KeyCode::Down => DiffAction::CursorDown,
+        KeyCode::Char('k') | KeyCode::Up => DiffAction::CursorUp,
+        KeyCode::Char('h') | KeyCode::Left => DiffAction::CollapseTreeNode,
+        KeyCode::Char('l') | KeyCode::Right => DiffAction::ExpandTreeNode,
+        KeyCode::Char('g') => DiffAction::CursorFirst,
+        KeyCode::Char('G') => DiffAction::CursorLast,
+        KeyCode::Char('n') => DiffAction::NextHunk,
+        KeyCode::Char('N') => DiffAction::PrevHunk,
+        KeyCode::Char('[') => DiffAction::ExpandContextAbove,
+        KeyCode::Char(']') => DiffAction::ExpandContextBelow,
+        KeyCode::Char(' ') | KeyCode::Tab => DiffAction::ToggleFocus,
+        KeyCode::Enter => DiffAction::ToggleTreeNode,
+        _ => return None,
+    };
+    Some(action)
+}
+
+/// Base and head revision `git diff <args>` compares
+fn compared_revisions(args: &[String]) -> Result<(String, String), Box<dyn Error>> {
+    let cached = args
+        .iter()
+        .any(|arg| arg == "--cached" || arg == "--staged");
+    let revisions: Vec<&str> = args
+        .iter()
+        .take_while(|arg| *arg != "--")
+        .filter(|arg| !arg.starts_with('-'))
+        .map(String::as_str)
+        .collect();
+
+    let revisions = match revisions.as_slice() {
+        [] if cached => ("HEAD".to_string(), INDEX.to_string()),
+        [] => (INDEX.to_string(), WORKTREE.to_string()),
+        [range] => match range.split_once("...") {
+            Some((base, head)) => {
+                let base = git(&["merge-base", or_head(base), or_head(head)])?;
+                (base.trim().to_string(), or_head(head).to_string())
+            }
+            None => match range.split_once("..") {
+                Some((base, head)) => (or_head(base).to_string(), or_head(head).to_string()),
+                None if cached => (range.to_string(), INDEX.to_string()),
+                None => (range.to_string(), WORKTREE.to_string()),
+            },
+        },
+        [base, head] => (base.to_string(), head.to_string()),
+        _ => return Err("expected at most two revisions".into()),
+    };
+    Ok(revisions)
+}
+
+/// Git reads an empty side of a range as `HEAD`
+fn or_head(revision: &str) -> &str {
+    if revision.is_empty() {
+        "HEAD"
+    } else {
+        revision
+    }
+}
+
+/// Run git and return its output
+fn git(args: &[&str]) -> Result<String, Box<dyn Error>> {
+    let output = Command::new("git").args(args).output()?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Reads context lines from the local repository
+struct LocalContext;
+
+#[async_trait]
+impl ContextProvider for LocalContext {
+    async fn fetch_lines(
+        &self,
+        path: &str,
+        commit_sha: &str,
+        start_line: u32,
+        end_line: u32,
+    ) -> Result<Vec<String>, ContextError> {
+        let content = if commit_sha == WORKTREE {
+            let root = git(&["rev-parse", "--show-toplevel"])
+                .map_err(|e| ContextError::Unavailable(e.to_string()))?;
+            std::fs::read_to_string(std::path::Path::new(root.trim()).join(path))
+                .map_err(|_| ContextError::FileNotFound(path.to_string()))?
+        } else {
+            git(&["show", &format!("{}:{}", commit_sha, path)])
+                .map_err(|_| ContextError::FileNotFound(path.to_string()))?
+        };
+
+        Ok(content
+            .lines()
+            .skip(start_line.saturating_sub(1) as usize)
+            .take((end_line + 1).saturating_sub(start_line) as usize)
+            .map(String::from)
+            .collect())
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+}
@@ -67,8 +67,15 @@ pub enum DiffAction {
     // === Comments ===
     /// Start adding a comment on current line/selection
     StartComment,
+    /// Start adding a comment on the whole file under the cursor
+    /// (file tree entry or hunk header row)
+    CommentOnFile,
+    /// Start adding a comment on the pull request itself
+    CommentOnPr,
     /// Insert a character into the comment editor
     CommentInsertChar(char),
+    /// Insert pasted text (possibly several lines) into the comment editor
+    CommentInsertText(String),
     /// Delete character before cursor in comment editor
     CommentBackspace,
     /// Delete character at cursor in comment editor
@@ -106,6 +113,10 @@ pub enum DiffAction {
     /// Expand context below current hunk
     ExpandContextBelow,
 
+    // === Blame ===
+    /// Show or hide who last touched the context and removed lines
+    ToggleBlame,
+
     // === General ===
     /// Close the diff viewer
     Close,
@@ -121,6 +132,7 @@ impl DiffAction {
         matches!(
             self,
             DiffAction::CommentInsertChar(_)
+                | DiffAction::CommentInsertText(_)
                 | DiffAction::CommentBackspace
                 | DiffAction::CommentDelete
                 | DiffAction::CommentCursorLeft
@@ -168,6 +180,7 @@ mod tests {
     #[test]
     fn test_is_comment_action() {
         assert!(DiffAction::CommentInsertChar('a').is_comment_action());
+        assert!(DiffAction::CommentInsertText("a\nb".into()).is_comment_action());
         assert!(DiffAction::CommentBackspace.is_comment_action());
         assert!(DiffAction::CommitComment.is_comment_action());
         assert!(!DiffAction::CursorDown.is_comment_action());
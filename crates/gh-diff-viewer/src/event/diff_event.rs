@@ -48,6 +48,22 @@ pub enum DiffEvent {
         count: u32,
     },
 
+    /// Blame is shown for a file that has none loaded yet.
+    RequestBlame {
+        /// File path relative to repository root (key for [`insert_blame`]).
+        ///
+        /// [`insert_blame`]: crate::DiffViewerState::insert_blame
+        file_path: String,
+        /// Path of the file at the base commit (differs for renames).
+        base_path: String,
+        /// Base commit SHA to blame at.
+        commit_sha: String,
+        /// First old-side line shown in the diff (1-indexed).
+        start_line: u32,
+        /// Last old-side line shown in the diff (inclusive).
+        end_line: u32,
+    },
+
     /// User submitted a comment (stored locally, not sent to API yet).
     CommentAdded(PendingComment),
 
@@ -115,6 +131,13 @@ mod tests {
                 from_line: 10,
                 count: 5,
             },
+            DiffEvent::RequestBlame {
+                file_path: "src/main.rs".to_string(),
+                base_path: "src/main.rs".to_string(),
+                commit_sha: "abc123".to_string(),
+                start_line: 1,
+                end_line: 40,
+            },
             DiffEvent::CommentAdded(PendingComment::new(
                 "src/main.rs",
                 CommentPosition::single(DiffSide::Right, 42),
@@ -140,6 +163,6 @@ mod tests {
             },
         ];
 
-        assert_eq!(events.len(), 8);
+        assert_eq!(events.len(), 9);
     }
 }
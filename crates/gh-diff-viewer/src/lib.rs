@@ -41,6 +41,9 @@
 //!     // Process DiffEvent (e.g., submit comment, fetch context)
 //! }
 //! ```
+//!
+//! `examples/git_diff.rs` hosts the viewer on a local `git diff`, with context
+//! expansion from the repository and commenting disabled.
 
 pub mod action;
 pub mod event;
@@ -56,12 +59,13 @@ pub use action::DiffAction;
 pub use event::DiffEvent;
 pub use highlight::DiffHighlighter;
 pub use model::{
-    CommentPosition, DiffLine, DiffSide, FileDiff, FileStatus, Hunk, LineKind, PendingComment,
-    PullRequestDiff, ReviewEvent,
+    BlameEntry, CommentPosition, DiffLine, DiffSide, FileDiff, FileStatus, Hunk, LineKind,
+    PendingComment, PullRequestDiff, ReviewEvent,
 };
 pub use parser::parse_unified_diff;
 pub use state::DiffViewerState;
 pub use traits::{
-    CommentError, CommentHandler, ContextError, ContextProvider, DefaultTheme, ThemeProvider,
+    BlameError, BlameProvider, CommentError, CommentHandler, ContextError, ContextProvider,
+    DefaultTheme, ThemeProvider,
 };
 pub use widget::{DiffViewer, FooterHint};
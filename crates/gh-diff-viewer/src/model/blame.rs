@@ -0,0 +1,84 @@
+//! Blame information for lines of the base file.
+
+use std::time::Duration;
+
+/// Who last touched a line, and when.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameEntry {
+    /// 1-indexed line number in the blamed file.
+    pub line: u32,
+    /// Author name (or login).
+    pub author: String,
+    /// SHA of the commit that last touched the line.
+    pub commit: String,
+    /// Time since that commit.
+    pub age: Duration,
+}
+
+impl BlameEntry {
+    /// Author initials, e.g. "JD" for "Jane Doe", "OC" for "octocat".
+    pub fn initials(&self) -> String {
+        let words: Vec<&str> = self
+            .author
+            .split(|c: char| c.is_whitespace() || matches!(c, '-' | '_' | '.'))
+            .filter(|word| !word.is_empty())
+            .collect();
+        let initials: String = match words.as_slice() {
+            [] => return "?".to_string(),
+            [single] => single.chars().take(2).collect(),
+            [first, .., last] => first.chars().take(1).chain(last.chars().take(1)).collect(),
+        };
+        initials.to_uppercase()
+    }
+
+    /// Compact age, e.g. "5m", "3h", "2d", "4w", "5mo", "2y".
+    pub fn short_age(&self) -> String {
+        const MINUTE: u64 = 60;
+        const HOUR: u64 = 60 * MINUTE;
+        const DAY: u64 = 24 * HOUR;
+        const WEEK: u64 = 7 * DAY;
+        const MONTH: u64 = 30 * DAY;
+        const YEAR: u64 = 365 * DAY;
+
+        let secs = self.age.as_secs();
+        match secs {
+            s if s >= YEAR => format!("{}y", s / YEAR),
+            s if s >= MONTH => format!("{}mo", s / MONTH),
+            s if s >= WEEK => format!("{}w", s / WEEK),
+            s if s >= DAY => format!("{}d", s / DAY),
+            s if s >= HOUR => format!("{}h", s / HOUR),
+            s => format!("{}m", s / MINUTE),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(author: &str, age_secs: u64) -> BlameEntry {
+        BlameEntry {
+            line: 1,
+            author: author.to_string(),
+            commit: "abc123".to_string(),
+            age: Duration::from_secs(age_secs),
+        }
+    }
+
+    #[test]
+    fn test_initials() {
+        assert_eq!(entry("Jane Doe", 0).initials(), "JD");
+        assert_eq!(entry("Jane van der Berg", 0).initials(), "JB");
+        assert_eq!(entry("octocat", 0).initials(), "OC");
+        assert_eq!(entry("", 0).initials(), "?");
+    }
+
+    #[test]
+    fn test_short_age() {
+        assert_eq!(entry("a", 30).short_age(), "0m");
+        assert_eq!(entry("a", 3 * 3600).short_age(), "3h");
+        assert_eq!(entry("a", 2 * 86400).short_age(), "2d");
+        assert_eq!(entry("a", 60 * 86400).short_age(), "2mo");
+        assert_eq!(entry("a", 800 * 86400).short_age(), "2y");
+    }
+}
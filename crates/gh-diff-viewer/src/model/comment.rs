@@ -18,6 +18,8 @@ pub struct PendingComment {
     pub body: String,
     /// When the comment was created locally.
     pub created_at: DateTime<Utc>,
+    /// Written against an older revision of the PR, so the position may be off.
+    pub outdated: bool,
 }
 
 impl PendingComment {
@@ -34,6 +36,7 @@ impl PendingComment {
             position,
             body: body.into(),
             created_at: Utc::now(),
+            outdated: false,
         }
     }
 
@@ -51,48 +54,131 @@ impl PendingComment {
             position,
             body: body.into(),
             created_at: Utc::now(),
+            outdated: false,
         }
     }
 }
 
 /// Where the comment is anchored in the diff.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct CommentPosition {
-    /// Which side of the diff (for split view / GitHub API).
-    pub side: DiffSide,
-    /// Line number (in the respective file version).
-    pub line: u32,
-    /// For multi-line comments: starting line.
-    pub start_line: Option<u32>,
+pub enum CommentPosition {
+    /// One or more lines of a file.
+    Line {
+        /// Which side of the diff (for split view / GitHub API).
+        side: DiffSide,
+        /// Line number (in the respective file version).
+        line: u32,
+        /// For multi-line comments: starting line.
+        start_line: Option<u32>,
+        /// For multi-line comments: side of the starting line, which differs
+        /// from `side` when the range starts on a deleted line and ends on
+        /// an added or unchanged one.
+        start_side: Option<DiffSide>,
+    },
+    /// A whole file, without a line.
+    File(String),
+    /// The pull request, without a location.
+    General,
 }
 
 impl CommentPosition {
     /// Create a single-line comment position.
     pub fn single(side: DiffSide, line: u32) -> Self {
-        Self {
+        Self::Line {
             side,
             line,
             start_line: None,
+            start_side: None,
         }
     }
 
     /// Create a multi-line comment position.
     pub fn range(side: DiffSide, start_line: u32, end_line: u32) -> Self {
-        Self {
-            side,
-            line: end_line,
-            start_line: Some(start_line),
+        Self::span((side, start_line), (side, end_line))
+    }
+
+    /// Create a comment position from its first to its last line.
+    ///
+    /// Both ends are `(side, line)` pairs, so a range may start on a deleted
+    /// line (left side) and end on an added one (right side). A range of a
+    /// single line is a single-line position.
+    pub fn span(start: (DiffSide, u32), end: (DiffSide, u32)) -> Self {
+        if start == end {
+            return Self::single(end.0, end.1);
+        }
+        Self::Line {
+            side: end.0,
+            line: end.1,
+            start_line: Some(start.1),
+            start_side: Some(start.0),
         }
     }
 
     /// Check if this is a multi-line comment.
     pub fn is_multiline(&self) -> bool {
-        self.start_line.is_some()
+        matches!(
+            self,
+            Self::Line {
+                start_line: Some(_),
+                ..
+            }
+        )
     }
 
-    /// Get the line range as (start, end).
-    pub fn line_range(&self) -> (u32, u32) {
-        (self.start_line.unwrap_or(self.line), self.line)
+    /// Get the (last) line of a line comment.
+    pub fn line(&self) -> Option<u32> {
+        match self {
+            Self::Line { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    /// Get the diff side of a line comment.
+    pub fn side(&self) -> Option<DiffSide> {
+        match self {
+            Self::Line { side, .. } => Some(*side),
+            _ => None,
+        }
+    }
+
+    /// Get the line range of a line comment as (start, end).
+    pub fn line_range(&self) -> Option<(u32, u32)> {
+        match self {
+            Self::Line {
+                line, start_line, ..
+            } => Some((start_line.unwrap_or(*line), *line)),
+            _ => None,
+        }
+    }
+
+    /// Check if this anchors the given line of a file.
+    ///
+    /// File and general comments never match a line, even when they
+    /// share the path of a line comment.
+    pub fn is_on_line(&self, line: u32) -> bool {
+        self.line() == Some(line)
+    }
+
+    /// Check if this anchors the given line of the given side.
+    ///
+    /// Line numbers of the two sides count different file versions, so
+    /// line 5 of the old file is not line 5 of the new one.
+    pub fn is_on(&self, side: DiffSide, line: u32) -> bool {
+        self.side() == Some(side) && self.is_on_line(line)
+    }
+
+    /// Short description for titles (e.g., "line 42", "lines 10-20", "file").
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Line {
+                line,
+                start_line: Some(start),
+                ..
+            } => format!("lines {}-{}", start, line),
+            Self::Line { line, .. } => format!("line {}", line),
+            Self::File(_) => "file".to_string(),
+            Self::General => "pull request".to_string(),
+        }
     }
 }
 
@@ -145,14 +231,47 @@ mod tests {
     fn test_comment_position_single() {
         let pos = CommentPosition::single(DiffSide::Right, 42);
         assert!(!pos.is_multiline());
-        assert_eq!(pos.line_range(), (42, 42));
+        assert_eq!(pos.line_range(), Some((42, 42)));
     }
 
     #[test]
     fn test_comment_position_multiline() {
         let pos = CommentPosition::range(DiffSide::Right, 10, 20);
         assert!(pos.is_multiline());
-        assert_eq!(pos.line_range(), (10, 20));
+        assert_eq!(pos.line_range(), Some((10, 20)));
+        assert_eq!(pos.describe(), "lines 10-20");
+    }
+
+    #[test]
+    fn test_comment_position_span() {
+        // Starting on a deleted line and ending on an added one
+        let pos = CommentPosition::span((DiffSide::Left, 7), (DiffSide::Right, 9));
+        assert_eq!(
+            pos,
+            CommentPosition::Line {
+                side: DiffSide::Right,
+                line: 9,
+                start_line: Some(7),
+                start_side: Some(DiffSide::Left),
+            }
+        );
+        assert!(pos.is_on(DiffSide::Right, 9));
+        assert!(!pos.is_on(DiffSide::Left, 9));
+
+        let single = CommentPosition::span((DiffSide::Left, 7), (DiffSide::Left, 7));
+        assert_eq!(single, CommentPosition::single(DiffSide::Left, 7));
+    }
+
+    #[test]
+    fn test_file_and_general_positions_have_no_line() {
+        let file = CommentPosition::File("src/lib.rs".to_string());
+        assert_eq!(file.line(), None);
+        assert_eq!(file.side(), None);
+        assert_eq!(file.line_range(), None);
+        assert!(!file.is_multiline());
+        assert!(!file.is_on_line(0));
+        assert!(!CommentPosition::General.is_on_line(0));
+        assert_eq!(CommentPosition::General.describe(), "pull request");
     }
 
     #[test]
@@ -1,5 +1,6 @@
 //! Diff data structures representing a pull request's changes.
 
+use super::DiffSide;
 use ratatui::style::Color;
 
 /// A complete diff for a pull request.
@@ -34,6 +35,31 @@ impl PullRequestDiff {
         self.total_additions = self.files.iter().map(|f| f.additions).sum();
         self.total_deletions = self.files.iter().map(|f| f.deletions).sum();
     }
+
+    /// Total line changes, e.g. `+12 −3`.
+    pub fn stat_text(&self) -> String {
+        format!("+{} −{}", self.total_additions, self.total_deletions)
+    }
+
+    /// Summary of the whole diff, e.g. `+12 −3 across 2 files`.
+    pub fn stat_summary(&self) -> String {
+        let files = self.files.len();
+        format!(
+            "{} across {} {}",
+            self.stat_text(),
+            files,
+            if files == 1 { "file" } else { "files" }
+        )
+    }
+
+    /// Largest number of changed lines in a single file.
+    pub fn max_file_changes(&self) -> usize {
+        self.files
+            .iter()
+            .map(|f| f.additions + f.deletions)
+            .max()
+            .unwrap_or(0)
+    }
 }
 
 /// Display info for a line: (hunk_index, optional_line_index).
@@ -55,6 +81,10 @@ pub struct FileDiff {
     pub additions: usize,
     /// Number of deleted lines.
     pub deletions: usize,
+    /// Whether git reported the content as binary (no hunks).
+    pub binary: bool,
+    /// File mode change as (old, new), e.g. `("100644", "100755")`.
+    pub mode_change: Option<(String, String)>,
 
     // === Cached state for rendering performance ===
     /// Cached flattened display info (hunk_idx, line_idx).
@@ -75,6 +105,8 @@ impl FileDiff {
             hunks: Vec::new(),
             additions: 0,
             deletions: 0,
+            binary: false,
+            mode_change: None,
             cached_display_info: None,
             cached_max_line_no: None,
             cached_display_name: None,
@@ -162,6 +194,26 @@ impl FileDiff {
             .count();
     }
 
+    /// Why a file has no hunks to show, e.g. for binary files or pure renames.
+    pub fn empty_reason(&self) -> Option<String> {
+        if !self.hunks.is_empty() {
+            return None;
+        }
+        let reason = if self.binary {
+            "Binary file not shown".to_string()
+        } else if let Some((old, new)) = &self.mode_change {
+            format!("File mode changed from {} to {}", old, new)
+        } else {
+            match self.status {
+                FileStatus::Renamed => "File renamed without changes".to_string(),
+                FileStatus::Copied => "File copied without changes".to_string(),
+                FileStatus::Added | FileStatus::Deleted => "Empty file".to_string(),
+                FileStatus::Modified => "No content changes".to_string(),
+            }
+        };
+        Some(reason)
+    }
+
     /// Get total number of displayable lines (for scrolling).
     pub fn total_lines(&self) -> usize {
         self.hunks.iter().map(|h| h.lines.len() + 1).sum() // +1 for hunk header
@@ -327,6 +379,21 @@ impl DiffLine {
     pub fn display_line_number(&self) -> Option<u32> {
         self.new_line.or(self.old_line)
     }
+
+    /// Get the side and line number GitHub anchors a comment on this line to.
+    ///
+    /// Deleted lines only exist in the old file, so they are anchored by their
+    /// old line number on the left side; added and unchanged lines by their new
+    /// line number on the right side. Markers have no anchor.
+    pub fn anchor(&self) -> Option<(DiffSide, u32)> {
+        match self.kind {
+            LineKind::Deletion => self.old_line.map(|line| (DiffSide::Left, line)),
+            LineKind::Addition | LineKind::Context => {
+                self.new_line.map(|line| (DiffSide::Right, line))
+            }
+            LineKind::HunkHeader | LineKind::Expansion => None,
+        }
+    }
 }
 
 /// Line type in the diff.
@@ -428,6 +495,45 @@ mod tests {
         assert_eq!(file.display_name(), "src/new.rs");
     }
 
+    #[test]
+    fn test_diff_line_anchor() {
+        assert_eq!(
+            DiffLine::deletion("old", 7).anchor(),
+            Some((DiffSide::Left, 7))
+        );
+        assert_eq!(
+            DiffLine::addition("new", 9).anchor(),
+            Some((DiffSide::Right, 9))
+        );
+        // Unchanged lines anchor on the new file even when numbers differ
+        assert_eq!(
+            DiffLine::context("same", 4, 6).anchor(),
+            Some((DiffSide::Right, 6))
+        );
+        assert_eq!(DiffLine::expansion_marker(10).anchor(), None);
+    }
+
+    #[test]
+    fn test_stat_summary() {
+        let mut diff = PullRequestDiff::new("base", "head");
+        assert_eq!(diff.stat_summary(), "+0 −0 across 0 files");
+
+        let mut changed = FileDiff::new("src/lib.rs");
+        changed.additions = 12;
+        changed.deletions = 3;
+        diff.files.push(changed);
+        diff.recalculate_totals();
+        assert_eq!(diff.stat_summary(), "+12 −3 across 1 file");
+
+        // Pure renames count as files without adding lines
+        let mut renamed = FileDiff::new("src/new.rs");
+        renamed.set_old_path(Some("src/old.rs".to_string()));
+        diff.files.push(renamed);
+        diff.recalculate_totals();
+        assert_eq!(diff.stat_summary(), "+12 −3 across 2 files");
+        assert_eq!(diff.max_file_changes(), 15);
+    }
+
     #[test]
     fn test_hunk_header_format() {
         let hunk = Hunk::new(10, 5, 10, 7);
@@ -1,14 +1,17 @@
 //! File tree model for navigation in the diff viewer.
 
 use super::{FileDiff, FileStatus};
+use std::collections::HashSet;
 
 /// Node in the file tree for navigation.
 #[derive(Debug, Clone)]
 pub struct FileTreeNode {
-    /// Display name (file or directory name).
+    /// Display name (file or directory name, `a/b` for compressed directories).
     pub name: String,
     /// Full path (for files, None for directories).
     pub path: Option<String>,
+    /// Full path of the node, also set for directories (e.g. `src/main/java`).
+    pub tree_path: String,
     /// Child nodes (for directories).
     pub children: Vec<FileTreeNode>,
     /// Whether this directory is expanded.
@@ -27,6 +30,7 @@ impl FileTreeNode {
         Self {
             name: name.into(),
             path: None,
+            tree_path: String::new(),
             children: Vec::new(),
             expanded: true,
             status: None,
@@ -37,9 +41,11 @@ impl FileTreeNode {
 
     /// Create a new file node.
     pub fn file(name: impl Into<String>, path: impl Into<String>, file_diff: &FileDiff) -> Self {
+        let path = path.into();
         Self {
             name: name.into(),
-            path: Some(path.into()),
+            tree_path: path.clone(),
+            path: Some(path),
             children: Vec::new(),
             expanded: false,
             status: Some(file_diff.status),
@@ -59,6 +65,10 @@ impl FileTreeNode {
 
         // Sort children: directories first, then files, both alphabetically
         root.sort_recursive();
+        // Render single-child directory chains as one node
+        root.compress_paths();
+        // Roll up file stats so directories show their aggregate
+        root.calculate_stats();
         root
     }
 
@@ -84,6 +94,11 @@ impl FileTreeNode {
                 dir.insert_path(&parts[1..], file_diff);
             } else {
                 let mut new_dir = FileTreeNode::directory(dir_name);
+                new_dir.tree_path = if self.tree_path.is_empty() {
+                    dir_name.to_string()
+                } else {
+                    format!("{}/{}", self.tree_path, dir_name)
+                };
                 new_dir.insert_path(&parts[1..], file_diff);
                 self.children.push(new_dir);
             }
@@ -107,6 +122,26 @@ impl FileTreeNode {
         }
     }
 
+    /// Merge directories whose only child is a directory into one node.
+    ///
+    /// `src/main/java/com/acme` with nothing but `acme` below `com` etc. becomes
+    /// a single `src/main/java/com/acme` node. The (unnamed) root is never merged.
+    fn compress_paths(&mut self) {
+        for child in &mut self.children {
+            if !child.is_directory() {
+                continue;
+            }
+            while child.children.len() == 1 && child.children[0].is_directory() {
+                let only = child.children.remove(0);
+                child.name = format!("{}/{}", child.name, only.name);
+                child.tree_path = only.tree_path;
+                child.expanded = only.expanded;
+                child.children = only.children;
+            }
+            child.compress_paths();
+        }
+    }
+
     /// Check if this node is a directory.
     pub fn is_directory(&self) -> bool {
         self.path.is_none()
@@ -138,6 +173,7 @@ impl FileTreeNode {
                 depth,
                 name: self.name.clone(),
                 path: self.path.clone(),
+                tree_path: self.tree_path.clone(),
                 is_dir: self.is_directory(),
                 is_expanded: self.expanded,
                 status: self.status,
@@ -179,24 +215,82 @@ impl FileTreeNode {
         }
     }
 
-    /// Find a node by path and toggle its expanded state.
+    /// Find a directory by its tree path and toggle its expanded state.
     pub fn toggle_at_path(&mut self, target_path: &str) -> bool {
-        if self.path.as_deref() == Some(target_path) {
-            return false; // Files can't be toggled
+        match self.find_directory_mut(target_path) {
+            Some(dir) => {
+                dir.toggle();
+                true
+            }
+            None => false,
         }
+    }
 
-        // Check if this directory's "virtual path" matches
-        // For now, just recurse and toggle matching dirs by name
+    /// Expand or collapse the directory at `target_path`.
+    /// Returns whether the directory exists and changed.
+    pub fn set_expanded(&mut self, target_path: &str, expanded: bool) -> bool {
+        match self.find_directory_mut(target_path) {
+            Some(dir) if dir.expanded != expanded => {
+                dir.expanded = expanded;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn find_directory_mut(&mut self, target_path: &str) -> Option<&mut FileTreeNode> {
         for child in &mut self.children {
-            if child.is_directory() && child.name == target_path {
-                child.toggle();
-                return true;
+            if !child.is_directory() {
+                continue;
+            }
+            if child.tree_path == target_path {
+                return Some(child);
             }
-            if child.toggle_at_path(target_path) {
-                return true;
+            if is_ancestor(&child.tree_path, target_path) {
+                return child.find_directory_mut(target_path);
             }
         }
-        false
+        None
+    }
+
+    /// Expand all directories above the file at `file_path`.
+    /// Returns whether any directory was expanded.
+    pub fn reveal(&mut self, file_path: &str) -> bool {
+        let mut changed = false;
+        for child in &mut self.children {
+            if child.is_directory() && is_ancestor(&child.tree_path, file_path) {
+                if !child.expanded {
+                    child.expanded = true;
+                    changed = true;
+                }
+                changed |= child.reveal(file_path);
+            }
+        }
+        changed
+    }
+
+    /// Tree paths of all collapsed directories.
+    pub fn collapsed_paths(&self) -> HashSet<String> {
+        let mut paths = HashSet::new();
+        self.collect_collapsed(&mut paths);
+        paths
+    }
+
+    fn collect_collapsed(&self, paths: &mut HashSet<String>) {
+        for child in self.children.iter().filter(|c| c.is_directory()) {
+            if !child.expanded {
+                paths.insert(child.tree_path.clone());
+            }
+            child.collect_collapsed(paths);
+        }
+    }
+
+    /// Collapse exactly the directories in `collapsed`, expanding all others.
+    pub fn apply_collapsed(&mut self, collapsed: &HashSet<String>) {
+        for child in self.children.iter_mut().filter(|c| c.is_directory()) {
+            child.expanded = !collapsed.contains(&child.tree_path);
+            child.apply_collapsed(collapsed);
+        }
     }
 
     /// Calculate aggregate stats for directories.
@@ -223,6 +317,78 @@ impl FileTreeNode {
     }
 }
 
+/// Whether `path` lies below the directory `dir`.
+fn is_ancestor(dir: &str, path: &str) -> bool {
+    path.len() > dir.len() && path.starts_with(dir) && path.as_bytes()[dir.len()] == b'/'
+}
+
+/// Elide the middle of `name` so it fits into `width` cells.
+///
+/// The file extension is kept whenever it leaves room for part of the stem,
+/// e.g. `VeryLongControllerFactory.java` becomes `VeryL…ctory.java`.
+pub fn elide_middle(name: &str, width: usize) -> String {
+    if gh_text_width::width(name) <= width {
+        return name.to_string();
+    }
+    if width <= 1 {
+        return gh_text_width::ELLIPSIS.repeat(width);
+    }
+
+    // Keep the extension (not for dotfiles) if at most half the width
+    let ext = name
+        .rfind('.')
+        .filter(|&dot| dot > 0)
+        .map(|dot| &name[dot..])
+        .filter(|ext| gh_text_width::width(ext) <= width / 2)
+        .unwrap_or("");
+    let stem = &name[..name.len() - ext.len()];
+
+    let budget = width - gh_text_width::width(ext) - 1;
+    let head = gh_text_width::clip(stem, budget.div_ceil(2));
+    // A wide character cut off the head leaves its cell to the tail
+    let tail = gh_text_width::clip_end(&stem[head.len()..], budget - gh_text_width::width(head));
+    format!("{}{}{}{}", head, gh_text_width::ELLIPSIS, tail, ext)
+}
+
+/// Split a stat bar of at most `width` cells into (addition, deletion) cells.
+///
+/// Like git's `--stat` bars, the bar length is proportional to the number of
+/// changed lines relative to `max_changes`, the largest change in the diff.
+/// A changed file always gets at least one cell, and each non-zero side keeps
+/// a cell as long as the bar has room for both.
+pub fn stat_bar(
+    additions: usize,
+    deletions: usize,
+    max_changes: usize,
+    width: usize,
+) -> (usize, usize) {
+    let total = additions + deletions;
+    if total == 0 || max_changes == 0 || width == 0 {
+        return (0, 0);
+    }
+
+    let cells = (total * width).div_ceil(max_changes).clamp(1, width);
+    let mut plus = (additions * cells + total / 2) / total;
+    if additions > 0 && plus == 0 {
+        plus = 1;
+    }
+    if deletions > 0 && plus == cells && cells > 1 {
+        plus -= 1;
+    }
+    (plus, cells - plus)
+}
+
+/// Width of the stat bar for a file tree pane `pane_width` cells wide.
+///
+/// Narrow panes drop the bar so file names keep their room.
+pub fn stat_bar_width(pane_width: usize) -> usize {
+    if pane_width < 24 {
+        0
+    } else {
+        (pane_width / 5).min(10)
+    }
+}
+
 /// A flattened file tree entry for rendering.
 #[derive(Debug, Clone)]
 pub struct FlatFileEntry {
@@ -232,6 +398,8 @@ pub struct FlatFileEntry {
     pub name: String,
     /// Full path (for files, None for directories).
     pub path: Option<String>,
+    /// Full path of the node, also set for directories.
+    pub tree_path: String,
     /// Whether this is a directory.
     pub is_dir: bool,
     /// Whether this directory is expanded.
@@ -337,6 +505,76 @@ mod tests {
         assert_eq!(flat[1].name, "lib.rs");
     }
 
+    #[test]
+    fn test_directory_stats_roll_up() {
+        let files = vec![
+            make_file_diff("src/main.rs", 10, 5),
+            make_file_diff("src/util/mod.rs", 3, 1),
+            make_file_diff("src/util/old_name.rs", 0, 0), // pure rename
+            make_file_diff("Cargo.toml", 2, 1),
+        ];
+
+        let tree = FileTreeNode::from_files(&files);
+        let src = &tree.children[0];
+        assert_eq!((src.additions, src.deletions), (13, 6));
+        let util = &src.children[0];
+        assert_eq!(util.name, "util");
+        assert_eq!((util.additions, util.deletions), (3, 1));
+        assert_eq!((tree.additions, tree.deletions), (15, 7));
+
+        // Collapsed directories keep their aggregate in the flat entries
+        let mut tree = tree;
+        tree.children[0].toggle();
+        let flat = tree.flatten();
+        assert_eq!((flat[0].additions, flat[0].deletions), (13, 6));
+    }
+
+    #[test]
+    fn test_directory_of_renames_has_no_stats() {
+        let files = vec![make_file_diff("docs/moved.md", 0, 0)];
+
+        let tree = FileTreeNode::from_files(&files);
+        let docs = &tree.children[0];
+        assert_eq!((docs.additions, docs.deletions), (0, 0));
+    }
+
+    #[test]
+    fn test_stat_bar_scales_to_largest_change() {
+        // The largest file fills the bar
+        assert_eq!(stat_bar(10, 5, 15, 5), (3, 2));
+        assert_eq!(stat_bar(20, 0, 20, 10), (10, 0));
+        assert_eq!(stat_bar(0, 20, 20, 10), (0, 10));
+        // Smaller files get a proportional share, rounded up
+        assert_eq!(stat_bar(3, 1, 15, 5), (1, 1));
+        assert_eq!(stat_bar(2, 1, 3, 5), (3, 2));
+        // Tiny changes still show one cell
+        assert_eq!(stat_bar(1, 0, 1000, 10), (1, 0));
+        assert_eq!(stat_bar(0, 1, 1000, 10), (0, 1));
+        // A one-cell bar favors additions
+        assert_eq!(stat_bar(1, 1, 1000, 10), (1, 0));
+    }
+
+    #[test]
+    fn test_stat_bar_never_exceeds_width() {
+        // Directory aggregates can exceed the largest file
+        assert_eq!(stat_bar(40, 40, 20, 6), (3, 3));
+        assert_eq!(stat_bar(99, 1, 100, 4), (3, 1));
+    }
+
+    #[test]
+    fn test_stat_bar_zero_change_rename() {
+        assert_eq!(stat_bar(0, 0, 15, 5), (0, 0));
+        assert_eq!(stat_bar(0, 0, 0, 5), (0, 0));
+        assert_eq!(stat_bar(2, 1, 3, 0), (0, 0));
+    }
+
+    #[test]
+    fn test_stat_bar_width() {
+        assert_eq!(stat_bar_width(16), 0);
+        assert_eq!(stat_bar_width(29), 5);
+        assert_eq!(stat_bar_width(80), 10);
+    }
+
     #[test]
     fn test_file_tree_collapse() {
         let files = vec![
@@ -356,12 +594,159 @@ mod tests {
         assert_eq!(flat.len(), 1); // just src/
     }
 
+    #[test]
+    fn test_single_child_directories_are_compressed() {
+        let files = vec![
+            make_file_diff("src/main/java/com/acme/App.java", 1, 0),
+            make_file_diff("src/main/java/com/acme/util/Strings.java", 1, 0),
+            make_file_diff("src/test/java/com/acme/AppTest.java", 1, 0),
+        ];
+
+        let tree = FileTreeNode::from_files(&files);
+        let flat = tree.flatten();
+        let names: Vec<(usize, &str)> = flat.iter().map(|e| (e.depth, e.name.as_str())).collect();
+        assert_eq!(
+            names,
+            vec![
+                (0, "src"),
+                (1, "main/java/com/acme"),
+                (2, "util"),
+                (3, "Strings.java"),
+                (2, "App.java"),
+                (1, "test/java/com/acme"),
+                (2, "AppTest.java"),
+            ]
+        );
+        assert_eq!(flat[1].tree_path, "src/main/java/com/acme");
+        assert_eq!(flat[1].path, None);
+        assert_eq!(
+            flat[4].path.as_deref(),
+            Some("src/main/java/com/acme/App.java")
+        );
+    }
+
+    #[test]
+    fn test_directory_with_single_file_is_not_compressed() {
+        let files = vec![make_file_diff("docs/guide/intro.md", 1, 0)];
+
+        let tree = FileTreeNode::from_files(&files);
+        let flat = tree.flatten();
+        assert_eq!(flat.len(), 2);
+        assert_eq!(flat[0].name, "docs/guide");
+        assert_eq!(flat[1].name, "intro.md");
+    }
+
+    #[test]
+    fn test_collapse_by_tree_path() {
+        let files = vec![
+            make_file_diff("src/a/one.rs", 1, 0),
+            make_file_diff("src/a/two.rs", 1, 0),
+            make_file_diff("src/b/three.rs", 1, 0),
+        ];
+        let mut tree = FileTreeNode::from_files(&files);
+        assert_eq!(tree.flatten().len(), 6);
+
+        // Directories with the same name elsewhere are not affected
+        assert!(tree.toggle_at_path("src/a"));
+        let flat = tree.flatten();
+        assert_eq!(flat.len(), 4);
+        assert!(!flat[1].is_expanded);
+        assert_eq!(flat[2].tree_path, "src/b");
+
+        assert!(!tree.set_expanded("src/a", false));
+        assert!(tree.set_expanded("src/a", true));
+        assert!(!tree.toggle_at_path("src/a/one.rs"));
+        assert!(!tree.toggle_at_path("missing"));
+        assert_eq!(tree.flatten().len(), 6);
+    }
+
+    #[test]
+    fn test_reveal_expands_ancestors() {
+        let files = vec![
+            make_file_diff("src/a/one.rs", 1, 0),
+            make_file_diff("src/a/two.rs", 1, 0),
+            make_file_diff("src/b.rs", 1, 0),
+        ];
+        let mut tree = FileTreeNode::from_files(&files);
+        tree.set_expanded("src/a", false);
+        tree.set_expanded("src", false);
+        assert_eq!(tree.flatten().len(), 1);
+
+        assert!(tree.reveal("src/a/two.rs"));
+        let paths: Vec<String> = tree.file_paths();
+        assert!(paths.contains(&"src/a/two.rs".to_string()));
+        // Already visible
+        assert!(!tree.reveal("src/a/two.rs"));
+        // `src/ab.rs` does not live in `src/a`
+        assert!(!FileTreeNode::from_files(&files).reveal("src/ab.rs"));
+    }
+
+    #[test]
+    fn test_collapsed_paths_round_trip() {
+        let files = vec![
+            make_file_diff("src/a/one.rs", 1, 0),
+            make_file_diff("src/a/two.rs", 1, 0),
+            make_file_diff("src/b.rs", 1, 0),
+        ];
+        let mut tree = FileTreeNode::from_files(&files);
+        tree.set_expanded("src/a", false);
+        let collapsed = tree.collapsed_paths();
+        assert_eq!(collapsed, HashSet::from(["src/a".to_string()]));
+
+        // A rebuilt tree of the same diff gets the same collapse state
+        let mut rebuilt = FileTreeNode::from_files(&files);
+        rebuilt.apply_collapsed(&collapsed);
+        assert_eq!(rebuilt.flatten().len(), tree.flatten().len());
+        assert_eq!(rebuilt.collapsed_paths(), collapsed);
+    }
+
+    #[test]
+    fn test_elide_middle_keeps_extension() {
+        assert_eq!(elide_middle("lib.rs", 10), "lib.rs");
+        assert_eq!(
+            elide_middle("VeryLongControllerFactory.java", 16),
+            "VeryL…ctory.java"
+        );
+        assert_eq!(
+            elide_middle("VeryLongControllerFactory.java", 16)
+                .chars()
+                .count(),
+            16
+        );
+        // Extensions too long for the width are elided like the rest
+        assert_eq!(elide_middle("archive.extension", 8), "arch…ion");
+        // Dotfiles have no extension
+        assert_eq!(elide_middle(".gitignore_global", 7), ".gi…bal");
+        assert_eq!(elide_middle("main/java/com/acme", 9), "main…acme");
+    }
+
+    #[test]
+    fn test_elide_middle_measures_cells() {
+        // Wide characters take two cells each
+        // 設定 does not fit the 3 cells of the head, the tail gets the spare one
+        assert_eq!(elide_middle("設定ファイル.toml", 12), "設…イル.toml");
+        assert_eq!(
+            gh_text_width::width(&elide_middle("設定ファイル.toml", 12)),
+            12
+        );
+        assert_eq!(elide_middle("🚀launch.md", 9), "🚀l…ch.md");
+        assert_eq!(elide_middle("設定.rs", 7), "設定.rs");
+    }
+
+    #[test]
+    fn test_elide_middle_tiny_widths() {
+        assert_eq!(elide_middle("Makefile", 1), "…");
+        assert_eq!(elide_middle("Makefile", 0), "");
+        assert_eq!(elide_middle("a.rs", 2), "a…");
+    }
+
     #[test]
     fn test_flat_entry_icon() {
         let dir = FlatFileEntry {
             depth: 0,
             name: "src".to_string(),
             path: None,
+            tree_path: "src".to_string(),
             is_dir: true,
             is_expanded: true,
             status: None,
@@ -393,6 +778,7 @@ mod tests {
             depth: 0,
             name: "src".to_string(),
             path: None,
+            tree_path: "src".to_string(),
             is_dir: true,
             is_expanded: true,
             status: None,
@@ -408,6 +794,7 @@ mod tests {
             depth: 1,
             name: "lib.rs".to_string(),
             path: Some("src/lib.rs".to_string()),
+            tree_path: "src/lib.rs".to_string(),
             is_dir: false,
             is_expanded: false,
             status: None,
@@ -430,6 +817,7 @@ mod tests {
             depth: 2,
             name: "mod.rs".to_string(),
             path: Some("src/utils/mod.rs".to_string()),
+            tree_path: "src/utils/mod.rs".to_string(),
             is_dir: false,
             is_expanded: false,
             status: None,
@@ -1,12 +1,14 @@
 //! Data models for diff representation.
 
+mod blame;
 mod comment;
 mod diff;
 mod file_tree;
 
+pub use blame::BlameEntry;
 pub use comment::{CommentPosition, DiffSide, PendingComment, ReviewEvent};
 pub use diff::{
     DiffLine, DisplayLineInfo, FileDiff, FileStatus, HighlightedSpan, Hunk, LineKind,
     PullRequestDiff,
 };
-pub use file_tree::{FileTreeNode, FlatFileEntry};
+pub use file_tree::{elide_middle, stat_bar, stat_bar_width, FileTreeNode, FlatFileEntry};
@@ -1,8 +1,15 @@
-//! Parse unified diff format (as returned by GitHub API).
-
-use crate::model::{DiffLine, FileDiff, FileStatus, Hunk, LineKind, PullRequestDiff};
+//! Parse unified diff format (as returned by the GitHub API or `git diff`).
+//!
+//! Git's extended headers are read, so files without hunks (mode changes,
+//! pure renames, binary files) are kept. Hunk bodies are read by the line
+//! counts of their header, so a removed `-- comment` or an added `++ counter`
+//! is never taken for a file header. Combined diffs of merge conflicts
+//! (`diff --cc`) are skipped.
+
+use crate::model::{DiffLine, FileDiff, FileStatus, Hunk, PullRequestDiff};
+use std::iter::{Enumerate, Peekable};
+use std::str::Lines;
 use thiserror::Error;
-use unidiff::{Hunk as UnidiffHunk, Line as UnidiffLine, PatchSet, PatchedFile};
 
 /// Errors that can occur during diff parsing.
 #[derive(Debug, Error)]
@@ -13,6 +20,12 @@ pub enum ParseError {
     InvalidFormat,
 }
 
+/// Path git uses for the missing side of added and deleted files.
+const DEV_NULL: &str = "/dev/null";
+
+/// Diff lines with their index, for error messages.
+type DiffLines<'a> = Peekable<Enumerate<Lines<'a>>>;
+
 /// Parse a unified diff string into a structured `PullRequestDiff`.
 ///
 /// # Arguments
@@ -30,103 +43,286 @@ pub fn parse_unified_diff(
     base_sha: impl Into<String>,
     head_sha: impl Into<String>,
 ) -> Result<PullRequestDiff, ParseError> {
-    let mut patch_set = PatchSet::new();
-    patch_set
-        .parse(diff_text)
-        .map_err(|e| ParseError::ParseFailed(e.to_string()))?;
-
     let mut diff = PullRequestDiff::new(base_sha, head_sha);
-
-    for patched_file in patch_set.files() {
-        let file_diff = parse_patched_file(patched_file)?;
-        diff.files.push(file_diff);
+    let mut lines = diff_text.lines().enumerate().peekable();
+    let mut file: Option<FileHeader> = None;
+    // Inside a combined diff, which is skipped up to the next file
+    let mut combined = false;
+
+    while let Some((index, line)) = lines.next() {
+        if line.starts_with("diff ") {
+            diff.files.extend(file.take().map(FileHeader::finish));
+            combined = line.starts_with("diff --cc ") || line.starts_with("diff --combined ");
+            file = line
+                .strip_prefix("diff --git ")
+                .map(FileHeader::from_git_line);
+            continue;
+        }
+        if combined {
+            continue;
+        }
+
+        if let Some(ranges) = line.strip_prefix("@@ ") {
+            let current = file.as_mut().ok_or(ParseError::InvalidFormat)?;
+            let hunk = parse_hunk(ranges, &mut lines).ok_or_else(|| {
+                ParseError::ParseFailed(format!("invalid hunk header on line {}", index + 1))
+            })?;
+            current.hunks.push(hunk);
+        } else if let Some(old_path) = line.strip_prefix("--- ") {
+            let Some(new_path) = lines.peek().and_then(|(_, next)| next.strip_prefix("+++ "))
+            else {
+                continue;
+            };
+            lines.next();
+            // Plain `diff -u` output has no `diff --git` line between files
+            if file
+                .as_ref()
+                .is_none_or(|file| file.has_markers || !file.hunks.is_empty())
+            {
+                diff.files.extend(file.take().map(FileHeader::finish));
+            }
+            let current = file.get_or_insert_with(FileHeader::default);
+            current.old_path = Some(parse_marker_path(old_path));
+            current.new_path = Some(parse_marker_path(new_path));
+            current.has_markers = true;
+        } else if let Some(current) = file.as_mut() {
+            current.apply_extended_header(line);
+        }
     }
 
+    diff.files.extend(file.map(FileHeader::finish));
     diff.recalculate_totals();
     Ok(diff)
 }
 
-fn parse_patched_file(file: &PatchedFile) -> Result<FileDiff, ParseError> {
-    let target = clean_path(&file.target_file);
-    let source = clean_path(&file.source_file);
-
-    let mut file_diff = FileDiff::new(&target);
+/// What the headers tell about a file, collected up to its last hunk.
+#[derive(Debug, Default)]
+struct FileHeader {
+    old_path: Option<String>,
+    new_path: Option<String>,
+    status: Option<FileStatus>,
+    old_mode: Option<String>,
+    new_mode: Option<String>,
+    binary: bool,
+    /// Whether the `---`/`+++` lines were seen
+    has_markers: bool,
+    hunks: Vec<Hunk>,
+}
 
-    // Determine file status
-    file_diff.status = determine_status(&source, &target, file);
+impl FileHeader {
+    /// Start a file from the paths of a `diff --git a/old b/new` line.
+    fn from_git_line(paths: &str) -> Self {
+        let (old_path, new_path) = split_git_paths(paths).unzip();
+        Self {
+            old_path,
+            new_path,
+            ..Self::default()
+        }
+    }
 
-    // Set old path if different
-    if source != target && !source.is_empty() && source != "/dev/null" {
-        file_diff.old_path = Some(source);
+    /// Apply a git extended header line, other lines are ignored.
+    fn apply_extended_header(&mut self, line: &str) {
+        if line.starts_with("new file mode ") {
+            self.status = Some(FileStatus::Added);
+        } else if line.starts_with("deleted file mode ") {
+            self.status = Some(FileStatus::Deleted);
+        } else if let Some(mode) = line.strip_prefix("old mode ") {
+            self.old_mode = Some(mode.to_string());
+        } else if let Some(mode) = line.strip_prefix("new mode ") {
+            self.new_mode = Some(mode.to_string());
+        } else if let Some(path) = line.strip_prefix("rename from ") {
+            self.status = Some(FileStatus::Renamed);
+            self.old_path = Some(unquote(path));
+        } else if let Some(path) = line.strip_prefix("rename to ") {
+            self.new_path = Some(unquote(path));
+        } else if let Some(path) = line.strip_prefix("copy from ") {
+            self.status = Some(FileStatus::Copied);
+            self.old_path = Some(unquote(path));
+        } else if let Some(path) = line.strip_prefix("copy to ") {
+            self.new_path = Some(unquote(path));
+        } else if line.starts_with("Binary files ") || line == "GIT binary patch" {
+            self.binary = true;
+        }
     }
 
-    // Parse hunks
-    for hunk in file.hunks() {
-        file_diff.hunks.push(parse_hunk(hunk)?);
+    fn finish(self) -> FileDiff {
+        let old_path = self.old_path.filter(|path| path != DEV_NULL);
+        let new_path = self.new_path.filter(|path| path != DEV_NULL);
+        let status = self
+            .status
+            .unwrap_or_else(|| determine_status(old_path.as_deref(), new_path.as_deref()));
+
+        let path = new_path.or_else(|| old_path.clone()).unwrap_or_default();
+        let mut file_diff = FileDiff::new(&path);
+        file_diff.status = status;
+        // Set old path if different
+        file_diff.old_path = old_path.filter(|old| *old != path);
+        file_diff.binary = self.binary;
+        file_diff.mode_change = self.old_mode.zip(self.new_mode);
+        file_diff.hunks = self.hunks;
+        file_diff.recalculate_stats();
+        file_diff
     }
+}
 
-    file_diff.recalculate_stats();
-    Ok(file_diff)
+fn determine_status(old_path: Option<&str>, new_path: Option<&str>) -> FileStatus {
+    match (old_path, new_path) {
+        (None, _) => FileStatus::Added,
+        (_, None) => FileStatus::Deleted,
+        (Some(old), Some(new)) if old != new => FileStatus::Renamed,
+        _ => FileStatus::Modified,
+    }
 }
 
-fn parse_hunk(hunk: &UnidiffHunk) -> Result<Hunk, ParseError> {
-    let mut parsed = Hunk::new(
-        hunk.source_start as u32,
-        hunk.source_length as u32,
-        hunk.target_start as u32,
-        hunk.target_length as u32,
-    );
+/// Parse a hunk from the ranges after `@@ ` and read its body.
+fn parse_hunk(ranges: &str, lines: &mut DiffLines) -> Option<Hunk> {
+    let (ranges, section) = ranges.split_once(" @@")?;
+    let (old, new) = ranges.split_once(' ')?;
+    let (old_start, old_count) = parse_range(old.strip_prefix('-')?)?;
+    let (new_start, new_count) = parse_range(new.strip_prefix('+')?)?;
 
     // Extract function context from section header if available
-    let header = &hunk.section_header;
-    if !header.is_empty() {
-        parsed.header = format!(
-            "@@ -{},{} +{},{} @@ {}",
-            parsed.old_start, parsed.old_count, parsed.new_start, parsed.new_count, header
-        );
+    let section = section.trim_start();
+    let mut hunk = if section.is_empty() {
+        Hunk::new(old_start, old_count, new_start, new_count)
+    } else {
+        Hunk::with_context(old_start, old_count, new_start, new_count, section)
+    };
+    read_hunk_lines(&mut hunk, lines);
+    Some(hunk)
+}
+
+/// Parse `start,count` of a hunk range, the count defaults to 1.
+fn parse_range(range: &str) -> Option<(u32, u32)> {
+    match range.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
     }
+}
 
-    // Parse lines
-    for line in hunk.lines() {
-        parsed.lines.push(parse_line(line)?);
+/// Read the body of `hunk` until its line counts are used up.
+///
+/// Stops early at a line that cannot belong to the hunk. Empty lines count
+/// as context, as some tools strip the leading space of blank lines.
+fn read_hunk_lines(hunk: &mut Hunk, lines: &mut DiffLines) {
+    let (mut old_line, mut new_line) = (hunk.old_start, hunk.new_start);
+    let (mut old_left, mut new_left) = (hunk.old_count, hunk.new_count);
+
+    while let Some(&(_, line)) = lines.peek() {
+        // "\ No newline at end of file" only annotates the line before
+        if line.starts_with('\\') {
+            lines.next();
+            continue;
+        }
+        let parsed = match line.as_bytes().first() {
+            Some(b' ') | None if old_left > 0 && new_left > 0 => {
+                let content = line.get(1..).unwrap_or_default();
+                DiffLine::context(content, old_line, new_line)
+            }
+            Some(b'-') if old_left > 0 => DiffLine::deletion(&line[1..], old_line),
+            Some(b'+') if new_left > 0 => DiffLine::addition(&line[1..], new_line),
+            _ => break,
+        };
+        lines.next();
+        if parsed.old_line.is_some() {
+            old_line += 1;
+            old_left -= 1;
+        }
+        if parsed.new_line.is_some() {
+            new_line += 1;
+            new_left -= 1;
+        }
+        hunk.lines.push(parsed);
     }
+}
 
-    Ok(parsed)
+/// Split the paths of a `diff --git` line into (old, new).
+///
+/// Unquoted paths may contain spaces, so the split is ambiguous. Both sides
+/// name the same file unless it was renamed, in which case the `rename from`
+/// and `rename to` lines give the paths anyway.
+fn split_git_paths(paths: &str) -> Option<(String, String)> {
+    if let Some((old, rest)) = take_quoted(paths) {
+        return Some((clean_path(&old), parse_marker_path(rest.trim_start())));
+    }
+    if let Some(start) = paths.find(" \"") {
+        let new = take_quoted(&paths[start + 1..])?.0;
+        return Some((clean_path(&paths[..start]), clean_path(&new)));
+    }
+
+    let mut renamed = None;
+    for (at, _) in paths.match_indices(' ') {
+        let (old, new) = (clean_path(&paths[..at]), &paths[at + 1..]);
+        if old == clean_path(new) {
+            return Some((old, clean_path(new)));
+        }
+        if renamed.is_none() && new.starts_with("b/") {
+            renamed = Some((old, clean_path(new)));
+        }
+    }
+    renamed
 }
 
-fn parse_line(line: &UnidiffLine) -> Result<DiffLine, ParseError> {
-    let content = line.value.to_string();
-    let source_line = line.source_line_no.map(|n| n as u32);
-    let target_line = line.target_line_no.map(|n| n as u32);
-
-    let kind = match line.line_type.as_str() {
-        " " => LineKind::Context,
-        "+" => LineKind::Addition,
-        "-" => LineKind::Deletion,
-        "\\" => LineKind::Context, // "\ No newline at end of file"
-        _ => LineKind::Context,
-    };
+/// Path of a `---`/`+++` line, without git's prefix or a trailing timestamp.
+fn parse_marker_path(raw: &str) -> String {
+    match take_quoted(raw) {
+        Some((path, _)) => clean_path(&path),
+        // `diff -u` appends a timestamp and git a tab after names with spaces
+        None => clean_path(raw.split('\t').next().unwrap_or(raw)),
+    }
+}
 
-    Ok(DiffLine {
-        kind,
-        content,
-        old_line: source_line,
-        new_line: target_line,
-        is_expanded: false,
-        highlighted: None,
-    })
+/// Unquote a path of a `rename`/`copy` line (these have no prefix).
+fn unquote(path: &str) -> String {
+    take_quoted(path).map_or_else(|| path.to_string(), |(path, _)| path)
 }
 
-fn determine_status(source: &str, target: &str, _file: &PatchedFile) -> FileStatus {
-    if source == "/dev/null" || source.is_empty() {
-        FileStatus::Added
-    } else if target == "/dev/null" || target.is_empty() {
-        FileStatus::Deleted
-    } else if source != target {
-        FileStatus::Renamed
-    } else {
-        FileStatus::Modified
+/// Read a C-style quoted path at the start of `text`, returning the path and the rest.
+///
+/// Git quotes paths with special or non-ASCII characters, non-ASCII bytes are
+/// escaped as octal (`"caf\303\251.txt"`).
+fn take_quoted(text: &str) -> Option<(String, &str)> {
+    let quoted = text.strip_prefix('"')?;
+    let bytes = quoted.as_bytes();
+    let mut path = Vec::new();
+    let mut at = 0;
+    while at < bytes.len() {
+        match bytes[at] {
+            b'"' => {
+                return Some((
+                    String::from_utf8_lossy(&path).into_owned(),
+                    &quoted[at + 1..],
+                ))
+            }
+            b'\\' => {
+                let escaped = *bytes.get(at + 1)?;
+                at += 2;
+                path.push(match escaped {
+                    b'0'..=b'7' => {
+                        let digits = bytes.get(at - 1..at + 2)?;
+                        at += 2;
+                        digits.iter().try_fold(0u8, |value, digit| match digit {
+                            b'0'..=b'7' => Some(value.wrapping_mul(8) + (digit - b'0')),
+                            _ => None,
+                        })?
+                    }
+                    b'a' => 0x07,
+                    b'b' => 0x08,
+                    b't' => b'\t',
+                    b'n' => b'\n',
+                    b'v' => 0x0b,
+                    b'f' => 0x0c,
+                    b'r' => b'\r',
+                    other => other,
+                });
+            }
+            byte => {
+                path.push(byte);
+                at += 1;
+            }
+        }
     }
+    None
 }
 
 /// Clean the path by removing a/b prefixes from git diff output.
@@ -147,6 +343,29 @@ fn clean_path(path: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::LineKind;
+
+    /// `git diff` of a commit touching one file per kind of change
+    const GIT_COMMIT_RANGE: &str = include_str!("../../tests/fixtures/git-commit-range.diff");
+    /// `git diff` during a merge conflict, a combined diff then a plain one
+    const GIT_MERGE_CONFLICT: &str = include_str!("../../tests/fixtures/git-merge-conflict.diff");
+    /// `git diff --binary` of a changed binary file
+    const GIT_BINARY_PATCH: &str = include_str!("../../tests/fixtures/git-binary-patch.diff");
+
+    fn file<'a>(diff: &'a PullRequestDiff, path: &str) -> &'a FileDiff {
+        diff.files
+            .iter()
+            .find(|file| file.path == path)
+            .unwrap_or_else(|| panic!("no file {}", path))
+    }
+
+    fn contents(file: &FileDiff) -> Vec<(LineKind, &str)> {
+        file.hunks
+            .iter()
+            .flat_map(|hunk| &hunk.lines)
+            .map(|line| (line.kind, line.content.as_str()))
+            .collect()
+    }
 
     const SAMPLE_DIFF: &str = r#"diff --git a/src/main.rs b/src/main.rs
 index abc123..def456 100644
@@ -290,4 +509,164 @@ index abc123..def456 100644
         assert!(addition.old_line.is_none());
         assert!(addition.new_line.is_some());
     }
+
+    #[test]
+    fn test_git_diff_keeps_files_without_hunks() {
+        let diff = parse_unified_diff(GIT_COMMIT_RANGE, "base", "head").unwrap();
+        assert_eq!(diff.files.len(), 9);
+
+        let binary = file(&diff, "logo.png");
+        assert!(binary.binary);
+        assert_eq!(binary.status, FileStatus::Modified);
+        assert_eq!(
+            binary.empty_reason().as_deref(),
+            Some("Binary file not shown")
+        );
+
+        let renamed = file(&diff, "new_name.txt");
+        assert_eq!(renamed.status, FileStatus::Renamed);
+        assert_eq!(renamed.old_path.as_deref(), Some("old_name.txt"));
+        assert!(renamed.hunks.is_empty());
+
+        let executable = file(&diff, "script.sh");
+        assert_eq!(
+            executable.mode_change,
+            Some(("100644".to_string(), "100755".to_string()))
+        );
+        assert_eq!(
+            executable.empty_reason().as_deref(),
+            Some("File mode changed from 100644 to 100755")
+        );
+    }
+
+    #[test]
+    fn test_git_diff_added_and_deleted_files() {
+        let diff = parse_unified_diff(GIT_COMMIT_RANGE, "base", "head").unwrap();
+
+        let added = file(&diff, "added.txt");
+        assert_eq!(added.status, FileStatus::Added);
+        assert_eq!(added.old_path, None);
+        assert_eq!(contents(added), vec![(LineKind::Addition, "new")]);
+
+        let deleted = file(&diff, "removed.txt");
+        assert_eq!(deleted.status, FileStatus::Deleted);
+        assert_eq!(deleted.hunks[0].lines[0].old_line, Some(1));
+        assert_eq!(deleted.deletions, 1);
+    }
+
+    #[test]
+    fn test_dashes_inside_hunks_are_lines() {
+        let diff = parse_unified_diff(GIT_COMMIT_RANGE, "base", "head").unwrap();
+        let query = file(&diff, "query.sql");
+
+        assert_eq!(query.hunks.len(), 1);
+        assert_eq!(
+            contents(query),
+            vec![
+                (LineKind::Context, "keep"),
+                (LineKind::Deletion, "-- old sql comment"),
+                (LineKind::Addition, "++ new counter"),
+                (LineKind::Context, "keep2"),
+            ]
+        );
+        assert_eq!(query.hunks[0].lines[3].old_line, Some(3));
+        assert_eq!(query.hunks[0].lines[3].new_line, Some(3));
+    }
+
+    #[test]
+    fn test_no_newline_markers_are_dropped() {
+        let diff = parse_unified_diff(GIT_COMMIT_RANGE, "base", "head").unwrap();
+        assert_eq!(
+            contents(file(&diff, "no_eol.txt")),
+            vec![
+                (LineKind::Context, "x"),
+                (LineKind::Deletion, "y"),
+                (LineKind::Addition, "z"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_quoted_and_spaced_paths() {
+        let diff = parse_unified_diff(GIT_COMMIT_RANGE, "base", "head").unwrap();
+        assert_eq!(file(&diff, "café.txt").additions, 1);
+        assert_eq!(file(&diff, "with space.txt").additions, 1);
+        assert!(diff
+            .files
+            .iter()
+            .all(|file| file.old_path.is_none() || file.status == FileStatus::Renamed));
+
+        assert_eq!(
+            split_git_paths(r#""a/tab\there.txt" b/plain.txt"#),
+            Some(("tab\there.txt".to_string(), "plain.txt".to_string()))
+        );
+        assert_eq!(
+            split_git_paths("a/x b/y b/x b/y"),
+            Some(("x b/y".to_string(), "x b/y".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_combined_diff_is_skipped() {
+        let diff = parse_unified_diff(GIT_MERGE_CONFLICT, "base", "head").unwrap();
+
+        assert_eq!(diff.files.len(), 1);
+        let query = &diff.files[0];
+        assert_eq!(query.path, "query.sql");
+        assert_eq!(
+            contents(query),
+            vec![
+                (LineKind::Context, "keep"),
+                (LineKind::Context, "++ new counter"),
+                (LineKind::Deletion, "keep2"),
+                (LineKind::Addition, "keep3"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_binary_patch_has_no_lines() {
+        let diff = parse_unified_diff(GIT_BINARY_PATCH, "base", "head").unwrap();
+
+        assert_eq!(diff.files.len(), 1);
+        assert!(diff.files[0].binary);
+        assert!(diff.files[0].hunks.is_empty());
+    }
+
+    #[test]
+    fn test_plain_diff_u_output() {
+        let diff = "\
+--- config.toml\t2024-03-01 12:00:00.000000000 +0100
++++ config.toml\t2024-03-02 12:00:00.000000000 +0100
+@@ -1 +1 @@
+-port = 80
++port = 8080
+--- notes.txt\t2024-03-01 12:00:00.000000000 +0100
++++ notes.txt\t2024-03-02 12:00:00.000000000 +0100
+@@ -1,2 +1 @@
+ todo
+--- done
+";
+
+        let parsed = parse_unified_diff(diff, "base", "head").unwrap();
+        let paths: Vec<_> = parsed.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["config.toml", "notes.txt"]);
+        assert_eq!(
+            contents(&parsed.files[1]),
+            vec![(LineKind::Context, "todo"), (LineKind::Deletion, "-- done")]
+        );
+    }
+
+    #[test]
+    fn test_invalid_hunk_header_fails() {
+        let diff = "diff --git a/x b/x\n--- a/x\n+++ b/x\n@@ -1,x +1 @@\n";
+        assert!(matches!(
+            parse_unified_diff(diff, "base", "head"),
+            Err(ParseError::ParseFailed(_))
+        ));
+        assert!(matches!(
+            parse_unified_diff("@@ -1 +1 @@\n-a\n+b\n", "base", "head"),
+            Err(ParseError::InvalidFormat)
+        ));
+    }
 }
@@ -33,9 +33,15 @@ impl CommentEditor {
     }
 
     /// Create a comment editor for a line range (multiline comment).
-    pub fn new_range(file_path: impl Into<String>, side: DiffSide, start: u32, end: u32) -> Self {
+    ///
+    /// Both ends are `(side, line)` anchors, see [`CommentPosition::span`].
+    pub fn new_range(
+        file_path: impl Into<String>,
+        start: (DiffSide, u32),
+        end: (DiffSide, u32),
+    ) -> Self {
         Self {
-            position: CommentPosition::range(side, start, end),
+            position: CommentPosition::span(start, end),
             file_path: file_path.into(),
             body: String::new(),
             cursor: 0,
@@ -44,6 +50,31 @@ impl CommentEditor {
         }
     }
 
+    /// Create a comment editor for a comment on a whole file.
+    pub fn new_file(file_path: impl Into<String>) -> Self {
+        let file_path = file_path.into();
+        Self {
+            position: CommentPosition::File(file_path.clone()),
+            file_path,
+            body: String::new(),
+            cursor: 0,
+            editing_index: None,
+            github_id: None,
+        }
+    }
+
+    /// Create a comment editor for a comment on the pull request itself.
+    pub fn new_general() -> Self {
+        Self {
+            position: CommentPosition::General,
+            file_path: String::new(),
+            body: String::new(),
+            cursor: 0,
+            editing_index: None,
+            github_id: None,
+        }
+    }
+
     /// Create a comment editor for editing an existing comment.
     pub fn edit_existing(
         file_path: impl Into<String>,
@@ -227,8 +258,23 @@ mod tests {
 
     #[test]
     fn test_multiline_comment() {
-        let editor = CommentEditor::new_range("test.rs", DiffSide::Right, 10, 15);
+        let editor =
+            CommentEditor::new_range("test.rs", (DiffSide::Right, 10), (DiffSide::Right, 15));
         assert!(editor.position.is_multiline());
-        assert_eq!(editor.position.line_range(), (10, 15));
+        assert_eq!(editor.position.line_range(), Some((10, 15)));
+    }
+
+    #[test]
+    fn test_file_and_general_comment() {
+        let editor = CommentEditor::new_file("src/lib.rs");
+        assert_eq!(
+            editor.position,
+            CommentPosition::File("src/lib.rs".to_string())
+        );
+        assert_eq!(editor.file_path, "src/lib.rs");
+
+        let editor = CommentEditor::new_general();
+        assert_eq!(editor.position, CommentPosition::General);
+        assert!(editor.file_path.is_empty());
     }
 }
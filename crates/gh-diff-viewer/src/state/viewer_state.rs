@@ -4,10 +4,10 @@ use super::{CommentEditor, NavigationState};
 use crate::action::DiffAction;
 use crate::event::{DiffEvent, ExpandDirection};
 use crate::model::{
-    DiffLine, DiffSide, FileDiff, FileTreeNode, FlatFileEntry, LineKind, PendingComment,
-    PullRequestDiff, ReviewEvent,
+    BlameEntry, CommentPosition, DiffLine, DiffSide, FileDiff, FileTreeNode, FlatFileEntry,
+    LineKind, PendingComment, PullRequestDiff, ReviewEvent,
 };
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 /// Main state for the diff viewer widget.
 #[derive(Debug, Clone)]
@@ -28,12 +28,18 @@ pub struct DiffViewerState {
     pub selected_review_event: ReviewEvent,
     /// Viewport height (for scroll calculations)
     pub viewport_height: usize,
+    /// Whether the blame gutter is shown.
+    pub show_blame: bool,
 
     // === Cached state for rendering performance ===
     /// Cached flattened file tree (invalidated on expand/collapse).
     cached_flat_tree: Option<Vec<FlatFileEntry>>,
     /// Cached comment line numbers per file path (invalidated on comment add/remove).
     cached_comment_lines: Option<std::collections::HashMap<String, HashSet<u32>>>,
+    /// Loaded blame per file path, keyed by old-side line number.
+    blame: HashMap<String, HashMap<u32, BlameEntry>>,
+    /// Files whose blame was requested (loaded or in flight).
+    blame_requested: HashSet<String>,
 }
 
 impl DiffViewerState {
@@ -52,6 +58,9 @@ impl DiffViewerState {
             viewport_height: 20, // Default, will be updated by orchestrator
             cached_flat_tree: None,
             cached_comment_lines: None,
+            show_blame: false,
+            blame: HashMap::new(),
+            blame_requested: HashSet::new(),
         };
 
         // Sync file tree cursor to the first actual file (skip directories)
@@ -104,10 +113,11 @@ impl DiffViewerState {
         if self.cached_comment_lines.is_none() {
             let mut map: std::collections::HashMap<String, HashSet<u32>> =
                 std::collections::HashMap::new();
+            // File and general comments have no line to mark
             for comment in &self.pending_comments {
-                map.entry(comment.path.clone())
-                    .or_default()
-                    .insert(comment.position.line);
+                if let Some(line) = comment.position.line() {
+                    map.entry(comment.path.clone()).or_default().insert(line);
+                }
             }
             self.cached_comment_lines = Some(map);
         }
@@ -120,6 +130,17 @@ impl DiffViewerState {
             .unwrap_or_default()
     }
 
+    /// Get the paths of files with a file-level comment.
+    pub fn file_comment_paths(&self) -> HashSet<String> {
+        self.pending_comments
+            .iter()
+            .filter_map(|comment| match &comment.position {
+                CommentPosition::File(path) => Some(path.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
     // === Cache invalidation ===
 
     /// Invalidate the flat tree cache (call after expand/collapse).
@@ -172,6 +193,31 @@ impl DiffViewerState {
         None
     }
 
+    /// Get the anchors of the first and last line of the visual selection.
+    ///
+    /// GitHub only accepts ranges within one hunk, so the selection is clamped
+    /// to the hunk under the cursor; hunk headers and markers are skipped.
+    fn selection_anchors(&self) -> Option<((DiffSide, u32), (DiffSide, u32))> {
+        let (start, end) = self.nav.visual_selection()?;
+        let file = self.current_file()?;
+        let cursor = self.nav.cursor_line;
+
+        // Display indices of the lines of the hunk under the cursor
+        let mut hunk_start = 0;
+        let lines = file.hunks.iter().find_map(|hunk| {
+            let first = hunk_start + 1;
+            hunk_start = first + hunk.lines.len();
+            (first..hunk_start)
+                .contains(&cursor)
+                .then_some((first, hunk_start))
+        })?;
+
+        let anchors: Vec<_> = (start.max(lines.0)..=end.min(lines.1 - 1))
+            .filter_map(|idx| self.get_line_at_display_index(file, idx)?.anchor())
+            .collect();
+        Some((*anchors.first()?, *anchors.last()?))
+    }
+
     /// Get total number of display lines for current file.
     pub fn current_file_line_count(&self) -> usize {
         self.current_file().map(|f| f.total_lines()).unwrap_or(0)
@@ -338,14 +384,17 @@ impl DiffViewerState {
             // === File Navigation ===
             DiffAction::NextFile => {
                 self.nav.next_file(self.diff.files.len());
+                self.reveal_selected_file();
                 self.emit_selection_changed()
             }
             DiffAction::PrevFile => {
                 self.nav.prev_file();
+                self.reveal_selected_file();
                 self.emit_selection_changed()
             }
             DiffAction::SelectFile(idx) => {
                 self.nav.select_file(*idx, self.diff.files.len());
+                self.reveal_selected_file();
                 self.emit_selection_changed()
             }
 
@@ -391,12 +440,19 @@ impl DiffViewerState {
                     None
                 }
             }
-            DiffAction::ExpandTreeNode | DiffAction::CollapseTreeNode => {
-                // Simplified - toggle handles both
+            DiffAction::ExpandTreeNode => {
                 if self.nav.file_tree_focused {
-                    self.toggle_directory_at_cursor();
+                    self.expand_at_cursor()
+                } else {
+                    None
+                }
+            }
+            DiffAction::CollapseTreeNode => {
+                if self.nav.file_tree_focused {
+                    self.collapse_at_cursor()
+                } else {
+                    None
                 }
-                None
             }
 
             // === Visual Mode ===
@@ -413,6 +469,11 @@ impl DiffViewerState {
 
             // === Comments ===
             DiffAction::StartComment => self.open_comment_editor(),
+            DiffAction::CommentOnFile => self.open_file_comment_editor(),
+            DiffAction::CommentOnPr => {
+                self.comment_editor = Some(CommentEditor::new_general());
+                None
+            }
             DiffAction::CommitComment => self.submit_comment(),
             DiffAction::CancelComment => {
                 self.comment_editor = None;
@@ -421,6 +482,7 @@ impl DiffViewerState {
 
             // Comment editing actions (handled here when not in comment mode)
             DiffAction::CommentInsertChar(_)
+            | DiffAction::CommentInsertText(_)
             | DiffAction::CommentBackspace
             | DiffAction::CommentDelete
             | DiffAction::CommentCursorLeft
@@ -433,6 +495,12 @@ impl DiffViewerState {
             DiffAction::ExpandContextAbove => self.request_expand(ExpandDirection::Up),
             DiffAction::ExpandContextBelow => self.request_expand(ExpandDirection::Down),
 
+            // === Blame ===
+            DiffAction::ToggleBlame => {
+                self.show_blame = !self.show_blame;
+                self.blame_request()
+            }
+
             // === Review ===
             DiffAction::ShowReviewPopup => {
                 self.show_review_popup = true;
@@ -455,6 +523,7 @@ impl DiffViewerState {
             // === Viewport ===
             DiffAction::SetViewport { width: _, height } => {
                 self.viewport_height = *height as usize;
+                self.nav.ensure_cursor_visible(self.viewport_height);
                 None
             }
         }
@@ -474,6 +543,10 @@ impl DiffViewerState {
                 editor.insert_char(*c);
                 None
             }
+            DiffAction::CommentInsertText(text) => {
+                editor.insert_str(text);
+                None
+            }
             DiffAction::CommentBackspace => {
                 editor.delete_char_before();
                 None
@@ -546,27 +619,15 @@ impl DiffViewerState {
         let file = self.current_file()?;
         let file_path = file.path.clone();
 
-        // Determine the side and line number
-        let (side, line) = if let Some(diff_line) = self.current_line() {
-            let side = match diff_line.kind {
-                LineKind::Deletion => DiffSide::Left,
-                _ => DiffSide::Right,
-            };
-            let line_no = diff_line.new_line.or(diff_line.old_line)?;
-            (side, line_no)
-        } else {
-            return None; // Can't comment on hunk headers
-        };
-
-        // Check for visual selection
-        let visual_selection = self.nav.visual_selection();
+        // Can't comment on hunk headers
+        let (side, line) = self.current_line()?.anchor()?;
 
         // Check if there's an existing comment at this position
         let existing_comment = self
             .pending_comments
             .iter()
             .enumerate()
-            .find(|(_, c)| c.path == file_path && c.position.line == line);
+            .find(|(_, c)| c.path == file_path && c.position.is_on(side, line));
 
         self.comment_editor = if let Some((idx, comment)) = existing_comment {
             // Edit existing comment
@@ -577,10 +638,9 @@ impl DiffViewerState {
                 idx,
                 comment.github_id,
             ))
-        } else if let Some((start_idx, end_idx)) = visual_selection {
+        } else if let Some((start, end)) = self.selection_anchors() {
             // New multi-line comment
-            let start_line = line.saturating_sub((end_idx - start_idx) as u32);
-            Some(CommentEditor::new_range(file_path, side, start_line, line))
+            Some(CommentEditor::new_range(file_path, start, end))
         } else {
             // New single-line comment
             Some(CommentEditor::new(file_path, side, line))
@@ -591,6 +651,49 @@ impl DiffViewerState {
         None
     }
 
+    /// Get the file a file-level comment would target.
+    ///
+    /// That is the file entry under the file tree cursor, or the current file
+    /// when the diff cursor is on a hunk header row.
+    pub fn file_comment_target(&self) -> Option<String> {
+        if self.nav.file_tree_focused {
+            let cursor_pos = self.nav.file_tree_cursor;
+            let entry = match &self.cached_flat_tree {
+                Some(entries) => entries.get(cursor_pos).cloned(),
+                None => self.file_tree.flatten().into_iter().nth(cursor_pos),
+            }?;
+            return if entry.is_dir { None } else { entry.path };
+        }
+        if self.current_line().is_some() {
+            return None; // Line rows take line comments
+        }
+        self.current_file().map(|file| file.path.clone())
+    }
+
+    /// Open the comment editor for a comment on the whole file.
+    /// If the file already has a file-level comment, edit it instead.
+    fn open_file_comment_editor(&mut self) -> Option<DiffEvent> {
+        let file_path = self.file_comment_target()?;
+
+        let existing_comment = self.pending_comments.iter().enumerate().find(
+            |(_, c)| matches!(&c.position, CommentPosition::File(path) if *path == file_path),
+        );
+
+        self.comment_editor = if let Some((idx, comment)) = existing_comment {
+            Some(CommentEditor::edit_existing(
+                file_path,
+                comment.position.clone(),
+                &comment.body,
+                idx,
+                comment.github_id,
+            ))
+        } else {
+            Some(CommentEditor::new_file(file_path))
+        };
+        self.nav.exit_visual_mode();
+        None
+    }
+
     /// Submit the current comment.
     fn submit_comment(&mut self) -> Option<DiffEvent> {
         let editor = self.comment_editor.take()?;
@@ -651,15 +754,15 @@ impl DiffViewerState {
     fn select_file_at_cursor(&mut self) -> Option<DiffEvent> {
         // Get entry info from cache - extract file_tree_cursor first to avoid borrow issues
         let cursor_pos = self.nav.file_tree_cursor;
-        let (is_dir, path, name) = {
+        let (is_dir, path, tree_path) = {
             let entries = self.flat_tree();
             let entry = entries.get(cursor_pos)?;
-            (entry.is_dir, entry.path.clone(), entry.name.clone())
+            (entry.is_dir, entry.path.clone(), entry.tree_path.clone())
         };
 
         if is_dir {
             // Toggle directory
-            self.toggle_directory_by_name(&name);
+            self.toggle_directory(&tree_path);
             None
         } else if let Some(path) = path {
             // Find the file index
@@ -702,25 +805,121 @@ impl DiffViewerState {
         }
     }
 
-    /// Toggle a directory by name and invalidate cache.
-    fn toggle_directory_by_name(&mut self, name: &str) {
-        self.file_tree.toggle_at_path(name);
+    /// Toggle a directory by its tree path and invalidate cache.
+    fn toggle_directory(&mut self, tree_path: &str) {
+        self.file_tree.toggle_at_path(tree_path);
         self.invalidate_flat_tree_cache();
     }
 
-    /// Toggle a directory at the current cursor position.
-    fn toggle_directory_at_cursor(&mut self) {
+    /// Expand the directory under the cursor, like `l` in a file manager.
+    /// An expanded directory moves the cursor to its first child, a file opens.
+    fn expand_at_cursor(&mut self) -> Option<DiffEvent> {
         let cursor_pos = self.nav.file_tree_cursor;
-        let name = {
-            let entries = self.flat_tree();
-            entries
-                .get(cursor_pos)
-                .filter(|e| e.is_dir)
-                .map(|e| e.name.clone())
+        let (is_dir, is_expanded, tree_path) = {
+            let entry = self.flat_tree().get(cursor_pos)?;
+            (entry.is_dir, entry.is_expanded, entry.tree_path.clone())
+        };
+
+        if !is_dir {
+            return self.select_file_at_cursor();
+        }
+        if is_expanded {
+            if cursor_pos + 1 < self.flat_tree_len() {
+                self.nav.file_tree_cursor += 1;
+            }
+            return self.auto_select_file_at_cursor();
+        }
+        self.file_tree.set_expanded(&tree_path, true);
+        self.invalidate_flat_tree_cache();
+        None
+    }
+
+    /// Collapse the directory under the cursor, like `h` in a file manager.
+    /// Files and collapsed directories move the cursor to their parent directory.
+    fn collapse_at_cursor(&mut self) -> Option<DiffEvent> {
+        let cursor_pos = self.nav.file_tree_cursor;
+        let (is_dir, is_expanded, tree_path, depth) = {
+            let entry = self.flat_tree().get(cursor_pos)?;
+            (
+                entry.is_dir,
+                entry.is_expanded,
+                entry.tree_path.clone(),
+                entry.depth,
+            )
         };
-        if let Some(name) = name {
-            self.toggle_directory_by_name(&name);
+
+        if is_dir && is_expanded {
+            self.file_tree.set_expanded(&tree_path, false);
+            self.invalidate_flat_tree_cache();
+        } else if depth > 0 {
+            // The parent is the closest entry above with a smaller depth
+            let parent = self.flat_tree()[..cursor_pos]
+                .iter()
+                .rposition(|e| e.depth < depth);
+            if let Some(parent) = parent {
+                self.nav.file_tree_cursor = parent;
+            }
+        }
+        None
+    }
+
+    /// Expand the ancestors of the selected file and move the tree cursor to it.
+    fn reveal_selected_file(&mut self) {
+        let Some(path) = self.current_file().map(|f| f.path.clone()) else {
+            return;
+        };
+        if self.file_tree.reveal(&path) {
+            self.invalidate_flat_tree_cache();
+        }
+        self.sync_file_tree_cursor_to_selected_file();
+    }
+
+    /// Comments not posted to GitHub yet, including the one being written.
+    ///
+    /// The open editor's text replaces the body of the comment it edits.
+    pub fn unsent_comments(&self) -> Vec<PendingComment> {
+        let mut unsent: Vec<PendingComment> = Vec::new();
+        for (idx, comment) in self.pending_comments.iter().enumerate() {
+            if comment.github_id.is_some() {
+                continue;
+            }
+            let mut comment = comment.clone();
+            if let Some(editor) = self
+                .comment_editor
+                .as_ref()
+                .filter(|e| e.editing_index == Some(idx) && !e.is_empty())
+            {
+                comment.body = editor.body.clone();
+            }
+            unsent.push(comment);
+        }
+
+        if let Some(editor) = self
+            .comment_editor
+            .as_ref()
+            .filter(|e| e.editing_index.is_none() && !e.is_empty())
+        {
+            unsent.push(PendingComment::new(
+                &editor.file_path,
+                editor.position.clone(),
+                &editor.body,
+            ));
         }
+        unsent
+    }
+
+    /// Tree paths of the collapsed directories, to restore them on a reload.
+    pub fn collapsed_dirs(&self) -> HashSet<String> {
+        self.file_tree.collapsed_paths()
+    }
+
+    /// Collapse the given directories, e.g. after reloading the same diff.
+    pub fn restore_collapsed_dirs(&mut self, collapsed: &HashSet<String>) {
+        self.file_tree.apply_collapsed(collapsed);
+        self.invalidate_flat_tree_cache();
+        self.sync_file_tree_cursor_to_selected_file();
+        let len = self.flat_tree_len();
+        self.nav.file_tree_cursor = self.nav.file_tree_cursor.min(len.saturating_sub(1));
     }
 
     /// Emit a selection changed event.
@@ -752,6 +951,71 @@ impl DiffViewerState {
         }
     }
 
+    /// Blame request for the current file, if one is due.
+    ///
+    /// Returns None while blame is hidden, once the file's blame was
+    /// requested (see [`request_blame`](Self::request_blame)) and when the
+    /// file has no old-side lines (e.g. added files).
+    pub fn blame_request(&self) -> Option<DiffEvent> {
+        if !self.show_blame {
+            return None;
+        }
+        let file = self.current_file()?;
+        if self.blame_requested.contains(&file.path) {
+            return None;
+        }
+
+        let old_lines = file
+            .hunks
+            .iter()
+            .flat_map(|hunk| &hunk.lines)
+            .filter(|line| matches!(line.kind, LineKind::Context | LineKind::Deletion))
+            .filter_map(|line| line.old_line);
+        let (start_line, end_line) = old_lines.fold(None, |range, line| match range {
+            None => Some((line, line)),
+            Some((start, end)) => Some((u32::min(start, line), u32::max(end, line))),
+        })?;
+
+        Some(DiffEvent::RequestBlame {
+            file_path: file.path.clone(),
+            base_path: file.old_path.clone().unwrap_or_else(|| file.path.clone()),
+            commit_sha: self.diff.base_sha.clone(),
+            start_line,
+            end_line,
+        })
+    }
+
+    /// Take the due blame request, so the file's blame is requested once.
+    pub fn request_blame(&mut self) -> Option<DiffEvent> {
+        let event = self.blame_request()?;
+        if let DiffEvent::RequestBlame { file_path, .. } = &event {
+            self.blame_requested.insert(file_path.clone());
+        }
+        Some(event)
+    }
+
+    /// Store loaded blame for a file.
+    pub fn insert_blame(&mut self, file_path: &str, entries: Vec<BlameEntry>) {
+        self.blame_requested.insert(file_path.to_string());
+        self.blame.insert(
+            file_path.to_string(),
+            entries
+                .into_iter()
+                .map(|entry| (entry.line, entry))
+                .collect(),
+        );
+    }
+
+    /// Forget a failed blame request so the next one asks again.
+    pub fn blame_failed(&mut self, file_path: &str) {
+        self.blame_requested.remove(file_path);
+    }
+
+    /// Loaded blame for a file, keyed by old-side line number.
+    pub fn blame_for_file(&self, path: &str) -> Option<&HashMap<u32, BlameEntry>> {
+        self.blame.get(path)
+    }
+
     /// Insert expanded context lines into a file.
     pub fn insert_expanded_lines(
         &mut self,
@@ -921,11 +1185,214 @@ mod tests {
         // Verify content
         assert_eq!(state.comment_editor.as_ref().unwrap().body, "Hi");
 
+        // Pasted text keeps its lines
+        state.handle_action(DiffAction::CommentInsertText(" there\nsecond line".into()));
+        state.handle_action(DiffAction::CommentInsertChar('!'));
+        assert_eq!(
+            state.comment_editor.as_ref().unwrap().body,
+            "Hi there\nsecond line!"
+        );
+
         // Cancel
         state.handle_action(DiffAction::CancelComment);
         assert!(!state.is_editing_comment());
     }
 
+    #[test]
+    fn test_range_comment_anchors_selected_lines() {
+        let mut diff = sample_diff();
+        let mut hunk = Hunk::new(20, 2, 21, 2);
+        hunk.lines.push(DiffLine::context("fn later() {}", 20, 21));
+        hunk.lines.push(DiffLine::context("", 21, 22));
+        diff.files[0].hunks.push(hunk);
+        let mut state = DiffViewerState::new(diff);
+        state.nav.file_tree_focused = false;
+
+        let mut select = |anchor: usize, cursor: usize| {
+            state.nav.cursor_line = anchor;
+            state.handle_action(DiffAction::EnterVisualMode);
+            state.nav.cursor_line = cursor;
+            state.handle_action(DiffAction::StartComment);
+            let position = state.comment_editor.take().unwrap().position;
+            assert!(!state.nav.is_visual_mode());
+            position
+        };
+
+        // From a deleted line to an added one
+        assert_eq!(
+            select(2, 3),
+            CommentPosition::span((DiffSide::Left, 2), (DiffSide::Right, 2))
+        );
+        // Selected upwards, the range still ends on the last line
+        assert_eq!(select(4, 1), CommentPosition::range(DiffSide::Right, 1, 4));
+        // Clamped to the hunk under the cursor
+        assert_eq!(
+            select(3, 7),
+            CommentPosition::range(DiffSide::Right, 21, 22)
+        );
+        // A single selected line is a single-line comment
+        assert_eq!(select(2, 2), CommentPosition::single(DiffSide::Left, 2));
+    }
+
+    #[test]
+    fn test_file_and_pr_comments_do_not_collide_with_line_comments() {
+        let mut state = DiffViewerState::new(sample_diff());
+        state.nav.file_tree_focused = false;
+
+        // Line comment on line 1
+        state.nav.cursor_line = 1;
+        state.handle_action(DiffAction::StartComment);
+        state.handle_action(DiffAction::CommentInsertChar('a'));
+        state.handle_action(DiffAction::CommitComment);
+
+        // No file comment from a line row
+        state.handle_action(DiffAction::CommentOnFile);
+        assert!(!state.is_editing_comment());
+
+        // File comment from the hunk header row
+        state.nav.cursor_line = 0;
+        state.handle_action(DiffAction::CommentOnFile);
+        state.handle_action(DiffAction::CommentInsertChar('f'));
+        let events = state.handle_action(DiffAction::CommitComment);
+        assert!(matches!(
+            &events[0],
+            DiffEvent::CommentAdded(c) if c.position == CommentPosition::File("src/main.rs".into())
+        ));
+
+        // PR comment from anywhere
+        state.nav.cursor_line = 2;
+        state.handle_action(DiffAction::CommentOnPr);
+        state.handle_action(DiffAction::CommentInsertChar('p'));
+        state.handle_action(DiffAction::CommitComment);
+
+        assert_eq!(state.pending_comments.len(), 3);
+        assert_eq!(
+            state.comment_lines_for_file("src/main.rs"),
+            HashSet::from([1])
+        );
+        assert_eq!(
+            state.file_comment_paths(),
+            HashSet::from(["src/main.rs".to_string()])
+        );
+
+        // Re-opening edits the matching comment of each kind
+        state.nav.cursor_line = 1;
+        state.handle_action(DiffAction::StartComment);
+        assert_eq!(
+            state.comment_editor.as_ref().unwrap().editing_index,
+            Some(0)
+        );
+        state.handle_action(DiffAction::CancelComment);
+
+        state.nav.file_tree_focused = true;
+        state.handle_action(DiffAction::CommentOnFile);
+        let editor = state.comment_editor.as_ref().unwrap();
+        assert_eq!(editor.editing_index, Some(1));
+        assert_eq!(editor.body, "f");
+    }
+
+    #[test]
+    fn test_unsent_comments_include_the_editor() {
+        let mut state = DiffViewerState::new(sample_diff());
+        state.nav.file_tree_focused = false;
+
+        // A posted and an unsent comment
+        state.nav.cursor_line = 1;
+        state.handle_action(DiffAction::StartComment);
+        state.handle_action(DiffAction::CommentInsertChar('a'));
+        state.handle_action(DiffAction::CommitComment);
+        state.pending_comments[0].github_id = Some(7);
+        state.nav.cursor_line = 2;
+        state.handle_action(DiffAction::StartComment);
+        state.handle_action(DiffAction::CommentInsertChar('b'));
+        state.handle_action(DiffAction::CommitComment);
+
+        let bodies = |state: &DiffViewerState| {
+            state
+                .unsent_comments()
+                .into_iter()
+                .map(|c| c.body)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(bodies(&state), vec!["b"]);
+
+        // Edits of an unsent comment and new comments being written count too
+        state.handle_action(DiffAction::StartComment);
+        state.handle_action(DiffAction::CommentInsertChar('c'));
+        assert_eq!(bodies(&state), vec!["bc"]);
+        state.handle_action(DiffAction::CancelComment);
+        state.handle_action(DiffAction::CommentOnPr);
+        state.handle_action(DiffAction::CommentInsertChar('d'));
+        assert_eq!(bodies(&state), vec!["b", "d"]);
+    }
+
+    fn nested_diff() -> PullRequestDiff {
+        let mut diff = PullRequestDiff::new("base", "head");
+        for path in ["src/a/one.rs", "src/a/two.rs", "src/b.rs"] {
+            diff.files.push(FileDiff::new(path));
+        }
+        diff
+    }
+
+    #[test]
+    fn test_tree_collapse_and_expand_like_a_file_manager() {
+        let mut state = DiffViewerState::new(nested_diff());
+        // src, src/a, one.rs, two.rs, b.rs
+        assert_eq!(state.nav.file_tree_cursor, 2);
+
+        // h on a file goes to its directory, then collapses it, then goes up
+        state.handle_action(DiffAction::CollapseTreeNode);
+        assert_eq!(state.nav.file_tree_cursor, 1);
+        state.handle_action(DiffAction::CollapseTreeNode);
+        assert_eq!(state.flat_tree_len(), 3);
+        state.handle_action(DiffAction::CollapseTreeNode);
+        assert_eq!(state.nav.file_tree_cursor, 0);
+
+        // l on an expanded directory enters it, then expands the collapsed one
+        state.handle_action(DiffAction::ExpandTreeNode);
+        assert_eq!(state.nav.file_tree_cursor, 1);
+        state.handle_action(DiffAction::ExpandTreeNode);
+        assert_eq!(state.flat_tree_len(), 5);
+        state.handle_action(DiffAction::ExpandTreeNode);
+        assert_eq!(state.nav.file_tree_cursor, 2);
+
+        // l on a file opens it
+        let events = state.handle_action(DiffAction::ExpandTreeNode);
+        assert!(matches!(
+            events[0],
+            DiffEvent::FileSelected { file_index: 0, .. }
+        ));
+        assert!(!state.nav.file_tree_focused);
+    }
+
+    #[test]
+    fn test_jumping_to_a_file_reveals_it() {
+        let mut state = DiffViewerState::new(nested_diff());
+        state.nav.file_tree_cursor = 1;
+        state.handle_action(DiffAction::CollapseTreeNode);
+        state.nav.file_tree_cursor = 0;
+        state.handle_action(DiffAction::CollapseTreeNode);
+        assert_eq!(state.flat_tree_len(), 1);
+
+        state.handle_action(DiffAction::SelectFile(1));
+        assert_eq!(state.flat_tree_len(), 5);
+        assert_eq!(state.nav.file_tree_cursor, 3);
+        assert!(state.collapsed_dirs().is_empty());
+    }
+
+    #[test]
+    fn test_restore_collapsed_dirs() {
+        let mut state = DiffViewerState::new(nested_diff());
+        state.nav.file_tree_cursor = 1;
+        state.handle_action(DiffAction::CollapseTreeNode);
+        let collapsed = state.collapsed_dirs();
+
+        let mut reloaded = DiffViewerState::new(nested_diff());
+        reloaded.restore_collapsed_dirs(&collapsed);
+        assert_eq!(reloaded.flat_tree_len(), 3);
+        assert!(reloaded.nav.file_tree_cursor < 3);
+    }
+
     #[test]
     fn test_set_viewport() {
         let diff = sample_diff();
@@ -937,4 +1404,47 @@ mod tests {
         });
         assert_eq!(state.viewport_height, 50);
     }
+
+    #[test]
+    fn test_blame_is_requested_once_per_file() {
+        let mut state = DiffViewerState::new(sample_diff());
+
+        let events = state.handle_action(DiffAction::ToggleBlame);
+        assert!(state.show_blame);
+        assert!(matches!(
+            events.as_slice(),
+            [DiffEvent::RequestBlame { file_path, commit_sha, start_line: 1, end_line: 3, .. }]
+                if file_path == "src/main.rs" && commit_sha == "base"
+        ));
+        assert!(state.request_blame().is_some());
+        assert!(state.blame_request().is_none());
+
+        state.insert_blame(
+            "src/main.rs",
+            vec![BlameEntry {
+                line: 2,
+                author: "Jane Doe".to_string(),
+                commit: "abc123".to_string(),
+                age: std::time::Duration::from_secs(3600),
+            }],
+        );
+        assert_eq!(
+            state.blame_for_file("src/main.rs").unwrap()[&2].author,
+            "Jane Doe"
+        );
+
+        // Hiding and showing again reuses the loaded blame
+        state.handle_action(DiffAction::ToggleBlame);
+        assert!(state.handle_action(DiffAction::ToggleBlame).is_empty());
+    }
+
+    #[test]
+    fn test_failed_blame_is_requested_again() {
+        let mut state = DiffViewerState::new(sample_diff());
+        state.handle_action(DiffAction::ToggleBlame);
+        state.request_blame();
+
+        state.blame_failed("src/main.rs");
+        assert!(state.request_blame().is_some());
+    }
 }
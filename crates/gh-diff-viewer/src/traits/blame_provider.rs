@@ -0,0 +1,72 @@
+//! Trait for fetching blame information.
+
+use crate::model::BlameEntry;
+use async_trait::async_trait;
+use std::ops::RangeInclusive;
+use thiserror::Error;
+
+/// Errors that can occur when fetching blame.
+#[derive(Debug, Error)]
+pub enum BlameError {
+    /// The requested file was not found.
+    #[error("File not found: {0}")]
+    FileNotFound(String),
+
+    /// A network error occurred.
+    #[error("Network error: {0}")]
+    NetworkError(String),
+
+    /// Rate limited by the API.
+    #[error("Rate limited, retry after {0} seconds")]
+    RateLimited(u64),
+
+    /// The blame provider is not available.
+    #[error("Blame provider unavailable: {0}")]
+    Unavailable(String),
+}
+
+/// Provides last-touched information for lines of a file.
+///
+/// Implement this trait to let the diff viewer show who last changed the
+/// context and removed lines of a diff, and when.
+#[async_trait]
+pub trait BlameProvider: Send + Sync {
+    /// Fetch blame for lines of a file at a specific commit.
+    ///
+    /// # Arguments
+    /// * `path` - File path relative to repository root
+    /// * `commit_sha` - The commit SHA to blame at
+    /// * `lines` - 1-indexed line range (inclusive)
+    ///
+    /// # Returns
+    /// One entry per line in the range that blame knows about.
+    async fn fetch_blame(
+        &self,
+        path: &str,
+        commit_sha: &str,
+        lines: RangeInclusive<u32>,
+    ) -> Result<Vec<BlameEntry>, BlameError>;
+
+    /// Check if the provider is available (e.g., has valid credentials).
+    fn is_available(&self) -> bool;
+}
+
+/// A no-op blame provider for when blame is disabled.
+#[allow(dead_code)]
+pub struct NoOpBlameProvider;
+
+#[async_trait]
+impl BlameProvider for NoOpBlameProvider {
+    async fn fetch_blame(
+        &self,
+        _path: &str,
+        _commit_sha: &str,
+        _lines: RangeInclusive<u32>,
+    ) -> Result<Vec<BlameEntry>, BlameError> {
+        Err(BlameError::Unavailable("Blame is disabled".to_string()))
+    }
+
+    fn is_available(&self) -> bool {
+        false
+    }
+}
@@ -50,21 +50,30 @@ pub enum CommentError {
 /// #[async_trait]
 /// impl CommentHandler for GithubCommentHandler {
 ///     async fn submit_comment(&self, comment: PendingComment) -> Result<CommentId, CommentError> {
-///         let (start, end) = comment.position.line_range();
+///         let CommentPosition::Line { side, line, start_line, start_side } = comment.position else {
+///             return Err(CommentError::SubmissionFailed("not a line comment".into()));
+///         };
+///         let lines = match (start_line, start_side) {
+///             (Some(start_line), Some(start_side)) => CommentLines::range(
+///                 start_side.as_github_str(),
+///                 start_line,
+///                 side.as_github_str(),
+///                 line,
+///             ),
+///             _ => CommentLines::single(side.as_github_str(), line),
+///         };
 ///
 ///         let result = self.client.create_review_comment(
 ///             &self.owner,
 ///             &self.repo,
 ///             self.pr_number,
-///             &comment.body,
 ///             &self.commit_sha,
 ///             &comment.path,
-///             end,
-///             comment.position.side.as_github_str(),
-///             if start != end { Some(start) } else { None },
+///             &lines,
+///             &comment.body,
 ///         ).await.map_err(|e| CommentError::SubmissionFailed(e.to_string()))?;
 ///
-///         Ok(result.id.to_string())
+///         Ok(result.to_string())
 ///     }
 ///
 ///     // ... other methods
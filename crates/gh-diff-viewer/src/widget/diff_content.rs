@@ -1,12 +1,15 @@
 //! Diff content widget for rendering the actual diff.
 
 use crate::highlight::DiffHighlighter;
-use crate::model::{DiffLine, FileDiff, LineKind};
+use crate::model::{BlameEntry, DiffLine, FileDiff, LineKind};
 use crate::traits::ThemeProvider;
 use ratatui::prelude::*;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Widget};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+
+/// Columns taken by the blame gutter ("JD  3mo ").
+pub const BLAME_GUTTER_WIDTH: u16 = 9;
 
 /// Pre-computed render data to avoid recomputation per frame.
 pub struct DiffRenderData<'a> {
@@ -59,6 +62,8 @@ pub struct DiffContentWidget<'a, T: ThemeProvider> {
     focused: bool,
     /// Footer hints to display at the bottom border.
     footer_hints: Vec<FooterHint>,
+    /// Blame of the old-side lines, shown in a gutter when set.
+    blame: Option<&'a HashMap<u32, BlameEntry>>,
 }
 
 impl<'a, T: ThemeProvider> DiffContentWidget<'a, T> {
@@ -82,6 +87,7 @@ impl<'a, T: ThemeProvider> DiffContentWidget<'a, T> {
             theme,
             focused,
             footer_hints: Vec::new(),
+            blame: None,
         }
     }
 
@@ -96,6 +102,12 @@ impl<'a, T: ThemeProvider> DiffContentWidget<'a, T> {
         self.footer_hints = hints;
         self
     }
+
+    /// Show a blame gutter for context and removed lines.
+    pub fn with_blame(mut self, blame: Option<&'a HashMap<u32, BlameEntry>>) -> Self {
+        self.blame = blame;
+        self
+    }
 }
 
 impl<T: ThemeProvider> Widget for DiffContentWidget<'_, T> {
@@ -155,12 +167,23 @@ impl<T: ThemeProvider> Widget for DiffContentWidget<'_, T> {
         let (Some(file), Some(render_data)) = (self.file, &self.render_data) else {
             // Render empty state
             let msg = "Select a file from the tree";
-            let x = inner.x + (inner.width.saturating_sub(msg.len() as u16)) / 2;
+            let x = inner.x + (inner.width.saturating_sub(gh_text_width::width(msg) as u16)) / 2;
             let y = inner.y + inner.height / 2;
             buf.set_string(x, y, msg, Style::default().fg(Color::DarkGray));
             return;
         };
 
+        if let Some(reason) = file.empty_reason() {
+            let x = inner.x
+                + (inner
+                    .width
+                    .saturating_sub(gh_text_width::width(&reason) as u16))
+                    / 2;
+            let y = inner.y + inner.height / 2;
+            buf.set_string(x, y, reason, Style::default().fg(Color::DarkGray));
+            return;
+        }
+
         // Use pre-computed values from render_data
         let line_no_width = render_data.line_no_width;
         let comment_lines = render_data.comment_lines;
@@ -252,12 +275,8 @@ impl<T: ThemeProvider> DiffContentWidget<'_, T> {
             buf.set_string(x + i, y, " ", style);
         }
 
-        // Render header text (truncate if needed)
-        let display_header = if header.len() > width as usize {
-            &header[..width as usize]
-        } else {
-            header
-        };
+        // Render header text (cut between graphemes if needed)
+        let display_header = gh_text_width::clip(header, width as usize);
         buf.set_string(x, y, display_header, style);
     }
 
@@ -313,6 +332,24 @@ impl<T: ThemeProvider> DiffContentWidget<'_, T> {
             base_style.fg(self.theme.line_number_foreground())
         };
 
+        // Blame gutter (added lines have no blame on the base side)
+        if let Some(blame) = self.blame {
+            let entry = match line.kind {
+                LineKind::Context | LineKind::Deletion => line.old_line.and_then(|n| blame.get(&n)),
+                _ => None,
+            };
+            let gutter = entry
+                .map(|entry| format!("{:<3}{:>5} ", entry.initials(), entry.short_age()))
+                .unwrap_or_else(|| " ".repeat(BLAME_GUTTER_WIDTH as usize));
+            buf.set_string(
+                current_x,
+                y,
+                &gutter,
+                line_no_style.add_modifier(Modifier::DIM),
+            );
+            current_x += BLAME_GUTTER_WIDTH;
+        }
+
         // Render old line number
         let old_no = line
             .old_line
@@ -380,12 +417,9 @@ impl<T: ThemeProvider> DiffContentWidget<'_, T> {
                     break;
                 }
 
+                // Cut by cells between graphemes, wide characters take two
                 let available = content_width - col;
-                let text = if span.text.len() > available {
-                    &span.text[..available]
-                } else {
-                    &span.text
-                };
+                let text = gh_text_width::clip(&span.text, available);
 
                 let mut style = base_style;
                 // Only apply syntax highlighting colors when not on cursor line
@@ -403,7 +437,10 @@ impl<T: ThemeProvider> DiffContentWidget<'_, T> {
                 }
 
                 buf.set_string(current_x + col as u16, y, text, style);
-                col += text.len();
+                col += gh_text_width::width(text);
+                if text.len() < span.text.len() {
+                    break; // A wide character did not fit in the last cell
+                }
             }
         }
 
@@ -473,4 +510,61 @@ mod tests {
             true,
         );
     }
+
+    #[test]
+    fn test_wide_characters_are_cut_by_cells() {
+        let mut file = FileDiff::new("docs/notes.txt");
+        let mut hunk = Hunk::new(1, 1, 1, 1);
+        hunk.lines.push(DiffLine::addition(
+            "漢字テスト 🚀🔥 the launch notes, e\u{301}",
+            1,
+        ));
+        file.hunks.push(hunk);
+
+        let line_no_width = file.line_no_width();
+        let total_lines = file.total_lines();
+        let display_name = file.display_name().to_string();
+        let comment_lines = HashSet::new();
+        let mut highlighter = DiffHighlighter::new();
+        let theme = DefaultTheme;
+
+        // Too narrow for the line, wide characters must not be cut in half
+        for width in 20..30 {
+            let area = Rect::new(0, 0, width, 5);
+            let mut buf = Buffer::empty(area);
+            DiffContentWidget::new(
+                Some(&file),
+                Some(DiffRenderData {
+                    line_no_width,
+                    comment_lines: &comment_lines,
+                    display_name: &display_name,
+                    total_lines,
+                }),
+                0,
+                0,
+                &mut highlighter,
+                &theme,
+                true,
+            )
+            .render(area, &mut buf);
+
+            // The cell after a wide character is covered by it
+            let rows: Vec<String> = (0..area.height)
+                .map(|y| {
+                    let mut row = String::new();
+                    let mut x = 0;
+                    while x < width {
+                        let symbol = buf[(x, y)].symbol();
+                        row.push_str(symbol);
+                        x += gh_text_width::width(symbol).max(1) as u16;
+                    }
+                    row
+                })
+                .collect();
+            assert!(rows
+                .iter()
+                .all(|row| gh_text_width::width(row) == width as usize));
+            assert!(rows.iter().any(|row| row.contains("漢字")), "{:#?}", rows);
+        }
+    }
 }
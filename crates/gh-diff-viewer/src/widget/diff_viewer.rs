@@ -8,6 +8,9 @@ use ratatui::prelude::*;
 use ratatui::widgets::Widget;
 use std::collections::HashSet;
 
+/// Narrower diff panes hide the blame gutter to leave room for the code.
+const MIN_BLAME_WIDTH: u16 = 100;
+
 /// The main diff viewer widget.
 ///
 /// This is a composite widget that combines:
@@ -34,6 +37,8 @@ pub struct DiffViewer<'a, T: ThemeProvider> {
     theme: &'a T,
     /// Footer hints to display.
     footer_hints: Vec<FooterHint>,
+    /// Files with more changed lines than this are shown in bold.
+    large_file_threshold: Option<usize>,
 }
 
 impl<'a, T: ThemeProvider> DiffViewer<'a, T> {
@@ -43,6 +48,7 @@ impl<'a, T: ThemeProvider> DiffViewer<'a, T> {
             highlighter,
             theme,
             footer_hints: Vec::new(),
+            large_file_threshold: None,
         }
     }
 
@@ -51,6 +57,12 @@ impl<'a, T: ThemeProvider> DiffViewer<'a, T> {
         self.footer_hints = hints;
         self
     }
+
+    /// Show files with more than `threshold` changed lines in bold in the file tree.
+    pub fn with_large_file_threshold(mut self, threshold: usize) -> Self {
+        self.large_file_threshold = Some(threshold);
+        self
+    }
 }
 
 impl<T: ThemeProvider> Widget for DiffViewer<'_, T> {
@@ -92,12 +104,18 @@ impl<T: ThemeProvider> DiffViewer<'_, T> {
         if state.nav.show_file_tree {
             // Get flat entries (populates cache if needed)
             let flat_entries = state.flat_tree().to_vec();
-            let file_tree = FileTreeWidget::new(
+            let commented_files = state.file_comment_paths();
+            let mut file_tree = FileTreeWidget::new(
                 &flat_entries,
                 file_tree_cursor,
                 file_tree_focused,
                 self.theme,
-            );
+            )
+            .with_commented_files(&commented_files)
+            .with_diff_stats(&state.diff);
+            if let Some(threshold) = self.large_file_threshold {
+                file_tree = file_tree.with_large_file_threshold(threshold);
+            }
             file_tree.render(chunks[0], buf);
         }
 
@@ -131,6 +149,12 @@ impl<T: ThemeProvider> DiffViewer<'_, T> {
             },
         );
 
+        // Blame only fits next to the code on wide panes
+        let blame = file_path
+            .as_deref()
+            .filter(|_| state.show_blame && chunks[1].width >= MIN_BLAME_WIDTH)
+            .and_then(|p| state.blame_for_file(p));
+
         let diff_content = DiffContentWidget::new(
             current_file,
             render_data_struct,
@@ -141,7 +165,8 @@ impl<T: ThemeProvider> DiffViewer<'_, T> {
             !file_tree_focused,
         )
         .with_selection(visual_selection)
-        .with_footer_hints(self.footer_hints.clone());
+        .with_footer_hints(self.footer_hints.clone())
+        .with_blame(blame);
 
         diff_content.render(chunks[1], buf);
 
@@ -182,7 +207,15 @@ impl<T: ThemeProvider> DiffViewer<'_, T> {
         Clear.render(popup_area, buf);
 
         // Draw border
-        let title = format!(" Comment on line {} ", editor.position.line);
+        let outdated = editor
+            .editing_index
+            .and_then(|idx| state.pending_comments.get(idx))
+            .is_some_and(|comment| comment.outdated);
+        let title = if outdated {
+            format!(" Comment on {} (outdated) ", editor.position.describe())
+        } else {
+            format!(" Comment on {} ", editor.position.describe())
+        };
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Yellow))
@@ -1,9 +1,15 @@
 //! File tree widget for navigation.
 
-use crate::model::{FileStatus, FlatFileEntry};
+use crate::model::{
+    elide_middle, stat_bar, stat_bar_width, FileStatus, FlatFileEntry, PullRequestDiff,
+};
 use crate::traits::ThemeProvider;
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Widget};
+use std::collections::HashSet;
+
+/// File names keep at least this many cells before the stats are dropped.
+const MIN_NAME_WIDTH: usize = 8;
 
 /// Widget for rendering the file tree navigation pane.
 pub struct FileTreeWidget<'a, T: ThemeProvider> {
@@ -15,6 +21,12 @@ pub struct FileTreeWidget<'a, T: ThemeProvider> {
     focused: bool,
     /// Theme provider.
     theme: &'a T,
+    /// Paths of files with a file-level comment.
+    commented_files: Option<&'a HashSet<String>>,
+    /// Diff the entries belong to, for stat bar scaling and the totals.
+    diff: Option<&'a PullRequestDiff>,
+    /// Files with more changed lines than this are shown in bold.
+    large_file_threshold: Option<usize>,
 }
 
 impl<'a, T: ThemeProvider> FileTreeWidget<'a, T> {
@@ -25,8 +37,29 @@ impl<'a, T: ThemeProvider> FileTreeWidget<'a, T> {
             selected,
             focused,
             theme,
+            commented_files: None,
+            diff: None,
+            large_file_threshold: None,
         }
     }
+
+    /// Mark the given files as having a file-level comment.
+    pub fn with_commented_files(mut self, paths: &'a HashSet<String>) -> Self {
+        self.commented_files = Some(paths);
+        self
+    }
+
+    /// Scale stat bars against this diff and show its totals in the bottom border.
+    pub fn with_diff_stats(mut self, diff: &'a PullRequestDiff) -> Self {
+        self.diff = Some(diff);
+        self
+    }
+
+    /// Show files with more than `threshold` changed lines in bold.
+    pub fn with_large_file_threshold(mut self, threshold: usize) -> Self {
+        self.large_file_threshold = Some(threshold);
+        self
+    }
 }
 
 impl<T: ThemeProvider> Widget for FileTreeWidget<'_, T> {
@@ -38,11 +71,22 @@ impl<T: ThemeProvider> Widget for FileTreeWidget<'_, T> {
             Style::default().fg(Color::DarkGray)
         };
 
-        let block = Block::default()
+        let mut block = Block::default()
             .borders(Borders::ALL)
             .border_style(border_style)
             .title(" Files ");
 
+        // Totals in the bottom border, shortened to what fits
+        if let Some(diff) = self.diff {
+            let inner_width = area.width.saturating_sub(2) as usize;
+            let summary = [diff.stat_summary(), diff.stat_text()]
+                .into_iter()
+                .find(|text| gh_text_width::width(text) + 2 <= inner_width);
+            if let Some(summary) = summary {
+                block = block.title_bottom(format!(" {} ", summary));
+            }
+        }
+
         let inner = block.inner(area);
         block.render(area, buf);
 
@@ -99,26 +143,39 @@ impl<T: ThemeProvider> FileTreeWidget<'_, T> {
             None => "",
         };
 
-        // Stats
-        let stats = if entry.additions > 0 || entry.deletions > 0 {
-            format!(" +{}/-{}", entry.additions, entry.deletions)
-        } else {
-            String::new()
+        // File-level comment marker after the name
+        let has_file_comment = match (&entry.path, self.commented_files) {
+            (Some(path), Some(paths)) => paths.contains(path),
+            _ => false,
         };
+        let marker = if has_file_comment { " 💬" } else { "" };
+        let marker_width = gh_text_width::width(marker);
+
+        // Stats for files, and aggregates for collapsed directories
+        let changes = entry.additions + entry.deletions;
+        let has_stats = changes > 0 && !(entry.is_dir && entry.is_expanded);
+        let bar_width = stat_bar_width(width as usize);
+        let bar_area = if bar_width > 0 { bar_width + 1 } else { 0 };
+        let stats = format!("+{} −{}", entry.additions, entry.deletions);
+        let stats_len = gh_text_width::width(&stats) + bar_area;
 
         // Calculate available width for name
-        // tree_prefix uses 3 chars per level ("├─ ", "│  ", etc.)
-        let prefix_len = tree_prefix.chars().count() + icon.chars().count() + status_char.len();
-        let stats_len = stats.len();
-        let available = (width as usize).saturating_sub(prefix_len + stats_len + 1);
-
-        // Truncate name if needed
-        let name = if entry.name.len() > available {
-            format!("{}…", &entry.name[..available.saturating_sub(1)])
+        // tree_prefix uses 3 cells per level ("├─ ", "│  ", etc.)
+        let prefix_len = gh_text_width::width(&tree_prefix)
+            + gh_text_width::width(icon)
+            + gh_text_width::width(status_char);
+        let name_width = gh_text_width::width(&entry.name);
+        let name_room = (width as usize).saturating_sub(prefix_len + marker_width + 1);
+        let show_stats = has_stats && name_room >= stats_len + name_width.min(MIN_NAME_WIDTH);
+        let available = if show_stats {
+            name_room - stats_len
         } else {
-            entry.name.clone()
+            name_room
         };
 
+        // Elide the middle of long names, keeping the extension
+        let name = elide_middle(&entry.name, available);
+
         // Determine style
         let base_style = if selected {
             Style::default()
@@ -144,7 +201,7 @@ impl<T: ThemeProvider> FileTreeWidget<'_, T> {
             base_style.fg(self.theme.file_tree_border())
         };
         buf.set_string(current_x, y, &tree_prefix, tree_style);
-        current_x += tree_prefix.chars().count() as u16;
+        current_x += gh_text_width::width(&tree_prefix) as u16;
 
         // Render icon
         let icon_style = if entry.is_dir && !selected {
@@ -153,7 +210,7 @@ impl<T: ThemeProvider> FileTreeWidget<'_, T> {
             base_style
         };
         buf.set_string(current_x, y, icon, icon_style);
-        current_x += icon.chars().count() as u16;
+        current_x += gh_text_width::width(icon) as u16;
 
         // Render status
         if !status_char.is_empty() {
@@ -163,21 +220,34 @@ impl<T: ThemeProvider> FileTreeWidget<'_, T> {
                 entry.status.map(|s| s.color()).unwrap_or(Color::White)
             };
             buf.set_string(current_x, y, status_char, base_style.fg(status_color));
-            current_x += status_char.len() as u16;
+            current_x += gh_text_width::width(status_char) as u16;
         }
 
-        // Render name
-        let name_style = if entry.is_dir && !selected {
+        // Render name, in bold for large files
+        let mut name_style = if entry.is_dir && !selected {
             base_style.fg(self.theme.file_tree_directory_foreground())
         } else {
             base_style
         };
+        if !entry.is_dir && self.large_file_threshold.is_some_and(|t| changes > t) {
+            name_style = name_style.add_modifier(Modifier::BOLD);
+        }
         buf.set_string(current_x, y, &name, name_style);
-        current_x += name.len() as u16;
+        current_x += gh_text_width::width(&name) as u16;
+
+        if has_file_comment {
+            let marker_style = if selected {
+                base_style
+            } else {
+                base_style.fg(self.theme.comment_indicator_foreground())
+            };
+            buf.set_string(current_x, y, marker, marker_style);
+            current_x += marker_width as u16;
+        }
 
-        // Render stats at the end
-        if !stats.is_empty() {
-            let stats_x = x + width - stats.len() as u16;
+        // Render stats at the end: `+12 −3 ++++-`
+        if show_stats {
+            let stats_x = x + width - stats_len as u16;
             if stats_x > current_x {
                 let (add_style, del_style) = if selected {
                     (base_style, base_style)
@@ -185,14 +255,20 @@ impl<T: ThemeProvider> FileTreeWidget<'_, T> {
                     (base_style.fg(Color::Green), base_style.fg(Color::Red))
                 };
 
-                // Parse and render colored stats
-                let parts: Vec<&str> = stats.split('/').collect();
-                if parts.len() == 2 {
-                    buf.set_string(stats_x, y, parts[0], add_style);
-                    buf.set_string(stats_x + parts[0].len() as u16, y, " / ", base_style);
-                    buf.set_string(stats_x + parts[0].len() as u16 + 1, y, parts[1], del_style);
-                } else {
-                    buf.set_string(stats_x, y, &stats, base_style);
+                let additions = format!("+{}", entry.additions);
+                let deletions = format!("−{}", entry.deletions);
+                buf.set_string(stats_x, y, &additions, add_style);
+                let deletions_x = stats_x + gh_text_width::width(&additions) as u16 + 1;
+                buf.set_string(deletions_x, y, &deletions, del_style);
+
+                // Directories show their totals only, the bar compares files
+                if bar_width > 0 && !entry.is_dir {
+                    let max_changes = self.diff.map_or(changes, |d| d.max_file_changes());
+                    let (plus, minus) =
+                        stat_bar(entry.additions, entry.deletions, max_changes, bar_width);
+                    let bar_x = x + width - bar_width as u16;
+                    buf.set_string(bar_x, y, "+".repeat(plus), add_style);
+                    buf.set_string(bar_x + plus as u16, y, "-".repeat(minus), del_style);
                 }
             }
         }
@@ -0,0 +1,344 @@
+//! Versioned file migration framework
+//!
+//! Each persisted file declares its current schema version and an ordered
+//! list of single-step migrations. Running a file's migrations:
+//! - Skips missing files and files already at the current version (idempotent)
+//! - Copies the original to `<file>.pre-migration-v<N>` before the first step
+//! - Writes the file after every step, so an interrupted run resumes from the
+//!   last completed version
+//! - Leaves files written by a newer release untouched (loaded read-only)
+
+use anyhow::{bail, Context, Result};
+use gh_pr_config::recovery::write_atomic;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// A single migration step from one schema version to the next
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub from: u32,
+    pub to: u32,
+    /// Transform the file contents of version `from` into version `to`
+    pub migrate: fn(&str) -> Result<String>,
+}
+
+/// A persisted file with its migrations
+#[derive(Debug, Clone)]
+pub struct MigratedFile {
+    /// Short name for logs and the status bar (e.g. "session")
+    pub name: &'static str,
+    /// Schema version this release reads and writes
+    pub current_version: u32,
+    /// Detect the schema version of the file contents
+    pub detect_version: fn(&str) -> Result<u32>,
+    /// Ordered migration steps
+    pub migrations: Vec<Migration>,
+}
+
+/// Outcome of migrating one file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationOutcome {
+    /// Already at the current version
+    UpToDate,
+    /// Migrated between the given versions
+    Migrated { from: u32, to: u32 },
+    /// Written by a newer release - left untouched and loaded read-only
+    NewerThanSupported { found: u32, supported: u32 },
+    /// A step failed; the file is left at the last completed version
+    Failed(String),
+}
+
+/// Result of migrating one file, for logging and the status bar
+#[derive(Debug, Clone)]
+pub struct MigrationReport {
+    pub name: &'static str,
+    pub path: PathBuf,
+    pub outcome: MigrationOutcome,
+}
+
+impl MigrationReport {
+    /// User-facing summary (None if nothing happened)
+    pub fn summary(&self) -> Option<String> {
+        match &self.outcome {
+            MigrationOutcome::UpToDate => None,
+            MigrationOutcome::Migrated { from, to } => {
+                Some(format!("Migrated {} v{}→v{}", self.name, from, to))
+            }
+            MigrationOutcome::NewerThanSupported { found, supported } => Some(format!(
+                "{} is from a newer version (v{} > v{}), loaded read-only",
+                self.name, found, supported
+            )),
+            MigrationOutcome::Failed(error) => {
+                Some(format!("Migrating {} failed: {}", self.name, error))
+            }
+        }
+    }
+
+    /// Whether the user should be warned about this outcome
+    pub fn is_warning(&self) -> bool {
+        matches!(
+            self.outcome,
+            MigrationOutcome::NewerThanSupported { .. } | MigrationOutcome::Failed(_)
+        )
+    }
+}
+
+/// Migrate the file at `path` to the current version
+///
+/// Returns None if the file does not exist or cannot be parsed (corrupted
+/// files are handled by the loaders' recovery).
+pub fn migrate_file(file: &MigratedFile, path: &Path) -> Option<MigrationReport> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => return None,
+        Err(e) => {
+            log::warn!("Cannot read {} {:?} for migration: {}", file.name, path, e);
+            return None;
+        }
+    };
+    let version = match (file.detect_version)(&content) {
+        Ok(version) => version,
+        Err(e) => {
+            log::warn!(
+                "Cannot detect {} schema version of {:?}: {}",
+                file.name,
+                path,
+                e
+            );
+            return None;
+        }
+    };
+
+    let outcome = if version == file.current_version {
+        MigrationOutcome::UpToDate
+    } else if version > file.current_version {
+        log::warn!(
+            "{} {:?} has schema version {}, newer than supported {} - not migrating",
+            file.name,
+            path,
+            version,
+            file.current_version
+        );
+        MigrationOutcome::NewerThanSupported {
+            found: version,
+            supported: file.current_version,
+        }
+    } else {
+        match run_steps(file, path, content, version) {
+            Ok(()) => MigrationOutcome::Migrated {
+                from: version,
+                to: file.current_version,
+            },
+            Err(e) => {
+                log::error!("Migrating {} {:?} failed: {:#}", file.name, path, e);
+                MigrationOutcome::Failed(format!("{:#}", e))
+            }
+        }
+    };
+
+    Some(MigrationReport {
+        name: file.name,
+        path: path.to_path_buf(),
+        outcome,
+    })
+}
+
+/// Apply the steps from `version` up to the current version
+fn run_steps(
+    file: &MigratedFile,
+    path: &Path,
+    mut content: String,
+    mut version: u32,
+) -> Result<()> {
+    backup_before_migration(path, version)?;
+
+    while version < file.current_version {
+        let step = file
+            .migrations
+            .iter()
+            .find(|m| m.from == version)
+            .with_context(|| format!("No migration from v{}", version))?;
+
+        content = (step.migrate)(&content)
+            .with_context(|| format!("Step v{}→v{} failed", step.from, step.to))?;
+        let migrated = (file.detect_version)(&content)?;
+        if migrated != step.to {
+            bail!(
+                "Step v{}→v{} produced version {}",
+                step.from,
+                step.to,
+                migrated
+            );
+        }
+
+        // Persist every step so an interrupted run resumes from here
+        write_atomic(path, content.as_bytes())?;
+        log::info!(
+            "Migrated {} {:?} v{}→v{}",
+            file.name,
+            path,
+            step.from,
+            step.to
+        );
+        version = step.to;
+    }
+    Ok(())
+}
+
+/// Copy the original file to `<file>.pre-migration-v<version>` (kept if it exists)
+fn backup_before_migration(path: &Path, version: u32) -> Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let backup = path.with_file_name(format!("{}.pre-migration-v{}", file_name, version));
+    if !backup.exists() {
+        fs::copy(path, &backup)
+            .with_context(|| format!("Failed to back up {:?} to {:?}", path, backup))?;
+        log::info!("Backed up {:?} to {:?} before migration", path, backup);
+    }
+    Ok(backup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fixture format: first line is "v<N>", the rest is payload
+    fn detect(content: &str) -> Result<u32> {
+        let first = content.lines().next().unwrap_or("v1");
+        Ok(first.trim_start_matches('v').parse()?)
+    }
+
+    fn bump(content: &str) -> Result<String> {
+        let version = detect(content)?;
+        let rest: Vec<&str> = content.lines().skip(1).collect();
+        Ok(format!("v{}\n{}+{}", version + 1, rest.join("\n"), version))
+    }
+
+    fn failing(_content: &str) -> Result<String> {
+        bail!("boom")
+    }
+
+    fn fixture(migrations: Vec<Migration>) -> MigratedFile {
+        MigratedFile {
+            name: "fixture",
+            current_version: 3,
+            detect_version: detect,
+            migrations,
+        }
+    }
+
+    fn steps() -> Vec<Migration> {
+        vec![
+            Migration {
+                from: 1,
+                to: 2,
+                migrate: bump,
+            },
+            Migration {
+                from: 2,
+                to: 3,
+                migrate: bump,
+            },
+        ]
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir()
+            .join(format!("gh-pr-config-migrate-{}", std::process::id()))
+            .join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_missing_file_is_skipped() {
+        let dir = test_dir("missing");
+        assert!(migrate_file(&fixture(steps()), &dir.join("none")).is_none());
+    }
+
+    #[test]
+    fn test_migrates_all_steps_with_backup() {
+        let dir = test_dir("all_steps");
+        let path = dir.join("file");
+        fs::write(&path, "v1\ndata").unwrap();
+
+        let report = migrate_file(&fixture(steps()), &path).unwrap();
+        assert_eq!(
+            report.outcome,
+            MigrationOutcome::Migrated { from: 1, to: 3 }
+        );
+        assert_eq!(report.summary().unwrap(), "Migrated fixture v1→v3");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "v3\ndata+1+2");
+        assert_eq!(
+            fs::read_to_string(dir.join("file.pre-migration-v1")).unwrap(),
+            "v1\ndata"
+        );
+
+        // Running again is a no-op
+        let report = migrate_file(&fixture(steps()), &path).unwrap();
+        assert_eq!(report.outcome, MigrationOutcome::UpToDate);
+        assert!(report.summary().is_none());
+    }
+
+    #[test]
+    fn test_failed_step_keeps_last_completed_version() {
+        let dir = test_dir("resume");
+        let path = dir.join("file");
+        fs::write(&path, "v1\ndata").unwrap();
+
+        let broken = vec![
+            steps()[0],
+            Migration {
+                from: 2,
+                to: 3,
+                migrate: failing,
+            },
+        ];
+        let report = migrate_file(&fixture(broken), &path).unwrap();
+        assert!(matches!(report.outcome, MigrationOutcome::Failed(_)));
+        assert!(report.is_warning());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "v2\ndata+1");
+
+        // A fixed release resumes from v2
+        let report = migrate_file(&fixture(steps()), &path).unwrap();
+        assert_eq!(
+            report.outcome,
+            MigrationOutcome::Migrated { from: 2, to: 3 }
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "v3\ndata+1+2");
+    }
+
+    #[test]
+    fn test_newer_version_is_left_untouched() {
+        let dir = test_dir("newer");
+        let path = dir.join("file");
+        fs::write(&path, "v7\nfuture").unwrap();
+
+        let report = migrate_file(&fixture(steps()), &path).unwrap();
+        assert_eq!(
+            report.outcome,
+            MigrationOutcome::NewerThanSupported {
+                found: 7,
+                supported: 3
+            }
+        );
+        assert!(report.is_warning());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "v7\nfuture");
+        assert!(!dir.join("file.pre-migration-v7").exists());
+    }
+
+    #[test]
+    fn test_missing_step_fails() {
+        let dir = test_dir("gap");
+        let path = dir.join("file");
+        fs::write(&path, "v1\ndata").unwrap();
+
+        let report = migrate_file(&fixture(vec![steps()[1]]), &path).unwrap();
+        assert!(matches!(report.outcome, MigrationOutcome::Failed(_)));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "v1\ndata");
+    }
+}
@@ -6,6 +6,13 @@
 //! # Migrations
 //!
 //! - `.session.json` → `~/.config/gh-pr-lander/session.toml`
+//! - Versioned files (session, recent repositories) are upgraded step by step
+//!   to their current `schema_version`, see [`framework`] and [`registry`].
+
+pub mod framework;
+pub mod registry;
+
+pub use framework::{migrate_file, MigratedFile, Migration, MigrationOutcome, MigrationReport};
 
 use anyhow::{Context, Result};
 use gh_pr_config::{global_session_path, Session};
@@ -28,10 +35,16 @@ struct OldSessionJson {
 ///
 /// This should be called during application bootstrap.
 /// Migrations are idempotent - they only run if needed.
-pub fn run_migrations() {
+/// Returns one report per existing versioned file.
+pub fn run_migrations() -> Vec<MigrationReport> {
     if let Err(e) = migrate_session() {
         log::warn!("Session migration failed: {}", e);
     }
+
+    registry::all()
+        .iter()
+        .filter_map(|(file, path)| migrate_file(file, path))
+        .collect()
 }
 
 /// Migrate `.session.json` to TOML format
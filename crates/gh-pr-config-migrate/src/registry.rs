@@ -0,0 +1,173 @@
+//! Registry of persisted files and their migration steps
+//!
+//! To change a file format: bump its version in `gh_pr_config::schema`,
+//! then append a step here with a fixture test.
+
+use anyhow::{Context, Result};
+use gh_pr_config::schema::{
+    self, RECENT_REPOSITORIES_SCHEMA_VERSION, SCHEMA_VERSION_FIELD, SESSION_SCHEMA_VERSION,
+};
+use gh_pr_config::{
+    global_session_path, local_session_path, DEFAULT_PROFILE, RECENT_REPOSITORIES_FILE,
+};
+use std::path::PathBuf;
+
+use crate::framework::{MigratedFile, Migration};
+
+/// Session file (`session.toml`, local and global)
+pub fn session() -> MigratedFile {
+    MigratedFile {
+        name: "session",
+        current_version: SESSION_SCHEMA_VERSION,
+        detect_version: schema::toml_schema_version,
+        migrations: vec![Migration {
+            from: 1,
+            to: 2,
+            migrate: session_v1_to_v2,
+        }],
+    }
+}
+
+/// Recent repositories file (`.gh-pr-lander.repos.json`)
+pub fn recent_repositories() -> MigratedFile {
+    MigratedFile {
+        name: "recent repositories",
+        current_version: RECENT_REPOSITORIES_SCHEMA_VERSION,
+        detect_version: schema::json_schema_version,
+        migrations: vec![
+            Migration {
+                from: 1,
+                to: 2,
+                migrate: recent_repositories_v1_to_v2,
+            },
+            Migration {
+                from: 2,
+                to: 3,
+                migrate: recent_repositories_v2_to_v3,
+            },
+        ],
+    }
+}
+
+/// All migrated files with the paths they live at
+pub fn all() -> Vec<(MigratedFile, PathBuf)> {
+    let mut files = Vec::new();
+    for path in [local_session_path(), global_session_path()]
+        .into_iter()
+        .flatten()
+    {
+        files.push((session(), path));
+    }
+    files.push((
+        recent_repositories(),
+        PathBuf::from(RECENT_REPOSITORIES_FILE),
+    ));
+    files
+}
+
+/// v1 → v2: `[meta] version` becomes the top-level `schema_version`
+fn session_v1_to_v2(content: &str) -> Result<String> {
+    let mut table: toml::Table = toml::from_str(content).context("Invalid session TOML")?;
+    if let Some(toml::Value::Table(meta)) = table.get_mut("meta") {
+        meta.remove("version");
+    }
+    table.insert(SCHEMA_VERSION_FIELD.to_string(), toml::Value::Integer(2));
+    Ok(toml::to_string_pretty(&table)?)
+}
+
+/// v1 → v2: the bare array is wrapped in `{ schema_version, repositories }`
+fn recent_repositories_v1_to_v2(content: &str) -> Result<String> {
+    let repositories: serde_json::Value =
+        serde_json::from_str(content).context("Invalid recent repositories JSON")?;
+    let wrapped = serde_json::json!({
+        SCHEMA_VERSION_FIELD: 2,
+        "repositories": repositories,
+    });
+    Ok(serde_json::to_string_pretty(&wrapped)?)
+}
+
+/// v2 → v3: the repositories become the "default" workspace profile
+fn recent_repositories_v2_to_v3(content: &str) -> Result<String> {
+    let value: serde_json::Value =
+        serde_json::from_str(content).context("Invalid recent repositories JSON")?;
+    let repositories = value
+        .get("repositories")
+        .cloned()
+        .unwrap_or_else(|| serde_json::json!([]));
+    let wrapped = serde_json::json!({
+        SCHEMA_VERSION_FIELD: 3,
+        "profiles": [{ "name": DEFAULT_PROFILE, "repositories": repositories }],
+    });
+    Ok(serde_json::to_string_pretty(&wrapped)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gh_pr_config::Session;
+
+    const SESSION_V1: &str = r#"[meta]
+last_modified = "2025-01-01T10:00:00Z"
+version = 1
+
+[session]
+selected_repo_org = "org"
+selected_repo_name = "repo"
+selected_repo_branch = "main"
+selected_pr_no = 42
+"#;
+
+    const RECENT_REPOSITORIES_V1: &str =
+        r#"[{"org": "org", "repo": "one", "branch": "main"}, {"org": "org", "repo": "two"}]"#;
+
+    #[test]
+    fn test_session_v1_to_v2() {
+        let migrated = session_v1_to_v2(SESSION_V1).unwrap();
+        assert_eq!(schema::toml_schema_version(&migrated).unwrap(), 2);
+        assert!(!migrated.contains("version = 1"));
+
+        let session: Session = toml::from_str(&migrated).unwrap();
+        assert_eq!(session.schema_version, 2);
+        assert_eq!(session.selected_pr_no(), Some(42));
+        assert_eq!(session.selected_repo().unwrap().1, "repo");
+    }
+
+    #[test]
+    fn test_recent_repositories_v1_to_v2() {
+        let migrated = recent_repositories_v1_to_v2(RECENT_REPOSITORIES_V1).unwrap();
+        assert_eq!(schema::json_schema_version(&migrated).unwrap(), 2);
+
+        let value: serde_json::Value = serde_json::from_str(&migrated).unwrap();
+        let repos = value["repositories"].as_array().unwrap();
+        assert_eq!(repos.len(), 2);
+        assert_eq!(repos[1]["repo"], "two");
+    }
+
+    #[test]
+    fn test_recent_repositories_v2_to_v3() {
+        let v2 = recent_repositories_v1_to_v2(RECENT_REPOSITORIES_V1).unwrap();
+        let migrated = recent_repositories_v2_to_v3(&v2).unwrap();
+        assert_eq!(schema::json_schema_version(&migrated).unwrap(), 3);
+
+        let value: serde_json::Value = serde_json::from_str(&migrated).unwrap();
+        let profiles = value["profiles"].as_array().unwrap();
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0]["name"], "default");
+        let repos = profiles[0]["repositories"].as_array().unwrap();
+        assert_eq!(repos.len(), 2);
+        assert_eq!(repos[1]["repo"], "two");
+        assert!(value.get("repositories").is_none());
+    }
+
+    #[test]
+    fn test_registries_reach_current_version() {
+        for file in [session(), recent_repositories()] {
+            let mut version = 1;
+            while let Some(step) = file.migrations.iter().find(|m| m.from == version) {
+                assert_eq!(step.to, step.from + 1, "{} steps must be single", file.name);
+                version = step.to;
+            }
+            assert_eq!(version, file.current_version, "{} has a gap", file.name);
+        }
+    }
+}
@@ -2,8 +2,186 @@
 //!
 //! Configuration loaded from gh-pr-tui.toml file.
 
+use crate::filename_template::FilenameTemplate;
+use crate::recovery::{self, Recovered, RecoveryNotice};
+use anyhow::Context;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
+use std::path::{Path, PathBuf};
+
+/// Commented default config written on first run
+///
+/// Every setting is commented out so the built-in defaults keep applying
+/// until the user opts in.
+const DEFAULT_CONFIG_TEMPLATE: &str = r##"# gh-pr-lander configuration
+#
+# Uncomment and adjust the settings you want to change.
+# Settings that are left commented out use the built-in defaults.
+
+# Command used to open PRs in your IDE (e.g. "code", "zed", "cursor")
+# ide_command = "code"
+
+# How URLs are opened: "system" (the default browser), a command the URL is
+# appended to (e.g. ["firefox", "-P", "work"]), "clipboard" to copy the URL
+# or "print" to show it in the status bar (headless machines)
+# browser_command = "system"
+
+# Directory used for cloning PRs
+# temp_dir = "/tmp/gh-pr-lander"
+
+# Default message for PR approvals
+# approval_message = ":rocket: thanks for your contribution"
+
+# Default message for PR comments (empty = enter a comment each time)
+# comment_message = ""
+
+# Default message for requesting changes on PRs
+# request_changes_message = "Please address the following concerns:"
+
+# Default message for closing PRs (also used when a repository's close
+# comment template can't be fetched)
+# close_message = "Closing this PR."
+
+# GitHub API requests per minute and host, shared by all background and
+# interactive requests (0 = unlimited)
+# requests_per_minute = 300
+
+# Number of tabs loaded in parallel at startup, the tab selected last time
+# always is (unset = all). The other tabs load once they are opened.
+# startup_load = 3
+
+# Days to keep your local PR notes once the PR is merged or closed
+# note_retention_days = 30
+
+# Status pages asked for incidents when GitHub requests keep failing, keyed
+# by host (github.com uses https://www.githubstatus.com/api/v2/summary.json)
+#
+# [status_pages]
+# "ghe.example.com" = "https://status.example.com/api/v2/summary.json"
+
+# Appearance and accessibility
+# theme: "dark" or "high-contrast" (labels every state, never color alone)
+# reduced_motion: replace spinners and animations with static indicators
+#
+# [ui]
+# theme = "dark"
+# reduced_motion = false
+# # Mark running operations (merge, rebase, ...) as possibly stuck after this many seconds
+# stuck_operation_secs = 60
+# # Tab past the last repository: "wrap" to the first one or "stop"
+# tab_cycling = "wrap"
+# # Files with more changed lines are shown in bold in the diff viewer's file tree
+# large_file_changes = 200
+# # Timestamps: "relative" (5m ago), "absolute" (2025-01-15 12:00) or "both"
+# timestamps = "relative"
+# # Time zone of absolute timestamps: "UTC" or an offset like "+05:30" (default: system time zone)
+# timezone = "UTC"
+# # strftime format of absolute timestamps and the {date} of export file names
+# date_format = "%Y-%m-%d %H:%M"
+# # Name of exported build logs; placeholders: {host}, {org}, {repo}, {pr}, {date}
+# export_filename = "{repo}-{pr}-{date}.log"
+# # Show the focused PR's description once the cursor rests on it for a second
+# auto_peek = false
+# # Fold successful and skipped jobs of the build logs into one "N succeeded" node
+# collapse_successful_jobs = true
+# # Bold the titles of PRs updated since they were last seen (marked with ●)
+# bold_unseen = true
+# # Sort PRs by what needs you (changes requested, review requested, failing CI) on start
+# sort_by_attention = false
+# # q on the main view quits; false leaves quitting to Ctrl+C and the "Quit" command
+# quit_on_close = true
+# # Largest number of changed lines of the XS, S, M and L size buckets, XL beyond
+# pr_size = { xs = 10, s = 50, m = 250, l = 1000 }
+
+# Logging to the debug log file (also shown in the debug console)
+# default/modules: "error", "warn", "info", "debug", "trace" or "off";
+# modules are keyed by module path (e.g. "gh_client" or "gh_pr_lander::middleware")
+# rotation: "size" (every max_size_mb), "daily" or "never"; keep_files old files are kept
+# format: "text", or "json" for one JSON object per line (lnav, jq)
+# The RUST_LOG environment variable ("info,gh_client=debug") overrides the levels.
+#
+# [logging]
+# default = "debug"
+# modules = { "gh_client" = "debug", "octocrab" = "warn" }
+# rotation = "size"
+# max_size_mb = 10
+# keep_files = 5
+# format = "text"
+
+# Timing of GitHub API calls, shown by the "Performance stats" command
+# summary_interval_minutes: log a one-line summary this often (0 = never)
+#
+# [metrics]
+# enabled = true
+# summary_interval_minutes = 10
+
+# Sections of the release notes drafted by "Generate release notes…"
+# A merged PR is listed under the first group matching one of its labels,
+# the conventional commit type of its title ("feat: ...", "fix(ui): ...")
+# or, with dependency_updates, being a dependency update. Others are listed
+# under other. Setting groups replaces the default ones below.
+#
+# [release_notes]
+# other = "Other Changes"
+#
+# [[release_notes.groups]]
+# title = "Features"
+# labels = ["feature", "enhancement"]
+# prefixes = ["feat"]
+#
+# [[release_notes.groups]]
+# title = "Bug Fixes"
+# labels = ["bug", "fix"]
+# prefixes = ["fix"]
+#
+# [[release_notes.groups]]
+# title = "Dependencies"
+# labels = ["dependencies"]
+# prefixes = ["deps"]
+# dependency_updates = true
+
+# External issue trackers - links issue references in PR titles/descriptions
+# Placeholders in url: $ISSUE_NO, $ORG, $REPO, $HOST
+#
+# [[issue_tracker]]
+# name = "Jira"
+# pattern = "PROJ-\\d+"
+# url = "https://jira.example.com/browse/$ISSUE_NO"
+# repos = ["my-org/*"]
+
+# Per-repository settings, keyed by "org/repo" or "org/*"
+# profile selects the account used for these repositories; its token comes
+# from GITHUB_TOKEN_<HOST>_<PROFILE>, the token store or `gh auth token --user`
+# delete_branch_after_merge presets "delete branch after merge" in the merge
+# confirmation (unset = the repository's "Automatically delete head branches")
+# close_comment_template is a file in the repository (default branch) that
+# pre-fills the close comment; placeholders: {pr_number}, {author}
+# default_filter names a saved filter (filters.toml) applied when the
+# repository's PRs are first loaded
+#
+# [repos."work-org/*"]
+# profile = "work"
+# delete_branch_after_merge = true
+# close_comment_template = ".github/CLOSING_COMMENT.md"
+# default_filter = "security-bumps"
+
+# Policies approve and/or merge matching PRs after a dry-run report
+# ("Run policy" command). All set matchers must match; empty lists match all.
+# ci: "success" (green CI), "not-failed" or "any"
+# action: "approve-merge", "approve" or "merge"
+# filter names a saved filter (filters.toml) the PRs must match as well
+#
+# [policies.auto-bot]
+# authors = ["dependabot[bot]", "renovate*"]
+# update_types = ["patch", "minor"]
+# ci = "success"
+# max_additions = 200
+# labels_allow = []
+# labels_deny = ["do-not-merge"]
+# filter = "security-bumps"
+# action = "approve-merge"
+"##;
 
 /// Configuration for an external issue tracker (Jira, Linear, etc.)
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -26,6 +204,10 @@ pub struct AppConfig {
     #[serde(default = "default_ide_command")]
     pub ide_command: String,
 
+    /// How URLs are opened (system browser, a command, clipboard or print)
+    #[serde(default)]
+    pub browser_command: BrowserCommand,
+
     /// Temporary directory for cloning PRs
     #[serde(default = "default_temp_dir")]
     pub temp_dir: String,
@@ -49,6 +231,535 @@ pub struct AppConfig {
     /// External issue tracker configurations
     #[serde(default)]
     pub issue_tracker: Vec<IssueTrackerConfig>,
+
+    /// Per-repository settings keyed by "org/repo" or "org/*"
+    #[serde(default)]
+    pub repos: BTreeMap<String, RepoSettings>,
+
+    /// API requests per minute and host (None = built-in default, 0 = unlimited)
+    #[serde(default)]
+    pub requests_per_minute: Option<u32>,
+
+    /// First tabs loaded at startup (None = all), the selected one always is
+    #[serde(default)]
+    pub startup_load: Option<usize>,
+
+    /// Days to keep local notes of merged or closed PRs
+    #[serde(default = "default_note_retention_days")]
+    pub note_retention_days: u32,
+
+    /// Statuspage API URLs keyed by host, for hosts other than github.com
+    #[serde(default)]
+    pub status_pages: BTreeMap<String, String>,
+
+    /// Appearance and accessibility settings
+    #[serde(default)]
+    pub ui: UiConfig,
+
+    /// Auto-approve/merge policies keyed by name (`[policies.<name>]`)
+    #[serde(default)]
+    pub policies: BTreeMap<String, PolicyConfig>,
+
+    /// Log levels, rotation and format of the log file
+    #[serde(default)]
+    pub logging: LoggingConfig,
+
+    /// Timing of GitHub API calls
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+
+    /// Sections of drafted release notes
+    #[serde(default)]
+    pub release_notes: ReleaseNotesConfig,
+}
+
+/// How URLs are opened (`browser_command`)
+///
+/// Written as "system", "clipboard", "print", a program name or a list of
+/// program and arguments the URL is appended to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum BrowserCommand {
+    /// The system's default browser (`open`, `xdg-open`, `start`)
+    #[default]
+    System,
+    /// Program and arguments, the URL is passed as last argument
+    Command(Vec<String>),
+    /// Copy the URL to the clipboard instead of opening it
+    Clipboard,
+    /// Show the URL in the status bar instead of opening it
+    Print,
+}
+
+impl Serialize for BrowserCommand {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::System => serializer.serialize_str("system"),
+            Self::Command(args) => args.serialize(serializer),
+            Self::Clipboard => serializer.serialize_str("clipboard"),
+            Self::Print => serializer.serialize_str("print"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BrowserCommand {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Name(String),
+            Args(Vec<String>),
+        }
+
+        let args = match Raw::deserialize(deserializer)? {
+            Raw::Name(name) => match name.as_str() {
+                "system" => return Ok(Self::System),
+                "clipboard" => return Ok(Self::Clipboard),
+                "print" => return Ok(Self::Print),
+                _ => vec![name],
+            },
+            Raw::Args(args) => args,
+        };
+        if args.first().is_none_or(|program| program.trim().is_empty()) {
+            return Err(serde::de::Error::custom("browser_command names no program"));
+        }
+        Ok(Self::Command(args))
+    }
+}
+
+/// Appearance and accessibility settings (`[ui]` section)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct UiConfig {
+    /// Name of the built-in theme ("dark" or "high-contrast")
+    #[serde(default = "default_theme")]
+    pub theme: String,
+
+    /// Replace spinners and animations with static indicators
+    #[serde(default)]
+    pub reduced_motion: bool,
+
+    /// Seconds after which a running operation is marked as possibly stuck
+    #[serde(default = "default_stuck_operation_secs")]
+    pub stuck_operation_secs: u64,
+
+    /// What Tab/Shift+Tab do past the first or last repository
+    #[serde(default)]
+    pub tab_cycling: TabCycling,
+
+    /// Changed lines above which the diff file tree shows a file in bold
+    #[serde(default = "default_large_file_changes")]
+    pub large_file_changes: usize,
+
+    /// How timestamps are shown (relative, absolute or both)
+    #[serde(default)]
+    pub timestamps: TimestampStyle,
+
+    /// Time zone of absolute timestamps, "UTC" or an offset like "+05:30"
+    /// (None = system time zone)
+    #[serde(default)]
+    pub timezone: Option<String>,
+
+    /// strftime format of absolute timestamps and exported file names
+    #[serde(
+        default = "default_date_format",
+        deserialize_with = "deserialize_date_format"
+    )]
+    pub date_format: String,
+
+    /// File name template of exported build logs
+    #[serde(default)]
+    pub export_filename: FilenameTemplate,
+
+    /// Show the peek popup when the cursor rests on a PR for a moment
+    #[serde(default)]
+    pub auto_peek: bool,
+
+    /// Fold successful and skipped jobs of the build logs into one node
+    #[serde(default = "default_true")]
+    pub collapse_successful_jobs: bool,
+
+    /// Bold the titles of PRs updated since they were last seen
+    #[serde(default = "default_true")]
+    pub bold_unseen: bool,
+
+    /// Start with the attention sort instead of newest first
+    #[serde(default)]
+    pub sort_by_attention: bool,
+
+    /// Close (q) on the root view quits the app
+    #[serde(default = "default_true")]
+    pub quit_on_close: bool,
+
+    /// Changed lines bounding the PR size buckets
+    #[serde(default)]
+    pub pr_size: PrSizeThresholds,
+}
+
+impl UiConfig {
+    /// Fixed offset of the configured time zone (None = system time zone)
+    ///
+    /// Unparsable values fall back to the system time zone.
+    pub fn timezone_offset(&self) -> Option<chrono::FixedOffset> {
+        let timezone = self.timezone.as_deref()?.trim();
+        if timezone.eq_ignore_ascii_case("utc") || timezone == "Z" {
+            return chrono::FixedOffset::east_opt(0);
+        }
+        match timezone.parse() {
+            Ok(offset) => Some(offset),
+            Err(_) => {
+                log::warn!(
+                    "Ignoring unknown time zone {:?}, using local time",
+                    timezone
+                );
+                None
+            }
+        }
+    }
+}
+
+/// How timestamps are shown
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimestampStyle {
+    /// Time since the event ("5m ago")
+    #[default]
+    Relative,
+    /// ISO 8601 date and time ("2025-01-15 12:00")
+    Absolute,
+    /// Absolute time followed by the relative one
+    Both,
+}
+
+/// Largest number of changed lines (additions + deletions) of each PR size
+/// bucket, bigger PRs are XL
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(default)]
+pub struct PrSizeThresholds {
+    pub xs: usize,
+    pub s: usize,
+    pub m: usize,
+    pub l: usize,
+}
+
+impl Default for PrSizeThresholds {
+    fn default() -> Self {
+        Self {
+            xs: 10,
+            s: 50,
+            m: 250,
+            l: 1000,
+        }
+    }
+}
+
+/// Behavior of repository tab cycling at the ends of the list
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TabCycling {
+    /// Jump from the last repository to the first and back
+    #[default]
+    Wrap,
+    /// Stay on the first/last repository
+    Stop,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            theme: default_theme(),
+            reduced_motion: false,
+            stuck_operation_secs: default_stuck_operation_secs(),
+            tab_cycling: TabCycling::default(),
+            large_file_changes: default_large_file_changes(),
+            timestamps: TimestampStyle::default(),
+            timezone: None,
+            date_format: default_date_format(),
+            export_filename: FilenameTemplate::default(),
+            auto_peek: false,
+            collapse_successful_jobs: true,
+            bold_unseen: true,
+            sort_by_attention: false,
+            quit_on_close: true,
+            pr_size: PrSizeThresholds::default(),
+        }
+    }
+}
+
+/// Log file settings (`[logging]` section)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct LoggingConfig {
+    /// Level of modules without an entry in `modules` ("info", "debug", ...)
+    #[serde(default = "default_log_level")]
+    pub default: String,
+
+    /// Levels per module path prefix (e.g. "gh_client" = "debug")
+    #[serde(default)]
+    pub modules: BTreeMap<String, String>,
+
+    /// When to start a new log file
+    #[serde(default)]
+    pub rotation: LogRotation,
+
+    /// Size in MiB after which a new file is started (`rotation = "size"`)
+    #[serde(default = "default_log_max_size_mb")]
+    pub max_size_mb: u64,
+
+    /// Number of old log files kept next to the active one
+    #[serde(default = "default_log_keep_files")]
+    pub keep_files: usize,
+
+    /// Line format of the log file
+    #[serde(default)]
+    pub format: LogFormat,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            default: default_log_level(),
+            modules: BTreeMap::new(),
+            rotation: LogRotation::default(),
+            max_size_mb: default_log_max_size_mb(),
+            keep_files: default_log_keep_files(),
+            format: LogFormat::default(),
+        }
+    }
+}
+
+/// API call metrics settings (`[metrics]` section)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct MetricsConfig {
+    /// Record call counts and latencies per endpoint
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Minutes between one-line summaries in the log (0 = never)
+    #[serde(default = "default_metrics_summary_interval_minutes")]
+    pub summary_interval_minutes: u64,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            summary_interval_minutes: default_metrics_summary_interval_minutes(),
+        }
+    }
+}
+
+/// Release notes settings (`[release_notes]` section)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ReleaseNotesConfig {
+    /// Sections in order, a PR is listed under the first one it matches
+    #[serde(default = "default_release_notes_groups")]
+    pub groups: Vec<ReleaseNotesGroup>,
+
+    /// Heading of the PRs matching no group
+    #[serde(default = "default_release_notes_other")]
+    pub other: String,
+}
+
+impl Default for ReleaseNotesConfig {
+    fn default() -> Self {
+        Self {
+            groups: default_release_notes_groups(),
+            other: default_release_notes_other(),
+        }
+    }
+}
+
+/// A section of the release notes (`[[release_notes.groups]]`)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ReleaseNotesGroup {
+    /// Heading of the section
+    pub title: String,
+
+    /// Labels of the PRs listed (case-insensitive)
+    #[serde(default)]
+    pub labels: Vec<String>,
+
+    /// Conventional commit types of the titles listed ("feat" for "feat: ...")
+    #[serde(default)]
+    pub prefixes: Vec<String>,
+
+    /// List dependency updates (Dependabot, Renovate) too
+    #[serde(default)]
+    pub dependency_updates: bool,
+}
+
+/// When the log file is rotated
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogRotation {
+    /// Once the file reaches `max_size_mb`
+    #[default]
+    Size,
+    /// On the first log line of a new day
+    Daily,
+    /// Never, one file per run
+    Never,
+}
+
+/// Line format of the log file
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogFormat {
+    /// `<timestamp> [LEVEL] target: message`
+    #[default]
+    Text,
+    /// One JSON object per line with `ts`, `level`, `target` and `msg`
+    Json,
+}
+
+/// Which PRs a policy acts on and what it does with them (`[policies.<name>]`)
+///
+/// Every matcher that is set must match; empty lists match everything.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct PolicyConfig {
+    /// Author patterns, `*` is a wildcard (e.g. "renovate*")
+    #[serde(default)]
+    pub authors: Vec<String>,
+
+    /// Allowed dependency update types ("patch", "minor", "major")
+    #[serde(default)]
+    pub update_types: Vec<String>,
+
+    /// Required CI state
+    #[serde(default)]
+    pub ci: PolicyCi,
+
+    /// Maximum number of added lines
+    #[serde(default)]
+    pub max_additions: Option<usize>,
+
+    /// The PR must carry at least one of these labels
+    #[serde(default)]
+    pub labels_allow: Vec<String>,
+
+    /// The PR must carry none of these labels
+    #[serde(default)]
+    pub labels_deny: Vec<String>,
+
+    /// Name of a saved filter the PR must match
+    #[serde(default)]
+    pub filter: Option<String>,
+
+    /// What to do with matching PRs
+    #[serde(default)]
+    pub action: PolicyAction,
+}
+
+/// CI state a policy requires
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PolicyCi {
+    /// All checks passed
+    #[default]
+    Success,
+    /// No failed checks or conflicts (pending is fine)
+    NotFailed,
+    /// CI is not looked at
+    Any,
+}
+
+/// What a policy does with matching PRs
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PolicyAction {
+    /// Approve, then merge
+    #[default]
+    ApproveMerge,
+    /// Approve only
+    Approve,
+    /// Merge only
+    Merge,
+}
+
+impl PolicyAction {
+    /// Whether the PR gets approved
+    pub fn approves(self) -> bool {
+        matches!(self, Self::ApproveMerge | Self::Approve)
+    }
+
+    /// Whether the PR gets merged
+    pub fn merges(self) -> bool {
+        matches!(self, Self::ApproveMerge | Self::Merge)
+    }
+}
+
+fn default_note_retention_days() -> u32 {
+    30
+}
+
+fn default_theme() -> String {
+    "dark".to_string()
+}
+
+fn default_stuck_operation_secs() -> u64 {
+    60
+}
+
+fn default_date_format() -> String {
+    "%Y-%m-%d %H:%M".to_string()
+}
+
+/// Reject strftime formats chrono cannot render
+fn deserialize_date_format<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use chrono::format::{Item, StrftimeItems};
+
+    let format = String::deserialize(deserializer)?;
+    if StrftimeItems::new(&format).any(|item| matches!(item, Item::Error)) {
+        return Err(serde::de::Error::custom(format!(
+            "invalid date format {:?}",
+            format
+        )));
+    }
+    Ok(format)
+}
+
+fn default_large_file_changes() -> usize {
+    200
+}
+
+fn default_log_level() -> String {
+    "debug".to_string()
+}
+
+fn default_log_max_size_mb() -> u64 {
+    10
+}
+
+fn default_log_keep_files() -> usize {
+    5
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_release_notes_groups() -> Vec<ReleaseNotesGroup> {
+    let group =
+        |title: &str, labels: &[&str], prefixes: &[&str], dependency_updates| ReleaseNotesGroup {
+            title: title.to_string(),
+            labels: labels.iter().map(|label| label.to_string()).collect(),
+            prefixes: prefixes.iter().map(|prefix| prefix.to_string()).collect(),
+            dependency_updates,
+        };
+    vec![
+        group("Features", &["feature", "enhancement"], &["feat"], false),
+        group("Bug Fixes", &["bug", "fix"], &["fix"], false),
+        group("Dependencies", &["dependencies"], &["deps"], true),
+    ]
+}
+
+fn default_release_notes_other() -> String {
+    "Other Changes".to_string()
+}
+
+fn default_metrics_summary_interval_minutes() -> u64 {
+    10
 }
 
 fn default_ide_command() -> String {
@@ -62,6 +773,23 @@ fn default_temp_dir() -> String {
         .to_string()
 }
 
+/// Settings applying to a repository or all repositories of an org
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct RepoSettings {
+    /// Account profile to use (None = the host's default account)
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Delete the head branch after merging (None = the repository's setting)
+    #[serde(default)]
+    pub delete_branch_after_merge: Option<bool>,
+    /// File in the repository pre-filling the close comment
+    #[serde(default)]
+    pub close_comment_template: Option<String>,
+    /// Name of a saved filter applied when the PRs are first loaded
+    #[serde(default)]
+    pub default_filter: Option<String>,
+}
+
 fn default_approval_message() -> String {
     ":rocket: thanks for your contribution".to_string()
 }
@@ -82,12 +810,23 @@ impl Default for AppConfig {
     fn default() -> Self {
         Self {
             ide_command: default_ide_command(),
+            browser_command: BrowserCommand::default(),
             temp_dir: default_temp_dir(),
             approval_message: default_approval_message(),
             comment_message: default_comment_message(),
             request_changes_message: default_request_changes_message(),
             close_message: default_close_message(),
             issue_tracker: Vec::new(),
+            repos: BTreeMap::new(),
+            requests_per_minute: None,
+            startup_load: None,
+            note_retention_days: default_note_retention_days(),
+            status_pages: BTreeMap::new(),
+            ui: UiConfig::default(),
+            policies: BTreeMap::new(),
+            logging: LoggingConfig::default(),
+            metrics: MetricsConfig::default(),
+            release_notes: ReleaseNotesConfig::default(),
         }
     }
 }
@@ -95,26 +834,122 @@ impl Default for AppConfig {
 impl AppConfig {
     /// Load config from CWD first, then home directory, or use defaults
     pub fn load() -> Self {
-        if let Some(content) = crate::load_config_file() {
-            match toml::from_str(&content) {
-                Ok(config) => {
-                    log::info!("Loaded app config from file");
-                    return config;
-                }
-                Err(e) => {
-                    log::warn!("Failed to parse config file: {}", e);
-                }
+        Self::load_with_recovery().0
+    }
+
+    /// Load config, recovering from a corrupted config file
+    ///
+    /// Invalid fields fall back to their defaults; an unparsable file is backed
+    /// up and defaults are used. The notice describes what happened.
+    pub fn load_with_recovery() -> (Self, Option<RecoveryNotice>) {
+        let path = match crate::app_config_path() {
+            Ok(path) => path,
+            Err(e) => {
+                log::warn!("Could not determine config path: {}", e);
+                return (Self::default(), None);
+            }
+        };
+
+        match recovery::load_toml_with_recovery::<Self>(&path, "config") {
+            Recovered::Missing => {
+                log::debug!("Using default app config");
+                (Self::default(), None)
+            }
+            Recovered::Loaded(config, notice) => {
+                log::info!("Loaded app config from file");
+                (config, notice)
             }
         }
+    }
+
+    /// Settings for a repository: "org/repo" wins over "org/*", which wins over "*"
+    pub fn repo_settings(&self, org: &str, repo: &str) -> Option<&RepoSettings> {
+        [
+            format!("{}/{}", org, repo),
+            format!("{}/*", org),
+            "*".to_string(),
+        ]
+        .iter()
+        .find_map(|key| self.repos.get(key))
+    }
 
-        log::debug!("Using default app config");
-        Self::default()
+    /// Account profile configured for a repository
+    pub fn profile_for(&self, org: &str, repo: &str) -> Option<&str> {
+        self.repo_settings(org, repo)
+            .and_then(|settings| settings.profile.as_deref())
+    }
+
+    /// Whether to delete head branches after merging in a repository
+    ///
+    /// The most specific entry that sets it wins, None if no entry does.
+    pub fn delete_branch_after_merge_for(&self, org: &str, repo: &str) -> Option<bool> {
+        [
+            format!("{}/{}", org, repo),
+            format!("{}/*", org),
+            "*".to_string(),
+        ]
+        .iter()
+        .find_map(|key| self.repos.get(key)?.delete_branch_after_merge)
+    }
+
+    /// Path of the close comment template of a repository
+    ///
+    /// The most specific entry that sets it wins, None if no entry does.
+    pub fn close_comment_template_for(&self, org: &str, repo: &str) -> Option<&str> {
+        [
+            format!("{}/{}", org, repo),
+            format!("{}/*", org),
+            "*".to_string(),
+        ]
+        .iter()
+        .find_map(|key| self.repos.get(key)?.close_comment_template.as_deref())
+    }
+
+    /// Name of the saved filter applied to a repository by default
+    ///
+    /// The most specific entry that sets it wins, None if no entry does.
+    pub fn default_filter_for(&self, org: &str, repo: &str) -> Option<&str> {
+        [
+            format!("{}/{}", org, repo),
+            format!("{}/*", org),
+            "*".to_string(),
+        ]
+        .iter()
+        .find_map(|key| self.repos.get(key)?.default_filter.as_deref())
+    }
+
+    /// Write a commented default config file if none exists yet
+    ///
+    /// Returns the path of the written file, or `None` if a config file already exists.
+    pub fn write_default_if_missing() -> anyhow::Result<Option<PathBuf>> {
+        let path = crate::app_config_path()?;
+        Ok(Self::write_default_to_path_if_missing(&path)?.then_some(path))
+    }
+
+    /// Write the commented default config to `path` unless it already exists
+    ///
+    /// Returns `true` if the file was written.
+    fn write_default_to_path_if_missing(path: &Path) -> anyhow::Result<bool> {
+        if path.exists() {
+            log::debug!(
+                "Config file {:?} already exists, not writing defaults",
+                path
+            );
+            return Ok(false);
+        }
+
+        recovery::write_atomic(path, DEFAULT_CONFIG_TEMPLATE.as_bytes())
+            .with_context(|| format!("Failed to write default config file: {:?}", path))?;
+
+        log::info!("Wrote default config to {:?}", path);
+        Ok(true)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
     #[test]
     fn test_default_config() {
@@ -132,10 +967,19 @@ mod tests {
         let toml = r#"
             ide_command = "zed"
             approval_message = "LGTM!"
+            startup_load = 3
+
+            [status_pages]
+            "ghe.example.com" = "https://status.example.com/api/v2/summary.json"
         "#;
         let config: AppConfig = toml::from_str(toml).unwrap();
         assert_eq!(config.ide_command, "zed");
         assert_eq!(config.approval_message, "LGTM!");
+        assert_eq!(config.startup_load, Some(3));
+        assert_eq!(
+            config.status_pages["ghe.example.com"],
+            "https://status.example.com/api/v2/summary.json"
+        );
         // temp_dir should use default
         assert!(!config.temp_dir.is_empty());
     }
@@ -155,6 +999,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_browser_command() {
+        let parse = |value: &str| {
+            toml::from_str::<AppConfig>(&format!("browser_command = {}", value))
+                .map(|config| config.browser_command)
+        };
+        assert_eq!(AppConfig::default().browser_command, BrowserCommand::System);
+        assert_eq!(parse(r#""system""#).unwrap(), BrowserCommand::System);
+        assert_eq!(parse(r#""clipboard""#).unwrap(), BrowserCommand::Clipboard);
+        assert_eq!(parse(r#""print""#).unwrap(), BrowserCommand::Print);
+        assert_eq!(
+            parse(r#""firefox""#).unwrap(),
+            BrowserCommand::Command(vec!["firefox".to_string()])
+        );
+        let profile = parse(r#"["firefox", "-P", "work"]"#).unwrap();
+        assert_eq!(
+            profile,
+            BrowserCommand::Command(vec!["firefox".into(), "-P".into(), "work".into()])
+        );
+        assert!(parse("[]").is_err());
+        assert!(parse(r#"" ""#).is_err());
+
+        // Written back the way it is read
+        let config = AppConfig {
+            browser_command: profile,
+            ..AppConfig::default()
+        };
+        let written = toml::to_string(&config).unwrap();
+        assert!(written.contains(r#"browser_command = ["firefox", "-P", "work"]"#));
+        assert_eq!(
+            toml::from_str::<AppConfig>(&written)
+                .unwrap()
+                .browser_command,
+            config.browser_command
+        );
+    }
+
     #[test]
     fn test_issue_tracker_config_parsing() {
         // In TOML, backslash needs escaping: \d becomes \\d in the file
@@ -178,6 +1059,338 @@ url = "https://$HOST/$ORG/$REPO/issues/$ISSUE_NO"
         );
     }
 
+    #[test]
+    fn test_profile_for_repo() {
+        let toml = r#"
+[repos."work-org/*"]
+profile = "work"
+
+[repos."work-org/oss"]
+profile = "personal"
+        "#;
+        let config: AppConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.profile_for("work-org", "service"), Some("work"));
+        assert_eq!(config.profile_for("work-org", "oss"), Some("personal"));
+        assert_eq!(config.profile_for("me", "dotfiles"), None);
+        assert!(AppConfig::default()
+            .profile_for("work-org", "service")
+            .is_none());
+    }
+
+    #[test]
+    fn test_delete_branch_after_merge_for_repo() {
+        let toml = r#"
+[repos."acme/*"]
+delete_branch_after_merge = true
+
+[repos."acme/legacy"]
+profile = "work"
+
+[repos."acme/keep"]
+delete_branch_after_merge = false
+        "#;
+        let config: AppConfig = toml::from_str(toml).unwrap();
+        // An entry without the option falls back to the org entry
+        assert_eq!(
+            config.delete_branch_after_merge_for("acme", "legacy"),
+            Some(true)
+        );
+        assert_eq!(
+            config.delete_branch_after_merge_for("acme", "keep"),
+            Some(false)
+        );
+        assert_eq!(config.delete_branch_after_merge_for("me", "dotfiles"), None);
+    }
+
+    #[test]
+    fn test_close_comment_template_for_repo() {
+        let toml = r#"
+[repos."acme/*"]
+close_comment_template = ".github/CLOSING_COMMENT.md"
+
+[repos."acme/legacy"]
+profile = "work"
+        "#;
+        let config: AppConfig = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config.close_comment_template_for("acme", "legacy"),
+            Some(".github/CLOSING_COMMENT.md")
+        );
+        assert_eq!(config.close_comment_template_for("me", "dotfiles"), None);
+    }
+
+    #[test]
+    fn test_default_filter_for_repo() {
+        let toml = r#"
+[repos."*"]
+default_filter = "mine"
+
+[repos."acme/*"]
+default_filter = "security-bumps"
+
+[repos."acme/legacy"]
+profile = "work"
+        "#;
+        let config: AppConfig = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config.default_filter_for("acme", "legacy"),
+            Some("security-bumps")
+        );
+        assert_eq!(config.default_filter_for("me", "dotfiles"), Some("mine"));
+    }
+
+    fn test_config_path(name: &str) -> PathBuf {
+        env::temp_dir()
+            .join(format!("gh-pr-config-test-{}", std::process::id()))
+            .join(name)
+            .join("config.toml")
+    }
+
+    #[test]
+    fn test_default_template_parses_to_defaults() {
+        let config: AppConfig = toml::from_str(DEFAULT_CONFIG_TEMPLATE).unwrap();
+        let defaults = AppConfig::default();
+        assert_eq!(config.ide_command, defaults.ide_command);
+        assert_eq!(config.approval_message, defaults.approval_message);
+        assert_eq!(config.close_message, defaults.close_message);
+        assert!(config.issue_tracker.is_empty());
+        assert!(config.repos.is_empty());
+        assert_eq!(config.ui, defaults.ui);
+        assert!(config.policies.is_empty());
+        assert_eq!(config.logging, defaults.logging);
+        assert_eq!(config.metrics, defaults.metrics);
+        assert_eq!(config.release_notes, defaults.release_notes);
+    }
+
+    #[test]
+    fn test_release_notes_config_parsing() {
+        let config: AppConfig = toml::from_str("[release_notes]\nother = \"Misc\"\n").unwrap();
+        assert_eq!(config.release_notes.other, "Misc");
+        assert_eq!(config.release_notes.groups.len(), 3);
+
+        let toml = r#"
+[[release_notes.groups]]
+title = "Breaking"
+labels = ["breaking"]
+"#;
+        let config: AppConfig = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config.release_notes.groups,
+            [ReleaseNotesGroup {
+                title: "Breaking".to_string(),
+                labels: vec!["breaking".to_string()],
+                prefixes: Vec::new(),
+                dependency_updates: false,
+            }]
+        );
+        assert_eq!(config.release_notes.other, "Other Changes");
+    }
+
+    #[test]
+    fn test_metrics_config_parsing() {
+        let config: AppConfig = toml::from_str("[metrics]\nenabled = false\n").unwrap();
+        assert!(!config.metrics.enabled);
+        assert_eq!(config.metrics.summary_interval_minutes, 10);
+
+        let config: AppConfig =
+            toml::from_str("[metrics]\nsummary_interval_minutes = 0\n").unwrap();
+        assert!(config.metrics.enabled);
+        assert_eq!(config.metrics.summary_interval_minutes, 0);
+    }
+
+    #[test]
+    fn test_logging_config_parsing() {
+        let toml = r#"
+[logging]
+default = "info"
+modules = { "gh_client" = "debug" }
+rotation = "daily"
+keep_files = 3
+format = "json"
+        "#;
+        let config: AppConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.logging.default, "info");
+        assert_eq!(config.logging.modules["gh_client"], "debug");
+        assert_eq!(config.logging.rotation, LogRotation::Daily);
+        assert_eq!(config.logging.max_size_mb, 10);
+        assert_eq!(config.logging.keep_files, 3);
+        assert_eq!(config.logging.format, LogFormat::Json);
+
+        let defaults = AppConfig::default().logging;
+        assert_eq!(defaults.default, "debug");
+        assert_eq!(defaults.rotation, LogRotation::Size);
+        assert_eq!(defaults.format, LogFormat::Text);
+    }
+
+    #[test]
+    fn test_policy_config_parsing() {
+        let toml = r#"
+[policies.auto-bot]
+authors = ["dependabot[bot]", "renovate*"]
+update_types = ["patch", "minor"]
+max_additions = 200
+labels_deny = ["do-not-merge"]
+
+[policies.approve-docs]
+labels_allow = ["docs"]
+ci = "not-failed"
+filter = "small-docs"
+action = "approve"
+        "#;
+        let config: AppConfig = toml::from_str(toml).unwrap();
+        let auto_bot = &config.policies["auto-bot"];
+        assert_eq!(auto_bot.authors, vec!["dependabot[bot]", "renovate*"]);
+        assert_eq!(auto_bot.max_additions, Some(200));
+        assert_eq!(auto_bot.ci, PolicyCi::Success);
+        assert_eq!(auto_bot.action, PolicyAction::ApproveMerge);
+
+        let docs = &config.policies["approve-docs"];
+        assert_eq!(docs.ci, PolicyCi::NotFailed);
+        assert_eq!(docs.filter.as_deref(), Some("small-docs"));
+        assert_eq!(auto_bot.filter, None);
+        assert!(docs.action.approves());
+        assert!(!docs.action.merges());
+    }
+
+    #[test]
+    fn test_ui_config_parsing() {
+        let toml = r#"
+[ui]
+theme = "high-contrast"
+reduced_motion = true
+stuck_operation_secs = 120
+tab_cycling = "stop"
+large_file_changes = 500
+timestamps = "both"
+timezone = "+05:30"
+date_format = "%d.%m.%Y"
+export_filename = "{org}-{repo}-{pr}.txt"
+auto_peek = true
+collapse_successful_jobs = false
+bold_unseen = false
+sort_by_attention = true
+quit_on_close = false
+pr_size = { m = 400, l = 2000 }
+        "#;
+        let config: AppConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.ui.theme, "high-contrast");
+        assert_eq!(config.ui.tab_cycling, TabCycling::Stop);
+        assert!(config.ui.reduced_motion);
+        assert_eq!(config.ui.stuck_operation_secs, 120);
+        assert_eq!(config.ui.large_file_changes, 500);
+        assert_eq!(config.ui.timestamps, TimestampStyle::Both);
+        assert_eq!(
+            config.ui.timezone_offset(),
+            chrono::FixedOffset::east_opt(5 * 3600 + 30 * 60)
+        );
+        assert_eq!(config.ui.date_format, "%d.%m.%Y");
+        assert_eq!(config.ui.export_filename.as_str(), "{org}-{repo}-{pr}.txt");
+        assert!(config.ui.auto_peek);
+        assert!(!config.ui.collapse_successful_jobs);
+        assert!(!config.ui.bold_unseen);
+        assert!(config.ui.sort_by_attention);
+        assert!(!config.ui.quit_on_close);
+        assert_eq!(
+            config.ui.pr_size,
+            PrSizeThresholds {
+                m: 400,
+                l: 2000,
+                ..Default::default()
+            }
+        );
+
+        let config: AppConfig = toml::from_str("[ui]\nreduced_motion = true\n").unwrap();
+        assert_eq!(config.ui.theme, "dark");
+        assert!(!AppConfig::default().ui.reduced_motion);
+        assert_eq!(config.ui.stuck_operation_secs, 60);
+        assert_eq!(config.ui.tab_cycling, TabCycling::Wrap);
+        assert_eq!(config.ui.large_file_changes, 200);
+        assert_eq!(config.ui.timestamps, TimestampStyle::Relative);
+        assert_eq!(config.ui.timezone_offset(), None);
+        assert_eq!(config.ui.date_format, "%Y-%m-%d %H:%M");
+        assert_eq!(config.ui.export_filename.as_str(), "{repo}-{pr}-{date}.log");
+        assert!(!config.ui.auto_peek);
+        assert!(config.ui.collapse_successful_jobs);
+        assert!(config.ui.bold_unseen);
+        assert!(!config.ui.sort_by_attention);
+        assert!(config.ui.quit_on_close);
+        assert_eq!(config.ui.pr_size.xs, 10);
+    }
+
+    #[test]
+    fn test_invalid_templates_are_reported_at_load() {
+        let path = test_config_path("templates");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            "[ui]\ntheme = \"high-contrast\"\ndate_format = \"%Y-%Q\"\nexport_filename = \"{repo}-{number}.log\"\n",
+        )
+        .unwrap();
+
+        let Recovered::Loaded(config, notice) =
+            recovery::load_toml_with_recovery::<AppConfig>(&path, "config")
+        else {
+            panic!("config file is missing");
+        };
+        let notice = notice.expect("invalid templates are reported");
+        assert_eq!(
+            notice.dropped_fields,
+            vec!["ui.date_format", "ui.export_filename"]
+        );
+        assert_eq!(
+            config.ui,
+            UiConfig {
+                theme: "high-contrast".to_string(),
+                ..UiConfig::default()
+            }
+        );
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_timezone_offset() {
+        let ui = |timezone: &str| UiConfig {
+            timezone: Some(timezone.to_string()),
+            ..UiConfig::default()
+        };
+        assert_eq!(
+            ui("UTC").timezone_offset(),
+            chrono::FixedOffset::east_opt(0)
+        );
+        assert_eq!(ui("Z").timezone_offset(), chrono::FixedOffset::east_opt(0));
+        assert_eq!(
+            ui("-08:00").timezone_offset(),
+            chrono::FixedOffset::west_opt(8 * 3600)
+        );
+        assert_eq!(ui("Mars/Olympus").timezone_offset(), None);
+    }
+
+    #[test]
+    fn test_write_default_if_missing_creates_file() {
+        let path = test_config_path("creates");
+        let _ = fs::remove_file(&path);
+
+        assert!(AppConfig::write_default_to_path_if_missing(&path).unwrap());
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content, DEFAULT_CONFIG_TEMPLATE);
+
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn test_write_default_if_missing_keeps_existing_file() {
+        let path = test_config_path("keeps");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "ide_command = \"zed\"\n").unwrap();
+
+        assert!(!AppConfig::write_default_to_path_if_missing(&path).unwrap());
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content, "ide_command = \"zed\"\n");
+
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
     #[test]
     fn test_issue_tracker_with_repos_filter() {
         let toml = r##"
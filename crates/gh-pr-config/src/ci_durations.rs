@@ -0,0 +1,197 @@
+//! History of CI durations
+//!
+//! How long the checks of a repository usually take, learned from the check
+//! runs that passed. Runs are kept per repository and check name (a workflow
+//! job on GitHub Actions), the newest [`MAX_RUNS_PER_CHECK`] of each, in
+//! `~/.cache/gh-pr-lander/ci-durations.json`. The history is a cache: a file
+//! that fails to parse starts a fresh history.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::paths;
+use crate::recovery;
+
+/// Passed runs kept per check, older ones are dropped
+pub const MAX_RUNS_PER_CHECK: usize = 20;
+
+/// A passed check run and how long it took
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckDuration {
+    /// Check run ID, so a run fetched again is recorded once
+    pub id: u64,
+    /// Seconds from start to completion
+    pub seconds: u64,
+}
+
+/// Recent check durations of all repositories
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CiDurations {
+    /// Runs by repository and check name, oldest first
+    #[serde(default)]
+    repos: BTreeMap<String, BTreeMap<String, Vec<CheckDuration>>>,
+}
+
+impl CiDurations {
+    /// Load the history, empty if there is none or it cannot be read
+    pub fn load() -> Self {
+        match paths::ci_durations_path() {
+            Ok(path) => Self::load_from_path(&path),
+            Err(e) => {
+                log::warn!("Cannot locate CI duration history: {:#}", e);
+                Self::default()
+            }
+        }
+    }
+
+    fn load_from_path(path: &Path) -> Self {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Self::default(),
+            Err(e) => {
+                log::warn!("Failed to read CI duration history {:?}: {}", path, e);
+                return Self::default();
+            }
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!(
+                "Discarding unreadable CI duration history {:?}: {}",
+                path,
+                e
+            );
+            Self::default()
+        })
+    }
+
+    /// Save the history to the cache directory
+    pub fn save(&self) -> Result<()> {
+        self.save_to_path(&paths::ci_durations_path()?)
+    }
+
+    fn save_to_path(&self, path: &Path) -> Result<()> {
+        let content =
+            serde_json::to_string(self).context("Failed to serialize CI duration history")?;
+        recovery::write_atomic(path, content.as_bytes())
+            .with_context(|| format!("Failed to write CI duration history: {:?}", path))
+    }
+
+    /// Record a passed run of `check`, returns false if it was known already
+    pub fn record(&mut self, repo: &str, check: &str, run: CheckDuration) -> bool {
+        let runs = self
+            .repos
+            .entry(repo.to_string())
+            .or_default()
+            .entry(check.to_string())
+            .or_default();
+        if runs.iter().any(|known| known.id == run.id) {
+            return false;
+        }
+        runs.push(run);
+        if runs.len() > MAX_RUNS_PER_CHECK {
+            runs.drain(..runs.len() - MAX_RUNS_PER_CHECK);
+        }
+        true
+    }
+
+    /// Median duration of `check` in seconds, `None` without history
+    pub fn median(&self, repo: &str, check: &str) -> Option<u64> {
+        let mut seconds: Vec<u64> = self
+            .repos
+            .get(repo)?
+            .get(check)?
+            .iter()
+            .map(|run| run.seconds)
+            .collect();
+        if seconds.is_empty() {
+            return None;
+        }
+        seconds.sort_unstable();
+        let middle = seconds.len() / 2;
+        Some(if seconds.len().is_multiple_of(2) {
+            (seconds[middle - 1] + seconds[middle]) / 2
+        } else {
+            seconds[middle]
+        })
+    }
+
+    /// How long `checks` usually take together, in seconds
+    ///
+    /// Checks run in parallel, so this is the median of the slowest one.
+    /// Checks without history are left out, `None` if none has any.
+    pub fn usual<'a>(&self, repo: &str, checks: impl IntoIterator<Item = &'a str>) -> Option<u64> {
+        checks
+            .into_iter()
+            .filter_map(|check| self.median(repo, check))
+            .max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(id: u64, seconds: u64) -> CheckDuration {
+        CheckDuration { id, seconds }
+    }
+
+    #[test]
+    fn test_record_keeps_the_newest_runs_once() {
+        let mut history = CiDurations::default();
+        assert!(history.record("acme/api", "test", run(1, 600)));
+        assert!(!history.record("acme/api", "test", run(1, 600)));
+        for id in 2..=MAX_RUNS_PER_CHECK as u64 + 5 {
+            history.record("acme/api", "test", run(id, 60 * id));
+        }
+
+        let runs = &history.repos["acme/api"]["test"];
+        assert_eq!(runs.len(), MAX_RUNS_PER_CHECK);
+        assert_eq!(runs[0].id, 6);
+        assert_eq!(runs.last().unwrap().id, MAX_RUNS_PER_CHECK as u64 + 5);
+    }
+
+    #[test]
+    fn test_median_and_usual_duration() {
+        let mut history = CiDurations::default();
+        for (id, seconds) in [(1, 300), (2, 900), (3, 400)] {
+            history.record("acme/api", "lint", run(id, seconds));
+        }
+        for (id, seconds) in [(4, 800), (5, 600)] {
+            history.record("acme/api", "test", run(id, seconds));
+        }
+
+        assert_eq!(history.median("acme/api", "lint"), Some(400));
+        assert_eq!(history.median("acme/api", "test"), Some(700));
+        assert_eq!(history.median("acme/api", "build"), None);
+        assert_eq!(history.median("acme/web", "lint"), None);
+        assert_eq!(
+            history.usual("acme/api", ["lint", "test", "build"]),
+            Some(700)
+        );
+        assert_eq!(history.usual("acme/api", ["build"]), None);
+    }
+
+    #[test]
+    fn test_history_survives_a_round_trip() {
+        let mut history = CiDurations::default();
+        history.record("acme/api", "test", run(7, 840));
+        history.record("ghe.corp/acme/api", "Build / linux", run(8, 1260));
+
+        let json = serde_json::to_string(&history).unwrap();
+        assert_eq!(serde_json::from_str::<CiDurations>(&json).unwrap(), history);
+
+        let dir = std::env::temp_dir().join(format!("gh-pr-config-ci-{}", std::process::id()));
+        let path = dir.join("ci-durations.json");
+        history.save_to_path(&path).unwrap();
+        assert_eq!(CiDurations::load_from_path(&path), history);
+
+        std::fs::write(&path, "{ not json").unwrap();
+        assert_eq!(CiDurations::load_from_path(&path), CiDurations::default());
+        assert_eq!(
+            CiDurations::load_from_path(&dir.join("missing.json")),
+            CiDurations::default()
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
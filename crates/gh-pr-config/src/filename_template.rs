@@ -0,0 +1,215 @@
+//! File names of exported artifacts
+//!
+//! Exports are named after a template like `{repo}-{pr}-{date}.log`. The
+//! template is checked when the config is loaded, so a typo in a placeholder
+//! shows up as an invalid config field instead of a strange file name.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Placeholders a filename template may use
+pub const PLACEHOLDERS: [&str; 5] = ["host", "org", "repo", "pr", "date"];
+
+/// Default template of exported build logs
+pub const DEFAULT_EXPORT_FILENAME: &str = "{repo}-{pr}-{date}.log";
+
+/// A validated filename template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilenameTemplate(String);
+
+/// Values substituted into a [`FilenameTemplate`]
+#[derive(Debug, Clone, Default)]
+pub struct FilenameValues<'a> {
+    pub host: &'a str,
+    pub org: &'a str,
+    pub repo: &'a str,
+    pub pr: &'a str,
+    pub date: &'a str,
+}
+
+impl FilenameValues<'_> {
+    fn get(&self, placeholder: &str) -> &str {
+        match placeholder {
+            "host" => self.host,
+            "org" => self.org,
+            "repo" => self.repo,
+            "pr" => self.pr,
+            "date" => self.date,
+            _ => "",
+        }
+    }
+}
+
+impl FilenameTemplate {
+    /// The template as written in the config
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Substitute the placeholders
+    ///
+    /// Values are made file name safe: anything but ASCII letters, digits,
+    /// `-`, `.` and `_` becomes `_` (e.g. "12:00" becomes "12_00").
+    pub fn render(&self, values: &FilenameValues<'_>) -> String {
+        let mut name = String::new();
+        let mut rest = self.0.as_str();
+        // Validated on parse: every `{` has a matching `}`
+        while let Some((before, after)) = rest.split_once('{') {
+            let Some((placeholder, after)) = after.split_once('}') else {
+                break;
+            };
+            name.push_str(before);
+            name.extend(values.get(placeholder).chars().map(sanitize));
+            rest = after;
+        }
+        name.push_str(rest);
+        name
+    }
+}
+
+impl Default for FilenameTemplate {
+    fn default() -> Self {
+        Self(DEFAULT_EXPORT_FILENAME.to_string())
+    }
+}
+
+impl fmt::Display for FilenameTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Why a filename template was rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilenameTemplateError {
+    /// The template is empty
+    Empty,
+    /// A `{` without its `}`
+    Unclosed,
+    /// A `{name}` that is not in [`PLACEHOLDERS`]
+    UnknownPlaceholder(String),
+    /// A path separator, exports are written to the current directory
+    PathSeparator,
+}
+
+impl fmt::Display for FilenameTemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "filename template is empty"),
+            Self::Unclosed => write!(f, "filename template has an unclosed '{{'"),
+            Self::UnknownPlaceholder(name) => write!(
+                f,
+                "unknown placeholder {{{}}}, expected one of {}",
+                name,
+                PLACEHOLDERS
+                    .iter()
+                    .map(|p| format!("{{{}}}", p))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::PathSeparator => write!(f, "filename template must not contain '/'"),
+        }
+    }
+}
+
+impl std::error::Error for FilenameTemplateError {}
+
+impl FromStr for FilenameTemplate {
+    type Err = FilenameTemplateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Err(FilenameTemplateError::Empty);
+        }
+        if s.contains(['/', '\\']) {
+            return Err(FilenameTemplateError::PathSeparator);
+        }
+        let mut rest = s;
+        while let Some(start) = rest.find('{') {
+            let end = rest[start..]
+                .find('}')
+                .map(|offset| start + offset)
+                .ok_or(FilenameTemplateError::Unclosed)?;
+            let name = &rest[start + 1..end];
+            if !PLACEHOLDERS.contains(&name) {
+                return Err(FilenameTemplateError::UnknownPlaceholder(name.to_string()));
+            }
+            rest = &rest[end + 1..];
+        }
+        Ok(Self(s.to_string()))
+    }
+}
+
+impl Serialize for FilenameTemplate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for FilenameTemplate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let template = String::deserialize(deserializer)?;
+        template.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+fn sanitize(c: char) -> char {
+    if c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_') {
+        c
+    } else {
+        '_'
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let values = FilenameValues {
+            host: "github.com",
+            org: "rust-lang",
+            repo: "rust",
+            pr: "42",
+            date: "2025-01-15 12:00",
+        };
+        let template = FilenameTemplate::default();
+        assert_eq!(template.render(&values), "rust-42-2025-01-15_12_00.log");
+
+        let template: FilenameTemplate = "{host}_{org}".parse().unwrap();
+        assert_eq!(template.render(&values), "github.com_rust-lang");
+        let template: FilenameTemplate = "build.log".parse().unwrap();
+        assert_eq!(template.render(&values), "build.log");
+    }
+
+    #[test]
+    fn test_placeholder_errors() {
+        assert_eq!(
+            "{repo}-{number}.log".parse::<FilenameTemplate>(),
+            Err(FilenameTemplateError::UnknownPlaceholder(
+                "number".to_string()
+            ))
+        );
+        assert_eq!(
+            "{repo}-{pr.log".parse::<FilenameTemplate>(),
+            Err(FilenameTemplateError::Unclosed)
+        );
+        assert_eq!(
+            "{}.log".parse::<FilenameTemplate>(),
+            Err(FilenameTemplateError::UnknownPlaceholder(String::new()))
+        );
+        assert_eq!(
+            " ".parse::<FilenameTemplate>(),
+            Err(FilenameTemplateError::Empty)
+        );
+        assert_eq!(
+            "logs/{repo}.log".parse::<FilenameTemplate>(),
+            Err(FilenameTemplateError::PathSeparator)
+        );
+
+        let error = "{number}".parse::<FilenameTemplate>().unwrap_err();
+        assert!(error.to_string().contains("{pr}"));
+    }
+}
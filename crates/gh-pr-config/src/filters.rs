@@ -0,0 +1,237 @@
+//! Saved PR filters
+//!
+//! A filter is a set of criteria a PR must all meet to be shown. Filters
+//! assembled in the filter builder can be saved under a name, applied from
+//! the command palette, used as the default filter of a repository
+//! (`[repos."org/repo"] default_filter`) and as matcher of a policy
+//! (`[policies.<name>] filter`).
+//! Saved filters live in `~/.config/gh-pr-lander/filters.toml`.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::paths;
+use crate::recovery::{self, Recovered, RecoveryNotice};
+use crate::schema::{self, FILTERS_SCHEMA_VERSION};
+
+/// CI state a filter asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FilterCi {
+    /// Required checks passed
+    Success,
+    /// Checks are still running or not yet reported
+    Pending,
+    /// The build failed
+    Failed,
+}
+
+impl FilterCi {
+    /// All CI states, in the order the filter builder cycles through them
+    pub const ALL: [FilterCi; 3] = [FilterCi::Success, FilterCi::Pending, FilterCi::Failed];
+
+    /// Name used in filter queries (`ci:failed`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FilterCi::Success => "success",
+            FilterCi::Pending => "pending",
+            FilterCi::Failed => "failed",
+        }
+    }
+
+    /// Parse a CI state name (case-insensitive)
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|ci| ci.as_str().eq_ignore_ascii_case(value))
+    }
+}
+
+/// Criteria of a PR filter (unset criteria match every PR)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FilterSpec {
+    /// Exact author login (case-insensitive)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Part of the author login (case-insensitive), e.g. "bot"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author_contains: Option<String>,
+    /// Labels the PR must all carry
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels_include: Vec<String>,
+    /// Labels the PR must not carry
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels_exclude: Vec<String>,
+    /// CI state
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ci: Option<FilterCi>,
+    /// Draft (true) or ready for review (false)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub draft: Option<bool>,
+    /// Dependency update type ("patch", "minor" or "major")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_type: Option<String>,
+    /// Only PRs opened more than this many days ago
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub older_than_days: Option<u32>,
+    /// Only PRs opened less than this many days ago
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub newer_than_days: Option<u32>,
+    /// Base branch the PR merges into, `*` is a wildcard (e.g. "release/*")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_branch: Option<String>,
+    /// Smallest size bucket ("XS", "S", "M", "L" or "XL")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_size: Option<String>,
+    /// Largest size bucket
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size: Option<String>,
+}
+
+impl FilterSpec {
+    /// Whether no criterion is set (the filter matches every PR)
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn default_schema_version() -> u32 {
+    FILTERS_SCHEMA_VERSION
+}
+
+/// All saved filters, by name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedFilters {
+    /// Schema version of the file (see `schema` module)
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
+    #[serde(default)]
+    pub filters: BTreeMap<String, FilterSpec>,
+}
+
+impl Default for SavedFilters {
+    fn default() -> Self {
+        Self {
+            schema_version: FILTERS_SCHEMA_VERSION,
+            filters: BTreeMap::new(),
+        }
+    }
+}
+
+impl SavedFilters {
+    /// Load saved filters, recovering from a corrupted file
+    pub fn load_with_recovery() -> (Self, Option<RecoveryNotice>) {
+        match paths::saved_filters_path() {
+            Ok(path) => Self::load_from_path(&path),
+            Err(e) => {
+                log::warn!("Cannot locate saved filters file: {:#}", e);
+                (Self::default(), None)
+            }
+        }
+    }
+
+    fn load_from_path(path: &Path) -> (Self, Option<RecoveryNotice>) {
+        if let Some(version) = schema::file_schema_version(path, schema::toml_schema_version) {
+            if version > FILTERS_SCHEMA_VERSION {
+                log::warn!(
+                    "Saved filters {:?} have schema version {} (newer than {}), loading read-only",
+                    path,
+                    version,
+                    FILTERS_SCHEMA_VERSION
+                );
+                let filters = std::fs::read_to_string(path)
+                    .ok()
+                    .and_then(|content| recovery::parse_toml_lenient(&content))
+                    .unwrap_or_default();
+                return (filters, None);
+            }
+        }
+
+        match recovery::load_toml_with_recovery::<Self>(path, "saved filters") {
+            Recovered::Missing => (Self::default(), None),
+            Recovered::Loaded(filters, notice) => {
+                log::info!(
+                    "Loaded {} saved filters from {:?}",
+                    filters.filters.len(),
+                    path
+                );
+                (filters, notice)
+            }
+        }
+    }
+
+    /// Save the filters to the config directory
+    pub fn save(&self) -> Result<()> {
+        self.save_to_path(&paths::saved_filters_path()?)
+    }
+
+    fn save_to_path(&self, path: &Path) -> Result<()> {
+        schema::ensure_writable(path, schema::toml_schema_version, FILTERS_SCHEMA_VERSION)?;
+        let content = toml::to_string_pretty(self).context("Failed to serialize saved filters")?;
+        recovery::write_atomic(path, content.as_bytes())
+            .with_context(|| format!("Failed to write saved filters file: {:?}", path))?;
+        log::info!("Saved {} filters to {:?}", self.filters.len(), path);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_filter_file() {
+        let filters: SavedFilters = toml::from_str(
+            r#"
+            [filters.security-bumps]
+            author_contains = "bot"
+            labels_include = ["security"]
+            ci = "success"
+            update_type = "patch"
+            older_than_days = 2
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(filters.schema_version, FILTERS_SCHEMA_VERSION);
+        let spec = &filters.filters["security-bumps"];
+        assert_eq!(spec.author_contains.as_deref(), Some("bot"));
+        assert_eq!(spec.labels_include, vec!["security"]);
+        assert_eq!(spec.ci, Some(FilterCi::Success));
+        assert_eq!(spec.update_type.as_deref(), Some("patch"));
+        assert_eq!(spec.older_than_days, Some(2));
+        assert_eq!(spec.draft, None);
+    }
+
+    #[test]
+    fn test_filters_survive_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("gh-pr-config-filters-{}", std::process::id()));
+        let path = dir.join("filters.toml");
+        let mut filters = SavedFilters::default();
+        filters.filters.insert(
+            "stale-drafts".to_string(),
+            FilterSpec {
+                draft: Some(true),
+                older_than_days: Some(30),
+                labels_exclude: vec!["keep".to_string()],
+                ..Default::default()
+            },
+        );
+
+        filters.save_to_path(&path).unwrap();
+        let (loaded, notice) = SavedFilters::load_from_path(&path);
+        assert!(notice.is_none());
+        assert_eq!(loaded, filters);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_ci_parse_is_case_insensitive() {
+        assert_eq!(FilterCi::parse("Failed"), Some(FilterCi::Failed));
+        assert_eq!(FilterCi::parse("green"), None);
+        assert!(FilterSpec::default().is_empty());
+    }
+}
@@ -1,32 +1,60 @@
 //! Configuration and file management for gh-pr-tui
 //!
 //! This crate provides:
-//! - File path utilities for config and cache files (via `paths` module)
+//! - File path utilities for config, cache and data files (via `paths` module)
 //! - Configuration file loading (TOML)
 //! - Application configuration (AppConfig)
 //! - Session persistence (Session)
-//! - Recent repositories persistence
+//! - Canonical repository identity (RepoId)
+//! - File name templates of exports (FilenameTemplate)
+//! - Recent repositories persistence, grouped in workspace profiles
+//! - Local per-PR notes
+//! - Snoozed PRs
+//! - Saved PR filters
+//! - History of CI durations
+//! - Schema versions of persisted files (via `schema` module)
 
 /// Default GitHub host (public GitHub)
 pub const DEFAULT_HOST: &str = "github.com";
 
 pub mod app_config;
+pub mod ci_durations;
 pub mod config_file;
+pub mod filename_template;
 pub mod files; // Deprecated: use `paths` module instead
+pub mod filters;
+pub mod notes;
 pub mod paths;
 pub mod recent_repositories;
+pub mod recovery;
+pub mod repo_id;
+pub mod schema;
 pub mod session;
+pub mod snooze;
 
-pub use app_config::{AppConfig, IssueTrackerConfig};
+pub use app_config::{
+    AppConfig, BrowserCommand, IssueTrackerConfig, LogFormat, LogRotation, LoggingConfig,
+    MetricsConfig, PolicyAction, PolicyCi, PolicyConfig, PrSizeThresholds, ReleaseNotesConfig,
+    ReleaseNotesGroup, RepoSettings, TabCycling, TimestampStyle, UiConfig,
+};
+pub use ci_durations::{CheckDuration, CiDurations};
 pub use config_file::load_config_file;
+pub use filename_template::{FilenameTemplate, FilenameTemplateError, FilenameValues};
+pub use filters::{FilterCi, FilterSpec, SavedFilters};
+pub use notes::{PrNote, PrNotes};
 pub use paths::{
-    api_cache_path, app_config_path, cache_dir, config_dir, global_session_path, has_local_session,
-    local_session_path, recent_repositories_path,
+    api_cache_path, app_config_path, audit_log_path, cache_dir, ci_durations_path, config_dir,
+    data_dir, global_session_path, has_local_session, local_session_path, notes_path,
+    recent_repositories_path, run_logs_dir, saved_filters_path, snoozed_path,
 };
 pub use recent_repositories::{
-    load_recent_repositories, save_recent_repositories, RecentRepository,
+    RecentRepository, WorkspaceProfile, WorkspaceProfiles, DEFAULT_PROFILE,
+    RECENT_REPOSITORIES_FILE,
 };
-pub use session::Session;
+pub use recovery::RecoveryNotice;
+pub use repo_id::{normalize_host, ParseRepoIdError, RepoId};
+pub use session::{SeenPr, Session, WatchedPr};
+pub use snooze::{SnoozeList, SnoozedPr};
 
 // Re-export deprecated functions for backward compatibility
 #[allow(deprecated)]
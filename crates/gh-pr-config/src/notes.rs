@@ -0,0 +1,219 @@
+//! Local per-PR notes
+//!
+//! Notes are private reminders attached to a pull request ("waiting on
+//! upstream fix"). They live in `~/.config/gh-pr-lander/notes.toml` and are
+//! never sent to GitHub.
+//!
+//! Once a PR is seen merged or closed its note is marked, and notes of PRs
+//! that have been done for longer than the retention period are pruned.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::paths;
+use crate::recovery::{self, Recovered, RecoveryNotice};
+use crate::schema::{self, NOTES_SCHEMA_VERSION};
+use crate::session::WatchedPr;
+
+/// A note attached to a pull request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrNote {
+    /// The pull request the note belongs to
+    pub pr: WatchedPr,
+    /// Note text
+    pub text: String,
+    /// When the note was last edited
+    pub updated_at: DateTime<Utc>,
+    /// When the PR was merged or closed (None while it is open)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub done_at: Option<DateTime<Utc>>,
+}
+
+fn default_schema_version() -> u32 {
+    NOTES_SCHEMA_VERSION
+}
+
+/// All local notes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrNotes {
+    /// Schema version of the file (see `schema` module)
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
+    #[serde(default)]
+    pub notes: Vec<PrNote>,
+}
+
+impl Default for PrNotes {
+    fn default() -> Self {
+        Self {
+            schema_version: NOTES_SCHEMA_VERSION,
+            notes: Vec::new(),
+        }
+    }
+}
+
+impl PrNotes {
+    /// Load notes, recovering from a corrupted file
+    ///
+    /// A corrupted file is backed up and treated as empty; the returned notice
+    /// describes what happened so it can be surfaced to the user.
+    pub fn load_with_recovery() -> (Self, Option<RecoveryNotice>) {
+        match paths::notes_path() {
+            Ok(path) => Self::load_from_path(&path),
+            Err(e) => {
+                log::warn!("Cannot locate notes file: {:#}", e);
+                (Self::default(), None)
+            }
+        }
+    }
+
+    fn load_from_path(path: &Path) -> (Self, Option<RecoveryNotice>) {
+        if let Some(version) = schema::file_schema_version(path, schema::toml_schema_version) {
+            if version > NOTES_SCHEMA_VERSION {
+                log::warn!(
+                    "Notes {:?} have schema version {} (newer than {}), loading read-only",
+                    path,
+                    version,
+                    NOTES_SCHEMA_VERSION
+                );
+                let notes = std::fs::read_to_string(path)
+                    .ok()
+                    .and_then(|content| recovery::parse_toml_lenient(&content))
+                    .unwrap_or_default();
+                return (notes, None);
+            }
+        }
+
+        match recovery::load_toml_with_recovery::<Self>(path, "notes") {
+            Recovered::Missing => (Self::default(), None),
+            Recovered::Loaded(notes, notice) => {
+                log::info!("Loaded {} notes from {:?}", notes.notes.len(), path);
+                (notes, notice)
+            }
+        }
+    }
+
+    /// Save notes to the config directory
+    pub fn save(&self) -> Result<()> {
+        self.save_to_path(&paths::notes_path()?)
+    }
+
+    fn save_to_path(&self, path: &Path) -> Result<()> {
+        schema::ensure_writable(path, schema::toml_schema_version, NOTES_SCHEMA_VERSION)?;
+        let content = toml::to_string_pretty(self).context("Failed to serialize notes")?;
+        recovery::write_atomic(path, content.as_bytes())
+            .with_context(|| format!("Failed to write notes file: {:?}", path))?;
+        log::info!("Saved {} notes to {:?}", self.notes.len(), path);
+        Ok(())
+    }
+
+    /// Note of a pull request
+    pub fn get(&self, pr: &WatchedPr) -> Option<&PrNote> {
+        self.notes.iter().find(|note| &note.pr == pr)
+    }
+
+    /// Set the note of a pull request (blank text removes it)
+    pub fn set(&mut self, pr: &WatchedPr, text: &str, now: DateTime<Utc>) {
+        let text = text.trim();
+        if text.is_empty() {
+            self.notes.retain(|note| &note.pr != pr);
+            return;
+        }
+        match self.notes.iter_mut().find(|note| &note.pr == pr) {
+            Some(note) => {
+                note.text = text.to_string();
+                note.updated_at = now;
+            }
+            None => self.notes.push(PrNote {
+                pr: pr.clone(),
+                text: text.to_string(),
+                updated_at: now,
+                done_at: None,
+            }),
+        }
+    }
+
+    /// Record that a pull request was merged or closed at `at`
+    ///
+    /// Returns whether a note was marked (the first time only).
+    pub fn mark_done(&mut self, pr: &WatchedPr, at: DateTime<Utc>) -> bool {
+        match self.notes.iter_mut().find(|note| &note.pr == pr) {
+            Some(note) if note.done_at.is_none() => {
+                note.done_at = Some(at);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove notes of PRs merged or closed longer than `retention` ago
+    ///
+    /// Returns the number of removed notes.
+    pub fn prune(&mut self, now: DateTime<Utc>, retention: Duration) -> usize {
+        let before = self.notes.len();
+        self.notes
+            .retain(|note| note.done_at.is_none_or(|done| now - done <= retention));
+        before - self.notes.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr(number: u64) -> WatchedPr {
+        WatchedPr::new(None, "org", "repo", number)
+    }
+
+    #[test]
+    fn test_set_updates_and_blank_removes() {
+        let now = Utc::now();
+        let mut notes = PrNotes::default();
+        notes.set(&pr(1), "  waiting on upstream fix ", now);
+        notes.set(&pr(1), "still waiting", now);
+        assert_eq!(notes.notes.len(), 1);
+        assert_eq!(notes.get(&pr(1)).unwrap().text, "still waiting");
+
+        notes.set(&pr(1), "   ", now);
+        assert!(notes.get(&pr(1)).is_none());
+    }
+
+    #[test]
+    fn test_prune_only_removes_long_done_prs() {
+        let now = Utc::now();
+        let mut notes = PrNotes::default();
+        for number in 1..=3 {
+            notes.set(&pr(number), "note", now);
+        }
+        assert!(notes.mark_done(&pr(1), now - Duration::days(40)));
+        assert!(notes.mark_done(&pr(2), now - Duration::days(2)));
+        // The first time a PR was seen done counts
+        assert!(!notes.mark_done(&pr(2), now));
+
+        assert_eq!(notes.prune(now, Duration::days(30)), 1);
+        assert!(notes.get(&pr(1)).is_none());
+        assert!(notes.get(&pr(2)).is_some());
+        assert!(notes.get(&pr(3)).is_some());
+    }
+
+    #[test]
+    fn test_notes_survive_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("gh-pr-config-notes-{}", std::process::id()));
+        let path = dir.join("notes.toml");
+        let mut notes = PrNotes::default();
+        notes.set(
+            &WatchedPr::new(Some("ghe.example.com"), "org", "repo", 7),
+            "waiting on upstream fix",
+            Utc::now(),
+        );
+
+        notes.save_to_path(&path).unwrap();
+        let (loaded, notice) = PrNotes::load_from_path(&path);
+        assert!(notice.is_none());
+        assert_eq!(loaded, notes);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
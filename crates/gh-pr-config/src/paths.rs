@@ -3,7 +3,7 @@
 //! Uses XDG directories via `dirs` crate with fallbacks.
 //!
 //! Platform-specific locations:
-//! - Linux: `~/.config/gh-pr-lander/`, `~/.cache/gh-pr-lander/`
+//! - Linux: `~/.config/gh-pr-lander/`, `~/.cache/gh-pr-lander/`, `~/.local/share/gh-pr-lander/`
 //! - macOS: `~/Library/Application Support/gh-pr-lander/`, `~/Library/Caches/gh-pr-lander/`
 //! - Windows: `%APPDATA%\gh-pr-lander\`, `%LOCALAPPDATA%\gh-pr-lander\`
 
@@ -31,6 +31,15 @@ pub fn cache_dir() -> Result<PathBuf> {
     Ok(dir)
 }
 
+/// Get the application data directory
+/// Returns ~/.local/share/gh-pr-lander/ on Linux, ~/Library/Application Support/gh-pr-lander/ on macOS
+pub fn data_dir() -> Result<PathBuf> {
+    let base = dirs::data_dir().context("Could not determine data directory")?;
+    let dir = base.join(APP_NAME);
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
 /// Get path to global session file
 pub fn global_session_path() -> Result<PathBuf> {
     Ok(config_dir()?.join("session.toml"))
@@ -51,11 +60,43 @@ pub fn recent_repositories_path() -> Result<PathBuf> {
     Ok(config_dir()?.join("recent-repositories.toml"))
 }
 
+/// Get path to the local PR notes file
+pub fn notes_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join("notes.toml"))
+}
+
+/// Get path to the snoozed PRs file
+pub fn snoozed_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join("snoozed.toml"))
+}
+
+/// Get path to the saved filters file
+pub fn saved_filters_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join("filters.toml"))
+}
+
 /// Get path to API cache file
 pub fn api_cache_path() -> Result<PathBuf> {
     Ok(cache_dir()?.join("gh-api-cache.json"))
 }
 
+/// Get path to the history of CI durations
+pub fn ci_durations_path() -> Result<PathBuf> {
+    Ok(cache_dir()?.join("ci-durations.json"))
+}
+
+/// Get the directory of downloaded workflow run log archives
+pub fn run_logs_dir() -> Result<PathBuf> {
+    let dir = cache_dir()?.join("run-logs");
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Get path to the audit log of operations on GitHub
+pub fn audit_log_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join("audit.jsonl"))
+}
+
 /// Get path to app config file
 pub fn app_config_path() -> Result<PathBuf> {
     Ok(config_dir()?.join("config.toml"))
@@ -1,12 +1,19 @@
 //! Recent repositories management
 //!
-//! Handles loading and saving recently used repositories.
+//! Handles loading and saving recently used repositories, grouped in named
+//! workspace profiles (e.g. "work" and "oss"). Each profile owns its
+//! repository list, a default filter and a theme override; files written
+//! before profiles existed load as the "default" profile.
 
-#[allow(deprecated)] // Intentionally using legacy path until migration complete
-use crate::files::{create_recent_repositories_file, open_recent_repositories_file};
+use crate::recovery::{self, Recovered, RecoveryNotice};
+use crate::repo_id::{normalize_host, RepoId};
+use crate::schema::{self, RECENT_REPOSITORIES_SCHEMA_VERSION};
 use crate::DEFAULT_HOST;
 use serde::{Deserialize, Serialize};
-use std::io::BufReader;
+use std::path::Path;
+
+/// Recent repositories file (legacy location in CWD until migration complete)
+pub const RECENT_REPOSITORIES_FILE: &str = ".gh-pr-lander.repos.json";
 
 /// A recently used repository entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,8 +26,15 @@ pub struct RecentRepository {
     #[serde(default = "default_branch")]
     pub branch: String,
     /// GitHub host (None = github.com)
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::repo_id::deserialize_host",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub host: Option<String>,
+    /// Account profile used for this repository (None = from config or default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
 }
 
 fn default_branch() -> String {
@@ -34,6 +48,7 @@ impl RecentRepository {
             repo: repo.into(),
             branch: branch.into(),
             host: None,
+            profile: None,
         }
     }
 
@@ -43,16 +58,21 @@ impl RecentRepository {
         branch: impl Into<String>,
         host: Option<String>,
     ) -> Self {
-        // Normalize github.com to None
-        let host = host.filter(|h| h != DEFAULT_HOST && !h.is_empty());
         Self {
             org: org.into(),
             repo: repo.into(),
             branch: branch.into(),
-            host,
+            host: normalize_host(host.as_deref()),
+            profile: None,
         }
     }
 
+    /// Set the account profile
+    pub fn with_profile(mut self, profile: Option<String>) -> Self {
+        self.profile = profile;
+        self
+    }
+
     /// Get the effective host (defaults to github.com)
     pub fn effective_host(&self) -> &str {
         self.host.as_deref().unwrap_or(DEFAULT_HOST)
@@ -62,46 +82,176 @@ impl RecentRepository {
     pub fn is_github_com(&self) -> bool {
         self.host.is_none()
     }
+
+    /// Canonical identity of this repository
+    pub fn id(&self) -> RepoId {
+        RepoId::new(self.host.as_deref(), &self.org, &self.repo)
+    }
 }
 
-/// Load recent repositories from the config file
-///
-/// Returns an empty vector if the file doesn't exist or can't be parsed.
-pub fn load_recent_repositories() -> Vec<RecentRepository> {
-    #[allow(deprecated)] // Intentionally using legacy path until migration complete
-    match open_recent_repositories_file() {
-        Ok(file) => {
-            let reader = BufReader::new(file);
-            match serde_json::from_reader(reader) {
-                Ok(repos) => {
-                    log::info!("Loaded recent repositories from .gh-pr-lander.repos.json");
-                    repos
-                }
-                Err(e) => {
-                    log::warn!("Failed to parse recent repositories file: {}", e);
-                    Vec::new()
-                }
+/// Name of the profile holding the repositories of files without profiles
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// A named set of repositories, switched as a whole
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceProfile {
+    pub name: String,
+    /// Repositories shown as tabs, in tab order
+    #[serde(default)]
+    pub repositories: Vec<RecentRepository>,
+    /// Saved filter of repositories without a configured `default_filter`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_filter: Option<String>,
+    /// Theme while the profile is active (None = `ui.theme`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+}
+
+impl WorkspaceProfile {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            repositories: Vec::new(),
+            default_filter: None,
+            theme: None,
+        }
+    }
+}
+
+/// All workspace profiles of the recent repositories file, in file order
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceProfiles {
+    pub profiles: Vec<WorkspaceProfile>,
+}
+
+/// On-disk format of the recent repositories file
+#[derive(Debug, Serialize)]
+struct RecentRepositoriesFile<'a> {
+    schema_version: u32,
+    profiles: &'a [WorkspaceProfile],
+}
+
+impl WorkspaceProfiles {
+    /// Only the "default" profile, holding `repositories`
+    pub fn from_repositories(repositories: Vec<RecentRepository>) -> Self {
+        Self {
+            profiles: vec![WorkspaceProfile {
+                repositories,
+                ..WorkspaceProfile::new(DEFAULT_PROFILE)
+            }],
+        }
+    }
+
+    /// Load the profiles, recovering from a corrupted file
+    ///
+    /// Invalid entries are dropped and valid ones kept; an unparsable file is
+    /// backed up and treated as empty. The notice describes what happened.
+    pub fn load_with_recovery() -> (Self, Option<RecoveryNotice>) {
+        Self::load_from_path(Path::new(RECENT_REPOSITORIES_FILE))
+    }
+
+    fn load_from_path(path: &Path) -> (Self, Option<RecoveryNotice>) {
+        let version = schema::file_schema_version(path, schema::json_schema_version);
+        if let Some(version) = version.filter(|v| *v > RECENT_REPOSITORIES_SCHEMA_VERSION) {
+            log::warn!(
+                "Recent repositories {:?} have schema version {} (newer than {}), loading read-only",
+                path,
+                version,
+                RECENT_REPOSITORIES_SCHEMA_VERSION
+            );
+            return (load_newer_profiles(path), None);
+        }
+
+        // Files not migrated yet hold the repositories of a single profile
+        let loaded = if version.is_some_and(|v| v >= 3) {
+            recovery::load_json_list_with_recovery(path, "recent repositories", "profiles")
+                .map(|profiles| Self { profiles })
+        } else {
+            recovery::load_json_list_with_recovery(path, "recent repositories", "repositories")
+                .map(Self::from_repositories)
+        };
+        match loaded {
+            Recovered::Missing => {
+                log::debug!("No recent repositories file found, starting fresh");
+                (Self::default(), None)
+            }
+            Recovered::Loaded(profiles, notice) => {
+                log::info!("Loaded recent repositories from {:?}", path);
+                (profiles, notice)
             }
         }
-        Err(_) => {
-            log::debug!("No recent repositories file found, starting fresh");
-            Vec::new()
+    }
+
+    /// Save the profiles to the recent repositories file
+    ///
+    /// Returns an error if the file cannot be created or written.
+    pub fn save(&self) -> anyhow::Result<()> {
+        self.save_to_path(Path::new(RECENT_REPOSITORIES_FILE))
+    }
+
+    fn save_to_path(&self, path: &Path) -> anyhow::Result<()> {
+        schema::ensure_writable(
+            path,
+            schema::json_schema_version,
+            RECENT_REPOSITORIES_SCHEMA_VERSION,
+        )?;
+        let content = serde_json::to_vec_pretty(&RecentRepositoriesFile {
+            schema_version: RECENT_REPOSITORIES_SCHEMA_VERSION,
+            profiles: &self.profiles,
+        })?;
+        recovery::write_atomic(path, &content)?;
+        log::info!("Saved {} profiles to {:?}", self.profiles.len(), path);
+        Ok(())
+    }
+
+    /// Profile named `name`
+    pub fn get(&self, name: &str) -> Option<&WorkspaceProfile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// Names of the profiles, in file order
+    pub fn names(&self) -> Vec<String> {
+        self.profiles.iter().map(|p| p.name.clone()).collect()
+    }
+
+    /// Replace the repositories of a profile, adding the profile if it is new
+    pub fn set_repositories(&mut self, name: &str, repositories: Vec<RecentRepository>) {
+        match self
+            .profiles
+            .iter_mut()
+            .find(|profile| profile.name == name)
+        {
+            Some(profile) => profile.repositories = repositories,
+            None => self.profiles.push(WorkspaceProfile {
+                repositories,
+                ..WorkspaceProfile::new(name)
+            }),
         }
     }
 }
 
-/// Save recent repositories to the config file
-///
-/// Returns an error if the file cannot be created or written.
-pub fn save_recent_repositories(repos: &[RecentRepository]) -> anyhow::Result<()> {
-    #[allow(deprecated)] // Intentionally using legacy path until migration complete
-    let file = create_recent_repositories_file()?;
-    serde_json::to_writer_pretty(file, repos)?;
-    log::info!(
-        "Saved {} recent repositories to .gh-pr-lander.repos.json",
-        repos.len()
-    );
-    Ok(())
+/// Read the profiles of a file written by a newer release, skipping unknown entries
+fn load_newer_profiles(path: &Path) -> WorkspaceProfiles {
+    let value = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .unwrap_or_default();
+    let entries = |key: &str| value.get(key).and_then(|v| v.as_array()).cloned();
+    match entries("profiles") {
+        Some(profiles) => WorkspaceProfiles {
+            profiles: profiles
+                .into_iter()
+                .filter_map(|entry| serde_json::from_value(entry).ok())
+                .collect(),
+        },
+        None => WorkspaceProfiles::from_repositories(
+            entries("repositories")
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|entry| serde_json::from_value(entry).ok())
+                .collect(),
+        ),
+    }
 }
 
 #[cfg(test)]
@@ -126,6 +276,18 @@ mod tests {
         assert_eq!(parsed.branch, "main");
     }
 
+    #[test]
+    fn test_legacy_host_is_normalized() {
+        let json = r#"{"org": "Acme", "repo": "api", "host": "GHE.Example.com"}"#;
+        let parsed: RecentRepository = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.host.as_deref(), Some("ghe.example.com"));
+        assert_eq!(parsed.id(), "ghe.example.com/acme/api".parse().unwrap());
+
+        let json = r#"{"org": "acme", "repo": "api", "host": "github.com"}"#;
+        let parsed: RecentRepository = serde_json::from_str(json).unwrap();
+        assert!(parsed.host.is_none());
+    }
+
     #[test]
     fn test_default_branch() {
         let json = r#"{"org": "test", "repo": "repo"}"#;
@@ -167,6 +329,43 @@ mod tests {
         assert!(!json.contains("host"));
     }
 
+    #[test]
+    fn test_profile_serde() {
+        let repo = RecentRepository::new("org", "repo", "main");
+        assert!(!serde_json::to_string(&repo).unwrap().contains("profile"));
+
+        let repo = repo.with_profile(Some("work".to_string()));
+        let json = serde_json::to_string(&repo).unwrap();
+        let parsed: RecentRepository = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.profile.as_deref(), Some("work"));
+    }
+
+    #[test]
+    fn test_load_keeps_valid_entries() {
+        let dir = std::env::temp_dir().join(format!("gh-pr-config-repos-{}", std::process::id()));
+        let path = dir.join("repos.json");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            &path,
+            r#"[{"org": "a", "repo": "one"}, {"org": 1, "repo": "two"}, {"org": "c", "repo": "three"}]"#,
+        )
+        .unwrap();
+
+        let (profiles, notice) = WorkspaceProfiles::load_from_path(&path);
+        let repos = &profiles.get(DEFAULT_PROFILE).unwrap().repositories;
+        let names: Vec<&str> = repos.iter().map(|r| r.repo.as_str()).collect();
+        assert_eq!(names, vec!["one", "three"]);
+        assert!(notice.is_some());
+
+        // Saving atomically writes the valid entries back
+        profiles.save_to_path(&path).unwrap();
+        let (reloaded, notice) = WorkspaceProfiles::load_from_path(&path);
+        assert_eq!(reloaded.get(DEFAULT_PROFILE).unwrap().repositories.len(), 2);
+        assert!(notice.is_none());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_host_serde_with_value() {
         let repo =
@@ -177,4 +376,56 @@ mod tests {
         let parsed: RecentRepository = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.host, Some("ghe.example.com".to_string()));
     }
+
+    #[test]
+    fn test_newer_file_is_read_only() {
+        let dir =
+            std::env::temp_dir().join(format!("gh-pr-config-repos-newer-{}", std::process::id()));
+        let path = dir.join("repos.json");
+        std::fs::create_dir_all(&dir).unwrap();
+        let content = r#"{"schema_version": 9, "repositories": [{"org": "a", "repo": "one", "pinned": true}]}"#;
+        std::fs::write(&path, content).unwrap();
+
+        let (profiles, notice) = WorkspaceProfiles::load_from_path(&path);
+        assert_eq!(profiles.get(DEFAULT_PROFILE).unwrap().repositories.len(), 1);
+        assert!(notice.is_none());
+        assert!(profiles.save_to_path(&path).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), content);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_profiles_round_trip() {
+        let dir =
+            std::env::temp_dir().join(format!("gh-pr-config-profiles-{}", std::process::id()));
+        let path = dir.join("repos.json");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            &path,
+            r#"{"schema_version": 2, "repositories": [{"org": "acme", "repo": "api"}]}"#,
+        )
+        .unwrap();
+
+        // A file without profiles is the default profile
+        let (mut profiles, notice) = WorkspaceProfiles::load_from_path(&path);
+        assert!(notice.is_none());
+        assert_eq!(profiles.names(), vec![DEFAULT_PROFILE]);
+
+        let oss = vec![RecentRepository::new("rust-lang", "rust", "master")];
+        profiles.set_repositories("oss", oss);
+        profiles.profiles[1].theme = Some("high-contrast".to_string());
+        profiles.save_to_path(&path).unwrap();
+
+        let (reloaded, notice) = WorkspaceProfiles::load_from_path(&path);
+        assert!(notice.is_none());
+        assert_eq!(reloaded.names(), vec![DEFAULT_PROFILE, "oss"]);
+        let oss = reloaded.get("oss").unwrap();
+        assert_eq!(oss.repositories[0].repo, "rust");
+        assert_eq!(oss.theme.as_deref(), Some("high-contrast"));
+        assert_eq!(oss.default_filter, None);
+        assert_eq!(reloaded.get(DEFAULT_PROFILE).unwrap().repositories.len(), 1);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
 }
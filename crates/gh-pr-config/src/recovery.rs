@@ -0,0 +1,571 @@
+//! Corruption recovery and atomic writes for persisted files
+//!
+//! Loaders in this crate never refuse to start because of a broken file:
+//! - Unreadable or unparsable files are renamed to `<file>.broken-<timestamp>`
+//!   and the loader continues with defaults.
+//! - Valid TOML/JSON with wrong-typed fields keeps every field that still
+//!   deserializes; the original file is backed up the same way.
+//! - Saving writes a temp file next to the target and renames it, so a crash
+//!   mid-save cannot leave a truncated file behind.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Describes a corrupted file that was backed up and reset
+#[derive(Debug, Clone)]
+pub struct RecoveryNotice {
+    /// What the file contains (e.g. "session")
+    pub what: &'static str,
+    /// Path of the corrupted file
+    pub path: PathBuf,
+    /// Where the corrupted file was moved to (None if the backup failed)
+    pub backup: Option<PathBuf>,
+    /// Names of the fields that were dropped (empty = whole file was reset)
+    pub dropped_fields: Vec<String>,
+    /// The underlying parse/read error
+    pub error: String,
+}
+
+impl RecoveryNotice {
+    /// User-facing message for the status bar
+    pub fn message(&self) -> String {
+        let backup = self
+            .backup
+            .as_ref()
+            .map(|p| format!("backup at {}", p.display()))
+            .unwrap_or_else(|| "backup failed".to_string());
+
+        if self.dropped_fields.is_empty() {
+            format!(
+                "{} was corrupted and has been reset, {}",
+                capitalize(self.what),
+                backup
+            )
+        } else {
+            format!(
+                "{} had invalid fields ({}) that have been reset, {}",
+                capitalize(self.what),
+                self.dropped_fields.join(", "),
+                backup
+            )
+        }
+    }
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Write `contents` to `path` atomically (temp file in the same directory + rename)
+///
+/// Until the rename, `path` keeps its old content, so a crash at any point
+/// leaves either the old or the new file, never a mix.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let tmp_path = write_temp(path, contents)?;
+    fs::rename(&tmp_path, path)
+        .with_context(|| format!("Failed to move {:?} to {:?}", tmp_path, path))
+        .inspect_err(|_| {
+            let _ = fs::remove_file(&tmp_path);
+        })
+}
+
+/// Write `contents` to a synced temp file next to `path`, returns its path
+///
+/// Every call gets its own temp file, so concurrent saves of the same file
+/// never write into each other.
+fn write_temp(path: &Path, contents: &[u8]) -> Result<PathBuf> {
+    static NEXT_TEMP: AtomicU64 = AtomicU64::new(0);
+
+    let parent = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(parent)
+        .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp_path = parent.join(format!(
+        ".{}.tmp-{}-{}",
+        file_name,
+        std::process::id(),
+        NEXT_TEMP.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let result = (|| -> Result<()> {
+        let mut file = fs::File::create(&tmp_path)
+            .with_context(|| format!("Failed to create temp file: {:?}", tmp_path))?;
+        file.write_all(contents)
+            .with_context(|| format!("Failed to write temp file: {:?}", tmp_path))?;
+        file.sync_all()
+            .with_context(|| format!("Failed to sync temp file: {:?}", tmp_path))
+    })();
+
+    match result {
+        Ok(()) => Ok(tmp_path),
+        Err(e) => {
+            let _ = fs::remove_file(&tmp_path);
+            Err(e)
+        }
+    }
+}
+
+/// Move a corrupted file out of the way to `<file>.broken-<timestamp>`
+pub fn backup_broken_file(path: &Path) -> Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let backup = path.with_file_name(format!(
+        "{}.broken-{}",
+        file_name,
+        Utc::now().format("%Y%m%d%H%M%S")
+    ));
+    fs::rename(path, &backup)
+        .with_context(|| format!("Failed to back up {:?} to {:?}", path, backup))?;
+    Ok(backup)
+}
+
+/// Result of loading a file with recovery
+#[derive(Debug)]
+pub enum Recovered<T> {
+    /// File does not exist
+    Missing,
+    /// File loaded (possibly partially, see the notice)
+    Loaded(T, Option<RecoveryNotice>),
+}
+
+impl<T> Recovered<T> {
+    /// Convert the loaded value, keeping the notice
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Recovered<U> {
+        match self {
+            Self::Missing => Recovered::Missing,
+            Self::Loaded(value, notice) => Recovered::Loaded(f(value), notice),
+        }
+    }
+}
+
+/// Load a TOML file, recovering from corruption
+///
+/// Missing fields fall back to their serde defaults, so `T` should tolerate
+/// an empty table.
+pub fn load_toml_with_recovery<T>(path: &Path, what: &'static str) -> Recovered<T>
+where
+    T: DeserializeOwned + Default,
+{
+    load_with_recovery(path, what, |content| {
+        let table: toml::Table = toml::from_str(content).map_err(|e| e.to_string())?;
+        match from_table::<T>(table.clone()) {
+            Ok(value) => Ok((value, Vec::new())),
+            Err(e) => {
+                log::warn!("{} {:?} has invalid fields: {}", what, path, e);
+                Ok(deserialize_toml_lenient(table))
+            }
+        }
+    })
+}
+
+/// Load a JSON list file, recovering from corruption
+///
+/// Accepts a bare array (unversioned format) or an object holding the list
+/// under `key`. Entries that fail to deserialize are dropped, all others are kept.
+pub fn load_json_list_with_recovery<T>(
+    path: &Path,
+    what: &'static str,
+    key: &str,
+) -> Recovered<Vec<T>>
+where
+    T: DeserializeOwned,
+{
+    load_with_recovery(path, what, |content| {
+        let value: serde_json::Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        let entries = match value {
+            serde_json::Value::Array(entries) => entries,
+            serde_json::Value::Object(mut object) => match object.remove(key) {
+                Some(serde_json::Value::Array(entries)) => entries,
+                None => Vec::new(),
+                Some(_) => return Err(format!("expected '{}' to be a JSON array", key)),
+            },
+            _ => return Err("expected a JSON array or object".to_string()),
+        };
+
+        let mut kept = Vec::new();
+        let mut dropped = Vec::new();
+        for (idx, entry) in entries.into_iter().enumerate() {
+            match serde_json::from_value(entry) {
+                Ok(item) => kept.push(item),
+                Err(e) => {
+                    log::warn!("Dropping invalid {} entry #{}: {}", what, idx, e);
+                    dropped.push(format!("entry #{}", idx));
+                }
+            }
+        }
+        Ok((kept, dropped))
+    })
+}
+
+/// Parse a TOML document leniently without touching the file on disk
+///
+/// Used for files written by a newer release: unknown or changed fields are
+/// skipped, and nothing is backed up or rewritten.
+pub fn parse_toml_lenient<T>(content: &str) -> Option<T>
+where
+    T: DeserializeOwned + Default,
+{
+    let table: toml::Table = toml::from_str(content).ok()?;
+    match from_table::<T>(table.clone()) {
+        Ok(value) => Some(value),
+        Err(_) => Some(deserialize_toml_lenient(table).0),
+    }
+}
+
+/// Shared load/backup logic; `parse` returns the value and the dropped fields
+fn load_with_recovery<T, F>(path: &Path, what: &'static str, parse: F) -> Recovered<T>
+where
+    T: Default,
+    F: FnOnce(&str) -> std::result::Result<(T, Vec<String>), String>,
+{
+    let parsed = match fs::read_to_string(path) {
+        Ok(content) => parse(&content),
+        Err(e) if e.kind() == ErrorKind::NotFound => return Recovered::Missing,
+        Err(e) => Err(format!("Failed to read file: {}", e)),
+    };
+
+    let (value, dropped_fields, error) = match parsed {
+        Ok((value, dropped)) if dropped.is_empty() => return Recovered::Loaded(value, None),
+        Ok((value, dropped)) => (value, dropped, "invalid fields".to_string()),
+        Err(error) => (T::default(), Vec::new(), error),
+    };
+
+    log::warn!("{} file {:?} is corrupted: {}", what, path, error);
+    let backup = match backup_broken_file(path) {
+        Ok(backup) => {
+            log::warn!("Backed up corrupted {} file to {:?}", what, backup);
+            Some(backup)
+        }
+        Err(e) => {
+            log::error!("{:#}", e);
+            None
+        }
+    };
+
+    Recovered::Loaded(
+        value,
+        Some(RecoveryNotice {
+            what,
+            path: path.to_path_buf(),
+            backup,
+            dropped_fields,
+            error,
+        }),
+    )
+}
+
+/// Deserialize a TOML table keeping every leaf value that still fits `T`
+///
+/// Leaves are added one at a time in key order; a leaf that makes the
+/// table fail to deserialize is dropped. Returns the value and the dotted
+/// paths of the dropped leaves.
+fn deserialize_toml_lenient<T>(table: toml::Table) -> (T, Vec<String>)
+where
+    T: DeserializeOwned + Default,
+{
+    let mut leaves = Vec::new();
+    collect_leaves(&table, &mut Vec::new(), &mut leaves);
+
+    let mut kept = toml::Table::new();
+    let mut dropped = Vec::new();
+    for (path, value) in leaves {
+        let mut candidate = kept.clone();
+        insert_leaf(&mut candidate, &path, value);
+        if from_table::<T>(candidate.clone()).is_ok() {
+            kept = candidate;
+        } else {
+            dropped.push(path.join("."));
+        }
+    }
+
+    let value = from_table(kept).unwrap_or_default();
+    (value, dropped)
+}
+
+/// Deserialize a typed value from a TOML table
+fn from_table<T: DeserializeOwned>(table: toml::Table) -> std::result::Result<T, toml::de::Error> {
+    toml::Value::Table(table).try_into()
+}
+
+/// Collect all non-table values with their key paths
+fn collect_leaves(
+    table: &toml::Table,
+    prefix: &mut Vec<String>,
+    out: &mut Vec<(Vec<String>, toml::Value)>,
+) {
+    for (key, value) in table {
+        prefix.push(key.clone());
+        match value {
+            toml::Value::Table(inner) => collect_leaves(inner, prefix, out),
+            leaf => out.push((prefix.clone(), leaf.clone())),
+        }
+        prefix.pop();
+    }
+}
+
+/// Insert a value at a key path, creating intermediate tables
+fn insert_leaf(table: &mut toml::Table, path: &[String], value: toml::Value) {
+    match path {
+        [] => {}
+        [key] => {
+            table.insert(key.clone(), value);
+        }
+        [key, rest @ ..] => {
+            let entry = table
+                .entry(key.clone())
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            if let toml::Value::Table(inner) = entry {
+                insert_leaf(inner, rest, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Default, Deserialize, PartialEq)]
+    struct Sample {
+        #[serde(default)]
+        name: String,
+        #[serde(default)]
+        count: u32,
+        #[serde(default)]
+        nested: Nested,
+    }
+
+    #[derive(Debug, Default, Deserialize, PartialEq)]
+    struct Nested {
+        #[serde(default)]
+        flag: bool,
+        #[serde(default)]
+        label: Option<String>,
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir()
+            .join(format!("gh-pr-config-recovery-{}", std::process::id()))
+            .join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn backups_in(dir: &Path) -> Vec<PathBuf> {
+        fs::read_dir(dir)
+            .unwrap()
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.to_string_lossy().contains(".broken-"))
+            .collect()
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let dir = test_dir("missing");
+        let result: Recovered<Sample> = load_toml_with_recovery(&dir.join("none.toml"), "sample");
+        assert!(matches!(result, Recovered::Missing));
+    }
+
+    #[test]
+    fn test_valid_file_has_no_notice() {
+        let dir = test_dir("valid");
+        let path = dir.join("sample.toml");
+        fs::write(&path, "name = \"ok\"\ncount = 3\n").unwrap();
+
+        let Recovered::Loaded(value, notice) = load_toml_with_recovery::<Sample>(&path, "sample")
+        else {
+            panic!("expected loaded");
+        };
+        assert_eq!(value.name, "ok");
+        assert_eq!(value.count, 3);
+        assert!(notice.is_none());
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_truncated_toml_is_backed_up() {
+        let dir = test_dir("truncated");
+        let path = dir.join("sample.toml");
+        fs::write(&path, "name = \"ok\"\n[nested]\nlabel = \"unterminat").unwrap();
+
+        let Recovered::Loaded(value, notice) = load_toml_with_recovery::<Sample>(&path, "sample")
+        else {
+            panic!("expected loaded");
+        };
+        assert_eq!(value, Sample::default());
+        let notice = notice.expect("notice expected");
+        assert!(notice.dropped_fields.is_empty());
+        assert!(notice
+            .message()
+            .contains("was corrupted and has been reset"));
+        assert!(!path.exists());
+        assert_eq!(backups_in(&dir).len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_utf8_is_backed_up() {
+        let dir = test_dir("utf8");
+        let path = dir.join("sample.toml");
+        fs::write(&path, [0x6e, 0x61, 0xff, 0xfe, 0x00]).unwrap();
+
+        let Recovered::Loaded(value, notice) = load_toml_with_recovery::<Sample>(&path, "sample")
+        else {
+            panic!("expected loaded");
+        };
+        assert_eq!(value, Sample::default());
+        assert!(notice.is_some());
+        assert_eq!(backups_in(&dir).len(), 1);
+    }
+
+    #[test]
+    fn test_wrong_typed_field_keeps_valid_fields() {
+        let dir = test_dir("wrong_type");
+        let path = dir.join("sample.toml");
+        fs::write(
+            &path,
+            "name = \"kept\"\ncount = \"not a number\"\n[nested]\nflag = true\nlabel = 42\n",
+        )
+        .unwrap();
+
+        let Recovered::Loaded(value, notice) = load_toml_with_recovery::<Sample>(&path, "sample")
+        else {
+            panic!("expected loaded");
+        };
+        assert_eq!(value.name, "kept");
+        assert_eq!(value.count, 0);
+        assert!(value.nested.flag);
+        assert_eq!(value.nested.label, None);
+
+        let notice = notice.expect("notice expected");
+        assert_eq!(notice.dropped_fields, vec!["count", "nested.label"]);
+        assert_eq!(backups_in(&dir).len(), 1);
+    }
+
+    #[test]
+    fn test_json_array_drops_invalid_entries() {
+        #[derive(Debug, Deserialize)]
+        struct Entry {
+            id: u32,
+        }
+
+        let dir = test_dir("json");
+        let path = dir.join("entries.json");
+        fs::write(&path, r#"[{"id": 1}, {"id": "two"}, {"id": 3}]"#).unwrap();
+
+        let Recovered::Loaded(entries, notice) =
+            load_json_list_with_recovery::<Entry>(&path, "entries", "entries")
+        else {
+            panic!("expected loaded");
+        };
+        let ids: Vec<u32> = entries.iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+        assert_eq!(notice.unwrap().dropped_fields, vec!["entry #1"]);
+    }
+
+    #[test]
+    fn test_json_list_in_versioned_object() {
+        let dir = test_dir("json_object");
+        let path = dir.join("entries.json");
+        fs::write(
+            &path,
+            r#"{"schema_version": 2, "entries": [{"id": 1}, {"id": 2}]}"#,
+        )
+        .unwrap();
+
+        let Recovered::Loaded(entries, notice) =
+            load_json_list_with_recovery::<serde_json::Value>(&path, "entries", "entries")
+        else {
+            panic!("expected loaded");
+        };
+        assert_eq!(entries.len(), 2);
+        assert!(notice.is_none());
+    }
+
+    #[test]
+    fn test_truncated_json_is_backed_up() {
+        let dir = test_dir("json_truncated");
+        let path = dir.join("entries.json");
+        fs::write(&path, r#"[{"id": 1}, {"id""#).unwrap();
+
+        let Recovered::Loaded(entries, notice) =
+            load_json_list_with_recovery::<serde_json::Value>(&path, "entries", "entries")
+        else {
+            panic!("expected loaded");
+        };
+        assert!(entries.is_empty());
+        assert!(notice.is_some());
+        assert_eq!(backups_in(&dir).len(), 1);
+    }
+
+    #[test]
+    fn test_crash_before_rename_keeps_the_old_file() {
+        let dir = test_dir("atomic_crash");
+        let path = dir.join("session.toml");
+        fs::write(&path, "selected_pr_no = 1\n").unwrap();
+
+        // The process dies after writing the temp file, before the rename
+        let tmp_path = write_temp(&path, b"selected_pr_no = 2\n").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "selected_pr_no = 1\n");
+        assert_eq!(
+            fs::read_to_string(&tmp_path).unwrap(),
+            "selected_pr_no = 2\n"
+        );
+        // The next save does not trip over the leftover temp file
+        write_atomic(&path, b"selected_pr_no = 3\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "selected_pr_no = 3\n");
+    }
+
+    #[test]
+    fn test_concurrent_writes_never_mix() {
+        let dir = test_dir("atomic_concurrent");
+        let path = dir.join("notes.json");
+        let contents: Vec<String> = (0..8).map(|i| i.to_string().repeat(4096)).collect();
+
+        std::thread::scope(|scope| {
+            for content in &contents {
+                let path = &path;
+                scope.spawn(move || write_atomic(path, content.as_bytes()).unwrap());
+            }
+        });
+
+        // One of the writes won as a whole
+        assert!(contents.contains(&fs::read_to_string(&path).unwrap()));
+        let entries: Vec<_> = fs::read_dir(&dir).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[test]
+    fn test_write_atomic_replaces_content() {
+        let dir = test_dir("atomic");
+        let path = dir.join("file.toml");
+        fs::write(&path, "old").unwrap();
+
+        write_atomic(&path, b"new").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        // No temp files left behind
+        let entries: Vec<_> = fs::read_dir(&dir).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+    }
+}
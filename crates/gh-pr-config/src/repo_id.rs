@@ -0,0 +1,406 @@
+//! Canonical repository identity
+//!
+//! `RepoId` names a repository across hosts. GitHub treats owner and
+//! repository names case-insensitively, and so does `RepoId`: it keeps the
+//! spelling it was created with for display, but compares, hashes and
+//! orders them lowercased. Hosts are normalized to lowercase, github.com is
+//! stored as `None` like everywhere else in the persisted files.
+//!
+//! Displays and parses as `host/org/repo`. Plain `org/repo` and clone URLs
+//! (`https://host/org/repo`, `git@host:org/repo.git`) parse too, and the
+//! `{ host, org, repo }` table of older files deserializes as well.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use crate::DEFAULT_HOST;
+
+/// Host, owner and name of a repository
+#[derive(Debug, Clone)]
+pub struct RepoId {
+    host: Option<String>,
+    org: String,
+    repo: String,
+}
+
+impl RepoId {
+    /// Create an id, `None` or an empty host meaning github.com
+    pub fn new(host: Option<&str>, org: impl Into<String>, repo: impl Into<String>) -> Self {
+        Self {
+            host: normalize_host(host),
+            org: org.into(),
+            repo: repo.into(),
+        }
+    }
+
+    /// GitHub host (None = github.com)
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_deref()
+    }
+
+    /// Get the effective host (defaults to github.com)
+    pub fn effective_host(&self) -> &str {
+        self.host.as_deref().unwrap_or(DEFAULT_HOST)
+    }
+
+    /// Organization or owner name, as spelled when created
+    pub fn org(&self) -> &str {
+        &self.org
+    }
+
+    /// Repository name, as spelled when created
+    pub fn repo(&self) -> &str {
+        &self.repo
+    }
+
+    /// Parse a clone URL or `host/org/repo`, ignoring anything after the name
+    ///
+    /// Unlike `FromStr`, a host is required, so a bare `org/repo` is `None`.
+    /// Supports:
+    /// - `https://github.com/org/repo`
+    /// - `https://github.example.com/org/repo/pull/1`
+    /// - `git@github.example.com:org/repo.git`
+    /// - `github.example.com/org/repo`
+    pub fn parse_url(url: &str) -> Option<Self> {
+        let url = url.trim();
+
+        let (host, path) = if let Some(rest) = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+        {
+            rest.split_once('/')?
+        } else if let Some(rest) = url.strip_prefix("git@") {
+            rest.split_once(':')?
+        } else {
+            // Short format needs something that looks like a host
+            let (host, path) = url.split_once('/')?;
+            if !host.contains('.') {
+                return None;
+            }
+            (host, path)
+        };
+        if host.is_empty() {
+            return None;
+        }
+
+        let (org, repo) = parse_org_repo(path)?;
+        Some(Self::new(Some(host), org, repo))
+    }
+
+    /// Key used for equality, hashing and ordering
+    fn key(&self) -> (&str, Folded<'_>, Folded<'_>) {
+        (self.effective_host(), Folded(&self.org), Folded(&self.repo))
+    }
+}
+
+/// Normalize a host: trimmed, lowercase, github.com and empty as `None`
+pub fn normalize_host(host: Option<&str>) -> Option<String> {
+    let host = host?.trim().trim_end_matches('/').to_ascii_lowercase();
+    (!host.is_empty() && host != DEFAULT_HOST).then_some(host)
+}
+
+/// Deserialize an optional host field, normalizing hosts written by older releases
+pub(crate) fn deserialize_host<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(normalize_host(
+        Option::<String>::deserialize(deserializer)?.as_deref(),
+    ))
+}
+
+/// Parse "org/repo[.git]", ignoring anything after the name
+fn parse_org_repo(path: &str) -> Option<(&str, &str)> {
+    let mut parts = path.split('/');
+    let org = parts.next()?;
+    let repo = parts.next()?;
+    let repo = repo.strip_suffix(".git").unwrap_or(repo);
+    (!org.is_empty() && !repo.is_empty()).then_some((org, repo))
+}
+
+/// A name compared without regard to ASCII case
+#[derive(Debug, Clone, Copy)]
+struct Folded<'a>(&'a str);
+
+impl Folded<'_> {
+    fn bytes(&self) -> impl Iterator<Item = u8> + '_ {
+        self.0.bytes().map(|b| b.to_ascii_lowercase())
+    }
+}
+
+impl PartialEq for Folded<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(other.0)
+    }
+}
+
+impl Eq for Folded<'_> {}
+
+impl PartialOrd for Folded<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Folded<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bytes().cmp(other.bytes())
+    }
+}
+
+impl Hash for Folded<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for b in self.bytes() {
+            state.write_u8(b);
+        }
+        // Terminate like `str` does, so ("ab", "c") and ("a", "bc") differ
+        state.write_u8(0xff);
+    }
+}
+
+impl PartialEq for RepoId {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for RepoId {}
+
+impl PartialOrd for RepoId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RepoId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl Hash for RepoId {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl fmt::Display for RepoId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}/{}", self.effective_host(), self.org, self.repo)
+    }
+}
+
+/// Error of parsing a `RepoId`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRepoIdError(String);
+
+impl fmt::Display for ParseRepoIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid repository \"{}\", expected [host/]org/repo",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseRepoIdError {}
+
+impl FromStr for RepoId {
+    type Err = ParseRepoIdError;
+
+    /// Parse `host/org/repo`, `org/repo` (github.com) or a clone URL
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseRepoIdError(s.to_string());
+        let trimmed = s.trim();
+        if trimmed.contains("://") || trimmed.starts_with("git@") {
+            return Self::parse_url(trimmed).ok_or_else(error);
+        }
+
+        let parts: Vec<&str> = trimmed.split('/').collect();
+        match parts.as_slice() {
+            [org, repo] if !org.is_empty() && !repo.is_empty() => Ok(Self::new(None, *org, *repo)),
+            [host, org, repo] if !host.is_empty() && !org.is_empty() && !repo.is_empty() => {
+                Ok(Self::new(Some(host), *org, *repo))
+            }
+            _ => Err(error()),
+        }
+    }
+}
+
+impl Serialize for RepoId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Persisted forms of a `RepoId`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RepoIdRepr {
+    /// `host/org/repo`
+    Text(String),
+    /// Table of files written before `RepoId` existed
+    Fields {
+        #[serde(default)]
+        host: Option<String>,
+        org: String,
+        repo: String,
+    },
+}
+
+impl<'de> Deserialize<'de> for RepoId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match RepoIdRepr::deserialize(deserializer)? {
+            RepoIdRepr::Text(text) => text.parse().map_err(serde::de::Error::custom),
+            RepoIdRepr::Fields { host, org, repo } => Ok(Self::new(host.as_deref(), org, repo)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// Hosts, owners and names to check the properties with
+    const HOSTS: &[Option<&str>] = &[
+        None,
+        Some("github.com"),
+        Some("ghe.example.com"),
+        Some("GHE.Example.COM"),
+        Some("git.internal.corp"),
+    ];
+    const ORGS: &[&str] = &["rust-lang", "Rust-Lang", "sassman", "ACME_corp", "a.b"];
+    const REPOS: &[&str] = &["rust", "gh-pr-tui-rs", "Hello-World", "dot.files", "x"];
+
+    fn all_ids() -> impl Iterator<Item = RepoId> {
+        HOSTS.iter().flat_map(|host| {
+            ORGS.iter().flat_map(move |org| {
+                REPOS
+                    .iter()
+                    .map(move |repo| RepoId::new(*host, *org, *repo))
+            })
+        })
+    }
+
+    #[test]
+    fn test_display_parse_round_trip() {
+        for id in all_ids() {
+            let text = id.to_string();
+            let parsed: RepoId = text.parse().unwrap();
+            assert_eq!(parsed, id, "{}", text);
+            // The spelling survives, not just the identity
+            assert_eq!(parsed.to_string(), text);
+            assert_eq!(parsed.org(), id.org());
+            assert_eq!(parsed.repo(), id.repo());
+        }
+    }
+
+    #[test]
+    fn test_url_forms_parse_to_the_same_id() {
+        for id in all_ids() {
+            let host = id.effective_host();
+            let (org, repo) = (id.org(), id.repo());
+            for url in [
+                format!("https://{}/{}/{}", host, org, repo),
+                format!("https://{}/{}/{}.git", host, org, repo),
+                format!("http://{}/{}/{}/pull/42", host, org, repo),
+                format!("git@{}:{}/{}.git", host, org, repo),
+                format!("  {}/{}/{}  ", host, org, repo),
+            ] {
+                assert_eq!(RepoId::parse_url(&url), Some(id.clone()), "{}", url);
+                assert_eq!(url.parse::<RepoId>(), Ok(id.clone()), "{}", url);
+            }
+        }
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct File {
+            repo: RepoId,
+        }
+
+        for id in all_ids() {
+            let file = File { repo: id.clone() };
+            let toml = toml::to_string(&file).unwrap();
+            assert_eq!(toml, format!("repo = \"{}\"\n", id));
+            assert_eq!(toml::from_str::<File>(&toml).unwrap(), file);
+
+            let json = serde_json::to_string(&file).unwrap();
+            assert_eq!(serde_json::from_str::<File>(&json).unwrap(), file);
+        }
+    }
+
+    #[test]
+    fn test_legacy_table_deserializes() {
+        #[derive(Debug, Deserialize)]
+        struct File {
+            repo: RepoId,
+        }
+
+        let file: File = toml::from_str("[repo]\norg = \"acme\"\nrepo = \"api\"\n").unwrap();
+        assert_eq!(file.repo, RepoId::new(None, "acme", "api"));
+
+        let file: File =
+            toml::from_str("[repo]\nhost = \"GHE.example.com\"\norg = \"Acme\"\nrepo = \"API\"\n")
+                .unwrap();
+        assert_eq!(file.repo.to_string(), "ghe.example.com/Acme/API");
+    }
+
+    #[test]
+    fn test_equality_ignores_case_but_not_host() {
+        let ids: HashSet<RepoId> = all_ids().collect();
+        // Case variants of owners and hosts collapse, github.com is the default
+        assert_eq!(ids.len(), 3 * 4 * 5);
+
+        let lower = RepoId::new(None, "rust-lang", "rust");
+        let upper = RepoId::new(Some("GitHub.com"), "Rust-Lang", "RUST");
+        assert_eq!(lower, upper);
+        assert_eq!(lower.cmp(&upper), Ordering::Equal);
+        assert_ne!(
+            lower,
+            RepoId::new(Some("ghe.example.com"), "rust-lang", "rust")
+        );
+        assert_ne!(
+            RepoId::new(None, "ab", "c"),
+            RepoId::new(None, "a", "bc"),
+            "org and repo do not run together"
+        );
+    }
+
+    #[test]
+    fn test_ordering_is_consistent_with_equality() {
+        let ids: Vec<RepoId> = all_ids().collect();
+        for a in &ids {
+            for b in &ids {
+                assert_eq!(a == b, a.cmp(b) == Ordering::Equal, "{} {}", a, b);
+                assert_eq!(a.cmp(b), b.cmp(a).reverse(), "{} {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        for text in [
+            "",
+            "acme",
+            "/api",
+            "acme/",
+            "a/b/c/d",
+            "https://github.com/acme",
+        ] {
+            assert!(text.parse::<RepoId>().is_err(), "{}", text);
+        }
+        assert_eq!(RepoId::parse_url("acme/api"), None);
+        assert_eq!(RepoId::parse_url("invalid"), None);
+        assert_eq!(
+            "acme".parse::<RepoId>().unwrap_err().to_string(),
+            "invalid repository \"acme\", expected [host/]org/repo"
+        );
+    }
+}
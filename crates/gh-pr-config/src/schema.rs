@@ -0,0 +1,115 @@
+//! Schema versions of persisted files
+//!
+//! Every persisted file carries a `schema_version` field. Files written by an
+//! older release are upgraded by `gh-pr-config-migrate` at bootstrap; files
+//! written by a newer release are loaded read-only so their data survives.
+
+use anyhow::{bail, Result};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+/// Current schema version of the session file
+pub const SESSION_SCHEMA_VERSION: u32 = 2;
+
+/// Current schema version of the recent repositories file
+pub const RECENT_REPOSITORIES_SCHEMA_VERSION: u32 = 3;
+
+/// Current schema version of the notes file
+pub const NOTES_SCHEMA_VERSION: u32 = 1;
+
+/// Current schema version of the snooze file
+pub const SNOOZE_SCHEMA_VERSION: u32 = 1;
+
+/// Current schema version of the saved filters file
+pub const FILTERS_SCHEMA_VERSION: u32 = 1;
+
+/// Name of the version field in every persisted file
+pub const SCHEMA_VERSION_FIELD: &str = "schema_version";
+
+/// Detect the schema version of a TOML file
+///
+/// Files without `schema_version` fall back to the legacy `[meta] version`
+/// field, and to version 1 if neither exists.
+pub fn toml_schema_version(content: &str) -> Result<u32> {
+    let table: toml::Table = toml::from_str(content)?;
+    let version = table
+        .get(SCHEMA_VERSION_FIELD)
+        .or_else(|| table.get("meta").and_then(|meta| meta.get("version")))
+        .and_then(|v| v.as_integer());
+    Ok(version.map(|v| v as u32).unwrap_or(1))
+}
+
+/// Detect the schema version of a JSON file
+///
+/// A bare top-level array is the unversioned version 1 format.
+pub fn json_schema_version(content: &str) -> Result<u32> {
+    let value: serde_json::Value = serde_json::from_str(content)?;
+    if value.is_array() {
+        return Ok(1);
+    }
+    let version = value.get(SCHEMA_VERSION_FIELD).and_then(|v| v.as_u64());
+    Ok(version.map(|v| v as u32).unwrap_or(1))
+}
+
+/// Read the schema version of the file at `path` (None if missing or unparsable)
+pub fn file_schema_version(path: &Path, detect: fn(&str) -> Result<u32>) -> Option<u32> {
+    let content = fs::read_to_string(path).ok()?;
+    detect(&content).ok()
+}
+
+/// Refuse to overwrite a file written by a newer release
+pub fn ensure_writable(path: &Path, detect: fn(&str) -> Result<u32>, supported: u32) -> Result<()> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(_) => return Ok(()), // Unreadable files are handled by recovery
+    };
+    match detect(&content) {
+        Ok(version) if version > supported => bail!(
+            "{:?} has schema version {} (newer than supported {}), refusing to overwrite",
+            path,
+            version,
+            supported
+        ),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toml_schema_version() {
+        assert_eq!(toml_schema_version("schema_version = 3\n").unwrap(), 3);
+        assert_eq!(toml_schema_version("[meta]\nversion = 1\n").unwrap(), 1);
+        assert_eq!(toml_schema_version("").unwrap(), 1);
+        assert!(toml_schema_version("not = [valid").is_err());
+    }
+
+    #[test]
+    fn test_json_schema_version() {
+        assert_eq!(json_schema_version("[]").unwrap(), 1);
+        assert_eq!(
+            json_schema_version(r#"{"schema_version": 2, "repositories": []}"#).unwrap(),
+            2
+        );
+        assert!(json_schema_version("{").is_err());
+    }
+
+    #[test]
+    fn test_ensure_writable_refuses_newer() {
+        let dir = std::env::temp_dir().join(format!("gh-pr-config-schema-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.toml");
+
+        assert!(ensure_writable(&path, toml_schema_version, 2).is_ok());
+        std::fs::write(&path, "schema_version = 2\n").unwrap();
+        assert!(ensure_writable(&path, toml_schema_version, 2).is_ok());
+        std::fs::write(&path, "schema_version = 9\n").unwrap();
+        assert!(ensure_writable(&path, toml_schema_version, 2).is_err());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -12,19 +12,111 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::fs;
 use std::path::Path;
 
 use crate::paths;
+use crate::recovery::{self, Recovered, RecoveryNotice};
+use crate::repo_id::{normalize_host, RepoId};
+use crate::schema::{self, SESSION_SCHEMA_VERSION};
 use crate::DEFAULT_HOST;
 
-const SESSION_VERSION: u32 = 1;
-
 /// Session metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionMeta {
+    #[serde(default = "Utc::now")]
     pub last_modified: DateTime<Utc>,
-    pub version: u32,
+}
+
+impl Default for SessionMeta {
+    fn default() -> Self {
+        Self {
+            last_modified: Utc::now(),
+        }
+    }
+}
+
+/// A pull request on the watch list, identified across repositories
+///
+/// Compares like its `RepoId`, so the spelling of owner and name does not
+/// matter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchedPr {
+    /// GitHub host (None = github.com)
+    #[serde(
+        default,
+        deserialize_with = "crate::repo_id::deserialize_host",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub host: Option<String>,
+    pub org: String,
+    pub repo: String,
+    pub number: u64,
+}
+
+impl WatchedPr {
+    pub fn new(
+        host: Option<&str>,
+        org: impl Into<String>,
+        repo: impl Into<String>,
+        number: u64,
+    ) -> Self {
+        Self {
+            host: normalize_host(host),
+            org: org.into(),
+            repo: repo.into(),
+            number,
+        }
+    }
+
+    /// Get the effective host (defaults to github.com)
+    pub fn effective_host(&self) -> &str {
+        self.host.as_deref().unwrap_or(DEFAULT_HOST)
+    }
+
+    /// Repository of the pull request
+    pub fn repo_id(&self) -> RepoId {
+        RepoId::new(self.host.as_deref(), &self.org, &self.repo)
+    }
+
+    /// Web URL of the pull request
+    pub fn html_url(&self) -> String {
+        format!(
+            "https://{}/{}/{}/pull/{}",
+            self.effective_host(),
+            self.org,
+            self.repo,
+            self.number
+        )
+    }
+}
+
+impl PartialEq for WatchedPr {
+    fn eq(&self, other: &Self) -> bool {
+        self.number == other.number && self.repo_id() == other.repo_id()
+    }
+}
+
+impl Eq for WatchedPr {}
+
+impl std::hash::Hash for WatchedPr {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.repo_id().hash(state);
+        self.number.hash(state);
+    }
+}
+
+impl std::fmt::Display for WatchedPr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}#{}", self.org, self.repo, self.number)
+    }
+}
+
+/// A pull request as it was last seen in the table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeenPr {
+    pub pr: WatchedPr,
+    /// GitHub's `updated_at` of the PR when it was last seen
+    pub updated_at: DateTime<Utc>,
 }
 
 /// Session data - the actual persisted state
@@ -38,11 +130,31 @@ pub struct SessionData {
     pub selected_repo_host: Option<String>,
     /// Selected PR number (not index) - more stable across refreshes
     pub selected_pr_no: Option<usize>,
+    /// Pull requests on the watch list
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub watched: Vec<WatchedPr>,
+    /// Whether the build logs list successful jobs (None = as configured)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_log_show_successful: Option<bool>,
+    /// Pull requests seen in the table, to mark the ones updated since
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub seen: Vec<SeenPr>,
+    /// Workspace profile the repositories were loaded from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+}
+
+fn default_schema_version() -> u32 {
+    SESSION_SCHEMA_VERSION
 }
 
 /// Complete session with metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
+    /// Schema version of the file (see `schema` module)
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
+    #[serde(default)]
     pub meta: SessionMeta,
     #[serde(default)]
     pub session: SessionData,
@@ -51,10 +163,8 @@ pub struct Session {
 impl Default for Session {
     fn default() -> Self {
         Self {
-            meta: SessionMeta {
-                last_modified: Utc::now(),
-                version: SESSION_VERSION,
-            },
+            schema_version: SESSION_SCHEMA_VERSION,
+            meta: SessionMeta::default(),
             session: SessionData::default(),
         }
     }
@@ -63,36 +173,69 @@ impl Default for Session {
 impl Session {
     /// Load session with precedence: local > global > default
     pub fn load() -> Self {
+        Self::load_with_recovery().0
+    }
+
+    /// Load session with precedence: local > global > default
+    ///
+    /// A corrupted session file is backed up and reset; the returned notice
+    /// describes what happened so it can be surfaced to the user.
+    pub fn load_with_recovery() -> (Self, Option<RecoveryNotice>) {
         // Try local first
         if paths::has_local_session() {
             if let Ok(path) = paths::local_session_path() {
-                if let Ok(session) = Self::load_from_path(&path) {
+                if let Some(loaded) = Self::load_from_path(&path) {
                     log::info!("Loaded local session from {:?}", path);
-                    return session;
+                    return loaded;
                 }
             }
         }
 
         // Try global
         if let Ok(path) = paths::global_session_path() {
-            if path.exists() {
-                if let Ok(session) = Self::load_from_path(&path) {
-                    log::info!("Loaded global session from {:?}", path);
-                    return session;
-                }
+            if let Some(loaded) = Self::load_from_path(&path) {
+                log::info!("Loaded global session from {:?}", path);
+                return loaded;
             }
         }
 
         log::info!("No existing session found, using defaults");
-        Self::default()
+        (Self::default(), None)
     }
 
-    /// Load session from specific path
-    fn load_from_path(path: &Path) -> Result<Self> {
-        let content = fs::read_to_string(path)
-            .with_context(|| format!("Failed to read session file: {:?}", path))?;
-        toml::from_str(&content)
-            .with_context(|| format!("Failed to parse session file: {:?}", path))
+    /// Load session from specific path (None if the file does not exist)
+    ///
+    /// A recovered session is written back to the same path, so a reset local
+    /// session keeps taking precedence over the global one. A session written
+    /// by a newer release is loaded read-only and never reset.
+    fn load_from_path(path: &Path) -> Option<(Self, Option<RecoveryNotice>)> {
+        if let Some(version) = schema::file_schema_version(path, schema::toml_schema_version) {
+            if version > SESSION_SCHEMA_VERSION {
+                log::warn!(
+                    "Session {:?} has schema version {} (newer than {}), loading read-only",
+                    path,
+                    version,
+                    SESSION_SCHEMA_VERSION
+                );
+                let content = std::fs::read_to_string(path).ok()?;
+                return Some((
+                    recovery::parse_toml_lenient(&content).unwrap_or_default(),
+                    None,
+                ));
+            }
+        }
+
+        match recovery::load_toml_with_recovery::<Self>(path, "session") {
+            Recovered::Missing => None,
+            Recovered::Loaded(session, notice) => {
+                if notice.is_some() {
+                    if let Err(e) = session.save_to_path(path) {
+                        log::error!("Failed to write reset session: {:#}", e);
+                    }
+                }
+                Some((session, notice))
+            }
+        }
     }
 
     /// Save session (to local if exists, otherwise global)
@@ -111,14 +254,10 @@ impl Session {
 
     /// Save session to specific path
     fn save_to_path(&self, path: &Path) -> Result<()> {
+        schema::ensure_writable(path, schema::toml_schema_version, SESSION_SCHEMA_VERSION)?;
         let content = toml::to_string_pretty(self).context("Failed to serialize session")?;
 
-        // Ensure parent directory exists
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-
-        fs::write(path, content)
+        recovery::write_atomic(path, content.as_bytes())
             .with_context(|| format!("Failed to write session file: {:?}", path))?;
 
         log::info!("Saved session to {:?}", path);
@@ -130,10 +269,7 @@ impl Session {
         self.session.selected_repo_org = Some(org.to_string());
         self.session.selected_repo_name = Some(name.to_string());
         self.session.selected_repo_branch = Some(branch.to_string());
-        // Normalize github.com to None
-        self.session.selected_repo_host = host
-            .filter(|h| *h != DEFAULT_HOST && !h.is_empty())
-            .map(|h| h.to_string());
+        self.session.selected_repo_host = normalize_host(host);
     }
 
     /// Update selected PR number
@@ -158,10 +294,56 @@ impl Session {
         }
     }
 
+    /// Get the identity of the selected repository
+    pub fn selected_repo_id(&self) -> Option<RepoId> {
+        self.selected_repo()
+            .map(|(org, name, _, host)| RepoId::new(host, org, name))
+    }
+
     /// Get selected PR number
     pub fn selected_pr_no(&self) -> Option<usize> {
         self.session.selected_pr_no
     }
+
+    /// Get the watch list
+    pub fn watched(&self) -> &[WatchedPr] {
+        &self.session.watched
+    }
+
+    /// Replace the watch list
+    pub fn set_watched(&mut self, watched: Vec<WatchedPr>) {
+        self.session.watched = watched;
+    }
+
+    /// Whether the build logs list successful jobs (None = as configured)
+    pub fn build_log_show_successful(&self) -> Option<bool> {
+        self.session.build_log_show_successful
+    }
+
+    /// Remember whether the build logs list successful jobs
+    pub fn set_build_log_show_successful(&mut self, show: Option<bool>) {
+        self.session.build_log_show_successful = show;
+    }
+
+    /// Get the pull requests last seen in the table
+    pub fn seen(&self) -> &[SeenPr] {
+        &self.session.seen
+    }
+
+    /// Replace the pull requests last seen in the table
+    pub fn set_seen(&mut self, seen: Vec<SeenPr>) {
+        self.session.seen = seen;
+    }
+
+    /// Get the active workspace profile
+    pub fn profile(&self) -> Option<&str> {
+        self.session.profile.as_deref()
+    }
+
+    /// Remember the active workspace profile
+    pub fn set_profile(&mut self, profile: Option<String>) {
+        self.session.profile = profile;
+    }
 }
 
 #[cfg(test)]
@@ -171,7 +353,7 @@ mod tests {
     #[test]
     fn test_default_session() {
         let session = Session::default();
-        assert_eq!(session.meta.version, SESSION_VERSION);
+        assert_eq!(session.schema_version, SESSION_SCHEMA_VERSION);
         assert!(session.session.selected_repo_org.is_none());
     }
 
@@ -206,6 +388,34 @@ mod tests {
         session.set_selected_repo("owner", "repo", "main", Some("github.com"));
         let (_, _, _, host) = session.selected_repo().unwrap();
         assert!(host.is_none());
+
+        session.set_selected_repo("Owner", "Repo", "main", Some("GHE.example.com"));
+        assert_eq!(
+            session.selected_repo_id(),
+            Some(RepoId::new(Some("ghe.example.com"), "owner", "repo"))
+        );
+    }
+
+    #[test]
+    fn test_watched_pr_ignores_case() {
+        let watched = WatchedPr::new(Some("GHE.example.com"), "Acme", "API", 7);
+        assert_eq!(watched.host.as_deref(), Some("ghe.example.com"));
+        assert_eq!(
+            watched,
+            WatchedPr::new(Some("ghe.example.com"), "acme", "api", 7)
+        );
+        assert_ne!(
+            watched,
+            WatchedPr::new(Some("ghe.example.com"), "acme", "api", 8)
+        );
+        assert_ne!(watched, WatchedPr::new(None, "acme", "api", 7));
+
+        let set: std::collections::HashSet<_> = [
+            watched.clone(),
+            WatchedPr::new(Some("ghe.example.com"), "acme", "api", 7),
+        ]
+        .into();
+        assert_eq!(set.len(), 1);
     }
 
     #[test]
@@ -226,6 +436,106 @@ mod tests {
         assert_eq!(parsed.selected_pr_no(), Some(42));
     }
 
+    #[test]
+    fn test_load_from_path_keeps_valid_fields() {
+        let dir = std::env::temp_dir().join(format!("gh-pr-config-session-{}", std::process::id()));
+        let path = dir.join("session.toml");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            &path,
+            "[session]\nselected_repo_org = \"org\"\nselected_repo_name = \"repo\"\n\
+             selected_repo_branch = \"main\"\nselected_pr_no = \"not a number\"\n",
+        )
+        .unwrap();
+
+        let (session, notice) = Session::load_from_path(&path).unwrap();
+        let (org, name, _, _) = session.selected_repo().unwrap();
+        assert_eq!((org, name), ("org", "repo"));
+        assert_eq!(session.selected_pr_no(), None);
+        assert_eq!(
+            notice.unwrap().dropped_fields,
+            vec!["session.selected_pr_no"]
+        );
+
+        // The reset session was written back and now loads cleanly
+        let (_, notice) = Session::load_from_path(&path).unwrap();
+        assert!(notice.is_none());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_newer_session_is_read_only() {
+        let dir =
+            std::env::temp_dir().join(format!("gh-pr-config-session-newer-{}", std::process::id()));
+        let path = dir.join("session.toml");
+        std::fs::create_dir_all(&dir).unwrap();
+        let content = "schema_version = 99\nfuture_field = true\n\n[session]\n\
+                       selected_repo_org = \"org\"\nselected_repo_name = \"repo\"\n\
+                       selected_repo_branch = \"main\"\nselected_pr_no = \"renamed later\"\n";
+        std::fs::write(&path, content).unwrap();
+
+        let (session, notice) = Session::load_from_path(&path).unwrap();
+        assert!(notice.is_none());
+        assert!(session.selected_repo().is_some());
+
+        // Neither loading nor saving touches the newer file
+        assert!(session.save_to_path(&path).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), content);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_watched_serialization() {
+        let mut session = Session::default();
+        let toml_str = toml::to_string_pretty(&session).unwrap();
+        assert!(!toml_str.contains("watched"));
+
+        session.set_watched(vec![
+            WatchedPr::new(None, "org", "repo", 7),
+            WatchedPr::new(Some("ghe.example.com"), "org", "internal", 12),
+        ]);
+        let toml_str = toml::to_string_pretty(&session).unwrap();
+        let parsed: Session = toml::from_str(&toml_str).unwrap();
+        assert_eq!(parsed.watched(), session.watched());
+        assert_eq!(parsed.watched()[0].to_string(), "org/repo#7");
+        assert_eq!(
+            parsed.watched()[1].html_url(),
+            "https://ghe.example.com/org/internal/pull/12"
+        );
+    }
+
+    #[test]
+    fn test_seen_serialization() {
+        let mut session = Session::default();
+        let toml_str = toml::to_string_pretty(&session).unwrap();
+        assert!(!toml_str.contains("seen"));
+
+        let updated_at = "2024-05-01T12:00:00Z".parse().unwrap();
+        session.set_seen(vec![SeenPr {
+            pr: WatchedPr::new(None, "org", "repo", 7),
+            updated_at,
+        }]);
+        let toml_str = toml::to_string_pretty(&session).unwrap();
+        let parsed: Session = toml::from_str(&toml_str).unwrap();
+        assert_eq!(parsed.seen(), session.seen());
+        assert_eq!(parsed.seen()[0].updated_at, updated_at);
+    }
+
+    #[test]
+    fn test_profile_serialization() {
+        let mut session = Session::default();
+        assert!(!toml::to_string_pretty(&session)
+            .unwrap()
+            .contains("profile"));
+
+        session.set_profile(Some("work".to_string()));
+        let toml_str = toml::to_string_pretty(&session).unwrap();
+        let parsed: Session = toml::from_str(&toml_str).unwrap();
+        assert_eq!(parsed.profile(), Some("work"));
+    }
+
     #[test]
     fn test_session_serialization_with_host() {
         let mut session = Session::default();
@@ -0,0 +1,220 @@
+//! Snoozed pull requests
+//!
+//! A snoozed PR is hidden from the PR table until it wakes up, either at its
+//! deadline or as soon as the PR changes (new head commit or update).
+//! Snoozes live in `~/.config/gh-pr-lander/snoozed.toml`.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::paths;
+use crate::recovery::{self, Recovered, RecoveryNotice};
+use crate::schema::{self, SNOOZE_SCHEMA_VERSION};
+use crate::session::WatchedPr;
+
+/// A snoozed pull request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnoozedPr {
+    /// The snoozed pull request
+    pub pr: WatchedPr,
+    /// Head commit when the PR was snoozed
+    pub head_sha: String,
+    /// Last update of the PR when it was snoozed
+    pub updated_at: DateTime<Utc>,
+    /// Deadline of the snooze (None = until the PR is updated)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl SnoozedPr {
+    /// Whether the deadline has passed
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.until.is_some_and(|until| until <= now)
+    }
+
+    /// Whether the PR changed since it was snoozed
+    pub fn is_outdated(&self, head_sha: &str, updated_at: DateTime<Utc>) -> bool {
+        self.head_sha != head_sha || self.updated_at != updated_at
+    }
+}
+
+fn default_schema_version() -> u32 {
+    SNOOZE_SCHEMA_VERSION
+}
+
+/// All snoozed pull requests
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnoozeList {
+    /// Schema version of the file (see `schema` module)
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
+    #[serde(default)]
+    pub snoozed: Vec<SnoozedPr>,
+}
+
+impl Default for SnoozeList {
+    fn default() -> Self {
+        Self {
+            schema_version: SNOOZE_SCHEMA_VERSION,
+            snoozed: Vec::new(),
+        }
+    }
+}
+
+impl SnoozeList {
+    /// Load snoozed PRs, recovering from a corrupted file
+    pub fn load_with_recovery() -> (Self, Option<RecoveryNotice>) {
+        match paths::snoozed_path() {
+            Ok(path) => Self::load_from_path(&path),
+            Err(e) => {
+                log::warn!("Cannot locate snooze file: {:#}", e);
+                (Self::default(), None)
+            }
+        }
+    }
+
+    fn load_from_path(path: &Path) -> (Self, Option<RecoveryNotice>) {
+        if let Some(version) = schema::file_schema_version(path, schema::toml_schema_version) {
+            if version > SNOOZE_SCHEMA_VERSION {
+                log::warn!(
+                    "Snoozed PRs {:?} have schema version {} (newer than {}), loading read-only",
+                    path,
+                    version,
+                    SNOOZE_SCHEMA_VERSION
+                );
+                let list = std::fs::read_to_string(path)
+                    .ok()
+                    .and_then(|content| recovery::parse_toml_lenient(&content))
+                    .unwrap_or_default();
+                return (list, None);
+            }
+        }
+
+        match recovery::load_toml_with_recovery::<Self>(path, "snoozed PRs") {
+            Recovered::Missing => (Self::default(), None),
+            Recovered::Loaded(list, notice) => {
+                log::info!("Loaded {} snoozed PRs from {:?}", list.snoozed.len(), path);
+                (list, notice)
+            }
+        }
+    }
+
+    /// Save snoozed PRs to the config directory
+    pub fn save(&self) -> Result<()> {
+        self.save_to_path(&paths::snoozed_path()?)
+    }
+
+    fn save_to_path(&self, path: &Path) -> Result<()> {
+        schema::ensure_writable(path, schema::toml_schema_version, SNOOZE_SCHEMA_VERSION)?;
+        let content = toml::to_string_pretty(self).context("Failed to serialize snoozed PRs")?;
+        recovery::write_atomic(path, content.as_bytes())
+            .with_context(|| format!("Failed to write snooze file: {:?}", path))?;
+        log::info!("Saved {} snoozed PRs to {:?}", self.snoozed.len(), path);
+        Ok(())
+    }
+
+    /// Snooze a pull request, replacing an earlier snooze of it
+    pub fn snooze(&mut self, entry: SnoozedPr) {
+        self.snoozed.retain(|snoozed| snoozed.pr != entry.pr);
+        self.snoozed.push(entry);
+    }
+
+    /// Wake a pull request up, returning its snooze
+    pub fn wake(&mut self, pr: &WatchedPr) -> Option<SnoozedPr> {
+        let index = self.snoozed.iter().position(|snoozed| &snoozed.pr == pr)?;
+        Some(self.snoozed.remove(index))
+    }
+
+    /// Wake up all pull requests whose deadline has passed
+    pub fn wake_expired(&mut self, now: DateTime<Utc>) -> Vec<SnoozedPr> {
+        let (expired, snoozed) = std::mem::take(&mut self.snoozed)
+            .into_iter()
+            .partition(|snoozed| snoozed.is_expired(now));
+        self.snoozed = snoozed;
+        expired
+    }
+
+    /// Wake a pull request up if it changed since it was snoozed
+    pub fn wake_updated(
+        &mut self,
+        pr: &WatchedPr,
+        head_sha: &str,
+        updated_at: DateTime<Utc>,
+    ) -> Option<SnoozedPr> {
+        let outdated = self
+            .snoozed
+            .iter()
+            .any(|snoozed| &snoozed.pr == pr && snoozed.is_outdated(head_sha, updated_at));
+        if outdated {
+            self.wake(pr)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn snoozed(number: u64, until: Option<DateTime<Utc>>, updated_at: DateTime<Utc>) -> SnoozedPr {
+        SnoozedPr {
+            pr: WatchedPr::new(None, "org", "repo", number),
+            head_sha: "abc".to_string(),
+            updated_at,
+            until,
+        }
+    }
+
+    #[test]
+    fn test_wake_expired_keeps_running_snoozes() {
+        let now = Utc::now();
+        let mut list = SnoozeList::default();
+        list.snooze(snoozed(1, Some(now - Duration::hours(1)), now));
+        list.snooze(snoozed(2, Some(now + Duration::days(1)), now));
+        list.snooze(snoozed(3, None, now));
+
+        let woken = list.wake_expired(now);
+        assert_eq!(woken.len(), 1);
+        assert_eq!(woken[0].pr.number, 1);
+        assert_eq!(list.snoozed.len(), 2);
+    }
+
+    #[test]
+    fn test_wake_updated_on_new_commit_or_update() {
+        let now = Utc::now();
+        let mut list = SnoozeList::default();
+        list.snooze(snoozed(1, None, now));
+        list.snooze(snoozed(2, None, now));
+        let pr1 = WatchedPr::new(None, "org", "repo", 1);
+        let pr2 = WatchedPr::new(None, "org", "repo", 2);
+
+        assert!(list.wake_updated(&pr1, "abc", now).is_none());
+        assert!(list.wake_updated(&pr1, "def", now).is_some());
+        assert!(list
+            .wake_updated(&pr2, "abc", now + Duration::minutes(5))
+            .is_some());
+        assert!(list.snoozed.is_empty());
+    }
+
+    #[test]
+    fn test_snoozes_survive_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("gh-pr-config-snooze-{}", std::process::id()));
+        let path = dir.join("snoozed.toml");
+        let now = Utc::now();
+        let mut list = SnoozeList::default();
+        list.snooze(snoozed(1, Some(now + Duration::days(3)), now));
+        list.snooze(snoozed(1, None, now));
+
+        list.save_to_path(&path).unwrap();
+        let (loaded, notice) = SnoozeList::load_from_path(&path);
+        assert!(notice.is_none());
+        assert_eq!(loaded, list);
+        assert_eq!(loaded.snoozed.len(), 1);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
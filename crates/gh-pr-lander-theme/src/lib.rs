@@ -58,8 +58,18 @@ pub struct Theme {
     pub tab_active_fg: Color,   // Text color for active tab
     pub tab_inactive_fg: Color, // Text color for inactive tabs (bg is tab_line_bg)
     pub tab_hint_fg: Color,     // Text color for hint at end of tab bar
+
+    // Diff colors
+    pub diff_addition_bg: Color,
+    pub diff_deletion_bg: Color,
+
+    /// Label every semantic state with text or a glyph, never color alone
+    pub text_labels: bool,
 }
 
+/// Names of the built-in themes, as used in the `[ui] theme` config option
+pub const THEME_NAMES: &[&str] = &["dark", "high-contrast"];
+
 impl Default for Theme {
     fn default() -> Self {
         Self::dark()
@@ -125,6 +135,91 @@ impl Theme {
             tab_active_fg: tailwind::SLATE.c950,
             tab_inactive_fg: tailwind::CYAN.c700,
             tab_hint_fg: tailwind::YELLOW.c200,
+
+            // Diff - dark green/red tints
+            diff_addition_bg: Color::Rgb(20, 40, 20),
+            diff_deletion_bg: Color::Rgb(40, 20, 20),
+
+            text_labels: false,
+        }
+    }
+
+    /// High-contrast theme - black background, bright primaries, text labels
+    ///
+    /// Every state that other themes signal by color alone also carries a
+    /// label or glyph, so the UI stays usable without telling colors apart.
+    pub fn high_contrast() -> Self {
+        Self {
+            // Backgrounds
+            bg_primary: Color::Black,
+            bg_secondary: Color::Black,
+            bg_tertiary: Color::Black,
+            bg_panel: Color::Black,
+
+            // Text - no dimmed grays
+            text_primary: Color::White,
+            text_secondary: Color::White,
+            text_muted: Color::Gray,
+            text_header: Color::White,
+
+            // Accents
+            accent_primary: Color::LightCyan,
+            accent_secondary: Color::LightYellow,
+
+            // Status
+            status_success: Color::LightGreen,
+            status_error: Color::LightRed,
+            status_warning: Color::LightYellow,
+            status_info: Color::LightCyan,
+            status_checking: Color::LightYellow,
+
+            // Actions
+            action_navigate: Color::LightCyan,
+            action_select: Color::LightCyan,
+            action_open: Color::LightYellow,
+            action_refresh: Color::LightYellow,
+            action_filter: Color::LightYellow,
+            action_merge: Color::LightGreen,
+            action_rebase: Color::LightYellow,
+            action_danger: Color::LightRed,
+            action_help: Color::White,
+
+            // Selection - inverted colors
+            selected_bg: Color::White,
+            selected_fg: Color::Black,
+
+            // Active/focused row
+            active_bg: Color::LightYellow,
+            active_fg: Color::Black,
+
+            // Table - no alternating tint
+            table_header_bg: Color::Black,
+            table_header_fg: Color::White,
+            table_row_fg: Color::White,
+            table_row_bg_normal: Color::Black,
+            table_row_bg_alt: Color::Black,
+
+            // Tab bar
+            tab_line_bg: Color::Black,
+            tab_active_bg: Color::White,
+            tab_active_fg: Color::Black,
+            tab_inactive_fg: Color::White,
+            tab_hint_fg: Color::LightYellow,
+
+            // Diff - saturated green/red backgrounds
+            diff_addition_bg: Color::Rgb(0, 80, 0),
+            diff_deletion_bg: Color::Rgb(110, 0, 0),
+
+            text_labels: true,
+        }
+    }
+
+    /// Built-in theme by name (see [`THEME_NAMES`])
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
         }
     }
 
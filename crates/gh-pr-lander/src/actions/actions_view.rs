@@ -0,0 +1,29 @@
+//! Actions View actions
+//!
+//! Actions for the workflow run history view of a repository.
+
+use crate::domain_models::Repository;
+use gh_client::WorkflowRun;
+
+/// Actions for the Actions (workflow run history) view
+#[derive(Debug, Clone)]
+pub enum ActionsViewAction {
+    /// Open the view for the current repository (middleware starts the fetch)
+    Open,
+    /// Fetching the runs of `branch` started
+    LoadStart { repo: Repository, branch: String },
+    /// Workflow runs were fetched
+    Loaded(Vec<WorkflowRun>),
+    /// Workflow runs could not be fetched
+    LoadError(String),
+    /// Move the cursor to the next run
+    NavigateNext,
+    /// Move the cursor to the previous run
+    NavigatePrevious,
+    /// Move the cursor to the newest run
+    NavigateToTop,
+    /// Move the cursor to the oldest run
+    NavigateToBottom,
+    /// Open the build logs of the run under the cursor (handled by middleware)
+    OpenSelected,
+}
@@ -0,0 +1,26 @@
+//! Audit log actions
+//!
+//! Actions for the panel listing the operations the app ran on GitHub.
+
+use crate::audit::AuditEntry;
+
+/// Actions for the audit log panel
+#[derive(Debug, Clone)]
+pub enum AuditLogAction {
+    /// Open the panel for the focused PR (the middleware reads the entries)
+    Open,
+    /// Read the entries again (handled by middleware)
+    Refresh,
+    /// The entries were read
+    Loaded(Vec<AuditEntry>),
+    /// Move the cursor to the next entry
+    NavigateNext,
+    /// Move the cursor to the previous entry
+    NavigatePrevious,
+    /// Move the cursor to the first entry
+    NavigateToTop,
+    /// Move the cursor to the last entry
+    NavigateToBottom,
+    /// Switch between the focused PR and its repository
+    ToggleScope,
+}
@@ -0,0 +1,35 @@
+//! Authentication actions
+//!
+//! Actions for managing GitHub tokens stored in the keychain (or the
+//! encrypted file fallback).
+
+/// Actions for stored GitHub tokens
+#[derive(Debug, Clone)]
+pub enum AuthAction {
+    /// Open the masked token input for the current repository's host
+    OpenTokenInput,
+    /// Character typed into the token input
+    InputChar(char),
+    /// Remove the last character of the token input
+    InputBackspace,
+    /// Clear the token input
+    InputClear,
+    /// Validate and store the entered token
+    SubmitToken,
+    /// Token validated and stored
+    TokenStored {
+        /// Account the token was stored for (`host` or `profile@host`)
+        account: String,
+        login: String,
+        /// Warning about the storage backend (e.g. encrypted file fallback)
+        warning: Option<String>,
+    },
+    /// Token was rejected or could not be stored
+    TokenRejected(String),
+    /// Close the token input
+    CloseTokenInput,
+    /// Delete the stored token for the current repository's host
+    DeleteStoredToken,
+    /// Show which hosts have stored tokens
+    ListStoredTokens,
+}
@@ -26,6 +26,10 @@ pub struct AvailableAction {
     pub label: &'static str,
     /// Category for grouping/ordering
     pub category: ActionCategory,
+    /// Number of items the action applies to, shown after the label
+    pub count: Option<usize>,
+    /// Whether the action's preconditions hold (disabled actions are greyed out)
+    pub enabled: bool,
 }
 
 impl AvailableAction {
@@ -35,9 +39,23 @@ impl AvailableAction {
             command,
             label,
             category,
+            count: None,
+            enabled: true,
         }
     }
 
+    /// Show how many items the action applies to (e.g., "Merge 4").
+    pub fn with_count(mut self, count: usize) -> Self {
+        self.count = Some(count);
+        self
+    }
+
+    /// Grey out the action unless `enabled` holds.
+    pub fn enabled_if(mut self, enabled: bool) -> Self {
+        self.enabled = self.enabled && enabled;
+        self
+    }
+
     /// Create a primary action.
     pub fn primary(command: CommandId, label: &'static str) -> Self {
         Self::new(command, label, ActionCategory::Primary)
@@ -2,6 +2,8 @@
 //!
 //! Actions for application initialization and configuration loading.
 
+use crate::state::BootstrapStage;
+
 /// Actions for application bootstrap/initialization
 ///
 /// Note: `ClientReady` has been moved to `Event` enum as it's a fact that middleware
@@ -16,9 +18,19 @@ pub enum BootstrapAction {
     /// Bootstrap process completed
     End,
     /// Application configuration loaded
-    ConfigLoaded(gh_pr_config::AppConfig),
+    ConfigLoaded(Box<gh_pr_config::AppConfig>),
     /// Request to load recent repositories from config
     LoadRecentRepositories,
     /// Recent repositories loaded
     LoadRecentRepositoriesDone,
+    /// A bootstrap stage finished successfully
+    StageCompleted(BootstrapStage),
+    /// A bootstrap stage finished with a warning (stage, message)
+    StageWarning(BootstrapStage, String),
+    /// A bootstrap stage failed (stage, actionable error message)
+    StageFailed(BootstrapStage, String),
+    /// Retry bootstrap after a failed stage
+    Retry,
+    /// Run a failed stage again, handled by the middleware owning the stage
+    RetryStage(BootstrapStage),
 }
@@ -0,0 +1,32 @@
+//! Branch picker actions
+//!
+//! Actions for the popup switching the base branch of the current repository tab.
+
+use crate::domain_models::Repository;
+
+/// Actions for the branch picker popup
+#[derive(Debug, Clone)]
+pub enum BranchPickerAction {
+    /// Open the picker for the current repository (middleware fetches the branches)
+    Open,
+    /// Show the picker for the given repository
+    Show(Repository),
+    /// Branches of the repository were fetched
+    Loaded(Vec<String>),
+    /// Branches could not be fetched
+    LoadFailed(String),
+    /// Character typed into the filter
+    InputChar(char),
+    /// Remove the last character of the filter
+    InputBackspace,
+    /// Clear the filter
+    InputClear,
+    /// Move the cursor to the next branch
+    NavigateNext,
+    /// Move the cursor to the previous branch
+    NavigatePrevious,
+    /// Show the PRs targeting the branch under the cursor (handled by middleware)
+    Select,
+    /// Close the picker without switching
+    Close,
+}
@@ -0,0 +1,28 @@
+//! Bug report actions
+//!
+//! Actions for the "Create bug report bundle" popup.
+
+use std::path::PathBuf;
+
+/// Actions for writing a bug report bundle
+#[derive(Debug, Clone)]
+pub enum BugReportAction {
+    /// Ask for the target path (middleware fills in the default path)
+    Open,
+    /// Show the path input with the given default path
+    Show { default_path: String },
+    /// Character typed into the path input
+    InputChar(char),
+    /// Remove the last character of the path input
+    InputBackspace,
+    /// Clear the path input
+    InputClear,
+    /// Write the bundle to the entered path
+    Submit,
+    /// Bundle was written
+    Written(PathBuf),
+    /// Bundle could not be written
+    Failed(String),
+    /// Close the path input
+    Close,
+}
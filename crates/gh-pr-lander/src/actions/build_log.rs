@@ -2,7 +2,9 @@
 //!
 //! Tagged actions for the build log panel.
 
-use crate::state::{BuildLogJobMetadata, BuildLogPrContext};
+use crate::domain_models::Repository;
+use crate::state::{BuildLogContext, BuildLogJobMetadata, BuildLogRunContext, ParsedRun};
+use std::sync::Arc;
 
 /// Tagged actions for the build log panel
 #[derive(Debug, Clone)]
@@ -10,13 +12,32 @@ pub enum BuildLogAction {
     // === Loading ===
     /// Open build logs for current PR (triggers async fetch)
     Open,
+    /// Open build logs for a single workflow run (triggers async fetch)
+    OpenRun {
+        repo: Repository,
+        run: BuildLogRunContext,
+    },
     /// Loading started
     LoadStart,
-    /// Logs loaded successfully
+    /// The workflow runs to load are known, their logs follow one by one
+    RunsFound {
+        context: BuildLogContext,
+        total: usize,
+    },
+    /// Logs of one workflow run loaded (or an error node if that failed)
+    WorkflowLoaded {
+        run: Arc<gh_actions_log_parser::WorkflowNode>,
+        jobs: Vec<BuildLogJobMetadata>,
+        /// Set if the run is finished, to keep for reopening
+        parsed_run: Option<ParsedRun>,
+    },
+    /// All logs loaded at once
     Loaded {
-        workflows: Vec<gh_actions_log_parser::WorkflowNode>,
+        workflows: Vec<Arc<gh_actions_log_parser::WorkflowNode>>,
         job_metadata: Vec<BuildLogJobMetadata>,
-        pr_context: BuildLogPrContext,
+        context: BuildLogContext,
+        /// Finished runs among the workflows, to keep for reopening
+        parsed_runs: Vec<ParsedRun>,
     },
     /// Loading failed
     LoadError(String),
@@ -58,6 +79,14 @@ pub enum BuildLogAction {
     // === View Options ===
     /// Toggle timestamp display
     ToggleTimestamps,
+    /// Toggle listing successful jobs instead of folding them
+    ToggleShowSuccessful,
+    /// Restore the choice of listing successful jobs (from the session)
+    SetShowSuccessful(bool),
+
+    // === Export ===
+    /// Write the loaded logs to a file in the current directory
+    Export,
 
     // === Viewport ===
     /// Update viewport height (called during render)
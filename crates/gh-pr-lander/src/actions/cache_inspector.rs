@@ -0,0 +1,42 @@
+//! Cache inspector actions
+//!
+//! Actions for the popup listing the cached GitHub API responses.
+
+use gh_client::CacheEntryInfo;
+
+/// Actions for the cache inspector popup
+#[derive(Debug, Clone)]
+pub enum CacheInspectorAction {
+    /// Open the popup (the middleware lists the entries)
+    Open,
+    /// List the entries again (handled by middleware)
+    Refresh,
+    /// The entries were listed
+    Loaded(Vec<CacheEntryInfo>),
+    /// Move the cursor to the next entry
+    NavigateNext,
+    /// Move the cursor to the previous entry
+    NavigatePrevious,
+    /// Move the cursor to the first entry
+    NavigateToTop,
+    /// Move the cursor to the last entry
+    NavigateToBottom,
+    /// Switch between sorting by size and by age
+    CycleSort,
+    /// Start typing the filter
+    EditFilter,
+    /// Start typing a pattern of keys to invalidate
+    EditPattern,
+    /// Character typed into the filter or pattern
+    InputChar(char),
+    /// Remove the last character of the filter or pattern
+    InputBackspace,
+    /// Clear the filter or pattern
+    InputClear,
+    /// Keep the filter, or invalidate the keys matching the pattern (middleware)
+    InputConfirm,
+    /// Drop the filter or pattern being typed
+    InputCancel,
+    /// Delete the entry under the cursor (handled by middleware)
+    DeleteSelected,
+}
@@ -20,6 +20,8 @@ pub enum CommandPaletteAction {
     Clear,
 
     // Specific actions
+    /// Open the command palette searching for the query
+    Open(String),
     /// Close the command palette
     Close,
     /// Execute selected command
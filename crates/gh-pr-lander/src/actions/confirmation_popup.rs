@@ -13,6 +13,8 @@ pub enum ConfirmationPopupAction {
         intent: ConfirmationIntent,
         default_message: String,
         repo_context: String,
+        /// Shown prominently above the message input
+        warnings: Vec<String>,
     },
 
     // Text input (translated from TextInputAction)
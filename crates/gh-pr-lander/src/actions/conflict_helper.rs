@@ -0,0 +1,26 @@
+//! Conflict helper actions
+//!
+//! Actions for the popup listing the files that likely conflict when a PR's
+//! branch cannot be updated.
+
+use crate::domain_models::{ConflictSummary, Pr, Repository, WatchedPr};
+
+/// Actions for the conflict helper popup
+#[derive(Debug, Clone)]
+pub enum ConflictHelperAction {
+    /// Open the popup for a PR (the middleware fetches both sides of the merge base)
+    Open { repo: Repository, pr: Box<Pr> },
+    /// Files changed on both sides of the merge base were compared
+    Loaded {
+        pr: WatchedPr,
+        summary: Box<ConflictSummary>,
+    },
+    /// Comparing the branches failed
+    LoadError { pr: WatchedPr, error: String },
+    /// Copy the commands resolving the conflicts locally (handled by middleware)
+    CopyCommands,
+    /// Open the PR's conflict editor in the browser (handled by middleware)
+    OpenConflictsPage,
+    /// Ask dependabot to recreate its PR from scratch (handled by middleware)
+    RecreateDependabot,
+}
@@ -24,6 +24,12 @@ pub enum DebugConsoleAction {
     SetVisibleHeight(usize),
     /// Batch update of lines from middleware
     LinesUpdated(Vec<String>),
+    /// Log a snapshot of the GitHub API request metrics
+    ShowApiUsage,
+    /// Ask for a new log level filter
+    EditLogLevel,
+    /// Replace the log level filter ("info,gh_client=debug")
+    SetLogLevel(String),
 }
 
 // Custom Debug to avoid logging full line contents (prevents feedback loop)
@@ -37,6 +43,9 @@ impl fmt::Debug for DebugConsoleAction {
             Self::Clear => write!(f, "Clear"),
             Self::SetVisibleHeight(h) => write!(f, "SetVisibleHeight({})", h),
             Self::LinesUpdated(lines) => write!(f, "LinesUpdated(<{} lines>)", lines.len()),
+            Self::ShowApiUsage => write!(f, "ShowApiUsage"),
+            Self::EditLogLevel => write!(f, "EditLogLevel"),
+            Self::SetLogLevel(spec) => write!(f, "SetLogLevel({:?})", spec),
         }
     }
 }
@@ -0,0 +1,20 @@
+//! Dependency updates actions
+//!
+//! Actions for the view grouping bot PRs of all repositories by dependency.
+
+/// Actions for the dependency updates view
+#[derive(Debug, Clone)]
+pub enum DependencyUpdatesAction {
+    /// Open the view
+    Open,
+    /// Move the cursor to the next PR
+    NavigateNext,
+    /// Move the cursor to the previous PR
+    NavigatePrevious,
+    /// Move the cursor to the first PR
+    NavigateToTop,
+    /// Move the cursor to the last PR
+    NavigateToBottom,
+    /// Open the PR under the cursor in the browser (handled by middleware)
+    OpenSelected,
+}
@@ -2,7 +2,8 @@
 //!
 //! Tagged actions for the diff viewer panel.
 
-use gh_diff_viewer::{DiffEvent, PullRequestDiff};
+use crate::domain_models::Repository;
+use gh_diff_viewer::{BlameEntry, CommentPosition, DiffEvent, PullRequestDiff};
 
 /// A review comment loaded from GitHub
 #[derive(Debug, Clone)]
@@ -15,6 +16,12 @@ pub struct LoadedComment {
     pub line: Option<u32>,
     /// Side: "LEFT" or "RIGHT"
     pub side: Option<String>,
+    /// First line of a multi-line comment
+    pub start_line: Option<u32>,
+    /// Side of the first line of a multi-line comment
+    pub start_side: Option<String>,
+    /// Comment on the whole file rather than a line
+    pub is_file_level: bool,
     /// Comment body
     pub body: String,
 }
@@ -30,6 +37,7 @@ pub enum DiffViewerAction {
     /// Diff loaded successfully
     Loaded {
         diff: PullRequestDiff,
+        repo: Repository,
         pr_number: u64,
         pr_title: String,
         head_sha: String,
@@ -86,6 +94,9 @@ pub enum DiffViewerAction {
     // === Generic Input (mode-aware, reducer decides based on inner state) ===
     /// Generic key press - reducer routes based on mode (navigation vs comment editing)
     KeyPress(char),
+    /// Pasted text - inserted in comment mode, ignored otherwise (so it
+    /// never runs as commands)
+    Paste(String),
     /// Backspace - deletes char in comment mode, no-op otherwise
     Backspace,
     /// Confirm - commits comment, selects file, or submits review based on mode
@@ -94,6 +105,10 @@ pub enum DiffViewerAction {
     // === Comments (explicit actions when needed) ===
     /// Start adding a comment on current line
     AddComment,
+    /// Start adding a comment on the file under the cursor
+    CommentOnFile,
+    /// Start adding a comment on the pull request
+    CommentOnPr,
     /// Cancel comment editing
     CancelComment,
     /// Commit the current comment (also triggered by Confirm in comment mode)
@@ -119,20 +134,21 @@ pub enum DiffViewerAction {
         pr_number: u64,
         event: gh_diff_viewer::ReviewEvent,
     },
-    /// Request to submit a single line comment via API (handled by GitHub middleware)
+    /// Request to submit a single comment via API (handled by GitHub middleware)
+    ///
+    /// Line and file comments become review comments, general comments go
+    /// to the PR conversation.
     SubmitCommentRequest {
         pr_number: u64,
         head_sha: String,
         path: String,
-        line: u32,
-        side: String,
+        position: CommentPosition,
         body: String,
     },
     /// Comment was successfully posted to GitHub (updates local state with github_id)
     CommentPosted {
         path: String,
-        line: u32,
-        side: String,
+        position: CommentPosition,
         github_id: u64,
     },
     /// Request to delete a comment via API (handled by GitHub middleware)
@@ -140,16 +156,29 @@ pub enum DiffViewerAction {
         pr_number: u64,
         github_id: u64,
         path: String,
-        line: u32,
-        side: String,
+        position: CommentPosition,
     },
     /// Comment was successfully deleted from GitHub
     CommentDeleted {
         path: String,
-        line: u32,
-        side: String,
+        position: CommentPosition,
     },
 
+    // === Blame ===
+    /// Show or hide who last touched the context and removed lines
+    ToggleBlame,
+    /// Fetch blame for the current file if it has none yet (handled by GitHub middleware)
+    FetchBlame,
+    /// Blame of a file was loaded
+    BlameLoaded {
+        path: String,
+        /// Base commit the blame belongs to (resolved if the diff had none)
+        base_sha: String,
+        entries: Vec<BlameEntry>,
+    },
+    /// Loading the blame of a file failed
+    BlameFailed { path: String, error: String },
+
     // === Events from DiffViewerState ===
     /// Forward an event from the diff viewer state
     Event(DiffEvent),
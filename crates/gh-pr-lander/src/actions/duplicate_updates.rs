@@ -0,0 +1,25 @@
+//! Duplicate updates actions
+//!
+//! Actions for the popup listing the PRs opening the same bot update in
+//! other repositories.
+
+/// Actions for the duplicate updates popup
+#[derive(Debug, Clone)]
+pub enum DuplicateUpdatesAction {
+    /// Open the popup for the bot PR under the cursor
+    Open,
+    /// Move the cursor to the next PR
+    NavigateNext,
+    /// Move the cursor to the previous PR
+    NavigatePrevious,
+    /// Pick or unpick the PR under the cursor
+    Toggle,
+    /// Pick every PR
+    PickAll,
+    /// Unpick every PR
+    PickNone,
+    /// Merge the picked PRs, per repository (handled by middleware)
+    Merge,
+    /// Close the popup
+    Close,
+}
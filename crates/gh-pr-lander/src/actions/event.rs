@@ -41,4 +41,22 @@ pub enum Event {
 
     /// Recent repositories have been loaded from config
     RecentRepositoriesLoaded,
+
+    // === Scheduler Events ===
+    /// The periodic background refresh interval elapsed
+    RefreshDue,
+
+    /// Time to save changed local data (emitted every few seconds)
+    AutosaveDue,
+
+    /// Key input paused for a moment (emitted once per pause)
+    InputPaused,
+
+    /// No user input arrived for a while (emitted once per idle period)
+    UserIdle,
+
+    // === Worker Events ===
+    /// A middleware panicked handling an action; the panic was caught and
+    /// the action went on to the next middleware
+    MiddlewarePanicked { name: &'static str },
 }
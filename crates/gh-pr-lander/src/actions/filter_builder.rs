@@ -0,0 +1,30 @@
+//! Filter builder actions
+//!
+//! Actions for the popup assembling a structured PR filter.
+
+/// Actions for the filter builder popup
+#[derive(Debug, Clone)]
+pub enum FilterBuilderAction {
+    /// Open the builder, prefilled with the current repository's filter
+    Open,
+    /// Move the cursor to the next row
+    NavigateNext,
+    /// Move the cursor to the previous row
+    NavigatePrevious,
+    /// Select the next value of a choice row
+    CycleNext,
+    /// Select the previous value of a choice row
+    CyclePrevious,
+    /// Cycle a choice row, or type a space into a text row
+    Toggle,
+    /// Character typed into the row under the cursor
+    InputChar(char),
+    /// Remove the last character of the row under the cursor
+    InputBackspace,
+    /// Clear the row under the cursor
+    InputClear,
+    /// Apply the filter, saving it if named (handled by middleware)
+    Apply,
+    /// Close the builder without changes
+    Close,
+}
@@ -4,6 +4,7 @@
 
 use ratatui::crossterm::event::KeyEvent;
 
+use crate::actions::Action;
 use crate::views::View;
 
 /// Global actions that affect the entire application
@@ -11,14 +12,27 @@ use crate::views::View;
 pub enum GlobalAction {
     /// Raw key pressed (before translation)
     KeyPressed(KeyEvent),
-    /// Close the current view (pop from stack)
+    /// The same raw key pressed `count` times in a row (coalesced key burst)
+    KeyRepeated { key: KeyEvent, count: usize },
+    /// Text pasted into the terminal (bracketed paste), in one piece
+    Paste(String),
+    /// Apply `action` `count` times in a single dispatch (coalesced navigation)
+    Repeat { action: Box<Action>, count: usize },
+    /// Close the current view, quitting on the root view (`ui.quit_on_close`)
     Close,
+    /// Remove exactly the top view; the root view is never removed
+    PopView,
     /// Quit the application
     Quit,
+    /// Quit, asking first while PR operations are in flight or review
+    /// comments unsent (handled by `QuitMiddleware`)
+    RequestQuit,
     /// Push a new view onto the stack
     PushView(Box<dyn View>),
     /// Replace entire view stack with new view
     ReplaceView(Box<dyn View>),
+    /// Terminal resized to `width` x `height` cells
+    Resized { width: u16, height: u16 },
     /// Periodic tick for animations
     Tick,
 }
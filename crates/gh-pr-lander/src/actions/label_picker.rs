@@ -0,0 +1,40 @@
+//! Label picker actions
+//!
+//! Actions for the popup to add and remove labels on the selected PRs.
+
+use crate::domain_models::Repository;
+use gh_client::Label;
+
+/// Actions for the label picker popup
+#[derive(Debug, Clone)]
+pub enum LabelPickerAction {
+    /// Open the picker for the target PRs (middleware collects them)
+    Open,
+    /// Show the picker for the given PRs with their current labels
+    Show {
+        repo: Repository,
+        prs: Vec<(usize, Vec<String>)>,
+    },
+    /// Repository labels were fetched
+    Loaded(Vec<Label>),
+    /// Repository labels could not be fetched
+    LoadFailed(String),
+    /// Character typed into the filter
+    InputChar(char),
+    /// Remove the last character of the filter
+    InputBackspace,
+    /// Clear the filter
+    InputClear,
+    /// Move the cursor to the next label
+    NavigateNext,
+    /// Move the cursor to the previous label
+    NavigatePrevious,
+    /// Toggle the label under the cursor
+    Toggle,
+    /// Apply the changes to the target PRs (handled by middleware)
+    Apply,
+    /// Changes were submitted - close the popup
+    Applied,
+    /// Close the picker without changes
+    Close,
+}
@@ -15,40 +15,74 @@ pub mod navigation;
 pub mod text_input;
 
 // Screen-specific action types
+pub mod actions_view;
+pub mod audit_log;
+pub mod auth;
 pub mod bootstrap;
+pub mod branch_picker;
+pub mod bug_report;
 pub mod build_log;
+pub mod cache_inspector;
 pub mod command_palette;
 pub mod confirmation_popup;
+pub mod conflict_helper;
 pub mod debug_console;
+pub mod dependency_updates;
 pub mod diff_viewer;
+pub mod duplicate_updates;
+pub mod filter_builder;
 pub mod key_bindings;
+pub mod label_picker;
 pub mod merge_bot;
+pub mod onboarding;
+pub mod perf_stats;
+pub mod pr_search;
 pub mod pull_request;
+pub mod release_notes;
 pub mod repository;
+pub mod reviewer_picker;
 pub mod session;
 pub mod splash;
 pub mod status_bar;
+pub mod watch_list;
 
 // Re-export all action types for convenience
+pub use actions_view::ActionsViewAction;
+pub use audit_log::AuditLogAction;
+pub use auth::AuthAction;
 pub use available_action::AvailableAction;
 pub use bootstrap::BootstrapAction;
+pub use branch_picker::BranchPickerAction;
+pub use bug_report::BugReportAction;
 pub use build_log::BuildLogAction;
+pub use cache_inspector::CacheInspectorAction;
 pub use command_palette::CommandPaletteAction;
 pub use confirmation_popup::ConfirmationPopupAction;
+pub use conflict_helper::ConflictHelperAction;
 pub use context_action::ContextAction;
 pub use debug_console::DebugConsoleAction;
+pub use dependency_updates::DependencyUpdatesAction;
 pub use diff_viewer::{DiffViewerAction, LoadedComment};
+pub use duplicate_updates::DuplicateUpdatesAction;
 pub use event::Event;
+pub use filter_builder::FilterBuilderAction;
 pub use global::GlobalAction;
 pub use key_bindings::KeyBindingsAction;
+pub use label_picker::LabelPickerAction;
 pub use merge_bot::MergeBotAction;
 pub use navigation::NavigationAction;
+pub use onboarding::OnboardingAction;
+pub use perf_stats::PerfStatsAction;
+pub use pr_search::PrSearchAction;
 pub use pull_request::PullRequestAction;
+pub use release_notes::ReleaseNotesAction;
 pub use repository::RepositoryAction;
+pub use reviewer_picker::ReviewerPickerAction;
 pub use session::SessionAction;
 pub use splash::SplashAction;
 pub use status_bar::StatusBarAction;
 pub use text_input::TextInputAction;
+pub use watch_list::WatchListAction;
 
 /// Root action enum - tagged by screen/domain
 ///
@@ -104,6 +138,40 @@ pub enum Action {
     Repository(RepositoryAction),
     /// Session management actions
     Session(SessionAction),
+    /// Onboarding wizard actions
+    Onboarding(OnboardingAction),
+    /// Stored GitHub token actions
+    Auth(AuthAction),
+    /// Server-side PR search actions
+    PrSearch(PrSearchAction),
+    /// Watch list actions
+    WatchList(WatchListAction),
+    /// Bug report bundle actions
+    BugReport(BugReportAction),
+    /// Label picker actions
+    LabelPicker(LabelPickerAction),
+    /// Reviewer picker actions
+    ReviewerPicker(ReviewerPickerAction),
+    /// Actions (workflow run history) view actions
+    ActionsView(ActionsViewAction),
+    /// Dependency updates view actions
+    DependencyUpdates(DependencyUpdatesAction),
+    /// Performance stats popup actions
+    PerfStats(PerfStatsAction),
+    /// API cache inspector popup actions
+    CacheInspector(CacheInspectorAction),
+    /// Rebase conflict helper popup actions
+    ConflictHelper(ConflictHelperAction),
+    /// Filter builder popup actions
+    FilterBuilder(FilterBuilderAction),
+    /// Audit log panel actions
+    AuditLog(AuditLogAction),
+    /// Duplicate bot updates popup actions
+    DuplicateUpdates(DuplicateUpdatesAction),
+    /// Release notes popup actions
+    ReleaseNotes(ReleaseNotesAction),
+    /// Branch picker popup actions
+    BranchPicker(BranchPickerAction),
 
     /// No-op action
     None,
@@ -123,4 +191,72 @@ impl Action {
     pub fn event(event: Event) -> Action {
         Action::Event(event)
     }
+
+    /// Whether this action changes something on GitHub and needs a token
+    ///
+    /// Used to reject mutations in read-only mode (no token configured).
+    pub fn requires_token(&self) -> bool {
+        match self {
+            Action::PullRequest(action) => matches!(
+                action,
+                PullRequestAction::MergeRequest
+                    | PullRequestAction::MergePrs { .. }
+                    | PullRequestAction::MergeRepoPrs { .. }
+                    | PullRequestAction::MergeBotUpdatesRequest
+                    | PullRequestAction::RunPolicyRequest
+                    | PullRequestAction::RunPolicy { .. }
+                    | PullRequestAction::RebaseRequest
+                    | PullRequestAction::RebaseStackRequest
+                    | PullRequestAction::ApproveRequest
+                    | PullRequestAction::CommentRequest
+                    | PullRequestAction::RequestChangesRequest
+                    | PullRequestAction::ApproveWithMessage { .. }
+                    | PullRequestAction::CommentOnPr { .. }
+                    | PullRequestAction::RequestChanges { .. }
+                    | PullRequestAction::CloseRequest
+                    | PullRequestAction::ClosePrWithMessage { .. }
+                    | PullRequestAction::RerunFailedJobs
+            ),
+            Action::MergeBot(action) => {
+                matches!(action, MergeBotAction::Start | MergeBotAction::AddToQueue)
+            }
+            Action::LabelPicker(action) => {
+                matches!(action, LabelPickerAction::Open | LabelPickerAction::Apply)
+            }
+            Action::ReviewerPicker(action) => {
+                matches!(
+                    action,
+                    ReviewerPickerAction::Open | ReviewerPickerAction::Apply
+                )
+            }
+            Action::ConflictHelper(action) => {
+                matches!(action, ConflictHelperAction::RecreateDependabot)
+            }
+            Action::DiffViewer(action) => matches!(
+                action,
+                DiffViewerAction::SubmitReviewRequest { .. }
+                    | DiffViewerAction::SubmitCommentRequest { .. }
+                    | DiffViewerAction::DeleteCommentRequest { .. }
+            ),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requires_token() {
+        assert!(Action::PullRequest(PullRequestAction::MergeRequest).requires_token());
+        assert!(Action::LabelPicker(LabelPickerAction::Apply).requires_token());
+        assert!(Action::ReviewerPicker(ReviewerPickerAction::Apply).requires_token());
+        assert!(!Action::ReviewerPicker(ReviewerPickerAction::Toggle).requires_token());
+        assert!(Action::ConflictHelper(ConflictHelperAction::RecreateDependabot).requires_token());
+        assert!(!Action::ConflictHelper(ConflictHelperAction::CopyCommands).requires_token());
+        assert!(!Action::PullRequest(PullRequestAction::Refresh).requires_token());
+        assert!(!Action::PullRequest(PullRequestAction::OpenBuildLogs).requires_token());
+        assert!(!Action::Global(GlobalAction::Quit).requires_token());
+    }
 }
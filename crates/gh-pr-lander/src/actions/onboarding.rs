@@ -0,0 +1,24 @@
+//! Onboarding actions
+//!
+//! Actions for the first-run onboarding wizard.
+
+use crate::domain_models::Repository;
+
+/// Actions for the onboarding wizard
+#[derive(Debug, Clone)]
+pub enum OnboardingAction {
+    /// Show the onboarding wizard (no repositories and no local session)
+    Show,
+    /// Repository detected from the current directory's git remote (None = not found)
+    RepositoryDetected(Option<Repository>),
+    /// Default config file handled (message shown in the wizard)
+    ConfigWritten(String),
+    /// Continue with the next step (Enter)
+    Next,
+    /// Add the detected repository and finish the wizard
+    AddDetectedRepository,
+    /// Drop into the add repository form and finish the wizard
+    AddRepositoryManually,
+    /// Skip the wizard
+    Skip,
+}
@@ -0,0 +1,16 @@
+//! Performance stats actions
+//!
+//! Actions for the popup showing the timing of GitHub API calls.
+
+use gh_client::ApiMetricsSnapshot;
+
+/// Actions for the performance stats popup
+#[derive(Debug, Clone)]
+pub enum PerfStatsAction {
+    /// Open the popup (the middleware takes a snapshot)
+    Open,
+    /// Take a new snapshot of the API metrics (handled by middleware)
+    Refresh,
+    /// A snapshot of the API metrics was taken
+    Loaded(Box<ApiMetricsSnapshot>),
+}
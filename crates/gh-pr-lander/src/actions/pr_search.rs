@@ -0,0 +1,44 @@
+//! PR search actions
+//!
+//! Server-side search of a repository's PRs (GitHub search syntax), shown in
+//! the PR table in place of the loaded list.
+
+use crate::domain_models::{Pr, Repository};
+
+/// Actions for the PR search mode
+#[derive(Debug, Clone)]
+pub enum PrSearchAction {
+    /// Open the search input for the current repository
+    OpenInput,
+    /// Character typed into the search input
+    InputChar(char),
+    /// Remove the last character of the search input
+    InputBackspace,
+    /// Clear the search input
+    InputClear,
+    /// Close the search input without searching
+    CloseInput,
+    /// Run the entered query
+    SubmitInput,
+    /// Start (or restart) a search for the current repository
+    Search { query: String },
+    /// A page of search results arrived
+    ResultsLoaded {
+        repo: Repository,
+        query: String,
+        page: u32,
+        total_count: u64,
+        incomplete: bool,
+        prs: Vec<Pr>,
+    },
+    /// The search failed (e.g. invalid query or search rate limit)
+    Failed { repo: Repository, error: String },
+    /// Fetch the next page of results
+    LoadMore,
+    /// Fetch full details of the search result under the cursor if needed
+    HydrateCursor,
+    /// Full details of a search result arrived
+    ResultHydrated { repo: Repository, pr: Box<Pr> },
+    /// Leave search mode and show the loaded PR list again
+    Exit,
+}
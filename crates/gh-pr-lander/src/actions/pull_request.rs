@@ -2,8 +2,13 @@
 //!
 //! Actions specific to the main PR view screen.
 
-use crate::domain_models::{MergeableStatus, Pr, Repository, ReviewDecision};
-use crate::state::PrFilter;
+use crate::domain_models::{
+    CiBudget, CiChecks, CommentCounts, MergeableStatus, OperationType, PolicyStep, Pr, PrReviewers,
+    PrTab, Repository, ReviewDecision, SnoozeDuration, WatchedPr,
+};
+use crate::state::{PrFilter, RepoPulse};
+use gh_pr_config::{FilterSpec, SnoozedPr};
+use std::collections::{BTreeMap, HashMap};
 
 /// Actions for the Pull Request screen
 #[derive(Debug, Clone)]
@@ -28,9 +33,22 @@ pub enum PullRequestAction {
     /// Start loading PRs for a repository
     LoadStart { repo: Repository },
     /// PRs loaded successfully for a repository
-    Loaded { repo: Repository, prs: Vec<Pr> },
-    /// Failed to load PRs for a repository
-    LoadError { repo: Repository, error: String },
+    ///
+    /// `from_cache` is when the PRs were cached for provisional results read
+    /// from the API cache at startup, which the fresh load replaces.
+    Loaded {
+        repo: Repository,
+        prs: Vec<Pr>,
+        from_cache: Option<chrono::DateTime<chrono::Local>>,
+    },
+    /// Failed to load PRs for a repository (`status` of GitHub API errors)
+    LoadError {
+        repo: Repository,
+        error: String,
+        status: Option<u16>,
+    },
+    /// PRs fetched in the background while the user was idle
+    Prefetched { repo: Repository, prs: Vec<Pr> },
 
     // Selection
     /// Toggle selection of the current PR (at cursor)
@@ -41,8 +59,8 @@ pub enum PullRequestAction {
     DeselectAll,
 
     // Operations
-    /// Open current PR in browser
-    OpenInBrowser,
+    /// Open a tab of the current PR (or the selected PRs) in browser
+    OpenInBrowser(PrTab),
     /// Open current PR diff in configured IDE
     OpenInIDE,
     /// Open CI build logs in browser
@@ -52,8 +70,28 @@ pub enum PullRequestAction {
     /// Refresh PRs for the current repository
     Refresh,
     // Merge operations
-    /// Request to merge selected PRs (or cursor PR if none selected)
+    /// Request to merge selected PRs (or cursor PR if none selected, asks first)
     MergeRequest,
+    /// Merge the given PRs of the current repository (confirmed merge)
+    MergePrs {
+        pr_numbers: Vec<u64>,
+        /// Delete the head branches after merging
+        delete_branch: bool,
+    },
+    /// Merge PRs of the given repository (confirmed cross-repo bulk merge)
+    MergeRepoPrs {
+        repo: Repository,
+        pr_numbers: Vec<u64>,
+    },
+    /// Request to merge all patch/minor bot PRs with green CI (asks first)
+    MergeBotUpdatesRequest,
+    /// Request a dry run of the configured policies (shows the report first)
+    RunPolicyRequest,
+    /// Approve and/or merge PRs of a repository, one after another
+    RunPolicy {
+        repo: Repository,
+        steps: Vec<PolicyStep>,
+    },
     /// Merge started for a PR
     MergeStart { repo: Repository, pr_number: usize },
 
@@ -62,6 +100,10 @@ pub enum PullRequestAction {
     RebaseRequest,
     /// Rebase started for a PR
     RebaseStart { repo: Repository, pr_number: usize },
+    /// Update the cursor PR and the PRs stacked on it, bottom-up
+    RebaseStackRequest,
+    /// List the files the cursor PR likely conflicts on
+    InspectConflicts,
 
     // Approve operations
     /// Request to approve selected PRs (shows confirmation popup)
@@ -108,30 +150,66 @@ pub enum PullRequestAction {
     CloseStart { repo: Repository, pr_number: usize },
 
     // CI/Build Status actions
-    /// Trigger a CI status check for a specific PR
+    /// Trigger a CI status check for a specific PR (`force_refresh` skips
+    /// cache reads, e.g. when derived from a forced refresh)
     CheckBuildStatus {
         repo: Repository,
         pr_number: u64,
         head_sha: String,
+        force_refresh: bool,
     },
     /// Update the build status of a specific PR after CI check completes
     BuildStatusUpdated {
         repo: Repository,
         pr_number: u64,
         status: MergeableStatus,
+        /// Progress of the running checks (None unless CI runs)
+        budget: Option<CiBudget>,
+        /// Check runs behind the status
+        checks: CiChecks,
     },
+    /// Update the CI state of the head of a repository's tracked branch
+    /// (`None` if the branch doesn't exist)
+    BranchStatusUpdated {
+        repo: Repository,
+        ci: Option<gh_client::CiState>,
+    },
+    /// Fetch the pulse of the selected repository if due (queued after a
+    /// tab switch, so the reducer selected the tab already)
+    FetchPulse,
+    /// PRs opened and merged in a repository over the last week
+    PulseUpdated { repo: Repository, pulse: RepoPulse },
     /// Update the review decision of a specific PR after fetching reviews
     ReviewDecisionUpdated {
         repo: Repository,
         pr_number: u64,
         decision: ReviewDecision,
     },
-    /// Update PR stats (additions/deletions) after fetching individual PR details
+    /// Reviewers of the open PRs of a repository, fetched for the attention sort
+    ReviewersLoaded {
+        repo: Repository,
+        reviewers: Vec<PrReviewers>,
+    },
+    /// Update PR stats (additions/deletions/changed files) after fetching
+    /// individual PR details
     StatsUpdated {
         repo: Repository,
         pr_number: u64,
         additions: usize,
         deletions: usize,
+        changed_files: usize,
+    },
+    /// Update the comment breakdown of a specific PR after fetching it
+    CommentCountsUpdated {
+        repo: Repository,
+        pr_number: u64,
+        counts: CommentCounts,
+    },
+    /// Replace the labels of a specific PR after editing them
+    LabelsUpdated {
+        repo: Repository,
+        pr_number: u64,
+        labels: Vec<String>,
     },
     /// Request to rerun failed jobs for the current PR
     RerunFailedJobs,
@@ -141,12 +219,72 @@ pub enum PullRequestAction {
         pr_number: u64,
         run_id: u64,
     },
+    /// A mutating operation on a PR ended (successfully or not)
+    OperationFinished {
+        repo: Repository,
+        pr_number: usize,
+        operation: OperationType,
+    },
 
     // Filters
     /// Cycle through filter presets
     CycleFilter,
+    /// Cycle the sort of the current repository (newest, attention)
+    CycleSort,
     /// Set a specific filter
     SetFilter(PrFilter),
+    /// Set the filter of a repository (e.g. its configured default filter)
+    SetRepoFilter { repo: Repository, filter: PrFilter },
+    /// Apply a saved filter by name
+    ApplySavedFilter(String),
+    /// Saved filters were loaded or changed
+    SavedFiltersUpdated(BTreeMap<String, FilterSpec>),
     /// Clear the current filter (show all PRs)
     ClearFilter,
+    /// Filter by the focused PR's author, or clear an author filter
+    ToggleAuthorFilter,
+    /// Cycle the PR state of the current repository (Open/Closed/Merged/All)
+    CycleStateFilter,
+    /// Watch the focused PR, or stop watching it
+    ToggleWatch,
+
+    // Local notes
+    /// Edit the local note of the focused PR
+    EditNote,
+    /// Save the note of a PR of the current repository (blank text removes it)
+    SaveNote { pr_number: u64, text: String },
+    /// Local notes were loaded or changed
+    NotesUpdated(HashMap<WatchedPr, String>),
+
+    // Peek
+    /// Show the focused PR's description in a popup, or close the popup
+    TogglePeek,
+    /// Close the peek popup
+    ClosePeek,
+    /// Description of a PR fetched for the peek popup, as of `updated_at`
+    PeekBodyLoaded {
+        pr: WatchedPr,
+        updated_at: chrono::DateTime<chrono::Utc>,
+        body: String,
+    },
+    /// Fetching the description of a PR for the peek popup failed
+    PeekBodyFailed { pr: WatchedPr, error: String },
+
+    // Snooze
+    /// Hide the selected PRs (or the focused PR) for a while
+    Snooze { duration: SnoozeDuration },
+    /// Wake the selected PRs (or the focused PR) up
+    Unsnooze,
+    /// Show snoozed PRs (dimmed) or hide them
+    ToggleShowSnoozed,
+    /// Snoozed PRs were loaded or changed
+    SnoozesUpdated(HashMap<WatchedPr, SnoozedPr>),
+
+    // Last seen
+    /// Remember the focused PR as seen in its current version
+    MarkSeen,
+    /// Remember all PRs of the current repository as seen
+    MarkAllSeen,
+    /// Last seen versions of PRs were loaded from the session
+    LastSeenLoaded(HashMap<WatchedPr, chrono::DateTime<chrono::Utc>>),
 }
@@ -0,0 +1,40 @@
+//! Release notes actions
+//!
+//! Actions for drafting release notes from the PRs merged since a tag or
+//! date, previewed in a popup.
+
+use crate::domain_models::{Repository, SinceRef};
+
+/// Actions for the release notes popup
+#[derive(Debug, Clone)]
+pub enum ReleaseNotesAction {
+    /// Ask where the notes start, the latest tag preset (handled by middleware)
+    Prepare,
+    /// Draft the notes of the PRs merged since `since` (the middleware fetches them)
+    Generate { repo: Repository, since: SinceRef },
+    /// The notes were drafted from `pr_count` merged PRs
+    Loaded {
+        repo: Repository,
+        since: SinceRef,
+        markdown: String,
+        pr_count: usize,
+    },
+    /// Fetching the merged PRs failed
+    LoadError {
+        repo: Repository,
+        since: SinceRef,
+        error: String,
+    },
+    /// Scroll down one line
+    NavigateNext,
+    /// Scroll up one line
+    NavigatePrevious,
+    /// Scroll to the first line
+    NavigateToTop,
+    /// Scroll to the last line
+    NavigateToBottom,
+    /// Copy the markdown to the clipboard (handled by middleware)
+    Copy,
+    /// Write the markdown to a file in the current directory (handled by middleware)
+    WriteFile,
+}
@@ -3,6 +3,8 @@
 //! Includes both repository operations and the add repository form.
 
 use crate::domain_models::Repository;
+use crate::state::ActiveProfile;
+use gh_client::HostCapabilities;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RepositoryAction {
@@ -13,12 +15,42 @@ pub enum RepositoryAction {
     /// Adds a new repository to the list
     AddRepository(Repository),
 
-    /// Remove the currently selected repository from the list
+    /// Ask to remove the currently selected repository from the list
     RemoveCurrentRepository,
 
+    /// Remove a repository from the list (confirmed) with its loaded data
+    RemoveRepository(Repository),
+
+    /// Add the repository removed last back at its position
+    UndoRemoveRepository,
+
     /// Load all repository related data (e.g., pull requests etc.)
     LoadRepositoryData(Repository),
 
+    /// Load the data of a tab that is not shown yet, in the background lane
+    PreloadRepositoryData(Repository),
+
+    /// Reload the pull requests of a repository, bypassing the cache
+    RefreshRepositoryData(Repository),
+
+    /// Show the PRs targeting another base branch in the tab of `repo`
+    SwitchBranch { repo: Repository, branch: String },
+
+    /// Login of the user authenticated for an account (`host` or `profile@host`)
+    AccountResolved { account: String, login: String },
+
+    /// GraphQL features supported by a host (e.g. "ghe.example.com")
+    HostCapabilitiesResolved {
+        host: String,
+        capabilities: HostCapabilities,
+    },
+
+    /// Replace the repositories with the ones of another workspace profile
+    SwitchProfile(String),
+
+    /// A workspace profile became active, the tabs of another one are dropped
+    ProfileActivated(ActiveProfile),
+
     // === Add Repository Form actions ===
     /// Move to next field (Tab)
     FormNextField,
@@ -0,0 +1,45 @@
+//! Reviewer picker actions
+//!
+//! Actions for the popup to request reviews on the PR under the cursor.
+
+use crate::domain_models::{Owner, Repository};
+
+/// Actions for the reviewer picker popup
+#[derive(Debug, Clone)]
+pub enum ReviewerPickerAction {
+    /// Open the picker for the PR under the cursor (middleware collects it)
+    Open,
+    /// Show the picker for a PR, its author cannot review it
+    Show {
+        repo: Repository,
+        pr_number: usize,
+        author: String,
+    },
+    /// Collaborators and the CODEOWNERS of the changed files were fetched
+    Loaded {
+        /// Owners of the changed files, most owned files first
+        suggested: Vec<(Owner, usize)>,
+        /// Logins of the repository's collaborators
+        collaborators: Vec<String>,
+    },
+    /// Collaborators could not be fetched
+    LoadFailed(String),
+    /// Character typed into the filter
+    InputChar(char),
+    /// Remove the last character of the filter
+    InputBackspace,
+    /// Clear the filter
+    InputClear,
+    /// Move the cursor to the next reviewer
+    NavigateNext,
+    /// Move the cursor to the previous reviewer
+    NavigatePrevious,
+    /// Toggle the reviewer under the cursor
+    Toggle,
+    /// Request the checked reviewers (handled by middleware)
+    Apply,
+    /// Request was submitted - close the popup
+    Applied,
+    /// Close the picker without requesting anyone
+    Close,
+}
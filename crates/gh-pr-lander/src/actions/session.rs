@@ -13,6 +13,8 @@ pub enum SessionAction {
         selected_repo: Option<(String, String, String, Option<String>)>,
         /// Selected PR number (not index)
         selected_pr_no: Option<usize>,
+        /// Active workspace profile
+        profile: Option<String>,
     },
 
     /// Request to restore session selection after repositories are loaded
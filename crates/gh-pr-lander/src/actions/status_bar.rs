@@ -2,7 +2,7 @@
 //!
 //! Actions for the status bar - user feedback for operations.
 
-use crate::state::StatusKind;
+use crate::state::{ApiUsage, OperationId, StatusKind};
 
 /// Actions for the status bar
 #[derive(Debug, Clone)]
@@ -15,6 +15,20 @@ pub enum StatusBarAction {
     },
     /// Clear all status messages
     Clear,
+    /// A long-running operation started (shown with its elapsed time)
+    OperationStarted {
+        id: OperationId,
+        label: String,
+        source: String,
+    },
+    /// A long-running operation finished (successfully or not)
+    OperationFinished { id: OperationId },
+    /// The GitHub API request rate was measured
+    ApiUsage(ApiUsage),
+    /// The status page reported an incident (None = operational again)
+    Incident(Option<String>),
+    /// The background worker stopped answering (false = it answers again)
+    WorkerUnresponsive(bool),
 }
 
 impl StatusBarAction {
@@ -27,6 +41,20 @@ impl StatusBarAction {
         }
     }
 
+    /// Create an operation started action
+    pub fn started(id: OperationId, label: impl Into<String>, source: impl Into<String>) -> Self {
+        Self::OperationStarted {
+            id,
+            label: label.into(),
+            source: source.into(),
+        }
+    }
+
+    /// Create an operation finished action
+    pub fn finished(id: OperationId) -> Self {
+        Self::OperationFinished { id }
+    }
+
     /// Create a success status action
     pub fn success(message: impl Into<String>, source: impl Into<String>) -> Self {
         Self::Push {
@@ -0,0 +1,35 @@
+//! Watch list actions
+//!
+//! Actions for PRs watched across repositories.
+
+use crate::domain_models::WatchedPr;
+use crate::state::WatchStatus;
+
+/// Actions for the watch list and its view
+#[derive(Debug, Clone)]
+pub enum WatchListAction {
+    /// Watch list restored from the session
+    Loaded(Vec<WatchedPr>),
+    /// Refresh the status of all watched PRs
+    Check,
+    /// Fresh status of a watched PR arrived
+    StatusFetched {
+        pr: WatchedPr,
+        status: WatchStatus,
+    },
+    /// Stop watching a PR
+    Remove(WatchedPr),
+    /// Open the watch list view
+    Open,
+
+    // Navigation (translated from NavigationAction)
+    NavigateNext,
+    NavigatePrevious,
+    NavigateToTop,
+    NavigateToBottom,
+
+    /// Open the selected PR in the browser
+    OpenSelected,
+    /// Stop watching the selected PR
+    RemoveSelected,
+}
@@ -0,0 +1,487 @@
+//! Audit log of the operations on GitHub
+//!
+//! Every mutating operation (merge, close, reviews, comments, rebase, label
+//! and reviewer changes, reruns) appends one JSON object per line to
+//! `audit.jsonl` in the data directory once it succeeded or failed:
+//!
+//! ```text
+//! {"ts":"2026-10-16T14:02:11.402+02:00","login":"alice","operation":"approve","repo":"acme/api","pr":123,"params":{"message":"LGTM"},"outcome":"success"}
+//! ```
+//!
+//! Entries are written by a dedicated thread fed through a channel, so
+//! recording never blocks a middleware or an async task. The file is rotated
+//! by size, `audit.1.jsonl` being the previous file. Parameters and errors
+//! pass through `redact_secrets`, and messages are truncated.
+
+use crate::bug_report::redact_secrets;
+use crate::domain_models::Repository;
+use chrono::{Local, SecondsFormat};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+
+/// Size in bytes after which a new file is started
+const MAX_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Number of rotated files kept next to the active one
+const KEEP_FILES: usize = 3;
+
+/// Characters of a parameter value kept in an entry
+const VALUE_LIMIT: usize = 200;
+
+/// Kind of operation on GitHub
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOperation {
+    Merge,
+    Close,
+    Approve,
+    Comment,
+    DeleteComment,
+    RequestChanges,
+    Rebase,
+    Labels,
+    Reviewers,
+    Rerun,
+}
+
+impl AuditOperation {
+    /// Human readable name
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Merge => "merge",
+            Self::Close => "close",
+            Self::Approve => "approve",
+            Self::Comment => "comment",
+            Self::DeleteComment => "delete comment",
+            Self::RequestChanges => "request changes",
+            Self::Rebase => "rebase",
+            Self::Labels => "labels",
+            Self::Reviewers => "reviewers",
+            Self::Rerun => "rerun",
+        }
+    }
+}
+
+/// Whether an operation went through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    Success,
+    Failure,
+}
+
+/// One line of the audit log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Local time the outcome was known (RFC 3339)
+    pub ts: String,
+    /// Login the operation ran as, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub login: Option<String>,
+    pub operation: AuditOperation,
+    /// Target repository ("org/repo")
+    pub repo: String,
+    /// GitHub host of the repository (None = github.com)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// Target PR
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pr: Option<usize>,
+    /// Parameters of the operation (message, merge method, labels, ...)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, String>,
+    pub outcome: AuditOutcome,
+    /// Why the operation failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AuditEntry {
+    /// Whether the entry is about an operation on `repo`
+    pub fn is_for(&self, repo: &Repository) -> bool {
+        self.repo == repo.display_name() && self.host == repo.host
+    }
+}
+
+/// An operation about to run, recorded once its outcome is known
+#[derive(Clone)]
+pub struct PendingAudit {
+    log: AuditLog,
+    entry: AuditEntry,
+}
+
+impl PendingAudit {
+    /// The operation on PR `pr_number`
+    pub fn pr(mut self, pr_number: usize) -> Self {
+        self.entry.pr = Some(pr_number);
+        self
+    }
+
+    /// Add a parameter, redacted and truncated
+    pub fn param(mut self, key: &str, value: impl fmt::Display) -> Self {
+        self.entry
+            .params
+            .insert(key.to_string(), sanitize(&value.to_string()));
+        self
+    }
+
+    /// Record that the operation went through
+    pub fn succeeded(self) {
+        self.record(AuditOutcome::Success, None);
+    }
+
+    /// Record that the operation failed with `error`
+    pub fn failed(self, error: impl fmt::Display) {
+        self.record(
+            AuditOutcome::Failure,
+            Some(sanitize(&format!("{:#}", error))),
+        );
+    }
+
+    /// Record the outcome of `result`
+    pub fn finish<T, E: fmt::Display>(self, result: &Result<T, E>) {
+        match result {
+            Ok(_) => self.succeeded(),
+            Err(e) => self.failed(e),
+        }
+    }
+
+    fn record(mut self, outcome: AuditOutcome, error: Option<String>) {
+        self.entry.ts = Local::now().to_rfc3339_opts(SecondsFormat::Millis, false);
+        self.entry.outcome = outcome;
+        self.entry.error = error;
+        self.log.record(self.entry);
+    }
+}
+
+/// Redact tokens and keep at most `VALUE_LIMIT` characters of a value
+fn sanitize(value: &str) -> String {
+    let value = redact_secrets(value);
+    if value.chars().count() <= VALUE_LIMIT {
+        return value;
+    }
+    let mut truncated: String = value.chars().take(VALUE_LIMIT).collect();
+    truncated.push('…');
+    truncated
+}
+
+enum Message {
+    Entry(Box<AuditEntry>),
+    /// Answered once every entry sent before is written
+    Sync(Sender<()>),
+}
+
+/// Handle of the audit log writer, cheap to clone into async tasks
+///
+/// A disabled log (demo mode, tests) drops every entry.
+#[derive(Clone, Default)]
+pub struct AuditLog {
+    messages: Option<Sender<Message>>,
+    /// Active file, read back by the viewer
+    path: Option<PathBuf>,
+}
+
+impl AuditLog {
+    /// Log to `audit.jsonl` in the data directory
+    pub fn open_default() -> Self {
+        match gh_pr_config::audit_log_path() {
+            Ok(path) => Self::open(path),
+            Err(e) => {
+                log::warn!("Audit log disabled, no data directory: {:#}", e);
+                Self::disabled()
+            }
+        }
+    }
+
+    /// Log to `path`, started on a dedicated writer thread
+    pub fn open(path: PathBuf) -> Self {
+        Self::open_with_limit(path, MAX_BYTES)
+    }
+
+    fn open_with_limit(path: PathBuf, max_bytes: u64) -> Self {
+        let (messages, rx) = mpsc::channel::<Message>();
+        let file_path = path.clone();
+        let spawned = thread::Builder::new()
+            .name("audit-log".to_string())
+            .spawn(move || {
+                let mut file = AuditFile::new(file_path, max_bytes);
+                for message in rx {
+                    match message {
+                        Message::Entry(entry) => {
+                            if let Err(e) = file.append(&entry) {
+                                log::error!("Failed to write audit log entry: {}", e);
+                            }
+                        }
+                        Message::Sync(done) => {
+                            let _ = done.send(());
+                        }
+                    }
+                }
+            });
+        match spawned {
+            Ok(_) => Self {
+                messages: Some(messages),
+                path: Some(path),
+            },
+            Err(e) => {
+                log::error!("Audit log disabled, cannot start its writer: {}", e);
+                Self::disabled()
+            }
+        }
+    }
+
+    /// Log dropping every entry
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Start the entry of an `operation` on `repo` run as `login`
+    pub fn begin(
+        &self,
+        operation: AuditOperation,
+        repo: &Repository,
+        login: Option<&str>,
+    ) -> PendingAudit {
+        PendingAudit {
+            log: self.clone(),
+            entry: AuditEntry {
+                ts: String::new(),
+                login: login.map(str::to_string),
+                operation,
+                repo: repo.display_name(),
+                host: repo.host.clone(),
+                pr: None,
+                params: BTreeMap::new(),
+                outcome: AuditOutcome::Success,
+                error: None,
+            },
+        }
+    }
+
+    /// Queue `entry` for the writer, returns at once
+    fn record(&self, entry: AuditEntry) {
+        if let Some(messages) = &self.messages {
+            if messages.send(Message::Entry(Box::new(entry))).is_err() {
+                log::warn!("Audit log writer stopped, entry dropped");
+            }
+        }
+    }
+
+    /// Wait until every entry recorded so far is written
+    pub fn sync(&self) {
+        if let Some(messages) = &self.messages {
+            let (done, rx) = mpsc::channel();
+            if messages.send(Message::Sync(done)).is_ok() {
+                let _ = rx.recv();
+            }
+        }
+    }
+
+    /// Every entry written so far, oldest first (none for a disabled log)
+    ///
+    /// Blocks until pending entries are written, call off the middleware thread.
+    pub fn entries(&self) -> Vec<AuditEntry> {
+        self.sync();
+        self.path.as_deref().map(read_entries).unwrap_or_default()
+    }
+}
+
+/// The active audit file, rotated by size
+struct AuditFile {
+    path: PathBuf,
+    max_bytes: u64,
+    /// Opened on the first entry
+    file: Option<File>,
+    written: u64,
+}
+
+impl AuditFile {
+    fn new(path: PathBuf, max_bytes: u64) -> Self {
+        Self {
+            path,
+            max_bytes,
+            file: None,
+            written: 0,
+        }
+    }
+
+    fn append(&mut self, entry: &AuditEntry) -> io::Result<()> {
+        let line = serde_json::to_string(entry)?;
+        if self.written >= self.max_bytes {
+            self.rotate()?;
+        }
+        let file = match self.file {
+            Some(ref mut file) => file,
+            None => {
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)?;
+                self.written = file.metadata()?.len();
+                self.file.insert(file)
+            }
+        };
+        // One write per line, so a crash never leaves half an entry behind
+        file.write_all(format!("{}\n", line).as_bytes())?;
+        self.written += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    /// Shift the rotated files by one and start a new active file
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file = None;
+        let _ = fs::remove_file(rotated_path(&self.path, KEEP_FILES));
+        for n in (1..KEEP_FILES).rev() {
+            let from = rotated_path(&self.path, n);
+            if from.exists() {
+                fs::rename(&from, rotated_path(&self.path, n + 1))?;
+            }
+        }
+        fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        self.written = 0;
+        Ok(())
+    }
+}
+
+/// Path of the `n`th rotated file (`audit.1.jsonl` next to `audit.jsonl`)
+fn rotated_path(path: &Path, n: usize) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{}.{}.{}", stem, n, ext.to_string_lossy()),
+        None => format!("{}.{}", stem, n),
+    };
+    path.with_file_name(name)
+}
+
+/// Every entry of the active and rotated files, oldest first
+///
+/// Lines that are not an entry (cut off by a crash) are skipped.
+fn read_entries(path: &Path) -> Vec<AuditEntry> {
+    let files = (1..=KEEP_FILES)
+        .rev()
+        .map(|n| rotated_path(path, n))
+        .chain(std::iter::once(path.to_path_buf()));
+
+    let mut entries = Vec::new();
+    for file in files {
+        let Ok(file) = File::open(&file) else {
+            continue;
+        };
+        entries.extend(
+            BufReader::new(file)
+                .lines()
+                .map_while(Result::ok)
+                .filter_map(|line| serde_json::from_str(&line).ok()),
+        );
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "gh-pr-lander-audit-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("audit.jsonl")
+    }
+
+    fn repo() -> Repository {
+        Repository::new("acme", "api", "main")
+    }
+
+    #[test]
+    fn test_one_json_line_per_operation() {
+        let path = temp_path("lines");
+        let log = AuditLog::open(path.clone());
+
+        log.begin(AuditOperation::Approve, &repo(), Some("alice"))
+            .pr(123)
+            .param("message", "LGTM")
+            .succeeded();
+        log.begin(AuditOperation::Merge, &repo(), Some("alice"))
+            .pr(124)
+            .finish(&Err::<(), _>(anyhow::anyhow!("not mergeable")));
+        log.sync();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+        for line in &lines {
+            serde_json::from_str::<serde_json::Value>(line).unwrap();
+        }
+        let value: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(value["operation"], "approve");
+        assert_eq!(value["login"], "alice");
+        assert_eq!(value["repo"], "acme/api");
+        assert_eq!(value["pr"], 123);
+        assert_eq!(value["params"]["message"], "LGTM");
+        assert_eq!(value["outcome"], "success");
+
+        let entries = read_entries(&path);
+        assert_eq!(entries[1].outcome, AuditOutcome::Failure);
+        assert_eq!(entries[1].error.as_deref(), Some("not mergeable"));
+        assert!(entries[1].is_for(&repo()));
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_tokens_are_redacted_and_messages_truncated() {
+        let path = temp_path("redact");
+        let log = AuditLog::open(path.clone());
+        log.begin(AuditOperation::Comment, &repo(), None)
+            .param("message", "token ghp_abcdefghijklmnop1234 leaked")
+            .param("body", "x".repeat(500))
+            .failed("Authorization: Bearer secret-value");
+        log.sync();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("ghp_abcdefghijklmnop1234"));
+        assert!(!content.contains("secret-value"));
+        let entry = &read_entries(&path)[0];
+        assert_eq!(entry.params["message"], "token [REDACTED] leaked");
+        assert_eq!(entry.params["body"].chars().count(), VALUE_LIMIT + 1);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_rotation_by_size_keeps_every_entry_readable() {
+        let path = temp_path("rotate");
+        let log = AuditLog::open_with_limit(path.clone(), 10);
+        for pr in 1..=6 {
+            log.begin(AuditOperation::Close, &repo(), None)
+                .pr(pr)
+                .succeeded();
+        }
+        log.sync();
+
+        // Each entry exceeds the limit: the active file and KEEP_FILES rotated ones
+        assert!(rotated_path(&path, KEEP_FILES).exists());
+        assert!(!rotated_path(&path, KEEP_FILES + 1).exists());
+        let prs: Vec<Option<usize>> = read_entries(&path).iter().map(|e| e.pr).collect();
+        assert_eq!(prs, vec![Some(3), Some(4), Some(5), Some(6)]);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_disabled_log_drops_entries() {
+        let log = AuditLog::disabled();
+        log.begin(AuditOperation::Rerun, &repo(), None).succeeded();
+        log.sync();
+    }
+}
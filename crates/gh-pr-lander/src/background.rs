@@ -7,19 +7,207 @@
 //!
 //! Actions dispatched by middleware via Dispatcher re-enter the middleware chain,
 //! enabling patterns like Event::ClientReady -> LoadRecentRepositories flow.
+//!
+//! A panicking middleware does not take the worker down: the panic is caught,
+//! reported in the status bar and the action goes on to the next middleware.
+//! The worker beats a [`Heartbeat`] on every round of its loop, so the main
+//! loop notices when input stays unanswered.
 
-use crate::actions::{Action, BootstrapAction, GlobalAction};
+use crate::actions::{Action, BootstrapAction, Event, GlobalAction, StatusBarAction};
 use crate::dispatcher::Dispatcher;
-use crate::middleware::Middleware;
+use crate::input_coalescing;
+use crate::middleware::MiddlewareChain;
 use crate::state::AppState;
-use std::sync::mpsc::{Receiver, Sender};
-use std::sync::{Arc, RwLock};
+use crate::utils::autosave::catching_panics;
+use gh_redux::HandlerPanic;
+use std::collections::VecDeque;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, RwLock};
 use std::thread;
 use std::time::{Duration, Instant};
 
 /// Shared state that background can read (main thread writes via reducer)
 pub type SharedState = Arc<RwLock<AppState>>;
 
+/// Interval of the periodic background refresh (e.g. the watch list)
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Interval at which changed local data (e.g. the session) is saved
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Time without key presses after which the user counts as idle
+const IDLE_DELAY: Duration = Duration::from_secs(5);
+
+/// Time without key presses after which the user paused (e.g. to read)
+const PAUSE_DELAY: Duration = Duration::from_secs(1);
+
+/// Caught panics after which a middleware is not run anymore
+const DISABLE_AFTER_PANICS: u32 = 3;
+
+/// Time input may stay unanswered before the worker counts as unresponsive
+const UNRESPONSIVE_AFTER: Duration = Duration::from_secs(10);
+
+/// When the background worker last went round its loop
+///
+/// Shared between the worker, which beats it, and the main loop, which
+/// checks it after sending input.
+#[derive(Debug, Clone)]
+pub struct Heartbeat {
+    last_beat: Arc<Mutex<Instant>>,
+}
+
+impl Default for Heartbeat {
+    fn default() -> Self {
+        Self {
+            last_beat: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+}
+
+impl Heartbeat {
+    fn beat(&self) {
+        if let Ok(mut last_beat) = self.last_beat.lock() {
+            *last_beat = Instant::now();
+        }
+    }
+
+    /// Whether input sent at `sent` is still unanswered after
+    /// [`UNRESPONSIVE_AFTER`] (the worker blocks or is gone)
+    pub fn unresponsive(&self, sent: Option<Instant>, now: Instant) -> bool {
+        let Some(sent) = sent else {
+            return false;
+        };
+        let beaten_since = self
+            .last_beat
+            .lock()
+            .map(|last_beat| *last_beat >= sent)
+            .unwrap_or(false);
+        !beaten_since && now.saturating_duration_since(sent) >= UNRESPONSIVE_AFTER
+    }
+}
+
+/// Runs actions through the middleware chain and forwards the ones it did
+/// not consume to the reducers
+///
+/// The background thread feeds it from the action channel and adds the
+/// timed events; tests step it directly, one action at a time.
+pub struct ActionProcessor {
+    middleware: MiddlewareChain,
+    /// Re-enters actions dispatched by middleware through the chain
+    dispatcher: Dispatcher,
+    result_tx: Sender<Action>,
+    /// Caught panics per middleware, in chain order
+    panics: Vec<u32>,
+}
+
+impl ActionProcessor {
+    pub fn new(
+        middleware: MiddlewareChain,
+        action_tx: Sender<Action>,
+        result_tx: Sender<Action>,
+    ) -> Self {
+        Self {
+            panics: vec![0; middleware.len()],
+            middleware,
+            dispatcher: Dispatcher::new(action_tx),
+            result_tx,
+        }
+    }
+
+    /// Insert `middleware` at `index` of the chain
+    #[cfg(test)]
+    pub fn insert_middleware(
+        &mut self,
+        index: usize,
+        middleware: Box<dyn crate::middleware::Middleware<Action, AppState> + Send>,
+    ) {
+        self.middleware.insert(index, middleware);
+        self.panics.insert(index, 0);
+    }
+
+    /// Run `action` through the middleware chain with the state snapshot
+    /// `state`, forwarding it to the reducers unless consumed
+    ///
+    /// Returns false when the reducers are gone (result channel closed).
+    pub fn process(&mut self, action: Action, state: &AppState) -> bool {
+        let should_forward = self.run_chain(&action, state);
+
+        // Events are NOT forwarded - they're only for middleware observation
+        // (forwarding would create an infinite loop via main loop re-routing)
+        !should_forward || matches!(action, Action::Event(_)) || self.result_tx.send(action).is_ok()
+    }
+
+    /// Let middleware save what it holds, then forward `Quit` to the reducers
+    fn shut_down(&mut self, action: Action, state: Option<&AppState>) {
+        if let Some(state) = state {
+            self.run_chain(&action, state);
+        }
+        if self.result_tx.send(action).is_err() {
+            log::error!("Failed to send quit action to main thread");
+        }
+    }
+
+    /// Dispatch an event through the middleware chain
+    fn dispatch(&self, action: Action) {
+        self.dispatcher.dispatch(action);
+    }
+
+    /// Run `action` through the middleware that is still enabled, reporting
+    /// the panics caught on the way
+    fn run_chain(&mut self, action: &Action, state: &AppState) -> bool {
+        let panics = &self.panics;
+        let (should_forward, caught) = catching_panics(|| {
+            gh_redux::run_chain_catching(
+                &mut self.middleware,
+                action,
+                state,
+                &self.dispatcher,
+                |index| panics[index] >= DISABLE_AFTER_PANICS,
+            )
+        });
+        for panic in caught {
+            self.report(panic, action);
+        }
+        should_forward
+    }
+
+    /// Log a caught panic and tell the user, disabling the middleware after
+    /// [`DISABLE_AFTER_PANICS`] of them
+    fn report(&mut self, panic: HandlerPanic, action: &Action) {
+        log::error!(
+            "Middleware {} panicked on {:?}: {}",
+            panic.name,
+            action,
+            panic.message
+        );
+        let name = short_name(panic.name);
+        self.panics[panic.index] += 1;
+        self.dispatch(Action::StatusBar(StatusBarAction::error(
+            format!("{} failed: {}", name, panic.message),
+            "Background",
+        )));
+        if self.panics[panic.index] == DISABLE_AFTER_PANICS {
+            log::error!("Disabling middleware {} after repeated panics", panic.name);
+            self.dispatch(Action::StatusBar(StatusBarAction::warning(
+                format!(
+                    "{} disabled after {} failures, restart to enable it again",
+                    name, DISABLE_AFTER_PANICS
+                ),
+                "Background",
+            )));
+        }
+        self.dispatch(Action::event(Event::MiddlewarePanicked {
+            name: panic.name,
+        }));
+    }
+}
+
+/// Type name of a middleware without its module path and type parameters
+fn short_name(name: &str) -> &str {
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
 /// Spawn the background worker thread
 ///
 /// - `action_rx`: receives actions from main thread and from Dispatcher (re-entry)
@@ -27,6 +215,7 @@ pub type SharedState = Arc<RwLock<AppState>>;
 /// - `result_tx`: sends actions to main thread for reducers (non-consumed actions)
 /// - `state`: shared state for middleware to read
 /// - `middleware`: the middleware chain
+/// - `heartbeat`: beaten on every round of the worker loop
 ///
 /// Returns a handle that can be used for graceful shutdown
 pub fn spawn_background_worker(
@@ -34,10 +223,13 @@ pub fn spawn_background_worker(
     action_tx: Sender<Action>,
     result_tx: Sender<Action>,
     state: SharedState,
-    middleware: Vec<Box<dyn Middleware + Send>>,
+    middleware: MiddlewareChain,
+    heartbeat: Heartbeat,
 ) -> thread::JoinHandle<()> {
     thread::spawn(move || {
-        background_loop(action_rx, action_tx, result_tx, state, middleware);
+        background_loop(
+            action_rx, action_tx, result_tx, state, middleware, heartbeat,
+        );
     })
 }
 
@@ -46,72 +238,94 @@ fn background_loop(
     action_tx: Sender<Action>,
     result_tx: Sender<Action>,
     state: SharedState,
-    mut middleware: Vec<Box<dyn Middleware + Send>>,
+    middleware: MiddlewareChain,
+    heartbeat: Heartbeat,
 ) {
     log::info!("Background worker started");
 
-    // Create dispatcher that re-enters actions through the middleware chain
-    let dispatcher = Dispatcher::new(action_tx);
+    let mut processor = ActionProcessor::new(middleware, action_tx, result_tx.clone());
 
     // Tick generation for splash animation
     let mut last_tick = Instant::now();
     let tick_rate = Duration::from_millis(150);
     let mut bootstrapping = true;
 
+    // Periodic refresh and autosave, starting once bootstrap ended
+    let mut last_refresh = Instant::now();
+    let mut last_autosave = Instant::now();
+
+    // Idle detection, one event per idle period
+    let mut last_input = Instant::now();
+    let mut idle_reported = false;
+    // Pauses only count after some input
+    let mut pause_reported = true;
+
+    // Received but not yet processed actions, so queued navigation can be merged
+    let mut queue: VecDeque<Action> = VecDeque::new();
+
     loop {
-        // Use recv_timeout to allow tick generation
-        match action_rx.recv_timeout(Duration::from_millis(10)) {
-            Ok(action) => {
-                // Check for shutdown signal
-                if matches!(action, Action::Global(GlobalAction::Quit)) {
-                    log::info!("Background worker received shutdown signal");
-                    if result_tx.send(action).is_err() {
-                        log::error!("Failed to send quit action to main thread");
-                    }
-                    break;
-                }
+        heartbeat.beat();
 
-                // Track bootstrap state for tick generation
-                if matches!(action, Action::Bootstrap(BootstrapAction::End)) {
-                    bootstrapping = false;
-                    log::debug!("Bootstrap ended, stopping tick generation");
-                }
+        // Use recv_timeout to allow tick generation
+        let received = if queue.is_empty() {
+            action_rx.recv_timeout(Duration::from_millis(10))
+        } else {
+            Err(RecvTimeoutError::Timeout)
+        };
+        match received {
+            Ok(action) => queue.push_back(action),
+            Err(RecvTimeoutError::Timeout) => {
+                // No action received, continue with the queue and the tick check
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                log::info!("Action channel disconnected, shutting down");
+                break;
+            }
+        }
+        queue.extend(action_rx.try_iter());
 
-                // Get current state snapshot for middleware
-                let current_state = match state.read() {
-                    Ok(s) => s.clone(),
-                    Err(e) => {
-                        log::error!("Failed to read shared state: {}", e);
-                        continue;
-                    }
-                };
-
-                // Run action through middleware chain
-                let mut should_forward = true;
-                for mw in &mut middleware {
-                    let continue_chain = mw.handle(&action, &current_state, &dispatcher);
-                    if !continue_chain {
-                        should_forward = false;
-                        break;
-                    }
-                }
+        if let Some(action) = input_coalescing::next_coalesced(&mut queue) {
+            // Check for shutdown signal
+            if matches!(action, Action::Global(GlobalAction::Quit)) {
+                log::info!("Background worker received shutdown signal");
+                // Middleware saves what it holds before the app exits
+                let current_state = state.read().map(|s| s.clone()).ok();
+                processor.shut_down(action, current_state.as_ref());
+                break;
+            }
 
-                // If middleware didn't consume the action, forward to reducer
-                // Note: Events are NOT forwarded - they're only for middleware observation
-                // (forwarding would create an infinite loop via main loop re-routing)
-                if should_forward
-                    && !matches!(action, Action::Event(_))
-                    && result_tx.send(action).is_err()
-                {
-                    log::error!("Result channel disconnected, shutting down");
-                    break;
-                }
+            if matches!(
+                action,
+                Action::Global(
+                    GlobalAction::KeyPressed(_)
+                        | GlobalAction::KeyRepeated { .. }
+                        | GlobalAction::Paste(_)
+                )
+            ) {
+                last_input = Instant::now();
+                idle_reported = false;
+                pause_reported = false;
             }
-            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
-                // No action received, continue to tick check
+
+            // Track bootstrap state for tick generation
+            if matches!(action, Action::Bootstrap(BootstrapAction::End)) {
+                bootstrapping = false;
+                log::debug!("Bootstrap ended, stopping tick generation");
             }
-            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
-                log::info!("Action channel disconnected, shutting down");
+
+            // Get current state snapshot for middleware
+            let current_state = match state.read() {
+                Ok(s) => s.clone(),
+                Err(e) => {
+                    log::error!("Failed to read shared state: {}", e);
+                    continue;
+                }
+            };
+
+            // Run action through middleware chain, forward it to the reducer
+            // if middleware didn't consume it
+            if !processor.process(action, &current_state) {
+                log::error!("Result channel disconnected, shutting down");
                 break;
             }
         }
@@ -124,7 +338,153 @@ fn background_loop(
             }
             last_tick = Instant::now();
         }
+
+        // Emit refresh event through the middleware chain
+        if !bootstrapping && last_refresh.elapsed() >= REFRESH_INTERVAL {
+            processor.dispatch(Action::event(Event::RefreshDue));
+            last_refresh = Instant::now();
+        }
+
+        // Emit autosave event so middleware can save changed data
+        if !bootstrapping && last_autosave.elapsed() >= AUTOSAVE_INTERVAL {
+            processor.dispatch(Action::event(Event::AutosaveDue));
+            last_autosave = Instant::now();
+        }
+
+        // Emit pause event so middleware can react to the cursor resting
+        if !bootstrapping && !pause_reported && last_input.elapsed() >= PAUSE_DELAY {
+            processor.dispatch(Action::event(Event::InputPaused));
+            pause_reported = true;
+        }
+
+        // Emit idle event so middleware can do optional work (e.g. prefetching)
+        if !bootstrapping && !idle_reported && last_input.elapsed() >= IDLE_DELAY {
+            processor.dispatch(Action::event(Event::UserIdle));
+            idle_reported = true;
+        }
     }
 
     log::info!("Background worker stopped");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::{logging_middleware, Middleware};
+    use std::sync::mpsc;
+
+    /// Consumes `Close` and re-dispatches it as a status message
+    struct Translate;
+
+    impl Middleware<Action, AppState> for Translate {
+        fn handle(&mut self, action: &Action, _state: &AppState, dispatcher: &Dispatcher) -> bool {
+            if matches!(action, Action::Global(GlobalAction::Close)) {
+                dispatcher.dispatch(Action::StatusBar(StatusBarAction::info("cleared", "test")));
+                return false;
+            }
+            true
+        }
+    }
+
+    /// Panics on `Close`
+    struct Faulty;
+
+    impl Middleware<Action, AppState> for Faulty {
+        fn handle(&mut self, action: &Action, _state: &AppState, _dispatcher: &Dispatcher) -> bool {
+            if matches!(action, Action::Global(GlobalAction::Close)) {
+                panic!("broken");
+            }
+            true
+        }
+    }
+
+    #[test]
+    fn test_panicking_middleware_is_reported_then_disabled() {
+        let (action_tx, action_rx) = mpsc::channel();
+        let (result_tx, result_rx) = mpsc::channel();
+        let mut processor = ActionProcessor::new(
+            vec![Box::new(Faulty), Box::new(Translate)],
+            action_tx,
+            result_tx,
+        );
+        let state = AppState::default();
+
+        for _ in 0..=DISABLE_AFTER_PANICS {
+            assert!(processor.process(Action::Global(GlobalAction::Close), &state));
+        }
+
+        let dispatched: Vec<Action> = action_rx.try_iter().collect();
+        let messages: Vec<&str> = dispatched
+            .iter()
+            .filter_map(|action| match action {
+                Action::StatusBar(StatusBarAction::Push { message, .. }) => Some(message.as_str()),
+                _ => None,
+            })
+            .collect();
+        // The next middleware still handled every action
+        assert_eq!(messages.iter().filter(|m| **m == "cleared").count(), 4);
+        assert_eq!(
+            messages
+                .iter()
+                .filter(|m| **m == "Faulty failed: broken")
+                .count(),
+            3
+        );
+        assert!(messages.contains(&"Faulty disabled after 3 failures, restart to enable it again"));
+        let panicked = dispatched
+            .iter()
+            .filter(|action| {
+                matches!(action, Action::Event(Event::MiddlewarePanicked { name }) if name.ends_with("::Faulty"))
+            })
+            .count();
+        assert_eq!(panicked, 3);
+        assert!(result_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_heartbeat_notices_unanswered_input() {
+        let heartbeat = Heartbeat::default();
+        thread::sleep(Duration::from_millis(1));
+        let sent = Instant::now();
+
+        assert!(!heartbeat.unresponsive(None, sent + UNRESPONSIVE_AFTER));
+        assert!(!heartbeat.unresponsive(Some(sent), sent + Duration::from_secs(1)));
+        assert!(heartbeat.unresponsive(Some(sent), sent + UNRESPONSIVE_AFTER));
+
+        heartbeat.beat();
+        assert!(!heartbeat.unresponsive(Some(sent), sent + UNRESPONSIVE_AFTER));
+    }
+
+    #[test]
+    fn test_dispatch_loop_forwards_and_reenters() {
+        let (action_tx, action_rx) = mpsc::channel();
+        let (result_tx, result_rx) = mpsc::channel();
+        let handle = spawn_background_worker(
+            action_rx,
+            action_tx.clone(),
+            result_tx,
+            SharedState::default(),
+            vec![Box::new(logging_middleware()), Box::new(Translate)],
+            Heartbeat::default(),
+        );
+
+        let next_forwarded = || {
+            result_rx
+                .iter()
+                .find(|action| !matches!(action, Action::Global(GlobalAction::Tick)))
+        };
+
+        action_tx.send(Action::Global(GlobalAction::Close)).unwrap();
+        assert!(matches!(
+            next_forwarded(),
+            Some(Action::StatusBar(StatusBarAction::Push { .. }))
+        ));
+
+        action_tx.send(Action::Global(GlobalAction::Quit)).unwrap();
+        assert!(matches!(
+            next_forwarded(),
+            Some(Action::Global(GlobalAction::Quit))
+        ));
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,102 @@
+//! Blame for the diff viewer
+//!
+//! Answers the diff viewer's blame requests from GitHub's GraphQL blame,
+//! which the cached client keeps per commit and file.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use gh_client::{BlameRange, GitHubClient};
+use gh_diff_viewer::{BlameEntry, BlameError, BlameProvider};
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+/// Blame of a repository's files, fetched through its GitHub client
+pub struct GitHubBlameProvider {
+    client: Arc<dyn GitHubClient>,
+    owner: String,
+    repo: String,
+}
+
+impl GitHubBlameProvider {
+    pub fn new(client: Arc<dyn GitHubClient>, owner: &str, repo: &str) -> Self {
+        Self {
+            client,
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl BlameProvider for GitHubBlameProvider {
+    async fn fetch_blame(
+        &self,
+        path: &str,
+        commit_sha: &str,
+        lines: RangeInclusive<u32>,
+    ) -> Result<Vec<BlameEntry>, BlameError> {
+        let ranges = self
+            .client
+            .fetch_blame(&self.owner, &self.repo, commit_sha, path)
+            .await
+            .map_err(|e| BlameError::NetworkError(format!("{:#}", e)))?;
+        Ok(blame_entries(&ranges, lines, Utc::now()))
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+}
+
+/// One entry per line of `lines` covered by the blame ranges
+fn blame_entries(
+    ranges: &[BlameRange],
+    lines: RangeInclusive<u32>,
+    now: DateTime<Utc>,
+) -> Vec<BlameEntry> {
+    ranges
+        .iter()
+        .flat_map(|range| {
+            let start = range.start_line.max(*lines.start());
+            let end = range.end_line.min(*lines.end());
+            let age = (now - range.committed_at).to_std().unwrap_or_default();
+            (start..=end).map(move |line| BlameEntry {
+                line,
+                author: range.author.clone(),
+                commit: range.commit_sha.clone(),
+                age,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn range(start_line: u32, end_line: u32, author: &str) -> BlameRange {
+        BlameRange {
+            start_line,
+            end_line,
+            commit_sha: "abc123".to_string(),
+            author: author.to_string(),
+            committed_at: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_entries_cover_the_requested_lines_only() {
+        let now = Utc.with_ymd_and_hms(2025, 1, 4, 0, 0, 0).unwrap();
+        let ranges = [range(1, 3, "jdoe"), range(4, 20, "octocat")];
+
+        let entries = blame_entries(&ranges, 3..=5, now);
+
+        let lines: Vec<(u32, &str)> = entries
+            .iter()
+            .map(|entry| (entry.line, entry.author.as_str()))
+            .collect();
+        assert_eq!(lines, vec![(3, "jdoe"), (4, "octocat"), (5, "octocat")]);
+        assert_eq!(entries[0].short_age(), "3d");
+    }
+}
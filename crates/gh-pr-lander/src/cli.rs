@@ -0,0 +1,237 @@
+//! Command line arguments
+//!
+//! Without arguments the app starts normally against GitHub, `--profile`
+//! picks the workspace profile to start in. `--demo` and
+//! `--bench` run on generated fixtures (see `demo`), `--version` prints the
+//! version and exits. `watch` runs without a UI (see `headless`).
+
+use crate::demo::DemoOptions;
+use crate::headless::WatchOptions;
+
+/// Command line usage
+pub const USAGE: &str = "\
+Usage: gh-pr-lander [--profile NAME]
+       gh-pr-lander [--demo | --bench] [--seed N] [--repos N] [--prs N] [--diff-lines N] [--log-lines N]
+       gh-pr-lander watch [--once] [--json] [--interval SECS] [--policy NAME] [--requests-per-minute N]
+       gh-pr-lander --version [--verbose]
+
+  --profile NAME  Start in the named workspace profile (created if new)
+  --demo          Run with generated data, without network access
+  --bench         Time view-model, diff, log and reducer hot paths and exit
+                  (run a release build for meaningful numbers)
+  --seed N        Seed of the generated data (default 42)
+  --repos N       Number of generated repositories (default 5)
+  --prs N         Number of PRs per repository (default 40, 5000 in --bench)
+  --diff-lines N  Lines of the generated diff (default 50000)
+  --log-lines N   Lines of the generated build log (default 100000)
+  --version       Print the version and exit
+  --verbose       With --version, also print OS, terminal and path details
+
+  watch           Print PR events (opened, CI settled, policy matched, closed)
+                  of the session's repositories instead of showing the UI
+  --once          Poll once, wait for the results and exit (for cron)
+  --json          Print one JSON object per line
+  --interval SECS Seconds between polls (default 300)
+  --policy NAME   Approve/merge the PRs the named policy matches
+  --requests-per-minute N  Cap of API requests per minute and host (default 30)";
+
+/// How the app was started
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunMode {
+    /// Normal operation against GitHub, in the named workspace profile
+    Normal { profile: Option<String> },
+    /// Generated data, no network
+    Demo(DemoOptions),
+    /// Run the benchmarks and exit
+    Bench(DemoOptions),
+    /// Print the version (and the environment when verbose) and exit
+    Version { verbose: bool },
+    /// Poll without a UI and print events
+    Watch(WatchOptions),
+}
+
+impl RunMode {
+    /// Parse the command line arguments (without the program name)
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut args = args.into_iter().peekable();
+        if args.peek().is_some_and(|arg| arg == "watch") {
+            args.next();
+            return Self::watch_from_args(args);
+        }
+
+        let mut demo = false;
+        let mut bench = false;
+        let mut version = false;
+        let mut verbose = false;
+        let mut options = DemoOptions::default();
+        let mut prs_per_repo = None;
+        let mut fixture_options = false;
+        let mut profile = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--demo" => demo = true,
+                "--bench" => bench = true,
+                "--version" | "-V" => version = true,
+                "--verbose" | "-v" => verbose = true,
+                "--profile" => profile = Some(parse_value::<String>(&arg, args.next())?),
+                "--seed" => options.seed = parse_value(&arg, args.next())?,
+                "--repos" => options.repos = parse_value(&arg, args.next())?,
+                "--prs" => prs_per_repo = Some(parse_value(&arg, args.next())?),
+                "--diff-lines" => options.diff_lines = parse_value(&arg, args.next())?,
+                "--log-lines" => options.log_lines = parse_value(&arg, args.next())?,
+                other => return Err(format!("Unknown argument: {}", other)),
+            }
+            fixture_options |= arg.starts_with("--")
+                && !matches!(
+                    arg.as_str(),
+                    "--demo" | "--bench" | "--version" | "--verbose" | "--profile"
+                );
+        }
+        if profile.is_some() && (demo || bench || version) {
+            return Err("--profile cannot be combined with other options".to_string());
+        }
+
+        if version {
+            if demo || bench || fixture_options {
+                return Err("--version cannot be combined with other options".to_string());
+            }
+            return Ok(RunMode::Version { verbose });
+        }
+        if verbose {
+            return Err("--verbose requires --version".to_string());
+        }
+        if demo && bench {
+            return Err("--demo and --bench cannot be combined".to_string());
+        }
+        if bench {
+            options.prs_per_repo = prs_per_repo.unwrap_or(5_000);
+            Ok(RunMode::Bench(options))
+        } else if demo {
+            options.prs_per_repo = prs_per_repo.unwrap_or(options.prs_per_repo);
+            Ok(RunMode::Demo(options))
+        } else if fixture_options {
+            Err("Fixture options require --demo or --bench".to_string())
+        } else {
+            Ok(RunMode::Normal { profile })
+        }
+    }
+
+    /// Parse the arguments following `watch`
+    fn watch_from_args(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut options = WatchOptions::default();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--once" => options.once = true,
+                "--json" => options.json = true,
+                "--interval" => options.interval_secs = parse_value(&arg, args.next())?,
+                "--policy" => options.policy = Some(parse_value(&arg, args.next())?),
+                "--requests-per-minute" => {
+                    options.requests_per_minute = parse_value(&arg, args.next())?
+                }
+                other => return Err(format!("Unknown watch argument: {}", other)),
+            }
+        }
+        if options.interval_secs == 0 {
+            return Err("--interval must be at least 1".to_string());
+        }
+        if options.requests_per_minute == 0 {
+            return Err("--requests-per-minute must be at least 1".to_string());
+        }
+        Ok(RunMode::Watch(options))
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("{} requires a value", flag))?;
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value for {}: {}", flag, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<RunMode, String> {
+        RunMode::from_args(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_run_mode_from_args() {
+        assert_eq!(parse(&[]), Ok(RunMode::Normal { profile: None }));
+        assert_eq!(
+            parse(&["--demo"]),
+            Ok(RunMode::Demo(DemoOptions::default()))
+        );
+
+        let Ok(RunMode::Bench(options)) = parse(&["--bench", "--seed", "7"]) else {
+            panic!("expected bench mode");
+        };
+        assert_eq!(options.seed, 7);
+        assert_eq!(options.prs_per_repo, 5_000);
+
+        assert!(parse(&["--demo", "--bench"]).is_err());
+        assert!(parse(&["--prs", "10"]).is_err());
+        assert!(parse(&["--seed", "1"]).is_err());
+        assert!(parse(&["--demo", "--seed"]).is_err());
+        assert!(parse(&["--demo", "--seed", "x"]).is_err());
+    }
+
+    #[test]
+    fn test_profile_from_args() {
+        assert_eq!(
+            parse(&["--profile", "work"]),
+            Ok(RunMode::Normal {
+                profile: Some("work".to_string())
+            })
+        );
+        assert!(parse(&["--profile"]).is_err());
+        assert!(parse(&["--profile", "work", "--demo"]).is_err());
+        assert!(parse(&["--profile", "work", "--seed", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_version_from_args() {
+        assert_eq!(
+            parse(&["--version"]),
+            Ok(RunMode::Version { verbose: false })
+        );
+        assert_eq!(
+            parse(&["--version", "--verbose"]),
+            Ok(RunMode::Version { verbose: true })
+        );
+        assert_eq!(parse(&["-v", "-V"]), Ok(RunMode::Version { verbose: true }));
+        assert!(parse(&["--verbose"]).is_err());
+        assert!(parse(&["--version", "--demo"]).is_err());
+        assert!(parse(&["--version", "--seed", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_watch_from_args() {
+        assert_eq!(
+            parse(&["watch"]),
+            Ok(RunMode::Watch(WatchOptions::default()))
+        );
+
+        let Ok(RunMode::Watch(options)) = parse(&[
+            "watch",
+            "--once",
+            "--json",
+            "--interval",
+            "60",
+            "--policy",
+            "auto-bot",
+        ]) else {
+            panic!("expected watch mode");
+        };
+        assert!(options.once && options.json);
+        assert_eq!(options.interval_secs, 60);
+        assert_eq!(options.policy.as_deref(), Some("auto-bot"));
+
+        assert!(parse(&["watch", "--demo"]).is_err());
+        assert!(parse(&["watch", "--interval", "0"]).is_err());
+        assert!(parse(&["watch", "--policy"]).is_err());
+        assert!(parse(&["--demo", "watch"]).is_err());
+    }
+}
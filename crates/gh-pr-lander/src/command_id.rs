@@ -6,7 +6,10 @@
 use serde::{Deserialize, Serialize};
 use strum::EnumIter;
 
-use crate::{actions::RepositoryAction, views::KeyBindingsView};
+use crate::{
+    actions::RepositoryAction,
+    views::{KeyBindingsView, StatusMessageView},
+};
 
 /// Unique identifier for each command in the application.
 ///
@@ -33,8 +36,14 @@ pub enum CommandId {
     RepositoryAdd,
     /// Remove the current repository from the list
     RepositoryRemove,
+    /// Add the repository removed last back to the list
+    RepositoryUndoRemove,
     /// Open the current repository in the browser
     RepositoryOpenInBrowser,
+    /// Pick the base branch whose PRs the current repository tab shows
+    RepositorySwitchBranch,
+    /// Show the repositories of another workspace profile
+    RepositorySwitchProfile,
     /// Switch to the next repository
     RepositoryNext,
     /// Switch to the previous repository
@@ -59,6 +68,16 @@ pub enum CommandId {
     DebugToggleConsoleView,
     /// Clear the debug console logs
     DebugClearLogs,
+    /// Show GitHub API request metrics
+    DebugShowApiUsage,
+    /// Change the log level filter without restarting
+    DebugSetLogLevel,
+    /// Show call counts and latencies of the GitHub API endpoints
+    DebugShowPerfStats,
+    /// List the cached GitHub API responses
+    DebugShowCacheInspector,
+    /// Write a zip with environment, state summary, config and logs
+    DebugCreateBugReport,
 
     // === Command palette ===
     /// Open the command palette
@@ -77,10 +96,20 @@ pub enum CommandId {
     // === PR Operations ===
     /// Open current PR in browser
     PrOpenInBrowser,
+    /// Open the changed files of the current PR in browser
+    PrOpenFilesInBrowser,
+    /// Open the checks of the current PR (or the focused failed job) in browser
+    PrOpenChecksInBrowser,
+    /// Open the commits of the current PR in browser
+    PrOpenCommitsInBrowser,
     /// Merge selected PRs
     PrMerge,
     /// Rebase/update selected PRs
     PrRebase,
+    /// Update the focused PR and all PRs stacked on it
+    PrRebaseStack,
+    /// List the files the focused PR likely conflicts on
+    PrInspectConflicts,
     /// Approve selected PRs (shows confirmation popup)
     PrApprove,
     /// Comment on selected PRs (shows confirmation popup)
@@ -89,6 +118,34 @@ pub enum CommandId {
     PrRequestChanges,
     /// Close selected PRs without merging (shows confirmation popup)
     PrClose,
+    /// Add and remove labels on selected PRs (shows label picker)
+    PrEditLabels,
+    /// Request reviews on the current PR (shows reviewer picker)
+    PrRequestReviewers,
+    /// Edit the local note of the current PR (never sent to GitHub)
+    PrEditNote,
+    /// Show the focused PR's description in a popup (dismissed by navigating)
+    PrPeek,
+    /// Snooze selected PRs until they are updated
+    PrSnooze,
+    /// Snooze selected PRs for one day
+    PrSnoozeDay,
+    /// Snooze selected PRs for three days
+    PrSnoozeThreeDays,
+    /// Snooze selected PRs for one week
+    PrSnoozeWeek,
+    /// Wake selected snoozed PRs up
+    PrUnsnooze,
+    /// Show or hide snoozed PRs
+    PrToggleShowSnoozed,
+    /// Mark all PRs of the current repository as seen
+    PrMarkAllSeen,
+    /// Merge all patch/minor bot PRs with green CI (shows confirmation popup)
+    PrMergeBotUpdates,
+    /// Show bot PRs of all repositories grouped by dependency
+    DependencyUpdatesOpen,
+    /// Dry-run the configured policies, approve/merge matches on confirmation
+    PrRunPolicy,
 
     // === CI/Build Status ===
     /// Rerun failed CI jobs for current PR
@@ -100,11 +157,45 @@ pub enum CommandId {
     /// Open current PR in configured IDE
     PrOpenInIDE,
 
+    // === Audit Log ===
+    /// List the operations run on GitHub for the current PR or repository
+    PrShowAuditLog,
+    /// Switch the audit log between the PR and its repository
+    AuditLogToggleScope,
+
+    // === Duplicate Updates ===
+    /// List the PRs opening the same bot update in other repositories
+    PrShowDuplicateUpdates,
+    /// Pick or unpick the PR under the cursor for merging
+    DuplicateUpdatesToggle,
+    /// Pick every PR for merging
+    DuplicateUpdatesPickAll,
+    /// Unpick every PR
+    DuplicateUpdatesPickNone,
+    /// Merge the picked PRs in their repositories
+    DuplicateUpdatesMerge,
+
     // === Filter & Search ===
     /// Cycle through filter presets
     PrCycleFilter,
     /// Clear the current filter
     PrClearFilter,
+    /// Show only PRs by the focused PR's author, or clear that filter
+    PrFilterByAuthor,
+    /// Assemble a filter from criteria, optionally saving it
+    PrFilterBuilder,
+    /// Cycle the PR state (Open/Closed/Merged/All)
+    PrCycleStateFilter,
+    /// Cycle the PR sort (newest, attention)
+    PrCycleSort,
+    /// Search the repository's PRs on GitHub
+    PrSearch,
+
+    // === Watch List ===
+    /// Watch the focused PR, or stop watching it
+    PrToggleWatch,
+    /// Show the watched PRs of all repositories
+    WatchListOpen,
 
     // === Merge Bot ===
     /// Start merge bot for selected PRs
@@ -117,10 +208,14 @@ pub enum CommandId {
     // === Help ===
     /// Toggle key bindings help panel
     KeyBindingsToggleView,
+    /// Show the full latest status bar message
+    StatusBarShowMessage,
 
     // === Build Log ===
     /// Open build logs viewer for current PR
     BuildLogOpen,
+    /// Show recent workflow runs on the current repository's branch
+    ActionsViewOpen,
     /// Navigate to next error in build logs
     BuildLogNextError,
     /// Navigate to previous error in build logs
@@ -129,10 +224,38 @@ pub enum CommandId {
     BuildLogToggle,
     /// Toggle timestamps in build logs
     BuildLogToggleTimestamps,
+    /// Toggle listing successful jobs in build logs
+    BuildLogToggleShowSuccessful,
     /// Expand all nodes in build logs
     BuildLogExpandAll,
     /// Collapse all nodes in build logs
     BuildLogCollapseAll,
+    /// Write the build logs to a file
+    BuildLogExport,
+
+    // === Cache Inspector ===
+    /// Delete the cached response under the cursor
+    CacheInspectorDelete,
+    /// Invalidate the cached responses whose key contains a typed pattern
+    CacheInspectorInvalidatePattern,
+    /// Filter the cached responses
+    CacheInspectorFilter,
+    /// Sort the cached responses by size or age
+    CacheInspectorCycleSort,
+
+    // === Conflict Helper ===
+    /// Copy the commands resolving the conflicts locally
+    ConflictHelperCopyCommands,
+    /// Ask dependabot to recreate the PR
+    ConflictHelperRecreate,
+
+    // === Release Notes ===
+    /// Draft release notes from the PRs merged since a tag or date
+    ReleaseNotesGenerate,
+    /// Copy the drafted release notes
+    ReleaseNotesCopy,
+    /// Write the drafted release notes to a file
+    ReleaseNotesWrite,
 
     // === Diff Viewer ===
     /// Open diff viewer for current PR
@@ -141,6 +264,10 @@ pub enum CommandId {
     DiffViewerSwitchPane,
     /// Add a comment on the current line
     DiffViewerAddComment,
+    /// Add a comment on the whole file
+    DiffViewerCommentOnFile,
+    /// Add a comment on the pull request
+    DiffViewerCommentOnPr,
     /// Submit the current comment
     DiffViewerSubmitComment,
     /// Cancel comment editing
@@ -149,11 +276,21 @@ pub enum CommandId {
     DiffViewerVisualMode,
     /// Show review submission popup
     DiffViewerShowReviewPopup,
+    /// Show or hide the blame gutter
+    DiffViewerToggleBlame,
     /// Page down in diff viewer
     DiffViewerPageDown,
     /// Page up in diff viewer
     DiffViewerPageUp,
 
+    // === Authentication ===
+    /// Validate and store a GitHub token in the keychain
+    AuthSetToken,
+    /// Delete the stored GitHub token for the current host
+    AuthDeleteToken,
+    /// List hosts with stored GitHub tokens
+    AuthListTokens,
+
     // === General ===
     /// Close the current view/panel
     GlobalClose,
@@ -168,9 +305,14 @@ impl CommandId {
     /// those are handled separately in the reducer.
     pub fn to_action(self) -> crate::actions::Action {
         use crate::actions::{
-            Action, ContextAction, DebugConsoleAction, GlobalAction, MergeBotAction,
-            NavigationAction, PullRequestAction,
+            Action, ActionsViewAction, AuditLogAction, AuthAction, BranchPickerAction,
+            BugReportAction, CacheInspectorAction, CommandPaletteAction, ContextAction,
+            DebugConsoleAction, DependencyUpdatesAction, DuplicateUpdatesAction,
+            FilterBuilderAction, GlobalAction, LabelPickerAction, MergeBotAction, NavigationAction,
+            PerfStatsAction, PrSearchAction, PullRequestAction, ReleaseNotesAction,
+            ReviewerPickerAction, WatchListAction,
         };
+        use crate::domain_models::{PrTab, SnoozeDuration};
         use crate::views::{AddRepositoryView, CommandPaletteView, DebugConsoleView};
 
         match self {
@@ -185,9 +327,16 @@ impl CommandId {
                 Action::Global(GlobalAction::PushView(Box::new(AddRepositoryView::new())))
             }
             Self::RepositoryRemove => Action::Repository(RepositoryAction::RemoveCurrentRepository),
+            Self::RepositoryUndoRemove => {
+                Action::Repository(RepositoryAction::UndoRemoveRepository)
+            }
             Self::RepositoryOpenInBrowser => {
                 Action::Repository(RepositoryAction::OpenRepositoryInBrowser)
             }
+            Self::RepositorySwitchBranch => Action::BranchPicker(BranchPickerAction::Open),
+            Self::RepositorySwitchProfile => Action::CommandPalette(CommandPaletteAction::Open(
+                crate::commands::PROFILE_COMMAND_PREFIX.to_string(),
+            )),
             Self::RepositoryNext => Action::PullRequest(PullRequestAction::RepositoryNext),
             Self::RepositoryPrevious => Action::PullRequest(PullRequestAction::RepositoryPrevious),
 
@@ -204,6 +353,11 @@ impl CommandId {
                 Action::Global(GlobalAction::PushView(Box::new(DebugConsoleView::new())))
             }
             Self::DebugClearLogs => Action::DebugConsole(DebugConsoleAction::Clear),
+            Self::DebugShowApiUsage => Action::DebugConsole(DebugConsoleAction::ShowApiUsage),
+            Self::DebugSetLogLevel => Action::DebugConsole(DebugConsoleAction::EditLogLevel),
+            Self::DebugShowPerfStats => Action::PerfStats(PerfStatsAction::Open),
+            Self::DebugShowCacheInspector => Action::CacheInspector(CacheInspectorAction::Open),
+            Self::DebugCreateBugReport => Action::BugReport(BugReportAction::Open),
 
             // Command palette
             Self::CommandPaletteOpen => {
@@ -217,13 +371,50 @@ impl CommandId {
             Self::PrRefresh => Action::PullRequest(PullRequestAction::Refresh),
 
             // PR Operations
-            Self::PrOpenInBrowser => Action::PullRequest(PullRequestAction::OpenInBrowser),
+            Self::PrOpenInBrowser => {
+                Action::PullRequest(PullRequestAction::OpenInBrowser(PrTab::Conversation))
+            }
+            Self::PrOpenFilesInBrowser => {
+                Action::PullRequest(PullRequestAction::OpenInBrowser(PrTab::Files))
+            }
+            Self::PrOpenChecksInBrowser => {
+                Action::PullRequest(PullRequestAction::OpenInBrowser(PrTab::Checks))
+            }
+            Self::PrOpenCommitsInBrowser => {
+                Action::PullRequest(PullRequestAction::OpenInBrowser(PrTab::Commits))
+            }
             Self::PrMerge => Action::PullRequest(PullRequestAction::MergeRequest),
             Self::PrRebase => Action::PullRequest(PullRequestAction::RebaseRequest),
+            Self::PrRebaseStack => Action::PullRequest(PullRequestAction::RebaseStackRequest),
+            Self::PrInspectConflicts => Action::PullRequest(PullRequestAction::InspectConflicts),
             Self::PrApprove => Action::PullRequest(PullRequestAction::ApproveRequest),
             Self::PrComment => Action::PullRequest(PullRequestAction::CommentRequest),
             Self::PrRequestChanges => Action::PullRequest(PullRequestAction::RequestChangesRequest),
             Self::PrClose => Action::PullRequest(PullRequestAction::CloseRequest),
+            Self::PrEditLabels => Action::LabelPicker(LabelPickerAction::Open),
+            Self::PrRequestReviewers => Action::ReviewerPicker(ReviewerPickerAction::Open),
+            Self::PrEditNote => Action::PullRequest(PullRequestAction::EditNote),
+            Self::PrPeek => Action::PullRequest(PullRequestAction::TogglePeek),
+            Self::PrSnooze => Action::PullRequest(PullRequestAction::Snooze {
+                duration: SnoozeDuration::UntilUpdated,
+            }),
+            Self::PrSnoozeDay => Action::PullRequest(PullRequestAction::Snooze {
+                duration: SnoozeDuration::Day,
+            }),
+            Self::PrSnoozeThreeDays => Action::PullRequest(PullRequestAction::Snooze {
+                duration: SnoozeDuration::ThreeDays,
+            }),
+            Self::PrSnoozeWeek => Action::PullRequest(PullRequestAction::Snooze {
+                duration: SnoozeDuration::Week,
+            }),
+            Self::PrUnsnooze => Action::PullRequest(PullRequestAction::Unsnooze),
+            Self::PrToggleShowSnoozed => Action::PullRequest(PullRequestAction::ToggleShowSnoozed),
+            Self::PrMarkAllSeen => Action::PullRequest(PullRequestAction::MarkAllSeen),
+            Self::PrMergeBotUpdates => {
+                Action::PullRequest(PullRequestAction::MergeBotUpdatesRequest)
+            }
+            Self::DependencyUpdatesOpen => Action::DependencyUpdates(DependencyUpdatesAction::Open),
+            Self::PrRunPolicy => Action::PullRequest(PullRequestAction::RunPolicyRequest),
 
             // CI/Build Status
             Self::PrRerunFailedJobs => Action::PullRequest(PullRequestAction::RerunFailedJobs),
@@ -232,9 +423,35 @@ impl CommandId {
             // IDE Integration
             Self::PrOpenInIDE => Action::PullRequest(PullRequestAction::OpenInIDE),
 
+            // Audit Log
+            Self::PrShowAuditLog => Action::AuditLog(AuditLogAction::Open),
+            Self::AuditLogToggleScope => Action::AuditLog(AuditLogAction::ToggleScope),
+
+            // Duplicate Updates
+            Self::PrShowDuplicateUpdates => Action::DuplicateUpdates(DuplicateUpdatesAction::Open),
+            Self::DuplicateUpdatesToggle => {
+                Action::DuplicateUpdates(DuplicateUpdatesAction::Toggle)
+            }
+            Self::DuplicateUpdatesPickAll => {
+                Action::DuplicateUpdates(DuplicateUpdatesAction::PickAll)
+            }
+            Self::DuplicateUpdatesPickNone => {
+                Action::DuplicateUpdates(DuplicateUpdatesAction::PickNone)
+            }
+            Self::DuplicateUpdatesMerge => Action::DuplicateUpdates(DuplicateUpdatesAction::Merge),
+
             // Filter & Search
             Self::PrCycleFilter => Action::PullRequest(PullRequestAction::CycleFilter),
             Self::PrClearFilter => Action::PullRequest(PullRequestAction::ClearFilter),
+            Self::PrFilterByAuthor => Action::PullRequest(PullRequestAction::ToggleAuthorFilter),
+            Self::PrFilterBuilder => Action::FilterBuilder(FilterBuilderAction::Open),
+            Self::PrCycleStateFilter => Action::PullRequest(PullRequestAction::CycleStateFilter),
+            Self::PrCycleSort => Action::PullRequest(PullRequestAction::CycleSort),
+            Self::PrSearch => Action::PrSearch(PrSearchAction::OpenInput),
+
+            // Watch List
+            Self::PrToggleWatch => Action::PullRequest(PullRequestAction::ToggleWatch),
+            Self::WatchListOpen => Action::WatchList(WatchListAction::Open),
 
             // Merge Bot
             Self::MergeBotStart => Action::MergeBot(MergeBotAction::Start),
@@ -245,19 +462,50 @@ impl CommandId {
             Self::KeyBindingsToggleView => {
                 Action::Global(GlobalAction::PushView(Box::new(KeyBindingsView::new())))
             }
+            Self::StatusBarShowMessage => {
+                Action::Global(GlobalAction::PushView(Box::new(StatusMessageView::new())))
+            }
 
             // Build Log
             Self::BuildLogOpen => Action::BuildLog(crate::actions::BuildLogAction::Open),
+            Self::ActionsViewOpen => Action::ActionsView(ActionsViewAction::Open),
             Self::BuildLogNextError => Action::BuildLog(crate::actions::BuildLogAction::NextError),
             Self::BuildLogPrevError => Action::BuildLog(crate::actions::BuildLogAction::PrevError),
             Self::BuildLogToggle => Action::BuildLog(crate::actions::BuildLogAction::Toggle),
             Self::BuildLogToggleTimestamps => {
                 Action::BuildLog(crate::actions::BuildLogAction::ToggleTimestamps)
             }
+            Self::BuildLogToggleShowSuccessful => {
+                Action::BuildLog(crate::actions::BuildLogAction::ToggleShowSuccessful)
+            }
             Self::BuildLogExpandAll => Action::BuildLog(crate::actions::BuildLogAction::ExpandAll),
             Self::BuildLogCollapseAll => {
                 Action::BuildLog(crate::actions::BuildLogAction::CollapseAll)
             }
+            Self::BuildLogExport => Action::BuildLog(crate::actions::BuildLogAction::Export),
+
+            // Conflict Helper
+            Self::CacheInspectorDelete => {
+                Action::CacheInspector(CacheInspectorAction::DeleteSelected)
+            }
+            Self::CacheInspectorInvalidatePattern => {
+                Action::CacheInspector(CacheInspectorAction::EditPattern)
+            }
+            Self::CacheInspectorFilter => Action::CacheInspector(CacheInspectorAction::EditFilter),
+            Self::CacheInspectorCycleSort => {
+                Action::CacheInspector(CacheInspectorAction::CycleSort)
+            }
+            Self::ConflictHelperCopyCommands => {
+                Action::ConflictHelper(crate::actions::ConflictHelperAction::CopyCommands)
+            }
+            Self::ConflictHelperRecreate => {
+                Action::ConflictHelper(crate::actions::ConflictHelperAction::RecreateDependabot)
+            }
+
+            // Release Notes
+            Self::ReleaseNotesGenerate => Action::ReleaseNotes(ReleaseNotesAction::Prepare),
+            Self::ReleaseNotesCopy => Action::ReleaseNotes(ReleaseNotesAction::Copy),
+            Self::ReleaseNotesWrite => Action::ReleaseNotes(ReleaseNotesAction::WriteFile),
 
             // Diff Viewer
             Self::DiffViewerOpen => Action::DiffViewer(crate::actions::DiffViewerAction::Open),
@@ -267,6 +515,12 @@ impl CommandId {
             Self::DiffViewerAddComment => {
                 Action::DiffViewer(crate::actions::DiffViewerAction::AddComment)
             }
+            Self::DiffViewerCommentOnFile => {
+                Action::DiffViewer(crate::actions::DiffViewerAction::CommentOnFile)
+            }
+            Self::DiffViewerCommentOnPr => {
+                Action::DiffViewer(crate::actions::DiffViewerAction::CommentOnPr)
+            }
             Self::DiffViewerSubmitComment => {
                 Action::DiffViewer(crate::actions::DiffViewerAction::CommitComment)
             }
@@ -279,14 +533,22 @@ impl CommandId {
             Self::DiffViewerShowReviewPopup => {
                 Action::DiffViewer(crate::actions::DiffViewerAction::ShowReviewPopup)
             }
+            Self::DiffViewerToggleBlame => {
+                Action::DiffViewer(crate::actions::DiffViewerAction::ToggleBlame)
+            }
             Self::DiffViewerPageDown => {
                 Action::DiffViewer(crate::actions::DiffViewerAction::PageDown)
             }
             Self::DiffViewerPageUp => Action::DiffViewer(crate::actions::DiffViewerAction::PageUp),
 
+            // Authentication
+            Self::AuthSetToken => Action::Auth(AuthAction::OpenTokenInput),
+            Self::AuthDeleteToken => Action::Auth(AuthAction::DeleteStoredToken),
+            Self::AuthListTokens => Action::Auth(AuthAction::ListStoredTokens),
+
             // General
             Self::GlobalClose => Action::Global(GlobalAction::Close),
-            Self::GlobalQuit => Action::Global(GlobalAction::Quit),
+            Self::GlobalQuit => Action::Global(GlobalAction::RequestQuit),
         }
     }
 
@@ -302,7 +564,10 @@ impl CommandId {
             // Repository
             Self::RepositoryAdd => "Add repository",
             Self::RepositoryRemove => "Remove repository",
+            Self::RepositoryUndoRemove => "Undo repository removal",
             Self::RepositoryOpenInBrowser => "Open repository in browser",
+            Self::RepositorySwitchBranch => "Switch base branch…",
+            Self::RepositorySwitchProfile => "Switch profile…",
             Self::RepositoryNext => "Next repository",
             Self::RepositoryPrevious => "Previous repository",
 
@@ -317,6 +582,11 @@ impl CommandId {
             // Debug
             Self::DebugToggleConsoleView => "Toggle debug console",
             Self::DebugClearLogs => "Clear debug logs",
+            Self::DebugShowApiUsage => "Show API usage",
+            Self::DebugSetLogLevel => "Set log level…",
+            Self::DebugShowPerfStats => "Performance stats",
+            Self::DebugShowCacheInspector => "Cache inspector",
+            Self::DebugCreateBugReport => "Create bug report bundle",
 
             // Command palette
             Self::CommandPaletteOpen => "Open command palette",
@@ -329,12 +599,31 @@ impl CommandId {
 
             // PR Operations
             Self::PrOpenInBrowser => "Open PR in browser",
+            Self::PrOpenFilesInBrowser => "Open PR files in browser",
+            Self::PrOpenChecksInBrowser => "Open PR checks in browser",
+            Self::PrOpenCommitsInBrowser => "Open PR commits in browser",
             Self::PrMerge => "Merge PRs",
             Self::PrRebase => "Rebase PRs",
+            Self::PrRebaseStack => "Rebase stack",
+            Self::PrInspectConflicts => "Inspect conflicts",
             Self::PrApprove => "Approve PRs",
             Self::PrComment => "Comment on PRs",
             Self::PrRequestChanges => "Request changes on PRs",
             Self::PrClose => "Close PRs",
+            Self::PrEditLabels => "Edit labels",
+            Self::PrRequestReviewers => "Request reviewers",
+            Self::PrEditNote => "Edit note",
+            Self::PrPeek => "Peek PR description",
+            Self::PrSnooze => "Snooze until updated",
+            Self::PrSnoozeDay => "Snooze for 1 day",
+            Self::PrSnoozeThreeDays => "Snooze for 3 days",
+            Self::PrSnoozeWeek => "Snooze for 1 week",
+            Self::PrUnsnooze => "Unsnooze",
+            Self::PrToggleShowSnoozed => "Toggle snoozed PRs",
+            Self::PrMarkAllSeen => "Mark all as seen",
+            Self::PrMergeBotUpdates => "Merge bot updates",
+            Self::DependencyUpdatesOpen => "Show dependency updates",
+            Self::PrRunPolicy => "Run policy…",
 
             // CI/Build Status
             Self::PrRerunFailedJobs => "Rerun failed CI jobs",
@@ -343,9 +632,29 @@ impl CommandId {
             // IDE Integration
             Self::PrOpenInIDE => "Open PR diff in IDE",
 
+            // Audit Log
+            Self::PrShowAuditLog => "Show audit log",
+            Self::AuditLogToggleScope => "Toggle PR / repository",
+
+            // Duplicate Updates
+            Self::PrShowDuplicateUpdates => "Show same update in other repos",
+            Self::DuplicateUpdatesToggle => "Pick PR",
+            Self::DuplicateUpdatesPickAll => "Pick all PRs",
+            Self::DuplicateUpdatesPickNone => "Pick no PR",
+            Self::DuplicateUpdatesMerge => "Merge picked PRs",
+
             // Filter & Search
             Self::PrCycleFilter => "Cycle PR filter",
             Self::PrClearFilter => "Clear PR filter",
+            Self::PrFilterByAuthor => "Filter by author",
+            Self::PrFilterBuilder => "Build filter",
+            Self::PrCycleStateFilter => "Cycle PR state",
+            Self::PrCycleSort => "Cycle PR sort",
+            Self::PrSearch => "Search PRs",
+
+            // Watch List
+            Self::PrToggleWatch => "Watch / unwatch PR",
+            Self::WatchListOpen => "Show watch list",
 
             // Merge Bot
             Self::MergeBotStart => "Start merge bot",
@@ -354,27 +663,48 @@ impl CommandId {
 
             // Help
             Self::KeyBindingsToggleView => "Show key bindings",
+            Self::StatusBarShowMessage => "Show status message",
 
             // Build Log
             Self::BuildLogOpen => "Open build logs",
+            Self::ActionsViewOpen => "Show workflow runs",
             Self::BuildLogNextError => "Next error",
             Self::BuildLogPrevError => "Previous error",
             Self::BuildLogToggle => "Toggle expand/collapse",
             Self::BuildLogToggleTimestamps => "Toggle timestamps",
+            Self::BuildLogToggleShowSuccessful => "Toggle successful jobs",
             Self::BuildLogExpandAll => "Expand all",
             Self::BuildLogCollapseAll => "Collapse all",
+            Self::BuildLogExport => "Export build log",
+            Self::CacheInspectorDelete => "Delete cached response",
+            Self::CacheInspectorInvalidatePattern => "Invalidate cached responses…",
+            Self::CacheInspectorFilter => "Filter cached responses",
+            Self::CacheInspectorCycleSort => "Sort cached responses",
+            Self::ConflictHelperCopyCommands => "Copy resolution commands",
+            Self::ConflictHelperRecreate => "Recreate dependabot PR",
+            Self::ReleaseNotesGenerate => "Generate release notes…",
+            Self::ReleaseNotesCopy => "Copy release notes",
+            Self::ReleaseNotesWrite => "Write release notes to file",
 
             // Diff Viewer
             Self::DiffViewerOpen => "Open diff viewer",
             Self::DiffViewerSwitchPane => "Switch pane",
             Self::DiffViewerAddComment => "Add comment",
+            Self::DiffViewerCommentOnFile => "Comment on file",
+            Self::DiffViewerCommentOnPr => "Comment on PR",
             Self::DiffViewerSubmitComment => "Submit comment",
             Self::DiffViewerCancelComment => "Cancel comment",
             Self::DiffViewerVisualMode => "Visual mode",
             Self::DiffViewerShowReviewPopup => "Submit review",
+            Self::DiffViewerToggleBlame => "Toggle blame",
             Self::DiffViewerPageDown => "Page down",
             Self::DiffViewerPageUp => "Page up",
 
+            // Authentication
+            Self::AuthSetToken => "Set GitHub token…",
+            Self::AuthDeleteToken => "Delete stored GitHub token",
+            Self::AuthListTokens => "List stored GitHub tokens",
+
             // General
             Self::GlobalClose => "Close",
             Self::GlobalQuit => "Quit",
@@ -393,7 +723,12 @@ impl CommandId {
             // Repository
             Self::RepositoryAdd => "Add a new repository to track",
             Self::RepositoryRemove => "Remove the current repository from the list",
+            Self::RepositoryUndoRemove => {
+                "Add the repository removed last back with the same settings"
+            }
             Self::RepositoryOpenInBrowser => "Open the current repository in your browser",
+            Self::RepositorySwitchBranch => "Show the PRs targeting another branch in this tab",
+            Self::RepositorySwitchProfile => "Replace the tabs with the repositories of another profile",
             Self::RepositoryNext => "Switch to the next repository",
             Self::RepositoryPrevious => "Switch to the previous repository",
 
@@ -408,6 +743,17 @@ impl CommandId {
             // Debug
             Self::DebugToggleConsoleView => "Show or hide the debug console",
             Self::DebugClearLogs => "Clear all debug console logs",
+            Self::DebugShowApiUsage => "Show GitHub API requests per category in the last minute",
+            Self::DebugSetLogLevel => "Change the log levels per module without restarting",
+            Self::DebugShowPerfStats => {
+                "Show call counts, errors and latencies per GitHub API endpoint since startup"
+            }
+            Self::DebugShowCacheInspector => {
+                "List cached GitHub API responses with their size and age, delete stale ones"
+            }
+            Self::DebugCreateBugReport => {
+                "Write a zip with version, state summary, redacted config and recent logs"
+            }
 
             // Command palette
             Self::CommandPaletteOpen => "Open the command palette to search and execute commands",
@@ -420,12 +766,43 @@ impl CommandId {
 
             // PR Operations
             Self::PrOpenInBrowser => "Open the current PR in your default web browser",
+            Self::PrOpenFilesInBrowser => "Open the Files tab of the selected PRs in your browser",
+            Self::PrOpenChecksInBrowser => {
+                "Open the Checks tab of the selected PRs, or the failed job focused in the build logs"
+            }
+            Self::PrOpenCommitsInBrowser => "Open the Commits tab of the selected PRs in your browser",
             Self::PrMerge => "Merge selected PRs (or current PR if none selected)",
             Self::PrRebase => "Update selected PRs with latest from base branch",
+            Self::PrRebaseStack => {
+                "Update the focused PR, then the PRs stacked on it in order (after the base merged)"
+            }
+            Self::PrInspectConflicts => {
+                "List the files the focused PR likely conflicts on with its base branch"
+            }
             Self::PrApprove => "Approve selected PRs with a review",
             Self::PrComment => "Post a comment on selected PRs",
             Self::PrRequestChanges => "Request changes on selected PRs with a review",
             Self::PrClose => "Close selected PRs without merging",
+            Self::PrEditLabels => "Add or remove labels on selected PRs",
+            Self::PrRequestReviewers => {
+                "Request reviews on the current PR, owners of its changed files suggested first"
+            }
+            Self::PrEditNote => "Edit your local note on the current PR",
+            Self::PrPeek => "Show the focused PR's description, branches and CI without leaving",
+            Self::PrSnooze => "Hide selected PRs until they get a new commit or update",
+            Self::PrSnoozeDay => "Hide selected PRs for a day (or until updated)",
+            Self::PrSnoozeThreeDays => "Hide selected PRs for three days (or until updated)",
+            Self::PrSnoozeWeek => "Hide selected PRs for a week (or until updated)",
+            Self::PrUnsnooze => "Show selected snoozed PRs again",
+            Self::PrToggleShowSnoozed => "Reveal snoozed PRs (dimmed) or hide them",
+            Self::PrMarkAllSeen => "Clear the ● markers of PRs updated since last seen",
+            Self::PrMergeBotUpdates => "Merge all patch/minor bot PRs with green CI (asks first)",
+            Self::DependencyUpdatesOpen => {
+                "Group Dependabot/Renovate PRs of all repositories by dependency"
+            }
+            Self::PrRunPolicy => {
+                "Show which PRs the configured policies approve/merge (asks first)"
+            }
 
             // CI/Build Status
             Self::PrRerunFailedJobs => "Rerun failed CI workflow jobs for the current PR",
@@ -434,9 +811,41 @@ impl CommandId {
             // IDE Integration
             Self::PrOpenInIDE => "Open the PR diff in your configured IDE (uses gh pr view)",
 
+            // Audit Log
+            Self::PrShowAuditLog => {
+                "List the merges, reviews, comments, ... run on GitHub for the current PR"
+            }
+            Self::AuditLogToggleScope => "List the audit entries of the PR or of its repository",
+
+            // Duplicate Updates
+            Self::PrShowDuplicateUpdates => {
+                "List the PRs opening the same bot update in the other repositories, to merge them together"
+            }
+            Self::DuplicateUpdatesToggle => "Pick or unpick the PR under the cursor for merging",
+            Self::DuplicateUpdatesPickAll => "Pick every PR of the update for merging",
+            Self::DuplicateUpdatesPickNone => "Unpick every PR of the update",
+            Self::DuplicateUpdatesMerge => "Merge the picked PRs, each in its repository",
+
             // Filter & Search
             Self::PrCycleFilter => "Cycle through filter presets (All, Ready, Needs Rebase, etc.)",
             Self::PrClearFilter => "Clear the current filter and show all PRs",
+            Self::PrFilterByAuthor => {
+                "Show only PRs by the author of the focused PR (press again to clear)"
+            }
+            Self::PrFilterBuilder => {
+                "Filter by author, labels, CI, draft, update type and age, and save the filter by name"
+            }
+            Self::PrCycleStateFilter => "Show open, closed, merged or all PRs of the repository",
+            Self::PrCycleSort => {
+                "Sort newest first, or by what needs you: changes requested, review requested, failing CI"
+            }
+            Self::PrSearch => {
+                "Search all PRs of the repository on GitHub (e.g. author:me is:merged)"
+            }
+
+            // Watch List
+            Self::PrToggleWatch => "Keep the focused PR's status fresh and notify on changes",
+            Self::WatchListOpen => "Show watched PRs of all repositories with their status",
 
             // Merge Bot
             Self::MergeBotStart => "Start automated merge bot for selected PRs",
@@ -445,15 +854,42 @@ impl CommandId {
 
             // Help
             Self::KeyBindingsToggleView => "Show or hide the key bindings help panel",
+            Self::StatusBarShowMessage => {
+                "Show the full latest status bar message with its time and source"
+            }
 
             // Build Log
             Self::BuildLogOpen => "Open the build logs viewer for the current PR",
+            Self::ActionsViewOpen => {
+                "Show recent workflow runs on the repository's branch, independent of PRs"
+            }
             Self::BuildLogNextError => "Jump to the next error in the build logs",
             Self::BuildLogPrevError => "Jump to the previous error in the build logs",
             Self::BuildLogToggle => "Toggle expand/collapse of the current tree node",
             Self::BuildLogToggleTimestamps => "Toggle timestamp display in log lines",
+            Self::BuildLogToggleShowSuccessful => {
+                "List successful jobs or fold them into one node in the build log tree"
+            }
             Self::BuildLogExpandAll => "Expand all nodes in the build log tree",
             Self::BuildLogCollapseAll => "Collapse all nodes in the build log tree",
+            Self::BuildLogExport => {
+                "Write the build logs to a file named after the export_filename setting"
+            }
+            Self::ConflictHelperCopyCommands => {
+                "Copy the git commands merging the base branch locally to the clipboard"
+            }
+            Self::ConflictHelperRecreate => "Ask dependabot to recreate the PR from scratch",
+            Self::ReleaseNotesGenerate => {
+                "Draft markdown release notes from the PRs merged since a tag or date"
+            }
+            Self::ReleaseNotesCopy => "Copy the drafted release notes to the clipboard",
+            Self::ReleaseNotesWrite => "Write the drafted release notes to a file",
+            Self::CacheInspectorDelete => "Drop the selected response so the next fetch hits GitHub",
+            Self::CacheInspectorInvalidatePattern => {
+                "Drop all cached responses whose key contains the typed text"
+            }
+            Self::CacheInspectorFilter => "Only list cached responses matching a key or category",
+            Self::CacheInspectorCycleSort => "Sort the cached responses by size or by age",
 
             // Diff Viewer
             Self::DiffViewerOpen => {
@@ -461,15 +897,29 @@ impl CommandId {
             }
             Self::DiffViewerSwitchPane => "Switch focus between file tree and diff content pane",
             Self::DiffViewerAddComment => "Add a comment on the current line or selection",
+            Self::DiffViewerCommentOnFile => {
+                "Add a comment on the whole file under the cursor (file tree or hunk header)"
+            }
+            Self::DiffViewerCommentOnPr => "Add a comment on the pull request conversation",
             Self::DiffViewerSubmitComment => "Submit the current comment",
             Self::DiffViewerCancelComment => "Cancel comment editing and discard changes",
             Self::DiffViewerVisualMode => "Enter visual mode for multi-line selection",
             Self::DiffViewerShowReviewPopup => {
                 "Show the review submission popup (Approve, Request Changes, Comment)"
             }
+            Self::DiffViewerToggleBlame => {
+                "Show who last changed the context and removed lines, and when"
+            }
             Self::DiffViewerPageDown => "Scroll down one page in the diff viewer",
             Self::DiffViewerPageUp => "Scroll up one page in the diff viewer",
 
+            // Authentication
+            Self::AuthSetToken => {
+                "Validate a token against GitHub and store it in the keychain for the current host"
+            }
+            Self::AuthDeleteToken => "Delete the stored token for the current repository's host",
+            Self::AuthListTokens => "Show which hosts have a stored token and where",
+
             // General
             Self::GlobalClose => "Close the current view or panel",
             Self::GlobalQuit => "Exit the application",
@@ -483,9 +933,15 @@ impl CommandId {
 
             Self::RepositoryAdd
             | Self::RepositoryRemove
+            | Self::RepositoryUndoRemove
             | Self::RepositoryOpenInBrowser
+            | Self::RepositorySwitchBranch
+            | Self::RepositorySwitchProfile
             | Self::RepositoryNext
-            | Self::RepositoryPrevious => "Repository",
+            | Self::RepositoryPrevious
+            | Self::ReleaseNotesGenerate
+            | Self::ReleaseNotesCopy
+            | Self::ReleaseNotesWrite => "Repository",
 
             Self::NavigateNext
             | Self::NavigatePrevious
@@ -494,7 +950,17 @@ impl CommandId {
             | Self::NavigateToTop
             | Self::NavigateToBottom => "Navigation",
 
-            Self::DebugToggleConsoleView | Self::DebugClearLogs => "Debug",
+            Self::DebugToggleConsoleView
+            | Self::DebugClearLogs
+            | Self::DebugShowApiUsage
+            | Self::DebugSetLogLevel
+            | Self::DebugShowPerfStats
+            | Self::DebugShowCacheInspector
+            | Self::CacheInspectorDelete
+            | Self::CacheInspectorInvalidatePattern
+            | Self::CacheInspectorFilter
+            | Self::CacheInspectorCycleSort
+            | Self::DebugCreateBugReport => "Debug",
 
             Self::CommandPaletteOpen => "Command Palette",
 
@@ -503,40 +969,83 @@ impl CommandId {
             | Self::PrDeselectAll
             | Self::PrRefresh
             | Self::PrOpenInBrowser
+            | Self::PrOpenFilesInBrowser
+            | Self::PrOpenChecksInBrowser
+            | Self::PrOpenCommitsInBrowser
             | Self::PrMerge
             | Self::PrRebase
+            | Self::PrRebaseStack
+            | Self::PrInspectConflicts
             | Self::PrApprove
             | Self::PrComment
             | Self::PrRequestChanges
             | Self::PrClose
+            | Self::PrEditLabels
+            | Self::PrRequestReviewers
+            | Self::PrEditNote
+            | Self::PrPeek
+            | Self::PrSnooze
+            | Self::PrSnoozeDay
+            | Self::PrSnoozeThreeDays
+            | Self::PrSnoozeWeek
+            | Self::PrUnsnooze
+            | Self::PrToggleShowSnoozed
+            | Self::PrMarkAllSeen
+            | Self::PrMergeBotUpdates
+            | Self::DependencyUpdatesOpen
+            | Self::PrRunPolicy
+            | Self::ConflictHelperCopyCommands
+            | Self::ConflictHelperRecreate
             | Self::PrRerunFailedJobs
             | Self::PrOpenBuildLogs
             | Self::PrOpenInIDE
+            | Self::PrShowAuditLog
+            | Self::AuditLogToggleScope
+            | Self::PrShowDuplicateUpdates
+            | Self::DuplicateUpdatesToggle
+            | Self::DuplicateUpdatesPickAll
+            | Self::DuplicateUpdatesPickNone
+            | Self::DuplicateUpdatesMerge
             | Self::PrCycleFilter
-            | Self::PrClearFilter => "Pull Request",
+            | Self::PrClearFilter
+            | Self::PrFilterByAuthor
+            | Self::PrFilterBuilder
+            | Self::PrCycleStateFilter
+            | Self::PrCycleSort
+            | Self::PrSearch => "Pull Request",
+
+            Self::PrToggleWatch | Self::WatchListOpen => "Watch List",
 
             Self::MergeBotStart | Self::MergeBotStop | Self::MergeBotAddToQueue => "Merge Bot",
 
-            Self::KeyBindingsToggleView => "Help",
+            Self::KeyBindingsToggleView | Self::StatusBarShowMessage => "Help",
 
             Self::BuildLogOpen
+            | Self::ActionsViewOpen
             | Self::BuildLogNextError
             | Self::BuildLogPrevError
             | Self::BuildLogToggle
             | Self::BuildLogToggleTimestamps
+            | Self::BuildLogToggleShowSuccessful
             | Self::BuildLogExpandAll
-            | Self::BuildLogCollapseAll => "Build Log",
+            | Self::BuildLogCollapseAll
+            | Self::BuildLogExport => "Build Log",
 
             Self::DiffViewerOpen
             | Self::DiffViewerSwitchPane
             | Self::DiffViewerAddComment
+            | Self::DiffViewerCommentOnFile
+            | Self::DiffViewerCommentOnPr
             | Self::DiffViewerSubmitComment
             | Self::DiffViewerCancelComment
             | Self::DiffViewerVisualMode
             | Self::DiffViewerShowReviewPopup
+            | Self::DiffViewerToggleBlame
             | Self::DiffViewerPageDown
             | Self::DiffViewerPageUp => "Diff Viewer",
 
+            Self::AuthSetToken | Self::AuthDeleteToken | Self::AuthListTokens => "Authentication",
+
             Self::GlobalClose | Self::GlobalQuit => "General",
         }
     }
@@ -547,10 +1056,12 @@ impl CommandId {
             "Navigation",
             "Repository",
             "Pull Request",
+            "Watch List",
             "Build Log",
             "Diff Viewer",
             "Merge Bot",
             "Command Palette",
+            "Authentication",
             "Debug",
             "Help",
             "General",
@@ -558,10 +1069,8 @@ impl CommandId {
     }
 
     /// Check if this command should appear in the command palette
-    /// there is a `palette_command_ids()` in `crates/gh-pr-lander/src/commands.rs`
-    /// that always gets forgotten and needs manual maintenance,
-    /// its defining which commands are in the pallette,
-    /// its actaully a semantical duplication to this very function here
+    ///
+    /// Read through `CommandRegistry`, which also holds the default bindings.
     pub fn show_in_palette(&self) -> bool {
         match self {
             // Semantic/context commands are keyboard-driven, not shown in palette
@@ -582,16 +1091,42 @@ impl CommandId {
             | Self::BuildLogPrevError
             | Self::BuildLogToggle
             | Self::BuildLogToggleTimestamps
+            | Self::BuildLogToggleShowSuccessful
             | Self::BuildLogExpandAll
-            | Self::BuildLogCollapseAll => false,
+            | Self::BuildLogCollapseAll
+            | Self::BuildLogExport => false,
+
+            // Conflict helper commands are keyboard-driven within the popup
+            Self::ConflictHelperCopyCommands | Self::ConflictHelperRecreate => false,
+
+            // Release notes commands are keyboard-driven within the popup
+            Self::ReleaseNotesCopy | Self::ReleaseNotesWrite => false,
+
+            // Audit log commands are keyboard-driven within the panel
+            Self::AuditLogToggleScope => false,
+
+            // Duplicate updates commands are keyboard-driven within the popup
+            Self::DuplicateUpdatesToggle
+            | Self::DuplicateUpdatesPickAll
+            | Self::DuplicateUpdatesPickNone
+            | Self::DuplicateUpdatesMerge => false,
+
+            // Cache inspector commands are keyboard-driven within the popup
+            Self::CacheInspectorDelete
+            | Self::CacheInspectorInvalidatePattern
+            | Self::CacheInspectorFilter
+            | Self::CacheInspectorCycleSort => false,
 
             // Diff viewer view-specific commands are keyboard-driven
             Self::DiffViewerSwitchPane
             | Self::DiffViewerAddComment
+            | Self::DiffViewerCommentOnFile
+            | Self::DiffViewerCommentOnPr
             | Self::DiffViewerSubmitComment
             | Self::DiffViewerCancelComment
             | Self::DiffViewerVisualMode
             | Self::DiffViewerShowReviewPopup
+            | Self::DiffViewerToggleBlame
             | Self::DiffViewerPageDown
             | Self::DiffViewerPageUp => false,
 
@@ -602,11 +1137,4 @@ impl CommandId {
             _ => true,
         }
     }
-
-    /// Get all command IDs that should appear in the command palette
-    pub fn palette_command_ids() -> Vec<CommandId> {
-        use strum::IntoEnumIterator;
-
-        Self::iter().filter(|id| id.show_in_palette()).collect()
-    }
 }
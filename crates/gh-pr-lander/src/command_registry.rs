@@ -0,0 +1,590 @@
+//! Command registry
+//!
+//! Single source of truth for everything known about a command: label,
+//! description, category, where it is active, whether the palette lists it,
+//! its default keybindings and the action it dispatches. The default keymap,
+//! the command palette and the help overlay are all derived from it, so a new
+//! `CommandId` cannot end up without a decision on its bindings.
+
+use std::sync::OnceLock;
+
+use strum::IntoEnumIterator;
+
+use crate::actions::Action;
+use crate::command_id::CommandId;
+use crate::keybindings::{parse_key_pattern, KeyBinding, Keymap, ParsedKeyPattern};
+
+/// Where the bindings of a command are active
+///
+/// Bindings only conflict within the same scope, or with a global binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CommandScope {
+    /// Every view (navigation, help, quit, ...)
+    Global,
+    /// The PR table
+    PullRequests,
+    /// The debug console
+    DebugConsole,
+    /// The build log viewer
+    BuildLog,
+    /// The diff viewer
+    DiffViewer,
+    /// The conflict helper popup
+    ConflictHelper,
+    /// The release notes popup
+    ReleaseNotes,
+    /// The cache inspector popup
+    CacheInspector,
+    /// The audit log panel
+    AuditLog,
+    /// The duplicate updates popup
+    DuplicateUpdates,
+}
+
+impl CommandScope {
+    /// Whether bindings of the two scopes can be active at the same time
+    pub fn overlaps(self, other: CommandScope) -> bool {
+        self == other || self == Self::Global || other == Self::Global
+    }
+}
+
+/// Everything known about one command
+#[derive(Debug, Clone)]
+pub struct CommandSpec {
+    pub id: CommandId,
+    /// Short human label (palette title)
+    pub title: &'static str,
+    /// One-line description (palette, help overlay)
+    pub description: &'static str,
+    /// Group in the palette and the help overlay
+    pub category: &'static str,
+    /// Where the bindings are active
+    pub scope: CommandScope,
+    /// Whether the command palette lists it
+    pub in_palette: bool,
+    /// Default keybindings, in the order their hints are shown
+    pub bindings: Vec<KeyBinding>,
+}
+
+impl CommandSpec {
+    fn new(id: CommandId) -> Self {
+        Self {
+            id,
+            title: id.title(),
+            description: id.description(),
+            category: id.category(),
+            scope: scope(id),
+            in_palette: id.show_in_palette(),
+            bindings: default_bindings(id)
+                .iter()
+                .map(|(keys, hint)| KeyBinding::new(*keys, *hint, id))
+                .collect(),
+        }
+    }
+
+    /// Whether the help overlay lists it (palette commands plus list navigation)
+    pub fn in_help(&self) -> bool {
+        self.in_palette
+            || matches!(
+                self.id,
+                CommandId::NavigateNext
+                    | CommandId::NavigatePrevious
+                    | CommandId::NavigateToTop
+                    | CommandId::NavigateToBottom
+            )
+    }
+
+    /// The action to dispatch
+    pub fn action(&self) -> Action {
+        self.id.to_action()
+    }
+}
+
+/// All commands, in `CommandId` order
+pub struct CommandRegistry {
+    specs: Vec<CommandSpec>,
+}
+
+impl CommandRegistry {
+    /// Build the registry from every `CommandId`
+    pub fn new() -> Self {
+        Self {
+            specs: CommandId::iter().map(CommandSpec::new).collect(),
+        }
+    }
+
+    /// The shared registry
+    pub fn global() -> &'static CommandRegistry {
+        static REGISTRY: OnceLock<CommandRegistry> = OnceLock::new();
+        REGISTRY.get_or_init(Self::new)
+    }
+
+    /// Spec of a command
+    pub fn get(&self, id: CommandId) -> &CommandSpec {
+        self.specs
+            .iter()
+            .find(|spec| spec.id == id)
+            .expect("every CommandId is registered")
+    }
+
+    /// All specs
+    pub fn specs(&self) -> impl Iterator<Item = &CommandSpec> {
+        self.specs.iter()
+    }
+
+    /// Commands listed in the command palette
+    pub fn palette_ids(&self) -> Vec<CommandId> {
+        self.specs()
+            .filter(|spec| spec.in_palette)
+            .map(|spec| spec.id)
+            .collect()
+    }
+
+    /// The default keymap
+    ///
+    /// When several commands share a key, the keyboard middleware tries them
+    /// in `CommandId` order (e.g. Tab: next repository before diff pane switch).
+    pub fn keymap(&self) -> Keymap {
+        Keymap::new(
+            self.specs()
+                .flat_map(|spec| spec.bindings.iter().cloned())
+                .collect(),
+        )
+    }
+
+    /// Pairs of default bindings that shadow each other in overlapping scopes
+    ///
+    /// Two bindings conflict on the same key, or when a single key is the
+    /// first key of a sequence (the single key wins, the sequence is dead).
+    pub fn conflicts(&self) -> Vec<(String, CommandId, CommandId)> {
+        let bindings: Vec<_> = self
+            .specs()
+            .flat_map(|spec| {
+                spec.bindings.iter().filter_map(|binding| {
+                    let pattern = parse_key_pattern(&binding.keys)?;
+                    Some((spec.id, spec.scope, binding.keys.as_str(), pattern))
+                })
+            })
+            .collect();
+
+        let mut conflicts = Vec::new();
+        for (i, (id, scope, keys, pattern)) in bindings.iter().enumerate() {
+            for (other_id, other_scope, other_keys, other_pattern) in &bindings[i + 1..] {
+                if id == other_id || !scope.overlaps(*other_scope) {
+                    continue;
+                }
+                let shadows = keys == other_keys
+                    || starts_sequence(pattern, other_pattern)
+                    || starts_sequence(other_pattern, pattern);
+                if shadows {
+                    conflicts.push((format!("{} / {}", keys, other_keys), *id, *other_id));
+                }
+            }
+        }
+        conflicts
+    }
+}
+
+impl Default for CommandRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether the plain key `single` is the first key of `sequence`
+fn starts_sequence(single: &ParsedKeyPattern, sequence: &ParsedKeyPattern) -> bool {
+    match (single, sequence) {
+        (
+            ParsedKeyPattern::Single { code, modifiers },
+            ParsedKeyPattern::Sequence { first, .. },
+        ) => {
+            *code == ratatui::crossterm::event::KeyCode::Char(*first)
+                && (modifiers.is_empty()
+                    || *modifiers == ratatui::crossterm::event::KeyModifiers::SHIFT)
+        }
+        _ => false,
+    }
+}
+
+/// Where the bindings of a command are active
+fn scope(id: CommandId) -> CommandScope {
+    use CommandId::*;
+
+    match id {
+        Confirm
+        | ToggleSelect
+        | SelectAll
+        | DeselectAll
+        | NavigateNext
+        | NavigatePrevious
+        | NavigateLeft
+        | NavigateRight
+        | NavigateToTop
+        | NavigateToBottom
+        | DebugToggleConsoleView
+        | DebugShowApiUsage
+        | DebugSetLogLevel
+        | DebugShowPerfStats
+        | DebugShowCacheInspector
+        | DebugCreateBugReport
+        | CommandPaletteOpen
+        | KeyBindingsToggleView
+        | StatusBarShowMessage
+        | AuthSetToken
+        | AuthDeleteToken
+        | AuthListTokens
+        | GlobalClose
+        | GlobalQuit => CommandScope::Global,
+
+        DebugClearLogs => CommandScope::DebugConsole,
+
+        BuildLogNextError
+        | BuildLogPrevError
+        | BuildLogToggle
+        | BuildLogToggleTimestamps
+        | BuildLogToggleShowSuccessful
+        | BuildLogExpandAll
+        | BuildLogCollapseAll
+        | BuildLogExport => CommandScope::BuildLog,
+
+        ConflictHelperCopyCommands | ConflictHelperRecreate => CommandScope::ConflictHelper,
+
+        ReleaseNotesCopy | ReleaseNotesWrite => CommandScope::ReleaseNotes,
+
+        CacheInspectorDelete
+        | CacheInspectorInvalidatePattern
+        | CacheInspectorFilter
+        | CacheInspectorCycleSort => CommandScope::CacheInspector,
+
+        AuditLogToggleScope => CommandScope::AuditLog,
+
+        DuplicateUpdatesToggle
+        | DuplicateUpdatesPickAll
+        | DuplicateUpdatesPickNone
+        | DuplicateUpdatesMerge => CommandScope::DuplicateUpdates,
+
+        DiffViewerSwitchPane
+        | DiffViewerAddComment
+        | DiffViewerCommentOnFile
+        | DiffViewerCommentOnPr
+        | DiffViewerSubmitComment
+        | DiffViewerCancelComment
+        | DiffViewerVisualMode
+        | DiffViewerShowReviewPopup
+        | DiffViewerToggleBlame
+        | DiffViewerPageDown
+        | DiffViewerPageUp => CommandScope::DiffViewer,
+
+        RepositoryAdd
+        | RepositoryRemove
+        | RepositoryUndoRemove
+        | RepositoryOpenInBrowser
+        | RepositorySwitchBranch
+        | RepositorySwitchProfile
+        | RepositoryNext
+        | RepositoryPrevious
+        | ReleaseNotesGenerate
+        | PrToggleSelection
+        | PrSelectAll
+        | PrDeselectAll
+        | PrRefresh
+        | PrOpenInBrowser
+        | PrOpenFilesInBrowser
+        | PrOpenChecksInBrowser
+        | PrOpenCommitsInBrowser
+        | PrMerge
+        | PrRebase
+        | PrRebaseStack
+        | PrInspectConflicts
+        | PrApprove
+        | PrComment
+        | PrRequestChanges
+        | PrClose
+        | PrEditLabels
+        | PrRequestReviewers
+        | PrEditNote
+        | PrPeek
+        | PrSnooze
+        | PrSnoozeDay
+        | PrSnoozeThreeDays
+        | PrSnoozeWeek
+        | PrUnsnooze
+        | PrToggleShowSnoozed
+        | PrMarkAllSeen
+        | PrMergeBotUpdates
+        | DependencyUpdatesOpen
+        | PrRunPolicy
+        | PrRerunFailedJobs
+        | PrOpenBuildLogs
+        | PrOpenInIDE
+        | PrShowAuditLog
+        | PrShowDuplicateUpdates
+        | PrCycleFilter
+        | PrClearFilter
+        | PrFilterByAuthor
+        | PrFilterBuilder
+        | PrCycleStateFilter
+        | PrCycleSort
+        | PrSearch
+        | PrToggleWatch
+        | WatchListOpen
+        | MergeBotStart
+        | MergeBotStop
+        | MergeBotAddToQueue
+        | BuildLogOpen
+        | ActionsViewOpen
+        | DiffViewerOpen => CommandScope::PullRequests,
+    }
+}
+
+/// Default `(keys, hint)` bindings of a command
+///
+/// Key patterns are described in [`parse_key_pattern`].
+fn default_bindings(id: CommandId) -> &'static [(&'static str, &'static str)] {
+    use CommandId::*;
+
+    match id {
+        // Navigation
+        NavigateNext => &[("j", "j"), ("down", "↓")],
+        NavigatePrevious => &[("k", "k"), ("up", "↑")],
+        NavigateLeft => &[("h", "h"), ("left", "←")],
+        NavigateRight => &[("l", "l"), ("right", "→")],
+        // Note: "gg" and "G" are handled specially in keyboard middleware
+        NavigateToTop => &[("g g", "gg")],
+        NavigateToBottom => &[("G", "G")],
+
+        // Repository
+        RepositoryNext => &[("tab", "Tab")],
+        RepositoryPrevious => &[("shift+tab", "Shift+Tab"), ("backtab", "Shift+Tab")],
+        RepositoryAdd => &[("r a", "r → a")],
+        RepositoryUndoRemove => &[("r u", "r → u")],
+        RepositoryOpenInBrowser => &[("r o", "r → o")],
+        RepositorySwitchBranch => &[("r b", "r → b")],
+        RepositorySwitchProfile => &[("r p", "r → p")],
+
+        // Debug
+        DebugToggleConsoleView => &[("`", "`")],
+        DebugClearLogs => &[("c", "c")],
+
+        // Command palette
+        CommandPaletteOpen => &[("ctrl+p", "Ctrl+P")],
+
+        // PR Selection
+        PrToggleSelection => &[("space", "Space")],
+        PrSelectAll => &[("ctrl+a", "Ctrl+A")],
+        PrDeselectAll => &[("u", "u")],
+        PrRefresh => &[("ctrl+r", "Ctrl+R")],
+
+        // PR Operations
+        PrOpenInBrowser => &[("enter", "Enter"), ("o o", "o -> o")],
+        PrOpenFilesInBrowser => &[("o f", "o -> f")],
+        PrOpenChecksInBrowser => &[("o c", "o -> c")],
+        PrOpenCommitsInBrowser => &[("o l", "o -> l")],
+        PrMerge => &[("p m", "p -> m")],
+        PrApprove => &[("p a", "p -> a")],
+        PrComment => &[("p c", "p -> c")],
+        PrRequestChanges => &[("p d", "p -> d")],
+        PrClose => &[("p x", "p -> x")],
+        PrEditLabels => &[("p t", "p -> t")],
+        PrRequestReviewers => &[("p R", "p -> R")],
+        PrEditNote => &[("p n", "p -> n")],
+        PrPeek => &[("p p", "p -> p")],
+        PrOpenInIDE => &[("p i", "p -> i")],
+        PrOpenBuildLogs => &[("p l", "p -> l")],
+        PrRebase => &[("p r", "p -> r")],
+        PrRebaseStack => &[("p u", "p -> u")],
+        PrInspectConflicts => &[("p C", "p -> C")],
+
+        // Dependency updates (Dependabot/Renovate)
+        PrMergeBotUpdates => &[("p b", "p -> b")],
+        DependencyUpdatesOpen => &[("p g", "p -> g")],
+        PrRunPolicy => &[("p P", "p -> P")],
+
+        // Snooze
+        PrSnooze => &[("z z", "z -> z")],
+        PrSnoozeDay => &[("z d", "z -> d")],
+        PrSnoozeThreeDays => &[("z 3", "z -> 3")],
+        PrSnoozeWeek => &[("z w", "z -> w")],
+        PrUnsnooze => &[("z u", "z -> u")],
+        PrToggleShowSnoozed => &[("z s", "z -> s")],
+        PrMarkAllSeen => &[("p v", "p -> v")],
+
+        // Filter & Search
+        PrCycleFilter => &[("f", "f")],
+        PrClearFilter => &[("F", "F")],
+        PrFilterByAuthor => &[("a", "a")],
+        PrFilterBuilder => &[("p f", "p -> f")],
+        PrCycleStateFilter => &[("p s", "p -> s")],
+        PrCycleSort => &[("S", "S")],
+        // Many terminals report Ctrl+/ as Ctrl+7
+        PrSearch => &[("ctrl+/", "Ctrl+/"), ("ctrl+7", "Ctrl+/")],
+
+        // Watch List
+        PrToggleWatch => &[("w w", "w -> w")],
+        WatchListOpen => &[("w l", "w -> l")],
+
+        // Build Log Operations
+        BuildLogOpen => &[("b l", "b -> l")],
+        ActionsViewOpen => &[("b r", "b -> r")],
+
+        // Diff Viewer
+        DiffViewerOpen => &[("d d", "d -> d")],
+        DiffViewerAddComment => &[("d c", "d -> c")],
+        DiffViewerCommentOnFile => &[("d f", "d -> f")],
+        DiffViewerCommentOnPr => &[("d p", "d -> p")],
+        DiffViewerShowReviewPopup => &[("d r", "d -> r")],
+        DiffViewerToggleBlame => &[("d b", "d -> b")],
+        DiffViewerVisualMode => &[("d v", "d -> v")],
+        DiffViewerSwitchPane => &[("tab", "Tab")],
+        DiffViewerPageDown => &[("ctrl+f", "Ctrl+F")],
+        DiffViewerPageUp => &[("ctrl+b", "Ctrl+B")],
+
+        // Help
+        KeyBindingsToggleView => &[("?", "?")],
+        StatusBarShowMessage => &[("!", "!")],
+
+        // Build Log (view-specific, gated by the active view)
+        BuildLogNextError => &[("n", "n")],
+        BuildLogPrevError => &[("N", "N")],
+        BuildLogToggleTimestamps => &[("t", "t")],
+        BuildLogToggleShowSuccessful => &[("s", "s")],
+        BuildLogExpandAll => &[("e", "e")],
+        BuildLogCollapseAll => &[("E", "E")],
+        BuildLogExport => &[("x", "x")],
+
+        // Conflict Helper (view-specific, gated by the active view)
+        ConflictHelperCopyCommands => &[("y", "y")],
+        ConflictHelperRecreate => &[("R", "R")],
+
+        // Release Notes (view-specific, gated by the active view)
+        ReleaseNotesCopy => &[("y", "y")],
+        ReleaseNotesWrite => &[("x", "x")],
+
+        // Cache Inspector (view-specific, gated by the active view)
+        // A single "d" would shadow the global "d d" (open diff)
+        CacheInspectorDelete => &[("delete", "Del")],
+        CacheInspectorInvalidatePattern => &[("x", "x")],
+        CacheInspectorFilter => &[("/", "/")],
+        CacheInspectorCycleSort => &[("s", "s")],
+
+        // Audit Log (view-specific, gated by the active view)
+        AuditLogToggleScope => &[("s", "s")],
+
+        // Duplicate Updates (view-specific, gated by the active view)
+        DuplicateUpdatesToggle => &[("space", "Space")],
+        DuplicateUpdatesPickAll => &[("a", "a")],
+        DuplicateUpdatesPickNone => &[("u", "u")],
+        DuplicateUpdatesMerge => &[("m", "m")],
+
+        // General
+        GlobalClose => &[("q", "q"), ("esc", "Esc")],
+        GlobalQuit => &[("ctrl+c", "Ctrl+C")],
+
+        // Semantic actions, translated by views from their own keys
+        Confirm | ToggleSelect | SelectAll | DeselectAll => &[],
+        // Toggled with Enter/Space through the build log view
+        BuildLogToggle => &[],
+        // Handled by the comment editor's text input
+        DiffViewerSubmitComment | DiffViewerCancelComment => &[],
+        // Merge bot is not yet tested nor stable
+        MergeBotStart | MergeBotStop | MergeBotAddToQueue => &[],
+        // Palette only
+        RepositoryRemove
+        | DebugShowApiUsage
+        | DebugSetLogLevel
+        | DebugShowPerfStats
+        | DebugShowCacheInspector
+        | DebugCreateBugReport
+        | PrRerunFailedJobs
+        | PrShowAuditLog
+        | PrShowDuplicateUpdates
+        | ReleaseNotesGenerate
+        | AuthSetToken
+        | AuthDeleteToken
+        | AuthListTokens => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_command_is_registered() {
+        let registry = CommandRegistry::new();
+        for id in CommandId::iter() {
+            assert_eq!(registry.get(id).id, id);
+        }
+        assert_eq!(registry.specs().count(), CommandId::iter().count());
+    }
+
+    #[test]
+    fn test_every_binding_parses() {
+        for spec in CommandRegistry::new().specs() {
+            for binding in &spec.bindings {
+                assert!(
+                    parse_key_pattern(&binding.keys).is_some(),
+                    "{:?}: invalid key pattern {:?}",
+                    spec.id,
+                    binding.keys
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_conflicting_default_bindings() {
+        assert_eq!(CommandRegistry::new().conflicts(), vec![]);
+    }
+
+    #[test]
+    fn test_conflict_detection() {
+        let single = parse_key_pattern("p").unwrap();
+        let sequence = parse_key_pattern("p a").unwrap();
+        assert!(starts_sequence(&single, &sequence));
+        assert!(!starts_sequence(
+            &parse_key_pattern("ctrl+p").unwrap(),
+            &sequence
+        ));
+        assert!(CommandScope::Global.overlaps(CommandScope::BuildLog));
+        assert!(!CommandScope::PullRequests.overlaps(CommandScope::DiffViewer));
+    }
+
+    #[test]
+    fn test_every_command_is_reachable() {
+        // Commands reachable by other means than a default binding or the palette
+        let indirect = [
+            CommandId::Confirm,
+            CommandId::ToggleSelect,
+            CommandId::SelectAll,
+            CommandId::DeselectAll,
+            CommandId::BuildLogToggle,
+            CommandId::DiffViewerSubmitComment,
+            CommandId::DiffViewerCancelComment,
+            CommandId::MergeBotStart,
+            CommandId::MergeBotStop,
+            CommandId::MergeBotAddToQueue,
+        ];
+        for spec in CommandRegistry::new().specs() {
+            assert!(
+                !spec.bindings.is_empty() || spec.in_palette || indirect.contains(&spec.id),
+                "{:?} has no binding and is not in the palette",
+                spec.id
+            );
+        }
+    }
+
+    #[test]
+    fn test_tab_prefers_repository_switch() {
+        let keymap = CommandRegistry::new().keymap();
+        let tab: Vec<_> = keymap
+            .bindings()
+            .filter(|binding| binding.keys == "tab")
+            .map(|binding| binding.command)
+            .collect();
+        assert_eq!(
+            tab,
+            vec![CommandId::RepositoryNext, CommandId::DiffViewerSwitchPane]
+        );
+    }
+}
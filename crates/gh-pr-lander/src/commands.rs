@@ -8,6 +8,7 @@
 
 use crate::actions::Action;
 use crate::command_id::CommandId;
+use crate::command_registry::CommandRegistry;
 use crate::keybindings::Keymap;
 
 /// Source of a command - either a static CommandId or a dynamic action
@@ -71,7 +72,7 @@ impl Command {
     /// Get the title for display
     pub fn title(&self) -> &str {
         match &self.source {
-            CommandSource::Static(id) => id.title(),
+            CommandSource::Static(id) => CommandRegistry::global().get(*id).title,
             CommandSource::Dynamic { title, .. } => title,
         }
     }
@@ -79,7 +80,7 @@ impl Command {
     /// Get the description for display
     pub fn description(&self) -> &str {
         match &self.source {
-            CommandSource::Static(id) => id.description(),
+            CommandSource::Static(id) => CommandRegistry::global().get(*id).description,
             CommandSource::Dynamic { description, .. } => description,
         }
     }
@@ -87,7 +88,7 @@ impl Command {
     /// Get the category for grouping
     pub fn category(&self) -> &str {
         match &self.source {
-            CommandSource::Static(id) => id.category(),
+            CommandSource::Static(id) => CommandRegistry::global().get(*id).category,
             CommandSource::Dynamic { category, .. } => category,
         }
     }
@@ -95,7 +96,7 @@ impl Command {
     /// Get the action to dispatch
     pub fn to_action(&self) -> Action {
         match &self.source {
-            CommandSource::Static(id) => id.to_action(),
+            CommandSource::Static(id) => CommandRegistry::global().get(*id).action(),
             CommandSource::Dynamic { action, .. } => (**action).clone(),
         }
     }
@@ -106,7 +107,8 @@ impl Command {
 /// Uses `compact_hint_for_command` to show all keybindings for a command
 /// (e.g., "q/Esc" for GlobalClose instead of just "q")
 pub fn get_palette_commands_with_hints(keymap: &Keymap) -> Vec<Command> {
-    CommandId::palette_command_ids()
+    CommandRegistry::global()
+        .palette_ids()
         .into_iter()
         .map(|id| {
             if let Some(hint) = keymap.compact_hint_for_command(id) {
@@ -138,6 +140,51 @@ pub fn filter_commands(commands: &[Command], query: &str) -> Vec<Command> {
         .collect()
 }
 
+/// Generate dynamic commands applying the saved filters ("Filter: security-bumps")
+pub fn get_saved_filter_commands(
+    filters: &std::collections::BTreeMap<String, gh_pr_config::FilterSpec>,
+) -> Vec<Command> {
+    use crate::actions::{Action, PullRequestAction};
+    use crate::domain_models::filter_spec;
+
+    filters
+        .iter()
+        .map(|(name, spec)| {
+            Command::dynamic(
+                Action::PullRequest(PullRequestAction::ApplySavedFilter(name.clone())),
+                format!("Filter: {}", name),
+                format!("Show only PRs matching {}", filter_spec::to_query(spec)),
+                "Filter",
+            )
+        })
+        .collect()
+}
+
+/// Title prefix of the commands switching to a workspace profile
+pub const PROFILE_COMMAND_PREFIX: &str = "Profile: ";
+
+/// Generate dynamic commands switching to the other workspace profiles ("Profile: oss")
+pub fn get_profile_commands(profile: Option<&crate::state::ActiveProfile>) -> Vec<Command> {
+    use crate::actions::{Action, RepositoryAction};
+
+    let Some(profile) = profile else {
+        return vec![];
+    };
+    profile
+        .names
+        .iter()
+        .filter(|name| **name != profile.name)
+        .map(|name| {
+            Command::dynamic(
+                Action::Repository(RepositoryAction::SwitchProfile(name.clone())),
+                format!("{}{}", PROFILE_COMMAND_PREFIX, name),
+                format!("Show the repositories of the {} profile", name),
+                "Profile",
+            )
+        })
+        .collect()
+}
+
 /// Generate dynamic commands for opening related issues
 ///
 /// Extracts issue references from the given PR text (title + description)
@@ -0,0 +1,106 @@
+//! Benchmarks of the hot paths on generated fixtures
+//!
+//! The lander is a binary crate, so its benchmarks run through `--bench`
+//! instead of a `benches/` target. Each case reports the mean and the fastest
+//! of a fixed number of iterations.
+
+use super::{fixtures, DemoOptions};
+use crate::actions::{Action, PullRequestAction, RepositoryAction};
+use crate::state::{AppState, RepositoryData};
+use crate::store::Store;
+use crate::view_models::PrTableViewModel;
+use gh_diff_viewer::{parse_unified_diff, DiffViewerState};
+use gh_pr_lander_theme::Theme;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Number of actions dispatched in the reducer throughput case
+const REDUCER_ACTIONS: u32 = 500;
+
+/// Run all benchmarks and print the results to stdout
+pub fn run(options: &DemoOptions) {
+    println!(
+        "gh-pr-lander benchmarks (seed {}, {} PRs, {}-line diff, {}-line log)",
+        options.seed, options.prs_per_repo, options.diff_lines, options.log_lines
+    );
+
+    let repo = fixtures::repositories(1, options.seed).remove(0);
+    let prs = fixtures::pull_requests(&repo, options.prs_per_repo, options.seed);
+
+    // View-model rebuild (runs on every render)
+    let repo_data = RepositoryData {
+        prs: prs.clone(),
+        ..Default::default()
+    };
+    let theme = Theme::default();
+    let ui = gh_pr_config::UiConfig::default();
+    measure("PrTableViewModel::from_repo_data", 20, || {
+        PrTableViewModel::from_repo_data(&repo_data, &repo, &theme, &ui)
+    });
+
+    // Diff open: parse and initial viewer state
+    let diff_text = fixtures::unified_diff(options.diff_lines, options.seed);
+    measure("parse_unified_diff + DiffViewerState::new", 5, || {
+        let diff = parse_unified_diff(&diff_text, "base", "head").expect("fixture diff parses");
+        DiffViewerState::new(diff)
+    });
+
+    // Build log parsing
+    let jobs: Vec<(String, String)> = (0..4)
+        .map(|job| {
+            (
+                format!("job-{}", job),
+                fixtures::workflow_log(options.log_lines / 4, options.seed + job),
+            )
+        })
+        .collect();
+    let zip = fixtures::workflow_logs_zip(&jobs);
+    measure("parse_workflow_logs + job_log_to_tree", 5, || {
+        let parsed = gh_actions_log_parser::parse_workflow_logs(&zip).expect("fixture log parses");
+        parsed
+            .jobs
+            .into_iter()
+            .map(gh_actions_log_parser::job_log_to_tree)
+            .collect::<Vec<_>>()
+    });
+
+    // Reducer throughput with a loaded repository
+    let mut store = Store::new(AppState::default());
+    store.dispatch(Action::Repository(RepositoryAction::AddRepository(
+        repo.clone(),
+    )));
+    store.dispatch(Action::PullRequest(PullRequestAction::Loaded {
+        repo,
+        prs,
+        from_cache: None,
+    }));
+    let elapsed = measure("reducer dispatch (NavigateNext)", 1, || {
+        for _ in 0..REDUCER_ACTIONS {
+            store.dispatch(Action::PullRequest(PullRequestAction::NavigateNext));
+        }
+    });
+    println!(
+        "{:<44} {:>10.0} actions/s",
+        "reducer throughput",
+        REDUCER_ACTIONS as f64 / elapsed.as_secs_f64()
+    );
+}
+
+/// Time `iterations` runs of `f`, print mean and fastest, return the mean
+fn measure<T>(name: &str, iterations: u32, mut f: impl FnMut() -> T) -> Duration {
+    let mut total = Duration::ZERO;
+    let mut fastest = Duration::MAX;
+    for _ in 0..iterations {
+        let start = Instant::now();
+        black_box(f());
+        let elapsed = start.elapsed();
+        total += elapsed;
+        fastest = fastest.min(elapsed);
+    }
+    let mean = total / iterations;
+    println!(
+        "{:<44} mean {:>10.2?}  min {:>10.2?}  ({} iterations)",
+        name, mean, fastest, iterations
+    );
+    mean
+}
@@ -0,0 +1,442 @@
+//! Deterministic fixture generators
+//!
+//! All generated data only depends on the seed, so a demo session or a
+//! benchmark run can be reproduced exactly.
+
+use crate::domain_models::{
+    MaturityState, MergeableStatus, Pr, PrState, Repository, ReviewDecision, UpdateType,
+};
+use crate::state::RepoPulse;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use gh_client::{CiState, WorkflowRun, WorkflowRunConclusion, WorkflowRunStatus};
+use std::fmt::Write as _;
+use std::io::Write as _;
+
+/// Small deterministic PRNG (SplitMix64)
+pub struct DemoRng(u64);
+
+impl DemoRng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Derive a generator for a named sub-fixture (e.g. one repository)
+    pub fn derive(seed: u64, name: &str) -> Self {
+        let salt = name.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+        Self(seed ^ salt)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform number in `0..n`
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n.max(1)
+    }
+
+    /// True with the given probability in percent
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.below(100) < percent
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    fn sha(&mut self) -> String {
+        format!(
+            "{:016x}{:016x}{:08x}",
+            self.next_u64(),
+            self.next_u64(),
+            self.next_u64() as u32
+        )
+    }
+}
+
+const ORGS: &[&str] = &["acme", "initech", "umbrella", "hooli", "globex"];
+const REPOS: &[&str] = &[
+    "api-gateway",
+    "billing",
+    "design-system",
+    "infra",
+    "mobile-app",
+    "search",
+    "web",
+];
+const AUTHORS: &[&str] = &[
+    "alice",
+    "bob",
+    "carol",
+    "dependabot[bot]",
+    "dave",
+    "erin",
+    "renovate[bot]",
+];
+const VERBS: &[&str] = &["Add", "Fix", "Refactor", "Remove", "Update", "Speed up"];
+const SUBJECTS: &[&str] = &[
+    "login flow",
+    "cache invalidation",
+    "retry logic",
+    "flaky integration test",
+    "error messages",
+    "dark mode",
+    "pagination",
+    "metrics export",
+];
+/// Dependency bumps of the bot PRs (name, from, to), shared by all repositories
+const BUMPS: &[(&str, &str, &str)] = &[
+    ("serde", "1.0.188", "1.0.190"),
+    ("tokio", "1.35.1", "1.36.0"),
+    ("clap", "4.4.18", "4.5.0"),
+    ("ratatui", "0.25.0", "0.26.0"),
+    ("actions/checkout", "v3", "v4"),
+];
+const STATUSES: &[MergeableStatus] = &[
+    MergeableStatus::Ready,
+    MergeableStatus::Ready,
+    MergeableStatus::Ready,
+    MergeableStatus::NeedsRebase,
+    MergeableStatus::BuildFailed,
+    MergeableStatus::Conflicted,
+    MergeableStatus::Blocked,
+    MergeableStatus::Unknown,
+];
+const REVIEWS: &[ReviewDecision] = &[
+    ReviewDecision::Approved,
+    ReviewDecision::Pending,
+    ReviewDecision::Pending,
+    ReviewDecision::ChangesRequested,
+];
+
+/// Fixed reference time, so fixtures don't depend on the clock
+fn reference_time() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap()
+}
+
+/// Generate `count` distinct repositories
+pub fn repositories(count: usize, seed: u64) -> Vec<Repository> {
+    let mut rng = DemoRng::new(seed);
+    (0..count)
+        .map(|i| {
+            let org = rng.pick(ORGS);
+            let name = REPOS[i % REPOS.len()];
+            let name = match i / REPOS.len() {
+                0 => name.to_string(),
+                round => format!("{}-{}", name, round + 1),
+            };
+            Repository::new(*org, name, "main")
+        })
+        .collect()
+}
+
+/// Generate `count` pull requests for `repo`, newest first
+pub fn pull_requests(repo: &Repository, count: usize, seed: u64) -> Vec<Pr> {
+    let mut rng = DemoRng::derive(seed, &repo.full_display_name());
+    // Separate generator, so bot PRs don't change the other fixtures
+    let mut bump_rng = DemoRng::derive(seed, &format!("{}:bumps", repo.full_display_name()));
+    let now = reference_time();
+    let mut prs: Vec<Pr> = Vec::with_capacity(count);
+
+    for i in 0..count {
+        let number = 100 + count - i;
+        let subject = rng.pick(SUBJECTS);
+        let title = format!("{} {}", rng.pick(VERBS), subject);
+        let head_branch = format!("feature/{}-{}", subject.replace(' ', "-"), number);
+
+        // Some PRs are stacked on the previous one
+        let base_branch = match prs.last() {
+            Some(parent) if rng.chance(8) => parent.head_branch.clone(),
+            _ => repo.branch.clone(),
+        };
+
+        let mut pr = Pr::new(number, title, *rng.pick(AUTHORS), rng.sha())
+            .with_branches(head_branch, base_branch)
+            .with_html_url(format!("{}/pull/{}", repo.web_url(), number));
+        pr.body = format!("Generated demo PR #{} for {}.", number, subject);
+        pr.comments = rng.below(12) as usize;
+        pr.mergeable = *rng.pick(STATUSES);
+        pr.needs_rebase = pr.mergeable == MergeableStatus::NeedsRebase;
+        pr.review_decision = *rng.pick(REVIEWS);
+        pr.maturity = if rng.chance(15) {
+            MaturityState::Draft
+        } else {
+            MaturityState::Ready
+        };
+        pr.additions = rng.below(800) as usize;
+        pr.deletions = rng.below(400) as usize;
+        pr.changed_files = Some(1 + (pr.additions + pr.deletions) / 40);
+        pr.created_at = now - Duration::minutes((i as i64 + 1) * 90 + rng.below(60) as i64);
+        pr.updated_at = pr.created_at + Duration::minutes(rng.below(24 * 60) as i64);
+        pr.state = PrState::Open;
+        if pr.author.ends_with("[bot]") {
+            pr = bot_bump(pr, &mut bump_rng);
+        }
+        prs.push(pr);
+    }
+
+    prs
+}
+
+/// Turn a PR of a bot author into a dependency bump in the bot's format
+fn bot_bump(mut pr: Pr, rng: &mut DemoRng) -> Pr {
+    let (name, from, to) = *rng.pick(BUMPS);
+    let slug = name.replace('/', "-");
+    let is_action = name.contains('/');
+    if pr.author.starts_with("dependabot") {
+        let ecosystem = if is_action { "github_actions" } else { "cargo" };
+        pr.title = format!("Bump {} from {} to {}", name, from, to);
+        pr.head_branch = format!("dependabot/{}/{}-{}", ecosystem, slug, to);
+        pr.body = format!("Bumps {} from {} to {}.", name, from, to);
+    } else {
+        pr.title = if is_action {
+            format!("Update {} action to {}", name, to)
+        } else {
+            format!("Update Rust crate {} to {}", name, to)
+        };
+        pr.head_branch = format!("renovate/{}-{}", slug, to);
+        pr.body = format!(
+            "| Package | Type | Update | Change |\n|---|---|---|---|\n| {} | dependencies | {} | `{}` -> `{}` |\n",
+            name,
+            UpdateType::between(from, to).map_or("", UpdateType::label),
+            from,
+            to
+        );
+    }
+    pr.with_detected_dependency_update()
+}
+
+const WORKFLOWS: &[&str] = &["CI", "Release", "Security audit", "Docs"];
+const EVENTS: &[&str] = &["push", "push", "push", "schedule", "workflow_dispatch"];
+
+/// Generate `count` workflow runs on the branch of `repo`, newest first
+pub fn workflow_runs(repo: &Repository, count: usize, seed: u64) -> Vec<WorkflowRun> {
+    let mut rng = DemoRng::derive(seed, &format!("{}:runs", repo.full_display_name()));
+    let now = reference_time();
+
+    (0..count)
+        .map(|i| {
+            let created_at = now - Duration::minutes(i as i64 * 45 + rng.below(30) as i64);
+            let (status, conclusion) = if i == 0 && rng.chance(50) {
+                (WorkflowRunStatus::InProgress, None)
+            } else if rng.chance(20) {
+                (
+                    WorkflowRunStatus::Completed,
+                    Some(WorkflowRunConclusion::Failure),
+                )
+            } else {
+                (
+                    WorkflowRunStatus::Completed,
+                    Some(WorkflowRunConclusion::Success),
+                )
+            };
+            let id = 9_000_000 + (count - i) as u64;
+            WorkflowRun {
+                id,
+                name: rng.pick(WORKFLOWS).to_string(),
+                status,
+                conclusion,
+                head_sha: rng.sha(),
+                head_branch: Some(repo.branch.clone()),
+                event: rng.pick(EVENTS).to_string(),
+                html_url: format!("{}/actions/runs/{}", repo.web_url(), id),
+                created_at,
+                updated_at: created_at + Duration::seconds(60 + rng.below(900) as i64),
+                run_attempt: 1,
+            }
+        })
+        .collect()
+}
+
+/// CI state of the head of the branch of `repo`, matching its newest run
+pub fn branch_ci_state(repo: &Repository, seed: u64) -> CiState {
+    match workflow_runs(repo, 1, seed)
+        .first()
+        .map(|run| run.conclusion)
+    {
+        Some(Some(WorkflowRunConclusion::Success)) => CiState::Success,
+        Some(Some(_)) => CiState::Failure,
+        Some(None) => CiState::Pending,
+        None => CiState::Unknown,
+    }
+}
+
+/// PRs opened and merged in `repo` over the last week
+pub fn pulse(repo: &Repository, seed: u64) -> RepoPulse {
+    let mut rng = DemoRng::derive(seed, &format!("{}:pulse", repo.full_display_name()));
+    let opened = rng.below(15);
+    RepoPulse {
+        opened,
+        merged: rng.below(opened + 3),
+    }
+}
+
+/// Generate a unified diff with about `lines` changed and context lines
+pub fn unified_diff(lines: usize, seed: u64) -> String {
+    const LINES_PER_FILE: usize = 500;
+    const LINES_PER_HUNK: usize = 50;
+
+    let mut rng = DemoRng::new(seed);
+    let mut diff = String::new();
+    let files = lines.div_ceil(LINES_PER_FILE).max(1);
+
+    for file in 0..files {
+        let path = format!("src/module_{}/file_{}.rs", file % 20, file);
+        let _ = write!(
+            diff,
+            "diff --git a/{path} b/{path}\nindex {}..{} 100644\n--- a/{path}\n+++ b/{path}\n",
+            &rng.sha()[..7],
+            &rng.sha()[..7],
+        );
+
+        let mut old_line = 1;
+        let mut new_line = 1;
+        for hunk in 0..LINES_PER_FILE / LINES_PER_HUNK {
+            let mut body = String::new();
+            let (mut old_count, mut new_count) = (0, 0);
+            for line in 0..LINES_PER_HUNK {
+                let text = format!("    let value_{}_{} = compute({});", hunk, line, file);
+                match rng.below(10) {
+                    0..=5 => {
+                        let _ = writeln!(body, " {}", text);
+                        old_count += 1;
+                        new_count += 1;
+                    }
+                    6..=7 => {
+                        let _ = writeln!(body, "+{}", text);
+                        new_count += 1;
+                    }
+                    _ => {
+                        let _ = writeln!(body, "-{}", text);
+                        old_count += 1;
+                    }
+                }
+            }
+            let _ = writeln!(
+                diff,
+                "@@ -{},{} +{},{} @@ fn section_{}()",
+                old_line, old_count, new_line, new_count, hunk
+            );
+            diff.push_str(&body);
+            // Leave a gap of unchanged lines between hunks
+            old_line += old_count + 10;
+            new_line += new_count + 10;
+        }
+    }
+
+    diff
+}
+
+/// Generate a GitHub Actions job log with `lines` lines
+pub fn workflow_log(lines: usize, seed: u64) -> String {
+    const LINES_PER_STEP: usize = 500;
+
+    let mut rng = DemoRng::new(seed);
+    let mut log = String::new();
+    let timestamp = "2025-01-15T12:00:00.0000000Z";
+
+    for line in 0..lines {
+        let step = line / LINES_PER_STEP;
+        match line % LINES_PER_STEP {
+            0 => {
+                let _ = writeln!(log, "{} ##[group]Run step {}", timestamp, step);
+            }
+            n if n == LINES_PER_STEP - 1 => {
+                let _ = writeln!(log, "{} ##[endgroup]", timestamp);
+            }
+            _ if rng.chance(1) => {
+                let _ = writeln!(
+                    log,
+                    "{} ##[error]\x1b[31mtest step_{}::case_{} failed\x1b[0m",
+                    timestamp, step, line
+                );
+            }
+            _ => {
+                let _ = writeln!(
+                    log,
+                    "{} \x1b[32m   Compiling\x1b[0m crate_{} v0.{}.0",
+                    timestamp,
+                    rng.below(300),
+                    step
+                );
+            }
+        }
+    }
+
+    log
+}
+
+/// Pack job logs into a ZIP archive as downloaded from the GitHub API
+pub fn workflow_logs_zip(jobs: &[(String, String)]) -> Vec<u8> {
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::FileOptions::<()>::default();
+    for (index, (name, content)) in jobs.iter().enumerate() {
+        zip.start_file(format!("{}_{}.txt", index, name), options)
+            .expect("writing to an in-memory zip cannot fail");
+        zip.write_all(content.as_bytes())
+            .expect("writing to an in-memory zip cannot fail");
+    }
+    zip.finish()
+        .expect("writing to an in-memory zip cannot fail")
+        .into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixtures_are_deterministic() {
+        let repos = repositories(10, 7);
+        assert_eq!(repos.len(), 10);
+        assert_eq!(
+            repos.iter().map(|r| r.display_name()).collect::<Vec<_>>(),
+            repositories(10, 7)
+                .iter()
+                .map(|r| r.display_name())
+                .collect::<Vec<_>>()
+        );
+
+        let prs = pull_requests(&repos[0], 50, 7);
+        let again = pull_requests(&repos[0], 50, 7);
+        assert_eq!(prs.len(), 50);
+        assert!(prs
+            .iter()
+            .zip(&again)
+            .all(|(a, b)| a.title == b.title && a.head_sha == b.head_sha));
+        assert_ne!(prs[0].head_sha, pull_requests(&repos[0], 50, 8)[0].head_sha);
+
+        let runs = workflow_runs(&repos[0], 10, 7);
+        assert_eq!(runs.len(), 10);
+        assert_eq!(
+            runs.iter().map(|r| r.id).collect::<Vec<_>>(),
+            workflow_runs(&repos[0], 10, 7)
+                .iter()
+                .map(|r| r.id)
+                .collect::<Vec<_>>()
+        );
+        assert!(runs.windows(2).all(|w| w[0].created_at >= w[1].created_at));
+    }
+
+    #[test]
+    fn test_unified_diff_parses() {
+        let diff = unified_diff(2_000, 1);
+        let parsed = gh_diff_viewer::parse_unified_diff(&diff, "base", "head").unwrap();
+        assert_eq!(parsed.files.len(), 4);
+    }
+
+    #[test]
+    fn test_workflow_log_parses() {
+        let zip = workflow_logs_zip(&[("build".to_string(), workflow_log(1_000, 1))]);
+        let parsed = gh_actions_log_parser::parse_workflow_logs(&zip).unwrap();
+        assert_eq!(parsed.jobs.len(), 1);
+        let job = gh_actions_log_parser::job_log_to_tree(parsed.jobs[0].clone());
+        assert_eq!(job.steps.len(), 2);
+    }
+}
@@ -0,0 +1,35 @@
+//! Demo and benchmark mode
+//!
+//! `--demo` boots the app with generated repositories, PRs, diffs and build
+//! logs instead of talking to GitHub, so the UI can be explored, screenshotted
+//! and debugged without credentials. `--bench` times the hot paths
+//! (view-model rebuild, diff open, log parsing, reducer throughput) on the
+//! same fixtures and prints the results.
+//!
+//! All fixtures are derived from `--seed`, so runs are reproducible. The
+//! flags themselves are parsed in `cli`.
+
+pub mod bench;
+pub mod fixtures;
+
+/// Sizes and seed of the generated data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DemoOptions {
+    pub seed: u64,
+    pub repos: usize,
+    pub prs_per_repo: usize,
+    pub diff_lines: usize,
+    pub log_lines: usize,
+}
+
+impl Default for DemoOptions {
+    fn default() -> Self {
+        Self {
+            seed: 42,
+            repos: 5,
+            prs_per_repo: 40,
+            diff_lines: 50_000,
+            log_lines: 100_000,
+        }
+    }
+}
@@ -8,34 +8,195 @@
 //! - Event::ClientReady triggers LoadRecentRepositories
 //! - LoadRecentRepositories flows through middleware and is handled by RepositoryMiddleware
 
-use crate::actions::Action;
-use std::sync::mpsc::Sender;
+use crate::actions::{Action, PullRequestAction, StatusBarAction};
+use crate::domain_models::{OperationType, Repository, WatchedPr};
+use crate::state::OperationId;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
 
 /// Dispatcher for sending actions through the middleware chain
 ///
 /// Actions dispatched here re-enter the middleware chain from the beginning,
 /// allowing middleware to trigger other middleware handlers.
-#[derive(Clone)]
-pub struct Dispatcher {
-    action_tx: Sender<Action>,
-}
+pub type Dispatcher = gh_redux::Dispatcher<Action>;
 
-impl Dispatcher {
-    /// Create a new dispatcher with the action channel
+/// Status bar operations of long-running middleware work
+pub trait OperationDispatcher {
+    /// Show a long-running operation in the status bar until the returned
+    /// guard is dropped
     ///
-    /// The action_tx should be a clone of the channel that feeds into the background worker,
-    /// so dispatched actions re-enter the middleware chain.
-    pub fn new(action_tx: Sender<Action>) -> Self {
-        Self { action_tx }
+    /// Move the guard into the spawned task, so the operation ends on every
+    /// exit path (success, error or early return).
+    fn start_operation(
+        &self,
+        label: impl Into<String>,
+        source: impl Into<String>,
+    ) -> OperationGuard;
+}
+
+impl OperationDispatcher for Dispatcher {
+    fn start_operation(
+        &self,
+        label: impl Into<String>,
+        source: impl Into<String>,
+    ) -> OperationGuard {
+        let id = OperationId::next();
+        self.dispatch(Action::StatusBar(StatusBarAction::started(
+            id, label, source,
+        )));
+        OperationGuard {
+            id,
+            dispatcher: self.clone(),
+        }
     }
+}
+
+/// Finishes its status bar operation when dropped
+pub struct OperationGuard {
+    id: OperationId,
+    dispatcher: Dispatcher,
+}
 
-    /// Dispatch an action to be processed through the middleware chain
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        self.dispatcher
+            .dispatch(Action::StatusBar(StatusBarAction::finished(self.id)));
+    }
+}
+
+/// Mutating PR operations in flight, shared with the tasks running them
+///
+/// Pressing a key twice must not merge or comment twice, so an operation is
+/// refused while the same operation runs on the same PR.
+#[derive(Debug, Clone, Default)]
+pub struct InFlightOperations {
+    running: Arc<Mutex<HashSet<(WatchedPr, OperationType)>>>,
+}
+
+impl InFlightOperations {
+    /// Mark `operation` on a PR as in flight until the returned guard is dropped
     ///
-    /// The action will re-enter the middleware chain from the beginning,
-    /// ensuring all middleware can observe and react to it.
-    pub fn dispatch(&self, action: Action) {
-        if let Err(e) = self.action_tx.send(action) {
-            log::error!("Dispatcher: failed to send action: {}", e);
+    /// Returns `None` and tells the user if the operation already runs on
+    /// the PR. Dispatching the operation's start action is up to the caller.
+    pub fn begin(
+        &self,
+        repo: &Repository,
+        pr_number: usize,
+        operation: OperationType,
+        dispatcher: &Dispatcher,
+    ) -> Option<InFlightGuard> {
+        let key = (repo.watched_pr(pr_number), operation);
+        let inserted = self
+            .running
+            .lock()
+            .map(|mut running| running.insert(key.clone()))
+            .unwrap_or(true);
+        if !inserted {
+            log::warn!("Dropping duplicate {} of {}", operation.noun(), key.0);
+            dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                format!(
+                    "{} already in progress for #{}",
+                    operation.noun(),
+                    pr_number
+                ),
+                operation.source(),
+            )));
+            return None;
         }
+        Some(InFlightGuard {
+            running: Arc::clone(&self.running),
+            key,
+            repo: repo.clone(),
+            pr_number,
+            dispatcher: dispatcher.clone(),
+        })
+    }
+
+    /// Whether `operation` runs on the PR
+    #[cfg(test)]
+    pub fn is_running(
+        &self,
+        repo: &Repository,
+        pr_number: usize,
+        operation: OperationType,
+    ) -> bool {
+        self.running
+            .lock()
+            .is_ok_and(|running| running.contains(&(repo.watched_pr(pr_number), operation)))
+    }
+}
+
+/// Keeps a PR operation in flight, ends it when dropped
+///
+/// Move the guard into the spawned task, like an [`OperationGuard`].
+pub struct InFlightGuard {
+    running: Arc<Mutex<HashSet<(WatchedPr, OperationType)>>>,
+    key: (WatchedPr, OperationType),
+    repo: Repository,
+    pr_number: usize,
+    dispatcher: Dispatcher,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if let Ok(mut running) = self.running.lock() {
+            running.remove(&self.key);
+        }
+        self.dispatcher
+            .dispatch(Action::PullRequest(PullRequestAction::OperationFinished {
+                repo: self.repo.clone(),
+                pr_number: self.pr_number,
+                operation: self.key.1,
+            }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_duplicate_operation_is_refused_until_finished() {
+        let (tx, rx) = mpsc::channel();
+        let dispatcher = Dispatcher::new(tx);
+        let repo = Repository::new("org", "repo", "main");
+        let in_flight = InFlightOperations::default();
+
+        let guard = in_flight.begin(&repo, 123, OperationType::Merge, &dispatcher);
+        assert!(guard.is_some());
+        assert!(in_flight.is_running(&repo, 123, OperationType::Merge));
+        // Other operations and PRs are independent
+        assert!(in_flight
+            .begin(&repo, 123, OperationType::Comment, &dispatcher)
+            .is_some());
+        assert!(in_flight
+            .begin(&repo, 124, OperationType::Merge, &dispatcher)
+            .is_some());
+        let _ = rx.try_iter().count();
+
+        assert!(in_flight
+            .begin(&repo, 123, OperationType::Merge, &dispatcher)
+            .is_none());
+        let notices: Vec<Action> = rx.try_iter().collect();
+        assert!(matches!(
+            notices.as_slice(),
+            [Action::StatusBar(StatusBarAction::Push { message, .. })]
+                if message == "merge already in progress for #123"
+        ));
+
+        drop(guard);
+        assert!(!in_flight.is_running(&repo, 123, OperationType::Merge));
+        assert!(matches!(
+            rx.try_iter().next(),
+            Some(Action::PullRequest(PullRequestAction::OperationFinished {
+                pr_number: 123,
+                operation: OperationType::Merge,
+                ..
+            }))
+        ));
+        assert!(in_flight
+            .begin(&repo, 123, OperationType::Merge, &dispatcher)
+            .is_some());
     }
 }
@@ -0,0 +1,165 @@
+//! PR attention
+//!
+//! Why a PR waits for the current user, used by the attention sort. A PR
+//! needs attention when the user requested changes and it was updated since,
+//! when the user's review is requested, or when CI fails on the user's own
+//! PR. Reviewer data comes from a batched GraphQL fetch, so PRs whose
+//! reviewers are not known yet only count for failing CI.
+
+use super::{MergeableStatus, Pr, ReviewDecision};
+use std::cmp::Ordering;
+
+/// Why a PR needs the current user, most urgent first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Attention {
+    /// The user requested changes and the PR was updated since
+    ChangesRequested,
+    /// The user's review is requested
+    ReviewRequested,
+    /// CI fails on a PR of the user
+    CiFailing,
+}
+
+impl Attention {
+    /// Why `pr` needs `login`, if it does
+    pub fn of(pr: &Pr, login: &str) -> Option<Self> {
+        if let Some(reviewers) = &pr.reviewers {
+            let changes_addressed = reviewers.latest_review_of(login).is_some_and(|review| {
+                review.state == ReviewDecision::ChangesRequested
+                    && pr.updated_at > review.submitted_at
+            });
+            if changes_addressed {
+                return Some(Self::ChangesRequested);
+            }
+            if reviewers.is_requested_from(login) {
+                return Some(Self::ReviewRequested);
+            }
+        }
+        (pr.author.eq_ignore_ascii_case(login) && pr.mergeable == MergeableStatus::BuildFailed)
+            .then_some(Self::CiFailing)
+    }
+
+    /// Short reason shown next to the PR
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Self::ChangesRequested => "changes requested",
+            Self::ReviewRequested => "review requested",
+            Self::CiFailing => "CI failing",
+        }
+    }
+}
+
+/// Order of two PRs in the attention sort of `login`
+///
+/// PRs needing attention come first by urgency, the rest follows. Within a
+/// group the PR waiting longest (created first) comes first, ties go to the
+/// lower number so the order is stable across reloads.
+pub fn attention_order(a: &Pr, b: &Pr, login: &str) -> Ordering {
+    // PRs without attention rank after all others
+    let rank = |pr: &Pr| Attention::of(pr, login).map_or(u8::MAX, |attention| attention as u8);
+    rank(a)
+        .cmp(&rank(b))
+        .then_with(|| a.created_at.cmp(&b.created_at))
+        .then_with(|| a.number.cmp(&b.number))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, TimeZone, Utc};
+    use gh_client::types::{LatestReview, PrReviewers};
+
+    fn at(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, day, 12, 0, 0).unwrap()
+    }
+
+    fn pr(number: usize, author: &str, created: u32) -> Pr {
+        let mut pr = Pr::new(number, format!("PR {}", number), author, "sha");
+        pr.created_at = at(created);
+        pr.updated_at = at(created);
+        pr
+    }
+
+    fn requested_from(mut pr: Pr, login: &str) -> Pr {
+        pr.reviewers = Some(PrReviewers {
+            number: pr.number as u64,
+            requested: vec![login.to_string()],
+            ..Default::default()
+        });
+        pr
+    }
+
+    fn reviewed(mut pr: Pr, login: &str, state: ReviewDecision, day: u32) -> Pr {
+        pr.reviewers = Some(PrReviewers {
+            number: pr.number as u64,
+            latest_reviews: vec![LatestReview {
+                author: login.to_string(),
+                state,
+                submitted_at: at(day),
+            }],
+            ..Default::default()
+        });
+        pr
+    }
+
+    fn failing(mut pr: Pr) -> Pr {
+        pr.mergeable = MergeableStatus::BuildFailed;
+        pr
+    }
+
+    fn sorted(mut prs: Vec<Pr>) -> Vec<usize> {
+        prs.sort_by(|a, b| attention_order(a, b, "me"));
+        prs.iter().map(|pr| pr.number).collect()
+    }
+
+    #[test]
+    fn test_changes_requested_counts_once_updated_since() {
+        let mut updated = reviewed(pr(1, "bob", 1), "me", ReviewDecision::ChangesRequested, 2);
+        updated.updated_at = at(3);
+        let waiting = reviewed(pr(2, "bob", 1), "me", ReviewDecision::ChangesRequested, 2);
+        let approved = reviewed(pr(3, "bob", 1), "ME", ReviewDecision::Approved, 2);
+
+        assert_eq!(
+            Attention::of(&updated, "Me"),
+            Some(Attention::ChangesRequested)
+        );
+        assert_eq!(Attention::of(&waiting, "me"), None);
+        assert_eq!(Attention::of(&approved, "me"), None);
+    }
+
+    #[test]
+    fn test_ci_failing_counts_on_own_prs_only() {
+        assert_eq!(
+            Attention::of(&failing(pr(1, "me", 1)), "me"),
+            Some(Attention::CiFailing)
+        );
+        assert_eq!(Attention::of(&failing(pr(2, "bob", 1)), "me"), None);
+        assert_eq!(Attention::of(&pr(3, "me", 1), "me"), None);
+    }
+
+    #[test]
+    fn test_groups_come_in_order_of_urgency() {
+        let mut changes = reviewed(pr(1, "bob", 5), "me", ReviewDecision::ChangesRequested, 6);
+        changes.updated_at = at(7);
+        let prs = vec![
+            pr(4, "bob", 1),
+            failing(pr(3, "me", 2)),
+            requested_from(pr(2, "bob", 3), "me"),
+            changes,
+        ];
+        assert_eq!(sorted(prs), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_ties_go_to_the_oldest_then_the_lowest_number() {
+        let prs = vec![
+            requested_from(pr(7, "bob", 2), "me"),
+            requested_from(pr(9, "bob", 1), "me"),
+            requested_from(pr(8, "bob", 2), "me"),
+            pr(3, "bob", 4),
+            pr(1, "bob", 4),
+            pr(2, "bob", 3),
+        ];
+        assert_eq!(sorted(prs), vec![9, 7, 8, 2, 1, 3]);
+    }
+}
@@ -0,0 +1,64 @@
+//! PR author names
+//!
+//! Bots show up under several logins (`dependabot[bot]`, `app/dependabot`,
+//! `dependabot-preview[bot]`); normalizing them keeps filtering, grouping and
+//! bot detection consistent.
+
+/// Bots known under several logins, by their normalized name
+const BOT_FAMILIES: [&str; 2] = ["dependabot", "renovate"];
+
+/// Normalized author login (`dependabot-preview[bot]` → `dependabot`)
+///
+/// The `[bot]` suffix and `app/` prefix are dropped and variants of known
+/// bots are folded into the bot's name. Human logins are kept as they are.
+pub fn normalize_author(login: &str) -> String {
+    let login = login.trim();
+    let login = login.strip_prefix("app/").unwrap_or(login);
+    let login = login.strip_suffix("[bot]").unwrap_or(login);
+
+    let lower = login.to_ascii_lowercase();
+    BOT_FAMILIES
+        .iter()
+        .find(|family| lower.starts_with(*family))
+        .map_or_else(|| login.to_string(), |family| family.to_string())
+}
+
+/// Whether `login` is one of the Dependabot accounts
+pub fn is_dependabot(login: &str) -> bool {
+    normalize_author(login) == "dependabot"
+}
+
+/// Whether `login` is one of the Renovate accounts
+pub fn is_renovate(login: &str) -> bool {
+    normalize_author(login) == "renovate"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bot_logins_are_folded() {
+        for login in [
+            "dependabot[bot]",
+            "dependabot",
+            "app/dependabot",
+            "dependabot-preview[bot]",
+            "Dependabot[bot]",
+        ] {
+            assert_eq!(normalize_author(login), "dependabot", "{}", login);
+            assert!(is_dependabot(login));
+        }
+        assert_eq!(normalize_author("renovate[bot]"), "renovate");
+        assert_eq!(normalize_author("renovate-bot"), "renovate");
+        assert!(is_renovate("app/renovate"));
+    }
+
+    #[test]
+    fn test_humans_and_other_bots_are_kept() {
+        assert_eq!(normalize_author("sassman"), "sassman");
+        assert_eq!(normalize_author("github-actions[bot]"), "github-actions");
+        assert!(!is_dependabot("sassman"));
+        assert!(!is_renovate("alice"));
+    }
+}
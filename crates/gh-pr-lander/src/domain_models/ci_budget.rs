@@ -0,0 +1,220 @@
+//! CI time budget
+//!
+//! How far the running checks of a PR are, measured against how long the
+//! checks of its repository usually take (see [`gh_pr_config::CiDurations`]).
+//! Without history only the elapsed time is known. Checks running past the
+//! usual duration are overdue rather than counting down below zero.
+
+use chrono::{DateTime, Utc};
+use gh_client::CheckResult;
+use gh_pr_config::{CheckDuration, CiDurations};
+use serde::{Deserialize, Serialize};
+
+/// Running checks of a PR and their usual duration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CiBudget {
+    /// Start of the earliest check of the head commit
+    pub started_at: DateTime<Utc>,
+    /// Seconds the checks usually take (None without history)
+    pub usual_seconds: Option<u64>,
+}
+
+/// Progress of running checks at some point in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiEta {
+    /// No history, only the elapsed seconds are known
+    Elapsed(u64),
+    /// Within the usual duration
+    OnTrack { elapsed: u64, usual: u64 },
+    /// Past the usual duration
+    Overdue { elapsed: u64, usual: u64 },
+}
+
+impl CiBudget {
+    /// Budget of the checks of a commit, `None` unless one is running
+    pub fn of_checks(checks: &[CheckResult], usual_seconds: Option<u64>) -> Option<Self> {
+        if !checks.iter().any(CheckResult::is_running) {
+            return None;
+        }
+        let started_at = checks.iter().filter_map(|check| check.started_at).min()?;
+        Some(Self {
+            started_at,
+            usual_seconds,
+        })
+    }
+
+    /// Progress at `now`
+    pub fn eta(&self, now: DateTime<Utc>) -> CiEta {
+        let elapsed = u64::try_from((now - self.started_at).num_seconds()).unwrap_or(0);
+        match self.usual_seconds {
+            None => CiEta::Elapsed(elapsed),
+            Some(usual) if elapsed > usual => CiEta::Overdue { elapsed, usual },
+            Some(usual) => CiEta::OnTrack { elapsed, usual },
+        }
+    }
+}
+
+/// Record the passed checks of a commit and get the budget of its running ones
+///
+/// `repo` keys the history, returns whether it learned a new run.
+pub fn learn_ci_budget(
+    history: &mut CiDurations,
+    repo: &str,
+    checks: &[CheckResult],
+) -> (Option<CiBudget>, bool) {
+    let mut learned = false;
+    for check in checks {
+        if let Some(seconds) = check.passed_seconds() {
+            let run = CheckDuration {
+                id: check.id,
+                seconds,
+            };
+            learned |= history.record(repo, &check.name, run);
+        }
+    }
+    let usual = history.usual(repo, checks.iter().map(|check| check.name.as_str()));
+    (CiBudget::of_checks(checks, usual), learned)
+}
+
+impl CiEta {
+    pub fn is_overdue(&self) -> bool {
+        matches!(self, Self::Overdue { .. })
+    }
+
+    /// Compact progress for the status column (e.g., "6/14m")
+    pub fn short(&self) -> String {
+        match *self {
+            Self::Elapsed(elapsed) => format!("{}m", minutes(elapsed)),
+            Self::OnTrack { elapsed, usual } | Self::Overdue { elapsed, usual } => {
+                format!("{}/{}m", minutes(elapsed), minutes(usual).max(1))
+            }
+        }
+    }
+
+    /// Progress in words for the details (e.g., "6m of usually 14m, ~8m left")
+    pub fn describe(&self) -> String {
+        match *self {
+            Self::Elapsed(elapsed) => {
+                format!("running {}m, no earlier runs to compare", minutes(elapsed))
+            }
+            Self::OnTrack { elapsed, usual } => format!(
+                "running {}m of usually {}m, ~{}m left",
+                minutes(elapsed),
+                minutes(usual).max(1),
+                minutes(usual - elapsed).max(1)
+            ),
+            Self::Overdue { elapsed, usual } => format!(
+                "running {}m, overdue (usually {}m)",
+                minutes(elapsed),
+                minutes(usual).max(1)
+            ),
+        }
+    }
+}
+
+/// Whole minutes, rounded to the nearest
+fn minutes(seconds: u64) -> u64 {
+    (seconds + 30) / 60
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+    use gh_client::types::{CheckConclusion, CheckRunStatus};
+
+    fn check(started: Option<i64>, completed: Option<i64>) -> CheckResult {
+        named(1, "test", started, completed)
+    }
+
+    /// Check that started and passed the given minutes ago
+    fn named(id: u64, name: &str, started: Option<i64>, completed: Option<i64>) -> CheckResult {
+        let at = |minutes| Utc::now() - TimeDelta::minutes(minutes);
+        CheckResult {
+            id,
+            name: name.to_string(),
+            status: match completed {
+                Some(_) => CheckRunStatus::Completed,
+                None => CheckRunStatus::InProgress,
+            },
+            conclusion: completed.map(|_| CheckConclusion::Success),
+            started_at: started.map(at),
+            completed_at: completed.map(at),
+            required: true,
+        }
+    }
+
+    #[test]
+    fn test_budget_only_while_a_check_runs() {
+        let done = [check(Some(20), Some(10)), check(None, None)];
+        assert_eq!(CiBudget::of_checks(&done, Some(840)), None);
+
+        let running = [check(Some(20), Some(10)), check(Some(5), None)];
+        let budget = CiBudget::of_checks(&running, Some(840)).unwrap();
+        assert_eq!(budget.started_at, running[0].started_at.unwrap());
+    }
+
+    #[test]
+    fn test_learned_runs_set_the_budget_of_later_commits() {
+        let mut history = CiDurations::default();
+        let passed = [
+            named(1, "lint", Some(30), Some(28)),
+            named(2, "test", Some(30), Some(16)),
+        ];
+        assert_eq!(
+            learn_ci_budget(&mut history, "github.com/acme/api", &passed),
+            (None, true)
+        );
+        // Fetching the same checks again learns nothing new
+        assert!(!learn_ci_budget(&mut history, "github.com/acme/api", &passed).1);
+
+        let running = [
+            named(3, "lint", Some(6), Some(4)),
+            named(4, "test", Some(6), None),
+        ];
+        let (budget, learned) = learn_ci_budget(&mut history, "github.com/acme/api", &running);
+        assert!(learned);
+        assert_eq!(budget.unwrap().usual_seconds, Some(14 * 60));
+
+        let (budget, _) = learn_ci_budget(&mut history, "github.com/acme/web", &running[1..]);
+        assert_eq!(budget.unwrap().usual_seconds, None);
+    }
+
+    #[test]
+    fn test_eta_counts_up_to_the_usual_duration_then_overdue() {
+        let started_at = Utc::now();
+        let at = |minutes| started_at + TimeDelta::minutes(minutes);
+        let budget = CiBudget {
+            started_at,
+            usual_seconds: Some(14 * 60),
+        };
+
+        let on_track = budget.eta(at(6));
+        assert_eq!(on_track.short(), "6/14m");
+        assert_eq!(on_track.describe(), "running 6m of usually 14m, ~8m left");
+        assert!(!on_track.is_overdue());
+
+        let overdue = budget.eta(at(18));
+        assert!(overdue.is_overdue());
+        assert_eq!(overdue.short(), "18/14m");
+        assert_eq!(overdue.describe(), "running 18m, overdue (usually 14m)");
+    }
+
+    #[test]
+    fn test_eta_without_history_shows_elapsed_only() {
+        let started_at = Utc::now();
+        let budget = CiBudget {
+            started_at,
+            usual_seconds: None,
+        };
+        let eta = budget.eta(started_at + TimeDelta::minutes(3));
+        assert_eq!(eta, CiEta::Elapsed(180));
+        assert_eq!(eta.short(), "3m");
+        assert_eq!(eta.describe(), "running 3m, no earlier runs to compare");
+        // A clock behind the check's start does not go negative
+        assert_eq!(
+            budget.eta(started_at - TimeDelta::minutes(1)),
+            CiEta::Elapsed(0)
+        );
+    }
+}
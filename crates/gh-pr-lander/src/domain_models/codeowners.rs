@@ -0,0 +1,304 @@
+//! CODEOWNERS
+//!
+//! Who owns which paths of a repository, parsed from its `CODEOWNERS` file.
+//! Patterns follow the gitignore rules as GitHub applies them: the last
+//! matching rule wins, and a rule without owners leaves its paths unowned.
+//! Reviewers are suggested by how many of a PR's changed files they own.
+
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Locations GitHub looks for the file, the first one found is used
+pub const CODEOWNERS_PATHS: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// An owner named in a rule
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Owner {
+    /// User login, without the `@`
+    User(String),
+    /// Team as `org/slug`, without the `@`
+    Team(String),
+    /// Email address of a user, GitHub resolves it to an account
+    Email(String),
+}
+
+impl Owner {
+    fn parse(token: &str) -> Option<Self> {
+        match token.strip_prefix('@') {
+            Some("") => None,
+            Some(name) if name.contains('/') => Some(Self::Team(name.to_string())),
+            Some(login) => Some(Self::User(login.to_string())),
+            None if token.contains('@') => Some(Self::Email(token.to_string())),
+            None => None,
+        }
+    }
+
+    /// Slug of a team, as requested through `team_reviewers`
+    pub fn team_slug(&self) -> Option<&str> {
+        match self {
+            Self::Team(name) => name.split_once('/').map(|(_, slug)| slug),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Owner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::User(name) | Self::Team(name) => write!(f, "@{}", name),
+            Self::Email(email) => write!(f, "{}", email),
+        }
+    }
+}
+
+/// A pattern and the owners of the paths it matches
+#[derive(Debug, Clone)]
+struct Rule {
+    pattern: Regex,
+    owners: Vec<Owner>,
+}
+
+/// Parsed `CODEOWNERS` file
+#[derive(Debug, Clone, Default)]
+pub struct CodeOwners {
+    /// Rules in file order
+    rules: Vec<Rule>,
+}
+
+impl CodeOwners {
+    /// Parse the file content, lines that cannot be parsed are skipped
+    pub fn parse(content: &str) -> Self {
+        let rules = content
+            .lines()
+            .filter_map(|line| {
+                let line = strip_comment(line);
+                let mut tokens = line.split_whitespace();
+                let pattern = pattern_regex(&tokens.next()?.replace("\\#", "#"))?;
+                let owners = tokens.filter_map(Owner::parse).collect();
+                Some(Rule { pattern, owners })
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Owners of a path, per the last rule matching it
+    pub fn owners_of(&self, path: &str) -> &[Owner] {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.pattern.is_match(path))
+            .map(|rule| rule.owners.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Owners of any of `paths`, most owned files first
+    ///
+    /// Email owners are left out, reviews can only be requested from logins
+    /// and teams.
+    pub fn suggest<'a>(&self, paths: impl IntoIterator<Item = &'a str>) -> Vec<(Owner, usize)> {
+        let mut counts: HashMap<&Owner, usize> = HashMap::new();
+        for path in paths {
+            for owner in self.owners_of(path) {
+                if !matches!(owner, Owner::Email(_)) {
+                    *counts.entry(owner).or_default() += 1;
+                }
+            }
+        }
+        let mut ranked: Vec<(Owner, usize)> = counts
+            .into_iter()
+            .map(|(owner, count)| (owner.clone(), count))
+            .collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranked
+    }
+}
+
+/// Drop a comment, which starts at a `#` that is not escaped
+fn strip_comment(line: &str) -> &str {
+    let mut escaped = false;
+    for (idx, c) in line.char_indices() {
+        match c {
+            '#' if !escaped => return &line[..idx],
+            '\\' => escaped = !escaped,
+            _ => escaped = false,
+        }
+    }
+    line
+}
+
+/// Regex matching the paths of a gitignore-style pattern
+///
+/// A slash at the start or in the middle anchors the pattern to the root,
+/// otherwise it matches at any depth. A matched directory owns everything
+/// below it, except for a trailing `*`, which only matches the files
+/// directly in the directory.
+fn pattern_regex(pattern: &str) -> Option<Regex> {
+    let directory_only = pattern.ends_with('/');
+    let trimmed = pattern.trim_end_matches('/');
+    let anchored = trimmed.contains('/');
+    let trimmed = trimmed.trim_start_matches('/');
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    let mut regex = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+    let mut chars = trimmed.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    let files_only = trimmed.rsplit('/').next() == Some("*");
+    regex.push_str(match (files_only, directory_only) {
+        (true, _) => "$",
+        (false, true) => "/.*$",
+        (false, false) => "(?:/.*)?$",
+    });
+    Regex::new(&regex).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(login: &str) -> Owner {
+        Owner::User(login.to_string())
+    }
+
+    fn team(name: &str) -> Owner {
+        Owner::Team(name.to_string())
+    }
+
+    fn matches(pattern: &str, path: &str) -> bool {
+        pattern_regex(pattern).unwrap().is_match(path)
+    }
+
+    #[test]
+    fn test_extensions_and_names_match_at_any_depth() {
+        assert!(matches("*.js", "app.js"));
+        assert!(matches("*.js", "web/src/app.js"));
+        assert!(!matches("*.js", "app.json"));
+        assert!(matches("Makefile", "tools/Makefile"));
+        assert!(matches("apps/", "web/apps/main.rs"));
+        assert!(!matches("apps/", "apps"));
+        assert!(matches("*", "any/file.rs"));
+    }
+
+    #[test]
+    fn test_slashes_anchor_to_the_root() {
+        assert!(matches("/docs/", "docs/guide/intro.md"));
+        assert!(!matches("/docs/", "web/docs/intro.md"));
+        assert!(matches("build/logs/", "build/logs/out.txt"));
+        assert!(!matches("build/logs/", "ci/build/logs/out.txt"));
+        assert!(matches("/scripts", "scripts/release.sh"));
+        assert!(matches("/scripts", "scripts"));
+    }
+
+    #[test]
+    fn test_trailing_star_matches_direct_children_only() {
+        assert!(matches("docs/*", "docs/getting-started.md"));
+        assert!(!matches("docs/*", "docs/build-app/troubleshooting.md"));
+        assert!(matches("/src/*.rs", "src/main.rs"));
+        assert!(!matches("/src/*.rs", "src/bin/tool.rs"));
+    }
+
+    #[test]
+    fn test_double_star_spans_directories() {
+        assert!(matches("**/logs", "logs/a.txt"));
+        assert!(matches("**/logs", "deep/down/logs/a.txt"));
+        assert!(matches("/docs/**/api.md", "docs/api.md"));
+        assert!(matches("/docs/**/api.md", "docs/v1/rest/api.md"));
+        assert!(matches("/assets/**", "assets/img/logo.svg"));
+        assert!(matches("?.txt", "notes/a.txt"));
+        assert!(!matches("?.txt", "ab.txt"));
+    }
+
+    #[test]
+    fn test_special_characters_are_literal() {
+        assert!(matches("/config.(dev).toml", "config.(dev).toml"));
+        assert!(!matches("/config.(dev).toml", "configx(dev).toml"));
+        assert!(pattern_regex("/").is_none());
+    }
+
+    #[test]
+    fn test_parse_owners_and_comments() {
+        let owners = CodeOwners::parse(
+            "# Default owners\n\
+             *            @acme/core  # everyone else\n\
+             \n\
+             *.rs         @alice @acme/rust-team dev@acme.io\n\
+             /docs/\\#*   @bob\n\
+             /vendor/\n\
+             invalid-owner-only   not-an-owner\n",
+        );
+
+        assert_eq!(owners.owners_of("README.md"), &[team("acme/core")]);
+        assert_eq!(
+            owners.owners_of("src/main.rs"),
+            &[
+                user("alice"),
+                team("acme/rust-team"),
+                Owner::Email("dev@acme.io".to_string())
+            ]
+        );
+        assert_eq!(owners.owners_of("docs/#1.md"), &[user("bob")]);
+        // A rule without owners leaves the path unowned
+        assert!(owners.owners_of("vendor/lib.rs").is_empty());
+        assert!(owners.owners_of("invalid-owner-only").is_empty());
+    }
+
+    #[test]
+    fn test_last_matching_rule_wins() {
+        let owners = CodeOwners::parse("/src/ @alice\n*.md @bob\n/src/api/ @carol\n");
+        assert_eq!(owners.owners_of("src/lib.rs"), &[user("alice")]);
+        assert_eq!(owners.owners_of("src/README.md"), &[user("bob")]);
+        assert_eq!(owners.owners_of("src/api/README.md"), &[user("carol")]);
+        assert!(owners.owners_of("Cargo.toml").is_empty());
+    }
+
+    #[test]
+    fn test_suggest_ranks_by_owned_files() {
+        let owners = CodeOwners::parse(
+            "*.rs @alice @acme/rust dev@acme.io\n/docs/ @bob\n/src/api/ @carol @alice\n",
+        );
+        let suggested = owners.suggest([
+            "src/lib.rs",
+            "src/api/mod.rs",
+            "docs/intro.md",
+            "tests/it.rs",
+            "Cargo.lock",
+        ]);
+        assert_eq!(
+            suggested,
+            vec![
+                (user("alice"), 3),
+                (team("acme/rust"), 2),
+                (user("bob"), 1),
+                (user("carol"), 1),
+            ]
+        );
+        assert!(CodeOwners::default().suggest(["src/lib.rs"]).is_empty());
+    }
+
+    #[test]
+    fn test_owner_display_and_team_slug() {
+        assert_eq!(team("acme/core").to_string(), "@acme/core");
+        assert_eq!(team("acme/core").team_slug(), Some("core"));
+        assert_eq!(user("alice").to_string(), "@alice");
+        assert_eq!(user("alice").team_slug(), None);
+        assert_eq!(Owner::parse("@"), None);
+        assert_eq!(Owner::parse("alice"), None);
+    }
+}
@@ -0,0 +1,356 @@
+//! Rebase conflict candidates
+//!
+//! GitHub only reports that updating a PR branch failed with a merge
+//! conflict, not which files conflict. A file can only conflict when both
+//! sides changed it since the merge base, so the candidates are the PR's
+//! files that also changed on the base branch. This is an approximation:
+//! changes to different parts of a file merge cleanly, and the ranking by
+//! churn only guesses which overlap is the worst.
+
+use super::Pr;
+use gh_client::ChangedFile;
+use std::collections::HashMap;
+
+/// GitHub lists at most this many files of a comparison
+pub const COMPARE_FILES_LIMIT: usize = 300;
+
+/// Lock files, better regenerated than merged by hand
+const LOCKFILES: [&str; 9] = [
+    "Cargo.lock",
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "poetry.lock",
+    "Gemfile.lock",
+    "composer.lock",
+    "go.sum",
+    "flake.lock",
+];
+
+/// How both sides changed a candidate file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConflictKind {
+    /// Deleted on one side and changed on the other (always conflicts)
+    ModifiedDeleted,
+    /// Added on both sides (conflicts unless the contents are identical)
+    BothAdded,
+    /// Changed on both sides (conflicts if the changes overlap)
+    BothModified,
+}
+
+impl ConflictKind {
+    /// Short label for the conflict summary
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ModifiedDeleted => "modified/deleted",
+            Self::BothAdded => "added on both sides",
+            Self::BothModified => "changed on both sides",
+        }
+    }
+}
+
+/// A file that likely conflicts when merging the base branch into the PR
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictCandidate {
+    /// Path of the file in the PR
+    pub path: String,
+    pub kind: ConflictKind,
+    /// Lines added and removed by the PR
+    pub pr_churn: u64,
+    /// Lines added and removed on the base branch since the merge base
+    pub base_churn: u64,
+}
+
+impl ConflictCandidate {
+    /// Whether the file is a lock file (regenerate it instead of merging)
+    pub fn is_lockfile(&self) -> bool {
+        let name = self.path.rsplit('/').next().unwrap_or(&self.path);
+        LOCKFILES.contains(&name)
+    }
+
+    /// Lines both sides touched at most, the likelier the changes overlap
+    fn overlap(&self) -> u64 {
+        self.pr_churn.min(self.base_churn)
+    }
+}
+
+/// Likely conflicting files of a PR, from both sides of its merge base
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ConflictSummary {
+    /// Candidates, likeliest first
+    pub candidates: Vec<ConflictCandidate>,
+    /// Files changed by the PR
+    pub pr_files: usize,
+    /// Files changed on the base branch since the merge base
+    pub base_files: usize,
+    /// Commits on the base branch since the merge base
+    pub behind_by: u64,
+    /// A side changed more files than GitHub lists, so candidates may be missing
+    pub truncated: bool,
+}
+
+impl ConflictSummary {
+    /// Summarize the files changed by the PR and on the base branch
+    pub fn new(pr_files: &[ChangedFile], base_files: &[ChangedFile], behind_by: u64) -> Self {
+        Self {
+            candidates: conflict_candidates(pr_files, base_files),
+            pr_files: pr_files.len(),
+            base_files: base_files.len(),
+            behind_by,
+            truncated: pr_files.len() >= COMPARE_FILES_LIMIT
+                || base_files.len() >= COMPARE_FILES_LIMIT,
+        }
+    }
+}
+
+/// Files changed on both sides since the merge base, likeliest conflicts first
+///
+/// Renames on either side are followed by the file's previous name. Files
+/// deleted on both sides merge cleanly and are left out. Certain conflicts
+/// come first, then files by how many lines both sides touched.
+pub fn conflict_candidates(
+    pr_files: &[ChangedFile],
+    base_files: &[ChangedFile],
+) -> Vec<ConflictCandidate> {
+    let mut base_by_path: HashMap<&str, &ChangedFile> = HashMap::new();
+    for file in base_files {
+        base_by_path.insert(file.filename.as_str(), file);
+        if let Some(ref previous) = file.previous_filename {
+            base_by_path.entry(previous.as_str()).or_insert(file);
+        }
+    }
+
+    let mut candidates: Vec<ConflictCandidate> = pr_files
+        .iter()
+        .filter_map(|file| {
+            let base = base_by_path.get(file.filename.as_str()).or_else(|| {
+                let previous = file.previous_filename.as_deref()?;
+                base_by_path.get(previous)
+            })?;
+            let kind = match (file.status.as_str(), base.status.as_str()) {
+                ("removed", "removed") => return None,
+                ("removed", _) | (_, "removed") => ConflictKind::ModifiedDeleted,
+                ("added", "added") => ConflictKind::BothAdded,
+                _ => ConflictKind::BothModified,
+            };
+            Some(ConflictCandidate {
+                path: file.filename.clone(),
+                kind,
+                pr_churn: file.churn(),
+                base_churn: base.churn(),
+            })
+        })
+        .collect();
+
+    candidates.sort_by(|a, b| {
+        a.kind
+            .cmp(&b.kind)
+            .then(b.overlap().cmp(&a.overlap()))
+            .then((b.pr_churn + b.base_churn).cmp(&(a.pr_churn + a.base_churn)))
+            .then(a.path.cmp(&b.path))
+    });
+    candidates
+}
+
+/// Whether a failed update-branch call failed on a merge conflict
+pub fn is_merge_conflict_error(error: &str) -> bool {
+    error.to_lowercase().contains("merge conflict")
+}
+
+/// Shell commands to merge the base branch into the PR locally
+///
+/// Fork branches are checked out with the GitHub CLI, which knows where to
+/// push them. The remote of the repository is assumed to be `origin`.
+pub fn resolution_commands(pr: &Pr, candidates: &[ConflictCandidate]) -> String {
+    let mut lines = vec![format!(
+        "# PR #{}: merge {} into {} and resolve the conflicts",
+        pr.number, pr.base_branch, pr.head_branch
+    )];
+    if pr.is_from_fork {
+        lines.push(format!("gh pr checkout {}", pr.number));
+        lines.push(format!("git fetch origin {}", pr.base_branch));
+    } else {
+        lines.push(format!(
+            "git fetch origin {} {}",
+            pr.base_branch, pr.head_branch
+        ));
+        lines.push(format!("git switch {}", pr.head_branch));
+        lines.push(format!("git merge --ff-only origin/{}", pr.head_branch));
+    }
+    lines.push(format!("git merge origin/{}", pr.base_branch));
+    if !candidates.is_empty() {
+        let paths: Vec<&str> = candidates.iter().map(|c| c.path.as_str()).collect();
+        lines.push(format!("# likely conflicting: {}", paths.join(" ")));
+    }
+    lines.push("git status".to_string());
+    lines.push("# after resolving: git add <files> && git commit --no-edit".to_string());
+    lines.push("git push".to_string());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, status: &str, additions: u64, deletions: u64) -> ChangedFile {
+        ChangedFile {
+            filename: name.to_string(),
+            previous_filename: None,
+            status: status.to_string(),
+            additions,
+            deletions,
+        }
+    }
+
+    fn renamed(name: &str, previous: &str) -> ChangedFile {
+        ChangedFile {
+            previous_filename: Some(previous.to_string()),
+            ..file(name, "renamed", 2, 1)
+        }
+    }
+
+    fn paths(candidates: &[ConflictCandidate]) -> Vec<&str> {
+        candidates.iter().map(|c| c.path.as_str()).collect()
+    }
+
+    #[test]
+    fn test_only_files_changed_on_both_sides_are_candidates() {
+        let pr = [
+            file("src/lib.rs", "modified", 10, 2),
+            file("src/only_pr.rs", "added", 40, 0),
+            file("README.md", "modified", 1, 1),
+        ];
+        let base = [
+            file("src/lib.rs", "modified", 3, 3),
+            file("src/only_base.rs", "modified", 5, 0),
+            file("README.md", "modified", 2, 0),
+        ];
+
+        let candidates = conflict_candidates(&pr, &base);
+        assert_eq!(paths(&candidates), vec!["src/lib.rs", "README.md"]);
+        assert_eq!(candidates[0].kind, ConflictKind::BothModified);
+        assert_eq!((candidates[0].pr_churn, candidates[0].base_churn), (12, 6));
+    }
+
+    #[test]
+    fn test_no_candidates_without_overlap() {
+        let pr = [file("a.rs", "modified", 1, 0)];
+        let base = [file("b.rs", "modified", 1, 0)];
+        assert!(conflict_candidates(&pr, &base).is_empty());
+        assert!(conflict_candidates(&[], &base).is_empty());
+    }
+
+    #[test]
+    fn test_certain_conflicts_rank_first_then_overlap() {
+        let pr = [
+            file("small.rs", "modified", 1, 0),
+            file("big.rs", "modified", 200, 50),
+            file("gone.rs", "modified", 1, 1),
+            file("new.rs", "added", 10, 0),
+            file("both_gone.rs", "removed", 0, 9),
+        ];
+        let base = [
+            file("small.rs", "modified", 300, 0),
+            file("big.rs", "modified", 30, 10),
+            file("gone.rs", "removed", 0, 80),
+            file("new.rs", "added", 12, 0),
+            file("both_gone.rs", "removed", 0, 9),
+        ];
+
+        let candidates = conflict_candidates(&pr, &base);
+        // Deleted on both sides merges cleanly
+        assert_eq!(
+            paths(&candidates),
+            vec!["gone.rs", "new.rs", "big.rs", "small.rs"]
+        );
+        assert_eq!(candidates[0].kind, ConflictKind::ModifiedDeleted);
+        assert_eq!(candidates[1].kind, ConflictKind::BothAdded);
+    }
+
+    #[test]
+    fn test_renames_are_followed_on_both_sides() {
+        // The PR renamed a file the base branch changed
+        let pr = [renamed("src/new_name.rs", "src/old_name.rs")];
+        let base = [file("src/old_name.rs", "modified", 4, 4)];
+        let candidates = conflict_candidates(&pr, &base);
+        assert_eq!(paths(&candidates), vec!["src/new_name.rs"]);
+
+        // The base branch renamed a file the PR changed
+        let pr = [file("src/old_name.rs", "modified", 4, 4)];
+        let base = [renamed("src/new_name.rs", "src/old_name.rs")];
+        let candidates = conflict_candidates(&pr, &base);
+        assert_eq!(paths(&candidates), vec!["src/old_name.rs"]);
+        assert_eq!(candidates[0].base_churn, 3);
+    }
+
+    #[test]
+    fn test_lockfiles_are_recognized() {
+        let candidates = conflict_candidates(
+            &[
+                file("Cargo.lock", "modified", 20, 20),
+                file("web/package-lock.json", "modified", 1, 1),
+                file("src/Cargo.lock.rs", "modified", 1, 1),
+            ],
+            &[
+                file("Cargo.lock", "modified", 5, 5),
+                file("web/package-lock.json", "modified", 1, 1),
+                file("src/Cargo.lock.rs", "modified", 1, 1),
+            ],
+        );
+        let lockfiles: Vec<bool> = candidates.iter().map(|c| c.is_lockfile()).collect();
+        assert_eq!(paths(&candidates)[0], "Cargo.lock");
+        assert_eq!(lockfiles, vec![true, false, true]);
+    }
+
+    #[test]
+    fn test_summary_notes_truncated_comparisons() {
+        let pr = [file("a.rs", "modified", 1, 0)];
+        let summary = ConflictSummary::new(&pr, &pr, 7);
+        assert_eq!(summary.candidates.len(), 1);
+        assert_eq!((summary.pr_files, summary.base_files), (1, 1));
+        assert_eq!(summary.behind_by, 7);
+        assert!(!summary.truncated);
+
+        let many: Vec<ChangedFile> = (0..COMPARE_FILES_LIMIT)
+            .map(|n| file(&format!("f{}.rs", n), "modified", 1, 0))
+            .collect();
+        assert!(ConflictSummary::new(&pr, &many, 1).truncated);
+    }
+
+    #[test]
+    fn test_merge_conflict_errors() {
+        assert!(is_merge_conflict_error(
+            "merge conflict between base and head"
+        ));
+        assert!(is_merge_conflict_error("Merge Conflict"));
+        assert!(!is_merge_conflict_error("Resource not accessible"));
+    }
+
+    #[test]
+    fn test_resolution_commands() {
+        let mut pr = Pr::new(42, "Fix", "alice", "abc").with_branches("fix/parser", "main");
+        let candidates = conflict_candidates(
+            &[file("src/lib.rs", "modified", 1, 0)],
+            &[file("src/lib.rs", "modified", 1, 0)],
+        );
+
+        let commands = resolution_commands(&pr, &candidates);
+        assert_eq!(
+            commands,
+            "# PR #42: merge main into fix/parser and resolve the conflicts\n\
+             git fetch origin main fix/parser\n\
+             git switch fix/parser\n\
+             git merge --ff-only origin/fix/parser\n\
+             git merge origin/main\n\
+             # likely conflicting: src/lib.rs\n\
+             git status\n\
+             # after resolving: git add <files> && git commit --no-edit\n\
+             git push"
+        );
+
+        pr.is_from_fork = true;
+        let commands = resolution_commands(&pr, &[]);
+        assert!(commands.contains("\ngh pr checkout 42\ngit fetch origin main\n"));
+        assert!(!commands.contains("likely conflicting"));
+    }
+}
@@ -0,0 +1,572 @@
+//! Dependency update metadata
+//!
+//! Dependabot and Renovate open PRs of a predictable shape. Dependabot encodes
+//! the dependency and version range in the title ("Bump serde from 1.0.188 to
+//! 1.0.190"), Renovate names the dependency in the title ("Update Rust crate
+//! serde to v1.0.190") and adds a table with the update type and version
+//! change to the body.
+//!
+//! The functions here are pure, so bot PRs can be badged, filtered, grouped
+//! and bulk merged without further API calls.
+
+use super::author;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// Semver size of a dependency update
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum UpdateType {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl UpdateType {
+    /// Lowercase name, as used by Renovate and the `update:` filter
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Patch => "patch",
+            Self::Minor => "minor",
+            Self::Major => "major",
+        }
+    }
+
+    /// Parse a lowercase or capitalized name ("patch", "Minor", ...)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "patch" => Some(Self::Patch),
+            "minor" => Some(Self::Minor),
+            "major" => Some(Self::Major),
+            _ => None,
+        }
+    }
+
+    /// Update type of a version change, derived from the version numbers
+    ///
+    /// Before 1.0 a minor bump is breaking, so it counts as major (as Cargo
+    /// and npm treat it). Returns `None` if a version is not numeric.
+    pub fn between(from: &str, to: &str) -> Option<Self> {
+        let from = version_parts(from)?;
+        let to = version_parts(to)?;
+        let part = |parts: &[u64], idx: usize| parts.get(idx).copied().unwrap_or(0);
+
+        if part(&from, 0) != part(&to, 0) {
+            Some(Self::Major)
+        } else if part(&from, 1) != part(&to, 1) {
+            if part(&from, 0) == 0 {
+                Some(Self::Major)
+            } else {
+                Some(Self::Minor)
+            }
+        } else {
+            Some(Self::Patch)
+        }
+    }
+}
+
+/// Numeric parts of a version ("v1.2.3" or "^1.2" -> [1, 2, 3] or [1, 2])
+fn version_parts(version: &str) -> Option<Vec<u64>> {
+    let version = version.trim_start_matches(['v', 'V', '^', '~', '=', '>', '<', ' ']);
+    let core = version.split(['-', '+', ' ']).next().unwrap_or_default();
+    if core.is_empty() {
+        return None;
+    }
+    core.split('.').map(|part| part.parse().ok()).collect()
+}
+
+/// Identity of the same update opened in several repositories
+///
+/// Dependabot and Renovate spell the target version differently ("1.0.201"
+/// vs "v1.0.201"), so the version is kept without its "v" prefix.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UpdateKey {
+    pub ecosystem: Option<String>,
+    /// Lowercase dependency name
+    pub name: String,
+    pub to_version: String,
+}
+
+/// A dependency update proposed by Dependabot or Renovate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyUpdate {
+    /// Package ecosystem (e.g. "cargo", "npm_and_yarn"), if known
+    pub ecosystem: Option<String>,
+    /// Name of the updated dependency
+    pub name: String,
+    /// Version before the update (Renovate titles only name the new one)
+    pub from_version: Option<String>,
+    /// Version after the update
+    pub to_version: String,
+    /// Size of the update (`None` for non-numeric versions)
+    pub update_type: Option<UpdateType>,
+}
+
+impl DependencyUpdate {
+    /// Extract the update metadata of a bot PR
+    ///
+    /// The bot is recognized by the head branch (`dependabot/...`,
+    /// `renovate/...`) or the author, so human PRs titled "Bump version" are
+    /// not mistaken for dependency updates. Grouped updates ("Bump the cargo
+    /// group with 3 updates") have no single dependency and yield `None`.
+    pub fn detect(title: &str, body: &str, head_branch: &str, author: &str) -> Option<Self> {
+        if head_branch.starts_with("dependabot/") || author::is_dependabot(author) {
+            Self::from_dependabot(title, head_branch)
+        } else if head_branch.starts_with("renovate/") || author::is_renovate(author) {
+            Self::from_renovate(title, body)
+        } else {
+            None
+        }
+    }
+
+    /// Parse a Dependabot title
+    ///
+    /// Handles "Bump X from A to B", "Update X requirement from A to B", an
+    /// optional " in /path" suffix and conventional commit prefixes. The
+    /// ecosystem is the second segment of the head branch
+    /// (`dependabot/cargo/serde-1.0.190`).
+    pub fn from_dependabot(title: &str, head_branch: &str) -> Option<Self> {
+        static TITLE: OnceLock<Regex> = OnceLock::new();
+        let re = TITLE.get_or_init(|| {
+            Regex::new(
+                r"(?i)^(?:[a-z-]+(?:\([^)]*\))?!?:\s*)?(?:bump|update)\s+(\S+)\s+(?:requirement\s+)?from\s+(\S+)\s+to\s+(\S+)(?:\s+in\s+\S+)?\s*$",
+            )
+            .unwrap()
+        });
+        let caps = re.captures(title.trim())?;
+        let from = caps[2].to_string();
+        let to = caps[3].to_string();
+
+        let ecosystem = head_branch
+            .strip_prefix("dependabot/")
+            .and_then(|rest| rest.split('/').next())
+            .filter(|eco| !eco.is_empty())
+            .map(str::to_string);
+
+        Some(Self {
+            ecosystem,
+            name: caps[1].to_string(),
+            update_type: UpdateType::between(&from, &to),
+            from_version: Some(from),
+            to_version: to,
+        })
+    }
+
+    /// Parse a Renovate title and body
+    ///
+    /// The title names the dependency ("Update Rust crate serde to
+    /// v1.0.190", "Update actions/checkout action to v4"); the body table
+    /// adds the update type and the old version. A body table with several
+    /// packages is a grouped update and yields `None`.
+    pub fn from_renovate(title: &str, body: &str) -> Option<Self> {
+        static TITLE: OnceLock<Regex> = OnceLock::new();
+        let re = TITLE.get_or_init(|| {
+            Regex::new(
+                r"(?i)^(?:[a-z-]+(?:\([^)]*\))?!?:\s*)?update\s+(.+?)\s+to\s+(\S+)(?:\s+\(.*\))?\s*$",
+            )
+            .unwrap()
+        });
+
+        let table = renovate_table_rows(body);
+        if table.len() > 1 {
+            return None;
+        }
+        let row = table.into_iter().next();
+
+        let (ecosystem, title_name, title_to) = match re.captures(title.trim()) {
+            Some(caps) => {
+                let (ecosystem, name) = split_renovate_subject(&caps[1]);
+                (ecosystem, name, Some(caps[2].to_string()))
+            }
+            None => (None, None, None),
+        };
+
+        let name = row.as_ref().map(|row| row.package.clone()).or(title_name)?;
+        let from_version = row.as_ref().and_then(|row| row.from.clone());
+        let to_version = row.as_ref().and_then(|row| row.to.clone()).or(title_to)?;
+        let update_type = row
+            .as_ref()
+            .and_then(|row| row.update_type)
+            .or_else(|| UpdateType::between(from_version.as_deref()?, &to_version));
+
+        Some(Self {
+            ecosystem,
+            name,
+            from_version,
+            to_version,
+            update_type,
+        })
+    }
+
+    /// Short description ("serde 1.0.188 → 1.0.190")
+    pub fn summary(&self) -> String {
+        match self.from_version {
+            Some(ref from) => format!("{} {} → {}", self.name, from, self.to_version),
+            None => format!("{} → {}", self.name, self.to_version),
+        }
+    }
+
+    /// Key that groups updates of the same dependency across repositories
+    pub fn group_key(&self) -> String {
+        self.name.to_ascii_lowercase()
+    }
+
+    /// Key of this exact update (ecosystem, dependency and target version)
+    pub fn update_key(&self) -> UpdateKey {
+        UpdateKey {
+            ecosystem: self.ecosystem.clone(),
+            name: self.group_key(),
+            to_version: self.to_version.trim_start_matches(['v', 'V']).to_string(),
+        }
+    }
+}
+
+/// Split the subject of a Renovate title into ecosystem and dependency name
+///
+/// "Rust crate serde" -> (cargo, serde), "actions/checkout action" ->
+/// (github_actions, actions/checkout), "dependency lodash" -> (None, lodash).
+fn split_renovate_subject(subject: &str) -> (Option<String>, Option<String>) {
+    const PREFIXES: &[(&str, Option<&str>)] = &[
+        ("rust crate ", Some("cargo")),
+        ("module ", Some("go_modules")),
+        ("npm package ", Some("npm_and_yarn")),
+        ("dependency ", None),
+    ];
+    const SUFFIXES: &[(&str, Option<&str>)] = &[
+        (" action", Some("github_actions")),
+        (" docker tag", Some("docker")),
+        (" docker digest", Some("docker")),
+    ];
+
+    let lower = subject.to_ascii_lowercase();
+    for (prefix, ecosystem) in PREFIXES {
+        if lower.starts_with(prefix) {
+            let name = subject[prefix.len()..].trim();
+            return (ecosystem.map(str::to_string), single_word(name));
+        }
+    }
+    for (suffix, ecosystem) in SUFFIXES {
+        if lower.ends_with(suffix) {
+            let name = subject[..subject.len() - suffix.len()].trim();
+            return (ecosystem.map(str::to_string), single_word(name));
+        }
+    }
+    (None, single_word(subject.trim()))
+}
+
+/// The name if it is a single word (multi-word subjects are not a package)
+fn single_word(name: &str) -> Option<String> {
+    (!name.is_empty() && !name.contains(char::is_whitespace)).then(|| name.to_string())
+}
+
+/// One package row of a Renovate body table
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RenovateRow {
+    package: String,
+    update_type: Option<UpdateType>,
+    from: Option<String>,
+    to: Option<String>,
+}
+
+/// Package rows of the Renovate table
+///
+/// ```text
+/// | Package | Type | Update | Change |
+/// |---|---|---|---|
+/// | [serde](https://serde.rs) | dependencies | patch | `1.0.188` -> `1.0.190` |
+/// ```
+fn renovate_table_rows(body: &str) -> Vec<RenovateRow> {
+    static CHANGE: OnceLock<Regex> = OnceLock::new();
+    let change_re = CHANGE.get_or_init(|| Regex::new(r"`([^`]+)`\s*(?:->|→)\s*`([^`]+)`").unwrap());
+
+    let mut lines = body.lines().map(str::trim).skip_while(|line| {
+        let lower = line.to_ascii_lowercase();
+        !(line.starts_with('|') && lower.contains("package") && lower.contains("change"))
+    });
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+    let columns: Vec<String> = table_cells(header)
+        .iter()
+        .map(|cell| cell.to_ascii_lowercase())
+        .collect();
+    let column = |name: &str| columns.iter().position(|cell| cell == name);
+    let (Some(package_col), Some(change_col)) = (column("package"), column("change")) else {
+        return Vec::new();
+    };
+    let update_col = column("update");
+
+    lines
+        .skip(1) // |---|---| separator
+        .take_while(|line| line.starts_with('|'))
+        .filter_map(|line| {
+            let cells = table_cells(line);
+            let package = markdown_link_text(cells.get(package_col)?)?;
+            let (from, to) = cells
+                .get(change_col)
+                .and_then(|cell| change_re.captures(cell))
+                .map(|caps| (Some(caps[1].to_string()), Some(caps[2].to_string())))
+                .unwrap_or_default();
+            let update_type = update_col
+                .and_then(|col| cells.get(col))
+                .and_then(|cell| UpdateType::parse(cell));
+            Some(RenovateRow {
+                package,
+                update_type,
+                from,
+                to,
+            })
+        })
+        .collect()
+}
+
+/// Cells of a markdown table row
+fn table_cells(line: &str) -> Vec<&str> {
+    line.trim()
+        .trim_start_matches('|')
+        .trim_end_matches('|')
+        .split('|')
+        .map(str::trim)
+        .collect()
+}
+
+/// Text of a leading markdown link ("[serde](url) ([source](url))" -> "serde")
+fn markdown_link_text(cell: &str) -> Option<String> {
+    let text = match cell.strip_prefix('[') {
+        Some(rest) => rest.split(']').next()?,
+        None => cell.split_whitespace().next()?,
+    };
+    let text = text.trim_matches('`').trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_type_between_versions() {
+        assert_eq!(
+            UpdateType::between("1.0.188", "1.0.190"),
+            Some(UpdateType::Patch)
+        );
+        assert_eq!(
+            UpdateType::between("1.2.0", "1.3.0"),
+            Some(UpdateType::Minor)
+        );
+        assert_eq!(UpdateType::between("1.9", "2.0"), Some(UpdateType::Major));
+        assert_eq!(UpdateType::between("v3", "v4"), Some(UpdateType::Major));
+        assert_eq!(UpdateType::between("^1.0", "^1.1"), Some(UpdateType::Minor));
+        // Before 1.0 a minor bump is breaking
+        assert_eq!(
+            UpdateType::between("0.11.2", "0.12.0"),
+            Some(UpdateType::Major)
+        );
+        assert_eq!(
+            UpdateType::between("0.11.2", "0.11.3"),
+            Some(UpdateType::Patch)
+        );
+        assert_eq!(
+            UpdateType::between("1.0.0-beta.1", "1.0.0"),
+            Some(UpdateType::Patch)
+        );
+        assert_eq!(UpdateType::between("abc1234", "def5678"), None);
+    }
+
+    #[test]
+    fn test_dependabot_titles() {
+        let update = DependencyUpdate::detect(
+            "Bump serde from 1.0.188 to 1.0.190",
+            "",
+            "dependabot/cargo/serde-1.0.190",
+            "dependabot[bot]",
+        )
+        .unwrap();
+        assert_eq!(
+            update,
+            DependencyUpdate {
+                ecosystem: Some("cargo".to_string()),
+                name: "serde".to_string(),
+                from_version: Some("1.0.188".to_string()),
+                to_version: "1.0.190".to_string(),
+                update_type: Some(UpdateType::Patch),
+            }
+        );
+
+        let update = DependencyUpdate::detect(
+            "build(deps-dev): bump @types/node from 20.1.0 to 22.0.0 in /web",
+            "",
+            "dependabot/npm_and_yarn/web/types/node-22.0.0",
+            "dependabot[bot]",
+        )
+        .unwrap();
+        assert_eq!(update.name, "@types/node");
+        assert_eq!(update.ecosystem.as_deref(), Some("npm_and_yarn"));
+        assert_eq!(update.update_type, Some(UpdateType::Major));
+
+        let update = DependencyUpdate::detect(
+            "Update tokio requirement from 1.35 to 1.36",
+            "",
+            "dependabot/cargo/tokio-1.36",
+            "dependabot[bot]",
+        )
+        .unwrap();
+        assert_eq!(update.name, "tokio");
+        assert_eq!(update.update_type, Some(UpdateType::Minor));
+    }
+
+    #[test]
+    fn test_dependabot_group_and_human_prs_are_ignored() {
+        assert!(DependencyUpdate::detect(
+            "Bump the cargo group with 3 updates",
+            "",
+            "dependabot/cargo/cargo-abc123",
+            "dependabot[bot]",
+        )
+        .is_none());
+        // Same title shape, but not opened by a bot
+        assert!(DependencyUpdate::detect(
+            "Bump version from 1.0.0 to 1.1.0",
+            "",
+            "release/1.1",
+            "alice",
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_renovate_title_and_body_table() {
+        let body = "This PR contains the following updates:\n\
+            \n\
+            | Package | Type | Update | Change |\n\
+            |---|---|---|---|\n\
+            | [serde](https://serde.rs) ([source](https://github.com/serde-rs/serde)) | dependencies | patch | `1.0.188` -> `1.0.190` |\n\
+            \n\
+            ---\n";
+        let update = DependencyUpdate::detect(
+            "chore(deps): update rust crate serde to v1.0.190",
+            body,
+            "renovate/serde-monorepo",
+            "renovate[bot]",
+        )
+        .unwrap();
+        assert_eq!(
+            update,
+            DependencyUpdate {
+                ecosystem: Some("cargo".to_string()),
+                name: "serde".to_string(),
+                from_version: Some("1.0.188".to_string()),
+                to_version: "1.0.190".to_string(),
+                update_type: Some(UpdateType::Patch),
+            }
+        );
+        assert_eq!(update.summary(), "serde 1.0.188 → 1.0.190");
+    }
+
+    #[test]
+    fn test_renovate_title_only() {
+        let update = DependencyUpdate::detect(
+            "Update actions/checkout action to v4",
+            "",
+            "renovate/actions-checkout-4.x",
+            "renovate[bot]",
+        )
+        .unwrap();
+        assert_eq!(update.name, "actions/checkout");
+        assert_eq!(update.ecosystem.as_deref(), Some("github_actions"));
+        assert_eq!(update.from_version, None);
+        assert_eq!(update.to_version, "v4");
+        assert_eq!(update.update_type, None);
+        assert_eq!(update.summary(), "actions/checkout → v4");
+
+        let update = DependencyUpdate::detect(
+            "Update dependency lodash to v4.17.21 (#123)",
+            "",
+            "renovate/lodash-4.x",
+            "renovate[bot]",
+        )
+        .unwrap();
+        assert_eq!(update.name, "lodash");
+        assert_eq!(update.ecosystem, None);
+    }
+
+    #[test]
+    fn test_renovate_group_is_ignored() {
+        let body = "| Package | Change |\n\
+            |---|---|\n\
+            | serde | `1.0.1` -> `1.0.2` |\n\
+            | serde_json | `1.0.1` -> `1.0.2` |\n";
+        assert!(DependencyUpdate::detect(
+            "Update serde monorepo",
+            body,
+            "renovate/serde-monorepo",
+            "renovate[bot]",
+        )
+        .is_none());
+        // Multi-word subjects without a table are not a single package
+        assert!(DependencyUpdate::detect(
+            "Update all non-major dependencies to latest",
+            "",
+            "renovate/all-minor-patch",
+            "renovate[bot]",
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_update_key_matches_dependabot_and_renovate_titles() {
+        let dependabot = |title: &str, branch: &str| {
+            DependencyUpdate::detect(title, "", branch, "dependabot[bot]")
+                .unwrap()
+                .update_key()
+        };
+        let renovate = |title: &str| {
+            DependencyUpdate::detect(title, "", "renovate/serde-1.x", "renovate[bot]")
+                .unwrap()
+                .update_key()
+        };
+
+        let key = dependabot(
+            "Bump serde from 1.0.200 to 1.0.201",
+            "dependabot/cargo/serde-1.0.201",
+        );
+        assert_eq!(
+            key,
+            UpdateKey {
+                ecosystem: Some("cargo".to_string()),
+                name: "serde".to_string(),
+                to_version: "1.0.201".to_string(),
+            }
+        );
+        // Prefixes, paths and spelling do not make a different update
+        assert_eq!(
+            dependabot(
+                "chore(deps): bump Serde from 1.0.199 to 1.0.201 in /crates/api",
+                "dependabot/cargo/crates/api/serde-1.0.201",
+            ),
+            key
+        );
+        assert_eq!(renovate("Update Rust crate serde to v1.0.201"), key);
+        assert_eq!(
+            renovate("fix(deps): update rust crate serde to 1.0.201 (#42)"),
+            key
+        );
+
+        // Another version or ecosystem is another update
+        assert_ne!(renovate("Update Rust crate serde to v1.0.202"), key);
+        assert_ne!(
+            dependabot(
+                "Bump serde from 1.0.200 to 1.0.201",
+                "dependabot/npm_and_yarn/serde-1.0.201",
+            ),
+            key
+        );
+    }
+
+    #[test]
+    fn test_group_key_ignores_case() {
+        let a = DependencyUpdate::from_dependabot("Bump Serde from 1.0.1 to 1.0.2", "").unwrap();
+        let b = DependencyUpdate::from_dependabot("Bump serde from 1.0.1 to 1.0.3", "").unwrap();
+        assert_eq!(a.group_key(), b.group_key());
+    }
+}
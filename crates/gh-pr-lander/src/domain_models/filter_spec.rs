@@ -0,0 +1,421 @@
+//! Structured PR filters
+//!
+//! A `FilterSpec` (defined in `gh_pr_config` so it can be saved) is built
+//! either by the filter builder popup or from a query typed into the search
+//! input (`author:*bot* ci:success age:>7d`). Both end up in `matches`, so
+//! a saved filter and the query it prints as always select the same PRs.
+//!
+//! Query syntax, all qualifiers must match:
+//! - `author:bob` - author is bob, `author:*bot*` - author contains "bot"
+//! - `label:x` / `-label:x` - carries / does not carry label x
+//! - `ci:success`, `ci:pending`, `ci:failed`
+//! - `draft:true`, `draft:false`
+//! - `update:patch`, `update:minor`, `update:major`
+//! - `age:>7d` (opened more than 7 days ago), `age:<7d`
+//! - `base:main` - merges into main, `base:release/*` - into a release branch
+//! - `size:M` - changes M lines, `size:<=M`, `size:>L` (see `PrSize`)
+
+use super::{normalize_author, MaturityState, MergeableStatus, Pr, PrSize, UpdateType};
+use crate::utils::issue_extractor::glob_match;
+use chrono::{DateTime, Duration, Utc};
+use gh_pr_config::{FilterCi, FilterSpec, PrSizeThresholds};
+
+/// Qualifiers GitHub search does not know; only queries using one of them
+/// are applied locally, everything else stays a GitHub search
+const LOCAL_QUALIFIERS: [&str; 5] = ["ci", "update", "age", "base", "size"];
+
+/// Whether `pr` meets every criterion of `spec` (`now` for the age criteria,
+/// `sizes` for the size criteria)
+///
+/// A PR whose stats are not fetched yet matches no size criterion.
+pub fn matches(spec: &FilterSpec, pr: &Pr, now: DateTime<Utc>, sizes: &PrSizeThresholds) -> bool {
+    if let Some(author) = &spec.author {
+        if !normalize_author(&pr.author).eq_ignore_ascii_case(&normalize_author(author)) {
+            return false;
+        }
+    }
+    if let Some(part) = &spec.author_contains {
+        if !pr.author.to_lowercase().contains(&part.to_lowercase()) {
+            return false;
+        }
+    }
+
+    let has_label = |label: &String| pr.labels.iter().any(|l| l.eq_ignore_ascii_case(label));
+    if !spec.labels_include.iter().all(has_label) || spec.labels_exclude.iter().any(has_label) {
+        return false;
+    }
+
+    if let Some(ci) = spec.ci {
+        let ci_matches = match ci {
+            FilterCi::Success => pr.mergeable.passes_required_checks(),
+            FilterCi::Pending => matches!(
+                pr.mergeable,
+                MergeableStatus::Unknown
+                    | MergeableStatus::Checking
+                    | MergeableStatus::Queued
+                    | MergeableStatus::WaitingApproval
+            ),
+            FilterCi::Failed => pr.mergeable == MergeableStatus::BuildFailed,
+        };
+        if !ci_matches {
+            return false;
+        }
+    }
+
+    if let Some(draft) = spec.draft {
+        if (pr.maturity == MaturityState::Draft) != draft {
+            return false;
+        }
+    }
+
+    if let Some(update_type) = &spec.update_type {
+        let wanted = UpdateType::parse(update_type);
+        let actual = pr.dependency_update.as_ref().and_then(|u| u.update_type);
+        if wanted.is_none() || actual != wanted {
+            return false;
+        }
+    }
+
+    if let Some(pattern) = &spec.base_branch {
+        if !glob_match(pattern, &pr.base_branch) {
+            return false;
+        }
+    }
+
+    if spec.min_size.is_some() || spec.max_size.is_some() {
+        let Some(size) = pr.size(sizes) else {
+            return false;
+        };
+        let bound = |name: &Option<String>| name.as_deref().and_then(PrSize::parse);
+        if bound(&spec.min_size).is_some_and(|min| size < min)
+            || bound(&spec.max_size).is_some_and(|max| size > max)
+        {
+            return false;
+        }
+    }
+
+    let age = now - pr.created_at;
+    if let Some(days) = spec.older_than_days {
+        if age <= Duration::days(days.into()) {
+            return false;
+        }
+    }
+    if let Some(days) = spec.newer_than_days {
+        if age >= Duration::days(days.into()) {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Parse a query typed into the search input into a filter
+///
+/// Returns `None` if a term is not a known qualifier, or if the query uses
+/// none of the local-only qualifiers (`ci:`, `update:`, `age:`, `base:`,
+/// `size:`); such
+/// queries are sent to GitHub search instead.
+pub fn parse(query: &str) -> Option<FilterSpec> {
+    let mut spec = FilterSpec::default();
+    let mut local = false;
+
+    for term in split_terms(query) {
+        let (negated, term) = match term.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, term.as_str()),
+        };
+        let (qualifier, value) = term.split_once(':')?;
+        let qualifier = qualifier.to_ascii_lowercase();
+        let value = value.trim_matches('"');
+        if value.is_empty() || (negated && qualifier != "label") {
+            return None;
+        }
+        local |= LOCAL_QUALIFIERS.contains(&qualifier.as_str());
+
+        match qualifier.as_str() {
+            "author" => match value.strip_prefix('*').and_then(|v| v.strip_suffix('*')) {
+                Some(part) if !part.is_empty() => spec.author_contains = Some(part.to_string()),
+                Some(_) => return None,
+                None => spec.author = Some(value.to_string()),
+            },
+            "label" if negated => spec.labels_exclude.push(value.to_string()),
+            "label" => spec.labels_include.push(value.to_string()),
+            "ci" => spec.ci = Some(FilterCi::parse(value)?),
+            "draft" => spec.draft = Some(value.parse().ok()?),
+            "update" => spec.update_type = Some(UpdateType::parse(value)?.label().to_string()),
+            "base" => spec.base_branch = Some(value.to_string()),
+            "size" => {
+                let (op, name) = match value.find(|c: char| c.is_ascii_alphabetic()) {
+                    Some(at) => value.split_at(at),
+                    None => return None,
+                };
+                let size = PrSize::parse(name)?;
+                let (min, max) = match op {
+                    "" => (Some(size), Some(size)),
+                    "<=" => (None, Some(size)),
+                    "<" => (None, Some(size.smaller()?)),
+                    ">=" => (Some(size), None),
+                    ">" => (Some(size.larger()?), None),
+                    _ => return None,
+                };
+                if let Some(min) = min {
+                    spec.min_size = Some(min.label().to_string());
+                }
+                if let Some(max) = max {
+                    spec.max_size = Some(max.label().to_string());
+                }
+            }
+            "age" => {
+                let days = value.strip_suffix('d')?;
+                if let Some(days) = days.strip_prefix('>') {
+                    spec.older_than_days = Some(days.parse().ok()?);
+                } else if let Some(days) = days.strip_prefix('<') {
+                    spec.newer_than_days = Some(days.parse().ok()?);
+                } else {
+                    return None;
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    local.then_some(spec)
+}
+
+/// The query selecting the same PRs as `spec` (inverse of `parse`)
+pub fn to_query(spec: &FilterSpec) -> String {
+    let mut terms = Vec::new();
+    if let Some(author) = &spec.author {
+        terms.push(format!("author:{}", quote(author)));
+    }
+    if let Some(part) = &spec.author_contains {
+        terms.push(format!("author:*{}*", quote(part)));
+    }
+    terms.extend(
+        spec.labels_include
+            .iter()
+            .map(|label| format!("label:{}", quote(label))),
+    );
+    terms.extend(
+        spec.labels_exclude
+            .iter()
+            .map(|label| format!("-label:{}", quote(label))),
+    );
+    if let Some(ci) = spec.ci {
+        terms.push(format!("ci:{}", ci.as_str()));
+    }
+    if let Some(draft) = spec.draft {
+        terms.push(format!("draft:{}", draft));
+    }
+    if let Some(update_type) = &spec.update_type {
+        terms.push(format!("update:{}", update_type));
+    }
+    if let Some(base) = &spec.base_branch {
+        terms.push(format!("base:{}", quote(base)));
+    }
+    if let Some(days) = spec.older_than_days {
+        terms.push(format!("age:>{}d", days));
+    }
+    if let Some(days) = spec.newer_than_days {
+        terms.push(format!("age:<{}d", days));
+    }
+    match (&spec.min_size, &spec.max_size) {
+        (Some(min), Some(max)) if min == max => terms.push(format!("size:{}", min)),
+        (min, max) => {
+            if let Some(min) = min {
+                terms.push(format!("size:>={}", min));
+            }
+            if let Some(max) = max {
+                terms.push(format!("size:<={}", max));
+            }
+        }
+    }
+    terms.join(" ")
+}
+
+/// Quote values containing spaces (`label:"needs review"`)
+fn quote(value: &str) -> String {
+    if value.contains(' ') {
+        format!("\"{}\"", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Split a query at whitespace outside of double quotes
+fn split_terms(query: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in query.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.push(c);
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    terms.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        terms.push(current);
+    }
+    terms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sizes() -> PrSizeThresholds {
+        PrSizeThresholds::default()
+    }
+
+    fn pr(author: &str, labels: &[&str], mergeable: MergeableStatus, age_days: i64) -> Pr {
+        let mut pr = Pr::new(1, "Bump serde", author, "sha");
+        pr.labels = labels.iter().map(|l| l.to_string()).collect();
+        pr.mergeable = mergeable;
+        pr.created_at = Utc::now() - Duration::days(age_days);
+        pr
+    }
+
+    #[test]
+    fn test_parse_and_print_round_trip() {
+        let query = "author:*bot* label:security -label:\"do not merge\" ci:success draft:false update:patch base:release/* age:>7d age:<30d size:>=S size:<=L";
+        let spec = parse(query).unwrap();
+        assert_eq!(spec.author_contains.as_deref(), Some("bot"));
+        assert_eq!(spec.labels_include, vec!["security"]);
+        assert_eq!(spec.labels_exclude, vec!["do not merge"]);
+        assert_eq!(spec.ci, Some(FilterCi::Success));
+        assert_eq!(spec.draft, Some(false));
+        assert_eq!(spec.update_type.as_deref(), Some("patch"));
+        assert_eq!(spec.base_branch.as_deref(), Some("release/*"));
+        assert_eq!(spec.older_than_days, Some(7));
+        assert_eq!(spec.newer_than_days, Some(30));
+        assert_eq!(spec.min_size.as_deref(), Some("S"));
+        assert_eq!(spec.max_size.as_deref(), Some("L"));
+
+        assert_eq!(to_query(&spec), query);
+        assert_eq!(parse(&to_query(&spec)), Some(spec));
+    }
+
+    #[test]
+    fn test_github_searches_are_not_parsed() {
+        // No local-only qualifier: GitHub search handles these
+        assert_eq!(parse("author:alice"), None);
+        assert_eq!(parse("label:bug is:open"), None);
+        assert_eq!(parse("fix login"), None);
+        // Unknown terms or values
+        assert_eq!(parse("ci:green"), None);
+        assert_eq!(parse("update:major review:none"), None);
+        assert_eq!(parse("age:7d"), None);
+        assert_eq!(parse("-ci:failed"), None);
+
+        let spec = parse("update:Major").unwrap();
+        assert_eq!(spec.update_type.as_deref(), Some("major"));
+    }
+
+    #[test]
+    fn test_matches_every_criterion() {
+        let now = Utc::now();
+        let bot = pr(
+            "dependabot[bot]",
+            &["security", "dependencies"],
+            MergeableStatus::Ready,
+            10,
+        );
+        let human = pr("alice", &["security"], MergeableStatus::BuildFailed, 2)
+            .with_branches("fix-login", "main");
+
+        let spec = parse("author:dependabot label:security ci:success age:>7d").unwrap();
+        assert!(matches(&spec, &bot, now, &sizes()));
+        assert!(!matches(&spec, &human, now, &sizes()));
+
+        assert!(matches(
+            &parse("author:*BOT* ci:success").unwrap(),
+            &bot,
+            now,
+            &sizes()
+        ));
+        assert!(matches(
+            &parse("ci:failed age:<7d").unwrap(),
+            &human,
+            now,
+            &sizes()
+        ));
+        assert!(!matches(
+            &parse("ci:pending").unwrap(),
+            &human,
+            now,
+            &sizes()
+        ));
+        assert!(!matches(
+            &parse("ci:success -label:dependencies").unwrap(),
+            &bot,
+            now,
+            &sizes()
+        ));
+        assert!(matches(
+            &parse("ci:failed draft:false").unwrap(),
+            &human,
+            now,
+            &sizes()
+        ));
+        assert!(!matches(
+            &parse("update:patch").unwrap(),
+            &human,
+            now,
+            &sizes()
+        ));
+
+        let release =
+            pr("alice", &[], MergeableStatus::Ready, 1).with_branches("fix", "release/1.4");
+        assert!(matches(
+            &parse("base:release/*").unwrap(),
+            &release,
+            now,
+            &sizes()
+        ));
+        assert!(!matches(
+            &parse("base:release/*").unwrap(),
+            &human,
+            now,
+            &sizes()
+        ));
+        assert!(matches(&parse("base:main").unwrap(), &human, now, &sizes()));
+
+        assert!(matches(&FilterSpec::default(), &human, now, &sizes()));
+    }
+
+    #[test]
+    fn test_size_qualifier() {
+        let spec = parse("size:<=m").unwrap();
+        assert_eq!((spec.min_size, spec.max_size.as_deref()), (None, Some("M")));
+        let spec = parse("size:>L").unwrap();
+        assert_eq!(spec.min_size.as_deref(), Some("XL"));
+        assert_eq!(to_query(&parse("size:M").unwrap()), "size:M");
+        assert_eq!(parse("size:<XS"), None);
+        assert_eq!(parse("size:=M"), None);
+        assert_eq!(parse("size:XXL"), None);
+
+        let now = Utc::now();
+        let unsized_pr = pr("alice", &[], MergeableStatus::Ready, 1);
+        let medium = unsized_pr.clone().with_stats(200, 50, 7);
+        let large = unsized_pr.clone().with_stats(200, 51, 7);
+        let at_most_m = parse("size:<=M").unwrap();
+        assert!(matches(&at_most_m, &medium, now, &sizes()));
+        assert!(!matches(&at_most_m, &large, now, &sizes()));
+        // Without stats the size is unknown
+        assert!(!matches(&at_most_m, &unsized_pr, now, &sizes()));
+
+        let custom = PrSizeThresholds {
+            m: 300,
+            ..Default::default()
+        };
+        assert!(matches(&at_most_m, &large, now, &custom));
+    }
+}
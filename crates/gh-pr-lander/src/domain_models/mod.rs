@@ -3,24 +3,53 @@
 //! Core domain types used throughout the application.
 //! These are pure domain concepts, separate from UI state.
 
+pub mod attention;
+pub mod author;
 pub mod auto_merge;
+pub mod ci_budget;
+pub mod codeowners;
+pub mod conflicts;
+pub mod dependency_update;
+pub mod filter_spec;
 pub mod operation_monitor;
+pub mod policy;
 pub mod pr_filter;
 pub mod pr_number;
+pub mod pr_size;
+pub mod pr_stack;
+pub mod pr_tab;
 pub mod pull_request;
+pub mod release_notes;
 pub mod repository;
+pub mod snooze;
 pub mod task_status;
 
 // Re-export commonly used types (allow unused - these are for external crate use)
+pub use attention::{attention_order, Attention};
+pub use author::normalize_author;
 #[allow(unused_imports)]
 pub use auto_merge::AutoMergePr;
+pub use ci_budget::{learn_ci_budget, CiBudget};
+pub use codeowners::{CodeOwners, Owner, CODEOWNERS_PATHS};
+pub use conflicts::{ConflictCandidate, ConflictKind, ConflictSummary};
+pub use dependency_update::{DependencyUpdate, UpdateKey, UpdateType};
+pub use gh_pr_config::WatchedPr;
 #[allow(unused_imports)]
 pub use operation_monitor::{OperationMonitor, OperationType};
+pub use policy::PolicyStep;
 #[allow(unused_imports)]
 pub use pr_filter::PrFilter;
 #[allow(unused_imports)]
 pub use pr_number::PrNumber;
-pub use pull_request::{LoadingState, MaturityState, MergeableStatus, Pr, ReviewDecision};
+pub use pr_size::PrSize;
+pub use pr_stack::{retain_stacked_on, PrStacks};
+pub use pr_tab::PrTab;
+pub use pull_request::{
+    CiChecks, CommentCounts, LoadingState, MaturityState, MergeableStatus, Pr, PrReviewers,
+    PrState, PrStateFilter, ReviewDecision,
+};
+pub use release_notes::SinceRef;
 pub use repository::Repository;
+pub use snooze::SnoozeDuration;
 #[allow(unused_imports)]
 pub use task_status::{TaskStatus, TaskStatusType};
@@ -5,22 +5,61 @@
 use std::time::Instant;
 
 /// Type of operation being monitored
-#[allow(dead_code)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum OperationType {
     /// Rebase operation
     Rebase,
     /// Merge operation
     Merge,
+    /// Approving review
+    Approve,
+    /// Comment on the PR
+    Comment,
+    /// Review requesting changes
+    RequestChanges,
+    /// Closing the PR
+    Close,
+    /// Rerun of failed CI jobs
+    Rerun,
 }
 
-#[allow(dead_code)]
 impl OperationType {
     /// Get the display label for this operation type
     pub fn label(&self) -> &'static str {
         match self {
             OperationType::Rebase => "Rebasing",
             OperationType::Merge => "Merging",
+            OperationType::Approve => "Approving",
+            OperationType::Comment => "Commenting",
+            OperationType::RequestChanges => "Requesting changes",
+            OperationType::Close => "Closing",
+            OperationType::Rerun => "Rerunning",
+        }
+    }
+
+    /// Name of the operation in messages ("merge already in progress")
+    pub fn noun(&self) -> &'static str {
+        match self {
+            OperationType::Rebase => "rebase",
+            OperationType::Merge => "merge",
+            OperationType::Approve => "approval",
+            OperationType::Comment => "comment",
+            OperationType::RequestChanges => "change request",
+            OperationType::Close => "close",
+            OperationType::Rerun => "rerun",
+        }
+    }
+
+    /// Status bar source of the operation's messages
+    pub fn source(&self) -> &'static str {
+        match self {
+            OperationType::Rebase => "Rebase",
+            OperationType::Merge => "Merge",
+            OperationType::Approve => "Approve",
+            OperationType::Comment => "Comment",
+            OperationType::RequestChanges => "Request Changes",
+            OperationType::Close => "Close",
+            OperationType::Rerun => "Rerun",
         }
     }
 }
@@ -0,0 +1,340 @@
+//! Auto-approve/merge policies
+//!
+//! Pure evaluation of the `[policies.<name>]` config sections against loaded
+//! PRs. Executing the resulting steps is up to the GitHub middleware.
+
+use super::{filter_spec, MaturityState, MergeableStatus, Pr, PrState};
+use crate::utils::issue_extractor::glob_match;
+use gh_pr_config::{FilterSpec, PolicyAction, PolicyCi, PolicyConfig, PrSizeThresholds};
+use std::collections::BTreeMap;
+
+/// What a policy decided for one PR
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyDecision {
+    /// PR number
+    pub pr_number: usize,
+    /// Why the policy skips the PR (empty = the PR matched)
+    pub mismatches: Vec<String>,
+}
+
+impl PolicyDecision {
+    /// Whether the policy acts on the PR
+    pub fn matched(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// One PR a policy run acts on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PolicyStep {
+    /// PR number
+    pub pr_number: u64,
+    /// Approve and/or merge
+    pub action: PolicyAction,
+}
+
+/// Evaluate `policy` against a single PR (`filters` = the saved filters,
+/// `sizes` bound their size criteria)
+pub fn evaluate(
+    policy: &PolicyConfig,
+    filters: &BTreeMap<String, FilterSpec>,
+    sizes: &PrSizeThresholds,
+    pr: &Pr,
+) -> PolicyDecision {
+    let mut mismatches = Vec::new();
+
+    if pr.state != PrState::Open {
+        mismatches.push("not open".to_string());
+    }
+    if pr.maturity == MaturityState::Draft {
+        mismatches.push("draft".to_string());
+    }
+
+    if !policy.authors.is_empty()
+        && !policy
+            .authors
+            .iter()
+            .any(|pattern| glob_match(pattern, &pr.author))
+    {
+        mismatches.push(format!("author {} not allowed", pr.author));
+    }
+
+    if !policy.update_types.is_empty() {
+        match pr.dependency_update.as_ref().and_then(|u| u.update_type) {
+            Some(update_type)
+                if policy
+                    .update_types
+                    .iter()
+                    .any(|allowed| allowed.eq_ignore_ascii_case(update_type.label())) => {}
+            Some(update_type) => {
+                mismatches.push(format!("{} update not allowed", update_type.label()))
+            }
+            None => mismatches.push("no dependency update".to_string()),
+        }
+    }
+
+    let ci_ok = match policy.ci {
+        PolicyCi::Success => pr.mergeable.passes_required_checks(),
+        PolicyCi::NotFailed => !matches!(
+            pr.mergeable,
+            MergeableStatus::BuildFailed | MergeableStatus::Conflicted
+        ),
+        PolicyCi::Any => true,
+    };
+    if !ci_ok {
+        mismatches.push(format!("CI {}", pr.mergeable.label()));
+    }
+
+    if let Some(max) = policy.max_additions {
+        if pr.additions > max {
+            mismatches.push(format!("{} additions > {}", pr.additions, max));
+        }
+    }
+
+    if !policy.labels_allow.is_empty() && !pr.labels.iter().any(|l| policy.labels_allow.contains(l))
+    {
+        mismatches.push(format!("none of labels {}", policy.labels_allow.join(", ")));
+    }
+    if let Some(label) = pr.labels.iter().find(|l| policy.labels_deny.contains(l)) {
+        mismatches.push(format!("labeled {}", label));
+    }
+
+    if let Some(name) = &policy.filter {
+        match filters.get(name) {
+            Some(spec) if filter_spec::matches(spec, pr, chrono::Utc::now(), sizes) => {}
+            Some(_) => mismatches.push(format!("filter {} not matched", name)),
+            None => mismatches.push(format!("unknown filter {}", name)),
+        }
+    }
+
+    PolicyDecision {
+        pr_number: pr.number,
+        mismatches,
+    }
+}
+
+/// Assign each PR to the first policy (by name) that matches it
+///
+/// Returns the matched steps with the name of their policy, and the
+/// decisions of the PRs no policy matched (with the first policy's reasons).
+pub fn plan(
+    policies: &BTreeMap<String, PolicyConfig>,
+    filters: &BTreeMap<String, FilterSpec>,
+    sizes: &PrSizeThresholds,
+    prs: &[&Pr],
+) -> (Vec<(String, PolicyStep)>, Vec<PolicyDecision>) {
+    let mut steps = Vec::new();
+    let mut skipped = Vec::new();
+
+    for pr in prs {
+        let mut first_mismatch = None;
+        let matched = policies.iter().find(|(_, policy)| {
+            let decision = evaluate(policy, filters, sizes, pr);
+            let matched = decision.matched();
+            first_mismatch.get_or_insert(decision);
+            matched
+        });
+        match (matched, first_mismatch) {
+            (Some((name, policy)), _) => steps.push((
+                name.clone(),
+                PolicyStep {
+                    pr_number: pr.number as u64,
+                    action: policy.action,
+                },
+            )),
+            (None, Some(decision)) => skipped.push(decision),
+            (None, None) => {}
+        }
+    }
+
+    (steps, skipped)
+}
+
+/// Human readable steps of a policy action ("approve + merge")
+pub fn action_label(action: PolicyAction) -> &'static str {
+    match action {
+        PolicyAction::ApproveMerge => "approve + merge",
+        PolicyAction::Approve => "approve",
+        PolicyAction::Merge => "merge",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sizes() -> PrSizeThresholds {
+        PrSizeThresholds::default()
+    }
+
+    fn bot_pr(number: usize, title: &str, branch: &str) -> Pr {
+        let mut pr = Pr::new(number, title, "dependabot[bot]", "sha")
+            .with_branches(branch, "main")
+            .with_detected_dependency_update();
+        pr.mergeable = MergeableStatus::Ready;
+        pr.additions = 4;
+        pr
+    }
+
+    fn auto_bot() -> PolicyConfig {
+        PolicyConfig {
+            authors: vec!["dependabot*".to_string(), "renovate*".to_string()],
+            update_types: vec!["patch".to_string(), "minor".to_string()],
+            max_additions: Some(100),
+            labels_deny: vec!["do-not-merge".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn patch_pr(number: usize) -> Pr {
+        bot_pr(
+            number,
+            "Bump serde from 1.0.188 to 1.0.190",
+            "dependabot/cargo/serde-1.0.190",
+        )
+    }
+
+    #[test]
+    fn test_matching_bot_pr() {
+        let pr = patch_pr(1);
+        assert!(pr.dependency_update.is_some());
+        assert!(evaluate(&auto_bot(), &BTreeMap::new(), &sizes(), &pr).matched());
+    }
+
+    #[test]
+    fn test_mismatch_reasons() {
+        let mut major = bot_pr(
+            2,
+            "Bump tokio from 0.2.0 to 1.0.0",
+            "dependabot/cargo/tokio-1.0.0",
+        );
+        major.mergeable = MergeableStatus::BuildFailed;
+        major.additions = 500;
+        major.labels = vec!["do-not-merge".to_string()];
+
+        let decision = evaluate(&auto_bot(), &BTreeMap::new(), &sizes(), &major);
+        assert_eq!(
+            decision.mismatches,
+            vec![
+                "major update not allowed",
+                "CI Build Failed",
+                "500 additions > 100",
+                "labeled do-not-merge",
+            ]
+        );
+
+        let human = Pr::new(3, "Refactor", "alice", "sha");
+        let decision = evaluate(&auto_bot(), &BTreeMap::new(), &sizes(), &human);
+        assert!(decision
+            .mismatches
+            .contains(&"author alice not allowed".to_string()));
+        assert!(decision
+            .mismatches
+            .contains(&"no dependency update".to_string()));
+    }
+
+    #[test]
+    fn test_ci_and_label_matchers() {
+        let mut pr = patch_pr(4);
+        pr.mergeable = MergeableStatus::Checking;
+        assert!(!evaluate(&auto_bot(), &BTreeMap::new(), &sizes(), &pr).matched());
+
+        let relaxed = PolicyConfig {
+            ci: PolicyCi::NotFailed,
+            labels_allow: vec!["dependencies".to_string()],
+            ..auto_bot()
+        };
+        assert_eq!(
+            evaluate(&relaxed, &BTreeMap::new(), &sizes(), &pr).mismatches,
+            vec!["none of labels dependencies"]
+        );
+        pr.labels = vec!["dependencies".to_string()];
+        assert!(evaluate(&relaxed, &BTreeMap::new(), &sizes(), &pr).matched());
+
+        pr.maturity = MaturityState::Draft;
+        assert_eq!(
+            evaluate(&relaxed, &BTreeMap::new(), &sizes(), &pr).mismatches,
+            vec!["draft"]
+        );
+    }
+
+    #[test]
+    fn test_saved_filter_matcher() {
+        let policy = PolicyConfig {
+            filter: Some("security-bumps".to_string()),
+            ..auto_bot()
+        };
+        let mut filters = BTreeMap::new();
+        let pr = patch_pr(5);
+        assert_eq!(
+            evaluate(&policy, &filters, &sizes(), &pr).mismatches,
+            vec!["unknown filter security-bumps"]
+        );
+
+        filters.insert(
+            "security-bumps".to_string(),
+            FilterSpec {
+                labels_include: vec!["security".to_string()],
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            evaluate(&policy, &filters, &sizes(), &pr).mismatches,
+            vec!["filter security-bumps not matched"]
+        );
+
+        let mut security = patch_pr(6);
+        security.labels = vec!["security".to_string()];
+        assert!(evaluate(&policy, &filters, &sizes(), &security).matched());
+    }
+
+    #[test]
+    fn test_plan_uses_first_matching_policy() {
+        let mut policies = BTreeMap::new();
+        policies.insert("auto-bot".to_string(), auto_bot());
+        policies.insert(
+            "docs".to_string(),
+            PolicyConfig {
+                labels_allow: vec!["docs".to_string()],
+                action: PolicyAction::Approve,
+                ..Default::default()
+            },
+        );
+
+        let bot = patch_pr(1);
+        let mut docs = Pr::new(2, "Fix typo", "alice", "sha");
+        docs.mergeable = MergeableStatus::Ready;
+        docs.labels = vec!["docs".to_string()];
+        let other = Pr::new(3, "Refactor", "bob", "sha");
+        assert!(other.dependency_update.is_none());
+
+        let (steps, skipped) = plan(
+            &policies,
+            &BTreeMap::new(),
+            &sizes(),
+            &[&bot, &docs, &other],
+        );
+        assert_eq!(
+            steps,
+            vec![
+                (
+                    "auto-bot".to_string(),
+                    PolicyStep {
+                        pr_number: 1,
+                        action: PolicyAction::ApproveMerge
+                    }
+                ),
+                (
+                    "docs".to_string(),
+                    PolicyStep {
+                        pr_number: 2,
+                        action: PolicyAction::Approve
+                    }
+                ),
+            ]
+        );
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].pr_number, 3);
+        assert!(!skipped[0].matched());
+    }
+}
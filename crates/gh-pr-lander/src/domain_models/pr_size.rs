@@ -0,0 +1,102 @@
+//! PR size buckets
+//!
+//! Review effort grows with the number of changed lines, so PRs are put into
+//! T-shirt sizes by their additions plus deletions. The bounds come from
+//! `[ui] pr_size`; a PR whose stats are not fetched yet has no size.
+
+use gh_pr_config::PrSizeThresholds;
+
+/// Size bucket of a PR, ordered from small to large
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PrSize {
+    Xs,
+    S,
+    M,
+    L,
+    Xl,
+}
+
+impl PrSize {
+    /// All buckets, smallest first
+    pub const ALL: [PrSize; 5] = [Self::Xs, Self::S, Self::M, Self::L, Self::Xl];
+
+    /// Bucket of a PR changing `lines` lines (each bound is inclusive)
+    pub fn of(lines: usize, thresholds: &PrSizeThresholds) -> Self {
+        if lines <= thresholds.xs {
+            Self::Xs
+        } else if lines <= thresholds.s {
+            Self::S
+        } else if lines <= thresholds.m {
+            Self::M
+        } else if lines <= thresholds.l {
+            Self::L
+        } else {
+            Self::Xl
+        }
+    }
+
+    /// Badge text, as used by the `size:` filter ("XS" ... "XL")
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Xs => "XS",
+            Self::S => "S",
+            Self::M => "M",
+            Self::L => "L",
+            Self::Xl => "XL",
+        }
+    }
+
+    /// Parse a bucket name (case-insensitive)
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|size| size.label().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// The next smaller bucket (None for XS)
+    pub fn smaller(self) -> Option<Self> {
+        Self::ALL.into_iter().rev().find(|size| *size < self)
+    }
+
+    /// The next larger bucket (None for XL)
+    pub fn larger(self) -> Option<Self> {
+        Self::ALL.into_iter().find(|size| *size > self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threshold_boundaries() {
+        let thresholds = PrSizeThresholds::default();
+        let size = |lines| PrSize::of(lines, &thresholds);
+        assert_eq!(size(0), PrSize::Xs);
+        assert_eq!(size(10), PrSize::Xs);
+        assert_eq!(size(11), PrSize::S);
+        assert_eq!(size(50), PrSize::S);
+        assert_eq!(size(51), PrSize::M);
+        assert_eq!(size(250), PrSize::M);
+        assert_eq!(size(251), PrSize::L);
+        assert_eq!(size(1000), PrSize::L);
+        assert_eq!(size(1001), PrSize::Xl);
+
+        let custom = PrSizeThresholds {
+            m: 400,
+            ..Default::default()
+        };
+        assert_eq!(PrSize::of(300, &custom), PrSize::M);
+    }
+
+    #[test]
+    fn test_parse_and_neighbors() {
+        assert_eq!(PrSize::parse("xl"), Some(PrSize::Xl));
+        assert_eq!(PrSize::parse(" M "), Some(PrSize::M));
+        assert_eq!(PrSize::parse("XXL"), None);
+        assert_eq!(PrSize::M.smaller(), Some(PrSize::S));
+        assert_eq!(PrSize::M.larger(), Some(PrSize::L));
+        assert_eq!(PrSize::Xs.smaller(), None);
+        assert_eq!(PrSize::Xl.larger(), None);
+    }
+}
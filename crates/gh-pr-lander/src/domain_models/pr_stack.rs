@@ -0,0 +1,177 @@
+//! Stacked pull requests
+//!
+//! A PR is stacked on another when its base branch is the other PR's head
+//! branch (PR B targets PR A's branch). Stacks are derived from the loaded
+//! PR list; malformed data (e.g. two PRs targeting each other) must never
+//! make the traversal loop.
+
+use super::Pr;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Stack relations between the PRs of one repository
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrStacks {
+    /// PR number -> number of the PR it is stacked on
+    parents: HashMap<usize, usize>,
+}
+
+impl PrStacks {
+    /// Compute the stack relations of a PR list
+    pub fn compute(prs: &[Pr]) -> Self {
+        let mut by_head: HashMap<&str, usize> = HashMap::new();
+        for pr in prs {
+            // A fork's branch is not in this repository, so no PR can target it
+            if !pr.head_branch.is_empty() && !pr.is_from_fork {
+                by_head.entry(pr.head_branch.as_str()).or_insert(pr.number);
+            }
+        }
+
+        let parents = prs
+            .iter()
+            .filter_map(|pr| {
+                let parent = *by_head.get(pr.base_branch.as_str())?;
+                (parent != pr.number).then_some((pr.number, parent))
+            })
+            .collect();
+
+        Self { parents }
+    }
+
+    /// The PR this PR is stacked on
+    pub fn parent(&self, number: usize) -> Option<usize> {
+        self.parents.get(&number).copied()
+    }
+
+    /// Number of PRs below this one in its stack (0 = not stacked)
+    pub fn depth(&self, number: usize) -> usize {
+        let mut seen = HashSet::from([number]);
+        let mut current = number;
+        while let Some(parent) = self.parent(current) {
+            if !seen.insert(parent) {
+                break; // Cycle
+            }
+            current = parent;
+        }
+        seen.len() - 1
+    }
+
+    /// PRs stacked directly on this one, in ascending order
+    pub fn dependents(&self, number: usize) -> Vec<usize> {
+        let mut dependents: Vec<usize> = self
+            .parents
+            .iter()
+            .filter(|(_, &parent)| parent == number)
+            .map(|(&child, _)| child)
+            .collect();
+        dependents.sort_unstable();
+        dependents
+    }
+
+    /// All PRs stacked on this one, level by level (update order)
+    pub fn descendants(&self, number: usize) -> Vec<usize> {
+        let mut seen = HashSet::from([number]);
+        let mut order = Vec::new();
+        let mut queue = VecDeque::from([number]);
+        while let Some(current) = queue.pop_front() {
+            for child in self.dependents(current) {
+                if seen.insert(child) {
+                    order.push(child);
+                    queue.push_back(child);
+                }
+            }
+        }
+        order
+    }
+}
+
+/// Keep the PRs that target `base`, directly or through a stack of PRs
+pub fn retain_stacked_on(prs: Vec<Pr>, base: &str) -> Vec<Pr> {
+    let stacks = PrStacks::compute(&prs);
+    let roots: HashSet<usize> = prs
+        .iter()
+        .filter(|pr| pr.base_branch == base)
+        .map(|pr| pr.number)
+        .collect();
+
+    let mut keep = roots.clone();
+    for root in &roots {
+        keep.extend(stacks.descendants(*root));
+    }
+
+    prs.into_iter()
+        .filter(|pr| keep.contains(&pr.number))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr(number: usize, head: &str, base: &str) -> Pr {
+        Pr::new(number, format!("PR {}", number), "author", "sha").with_branches(head, base)
+    }
+
+    #[test]
+    fn test_multi_level_stack() {
+        let prs = vec![
+            pr(1, "feature-a", "main"),
+            pr(2, "feature-b", "feature-a"),
+            pr(3, "feature-c", "feature-b"),
+            pr(4, "feature-d", "feature-a"),
+            pr(5, "other", "main"),
+        ];
+        let stacks = PrStacks::compute(&prs);
+
+        assert_eq!(stacks.parent(1), None);
+        assert_eq!(stacks.parent(3), Some(2));
+        assert_eq!(stacks.depth(1), 0);
+        assert_eq!(stacks.depth(3), 2);
+        assert_eq!(stacks.dependents(1), vec![2, 4]);
+        assert_eq!(stacks.descendants(1), vec![2, 4, 3]);
+        assert!(stacks.descendants(5).is_empty());
+    }
+
+    #[test]
+    fn test_fork_branches_are_not_stack_parents() {
+        let prs = vec![
+            pr(1, "fix", "main").with_fork("contributor", "repo", true),
+            pr(2, "feature", "fix"),
+        ];
+        let stacks = PrStacks::compute(&prs);
+
+        assert_eq!(stacks.parent(2), None);
+        assert!(stacks.dependents(1).is_empty());
+    }
+
+    #[test]
+    fn test_cycles_do_not_loop() {
+        let prs = vec![
+            pr(1, "a", "b"),
+            pr(2, "b", "a"),
+            pr(3, "c", "c"), // targets its own branch
+        ];
+        let stacks = PrStacks::compute(&prs);
+
+        assert_eq!(stacks.parent(1), Some(2));
+        assert_eq!(stacks.parent(2), Some(1));
+        assert_eq!(stacks.depth(1), 1);
+        assert_eq!(stacks.descendants(1), vec![2]);
+        assert_eq!(stacks.parent(3), None);
+        assert_eq!(stacks.depth(3), 0);
+    }
+
+    #[test]
+    fn test_retain_stacked_on() {
+        let prs = vec![
+            pr(1, "feature-a", "main"),
+            pr(2, "feature-b", "feature-a"),
+            pr(3, "backport", "release-1.x"),
+            pr(4, "fix", "backport"),
+        ];
+        let numbers: Vec<usize> = retain_stacked_on(prs, "main")
+            .iter()
+            .map(|pr| pr.number)
+            .collect();
+        assert_eq!(numbers, vec![1, 2]);
+    }
+}
@@ -0,0 +1,89 @@
+//! Pull request tabs
+//!
+//! The tabs of a PR page on GitHub, for opening one of them directly.
+
+/// A tab of a pull request page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PrTab {
+    /// Description and discussion (the PR's own URL)
+    #[default]
+    Conversation,
+    /// Changed files
+    Files,
+    /// CI check runs
+    Checks,
+    /// Commits of the PR
+    Commits,
+    /// Web editor for resolving merge conflicts
+    Conflicts,
+}
+
+impl PrTab {
+    /// Path suffix of the tab, appended to the PR's URL
+    pub fn suffix(self) -> &'static str {
+        match self {
+            Self::Conversation => "",
+            Self::Files => "/files",
+            Self::Checks => "/checks",
+            Self::Commits => "/commits",
+            Self::Conflicts => "/conflicts",
+        }
+    }
+
+    /// URL of the tab, given the PR's `html_url`
+    ///
+    /// Works for any host and path prefix (GitHub Enterprise), as only the
+    /// path after the PR number changes. Query and fragment are dropped.
+    pub fn url(self, html_url: &str) -> String {
+        let end = html_url.find(['?', '#']).unwrap_or(html_url.len());
+        let base = html_url[..end].trim_end_matches('/');
+        format!("{}{}", base, self.suffix())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tab_urls() {
+        let url = "https://github.com/acme/api/pull/42";
+        assert_eq!(PrTab::Conversation.url(url), url);
+        assert_eq!(
+            PrTab::Files.url(url),
+            "https://github.com/acme/api/pull/42/files"
+        );
+        assert_eq!(
+            PrTab::Checks.url(url),
+            "https://github.com/acme/api/pull/42/checks"
+        );
+        assert_eq!(
+            PrTab::Commits.url(url),
+            "https://github.com/acme/api/pull/42/commits"
+        );
+        assert_eq!(
+            PrTab::Conflicts.url(url),
+            "https://github.com/acme/api/pull/42/conflicts"
+        );
+    }
+
+    #[test]
+    fn test_tab_urls_of_enterprise_hosts_and_odd_urls() {
+        assert_eq!(
+            PrTab::Checks.url("https://ghe.example.com/git/acme/api/pull/7"),
+            "https://ghe.example.com/git/acme/api/pull/7/checks"
+        );
+        assert_eq!(
+            PrTab::Files.url("https://ghe.example.com:8443/acme/api/pull/7/"),
+            "https://ghe.example.com:8443/acme/api/pull/7/files"
+        );
+        assert_eq!(
+            PrTab::Commits.url("https://github.com/acme/api/pull/7#issuecomment-1"),
+            "https://github.com/acme/api/pull/7/commits"
+        );
+        assert_eq!(
+            PrTab::Conversation.url("https://github.com/acme/api/pull/7?notification=1"),
+            "https://github.com/acme/api/pull/7"
+        );
+    }
+}
@@ -2,11 +2,15 @@
 //!
 //! Domain model for GitHub Pull Requests.
 
+use super::{PrSize, Repository};
 use chrono::{DateTime, Utc};
+use gh_pr_config::PrSizeThresholds;
 use serde::{Deserialize, Serialize};
 
 // Re-export from gh_client for convenience
-pub use gh_client::types::{MaturityState, ReviewDecision};
+pub use gh_client::types::{
+    CommentCounts, MaturityState, PrReviewers, PrState, PrStateFilter, ReviewDecision,
+};
 
 /// A GitHub Pull Request
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,14 +25,26 @@ pub struct Pr {
     pub author: String,
     /// Number of comments
     pub comments: usize,
+    /// Comment breakdown, fetched in the background (None until then)
+    #[serde(default)]
+    pub comment_counts: Option<CommentCounts>,
     /// Current mergeable status
     pub mergeable: MergeableStatus,
+    /// Progress of the running checks (None unless CI runs)
+    #[serde(default)]
+    pub ci_budget: Option<super::CiBudget>,
+    /// Check runs of the head commit, fetched with the CI status
+    #[serde(default)]
+    pub ci_checks: CiChecks,
     /// Whether the PR needs rebase (behind base branch)
     pub needs_rebase: bool,
     /// HEAD commit SHA (for CI status checks)
     pub head_sha: String,
     /// HEAD branch name (e.g., "feature/my-branch")
     pub head_branch: String,
+    /// Base branch the PR merges into (another PR's head branch when stacked)
+    #[serde(default)]
+    pub base_branch: String,
     /// When the PR was created
     pub created_at: DateTime<Utc>,
     /// When the PR was last updated
@@ -39,10 +55,42 @@ pub struct Pr {
     pub additions: usize,
     /// Number of lines deleted
     pub deletions: usize,
+    /// Number of changed files, fetched along with the additions and
+    /// deletions (None until then, the line counts are 0 meanwhile)
+    #[serde(default)]
+    pub changed_files: Option<usize>,
     /// Draft vs Ready state
     pub maturity: MaturityState,
     /// Review decision state (approved, changes requested, etc.)
     pub review_decision: ReviewDecision,
+    /// Requested reviewers and latest reviews, fetched for the attention sort
+    /// (None until then)
+    #[serde(default)]
+    pub reviewers: Option<PrReviewers>,
+    /// Open, closed or merged
+    #[serde(default)]
+    pub state: PrState,
+    /// Names of the labels on the PR
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Dependency update proposed by Dependabot or Renovate (None for other PRs)
+    #[serde(default)]
+    pub dependency_update: Option<super::DependencyUpdate>,
+    /// Owner of the repository holding the head branch (the fork owner for fork PRs)
+    #[serde(default)]
+    pub head_repo_owner: Option<String>,
+    /// Name of the repository holding the head branch
+    #[serde(default)]
+    pub head_repo_name: Option<String>,
+    /// Head branch lives in a fork (its branch cannot be deleted here)
+    #[serde(default)]
+    pub is_from_fork: bool,
+    /// Maintainers of the base repository may push to the fork's head branch
+    #[serde(default)]
+    pub maintainer_can_modify: bool,
+    /// Mergeable state GitHub reported that is unknown here, as sent
+    #[serde(default)]
+    pub mergeable_state_raw: Option<String>,
 }
 
 impl Pr {
@@ -59,17 +107,136 @@ impl Pr {
             body: String::new(),
             author: author.into(),
             comments: 0,
+            comment_counts: None,
             mergeable: MergeableStatus::Unknown,
+            ci_budget: None,
+            ci_checks: CiChecks::default(),
             needs_rebase: false,
             head_sha: head_sha.into(),
             head_branch: String::new(),
+            base_branch: String::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
             html_url: String::new(),
             additions: 0,
             deletions: 0,
+            changed_files: None,
             maturity: MaturityState::Ready,
             review_decision: ReviewDecision::Unknown,
+            reviewers: None,
+            state: PrState::Open,
+            labels: Vec::new(),
+            dependency_update: None,
+            head_repo_owner: None,
+            head_repo_name: None,
+            is_from_fork: false,
+            maintainer_can_modify: false,
+            mergeable_state_raw: None,
+        }
+    }
+
+    /// Set the head and base branches
+    pub fn with_branches(mut self, head: impl Into<String>, base: impl Into<String>) -> Self {
+        self.head_branch = head.into();
+        self.base_branch = base.into();
+        self
+    }
+
+    /// Detect the dependency update of a bot PR from its title, body and branch
+    pub fn with_detected_dependency_update(mut self) -> Self {
+        self.dependency_update = super::DependencyUpdate::detect(
+            &self.title,
+            &self.body,
+            &self.head_branch,
+            &self.author,
+        );
+        self
+    }
+
+    /// Mark the PR as coming from a fork of the base repository
+    pub fn with_fork(
+        mut self,
+        owner: impl Into<String>,
+        name: impl Into<String>,
+        maintainer_can_modify: bool,
+    ) -> Self {
+        self.head_repo_owner = Some(owner.into());
+        self.head_repo_name = Some(name.into());
+        self.is_from_fork = true;
+        self.maintainer_can_modify = maintainer_can_modify;
+        self
+    }
+
+    /// Set the fetched line and file counts
+    pub fn with_stats(mut self, additions: usize, deletions: usize, changed_files: usize) -> Self {
+        self.additions = additions;
+        self.deletions = deletions;
+        self.changed_files = Some(changed_files);
+        self
+    }
+
+    /// Changed lines (additions + deletions), None until the stats are fetched
+    pub fn changed_lines(&self) -> Option<usize> {
+        self.changed_files.map(|_| self.additions + self.deletions)
+    }
+
+    /// Size bucket by the changed lines, None until the stats are fetched
+    pub fn size(&self, thresholds: &PrSizeThresholds) -> Option<PrSize> {
+        self.changed_lines()
+            .map(|lines| PrSize::of(lines, thresholds))
+    }
+
+    /// Take over the stats fetched for the same head commit before
+    ///
+    /// The PR list does not carry stats, so a reload would show every PR as
+    /// unsized until its details are fetched again.
+    pub fn keep_stats_of(&mut self, previous: &Pr) {
+        if self.changed_files.is_none()
+            && previous.changed_files.is_some()
+            && previous.head_sha == self.head_sha
+        {
+            self.additions = previous.additions;
+            self.deletions = previous.deletions;
+            self.changed_files = previous.changed_files;
+        }
+    }
+
+    /// Base branch, if it is not the branch configured for `repo`
+    ///
+    /// An unknown (empty) base branch is not reported.
+    pub fn unusual_base(&self, repo: &Repository) -> Option<&str> {
+        Some(self.base_branch.as_str()).filter(|base| !base.is_empty() && *base != repo.branch)
+    }
+
+    /// Head branch as GitHub shows it: "user:branch" for fork PRs
+    pub fn head_label(&self) -> String {
+        match (&self.head_repo_owner, self.is_from_fork) {
+            (Some(owner), true) => format!("{}:{}", owner, self.head_branch),
+            _ => self.head_branch.clone(),
+        }
+    }
+
+    /// "owner/name" of the fork holding the head branch (None for same-repo PRs)
+    pub fn fork_full_name(&self) -> Option<String> {
+        if !self.is_from_fork {
+            return None;
+        }
+        match (&self.head_repo_owner, &self.head_repo_name) {
+            (Some(owner), Some(name)) => Some(format!("{}/{}", owner, name)),
+            _ => None,
+        }
+    }
+
+    /// Why the base repository cannot update the head branch, None if it can
+    pub fn update_branch_blocker(&self) -> Option<&'static str> {
+        if !self.is_from_fork {
+            None
+        } else if self.head_repo_owner.is_none() {
+            Some("its fork was deleted")
+        } else if !self.maintainer_can_modify {
+            Some("its fork does not allow edits from maintainers")
+        } else {
+            None
         }
     }
 
@@ -88,12 +255,18 @@ pub enum MergeableStatus {
     Unknown,
     /// Background check in progress
     Checking,
+    /// CI workflows are queued, waiting for a runner
+    Queued,
+    /// A CI deployment waits for approval before it can run
+    WaitingApproval,
     /// Ready to merge (no issues)
     Ready,
     /// Branch is behind, needs rebase
     NeedsRebase,
     /// CI/build checks failed
     BuildFailed,
+    /// Required checks passed, checks branch protection does not require failed
+    OptionalFailed,
     /// Has merge conflicts
     Conflicted,
     /// Blocked by reviews or other checks
@@ -104,18 +277,52 @@ pub enum MergeableStatus {
     Merging,
 }
 
+/// Check runs of a PR's head commit
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CiChecks {
+    pub checks: Vec<gh_client::CheckResult>,
+    /// Branch protection told which checks are required, otherwise all are
+    pub required_known: bool,
+}
+
+impl CiChecks {
+    pub fn of(ci: &gh_client::CiStatus) -> Self {
+        Self {
+            checks: ci.checks.clone(),
+            required_known: ci.required_known,
+        }
+    }
+}
+
 impl From<gh_client::CiState> for MergeableStatus {
     fn from(ci: gh_client::CiState) -> Self {
         match ci {
             gh_client::CiState::Success => MergeableStatus::Ready,
             gh_client::CiState::Failure => MergeableStatus::BuildFailed,
             gh_client::CiState::Pending => MergeableStatus::Checking,
+            gh_client::CiState::Queued => MergeableStatus::Queued,
+            gh_client::CiState::WaitingApproval => MergeableStatus::WaitingApproval,
             gh_client::CiState::Unknown => MergeableStatus::Unknown,
         }
     }
 }
 
 impl MergeableStatus {
+    /// Status of a PR's CI, failures of optional checks alone don't fail it
+    pub fn of_ci(ci: &gh_client::CiStatus) -> Self {
+        if ci.state == gh_client::CiState::Failure
+            && ci.required_failed == 0
+            && ci.optional_failed > 0
+        {
+            return if ci.required_pending > 0 {
+                Self::Checking
+            } else {
+                Self::OptionalFailed
+            };
+        }
+        ci.state.into()
+    }
+
     /// Get the display icon for this status
     ///
     /// Icons are aligned with BuildLogJobStatus for consistency.
@@ -123,9 +330,12 @@ impl MergeableStatus {
         match self {
             Self::Unknown => "❓",
             Self::Checking => "⏳",
+            Self::Queued => "🕒",
+            Self::WaitingApproval => "✋",
             Self::Ready => "✅",
             Self::NeedsRebase => "🔂",
             Self::BuildFailed => "🚨",
+            Self::OptionalFailed => "🟡",
             Self::Conflicted => "💥",
             Self::Blocked => "🚫",
             Self::Rebasing => "🔃",
@@ -138,15 +348,38 @@ impl MergeableStatus {
         match self {
             Self::Unknown => "Unknown",
             Self::Checking => "Checking...",
+            Self::Queued => "Queued",
+            Self::WaitingApproval => "Awaiting Approval",
             Self::Ready => "Ready",
             Self::NeedsRebase => "Needs Rebase",
             Self::BuildFailed => "Build Failed",
+            Self::OptionalFailed => "optional ✗",
             Self::Conflicted => "Conflicts",
             Self::Blocked => "Blocked",
             Self::Rebasing => "Rebasing...",
             Self::Merging => "Merging...",
         }
     }
+
+    /// Explanation of statuses whose label alone does not tell what to do
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            Self::Queued => Some("CI is queued and waits for a free runner"),
+            Self::WaitingApproval => Some("CI waits for a reviewer to approve its deployment"),
+            Self::OptionalFailed => Some("only checks branch protection does not require failed"),
+            _ => None,
+        }
+    }
+
+    /// Whether CI is still working on this PR
+    pub fn is_ci_in_flight(&self) -> bool {
+        matches!(self, Self::Checking | Self::Queued | Self::WaitingApproval)
+    }
+
+    /// Whether all checks branch protection requires passed
+    pub fn passes_required_checks(&self) -> bool {
+        matches!(self, Self::Ready | Self::OptionalFailed)
+    }
 }
 
 /// Loading state for PR data
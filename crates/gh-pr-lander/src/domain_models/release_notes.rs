@@ -0,0 +1,232 @@
+//! Release notes
+//!
+//! Drafts release notes from the PRs merged since a tag or date. Every PR is
+//! listed under the first `[release_notes]` group it matches, by label, the
+//! conventional commit type of its title or being a dependency update, and
+//! the rest under the "other" heading. Nothing is published, the markdown is
+//! only previewed, copied or written to a file.
+
+use super::Pr;
+use chrono::NaiveDate;
+use gh_pr_config::{ReleaseNotesConfig, ReleaseNotesGroup};
+
+/// Where the release notes start
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SinceRef {
+    /// PRs merged after the tagged commit
+    Tag(String),
+    /// PRs merged on or after the day
+    Date(NaiveDate),
+}
+
+impl SinceRef {
+    /// A date as `YYYY-MM-DD`, anything else is taken for a tag
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        if input.is_empty() {
+            return None;
+        }
+        Some(match NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+            Ok(date) => Self::Date(date),
+            Err(_) => Self::Tag(input.to_string()),
+        })
+    }
+}
+
+impl std::fmt::Display for SinceRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tag(tag) => write!(f, "{}", tag),
+            Self::Date(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+        }
+    }
+}
+
+/// A heading of the release notes with its PRs, sorted by number
+#[derive(Debug)]
+pub struct Section<'a> {
+    pub title: &'a str,
+    pub prs: Vec<&'a Pr>,
+}
+
+/// The PRs grouped by the configured groups, in their order
+///
+/// Groups without PRs are left out, the PRs matching no group come last
+/// under the "other" heading.
+pub fn group<'a>(prs: &'a [Pr], config: &'a ReleaseNotesConfig) -> Vec<Section<'a>> {
+    let mut sections: Vec<Section> = config
+        .groups
+        .iter()
+        .map(|group| Section {
+            title: &group.title,
+            prs: Vec::new(),
+        })
+        .collect();
+    let mut other = Vec::new();
+    for pr in prs {
+        match config.groups.iter().position(|group| matches(group, pr)) {
+            Some(index) => sections[index].prs.push(pr),
+            None => other.push(pr),
+        }
+    }
+    sections.push(Section {
+        title: &config.other,
+        prs: other,
+    });
+
+    sections.retain(|section| !section.prs.is_empty());
+    for section in &mut sections {
+        section.prs.sort_by_key(|pr| pr.number);
+    }
+    sections
+}
+
+/// Markdown of the release notes
+pub fn render_markdown(since: &SinceRef, prs: &[Pr], config: &ReleaseNotesConfig) -> String {
+    let mut markdown = format!("## Changes since {}\n", since);
+    let sections = group(prs, config);
+    if sections.is_empty() {
+        markdown.push_str(&format!("\nNo PRs merged since {}.\n", since));
+        return markdown;
+    }
+    for section in sections {
+        markdown.push_str(&format!("\n### {}\n\n", section.title));
+        for pr in section.prs {
+            markdown.push_str(&format!(
+                "- {} ([#{}]({})) by @{}\n",
+                pr.title.trim(),
+                pr.number,
+                pr.html_url,
+                pr.author
+            ));
+        }
+    }
+    markdown
+}
+
+fn matches(group: &ReleaseNotesGroup, pr: &Pr) -> bool {
+    let labeled = pr.labels.iter().any(|label| {
+        group
+            .labels
+            .iter()
+            .any(|wanted| wanted.eq_ignore_ascii_case(label))
+    });
+    let typed = commit_type(&pr.title).is_some_and(|kind| {
+        group
+            .prefixes
+            .iter()
+            .any(|prefix| prefix.eq_ignore_ascii_case(kind))
+    });
+    labeled || typed || (group.dependency_updates && pr.dependency_update.is_some())
+}
+
+/// Conventional commit type of a title ("feat" of "feat(ui)!: Dark mode")
+fn commit_type(title: &str) -> Option<&str> {
+    let (head, _) = title.split_once(':')?;
+    let head = head.trim_end_matches('!');
+    let kind = head.split_once('(').map_or(head, |(kind, _)| kind).trim();
+    (!kind.is_empty() && kind.chars().all(|c| c.is_ascii_alphanumeric())).then_some(kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr(number: usize, title: &str, labels: &[&str]) -> Pr {
+        let mut pr = Pr::new(number, title, "alice", "sha");
+        pr.html_url = format!("https://github.com/acme/api/pull/{}", number);
+        pr.labels = labels.iter().map(|label| label.to_string()).collect();
+        pr
+    }
+
+    fn titles(sections: &[Section]) -> Vec<(String, Vec<usize>)> {
+        sections
+            .iter()
+            .map(|section| {
+                (
+                    section.title.to_string(),
+                    section.prs.iter().map(|pr| pr.number).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_since_ref_parse() {
+        assert_eq!(
+            SinceRef::parse(" 2025-01-15 "),
+            Some(SinceRef::Date(
+                NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+            ))
+        );
+        assert_eq!(
+            SinceRef::parse("v1.4.0"),
+            Some(SinceRef::Tag("v1.4.0".to_string()))
+        );
+        assert_eq!(SinceRef::parse("  "), None);
+    }
+
+    #[test]
+    fn test_commit_type() {
+        assert_eq!(commit_type("feat: Dark mode"), Some("feat"));
+        assert_eq!(commit_type("fix(ui)!: Crash on resize"), Some("fix"));
+        assert_eq!(commit_type("Fix crash: on resize"), None);
+        assert_eq!(commit_type("Dark mode"), None);
+    }
+
+    #[test]
+    fn test_group_by_first_matching_group() {
+        let mut bump = Pr::new(
+            5,
+            "Bump serde from 1.0.188 to 1.0.190",
+            "dependabot[bot]",
+            "",
+        )
+        .with_branches("dependabot/cargo/serde-1.0.190", "main")
+        .with_detected_dependency_update();
+        bump.html_url = "https://github.com/acme/api/pull/5".to_string();
+        let prs = vec![
+            pr(4, "fix: Crash on resize", &[]),
+            pr(2, "Dark mode", &["Enhancement"]),
+            pr(3, "Update README", &[]),
+            pr(1, "feat: Export logs", &["bug"]),
+            bump,
+        ];
+        let config = ReleaseNotesConfig::default();
+
+        // A feature labeled "bug" is a feature, the first group wins
+        assert_eq!(
+            titles(&group(&prs, &config)),
+            [
+                ("Features".to_string(), vec![1, 2]),
+                ("Bug Fixes".to_string(), vec![4]),
+                ("Dependencies".to_string(), vec![5]),
+                ("Other Changes".to_string(), vec![3]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let config = ReleaseNotesConfig::default();
+        let since = SinceRef::Tag("v1.4.0".to_string());
+        let prs = vec![pr(12, "feat: Dark mode ", &[]), pr(7, "Update README", &[])];
+        assert_eq!(
+            render_markdown(&since, &prs, &config),
+            "## Changes since v1.4.0\n\
+             \n\
+             ### Features\n\
+             \n\
+             - feat: Dark mode ([#12](https://github.com/acme/api/pull/12)) by @alice\n\
+             \n\
+             ### Other Changes\n\
+             \n\
+             - Update README ([#7](https://github.com/acme/api/pull/7)) by @alice\n"
+        );
+
+        assert_eq!(
+            render_markdown(&since, &[], &config),
+            "## Changes since v1.4.0\n\nNo PRs merged since v1.4.0.\n"
+        );
+    }
+}
@@ -2,11 +2,16 @@
 //!
 //! Domain model for GitHub repositories.
 
-use gh_pr_config::DEFAULT_HOST;
+use gh_pr_config::{normalize_host, AppConfig, RepoId, WatchedPr, DEFAULT_HOST};
 use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
 
 /// A tracked GitHub repository
-#[derive(Debug, Clone, Default, Serialize, Deserialize, Eq, PartialEq, Hash)]
+///
+/// Two repositories are equal when they share the same [`RepoId`] (host, org
+/// and name compared case-insensitively), saved branch and profile. Switching
+/// the base branch shown keeps a tab's identity.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Repository {
     /// Organization or owner name
     pub org: String,
@@ -17,6 +22,13 @@ pub struct Repository {
     /// GitHub host (None = github.com)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub host: Option<String>,
+    /// Account profile (None = the host's default account)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Branch the repository was added with, while PRs targeting another
+    /// one are shown (None = `branch` is the configured one)
+    #[serde(skip)]
+    pub configured_branch: Option<String>,
 }
 
 impl Repository {
@@ -27,6 +39,8 @@ impl Repository {
             repo: repo.into(),
             branch: branch.into(),
             host: None,
+            profile: None,
+            configured_branch: None,
         }
     }
 
@@ -37,16 +51,57 @@ impl Repository {
         branch: impl Into<String>,
         host: Option<String>,
     ) -> Self {
-        // Normalize github.com to None
-        let host = host.filter(|h| h != DEFAULT_HOST && !h.is_empty());
         Self {
             org: org.into(),
             repo: repo.into(),
             branch: branch.into(),
-            host,
+            host: normalize_host(host.as_deref()),
+            profile: None,
+            configured_branch: None,
         }
     }
 
+    /// Use the given account profile for this repository
+    pub fn with_profile(mut self, profile: Option<String>) -> Self {
+        self.profile = profile.filter(|p| !p.is_empty());
+        self
+    }
+
+    /// Fall back to the profile configured for this repository (`[repos."org/*"]`)
+    pub fn with_configured_profile(self, config: &AppConfig) -> Self {
+        if self.profile.is_some() {
+            return self;
+        }
+        let profile = config.profile_for(&self.org, &self.repo).map(String::from);
+        self.with_profile(profile)
+    }
+
+    /// Show the PRs targeting `branch`, remembering the configured branch
+    pub fn with_base_branch(mut self, branch: impl Into<String>) -> Self {
+        let configured = self
+            .configured_branch
+            .take()
+            .unwrap_or_else(|| self.branch.clone());
+        self.branch = branch.into();
+        self.configured_branch = Some(configured).filter(|configured| *configured != self.branch);
+        self
+    }
+
+    /// Branch to save and restore, the one picked at runtime is not kept
+    pub fn saved_branch(&self) -> &str {
+        self.configured_branch.as_deref().unwrap_or(&self.branch)
+    }
+
+    /// Canonical identity of this repository, independent of branch and profile
+    pub fn id(&self) -> RepoId {
+        RepoId::new(self.host.as_deref(), &self.org, &self.repo)
+    }
+
+    /// Key of the account used for this repository (`host` or `profile@host`)
+    pub fn account_key(&self) -> String {
+        gh_client::account_key(self.host.as_deref(), self.profile.as_deref())
+    }
+
     /// Get the effective host (defaults to github.com)
     pub fn effective_host(&self) -> &str {
         self.host.as_deref().unwrap_or(DEFAULT_HOST)
@@ -57,6 +112,16 @@ impl Repository {
         self.host.is_none()
     }
 
+    /// Watch list key of a PR in this repository
+    pub fn watched_pr(&self, number: usize) -> WatchedPr {
+        WatchedPr::new(self.host.as_deref(), &self.org, &self.repo, number as u64)
+    }
+
+    /// Whether a watched PR belongs to this repository
+    pub fn contains_watched(&self, watched: &WatchedPr) -> bool {
+        self.id() == watched.repo_id()
+    }
+
     /// Get the web URL for this repository
     pub fn web_url(&self) -> String {
         format!(
@@ -107,6 +172,24 @@ impl Repository {
     }
 }
 
+impl PartialEq for Repository {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+            && self.saved_branch() == other.saved_branch()
+            && self.profile == other.profile
+    }
+}
+
+impl Eq for Repository {}
+
+impl Hash for Repository {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
+        self.saved_branch().hash(state);
+        self.profile.hash(state);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,6 +209,18 @@ mod tests {
         assert_eq!(repo.effective_host(), "ghe.example.com");
     }
 
+    #[test]
+    fn test_watched_pr() {
+        let repo = Repository::new("org", "repo", "main");
+        let watched = repo.watched_pr(7);
+        assert!(repo.contains_watched(&watched));
+
+        let other =
+            Repository::with_host("org", "repo", "main", Some("ghe.example.com".to_string()));
+        assert!(!other.contains_watched(&watched));
+        assert!(other.contains_watched(&other.watched_pr(7)));
+    }
+
     #[test]
     fn test_web_url() {
         let repo = Repository::new("rust-lang", "rust", "main");
@@ -166,11 +261,91 @@ mod tests {
         assert_eq!(repo.full_display_name(), "ghe.example.com:org/repo@main");
     }
 
+    #[test]
+    fn test_account_key() {
+        let repo = Repository::new("org", "repo", "main");
+        assert_eq!(repo.account_key(), "github.com");
+
+        let repo = repo.with_profile(Some("work".to_string()));
+        assert_eq!(repo.account_key(), "work@github.com");
+    }
+
+    #[test]
+    fn test_configured_profile() {
+        let mut config = AppConfig::default();
+        config.repos.insert(
+            "work-org/*".to_string(),
+            gh_pr_config::RepoSettings {
+                profile: Some("work".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let repo = Repository::new("work-org", "service", "main").with_configured_profile(&config);
+        assert_eq!(repo.profile.as_deref(), Some("work"));
+
+        // An explicit profile wins over the config
+        let repo = Repository::new("work-org", "service", "main")
+            .with_profile(Some("personal".to_string()))
+            .with_configured_profile(&config);
+        assert_eq!(repo.profile.as_deref(), Some("personal"));
+
+        let repo = Repository::new("me", "dotfiles", "main").with_configured_profile(&config);
+        assert!(repo.profile.is_none());
+    }
+
     #[test]
     fn test_host_normalization() {
         // github.com should be normalized to None
         let repo = Repository::with_host("org", "repo", "main", Some("github.com".to_string()));
         assert!(repo.is_github_com());
         assert!(repo.host.is_none());
+
+        let repo =
+            Repository::with_host("org", "repo", "main", Some("GHE.Example.com".to_string()));
+        assert_eq!(repo.host.as_deref(), Some("ghe.example.com"));
+    }
+
+    #[test]
+    fn test_equality_ignores_case() {
+        let repo = Repository::new("Rust-Lang", "Rust", "main");
+        assert_eq!(repo, Repository::new("rust-lang", "rust", "main"));
+        assert_eq!(repo.id().to_string(), "github.com/Rust-Lang/Rust");
+        assert!(repo.contains_watched(&Repository::new("rust-lang", "rust", "dev").watched_pr(1)));
+
+        assert_ne!(repo, Repository::new("rust-lang", "rust", "dev"));
+        assert_ne!(
+            repo,
+            Repository::with_host(
+                "rust-lang",
+                "rust",
+                "main",
+                Some("ghe.example.com".to_string())
+            )
+        );
+
+        let set: std::collections::HashSet<_> =
+            [repo.clone(), Repository::new("RUST-LANG", "rust", "main")].into();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_with_base_branch_remembers_configured_branch() {
+        let repo = Repository::new("acme", "api", "main").with_base_branch("release/2.x");
+        assert_eq!(repo.branch, "release/2.x");
+        assert_eq!(repo.saved_branch(), "main");
+        // Still the tab of main, not one configured for release/2.x
+        let configured = Repository::new("acme", "api", "main");
+        assert_eq!(repo, configured);
+        assert!(std::collections::HashSet::from([configured]).contains(&repo));
+        assert_ne!(repo, Repository::new("acme", "api", "release/2.x"));
+
+        let repo = repo.with_base_branch("release/3.x");
+        assert_eq!(repo.saved_branch(), "main");
+
+        // Back on the configured branch, nothing is remembered
+        let repo = repo.with_base_branch("main");
+        assert_eq!(repo.configured_branch, None);
+        assert_eq!(repo.saved_branch(), "main");
     }
 }
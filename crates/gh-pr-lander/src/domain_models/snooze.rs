@@ -0,0 +1,61 @@
+//! Snooze durations
+//!
+//! How long a PR stays hidden from the PR table. Every snooze also ends as
+//! soon as the PR is updated.
+
+use chrono::{DateTime, Duration, Utc};
+
+/// How long to snooze a pull request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SnoozeDuration {
+    /// Until the PR gets a new commit or update
+    #[default]
+    UntilUpdated,
+    /// One day
+    Day,
+    /// Three days
+    ThreeDays,
+    /// One week
+    Week,
+}
+
+impl SnoozeDuration {
+    /// Deadline of a snooze starting at `now` (None = until updated)
+    pub fn deadline(self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::UntilUpdated => None,
+            Self::Day => Some(now + Duration::days(1)),
+            Self::ThreeDays => Some(now + Duration::days(3)),
+            Self::Week => Some(now + Duration::weeks(1)),
+        }
+    }
+
+    /// Human readable duration ("for 3 days")
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::UntilUpdated => "until updated",
+            Self::Day => "for 1 day",
+            Self::ThreeDays => "for 3 days",
+            Self::Week => "for 1 week",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadline() {
+        let now = Utc::now();
+        assert_eq!(SnoozeDuration::UntilUpdated.deadline(now), None);
+        assert_eq!(
+            SnoozeDuration::ThreeDays.deadline(now),
+            Some(now + Duration::days(3))
+        );
+        assert_eq!(
+            SnoozeDuration::Week.deadline(now),
+            Some(now + Duration::days(7))
+        );
+    }
+}
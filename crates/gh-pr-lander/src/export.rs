@@ -0,0 +1,137 @@
+//! Exports of on-screen data to files
+//!
+//! Exports are written to the current directory and named after the
+//! `[ui] export_filename` template, e.g. `rust-42-2025-01-15_12_00.log`.
+//! Release notes are named after the repository and their start instead,
+//! e.g. `rust-release-notes-since-1.84.0.md`.
+
+use crate::domain_models::{Repository, SinceRef};
+use crate::state::{BuildLogContext, BuildLogState};
+use crate::utils::format::format_date;
+use chrono::{DateTime, Utc};
+use gh_actions_log_parser::WorkflowNode;
+use gh_pr_config::{FilenameValues, UiConfig};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Path of a build log export of `repo`
+///
+/// `{pr}` is the PR number, or the run id for logs opened from the Actions view.
+pub fn build_log_export_path(
+    config: &UiConfig,
+    repo: &Repository,
+    build_log: &BuildLogState,
+    now: DateTime<Utc>,
+) -> PathBuf {
+    let pr = match &build_log.context {
+        Some(BuildLogContext::Pr(pr)) => pr.number.to_string(),
+        Some(BuildLogContext::Run(run)) => run.run_id.to_string(),
+        None => String::new(),
+    };
+    let date = format_date(now, config);
+    PathBuf::from(config.export_filename.render(&FilenameValues {
+        host: repo.effective_host(),
+        org: &repo.org,
+        repo: &repo.repo,
+        pr: &pr,
+        date: &date,
+    }))
+}
+
+/// Path of the release notes of `repo` since `since`
+///
+/// Characters other than letters, digits, `.`, `-` and `_` become `_`, so
+/// a tag like `release/1.0` stays one file name.
+pub fn release_notes_export_path(repo: &Repository, since: &SinceRef) -> PathBuf {
+    let name = format!("{}-release-notes-since-{}.md", repo.repo, since);
+    PathBuf::from(
+        name.chars()
+            .map(|c| {
+                if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>(),
+    )
+}
+
+/// Plain text of all loaded build logs, one section per job and step
+pub fn build_log_text(workflows: &[Arc<WorkflowNode>]) -> String {
+    let mut text = String::new();
+    for workflow in workflows {
+        for job in &workflow.jobs {
+            let _ = writeln!(text, "=== {} / {} ===", workflow.name, job.name);
+            for step in &job.steps {
+                let _ = writeln!(text, "--- {} ---", step.name);
+                for line in step.lines.iter().filter(|line| !line.is_metadata) {
+                    match &line.timestamp {
+                        Some(timestamp) => {
+                            let _ = writeln!(text, "{} {}", timestamp, line.display_content);
+                        }
+                        None => {
+                            let _ = writeln!(text, "{}", line.display_content);
+                        }
+                    }
+                }
+            }
+            text.push('\n');
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::BuildLogPrContext;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_build_log_export_path() {
+        let repo = Repository::new("rust-lang", "rust", "main");
+        let build_log = BuildLogState {
+            context: Some(BuildLogContext::Pr(BuildLogPrContext {
+                number: 42,
+                title: "Fix".to_string(),
+                author: "octocat".to_string(),
+            })),
+            ..BuildLogState::default()
+        };
+        let config = UiConfig {
+            timezone: Some("UTC".to_string()),
+            ..UiConfig::default()
+        };
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+
+        assert_eq!(
+            build_log_export_path(&config, &repo, &build_log, now),
+            PathBuf::from("rust-42-2025-01-15_12_00.log")
+        );
+
+        let config = UiConfig {
+            date_format: "%Y%m%d".to_string(),
+            export_filename: "{org}_{repo}_{date}.txt".parse().unwrap(),
+            ..config
+        };
+        assert_eq!(
+            build_log_export_path(&config, &repo, &build_log, now),
+            PathBuf::from("rust-lang_rust_20250115.txt")
+        );
+    }
+
+    #[test]
+    fn test_release_notes_export_path() {
+        let repo = Repository::new("rust-lang", "rust", "main");
+        assert_eq!(
+            release_notes_export_path(&repo, &SinceRef::Tag("release/1.84.0".to_string())),
+            PathBuf::from("rust-release-notes-since-release_1.84.0.md")
+        );
+        assert_eq!(
+            release_notes_export_path(&repo, &SinceRef::parse("2025-01-15").unwrap()),
+            PathBuf::from("rust-release-notes-since-2025-01-15.md")
+        );
+    }
+}
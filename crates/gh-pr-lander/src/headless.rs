@@ -0,0 +1,626 @@
+//! Headless watch mode
+//!
+//! `gh-pr-lander watch` runs the usual middleware chain and reducers without a
+//! terminal UI. The repositories of the session are polled periodically and
+//! changes are printed to stdout, one line per event (or one JSON object per
+//! line with `--json`). A named policy can be run on matching PRs.
+
+use crate::actions::{
+    Action, BootstrapAction, GlobalAction, PullRequestAction, RepositoryAction, StatusBarAction,
+};
+use crate::background::{spawn_background_worker, Heartbeat, SharedState};
+use crate::domain_models::{policy, MergeableStatus, PolicyStep, PrState, Repository};
+use crate::middleware::MiddlewareChain;
+use crate::state::{AppState, MainViewState, StatusKind};
+use crate::store::Store;
+use crate::utils::format::format_count;
+use chrono::Local;
+use gh_client::RequestBudget;
+use gh_pr_config::PolicyConfig;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// Quiet time after which a `--once` run counts as settled
+const SETTLE_DELAY: Duration = Duration::from_secs(3);
+
+/// Longest a `--once` run waits for its poll to settle
+const ONCE_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Options of the `watch` run mode
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchOptions {
+    /// Print one JSON object per line instead of text
+    pub json: bool,
+    /// Poll once, wait for the results and exit
+    pub once: bool,
+    /// Seconds between two polls
+    pub interval_secs: u64,
+    /// Policy to run on matching PRs (None = only report matches)
+    pub policy: Option<String>,
+    /// Upper limit of API requests per minute and host
+    pub requests_per_minute: u32,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            json: false,
+            once: false,
+            interval_secs: 300,
+            policy: None,
+            requests_per_minute: 30,
+        }
+    }
+}
+
+/// Kind of a watch event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchEventKind {
+    /// A repository was loaded for the first time
+    Tracking,
+    /// A PR was opened
+    Opened,
+    /// CI of a PR settled green
+    CiPassed,
+    /// CI of a PR settled red
+    CiFailed,
+    /// A PR is no longer open (merged or closed)
+    Closed,
+    /// A policy matches a PR
+    PolicyMatched,
+    /// Status message of an operation (e.g. a policy run summary)
+    Status,
+    /// Something went wrong
+    Error,
+}
+
+impl WatchEventKind {
+    /// Name used in text and JSON output
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Tracking => "tracking",
+            Self::Opened => "opened",
+            Self::CiPassed => "ci_passed",
+            Self::CiFailed => "ci_failed",
+            Self::Closed => "closed",
+            Self::PolicyMatched => "policy_matched",
+            Self::Status => "status",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// Something worth a line in the watch output
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WatchEvent {
+    pub event: WatchEventKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pr: Option<usize>,
+    pub message: String,
+}
+
+impl WatchEvent {
+    fn new(event: WatchEventKind, message: impl Into<String>) -> Self {
+        Self {
+            event,
+            repo: None,
+            pr: None,
+            message: message.into(),
+        }
+    }
+
+    fn for_pr(event: WatchEventKind, repo: &str, pr: usize, message: impl Into<String>) -> Self {
+        Self {
+            event,
+            repo: Some(repo.to_string()),
+            pr: Some(pr),
+            message: message.into(),
+        }
+    }
+
+    /// Format as one output line, stamped with `time`
+    pub fn format(&self, json: bool, time: &str) -> String {
+        if json {
+            let mut value = serde_json::to_value(self).unwrap_or_default();
+            if let Some(object) = value.as_object_mut() {
+                object.insert("time".to_string(), time.into());
+            }
+            return value.to_string();
+        }
+        let subject = match (&self.repo, self.pr) {
+            (Some(repo), Some(pr)) => format!("{}#{}", repo, pr),
+            (Some(repo), None) => repo.clone(),
+            _ => "-".to_string(),
+        };
+        format!(
+            "{}  {:<14}  {}  {}",
+            time,
+            self.event.as_str(),
+            subject,
+            self.message
+        )
+    }
+}
+
+/// A policy step that matched for the first time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyMatch {
+    pub repo: Repository,
+    pub policy: String,
+    pub step: PolicyStep,
+}
+
+/// Turns successive states into events by remembering what was seen
+#[derive(Debug, Default)]
+pub struct WatchReporter {
+    /// CI status of the open PRs of every loaded repository
+    known: HashMap<Repository, HashMap<usize, MergeableStatus>>,
+    /// Policy matches already reported
+    matched: HashSet<(Repository, usize)>,
+}
+
+impl WatchReporter {
+    /// Compare `main_view` with the previous observation
+    ///
+    /// The first load of a repository only reports how many PRs are tracked.
+    pub fn observe(
+        &mut self,
+        main_view: &MainViewState,
+        policies: &BTreeMap<String, PolicyConfig>,
+    ) -> (Vec<WatchEvent>, Vec<PolicyMatch>) {
+        let mut events = Vec::new();
+        let mut matches = Vec::new();
+
+        for (idx, repo) in main_view.repositories.iter().enumerate() {
+            let Some(data) = main_view.repo_data.get(&idx) else {
+                continue;
+            };
+            if data.loading_state != crate::domain_models::LoadingState::Loaded {
+                continue;
+            }
+            let name = repo.display_name();
+            let open: Vec<_> = data
+                .prs
+                .iter()
+                .filter(|pr| pr.state == PrState::Open)
+                .collect();
+            let current: HashMap<usize, MergeableStatus> =
+                open.iter().map(|pr| (pr.number, pr.mergeable)).collect();
+
+            match self.known.get(repo) {
+                None => events.push(WatchEvent {
+                    repo: Some(name.clone()),
+                    ..WatchEvent::new(
+                        WatchEventKind::Tracking,
+                        format_count(open.len(), "open PR", "open PRs"),
+                    )
+                }),
+                Some(previous) => {
+                    for pr in &open {
+                        match previous.get(&pr.number) {
+                            None => events.push(WatchEvent::for_pr(
+                                WatchEventKind::Opened,
+                                &name,
+                                pr.number,
+                                format!("{} (by {})", pr.title, pr.author),
+                            )),
+                            Some(
+                                MergeableStatus::Unknown
+                                | MergeableStatus::Checking
+                                | MergeableStatus::Queued
+                                | MergeableStatus::WaitingApproval,
+                            ) => {
+                                let kind = match pr.mergeable {
+                                    MergeableStatus::Ready | MergeableStatus::OptionalFailed => {
+                                        WatchEventKind::CiPassed
+                                    }
+                                    MergeableStatus::BuildFailed => WatchEventKind::CiFailed,
+                                    _ => continue,
+                                };
+                                events.push(WatchEvent::for_pr(
+                                    kind,
+                                    &name,
+                                    pr.number,
+                                    pr.title.clone(),
+                                ));
+                            }
+                            Some(_) => {}
+                        }
+                    }
+                    let mut gone: Vec<usize> = previous
+                        .keys()
+                        .filter(|number| !current.contains_key(number))
+                        .copied()
+                        .collect();
+                    gone.sort_unstable();
+                    events.extend(gone.into_iter().map(|number| {
+                        WatchEvent::for_pr(
+                            WatchEventKind::Closed,
+                            &name,
+                            number,
+                            "no longer open (merged or closed)",
+                        )
+                    }));
+                }
+            }
+
+            self.matched
+                .retain(|(r, number)| r != repo || current.contains_key(number));
+            let (steps, _) = policy::plan(
+                policies,
+                &main_view.saved_filters,
+                &main_view.pr_sizes,
+                &open,
+            );
+            for (policy_name, step) in steps {
+                let number = step.pr_number as usize;
+                if self.matched.insert((repo.clone(), number)) {
+                    events.push(WatchEvent::for_pr(
+                        WatchEventKind::PolicyMatched,
+                        &name,
+                        number,
+                        format!("{}: {}", policy_name, policy::action_label(step.action)),
+                    ));
+                    matches.push(PolicyMatch {
+                        repo: repo.clone(),
+                        policy: policy_name,
+                        step,
+                    });
+                }
+            }
+
+            self.known.insert(repo.clone(), current);
+        }
+
+        (events, matches)
+    }
+}
+
+/// Whether nothing is loading, checking CI or running anymore
+///
+/// PRs waiting for a deployment approval count as settled: nothing changes
+/// until someone acts on them.
+fn settled(state: &AppState) -> bool {
+    let main_view = &state.main_view;
+    state.status_bar.operations.is_empty()
+        && main_view.repo_data.values().all(|data| {
+            data.loading_state != crate::domain_models::LoadingState::Loading
+                && data.prs.iter().all(|pr| {
+                    !matches!(
+                        pr.mergeable,
+                        MergeableStatus::Checking | MergeableStatus::Queued
+                    )
+                })
+        })
+}
+
+/// Send Quit to the background worker on Ctrl+C (SIGINT)
+fn spawn_interrupt_handler(action_tx: Sender<Action>) {
+    std::thread::spawn(move || {
+        let runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                log::error!("Failed to create the interrupt handler runtime: {}", e);
+                return;
+            }
+        };
+        if runtime.block_on(tokio::signal::ctrl_c()).is_ok() {
+            log::info!("Interrupted, shutting down");
+            action_tx.send(Action::Global(GlobalAction::Quit)).ok();
+        }
+    });
+}
+
+/// Prints events to stdout
+struct WatchOutput {
+    json: bool,
+}
+
+impl WatchOutput {
+    fn print(&self, event: &WatchEvent) {
+        let time = if self.json {
+            Local::now().to_rfc3339()
+        } else {
+            Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
+        };
+        let mut stdout = std::io::stdout().lock();
+        // A closed pipe (e.g. `| head`) must not panic the watcher
+        if writeln!(stdout, "{}", event.format(self.json, &time)).is_err() {
+            log::warn!("Failed to write watch event to stdout");
+        }
+    }
+}
+
+/// Run the watch mode until interrupted (or after one poll with `--once`)
+///
+/// Returns `false` if the watcher could not start (e.g. no token or no
+/// repositories).
+pub fn run(options: &WatchOptions, middleware: MiddlewareChain) -> bool {
+    let (action_tx, action_rx) = mpsc::channel::<Action>();
+    let (result_tx, result_rx) = mpsc::channel::<Action>();
+
+    let initial_state = AppState::default();
+    let shared_state: SharedState = Arc::new(RwLock::new(initial_state.clone()));
+    let mut store = Store::new(initial_state);
+
+    let bg_handle = spawn_background_worker(
+        action_rx,
+        action_tx.clone(),
+        result_tx,
+        shared_state.clone(),
+        middleware,
+        Heartbeat::default(),
+    );
+    spawn_interrupt_handler(action_tx.clone());
+    action_tx
+        .send(Action::Bootstrap(BootstrapAction::Start))
+        .ok();
+
+    let output = WatchOutput { json: options.json };
+    let interval = Duration::from_secs(options.interval_secs.max(1));
+    let mut reporter = WatchReporter::default();
+    let mut bootstrapped = false;
+    let mut changed = false;
+    let mut last_poll: Option<Instant> = None;
+    let mut last_activity = Instant::now();
+    let mut executed: HashSet<(Repository, u64)> = HashSet::new();
+    let mut success = true;
+
+    loop {
+        match result_rx.recv_timeout(Duration::from_millis(200)) {
+            Ok(Action::Global(GlobalAction::Quit)) => break,
+            Ok(Action::Event(event)) => {
+                // Events re-enter the middleware chain, as in the TUI main loop
+                action_tx.send(Action::Event(event)).ok();
+                continue;
+            }
+            Ok(action) => {
+                last_activity = Instant::now();
+                match &action {
+                    Action::Bootstrap(BootstrapAction::ConfigLoaded(config)) => {
+                        // Never exceed the watch limit, even if the config allows more
+                        let limit = config
+                            .requests_per_minute
+                            .filter(|&rpm| rpm > 0)
+                            .map_or(options.requests_per_minute, |rpm| {
+                                rpm.min(options.requests_per_minute)
+                            });
+                        RequestBudget::global().set_requests_per_minute(Some(limit));
+                    }
+                    Action::Bootstrap(BootstrapAction::StageFailed(_, error)) => {
+                        output.print(&WatchEvent::new(WatchEventKind::Error, error.clone()));
+                        success = false;
+                        break;
+                    }
+                    Action::Bootstrap(BootstrapAction::End) => bootstrapped = true,
+                    Action::StatusBar(StatusBarAction::Push { kind, message, .. })
+                        if *kind != StatusKind::Running =>
+                    {
+                        let event = if *kind == StatusKind::Error {
+                            WatchEventKind::Error
+                        } else {
+                            WatchEventKind::Status
+                        };
+                        output.print(&WatchEvent::new(event, message.clone()));
+                    }
+                    _ => {}
+                }
+                store.dispatch(action);
+                if let Ok(mut shared) = shared_state.write() {
+                    *shared = store.state().clone();
+                }
+                changed = true;
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        if !bootstrapped {
+            continue;
+        }
+        let state = store.state();
+        if state.main_view.repositories.is_empty() {
+            output.print(&WatchEvent::new(
+                WatchEventKind::Error,
+                "No repositories in the session, add them in the TUI first",
+            ));
+            success = false;
+            break;
+        }
+
+        if changed {
+            changed = false;
+            let (events, matches) = reporter.observe(&state.main_view, &state.app_config.policies);
+            events.iter().for_each(|event| output.print(event));
+
+            let mut runs: HashMap<Repository, Vec<PolicyStep>> = HashMap::new();
+            for matched in matches {
+                if options.policy.as_deref() == Some(matched.policy.as_str())
+                    && executed.insert((matched.repo.clone(), matched.step.pr_number))
+                {
+                    runs.entry(matched.repo).or_default().push(matched.step);
+                }
+            }
+            for (repo, steps) in runs {
+                last_activity = Instant::now();
+                action_tx
+                    .send(Action::PullRequest(PullRequestAction::RunPolicy {
+                        repo,
+                        steps,
+                    }))
+                    .ok();
+            }
+        }
+
+        if let (true, Some(polled_at)) = (options.once, last_poll) {
+            if settled(state) && last_activity.elapsed() >= SETTLE_DELAY {
+                break;
+            }
+            if polled_at.elapsed() >= ONCE_TIMEOUT {
+                output.print(&WatchEvent::new(
+                    WatchEventKind::Error,
+                    "Gave up waiting for the poll to finish",
+                ));
+                success = false;
+                break;
+            }
+        } else if last_poll.is_none_or(|polled_at| polled_at.elapsed() >= interval) {
+            log::info!(
+                "Watch: polling {}",
+                format_count(
+                    state.main_view.repositories.len(),
+                    "repository",
+                    "repositories"
+                )
+            );
+            for repo in &state.main_view.repositories {
+                action_tx
+                    .send(Action::Repository(RepositoryAction::RefreshRepositoryData(
+                        repo.clone(),
+                    )))
+                    .ok();
+            }
+            last_poll = Some(Instant::now());
+            last_activity = Instant::now();
+        }
+    }
+
+    // Graceful shutdown: the cache and session are written as they change,
+    // so waiting for the background worker is enough
+    action_tx.send(Action::Global(GlobalAction::Quit)).ok();
+    drop(action_tx);
+    if let Err(e) = bg_handle.join() {
+        log::error!("Background thread panicked: {:?}", e);
+    }
+    success
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::{LoadingState, Pr};
+    use crate::state::RepositoryData;
+
+    fn main_view(prs: Vec<Pr>) -> MainViewState {
+        let mut main_view = MainViewState {
+            repositories: vec![Repository::new("org", "repo", "main")],
+            ..Default::default()
+        };
+        main_view.repo_data.insert(
+            0,
+            RepositoryData {
+                prs,
+                loading_state: LoadingState::Loaded,
+                ..Default::default()
+            },
+        );
+        main_view
+    }
+
+    fn pr(number: usize, mergeable: MergeableStatus) -> Pr {
+        let mut pr = Pr::new(number, format!("PR {}", number), "alice", "sha");
+        pr.mergeable = mergeable;
+        pr
+    }
+
+    fn kinds(events: &[WatchEvent]) -> Vec<(WatchEventKind, Option<usize>)> {
+        events.iter().map(|e| (e.event, e.pr)).collect()
+    }
+
+    #[test]
+    fn test_reporter_diffs_successive_states() {
+        let policies = BTreeMap::new();
+        let mut reporter = WatchReporter::default();
+
+        let (events, _) = reporter.observe(
+            &main_view(vec![
+                pr(1, MergeableStatus::Checking),
+                pr(2, MergeableStatus::Ready),
+            ]),
+            &policies,
+        );
+        assert_eq!(kinds(&events), vec![(WatchEventKind::Tracking, None)]);
+        assert_eq!(events[0].message, "2 open PRs");
+
+        let (events, _) = reporter.observe(
+            &main_view(vec![
+                pr(1, MergeableStatus::BuildFailed),
+                pr(3, MergeableStatus::Unknown),
+            ]),
+            &policies,
+        );
+        assert_eq!(
+            kinds(&events),
+            vec![
+                (WatchEventKind::CiFailed, Some(1)),
+                (WatchEventKind::Opened, Some(3)),
+                (WatchEventKind::Closed, Some(2)),
+            ]
+        );
+
+        let (events, _) = reporter.observe(
+            &main_view(vec![
+                pr(1, MergeableStatus::BuildFailed),
+                pr(3, MergeableStatus::Ready),
+            ]),
+            &policies,
+        );
+        assert_eq!(kinds(&events), vec![(WatchEventKind::CiPassed, Some(3))]);
+    }
+
+    #[test]
+    fn test_reporter_reports_policy_matches_once() {
+        let mut policies = BTreeMap::new();
+        policies.insert(
+            "approve-alice".to_string(),
+            PolicyConfig {
+                authors: vec!["alice".to_string()],
+                ..Default::default()
+            },
+        );
+        let mut reporter = WatchReporter::default();
+        let view = main_view(vec![
+            pr(1, MergeableStatus::Ready),
+            pr(2, MergeableStatus::Checking),
+        ]);
+
+        let (events, matches) = reporter.observe(&view, &policies);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].policy, "approve-alice");
+        assert_eq!(matches[0].step.pr_number, 1);
+        assert!(events
+            .iter()
+            .any(|e| e.event == WatchEventKind::PolicyMatched && e.pr == Some(1)));
+
+        let (_, matches) = reporter.observe(&view, &policies);
+        assert!(matches.is_empty());
+    }
+
+    #[test]
+    fn test_event_format() {
+        let event = WatchEvent::for_pr(WatchEventKind::Opened, "org/repo", 7, "Fix it");
+        assert_eq!(
+            event.format(false, "2026-01-01 10:00:00"),
+            "2026-01-01 10:00:00  opened          org/repo#7  Fix it"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&event.format(true, "2026-01-01T10:00:00+00:00")).unwrap();
+        assert_eq!(json["event"], "opened");
+        assert_eq!(json["pr"], 7);
+        assert_eq!(json["time"], "2026-01-01T10:00:00+00:00");
+
+        let status = WatchEvent::new(WatchEventKind::Status, "done");
+        let json: serde_json::Value = serde_json::from_str(&status.format(true, "t")).unwrap();
+        assert!(json.get("repo").is_none());
+    }
+}
@@ -0,0 +1,213 @@
+//! Input coalescing
+//!
+//! Over high-latency terminals (mosh, ssh) key events pile up while a frame
+//! renders. Replaying them one by one makes a held `j` overshoot, because the
+//! cursor keeps moving after the key was released. Instead:
+//! - the main loop collapses a burst of the same key into one
+//!   `GlobalAction::KeyRepeated`
+//! - the keyboard middleware turns a repeated navigation key into a single
+//!   `GlobalAction::Repeat` of the navigation action
+//! - the background worker merges navigation actions still waiting in its
+//!   queue into the preceding one
+//!
+//! Only identical, adjacent keys or navigation actions are merged, so the
+//! order relative to any other input is kept.
+
+use crate::actions::{Action, GlobalAction, NavigationAction};
+use ratatui::crossterm::event::KeyEvent;
+use std::collections::VecDeque;
+
+/// Collapse runs of identical key presses into `KeyRepeated` actions
+pub fn coalesce_keys(keys: impl IntoIterator<Item = KeyEvent>) -> Vec<Action> {
+    let mut runs: Vec<(KeyEvent, usize)> = Vec::new();
+    for key in keys {
+        match runs.last_mut() {
+            Some((last, count)) if *last == key => *count += 1,
+            _ => runs.push((key, 1)),
+        }
+    }
+
+    runs.into_iter()
+        .map(|(key, count)| match count {
+            1 => Action::Global(GlobalAction::KeyPressed(key)),
+            count => Action::Global(GlobalAction::KeyRepeated { key, count }),
+        })
+        .collect()
+}
+
+/// `action` applied `count` times, as a single action
+pub fn repeat(action: Action, count: usize) -> Action {
+    match count {
+        1 => action,
+        count => Action::Global(GlobalAction::Repeat {
+            action: Box::new(action),
+            count,
+        }),
+    }
+}
+
+/// The navigation and its count, for plain and repeated navigation actions
+fn navigation(action: &Action) -> Option<(NavigationAction, usize)> {
+    match action {
+        Action::Navigate(nav) => Some((*nav, 1)),
+        Action::Global(GlobalAction::Repeat { action, count }) => match **action {
+            Action::Navigate(nav) => Some((nav, *count)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Take the next action off the queue, merged with identical navigation
+/// actions directly behind it
+pub fn next_coalesced(queue: &mut VecDeque<Action>) -> Option<Action> {
+    let action = queue.pop_front()?;
+    let Some((nav, mut count)) = navigation(&action) else {
+        return Some(action);
+    };
+
+    while let Some((next, next_count)) = queue.front().and_then(navigation) {
+        if next != nav {
+            break;
+        }
+        count += next_count;
+        queue.pop_front();
+    }
+
+    if count > 1 {
+        log::debug!("Coalesced {} x {:?}", count, nav);
+    }
+    Some(repeat(Action::Navigate(nav), count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::PullRequestAction;
+    use crate::demo::fixtures;
+    use crate::dispatcher::Dispatcher;
+    use crate::middleware::keyboard_middleware::KeyboardMiddleware;
+    use crate::middleware::navigation_middleware::NavigationMiddleware;
+    use crate::middleware::Middleware;
+    use crate::state::AppState;
+    use crate::store::Store;
+    use crate::views::PullRequestView;
+    use ratatui::crossterm::event::{KeyCode, KeyModifiers};
+    use std::sync::mpsc;
+
+    fn key(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    /// Store with one repository of 60 PRs on screen
+    fn pr_store() -> Store {
+        let mut store = Store::new(AppState::default());
+        store.dispatch(Action::Global(GlobalAction::ReplaceView(Box::new(
+            PullRequestView::new(),
+        ))));
+        let repo = fixtures::repositories(1, 1).remove(0);
+        let prs = fixtures::pull_requests(&repo, 60, 1);
+        store.dispatch(Action::Repository(
+            crate::actions::RepositoryAction::AddRepository(repo.clone()),
+        ));
+        store.dispatch(Action::PullRequest(PullRequestAction::Loaded {
+            repo,
+            prs,
+            from_cache: None,
+        }));
+        store
+    }
+
+    /// Run `action` through `middleware`, returning what it dispatched
+    fn run(
+        middleware: &mut dyn Middleware<Action, AppState>,
+        action: &Action,
+        state: &AppState,
+    ) -> Vec<Action> {
+        let (tx, rx) = mpsc::channel();
+        middleware.handle(action, state, &Dispatcher::new(tx));
+        rx.try_iter().collect()
+    }
+
+    #[test]
+    fn test_coalesce_keys_keeps_order() {
+        let actions = coalesce_keys([key('j'), key('j'), key('x'), key('j')]);
+        assert_eq!(actions.len(), 3);
+        assert!(matches!(
+            actions[0],
+            Action::Global(GlobalAction::KeyRepeated { count: 2, .. })
+        ));
+        assert!(matches!(actions[1], Action::Global(GlobalAction::KeyPressed(k)) if k == key('x')));
+        assert!(matches!(actions[2], Action::Global(GlobalAction::KeyPressed(k)) if k == key('j')));
+    }
+
+    #[test]
+    fn test_next_coalesced_merges_adjacent_navigation() {
+        let mut queue = VecDeque::from([
+            Action::Navigate(NavigationAction::Next),
+            repeat(Action::Navigate(NavigationAction::Next), 3),
+            Action::Navigate(NavigationAction::Previous),
+            Action::Navigate(NavigationAction::Next),
+        ]);
+
+        let first = next_coalesced(&mut queue).unwrap();
+        assert_eq!(navigation(&first), Some((NavigationAction::Next, 4)));
+        let second = next_coalesced(&mut queue).unwrap();
+        assert!(matches!(
+            second,
+            Action::Navigate(NavigationAction::Previous)
+        ));
+        let third = next_coalesced(&mut queue).unwrap();
+        assert!(matches!(third, Action::Navigate(NavigationAction::Next)));
+        assert!(next_coalesced(&mut queue).is_none());
+    }
+
+    #[test]
+    fn test_next_coalesced_does_not_merge_other_actions() {
+        let mut queue = VecDeque::from([
+            Action::Global(GlobalAction::Tick),
+            Action::Global(GlobalAction::Tick),
+        ]);
+        assert!(matches!(
+            next_coalesced(&mut queue),
+            Some(Action::Global(GlobalAction::Tick))
+        ));
+        assert_eq!(queue.len(), 1);
+    }
+
+    #[test]
+    fn test_burst_of_50_j_presses() {
+        let mut store = pr_store();
+        let keys = coalesce_keys(std::iter::repeat_n(key('j'), 50));
+        assert_eq!(keys.len(), 1);
+
+        let mut keyboard = KeyboardMiddleware::new();
+        let mut navigation = NavigationMiddleware::new();
+        let mut dispatches = 0;
+        for key_action in &keys {
+            for nav in run(&mut keyboard, key_action, store.state()) {
+                for translated in run(&mut navigation, &nav, store.state()) {
+                    store.dispatch(translated);
+                    dispatches += 1;
+                }
+            }
+        }
+
+        assert!(dispatches <= 2, "{} dispatches", dispatches);
+        let repo_data = &store.state().main_view.repo_data[&0];
+        assert_eq!(repo_data.selected_pr, 50);
+    }
+
+    #[test]
+    fn test_repeated_non_navigation_key_is_replayed() {
+        let store = pr_store();
+        let mut keyboard = KeyboardMiddleware::new();
+        // Space toggles the selection; three presses are three toggles
+        let action = Action::Global(GlobalAction::KeyRepeated {
+            key: key(' '),
+            count: 3,
+        });
+        let dispatched = run(&mut keyboard, &action, store.state());
+        assert_eq!(dispatched.len(), 3);
+    }
+}
@@ -5,7 +5,7 @@
 
 use std::fs::File;
 use std::io::{BufRead, BufReader, Seek, SeekFrom};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Reads log file with tailing support
 ///
@@ -69,6 +69,17 @@ impl FileLogReader {
         Ok(new_lines)
     }
 
+    /// Path of the file being read
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Switch to another file (e.g. after log rotation), reading it from the start
+    pub fn follow(&mut self, path: PathBuf) {
+        self.path = path;
+        self.last_position = 0;
+    }
+
     /// Reset position to re-read from start
     pub fn clear(&mut self) {
         self.last_position = 0;
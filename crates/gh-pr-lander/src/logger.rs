@@ -1,59 +1,619 @@
-//! File-based logging using simplelog
+//! File-based logging
 //!
 //! Log file location depends on build type:
 //! - Debug builds: current working directory (for development convenience)
 //! - Release builds: cache directory (~/.cache/gh-pr-lander/ on Linux)
 //!
-//! The debug console reads from this file when opened.
+//! Levels are filtered per module, from the `[logging]` config section or
+//! `RUST_LOG` ("info,gh_client=debug"), and can be changed at runtime with
+//! `set_filter`. The file is rotated by size or day; the debug console
+//! follows the active file through the shared `LogFile` handle.
 
-use simplelog::{ConfigBuilder, LevelFilter, WriteLogger};
-use std::fs::File;
-use std::path::PathBuf;
+use chrono::{DateTime, Local, NaiveDate, SecondsFormat};
+use gh_pr_config::{LogFormat, LogRotation, LoggingConfig};
+use log::{LevelFilter, Log, Metadata, Record};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
 
-/// Get the log file path based on build type
-fn log_file_path() -> PathBuf {
-    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
-    let filename = format!("debug-{}.log", timestamp);
+/// Prefix of log file names (`debug-<timestamp>.log`)
+const FILE_PREFIX: &str = "debug-";
 
+/// Extension of log file names
+const FILE_SUFFIX: &str = ".log";
+
+/// Dependencies logging every request, capped at warn unless configured
+const NOISY_MODULES: [&str; 6] = ["octocrab", "hyper", "hyper_util", "reqwest", "rustls", "h2"];
+
+/// The installed logger, reconfigured through the functions of this module
+static LOGGER: OnceLock<FileLogger> = OnceLock::new();
+
+/// Log levels per module path prefix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFilter {
+    /// Level of modules without an entry
+    default: LevelFilter,
+    /// Module path prefixes and their level, in the order they were given
+    modules: Vec<(String, LevelFilter)>,
+}
+
+impl LogFilter {
+    /// Filter logging everything at `default` level
+    pub fn new(default: LevelFilter) -> Self {
+        Self {
+            default,
+            modules: Vec::new(),
+        }
+    }
+
+    /// Parse an env_logger style spec ("info,gh_client=debug")
+    ///
+    /// A bare level sets the default level, `module=level` entries set the
+    /// level of a module and its submodules.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut filter = Self::new(LevelFilter::Info);
+        for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            match part.split_once('=') {
+                Some((module, level)) => filter.set_module(module, parse_level(level)?),
+                None => filter.default = parse_level(part)?,
+            }
+        }
+        Ok(filter)
+    }
+
+    /// Filter of the `[logging]` config section
+    pub fn from_config(config: &LoggingConfig) -> Result<Self, String> {
+        let mut filter = Self::new(parse_level(&config.default)?);
+        for (module, level) in &config.modules {
+            filter.set_module(module, parse_level(level)?);
+        }
+        Ok(filter)
+    }
+
+    /// Set the level of a module, replacing an earlier entry
+    fn set_module(&mut self, module: &str, level: LevelFilter) {
+        // Crate names are written with dashes, their module paths use underscores
+        let module = module.trim().replace('-', "_");
+        self.modules.retain(|(m, _)| *m != module);
+        self.modules.push((module, level));
+    }
+
+    /// Level of log records of `target`
+    ///
+    /// The longest matching module entry wins; noisy dependencies without an
+    /// entry are capped at warn.
+    pub fn level_for(&self, target: &str) -> LevelFilter {
+        self.modules
+            .iter()
+            .filter(|(module, _)| matches_module(target, module))
+            .max_by_key(|(module, _)| module.len())
+            .map(|(_, level)| *level)
+            .unwrap_or_else(|| {
+                if NOISY_MODULES.iter().any(|m| matches_module(target, m)) {
+                    self.default.min(LevelFilter::Warn)
+                } else {
+                    self.default
+                }
+            })
+    }
+
+    /// Most verbose level of any module
+    pub fn max_level(&self) -> LevelFilter {
+        self.modules
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.default, std::cmp::max)
+    }
+
+    /// The filter as a spec accepted by `parse`
+    pub fn spec(&self) -> String {
+        std::iter::once(self.default.as_str().to_lowercase())
+            .chain(
+                self.modules
+                    .iter()
+                    .map(|(module, level)| format!("{}={}", module, level.as_str().to_lowercase())),
+            )
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+fn parse_level(level: &str) -> Result<LevelFilter, String> {
+    level
+        .trim()
+        .parse()
+        .map_err(|_| format!("unknown log level '{}'", level.trim()))
+}
+
+/// Whether `target` is `module` or one of its submodules
+fn matches_module(target: &str, module: &str) -> bool {
+    target
+        .strip_prefix(module)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+/// Shared path of the active log file, updated on rotation
+#[derive(Debug, Clone)]
+pub struct LogFile(Arc<RwLock<PathBuf>>);
+
+impl LogFile {
+    pub fn new(path: PathBuf) -> Self {
+        Self(Arc::new(RwLock::new(path)))
+    }
+
+    /// Path of the file currently written to
+    pub fn path(&self) -> PathBuf {
+        self.0.read().map(|p| p.clone()).unwrap_or_default()
+    }
+
+    fn set(&self, path: PathBuf) {
+        if let Ok(mut current) = self.0.write() {
+            *current = path;
+        }
+    }
+}
+
+/// When to rotate and how many old files to keep
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RotationPolicy {
+    rotation: LogRotation,
+    max_bytes: u64,
+    keep_files: usize,
+}
+
+impl RotationPolicy {
+    fn from_config(config: &LoggingConfig) -> Self {
+        Self {
+            rotation: config.rotation,
+            max_bytes: config.max_size_mb.max(1) * 1024 * 1024,
+            keep_files: config.keep_files,
+        }
+    }
+}
+
+impl Default for RotationPolicy {
+    fn default() -> Self {
+        Self::from_config(&LoggingConfig::default())
+    }
+}
+
+/// Log file starting a new file when the rotation policy says so
+struct RollingFile {
+    dir: PathBuf,
+    current: LogFile,
+    file: File,
+    written: u64,
+    opened_on: NaiveDate,
+    policy: RotationPolicy,
+    /// Last failure to remove an old file, reported with the next log line
+    prune_error: Option<String>,
+}
+
+impl RollingFile {
+    /// Create a new log file in `dir` and publish its path through `current`
+    fn open(dir: PathBuf, current: LogFile, policy: RotationPolicy) -> io::Result<Self> {
+        let now = Local::now();
+        let path = new_file_path(&dir, now);
+        let file = File::create(&path)?;
+        current.set(path);
+        let mut rolling = Self {
+            dir,
+            current,
+            file,
+            written: 0,
+            opened_on: now.date_naive(),
+            policy,
+            prune_error: None,
+        };
+        rolling.prune();
+        Ok(rolling)
+    }
+
+    fn needs_rotation(&self, today: NaiveDate) -> bool {
+        match self.policy.rotation {
+            LogRotation::Size => self.written >= self.policy.max_bytes,
+            LogRotation::Daily => today != self.opened_on,
+            LogRotation::Never => false,
+        }
+    }
+
+    fn write_line(&mut self, line: &str, now: DateTime<Local>) -> io::Result<()> {
+        if self.needs_rotation(now.date_naive()) {
+            self.rotate(now)?;
+        }
+        writeln!(self.file, "{}", line)?;
+        self.written += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    fn rotate(&mut self, now: DateTime<Local>) -> io::Result<()> {
+        let path = new_file_path(&self.dir, now);
+        self.file = File::create(&path)?;
+        self.current.set(path);
+        self.written = 0;
+        self.opened_on = now.date_naive();
+        self.prune();
+        Ok(())
+    }
+
+    /// Remove the oldest log files beyond `keep_files`, never the active one
+    fn prune(&mut self) {
+        let active = self.current.path();
+        let dir = if self.dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            self.dir.as_path()
+        };
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        let mut old: Vec<(std::time::SystemTime, PathBuf)> = entries
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                name.starts_with(FILE_PREFIX) && name.ends_with(FILE_SUFFIX)
+            })
+            .filter(|entry| entry.file_name() != active.file_name().unwrap_or_default())
+            .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+            .collect();
+        // Newest first
+        old.sort_by(|a, b| b.cmp(a));
+        for (_, path) in old.into_iter().skip(self.policy.keep_files) {
+            if let Err(e) = fs::remove_file(&path) {
+                self.prune_error = Some(format!(
+                    "Failed to remove old log file {}: {}",
+                    path.display(),
+                    e
+                ));
+            }
+        }
+    }
+}
+
+/// Directory of the log files based on build type
+fn log_dir() -> PathBuf {
     if cfg!(debug_assertions) {
         // Debug build: log in current directory for convenience
-        PathBuf::from(filename)
+        PathBuf::new()
     } else {
         // Release build: log in cache directory
-        gh_pr_config::cache_dir()
-            .map(|dir| dir.join(&filename))
-            .unwrap_or_else(|_| PathBuf::from(filename))
+        gh_pr_config::cache_dir().unwrap_or_default()
+    }
+}
+
+/// Timestamped path of a new log file, not clashing with existing files
+fn new_file_path(dir: &Path, now: DateTime<Local>) -> PathBuf {
+    let stem = format!("{}{}", FILE_PREFIX, now.format("%Y%m%d-%H%M%S"));
+    let mut path = dir.join(format!("{}{}", stem, FILE_SUFFIX));
+    let mut counter = 1;
+    while path.exists() {
+        path = dir.join(format!("{}-{}{}", stem, counter, FILE_SUFFIX));
+        counter += 1;
+    }
+    path
+}
+
+/// One log line in the configured format
+fn format_record(format: LogFormat, now: DateTime<Local>, record: &Record) -> String {
+    let ts = now.to_rfc3339_opts(SecondsFormat::Millis, false);
+    match format {
+        LogFormat::Text => format!(
+            "{} [{}] {}: {}",
+            ts,
+            record.level(),
+            record.target(),
+            record.args()
+        ),
+        LogFormat::Json => serde_json::json!({
+            "ts": ts,
+            "level": record.level().as_str(),
+            "target": record.target(),
+            "msg": record.args().to_string(),
+        })
+        .to_string(),
+    }
+}
+
+/// Output side of the logger, behind one lock so lines never interleave
+struct Output {
+    file: RollingFile,
+    format: LogFormat,
+}
+
+impl Output {
+    /// Write `record`, followed by a warning if pruning old files failed
+    fn write(&mut self, record: &Record, now: DateTime<Local>) -> io::Result<()> {
+        let line = format_record(self.format, now, record);
+        self.file.write_line(&line, now)?;
+        if let Some(error) = self.file.prune_error.take() {
+            let warning = format_record(
+                self.format,
+                now,
+                &Record::builder()
+                    .args(format_args!("{}", error))
+                    .level(log::Level::Warn)
+                    .target(module_path!())
+                    .build(),
+            );
+            self.file.write_line(&warning, now)?;
+        }
+        Ok(())
+    }
+}
+
+struct FileLogger {
+    filter: RwLock<LogFilter>,
+    output: Mutex<Output>,
+    /// Whether `RUST_LOG` set the levels (config levels are ignored then)
+    env_filter: bool,
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.filter
+            .read()
+            .is_ok_and(|filter| metadata.level() <= filter.level_for(metadata.target()))
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let now = Local::now();
+        if let Ok(mut output) = self.output.lock() {
+            // Nowhere to report a failing log file, the terminal belongs to the UI
+            let _ = output.write(record, now);
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut output) = self.output.lock() {
+            let _ = output.file.file.flush();
+        }
     }
 }
 
 /// Initialize file-based logging
 ///
-/// Creates a log file with timestamp.
-/// Returns the path to the log file for use by the debug console.
-pub fn init() -> PathBuf {
-    let log_file = log_file_path();
-
-    let level = std::env::var("RUST_LOG")
-        .map(|v| match v.to_lowercase().as_str() {
-            "error" => LevelFilter::Error,
-            "warn" => LevelFilter::Warn,
-            "info" => LevelFilter::Info,
-            "debug" => LevelFilter::Debug,
-            "trace" => LevelFilter::Trace,
-            _ => LevelFilter::Info,
-        })
-        .unwrap_or(LevelFilter::Debug);
+/// Creates a log file with timestamp, using the default `[logging]` settings
+/// until `configure` is called with the loaded config. Returns the handle of
+/// the active log file for the debug console.
+pub fn init() -> LogFile {
+    let (filter, env_filter) = match std::env::var("RUST_LOG") {
+        Ok(spec) => (
+            LogFilter::parse(&spec).unwrap_or_else(|_| LogFilter::new(LevelFilter::Info)),
+            true,
+        ),
+        Err(_) => (LogFilter::new(LevelFilter::Debug), false),
+    };
+
+    let current = LogFile::new(PathBuf::new());
+    let file = RollingFile::open(log_dir(), current.clone(), RotationPolicy::default())
+        .expect("Failed to create log file");
+
+    let max_level = filter.max_level();
+    let logger = LOGGER.get_or_init(|| FileLogger {
+        filter: RwLock::new(filter),
+        output: Mutex::new(Output {
+            file,
+            format: LogFormat::default(),
+        }),
+        env_filter,
+    });
+    log::set_logger(logger).expect("Failed to initialize logger");
+    log::set_max_level(max_level);
+
+    current
+}
+
+/// Apply the `[logging]` config section
+///
+/// Levels set through `RUST_LOG` take precedence over the configured ones.
+pub fn configure(config: &LoggingConfig) -> Result<(), String> {
+    let Some(logger) = LOGGER.get() else {
+        return Ok(());
+    };
+    if let Ok(mut output) = logger.output.lock() {
+        output.format = config.format;
+        output.file.policy = RotationPolicy::from_config(config);
+        output.file.prune();
+    }
+    if !logger.env_filter {
+        set_filter(LogFilter::from_config(config)?);
+    }
+    Ok(())
+}
 
-    // Configure simplelog with timestamps
-    let config = ConfigBuilder::new()
-        .set_time_format_rfc3339()
-        .set_time_offset_to_local()
-        .unwrap_or_else(|c| c) // Fallback if local time offset fails
-        .build();
+/// Replace the level filter without restarting
+pub fn set_filter(filter: LogFilter) {
+    let Some(logger) = LOGGER.get() else {
+        return;
+    };
+    log::set_max_level(filter.max_level());
+    if let Ok(mut current) = logger.filter.write() {
+        *current = filter;
+    }
+}
+
+/// The active level filter (None before `init`)
+pub fn current_filter() -> Option<LogFilter> {
+    LOGGER
+        .get()?
+        .filter
+        .read()
+        .ok()
+        .map(|filter| filter.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_parse_spec_and_levels_per_module() {
+        let filter =
+            LogFilter::parse("warn, gh_client=debug,gh_pr_lander::middleware=trace").unwrap();
+        assert_eq!(filter.level_for("gh_pr_lander::views"), LevelFilter::Warn);
+        assert_eq!(filter.level_for("gh_client"), LevelFilter::Debug);
+        assert_eq!(filter.level_for("gh_client::cache"), LevelFilter::Debug);
+        assert_eq!(filter.level_for("gh_client_extra"), LevelFilter::Warn);
+        assert_eq!(
+            filter.level_for("gh_pr_lander::middleware::github_middleware"),
+            LevelFilter::Trace
+        );
+        assert_eq!(filter.max_level(), LevelFilter::Trace);
+        assert_eq!(
+            filter.spec(),
+            "warn,gh_client=debug,gh_pr_lander::middleware=trace"
+        );
+        assert_eq!(LogFilter::parse(&filter.spec()).unwrap(), filter);
+
+        assert!(LogFilter::parse("loud").is_err());
+        assert!(LogFilter::parse("gh_client=loud").is_err());
+    }
+
+    #[test]
+    fn test_noisy_modules_are_capped_unless_configured() {
+        let filter = LogFilter::new(LevelFilter::Trace);
+        assert_eq!(filter.level_for("octocrab::service"), LevelFilter::Warn);
+        assert_eq!(filter.level_for("hyper_util::client"), LevelFilter::Warn);
+
+        let config = LoggingConfig {
+            default: "error".to_string(),
+            modules: BTreeMap::from([("octocrab".to_string(), "trace".to_string())]),
+            ..Default::default()
+        };
+        let filter = LogFilter::from_config(&config).unwrap();
+        assert_eq!(filter.level_for("octocrab::service"), LevelFilter::Trace);
+        assert_eq!(filter.level_for("hyper"), LevelFilter::Error);
+    }
+
+    #[test]
+    fn test_config_modules_accept_crate_names() {
+        let config = LoggingConfig {
+            modules: BTreeMap::from([("gh-client".to_string(), "trace".to_string())]),
+            ..Default::default()
+        };
+        let filter = LogFilter::from_config(&config).unwrap();
+        assert_eq!(filter.level_for("gh_client::api"), LevelFilter::Trace);
+    }
+
+    #[test]
+    fn test_json_format_is_one_object_per_line() {
+        let now = Local::now();
+        let line = format_record(
+            LogFormat::Json,
+            now,
+            &Record::builder()
+                .args(format_args!("loaded \"3\" PRs"))
+                .level(log::Level::Info)
+                .target("gh_pr_lander::middleware")
+                .build(),
+        );
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["level"], "INFO");
+        assert_eq!(value["target"], "gh_pr_lander::middleware");
+        assert_eq!(value["msg"], "loaded \"3\" PRs");
+    }
+
+    #[test]
+    fn test_rotation_by_size_follows_and_prunes_files() {
+        let dir =
+            std::env::temp_dir().join(format!("gh-pr-lander-log-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let current = LogFile::new(PathBuf::new());
+        let policy = RotationPolicy {
+            rotation: LogRotation::Size,
+            max_bytes: 10,
+            keep_files: 1,
+        };
+        let mut file = RollingFile::open(dir.clone(), current.clone(), policy).unwrap();
+        let first = current.path();
+        assert!(first.starts_with(&dir));
+
+        let now = Local::now();
+        file.write_line("more than ten bytes", now).unwrap();
+        assert_eq!(current.path(), first);
+        file.write_line("rotated", now).unwrap();
+        let second = current.path();
+        assert_ne!(second, first);
+        assert_eq!(fs::read_to_string(&second).unwrap(), "rotated\n");
+
+        file.write_line("rotated again, dropping the first file", now)
+            .unwrap();
+        file.write_line("third", now).unwrap();
+        let remaining = fs::read_dir(&dir).unwrap().count();
+        assert_eq!(remaining, 2, "active file plus one kept");
+        assert!(!first.exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 
-    let file = File::create(&log_file).expect("Failed to create log file");
+    #[test]
+    fn test_daily_rotation() {
+        let dir =
+            std::env::temp_dir().join(format!("gh-pr-lander-log-daily-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
 
-    WriteLogger::init(level, config, file).expect("Failed to initialize logger");
+        let current = LogFile::new(PathBuf::new());
+        let policy = RotationPolicy {
+            rotation: LogRotation::Daily,
+            max_bytes: 10,
+            keep_files: 5,
+        };
+        let file = RollingFile::open(dir.clone(), current, policy).unwrap();
+        let today = Local::now().date_naive();
+        assert!(!file.needs_rotation(today));
+        assert!(file.needs_rotation(today.succ_opt().unwrap()));
 
-    log_file
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_failed_prune_is_logged_with_the_next_line() {
+        let dir =
+            std::env::temp_dir().join(format!("gh-pr-lander-log-prune-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        // A directory named like a log file cannot be removed as a file
+        fs::create_dir_all(dir.join("debug-old.log")).unwrap();
+
+        let current = LogFile::new(PathBuf::new());
+        let policy = RotationPolicy {
+            rotation: LogRotation::Never,
+            max_bytes: 10,
+            keep_files: 0,
+        };
+        let file = RollingFile::open(dir.clone(), current.clone(), policy).unwrap();
+        assert!(file.prune_error.is_some());
+
+        let mut output = Output {
+            file,
+            format: LogFormat::Text,
+        };
+        output
+            .write(
+                &Record::builder()
+                    .args(format_args!("hello"))
+                    .level(log::Level::Info)
+                    .target("gh_pr_lander")
+                    .build(),
+                Local::now(),
+            )
+            .unwrap();
+        assert!(output.file.prune_error.is_none());
+
+        let written = fs::read_to_string(current.path()).unwrap();
+        let lines: Vec<_> = written.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("gh_pr_lander: hello"));
+        assert!(lines[1].contains("[WARN] gh_pr_lander::logger: Failed to remove old log file"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
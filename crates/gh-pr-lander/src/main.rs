@@ -1,7 +1,7 @@
 use ratatui::{
     backend::CrosstermBackend,
     crossterm::{
-        event::{self, Event, KeyEventKind},
+        event::{self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyEventKind},
         execute,
         terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     },
@@ -13,49 +13,103 @@ use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
 mod actions;
+mod audit;
 mod background;
+mod blame;
+mod bug_report;
 mod capabilities;
+mod cli;
 mod command_id;
+mod command_registry;
 mod commands;
+mod demo;
 mod dispatcher;
 mod domain_models;
+mod export;
+mod headless;
+mod input_coalescing;
 mod keybindings;
 mod keymap;
 mod log_reader;
 mod logger;
 mod middleware;
 mod reducers;
+#[cfg(test)]
+mod smoke_tests;
 mod state;
 mod store;
 mod utils;
 mod view_models;
 mod views;
 
-use actions::{Action, BootstrapAction, GlobalAction};
-use background::{spawn_background_worker, SharedState};
+use actions::{Action, BootstrapAction, GlobalAction, StatusBarAction};
+use audit::AuditLog;
+use background::{spawn_background_worker, Heartbeat, SharedState};
+use cli::RunMode;
 use middleware::{
-    app_config_middleware::AppConfigMiddleware, bootstrap_middleware::BootstrapMiddleware,
-    command_palette_middleware::CommandPaletteMiddleware,
+    app_config_middleware::AppConfigMiddleware, audit_log_middleware::AuditLogMiddleware,
+    bootstrap_middleware::BootstrapMiddleware, bug_report_middleware::BugReportMiddleware,
+    ci_middleware::CiMiddleware, command_palette_middleware::CommandPaletteMiddleware,
     confirmation_popup_middleware::ConfirmationPopupMiddleware,
-    debug_console_middleware::DebugConsoleMiddleware, diff_viewer_middleware::DiffViewerMiddleware,
-    github_middleware::GitHubMiddleware, keyboard_middleware::KeyboardMiddleware,
-    navigation_middleware::NavigationMiddleware, pull_request_middleware::PullRequestMiddleware,
-    repository_middleware::RepositoryMiddleware, session_middleware::SessionMiddleware,
-    text_input_middleware::TextInputMiddleware, Middleware,
+    debug_console_middleware::DebugConsoleMiddleware, demo_middleware::DemoMiddleware,
+    diff_viewer_middleware::DiffViewerMiddleware, export_middleware::ExportMiddleware,
+    github_context::GitHubContext, github_middleware::GitHubMiddleware,
+    incident_middleware::IncidentMiddleware, keyboard_middleware::KeyboardMiddleware,
+    local_tools_middleware::LocalToolsMiddleware, logging_middleware,
+    navigation_middleware::NavigationMiddleware, notes_middleware::NotesMiddleware,
+    onboarding_middleware::OnboardingMiddleware, perf_stats_middleware::PerfStatsMiddleware,
+    pr_load_middleware::PrLoadMiddleware, pr_mutation_middleware::PrMutationMiddleware,
+    pull_request_middleware::PullRequestMiddleware, quit_middleware::QuitMiddleware,
+    repository_middleware::RepositoryMiddleware, saved_filter_middleware::SavedFilterMiddleware,
+    session_middleware::SessionMiddleware, snooze_middleware::SnoozeMiddleware,
+    text_input_middleware::TextInputMiddleware, Middleware, MiddlewareChain,
 };
 use state::AppState;
 use store::Store;
 
 fn main() -> io::Result<()> {
-    // Initialize file-based logger (returns log file path for debug console)
+    let run_mode = match RunMode::from_args(std::env::args().skip(1)) {
+        Ok(mode) => mode,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    if let RunMode::Version { verbose } = run_mode {
+        if verbose {
+            println!("{}", bug_report::EnvironmentInfo::collect(None));
+        } else {
+            println!("gh-pr-lander {}", env!("CARGO_PKG_VERSION"));
+        }
+        return Ok(());
+    }
+    if let RunMode::Bench(options) = &run_mode {
+        demo::bench::run(options);
+        return Ok(());
+    }
+
+    // Initialize file-based logger (returns the active log file for debug console)
     let log_file = logger::init();
 
     log::info!("Starting GitHub PR Lander");
 
+    if let RunMode::Watch(options) = &run_mode {
+        // No terminal setup, events are printed to stdout
+        let success = headless::run(options, build_middleware(&run_mode, log_file));
+        log::info!("Exiting GitHub PR Lander watch mode");
+        if !success {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Write pending saves when killed (tmux kill-pane, SSH drop) or panicking
+    utils::autosave::flush_on_crash(restore_terminal);
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
@@ -71,33 +125,28 @@ fn main() -> io::Result<()> {
     let mut store = Store::new(initial_state);
 
     // Build middleware list (will run on background thread)
-    let middleware: Vec<Box<dyn Middleware + Send>> = vec![
-        Box::new(BootstrapMiddleware::new()),
-        Box::new(SessionMiddleware::new()), // Session load/save - early in chain
-        Box::new(AppConfigMiddleware::new()), // Load app config early
-        Box::new(GitHubMiddleware::new()),  // GitHub client & API operations
-        Box::new(KeyboardMiddleware::new()),
-        // Translation middlewares - convert generic actions to view-specific actions
-        Box::new(NavigationMiddleware::new()),
-        Box::new(TextInputMiddleware::new()),
-        // View-specific middlewares
-        Box::new(DiffViewerMiddleware::new()),
-        Box::new(CommandPaletteMiddleware::new()),
-        Box::new(ConfirmationPopupMiddleware::new()),
-        Box::new(RepositoryMiddleware::new()),
-        Box::new(PullRequestMiddleware::new()), // Bulk loading coordination
-        Box::new(DebugConsoleMiddleware::new(log_file)), // Debug console log reader
-    ];
+    let middleware = build_middleware(&run_mode, log_file);
 
     // Spawn background worker with all middleware
+    let heartbeat = Heartbeat::default();
     let bg_handle = spawn_background_worker(
         action_rx,         // Background receives actions
         action_tx.clone(), // For Dispatcher to re-enter middleware
         result_tx,         // Background sends results to reducers
         shared_state.clone(),
         middleware,
+        heartbeat.clone(),
     );
 
+    // Viewports are derived from the terminal size, so report it first
+    let size = terminal.size()?;
+    action_tx
+        .send(Action::Global(GlobalAction::Resized {
+            width: size.width,
+            height: size.height,
+        }))
+        .ok();
+
     // Send bootstrap action to background
     action_tx
         .send(Action::Bootstrap(BootstrapAction::Start))
@@ -110,6 +159,7 @@ fn main() -> io::Result<()> {
         &action_tx,
         &result_rx,
         &shared_state,
+        &heartbeat,
     );
 
     // Graceful shutdown: signal background and wait
@@ -120,7 +170,11 @@ fn main() -> io::Result<()> {
 
     // Restore terminal
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        DisableBracketedPaste,
+        LeaveAlternateScreen
+    )?;
     terminal.show_cursor()?;
 
     if let Err(err) = result {
@@ -131,6 +185,72 @@ fn main() -> io::Result<()> {
     Ok(())
 }
 
+/// Leave the alternate screen and raw mode, best effort on the way out
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), DisableBracketedPaste, LeaveAlternateScreen);
+}
+
+/// The middleware chain of a run mode (runs on the background thread)
+fn build_middleware(run_mode: &RunMode, log_file: logger::LogFile) -> MiddlewareChain {
+    let mut middleware: MiddlewareChain = vec![
+        Box::new(logging_middleware()), // Observes every action, never consumes
+        Box::new(BootstrapMiddleware::new()),
+    ];
+    match run_mode {
+        RunMode::Demo(options) => {
+            // Generated data instead of GitHub; never touches the session files
+            middleware.push(Box::new(AppConfigMiddleware::new()));
+            middleware.push(Box::new(DemoMiddleware::new(options.clone())));
+            middleware.push(Box::new(NotesMiddleware::in_memory()));
+            middleware.push(Box::new(SnoozeMiddleware::in_memory()));
+            middleware.push(Box::new(SavedFilterMiddleware::in_memory()));
+            middleware.push(Box::new(AuditLogMiddleware::new(AuditLog::disabled())));
+        }
+        _ => {
+            middleware.push(Box::new(SessionMiddleware::new())); // Session load/save - early in chain
+            middleware.push(Box::new(AppConfigMiddleware::new())); // Load app config early
+            middleware.push(Box::new(IncidentMiddleware::new())); // GitHub status page on repeated errors
+
+            // GitHub API operations, sharing one runtime and client manager
+            let github = GitHubContext::new();
+            middleware.push(Box::new(GitHubMiddleware::new(github.clone()))); // Clients, read-only gate
+            middleware.push(Box::new(PrLoadMiddleware::new(github.clone()))); // PRs, search, diffs
+            middleware.push(Box::new(PrMutationMiddleware::new(github.clone()))); // Merge, review, ...
+            middleware.push(Box::new(CiMiddleware::new(github.clone()))); // CI status, build logs
+            middleware.push(Box::new(AuditLogMiddleware::new(github.audit_log.clone()))); // Audit log reader
+            middleware.push(Box::new(LocalToolsMiddleware::new(github))); // Browser, IDE, clipboard
+            middleware.push(Box::new(NotesMiddleware::new())); // Local per-PR notes
+            middleware.push(Box::new(SnoozeMiddleware::new())); // Snoozed PRs
+            middleware.push(Box::new(SavedFilterMiddleware::new())); // Saved filters
+            if !matches!(run_mode, RunMode::Watch(_)) {
+                middleware.push(Box::new(OnboardingMiddleware::new())); // First-run wizard
+            }
+        }
+    }
+    middleware.extend([
+        Box::new(KeyboardMiddleware::new()) as Box<dyn Middleware<Action, AppState> + Send>,
+        // Translation middlewares - convert generic actions to view-specific actions
+        Box::new(NavigationMiddleware::new()),
+        Box::new(TextInputMiddleware::new()),
+        // View-specific middlewares
+        Box::new(DiffViewerMiddleware::new()),
+        Box::new(CommandPaletteMiddleware::new()),
+        Box::new(ConfirmationPopupMiddleware::new()),
+        Box::new(QuitMiddleware::new()), // Asks before quitting with unfinished work
+        Box::new(RepositoryMiddleware::new().starting_in(match run_mode {
+            RunMode::Normal { profile } => profile.clone(),
+            _ => None,
+        })),
+        Box::new(PullRequestMiddleware::new()), // Bulk loading coordination
+        Box::new(BugReportMiddleware::new(log_file.clone())), // Bug report bundle
+        Box::new(ExportMiddleware::new()),      // Build log exports
+        Box::new(DebugConsoleMiddleware::new(log_file)), // Debug console log reader
+        Box::new(PerfStatsMiddleware::new()),   // API metrics snapshots and summaries
+    ]);
+    middleware
+}
+
 /// Maximum time budget for processing actions before rendering
 /// This ensures smooth animations even when many actions are queued
 const RENDER_BUDGET: Duration = Duration::from_millis(16); // ~60fps frame budget
@@ -141,7 +261,10 @@ fn run_app(
     action_tx: &mpsc::Sender<Action>,
     result_rx: &mpsc::Receiver<Action>,
     shared_state: &SharedState,
+    heartbeat: &Heartbeat,
 ) -> io::Result<()> {
+    // When input was last sent to the background worker
+    let mut last_sent: Option<Instant> = None;
     loop {
         // === PHASE 1: Process results from background (time-budgeted) ===
         let start = Instant::now();
@@ -184,37 +307,26 @@ fn run_app(
             );
         }
 
-        // === PHASE 2: Render ===
-        let mut terminal_height = 0u16;
-        terminal.draw(|frame| {
-            let area = frame.area();
-            terminal_height = area.height;
-            views::render(store.state(), area, frame);
-        })?;
-
-        // Update debug console visible height based on terminal size
-        // (70% of screen height minus 2 for borders)
-        let debug_console_height = ((terminal_height as usize) * 70 / 100).saturating_sub(2);
-        if store.state().debug_console.visible_height != debug_console_height {
-            store.dispatch(Action::DebugConsole(
-                crate::actions::DebugConsoleAction::SetVisibleHeight(debug_console_height),
-            ));
+        // Tell the user when input stays unanswered (the worker blocks or died)
+        let unresponsive = heartbeat.unresponsive(last_sent, Instant::now());
+        if unresponsive != store.state().status_bar.worker_unresponsive {
+            if unresponsive {
+                log::error!("Background worker unresponsive");
+            }
+            store.dispatch(Action::StatusBar(StatusBarAction::WorkerUnresponsive(
+                unresponsive,
+            )));
+            if let Ok(mut shared) = shared_state.write() {
+                *shared = store.state().clone();
+            }
         }
 
-        // Update diff viewer viewport height based on terminal size
-        // (full height minus 3 for status bar and borders)
-        let terminal_width = terminal.size()?.width;
-        let diff_viewport_height = terminal_height.saturating_sub(3) as usize;
-        if let Some(ref inner) = store.state().diff_viewer.inner {
-            if inner.viewport_height != diff_viewport_height {
-                store.dispatch(Action::DiffViewer(
-                    crate::actions::DiffViewerAction::SetViewport {
-                        width: terminal_width,
-                        height: diff_viewport_height as u16,
-                    },
-                ));
-            }
+        // === PHASE 2: Render ===
+        if utils::autosave::take_caught_panic() {
+            // Repaint every cell in case the panic left output on the screen
+            terminal.clear()?;
         }
+        terminal.draw(|frame| views::render(store.state(), frame.area(), frame))?;
 
         // === PHASE 3: Check quit condition ===
         if !store.state().running {
@@ -224,15 +336,39 @@ fn run_app(
         }
 
         // === PHASE 4: Handle user input ===
+        // Drain every pending event, so a key burst is absorbed in one frame
         if event::poll(Duration::from_millis(10))? {
-            if let Event::Key(key) = event::read()? {
-                // Only process key press events (ignore key release)
-                if key.kind == KeyEventKind::Press {
-                    // Send to background for middleware processing
-                    action_tx
-                        .send(Action::Global(GlobalAction::KeyPressed(key)))
-                        .ok();
+            let mut keys = Vec::new();
+            let mut actions = Vec::new();
+            let mut resized = None;
+            loop {
+                match event::read()? {
+                    // Only process key press events (ignore key release)
+                    Event::Key(key) if key.kind == KeyEventKind::Press => keys.push(key),
+                    // Keys typed before the paste stay before it
+                    Event::Paste(text) => {
+                        actions.extend(input_coalescing::coalesce_keys(std::mem::take(&mut keys)));
+                        actions.push(Action::Global(GlobalAction::Paste(text)));
+                    }
+                    // Only the last size of a resize burst matters
+                    Event::Resize(width, height) => resized = Some((width, height)),
+                    _ => {}
                 }
+                if !event::poll(Duration::ZERO)? {
+                    break;
+                }
+            }
+            // Send to background for middleware processing
+            actions.extend(input_coalescing::coalesce_keys(keys));
+            for action in actions {
+                action_tx.send(action).ok();
+                last_sent = Some(Instant::now());
+            }
+            if let Some((width, height)) = resized {
+                action_tx
+                    .send(Action::Global(GlobalAction::Resized { width, height }))
+                    .ok();
+                last_sent = Some(Instant::now());
             }
         }
     }
@@ -2,10 +2,11 @@
 //!
 //! Handles loading application configuration on bootstrap.
 
-use crate::actions::{Action, BootstrapAction};
+use crate::actions::{Action, BootstrapAction, StatusBarAction};
 use crate::dispatcher::Dispatcher;
 use crate::middleware::Middleware;
-use crate::state::AppState;
+use crate::state::{AppState, BootstrapStage};
+use gh_client::RequestBudget;
 use gh_pr_config::AppConfig;
 
 /// Middleware for loading application configuration
@@ -27,19 +28,39 @@ impl Default for AppConfigMiddleware {
     }
 }
 
-impl Middleware for AppConfigMiddleware {
+impl Middleware<Action, AppState> for AppConfigMiddleware {
     fn handle(&mut self, action: &Action, _state: &AppState, dispatcher: &Dispatcher) -> bool {
         match action {
             Action::Bootstrap(BootstrapAction::Start) => {
                 if !self.config_loaded {
                     log::info!("AppConfigMiddleware: Loading application configuration");
                     // This can block - we're on the background thread
-                    let config = AppConfig::load();
+                    let (config, notice) = AppConfig::load_with_recovery();
                     log::info!(
                         "AppConfigMiddleware: Loaded config (ide_command: {})",
                         config.ide_command
                     );
-                    dispatcher.dispatch(Action::Bootstrap(BootstrapAction::ConfigLoaded(config)));
+                    if let Err(e) = crate::logger::configure(&config.logging) {
+                        dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                            format!("Invalid [logging] config: {}", e),
+                            "Config",
+                        )));
+                    }
+                    if let Some(rpm) = config.requests_per_minute {
+                        RequestBudget::global().set_requests_per_minute(Some(rpm));
+                    }
+                    if let Some(notice) = notice {
+                        dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                            notice.message(),
+                            "Config",
+                        )));
+                    }
+                    dispatcher.dispatch(Action::Bootstrap(BootstrapAction::ConfigLoaded(
+                        Box::new(config),
+                    )));
+                    dispatcher.dispatch(Action::Bootstrap(BootstrapAction::StageCompleted(
+                        BootstrapStage::LoadConfig,
+                    )));
                     self.config_loaded = true;
                 }
                 true // Pass through
@@ -0,0 +1,98 @@
+//! Audit Log Middleware
+//!
+//! Reads the audit log for the audit log panel when it opens or refreshes.
+//! The file is read on an [`IoWorker`] after the pending entries are written,
+//! so an operation that just finished is listed.
+//!
+//! In demo mode the log is disabled and the panel stays empty.
+
+use crate::actions::{Action, AuditLogAction};
+use crate::audit::AuditLog;
+use crate::dispatcher::Dispatcher;
+use crate::middleware::Middleware;
+use crate::state::AppState;
+use crate::utils::io_worker::IoWorker;
+
+/// Middleware reading the audit log
+pub struct AuditLogMiddleware {
+    log: AuditLog,
+    /// Reads the log files off the middleware thread
+    io: IoWorker,
+}
+
+impl AuditLogMiddleware {
+    /// Read the entries recorded by `log`
+    pub fn new(log: AuditLog) -> Self {
+        Self {
+            log,
+            io: IoWorker::spawn("audit-log-io"),
+        }
+    }
+
+    fn load(&self, dispatcher: &Dispatcher) {
+        let (log, dispatcher) = (self.log.clone(), dispatcher.clone());
+        self.io.run(move || {
+            dispatcher.dispatch(Action::AuditLog(AuditLogAction::Loaded(log.entries())));
+        });
+    }
+}
+
+impl Middleware<Action, AppState> for AuditLogMiddleware {
+    fn handle(&mut self, action: &Action, _state: &AppState, dispatcher: &Dispatcher) -> bool {
+        match action {
+            Action::AuditLog(AuditLogAction::Open) => {
+                self.load(dispatcher);
+                true // Let reducer open the panel
+            }
+            Action::AuditLog(AuditLogAction::Refresh) => {
+                self.load(dispatcher);
+                false // Consume action
+            }
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::AuditOperation;
+    use crate::domain_models::Repository;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_open_reads_entries_recorded_so_far() {
+        let dir = std::env::temp_dir().join(format!(
+            "gh-pr-lander-audit-middleware-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("audit.jsonl");
+        let _ = std::fs::remove_file(&path);
+
+        let log = AuditLog::open(path);
+        let repo = Repository::new("acme", "api", "main");
+        log.begin(AuditOperation::Close, &repo, None)
+            .pr(3)
+            .succeeded();
+
+        let mut middleware = AuditLogMiddleware::new(log);
+        let (tx, rx) = mpsc::channel();
+        let passed = middleware.handle(
+            &Action::AuditLog(AuditLogAction::Open),
+            &AppState::default(),
+            &Dispatcher::new(tx),
+        );
+        assert!(passed);
+
+        match rx.recv_timeout(Duration::from_secs(5)).unwrap() {
+            Action::AuditLog(AuditLogAction::Loaded(entries)) => {
+                assert_eq!(entries.len(), 1);
+                assert_eq!(entries[0].operation, AuditOperation::Close);
+            }
+            other => panic!("unexpected action {:?}", other),
+        }
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -3,21 +3,51 @@
 //! Manages application startup sequence:
 //! - Dispatches LoadRecentRepositories to trigger repository loading
 //! - Listens for LoadRecentRepositoriesDone to dispatch BootstrapEnd
+//! - Logs the timing of each bootstrap stage so slow steps are diagnosable
+//! - Runs only the failed stage again on Retry
 //!
 //! Note: Tick generation for splash animation is now handled by the background worker.
 
+use std::time::Instant;
+
 use crate::actions::{Action, BootstrapAction, GlobalAction};
 use crate::dispatcher::Dispatcher;
 use crate::middleware::Middleware;
-use crate::state::AppState;
+use crate::state::{AppState, BootstrapStage};
 use crate::views::PullRequestView;
 
 /// Bootstrap middleware - manages application startup
-pub struct BootstrapMiddleware;
+pub struct BootstrapMiddleware {
+    /// When the current bootstrap attempt started
+    started_at: Option<Instant>,
+    /// When the last stage finished (start of the next stage)
+    last_stage_at: Option<Instant>,
+    /// First stage that failed in the current attempt
+    failed: Option<BootstrapStage>,
+}
 
 impl BootstrapMiddleware {
     pub fn new() -> Self {
-        Self
+        Self {
+            started_at: None,
+            last_stage_at: None,
+            failed: None,
+        }
+    }
+
+    /// Log a finished stage with its duration and the total elapsed time
+    fn log_stage(&mut self, label: &str, outcome: &str) {
+        let now = Instant::now();
+        let stage_elapsed = self.last_stage_at.map(|t| now - t).unwrap_or_default();
+        let total_elapsed = self.started_at.map(|t| now - t).unwrap_or_default();
+        log::info!(
+            "BootstrapMiddleware: Stage '{}' {} in {:?} (total {:?})",
+            label,
+            outcome,
+            stage_elapsed,
+            total_elapsed
+        );
+        self.last_stage_at = Some(now);
     }
 }
 
@@ -27,17 +57,55 @@ impl Default for BootstrapMiddleware {
     }
 }
 
-impl Middleware for BootstrapMiddleware {
+impl Middleware<Action, AppState> for BootstrapMiddleware {
     fn handle(&mut self, action: &Action, _state: &AppState, dispatcher: &Dispatcher) -> bool {
         match action {
             Action::Bootstrap(BootstrapAction::Start) => {
                 log::info!("BootstrapMiddleware: Bootstrap starting");
+                let now = Instant::now();
+                self.started_at = Some(now);
+                self.last_stage_at = Some(now);
+                self.failed = None;
                 // NOTE: Repository loading is triggered by Event::ClientReady from github_middleware
                 // NOTE: Tick generation for splash animation is handled by background worker
                 true
             }
 
+            Action::Bootstrap(BootstrapAction::StageCompleted(stage)) => {
+                self.log_stage(stage.label(), "completed");
+                true
+            }
+
+            Action::Bootstrap(BootstrapAction::StageWarning(stage, warning)) => {
+                self.log_stage(stage.label(), "completed with a warning");
+                log::warn!("BootstrapMiddleware: {}", warning);
+                true
+            }
+
+            Action::Bootstrap(BootstrapAction::StageFailed(stage, error)) => {
+                self.log_stage(stage.label(), "failed");
+                log::error!("BootstrapMiddleware: {}", error);
+                self.failed.get_or_insert(*stage);
+                true
+            }
+
+            // Stages that completed (config, session) are not run again
+            Action::Bootstrap(BootstrapAction::Retry) => {
+                if let Some(stage) = self.failed.take() {
+                    log::info!("BootstrapMiddleware: Retrying stage '{}'", stage.label());
+                    self.last_stage_at = Some(Instant::now());
+                    dispatcher.dispatch(Action::Bootstrap(BootstrapAction::RetryStage(stage)));
+                }
+                true // Let reducer reset the failed stages
+            }
+
             Action::Bootstrap(BootstrapAction::LoadRecentRepositoriesDone) => {
+                if self.failed.is_some() {
+                    log::warn!(
+                        "BootstrapMiddleware: Repository loading done, but a stage failed - staying on splash"
+                    );
+                    return true;
+                }
                 log::info!("BootstrapMiddleware: Repository loading done, ending bootstrap");
                 dispatcher.dispatch(Action::Bootstrap(BootstrapAction::End));
                 dispatcher.dispatch(Action::Global(GlobalAction::ReplaceView(Box::new(
@@ -47,7 +115,14 @@ impl Middleware for BootstrapMiddleware {
             }
 
             Action::Bootstrap(BootstrapAction::End) => {
-                log::info!("BootstrapMiddleware: Bootstrap ended");
+                if let Some(started_at) = self.started_at {
+                    log::info!(
+                        "BootstrapMiddleware: Bootstrap ended after {:?}",
+                        started_at.elapsed()
+                    );
+                } else {
+                    log::info!("BootstrapMiddleware: Bootstrap ended");
+                }
                 true
             }
 
@@ -0,0 +1,78 @@
+//! Bug Report Middleware
+//!
+//! Writes the bug report bundle:
+//! - `Open`: proposes a timestamped path in the current directory
+//! - `Submit`: collects the report and writes the zip to the entered path
+
+use crate::actions::{Action, BugReportAction, StatusBarAction};
+use crate::bug_report::{self, BugReport};
+use crate::dispatcher::Dispatcher;
+use crate::logger::LogFile;
+use crate::middleware::Middleware;
+use crate::state::AppState;
+
+/// Middleware writing bug report bundles
+pub struct BugReportMiddleware {
+    /// Current log file, its tail goes into the bundle
+    log_file: LogFile,
+}
+
+impl BugReportMiddleware {
+    pub fn new(log_file: LogFile) -> Self {
+        Self { log_file }
+    }
+}
+
+impl Middleware<Action, AppState> for BugReportMiddleware {
+    fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        match action {
+            Action::BugReport(BugReportAction::Open) => {
+                let default_path = bug_report::default_report_path(chrono::Local::now());
+                dispatcher.dispatch(Action::BugReport(BugReportAction::Show {
+                    default_path: default_path.display().to_string(),
+                }));
+                false
+            }
+
+            Action::BugReport(BugReportAction::Submit) => {
+                let Some(ref popup) = state.bug_report else {
+                    return false;
+                };
+                let input = popup.path.trim();
+                if input.is_empty() {
+                    return false;
+                }
+                let path = bug_report::expand_path(input);
+
+                log::info!(
+                    "BugReportMiddleware: Writing bug report to {}",
+                    path.display()
+                );
+                let report = BugReport::collect(state, Some(&self.log_file.path()));
+                match report.write_zip(&path) {
+                    Ok(()) => {
+                        dispatcher
+                            .dispatch(Action::BugReport(BugReportAction::Written(path.clone())));
+                        dispatcher.dispatch(Action::StatusBar(StatusBarAction::success(
+                            format!("Bug report written to {}", path.display()),
+                            "Bug report",
+                        )));
+                    }
+                    Err(e) => {
+                        log::error!("BugReportMiddleware: Failed to write bug report: {:#}", e);
+                        let message = format!("Failed to write {}: {:#}", path.display(), e);
+                        dispatcher
+                            .dispatch(Action::BugReport(BugReportAction::Failed(message.clone())));
+                        dispatcher.dispatch(Action::StatusBar(StatusBarAction::error(
+                            message,
+                            "Bug report",
+                        )));
+                    }
+                }
+                true // Let reducer mark the popup as writing
+            }
+
+            _ => true,
+        }
+    }
+}
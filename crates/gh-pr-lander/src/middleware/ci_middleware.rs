@@ -0,0 +1,1197 @@
+//! CI Middleware
+//!
+//! Everything about the checks of PRs:
+//! - CI status and review decision (on request and when switching repositories)
+//! - Rerun of failed workflow runs
+//! - Build logs (download, parse and cache workflow run logs)
+//! - Actions view (recent workflow runs of the tracked branch)
+
+use crate::actions::{
+    Action, ActionsViewAction, BuildLogAction, GlobalAction, PullRequestAction, StatusBarAction,
+};
+use crate::audit::AuditOperation;
+use crate::dispatcher::{Dispatcher, OperationDispatcher};
+use crate::domain_models::{
+    learn_ci_budget, CiBudget, CiChecks, MergeableStatus, OperationType, Pr, Repository,
+};
+use crate::middleware::github_context::{target_pr_details, GitHubContext};
+use crate::middleware::Middleware;
+use crate::state::{
+    AppState, BuildLogContext, BuildLogJobMetadata, BuildLogJobStatus, BuildLogPrContext,
+    ParsedRun, ACTIONS_VIEW_RUN_LIMIT,
+};
+use crate::utils::autosave::PendingSaves;
+use crate::utils::cycling::{next_index, previous_index};
+use crate::views::BuildLogView;
+use anyhow::Context;
+use gh_client::{octocrab::Octocrab, RequestBudget, RequestCategory, RunLogCache, RunLogKey};
+use gh_pr_config::CiDurations;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Middleware for CI status, reruns and build logs
+pub struct CiMiddleware {
+    github: GitHubContext,
+}
+
+impl CiMiddleware {
+    pub fn new(github: GitHubContext) -> Self {
+        Self { github }
+    }
+
+    /// Fetch the recent workflow runs on the repository's branch for the Actions view
+    fn fetch_recent_runs(&self, repo: Repository, dispatcher: &Dispatcher) {
+        let branch = repo.branch.clone();
+        dispatcher.dispatch(Action::ActionsView(ActionsViewAction::LoadStart {
+            repo: repo.clone(),
+            branch: branch.clone(),
+        }));
+
+        self.github
+            .task(&repo, dispatcher)
+            .operation(format!("Loading workflow runs on {}", branch), "Actions")
+            .on_failure(|error| Action::ActionsView(ActionsViewAction::LoadError(error)))
+            .spawn(move |client, dispatcher| async move {
+                let runs = client
+                    .fetch_recent_workflow_runs(
+                        &repo.org,
+                        &repo.repo,
+                        &branch,
+                        ACTIONS_VIEW_RUN_LIMIT,
+                    )
+                    .await?;
+                dispatcher.dispatch(Action::ActionsView(ActionsViewAction::Loaded(runs)));
+                Ok(())
+            });
+    }
+
+    /// Push the build log view and fetch the logs of `source` in the background
+    fn load_build_logs(
+        &self,
+        repo: Repository,
+        source: BuildLogSource,
+        context: BuildLogContext,
+        subject: String,
+        state: &AppState,
+        dispatcher: &Dispatcher,
+    ) {
+        let dispatcher = dispatcher.clone();
+        // Finished runs parsed before are reused instead of downloaded again
+        let parsed_runs: Vec<ParsedRun> = state.build_log.parsed_runs.iter().cloned().collect();
+        let client_manager = Arc::clone(&self.github.client_manager);
+
+        // Dispatch loading state and push view
+        dispatcher.dispatch(Action::BuildLog(BuildLogAction::LoadStart));
+        let operation =
+            dispatcher.start_operation(format!("Loading build logs for {}", subject), "Build Logs");
+        dispatcher.dispatch(Action::Global(GlobalAction::PushView(Box::new(
+            BuildLogView::new(),
+        ))));
+
+        // Spawn async task to fetch build logs
+        self.github.runtime.spawn(async move {
+            let _operation = operation;
+            // Get octocrab client inside async task
+            let (octocrab, budget_host) = {
+                let mut manager = client_manager.lock().await;
+                match manager
+                    .clone_client(repo.host.as_deref(), repo.profile.as_deref())
+                    .await
+                {
+                    Ok(c) => (
+                        c.inner().inner().octocrab_arc(),
+                        c.inner().inner().budget_host().to_string(),
+                    ),
+                    Err(e) => {
+                        log::error!("Failed to get client for build logs: {}", e);
+                        dispatcher
+                            .dispatch(Action::BuildLog(BuildLogAction::LoadError(e.to_string())));
+                        dispatcher.dispatch(Action::StatusBar(StatusBarAction::error(
+                            format!("Failed to load build logs: {}", e),
+                            "Build Logs",
+                        )));
+                        return;
+                    }
+                }
+            };
+
+            let runs = match source {
+                BuildLogSource::HeadSha(head_sha) => {
+                    RequestBudget::global()
+                        .acquire(&budget_host, RequestCategory::Actions)
+                        .await;
+                    fetch_head_sha_runs(&octocrab, &repo.org, &repo.repo, &head_sha).await
+                }
+                BuildLogSource::Run(run) => Ok(vec![run]),
+            };
+            let runs = match runs {
+                Ok(runs) => runs,
+                Err(e) => {
+                    log::error!("Failed to load build logs: {}", e);
+                    dispatcher.dispatch(Action::BuildLog(BuildLogAction::LoadError(e.clone())));
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::error(
+                        format!("Failed to load build logs: {}", e),
+                        "Build Logs",
+                    )));
+                    return;
+                }
+            };
+            dispatcher.dispatch(Action::BuildLog(BuildLogAction::RunsFound {
+                context,
+                total: runs.len(),
+            }));
+
+            // Each run is dispatched as soon as it is parsed, so the tree fills in
+            let fetcher = Arc::new(BuildLogFetcher {
+                octocrab,
+                budget_host,
+                repo,
+                parsed_runs,
+                cache: gh_pr_config::run_logs_dir()
+                    .map(RunLogCache::new)
+                    .map_err(|e| log::warn!("No run log cache directory: {}", e))
+                    .ok(),
+            });
+            let downloads = Arc::new(tokio::sync::Semaphore::new(BUILD_LOG_CONCURRENCY));
+            let mut tasks = tokio::task::JoinSet::new();
+            for run in runs {
+                let (fetcher, downloads) = (Arc::clone(&fetcher), Arc::clone(&downloads));
+                let dispatcher = dispatcher.clone();
+                tasks.spawn(async move {
+                    let Ok(_permit) = downloads.acquire().await else {
+                        return;
+                    };
+                    let loaded = fetcher.fetch(run).await;
+                    dispatcher.dispatch(Action::BuildLog(BuildLogAction::WorkflowLoaded {
+                        run: loaded.workflow,
+                        jobs: loaded.job_metadata,
+                        parsed_run: loaded.parsed_run,
+                    }));
+                });
+            }
+            while let Some(result) = tasks.join_next().await {
+                if let Err(e) = result {
+                    log::error!("Build log task failed: {}", e);
+                }
+            }
+
+            dispatcher.dispatch(Action::StatusBar(StatusBarAction::success(
+                format!("Build logs loaded for {}", subject),
+                "Build Logs",
+            )));
+        });
+    }
+
+    /// Trigger CI status checks for PRs that don't have status loaded yet
+    fn trigger_ci_status_if_needed(
+        &self,
+        repo_idx: usize,
+        state: &AppState,
+        dispatcher: &Dispatcher,
+    ) {
+        if !self.github.has_default_client() {
+            return;
+        }
+
+        let Some(repo) = state.main_view.repositories.get(repo_idx) else {
+            return;
+        };
+
+        // Check if this repository has PRs loaded
+        if let Some(repo_data) = state.main_view.repo_data.get(&repo_idx) {
+            // Filter PRs with Unknown status
+            let prs_needing_status: Vec<_> = repo_data
+                .prs
+                .iter()
+                .filter(|pr| matches!(pr.mergeable, MergeableStatus::Unknown))
+                .cloned()
+                .collect();
+
+            dispatch_ci_status_checks(repo, &prs_needing_status, dispatcher, false);
+        }
+    }
+}
+
+impl Middleware<Action, AppState> for CiMiddleware {
+    fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        match action {
+            // Handle repository switching - trigger CI status checks if needed
+            Action::PullRequest(PullRequestAction::RepositoryNext) => {
+                if let Some(next_repo_idx) = next_index(
+                    state.main_view.selected_repository,
+                    state.main_view.repositories.len(),
+                    state.main_view.tab_cycling,
+                ) {
+                    self.trigger_ci_status_if_needed(next_repo_idx, state, dispatcher);
+                }
+                true // Let action pass through to reducer
+            }
+
+            Action::PullRequest(PullRequestAction::RepositoryPrevious) => {
+                if let Some(prev_repo_idx) = previous_index(
+                    state.main_view.selected_repository,
+                    state.main_view.repositories.len(),
+                    state.main_view.tab_cycling,
+                ) {
+                    self.trigger_ci_status_if_needed(prev_repo_idx, state, dispatcher);
+                }
+                true // Let action pass through to reducer
+            }
+
+            Action::PullRequest(PullRequestAction::RerunFailedJobs) => {
+                let targets = target_pr_details(state);
+                let Some(repo) = state
+                    .main_view
+                    .repositories
+                    .get(state.main_view.selected_repository)
+                    .filter(|_| !targets.is_empty())
+                else {
+                    log::warn!("No PRs selected for rerunning jobs");
+                    return false;
+                };
+
+                log::info!("Rerunning failed jobs for {} PR(s)", targets.len());
+
+                // Rerun failed jobs for each target PR
+                for pr in targets {
+                    let Some(in_flight) = self.github.in_flight.begin(
+                        repo,
+                        pr.number,
+                        OperationType::Rerun,
+                        dispatcher,
+                    ) else {
+                        continue;
+                    };
+                    let repo = repo.clone();
+                    let pr_number = pr.number as u64;
+                    let head_sha = pr.head_sha.clone();
+                    let audit = self
+                        .github
+                        .audit(AuditOperation::Rerun, &repo, state)
+                        .pr(pr.number);
+
+                    // Fetch workflow runs, then rerun failed ones
+                    self.github
+                        .task(&repo, dispatcher)
+                        .in_flight(in_flight)
+                        .report("Rerun failed", "Rerun")
+                        .spawn(move |client, dispatcher| async move {
+                            let runs = match client
+                                .fetch_workflow_runs(&repo.org, &repo.repo, &head_sha)
+                                .await
+                            {
+                                Ok(runs) => runs,
+                                Err(e) => {
+                                    log::error!(
+                                        "Failed to fetch workflow runs for PR #{}: {}",
+                                        pr_number,
+                                        e
+                                    );
+                                    return Ok(());
+                                }
+                            };
+                            // Filter to failed runs and rerun each
+                            let failed_runs: Vec<_> = runs
+                                .into_iter()
+                                .filter(|r| {
+                                    r.conclusion.as_ref().is_some_and(|c| {
+                                        matches!(
+                                            c,
+                                            gh_client::WorkflowRunConclusion::Failure
+                                                | gh_client::WorkflowRunConclusion::TimedOut
+                                        )
+                                    })
+                                })
+                                .collect();
+                            if failed_runs.is_empty() {
+                                log::info!(
+                                    "No failed workflow runs to rerun for PR #{}",
+                                    pr_number
+                                );
+                            }
+
+                            for run in failed_runs {
+                                dispatcher.dispatch(Action::PullRequest(
+                                    PullRequestAction::RerunStart {
+                                        repo: repo.clone(),
+                                        pr_number,
+                                        run_id: run.id,
+                                    },
+                                ));
+
+                                let result = client
+                                    .rerun_failed_jobs(&repo.org, &repo.repo, run.id)
+                                    .await;
+                                audit
+                                    .clone()
+                                    .param("run_id", run.id)
+                                    .param("workflow", &run.name)
+                                    .finish(&result);
+                                let status = match result {
+                                    Ok(()) => {
+                                        log::info!(
+                                            "Successfully triggered rerun for workflow {} (PR #{})",
+                                            run.name,
+                                            pr_number
+                                        );
+                                        StatusBarAction::success(
+                                            format!(
+                                                "Rerun triggered for {} (PR #{})",
+                                                run.name, pr_number
+                                            ),
+                                            "Rerun",
+                                        )
+                                    }
+                                    Err(e) => {
+                                        log::error!(
+                                            "Failed to rerun workflow {} (PR #{}): {}",
+                                            run.name,
+                                            pr_number,
+                                            e
+                                        );
+                                        StatusBarAction::error(
+                                            format!("Rerun failed: {}", e),
+                                            "Rerun",
+                                        )
+                                    }
+                                };
+                                dispatcher.dispatch(Action::StatusBar(status));
+                            }
+                            Ok(())
+                        });
+                }
+                false // Consume action
+            }
+
+            // === Build Log Operations ===
+            Action::BuildLog(BuildLogAction::Open) => {
+                let repo_idx = state.main_view.selected_repository;
+
+                // Get repository info
+                let Some(repo) = state.main_view.repositories.get(repo_idx).cloned() else {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        "No repository selected",
+                        "Build Logs",
+                    )));
+                    return false;
+                };
+
+                // Get repository data
+                let Some(repo_data) = state.main_view.repo_data.get(&repo_idx) else {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        "No repository data loaded",
+                        "Build Logs",
+                    )));
+                    return false;
+                };
+
+                // Get current PR
+                let Some(pr) = repo_data.prs.get(repo_data.selected_pr) else {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        "No PR selected",
+                        "Build Logs",
+                    )));
+                    return false;
+                };
+
+                let context = BuildLogContext::Pr(BuildLogPrContext {
+                    number: pr.number,
+                    title: pr.title.clone(),
+                    author: pr.author.clone(),
+                });
+                self.load_build_logs(
+                    repo,
+                    BuildLogSource::HeadSha(pr.head_sha.clone()),
+                    context,
+                    format!("PR #{}", pr.number),
+                    state,
+                    dispatcher,
+                );
+
+                false // Consume action
+            }
+
+            // === Actions View (workflow run history) ===
+            Action::ActionsView(ActionsViewAction::Open) => {
+                let Some(repo) = state
+                    .main_view
+                    .repositories
+                    .get(state.main_view.selected_repository)
+                    .cloned()
+                else {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        "No repository selected",
+                        "Actions",
+                    )));
+                    return false;
+                };
+                self.fetch_recent_runs(repo, dispatcher);
+                true // Reducer pushes the view
+            }
+
+            Action::ActionsView(ActionsViewAction::OpenSelected) => {
+                let view = &state.actions_view;
+                if let (Some(repo), Some(run)) = (view.repo.clone(), view.selected_run_context()) {
+                    dispatcher.dispatch(Action::BuildLog(BuildLogAction::OpenRun { repo, run }));
+                }
+                false // Consume action
+            }
+
+            Action::BuildLog(BuildLogAction::OpenRun { repo, run }) => {
+                let source = BuildLogSource::Run(BuildLogRun {
+                    id: run.run_id,
+                    name: run.workflow_name.clone(),
+                    waiting: run.waiting,
+                    attempt: Some(run.attempt),
+                });
+                self.load_build_logs(
+                    repo.clone(),
+                    source,
+                    BuildLogContext::Run(run.clone()),
+                    format!("run {}", run.run_id),
+                    state,
+                    dispatcher,
+                );
+                false // Consume action
+            }
+
+            // Handle CI status check request
+            Action::PullRequest(PullRequestAction::CheckBuildStatus {
+                repo,
+                pr_number,
+                head_sha,
+                force_refresh,
+            }) => {
+                let (repo_for_ci, pr_number) = (repo.clone(), *pr_number);
+                let head_sha = head_sha.clone();
+                let ci_durations = self.github.ci_durations.clone();
+
+                // Errors are only logged, the status is left as-is
+                log::info!("Spawning CI status fetch for PR #{}", pr_number);
+                self.github
+                    .task(repo, dispatcher)
+                    .force_refresh(*force_refresh)
+                    .background()
+                    .spawn(move |client, dispatcher| async move {
+                        let ci_status = client
+                            .fetch_ci_status(&repo_for_ci.org, &repo_for_ci.repo, &head_sha)
+                            .await
+                            .with_context(|| {
+                                format!("Failed to fetch CI status for PR #{}", pr_number)
+                            })?;
+                        // PRs merge into the tracked branch, its protection
+                        // tells which failing checks actually block them
+                        let required = match client
+                            .fetch_required_checks(
+                                &repo_for_ci.org,
+                                &repo_for_ci.repo,
+                                &repo_for_ci.branch,
+                            )
+                            .await
+                        {
+                            Ok(required) => required.filter(|names| !names.is_empty()),
+                            Err(e) => {
+                                log::warn!(
+                                    "Failed to fetch required checks of {}/{}@{}: {:#}",
+                                    repo_for_ci.org,
+                                    repo_for_ci.repo,
+                                    repo_for_ci.branch,
+                                    e
+                                );
+                                None
+                            }
+                        };
+                        let ci_status = ci_status.with_required(required.as_deref());
+                        let status = MergeableStatus::of_ci(&ci_status);
+                        log::info!(
+                            "CI status fetched for PR #{}: {:?} (passed: {}, failed: {} of which optional {}, pending: {})",
+                            pr_number,
+                            status,
+                            ci_status.passed,
+                            ci_status.failed,
+                            ci_status.optional_failed,
+                            ci_status.pending
+                        );
+                        let budget = ci_durations.and_then(|history| {
+                            learn_from_checks(&history, &repo_for_ci, &ci_status.checks)
+                        });
+                        dispatcher.dispatch(Action::PullRequest(
+                            PullRequestAction::BuildStatusUpdated {
+                                repo: repo_for_ci,
+                                pr_number,
+                                status,
+                                budget,
+                                checks: CiChecks::of(&ci_status),
+                            },
+                        ));
+                        Ok(())
+                    });
+
+                // Errors are only logged, the decision is left as Unknown
+                log::info!("Spawning review decision fetch for PR #{}", pr_number);
+                let repo_for_decision = repo.clone();
+                self.github
+                    .task(repo, dispatcher)
+                    .force_refresh(*force_refresh)
+                    .spawn(move |client, dispatcher| async move {
+                        let repo = repo_for_decision;
+                        let decision = client
+                            .fetch_review_decision(&repo.org, &repo.repo, pr_number)
+                            .await
+                            .with_context(|| {
+                                format!("Failed to fetch review decision for PR #{}", pr_number)
+                            })?;
+                        log::info!(
+                            "Review decision fetched for PR #{}: {:?}",
+                            pr_number,
+                            decision
+                        );
+                        dispatcher.dispatch(Action::PullRequest(
+                            PullRequestAction::ReviewDecisionUpdated {
+                                repo,
+                                pr_number,
+                                decision,
+                            },
+                        ));
+                        Ok(())
+                    });
+
+                true // Let action pass through
+            }
+
+            _ => true, // Pass through other actions
+        }
+    }
+}
+
+/// Where to fetch build logs from
+enum BuildLogSource {
+    /// All workflow runs of a commit (a PR's head)
+    HeadSha(String),
+    /// A single workflow run
+    Run(BuildLogRun),
+}
+
+/// A workflow run to fetch the build logs of
+struct BuildLogRun {
+    id: u64,
+    name: String,
+    /// Waiting for a deployment approval, so there are no logs yet
+    waiting: bool,
+    /// Attempt of the run, if known before its jobs are fetched
+    attempt: Option<u64>,
+}
+
+/// Workflow runs of a commit
+async fn fetch_head_sha_runs(
+    octocrab: &Octocrab,
+    owner: &str,
+    repo: &str,
+    head_sha: &str,
+) -> Result<Vec<BuildLogRun>, String> {
+    let url = format!(
+        "/repos/{}/{}/actions/runs?head_sha={}",
+        owner, repo, head_sha
+    );
+
+    #[derive(Debug, serde::Deserialize)]
+    struct WorkflowRunsResponse {
+        workflow_runs: Vec<WorkflowRunData>,
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    struct WorkflowRunData {
+        id: u64,
+        name: String,
+        status: Option<String>,
+        run_attempt: Option<u64>,
+    }
+
+    let workflow_runs: WorkflowRunsResponse = octocrab
+        .get(&url, None::<&()>)
+        .await
+        .map_err(|e| format!("Failed to fetch workflow runs: {}", e))?;
+
+    Ok(workflow_runs
+        .workflow_runs
+        .into_iter()
+        .map(|run| BuildLogRun {
+            id: run.id,
+            waiting: run.status.as_deref() == Some("waiting"),
+            name: run.name,
+            attempt: run.run_attempt,
+        })
+        .collect())
+}
+
+/// Environments a waiting workflow run needs an approval for
+async fn fetch_pending_environments(
+    octocrab: &Octocrab,
+    owner: &str,
+    repo: &str,
+    run_id: u64,
+) -> Result<Vec<String>, String> {
+    let url = format!(
+        "/repos/{}/{}/actions/runs/{}/pending_deployments",
+        owner, repo, run_id
+    );
+
+    #[derive(Debug, serde::Deserialize)]
+    struct PendingDeployment {
+        environment: Environment,
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Environment {
+        name: String,
+    }
+
+    let deployments: Vec<PendingDeployment> = octocrab
+        .get(&url, None::<&()>)
+        .await
+        .map_err(|e| format!("Failed to fetch pending deployments: {}", e))?;
+
+    Ok(deployments
+        .into_iter()
+        .map(|deployment| deployment.environment.name)
+        .collect())
+}
+
+/// Workflow name of a run waiting for approval, naming the environments if known
+fn waiting_workflow_name(workflow_name: &str, environments: &[String]) -> String {
+    if environments.is_empty() {
+        format!("{} ⏸ waiting for approval", workflow_name)
+    } else {
+        format!(
+            "{} ⏸ waiting for approval: {}",
+            workflow_name,
+            environments.join(", ")
+        )
+    }
+}
+
+/// Number of workflow runs whose logs are downloaded and parsed at once
+const BUILD_LOG_CONCURRENCY: usize = 3;
+
+/// Build logs of one workflow run, ready for display
+struct LoadedWorkflow {
+    workflow: Arc<gh_actions_log_parser::WorkflowNode>,
+    job_metadata: Vec<BuildLogJobMetadata>,
+    /// Set if the run is finished
+    parsed_run: Option<ParsedRun>,
+}
+
+impl LoadedWorkflow {
+    /// A workflow node without jobs, e.g. for a run without logs
+    fn placeholder(name: String, has_failures: bool) -> Self {
+        Self {
+            workflow: Arc::new(gh_actions_log_parser::WorkflowNode {
+                name,
+                jobs: Vec::new(),
+                has_failures,
+                total_errors: 0,
+            }),
+            job_metadata: Vec::new(),
+            parsed_run: None,
+        }
+    }
+}
+
+/// Fetches and parses the build logs of workflow runs of a repository
+struct BuildLogFetcher {
+    octocrab: Arc<Octocrab>,
+    /// Key of the client's bucket in the request budget
+    budget_host: String,
+    repo: Repository,
+    /// Runs parsed before, reused instead of downloaded again
+    parsed_runs: Vec<ParsedRun>,
+    /// Log archives of finished runs on disk (None if there is no cache directory)
+    cache: Option<RunLogCache>,
+}
+
+impl BuildLogFetcher {
+    /// Wait for the request budget before a request
+    async fn acquire(&self) {
+        RequestBudget::global()
+            .acquire(&self.budget_host, RequestCategory::Actions)
+            .await;
+    }
+
+    /// Fetch and parse the build logs of a workflow run
+    ///
+    /// Finished run attempts are taken from `parsed_runs` if present, else
+    /// parsed from their archive in the run log cache, and only downloaded
+    /// as a last resort. A run whose logs can't be loaded becomes an error node.
+    async fn fetch(&self, run: BuildLogRun) -> LoadedWorkflow {
+        let (owner, repo) = (self.repo.org.as_str(), self.repo.repo.as_str());
+        let (run_id, workflow_name) = (run.id, run.name);
+
+        // A waiting run has no logs yet, only show what it waits for
+        if run.waiting {
+            self.acquire().await;
+            let environments = fetch_pending_environments(&self.octocrab, owner, repo, run_id)
+                .await
+                .unwrap_or_else(|e| {
+                    log::warn!("{} (run {})", e, run_id);
+                    Vec::new()
+                });
+            return LoadedWorkflow::placeholder(
+                waiting_workflow_name(&workflow_name, &environments),
+                false,
+            );
+        }
+
+        // Fetch jobs for this workflow run
+        let jobs_url = format!("/repos/{}/{}/actions/runs/{}/jobs", owner, repo, run_id);
+
+        #[derive(Debug, serde::Deserialize)]
+        struct JobsResponse {
+            jobs: Vec<WorkflowJob>,
+        }
+
+        #[derive(Debug, serde::Deserialize)]
+        struct WorkflowJob {
+            name: String,
+            html_url: String,
+            conclusion: Option<String>,
+            started_at: Option<String>,
+            completed_at: Option<String>,
+            run_attempt: Option<u64>,
+        }
+
+        self.acquire().await;
+        let jobs_response: Result<JobsResponse, _> =
+            self.octocrab.get(&jobs_url, None::<&()>).await;
+        let jobs = match jobs_response {
+            Ok(response) => response.jobs,
+            Err(e) => {
+                log::warn!("Failed to fetch jobs of run {}: {}", run_id, e);
+                Vec::new()
+            }
+        };
+
+        // Logs of a finished attempt won't change anymore
+        let attempt = jobs
+            .first()
+            .and_then(|job| job.run_attempt)
+            .or(run.attempt)
+            .unwrap_or(1);
+        let finished = !jobs.is_empty() && jobs.iter().all(|job| job.conclusion.is_some());
+
+        if finished {
+            if let Some(parsed) = self
+                .parsed_runs
+                .iter()
+                .find(|parsed| parsed.is_attempt(&self.repo, run_id, attempt))
+            {
+                log::debug!("Reusing parsed logs of run {}", run_id);
+                return LoadedWorkflow {
+                    workflow: Arc::clone(&parsed.workflow),
+                    job_metadata: parsed.job_metadata.clone(),
+                    parsed_run: Some(parsed.clone()),
+                };
+            }
+        }
+
+        let download = || async {
+            self.acquire().await;
+            // Convert u64 to RunId using .into()
+            self.octocrab
+                .actions()
+                .download_workflow_run_logs(owner, repo, run_id.into())
+                .await
+                .map(|data| data.to_vec())
+        };
+        let key = RunLogKey {
+            host: self.repo.effective_host(),
+            org: owner,
+            repo,
+            run_id,
+            attempt,
+        };
+        let downloaded = match &self.cache {
+            // Logs of a finished attempt won't change, so they are kept on disk
+            Some(cache) if finished => cache.get_or_fetch(&key, download).await,
+            _ => download().await,
+        };
+        let log_data = match downloaded {
+            Ok(data) => data,
+            Err(e) => {
+                log::warn!(
+                    "Failed to download workflow logs for {} (id: {}): {}",
+                    workflow_name,
+                    run_id,
+                    e
+                );
+                return LoadedWorkflow::placeholder(
+                    format!("{} ⚠ failed to download logs: {}", workflow_name, e),
+                    true,
+                );
+            }
+        };
+
+        // Unzipping and parsing is CPU-bound, keep it off the async workers
+        let name = workflow_name.clone();
+        let parsed = tokio::task::spawn_blocking(move || {
+            let parsed_log =
+                gh_actions_log_parser::parse_workflow_logs(&log_data).map_err(|e| e.to_string())?;
+
+            // Build workflow node from parsed log
+            let mut workflow_node = gh_actions_log_parser::WorkflowNode {
+                name: name.clone(),
+                jobs: Vec::new(),
+                has_failures: false,
+                total_errors: 0,
+            };
+            let mut job_metadata = Vec::new();
+
+            // Process each job from the parsed log
+            for job_log in parsed_log.jobs {
+                // Find matching GitHub API job by name
+                let github_job = jobs.iter().find(|j| job_log.name.contains(&j.name));
+
+                // Count errors in this job
+                let error_count = count_errors_in_job(&job_log);
+
+                // Parse job status
+                let status = if let Some(job) = github_job {
+                    conclusion_to_build_log_status(job.conclusion.as_deref())
+                } else if error_count > 0 {
+                    BuildLogJobStatus::Failure
+                } else {
+                    BuildLogJobStatus::Success
+                };
+
+                // Calculate duration
+                let duration = github_job.and_then(|job| {
+                    if let (Some(ref started), Some(ref completed)) =
+                        (&job.started_at, &job.completed_at)
+                    {
+                        parse_duration(started, completed)
+                    } else {
+                        None
+                    }
+                });
+
+                // Build job metadata
+                job_metadata.push(BuildLogJobMetadata {
+                    name: job_log.name.clone(),
+                    workflow_name: name.clone(),
+                    status,
+                    error_count,
+                    duration,
+                    html_url: github_job.map(|j| j.html_url.clone()).unwrap_or_default(),
+                });
+
+                // Convert job_log to JobNode using the parser's built-in function
+                let job_node = gh_actions_log_parser::job_log_to_tree(job_log);
+                workflow_node.total_errors += job_node.error_count;
+                if job_node.error_count > 0 {
+                    workflow_node.has_failures = true;
+                }
+                workflow_node.jobs.push(job_node);
+            }
+
+            Ok::<_, String>((workflow_node, job_metadata))
+        })
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|parsed| parsed);
+        let (workflow_node, job_metadata) = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                log::warn!("Failed to parse workflow logs for {}: {}", workflow_name, e);
+                return LoadedWorkflow::placeholder(
+                    format!("{} ⚠ failed to parse logs: {}", workflow_name, e),
+                    true,
+                );
+            }
+        };
+
+        let workflow = Arc::new(workflow_node);
+        let parsed_run = finished.then(|| {
+            ParsedRun::new(
+                self.repo.clone(),
+                run_id,
+                attempt,
+                Arc::clone(&workflow),
+                job_metadata.clone(),
+            )
+        });
+        LoadedWorkflow {
+            workflow,
+            job_metadata,
+            parsed_run,
+        }
+    }
+}
+
+/// Count errors in a job log
+fn count_errors_in_job(job_log: &gh_actions_log_parser::JobLog) -> usize {
+    job_log
+        .lines
+        .iter()
+        .filter(|line| {
+            if let Some(ref cmd) = line.command {
+                matches!(cmd, gh_actions_log_parser::WorkflowCommand::Error { .. })
+            } else {
+                line.content.to_lowercase().contains("error:")
+            }
+        })
+        .count()
+}
+
+/// Convert GitHub job conclusion to BuildLogJobStatus
+fn conclusion_to_build_log_status(conclusion: Option<&str>) -> BuildLogJobStatus {
+    match conclusion {
+        Some("success") => BuildLogJobStatus::Success,
+        Some("failure") => BuildLogJobStatus::Failure,
+        Some("cancelled") => BuildLogJobStatus::Cancelled,
+        Some("skipped") => BuildLogJobStatus::Skipped,
+        None => BuildLogJobStatus::InProgress,
+        _ => BuildLogJobStatus::Unknown,
+    }
+}
+
+/// Parse duration from GitHub API timestamps
+fn parse_duration(started: &str, completed: &str) -> Option<Duration> {
+    use chrono::DateTime;
+    if let (Ok(start), Ok(end)) = (
+        DateTime::parse_from_rfc3339(started),
+        DateTime::parse_from_rfc3339(completed),
+    ) {
+        let duration = end.signed_duration_since(start);
+        Some(Duration::from_secs(duration.num_seconds().max(0) as u64))
+    } else {
+        None
+    }
+}
+
+/// Learn the durations of passed checks and get the budget of running ones
+///
+/// Saves the history in the background when it learned a new run.
+fn learn_from_checks(
+    history: &Mutex<CiDurations>,
+    repo: &Repository,
+    checks: &[gh_client::CheckResult],
+) -> Option<CiBudget> {
+    let mut history = history.lock().unwrap();
+    let (budget, learned) = learn_ci_budget(&mut history, &repo.id().to_string(), checks);
+    if learned {
+        let snapshot = history.clone();
+        PendingSaves::global().stage("ci-durations", move || {
+            if let Err(e) = snapshot.save() {
+                log::warn!("Failed to save CI duration history: {:#}", e);
+            }
+        });
+        tokio::task::spawn_blocking(|| PendingSaves::global().flush());
+    }
+    budget
+}
+
+/// Dispatch CheckBuildStatus actions for the given PRs
+///
+/// `force_refresh` skips cache reads, for checks derived from a forced refresh.
+pub fn dispatch_ci_status_checks(
+    repo: &Repository,
+    prs: &[Pr],
+    dispatcher: &Dispatcher,
+    force_refresh: bool,
+) {
+    for pr in prs {
+        dispatcher.dispatch(Action::PullRequest(PullRequestAction::CheckBuildStatus {
+            repo: repo.clone(),
+            pr_number: pr.number as u64,
+            head_sha: pr.head_sha.clone(),
+            force_refresh,
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_waiting_workflow_name() {
+        assert_eq!(
+            waiting_workflow_name("Deploy", &[]),
+            "Deploy ⏸ waiting for approval"
+        );
+        assert_eq!(
+            waiting_workflow_name("Deploy", &["staging".to_string(), "prod".to_string()]),
+            "Deploy ⏸ waiting for approval: staging, prod"
+        );
+    }
+
+    mod dispatch {
+        use super::*;
+        use crate::domain_models::ReviewDecision;
+        use crate::middleware::test_clients::{
+            green, repo, run_middleware, state, status_messages, MockGitHubClient,
+        };
+        use crate::state::StatusKind;
+        use gh_client::types::{CheckConclusion, CheckRunStatus, CiStatus};
+
+        /// Handle `action` and return every action dispatched until all
+        /// spawned operations finished
+        fn run(client: MockGitHubClient, action: Action) -> (Vec<Action>, Arc<MockGitHubClient>) {
+            run_in(&state(), client, action)
+        }
+
+        /// [`run`] with the given state
+        fn run_in(
+            state: &AppState,
+            client: MockGitHubClient,
+            action: Action,
+        ) -> (Vec<Action>, Arc<MockGitHubClient>) {
+            run_middleware(CiMiddleware::new, state, client, action)
+        }
+
+        #[test]
+        fn test_check_build_status_updates_status_and_review() {
+            let client = MockGitHubClient {
+                ci_status: Some(green()),
+                review_decision: Some(ReviewDecision::Approved),
+                ..Default::default()
+            };
+            let action = Action::PullRequest(PullRequestAction::CheckBuildStatus {
+                repo: repo(),
+                pr_number: 7,
+                head_sha: "abc".to_string(),
+                force_refresh: true,
+            });
+            let (actions, client) = run(client, action);
+
+            assert!(actions.iter().any(|a| matches!(
+                a,
+                Action::PullRequest(PullRequestAction::BuildStatusUpdated {
+                    pr_number: 7,
+                    status: MergeableStatus::Ready,
+                    ..
+                })
+            )));
+            assert!(actions.iter().any(|a| matches!(
+                a,
+                Action::PullRequest(PullRequestAction::ReviewDecisionUpdated {
+                    pr_number: 7,
+                    decision: ReviewDecision::Approved,
+                    ..
+                })
+            )));
+            let mut calls = client.calls();
+            calls.sort();
+            assert_eq!(
+                calls,
+                vec![
+                    "fetch_ci_status acme/api abc",
+                    "fetch_required_checks acme/api main",
+                    "fetch_review_decision acme/api#7"
+                ]
+            );
+        }
+
+        #[test]
+        fn test_rerun_restarts_only_failed_runs() {
+            use crate::domain_models::Pr;
+            use crate::state::RepositoryData;
+            use gh_client::{WorkflowRun, WorkflowRunConclusion, WorkflowRunStatus};
+
+            let run = |id, name: &str, conclusion| WorkflowRun {
+                id,
+                name: name.to_string(),
+                status: WorkflowRunStatus::Completed,
+                conclusion: Some(conclusion),
+                head_sha: "abc".to_string(),
+                head_branch: None,
+                event: "pull_request".to_string(),
+                html_url: String::new(),
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+                run_attempt: 1,
+            };
+            let mut state = state();
+            state.main_view.repo_data.insert(
+                0,
+                RepositoryData {
+                    prs: vec![Pr::new(7, "Fix", "alice", "abc")],
+                    ..Default::default()
+                },
+            );
+            let client = MockGitHubClient {
+                workflow_runs: vec![
+                    run(1, "Lint", WorkflowRunConclusion::Success),
+                    run(2, "Test", WorkflowRunConclusion::Failure),
+                ],
+                ..Default::default()
+            };
+            let action = Action::PullRequest(PullRequestAction::RerunFailedJobs);
+            let (actions, client) = run_in(&state, client, action);
+
+            assert_eq!(
+                status_messages(&actions),
+                vec![(
+                    StatusKind::Success,
+                    "Rerun triggered for Test (PR #7)".to_string()
+                )]
+            );
+            assert_eq!(
+                client.calls(),
+                vec![
+                    "fetch_workflow_runs acme/api abc",
+                    "rerun_failed_jobs acme/api 2"
+                ]
+            );
+        }
+
+        #[test]
+        fn test_failing_optional_checks_do_not_fail_the_pr() {
+            let check = |name: &str, conclusion| gh_client::CheckResult {
+                id: 1,
+                name: name.to_string(),
+                status: CheckRunStatus::Completed,
+                conclusion: Some(conclusion),
+                started_at: None,
+                completed_at: None,
+                required: true,
+            };
+            let ci_status = CiStatus::from_results(vec![
+                check("test", CheckConclusion::Success),
+                check("lint", CheckConclusion::Failure),
+            ]);
+            let status_of = |required_checks: Option<Vec<String>>| {
+                let client = MockGitHubClient {
+                    ci_status: Some(ci_status.clone()),
+                    review_decision: Some(ReviewDecision::Approved),
+                    required_checks,
+                    ..Default::default()
+                };
+                let action = Action::PullRequest(PullRequestAction::CheckBuildStatus {
+                    repo: repo(),
+                    pr_number: 7,
+                    head_sha: "abc".to_string(),
+                    force_refresh: false,
+                });
+                run(client, action).0.into_iter().find_map(|a| match a {
+                    Action::PullRequest(PullRequestAction::BuildStatusUpdated {
+                        status,
+                        checks,
+                        ..
+                    }) => Some((status, checks.required_known)),
+                    _ => None,
+                })
+            };
+
+            assert_eq!(
+                status_of(Some(vec!["test".to_string()])),
+                Some((MergeableStatus::OptionalFailed, true))
+            );
+            assert_eq!(
+                status_of(Some(vec!["test".to_string(), "lint".to_string()])),
+                Some((MergeableStatus::BuildFailed, true))
+            );
+            // Unreadable protection treats every check as required
+            assert_eq!(status_of(None), Some((MergeableStatus::BuildFailed, false)));
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! GitHub client factory
+//!
+//! The GitHub middleware asks a factory for the client of a repository
+//! instead of locking the `ClientManager` itself, so its handlers can be
+//! tested against a mock client.
+
+use crate::domain_models::Repository;
+use async_trait::async_trait;
+use gh_client::{CacheMode, ClientManager, GitHubClient};
+use std::sync::Arc;
+use tokio::sync::Mutex as TokioMutex;
+
+/// Hands out the GitHub client of a repository
+#[async_trait]
+pub trait ClientFactory: Send + Sync {
+    /// Client for the repository's host and profile
+    ///
+    /// With `force_refresh`, responses are fetched even when cached (and
+    /// still written to the cache).
+    async fn client(
+        &self,
+        repo: &Repository,
+        force_refresh: bool,
+    ) -> anyhow::Result<Arc<dyn GitHubClient>>;
+
+    /// Whether the repository's client has no token (public data only)
+    async fn is_anonymous(&self, repo: &Repository) -> bool;
+
+    /// Login of the user the repository's client is authenticated as
+    async fn login(&self, repo: &Repository) -> anyhow::Result<String>;
+}
+
+/// Production factory, handing out the clients of the client manager
+pub struct ManagedClientFactory {
+    client_manager: Arc<TokioMutex<ClientManager>>,
+}
+
+impl ManagedClientFactory {
+    pub fn new(client_manager: Arc<TokioMutex<ClientManager>>) -> Self {
+        Self { client_manager }
+    }
+}
+
+#[async_trait]
+impl ClientFactory for ManagedClientFactory {
+    async fn client(
+        &self,
+        repo: &Repository,
+        force_refresh: bool,
+    ) -> anyhow::Result<Arc<dyn GitHubClient>> {
+        let client = self
+            .client_manager
+            .lock()
+            .await
+            .clone_client(repo.host.as_deref(), repo.profile.as_deref())
+            .await?;
+        Ok(if force_refresh {
+            Arc::new(client.with_mode(CacheMode::WriteOnly))
+        } else {
+            Arc::new(client)
+        })
+    }
+
+    async fn is_anonymous(&self, repo: &Repository) -> bool {
+        self.client_manager
+            .lock()
+            .await
+            .is_anonymous(repo.host.as_deref(), repo.profile.as_deref())
+    }
+
+    async fn login(&self, repo: &Repository) -> anyhow::Result<String> {
+        self.client_manager
+            .lock()
+            .await
+            .login(repo.host.as_deref(), repo.profile.as_deref())
+            .await
+    }
+}
@@ -0,0 +1,263 @@
+//! Client Task
+//!
+//! Every GitHub operation of the middlewares has the same frame: get the
+//! repository's client, run some requests on the runtime, keep the status
+//! bar operation and in-flight guard alive until done, and report a failure.
+//! [`ClientTask`] is that frame, so a handler only writes its requests:
+//!
+//! ```ignore
+//! self.github
+//!     .task(&repo, dispatcher)
+//!     .operation(format!("Closing PR #{}", pr_number), "Close")
+//!     .report("Close failed", "Close")
+//!     .spawn(move |client, dispatcher| async move {
+//!         client.close_pull_request(&repo.org, &repo.repo, pr_number).await?;
+//!         dispatcher.dispatch(/* success */);
+//!         Ok(())
+//!     });
+//! ```
+
+use crate::actions::{Action, StatusBarAction};
+use crate::dispatcher::{Dispatcher, InFlightGuard, OperationDispatcher, OperationGuard};
+use crate::domain_models::Repository;
+use crate::middleware::client_factory::ClientFactory;
+use crate::middleware::github_context::GitHubContext;
+use gh_client::{background, GitHubClient};
+use std::future::Future;
+use std::sync::Arc;
+use tokio::runtime::Handle;
+
+/// Turns the error of a failed task into an action
+type FailureAction = Box<dyn FnOnce(String) -> Action + Send>;
+
+/// An operation on a repository's client, run in the background
+///
+/// Errors of getting the client and of the operation itself are handled
+/// alike: logged, shown in the status bar with [`report`](Self::report)
+/// and turned into an action with [`on_failure`](Self::on_failure).
+/// Without either they are only logged, for fetches whose result is optional.
+pub struct ClientTask {
+    runtime: Handle,
+    clients: Arc<dyn ClientFactory>,
+    repo: Repository,
+    dispatcher: Dispatcher,
+    force_refresh: bool,
+    background: bool,
+    operation: Option<OperationGuard>,
+    in_flight: Option<InFlightGuard>,
+    /// Status bar message prefix and source of a failure
+    report: Option<(String, &'static str)>,
+    on_failure: Option<FailureAction>,
+}
+
+impl ClientTask {
+    pub fn new(github: &GitHubContext, repo: &Repository, dispatcher: &Dispatcher) -> Self {
+        Self {
+            runtime: github.runtime.clone(),
+            clients: Arc::clone(&github.clients),
+            repo: repo.clone(),
+            dispatcher: dispatcher.clone(),
+            force_refresh: false,
+            background: false,
+            operation: None,
+            in_flight: None,
+            report: None,
+            on_failure: None,
+        }
+    }
+
+    /// Fetch responses even when cached (and still write them to the cache)
+    pub fn force_refresh(mut self, force_refresh: bool) -> Self {
+        self.force_refresh = force_refresh;
+        self
+    }
+
+    /// Run in the background request lane, behind user-initiated requests
+    pub fn background(mut self) -> Self {
+        self.background = true;
+        self
+    }
+
+    /// Show the task as a running operation in the status bar
+    ///
+    /// Started right away, so the operation shows while the task is queued.
+    pub fn operation(mut self, label: impl Into<String>, source: &'static str) -> Self {
+        self.operation = Some(self.dispatcher.start_operation(label, source));
+        self
+    }
+
+    /// Keep a PR operation in flight until the task ends
+    pub fn in_flight(mut self, guard: InFlightGuard) -> Self {
+        self.in_flight = Some(guard);
+        self
+    }
+
+    /// Show a failure as "`context`: error" in the status bar
+    pub fn report(mut self, context: impl Into<String>, source: &'static str) -> Self {
+        self.report = Some((context.into(), source));
+        self
+    }
+
+    /// Dispatch the action made from the error message of a failure, after
+    /// the status bar message if any
+    pub fn on_failure(mut self, action: impl FnOnce(String) -> Action + Send + 'static) -> Self {
+        self.on_failure = Some(Box::new(action));
+        self
+    }
+
+    /// Get the client and run `run` with it on the runtime
+    pub fn spawn<F, Fut>(self, run: F)
+    where
+        F: FnOnce(Arc<dyn GitHubClient>, Dispatcher) -> Fut + Send + 'static,
+        Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        let Self {
+            runtime,
+            clients,
+            repo,
+            dispatcher,
+            force_refresh,
+            background: in_background,
+            operation,
+            in_flight,
+            report,
+            on_failure,
+        } = self;
+
+        let task = async move {
+            let _in_flight = in_flight;
+            let _operation = operation;
+            let result = match clients.client(&repo, force_refresh).await {
+                Ok(client) => run(client, dispatcher.clone()).await,
+                Err(e) => Err(e),
+            };
+            let Err(e) = result else {
+                return;
+            };
+
+            match report {
+                Some((context, source)) => {
+                    log::error!("{}: {:#}", context, e);
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::error(
+                        format!("{}: {}", context, e),
+                        source,
+                    )));
+                }
+                None => log::warn!("{}/{}: {:#}", repo.org, repo.repo, e),
+            }
+            if let Some(on_failure) = on_failure {
+                dispatcher.dispatch(on_failure(e.to_string()));
+            }
+        };
+
+        if in_background {
+            runtime.spawn(background(task));
+        } else {
+            runtime.spawn(task);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::PullRequestAction;
+    use crate::domain_models::OperationType;
+    use crate::middleware::test_clients::{
+        settle, status_messages, test_runtime, MockClientFactory, MockGitHubClient,
+    };
+    use crate::state::StatusKind;
+    use std::sync::mpsc::channel;
+
+    fn repo() -> Repository {
+        Repository::new("acme", "api", "main")
+    }
+
+    /// Spawn the task built by `build` and return every dispatched action
+    fn run(
+        client: MockGitHubClient,
+        build: impl FnOnce(&GitHubContext, &Dispatcher) -> ClientTask,
+        body: impl FnOnce(Arc<dyn GitHubClient>, Dispatcher) -> anyhow::Result<()> + Send + 'static,
+    ) -> Vec<Action> {
+        let runtime = test_runtime();
+        let github = GitHubContext::with_clients(
+            Arc::new(MockClientFactory::new(client)),
+            runtime.handle().clone(),
+        );
+        let (tx, rx) = channel();
+        build(&github, &Dispatcher::new(tx))
+            .spawn(move |client, dispatcher| async move { body(client, dispatcher) });
+        settle(&runtime);
+        rx.try_iter().collect()
+    }
+
+    #[test]
+    fn test_success_ends_the_operation_after_the_body() {
+        let actions = run(
+            MockGitHubClient::default(),
+            |github, dispatcher| {
+                github
+                    .task(&repo(), dispatcher)
+                    .operation("Working", "Test")
+            },
+            |_, dispatcher| {
+                dispatcher.dispatch(Action::PullRequest(PullRequestAction::Refresh));
+                Ok(())
+            },
+        );
+        assert!(matches!(
+            actions.as_slice(),
+            [
+                Action::StatusBar(StatusBarAction::OperationStarted { .. }),
+                Action::PullRequest(PullRequestAction::Refresh),
+                Action::StatusBar(StatusBarAction::OperationFinished { .. }),
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_failures_are_reported_then_dispatched() {
+        let actions = run(
+            MockGitHubClient::default(),
+            |github, dispatcher| {
+                let in_flight = github
+                    .in_flight
+                    .begin(&repo(), 7, OperationType::Close, dispatcher)
+                    .unwrap();
+                github
+                    .task(&repo(), dispatcher)
+                    .in_flight(in_flight)
+                    .report("Close failed", "Close")
+                    .on_failure(|error| {
+                        Action::PullRequest(PullRequestAction::LoadError {
+                            repo: repo(),
+                            error,
+                            status: None,
+                        })
+                    })
+            },
+            |_, _| Err(anyhow::anyhow!("Forbidden")),
+        );
+        assert_eq!(
+            status_messages(&actions),
+            vec![(StatusKind::Error, "Close failed: Forbidden".to_string())]
+        );
+        assert!(matches!(
+            &actions[1..],
+            [
+                Action::PullRequest(PullRequestAction::LoadError { error, .. }),
+                Action::PullRequest(PullRequestAction::OperationFinished { pr_number: 7, .. }),
+            ] if error == "Forbidden"
+        ));
+    }
+
+    #[test]
+    fn test_unreported_failures_are_only_logged() {
+        let actions = run(
+            MockGitHubClient::default(),
+            |github, dispatcher| github.task(&repo(), dispatcher).background(),
+            |_, _| Err(anyhow::anyhow!("Not Found")),
+        );
+        assert!(actions.is_empty());
+    }
+}
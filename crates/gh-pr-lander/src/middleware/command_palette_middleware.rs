@@ -4,7 +4,10 @@
 //! Text input and navigation are handled via view translation (translate_text_input/translate_navigation).
 
 use crate::actions::{Action, CommandPaletteAction};
-use crate::commands::{filter_commands, get_issue_commands, get_palette_commands_with_hints};
+use crate::commands::{
+    filter_commands, get_issue_commands, get_palette_commands_with_hints, get_profile_commands,
+    get_saved_filter_commands,
+};
 use crate::dispatcher::Dispatcher;
 use crate::middleware::Middleware;
 use crate::state::AppState;
@@ -70,12 +73,14 @@ impl Default for CommandPaletteMiddleware {
     }
 }
 
-impl Middleware for CommandPaletteMiddleware {
+impl Middleware<Action, AppState> for CommandPaletteMiddleware {
     fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
         // Handle command execution - dispatch the selected command's action
         if let Action::CommandPalette(CommandPaletteAction::Execute) = action {
             // Get static commands
             let mut all_commands = get_palette_commands_with_hints(&state.keymap);
+            all_commands.extend(get_saved_filter_commands(&state.main_view.saved_filters));
+            all_commands.extend(get_profile_commands(state.main_view.profile.as_ref()));
 
             // Add dynamic issue commands based on selected PRs and repo context
             let pr_texts = Self::get_selected_pr_texts(state);
@@ -2,18 +2,28 @@
 //!
 //! Handles the Confirm action by extracting the intent and message from state,
 //! then dispatching the appropriate PR action.
+//!
+//! Only the first Confirm of a popup is handled: a second Enter pressed
+//! before the popup closed would otherwise run the operation twice.
 
-use crate::actions::{Action, ConfirmationPopupAction, PullRequestAction};
+use crate::actions::{
+    Action, ConfirmationPopupAction, DebugConsoleAction, GlobalAction, PullRequestAction,
+    ReleaseNotesAction, RepositoryAction, StatusBarAction,
+};
 use crate::dispatcher::Dispatcher;
+use crate::domain_models::SinceRef;
 use crate::middleware::Middleware;
 use crate::state::{AppState, ConfirmationIntent};
 
 /// Middleware that handles confirmation popup action dispatching
-pub struct ConfirmationPopupMiddleware;
+pub struct ConfirmationPopupMiddleware {
+    /// The shown popup was confirmed already (reset by the next Show)
+    confirmed: bool,
+}
 
 impl ConfirmationPopupMiddleware {
     pub fn new() -> Self {
-        Self
+        Self { confirmed: false }
     }
 }
 
@@ -23,20 +33,49 @@ impl Default for ConfirmationPopupMiddleware {
     }
 }
 
-impl Middleware for ConfirmationPopupMiddleware {
+impl Middleware<Action, AppState> for ConfirmationPopupMiddleware {
     fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        if let Action::ConfirmationPopup(ConfirmationPopupAction::Show { .. }) = action {
+            self.confirmed = false;
+            return true;
+        }
+
         // Handle Confirm action - dispatch the appropriate PR action based on intent
         if let Action::ConfirmationPopup(ConfirmationPopupAction::Confirm) = action {
+            if self.confirmed {
+                log::debug!("Ignoring repeated confirm of the closing confirmation popup");
+                return false;
+            }
             if let Some(popup) = &state.confirmation_popup {
+                // Release notes start at a tag or date, the popup stays without one
+                let since = SinceRef::parse(&popup.input_value);
+                if matches!(popup.intent, ConfirmationIntent::ReleaseNotes { .. })
+                    && since.is_none()
+                {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        "Enter a tag or a date (YYYY-MM-DD)",
+                        "Release Notes",
+                    )));
+                    return false;
+                }
+                self.confirmed = true;
                 let message = popup.input_value.clone();
                 let pr_action = match &popup.intent {
+                    ConfirmationIntent::Merge {
+                        pr_numbers,
+                        delete_branch,
+                    } => Action::PullRequest(PullRequestAction::MergePrs {
+                        pr_numbers: pr_numbers.clone(),
+                        delete_branch: *delete_branch,
+                    }),
                     ConfirmationIntent::Approve { pr_numbers } => {
                         Action::PullRequest(PullRequestAction::ApproveWithMessage {
                             pr_numbers: pr_numbers.clone(),
                             message,
                         })
                     }
-                    ConfirmationIntent::Comment { pr_numbers } => {
+                    ConfirmationIntent::Comment { pr_numbers }
+                    | ConfirmationIntent::RetryComment { pr_numbers, .. } => {
                         Action::PullRequest(PullRequestAction::CommentOnPr {
                             pr_numbers: pr_numbers.clone(),
                             message,
@@ -54,6 +93,37 @@ impl Middleware for ConfirmationPopupMiddleware {
                             message,
                         })
                     }
+                    ConfirmationIntent::EditNote { pr_number } => {
+                        Action::PullRequest(PullRequestAction::SaveNote {
+                            pr_number: *pr_number,
+                            text: message,
+                        })
+                    }
+                    ConfirmationIntent::MergeBotUpdates { pr_numbers, .. } => {
+                        Action::PullRequest(PullRequestAction::MergePrs {
+                            pr_numbers: pr_numbers.clone(),
+                            delete_branch: false,
+                        })
+                    }
+                    ConfirmationIntent::SetLogLevel => {
+                        Action::DebugConsole(DebugConsoleAction::SetLogLevel(message))
+                    }
+                    ConfirmationIntent::RunPolicy { repo, steps, .. } => {
+                        Action::PullRequest(PullRequestAction::RunPolicy {
+                            repo: repo.as_ref().clone(),
+                            steps: steps.clone(),
+                        })
+                    }
+                    ConfirmationIntent::RemoveRepository { repo, .. } => Action::Repository(
+                        RepositoryAction::RemoveRepository(repo.as_ref().clone()),
+                    ),
+                    ConfirmationIntent::Quit { .. } => Action::Global(GlobalAction::Quit),
+                    ConfirmationIntent::ReleaseNotes { repo } => {
+                        Action::ReleaseNotes(ReleaseNotesAction::Generate {
+                            repo: repo.as_ref().clone(),
+                            since: since.unwrap_or(SinceRef::Tag(message)),
+                        })
+                    }
                 };
 
                 log::debug!(
@@ -75,3 +145,56 @@ impl Middleware for ConfirmationPopupMiddleware {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::ConfirmationPopupState;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_repeated_confirm_merges_once() {
+        let (tx, rx) = mpsc::channel();
+        let dispatcher = Dispatcher::new(tx);
+        let mut middleware = ConfirmationPopupMiddleware::new();
+        let intent = ConfirmationIntent::Merge {
+            pr_numbers: vec![123],
+            delete_branch: false,
+        };
+        let state = AppState {
+            confirmation_popup: Some(ConfirmationPopupState::new(
+                intent.clone(),
+                String::new(),
+                "org/repo".to_string(),
+            )),
+            ..AppState::default()
+        };
+        let confirm = Action::ConfirmationPopup(ConfirmationPopupAction::Confirm);
+        let merges = |rx: &mpsc::Receiver<Action>| {
+            rx.try_iter()
+                .filter(|action| {
+                    matches!(
+                        action,
+                        Action::PullRequest(PullRequestAction::MergePrs { .. })
+                    )
+                })
+                .count()
+        };
+
+        // Both Enters are handled before the popup closed
+        middleware.handle(&confirm, &state, &dispatcher);
+        middleware.handle(&confirm, &state, &dispatcher);
+        assert_eq!(merges(&rx), 1);
+
+        // The next popup can be confirmed again
+        let show = Action::ConfirmationPopup(ConfirmationPopupAction::Show {
+            intent,
+            default_message: String::new(),
+            repo_context: "org/repo".to_string(),
+            warnings: Vec::new(),
+        });
+        middleware.handle(&show, &state, &dispatcher);
+        middleware.handle(&confirm, &state, &dispatcher);
+        assert_eq!(merges(&rx), 1);
+    }
+}
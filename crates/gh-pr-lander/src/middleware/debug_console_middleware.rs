@@ -4,21 +4,26 @@
 //! - Initialize reader and start polling thread when debug console is opened
 //! - Polling thread dispatches line updates at regular intervals
 //! - Stop polling thread when console is closed
+//! - Follow the active log file after rotation
+//! - Change the log level at runtime ("Set log level…")
 
-use crate::actions::{Action, DebugConsoleAction, GlobalAction};
+use crate::actions::{
+    Action, ConfirmationPopupAction, DebugConsoleAction, GlobalAction, StatusBarAction,
+};
 use crate::dispatcher::Dispatcher;
 use crate::log_reader::FileLogReader;
+use crate::logger::{self, LogFile, LogFilter};
 use crate::middleware::Middleware;
-use crate::state::AppState;
+use crate::state::{AppState, ConfirmationIntent};
 use crate::views::ViewId;
-use std::path::PathBuf;
+use gh_client::RequestBudget;
 use std::sync::{Arc, Mutex};
 use std::thread::{self, JoinHandle};
 use std::time::Duration;
 
 /// Middleware for managing debug console log reading
 pub struct DebugConsoleMiddleware {
-    log_file: PathBuf,
+    log_file: LogFile,
     /// Shared reader - accessed by both middleware and polling thread
     reader: Arc<Mutex<Option<FileLogReader>>>,
     /// Flag to signal polling thread to stop
@@ -28,7 +33,7 @@ pub struct DebugConsoleMiddleware {
 }
 
 impl DebugConsoleMiddleware {
-    pub fn new(log_file: PathBuf) -> Self {
+    pub fn new(log_file: LogFile) -> Self {
         Self {
             log_file,
             reader: Arc::new(Mutex::new(None)),
@@ -52,7 +57,7 @@ impl DebugConsoleMiddleware {
         {
             let mut reader_guard = self.reader.lock().unwrap();
             if reader_guard.is_none() {
-                let mut reader = FileLogReader::new(self.log_file.clone(), 10_000);
+                let mut reader = FileLogReader::new(self.log_file.path(), 10_000);
                 match reader.read_initial() {
                     Ok(initial_lines) if !initial_lines.is_empty() => {
                         dispatcher.dispatch(Action::DebugConsole(
@@ -74,6 +79,7 @@ impl DebugConsoleMiddleware {
         // Spawn polling thread
         let reader = Arc::clone(&self.reader);
         let polling_active = Arc::clone(&self.polling_active);
+        let log_file = self.log_file.clone();
         let dispatcher = dispatcher.clone();
 
         self.poll_thread = Some(thread::spawn(move || {
@@ -89,7 +95,7 @@ impl DebugConsoleMiddleware {
                 let new_lines = {
                     if let Ok(mut reader_guard) = reader.lock() {
                         if let Some(reader) = reader_guard.as_mut() {
-                            reader.poll_new_lines().ok()
+                            Self::poll_following_rotation(reader, &log_file)
                         } else {
                             None
                         }
@@ -112,6 +118,58 @@ impl DebugConsoleMiddleware {
         }));
     }
 
+    /// New lines of the log, switching to the active file after a rotation
+    fn poll_following_rotation(
+        reader: &mut FileLogReader,
+        log_file: &LogFile,
+    ) -> Option<Vec<String>> {
+        let mut lines = reader.poll_new_lines().ok()?;
+        let active = log_file.path();
+        if reader.path() != active {
+            log::debug!(
+                "Debug console: following rotated log file {}",
+                active.display()
+            );
+            reader.follow(active);
+            lines.extend(reader.poll_new_lines().ok()?);
+        }
+        Some(lines)
+    }
+
+    /// Ask for a new log filter, prefilled with the active one
+    fn edit_log_level(dispatcher: &Dispatcher) {
+        let current = logger::current_filter()
+            .map(|filter| filter.spec())
+            .unwrap_or_default();
+        dispatcher.dispatch(Action::ConfirmationPopup(ConfirmationPopupAction::Show {
+            intent: ConfirmationIntent::SetLogLevel,
+            default_message: current,
+            repo_context: String::new(),
+            warnings: Vec::new(),
+        }));
+    }
+
+    /// Apply a log filter spec ("info,gh_client=debug")
+    fn set_log_level(spec: &str, dispatcher: &Dispatcher) {
+        match LogFilter::parse(spec) {
+            Ok(filter) => {
+                let spec = filter.spec();
+                logger::set_filter(filter);
+                log::info!("Log level set to {}", spec);
+                dispatcher.dispatch(Action::StatusBar(StatusBarAction::success(
+                    format!("Log level set to {}", spec),
+                    "Debug",
+                )));
+            }
+            Err(e) => {
+                dispatcher.dispatch(Action::StatusBar(StatusBarAction::error(
+                    format!("Log level not changed: {}", e),
+                    "Debug",
+                )));
+            }
+        }
+    }
+
     /// Stop the polling thread
     fn stop_polling(&mut self) {
         // Signal thread to stop
@@ -130,7 +188,7 @@ impl Drop for DebugConsoleMiddleware {
     }
 }
 
-impl Middleware for DebugConsoleMiddleware {
+impl Middleware<Action, AppState> for DebugConsoleMiddleware {
     fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
         match action {
             // When debug console is opened, start polling
@@ -142,7 +200,9 @@ impl Middleware for DebugConsoleMiddleware {
             }
 
             // When console is closed, stop polling
-            Action::Global(GlobalAction::Close) if Self::is_console_visible(state) => {
+            Action::Global(GlobalAction::Close | GlobalAction::PopView)
+                if Self::is_console_visible(state) =>
+            {
                 self.stop_polling();
                 true
             }
@@ -158,6 +218,29 @@ impl Middleware for DebugConsoleMiddleware {
                 true
             }
 
+            Action::DebugConsole(DebugConsoleAction::EditLogLevel) => {
+                Self::edit_log_level(dispatcher);
+                false
+            }
+
+            Action::DebugConsole(DebugConsoleAction::SetLogLevel(spec)) => {
+                Self::set_log_level(spec, dispatcher);
+                false
+            }
+
+            Action::DebugConsole(DebugConsoleAction::ShowApiUsage) => {
+                let metrics = RequestBudget::global().metrics();
+                log::info!("GitHub API usage: {}", metrics);
+                for (category, count) in &metrics.total {
+                    log::info!("  {}: {} requests since startup", category.as_str(), count);
+                }
+                dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+                    format!("API usage: {}", metrics),
+                    "Debug",
+                )));
+                false
+            }
+
             _ => true,
         }
     }
@@ -0,0 +1,272 @@
+//! Demo Middleware
+//!
+//! Replaces the GitHub, session and onboarding middleware in `--demo` mode:
+//! - Bootstraps with generated repositories instead of the recent repositories file
+//! - Answers PR loads, diff, build log and workflow run requests with generated fixtures
+//! - Never touches the network or the user's session files
+
+use crate::actions::{
+    Action, ActionsViewAction, BootstrapAction, BuildLogAction, DiffViewerAction, GlobalAction,
+    PullRequestAction, RepositoryAction, StatusBarAction,
+};
+use crate::demo::{fixtures, DemoOptions};
+use crate::dispatcher::Dispatcher;
+use crate::domain_models::{Pr, Repository};
+use crate::middleware::Middleware;
+use crate::state::{
+    AppState, BootstrapStage, BuildLogContext, BuildLogJobMetadata, BuildLogJobStatus,
+    BuildLogPrContext, ACTIONS_VIEW_RUN_LIMIT,
+};
+use crate::views::BuildLogView;
+use std::sync::Arc;
+
+/// Number of jobs the generated build log is split into
+const DEMO_JOBS: usize = 4;
+
+/// Middleware serving generated data
+pub struct DemoMiddleware {
+    options: DemoOptions,
+}
+
+impl DemoMiddleware {
+    pub fn new(options: DemoOptions) -> Self {
+        Self { options }
+    }
+
+    fn dispatch_prs(&self, repo: &Repository, dispatcher: &Dispatcher) {
+        let prs = fixtures::pull_requests(repo, self.options.prs_per_repo, self.options.seed);
+        dispatcher.dispatch(Action::PullRequest(PullRequestAction::Loaded {
+            repo: repo.clone(),
+            prs,
+            from_cache: None,
+        }));
+        dispatcher.dispatch(Action::PullRequest(
+            PullRequestAction::BranchStatusUpdated {
+                repo: repo.clone(),
+                ci: Some(fixtures::branch_ci_state(repo, self.options.seed)),
+            },
+        ));
+        dispatcher.dispatch(Action::PullRequest(PullRequestAction::PulseUpdated {
+            repo: repo.clone(),
+            pulse: fixtures::pulse(repo, self.options.seed),
+        }));
+    }
+
+    /// The PR under the cursor of the selected repository
+    fn current_pr(state: &AppState) -> Option<&Pr> {
+        let repo_data = state
+            .main_view
+            .repo_data
+            .get(&state.main_view.selected_repository)?;
+        repo_data.prs.get(repo_data.selected_pr)
+    }
+
+    fn open_diff(&self, state: &AppState, dispatcher: &Dispatcher) {
+        let (Some(repo), Some(pr)) = (
+            state
+                .main_view
+                .repositories
+                .get(state.main_view.selected_repository),
+            Self::current_pr(state),
+        ) else {
+            return;
+        };
+        dispatcher.dispatch(Action::DiffViewer(DiffViewerAction::LoadStart));
+
+        let diff_text = fixtures::unified_diff(self.options.diff_lines, pr.number as u64);
+        match gh_diff_viewer::parse_unified_diff(&diff_text, "base", &pr.head_sha) {
+            Ok(diff) => dispatcher.dispatch(Action::DiffViewer(DiffViewerAction::Loaded {
+                diff,
+                repo: repo.clone(),
+                pr_number: pr.number as u64,
+                pr_title: pr.title.clone(),
+                head_sha: pr.head_sha.clone(),
+                comments: Vec::new(),
+            })),
+            Err(e) => dispatcher.dispatch(Action::DiffViewer(DiffViewerAction::LoadError(
+                e.to_string(),
+            ))),
+        }
+    }
+
+    fn open_build_logs(&self, state: &AppState, dispatcher: &Dispatcher) {
+        let Some(pr) = Self::current_pr(state) else {
+            return;
+        };
+        let context = BuildLogContext::Pr(BuildLogPrContext {
+            number: pr.number,
+            title: pr.title.clone(),
+            author: pr.author.clone(),
+        });
+        self.load_build_logs(context, pr.number as u64, dispatcher);
+    }
+
+    fn open_recent_runs(&self, state: &AppState, dispatcher: &Dispatcher) {
+        let Some(repo) = state
+            .main_view
+            .repositories
+            .get(state.main_view.selected_repository)
+        else {
+            return;
+        };
+        dispatcher.dispatch(Action::ActionsView(ActionsViewAction::LoadStart {
+            repo: repo.clone(),
+            branch: repo.branch.clone(),
+        }));
+        dispatcher.dispatch(Action::ActionsView(ActionsViewAction::Loaded(
+            fixtures::workflow_runs(repo, ACTIONS_VIEW_RUN_LIMIT, self.options.seed),
+        )));
+    }
+
+    /// Generate build logs, the same `log_seed` always gives the same logs
+    fn load_build_logs(&self, context: BuildLogContext, log_seed: u64, dispatcher: &Dispatcher) {
+        dispatcher.dispatch(Action::BuildLog(BuildLogAction::LoadStart));
+        dispatcher.dispatch(Action::Global(GlobalAction::PushView(Box::new(
+            BuildLogView::new(),
+        ))));
+
+        let jobs: Vec<(String, String)> = (0..DEMO_JOBS)
+            .map(|job| {
+                let seed = self.options.seed ^ (log_seed * DEMO_JOBS as u64 + job as u64);
+                (
+                    format!("build ({})", job),
+                    fixtures::workflow_log(self.options.log_lines / DEMO_JOBS, seed),
+                )
+            })
+            .collect();
+        let parsed =
+            match gh_actions_log_parser::parse_workflow_logs(&fixtures::workflow_logs_zip(&jobs)) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    dispatcher.dispatch(Action::BuildLog(BuildLogAction::LoadError(e.to_string())));
+                    return;
+                }
+            };
+
+        let mut workflow = gh_actions_log_parser::WorkflowNode {
+            name: "CI".to_string(),
+            jobs: Vec::new(),
+            has_failures: false,
+            total_errors: 0,
+        };
+        let mut job_metadata = Vec::new();
+        for job_log in parsed.jobs {
+            let job = gh_actions_log_parser::job_log_to_tree(job_log);
+            job_metadata.push(BuildLogJobMetadata {
+                name: job.name.clone(),
+                workflow_name: workflow.name.clone(),
+                status: if job.error_count > 0 {
+                    BuildLogJobStatus::Failure
+                } else {
+                    BuildLogJobStatus::Success
+                },
+                error_count: job.error_count,
+                duration: None,
+                html_url: String::new(),
+            });
+            workflow.total_errors += job.error_count;
+            workflow.has_failures |= job.error_count > 0;
+            workflow.jobs.push(job);
+        }
+
+        dispatcher.dispatch(Action::BuildLog(BuildLogAction::Loaded {
+            workflows: vec![Arc::new(workflow)],
+            job_metadata,
+            context,
+            parsed_runs: Vec::new(),
+        }));
+    }
+}
+
+impl Middleware<Action, AppState> for DemoMiddleware {
+    fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        match action {
+            Action::Bootstrap(BootstrapAction::Start) => {
+                log::info!(
+                    "DemoMiddleware: Starting demo mode (seed {})",
+                    self.options.seed
+                );
+                for stage in [BootstrapStage::ResolveToken, BootstrapStage::InitClient] {
+                    dispatcher.dispatch(Action::Bootstrap(BootstrapAction::StageCompleted(stage)));
+                }
+                dispatcher.dispatch(Action::Bootstrap(BootstrapAction::LoadRecentRepositories));
+                true // Let the config load
+            }
+
+            Action::Bootstrap(BootstrapAction::LoadRecentRepositories) => {
+                dispatcher.dispatch(Action::Bootstrap(BootstrapAction::StageCompleted(
+                    BootstrapStage::LoadRecentRepos,
+                )));
+                for repo in fixtures::repositories(self.options.repos, self.options.seed) {
+                    if !state.main_view.repositories.contains(&repo) {
+                        dispatcher.dispatch(Action::Repository(RepositoryAction::AddRepository(
+                            repo.clone(),
+                        )));
+                    }
+                    self.dispatch_prs(&repo, dispatcher);
+                }
+                dispatcher.dispatch(Action::Bootstrap(BootstrapAction::StageCompleted(
+                    BootstrapStage::LoadFirstRepo,
+                )));
+                dispatcher.dispatch(Action::Bootstrap(
+                    BootstrapAction::LoadRecentRepositoriesDone,
+                ));
+                dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+                    "Demo mode: generated data, no changes are sent to GitHub",
+                    "Demo",
+                )));
+                false // Don't load the user's repositories
+            }
+
+            Action::Repository(
+                RepositoryAction::LoadRepositoryData(repo)
+                | RepositoryAction::PreloadRepositoryData(repo),
+            )
+            | Action::PullRequest(PullRequestAction::LoadStart { repo }) => {
+                self.dispatch_prs(repo, dispatcher);
+                true // Let reducer set the loading state
+            }
+
+            Action::PullRequest(PullRequestAction::Refresh) => {
+                if let Some(repo) = state
+                    .main_view
+                    .repositories
+                    .get(state.main_view.selected_repository)
+                {
+                    self.dispatch_prs(repo, dispatcher);
+                }
+                true
+            }
+
+            Action::DiffViewer(DiffViewerAction::Open) => {
+                self.open_diff(state, dispatcher);
+                true // Let reducer push the diff viewer
+            }
+
+            Action::BuildLog(BuildLogAction::Open) => {
+                self.open_build_logs(state, dispatcher);
+                false // Consume action
+            }
+
+            Action::BuildLog(BuildLogAction::OpenRun { run, .. }) => {
+                self.load_build_logs(BuildLogContext::Run(run.clone()), run.run_id, dispatcher);
+                false // Consume action
+            }
+
+            Action::ActionsView(ActionsViewAction::Open) => {
+                self.open_recent_runs(state, dispatcher);
+                true // Let reducer push the view
+            }
+
+            Action::ActionsView(ActionsViewAction::OpenSelected) => {
+                let view = &state.actions_view;
+                if let (Some(repo), Some(run)) = (view.repo.clone(), view.selected_run_context()) {
+                    dispatcher.dispatch(Action::BuildLog(BuildLogAction::OpenRun { repo, run }));
+                }
+                false // Consume action
+            }
+
+            _ => true,
+        }
+    }
+}
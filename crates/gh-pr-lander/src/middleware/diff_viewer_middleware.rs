@@ -4,8 +4,10 @@
 //! - Close logic when Escape is pressed and there's nothing to cancel/escape from
 //! - Comment submission when Confirm is pressed while editing a comment
 //! - Review submission when Confirm is pressed in the review popup
+//! - Notice when a reloaded PR was updated and its review state is reset
+//! - Blame fetch for the current file while blame is shown
 
-use crate::actions::{Action, DiffViewerAction, GlobalAction};
+use crate::actions::{Action, DiffViewerAction, GlobalAction, StatusBarAction};
 use crate::dispatcher::Dispatcher;
 use crate::middleware::Middleware;
 use crate::state::AppState;
@@ -25,8 +27,16 @@ impl Default for DiffViewerMiddleware {
     }
 }
 
-impl Middleware for DiffViewerMiddleware {
+impl Middleware<Action, AppState> for DiffViewerMiddleware {
     fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        // Queued, so it is handled once this action changed the state
+        // (e.g. selected another file)
+        if let Action::DiffViewer(action) = action {
+            if wants_blame(action, state) {
+                dispatcher.dispatch(Action::DiffViewer(DiffViewerAction::FetchBlame));
+            }
+        }
+
         match action {
             // Handle Escape: close view if nothing to escape from
             Action::DiffViewer(DiffViewerAction::EscapeOrFocusTree) => {
@@ -47,14 +57,14 @@ impl Middleware for DiffViewerMiddleware {
                     }
                     // Already in file tree with nothing to cancel - close the view
                     log::debug!("DiffViewerMiddleware: EscapeOrFocusTree - closing view");
-                    dispatcher.dispatch(Action::Global(GlobalAction::Close));
+                    dispatcher.dispatch(Action::Global(GlobalAction::PopView));
                     false // Consume the action
                 } else {
                     // No inner state - close the view
                     log::debug!(
                         "DiffViewerMiddleware: EscapeOrFocusTree with no inner - closing view"
                     );
-                    dispatcher.dispatch(Action::Global(GlobalAction::Close));
+                    dispatcher.dispatch(Action::Global(GlobalAction::PopView));
                     false
                 }
             }
@@ -77,8 +87,7 @@ impl Middleware for DiffViewerMiddleware {
                                             pr_number,
                                             github_id,
                                             path: editor.file_path.clone(),
-                                            line: editor.position.line,
-                                            side: editor.position.side.as_github_str().to_string(),
+                                            position: editor.position.clone(),
                                         },
                                     ));
                                 }
@@ -93,8 +102,7 @@ impl Middleware for DiffViewerMiddleware {
                                         pr_number,
                                         head_sha: head_sha.clone(),
                                         path: editor.file_path.clone(),
-                                        line: editor.position.line,
-                                        side: editor.position.side.as_github_str().to_string(),
+                                        position: editor.position.clone(),
                                         body: editor.body.clone(),
                                     },
                                 ));
@@ -126,8 +134,51 @@ impl Middleware for DiffViewerMiddleware {
                 true
             }
 
+            // Reloading a PR that was updated meanwhile resets its review state
+            Action::DiffViewer(DiffViewerAction::Loaded {
+                repo,
+                pr_number,
+                head_sha,
+                ..
+            }) => {
+                if let Some(drafts) = state
+                    .diff_viewer
+                    .outdated_review(repo, *pr_number, head_sha)
+                {
+                    let message = match drafts {
+                        0 => "PR was updated, review state reset".to_string(),
+                        1 => "PR was updated, review state reset (kept 1 unsent comment as outdated draft)".to_string(),
+                        n => format!(
+                            "PR was updated, review state reset (kept {} unsent comments as outdated drafts)",
+                            n
+                        ),
+                    };
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        message,
+                        "Diff Viewer",
+                    )));
+                }
+                true
+            }
+
             // All other actions pass through
             _ => true,
         }
     }
 }
+
+/// Whether blame may be due after `action`: it is toggled or shown already
+fn wants_blame(action: &DiffViewerAction, state: &AppState) -> bool {
+    let Some(ref inner) = state.diff_viewer.inner else {
+        return false;
+    };
+    match action {
+        DiffViewerAction::ToggleBlame => true,
+        DiffViewerAction::FetchBlame
+        | DiffViewerAction::BlameLoaded { .. }
+        | DiffViewerAction::BlameFailed { .. } => false,
+        // 'b' toggles blame in normal mode, elsewhere the fetch finds nothing due
+        DiffViewerAction::KeyPress('b') => true,
+        _ => inner.show_blame,
+    }
+}
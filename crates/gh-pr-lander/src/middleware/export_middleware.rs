@@ -0,0 +1,106 @@
+//! Export Middleware
+//!
+//! Writes exports to the current directory, off the middleware thread:
+//! - `BuildLogAction::Export`: the loaded build logs as plain text
+//! - `ReleaseNotesAction::WriteFile`: the drafted release notes as markdown
+
+use crate::actions::{Action, BuildLogAction, ReleaseNotesAction, StatusBarAction};
+use crate::dispatcher::Dispatcher;
+use crate::export;
+use crate::middleware::Middleware;
+use crate::state::AppState;
+use crate::utils::io_worker::IoWorker;
+use std::path::PathBuf;
+
+/// Middleware writing exports
+pub struct ExportMiddleware {
+    io: IoWorker,
+}
+
+impl ExportMiddleware {
+    pub fn new() -> Self {
+        Self {
+            io: IoWorker::spawn("export-io"),
+        }
+    }
+
+    /// Write `text` to `path`, reporting `what` ("Build logs") in the status bar
+    fn write(&self, path: PathBuf, text: String, what: &'static str, dispatcher: &Dispatcher) {
+        let dispatcher = dispatcher.clone();
+        self.io.run(move || {
+            let status = match std::fs::write(&path, text) {
+                Ok(()) => {
+                    log::info!("ExportMiddleware: {} written to {}", what, path.display());
+                    StatusBarAction::success(
+                        format!("{} exported to {}", what, path.display()),
+                        "Export",
+                    )
+                }
+                Err(e) => {
+                    log::error!(
+                        "ExportMiddleware: Failed to write {}: {}",
+                        path.display(),
+                        e
+                    );
+                    StatusBarAction::error(
+                        format!("Failed to write {}: {}", path.display(), e),
+                        "Export",
+                    )
+                }
+            };
+            dispatcher.dispatch(Action::StatusBar(status));
+        });
+    }
+}
+
+impl Default for ExportMiddleware {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Middleware<Action, AppState> for ExportMiddleware {
+    fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        match action {
+            Action::BuildLog(BuildLogAction::Export) => {
+                let repo = state
+                    .main_view
+                    .repositories
+                    .get(state.main_view.selected_repository);
+                let (Some(repo), false) = (repo, state.build_log.workflows.is_empty()) else {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        "No build logs to export",
+                        "Export",
+                    )));
+                    return false;
+                };
+
+                let path = export::build_log_export_path(
+                    &state.app_config.ui,
+                    repo,
+                    &state.build_log,
+                    chrono::Utc::now(),
+                );
+                let text = export::build_log_text(&state.build_log.workflows);
+                self.write(path, text, "Build logs", dispatcher);
+                false
+            }
+
+            Action::ReleaseNotes(ReleaseNotesAction::WriteFile) => {
+                let notes = &state.release_notes;
+                let (Some((repo, since)), Some(markdown)) = (&notes.target, &notes.markdown) else {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        "No release notes to export",
+                        "Export",
+                    )));
+                    return false;
+                };
+                let path = export::release_notes_export_path(repo, since);
+                self.write(path, markdown.clone(), "Release notes", dispatcher);
+                false
+            }
+
+            _ => true,
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! GitHub Context
+//!
+//! The GitHub API operations are split over focused middlewares (client
+//! setup, PR loading, PR mutations, CI, local tools). They share one tokio
+//! runtime, client manager and API cache through a [`GitHubContext`], which
+//! is created once and cloned into each of them.
+
+use crate::actions::{Action, StatusBarAction};
+use crate::audit::{AuditLog, AuditOperation, PendingAudit};
+use crate::dispatcher::{Dispatcher, InFlightOperations};
+use crate::domain_models::{Pr, PrState, Repository};
+use crate::middleware::client_factory::{ClientFactory, ManagedClientFactory};
+use crate::middleware::client_task::ClientTask;
+use crate::state::AppState;
+use gh_client::{ApiCache, ApiMetrics, ClientManager, TokenStore};
+use gh_pr_config::CiDurations;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::{Handle, Runtime};
+use tokio::sync::Mutex as TokioMutex;
+
+/// Runtime, clients and caches shared by the GitHub middlewares
+#[derive(Clone)]
+pub struct GitHubContext {
+    /// Handle of the tokio runtime running the async operations
+    pub runtime: Handle,
+    /// Runtime created by `new()`, kept alive as long as any middleware
+    _owned_runtime: Option<Arc<Runtime>>,
+    /// Client manager for multi-host support (tokens and login)
+    pub client_manager: Arc<TokioMutex<ClientManager>>,
+    /// Hands out the client of a repository for API operations
+    pub clients: Arc<dyn ClientFactory>,
+    /// API cache shared with the clients, read directly for cached PR lists
+    pub cache: Arc<Mutex<ApiCache>>,
+    /// Mutating PR operations running, to drop duplicate requests
+    pub in_flight: InFlightOperations,
+    /// How long checks usually take, learned from CI status fetches (None in
+    /// tests, which must not touch the real history)
+    pub ci_durations: Option<Arc<Mutex<CiDurations>>>,
+    /// Record of every mutating operation (disabled in tests unless set)
+    pub audit_log: AuditLog,
+}
+
+impl GitHubContext {
+    /// Create the runtime, API cache and client manager
+    pub fn new() -> Self {
+        let runtime = Runtime::new().expect("Failed to create tokio runtime");
+
+        // Initialize cache from config path
+        let cache_file =
+            gh_pr_config::api_cache_path().expect("API Cache path should always exist.");
+        let cache = Arc::new(Mutex::new(ApiCache::new(cache_file).unwrap_or_default()));
+
+        // Create client manager with shared cache
+        ApiMetrics::global().attach_cache(Arc::clone(&cache));
+        let mut client_manager = ClientManager::new(Arc::clone(&cache));
+        match gh_pr_config::config_dir() {
+            Ok(dir) => {
+                client_manager = client_manager.with_token_store(Arc::new(TokenStore::new(dir)));
+            }
+            Err(e) => log::warn!("GitHubContext: Token store unavailable: {}", e),
+        }
+
+        let client_manager = Arc::new(TokioMutex::new(client_manager));
+        Self {
+            runtime: runtime.handle().clone(),
+            _owned_runtime: Some(Arc::new(runtime)),
+            clients: Arc::new(ManagedClientFactory::new(Arc::clone(&client_manager))),
+            client_manager,
+            cache,
+            in_flight: InFlightOperations::default(),
+            ci_durations: Some(Arc::new(Mutex::new(CiDurations::load()))),
+            audit_log: AuditLog::open_default(),
+        }
+    }
+
+    /// Create a context on an externally owned runtime, taking its clients
+    /// from `clients`
+    ///
+    /// Spawned operations only run while the runtime is driven, which lets
+    /// tests on a current-thread runtime observe every dispatched action.
+    #[cfg(test)]
+    pub fn with_clients(clients: Arc<dyn ClientFactory>, runtime: Handle) -> Self {
+        let cache = Arc::new(Mutex::new(ApiCache::default()));
+        Self {
+            runtime,
+            _owned_runtime: None,
+            client_manager: Arc::new(TokioMutex::new(ClientManager::new(Arc::clone(&cache)))),
+            clients,
+            cache,
+            in_flight: InFlightOperations::default(),
+            ci_durations: None,
+            audit_log: AuditLog::disabled(),
+        }
+    }
+
+    /// Whether the client of the default host (github.com) is set up
+    ///
+    /// Checked without blocking, a busy client manager counts as not ready.
+    pub fn has_default_client(&self) -> bool {
+        if let Ok(guard) = self.client_manager.try_lock() {
+            guard.has_client(None)
+        } else {
+            false
+        }
+    }
+
+    /// Start the audit log entry of an operation on `repo`, run as the
+    /// login of its host
+    pub fn audit(
+        &self,
+        operation: AuditOperation,
+        repo: &Repository,
+        state: &AppState,
+    ) -> PendingAudit {
+        self.audit_log
+            .begin(operation, repo, state.main_view.login_for(repo))
+    }
+
+    /// Run an operation on the client of `repo`, see [`ClientTask`]
+    pub fn task(&self, repo: &Repository, dispatcher: &Dispatcher) -> ClientTask {
+        ClientTask::new(self, repo, dispatcher)
+    }
+}
+
+impl Default for GitHubContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Target PRs of an operation: the selected PRs, else the cursor PR
+///
+/// Selected PRs count even when they are not loaded (any more).
+pub fn target_prs(state: &AppState) -> Vec<(Repository, usize)> {
+    let repo_idx = state.main_view.selected_repository;
+    let Some(repo) = state.main_view.repositories.get(repo_idx) else {
+        return vec![];
+    };
+    match state.main_view.repo_data.get(&repo_idx) {
+        Some(repo_data) if !repo_data.selected_pr_numbers.is_empty() => repo_data
+            .selected_pr_numbers
+            .iter()
+            .map(|&number| (repo.clone(), number))
+            .collect(),
+        _ => target_pr_details(state)
+            .into_iter()
+            .map(|pr| (repo.clone(), pr.number))
+            .collect(),
+    }
+}
+
+/// Loaded target PRs with their authors
+pub fn target_prs_with_author(state: &AppState) -> Vec<(Repository, usize, String)> {
+    let Some(repo) = state
+        .main_view
+        .repositories
+        .get(state.main_view.selected_repository)
+    else {
+        return vec![];
+    };
+    target_pr_details(state)
+        .into_iter()
+        .map(|pr| (repo.clone(), pr.number, pr.author.clone()))
+        .collect()
+}
+
+/// Loaded PRs among the targets of an operation
+pub fn target_pr_details(state: &AppState) -> Vec<&Pr> {
+    let repo_idx = state.main_view.selected_repository;
+    let Some(repo_data) = state.main_view.repo_data.get(&repo_idx) else {
+        return vec![];
+    };
+    if repo_data.selected_pr_numbers.is_empty() {
+        return repo_data
+            .prs
+            .get(repo_data.selected_pr)
+            .into_iter()
+            .collect();
+    }
+    repo_data
+        .prs
+        .iter()
+        .filter(|pr| repo_data.selected_pr_numbers.contains(&pr.number))
+        .collect()
+}
+
+/// Refuse an operation on closed or merged target PRs, explaining why
+///
+/// Returns true if the operation must not run.
+pub fn reject_inactive_targets(
+    state: &AppState,
+    operation: &str,
+    source: &str,
+    dispatcher: &Dispatcher,
+) -> bool {
+    let Some(inactive) = target_pr_details(state)
+        .into_iter()
+        .find(|pr| pr.state != PrState::Open)
+    else {
+        return false;
+    };
+
+    let reason = match inactive.state {
+        PrState::Merged => "already merged",
+        _ => "closed",
+    };
+    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+        format!(
+            "Cannot {} PR #{}: it is {}",
+            operation, inactive.number, reason
+        ),
+        source,
+    )));
+    true
+}
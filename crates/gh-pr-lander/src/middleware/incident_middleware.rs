@@ -0,0 +1,315 @@
+//! Incident Middleware
+//!
+//! Tells a GitHub incident apart from a broken setup.
+//!
+//! - Asks the status page of the current host once several errors were
+//!   reported in a row, at most every [`CHECK_INTERVAL`]
+//! - During an incident the status bar shows a banner and further errors only
+//!   go to the log (the GitHub middleware pauses background refreshes)
+//! - Asks again on every refresh tick until the incident is resolved
+
+use crate::actions::{Action, Event, StatusBarAction};
+use crate::dispatcher::Dispatcher;
+use crate::middleware::Middleware;
+use crate::state::{AppState, StatusKind};
+use gh_client::{StatusPage, StatusSource, DEFAULT_HOST, GITHUB_STATUS_URL};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::runtime::{Handle, Runtime};
+
+/// Errors in a row before the status page is asked
+pub const ERROR_THRESHOLD: u32 = 3;
+
+/// Minimum time between two status page checks
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(120);
+
+/// Where the status of a host comes from
+enum Sources {
+    /// The host's status page from the config (githubstatus.com for github.com)
+    StatusPages,
+    /// The same source for every host (tests)
+    #[cfg(test)]
+    Fixed(Arc<dyn StatusSource>),
+}
+
+/// Middleware checking the GitHub status page when requests keep failing
+pub struct IncidentMiddleware {
+    runtime: Handle,
+    _owned_runtime: Option<Runtime>,
+    sources: Sources,
+    /// Errors reported since the last success
+    consecutive_errors: u32,
+    /// When the status page was last asked
+    last_check: Option<Instant>,
+}
+
+impl IncidentMiddleware {
+    pub fn new() -> Self {
+        let runtime = Runtime::new().expect("Failed to create tokio runtime");
+        Self {
+            runtime: runtime.handle().clone(),
+            _owned_runtime: Some(runtime),
+            sources: Sources::StatusPages,
+            consecutive_errors: 0,
+            last_check: None,
+        }
+    }
+
+    /// Create a middleware asking `source` on an externally owned runtime
+    #[cfg(test)]
+    pub fn with_source(source: Arc<dyn StatusSource>, runtime: Handle) -> Self {
+        Self {
+            runtime,
+            _owned_runtime: None,
+            sources: Sources::Fixed(source),
+            consecutive_errors: 0,
+            last_check: None,
+        }
+    }
+
+    /// Status source of the current repository's host
+    fn source(&self, state: &AppState) -> Option<Arc<dyn StatusSource>> {
+        match &self.sources {
+            #[cfg(test)]
+            Sources::Fixed(source) => Some(Arc::clone(source)),
+            Sources::StatusPages => {
+                let host = state
+                    .main_view
+                    .repositories
+                    .get(state.main_view.selected_repository)
+                    .map_or(DEFAULT_HOST, |repo| repo.effective_host());
+                let url = match state.app_config.status_pages.get(host) {
+                    Some(url) => url.as_str(),
+                    None if host == DEFAULT_HOST => GITHUB_STATUS_URL,
+                    None => return None,
+                };
+                StatusPage::new(url)
+                    .map_err(|e| log::warn!("IncidentMiddleware: {:#}", e))
+                    .ok()
+                    .map(|page| Arc::new(page) as Arc<dyn StatusSource>)
+            }
+        }
+    }
+
+    /// Ask the status page, unless it was asked recently
+    fn check(&mut self, state: &AppState, dispatcher: &Dispatcher) {
+        if self
+            .last_check
+            .is_some_and(|checked| checked.elapsed() < CHECK_INTERVAL)
+        {
+            return;
+        }
+        let Some(source) = self.source(state) else {
+            return;
+        };
+        self.last_check = Some(Instant::now());
+
+        let dispatcher = dispatcher.clone();
+        self.runtime.spawn(async move {
+            match source.incident().await {
+                Ok(incident) => dispatcher.dispatch(Action::StatusBar(StatusBarAction::Incident(
+                    incident.map(|incident| incident.description),
+                ))),
+                Err(e) => log::warn!("IncidentMiddleware: {:#}", e),
+            }
+        });
+    }
+}
+
+impl Default for IncidentMiddleware {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Middleware<Action, AppState> for IncidentMiddleware {
+    fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        let incident = state.status_bar.incident.as_deref();
+        match action {
+            Action::StatusBar(StatusBarAction::Push { kind, message, .. }) => match kind {
+                StatusKind::Error if incident.is_some() => {
+                    log::warn!("During GitHub incident: {}", message);
+                    false // The banner already explains it
+                }
+                StatusKind::Error => {
+                    self.consecutive_errors += 1;
+                    if self.consecutive_errors >= ERROR_THRESHOLD {
+                        self.check(state, dispatcher);
+                    }
+                    true
+                }
+                StatusKind::Success => {
+                    self.consecutive_errors = 0;
+                    true
+                }
+                _ => true,
+            },
+
+            Action::StatusBar(StatusBarAction::Incident(reported)) => {
+                match (incident, reported) {
+                    (None, Some(description)) => {
+                        log::warn!("GitHub incident: {}", description);
+                        dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                            format!(
+                                "GitHub incident: {} - errors are only logged and background refresh pauses until it is resolved",
+                                description
+                            ),
+                            "Incident",
+                        )));
+                    }
+                    (Some(_), None) => {
+                        log::info!("GitHub incident resolved");
+                        self.consecutive_errors = 0;
+                        dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+                            "GitHub incident resolved, background refresh resumed",
+                            "Incident",
+                        )));
+                    }
+                    _ => {}
+                }
+                true // Let the reducer show or hide the banner
+            }
+
+            Action::Event(Event::RefreshDue) if incident.is_some() => {
+                self.check(state, dispatcher);
+                true
+            }
+
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::test_clients::{settle, status_messages, test_runtime};
+    use async_trait::async_trait;
+    use gh_client::Incident;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::mpsc::channel;
+
+    /// Reports a fixed incident and counts how often it was asked
+    struct FakeStatus {
+        incident: Option<&'static str>,
+        checks: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl StatusSource for FakeStatus {
+        async fn incident(&self) -> anyhow::Result<Option<Incident>> {
+            self.checks.fetch_add(1, Ordering::SeqCst);
+            Ok(self.incident.map(|description| Incident {
+                description: description.to_string(),
+            }))
+        }
+    }
+
+    fn fake(incident: Option<&'static str>) -> Arc<FakeStatus> {
+        Arc::new(FakeStatus {
+            incident,
+            checks: AtomicUsize::new(0),
+        })
+    }
+
+    fn error() -> Action {
+        Action::StatusBar(StatusBarAction::error("Failed to load PRs", "Load"))
+    }
+
+    /// Handle `actions` in order and return every action dispatched
+    fn run(
+        middleware: &mut IncidentMiddleware,
+        state: &AppState,
+        actions: &[Action],
+    ) -> Vec<Action> {
+        let (tx, rx) = channel();
+        let dispatcher = Dispatcher::new(tx);
+        for action in actions {
+            middleware.handle(action, state, &dispatcher);
+        }
+        rx.try_iter().collect()
+    }
+
+    #[test]
+    fn test_status_page_is_asked_after_repeated_errors() {
+        let runtime = test_runtime();
+        let source = fake(Some("degraded performance: API Requests"));
+        let mut middleware = IncidentMiddleware::with_source(
+            Arc::clone(&source) as Arc<dyn StatusSource>,
+            runtime.handle().clone(),
+        );
+        let state = AppState::default();
+
+        run(&mut middleware, &state, &[error(), error()]);
+        settle(&runtime);
+        assert_eq!(source.checks.load(Ordering::SeqCst), 0);
+
+        let (tx, rx) = channel();
+        middleware.handle(&error(), &state, &Dispatcher::new(tx));
+        settle(&runtime);
+        let actions: Vec<Action> = rx.try_iter().collect();
+        assert!(matches!(
+            actions.as_slice(),
+            [Action::StatusBar(StatusBarAction::Incident(Some(description)))]
+                if description == "degraded performance: API Requests"
+        ));
+
+        // Checked results are reused for a while
+        run(&mut middleware, &state, &[error()]);
+        settle(&runtime);
+        assert_eq!(source.checks.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_success_resets_the_error_count() {
+        let runtime = test_runtime();
+        let source = fake(None);
+        let mut middleware = IncidentMiddleware::with_source(
+            Arc::clone(&source) as Arc<dyn StatusSource>,
+            runtime.handle().clone(),
+        );
+        let success = Action::StatusBar(StatusBarAction::success("Loaded", "Load"));
+
+        run(
+            &mut middleware,
+            &AppState::default(),
+            &[error(), error(), success, error(), error()],
+        );
+        settle(&runtime);
+        assert_eq!(source.checks.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_incident_mutes_errors_until_resolved() {
+        let runtime = test_runtime();
+        let mut middleware = IncidentMiddleware::with_source(fake(None), runtime.handle().clone());
+        let mut state = AppState::default();
+
+        let incident = Action::StatusBar(StatusBarAction::Incident(Some("outage".to_string())));
+        let actions = run(&mut middleware, &state, &[incident]);
+        assert_eq!(status_messages(&actions).len(), 1);
+
+        state.status_bar.incident = Some("outage".to_string());
+        let (tx, _rx) = channel();
+        let dispatcher = Dispatcher::new(tx);
+        assert!(!middleware.handle(&error(), &state, &dispatcher));
+        assert!(middleware.handle(
+            &Action::StatusBar(StatusBarAction::warning("Slow", "Load")),
+            &state,
+            &dispatcher
+        ));
+
+        // A refresh tick asks again, the fake reports the incident resolved
+        middleware.handle(&Action::Event(Event::RefreshDue), &state, &dispatcher);
+        settle(&runtime);
+        let resolved = Action::StatusBar(StatusBarAction::Incident(None));
+        let actions = run(&mut middleware, &state, &[resolved]);
+        assert_eq!(
+            status_messages(&actions),
+            vec![(
+                StatusKind::Info,
+                "GitHub incident resolved, background refresh resumed".to_string()
+            )]
+        );
+    }
+}
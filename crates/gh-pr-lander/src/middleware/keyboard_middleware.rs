@@ -3,7 +3,10 @@
 //! This middleware uses a three-layer approach to handle keyboard input:
 //!
 //! ## Layer 1: Priority Keys
-//! Keys that always work regardless of context (Ctrl+C, Esc).
+//! Keys that always work regardless of context (Ctrl+C, Esc), plus the
+//! retry/quit keys of a failed splash screen. Esc does what the top-most view
+//! declares (`View::escape_behavior`): pop it, or forward Escape to it. It
+//! never quits the app.
 //! These are handled directly before any other processing.
 //!
 //! ## Layer 2: Capabilities
@@ -13,19 +16,31 @@
 //! ## Layer 3: Keymap + Gating
 //! Look up keys in the keymap, then check if the active view accepts the action.
 //! This prevents actions from "leaking" to reducers when a different view is active.
+//! Text input views additionally never open or replace views, so a shortcut
+//! typed into a text field can't act on the views below it.
+//!
+//! Pasted text (bracketed paste) skips the layers: it goes to the text input
+//! of the top-most view as a whole, or nowhere.
 
-use crate::actions::{Action, GlobalAction, NavigationAction, TextInputAction};
+use crate::actions::{
+    Action, AuthAction, BootstrapAction, GlobalAction, NavigationAction, PullRequestAction,
+    TextInputAction,
+};
+use crate::command_registry::CommandRegistry;
 use crate::dispatcher::Dispatcher;
+use crate::input_coalescing;
 use crate::keybindings::PendingKey;
 use crate::middleware::Middleware;
 use crate::state::AppState;
+use crate::views::{EscapeBehavior, View, ViewId};
 use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::sync::mpsc;
 use std::time::Instant;
 
 /// KeyboardMiddleware handles keyboard input using a three-layer approach
 ///
 /// # Layers
-/// 1. **Priority keys**: Ctrl+C (quit), Esc (close) - always work
+/// 1. **Priority keys**: Ctrl+C (quit), Esc (pop or forward) - always work
 /// 2. **Capabilities**: TEXT_INPUT routes chars to text input
 /// 3. **Keymap + Gating**: Look up in keymap, check view accepts action
 pub struct KeyboardMiddleware {
@@ -47,26 +62,53 @@ impl KeyboardMiddleware {
         // LAYER 1: Priority keys (always work)
         // ═══════════════════════════════════════════════════════════════════
 
-        // Ctrl+C: Emergency quit - always works
+        // Ctrl+C: quit - always works, with unfinished work a second press
+        // (on the quit confirmation) quits anyway
         if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
-            log::debug!("Layer 1: Ctrl+C - dispatching Quit");
-            dispatcher.dispatch(Action::Global(GlobalAction::Quit));
+            log::debug!("Layer 1: Ctrl+C - dispatching RequestQuit");
+            dispatcher.dispatch(Action::Global(GlobalAction::RequestQuit));
             return false;
         }
 
-        // Esc: Route based on capabilities
-        // - If view accepts text input, send TextInputAction::Escape (view decides: cancel/close)
-        // - Otherwise, dispatch Global(Close) to close the view
+        // Failed bootstrap: r retries, t sets a token, q quits
+        // (splash has no keymap-driven commands)
+        if state.splash.has_failed() && view.is_some_and(|v| v.view_id() == ViewId::Splash) {
+            match key.code {
+                KeyCode::Char('r') => {
+                    log::debug!("Layer 1: r on failed splash - dispatching Bootstrap Retry");
+                    dispatcher.dispatch(Action::Bootstrap(BootstrapAction::Retry));
+                    return false;
+                }
+                KeyCode::Char('t') => {
+                    log::debug!("Layer 1: t on failed splash - opening token input");
+                    dispatcher.dispatch(Action::Auth(AuthAction::OpenTokenInput));
+                    return false;
+                }
+                KeyCode::Char('q') => {
+                    log::debug!("Layer 1: q on failed splash - dispatching Quit");
+                    dispatcher.dispatch(Action::Global(GlobalAction::Quit));
+                    return false;
+                }
+                _ => {}
+            }
+        }
+
+        // Esc: the top-most view decides, see `View::escape_behavior`
         if key.code == KeyCode::Esc {
-            if capabilities.accepts_text_input() {
-                log::debug!("Layer 1: Esc - routing to TextInput::Escape (view has TEXT_INPUT)");
-                dispatcher.dispatch(Action::TextInput(TextInputAction::Escape));
-            } else {
-                log::debug!("Layer 1: Esc - dispatching Close");
-                dispatcher.dispatch(Action::Global(GlobalAction::Close));
+            match view.map_or(EscapeBehavior::Pop, |v| v.escape_behavior(state)) {
+                EscapeBehavior::Forward(action) => {
+                    log::debug!("Layer 1: Esc - forwarding {:?}", action);
+                    dispatcher.dispatch(*action);
+                }
+                EscapeBehavior::Pop => {
+                    log::debug!("Layer 1: Esc - dispatching PopView");
+                    dispatcher.dispatch(Action::Global(GlobalAction::PopView));
+                }
             }
             return false;
         }
+        let peeking = view.is_some_and(|v| v.view_id() == ViewId::PullRequestView)
+            && state.main_view.peek.is_some();
 
         // ═══════════════════════════════════════════════════════════════════
         // LAYER 2: Capability-based routing
@@ -173,11 +215,21 @@ impl KeyboardMiddleware {
 
         // If keymap matched, try each command until one is accepted (gating)
         for cmd_id in command_ids {
-            let action = cmd_id.to_action();
+            let mut action = CommandRegistry::global().get(cmd_id).action();
+            // Closing while peeking closes the popup, not the view
+            if peeking && matches!(action, Action::Global(GlobalAction::Close)) {
+                action = Action::PullRequest(PullRequestAction::ClosePeek);
+            }
 
             // Gating: Check if active view accepts this action
             if let Some(view) = view {
-                if view.accepts_action(&action) {
+                if capabilities.accepts_text_input() && opens_other_view(&**view, &action) {
+                    log::debug!(
+                        "Layer 3: Command {:?} would open a view over text input {:?}, ignored",
+                        cmd_id,
+                        view.view_id()
+                    );
+                } else if view.accepts_action(&action) {
                     log::debug!(
                         "Layer 3: Command {:?} accepted by view, dispatching",
                         cmd_id
@@ -203,21 +255,313 @@ impl KeyboardMiddleware {
     }
 }
 
+/// Whether `action` would put a view other than `top` on screen
+///
+/// Pushing the view that is already on top toggles it closed (e.g. Ctrl+P in
+/// the command palette), so that one is allowed.
+fn opens_other_view(top: &dyn View, action: &Action) -> bool {
+    match action {
+        Action::Global(GlobalAction::PushView(view)) => view.view_id() != top.view_id(),
+        Action::Global(GlobalAction::ReplaceView(_)) => true,
+        _ => false,
+    }
+}
+
+impl KeyboardMiddleware {
+    /// Handle a key pressed `count` times in a row
+    ///
+    /// A navigation key becomes a single repeated navigation action. Any
+    /// other key is handled once per press, as if it was not coalesced.
+    fn handle_repeated_key(
+        &mut self,
+        key: KeyEvent,
+        count: usize,
+        state: &AppState,
+        dispatcher: &Dispatcher,
+    ) -> bool {
+        // Handle the first press against a capturing dispatcher to see what it does
+        let (tx, rx) = mpsc::channel();
+        self.handle_key(key, state, &Dispatcher::new(tx));
+        let mut actions: Vec<Action> = rx.try_iter().collect();
+
+        if let [Action::Navigate(nav)] = actions.as_slice() {
+            dispatcher.dispatch(input_coalescing::repeat(Action::Navigate(*nav), count));
+            return false;
+        }
+
+        for action in actions.drain(..) {
+            dispatcher.dispatch(action);
+        }
+        for _ in 1..count {
+            self.handle_key(key, state, dispatcher);
+        }
+        false
+    }
+
+    /// Handle pasted text
+    ///
+    /// It goes to the text input of the top-most view as a whole and never
+    /// through the keymap, so pasted characters can't trigger commands.
+    /// Outside text input it is dropped.
+    fn handle_paste(&mut self, text: &str, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        self.pending_key = None;
+        let Some(view) = state
+            .view_stack
+            .last()
+            .filter(|view| view.capabilities(state).accepts_text_input())
+        else {
+            log::debug!("Paste outside text input ignored");
+            return false;
+        };
+        for action in view.translate_paste(text) {
+            dispatcher.dispatch(action);
+        }
+        false
+    }
+}
+
 impl Default for KeyboardMiddleware {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl Middleware for KeyboardMiddleware {
+impl Middleware<Action, AppState> for KeyboardMiddleware {
     fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
-        // Only intercept Global KeyPressed actions
-        if let Action::Global(GlobalAction::KeyPressed(key)) = action {
-            log::debug!("KeyboardMiddleware: key={:?}", key);
-            return self.handle_key(*key, state, dispatcher);
+        // Only intercept Global KeyPressed / KeyRepeated actions
+        match action {
+            Action::Global(GlobalAction::KeyPressed(key)) => {
+                log::debug!("KeyboardMiddleware: key={:?}", key);
+                return self.handle_key(*key, state, dispatcher);
+            }
+            Action::Global(GlobalAction::KeyRepeated { key, count }) => {
+                log::debug!("KeyboardMiddleware: key={:?} x {}", key, count);
+                return self.handle_repeated_key(*key, *count, state, dispatcher);
+            }
+            Action::Global(GlobalAction::Paste(text)) => {
+                log::debug!(
+                    "KeyboardMiddleware: paste of {} chars",
+                    text.chars().count()
+                );
+                return self.handle_paste(text, state, dispatcher);
+            }
+            _ => {}
         }
 
         // All other actions pass through
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{CommandPaletteAction, DiffViewerAction};
+    use crate::state::PrPeekState;
+    use crate::store::Store;
+    use crate::views::{
+        AddRepositoryView, CommandPaletteView, ConfirmationPopupView, DiffViewerView,
+        KeyBindingsView, PullRequestView,
+    };
+
+    const ESC: KeyEvent = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+    const Q: KeyEvent = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
+    const CTRL_P: KeyEvent = KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL);
+
+    /// Store with the PR view at the root and `layers` pushed on top of it
+    fn stack(layers: Vec<Box<dyn View>>) -> Store {
+        let mut store = Store::new(AppState::default());
+        store.dispatch(Action::Global(GlobalAction::ReplaceView(Box::new(
+            PullRequestView::new(),
+        ))));
+        for layer in layers {
+            store.dispatch(Action::Global(GlobalAction::PushView(layer)));
+        }
+        store
+    }
+
+    /// Press `keys` in order, reducing every action the middleware dispatches
+    fn press(store: &mut Store, keys: &[KeyEvent]) -> Vec<Action> {
+        let mut middleware = KeyboardMiddleware::new();
+        let mut dispatched = Vec::new();
+        for key in keys {
+            let (tx, rx) = mpsc::channel();
+            middleware.handle(
+                &Action::Global(GlobalAction::KeyPressed(*key)),
+                store.state(),
+                &Dispatcher::new(tx),
+            );
+            for action in rx.try_iter() {
+                store.dispatch(action.clone());
+                dispatched.push(action);
+            }
+        }
+        dispatched
+    }
+
+    fn view_ids(store: &Store) -> Vec<ViewId> {
+        store
+            .state()
+            .view_stack
+            .iter()
+            .map(|view| view.view_id())
+            .collect()
+    }
+
+    /// Views pushed above the main view, and the stack left after Esc
+    type EscapeCase = (Vec<Box<dyn View>>, Vec<ViewId>);
+
+    #[test]
+    fn test_escape_pops_exactly_the_top_layer() {
+        let cases: Vec<EscapeCase> = vec![
+            (vec![], vec![ViewId::PullRequestView]),
+            (
+                vec![Box::new(KeyBindingsView::new())],
+                vec![ViewId::PullRequestView],
+            ),
+            (
+                vec![Box::new(CommandPaletteView::new())],
+                vec![ViewId::PullRequestView],
+            ),
+            (
+                vec![Box::new(AddRepositoryView::new())],
+                vec![ViewId::PullRequestView],
+            ),
+            (
+                vec![
+                    Box::new(DiffViewerView::new()),
+                    Box::new(ConfirmationPopupView::new()),
+                ],
+                vec![ViewId::PullRequestView, ViewId::DiffViewer],
+            ),
+            (
+                vec![
+                    Box::new(KeyBindingsView::new()),
+                    Box::new(CommandPaletteView::new()),
+                ],
+                vec![ViewId::PullRequestView, ViewId::KeyBindings],
+            ),
+        ];
+
+        for (layers, expected) in cases {
+            let mut store = stack(layers);
+            press(&mut store, &[ESC]);
+            assert_eq!(view_ids(&store), expected);
+            assert!(store.state().running, "Esc never quits");
+        }
+    }
+
+    #[test]
+    fn test_escape_dispatches_what_the_top_view_declares() {
+        let mut store = stack(vec![Box::new(CommandPaletteView::new())]);
+        assert!(matches!(
+            press(&mut store, &[ESC]).as_slice(),
+            [Action::TextInput(TextInputAction::Escape)]
+        ));
+
+        let mut store = stack(vec![]);
+        assert!(matches!(
+            press(&mut store, &[ESC]).as_slice(),
+            [Action::Global(GlobalAction::PopView)]
+        ));
+
+        // The diff viewer decides itself whether Esc cancels a comment or closes it
+        let mut store = stack(vec![Box::new(DiffViewerView::new())]);
+        assert!(matches!(
+            press(&mut store, &[ESC]).as_slice(),
+            [Action::TextInput(TextInputAction::Escape)]
+        ));
+        assert!(matches!(
+            DiffViewerView::new().translate_text_input(TextInputAction::Escape),
+            Some(Action::DiffViewer(DiffViewerAction::EscapeOrFocusTree))
+        ));
+
+        let mut store = stack(vec![]);
+        store.state_mut().main_view.peek = Some(PrPeekState {
+            repo_idx: 0,
+            pr_number: 1,
+            error: None,
+        });
+        assert!(matches!(
+            press(&mut store, &[ESC]).as_slice(),
+            [Action::PullRequest(PullRequestAction::ClosePeek)]
+        ));
+        assert_eq!(view_ids(&store), vec![ViewId::PullRequestView]);
+    }
+
+    #[test]
+    fn test_q_quits_only_from_the_root_view() {
+        let mut store = stack(vec![Box::new(KeyBindingsView::new())]);
+        press(&mut store, &[Q]);
+        assert_eq!(view_ids(&store), vec![ViewId::PullRequestView]);
+        assert!(store.state().running);
+
+        press(&mut store, &[Q]);
+        assert!(!store.state().running);
+
+        // Configured not to quit, q on the root view does nothing
+        let mut store = stack(vec![]);
+        store.state_mut().app_config.ui.quit_on_close = false;
+        press(&mut store, &[Q]);
+        assert_eq!(view_ids(&store), vec![ViewId::PullRequestView]);
+        assert!(store.state().running);
+    }
+
+    #[test]
+    fn test_text_input_keeps_keys_from_the_views_below() {
+        // q is typed into the palette, it neither closes nor quits
+        let mut store = stack(vec![Box::new(CommandPaletteView::new())]);
+        assert!(matches!(
+            press(&mut store, &[Q]).as_slice(),
+            [Action::TextInput(TextInputAction::Char('q'))]
+        ));
+        assert_eq!(
+            view_ids(&store),
+            vec![ViewId::PullRequestView, ViewId::CommandPalette]
+        );
+        assert!(store.state().running);
+
+        // Ctrl+P does not open the palette over a text field
+        let mut store = stack(vec![Box::new(AddRepositoryView::new())]);
+        assert!(press(&mut store, &[CTRL_P]).is_empty());
+        assert_eq!(
+            view_ids(&store),
+            vec![ViewId::PullRequestView, ViewId::AddRepository]
+        );
+
+        // ... but still toggles the palette itself closed
+        let mut store = stack(vec![Box::new(CommandPaletteView::new())]);
+        press(&mut store, &[CTRL_P]);
+        assert_eq!(view_ids(&store), vec![ViewId::PullRequestView]);
+    }
+
+    #[test]
+    fn test_paste_goes_to_text_input_only() {
+        let paste = |store: &mut Store, text: &str| {
+            let mut middleware = KeyboardMiddleware::new();
+            let (tx, rx) = mpsc::channel();
+            middleware.handle(
+                &Action::Global(GlobalAction::Paste(text.to_string())),
+                store.state(),
+                &Dispatcher::new(tx),
+            );
+            rx.try_iter().collect::<Vec<_>>()
+        };
+
+        // Typed into the palette as one line, q and the newline included
+        let mut store = stack(vec![Box::new(CommandPaletteView::new())]);
+        let typed: String = paste(&mut store, "q a\n")
+            .into_iter()
+            .map(|action| match action {
+                Action::CommandPalette(CommandPaletteAction::Char(c)) => c,
+                other => panic!("unexpected {:?}", other),
+            })
+            .collect();
+        assert_eq!(typed, "q a");
+
+        // Pasted characters never run as commands
+        let mut store = stack(vec![]);
+        assert!(paste(&mut store, "q").is_empty());
+        assert!(store.state().running);
+    }
+}
@@ -0,0 +1,377 @@
+//! Local Tools Middleware
+//!
+//! Hands PRs over to tools outside the app:
+//! - Browser (PR tabs, build logs, related issues, watched PRs)
+//! - IDE (fresh clone with the PR checked out)
+//! - Clipboard (conflict resolution commands, release notes)
+
+use crate::actions::{
+    Action, ConflictHelperAction, DependencyUpdatesAction, PullRequestAction, ReleaseNotesAction,
+    StatusBarAction, WatchListAction,
+};
+use crate::dispatcher::Dispatcher;
+use crate::domain_models::{conflicts, PrTab, Repository};
+use crate::middleware::github_context::{target_pr_details, GitHubContext};
+use crate::middleware::Middleware;
+use crate::state::{dependency_groups, AppState};
+use crate::utils::browser::UrlOpener;
+use crate::utils::clipboard::copy_to_clipboard;
+use crate::views::ViewId;
+use std::path::PathBuf;
+
+/// Middleware opening PRs in the browser, IDE and clipboard
+pub struct LocalToolsMiddleware {
+    github: GitHubContext,
+}
+
+impl LocalToolsMiddleware {
+    pub fn new(github: GitHubContext) -> Self {
+        Self { github }
+    }
+
+    /// Open `url` as configured by `browser_command`
+    fn open_url(&self, state: &AppState, url: String, dispatcher: &Dispatcher) {
+        UrlOpener::from_config(&state.app_config).spawn(&self.github.runtime, url, dispatcher);
+    }
+}
+
+impl Middleware<Action, AppState> for LocalToolsMiddleware {
+    fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        match action {
+            Action::WatchList(WatchListAction::OpenSelected) => {
+                if let Some(entry) = state.main_view.watch_list.selected_entry() {
+                    self.open_url(state, entry.pr.html_url(), dispatcher);
+                }
+                false // Consume action
+            }
+
+            Action::DependencyUpdates(DependencyUpdatesAction::OpenSelected) => {
+                let groups = dependency_groups(&state.main_view);
+                if let Some(entry) = state.dependency_updates.selected_entry(&groups) {
+                    self.open_url(state, entry.html_url.clone(), dispatcher);
+                }
+                false // Consume action
+            }
+
+            Action::ConflictHelper(ConflictHelperAction::CopyCommands) => {
+                let Some((_, ref pr)) = state.conflict_helper.target else {
+                    return false;
+                };
+                let candidates = state
+                    .conflict_helper
+                    .summary
+                    .as_ref()
+                    .map(|summary| summary.candidates.as_slice())
+                    .unwrap_or_default();
+                let commands = conflicts::resolution_commands(pr, candidates);
+                let dispatcher = dispatcher.clone();
+                self.github.runtime.spawn(async move {
+                    let action = match copy_to_clipboard(commands).await {
+                        Ok(()) => StatusBarAction::success(
+                            "Resolution commands copied to the clipboard",
+                            "Conflicts",
+                        ),
+                        Err(e) => StatusBarAction::error(
+                            format!("Failed to copy to the clipboard: {}", e),
+                            "Conflicts",
+                        ),
+                    };
+                    dispatcher.dispatch(Action::StatusBar(action));
+                });
+                false // Consume action
+            }
+
+            Action::ReleaseNotes(ReleaseNotesAction::Copy) => {
+                let Some(markdown) = state.release_notes.markdown.clone() else {
+                    return false;
+                };
+                let dispatcher = dispatcher.clone();
+                self.github.runtime.spawn(async move {
+                    let action = match copy_to_clipboard(markdown).await {
+                        Ok(()) => StatusBarAction::success(
+                            "Release notes copied to the clipboard",
+                            "Release Notes",
+                        ),
+                        Err(e) => StatusBarAction::error(
+                            format!("Failed to copy to the clipboard: {}", e),
+                            "Release Notes",
+                        ),
+                    };
+                    dispatcher.dispatch(Action::StatusBar(action));
+                });
+                false // Consume action
+            }
+
+            Action::ConflictHelper(ConflictHelperAction::OpenConflictsPage) => {
+                if let Some((_, ref pr)) = state.conflict_helper.target {
+                    log::info!("Opening conflicts of PR #{} in browser", pr.number);
+                    self.open_url(state, PrTab::Conflicts.url(&pr.html_url), dispatcher);
+                }
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::OpenInBrowser(tab)) => {
+                // A failed job focused in the build logs has its own page
+                if *tab == PrTab::Checks && state.active_view().view_id() == ViewId::BuildLog {
+                    if let Some(job) = state
+                        .build_log
+                        .focused_job()
+                        .filter(|job| job.status.is_failed() && !job.html_url.is_empty())
+                    {
+                        log::info!("Opening failed job {} in browser", job.name);
+                        self.open_url(state, job.html_url.clone(), dispatcher);
+                        return false;
+                    }
+                }
+
+                let targets = target_pr_details(state);
+                if targets.is_empty() {
+                    log::warn!("No PRs selected for opening in browser");
+                    return false;
+                }
+
+                log::info!("Opening {} PR(s) in browser ({:?})", targets.len(), tab);
+
+                for pr in targets {
+                    self.open_url(state, tab.url(&pr.html_url), dispatcher);
+                }
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::OpenRelatedIssue { url }) => {
+                log::info!("Opening related issue: {}", url);
+                self.open_url(state, url.clone(), dispatcher);
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::OpenBuildLogs) => {
+                let targets = target_pr_details(state);
+                let Some(repo) = state
+                    .main_view
+                    .repositories
+                    .get(state.main_view.selected_repository)
+                    .filter(|_| !targets.is_empty())
+                else {
+                    log::warn!("No PRs selected for opening build logs");
+                    return false;
+                };
+
+                log::info!("Opening build logs for {} PR(s)", targets.len());
+
+                for pr in targets {
+                    let url = format!(
+                        "{}/actions?query=branch%3A{}",
+                        repo.web_url(),
+                        pr.head_branch
+                    );
+                    self.open_url(state, url, dispatcher);
+                }
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::OpenInIDE) => {
+                let targets = target_pr_details(state);
+                let Some(repo) = state
+                    .main_view
+                    .repositories
+                    .get(state.main_view.selected_repository)
+                    .filter(|_| !targets.is_empty())
+                else {
+                    log::warn!("No PRs selected for opening in IDE");
+                    return false;
+                };
+
+                log::info!("Opening {} PR(s) in IDE", targets.len());
+
+                // Get config values before spawning (they need to be moved into the closure)
+                let ide_command = state.app_config.ide_command.clone();
+                let temp_dir_base = state.app_config.temp_dir.clone();
+
+                // Spawn blocking task for each PR to open in IDE
+                for pr in targets {
+                    let pr_number = pr.number;
+                    let ide_command = ide_command.clone();
+                    let temp_dir_base = temp_dir_base.clone();
+                    let org = repo.org.clone();
+                    let repo_name = repo.repo.clone();
+                    let repo_host = repo.host.clone();
+                    let remote_commands =
+                        checkout_remote_commands(repo, pr.fork_full_name().as_deref());
+
+                    self.github.runtime.spawn_blocking(move || {
+                        use std::process::Command;
+
+                        let temp_dir = PathBuf::from(&temp_dir_base);
+
+                        // Create temp directory if it doesn't exist
+                        if let Err(err) = std::fs::create_dir_all(&temp_dir) {
+                            log::error!("Failed to create temp directory: {}", err);
+                            return;
+                        }
+
+                        // Create unique directory for this PR (include host for GHE)
+                        let host_prefix = match &repo_host {
+                            Some(h) if h != gh_client::DEFAULT_HOST => {
+                                // Sanitize hostname for filesystem (replace dots with dashes)
+                                format!("{}-", h.replace('.', "-"))
+                            }
+                            _ => String::new(),
+                        };
+                        let dir_name =
+                            format!("{}{}-{}-pr-{}", host_prefix, org, repo_name, pr_number);
+                        let pr_dir = temp_dir.join(dir_name);
+
+                        // Remove existing directory if present
+                        if pr_dir.exists() {
+                            if let Err(err) = std::fs::remove_dir_all(&pr_dir) {
+                                log::error!("Failed to remove existing directory: {}", err);
+                                return;
+                            }
+                        }
+
+                        // Clone the repository using gh repo clone
+                        log::info!("Cloning {}/{} to {:?}", org, repo_name, pr_dir);
+                        let mut clone_args = vec![
+                            "repo".to_string(),
+                            "clone".to_string(),
+                            format!("{}/{}", org, repo_name),
+                            pr_dir.to_string_lossy().to_string(),
+                        ];
+                        // Add --hostname for GitHub Enterprise hosts
+                        if let Some(ref host) = repo_host {
+                            if host != gh_client::DEFAULT_HOST {
+                                clone_args.push("--hostname".to_string());
+                                clone_args.push(host.clone());
+                            }
+                        }
+                        let clone_output = Command::new("gh").args(&clone_args).output();
+
+                        match clone_output {
+                            Err(err) => {
+                                log::error!("Failed to run gh repo clone: {}", err);
+                                return;
+                            }
+                            Ok(output) if !output.status.success() => {
+                                let stderr = String::from_utf8_lossy(&output.stderr);
+                                log::error!("gh repo clone failed: {}", stderr);
+                                return;
+                            }
+                            _ => {}
+                        }
+
+                        // Checkout the PR using gh pr checkout
+                        log::info!("Checking out PR #{}", pr_number);
+                        let checkout_output = Command::new("gh")
+                            .args(["pr", "checkout", &pr_number.to_string()])
+                            .current_dir(&pr_dir)
+                            .output();
+
+                        match checkout_output {
+                            Err(err) => {
+                                log::error!("Failed to run gh pr checkout: {}", err);
+                                return;
+                            }
+                            Ok(output) if !output.status.success() => {
+                                let stderr = String::from_utf8_lossy(&output.stderr);
+                                log::error!("gh pr checkout failed: {}", stderr);
+                                return;
+                            }
+                            _ => {}
+                        }
+
+                        // Switch remotes to SSH (gh checkout doesn't do this); pushes of
+                        // fork PRs go to the fork, not to origin
+                        for args in &remote_commands {
+                            let output =
+                                Command::new("git").args(args).current_dir(&pr_dir).output();
+                            match output {
+                                Ok(output) if output.status.success() => {}
+                                Ok(output) => log::warn!(
+                                    "git {} failed: {}",
+                                    args.join(" "),
+                                    String::from_utf8_lossy(&output.stderr)
+                                ),
+                                // Continue anyway - HTTPS will still work
+                                Err(err) => log::warn!("Failed to run git {}: {}", args[0], err),
+                            }
+                        }
+
+                        // Open in configured IDE
+                        if Command::new(&ide_command).arg(&pr_dir).spawn().is_ok() {
+                            log::info!(
+                                "Opened PR #{} in {} at {:?}",
+                                pr_number,
+                                ide_command,
+                                pr_dir
+                            );
+                        } else {
+                            log::error!(
+                                "Failed to open IDE '{}'. PR cloned at: {:?}",
+                                ide_command,
+                                pr_dir
+                            );
+                        }
+                    });
+                }
+                false // Consume action
+            }
+
+            _ => true, // Pass through other actions
+        }
+    }
+}
+
+/// Git commands that point the remotes of a fresh PR checkout at SSH URLs
+///
+/// `origin` is the base repository. For a PR from the fork `owner/name`,
+/// the fork is added as a remote named after its owner and becomes the push
+/// default of the checkout, so pushing never targets the base repository.
+fn checkout_remote_commands(repo: &Repository, fork: Option<&str>) -> Vec<Vec<String>> {
+    let args = |args: &[&str]| -> Vec<String> { args.iter().map(|arg| arg.to_string()).collect() };
+    let mut commands = vec![args(&[
+        "remote",
+        "set-url",
+        "origin",
+        repo.ssh_url().as_str(),
+    ])];
+    let fork = fork.and_then(|fork| Some((fork.split_once('/')?.0, fork)));
+    if let Some((owner, fork)) = fork {
+        let fork_url = format!("git@{}:{}.git", repo.effective_host(), fork);
+        commands.push(args(&["remote", "add", owner, fork_url.as_str()]));
+        commands.push(args(&["config", "remote.pushDefault", owner]));
+    }
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkout_remotes_push_fork_prs_to_the_fork() {
+        let repo = Repository::new("org", "repo", "main");
+        let commands = checkout_remote_commands(&repo, None);
+        assert_eq!(
+            commands,
+            vec![vec![
+                "remote",
+                "set-url",
+                "origin",
+                "git@github.com:org/repo.git"
+            ]]
+        );
+
+        let commands = checkout_remote_commands(&repo, Some("alice/repo-fork"));
+        assert_eq!(commands.len(), 3);
+        assert_eq!(
+            commands[1],
+            vec![
+                "remote",
+                "add",
+                "alice",
+                "git@github.com:alice/repo-fork.git"
+            ]
+        );
+        assert_eq!(commands[2], vec!["config", "remote.pushDefault", "alice"]);
+    }
+}
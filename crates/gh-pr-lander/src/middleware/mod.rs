@@ -1,32 +1,53 @@
-use crate::actions::Action;
-use crate::dispatcher::Dispatcher;
+use crate::actions::{Action, GlobalAction};
 use crate::state::AppState;
 
 pub mod app_config_middleware;
+pub mod audit_log_middleware;
 pub mod bootstrap_middleware;
+pub mod bug_report_middleware;
+pub mod ci_middleware;
+pub mod client_factory;
+pub mod client_task;
 pub mod command_palette_middleware;
 pub mod confirmation_popup_middleware;
 pub mod debug_console_middleware;
+pub mod demo_middleware;
 pub mod diff_viewer_middleware;
+pub mod export_middleware;
+pub mod github_context;
 pub mod github_middleware;
+pub mod incident_middleware;
 pub mod keyboard_middleware;
+pub mod local_tools_middleware;
 pub mod navigation_middleware;
+pub mod notes_middleware;
+pub mod onboarding_middleware;
+pub mod perf_stats_middleware;
+pub mod pr_load_middleware;
+pub mod pr_mutation_middleware;
 pub mod pull_request_middleware;
+pub mod quit_middleware;
 pub mod repository_middleware;
+pub mod saved_filter_middleware;
 pub mod session_middleware;
+pub mod snooze_middleware;
+#[cfg(test)]
+pub mod test_clients;
 pub mod text_input_middleware;
 
-/// Middleware trait - intercepts actions before they reach the reducer
+/// Middleware - intercepts actions before they reach the reducer
 ///
-/// Middleware runs on the background thread, so it can perform blocking operations
-/// (API calls, file I/O) without affecting the UI render loop.
-pub trait Middleware: Send {
-    /// Handle an action
-    ///
-    /// - `action`: The action to process
-    /// - `state`: Current application state (read-only snapshot)
-    /// - `dispatcher`: Use to dispatch actions that should re-enter middleware chain
-    ///
-    /// Returns `true` to continue chain, `false` to consume action
-    fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool;
+/// The trait is shared with the other apps through `gh-redux`. Middleware runs
+/// on the background thread, so it can perform blocking operations (API calls,
+/// file I/O) without affecting the UI render loop.
+pub use gh_redux::Middleware;
+
+/// The middleware chain, in the order actions pass through it
+pub type MiddlewareChain = Vec<Box<dyn Middleware<Action, AppState> + Send>>;
+
+/// Logs every action at trace level, except the splash animation ticks
+pub fn logging_middleware() -> gh_redux::LoggingMiddleware<Action> {
+    gh_redux::LoggingMiddleware::new(log::Level::Trace, |action| {
+        matches!(action, Action::Global(GlobalAction::Tick))
+    })
 }
@@ -5,8 +5,9 @@
 //!
 //! This ensures translated actions go through the full middleware chain.
 
-use crate::actions::Action;
+use crate::actions::{Action, GlobalAction};
 use crate::dispatcher::Dispatcher;
+use crate::input_coalescing;
 use crate::middleware::Middleware;
 use crate::state::AppState;
 
@@ -25,7 +26,7 @@ impl Default for NavigationMiddleware {
     }
 }
 
-impl Middleware for NavigationMiddleware {
+impl Middleware<Action, AppState> for NavigationMiddleware {
     fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
         if let Action::Navigate(nav) = action {
             if let Some(view) = state.view_stack.last() {
@@ -44,6 +45,26 @@ impl Middleware for NavigationMiddleware {
             log::debug!("Navigation action not handled by active view: {:?}", nav);
         }
 
+        // Repeated navigation is translated once and stays repeated
+        if let Action::Global(GlobalAction::Repeat { action, count }) = action {
+            if let Action::Navigate(nav) = **action {
+                if let Some(translated) = state
+                    .view_stack
+                    .last()
+                    .and_then(|view| view.translate_navigation(nav))
+                {
+                    log::debug!(
+                        "NavigationMiddleware: Translating {} x {:?} to {:?}",
+                        count,
+                        nav,
+                        translated
+                    );
+                    dispatcher.dispatch(input_coalescing::repeat(translated, *count));
+                    return false;
+                }
+            }
+        }
+
         // Pass through all other actions
         true
     }
@@ -0,0 +1,218 @@
+//! Notes Middleware
+//!
+//! Owns the local per-PR notes and keeps them in sync with the state.
+//!
+//! - Loads the notes file on bootstrap
+//! - Opens the note editor (the confirmation popup) pre-filled with the note
+//! - Saves edited notes right away, so they survive a crash
+//! - Marks notes of PRs seen merged or closed, and prunes them once they
+//!   are older than `note_retention_days`
+//!
+//! Notes are never sent to GitHub. In demo mode they are kept in memory only.
+
+use crate::actions::{
+    Action, BootstrapAction, ConfirmationPopupAction, PullRequestAction, StatusBarAction,
+    WatchListAction,
+};
+use crate::dispatcher::Dispatcher;
+use crate::domain_models::{Pr, PrState, Repository};
+use crate::middleware::Middleware;
+use crate::state::{AppState, ConfirmationIntent};
+use crate::utils::autosave::PendingSaves;
+use crate::utils::io_worker::IoWorker;
+use chrono::{Duration, Utc};
+use gh_pr_config::PrNotes;
+
+/// Middleware for local per-PR notes
+pub struct NotesMiddleware {
+    notes: PrNotes,
+    /// Whether notes are read from and written to the notes file
+    persist: bool,
+    /// Writes the notes file off the middleware thread
+    io: IoWorker,
+}
+
+impl NotesMiddleware {
+    pub fn new() -> Self {
+        Self {
+            notes: PrNotes::default(),
+            persist: true,
+            io: IoWorker::spawn("notes-io"),
+        }
+    }
+
+    /// Notes that are never written to disk (demo mode)
+    pub fn in_memory() -> Self {
+        Self {
+            notes: PrNotes::default(),
+            persist: false,
+            io: IoWorker::spawn("notes-io"),
+        }
+    }
+
+    /// Write the notes file and publish the notes to the state
+    fn store(&self, dispatcher: &Dispatcher) {
+        if self.persist {
+            let notes = self.notes.clone();
+            let dispatcher = dispatcher.clone();
+            PendingSaves::global().stage("notes", move || {
+                if let Err(e) = notes.save() {
+                    log::error!("Failed to save notes: {:#}", e);
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::error(
+                        format!("Failed to save notes: {:#}", e),
+                        "Notes",
+                    )));
+                }
+            });
+            self.io.run(|| {
+                PendingSaves::global().flush();
+            });
+        }
+        self.publish(dispatcher);
+    }
+
+    fn publish(&self, dispatcher: &Dispatcher) {
+        let notes = self
+            .notes
+            .notes
+            .iter()
+            .map(|note| (note.pr.clone(), note.text.clone()))
+            .collect();
+        dispatcher.dispatch(Action::PullRequest(PullRequestAction::NotesUpdated(notes)));
+    }
+
+    /// Mark notes of merged or closed PRs, then prune expired ones
+    fn track_done_prs(
+        &mut self,
+        repo: &Repository,
+        prs: &[Pr],
+        state: &AppState,
+        dispatcher: &Dispatcher,
+    ) {
+        let mut changed = false;
+        for pr in prs.iter().filter(|pr| pr.state != PrState::Open) {
+            // A done PR is not updated anymore, so this is when it was merged or closed
+            changed |= self
+                .notes
+                .mark_done(&repo.watched_pr(pr.number), pr.updated_at);
+        }
+        changed |= self.prune(state) > 0;
+        if changed {
+            self.store(dispatcher);
+        }
+    }
+
+    fn prune(&mut self, state: &AppState) -> usize {
+        let retention = Duration::days(state.app_config.note_retention_days as i64);
+        let pruned = self.notes.prune(Utc::now(), retention);
+        if pruned > 0 {
+            log::info!("Pruned {} notes of merged or closed PRs", pruned);
+        }
+        pruned
+    }
+
+    /// Open the note editor for the PR under the cursor
+    fn edit_note(&self, state: &AppState, dispatcher: &Dispatcher) {
+        let repo_idx = state.main_view.selected_repository;
+        let Some(repo) = state.main_view.repositories.get(repo_idx) else {
+            return;
+        };
+        let Some(pr) = state
+            .main_view
+            .repo_data
+            .get(&repo_idx)
+            .and_then(|data| data.prs.get(data.selected_pr))
+        else {
+            log::warn!("No PR under the cursor to edit a note for");
+            return;
+        };
+
+        let default_message = state
+            .main_view
+            .note_for(repo, pr.number)
+            .unwrap_or_default()
+            .to_string();
+        dispatcher.dispatch(Action::ConfirmationPopup(ConfirmationPopupAction::Show {
+            intent: ConfirmationIntent::EditNote {
+                pr_number: pr.number as u64,
+            },
+            default_message,
+            repo_context: format!("{}/{}", repo.org, repo.repo),
+            warnings: Vec::new(),
+        }));
+    }
+
+    fn save_note(&mut self, pr_number: u64, text: &str, state: &AppState, dispatcher: &Dispatcher) {
+        let Some(repo) = state
+            .main_view
+            .repositories
+            .get(state.main_view.selected_repository)
+        else {
+            return;
+        };
+        let pr = repo.watched_pr(pr_number as usize);
+        self.notes.set(&pr, text, Utc::now());
+        self.store(dispatcher);
+
+        let message = if self.notes.get(&pr).is_some() {
+            format!("Saved note for {}", pr)
+        } else {
+            format!("Removed note for {}", pr)
+        };
+        dispatcher.dispatch(Action::StatusBar(StatusBarAction::success(
+            message, "Notes",
+        )));
+    }
+}
+
+impl Default for NotesMiddleware {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Middleware<Action, AppState> for NotesMiddleware {
+    fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        match action {
+            Action::Bootstrap(BootstrapAction::Start) if self.persist => {
+                let (notes, notice) = PrNotes::load_with_recovery();
+                if let Some(notice) = notice {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        notice.message(),
+                        "Notes",
+                    )));
+                }
+                self.notes = notes;
+                self.publish(dispatcher);
+                true // Pass through
+            }
+
+            Action::PullRequest(PullRequestAction::EditNote) => {
+                self.edit_note(state, dispatcher);
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::SaveNote { pr_number, text }) => {
+                self.save_note(*pr_number, text, state, dispatcher);
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::Loaded { repo, prs, .. })
+            | Action::PullRequest(PullRequestAction::Prefetched { repo, prs }) => {
+                self.track_done_prs(repo, prs, state, dispatcher);
+                true // Let reducer store the PRs
+            }
+
+            Action::WatchList(WatchListAction::StatusFetched { pr, status })
+                if status.is_done() =>
+            {
+                if self.notes.mark_done(pr, Utc::now()) {
+                    self.store(dispatcher);
+                }
+                true // Let reducer store the status
+            }
+
+            _ => true,
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Onboarding Middleware
+//!
+//! Orchestrates the first-run onboarding wizard:
+//! - Shows the wizard when bootstrap finds no repositories and no local session
+//! - Writes a commented default config file if none exists
+//! - Detects a repository from the current directory's git remote
+//! - Adds the detected repository on request
+
+use std::process::Command;
+
+use crate::actions::{Action, BootstrapAction, OnboardingAction, RepositoryAction};
+use crate::dispatcher::Dispatcher;
+use crate::domain_models::Repository;
+use crate::middleware::Middleware;
+use crate::state::{parse_github_url, AppState, OnboardingStep};
+use crate::utils::io_worker::IoWorker;
+use gh_pr_config::{has_local_session, AppConfig};
+
+/// Middleware driving the onboarding wizard
+pub struct OnboardingMiddleware {
+    /// Writes the config and asks `git` off the middleware thread
+    io: IoWorker,
+}
+
+impl OnboardingMiddleware {
+    pub fn new() -> Self {
+        Self {
+            io: IoWorker::spawn("onboarding-io"),
+        }
+    }
+
+    /// Write the default config and report the outcome to the wizard
+    fn write_default_config(dispatcher: &Dispatcher) {
+        let message = match AppConfig::write_default_if_missing() {
+            Ok(Some(path)) => format!("Wrote a commented default config to {}", path.display()),
+            Ok(None) => "Using your existing config file.".to_string(),
+            Err(e) => {
+                log::warn!(
+                    "OnboardingMiddleware: Failed to write default config: {}",
+                    e
+                );
+                format!("Could not write default config: {}", e)
+            }
+        };
+        dispatcher.dispatch(Action::Onboarding(OnboardingAction::ConfigWritten(message)));
+    }
+}
+
+impl Default for OnboardingMiddleware {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Middleware<Action, AppState> for OnboardingMiddleware {
+    fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        match action {
+            Action::Bootstrap(BootstrapAction::End) => {
+                if state.main_view.repositories.is_empty() && !has_local_session() {
+                    log::info!("OnboardingMiddleware: First run detected, showing wizard");
+                    dispatcher.dispatch(Action::Onboarding(OnboardingAction::Show));
+                }
+                true
+            }
+
+            Action::Onboarding(OnboardingAction::Show) => {
+                let config = state.app_config.clone();
+                let dispatcher = dispatcher.clone();
+                self.io.run(move || {
+                    Self::write_default_config(&dispatcher);
+                    let detected = detect_repository_from_git_remote()
+                        .map(|repo| repo.with_configured_profile(&config));
+                    log::info!("OnboardingMiddleware: Detected repository: {:?}", detected);
+                    dispatcher.dispatch(Action::Onboarding(OnboardingAction::RepositoryDetected(
+                        detected,
+                    )));
+                });
+                true // Let reducer push the wizard view
+            }
+
+            Action::Onboarding(OnboardingAction::Next) => {
+                let Some(ref onboarding) = state.onboarding else {
+                    return false;
+                };
+                if onboarding.step == OnboardingStep::Repository {
+                    // Finishing the repository step picks the detected repo if any
+                    let next = if onboarding.detected_repo.is_some() {
+                        OnboardingAction::AddDetectedRepository
+                    } else {
+                        OnboardingAction::AddRepositoryManually
+                    };
+                    dispatcher.dispatch(Action::Onboarding(next));
+                    return false;
+                }
+                true
+            }
+
+            Action::Onboarding(OnboardingAction::AddDetectedRepository) => {
+                let Some(repo) = state
+                    .onboarding
+                    .as_ref()
+                    .and_then(|o| o.detected_repo.clone())
+                else {
+                    // Nothing detected - ignore the key
+                    return false;
+                };
+                log::info!(
+                    "OnboardingMiddleware: Adding detected repository {}",
+                    repo.full_display_name()
+                );
+                dispatcher.dispatch(Action::Repository(RepositoryAction::AddRepository(
+                    repo.clone(),
+                )));
+                dispatcher.dispatch(Action::Repository(RepositoryAction::LoadRepositoryData(
+                    repo,
+                )));
+                true // Let reducer close the wizard
+            }
+
+            _ => true,
+        }
+    }
+}
+
+/// Detect a GitHub repository from the `origin` remote of the current directory
+fn detect_repository_from_git_remote() -> Option<Repository> {
+    let output = Command::new("git")
+        .args(["remote", "get-url", "origin"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let url = String::from_utf8(output.stdout).ok()?;
+    let (host, org, repo) = parse_github_url(url.trim())?;
+
+    Some(Repository::with_host(
+        org,
+        repo,
+        detect_default_branch(),
+        host,
+    ))
+}
+
+/// Detect the default branch of `origin`, falling back to "main"
+fn detect_default_branch() -> String {
+    Command::new("git")
+        .args(["symbolic-ref", "--short", "refs/remotes/origin/HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .and_then(|head| {
+            head.trim()
+                .strip_prefix("origin/")
+                .map(|branch| branch.to_string())
+        })
+        .filter(|branch| !branch.is_empty())
+        .unwrap_or_else(|| "main".to_string())
+}
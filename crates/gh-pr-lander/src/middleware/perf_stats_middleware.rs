@@ -0,0 +1,122 @@
+//! Performance Stats Middleware
+//!
+//! Publishes snapshots of the GitHub API metrics.
+//!
+//! - Applies the `[metrics]` config once it is loaded
+//! - Takes a snapshot when the performance stats popup opens or refreshes,
+//!   and on every refresh tick while it is shown
+//! - Publishes the request rate of the last minute for the status bar on
+//!   every refresh tick
+//! - Logs a one-line summary every `summary_interval_minutes`
+
+use crate::actions::{Action, BootstrapAction, Event, PerfStatsAction, StatusBarAction};
+use crate::dispatcher::Dispatcher;
+use crate::middleware::Middleware;
+use crate::state::{ApiUsage, AppState};
+use crate::views::ViewId;
+use gh_client::{ApiMetrics, RequestBudget};
+use std::time::{Duration, Instant};
+
+/// Summary interval until the config is loaded
+const DEFAULT_SUMMARY_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Middleware for the API metrics snapshots and summaries
+pub struct PerfStatsMiddleware {
+    /// Time between summaries in the log (None = never)
+    summary_interval: Option<Duration>,
+    last_summary: Instant,
+}
+
+impl PerfStatsMiddleware {
+    pub fn new() -> Self {
+        Self {
+            summary_interval: Some(DEFAULT_SUMMARY_INTERVAL),
+            last_summary: Instant::now(),
+        }
+    }
+
+    fn publish(dispatcher: &Dispatcher) {
+        let snapshot = ApiMetrics::global().snapshot();
+        dispatcher.dispatch(Action::PerfStats(PerfStatsAction::Loaded(Box::new(
+            snapshot,
+        ))));
+    }
+
+    /// Whether a summary is due `now`
+    fn summary_due(&self, now: Instant) -> bool {
+        self.summary_interval
+            .is_some_and(|interval| now.duration_since(self.last_summary) >= interval)
+    }
+}
+
+impl Default for PerfStatsMiddleware {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Middleware<Action, AppState> for PerfStatsMiddleware {
+    fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        match action {
+            Action::Bootstrap(BootstrapAction::ConfigLoaded(config)) => {
+                ApiMetrics::global().set_enabled(config.metrics.enabled);
+                self.summary_interval = (config.metrics.enabled
+                    && config.metrics.summary_interval_minutes > 0)
+                    .then(|| Duration::from_secs(config.metrics.summary_interval_minutes * 60));
+                true // Let reducer store the config
+            }
+
+            Action::PerfStats(PerfStatsAction::Open) => {
+                Self::publish(dispatcher);
+                true // Let reducer open the popup
+            }
+
+            Action::PerfStats(PerfStatsAction::Refresh) => {
+                Self::publish(dispatcher);
+                false
+            }
+
+            Action::Event(Event::RefreshDue) => {
+                let now = Instant::now();
+                if self.summary_due(now) {
+                    log::info!(
+                        "GitHub API performance: {}",
+                        ApiMetrics::global().snapshot()
+                    );
+                    self.last_summary = now;
+                }
+                if state
+                    .view_stack
+                    .last()
+                    .is_some_and(|view| view.view_id() == ViewId::PerfStats)
+                {
+                    Self::publish(dispatcher);
+                }
+                let metrics = RequestBudget::global().metrics();
+                dispatcher.dispatch(Action::StatusBar(StatusBarAction::ApiUsage(ApiUsage {
+                    requests_last_minute: metrics.last_minute_total(),
+                    requests_per_minute: metrics.requests_per_minute,
+                })));
+                true // Pass through
+            }
+
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_interval() {
+        let mut middleware = PerfStatsMiddleware::new();
+        let start = middleware.last_summary;
+        assert!(!middleware.summary_due(start + Duration::from_secs(60)));
+        assert!(middleware.summary_due(start + DEFAULT_SUMMARY_INTERVAL));
+
+        middleware.summary_interval = None;
+        assert!(!middleware.summary_due(start + DEFAULT_SUMMARY_INTERVAL));
+    }
+}
@@ -0,0 +1,2234 @@
+//! PR Load Middleware
+//!
+//! Fetches pull requests and what is shown about them:
+//! - PR loading (fetch_pull_requests), prefetching neighbour repositories
+//! - Server-side PR search (paged, details fetched on demand)
+//! - Watch list status checks (periodic, with change notifications)
+//! - Rebase conflict helper (branch comparison)
+//! - Release notes (PRs merged since a tag or date)
+//! - Branches to pick the base branch of a tab from
+//! - Diff viewer (diff, review comments, blame)
+//! - Reviewers, descriptions and tracked branch status
+
+use crate::actions::{
+    Action, BranchPickerAction, ConfirmationPopupAction, ConflictHelperAction, DiffViewerAction,
+    Event, GlobalAction, LoadedComment, PrSearchAction, PullRequestAction, ReleaseNotesAction,
+    RepositoryAction, StatusBarAction, WatchListAction,
+};
+use crate::blame::GitHubBlameProvider;
+use crate::dispatcher::{Dispatcher, OperationDispatcher};
+use crate::domain_models::{
+    release_notes, retain_stacked_on, CiChecks, ConflictSummary, LoadingState, MergeableStatus, Pr,
+    PrState, PrStateFilter, Repository, SinceRef, WatchedPr,
+};
+use crate::middleware::ci_middleware::dispatch_ci_status_checks;
+use crate::middleware::github_context::GitHubContext;
+use crate::middleware::Middleware;
+use crate::state::{
+    AppState, ConfirmationIntent, InaccessibleRepo, PrFilter, PrSort, RepoPulse, WatchStatus,
+    PULSE_DAYS,
+};
+use crate::utils::cycling::{next_index, previous_index};
+use anyhow::Context;
+use gh_client::{
+    background, github_status, repo_search_query, CacheMode, Feature, GitHubClient, PullRequest,
+    RequestBudget, SEARCH_PAGE_SIZE,
+};
+use gh_diff_viewer::{BlameProvider, DiffEvent};
+use std::sync::Arc;
+
+/// Middleware loading PRs and their details from GitHub
+pub struct PrLoadMiddleware {
+    github: GitHubContext,
+}
+
+impl PrLoadMiddleware {
+    pub fn new(github: GitHubContext) -> Self {
+        Self { github }
+    }
+
+    /// Fetch the login of the repository's account once, for the repo header
+    fn resolve_account_login(&self, repo: &Repository, state: &AppState, dispatcher: &Dispatcher) {
+        let account = repo.account_key();
+        if state.main_view.account_logins.contains_key(&account) {
+            return;
+        }
+        let repo = repo.clone();
+        let clients = Arc::clone(&self.github.clients);
+        let dispatcher = dispatcher.clone();
+
+        self.github.runtime.spawn(async move {
+            match clients.login(&repo).await {
+                Ok(login) => {
+                    dispatcher.dispatch(Action::Repository(RepositoryAction::AccountResolved {
+                        account,
+                        login,
+                    }))
+                }
+                Err(e) => log::warn!("PrLoadMiddleware: No login for {}: {:#}", account, e),
+            }
+        });
+    }
+
+    /// Probe the GraphQL features of the repository's host once
+    fn resolve_host_capabilities(
+        &self,
+        repo: &Repository,
+        state: &AppState,
+        dispatcher: &Dispatcher,
+    ) {
+        let host = repo.effective_host().to_string();
+        if state.main_view.host_capabilities.contains_key(&host) {
+            return;
+        }
+        let repo = repo.clone();
+        let clients = Arc::clone(&self.github.clients);
+        let dispatcher = dispatcher.clone();
+
+        self.github.runtime.spawn(async move {
+            // Clients of a host share the probed capabilities
+            match clients.client(&repo, false).await {
+                Ok(client) => {
+                    let capabilities = client.capabilities().await;
+                    dispatcher.dispatch(Action::Repository(
+                        RepositoryAction::HostCapabilitiesResolved { host, capabilities },
+                    ))
+                }
+                Err(e) => log::warn!("PrLoadMiddleware: No capabilities for {}: {:#}", host, e),
+            }
+        });
+    }
+
+    /// Compare both sides of a PR's merge base for the conflict helper
+    fn compare_for_conflicts(&self, repo: &Repository, pr: &Pr, dispatcher: &Dispatcher) {
+        let watched = repo.watched_pr(pr.number);
+        let base = pr.base_branch.clone();
+        let head = pr.head_sha.clone();
+        let failed = watched.clone();
+        let repo_for_compare = repo.clone();
+        self.github
+            .task(repo, dispatcher)
+            .on_failure(move |error| {
+                Action::ConflictHelper(ConflictHelperAction::LoadError { pr: failed, error })
+            })
+            .spawn(move |client, dispatcher| async move {
+                let repo = repo_for_compare;
+                // The PR's changes since the merge base, and the base branch's
+                let (pr_side, base_side) = tokio::join!(
+                    client.fetch_compare(&repo.org, &repo.repo, &base, &head),
+                    client.fetch_compare(&repo.org, &repo.repo, &head, &base),
+                );
+                let (pr_side, base_side) = (pr_side?, base_side?);
+                dispatcher.dispatch(Action::ConflictHelper(ConflictHelperAction::Loaded {
+                    pr: watched,
+                    summary: Box::new(ConflictSummary::new(
+                        &pr_side.files,
+                        &base_side.files,
+                        pr_side.behind_by,
+                    )),
+                }));
+                Ok(())
+            });
+    }
+
+    /// Show the branch picker for the selected repository tab
+    fn open_branch_picker(&self, state: &AppState, dispatcher: &Dispatcher) {
+        let repo_idx = state.main_view.selected_repository;
+        let Some(repo) = state.main_view.repositories.get(repo_idx).cloned() else {
+            dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                "No repository selected",
+                "Switch Branch",
+            )));
+            return;
+        };
+        dispatcher.dispatch(Action::BranchPicker(BranchPickerAction::Show(repo.clone())));
+
+        self.github
+            .task(&repo, dispatcher)
+            .on_failure(|error| Action::BranchPicker(BranchPickerAction::LoadFailed(error)))
+            .spawn(move |client, dispatcher| async move {
+                let branches = client.fetch_branches(&repo.org, &repo.repo).await?;
+                dispatcher.dispatch(Action::BranchPicker(BranchPickerAction::Loaded(branches)));
+                Ok(())
+            });
+    }
+
+    /// Ask where the release notes of the selected repository start, preset
+    /// with its latest tag
+    fn prepare_release_notes(&self, state: &AppState, dispatcher: &Dispatcher) {
+        let Some(repo) = state
+            .main_view
+            .repositories
+            .get(state.main_view.selected_repository)
+            .cloned()
+        else {
+            dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                "No repository selected",
+                "Release Notes",
+            )));
+            return;
+        };
+        let repo_for_tag = repo.clone();
+        self.github
+            .task(&repo, dispatcher)
+            .operation("Looking up the latest tag", "Release Notes")
+            .spawn(move |client, dispatcher| async move {
+                let repo = repo_for_tag;
+                // Without a tag to preset, the start is typed
+                let (tag, warnings) = match client.fetch_latest_tag(&repo.org, &repo.repo).await {
+                    Ok(tag) => (tag.unwrap_or_default(), Vec::new()),
+                    Err(e) => {
+                        log::warn!(
+                            "PrLoadMiddleware: No latest tag of {}/{}: {:#}",
+                            repo.org,
+                            repo.repo,
+                            e
+                        );
+                        (String::new(), vec![format!("Latest tag unknown: {:#}", e)])
+                    }
+                };
+                dispatcher.dispatch(Action::ConfirmationPopup(ConfirmationPopupAction::Show {
+                    intent: ConfirmationIntent::ReleaseNotes {
+                        repo: Box::new(repo.clone()),
+                    },
+                    default_message: tag,
+                    repo_context: format!("{}/{}", repo.org, repo.repo),
+                    warnings,
+                }));
+                Ok(())
+            });
+    }
+
+    /// Draft release notes from the PRs merged into the tracked branch since
+    /// a tag or date
+    ///
+    /// A tag stands for the time of its commit. At most
+    /// `RELEASE_NOTES_MAX_PAGES` search pages are fetched.
+    fn generate_release_notes(
+        &self,
+        repo: &Repository,
+        since: &SinceRef,
+        state: &AppState,
+        dispatcher: &Dispatcher,
+    ) {
+        let config = state.app_config.release_notes.clone();
+        let (failed_repo, failed_since) = (repo.clone(), since.clone());
+        let (repo_for_notes, since_for_notes) = (repo.clone(), since.clone());
+        self.github
+            .task(repo, dispatcher)
+            .operation(
+                format!("Drafting release notes since {}", since),
+                "Release Notes",
+            )
+            .on_failure(move |error| {
+                Action::ReleaseNotes(ReleaseNotesAction::LoadError {
+                    repo: failed_repo,
+                    since: failed_since,
+                    error,
+                })
+            })
+            .spawn(move |client, dispatcher| async move {
+                let (repo, since) = (repo_for_notes, since_for_notes);
+                let merged = match &since {
+                    SinceRef::Date(date) => format!("merged:>={}", date.format("%Y-%m-%d")),
+                    SinceRef::Tag(tag) => {
+                        let date = client
+                            .fetch_ref_date(&repo.org, &repo.repo, tag)
+                            .await
+                            .with_context(|| format!("Failed to look up tag {}", tag))?
+                            .with_context(|| format!("Tag {} not found", tag))?;
+                        format!("merged:>{}", date.format("%Y-%m-%dT%H:%M:%SZ"))
+                    }
+                };
+                let query = format!("is:merged base:{} {}", repo.branch, merged);
+
+                let mut prs = Vec::new();
+                for page in 1..=RELEASE_NOTES_MAX_PAGES {
+                    let results = client
+                        .search_pull_requests_in_repo(&repo.org, &repo.repo, &query, page)
+                        .await?;
+                    let more =
+                        results.has_more(page, SEARCH_PAGE_SIZE) && !results.items.is_empty();
+                    prs.extend(results.items.into_iter().map(convert_to_domain_pr));
+                    if !more {
+                        break;
+                    }
+                    if page == RELEASE_NOTES_MAX_PAGES {
+                        dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                            format!("Release notes list the first {} merged PRs only", prs.len()),
+                            "Release Notes",
+                        )));
+                    }
+                }
+                log::info!(
+                    "Release notes of {}/{} since {}: {} merged PRs",
+                    repo.org,
+                    repo.repo,
+                    since,
+                    prs.len()
+                );
+
+                let markdown = release_notes::render_markdown(&since, &prs, &config);
+                dispatcher.dispatch(Action::ReleaseNotes(ReleaseNotesAction::Loaded {
+                    repo,
+                    since,
+                    markdown,
+                    pr_count: prs.len(),
+                }));
+                Ok(())
+            });
+    }
+
+    /// Fetch blame for the diff viewer's current file if it has none yet
+    ///
+    /// Diffs don't know their base commit, so the merge base with the PR's
+    /// base branch is looked up first and handed back with the blame.
+    fn fetch_blame(&self, state: &AppState, dispatcher: &Dispatcher) {
+        let diff_viewer = &state.diff_viewer;
+        let Some(DiffEvent::RequestBlame {
+            file_path,
+            base_path,
+            commit_sha,
+            start_line,
+            end_line,
+        }) = diff_viewer
+            .inner
+            .as_ref()
+            .and_then(|inner| inner.blame_request())
+        else {
+            return;
+        };
+        let (Some(repo), Some(pr_number), Some(head_sha)) = (
+            diff_viewer.repo.clone(),
+            diff_viewer.pr_number,
+            diff_viewer.head_sha.clone(),
+        ) else {
+            return;
+        };
+        let base_branch = state
+            .main_view
+            .repositories
+            .iter()
+            .position(|r| *r == repo)
+            .and_then(|idx| state.main_view.repo_data.get(&idx))
+            .and_then(|data| data.prs.iter().find(|pr| pr.number as u64 == pr_number))
+            .map(|pr| pr.base_branch.clone());
+
+        log::debug!("Fetching blame of {} for PR #{}", base_path, pr_number);
+        let failed_path = file_path.clone();
+        self.github
+            .task(&repo, dispatcher)
+            .report(
+                format!("Failed to load blame of {}", base_path),
+                "Diff Viewer",
+            )
+            .on_failure(|error| {
+                Action::DiffViewer(DiffViewerAction::BlameFailed {
+                    path: failed_path,
+                    error,
+                })
+            })
+            .spawn(move |client, dispatcher| async move {
+                let base_sha = match (commit_sha.is_empty(), base_branch) {
+                    (false, _) => commit_sha,
+                    (true, Some(base_branch)) => {
+                        client
+                            .fetch_compare(&repo.org, &repo.repo, &base_branch, &head_sha)
+                            .await?
+                            .merge_base_sha
+                    }
+                    (true, None) => anyhow::bail!("Base branch of PR #{} unknown", pr_number),
+                };
+                let entries = GitHubBlameProvider::new(client, &repo.org, &repo.repo)
+                    .fetch_blame(&base_path, &base_sha, start_line..=end_line)
+                    .await?;
+                dispatcher.dispatch(Action::DiffViewer(DiffViewerAction::BlameLoaded {
+                    path: file_path,
+                    base_sha,
+                    entries,
+                }));
+                Ok(())
+            });
+    }
+
+    /// Fetch the reviewers of a repository's open PRs for the attention sort
+    ///
+    /// Skipped on hosts without review decisions. Fetched in the background
+    /// lane unless the user just switched to the sort.
+    fn fetch_reviewers(
+        &self,
+        repo: &Repository,
+        prs: &[Pr],
+        state: &AppState,
+        dispatcher: &Dispatcher,
+        background_lane: bool,
+    ) {
+        if !state.main_view.host_supports(repo, Feature::ReviewDecision) {
+            return;
+        }
+        let pr_numbers: Vec<u64> = prs
+            .iter()
+            .filter(|pr| pr.state == PrState::Open)
+            .map(|pr| pr.number as u64)
+            .collect();
+        if pr_numbers.is_empty() {
+            return;
+        }
+
+        log::debug!(
+            "Fetching reviewers of {} PRs of {}/{}",
+            pr_numbers.len(),
+            repo.org,
+            repo.repo
+        );
+        let mut task = self.github.task(repo, dispatcher);
+        if background_lane {
+            task = task.background();
+        }
+        let repo = repo.clone();
+        task.spawn(move |client, dispatcher| async move {
+            match client
+                .fetch_reviewers(&repo.org, &repo.repo, &pr_numbers)
+                .await
+            {
+                Ok(reviewers) => {
+                    dispatcher.dispatch(Action::PullRequest(PullRequestAction::ReviewersLoaded {
+                        repo,
+                        reviewers,
+                    }));
+                }
+                // Expected without a token, PRs are then sorted by CI and age only
+                Err(e) => log::debug!(
+                    "Failed to fetch reviewers of {}/{}: {:#}",
+                    repo.org,
+                    repo.repo,
+                    e
+                ),
+            }
+            Ok(())
+        });
+    }
+
+    /// Fetch the description of the focused PR for the peek popup if unknown
+    ///
+    /// Fetched bypassing the API cache, the result is kept in the state for
+    /// as long as the PR isn't updated.
+    fn fetch_peek_body(&self, state: &AppState, dispatcher: &Dispatcher) {
+        let main_view = &state.main_view;
+        let repo_idx = main_view.selected_repository;
+        let (Some(repo), Some(pr)) = (
+            main_view.repositories.get(repo_idx),
+            main_view
+                .repo_data
+                .get(&repo_idx)
+                .and_then(|data| data.prs.get(data.selected_pr)),
+        ) else {
+            return;
+        };
+        if main_view.peek_body(repo, pr).is_some() {
+            return;
+        }
+
+        let watched = repo.watched_pr(pr.number);
+        let failed = watched.clone();
+        let updated_at = pr.updated_at;
+        self.github
+            .task(repo, dispatcher)
+            .force_refresh(true)
+            .on_failure(move |error| {
+                Action::PullRequest(PullRequestAction::PeekBodyFailed { pr: failed, error })
+            })
+            .spawn(move |client, dispatcher| async move {
+                let fetched = client
+                    .fetch_pull_request(&watched.org, &watched.repo, watched.number)
+                    .await?;
+                dispatcher.dispatch(Action::PullRequest(PullRequestAction::PeekBodyLoaded {
+                    pr: watched,
+                    updated_at,
+                    body: fetched.body.unwrap_or_default(),
+                }));
+                Ok(())
+            });
+    }
+
+    /// Fetch the CI state of the head of the repository's tracked branch
+    fn fetch_branch_status(&self, repo: &Repository, dispatcher: &Dispatcher, force_refresh: bool) {
+        let repo_for_status = repo.clone();
+        self.github
+            .task(repo, dispatcher)
+            .force_refresh(force_refresh)
+            .background()
+            .spawn(move |client, dispatcher| async move {
+                let repo = repo_for_status;
+                let head_sha = client
+                    .fetch_branch_head_sha(&repo.org, &repo.repo, &repo.branch)
+                    .await
+                    .with_context(|| format!("Failed to fetch head of {}", repo.branch))?;
+                let Some(head_sha) = head_sha else {
+                    log::info!(
+                        "Branch {} of {}/{} not found, skipping its CI status",
+                        repo.branch,
+                        repo.org,
+                        repo.repo
+                    );
+                    dispatcher.dispatch(Action::PullRequest(
+                        PullRequestAction::BranchStatusUpdated { repo, ci: None },
+                    ));
+                    return Ok(());
+                };
+
+                let status = client
+                    .fetch_ci_status(&repo.org, &repo.repo, &head_sha)
+                    .await
+                    .with_context(|| format!("Failed to fetch CI status of {}", repo.branch))?;
+                dispatcher.dispatch(Action::PullRequest(
+                    PullRequestAction::BranchStatusUpdated {
+                        repo,
+                        ci: Some(status.state),
+                    },
+                ));
+                Ok(())
+            });
+    }
+
+    /// Count the PRs opened and merged in the repository over the last week
+    ///
+    /// Two search counts in the background lane. Without search access the
+    /// header simply shows no figures.
+    fn fetch_pulse(&self, repo: &Repository, dispatcher: &Dispatcher) {
+        let since = (chrono::Utc::now() - chrono::TimeDelta::days(PULSE_DAYS)).format("%Y-%m-%d");
+        let opened = repo_search_query(&repo.org, &repo.repo, &format!("created:>={}", since));
+        let merged = repo_search_query(&repo.org, &repo.repo, &format!("merged:>={}", since));
+        let repo_for_pulse = repo.clone();
+        self.github.task(repo, dispatcher).background().spawn(
+            move |client, dispatcher| async move {
+                let repo = repo_for_pulse;
+                let counts = async {
+                    let opened = client.count_search(&opened).await?;
+                    let merged = client.count_search(&merged).await?;
+                    anyhow::Ok(RepoPulse { opened, merged })
+                };
+                match counts.await {
+                    Ok(pulse) => {
+                        dispatcher.dispatch(Action::PullRequest(PullRequestAction::PulseUpdated {
+                            repo,
+                            pulse,
+                        }))
+                    }
+                    Err(e) => {
+                        log::debug!("Failed to count PRs of {}/{}: {:#}", repo.org, repo.repo, e)
+                    }
+                }
+                Ok(())
+            },
+        );
+    }
+
+    /// Drop the cached responses of a removed repository
+    ///
+    /// Kept while another tab tracks the same repository with the same
+    /// account (e.g. a different branch), which still reads them.
+    fn invalidate_repo_cache(&self, repo: &Repository, state: &AppState) {
+        let shared =
+            state.main_view.repositories.iter().any(|other| {
+                other != repo && other.id() == repo.id() && other.profile == repo.profile
+            });
+        if shared {
+            return;
+        }
+        // Keys are `METHOD:{namespace}/repos/...`, the colon anchors the namespace
+        let pattern = format!(
+            ":{}/repos/{}/{}/",
+            gh_client::cache_namespace(repo.host.as_deref(), repo.profile.as_deref()),
+            repo.org,
+            repo.repo
+        );
+        log::info!("Invalidating cached responses matching {}", pattern);
+        self.github
+            .cache
+            .lock()
+            .unwrap()
+            .invalidate_pattern(&pattern);
+    }
+
+    /// Show the cached PR list of a repository without any request
+    ///
+    /// Dispatches it as provisional `Loaded` result, returning whether there
+    /// was one.
+    fn show_cached_prs(
+        &self,
+        repo: &Repository,
+        pr_state: PrStateFilter,
+        dispatcher: &Dispatcher,
+    ) -> bool {
+        let cached = {
+            let cache = self.github.cache.lock().unwrap();
+            gh_client::peek_pull_requests(
+                &cache,
+                &gh_client::cache_namespace(repo.host.as_deref(), repo.profile.as_deref()),
+                &repo.org,
+                &repo.repo,
+                None,
+                pr_state,
+            )
+        };
+        let Some(cached) = cached else {
+            return false;
+        };
+
+        let prs = retain_stacked_on(
+            cached.prs.into_iter().map(convert_to_domain_pr).collect(),
+            &repo.branch,
+        );
+        log::info!(
+            "PrLoad: Showing {} cached PRs for {}/{} while refreshing",
+            prs.len(),
+            repo.org,
+            repo.repo
+        );
+        dispatcher.dispatch(Action::PullRequest(PullRequestAction::Loaded {
+            repo: repo.clone(),
+            prs,
+            from_cache: Some(cached.cached_at.with_timezone(&chrono::Local)),
+        }));
+        true
+    }
+
+    /// Handle loading PRs for a repository
+    fn handle_pr_load(
+        &self,
+        repo_idx: usize,
+        state: &AppState,
+        dispatcher: &Dispatcher,
+        force_refresh: bool,
+    ) -> bool {
+        log::info!(
+            "PrLoad({}) received, repos in state: {}, force_refresh: {}",
+            repo_idx,
+            state.main_view.repositories.len(),
+            force_refresh
+        );
+
+        // Get the repository at this index
+        let Some(repo) = state.main_view.repositories.get(repo_idx) else {
+            log::warn!(
+                "PrLoad: Repository at index {} not found (state has {} repos), will retry",
+                repo_idx,
+                state.main_view.repositories.len()
+            );
+            return true;
+        };
+
+        self.handle_pr_load_2(repo, state, dispatcher, force_refresh, false)
+    }
+
+    fn handle_pr_load_2(
+        &self,
+        repo: &Repository,
+        state: &AppState,
+        dispatcher: &Dispatcher,
+        force_refresh: bool,
+        in_background: bool,
+    ) -> bool {
+        let repo_data = state
+            .main_view
+            .repositories
+            .iter()
+            .position(|r| r.id() == repo.id())
+            .and_then(|idx| state.main_view.repo_data.get(&idx));
+
+        // Inaccessible repositories are retried rarely, unless asked to (Ctrl+r)
+        let inaccessible = repo_data.and_then(|data| data.inaccessible.as_ref());
+        if let Some(inaccessible) = inaccessible {
+            if !force_refresh && !inaccessible_retry_due(inaccessible, chrono::Local::now()) {
+                log::debug!(
+                    "PrLoad: {}/{} is inaccessible, skipping until the next retry",
+                    repo.org,
+                    repo.repo
+                );
+                return false; // Keep the error state
+            }
+        }
+        let inaccessible = inaccessible.is_some();
+
+        log::info!("PrLoad: Loading PRs for {}/{}", repo.org, repo.repo);
+        self.fetch_branch_status(repo, dispatcher, force_refresh);
+
+        let pr_state = repo_data.map(|data| data.pr_state).unwrap_or_default();
+        // Never loaded yet (startup): show the cached list while fetching a fresh one
+        let never_loaded = repo_data.is_none_or(|data| data.last_updated.is_none());
+        let force_refresh =
+            force_refresh || (never_loaded && self.show_cached_prs(repo, pr_state, dispatcher));
+        // Hosts without GraphQL review threads cannot count comments
+        let comment_counts = state.main_view.host_supports(repo, Feature::ReviewThreads);
+        let repo = repo.clone();
+        let dispatcher = dispatcher.clone();
+        let clients = Arc::clone(&self.github.clients);
+
+        // Spawn async task to load PRs
+        let mode = if force_refresh {
+            "force refresh"
+        } else {
+            "cached"
+        };
+        log::info!(
+            "Spawning async task to load PRs for {}/{} ({})",
+            repo.org,
+            repo.repo,
+            mode
+        );
+
+        let load = async move {
+            log::info!(
+                "Async task started: Loading PRs for {}/{}",
+                repo.org,
+                repo.repo
+            );
+
+            // Get client for this repository's host
+            let client = match clients.client(&repo, force_refresh).await {
+                Ok(c) => c,
+                Err(e) => {
+                    log::error!("Failed to get client for host {:?}: {}", repo.host, e);
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::error(
+                        format!("Failed to connect to GitHub: {}", e),
+                        "Load",
+                    )));
+                    dispatcher.dispatch(Action::PullRequest(PullRequestAction::LoadError {
+                        repo,
+                        error: e.to_string(),
+                        status: None,
+                    }));
+                    return;
+                }
+            };
+
+            // Fetch PRs of all branches so PRs stacked on other PRs are included
+            match client
+                .fetch_pull_requests(&repo.org, &repo.repo, None, pr_state)
+                .await
+            {
+                Ok(prs) => {
+                    let domain_prs: Vec<Pr> = retain_stacked_on(
+                        prs.into_iter().map(convert_to_domain_pr).collect(),
+                        &repo.branch,
+                    );
+                    log::info!(
+                        "Loaded {} PRs for {}/{}",
+                        domain_prs.len(),
+                        repo.org,
+                        repo.repo
+                    );
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+                        format!(
+                            "Loaded {} PRs from {}/{}",
+                            domain_prs.len(),
+                            repo.org,
+                            repo.repo
+                        ),
+                        "Load",
+                    )));
+
+                    // First dispatch Loaded to populate repo_data.prs
+                    dispatcher.dispatch(Action::PullRequest(PullRequestAction::Loaded {
+                        repo: repo.clone(),
+                        prs: domain_prs.clone(),
+                        from_cache: None,
+                    }));
+
+                    // Then trigger CI status checks for each open PR (background fetch)
+                    // This must come AFTER Loaded so the PRs exist when BuildStatusUpdated arrives
+                    let open_prs: Vec<Pr> = domain_prs
+                        .iter()
+                        .filter(|pr| pr.state == PrState::Open)
+                        .cloned()
+                        .collect();
+                    // A forced refresh must not be answered from the cache by these either
+                    dispatch_ci_status_checks(&repo, &open_prs, &dispatcher, force_refresh);
+
+                    // Also trigger background fetch for PR stats (additions/deletions)
+                    dispatch_pr_stats_fetch(
+                        &repo,
+                        &domain_prs,
+                        &dispatcher,
+                        client,
+                        comment_counts,
+                    );
+                }
+                Err(e) => {
+                    log::error!("Failed to load PRs for {}/{}: {}", repo.org, repo.repo, e);
+                    let status = github_status(&e);
+                    let anonymous = clients.is_anonymous(&repo).await;
+                    let error = load_error_message(&repo, anonymous, status, &e);
+                    // The tab already shows why an inaccessible repository failed
+                    if !inaccessible {
+                        dispatcher.dispatch(Action::StatusBar(StatusBarAction::error(
+                            format!("Failed to load PRs: {}", error),
+                            "Load",
+                        )));
+                    }
+                    dispatcher.dispatch(Action::PullRequest(PullRequestAction::LoadError {
+                        repo,
+                        error,
+                        status,
+                    }));
+                }
+            }
+        };
+        // Tabs loaded before they are shown leave the budget to the shown one
+        if in_background {
+            self.github.runtime.spawn(background(load));
+        } else {
+            self.github.runtime.spawn(load);
+        }
+
+        true // Let action pass through to reducer (to set loading state)
+    }
+
+    /// Load the PRs of inaccessible repositories again once their retry is due
+    ///
+    /// A successful load clears the marker, so access that is granted again
+    /// is picked up without user action.
+    fn retry_inaccessible_repos(&self, state: &AppState, dispatcher: &Dispatcher) {
+        let now = chrono::Local::now();
+        for (idx, repo) in state.main_view.repositories.iter().enumerate() {
+            let due = state
+                .main_view
+                .repo_data
+                .get(&idx)
+                .and_then(|data| data.inaccessible.as_ref())
+                .is_some_and(|inaccessible| inaccessible_retry_due(inaccessible, now));
+            if due {
+                log::info!(
+                    "Retrying inaccessible repository {}/{}",
+                    repo.org,
+                    repo.repo
+                );
+                dispatcher.dispatch(Action::Repository(RepositoryAction::LoadRepositoryData(
+                    repo.clone(),
+                )));
+            }
+        }
+    }
+
+    /// Refresh the PR lists of the tabs next to the current one while idle
+    ///
+    /// Runs in the background request lane and only with budget headroom, so
+    /// it never delays user-initiated requests. Results go through the normal
+    /// cached path and land in `repo_data` without any loading state.
+    fn prefetch_neighbors(&self, state: &AppState, dispatcher: &Dispatcher) {
+        if !self.github.has_default_client() {
+            return;
+        }
+
+        let count = state.main_view.repositories.len();
+        if count < 2 {
+            return;
+        }
+        let current = state.main_view.selected_repository;
+        let mut neighbors = vec![(current + 1) % count, (current + count - 1) % count];
+        neighbors.dedup();
+
+        for idx in neighbors {
+            let (Some(repo), Some(data)) = (
+                state.main_view.repositories.get(idx),
+                state.main_view.repo_data.get(&idx),
+            ) else {
+                continue;
+            };
+            let recently_updated = data
+                .last_updated
+                .is_some_and(|t| chrono::Local::now() - t < PREFETCH_MIN_AGE);
+            if data.loading_state != LoadingState::Loaded
+                || data.search.is_some()
+                || recently_updated
+            {
+                continue;
+            }
+
+            let repo = repo.clone();
+            let pr_state = data.pr_state;
+            let dispatcher = dispatcher.clone();
+            let client_manager = Arc::clone(&self.github.client_manager);
+
+            self.github.runtime.spawn(background(async move {
+                let client = {
+                    let mut manager = client_manager.lock().await;
+                    match manager
+                        .clone_client(repo.host.as_deref(), repo.profile.as_deref())
+                        .await
+                    {
+                        Ok(c) => c,
+                        Err(_) => return,
+                    }
+                };
+                if !RequestBudget::global().has_headroom(client.inner().inner().budget_host()) {
+                    log::debug!(
+                        "Prefetch: skipping {}/{}, request budget is low",
+                        repo.org,
+                        repo.repo
+                    );
+                    return;
+                }
+
+                log::debug!("Prefetch: refreshing PRs of {}/{}", repo.org, repo.repo);
+                match client
+                    .fetch_pull_requests(&repo.org, &repo.repo, None, pr_state)
+                    .await
+                {
+                    Ok(prs) => {
+                        let prs = retain_stacked_on(
+                            prs.into_iter().map(convert_to_domain_pr).collect(),
+                            &repo.branch,
+                        );
+                        log::debug!("Prefetch: {} PRs for {}/{}", prs.len(), repo.org, repo.repo);
+                        dispatcher.dispatch(Action::PullRequest(PullRequestAction::Prefetched {
+                            repo,
+                            prs,
+                        }));
+                    }
+                    Err(e) => {
+                        log::debug!("Prefetch of {}/{} failed: {:#}", repo.org, repo.repo, e);
+                    }
+                }
+            }));
+        }
+    }
+
+    /// Fetch a page of search results for the current repository
+    fn fetch_search_page(&self, query: &str, page: u32, state: &AppState, dispatcher: &Dispatcher) {
+        let Some(repo) = state
+            .main_view
+            .repositories
+            .get(state.main_view.selected_repository)
+            .cloned()
+        else {
+            return;
+        };
+        let query = query.to_string();
+        let label = match page {
+            1 => format!("Searching for '{}'", query),
+            page => format!("Loading page {} for '{}'", page, query),
+        };
+        let failed_repo = repo.clone();
+        self.github
+            .task(&repo, dispatcher)
+            .operation(label, "Search")
+            .report("Search failed", "Search")
+            .on_failure(move |error| {
+                Action::PrSearch(PrSearchAction::Failed {
+                    repo: failed_repo,
+                    error,
+                })
+            })
+            .spawn(move |client, dispatcher| async move {
+                let results = client
+                    .search_pull_requests_in_repo(&repo.org, &repo.repo, &query, page)
+                    .await?;
+                log::info!(
+                    "Search '{}' in {}/{}: page {} of {} results",
+                    query,
+                    repo.org,
+                    repo.repo,
+                    page,
+                    results.total_count
+                );
+                if results.incomplete {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        "Search timed out on GitHub, results may be incomplete",
+                        "Search",
+                    )));
+                }
+                dispatcher.dispatch(Action::PrSearch(PrSearchAction::ResultsLoaded {
+                    repo,
+                    query,
+                    page,
+                    total_count: results.total_count,
+                    incomplete: results.incomplete,
+                    prs: results
+                        .items
+                        .into_iter()
+                        .map(convert_to_domain_pr)
+                        .collect(),
+                }));
+                if page == 1 {
+                    dispatcher.dispatch(Action::PrSearch(PrSearchAction::HydrateCursor));
+                }
+                Ok(())
+            });
+    }
+
+    /// Fetch full details of the search result under the cursor, and the
+    /// next page of results once the cursor reaches the last row
+    fn hydrate_search_cursor(&self, state: &AppState, dispatcher: &Dispatcher) {
+        let repo_idx = state.main_view.selected_repository;
+        let Some(repo) = state.main_view.repositories.get(repo_idx).cloned() else {
+            return;
+        };
+        let Some(data) = state.main_view.repo_data.get(&repo_idx) else {
+            return;
+        };
+        let Some(ref search) = data.search else {
+            return;
+        };
+
+        if data.selected_pr + 1 >= data.prs.len() && search.has_more() && !search.loading {
+            dispatcher.dispatch(Action::PrSearch(PrSearchAction::LoadMore));
+        }
+
+        // Search results lack the head SHA and mergeable state
+        let Some(pr) = data.prs.get(data.selected_pr) else {
+            return;
+        };
+        if !pr.head_sha.is_empty() || search.hydrated.contains(&pr.number) {
+            return;
+        }
+        let pr_number = pr.number;
+        self.github
+            .task(&repo, dispatcher)
+            .spawn(move |client, dispatcher| async move {
+                let pr = client
+                    .fetch_pull_request(&repo.org, &repo.repo, pr_number as u64)
+                    .await
+                    .with_context(|| format!("Failed to fetch details of PR #{}", pr_number))?;
+                let pr = convert_to_domain_pr(pr);
+                let is_open = pr.state == PrState::Open;
+                dispatcher.dispatch(Action::PrSearch(PrSearchAction::ResultHydrated {
+                    repo: repo.clone(),
+                    pr: Box::new(pr.clone()),
+                }));
+                if is_open {
+                    dispatch_ci_status_checks(&repo, &[pr], &dispatcher, false);
+                }
+                Ok(())
+            });
+    }
+
+    /// Fetch the current status of watched PRs in the background
+    fn check_watched_prs(
+        &self,
+        watched: Vec<WatchedPr>,
+        state: &AppState,
+        dispatcher: &Dispatcher,
+    ) {
+        if !self.github.has_default_client() {
+            return;
+        }
+
+        for pr in watched {
+            // Use the account of a matching tracked repository, else the configured one
+            let profile = state
+                .main_view
+                .repositories
+                .iter()
+                .find(|repo| repo.contains_watched(&pr))
+                .map(|repo| repo.profile.clone())
+                .unwrap_or_else(|| {
+                    state
+                        .app_config
+                        .profile_for(&pr.org, &pr.repo)
+                        .map(String::from)
+                });
+            let dispatcher = dispatcher.clone();
+            let client_manager = Arc::clone(&self.github.client_manager);
+
+            self.github.runtime.spawn(background(async move {
+                let client = {
+                    let mut manager = client_manager.lock().await;
+                    match manager
+                        .clone_client(pr.host.as_deref(), profile.as_deref())
+                        .await
+                    {
+                        Ok(c) => c.with_mode(CacheMode::WriteOnly),
+                        Err(e) => {
+                            log::warn!("Cannot check watched PR {}: {}", pr, e);
+                            return;
+                        }
+                    }
+                };
+
+                let details = match client
+                    .fetch_pull_request(&pr.org, &pr.repo, pr.number)
+                    .await
+                {
+                    Ok(details) => details,
+                    Err(e) => {
+                        log::warn!("Failed to check watched PR {}: {:#}", pr, e);
+                        return;
+                    }
+                };
+
+                // CI and reviews only matter while the PR is open
+                let (ci, review) = if details.state == PrState::Open {
+                    let ci = client
+                        .fetch_ci_status(&pr.org, &pr.repo, &details.head_sha)
+                        .await
+                        .map(|status| status.state)
+                        .ok();
+                    let review = client
+                        .fetch_review_decision(&pr.org, &pr.repo, pr.number)
+                        .await
+                        .unwrap_or(details.review_decision);
+                    (ci, review)
+                } else {
+                    (None, details.review_decision)
+                };
+
+                dispatcher.dispatch(Action::WatchList(WatchListAction::StatusFetched {
+                    pr,
+                    status: WatchStatus {
+                        title: details.title,
+                        state: details.state,
+                        ci,
+                        review,
+                    },
+                }));
+            }));
+        }
+    }
+
+    /// Report transitions of a watched PR and prune it once merged or closed
+    fn report_watch_status(
+        pr: &WatchedPr,
+        status: &WatchStatus,
+        state: &AppState,
+        dispatcher: &Dispatcher,
+    ) {
+        let previous = state
+            .main_view
+            .watch_list
+            .get(pr)
+            .and_then(|entry| entry.status.as_ref());
+
+        if let Some(previous) = previous {
+            for transition in status.transitions(previous) {
+                let message = format!("Watched {} {}", pr, transition.description());
+                let action = if transition.is_bad() {
+                    StatusBarAction::warning(message, "Watch")
+                } else {
+                    StatusBarAction::success(message, "Watch")
+                };
+                dispatcher.dispatch(Action::StatusBar(action));
+            }
+        }
+
+        if status.is_done() {
+            let state_label = if status.state == PrState::Merged {
+                "merged"
+            } else {
+                "closed"
+            };
+            dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+                format!("{} was {}, removed from watch list", pr, state_label),
+                "Watch",
+            )));
+            dispatcher.dispatch(Action::WatchList(WatchListAction::Remove(pr.clone())));
+        }
+    }
+}
+
+impl Middleware<Action, AppState> for PrLoadMiddleware {
+    fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        match action {
+            // Background refreshes would only fail during a GitHub incident
+            Action::Event(Event::RefreshDue) if state.status_bar.incident.is_some() => true,
+
+            Action::Event(Event::RefreshDue) => {
+                dispatcher.dispatch(Action::WatchList(WatchListAction::Check));
+                self.retry_inaccessible_repos(state, dispatcher);
+                true // Let action pass through
+            }
+
+            Action::WatchList(WatchListAction::Check) => {
+                let watched = state.main_view.watch_list.watched();
+                log::debug!("Checking {} watched PRs", watched.len());
+                self.check_watched_prs(watched, state, dispatcher);
+                false // Consume action
+            }
+
+            Action::WatchList(WatchListAction::StatusFetched { pr, status }) => {
+                Self::report_watch_status(pr, status, state, dispatcher);
+                true // Let reducer store the status
+            }
+
+            Action::WatchList(WatchListAction::RemoveSelected) => {
+                if let Some(entry) = state.main_view.watch_list.selected_entry() {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+                        format!("Stopped watching {}", entry.pr),
+                        "Watch",
+                    )));
+                    dispatcher
+                        .dispatch(Action::WatchList(WatchListAction::Remove(entry.pr.clone())));
+                }
+                false // Consume action
+            }
+
+            // Opening the peek popup fetches the description if the list had none
+            Action::PullRequest(PullRequestAction::TogglePeek) => {
+                if state.main_view.peek.is_none() {
+                    self.fetch_peek_body(state, dispatcher);
+                }
+                true // Let reducer open or close the popup
+            }
+
+            // Watching a PR checks it right away (before the reducer adds it)
+            Action::PullRequest(PullRequestAction::ToggleWatch) => {
+                let repo_idx = state.main_view.selected_repository;
+                let watched = state.main_view.repositories.get(repo_idx).and_then(|repo| {
+                    let data = state.main_view.repo_data.get(&repo_idx)?;
+                    let pr = data.prs.get(data.selected_pr)?;
+                    Some(repo.watched_pr(pr.number))
+                });
+                if let Some(watched) = watched {
+                    let message = if state.main_view.watch_list.contains(&watched) {
+                        format!("Stopped watching {}", watched)
+                    } else {
+                        self.check_watched_prs(vec![watched.clone()], state, dispatcher);
+                        format!("Watching {}", watched)
+                    };
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(message, "Watch")));
+                }
+                true // Let reducer toggle the watch
+            }
+
+            Action::Repository(RepositoryAction::LoadRepositoryData(repo)) => {
+                self.resolve_account_login(repo, state, dispatcher);
+                self.resolve_host_capabilities(repo, state, dispatcher);
+                dispatcher.dispatch(Action::PullRequest(PullRequestAction::FetchPulse));
+                self.handle_pr_load_2(repo, state, dispatcher, false, false)
+            }
+
+            // The pulse is fetched once the tab is shown
+            Action::Repository(RepositoryAction::PreloadRepositoryData(repo)) => {
+                self.resolve_account_login(repo, state, dispatcher);
+                self.resolve_host_capabilities(repo, state, dispatcher);
+                self.handle_pr_load_2(repo, state, dispatcher, false, true)
+            }
+
+            Action::Repository(RepositoryAction::HostCapabilitiesResolved {
+                host,
+                capabilities,
+            }) => {
+                // Explain once per host why some features are unavailable
+                let unsupported = capabilities.unsupported();
+                if !unsupported.is_empty() && !state.main_view.host_capabilities.contains_key(host)
+                {
+                    let features = unsupported
+                        .iter()
+                        .map(|feature| feature.label())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        format!("{} does not support {}", host, features),
+                        "Capabilities",
+                    )));
+                }
+                true
+            }
+
+            Action::Repository(RepositoryAction::RefreshRepositoryData(repo)) => {
+                self.handle_pr_load_2(repo, state, dispatcher, true, false)
+            }
+
+            Action::Repository(RepositoryAction::RemoveRepository(repo)) => {
+                self.invalidate_repo_cache(repo, state);
+                true // Let reducer remove it
+            }
+
+            // Handle PR load start - actually fetch the PRs
+            Action::PullRequest(PullRequestAction::LoadStart { repo }) => {
+                self.handle_pr_load_2(repo, state, dispatcher, false, false)
+            }
+
+            // Switching the PR state reloads the list (after the reducer updated it)
+            Action::PullRequest(PullRequestAction::CycleStateFilter) => {
+                let repo_idx = state.main_view.selected_repository;
+                if let Some(repo) = state.main_view.repositories.get(repo_idx) {
+                    let next = state
+                        .main_view
+                        .repo_data
+                        .get(&repo_idx)
+                        .map(|data| data.next_pr_state())
+                        .unwrap_or(PrStateFilter::Closed);
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+                        format!("Showing {} PRs of {}", next.as_str(), repo.display_name()),
+                        "Filter",
+                    )));
+                    dispatcher.dispatch(Action::PullRequest(PullRequestAction::LoadStart {
+                        repo: repo.clone(),
+                    }));
+                }
+                true // Let reducer switch the state
+            }
+
+            // Reviewers are fetched once the fresh list is in, cached lists
+            // are followed by a fresh load anyway
+            Action::PullRequest(PullRequestAction::Loaded {
+                repo,
+                prs,
+                from_cache: None,
+            })
+            | Action::PullRequest(PullRequestAction::Prefetched { repo, prs }) => {
+                let main_view = &state.main_view;
+                let sorted_by_attention = main_view
+                    .repositories
+                    .iter()
+                    .position(|r| r == repo)
+                    .is_some_and(|repo_idx| main_view.sort_of(repo_idx) == PrSort::Attention);
+                if sorted_by_attention {
+                    self.fetch_reviewers(repo, prs, state, dispatcher, true);
+                }
+                true
+            }
+
+            // Switching to the attention sort needs the reviewers
+            Action::PullRequest(PullRequestAction::CycleSort) => {
+                let main_view = &state.main_view;
+                let repo_idx = main_view.selected_repository;
+                let next = main_view.sort_of(repo_idx).next();
+                if let (Some(repo), Some(data)) = (
+                    main_view.repositories.get(repo_idx),
+                    main_view.repo_data.get(&repo_idx),
+                ) {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+                        format!("Sorting {} by {}", repo.display_name(), next.label()),
+                        "Sort",
+                    )));
+                    if next == PrSort::Attention {
+                        self.fetch_reviewers(repo, &data.prs, state, dispatcher, false);
+                    }
+                }
+                true // Let reducer sort the PRs
+            }
+
+            // Handle PR refresh request (force refresh - bypass cache)
+            Action::PullRequest(PullRequestAction::Refresh) => {
+                let repo_idx = state.main_view.selected_repository;
+                // In search mode, refresh re-runs the search
+                if let Some(search) = state
+                    .main_view
+                    .repo_data
+                    .get(&repo_idx)
+                    .and_then(|data| data.search.as_ref())
+                {
+                    dispatcher.dispatch(Action::PrSearch(PrSearchAction::Search {
+                        query: search.query.clone(),
+                    }));
+                    return false;
+                }
+                self.handle_pr_load(repo_idx, state, dispatcher, true)
+            }
+
+            // The pulse is fetched once the tab is shown, tabs left out at
+            // startup (`startup_load`) are loaded then
+            Action::PullRequest(
+                switch
+                @ (PullRequestAction::RepositoryNext | PullRequestAction::RepositoryPrevious),
+            ) => {
+                let main_view = &state.main_view;
+                let step = match switch {
+                    PullRequestAction::RepositoryNext => next_index,
+                    _ => previous_index,
+                };
+                let target = step(
+                    main_view.selected_repository,
+                    main_view.repositories.len(),
+                    main_view.tab_cycling,
+                );
+                let never_requested = |idx: &usize| {
+                    main_view
+                        .repo_data
+                        .get(idx)
+                        .is_none_or(|data| data.loading_state == LoadingState::Idle)
+                };
+                if let Some(repo) = target
+                    .filter(never_requested)
+                    .and_then(|idx| main_view.repositories.get(idx))
+                {
+                    dispatcher.dispatch(Action::Repository(RepositoryAction::LoadRepositoryData(
+                        repo.clone(),
+                    )));
+                }
+                dispatcher.dispatch(Action::PullRequest(PullRequestAction::FetchPulse));
+                true // Let reducer switch the tab
+            }
+
+            Action::PullRequest(PullRequestAction::FetchPulse) => {
+                let main_view = &state.main_view;
+                let repo_idx = main_view.selected_repository;
+                let Some(repo) = main_view.repositories.get(repo_idx) else {
+                    return false;
+                };
+                let due = main_view
+                    .repo_data
+                    .get(&repo_idx)
+                    .is_none_or(|data| data.pulse_due(chrono::Local::now()));
+                if !due || state.status_bar.incident.is_some() {
+                    return false;
+                }
+                self.fetch_pulse(repo, dispatcher);
+                true // Let reducer remember the request
+            }
+
+            // Moving the cursor over search results fetches their details
+            action if is_cursor_move(action) => {
+                let in_search = state
+                    .main_view
+                    .repo_data
+                    .get(&state.main_view.selected_repository)
+                    .is_some_and(|data| data.search.is_some());
+                if in_search {
+                    dispatcher.dispatch(Action::PrSearch(PrSearchAction::HydrateCursor));
+                }
+                true // Let reducer move the cursor
+            }
+
+            Action::PrSearch(PrSearchAction::SubmitInput) => {
+                let query = state
+                    .pr_search_input
+                    .as_ref()
+                    .map(|input| input.query.trim().to_string())
+                    .unwrap_or_default();
+                // Local filters (`update:major`) are applied by PullRequestMiddleware
+                if !query.is_empty() && PrFilter::parse(&query).is_none() {
+                    dispatcher.dispatch(Action::PrSearch(PrSearchAction::Search { query }));
+                }
+                true // Let reducer close the popup
+            }
+
+            Action::PrSearch(PrSearchAction::Search { query }) => {
+                self.fetch_search_page(query, 1, state, dispatcher);
+                true // Let reducer enter search mode
+            }
+
+            Action::PrSearch(PrSearchAction::LoadMore) => {
+                if let Some(search) = state
+                    .main_view
+                    .repo_data
+                    .get(&state.main_view.selected_repository)
+                    .and_then(|data| data.search.as_ref())
+                {
+                    if search.loading || !search.has_more() {
+                        return false;
+                    }
+                    self.fetch_search_page(&search.query, search.page + 1, state, dispatcher);
+                }
+                true // Let reducer mark the page as loading
+            }
+
+            Action::PrSearch(PrSearchAction::HydrateCursor) => {
+                self.hydrate_search_cursor(state, dispatcher);
+                true // Let reducer remember the fetched PR
+            }
+
+            Action::Event(Event::UserIdle) => {
+                if state.status_bar.incident.is_none() {
+                    self.prefetch_neighbors(state, dispatcher);
+                }
+                true
+            }
+
+            Action::ConflictHelper(ConflictHelperAction::Open { repo, pr }) => {
+                self.compare_for_conflicts(repo, pr, dispatcher);
+                true // Let reducer open the popup
+            }
+
+            Action::BranchPicker(BranchPickerAction::Open) => {
+                self.open_branch_picker(state, dispatcher);
+                false
+            }
+
+            // Typing a branch that is not listed still switches to it
+            Action::BranchPicker(BranchPickerAction::Select) => {
+                if let Some(picker) = state.branch_picker.as_ref() {
+                    if let Some(branch) = picker.choice().filter(|b| *b != picker.repo.branch) {
+                        dispatcher.dispatch(Action::Repository(RepositoryAction::SwitchBranch {
+                            repo: picker.repo.clone(),
+                            branch,
+                        }));
+                    }
+                }
+                true // Let reducer close the popup
+            }
+
+            Action::ReleaseNotes(ReleaseNotesAction::Prepare) => {
+                self.prepare_release_notes(state, dispatcher);
+                false
+            }
+
+            Action::ReleaseNotes(ReleaseNotesAction::Generate { repo, since }) => {
+                self.generate_release_notes(repo, since, state, dispatcher);
+                true // Let reducer open the popup
+            }
+
+            Action::DiffViewer(DiffViewerAction::FetchBlame) => {
+                self.fetch_blame(state, dispatcher);
+                true // Let the reducer mark the request as taken
+            }
+
+            Action::DiffViewer(DiffViewerAction::Open) => {
+                let repo_idx = state.main_view.selected_repository;
+
+                // Get repository info
+                let Some(repo) = state.main_view.repositories.get(repo_idx).cloned() else {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        "No repository selected",
+                        "Diff Viewer",
+                    )));
+                    return false;
+                };
+
+                // Get repository data
+                let Some(repo_data) = state.main_view.repo_data.get(&repo_idx) else {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        "No repository data loaded",
+                        "Diff Viewer",
+                    )));
+                    return false;
+                };
+
+                // Get current PR
+                let Some(pr) = repo_data.prs.get(repo_data.selected_pr) else {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        "No PR selected",
+                        "Diff Viewer",
+                    )));
+                    return false;
+                };
+
+                // Capture PR context
+                let pr_number = pr.number as u64;
+                let pr_title = pr.title.clone();
+                let head_sha = pr.head_sha.clone();
+                let base_sha = String::new(); // We'll get this from the API
+                let repo_org = repo.org.clone();
+                let repo_name = repo.repo.clone();
+                let dispatcher = dispatcher.clone();
+                let clients = Arc::clone(&self.github.clients);
+
+                // Dispatch loading state
+                dispatcher.dispatch(Action::DiffViewer(DiffViewerAction::LoadStart));
+                let operation = dispatcher
+                    .start_operation(format!("Loading diff for PR #{}", pr_number), "Diff Viewer");
+
+                // Spawn async task to fetch diff and comments
+                self.github.runtime.spawn(async move {
+                    let _operation = operation;
+                    // Get client for this repository's host
+                    let client = match clients.client(&repo, false).await {
+                        Ok(client) => client,
+                        Err(e) => {
+                            log::error!("Failed to get client: {}", e);
+                            dispatcher.dispatch(Action::StatusBar(StatusBarAction::error(
+                                format!("Failed to load diff: {}", e),
+                                "Diff Viewer",
+                            )));
+                            dispatcher.dispatch(Action::DiffViewer(DiffViewerAction::LoadError(
+                                e.to_string(),
+                            )));
+                            return;
+                        }
+                    };
+
+                    // Fetch diff
+                    let diff_result: Result<String, String> = client
+                        .fetch_pull_request_diff(&repo_org, &repo_name, pr_number)
+                        .await
+                        .map_err(|e| format!("{:#}", e));
+
+                    // Fetch comments (non-blocking failure)
+                    let api_comments: Vec<gh_client::ReviewComment> = client
+                        .fetch_review_comments(&repo_org, &repo_name, pr_number)
+                        .await
+                        .unwrap_or_else(|e| {
+                            log::warn!("Failed to fetch review comments: {}", e);
+                            vec![]
+                        });
+
+                    match diff_result {
+                        Ok(diff_text) => {
+                            // Parse the diff
+                            match gh_diff_viewer::parse_unified_diff(
+                                &diff_text, &base_sha, &head_sha,
+                            ) {
+                                Ok(diff) => {
+                                    // Convert API comments to LoadedComment
+                                    let comments: Vec<LoadedComment> = api_comments
+                                        .into_iter()
+                                        .map(|c| LoadedComment {
+                                            github_id: c.id,
+                                            path: c.path,
+                                            line: c.line,
+                                            side: c.side,
+                                            start_line: c.start_line,
+                                            start_side: c.start_side,
+                                            is_file_level: c.is_file_level,
+                                            body: c.body,
+                                        })
+                                        .collect();
+
+                                    dispatcher.dispatch(Action::DiffViewer(
+                                        DiffViewerAction::Loaded {
+                                            diff,
+                                            repo: repo.clone(),
+                                            pr_number,
+                                            pr_title,
+                                            head_sha: head_sha.clone(),
+                                            comments,
+                                        },
+                                    ));
+                                    dispatcher.dispatch(Action::StatusBar(
+                                        StatusBarAction::success(
+                                            format!("Diff loaded for PR #{}", pr_number),
+                                            "Diff Viewer",
+                                        ),
+                                    ));
+                                }
+                                Err(e) => {
+                                    log::error!("Failed to parse diff: {}", e);
+                                    dispatcher.dispatch(Action::DiffViewer(
+                                        DiffViewerAction::LoadError(format!(
+                                            "Failed to parse diff: {}",
+                                            e
+                                        )),
+                                    ));
+                                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::error(
+                                        format!("Failed to parse diff: {}", e),
+                                        "Diff Viewer",
+                                    )));
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            log::error!("Failed to fetch diff: {}", e);
+                            dispatcher.dispatch(Action::DiffViewer(DiffViewerAction::LoadError(
+                                e.clone(),
+                            )));
+                            dispatcher.dispatch(Action::StatusBar(StatusBarAction::error(
+                                format!("Failed to fetch diff: {}", e),
+                                "Diff Viewer",
+                            )));
+                        }
+                    }
+                });
+
+                true // Let action pass through to push view
+            }
+
+            _ => true, // Pass through other actions
+        }
+    }
+}
+
+/// Minimum age of a repository's PR list before the idle prefetcher refreshes it
+const PREFETCH_MIN_AGE: chrono::TimeDelta = chrono::TimeDelta::minutes(2);
+
+/// Search pages of merged PRs listed in release notes (30 PRs each)
+const RELEASE_NOTES_MAX_PAGES: u32 = 10;
+
+/// Time between automatic retries of a repository the token cannot access
+const INACCESSIBLE_RETRY_INTERVAL: chrono::TimeDelta = chrono::TimeDelta::minutes(30);
+
+/// Dispatch background fetch for PR stats (additions/deletions, comments)
+///
+/// The GitHub list PRs endpoint doesn't include additions/deletions nor the
+/// comment breakdown, so we need to fetch them per PR. Rows show each stat
+/// as it arrives; comments only with `comment_counts`.
+fn dispatch_pr_stats_fetch(
+    repo: &Repository,
+    prs: &[Pr],
+    dispatcher: &Dispatcher,
+    client: Arc<dyn GitHubClient>,
+    comment_counts: bool,
+) {
+    for pr in prs {
+        let pr_number = pr.number as u64;
+        let repo = repo.clone();
+        let dispatcher = dispatcher.clone();
+        let client = Arc::clone(&client);
+
+        // Spawn async task for each PR
+        tokio::spawn(background(async move {
+            match client
+                .fetch_pull_request(&repo.org, &repo.repo, pr_number)
+                .await
+            {
+                Ok(pr_details) => {
+                    log::debug!(
+                        "Fetched stats for PR #{}: +{} -{}",
+                        pr_number,
+                        pr_details.additions,
+                        pr_details.deletions
+                    );
+                    dispatcher.dispatch(Action::PullRequest(PullRequestAction::StatsUpdated {
+                        repo: repo.clone(),
+                        pr_number,
+                        additions: pr_details.additions as usize,
+                        deletions: pr_details.deletions as usize,
+                        changed_files: pr_details.changed_files.unwrap_or_default() as usize,
+                    }));
+                }
+                Err(e) => {
+                    log::warn!("Failed to fetch stats for PR #{}: {}", pr_number, e);
+                }
+            }
+
+            if !comment_counts {
+                return;
+            }
+            match client
+                .fetch_comment_counts(&repo.org, &repo.repo, pr_number)
+                .await
+            {
+                Ok(counts) => {
+                    dispatcher.dispatch(Action::PullRequest(
+                        PullRequestAction::CommentCountsUpdated {
+                            repo,
+                            pr_number,
+                            counts,
+                        },
+                    ));
+                }
+                Err(e) => {
+                    // Expected without a token, the cell just stays blank
+                    log::debug!(
+                        "Failed to fetch comment counts for PR #{}: {}",
+                        pr_number,
+                        e
+                    );
+                }
+            }
+        }));
+    }
+}
+
+/// Whether `action` moves the PR cursor (also when repeated)
+fn is_cursor_move(action: &Action) -> bool {
+    match action {
+        Action::PullRequest(
+            PullRequestAction::NavigateNext
+            | PullRequestAction::NavigatePrevious
+            | PullRequestAction::NavigateToTop
+            | PullRequestAction::NavigateToBottom,
+        ) => true,
+        Action::Global(GlobalAction::Repeat { action, .. }) => is_cursor_move(action),
+        _ => false,
+    }
+}
+
+/// Whether an inaccessible repository is due for its next automatic retry
+fn inaccessible_retry_due(
+    inaccessible: &InaccessibleRepo,
+    now: chrono::DateTime<chrono::Local>,
+) -> bool {
+    now - inaccessible.last_attempt >= INACCESSIBLE_RETRY_INTERVAL
+}
+
+/// Error shown for a repository whose PRs could not be loaded
+///
+/// Without a token GitHub hides private repositories behind 404 (or 401),
+/// so point the user to the token setup instead of a bare "Not Found".
+fn load_error_message(
+    repo: &Repository,
+    anonymous: bool,
+    status: Option<u16>,
+    error: &impl std::fmt::Display,
+) -> String {
+    match status {
+        Some(401 | 404) if anonymous => format!(
+            "{}/{} not found without a token - private repositories need a token \
+             (use \"Set GitHub token…\")",
+            repo.org, repo.repo
+        ),
+        _ => error.to_string(),
+    }
+}
+
+/// Convert gh-client PullRequest to domain Pr
+fn convert_to_domain_pr(pr: PullRequest) -> Pr {
+    let mergeable = match pr.mergeable_state {
+        Some(gh_client::types::MergeableState::Clean) => MergeableStatus::Ready,
+        Some(gh_client::types::MergeableState::Behind) => MergeableStatus::NeedsRebase,
+        Some(gh_client::types::MergeableState::Dirty) => MergeableStatus::Conflicted,
+        Some(gh_client::types::MergeableState::Blocked) => MergeableStatus::Blocked,
+        Some(gh_client::types::MergeableState::Unstable) => MergeableStatus::BuildFailed,
+        _ => MergeableStatus::Unknown,
+    };
+
+    Pr {
+        number: pr.number as usize,
+        title: pr.title,
+        body: pr.body.unwrap_or_default(),
+        author: pr.author,
+        comments: pr.comments as usize,
+        comment_counts: None,
+        mergeable,
+        ci_budget: None,
+        ci_checks: CiChecks::default(),
+        needs_rebase: matches!(mergeable, MergeableStatus::NeedsRebase),
+        head_sha: pr.head_sha,
+        head_branch: pr.head_branch,
+        base_branch: pr.base_branch,
+        created_at: pr.created_at,
+        updated_at: pr.updated_at,
+        html_url: pr.html_url,
+        additions: pr.additions as usize,
+        deletions: pr.deletions as usize,
+        changed_files: pr.changed_files.map(|files| files as usize),
+        maturity: pr.maturity,
+        review_decision: pr.review_decision,
+        reviewers: None,
+        state: pr.state,
+        labels: pr.labels,
+        dependency_update: None,
+        head_repo_owner: pr.head_repo_owner,
+        head_repo_name: pr.head_repo_name,
+        is_from_fork: pr.is_from_fork,
+        maintainer_can_modify: pr.maintainer_can_modify,
+        mergeable_state_raw: pr.mergeable_state_raw,
+    }
+    .with_detected_dependency_update()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_error_explains_private_repos_without_token() {
+        let repo = Repository::new("acme", "secret", "main");
+        let message = load_error_message(&repo, true, Some(404), &"Not Found");
+        assert!(message.starts_with("acme/secret not found without a token"));
+
+        // With a token, or for other errors, the original error is kept
+        assert_eq!(
+            load_error_message(&repo, false, Some(404), &"Not Found"),
+            "Not Found"
+        );
+        assert_eq!(load_error_message(&repo, true, None, &"timeout"), "timeout");
+    }
+
+    #[test]
+    fn test_inaccessible_retry_due() {
+        let last_attempt = chrono::Local::now();
+        let inaccessible = InaccessibleRepo {
+            error: "Not Found".to_string(),
+            last_attempt,
+        };
+        assert!(!inaccessible_retry_due(
+            &inaccessible,
+            last_attempt + chrono::TimeDelta::minutes(29)
+        ));
+        assert!(inaccessible_retry_due(
+            &inaccessible,
+            last_attempt + INACCESSIBLE_RETRY_INTERVAL
+        ));
+    }
+
+    #[test]
+    fn test_convert_keeps_unknown_mergeable_state() {
+        let mut pr = crate::middleware::test_clients::pull_request(7, "abc");
+        pr.mergeable_state = Some(gh_client::types::MergeableState::Unknown);
+        pr.mergeable_state_raw = Some("behind_policy".to_string());
+
+        let pr = convert_to_domain_pr(pr);
+        assert_eq!(pr.mergeable, MergeableStatus::Unknown);
+        assert!(!pr.needs_rebase);
+        assert_eq!(pr.mergeable_state_raw.as_deref(), Some("behind_policy"));
+    }
+
+    mod dispatch {
+        use super::*;
+        use crate::domain_models::CommentCounts;
+        use crate::middleware::test_clients::{
+            green, pull_request, repo, run_middleware, state, status_messages, MockGitHubClient,
+        };
+        use crate::state::{RepositoryData, StatusKind};
+        use chrono::TimeZone;
+        use gh_client::types::CiState;
+
+        /// Handle `action` and return every action dispatched until all
+        /// spawned operations finished
+        fn run(client: MockGitHubClient, action: Action) -> (Vec<Action>, Arc<MockGitHubClient>) {
+            run_in(&state(), client, action)
+        }
+
+        /// [`run`] with the given state
+        fn run_in(
+            state: &AppState,
+            client: MockGitHubClient,
+            action: Action,
+        ) -> (Vec<Action>, Arc<MockGitHubClient>) {
+            run_middleware(PrLoadMiddleware::new, state, client, action)
+        }
+
+        #[test]
+        fn test_load_dispatches_loaded_and_follow_ups() {
+            let client = MockGitHubClient {
+                pull_requests: vec![pull_request(7, "abc")],
+                branch_head_sha: Some("def".to_string()),
+                ci_status: Some(green()),
+                comment_counts: Some(CommentCounts {
+                    unresolved_threads: 2,
+                    review_comments: 5,
+                    issue_comments: 1,
+                }),
+                ..Default::default()
+            };
+            let action = Action::PullRequest(PullRequestAction::LoadStart { repo: repo() });
+            let (actions, _) = run(client, action);
+
+            let loaded = actions
+                .iter()
+                .position(|a| matches!(a, Action::PullRequest(PullRequestAction::Loaded { .. })))
+                .expect("PRs are loaded");
+            let Action::PullRequest(PullRequestAction::Loaded {
+                prs, from_cache, ..
+            }) = &actions[loaded]
+            else {
+                unreachable!()
+            };
+            assert_eq!(prs.len(), 1);
+            assert_eq!(prs[0].number, 7);
+            assert!(from_cache.is_none());
+
+            let check = actions
+                .iter()
+                .position(|a| {
+                    matches!(
+                        a,
+                        Action::PullRequest(PullRequestAction::CheckBuildStatus {
+                            pr_number: 7,
+                            head_sha,
+                            force_refresh: false,
+                            ..
+                        }) if head_sha == "abc"
+                    )
+                })
+                .expect("CI status is checked");
+            assert!(loaded < check);
+            assert!(actions.iter().any(|a| matches!(
+                a,
+                Action::PullRequest(PullRequestAction::StatsUpdated {
+                    pr_number: 7,
+                    additions: 10,
+                    deletions: 2,
+                    ..
+                })
+            )));
+            assert!(actions.iter().any(|a| matches!(
+                a,
+                Action::PullRequest(PullRequestAction::CommentCountsUpdated {
+                    pr_number: 7,
+                    counts: CommentCounts {
+                        unresolved_threads: 2,
+                        ..
+                    },
+                    ..
+                })
+            )));
+            assert!(actions.iter().any(|a| matches!(
+                a,
+                Action::PullRequest(PullRequestAction::BranchStatusUpdated {
+                    ci: Some(CiState::Success),
+                    ..
+                })
+            )));
+            assert_eq!(
+                status_messages(&actions),
+                vec![(StatusKind::Info, "Loaded 1 PRs from acme/api".to_string())]
+            );
+        }
+
+        #[test]
+        fn test_load_error() {
+            let client = MockGitHubClient::default().failing("fetch_pull_requests", "timeout");
+            let action = Action::PullRequest(PullRequestAction::LoadStart { repo: repo() });
+            let (actions, _) = run(client, action);
+
+            assert!(actions.iter().any(|a| matches!(
+                a,
+                Action::PullRequest(PullRequestAction::LoadError { error, status: None, .. })
+                    if error == "timeout"
+            )));
+            assert_eq!(
+                status_messages(&actions),
+                vec![(StatusKind::Error, "Failed to load PRs: timeout".to_string())]
+            );
+        }
+
+        #[test]
+        fn test_blame_resolves_the_merge_base_first() {
+            use crate::domain_models::Pr;
+            use crate::state::RepositoryData;
+            use gh_client::types::{BlameRange, Comparison};
+
+            let mut state = state();
+            let pr = Pr::new(7, "Fix", "alice", "head").with_branches("fix", "main");
+            state.main_view.repo_data.insert(
+                0,
+                RepositoryData {
+                    prs: vec![pr],
+                    ..Default::default()
+                },
+            );
+            let diff = gh_diff_viewer::parse_unified_diff(
+                "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,2 +1,2 @@\n fn a() {}\n-fn b() {}\n+fn c() {}\n",
+                "",
+                "head",
+            )
+            .unwrap();
+            state
+                .diff_viewer
+                .load(diff, repo(), 7, "Fix".to_string(), "head".to_string());
+            state.diff_viewer.inner.as_mut().unwrap().show_blame = true;
+
+            let client = MockGitHubClient {
+                comparison: Some(Comparison {
+                    merge_base_sha: "base".to_string(),
+                    ahead_by: 1,
+                    behind_by: 0,
+                    files: vec![],
+                }),
+                blame: Some(vec![BlameRange {
+                    start_line: 1,
+                    end_line: 9,
+                    commit_sha: "old".to_string(),
+                    author: "jdoe".to_string(),
+                    committed_at: chrono::Utc::now(),
+                }]),
+                ..Default::default()
+            };
+            let (actions, client) = run_in(
+                &state,
+                client,
+                Action::DiffViewer(DiffViewerAction::FetchBlame),
+            );
+
+            assert_eq!(
+                client.calls(),
+                vec![
+                    "fetch_compare acme/api main...head",
+                    "fetch_blame acme/api base:src/lib.rs",
+                ]
+            );
+            assert!(matches!(
+                actions.as_slice(),
+                [Action::DiffViewer(DiffViewerAction::BlameLoaded { path, base_sha, entries })]
+                    if path == "src/lib.rs" && base_sha == "base" && entries.len() == 2
+            ));
+        }
+
+        #[test]
+        fn test_reviewers_are_fetched_for_the_attention_sort() {
+            use crate::domain_models::{Pr, PrReviewers};
+            use crate::state::{PrSort, RepositoryData};
+
+            let mut closed = Pr::new(8, "Old", "alice", "sha");
+            closed.state = PrState::Closed;
+            let prs = vec![Pr::new(7, "Fix", "alice", "sha"), closed];
+            let loaded = || {
+                Action::PullRequest(PullRequestAction::Loaded {
+                    repo: repo(),
+                    prs: prs.clone(),
+                    from_cache: None,
+                })
+            };
+            let client = || MockGitHubClient {
+                reviewers: Some(vec![PrReviewers {
+                    number: 7,
+                    requested: vec!["me".to_string()],
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            };
+
+            // Newest first needs no reviewers
+            let (actions, client_used) = run(client(), loaded());
+            assert!(actions.is_empty());
+            assert!(client_used.calls().is_empty());
+
+            let mut state = state();
+            state.main_view.default_sort = PrSort::Attention;
+            let (actions, client_used) = run_in(&state, client(), loaded());
+            assert_eq!(client_used.calls(), vec!["fetch_reviewers acme/api [7]"]);
+            assert!(matches!(
+                actions.as_slice(),
+                [Action::PullRequest(PullRequestAction::ReviewersLoaded { reviewers, .. })]
+                    if reviewers[0].is_requested_from("me")
+            ));
+
+            // Switching to the sort fetches them right away
+            let mut state = self::state();
+            state.main_view.repo_data.insert(
+                0,
+                RepositoryData {
+                    prs,
+                    ..Default::default()
+                },
+            );
+            let cycle = Action::PullRequest(PullRequestAction::CycleSort);
+            let (actions, client_used) = run_in(&state, client(), cycle);
+            assert_eq!(client_used.calls(), vec!["fetch_reviewers acme/api [7]"]);
+            assert_eq!(
+                status_messages(&actions),
+                vec![(
+                    StatusKind::Info,
+                    "Sorting acme/api by attention".to_string()
+                )]
+            );
+        }
+
+        #[test]
+        fn test_pulse_counts_opened_and_merged_prs() {
+            let client = MockGitHubClient {
+                search_counts: vec![("created:", 12), ("merged:", 9)],
+                ..Default::default()
+            };
+            let (actions, client) = run(client, Action::PullRequest(PullRequestAction::FetchPulse));
+
+            assert!(matches!(
+                actions.as_slice(),
+                [Action::PullRequest(PullRequestAction::PulseUpdated {
+                    pulse: RepoPulse {
+                        opened: 12,
+                        merged: 9
+                    },
+                    ..
+                })]
+            ));
+            let calls = client.calls();
+            assert_eq!(calls.len(), 2);
+            assert!(calls[0].starts_with("count_search repo:acme/api is:pr created:>="));
+            assert!(calls[1].starts_with("count_search repo:acme/api is:pr merged:>="));
+        }
+
+        #[test]
+        fn test_pulse_is_kept_and_failures_are_silent() {
+            let mut fetched = state();
+            fetched.main_view.repo_data.insert(
+                0,
+                RepositoryData {
+                    pulse_requested: Some(chrono::Local::now()),
+                    ..Default::default()
+                },
+            );
+            let (actions, client) = run_in(
+                &fetched,
+                MockGitHubClient::default(),
+                Action::PullRequest(PullRequestAction::FetchPulse),
+            );
+            assert!(actions.is_empty());
+            assert!(client.calls().is_empty());
+
+            // e.g. search not allowed for the token
+            let client = MockGitHubClient::default().failing("count_search", "Forbidden");
+            let (actions, _) = run(client, Action::PullRequest(PullRequestAction::FetchPulse));
+            assert!(actions.is_empty());
+        }
+
+        #[test]
+        fn test_release_notes_since_a_tag() {
+            let tagged = chrono::Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+            let mut feature = pull_request(12, "");
+            feature.title = "feat: Dark mode".to_string();
+            let client = MockGitHubClient {
+                ref_dates: [("v1.4.0".to_string(), tagged)].into(),
+                search_results: vec![pull_request(7, ""), feature],
+                ..Default::default()
+            };
+            let since = SinceRef::Tag("v1.4.0".to_string());
+            let (actions, client) = run(
+                client,
+                Action::ReleaseNotes(ReleaseNotesAction::Generate {
+                    repo: repo(),
+                    since: since.clone(),
+                }),
+            );
+
+            assert_eq!(
+                client.calls(),
+                vec![
+                    "fetch_ref_date acme/api v1.4.0",
+                    "search_pull_requests_in_repo acme/api is:merged base:main merged:>2025-01-15T12:00:00Z",
+                ]
+            );
+            let Some((markdown, pr_count)) = actions.iter().find_map(|action| match action {
+                Action::ReleaseNotes(ReleaseNotesAction::Loaded {
+                    markdown, pr_count, ..
+                }) => Some((markdown, pr_count)),
+                _ => None,
+            }) else {
+                panic!("release notes loaded: {:?}", actions);
+            };
+            assert_eq!(*pr_count, 2);
+            assert!(markdown
+                .starts_with("## Changes since v1.4.0\n\n### Features\n\n- feat: Dark mode"));
+
+            // An unknown tag fails the popup
+            let (actions, _) = run(
+                MockGitHubClient::default(),
+                Action::ReleaseNotes(ReleaseNotesAction::Generate {
+                    repo: repo(),
+                    since,
+                }),
+            );
+            assert!(actions.iter().any(|action| matches!(
+                action,
+                Action::ReleaseNotes(ReleaseNotesAction::LoadError { error, .. })
+                    if error.contains("Tag v1.4.0 not found")
+            )));
+        }
+
+        #[test]
+        fn test_branch_picker_loads_and_switches() {
+            let client = MockGitHubClient {
+                branches: Some(vec!["main".to_string(), "release/2.x".to_string()]),
+                ..Default::default()
+            };
+            let (actions, client) = run(client, Action::BranchPicker(BranchPickerAction::Open));
+            assert_eq!(client.calls(), vec!["fetch_branches acme/api"]);
+            assert!(matches!(
+                &actions[0],
+                Action::BranchPicker(BranchPickerAction::Show(shown)) if *shown == repo()
+            ));
+            assert!(actions.iter().any(|action| matches!(
+                action,
+                Action::BranchPicker(BranchPickerAction::Loaded(branches)) if branches.len() == 2
+            )));
+
+            let mut state = state();
+            let mut picker = crate::state::BranchPickerState::new(repo());
+            picker.set_branches(vec!["main".to_string(), "release/2.x".to_string()]);
+            picker.select_next();
+            state.branch_picker = Some(picker);
+            let (actions, _) = run_in(
+                &state,
+                MockGitHubClient::default(),
+                Action::BranchPicker(BranchPickerAction::Select),
+            );
+            assert!(actions.iter().any(|action| matches!(
+                action,
+                Action::Repository(RepositoryAction::SwitchBranch { branch, .. })
+                    if branch == "release/2.x"
+            )));
+        }
+
+        #[test]
+        fn test_release_notes_preset_the_latest_tag() {
+            let client = MockGitHubClient {
+                latest_tag: Some("v1.4.0".to_string()),
+                ..Default::default()
+            };
+            let (actions, _) = run(client, Action::ReleaseNotes(ReleaseNotesAction::Prepare));
+            assert!(actions.iter().any(|action| matches!(
+                action,
+                Action::ConfirmationPopup(ConfirmationPopupAction::Show {
+                    intent: ConfirmationIntent::ReleaseNotes { .. },
+                    default_message,
+                    ..
+                }) if default_message == "v1.4.0"
+            )));
+        }
+    }
+}
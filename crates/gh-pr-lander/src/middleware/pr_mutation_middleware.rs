@@ -0,0 +1,2825 @@
+//! PR Mutation Middleware
+//!
+//! Operations that change pull requests on GitHub:
+//! - Merge (with branch deletion) and policy runs
+//! - Rebase (update branch, whole stacks, dependabot rebase/recreate)
+//! - Reviews (approve, request changes, diff viewer reviews and comments)
+//! - Comments and close, with confirmation popups
+//! - Labels and reviewers (pickers, CODEOWNERS suggestions)
+
+use crate::actions::{
+    Action, ConflictHelperAction, DiffViewerAction, LabelPickerAction, PullRequestAction,
+    RepositoryAction, ReviewerPickerAction, StatusBarAction,
+};
+use crate::audit::{AuditOperation, PendingAudit};
+use crate::command_id::CommandId;
+use crate::dispatcher::{Dispatcher, InFlightGuard, OperationDispatcher};
+use crate::domain_models::{
+    author, conflicts, CodeOwners, MergeableStatus, OperationType, Owner, PolicyStep, Pr, PrStacks,
+    PrState, Repository, CODEOWNERS_PATHS,
+};
+use crate::keymap::Keymap;
+use crate::middleware::client_factory::ClientFactory;
+use crate::middleware::github_context::{
+    reject_inactive_targets, target_prs, target_prs_with_author, GitHubContext,
+};
+use crate::middleware::Middleware;
+use crate::state::{AppState, LabelChanges};
+use crate::utils::format::format_count;
+use crate::utils::template::{self, PrPlaceholders};
+use anyhow::Context;
+use gh_client::{
+    CommentLines, GitHubClient, MergeMethod, MergeRejection, MergeResult, ReviewEvent,
+};
+use gh_diff_viewer::{CommentPosition, DiffSide};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Middleware for the operations changing PRs on GitHub
+pub struct PrMutationMiddleware {
+    github: GitHubContext,
+    /// CODEOWNERS per repository, with the base branch head it was read at
+    /// (None when the repository has none)
+    codeowners: Arc<Mutex<CodeOwnersCache>>,
+}
+
+/// Parsed CODEOWNERS by repository, keyed with the commit they were read at
+type CodeOwnersCache = HashMap<Repository, (String, Option<CodeOwners>)>;
+
+impl PrMutationMiddleware {
+    pub fn new(github: GitHubContext) -> Self {
+        Self {
+            github,
+            codeowners: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Open PRs of a repository stacked (directly) on the given PR
+    fn stacked_dependents(state: &AppState, repo: &Repository, pr_number: usize) -> Vec<usize> {
+        let Some(repo_data) = state.main_view.repo_data_of(repo) else {
+            return vec![];
+        };
+        PrStacks::compute(&repo_data.prs)
+            .dependents(pr_number)
+            .into_iter()
+            .filter(|number| {
+                repo_data
+                    .prs
+                    .iter()
+                    .any(|pr| pr.number == *number && pr.state == PrState::Open)
+            })
+            .collect()
+    }
+
+    /// Ask before merging, with "delete branch after merge" preset
+    ///
+    /// The preset comes from the `[repos]` config, or else from the
+    /// repository's "Automatically delete head branches" setting.
+    fn confirm_merge(
+        &self,
+        targets: Vec<(Repository, usize)>,
+        state: &AppState,
+        dispatcher: &Dispatcher,
+    ) {
+        let Some((repo, _)) = targets.first().cloned() else {
+            return;
+        };
+        let pr_numbers: Vec<u64> = targets.iter().map(|(_, n)| *n as u64).collect();
+        let repo_context = format!("{}/{}", repo.org, repo.repo);
+        let warnings = state.main_view.base_branch_warnings(&targets);
+        let show = move |delete_branch: bool| {
+            Action::ConfirmationPopup(crate::actions::ConfirmationPopupAction::Show {
+                intent: crate::state::ConfirmationIntent::Merge {
+                    pr_numbers: pr_numbers.clone(),
+                    delete_branch,
+                },
+                default_message: String::new(),
+                repo_context: repo_context.clone(),
+                warnings: warnings.clone(),
+            })
+        };
+
+        if let Some(delete_branch) = state
+            .app_config
+            .delete_branch_after_merge_for(&repo.org, &repo.repo)
+        {
+            dispatcher.dispatch(show(delete_branch));
+            return;
+        }
+
+        // Without the setting the branch is kept
+        let show_kept = show.clone();
+        self.github
+            .task(&repo, dispatcher)
+            .on_failure(move |_| show_kept(false))
+            .spawn(move |client, dispatcher| async move {
+                let settings = client
+                    .fetch_repository_settings(&repo.org, &repo.repo)
+                    .await
+                    .context("Failed to fetch repository settings")?;
+                dispatcher.dispatch(show(settings.delete_branch_on_merge.unwrap_or(false)));
+                Ok(())
+            });
+    }
+
+    /// Merge PRs with the default merge method, one background task per PR
+    ///
+    /// With `delete_branch`, each head branch is deleted after a successful
+    /// merge, unless it is the default branch, lives in a fork or has
+    /// stacked PRs on top.
+    fn merge_targets(
+        &self,
+        targets: Vec<(Repository, usize)>,
+        delete_branch: bool,
+        state: &AppState,
+        dispatcher: &Dispatcher,
+    ) {
+        for (repo, pr_number) in targets {
+            let Some(in_flight) =
+                self.github
+                    .in_flight
+                    .begin(&repo, pr_number, OperationType::Merge, dispatcher)
+            else {
+                continue;
+            };
+            let dependents = Self::stacked_dependents(state, &repo, pr_number);
+            let head = state
+                .main_view
+                .repo_data_of(&repo)
+                .and_then(|data| data.prs.iter().find(|pr| pr.number == pr_number))
+                .map(|pr| (pr.head_branch.clone(), pr.is_from_fork));
+            // Merges in other repositories (bulk merge of an update across
+            // repositories) reload that repository instead of the current one
+            let refresh = if state
+                .main_view
+                .repositories
+                .get(state.main_view.selected_repository)
+                == Some(&repo)
+            {
+                Action::PullRequest(PullRequestAction::Refresh)
+            } else {
+                Action::Repository(RepositoryAction::RefreshRepositoryData(repo.clone()))
+            };
+            let delete_branch = delete_branch.then_some((head, !dependents.is_empty()));
+            if !dependents.is_empty() {
+                let list: Vec<String> = dependents.iter().map(|n| format!("#{}", n)).collect();
+                dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                    format!(
+                        "PR #{} has stacked PRs ({}), run \"Rebase stack\" on them after the merge",
+                        pr_number,
+                        list.join(", ")
+                    ),
+                    "Merge",
+                )));
+            }
+
+            dispatcher.dispatch(Action::PullRequest(PullRequestAction::MergeStart {
+                repo: repo.clone(),
+                pr_number,
+            }));
+            let audit = self
+                .github
+                .audit(AuditOperation::Merge, &repo, state)
+                .pr(pr_number)
+                .param("method", merge_method_name(MergeMethod::default()))
+                .param("delete_branch", delete_branch.is_some());
+            let keymap = state.keymap.clone();
+            self.github
+                .task(&repo, dispatcher)
+                .in_flight(in_flight)
+                .operation(format!("Merging PR #{}", pr_number), "Merge")
+                .report("Merge error", "Merge")
+                .spawn(move |client, dispatcher| async move {
+                    let result = match client
+                        .merge_pull_request(
+                            &repo.org,
+                            &repo.repo,
+                            pr_number as u64,
+                            MergeMethod::default(),
+                            None,
+                            None,
+                        )
+                        .await
+                    {
+                        Ok(result) => result,
+                        Err(e) => {
+                            audit.failed(&e);
+                            return Err(e);
+                        }
+                    };
+                    if !result.merged {
+                        audit.failed(&result.message);
+                        log::error!("Merge failed: {}", result.message);
+                        dispatcher.dispatch(Action::StatusBar(StatusBarAction::error(
+                            merge_failure_message(pr_number, &result, &keymap),
+                            "Merge",
+                        )));
+                        return Ok(());
+                    }
+
+                    audit.succeeded();
+                    log::info!("Successfully merged PR #{}", pr_number);
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::success(
+                        format!("PR #{} merged", pr_number),
+                        "Merge",
+                    )));
+                    if let Some((head, has_dependents)) = delete_branch {
+                        delete_merged_branch(
+                            client.as_ref(),
+                            &repo,
+                            pr_number,
+                            head,
+                            has_dependents,
+                            &dispatcher,
+                        )
+                        .await;
+                    }
+                    // Trigger refresh to update PR list
+                    dispatcher.dispatch(refresh);
+                    Ok(())
+                });
+        }
+    }
+
+    /// Approve and/or merge the PRs of a policy run one after another
+    ///
+    /// Runs sequentially so a PR is only merged after its approval went
+    /// through, and ends with a summary in the status bar.
+    fn run_policy(
+        &self,
+        repo: Repository,
+        steps: Vec<PolicyStep>,
+        approval_message: Option<String>,
+        state: &AppState,
+        dispatcher: &Dispatcher,
+    ) {
+        let approve_audit = self
+            .github
+            .audit(AuditOperation::Approve, &repo, state)
+            .param("source", "policy");
+        let approve_audit = match &approval_message {
+            Some(message) => approve_audit.param("message", message),
+            None => approve_audit,
+        };
+        let merge_audit = self
+            .github
+            .audit(AuditOperation::Merge, &repo, state)
+            .param("source", "policy")
+            .param("method", merge_method_name(MergeMethod::default()));
+        let in_flight = self.github.in_flight.clone();
+        let label = format!(
+            "Running policy on {}",
+            format_count(steps.len(), "PR", "PRs")
+        );
+        self.github
+            .task(&repo, dispatcher)
+            .operation(label, "Policy")
+            .report("Policy run failed", "Policy")
+            .spawn(move |client, dispatcher| async move {
+                let (mut approved, mut merged, mut failed) = (0, 0, Vec::new());
+                for step in steps {
+                    let pr_number = step.pr_number;
+                    if step.action.approves() {
+                        let Some(_in_flight) = in_flight.begin(
+                            &repo,
+                            pr_number as usize,
+                            OperationType::Approve,
+                            &dispatcher,
+                        ) else {
+                            failed.push(format!("#{}", pr_number));
+                            continue;
+                        };
+                        dispatcher.dispatch(Action::PullRequest(PullRequestAction::ApproveStart {
+                            repo: repo.clone(),
+                            pr_number: pr_number as usize,
+                        }));
+                        let result = client
+                            .create_review(
+                                &repo.org,
+                                &repo.repo,
+                                pr_number,
+                                ReviewEvent::Approve,
+                                approval_message.as_deref(),
+                            )
+                            .await;
+                        approve_audit.clone().pr(pr_number as usize).finish(&result);
+                        if let Err(e) = result {
+                            log::error!("Policy approve of PR #{} failed: {}", pr_number, e);
+                            failed.push(format!("#{}", pr_number));
+                            continue;
+                        }
+                        approved += 1;
+                        dispatcher.dispatch(Action::PullRequest(
+                            PullRequestAction::ReviewDecisionUpdated {
+                                repo: repo.clone(),
+                                pr_number,
+                                decision: crate::domain_models::ReviewDecision::Approved,
+                            },
+                        ));
+                    }
+
+                    if step.action.merges() {
+                        let Some(_in_flight) = in_flight.begin(
+                            &repo,
+                            pr_number as usize,
+                            OperationType::Merge,
+                            &dispatcher,
+                        ) else {
+                            failed.push(format!("#{}", pr_number));
+                            continue;
+                        };
+                        dispatcher.dispatch(Action::PullRequest(PullRequestAction::MergeStart {
+                            repo: repo.clone(),
+                            pr_number: pr_number as usize,
+                        }));
+                        let audit = merge_audit.clone().pr(pr_number as usize);
+                        match client
+                            .merge_pull_request(
+                                &repo.org,
+                                &repo.repo,
+                                pr_number,
+                                MergeMethod::default(),
+                                None,
+                                None,
+                            )
+                            .await
+                        {
+                            Ok(result) if result.merged => {
+                                audit.succeeded();
+                                merged += 1;
+                            }
+                            Ok(result) => {
+                                audit.failed(&result.message);
+                                log::error!(
+                                    "Policy merge of PR #{} failed: {}",
+                                    pr_number,
+                                    result.message
+                                );
+                                failed.push(match result.rejection {
+                                    Some(rejection) => {
+                                        format!("#{} ({})", pr_number, rejection.describe())
+                                    }
+                                    None => format!("#{}", pr_number),
+                                });
+                            }
+                            Err(e) => {
+                                audit.failed(&e);
+                                log::error!("Policy merge of PR #{} failed: {}", pr_number, e);
+                                failed.push(format!("#{}", pr_number));
+                            }
+                        }
+                    }
+                }
+
+                if merged > 0 {
+                    dispatcher.dispatch(Action::Repository(
+                        RepositoryAction::RefreshRepositoryData(repo),
+                    ));
+                }
+                let summary = format!("Policy run: {} approved, {} merged", approved, merged);
+                dispatcher.dispatch(Action::StatusBar(if failed.is_empty() {
+                    StatusBarAction::success(summary, "Policy")
+                } else {
+                    StatusBarAction::warning(
+                        format!("{}, failed: {}", summary, failed.join(", ")),
+                        "Policy",
+                    )
+                }));
+                Ok(())
+            });
+    }
+
+    /// Show the label picker for the target PRs and fetch the repository labels
+    fn open_label_picker(&self, state: &AppState, dispatcher: &Dispatcher) {
+        let repo_idx = state.main_view.selected_repository;
+        let Some(repo) = state.main_view.repositories.get(repo_idx).cloned() else {
+            return;
+        };
+        let targets: Vec<usize> = target_prs(state)
+            .into_iter()
+            .map(|(_, number)| number)
+            .collect();
+        let Some(repo_data) = state.main_view.repo_data.get(&repo_idx) else {
+            return;
+        };
+        let prs: Vec<(usize, Vec<String>)> = repo_data
+            .prs
+            .iter()
+            .filter(|pr| targets.contains(&pr.number))
+            .map(|pr| (pr.number, pr.labels.clone()))
+            .collect();
+        if prs.is_empty() {
+            dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                "No pull request selected",
+                "Labels",
+            )));
+            return;
+        }
+
+        dispatcher.dispatch(Action::LabelPicker(LabelPickerAction::Show {
+            repo: repo.clone(),
+            prs,
+        }));
+
+        self.github
+            .task(&repo, dispatcher)
+            .on_failure(|error| Action::LabelPicker(LabelPickerAction::LoadFailed(error)))
+            .spawn(move |client, dispatcher| async move {
+                let labels = client.fetch_repo_labels(&repo.org, &repo.repo).await?;
+                dispatcher.dispatch(Action::LabelPicker(LabelPickerAction::Loaded(labels)));
+                Ok(())
+            });
+    }
+
+    /// Show the reviewer picker for the PR under the cursor
+    ///
+    /// Collaborators are fetched alongside the owners of the PR's changed
+    /// files. Without a readable CODEOWNERS only collaborators are listed.
+    fn open_reviewer_picker(&self, state: &AppState, dispatcher: &Dispatcher) {
+        let repo_idx = state.main_view.selected_repository;
+        let (Some(repo), Some(repo_data)) = (
+            state.main_view.repositories.get(repo_idx).cloned(),
+            state.main_view.repo_data.get(&repo_idx),
+        ) else {
+            return;
+        };
+        let Some(pr) = repo_data.prs.get(repo_data.selected_pr).cloned() else {
+            dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                "No pull request selected",
+                "Reviewers",
+            )));
+            return;
+        };
+
+        dispatcher.dispatch(Action::ReviewerPicker(ReviewerPickerAction::Show {
+            repo: repo.clone(),
+            pr_number: pr.number,
+            author: pr.author.clone(),
+        }));
+
+        let codeowners = Arc::clone(&self.codeowners);
+        self.github
+            .task(&repo, dispatcher)
+            .on_failure(|error| Action::ReviewerPicker(ReviewerPickerAction::LoadFailed(error)))
+            .spawn(move |client, dispatcher| async move {
+                let (collaborators, suggested) = tokio::join!(
+                    client.fetch_collaborators(&repo.org, &repo.repo),
+                    suggest_code_owners(client.as_ref(), &repo, &pr, &codeowners),
+                );
+                dispatcher.dispatch(Action::ReviewerPicker(ReviewerPickerAction::Loaded {
+                    suggested,
+                    collaborators: collaborators?,
+                }));
+                Ok(())
+            });
+    }
+
+    /// Request reviews from the users and teams checked in the reviewer picker
+    fn request_reviewers(&self, state: &AppState, dispatcher: &Dispatcher) {
+        let Some(ref picker) = state.reviewer_picker else {
+            return;
+        };
+        let (reviewers, team_reviewers) = picker.requested();
+        if reviewers.is_empty() && team_reviewers.is_empty() {
+            dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+                "No reviewers selected",
+                "Reviewers",
+            )));
+            dispatcher.dispatch(Action::ReviewerPicker(ReviewerPickerAction::Close));
+            return;
+        }
+
+        let repo = picker.repo.clone();
+        let pr_number = picker.pr_number;
+        let audit = self
+            .github
+            .audit(AuditOperation::Reviewers, &repo, state)
+            .pr(pr_number)
+            .param("reviewers", reviewers.join(", "))
+            .param("team_reviewers", team_reviewers.join(", "));
+        dispatcher.dispatch(Action::ReviewerPicker(ReviewerPickerAction::Applied));
+
+        self.github
+            .task(&repo, dispatcher)
+            .operation(
+                format!("Requesting reviews on PR #{}", pr_number),
+                "Reviewers",
+            )
+            .report(
+                format!("Reviews on PR #{} not requested", pr_number),
+                "Reviewers",
+            )
+            .spawn(move |client, dispatcher| async move {
+                let result = client
+                    .request_reviewers(
+                        &repo.org,
+                        &repo.repo,
+                        pr_number as u64,
+                        &reviewers,
+                        &team_reviewers,
+                    )
+                    .await;
+                audit.finish(&result);
+                result?;
+                let count = reviewers.len() + team_reviewers.len();
+                dispatcher.dispatch(Action::StatusBar(StatusBarAction::success(
+                    format!(
+                        "PR #{}: requested {}",
+                        pr_number,
+                        format_count(count, "review", "reviews")
+                    ),
+                    "Reviewers",
+                )));
+                Ok(())
+            });
+    }
+
+    /// Ask dependabot to recreate the PR of the conflict helper
+    fn recreate_dependabot_pr(&self, state: &AppState, dispatcher: &Dispatcher) {
+        let Some((repo, pr)) = state.conflict_helper.target.clone() else {
+            return;
+        };
+        if !author::is_dependabot(&pr.author) {
+            dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                format!("PR #{} is not a dependabot PR", pr.number),
+                "Conflicts",
+            )));
+            return;
+        }
+
+        let audit = self
+            .github
+            .audit(AuditOperation::Comment, &repo, state)
+            .pr(pr.number)
+            .param("message", "@dependabot recreate");
+        self.github
+            .task(&repo, dispatcher)
+            .report("Recreate request failed", "Conflicts")
+            .spawn(move |client, dispatcher| async move {
+                let result = client
+                    .create_issue_comment(
+                        &repo.org,
+                        &repo.repo,
+                        pr.number as u64,
+                        "@dependabot recreate",
+                    )
+                    .await;
+                audit.finish(&result);
+                result?;
+                log::info!("Requested dependabot recreate for PR #{}", pr.number);
+                dispatcher.dispatch(Action::StatusBar(StatusBarAction::success(
+                    format!("Dependabot recreate requested for PR #{}", pr.number),
+                    "Conflicts",
+                )));
+                Ok(())
+            });
+    }
+
+    /// Apply the label picker's changes, one task per PR with its own result
+    fn apply_label_changes(&self, state: &AppState, dispatcher: &Dispatcher) {
+        let Some(ref picker) = state.label_picker else {
+            return;
+        };
+        let changes = picker.changes();
+        if changes.is_empty() {
+            dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+                "No label changes",
+                "Labels",
+            )));
+            dispatcher.dispatch(Action::LabelPicker(LabelPickerAction::Close));
+            return;
+        }
+
+        for (pr_number, change) in changes {
+            let current = picker
+                .prs
+                .iter()
+                .find(|(number, _)| *number == pr_number)
+                .map(|(_, labels)| labels.clone())
+                .unwrap_or_default();
+            let repo = picker.repo.clone();
+            let audit = self
+                .github
+                .audit(AuditOperation::Labels, &repo, state)
+                .pr(pr_number)
+                .param("add", change.add.join(", "))
+                .param("remove", change.remove.join(", "));
+            self.github
+                .task(&repo, dispatcher)
+                .operation(format!("Updating labels of PR #{}", pr_number), "Labels")
+                .report(format!("Labels of PR #{} not updated", pr_number), "Labels")
+                .spawn(move |client, dispatcher| async move {
+                    let result = async {
+                        if !change.add.is_empty() {
+                            client
+                                .add_labels(&repo.org, &repo.repo, pr_number as u64, &change.add)
+                                .await?;
+                        }
+                        for label in &change.remove {
+                            client
+                                .remove_label(&repo.org, &repo.repo, pr_number as u64, label)
+                                .await?;
+                        }
+                        anyhow::Ok(())
+                    }
+                    .await;
+                    audit.finish(&result);
+                    result?;
+
+                    let labels = apply_label_change(current, &change);
+                    dispatcher.dispatch(Action::PullRequest(PullRequestAction::LabelsUpdated {
+                        repo: repo.clone(),
+                        pr_number: pr_number as u64,
+                        labels,
+                    }));
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::success(
+                        format!("PR #{}: {}", pr_number, describe_label_change(&change)),
+                        "Labels",
+                    )));
+                    Ok(())
+                });
+        }
+
+        dispatcher.dispatch(Action::LabelPicker(LabelPickerAction::Applied));
+    }
+
+    /// Get repository context string for confirmation popup
+    fn get_repo_context(&self, state: &AppState) -> String {
+        let repo_idx = state.main_view.selected_repository;
+        state
+            .main_view
+            .repositories
+            .get(repo_idx)
+            .map(|r| format!("{}/{}", r.org, r.repo))
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+}
+
+impl Middleware<Action, AppState> for PrMutationMiddleware {
+    fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        match action {
+            Action::ConflictHelper(ConflictHelperAction::RecreateDependabot) => {
+                self.recreate_dependabot_pr(state, dispatcher);
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::MergeRequest) => {
+                let targets = target_prs(state);
+                if targets.is_empty() {
+                    log::warn!("No PRs selected for merge");
+                    return false;
+                }
+                if reject_inactive_targets(state, "merge", "Merge", dispatcher) {
+                    return false;
+                }
+                self.confirm_merge(targets, state, dispatcher);
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::MergePrs {
+                pr_numbers,
+                delete_branch,
+            }) => {
+                let repo_idx = state.main_view.selected_repository;
+                let Some(repo) = state.main_view.repositories.get(repo_idx) else {
+                    log::error!("No repository selected");
+                    return false;
+                };
+                let targets = pr_numbers
+                    .iter()
+                    .map(|&number| (repo.clone(), number as usize))
+                    .collect();
+                self.merge_targets(targets, *delete_branch, state, dispatcher);
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::MergeRepoPrs { repo, pr_numbers }) => {
+                let targets = pr_numbers
+                    .iter()
+                    .map(|&number| (repo.clone(), number as usize))
+                    .collect();
+                self.merge_targets(targets, false, state, dispatcher);
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::RunPolicy { repo, steps }) => {
+                if steps.is_empty() {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+                        "Policy run: nothing to do",
+                        "Policy",
+                    )));
+                    return false;
+                }
+                let message = Some(state.app_config.approval_message.clone())
+                    .filter(|message| !message.is_empty());
+                self.run_policy(repo.clone(), steps.clone(), message, state, dispatcher);
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::RebaseRequest) => {
+                let targets = target_prs_with_author(state);
+                if targets.is_empty() {
+                    log::warn!("No PRs selected for rebase");
+                    return false;
+                }
+                if reject_inactive_targets(state, "rebase", "Rebase", dispatcher) {
+                    return false;
+                }
+
+                for (repo, pr_number, author) in targets {
+                    let is_dependabot = author::is_dependabot(&author);
+                    // The update-branch API pushes to the head branch, which a fork may forbid
+                    let blocker = state
+                        .main_view
+                        .find_pr(pr_number)
+                        .filter(|_| !is_dependabot)
+                        .and_then(|pr| Some((pr.head_label(), pr.update_branch_blocker()?)));
+                    if let Some((head, reason)) = blocker {
+                        dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                            format!(
+                                "Cannot update branch {} of PR #{}: {}",
+                                head, pr_number, reason
+                            ),
+                            "Rebase",
+                        )));
+                        continue;
+                    }
+                    let Some(in_flight) = self.github.in_flight.begin(
+                        &repo,
+                        pr_number,
+                        OperationType::Rebase,
+                        dispatcher,
+                    ) else {
+                        continue;
+                    };
+                    let pr = state.main_view.find_pr(pr_number).cloned();
+
+                    dispatcher.dispatch(Action::PullRequest(PullRequestAction::RebaseStart {
+                        repo: repo.clone(),
+                        pr_number,
+                    }));
+                    let audit = self
+                        .github
+                        .audit(AuditOperation::Rebase, &repo, state)
+                        .pr(pr_number);
+                    let task = self
+                        .github
+                        .task(&repo, dispatcher)
+                        .in_flight(in_flight)
+                        .operation(format!("Updating branch for PR #{}", pr_number), "Rebase");
+
+                    if is_dependabot {
+                        // For dependabot PRs, post a comment to trigger rebase
+                        let audit = audit.param("via", "@dependabot rebase");
+                        task.report("Rebase request failed", "Rebase").spawn(
+                            move |client, dispatcher| async move {
+                                let result = client
+                                    .create_issue_comment(
+                                        &repo.org,
+                                        &repo.repo,
+                                        pr_number as u64,
+                                        "@dependabot rebase",
+                                    )
+                                    .await;
+                                audit.finish(&result);
+                                result?;
+                                log::info!("Requested dependabot rebase for PR #{}", pr_number);
+                                dispatcher.dispatch(Action::StatusBar(StatusBarAction::success(
+                                    format!("Dependabot rebase requested for PR #{}", pr_number),
+                                    "Rebase",
+                                )));
+                                Ok(())
+                            },
+                        );
+                        continue;
+                    }
+
+                    // For regular PRs, use the update branch API
+                    task.report("Rebase failed", "Rebase").spawn(
+                        move |client, dispatcher| async move {
+                            let result = client
+                                .update_pull_request_branch(&repo.org, &repo.repo, pr_number as u64)
+                                .await;
+                            audit.finish(&result);
+                            match result {
+                                Ok(()) => {
+                                    log::info!("Successfully rebased PR #{}", pr_number);
+                                    dispatcher.dispatch(Action::StatusBar(
+                                        StatusBarAction::success(
+                                            format!("PR #{} branch updated", pr_number),
+                                            "Rebase",
+                                        ),
+                                    ));
+                                    // Trigger refresh to update PR status
+                                    dispatcher
+                                        .dispatch(Action::PullRequest(PullRequestAction::Refresh));
+                                }
+                                Err(e) if conflicts::is_merge_conflict_error(&e.to_string()) => {
+                                    log::warn!("Rebase of PR #{} conflicts: {}", pr_number, e);
+                                    dispatcher.dispatch(Action::StatusBar(
+                                        StatusBarAction::warning(
+                                            format!(
+                                                "PR #{} has merge conflicts with its base branch",
+                                                pr_number
+                                            ),
+                                            "Rebase",
+                                        ),
+                                    ));
+                                    if let Some(pr) = pr {
+                                        dispatcher.dispatch(Action::ConflictHelper(
+                                            ConflictHelperAction::Open {
+                                                repo,
+                                                pr: Box::new(pr),
+                                            },
+                                        ));
+                                    }
+                                }
+                                Err(e) => return Err(e),
+                            }
+                            Ok(())
+                        },
+                    );
+                }
+                false // Consume action
+            }
+
+            // PR Actions that show confirmation popup
+            Action::PullRequest(PullRequestAction::RebaseStackRequest) => {
+                let repo_idx = state.main_view.selected_repository;
+                let Some(repo) = state.main_view.repositories.get(repo_idx).cloned() else {
+                    return false;
+                };
+                let Some(repo_data) = state.main_view.repo_data.get(&repo_idx) else {
+                    return false;
+                };
+                let Some(focused) = repo_data.prs.get(repo_data.selected_pr) else {
+                    return false;
+                };
+                if focused.state != PrState::Open {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        format!(
+                            "Cannot rebase stack of PR #{}: it is not open",
+                            focused.number
+                        ),
+                        "Rebase",
+                    )));
+                    return false;
+                }
+
+                // Bottom-up: each PR is updated after the PR it is stacked on
+                let stacks = PrStacks::compute(&repo_data.prs);
+                let mut skipped = Vec::new();
+                let order: Vec<usize> = std::iter::once(focused.number)
+                    .chain(stacks.descendants(focused.number))
+                    .filter(|number| {
+                        let Some(pr) = repo_data.prs.iter().find(|pr| pr.number == *number) else {
+                            return false;
+                        };
+                        if let Some(reason) = pr.update_branch_blocker() {
+                            skipped.push(format!("#{} ({})", pr.number, reason));
+                            return false;
+                        }
+                        pr.state == PrState::Open
+                    })
+                    .collect();
+                if !skipped.is_empty() {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        format!("Not updating fork PRs: {}", skipped.join(", ")),
+                        "Rebase",
+                    )));
+                }
+                if order.is_empty() {
+                    return false;
+                }
+
+                let in_flight = self.github.in_flight.clone();
+                let audit = self
+                    .github
+                    .audit(AuditOperation::Rebase, &repo, state)
+                    .param("source", "stack");
+                let label = format!(
+                    "Updating stack of PR #{} ({})",
+                    focused.number,
+                    format_count(order.len(), "PR", "PRs")
+                );
+                self.github
+                    .task(&repo, dispatcher)
+                    .operation(label, "Rebase")
+                    .report("Rebase stack failed", "Rebase")
+                    .spawn(move |client, dispatcher| async move {
+                        let mut failed = Vec::new();
+                        for pr_number in &order {
+                            let Some(_in_flight) = in_flight.begin(
+                                &repo,
+                                *pr_number,
+                                OperationType::Rebase,
+                                &dispatcher,
+                            ) else {
+                                failed.push(format!("#{}", pr_number));
+                                continue;
+                            };
+                            dispatcher.dispatch(Action::PullRequest(
+                                PullRequestAction::RebaseStart {
+                                    repo: repo.clone(),
+                                    pr_number: *pr_number,
+                                },
+                            ));
+                            // A PR that is already up to date fails; the rest of the stack still runs
+                            let result = client
+                                .update_pull_request_branch(
+                                    &repo.org,
+                                    &repo.repo,
+                                    *pr_number as u64,
+                                )
+                                .await;
+                            audit.clone().pr(*pr_number).finish(&result);
+                            if let Err(e) = result {
+                                log::warn!("Stack update of PR #{} failed: {}", pr_number, e);
+                                failed.push(format!("#{}", pr_number));
+                            }
+                        }
+
+                        let status = if failed.is_empty() {
+                            StatusBarAction::success(
+                                format!(
+                                    "Updated stack of {}",
+                                    format_count(order.len(), "PR", "PRs")
+                                ),
+                                "Rebase",
+                            )
+                        } else {
+                            StatusBarAction::warning(
+                                format!(
+                                    "Updated stack, {} of {} PRs failed: {}",
+                                    failed.len(),
+                                    order.len(),
+                                    failed.join(", ")
+                                ),
+                                "Rebase",
+                            )
+                        };
+                        dispatcher.dispatch(Action::StatusBar(status));
+                        dispatcher.dispatch(Action::PullRequest(PullRequestAction::Refresh));
+                        Ok(())
+                    });
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::ApproveRequest) => {
+                let targets = target_prs(state);
+                if targets.is_empty() {
+                    log::warn!("No PRs selected for approval");
+                    return false;
+                }
+
+                // Guard against approving own PRs and PRs without green CI
+                let repo_idx = state.main_view.selected_repository;
+                let prs: Vec<&Pr> = state
+                    .main_view
+                    .repo_data
+                    .get(&repo_idx)
+                    .map(|repo_data| {
+                        repo_data
+                            .prs
+                            .iter()
+                            .filter(|pr| targets.iter().any(|(_, number)| *number == pr.number))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let viewer_login = targets
+                    .first()
+                    .and_then(|(repo, _)| state.main_view.account_logins.get(&repo.account_key()));
+                let (pr_numbers, warnings) =
+                    approval_targets(&prs, viewer_login.map(String::as_str));
+                if pr_numbers.is_empty() {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        "Can not approve your own pull request",
+                        "Approve",
+                    )));
+                    return false;
+                }
+
+                let repo_context = self.get_repo_context(state);
+                let default_message = state.app_config.approval_message.clone();
+
+                dispatcher.dispatch(Action::ConfirmationPopup(
+                    crate::actions::ConfirmationPopupAction::Show {
+                        intent: crate::state::ConfirmationIntent::Approve { pr_numbers },
+                        default_message,
+                        repo_context,
+                        warnings,
+                    },
+                ));
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::CommentRequest) => {
+                let targets = target_prs(state);
+                if targets.is_empty() {
+                    log::warn!("No PRs selected for comment");
+                    return false;
+                }
+
+                let pr_numbers: Vec<u64> = targets.iter().map(|(_, pr)| *pr as u64).collect();
+                let repo_context = self.get_repo_context(state);
+                let default_message = state.app_config.comment_message.clone();
+
+                dispatcher.dispatch(Action::ConfirmationPopup(
+                    crate::actions::ConfirmationPopupAction::Show {
+                        intent: crate::state::ConfirmationIntent::Comment { pr_numbers },
+                        default_message,
+                        repo_context,
+                        warnings: Vec::new(),
+                    },
+                ));
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::RequestChangesRequest) => {
+                let targets = target_prs(state);
+                if targets.is_empty() {
+                    log::warn!("No PRs selected for request changes");
+                    return false;
+                }
+
+                let pr_numbers: Vec<u64> = targets.iter().map(|(_, pr)| *pr as u64).collect();
+                let repo_context = self.get_repo_context(state);
+                let default_message = state.app_config.request_changes_message.clone();
+
+                dispatcher.dispatch(Action::ConfirmationPopup(
+                    crate::actions::ConfirmationPopupAction::Show {
+                        intent: crate::state::ConfirmationIntent::RequestChanges { pr_numbers },
+                        default_message,
+                        repo_context,
+                        warnings: Vec::new(),
+                    },
+                ));
+                false // Consume action
+            }
+
+            // Actual execution actions (from confirmation popup)
+            Action::PullRequest(PullRequestAction::ApproveWithMessage {
+                pr_numbers,
+                message,
+            }) => {
+                let repo_idx = state.main_view.selected_repository;
+                let Some(repo) = state.main_view.repositories.get(repo_idx).cloned() else {
+                    log::error!("No repository selected");
+                    return false;
+                };
+
+                let message = if message.is_empty() {
+                    None
+                } else {
+                    Some(message.clone())
+                };
+
+                for &pr_number in pr_numbers {
+                    let Some(in_flight) = self.github.in_flight.begin(
+                        &repo,
+                        pr_number as usize,
+                        OperationType::Approve,
+                        dispatcher,
+                    ) else {
+                        continue;
+                    };
+                    dispatcher.dispatch(Action::PullRequest(PullRequestAction::ApproveStart {
+                        repo: repo.clone(),
+                        pr_number: pr_number as usize,
+                    }));
+                    let mut audit = self
+                        .github
+                        .audit(AuditOperation::Approve, &repo, state)
+                        .pr(pr_number as usize);
+                    if let Some(message) = &message {
+                        audit = audit.param("message", message);
+                    }
+                    let (repo, message) = (repo.clone(), message.clone());
+                    self.github
+                        .task(&repo, dispatcher)
+                        .in_flight(in_flight)
+                        .operation(format!("Approving PR #{}", pr_number), "Approve")
+                        .report("Approve failed", "Approve")
+                        .spawn(move |client, dispatcher| async move {
+                            let result = client
+                                .create_review(
+                                    &repo.org,
+                                    &repo.repo,
+                                    pr_number,
+                                    ReviewEvent::Approve,
+                                    message.as_deref(),
+                                )
+                                .await;
+                            audit.finish(&result);
+                            if let Err(e) = result {
+                                log::error!("Approve error: {}", e);
+                                dispatcher.dispatch(Action::StatusBar(StatusBarAction::error(
+                                    approve_error_message(pr_number, &e),
+                                    "Approve",
+                                )));
+                                return Ok(());
+                            }
+
+                            log::info!("Successfully approved PR #{}", pr_number);
+                            dispatcher.dispatch(Action::StatusBar(StatusBarAction::success(
+                                format!("PR #{} approved", pr_number),
+                                "Approve",
+                            )));
+                            // Update review decision locally
+                            dispatcher.dispatch(Action::PullRequest(
+                                PullRequestAction::ReviewDecisionUpdated {
+                                    repo,
+                                    pr_number,
+                                    decision: crate::domain_models::ReviewDecision::Approved,
+                                },
+                            ));
+                            Ok(())
+                        });
+                }
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::CommentOnPr {
+                pr_numbers,
+                message,
+            }) => {
+                let repo_idx = state.main_view.selected_repository;
+                let Some(repo) = state.main_view.repositories.get(repo_idx).cloned() else {
+                    log::error!("No repository selected");
+                    return false;
+                };
+
+                let mut posts = Vec::new();
+                for pr_number in pr_numbers {
+                    let pr_num = *pr_number as usize;
+                    let Some(in_flight) = self.github.in_flight.begin(
+                        &repo,
+                        pr_num,
+                        OperationType::Comment,
+                        dispatcher,
+                    ) else {
+                        continue;
+                    };
+                    dispatcher.dispatch(Action::PullRequest(PullRequestAction::CommentStart {
+                        repo: repo.clone(),
+                        pr_number: pr_num,
+                    }));
+                    // PRs not loaded (any more) only know their number
+                    let placeholders = state.main_view.find_pr(pr_num).map_or_else(
+                        || PrPlaceholders {
+                            number: pr_num,
+                            ..Default::default()
+                        },
+                        PrPlaceholders::of,
+                    );
+                    let body = placeholders.render(message);
+                    let audit = self
+                        .github
+                        .audit(AuditOperation::Comment, &repo, state)
+                        .pr(pr_num)
+                        .param("message", &body);
+                    posts.push((*pr_number, body, in_flight, audit));
+                }
+                let Some((first, ..)) = posts.first() else {
+                    return false;
+                };
+
+                let label = match posts.len() {
+                    1 => format!("Commenting on PR #{}", first),
+                    count => format!("Commenting on {} PRs", count),
+                };
+                let operation = dispatcher.start_operation(label, "Comment");
+                let clients = Arc::clone(&self.github.clients);
+                let dispatcher = dispatcher.clone();
+                let repo_context = format!("{}/{}", repo.org, repo.repo);
+                let template = message.clone();
+
+                self.github.runtime.spawn(async move {
+                    let _operation = operation;
+                    let pr_numbers: Vec<u64> = posts.iter().map(|(pr, ..)| *pr).collect();
+                    let failures = post_comments(clients, repo, posts).await;
+
+                    let summary = comment_summary(&pr_numbers, &failures);
+                    if failures.is_empty() {
+                        log::info!("{}", summary);
+                        dispatcher.dispatch(Action::StatusBar(StatusBarAction::success(
+                            summary, "Comment",
+                        )));
+                        return;
+                    }
+                    log::error!("{}", summary);
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::error(
+                        summary, "Comment",
+                    )));
+
+                    // Offer to post again on the PRs that failed
+                    if pr_numbers.len() > 1 {
+                        dispatcher.dispatch(Action::ConfirmationPopup(
+                            crate::actions::ConfirmationPopupAction::Show {
+                                intent: crate::state::ConfirmationIntent::RetryComment {
+                                    pr_numbers: failures.iter().map(|(pr, _)| *pr).collect(),
+                                    failures: failures
+                                        .iter()
+                                        .map(|(pr, reason)| format!("#{}  {}", pr, reason))
+                                        .collect(),
+                                },
+                                default_message: template,
+                                repo_context,
+                                warnings: Vec::new(),
+                            },
+                        ));
+                    }
+                });
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::RequestChanges {
+                pr_numbers,
+                message,
+            }) => {
+                let repo_idx = state.main_view.selected_repository;
+                let Some(repo) = state.main_view.repositories.get(repo_idx).cloned() else {
+                    log::error!("No repository selected");
+                    return false;
+                };
+
+                for &pr_number in pr_numbers {
+                    let Some(in_flight) = self.github.in_flight.begin(
+                        &repo,
+                        pr_number as usize,
+                        OperationType::RequestChanges,
+                        dispatcher,
+                    ) else {
+                        continue;
+                    };
+                    dispatcher.dispatch(Action::PullRequest(
+                        PullRequestAction::RequestChangesStart {
+                            repo: repo.clone(),
+                            pr_number: pr_number as usize,
+                        },
+                    ));
+                    let audit = self
+                        .github
+                        .audit(AuditOperation::RequestChanges, &repo, state)
+                        .pr(pr_number as usize)
+                        .param("message", message);
+                    let (repo, message) = (repo.clone(), message.clone());
+                    self.github
+                        .task(&repo, dispatcher)
+                        .in_flight(in_flight)
+                        .operation(
+                            format!("Requesting changes on PR #{}", pr_number),
+                            "Request Changes",
+                        )
+                        .report("Request changes failed", "Request Changes")
+                        .spawn(move |client, dispatcher| async move {
+                            let result = client
+                                .create_review(
+                                    &repo.org,
+                                    &repo.repo,
+                                    pr_number,
+                                    ReviewEvent::RequestChanges,
+                                    Some(&message),
+                                )
+                                .await;
+                            audit.finish(&result);
+                            result?;
+                            log::info!("Successfully requested changes on PR #{}", pr_number);
+                            dispatcher.dispatch(Action::StatusBar(StatusBarAction::success(
+                                format!("Requested changes on PR #{}", pr_number),
+                                "Request Changes",
+                            )));
+                            // Update review decision locally
+                            dispatcher.dispatch(Action::PullRequest(
+                                PullRequestAction::ReviewDecisionUpdated {
+                                    repo,
+                                    pr_number,
+                                    decision:
+                                        crate::domain_models::ReviewDecision::ChangesRequested,
+                                },
+                            ));
+                            Ok(())
+                        });
+                }
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::CloseRequest) => {
+                let targets = target_prs_with_author(state);
+                if targets.is_empty() {
+                    log::warn!("No PRs selected for closing");
+                    return false;
+                }
+
+                let pr_numbers: Vec<u64> = targets.iter().map(|(_, pr, _)| *pr as u64).collect();
+                let repo_context = self.get_repo_context(state);
+                let close_message = state.app_config.close_message.clone();
+                let show = move |default_message| {
+                    Action::ConfirmationPopup(crate::actions::ConfirmationPopupAction::Show {
+                        intent: crate::state::ConfirmationIntent::Close { pr_numbers },
+                        default_message,
+                        repo_context,
+                        warnings: Vec::new(),
+                    })
+                };
+
+                let repo = targets[0].0.clone();
+                let Some(template_path) = state
+                    .app_config
+                    .close_comment_template_for(&repo.org, &repo.repo)
+                    .map(str::to_string)
+                else {
+                    dispatcher.dispatch(show(close_message));
+                    return false;
+                };
+
+                // The repository's template pre-fills the comment, the
+                // global close message stands in if it can't be fetched
+                let prs: Vec<(usize, String)> = targets
+                    .into_iter()
+                    .map(|(_, pr, author)| (pr, author))
+                    .collect();
+                let clients = Arc::clone(&self.github.clients);
+                let dispatcher = dispatcher.clone();
+                self.github.runtime.spawn(async move {
+                    let template = async {
+                        clients
+                            .client(&repo, false)
+                            .await?
+                            .fetch_file_content(&repo.org, &repo.repo, &template_path, None)
+                            .await
+                    };
+                    let message = match template.await {
+                        Ok(template) => expand_close_template(&template, &prs),
+                        Err(e) => {
+                            log::debug!(
+                                "Close comment template {} unavailable, using close_message: {:#}",
+                                template_path,
+                                e
+                            );
+                            close_message
+                        }
+                    };
+                    dispatcher.dispatch(show(message));
+                });
+                false // Consume action
+            }
+
+            Action::LabelPicker(LabelPickerAction::Open) => {
+                self.open_label_picker(state, dispatcher);
+                false
+            }
+
+            Action::LabelPicker(LabelPickerAction::Apply) => {
+                let applying = state.label_picker.as_ref().is_some_and(|p| p.applying);
+                if !applying {
+                    self.apply_label_changes(state, dispatcher);
+                }
+                true
+            }
+
+            Action::ReviewerPicker(ReviewerPickerAction::Open) => {
+                self.open_reviewer_picker(state, dispatcher);
+                false
+            }
+
+            Action::ReviewerPicker(ReviewerPickerAction::Apply) => {
+                let applying = state.reviewer_picker.as_ref().is_some_and(|p| p.applying);
+                if !applying {
+                    self.request_reviewers(state, dispatcher);
+                }
+                true
+            }
+
+            Action::PullRequest(PullRequestAction::ClosePrWithMessage {
+                pr_numbers,
+                message,
+            }) => {
+                let repo_idx = state.main_view.selected_repository;
+                let Some(repo) = state.main_view.repositories.get(repo_idx).cloned() else {
+                    log::error!("No repository selected");
+                    return false;
+                };
+
+                for &pr_number in pr_numbers {
+                    let Some(in_flight) = self.github.in_flight.begin(
+                        &repo,
+                        pr_number as usize,
+                        OperationType::Close,
+                        dispatcher,
+                    ) else {
+                        continue;
+                    };
+                    dispatcher.dispatch(Action::PullRequest(PullRequestAction::CloseStart {
+                        repo: repo.clone(),
+                        pr_number: pr_number as usize,
+                    }));
+                    let mut audit = self
+                        .github
+                        .audit(AuditOperation::Close, &repo, state)
+                        .pr(pr_number as usize);
+                    if !message.is_empty() {
+                        audit = audit.param("message", message);
+                    }
+                    let (repo, message) = (repo.clone(), message.clone());
+                    self.github
+                        .task(&repo, dispatcher)
+                        .in_flight(in_flight)
+                        .operation(format!("Closing PR #{}", pr_number), "Close")
+                        .report("Close failed", "Close")
+                        .spawn(move |client, dispatcher| async move {
+                            // Post comment if message is not empty
+                            if !message.is_empty() {
+                                if let Err(e) = client
+                                    .create_issue_comment(
+                                        &repo.org, &repo.repo, pr_number, &message,
+                                    )
+                                    .await
+                                {
+                                    log::warn!(
+                                        "Failed to post close comment on PR #{}: {}",
+                                        pr_number,
+                                        e
+                                    );
+                                }
+                            }
+
+                            let result = client
+                                .close_pull_request(&repo.org, &repo.repo, pr_number)
+                                .await;
+                            audit.finish(&result);
+                            result?;
+                            log::info!("Successfully closed PR #{}", pr_number);
+                            dispatcher.dispatch(Action::StatusBar(StatusBarAction::success(
+                                format!("PR #{} closed", pr_number),
+                                "Close",
+                            )));
+                            // Trigger refresh to update PR list
+                            dispatcher.dispatch(Action::PullRequest(PullRequestAction::Refresh));
+                            Ok(())
+                        });
+                }
+                false // Consume action
+            }
+
+            // === Diff Viewer Operations ===
+            Action::DiffViewer(DiffViewerAction::SubmitReviewRequest { pr_number, event }) => {
+                let repo_idx = state.main_view.selected_repository;
+                let Some(repo) = state.main_view.repositories.get(repo_idx).cloned() else {
+                    log::error!("No repository selected for review submission");
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::error(
+                        "No repository selected",
+                        "Review",
+                    )));
+                    return false;
+                };
+
+                let pr_number = *pr_number;
+                let event = *event;
+
+                // Convert gh_diff_viewer::ReviewEvent to gh_client::ReviewEvent
+                let api_event = match event {
+                    gh_diff_viewer::ReviewEvent::Approve => ReviewEvent::Approve,
+                    gh_diff_viewer::ReviewEvent::RequestChanges => ReviewEvent::RequestChanges,
+                    gh_diff_viewer::ReviewEvent::Comment => ReviewEvent::Comment,
+                };
+
+                let event_name = match event {
+                    gh_diff_viewer::ReviewEvent::Approve => "Approve",
+                    gh_diff_viewer::ReviewEvent::RequestChanges => "Request Changes",
+                    gh_diff_viewer::ReviewEvent::Comment => "Comment",
+                };
+
+                let operation = match event {
+                    gh_diff_viewer::ReviewEvent::Approve => AuditOperation::Approve,
+                    gh_diff_viewer::ReviewEvent::RequestChanges => AuditOperation::RequestChanges,
+                    gh_diff_viewer::ReviewEvent::Comment => AuditOperation::Comment,
+                };
+                let audit = self
+                    .github
+                    .audit(operation, &repo, state)
+                    .pr(pr_number as usize)
+                    .param("source", "diff viewer");
+                self.github
+                    .task(&repo, dispatcher)
+                    .operation(
+                        format!("Submitting {} review for PR #{}", event_name, pr_number),
+                        "Review",
+                    )
+                    .report("Review failed", "Review")
+                    .spawn(move |client, dispatcher| async move {
+                        let result = client
+                            .create_review(&repo.org, &repo.repo, pr_number, api_event, None)
+                            .await;
+                        audit.finish(&result);
+                        result?;
+                        log::info!(
+                            "Successfully submitted {} review for PR #{}",
+                            event_name,
+                            pr_number
+                        );
+                        dispatcher.dispatch(Action::StatusBar(StatusBarAction::success(
+                            format!("{} review submitted for PR #{}", event_name, pr_number),
+                            "Review",
+                        )));
+                        // Update review decision locally based on event type
+                        let decision = match event {
+                            gh_diff_viewer::ReviewEvent::Approve => {
+                                Some(crate::domain_models::ReviewDecision::Approved)
+                            }
+                            gh_diff_viewer::ReviewEvent::RequestChanges => {
+                                Some(crate::domain_models::ReviewDecision::ChangesRequested)
+                            }
+                            gh_diff_viewer::ReviewEvent::Comment => None, // Comments don't change decision
+                        };
+                        if let Some(decision) = decision {
+                            dispatcher.dispatch(Action::PullRequest(
+                                PullRequestAction::ReviewDecisionUpdated {
+                                    repo,
+                                    pr_number,
+                                    decision,
+                                },
+                            ));
+                        }
+                        Ok(())
+                    });
+
+                false // Consume action
+            }
+
+            Action::DiffViewer(DiffViewerAction::SubmitCommentRequest {
+                pr_number,
+                head_sha,
+                path,
+                position,
+                body,
+            }) => {
+                let repo_idx = state.main_view.selected_repository;
+                let Some(repo) = state.main_view.repositories.get(repo_idx).cloned() else {
+                    log::error!("No repository selected for comment submission");
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::error(
+                        "No repository selected",
+                        "Comment",
+                    )));
+                    return false;
+                };
+
+                let pr_number = *pr_number;
+                let head_sha = head_sha.clone();
+                let path = path.clone();
+                let position = position.clone();
+                let body = body.clone();
+                let location = comment_location(&path, &position);
+                let audit = self
+                    .github
+                    .audit(AuditOperation::Comment, &repo, state)
+                    .pr(pr_number as usize)
+                    .param("location", &location)
+                    .param("message", &body);
+
+                self.github
+                    .task(&repo, dispatcher)
+                    .operation(format!("Posting comment on PR #{}", pr_number), "Comment")
+                    .report("Comment failed", "Comment")
+                    .spawn(move |client, dispatcher| async move {
+                        // File comments are review comments without a line, general
+                        // comments belong to the PR conversation
+                        let result = match &position {
+                            CommentPosition::Line {
+                                side,
+                                line,
+                                start_line,
+                                start_side,
+                            } => {
+                                client
+                                    .create_review_comment(
+                                        &repo.org,
+                                        &repo.repo,
+                                        pr_number,
+                                        &head_sha,
+                                        &path,
+                                        &comment_lines(*side, *line, *start_line, *start_side),
+                                        &body,
+                                    )
+                                    .await
+                            }
+                            CommentPosition::File(file) => {
+                                client
+                                    .create_file_review_comment(
+                                        &repo.org, &repo.repo, pr_number, &head_sha, file, &body,
+                                    )
+                                    .await
+                            }
+                            CommentPosition::General => {
+                                client
+                                    .create_issue_comment(&repo.org, &repo.repo, pr_number, &body)
+                                    .await
+                            }
+                        };
+                        audit.finish(&result);
+                        let github_id = result?;
+
+                        log::info!(
+                            "Successfully posted comment on PR #{} at {} (id: {})",
+                            pr_number,
+                            location,
+                            github_id
+                        );
+                        dispatcher.dispatch(Action::StatusBar(StatusBarAction::success(
+                            format!("Comment posted on {}", location),
+                            "Comment",
+                        )));
+                        // Update local state with the GitHub comment ID
+                        dispatcher.dispatch(Action::DiffViewer(DiffViewerAction::CommentPosted {
+                            path,
+                            position,
+                            github_id,
+                        }));
+                        Ok(())
+                    });
+
+                false // Consume action
+            }
+
+            Action::DiffViewer(DiffViewerAction::DeleteCommentRequest {
+                pr_number,
+                github_id,
+                path,
+                position,
+            }) => {
+                let repo_idx = state.main_view.selected_repository;
+                let Some(repo) = state.main_view.repositories.get(repo_idx).cloned() else {
+                    log::error!("No repository selected for comment deletion");
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::error(
+                        "No repository selected",
+                        "Comment",
+                    )));
+                    return false;
+                };
+
+                let pr_number = *pr_number;
+                let github_id = *github_id;
+                let path = path.clone();
+                let position = position.clone();
+                let location = comment_location(&path, &position);
+
+                log::info!(
+                    "Deleting comment {} on PR #{} at {}",
+                    github_id,
+                    pr_number,
+                    location
+                );
+                let audit = self
+                    .github
+                    .audit(AuditOperation::DeleteComment, &repo, state)
+                    .pr(pr_number as usize)
+                    .param("location", &location)
+                    .param("comment_id", github_id);
+
+                self.github
+                    .task(&repo, dispatcher)
+                    .operation(format!("Deleting comment on {}", location), "Comment")
+                    .report("Delete failed", "Comment")
+                    .spawn(move |client, dispatcher| async move {
+                        let result = match position {
+                            CommentPosition::General => {
+                                client
+                                    .delete_issue_comment(&repo.org, &repo.repo, github_id)
+                                    .await
+                            }
+                            _ => {
+                                client
+                                    .delete_review_comment(&repo.org, &repo.repo, github_id)
+                                    .await
+                            }
+                        };
+                        audit.finish(&result);
+                        result?;
+
+                        log::info!(
+                            "Successfully deleted comment {} on PR #{} at {}",
+                            github_id,
+                            pr_number,
+                            location
+                        );
+                        dispatcher.dispatch(Action::StatusBar(StatusBarAction::success(
+                            format!("Comment deleted from {}", location),
+                            "Comment",
+                        )));
+                        // Update local state to remove the comment
+                        dispatcher.dispatch(Action::DiffViewer(DiffViewerAction::CommentDeleted {
+                            path,
+                            position,
+                        }));
+                        Ok(())
+                    });
+
+                false // Consume action
+            }
+
+            _ => true, // Pass through other actions
+        }
+    }
+}
+
+/// Number of comments posted at once by a bulk comment
+const COMMENT_CONCURRENCY: usize = 4;
+
+/// Post each rendered comment on its PR, returns the PRs that failed with why
+async fn post_comments(
+    clients: Arc<dyn ClientFactory>,
+    repo: Repository,
+    posts: Vec<(u64, String, InFlightGuard, PendingAudit)>,
+) -> Vec<(u64, String)> {
+    let client = match clients.client(&repo, false).await {
+        Ok(client) => client,
+        Err(e) => {
+            log::error!("Failed to get client: {}", e);
+            let reason = first_line(&e.to_string());
+            return posts
+                .into_iter()
+                .map(|(pr_number, _, _, audit)| {
+                    audit.failed(&e);
+                    (pr_number, reason.clone())
+                })
+                .collect();
+        }
+    };
+
+    let posting = Arc::new(tokio::sync::Semaphore::new(COMMENT_CONCURRENCY));
+    let mut tasks = tokio::task::JoinSet::new();
+    for (pr_number, body, in_flight, audit) in posts {
+        let (client, posting, repo) = (Arc::clone(&client), Arc::clone(&posting), repo.clone());
+        tasks.spawn(async move {
+            let _in_flight = in_flight;
+            let Ok(_permit) = posting.acquire().await else {
+                audit.failed("cancelled");
+                return Some((pr_number, "cancelled".to_string()));
+            };
+            let result = client
+                .create_issue_comment(&repo.org, &repo.repo, pr_number, &body)
+                .await;
+            audit.finish(&result);
+            match result {
+                Ok(_) => {
+                    log::info!("Successfully commented on PR #{}", pr_number);
+                    None
+                }
+                Err(e) => {
+                    log::error!("Comment error on PR #{}: {}", pr_number, e);
+                    Some((pr_number, first_line(&e.to_string())))
+                }
+            }
+        });
+    }
+
+    let mut failures = Vec::new();
+    while let Some(result) = tasks.join_next().await {
+        match result {
+            Ok(Some(failure)) => failures.push(failure),
+            Ok(None) => {}
+            Err(e) => log::error!("Comment task failed: {}", e),
+        }
+    }
+    failures.sort();
+    failures
+}
+
+/// First line of an error, short enough for the status bar
+fn first_line(error: &str) -> String {
+    error.lines().next().unwrap_or_default().trim().to_string()
+}
+
+/// Status bar message of a bulk comment on `pr_numbers`
+fn comment_summary(pr_numbers: &[u64], failures: &[(u64, String)]) -> String {
+    match (pr_numbers, failures) {
+        ([pr_number], []) => format!("Commented on PR #{}", pr_number),
+        (_, []) => format!("Commented on {} PRs", pr_numbers.len()),
+        ([_], [(_, reason)]) => format!("Comment failed: {}", reason),
+        _ => {
+            let failed: Vec<String> = failures
+                .iter()
+                .map(|(pr_number, reason)| format!("#{} ({})", pr_number, reason))
+                .collect();
+            format!(
+                "Posted to {}/{}, failed: {}",
+                pr_numbers.len() - failures.len(),
+                pr_numbers.len(),
+                failed.join(", ")
+            )
+        }
+    }
+}
+
+/// PRs that can be approved, and the warnings to show in the confirmation
+///
+/// PRs authored by `viewer_login` are left out (GitHub rejects approving
+/// them), PRs without passing CI are kept but flagged.
+fn approval_targets(prs: &[&Pr], viewer_login: Option<&str>) -> (Vec<u64>, Vec<String>) {
+    let mut pr_numbers = Vec::new();
+    let mut warnings = Vec::new();
+    for pr in prs {
+        if viewer_login.is_some_and(|login| pr.author.eq_ignore_ascii_case(login)) {
+            warnings.push(format!("#{} skipped: you are its author", pr.number));
+            continue;
+        }
+        match pr.mergeable {
+            MergeableStatus::BuildFailed => warnings.push(format!("#{}: CI is failing", pr.number)),
+            MergeableStatus::OptionalFailed => {
+                warnings.push(format!("#{}: optional checks are failing", pr.number))
+            }
+            MergeableStatus::Checking => {
+                warnings.push(format!("#{}: CI is still running", pr.number))
+            }
+            MergeableStatus::Queued => warnings.push(format!("#{}: CI is queued", pr.number)),
+            MergeableStatus::WaitingApproval => {
+                warnings.push(format!("#{}: CI waits for approval", pr.number))
+            }
+            MergeableStatus::Unknown => {
+                warnings.push(format!("#{}: CI has not reported yet", pr.number))
+            }
+            _ => {}
+        }
+        pr_numbers.push(pr.number as u64);
+    }
+    (pr_numbers, warnings)
+}
+
+/// Delete the head branch of a merged PR, reporting the outcome separately
+///
+/// `head` is the PR's head branch and whether it comes from a fork (None if
+/// the PR was not loaded).
+async fn delete_merged_branch(
+    client: &dyn GitHubClient,
+    repo: &Repository,
+    pr_number: usize,
+    head: Option<(String, bool)>,
+    has_dependents: bool,
+    dispatcher: &Dispatcher,
+) {
+    let (branch, is_from_fork) = head.unwrap_or_default();
+    let default_branch = match client
+        .fetch_repository_settings(&repo.org, &repo.repo)
+        .await
+    {
+        Ok(settings) => settings.default_branch,
+        Err(e) => {
+            dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                format!(
+                    "PR #{}: branch not deleted, repository settings unavailable: {}",
+                    pr_number, e
+                ),
+                "Merge",
+            )));
+            return;
+        }
+    };
+
+    if let Some(reason) =
+        branch_deletion_skip_reason(&branch, is_from_fork, &default_branch, has_dependents)
+    {
+        log::info!(
+            "PR #{}: not deleting branch '{}': {}",
+            pr_number,
+            branch,
+            reason
+        );
+        dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+            format!("PR #{}: branch not deleted ({})", pr_number, reason),
+            "Merge",
+        )));
+        return;
+    }
+
+    match client.delete_branch(&repo.org, &repo.repo, &branch).await {
+        Ok(()) => {
+            log::info!("Deleted branch '{}' of PR #{}", branch, pr_number);
+            dispatcher.dispatch(Action::StatusBar(StatusBarAction::success(
+                format!("PR #{}: branch '{}' deleted", pr_number, branch),
+                "Merge",
+            )));
+        }
+        Err(e) => {
+            log::error!("Failed to delete branch '{}': {}", branch, e);
+            dispatcher.dispatch(Action::StatusBar(StatusBarAction::error(
+                format!(
+                    "PR #{} merged, but deleting branch '{}' failed: {}",
+                    pr_number, branch, e
+                ),
+                "Merge",
+            )));
+        }
+    }
+}
+
+/// Why the head branch of a merged PR must be kept, None if it can go
+fn branch_deletion_skip_reason(
+    branch: &str,
+    is_from_fork: bool,
+    default_branch: &str,
+    has_dependents: bool,
+) -> Option<&'static str> {
+    if branch.is_empty() {
+        Some("head branch unknown")
+    } else if branch == default_branch {
+        Some("it is the default branch")
+    } else if is_from_fork {
+        Some("it lives in a fork")
+    } else if has_dependents {
+        Some("stacked PRs are based on it")
+    } else {
+        None
+    }
+}
+
+/// Where a comment sits, for status messages ("src/lib.rs:42", "src/lib.rs", "the PR")
+fn comment_location(path: &str, position: &CommentPosition) -> String {
+    match position {
+        CommentPosition::Line { line, .. } => format!("{}:{}", path, line),
+        CommentPosition::File(file) => file.clone(),
+        CommentPosition::General => "the PR".to_string(),
+    }
+}
+
+/// Lines a line comment is anchored to on GitHub
+///
+/// A range keeps the side of each end, so it can start on a deleted line and
+/// end on an added one.
+fn comment_lines(
+    side: DiffSide,
+    line: u32,
+    start_line: Option<u32>,
+    start_side: Option<DiffSide>,
+) -> CommentLines {
+    match start_line {
+        Some(start_line) => CommentLines::range(
+            start_side.unwrap_or(side).as_github_str(),
+            start_line,
+            side.as_github_str(),
+            line,
+        ),
+        None => CommentLines::single(side.as_github_str(), line),
+    }
+}
+
+/// Name of a merge method in the audit log ("squash")
+fn merge_method_name(method: MergeMethod) -> &'static str {
+    match method {
+        MergeMethod::Merge => "merge",
+        MergeMethod::Squash => "squash",
+        MergeMethod::Rebase => "rebase",
+    }
+}
+
+/// Status bar message for a merge GitHub refused
+///
+/// Known reasons come with the command that gets the PR unstuck, anything
+/// else shows GitHub's own message.
+fn merge_failure_message(pr_number: usize, result: &MergeResult, keymap: &Keymap) -> String {
+    let Some(rejection) = result.rejection else {
+        return format!("Merge failed: {}", result.message);
+    };
+    let (command, next_step) = match rejection {
+        MergeRejection::ReviewRequired => (CommandId::PrRequestReviewers, "request reviewers"),
+        MergeRejection::ChecksPending => (CommandId::PrOpenChecksInBrowser, "follow the checks"),
+        MergeRejection::ChecksFailed => (CommandId::PrOpenBuildLogs, "open the build logs"),
+        MergeRejection::OutOfDate => (CommandId::PrRebase, "rebase it"),
+        MergeRejection::Conflicts => (CommandId::PrInspectConflicts, "inspect the conflicts"),
+        MergeRejection::MergeQueueRequired => (CommandId::PrOpenInBrowser, "queue it on GitHub"),
+        MergeRejection::Draft => (CommandId::PrOpenInBrowser, "mark it ready on GitHub"),
+    };
+    let hint = match keymap.compact_hint_for_command(command) {
+        Some(key) => format!("press '{}' to {}", key, next_step),
+        None => format!("run \"{}\" to {}", command.title(), next_step),
+    };
+    format!(
+        "Can not merge PR #{}: {}, {}",
+        pr_number,
+        rejection.describe(),
+        hint
+    )
+}
+
+/// Status bar message for a failed approval
+fn approve_error_message(pr_number: u64, error: &impl std::fmt::Display) -> String {
+    let error = error.to_string();
+    // GitHub answers 422 when approving an own PR
+    if error.contains("Can not approve your own pull request") {
+        format!("Can not approve PR #{}: you are its author", pr_number)
+    } else {
+        format!("Approve failed: {}", error)
+    }
+}
+
+/// PR labels after a successful label change
+fn apply_label_change(mut labels: Vec<String>, change: &LabelChanges) -> Vec<String> {
+    labels.retain(|label| !change.remove.contains(label));
+    for label in &change.add {
+        if !labels.contains(label) {
+            labels.push(label.clone());
+        }
+    }
+    labels
+}
+
+/// Short summary of a label change (e.g. "+skip-changelog -wip")
+fn describe_label_change(change: &LabelChanges) -> String {
+    change
+        .add
+        .iter()
+        .map(|label| format!("+{}", label))
+        .chain(change.remove.iter().map(|label| format!("-{}", label)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Owners of a PR's changed files, most owned files first
+///
+/// The CODEOWNERS file is read at the head of the PR's base branch and kept
+/// until that branch moves. Empty when the repository has none or anything
+/// can't be fetched, the picker then lists the collaborators only.
+async fn suggest_code_owners(
+    client: &dyn GitHubClient,
+    repo: &Repository,
+    pr: &Pr,
+    cache: &Mutex<CodeOwnersCache>,
+) -> Vec<(Owner, usize)> {
+    let suggested = async {
+        let base_sha = client
+            .fetch_branch_head_sha(&repo.org, &repo.repo, &pr.base_branch)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("branch {} not found", pr.base_branch))?;
+        let cached = cache
+            .lock()
+            .unwrap()
+            .get(repo)
+            .filter(|(sha, _)| *sha == base_sha)
+            .map(|(_, owners)| owners.clone());
+        let owners = match cached {
+            Some(owners) => owners,
+            None => {
+                let owners = read_codeowners(client, repo, &base_sha).await;
+                cache
+                    .lock()
+                    .unwrap()
+                    .insert(repo.clone(), (base_sha, owners.clone()));
+                owners
+            }
+        };
+        let Some(owners) = owners else {
+            return Ok(Vec::new());
+        };
+        let comparison = client
+            .fetch_compare(&repo.org, &repo.repo, &pr.base_branch, &pr.head_sha)
+            .await?;
+        anyhow::Ok(owners.suggest(comparison.files.iter().map(|f| f.filename.as_str())))
+    }
+    .await;
+
+    suggested.unwrap_or_else(|e| {
+        log::warn!(
+            "No reviewer suggestions for {}/{}#{}: {:#}",
+            repo.org,
+            repo.repo,
+            pr.number,
+            e
+        );
+        Vec::new()
+    })
+}
+
+/// First CODEOWNERS found at a commit, in the order GitHub looks for it
+async fn read_codeowners(
+    client: &dyn GitHubClient,
+    repo: &Repository,
+    git_ref: &str,
+) -> Option<CodeOwners> {
+    for path in CODEOWNERS_PATHS {
+        match client
+            .fetch_file_content(&repo.org, &repo.repo, path, Some(git_ref))
+            .await
+        {
+            Ok(content) => return Some(CodeOwners::parse(&content)),
+            Err(e) => log::debug!("No {} in {}/{}: {:#}", path, repo.org, repo.repo, e),
+        }
+    }
+    None
+}
+
+/// Close comment from a repository's template
+///
+/// `{pr_number}` and `{author}` list the closed PRs and their authors.
+fn expand_close_template(template: &str, prs: &[(usize, String)]) -> String {
+    let pr_numbers: Vec<String> = prs.iter().map(|(pr, _)| pr.to_string()).collect();
+    let mut authors: Vec<&str> = Vec::new();
+    for (_, author) in prs {
+        if !authors.contains(&author.as_str()) {
+            authors.push(author);
+        }
+    }
+    template::render(template.trim_end(), |name| match name {
+        "pr_number" => Some(pr_numbers.join(", ")),
+        "author" => Some(authors.join(", ")),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr(number: usize, author: &str, mergeable: MergeableStatus) -> Pr {
+        let mut pr = Pr::new(number, "title", author, "sha");
+        pr.mergeable = mergeable;
+        pr
+    }
+
+    #[test]
+    fn test_expand_close_template() {
+        let template = "Thanks @{author}! Closing #{pr_number} as per POLICY.md.\n";
+        assert_eq!(
+            expand_close_template(template, &[(7, "alice".to_string())]),
+            "Thanks @alice! Closing #7 as per POLICY.md."
+        );
+        assert_eq!(
+            expand_close_template(
+                "{pr_number} by {author}",
+                &[
+                    (7, "alice".to_string()),
+                    (8, "bob".to_string()),
+                    (9, "alice".to_string())
+                ]
+            ),
+            "7, 8, 9 by alice, bob"
+        );
+    }
+
+    #[test]
+    fn test_branch_deletion_protection() {
+        assert_eq!(
+            branch_deletion_skip_reason("feature", false, "main", false),
+            None
+        );
+        assert_eq!(
+            branch_deletion_skip_reason("main", false, "main", false),
+            Some("it is the default branch")
+        );
+        assert_eq!(
+            branch_deletion_skip_reason("feature", true, "main", false),
+            Some("it lives in a fork")
+        );
+        assert_eq!(
+            branch_deletion_skip_reason("feature", false, "main", true),
+            Some("stacked PRs are based on it")
+        );
+        assert!(branch_deletion_skip_reason("", false, "main", false).is_some());
+    }
+
+    #[test]
+    fn test_approval_targets_skips_own_prs() {
+        let own = pr(1, "Alice", MergeableStatus::Ready);
+        let other = pr(2, "bob", MergeableStatus::Ready);
+        let (pr_numbers, warnings) = approval_targets(&[&own, &other], Some("alice"));
+        assert_eq!(pr_numbers, vec![2]);
+        assert_eq!(warnings, vec!["#1 skipped: you are its author"]);
+
+        // Without a known login nothing is skipped
+        let (pr_numbers, _) = approval_targets(&[&own, &other], None);
+        assert_eq!(pr_numbers, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_approval_targets_flags_ci() {
+        let failing = pr(1, "bob", MergeableStatus::BuildFailed);
+        let running = pr(2, "bob", MergeableStatus::Checking);
+        let conflicted = pr(3, "bob", MergeableStatus::Conflicted);
+        let (pr_numbers, warnings) =
+            approval_targets(&[&failing, &running, &conflicted], Some("alice"));
+        assert_eq!(pr_numbers, vec![1, 2, 3]);
+        assert_eq!(
+            warnings,
+            vec!["#1: CI is failing", "#2: CI is still running"]
+        );
+    }
+
+    #[test]
+    fn test_merge_failure_message() {
+        let refused = |message: &str| MergeResult {
+            merged: false,
+            sha: None,
+            message: message.to_string(),
+            rejection: MergeRejection::parse(message),
+        };
+        let keymap = crate::keymap::default_keymap();
+
+        assert_eq!(
+            merge_failure_message(7, &refused("Head branch is not up to date"), &keymap),
+            "Can not merge PR #7: the branch is out of date with its base, press 'p -> r' to rebase it"
+        );
+        assert_eq!(
+            merge_failure_message(
+                7,
+                &refused("Required status check \"build\" is failing."),
+                &keymap
+            ),
+            "Can not merge PR #7: required checks are failing, press 'p -> l' to open the build logs"
+        );
+        // Without a key the command palette entry is named
+        assert_eq!(
+            merge_failure_message(7, &refused("Pull Request is not mergeable"), &Keymap::new(vec![])),
+            "Can not merge PR #7: the branch has merge conflicts, run \"Inspect conflicts\" to inspect the conflicts"
+        );
+        assert_eq!(
+            merge_failure_message(7, &refused("Head branch was modified."), &keymap),
+            "Merge failed: Head branch was modified."
+        );
+    }
+
+    #[test]
+    fn test_approve_error_message() {
+        let own = "Unprocessable Entity: Review Can not approve your own pull request";
+        assert_eq!(
+            approve_error_message(7, &own),
+            "Can not approve PR #7: you are its author"
+        );
+        assert_eq!(
+            approve_error_message(7, &"timeout"),
+            "Approve failed: timeout"
+        );
+    }
+
+    #[test]
+    fn test_label_change_helpers() {
+        let change = LabelChanges {
+            add: vec!["skip-changelog".to_string(), "bug".to_string()],
+            remove: vec!["wip".to_string()],
+        };
+        assert_eq!(describe_label_change(&change), "+skip-changelog +bug -wip");
+        assert_eq!(
+            apply_label_change(vec!["wip".to_string(), "bug".to_string()], &change),
+            vec!["bug".to_string(), "skip-changelog".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_comment_summary() {
+        let failed = |pr: u64| (pr, "Not Found".to_string());
+        assert_eq!(comment_summary(&[7], &[]), "Commented on PR #7");
+        assert_eq!(comment_summary(&[7, 8], &[]), "Commented on 2 PRs");
+        assert_eq!(
+            comment_summary(&[7], &[failed(7)]),
+            "Comment failed: Not Found"
+        );
+        assert_eq!(
+            comment_summary(&[7, 8, 9], &[failed(7), failed(9)]),
+            "Posted to 1/3, failed: #7 (Not Found), #9 (Not Found)"
+        );
+    }
+
+    #[test]
+    fn test_comment_lines_from_diff_selection() {
+        use gh_diff_viewer::{parse_unified_diff, DiffAction, DiffViewerState};
+
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -10,4 +10,4 @@ fn setup() {
+ let a = 1;
+-let b = 2;
++let b = 3;
+ let c = 4;
+@@ -40,2 +40,3 @@ fn teardown() {
+ drop(a);
++drop(b);
+ drop(c);
+";
+        let mut viewer = DiffViewerState::new(parse_unified_diff(diff, "base", "head").unwrap());
+        viewer.nav.file_tree_focused = false;
+        let mut lines_of = |anchor: usize, cursor: usize| {
+            viewer.nav.cursor_line = anchor;
+            viewer.handle_action(DiffAction::EnterVisualMode);
+            viewer.nav.cursor_line = cursor;
+            viewer.handle_action(DiffAction::StartComment);
+            let CommentPosition::Line {
+                side,
+                line,
+                start_line,
+                start_side,
+            } = viewer.comment_editor.take().unwrap().position
+            else {
+                panic!("not a line comment");
+            };
+            comment_lines(side, line, start_line, start_side)
+        };
+
+        // A deleted line is anchored on the old file
+        assert_eq!(lines_of(2, 2), CommentLines::single("LEFT", 11));
+        // From the deleted line to the added one, selected upwards
+        assert_eq!(lines_of(3, 2), CommentLines::range("LEFT", 11, "RIGHT", 11));
+        // Across the hunk header, only the lines of the cursor's hunk count
+        assert_eq!(
+            lines_of(3, 8),
+            CommentLines::range("RIGHT", 40, "RIGHT", 42)
+        );
+    }
+
+    mod dispatch {
+        use super::*;
+        use crate::domain_models::ReviewDecision;
+        use crate::middleware::test_clients::{
+            run_middleware, state, status_messages, MockGitHubClient,
+        };
+        use crate::state::StatusKind;
+
+        /// Handle `action` and return every action dispatched until all
+        /// spawned operations finished
+        fn run(client: MockGitHubClient, action: Action) -> (Vec<Action>, Arc<MockGitHubClient>) {
+            run_in(&state(), client, action)
+        }
+
+        /// [`run`] with the given state
+        fn run_in(
+            state: &AppState,
+            client: MockGitHubClient,
+            action: Action,
+        ) -> (Vec<Action>, Arc<MockGitHubClient>) {
+            run_middleware(PrMutationMiddleware::new, state, client, action)
+        }
+
+        fn merge(pr_number: u64) -> Action {
+            Action::PullRequest(PullRequestAction::MergePrs {
+                pr_numbers: vec![pr_number],
+                delete_branch: false,
+            })
+        }
+
+        #[test]
+        fn test_merge_reports_success_and_refreshes() {
+            let client = MockGitHubClient {
+                merge_result: Some(MergeResult {
+                    merged: true,
+                    sha: Some("merge-sha".to_string()),
+                    message: "Pull Request successfully merged".to_string(),
+                    rejection: None,
+                }),
+                ..Default::default()
+            };
+            let (actions, client) = run(client, merge(7));
+
+            assert!(matches!(
+                actions.first(),
+                Some(Action::PullRequest(PullRequestAction::MergeStart {
+                    pr_number: 7,
+                    ..
+                }))
+            ));
+            assert!(matches!(
+                actions[1],
+                Action::StatusBar(StatusBarAction::OperationStarted { .. })
+            ));
+            assert_eq!(
+                status_messages(&actions),
+                vec![(StatusKind::Success, "PR #7 merged".to_string())]
+            );
+            let refresh = actions
+                .iter()
+                .position(|a| matches!(a, Action::PullRequest(PullRequestAction::Refresh)))
+                .expect("merge triggers a refresh");
+            let finished = actions
+                .iter()
+                .position(|a| {
+                    matches!(
+                        a,
+                        Action::StatusBar(StatusBarAction::OperationFinished { .. })
+                    )
+                })
+                .expect("operation ends");
+            assert!(refresh < finished);
+            assert_eq!(client.calls(), vec!["merge_pull_request acme/api#7"]);
+        }
+
+        #[test]
+        fn test_merge_reports_failures() {
+            let client = MockGitHubClient {
+                merge_result: Some(MergeResult {
+                    merged: false,
+                    sha: None,
+                    message: "Base branch was modified".to_string(),
+                    rejection: None,
+                }),
+                ..Default::default()
+            };
+            let (actions, _) = run(client, merge(7));
+            assert_eq!(
+                status_messages(&actions),
+                vec![(
+                    StatusKind::Error,
+                    "Merge failed: Base branch was modified".to_string()
+                )]
+            );
+            assert!(!actions
+                .iter()
+                .any(|a| matches!(a, Action::PullRequest(PullRequestAction::Refresh))));
+
+            let client = MockGitHubClient {
+                merge_result: Some(MergeResult {
+                    merged: false,
+                    sha: None,
+                    message: "At least 1 approving review is required".to_string(),
+                    rejection: Some(MergeRejection::ReviewRequired),
+                }),
+                ..Default::default()
+            };
+            let (actions, _) = run(client, merge(7));
+            assert_eq!(
+                status_messages(&actions),
+                vec![(
+                    StatusKind::Error,
+                    "Can not merge PR #7: an approving review is required, press 'p -> R' to request reviewers"
+                        .to_string()
+                )]
+            );
+
+            let client = MockGitHubClient::default().failing("merge_pull_request", "Not Found");
+            let (actions, _) = run(client, merge(7));
+            assert_eq!(
+                status_messages(&actions),
+                vec![(StatusKind::Error, "Merge error: Not Found".to_string())]
+            );
+        }
+
+        #[test]
+        fn test_approve_with_message() {
+            let action = Action::PullRequest(PullRequestAction::ApproveWithMessage {
+                pr_numbers: vec![7],
+                message: "LGTM".to_string(),
+            });
+            let (actions, client) = run(MockGitHubClient::default(), action.clone());
+
+            assert_eq!(
+                status_messages(&actions),
+                vec![(StatusKind::Success, "PR #7 approved".to_string())]
+            );
+            assert!(actions.iter().any(|a| matches!(
+                a,
+                Action::PullRequest(PullRequestAction::ReviewDecisionUpdated {
+                    pr_number: 7,
+                    decision: ReviewDecision::Approved,
+                    ..
+                })
+            )));
+            assert_eq!(
+                client.calls(),
+                vec!["create_review acme/api#7 Approve LGTM"]
+            );
+
+            let client = MockGitHubClient::default().failing("create_review", "Forbidden");
+            let (actions, _) = run(client, action);
+            assert_eq!(
+                status_messages(&actions),
+                vec![(StatusKind::Error, "Approve failed: Forbidden".to_string())]
+            );
+            assert!(!actions.iter().any(|a| matches!(
+                a,
+                Action::PullRequest(PullRequestAction::ReviewDecisionUpdated { .. })
+            )));
+        }
+
+        #[test]
+        fn test_close_comments_before_closing() {
+            let action = Action::PullRequest(PullRequestAction::ClosePrWithMessage {
+                pr_numbers: vec![7],
+                message: "Superseded by #8".to_string(),
+            });
+            let (actions, client) = run(MockGitHubClient::default(), action.clone());
+
+            assert_eq!(
+                status_messages(&actions),
+                vec![(StatusKind::Success, "PR #7 closed".to_string())]
+            );
+            assert!(actions
+                .iter()
+                .any(|a| matches!(a, Action::PullRequest(PullRequestAction::Refresh))));
+            assert_eq!(
+                client.calls(),
+                vec![
+                    "create_issue_comment acme/api#7 Superseded by #8",
+                    "close_pull_request acme/api#7"
+                ]
+            );
+
+            let client = MockGitHubClient::default().failing("close_pull_request", "Forbidden");
+            let (actions, _) = run(client, action);
+            assert_eq!(
+                status_messages(&actions),
+                vec![(StatusKind::Error, "Close failed: Forbidden".to_string())]
+            );
+            assert!(matches!(
+                actions.last(),
+                Some(Action::PullRequest(PullRequestAction::OperationFinished {
+                    pr_number: 7,
+                    ..
+                }))
+            ));
+        }
+
+        #[test]
+        fn test_close_prefills_the_repository_template() {
+            use crate::actions::ConfirmationPopupAction;
+            use crate::domain_models::Pr;
+            use crate::state::RepositoryData;
+
+            let mut state = state();
+            state.main_view.repo_data.insert(
+                0,
+                RepositoryData {
+                    prs: vec![Pr::new(7, "Fix", "alice", "sha")],
+                    ..Default::default()
+                },
+            );
+            state.app_config.repos.insert(
+                "acme/*".to_string(),
+                gh_pr_config::RepoSettings {
+                    close_comment_template: Some(".github/CLOSING.md".to_string()),
+                    ..Default::default()
+                },
+            );
+            let close = || Action::PullRequest(PullRequestAction::CloseRequest);
+            let message = |actions: &[Action]| match actions {
+                [Action::ConfirmationPopup(ConfirmationPopupAction::Show {
+                    default_message,
+                    ..
+                })] => default_message.clone(),
+                _ => panic!("unexpected actions {:?}", actions),
+            };
+
+            let client = MockGitHubClient {
+                files: std::collections::HashMap::from([(
+                    ".github/CLOSING.md".to_string(),
+                    "Closing #{pr_number} by @{author}, see POLICY.md\n".to_string(),
+                )]),
+                ..Default::default()
+            };
+            let (actions, client) = run_in(&state, client, close());
+            assert_eq!(message(&actions), "Closing #7 by @alice, see POLICY.md");
+            assert_eq!(
+                client.calls(),
+                vec!["fetch_file_content acme/api .github/CLOSING.md@HEAD"]
+            );
+
+            // Without the template, the global close message is used silently
+            let (actions, _) = run_in(&state, MockGitHubClient::default(), close());
+            assert_eq!(message(&actions), state.app_config.close_message);
+        }
+
+        #[test]
+        fn test_merge_confirmation_warns_about_unusual_bases() {
+            use crate::actions::ConfirmationPopupAction;
+            use crate::domain_models::Pr;
+            use crate::state::RepositoryData;
+
+            let mut state = state();
+            state.main_view.repo_data.insert(
+                0,
+                RepositoryData {
+                    prs: vec![
+                        Pr::new(7, "Bump serde", "dependabot[bot]", "a")
+                            .with_branches("bump", "release/1.4"),
+                        Pr::new(8, "Fix", "alice", "b").with_branches("fix", "main"),
+                    ],
+                    selected_pr_numbers: [7, 8].into_iter().collect(),
+                    ..Default::default()
+                },
+            );
+            state.app_config.repos.insert(
+                "acme/api".to_string(),
+                gh_pr_config::RepoSettings {
+                    delete_branch_after_merge: Some(false),
+                    ..Default::default()
+                },
+            );
+
+            let (actions, _) = run_in(
+                &state,
+                MockGitHubClient::default(),
+                Action::PullRequest(PullRequestAction::MergeRequest),
+            );
+            match actions.as_slice() {
+                [Action::ConfirmationPopup(ConfirmationPopupAction::Show { warnings, .. })] => {
+                    assert_eq!(warnings, &vec!["#7: merging into release/1.4!".to_string()])
+                }
+                _ => panic!("unexpected actions {:?}", actions),
+            }
+        }
+
+        #[test]
+        fn test_reviewer_picker_suggests_code_owners() {
+            use crate::domain_models::Pr;
+            use crate::state::RepositoryData;
+            use gh_client::types::{ChangedFile, Comparison};
+
+            let mut pr = Pr::new(7, "Fix", "alice", "head");
+            pr.base_branch = "main".to_string();
+            let mut state = state();
+            state.main_view.repo_data.insert(
+                0,
+                RepositoryData {
+                    prs: vec![pr],
+                    ..Default::default()
+                },
+            );
+            let changed = |filename: &str| ChangedFile {
+                filename: filename.to_string(),
+                previous_filename: None,
+                status: "modified".to_string(),
+                additions: 1,
+                deletions: 1,
+            };
+            let client = MockGitHubClient {
+                collaborators: Some(vec!["alice".to_string(), "dave".to_string()]),
+                branch_head_sha: Some("base".to_string()),
+                files: std::collections::HashMap::from([(
+                    "CODEOWNERS".to_string(),
+                    "*.rs @bob\n/docs/ @acme/writers @alice\n".to_string(),
+                )]),
+                comparison: Some(Comparison {
+                    merge_base_sha: "base".to_string(),
+                    ahead_by: 1,
+                    behind_by: 0,
+                    files: vec![changed("src/lib.rs"), changed("docs/intro.md")],
+                }),
+                ..Default::default()
+            };
+            let (actions, client) = run_in(
+                &state,
+                client,
+                Action::ReviewerPicker(ReviewerPickerAction::Open),
+            );
+
+            assert!(matches!(
+                actions.as_slice(),
+                [
+                    Action::ReviewerPicker(ReviewerPickerAction::Show { pr_number: 7, .. }),
+                    Action::ReviewerPicker(ReviewerPickerAction::Loaded { suggested, collaborators }),
+                ] if suggested == &[
+                    (Owner::User("alice".to_string()), 1),
+                    (Owner::User("bob".to_string()), 1),
+                    (Owner::Team("acme/writers".to_string()), 1),
+                ] && collaborators.len() == 2
+            ));
+            // CODEOWNERS is looked up where GitHub does, at the base branch head
+            assert_eq!(
+                client.calls(),
+                vec![
+                    "fetch_collaborators acme/api",
+                    "fetch_branch_head_sha acme/api main",
+                    "fetch_file_content acme/api .github/CODEOWNERS@base",
+                    "fetch_file_content acme/api CODEOWNERS@base",
+                    "fetch_compare acme/api main...head",
+                ]
+            );
+        }
+
+        #[test]
+        fn test_bulk_comment_fills_in_placeholders_and_offers_a_retry() {
+            use crate::actions::ConfirmationPopupAction;
+            use crate::domain_models::Pr;
+            use crate::state::{ConfirmationIntent, RepositoryData};
+
+            let mut state = state();
+            state.main_view.repo_data.insert(
+                0,
+                RepositoryData {
+                    prs: vec![
+                        Pr::new(7, "Fix", "alice", "sha").with_branches("fix", "main"),
+                        Pr::new(8, "Bump", "bob", "sha").with_branches("bump", "main"),
+                    ],
+                    ..Default::default()
+                },
+            );
+            let comment = |pr_numbers: Vec<u64>| {
+                Action::PullRequest(PullRequestAction::CommentOnPr {
+                    pr_numbers,
+                    message: "@{author} please rebase {branch}".to_string(),
+                })
+            };
+
+            let client = MockGitHubClient {
+                pr_errors: std::collections::HashMap::from([(
+                    8,
+                    "Forbidden\nlocked conversation".to_string(),
+                )]),
+                ..Default::default()
+            };
+            let (actions, client) = run_in(&state, client, comment(vec![7, 8, 9]));
+            let mut calls = client.calls();
+            calls.sort();
+            assert_eq!(
+                calls,
+                vec![
+                    "create_issue_comment acme/api#7 @alice please rebase fix",
+                    "create_issue_comment acme/api#8 @bob please rebase bump",
+                    "create_issue_comment acme/api#9 @{author} please rebase {branch}",
+                ]
+            );
+            assert_eq!(
+                status_messages(&actions),
+                vec![(
+                    StatusKind::Error,
+                    "Posted to 2/3, failed: #8 (Forbidden)".to_string()
+                )]
+            );
+            let retry = actions.iter().find_map(|action| match action {
+                Action::ConfirmationPopup(ConfirmationPopupAction::Show {
+                    intent:
+                        ConfirmationIntent::RetryComment {
+                            pr_numbers,
+                            failures,
+                        },
+                    default_message,
+                    ..
+                }) => Some((
+                    pr_numbers.clone(),
+                    failures.clone(),
+                    default_message.clone(),
+                )),
+                _ => None,
+            });
+            assert_eq!(
+                retry,
+                Some((
+                    vec![8],
+                    vec!["#8  Forbidden".to_string()],
+                    "@{author} please rebase {branch}".to_string()
+                ))
+            );
+
+            let (actions, _) = run_in(&state, MockGitHubClient::default(), comment(vec![7]));
+            assert_eq!(
+                status_messages(&actions),
+                vec![(StatusKind::Success, "Commented on PR #7".to_string())]
+            );
+        }
+
+        #[test]
+        fn test_operations_are_audited() {
+            use crate::audit::AuditLog;
+            use crate::middleware::test_clients::repo;
+
+            let dir = std::env::temp_dir().join(format!(
+                "gh-pr-lander-mutation-audit-{}",
+                std::process::id()
+            ));
+            std::fs::create_dir_all(&dir).unwrap();
+            let path = dir.join("audit.jsonl");
+            let _ = std::fs::remove_file(&path);
+            let log = AuditLog::open(path.clone());
+            let audited = |mut github: GitHubContext| {
+                github.audit_log = log.clone();
+                PrMutationMiddleware::new(github)
+            };
+
+            let mut state = state();
+            state
+                .main_view
+                .account_logins
+                .insert(repo().account_key(), "alice".to_string());
+            let approve = Action::PullRequest(PullRequestAction::ApproveWithMessage {
+                pr_numbers: vec![7, 8],
+                message: "LGTM".to_string(),
+            });
+            run_middleware(audited, &state, MockGitHubClient::default(), approve);
+            let client = MockGitHubClient::default().failing("merge_pull_request", "Not Found");
+            run_middleware(audited, &state, client, merge(9));
+            log.sync();
+
+            // One JSON object per operation
+            let content = std::fs::read_to_string(&path).unwrap();
+            let lines: Vec<serde_json::Value> = content
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect();
+            assert_eq!(lines.len(), 3);
+            let mut approved: Vec<u64> = lines[..2]
+                .iter()
+                .map(|line| line["pr"].as_u64().unwrap())
+                .collect();
+            approved.sort();
+            assert_eq!(approved, vec![7, 8]);
+            for line in &lines[..2] {
+                assert_eq!(line["operation"], "approve");
+                assert_eq!(line["login"], "alice");
+                assert_eq!(line["params"]["message"], "LGTM");
+                assert_eq!(line["outcome"], "success");
+            }
+            assert_eq!(lines[2]["operation"], "merge");
+            assert_eq!(lines[2]["pr"], 9);
+            assert_eq!(lines[2]["params"]["method"], "squash");
+            assert_eq!(lines[2]["outcome"], "failure");
+            assert!(lines[2]["error"].as_str().unwrap().contains("Not Found"));
+
+            std::fs::remove_dir_all(&dir).ok();
+        }
+    }
+}
@@ -1,21 +1,277 @@
 //! Pull Request Middleware
 //!
-//! Handles PR-specific side effects.
+//! Handles PR-specific side effects that need no API call.
 //!
-//! Note: Actual GitHub API calls are handled by GitHubMiddleware.
+//! - Applies local filters (`update:major ci:success`) typed into the search input
+//! - Asks before merging all patch/minor bot PRs with green CI
+//! - Shows the dry-run report of the configured policies
+//! - Opens the peek popup when the cursor rests on a PR (`ui.auto_peek`)
+//! - Marks a PR as seen when the cursor rests on it or its diff or
+//!   description is opened
+//! - Opens the conflict helper for the PR under the cursor
+//! - Lists the same bot update in other repositories and merges the picked
+//!   PRs, each in its repository
+//!
+//! Note: Actual GitHub API calls are handled by PrLoadMiddleware and
+//! PrMutationMiddleware.
 //! Bulk loading coordination is handled by RepositoryMiddleware.
 
-use crate::actions::Action;
+use crate::actions::{
+    Action, ConfirmationPopupAction, ConflictHelperAction, DiffViewerAction,
+    DuplicateUpdatesAction, Event, PrSearchAction, PullRequestAction, StatusBarAction,
+};
 use crate::dispatcher::Dispatcher;
+use crate::domain_models::{policy, Repository, WatchedPr};
 use crate::middleware::Middleware;
-use crate::state::AppState;
+use crate::state::{AppState, ConfirmationIntent, PrFilter};
+use crate::utils::format::format_count;
+use crate::views::ViewId;
 
 /// Middleware for Pull Request side effects
-pub struct PullRequestMiddleware;
+pub struct PullRequestMiddleware {
+    /// PR the peek popup was last opened for automatically, so closing it
+    /// keeps it closed until the cursor moves on
+    auto_peeked: Option<WatchedPr>,
+}
 
 impl PullRequestMiddleware {
     pub fn new() -> Self {
-        Self
+        Self { auto_peeked: None }
+    }
+
+    /// Open the peek popup for the PR the cursor rests on, once per PR
+    fn auto_peek(&mut self, state: &AppState, dispatcher: &Dispatcher) {
+        let main_view = &state.main_view;
+        if !state.app_config.ui.auto_peek
+            || main_view.peek.is_some()
+            || state.active_view().view_id() != ViewId::PullRequestView
+        {
+            return;
+        }
+        let repo_idx = main_view.selected_repository;
+        let Some(watched) = main_view.repositories.get(repo_idx).and_then(|repo| {
+            let data = main_view.repo_data.get(&repo_idx)?;
+            let pr = data.prs.get(data.selected_pr)?;
+            Some(repo.watched_pr(pr.number))
+        }) else {
+            return;
+        };
+        if self.auto_peeked.as_ref() != Some(&watched) {
+            self.auto_peeked = Some(watched);
+            dispatcher.dispatch(Action::PullRequest(PullRequestAction::TogglePeek));
+        }
+    }
+
+    /// Mark the focused PR as seen, unless its current version already is
+    fn mark_focused_seen(state: &AppState, dispatcher: &Dispatcher) {
+        let main_view = &state.main_view;
+        let repo_idx = main_view.selected_repository;
+        let seen = main_view
+            .repositories
+            .get(repo_idx)
+            .zip(main_view.repo_data.get(&repo_idx))
+            .and_then(|(repo, data)| {
+                let pr = data.prs.get(data.selected_pr)?;
+                Some(
+                    main_view
+                        .last_seen
+                        .get(&repo.watched_pr(pr.number))
+                        .is_some_and(|seen| *seen >= pr.updated_at),
+                )
+            });
+        if seen == Some(false) {
+            dispatcher.dispatch(Action::PullRequest(PullRequestAction::MarkSeen));
+        }
+    }
+
+    /// Open the conflict helper for the PR under the cursor
+    fn inspect_conflicts(state: &AppState, dispatcher: &Dispatcher) {
+        let main_view = &state.main_view;
+        let repo_idx = main_view.selected_repository;
+        let target = main_view
+            .repositories
+            .get(repo_idx)
+            .zip(main_view.repo_data.get(&repo_idx))
+            .and_then(|(repo, data)| Some((repo, data.prs.get(data.selected_pr)?)));
+        match target {
+            Some((repo, pr)) => {
+                dispatcher.dispatch(Action::ConflictHelper(ConflictHelperAction::Open {
+                    repo: repo.clone(),
+                    pr: Box::new(pr.clone()),
+                }))
+            }
+            None => dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                "No pull request selected",
+                "Conflicts",
+            ))),
+        }
+    }
+
+    /// List the bot PRs ready to merge in a confirmation popup
+    fn request_bot_merge(state: &AppState, dispatcher: &Dispatcher) {
+        let repo_idx = state.main_view.selected_repository;
+        let Some(repo) = state.main_view.repositories.get(repo_idx) else {
+            return;
+        };
+        let prs = state.main_view.mergeable_bot_updates(repo_idx);
+        if prs.is_empty() {
+            dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+                "No patch/minor bot PRs with green CI to merge",
+                "Merge",
+            )));
+            return;
+        }
+
+        let updates = prs
+            .iter()
+            .filter_map(|pr| {
+                let update = pr.dependency_update.as_ref()?;
+                let update_type = update.update_type.map_or("", |t| t.label());
+                Some(format!(
+                    "#{}  {} ({})",
+                    pr.number,
+                    update.summary(),
+                    update_type
+                ))
+            })
+            .collect();
+        let targets: Vec<(Repository, usize)> =
+            prs.iter().map(|pr| (repo.clone(), pr.number)).collect();
+        dispatcher.dispatch(Action::ConfirmationPopup(ConfirmationPopupAction::Show {
+            intent: ConfirmationIntent::MergeBotUpdates {
+                pr_numbers: prs.iter().map(|pr| pr.number as u64).collect(),
+                updates,
+            },
+            default_message: String::new(),
+            repo_context: format!("{}/{}", repo.org, repo.repo),
+            warnings: state.main_view.base_branch_warnings(&targets),
+        }));
+    }
+
+    /// Whether the PR under the cursor is a bot update opened in other
+    /// repositories too, warns if not
+    fn check_duplicates(state: &AppState, dispatcher: &Dispatcher) -> bool {
+        let main_view = &state.main_view;
+        let duplicated = main_view
+            .repo_data
+            .get(&main_view.selected_repository)
+            .and_then(|data| data.prs.get(data.selected_pr))
+            .and_then(|pr| main_view.bot_updates.duplicated_key(pr))
+            .is_some();
+        if !duplicated {
+            dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+                "The PR is not a bot update opened in other repositories",
+                "Merge",
+            )));
+        }
+        duplicated
+    }
+
+    /// Merge the PRs picked in the duplicate updates popup, per repository
+    ///
+    /// Returns false if nothing is picked and the popup stays open.
+    fn merge_duplicates(state: &AppState, dispatcher: &Dispatcher) -> bool {
+        let Some(popup) = &state.duplicate_updates else {
+            return true;
+        };
+        let groups = popup.picked_by_repo();
+        if groups.is_empty() {
+            dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                "Pick at least one PR to merge",
+                "Merge",
+            )));
+            return false;
+        }
+
+        dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+            format!(
+                "Merging {} of {} in {}",
+                format_count(popup.picked.len(), "PR", "PRs"),
+                popup.summary,
+                format_count(groups.len(), "repository", "repositories")
+            ),
+            "Merge",
+        )));
+        for (repo, pr_numbers) in groups {
+            dispatcher.dispatch(Action::PullRequest(PullRequestAction::MergeRepoPrs {
+                repo,
+                pr_numbers,
+            }));
+        }
+        true
+    }
+
+    /// Evaluate the configured policies and show what a run would do
+    fn request_policy_run(state: &AppState, dispatcher: &Dispatcher) {
+        let policies = &state.app_config.policies;
+        if policies.is_empty() {
+            dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+                "No policies configured, add a [policies.<name>] section to the config file",
+                "Policy",
+            )));
+            return;
+        }
+        let repo_idx = state.main_view.selected_repository;
+        let (Some(repo), Some(repo_data)) = (
+            state.main_view.repositories.get(repo_idx),
+            state.main_view.repo_data.get(&repo_idx),
+        ) else {
+            return;
+        };
+
+        let prs: Vec<_> = repo_data.prs.iter().collect();
+        let (steps, skipped) = policy::plan(
+            policies,
+            &state.main_view.saved_filters,
+            &state.main_view.pr_sizes,
+            &prs,
+        );
+        let describe = |number: usize| {
+            repo_data
+                .prs
+                .iter()
+                .find(|pr| pr.number == number)
+                .map(|pr| match &pr.dependency_update {
+                    Some(update) => update.summary(),
+                    None => pr.title.clone(),
+                })
+                .unwrap_or_default()
+        };
+
+        let mut report: Vec<String> = steps
+            .iter()
+            .map(|(name, step)| {
+                format!(
+                    "#{}  {}: {}  ({})",
+                    step.pr_number,
+                    name,
+                    policy::action_label(step.action),
+                    describe(step.pr_number as usize)
+                )
+            })
+            .collect();
+        if steps.is_empty() {
+            report.push(format!("No PR matches a policy ({} checked)", prs.len()));
+        }
+        report.extend(skipped.iter().map(|decision| {
+            format!(
+                "#{}  skipped: {}",
+                decision.pr_number,
+                decision.mismatches.join(", ")
+            )
+        }));
+
+        dispatcher.dispatch(Action::ConfirmationPopup(ConfirmationPopupAction::Show {
+            intent: ConfirmationIntent::RunPolicy {
+                repo: Box::new(repo.clone()),
+                pr_numbers: steps.iter().map(|(_, step)| step.pr_number).collect(),
+                steps: steps.into_iter().map(|(_, step)| step).collect(),
+                report,
+            },
+            default_message: String::new(),
+            repo_context: format!("{}/{}", repo.org, repo.repo),
+            warnings: Vec::new(),
+        }));
     }
 }
 
@@ -25,10 +281,64 @@ impl Default for PullRequestMiddleware {
     }
 }
 
-impl Middleware for PullRequestMiddleware {
-    fn handle(&mut self, _action: &Action, _state: &AppState, _dispatcher: &Dispatcher) -> bool {
-        // Currently all PR actions are handled by GitHubMiddleware
-        // This middleware can be extended for PR-specific side effects
-        true // Pass through all actions
+impl Middleware<Action, AppState> for PullRequestMiddleware {
+    fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        match action {
+            Action::PrSearch(PrSearchAction::SubmitInput) => {
+                let filter = state
+                    .pr_search_input
+                    .as_ref()
+                    .and_then(|input| PrFilter::parse(&input.query));
+                if let Some(filter) = filter {
+                    dispatcher.dispatch(Action::PullRequest(PullRequestAction::SetFilter(filter)));
+                }
+                true // Let reducer close the popup
+            }
+
+            Action::PullRequest(PullRequestAction::MergeBotUpdatesRequest) => {
+                Self::request_bot_merge(state, dispatcher);
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::RunPolicyRequest) => {
+                Self::request_policy_run(state, dispatcher);
+                false // Consume action
+            }
+
+            Action::Event(Event::InputPaused) => {
+                if state.active_view().view_id() == ViewId::PullRequestView {
+                    Self::mark_focused_seen(state, dispatcher);
+                }
+                self.auto_peek(state, dispatcher);
+                true
+            }
+
+            Action::DiffViewer(DiffViewerAction::Open) => {
+                Self::mark_focused_seen(state, dispatcher);
+                true
+            }
+
+            Action::DuplicateUpdates(DuplicateUpdatesAction::Open) => {
+                Self::check_duplicates(state, dispatcher) // Let reducer open the popup
+            }
+
+            Action::DuplicateUpdates(DuplicateUpdatesAction::Merge) => {
+                Self::merge_duplicates(state, dispatcher) // Let reducer close the popup
+            }
+
+            Action::PullRequest(PullRequestAction::InspectConflicts) => {
+                Self::inspect_conflicts(state, dispatcher);
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::TogglePeek)
+                if state.main_view.peek.is_none() =>
+            {
+                Self::mark_focused_seen(state, dispatcher);
+                true
+            }
+
+            _ => true,
+        }
     }
 }
@@ -0,0 +1,260 @@
+//! Quit Middleware
+//!
+//! Quitting ("q" on the root view, Ctrl+C, the Quit command) while PR
+//! operations are in flight or review comments are unsent asks first, listing
+//! them: [q]uit anyway, [w]ait or [c]ancel.
+//!
+//! Nothing sent to GitHub is called back either way. Waiting keeps the app
+//! open with a status bar indicator and quits once the operations in flight
+//! finished, so their outcome is seen (and logged) before the app exits.
+//! Requesting to quit again while waiting quits right away.
+
+use crate::actions::{Action, ConfirmationPopupAction, GlobalAction, PullRequestAction};
+use crate::dispatcher::{Dispatcher, OperationDispatcher, OperationGuard};
+use crate::middleware::Middleware;
+use crate::state::{AppState, ConfirmationIntent, UnfinishedWork};
+
+/// Middleware asking before quitting with unfinished work
+pub struct QuitMiddleware {
+    /// Quitting once the operations in flight finished, shown in the status bar
+    waiting: Option<OperationGuard>,
+}
+
+impl QuitMiddleware {
+    pub fn new() -> Self {
+        Self { waiting: None }
+    }
+
+    /// Quit once the operations in flight finished (right away without any)
+    fn wait(&mut self, state: &AppState, dispatcher: &Dispatcher) {
+        let work = UnfinishedWork::of(state);
+        if work.operations.is_empty() {
+            dispatcher.dispatch(Action::Global(GlobalAction::Quit));
+            return;
+        }
+        let label = format!("Waiting for {} before quitting", work.operations_summary());
+        log::info!("QuitMiddleware: {}", label);
+        self.waiting = Some(dispatcher.start_operation(label, "Quit"));
+    }
+}
+
+impl Default for QuitMiddleware {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether waiting is offered, if the quit confirmation is shown
+fn quit_confirmation(state: &AppState) -> Option<bool> {
+    match state.confirmation_popup.as_ref().map(|popup| &popup.intent) {
+        Some(ConfirmationIntent::Quit { can_wait, .. }) => Some(*can_wait),
+        _ => None,
+    }
+}
+
+impl Middleware<Action, AppState> for QuitMiddleware {
+    fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        match action {
+            // Closing the root view quits, so it asks first as well
+            Action::Global(GlobalAction::Close)
+                if state.view_stack.len() <= 1 && state.app_config.ui.quit_on_close =>
+            {
+                dispatcher.dispatch(Action::Global(GlobalAction::RequestQuit));
+                false
+            }
+
+            Action::Global(GlobalAction::RequestQuit) => {
+                let work = UnfinishedWork::of(state);
+                // Asked already: a second request quits anyway
+                if work.is_empty() || self.waiting.is_some() || quit_confirmation(state).is_some() {
+                    dispatcher.dispatch(Action::Global(GlobalAction::Quit));
+                } else {
+                    log::info!("QuitMiddleware: Asking before quitting, {}", work.summary());
+                    dispatcher.dispatch(Action::ConfirmationPopup(ConfirmationPopupAction::Show {
+                        intent: work.quit_intent(),
+                        default_message: String::new(),
+                        repo_context: String::new(),
+                        warnings: Vec::new(),
+                    }));
+                }
+                false
+            }
+
+            // The confirmation has no message, its keys choose
+            Action::ConfirmationPopup(ConfirmationPopupAction::Char(c)) => {
+                let Some(can_wait) = quit_confirmation(state) else {
+                    return true;
+                };
+                match c {
+                    'q' => dispatcher.dispatch(Action::Global(GlobalAction::Quit)),
+                    'w' if can_wait => {
+                        self.wait(state, dispatcher);
+                        dispatcher.dispatch(Action::ConfirmationPopup(
+                            ConfirmationPopupAction::Confirmed,
+                        ));
+                    }
+                    'c' => dispatcher
+                        .dispatch(Action::ConfirmationPopup(ConfirmationPopupAction::Cancel)),
+                    _ => {}
+                }
+                false
+            }
+
+            // Seen before the reducer, so the finished one is still in flight
+            Action::PullRequest(PullRequestAction::OperationFinished {
+                repo,
+                pr_number,
+                operation,
+            }) if self.waiting.is_some() => {
+                let finished = (repo.watched_pr(*pr_number), *operation);
+                if state
+                    .main_view
+                    .in_flight
+                    .iter()
+                    .all(|running| *running == finished)
+                {
+                    log::info!("QuitMiddleware: Operations finished, quitting");
+                    dispatcher.dispatch(Action::Global(GlobalAction::Quit));
+                }
+                true
+            }
+
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::StatusBarAction;
+    use crate::domain_models::{OperationType, Repository};
+    use crate::state::ConfirmationPopupState;
+    use std::sync::mpsc;
+
+    fn handle(middleware: &mut QuitMiddleware, state: &AppState, action: Action) -> Vec<Action> {
+        let (tx, rx) = mpsc::channel();
+        middleware.handle(&action, state, &Dispatcher::new(tx));
+        rx.try_iter().collect()
+    }
+
+    fn quits(actions: &[Action]) -> bool {
+        actions
+            .iter()
+            .any(|action| matches!(action, Action::Global(GlobalAction::Quit)))
+    }
+
+    /// State with merges of `pr_numbers` in flight
+    fn merging(pr_numbers: &[usize]) -> AppState {
+        let mut state = AppState::default();
+        let repo = Repository::new("acme", "api", "main");
+        for pr_number in pr_numbers {
+            state
+                .main_view
+                .in_flight
+                .insert((repo.watched_pr(*pr_number), OperationType::Merge));
+        }
+        state
+    }
+
+    /// `state` with the confirmation shown by `actions`
+    fn confirming(mut state: AppState, actions: &[Action]) -> AppState {
+        let intent = actions
+            .iter()
+            .find_map(|action| match action {
+                Action::ConfirmationPopup(ConfirmationPopupAction::Show { intent, .. }) => {
+                    Some(intent.clone())
+                }
+                _ => None,
+            })
+            .expect("quit confirmation");
+        state.confirmation_popup = Some(ConfirmationPopupState::new(
+            intent,
+            String::new(),
+            String::new(),
+        ));
+        state
+    }
+
+    fn finished(pr_number: usize) -> Action {
+        Action::PullRequest(PullRequestAction::OperationFinished {
+            repo: Repository::new("acme", "api", "main"),
+            pr_number,
+            operation: OperationType::Merge,
+        })
+    }
+
+    #[test]
+    fn test_quits_right_away_without_unfinished_work() {
+        let mut middleware = QuitMiddleware::new();
+        let state = AppState::default();
+        let actions = handle(
+            &mut middleware,
+            &state,
+            Action::Global(GlobalAction::RequestQuit),
+        );
+        assert!(quits(&actions));
+    }
+
+    #[test]
+    fn test_asks_first_and_a_second_request_quits() {
+        let mut middleware = QuitMiddleware::new();
+        let state = merging(&[1, 2]);
+        let request = Action::Global(GlobalAction::RequestQuit);
+
+        let actions = handle(&mut middleware, &state, request.clone());
+        assert!(!quits(&actions));
+        let state = confirming(state, &actions);
+        assert_eq!(
+            state.confirmation_popup.as_ref().unwrap().target_info(),
+            "2 merges in progress"
+        );
+
+        // Ctrl+C again, or q on the confirmation
+        assert!(quits(&handle(&mut middleware, &state, request)));
+        assert!(quits(&handle(
+            &mut middleware,
+            &state,
+            Action::ConfirmationPopup(ConfirmationPopupAction::Char('q'))
+        )));
+        assert!(matches!(
+            handle(
+                &mut middleware,
+                &state,
+                Action::ConfirmationPopup(ConfirmationPopupAction::Char('c'))
+            )
+            .as_slice(),
+            [Action::ConfirmationPopup(ConfirmationPopupAction::Cancel)]
+        ));
+    }
+
+    #[test]
+    fn test_waiting_quits_once_the_operations_finished() {
+        let mut middleware = QuitMiddleware::new();
+        let state = merging(&[1, 2]);
+        let actions = handle(
+            &mut middleware,
+            &state,
+            Action::Global(GlobalAction::RequestQuit),
+        );
+        let state = confirming(state, &actions);
+
+        let actions = handle(
+            &mut middleware,
+            &state,
+            Action::ConfirmationPopup(ConfirmationPopupAction::Char('w')),
+        );
+        assert!(!quits(&actions));
+        assert!(actions.iter().any(|action| matches!(
+            action,
+            Action::StatusBar(StatusBarAction::OperationStarted { label, .. })
+                if label == "Waiting for 2 merges before quitting"
+        )));
+
+        // One merge is left, then none
+        let mut state = merging(&[1, 2]);
+        assert!(!quits(&handle(&mut middleware, &state, finished(1))));
+        state = merging(&[2]);
+        assert!(quits(&handle(&mut middleware, &state, finished(2))));
+    }
+}
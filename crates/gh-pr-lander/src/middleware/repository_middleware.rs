@@ -5,20 +5,62 @@
 //! - Managing the add repository form view
 //! - Translating generic TextInput actions to AddRepository-specific actions
 //! - Opening repository URLs in the browser
+//! - Confirming the removal of a repository and undoing it
+//! - Reloading a tab switched to another base branch
+//! - Loading the tabs in parallel at startup, reporting when all are ready
+//! - Activating a workspace profile at startup and replacing the tabs with the
+//!   repositories of another one
 
 use std::collections::HashSet;
+use std::time::Instant;
 
 use crate::actions::{
-    Action, BootstrapAction, PullRequestAction, RepositoryAction, StatusBarAction,
+    Action, BootstrapAction, ConfirmationPopupAction, PullRequestAction, RepositoryAction,
+    SessionAction, StatusBarAction,
 };
 use crate::dispatcher::Dispatcher;
 use crate::domain_models::Repository;
 use crate::middleware::Middleware;
-use crate::state::AppState;
-use crate::utils::browser::open_url;
-use gh_pr_config::load_recent_repositories;
+use crate::state::{
+    ActiveProfile, AppState, BootstrapStage, ConfirmationIntent, UNDO_REMOVE_WINDOW,
+};
+use crate::utils::browser::UrlOpener;
+use gh_pr_config::{RecentRepository, WorkspaceProfile, WorkspaceProfiles, DEFAULT_PROFILE};
 use tokio::runtime::Runtime;
 
+/// Tabs loaded at startup
+struct BulkLoad {
+    started_at: Instant,
+    /// Tabs loaded (or failed) in parallel
+    total: usize,
+    /// Tabs that failed to load
+    failed: usize,
+    /// Tabs left to load once opened (`startup_load`)
+    deferred: usize,
+    /// Whether the load ends the bootstrap, not a profile switch
+    bootstrap: bool,
+}
+
+impl BulkLoad {
+    /// Status line of the finished load, e.g. "6/6 repositories loaded in 3.2s"
+    fn summary(&self) -> StatusBarAction {
+        let mut message = format!(
+            "{}/{} repositories loaded in {:.1}s",
+            self.total - self.failed,
+            self.total,
+            self.started_at.elapsed().as_secs_f64()
+        );
+        if self.deferred > 0 {
+            message.push_str(&format!(", {} more load when opened", self.deferred));
+        }
+        if self.failed > 0 {
+            StatusBarAction::warning(message, "Load")
+        } else {
+            StatusBarAction::success(message, "Load")
+        }
+    }
+}
+
 /// Repository middleware - handles repository loading and add repository form
 pub struct RepositoryMiddleware {
     /// Tokio runtime for async operations (opening URLs)
@@ -26,6 +68,14 @@ pub struct RepositoryMiddleware {
     /// Track pending bulk load repository indices
     /// When all are loaded, we dispatch LoadRecentRepositoriesDone
     pending_bulk_load: HashSet<Repository>,
+    /// Whether the first repository of the bulk load has finished loading
+    first_repo_loaded: bool,
+    /// Progress of the startup load, reported once all its tabs are done
+    bulk_load: Option<BulkLoad>,
+    /// Workspace profiles to use instead of the recent repositories file
+    profiles: Option<WorkspaceProfiles>,
+    /// Profile to start in (`--profile`), else the one of the last session
+    start_profile: Option<String>,
 }
 
 impl RepositoryMiddleware {
@@ -33,7 +83,57 @@ impl RepositoryMiddleware {
         Self {
             runtime: Runtime::new().expect("Failed to create tokio runtime"),
             pending_bulk_load: HashSet::new(),
+            first_repo_loaded: true,
+            bulk_load: None,
+            profiles: None,
+            start_profile: None,
+        }
+    }
+
+    /// Start in the profile `name`, created if there is none of that name
+    pub fn starting_in(mut self, name: Option<String>) -> Self {
+        self.start_profile = name;
+        self
+    }
+
+    /// Middleware using `profiles` instead of the recent repositories file
+    #[cfg(test)]
+    pub fn with_profiles(profiles: WorkspaceProfiles) -> Self {
+        Self {
+            profiles: Some(profiles),
+            ..Self::new()
+        }
+    }
+
+    /// The workspace profiles, a broken file is reported and backed up
+    fn load_profiles(&self, dispatcher: &Dispatcher) -> WorkspaceProfiles {
+        if let Some(profiles) = &self.profiles {
+            return profiles.clone();
+        }
+        let (profiles, notice) = WorkspaceProfiles::load_with_recovery();
+        if let Some(notice) = notice {
+            dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                notice.message(),
+                "Repositories",
+            )));
+        }
+        profiles
+    }
+
+    /// Make `profile` the active one, the reducer drops the tabs of the previous one
+    fn activate(profile: &WorkspaceProfile, profiles: &WorkspaceProfiles, dispatcher: &Dispatcher) {
+        let mut names = profiles.names();
+        if !names.contains(&profile.name) {
+            names.push(profile.name.clone());
         }
+        dispatcher.dispatch(Action::Repository(RepositoryAction::ProfileActivated(
+            ActiveProfile {
+                name: profile.name.clone(),
+                names,
+                default_filter: profile.default_filter.clone(),
+                theme: profile.theme.clone(),
+            },
+        )));
     }
 
     /// Get the GitHub URL for the currently selected repository
@@ -45,9 +145,26 @@ impl RepositoryMiddleware {
             .get(repo_idx)
             .map(|repo| repo.web_url())
     }
+    /// Complete the first-repo stage once a repository of the bulk load shows PRs
+    ///
+    /// Also called for PRs shown from the cache, which ends the splash screen
+    /// without waiting for the network.
+    fn mark_first_repo_shown(&mut self, repo: &Repository, dispatcher: &Dispatcher) {
+        if !self.first_repo_loaded && self.pending_bulk_load.contains(repo) {
+            self.first_repo_loaded = true;
+            dispatcher.dispatch(Action::Bootstrap(BootstrapAction::StageCompleted(
+                BootstrapStage::LoadFirstRepo,
+            )));
+        }
+    }
+
     /// Mark a repository as done loading and check if bulk load is complete
-    fn mark_bulk_load_done(&mut self, repo: Repository, dispatcher: &Dispatcher) {
+    fn mark_bulk_load_done(&mut self, repo: Repository, failed: bool, dispatcher: &Dispatcher) {
+        self.mark_first_repo_shown(&repo, dispatcher);
         if self.pending_bulk_load.remove(&repo) {
+            if let Some(bulk_load) = &mut self.bulk_load {
+                bulk_load.failed += usize::from(failed);
+            }
             log::debug!(
                 "PullRequestMiddleware: Repo {} done, {} remaining in bulk load",
                 repo.full_display_name(),
@@ -56,10 +173,99 @@ impl RepositoryMiddleware {
 
             if self.pending_bulk_load.is_empty() {
                 log::info!("PullRequestMiddleware: All bulk repositories loaded");
+                let bulk_load = self.bulk_load.take();
+                if let Some(bulk_load) = &bulk_load {
+                    dispatcher.dispatch(Action::StatusBar(bulk_load.summary()));
+                }
+                if bulk_load.is_none_or(|bulk_load| bulk_load.bootstrap) {
+                    dispatcher.dispatch(Action::Bootstrap(
+                        BootstrapAction::LoadRecentRepositoriesDone,
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Add the tabs of `recent` and load them in parallel
+    ///
+    /// The bootstrap keeps tabs already present when it is retried and ends
+    /// once all are loaded, a profile switch starts from no tabs.
+    fn load_repositories(
+        &mut self,
+        recent: Vec<RecentRepository>,
+        bootstrap: bool,
+        state: &AppState,
+        dispatcher: &Dispatcher,
+    ) {
+        if recent.is_empty() {
+            log::info!("RepositoryMiddleware: No recent repositories found");
+            if bootstrap {
+                dispatcher.dispatch(Action::Bootstrap(BootstrapAction::StageCompleted(
+                    BootstrapStage::LoadFirstRepo,
+                )));
+                // Even if no repos, signal that loading is done
                 dispatcher.dispatch(Action::Bootstrap(
                     BootstrapAction::LoadRecentRepositoriesDone,
                 ));
             }
+            return;
+        }
+
+        self.first_repo_loaded = !bootstrap;
+        let repositories: Vec<Repository> = recent
+            .into_iter()
+            .map(|r| {
+                Repository::with_host(r.org, r.repo, r.branch, r.host)
+                    .with_profile(r.profile)
+                    .with_configured_profile(&state.app_config)
+            })
+            .collect();
+        log::info!(
+            "RepositoryMiddleware: Found {} recent repositories",
+            repositories.len()
+        );
+        log::info!("Adding {} repositories from config", repositories.len());
+
+        for repo in &repositories {
+            // Already present when bootstrap is retried
+            if !bootstrap || !state.main_view.repositories.contains(repo) {
+                dispatcher.dispatch(Action::Repository(RepositoryAction::AddRepository(
+                    repo.clone(),
+                )));
+            }
+        }
+
+        // The tab selected last time loads first and in the interactive
+        // lane, the others in parallel behind it
+        let selected = repositories
+            .iter()
+            .position(|repo| bootstrap && state.main_view.is_session_repo(repo))
+            .unwrap_or(0);
+        let limit = state.app_config.startup_load.unwrap_or(usize::MAX);
+        let preloaded: Vec<&Repository> = repositories
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| *idx != selected && *idx < limit)
+            .map(|(_, repo)| repo)
+            .collect();
+        self.bulk_load = Some(BulkLoad {
+            started_at: Instant::now(),
+            total: preloaded.len() + 1,
+            failed: 0,
+            deferred: repositories.len() - preloaded.len() - 1,
+            bootstrap,
+        });
+
+        let selected = &repositories[selected];
+        self.pending_bulk_load.insert(selected.clone());
+        dispatcher.dispatch(Action::Repository(RepositoryAction::LoadRepositoryData(
+            selected.clone(),
+        )));
+        for repo in preloaded {
+            self.pending_bulk_load.insert(repo.clone());
+            dispatcher.dispatch(Action::Repository(RepositoryAction::PreloadRepositoryData(
+                repo.clone(),
+            )));
         }
     }
 }
@@ -70,44 +276,79 @@ impl Default for RepositoryMiddleware {
     }
 }
 
-impl Middleware for RepositoryMiddleware {
+impl Middleware<Action, AppState> for RepositoryMiddleware {
     fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
         match action {
             // Handle loading recent repositories from config
             Action::Bootstrap(BootstrapAction::LoadRecentRepositories) => {
                 log::info!("RepositoryMiddleware: Loading recent repositories from config");
 
-                let recent_repos = load_recent_repositories();
-                if !recent_repos.is_empty() {
-                    let repositories: Vec<Repository> = recent_repos
-                        .into_iter()
-                        .map(|r| Repository::with_host(r.org, r.repo, r.branch, r.host))
-                        .collect();
-                    log::info!(
-                        "RepositoryMiddleware: Found {} recent repositories",
-                        repositories.len()
-                    );
-                    log::info!("Adding {} repositories from config", repositories.len());
+                let profiles = self.load_profiles(dispatcher);
+                dispatcher.dispatch(Action::Bootstrap(BootstrapAction::StageCompleted(
+                    BootstrapStage::LoadRecentRepos,
+                )));
 
-                    for repo in repositories.into_iter() {
-                        dispatcher.dispatch(Action::Repository(RepositoryAction::AddRepository(
-                            repo.clone(),
-                        )));
+                // The profile asked for on the command line, else the one of the last session
+                let session_profile = state
+                    .main_view
+                    .pending_session_profile
+                    .as_deref()
+                    .filter(|name| profiles.get(name).is_some());
+                let profile = match self.start_profile.as_deref() {
+                    Some(name) => match profiles.get(name) {
+                        Some(profile) => profile.clone(),
+                        None => {
+                            dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+                                format!("Created profile {}", name),
+                                "Profile",
+                            )));
+                            WorkspaceProfile::new(name)
+                        }
+                    },
+                    None => session_profile
+                        .and_then(|name| profiles.get(name))
+                        .or(profiles.profiles.first())
+                        .cloned()
+                        .unwrap_or_else(|| WorkspaceProfile::new(DEFAULT_PROFILE)),
+                };
+                Self::activate(&profile, &profiles, dispatcher);
+                self.load_repositories(profile.repositories, true, state, dispatcher);
 
-                        // when does it actually get removed?
-                        self.pending_bulk_load.insert(repo.clone());
+                true // Let action pass through
+            }
 
-                        dispatcher.dispatch(Action::Repository(
-                            RepositoryAction::LoadRepositoryData(repo.clone()),
-                        ));
-                    }
-                } else {
-                    log::info!("RepositoryMiddleware: No recent repositories found");
-                    // Even if no repos, signal that loading is done
-                    dispatcher.dispatch(Action::Bootstrap(
-                        BootstrapAction::LoadRecentRepositoriesDone,
-                    ));
+            // Replace the tabs with the repositories of another profile
+            Action::Repository(RepositoryAction::SwitchProfile(name)) => {
+                if state
+                    .main_view
+                    .profile
+                    .as_ref()
+                    .is_some_and(|active| active.name == *name)
+                {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+                        format!("Profile {} is already active", name),
+                        "Profile",
+                    )));
+                    return false;
                 }
+                let profiles = self.load_profiles(dispatcher);
+                let Some(profile) = profiles.get(name).cloned() else {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        format!("No profile named {}", name),
+                        "Profile",
+                    )));
+                    return false;
+                };
+
+                // Loads still running report to tabs that are gone
+                self.pending_bulk_load.clear();
+                self.bulk_load = None;
+                Self::activate(&profile, &profiles, dispatcher);
+                dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+                    format!("Switched to profile {}", name),
+                    "Profile",
+                )));
+                self.load_repositories(profile.repositories, false, state, dispatcher);
 
                 true // Let action pass through
             }
@@ -115,7 +356,10 @@ impl Middleware for RepositoryMiddleware {
             // When a single repository is added via form confirm
             Action::Repository(RepositoryAction::FormConfirm) => {
                 if state.add_repo_form.is_valid() {
-                    let repo = state.add_repo_form.to_repository();
+                    let repo = state
+                        .add_repo_form
+                        .to_repository()
+                        .with_configured_profile(&state.app_config);
                     // First add the repository to the list
                     dispatcher.dispatch(Action::Repository(RepositoryAction::AddRepository(
                         repo.clone(),
@@ -130,6 +374,108 @@ impl Middleware for RepositoryMiddleware {
                 true // Let action pass through to reducer
             }
 
+            // Removing a repository is confirmed first, it drops its data
+            Action::Repository(RepositoryAction::RemoveCurrentRepository) => {
+                let repo_idx = state.main_view.selected_repository;
+                let Some(repo) = state.main_view.repositories.get(repo_idx) else {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        "No repository selected",
+                        "Remove Repository",
+                    )));
+                    return false;
+                };
+                let details = vec![
+                    format!(
+                        "Remove {} from gh-pr-lander? (does not affect GitHub)",
+                        repo.display_name()
+                    ),
+                    "Its loaded PRs, filters and seen markers are dropped, local notes are kept"
+                        .to_string(),
+                ];
+                dispatcher.dispatch(Action::ConfirmationPopup(ConfirmationPopupAction::Show {
+                    intent: ConfirmationIntent::RemoveRepository {
+                        repo: Box::new(repo.clone()),
+                        details,
+                    },
+                    default_message: String::new(),
+                    repo_context: repo.display_name(),
+                    warnings: Vec::new(),
+                }));
+                false // Consume action, removed on confirm
+            }
+
+            Action::Repository(RepositoryAction::RemoveRepository(repo)) => {
+                // Its pending load no longer holds up the bulk load
+                if self.pending_bulk_load.contains(repo) {
+                    if let Some(bulk_load) = &mut self.bulk_load {
+                        bulk_load.total -= 1;
+                    }
+                }
+                self.mark_bulk_load_done(repo.clone(), false, dispatcher);
+                dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+                    format!(
+                        "Removed {} - \"Undo repository removal\" (r → u) adds it back",
+                        repo.display_name()
+                    ),
+                    "Remove Repository",
+                )));
+                true // Let reducer remove it
+            }
+
+            Action::Repository(RepositoryAction::UndoRemoveRepository) => {
+                let now = chrono::Local::now();
+                let Some(removed) = state
+                    .main_view
+                    .removed_repository
+                    .as_ref()
+                    .filter(|removed| now - removed.removed_at <= UNDO_REMOVE_WINDOW)
+                else {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+                        "No recently removed repository to add back",
+                        "Remove Repository",
+                    )));
+                    return false;
+                };
+                dispatcher.dispatch(Action::StatusBar(StatusBarAction::success(
+                    format!("Added {} back", removed.repo.display_name()),
+                    "Remove Repository",
+                )));
+                dispatcher.dispatch(Action::Repository(RepositoryAction::LoadRepositoryData(
+                    removed.repo.clone(),
+                )));
+                true // Let reducer insert it at its position
+            }
+
+            // A tab shows one base branch, another tab may already show the new one
+            Action::Repository(RepositoryAction::SwitchBranch { repo, branch }) => {
+                let switched = repo.clone().with_base_branch(branch.clone());
+                let shown_elsewhere = state.main_view.repositories.iter().any(|other| {
+                    other != repo
+                        && other.id() == switched.id()
+                        && other.profile == switched.profile
+                        && other.branch == switched.branch
+                });
+                if shown_elsewhere {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        format!(
+                            "{} @ {} is already open in another tab",
+                            switched.display_name(),
+                            switched.branch
+                        ),
+                        "Switch Branch",
+                    )));
+                    return false;
+                }
+                dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(
+                    format!("Showing PRs targeting {}", switched.branch),
+                    "Switch Branch",
+                )));
+                dispatcher.dispatch(Action::Repository(RepositoryAction::LoadRepositoryData(
+                    switched,
+                )));
+                true // Let reducer switch the tab before the PRs arrive
+            }
+
             // Handle opening repository in browser
             Action::Repository(RepositoryAction::OpenRepositoryInBrowser) => {
                 if let Some(url) = Self::get_current_repo_url(state) {
@@ -148,7 +494,11 @@ impl Middleware for RepositoryMiddleware {
                         "Open Repository",
                     )));
 
-                    self.runtime.spawn(open_url(url));
+                    UrlOpener::from_config(&state.app_config).spawn(
+                        self.runtime.handle(),
+                        url,
+                        dispatcher,
+                    );
                 } else {
                     log::warn!("No repository selected to open in browser");
                     dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
@@ -159,15 +509,26 @@ impl Middleware for RepositoryMiddleware {
                 false // Consume action
             }
 
-            // Handle PR loaded - check if bulk load is complete
-            Action::PullRequest(PullRequestAction::Loaded { repo, .. }) => {
-                self.mark_bulk_load_done(repo.clone(), dispatcher);
+            // Cached PRs end the splash screen, the fresh ones count for the bulk load
+            Action::PullRequest(PullRequestAction::Loaded {
+                repo, from_cache, ..
+            }) => {
+                if from_cache.is_some() {
+                    self.mark_first_repo_shown(repo, dispatcher);
+                } else {
+                    self.mark_bulk_load_done(repo.clone(), false, dispatcher);
+                }
+                // The last session's tab is selected once it shows PRs, without
+                // waiting for the other tabs
+                if state.main_view.is_session_repo(repo) {
+                    dispatcher.dispatch(Action::Session(SessionAction::RestoreSelection));
+                }
                 true // Let action pass through to reducer
             }
 
             // Handle PR load error - still counts as "done" for bulk tracking
             Action::PullRequest(PullRequestAction::LoadError { repo, .. }) => {
-                self.mark_bulk_load_done(repo.clone(), dispatcher);
+                self.mark_bulk_load_done(repo.clone(), true, dispatcher);
                 true // Let action pass through to reducer
             }
 
@@ -0,0 +1,346 @@
+//! Saved Filter Middleware
+//!
+//! Owns the saved filters and keeps them in sync with the state.
+//!
+//! - Loads the saved filters file on bootstrap
+//! - Applies the filter assembled in the filter builder, saving it if named
+//! - Applies a repository's configured default filter, else the one of the
+//!   active workspace profile, when its PRs are loaded for the first time
+//!
+//! In demo mode saved filters are kept in memory only.
+
+use crate::actions::{
+    Action, BootstrapAction, FilterBuilderAction, PullRequestAction, RepositoryAction,
+    StatusBarAction,
+};
+use crate::dispatcher::Dispatcher;
+use crate::domain_models::Repository;
+use crate::middleware::Middleware;
+use crate::state::{AppState, PrFilter};
+use crate::utils::autosave::PendingSaves;
+use crate::utils::io_worker::IoWorker;
+use gh_pr_config::SavedFilters;
+use std::collections::HashSet;
+
+/// Middleware for saved filters
+pub struct SavedFilterMiddleware {
+    filters: SavedFilters,
+    /// Whether filters are read from and written to the filters file
+    persist: bool,
+    /// Repositories whose default filter was applied already
+    defaulted: HashSet<Repository>,
+    /// Writes the filters file off the middleware thread
+    io: IoWorker,
+}
+
+impl SavedFilterMiddleware {
+    pub fn new() -> Self {
+        Self {
+            filters: SavedFilters::default(),
+            persist: true,
+            defaulted: HashSet::new(),
+            io: IoWorker::spawn("filters-io"),
+        }
+    }
+
+    /// Saved filters that are never written to disk (demo mode)
+    pub fn in_memory() -> Self {
+        Self {
+            persist: false,
+            ..Self::new()
+        }
+    }
+
+    /// Write the filters file and publish the filters to the state
+    fn store(&self, dispatcher: &Dispatcher) {
+        if self.persist {
+            let filters = self.filters.clone();
+            let dispatcher = dispatcher.clone();
+            PendingSaves::global().stage("filters", move || {
+                if let Err(e) = filters.save() {
+                    log::error!("Failed to save filters: {:#}", e);
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::error(
+                        format!("Failed to save filters: {:#}", e),
+                        "Filter",
+                    )));
+                }
+            });
+            self.io.run(|| {
+                PendingSaves::global().flush();
+            });
+        }
+        self.publish(dispatcher);
+    }
+
+    fn publish(&self, dispatcher: &Dispatcher) {
+        dispatcher.dispatch(Action::PullRequest(PullRequestAction::SavedFiltersUpdated(
+            self.filters.filters.clone(),
+        )));
+    }
+
+    /// Apply the filter of the builder, saving it first if it has a name
+    ///
+    /// Returns false if the builder has to stay open.
+    fn apply(&mut self, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        let Some(builder) = &state.filter_builder else {
+            return true;
+        };
+        let spec = builder.spec();
+        let name = builder.name().map(str::to_string);
+
+        if spec.is_empty() {
+            if let Some(name) = name {
+                dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                    format!("Set at least one criterion to save filter {}", name),
+                    "Filter",
+                )));
+                return false;
+            }
+            dispatcher.dispatch(Action::PullRequest(PullRequestAction::ClearFilter));
+            return true;
+        }
+
+        if let Some(name) = &name {
+            self.filters.filters.insert(name.clone(), spec.clone());
+            self.store(dispatcher);
+            dispatcher.dispatch(Action::StatusBar(StatusBarAction::success(
+                format!("Saved filter {}", name),
+                "Filter",
+            )));
+        }
+        dispatcher.dispatch(Action::PullRequest(PullRequestAction::SetFilter(
+            PrFilter::Spec {
+                name,
+                spec: Box::new(spec),
+            },
+        )));
+        true
+    }
+
+    /// Apply the configured default filter of a repository loaded the first time
+    fn apply_default(&mut self, repo: &Repository, state: &AppState, dispatcher: &Dispatcher) {
+        if !self.defaulted.insert(repo.clone()) {
+            return;
+        }
+        let profile_filter = state
+            .main_view
+            .profile
+            .as_ref()
+            .and_then(|profile| profile.default_filter.as_deref());
+        let Some(name) = state
+            .app_config
+            .default_filter_for(&repo.org, &repo.repo)
+            .or(profile_filter)
+        else {
+            return;
+        };
+        match self.filters.filters.get(name) {
+            Some(spec) => {
+                dispatcher.dispatch(Action::PullRequest(PullRequestAction::SetRepoFilter {
+                    repo: repo.clone(),
+                    filter: PrFilter::Spec {
+                        name: Some(name.to_string()),
+                        spec: Box::new(spec.clone()),
+                    },
+                }))
+            }
+            None => dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                format!(
+                    "Default filter {} of {} is not a saved filter",
+                    name,
+                    repo.display_name()
+                ),
+                "Filter",
+            ))),
+        }
+    }
+}
+
+impl Default for SavedFilterMiddleware {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Middleware<Action, AppState> for SavedFilterMiddleware {
+    fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        match action {
+            Action::Bootstrap(BootstrapAction::Start) if self.persist => {
+                let (filters, notice) = SavedFilters::load_with_recovery();
+                if let Some(notice) = notice {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        notice.message(),
+                        "Filter",
+                    )));
+                }
+                self.filters = filters;
+                self.publish(dispatcher);
+                true // Pass through
+            }
+
+            Action::FilterBuilder(FilterBuilderAction::Apply) => self.apply(state, dispatcher),
+
+            Action::PullRequest(PullRequestAction::ApplySavedFilter(name)) => {
+                if self.filters.filters.contains_key(name) {
+                    return true; // Let reducer apply it
+                }
+                dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                    format!("No saved filter named {}", name),
+                    "Filter",
+                )));
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::Loaded { repo, .. }) => {
+                self.apply_default(repo, state, dispatcher);
+                true // Let reducer store the PRs
+            }
+
+            // The tabs of another profile start with their default filter again
+            Action::Repository(RepositoryAction::ProfileActivated(_)) => {
+                self.defaulted.clear();
+                true
+            }
+
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{FilterBuilderState, FilterRow};
+    use gh_pr_config::FilterSpec;
+    use std::sync::mpsc;
+
+    fn handle(
+        middleware: &mut SavedFilterMiddleware,
+        state: &AppState,
+        action: Action,
+    ) -> (bool, Vec<Action>) {
+        let (tx, rx) = mpsc::channel();
+        let passed = middleware.handle(&action, state, &Dispatcher::new(tx));
+        (passed, rx.try_iter().collect())
+    }
+
+    fn builder(criteria: &[(FilterRow, &str)]) -> FilterBuilderState {
+        let mut builder = FilterBuilderState::default();
+        for (row, value) in criteria {
+            while builder.selected_row() != *row {
+                builder.select_next();
+            }
+            value.chars().for_each(|c| builder.push_char(c));
+        }
+        builder
+    }
+
+    #[test]
+    fn test_apply_saves_named_filter() {
+        let mut middleware = SavedFilterMiddleware::in_memory();
+        let mut state = AppState {
+            filter_builder: Some(builder(&[
+                (FilterRow::LabelsInclude, "security"),
+                (FilterRow::SaveAs, "security-bumps"),
+            ])),
+            ..Default::default()
+        };
+
+        let apply = || Action::FilterBuilder(FilterBuilderAction::Apply);
+        let (passed, actions) = handle(&mut middleware, &state, apply());
+        assert!(passed);
+        assert_eq!(
+            middleware
+                .filters
+                .filters
+                .get("security-bumps")
+                .unwrap()
+                .labels_include,
+            vec!["security"]
+        );
+        assert!(matches!(
+            actions.as_slice(),
+            [
+                Action::PullRequest(PullRequestAction::SavedFiltersUpdated(_)),
+                Action::StatusBar(_),
+                Action::PullRequest(PullRequestAction::SetFilter(PrFilter::Spec {
+                    name: Some(_),
+                    ..
+                })),
+            ]
+        ));
+
+        // A name without criteria keeps the builder open
+        state.filter_builder = Some(builder(&[(FilterRow::SaveAs, "empty")]));
+        let (passed, _) = handle(&mut middleware, &state, apply());
+        assert!(!passed);
+        assert!(!middleware.filters.filters.contains_key("empty"));
+    }
+
+    #[test]
+    fn test_default_filter_applied_on_first_load() {
+        let mut middleware = SavedFilterMiddleware::in_memory();
+        middleware
+            .filters
+            .filters
+            .insert("mine".to_string(), FilterSpec::default());
+        let mut state = AppState::default();
+        state.app_config.repos.insert(
+            "acme/*".to_string(),
+            gh_pr_config::RepoSettings {
+                default_filter: Some("mine".to_string()),
+                ..Default::default()
+            },
+        );
+        let repo = Repository::new("acme", "api", "main");
+        let loaded = || {
+            Action::PullRequest(PullRequestAction::Loaded {
+                repo: repo.clone(),
+                prs: Vec::new(),
+                from_cache: None,
+            })
+        };
+
+        let (_, actions) = handle(&mut middleware, &state, loaded());
+        assert!(matches!(
+            actions.as_slice(),
+            [Action::PullRequest(PullRequestAction::SetRepoFilter { .. })]
+        ));
+        // Reloading keeps the filter the user chose since
+        let (_, actions) = handle(&mut middleware, &state, loaded());
+        assert!(actions.is_empty());
+    }
+
+    #[test]
+    fn test_profile_default_filter() {
+        let mut middleware = SavedFilterMiddleware::in_memory();
+        middleware
+            .filters
+            .filters
+            .insert("mine".to_string(), FilterSpec::default());
+        let mut state = AppState::default();
+        state.main_view.profile = Some(crate::state::ActiveProfile {
+            name: "work".to_string(),
+            names: vec!["work".to_string()],
+            default_filter: Some("mine".to_string()),
+            theme: None,
+        });
+
+        let (_, actions) = handle(
+            &mut middleware,
+            &state,
+            Action::PullRequest(PullRequestAction::Loaded {
+                repo: Repository::new("acme", "api", "main"),
+                prs: Vec::new(),
+                from_cache: None,
+            }),
+        );
+        assert!(matches!(
+            actions.as_slice(),
+            [Action::PullRequest(PullRequestAction::SetRepoFilter {
+                filter: PrFilter::Spec { name: Some(name), .. },
+                ..
+            })] if name == "mine"
+        ));
+    }
+}
@@ -7,20 +7,39 @@
 //! - Loads session from disk during bootstrap
 //! - Dispatches `Session::Loaded` action to store pending selection
 //! - Dispatches `Session::RestoreSelection` after repositories load
-//! - Saves session on quit
+//! - Restores and saves the watch list
+//! - Restores and saves whether the build logs list successful jobs
+//! - Restores and saves the last seen version of PRs (of tracked repositories)
+//! - Restores and saves the active workspace profile, its repositories are
+//!   saved in place, leaving the other profiles untouched
+//! - Saves session and repositories every few seconds while they changed
+//!   (reducers bump `session_revision` on changes) and once more on quit
+//! - Saves the profile left behind before switching to another one
 //! - Uses local session file if it exists, otherwise global
+//!
+//! Saves go through [`PendingSaves`], so a crash flushes the last staged
+//! session and two saves never interleave.
 
-use crate::actions::{Action, BootstrapAction, GlobalAction, SessionAction};
+use crate::actions::{
+    Action, BootstrapAction, BuildLogAction, Event, GlobalAction, PullRequestAction,
+    RepositoryAction, SessionAction, StatusBarAction, WatchListAction,
+};
 use crate::dispatcher::Dispatcher;
 use crate::middleware::Middleware;
 use crate::state::AppState;
-use gh_pr_config::{save_recent_repositories, RecentRepository, Session};
+use crate::utils::autosave::PendingSaves;
+use crate::utils::io_worker::IoWorker;
+use gh_pr_config::{RecentRepository, SeenPr, Session, WorkspaceProfiles, DEFAULT_PROFILE};
 use std::sync::{Arc, Mutex};
 
 /// Middleware for session state persistence
 pub struct SessionMiddleware {
     session: Arc<Mutex<Session>>,
     loaded: bool,
+    /// `session_revision` of the state last saved (None until bootstrap ended)
+    saved_revision: Option<u64>,
+    /// Writes the session files off the middleware thread
+    io: IoWorker,
 }
 
 impl SessionMiddleware {
@@ -28,16 +47,30 @@ impl SessionMiddleware {
         Self {
             session: Arc::new(Mutex::new(Session::default())),
             loaded: false,
+            saved_revision: None,
+            io: IoWorker::spawn("session-io"),
         }
     }
 
-    fn save_session(&self, state: &AppState) {
+    /// Stage the session and repositories of `state` for the next flush
+    fn stage(&mut self, state: &AppState) {
+        self.stage_session(state);
+        self.stage_repositories(state);
+        self.saved_revision = Some(state.main_view.session_revision);
+    }
+
+    fn stage_session(&self, state: &AppState) {
         let mut session = self.session.lock().unwrap();
         let selected_idx = state.main_view.selected_repository;
 
         // Save current selection
         if let Some(repo) = state.main_view.repositories.get(selected_idx) {
-            session.set_selected_repo(&repo.org, &repo.repo, &repo.branch, repo.host.as_deref());
+            session.set_selected_repo(
+                &repo.org,
+                &repo.repo,
+                repo.saved_branch(),
+                repo.host.as_deref(),
+            );
 
             // Save selected PR number (not index) for this repository
             if let Some(repo_data) = state.main_view.repo_data.get(&selected_idx) {
@@ -47,22 +80,69 @@ impl SessionMiddleware {
             }
         }
 
-        if let Err(e) = session.save() {
-            log::error!("Failed to save session: {}", e);
+        session.set_watched(state.main_view.watch_list.watched());
+        session.set_build_log_show_successful(state.build_log.show_successful);
+        if let Some(profile) = &state.main_view.profile {
+            session.set_profile(Some(profile.name.clone()));
         }
+
+        // PRs of repositories no longer tracked are forgotten
+        let mut seen: Vec<SeenPr> = state
+            .main_view
+            .last_seen
+            .iter()
+            .filter(|(pr, _)| {
+                state
+                    .main_view
+                    .repositories
+                    .iter()
+                    .any(|repo| repo.contains_watched(pr))
+            })
+            .map(|(pr, updated_at)| SeenPr {
+                pr: pr.clone(),
+                updated_at: *updated_at,
+            })
+            .collect();
+        seen.sort_by(|a, b| {
+            (&a.pr.org, &a.pr.repo, a.pr.number).cmp(&(&b.pr.org, &b.pr.repo, b.pr.number))
+        });
+        session.set_seen(seen);
+
+        let mut session = session.clone();
+        PendingSaves::global().stage("session", move || {
+            if let Err(e) = session.save() {
+                log::error!("Failed to save session: {}", e);
+            }
+        });
     }
 
-    fn save_repositories(&self, state: &AppState) {
+    fn stage_repositories(&self, state: &AppState) {
         let repos: Vec<RecentRepository> = state
             .main_view
             .repositories
             .iter()
-            .map(|r| RecentRepository::with_host(&r.org, &r.repo, &r.branch, r.host.clone()))
+            .map(|r| {
+                // Only persist profiles that differ from the configured one
+                let configured = state.app_config.profile_for(&r.org, &r.repo);
+                let profile = r.profile.clone().filter(|p| Some(p.as_str()) != configured);
+                RecentRepository::with_host(&r.org, &r.repo, r.saved_branch(), r.host.clone())
+                    .with_profile(profile)
+            })
             .collect();
+        let profile = state
+            .main_view
+            .profile
+            .as_ref()
+            .map_or(DEFAULT_PROFILE, |profile| profile.name.as_str())
+            .to_string();
 
-        if let Err(e) = save_recent_repositories(&repos) {
-            log::error!("Failed to save recent repositories: {}", e);
-        }
+        PendingSaves::global().stage("repositories", move || {
+            let (mut profiles, _) = WorkspaceProfiles::load_with_recovery();
+            profiles.set_repositories(&profile, repos);
+            if let Err(e) = profiles.save() {
+                log::error!("Failed to save recent repositories: {}", e);
+            }
+        });
     }
 }
 
@@ -72,16 +152,32 @@ impl Default for SessionMiddleware {
     }
 }
 
-impl Middleware for SessionMiddleware {
+impl Middleware<Action, AppState> for SessionMiddleware {
     fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
         match action {
             Action::Bootstrap(BootstrapAction::Start) => {
                 if !self.loaded {
                     // Run migrations before loading session
-                    gh_pr_config_migrate::run_migrations();
+                    for report in gh_pr_config_migrate::run_migrations() {
+                        let Some(summary) = report.summary() else {
+                            continue;
+                        };
+                        let status = if report.is_warning() {
+                            StatusBarAction::warning(summary, "Migration")
+                        } else {
+                            StatusBarAction::info(summary, "Migration")
+                        };
+                        dispatcher.dispatch(Action::StatusBar(status));
+                    }
 
                     log::info!("SessionMiddleware: Loading session");
-                    let session = Session::load();
+                    let (session, notice) = Session::load_with_recovery();
+                    if let Some(notice) = notice {
+                        dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                            notice.message(),
+                            "Session",
+                        )));
+                    }
 
                     // Dispatch session loaded action with selected repo info
                     let selected_repo = session.selected_repo().map(|(org, name, branch, host)| {
@@ -97,7 +193,22 @@ impl Middleware for SessionMiddleware {
                     dispatcher.dispatch(Action::Session(SessionAction::Loaded {
                         selected_repo,
                         selected_pr_no,
+                        profile: session.profile().map(String::from),
                     }));
+                    dispatcher.dispatch(Action::WatchList(WatchListAction::Loaded(
+                        session.watched().to_vec(),
+                    )));
+                    dispatcher.dispatch(Action::PullRequest(PullRequestAction::LastSeenLoaded(
+                        session
+                            .seen()
+                            .iter()
+                            .map(|seen| (seen.pr.clone(), seen.updated_at))
+                            .collect(),
+                    )));
+                    if let Some(show) = session.build_log_show_successful() {
+                        dispatcher
+                            .dispatch(Action::BuildLog(BuildLogAction::SetShowSuccessful(show)));
+                    }
 
                     *self.session.lock().unwrap() = session;
                     self.loaded = true;
@@ -111,11 +222,39 @@ impl Middleware for SessionMiddleware {
                 true // Pass through
             }
 
-            // Save session and repositories on on close, when at root view
-            Action::Global(GlobalAction::Close) if state.view_stack.len() == 1 => {
+            // Changes made while bootstrapping were loaded from disk
+            Action::Bootstrap(BootstrapAction::End) => {
+                self.saved_revision
+                    .get_or_insert(state.main_view.session_revision);
+                true
+            }
+
+            Action::Event(Event::AutosaveDue) => {
+                if self
+                    .saved_revision
+                    .is_some_and(|saved| saved != state.main_view.session_revision)
+                {
+                    log::debug!("SessionMiddleware: Autosaving session");
+                    self.stage(state);
+                    self.io.run(|| {
+                        PendingSaves::global().flush();
+                    });
+                }
+                true
+            }
+
+            // Save the repositories of the profile left behind before its tabs are dropped
+            Action::Repository(RepositoryAction::SwitchProfile(_)) if self.loaded => {
+                self.stage(state);
+                PendingSaves::global().flush();
+                true
+            }
+
+            // Save session and repositories before the app exits, waiting for the write
+            Action::Global(GlobalAction::Quit) if self.loaded => {
                 log::info!("SessionMiddleware: Saving state before quit");
-                self.save_session(state);
-                self.save_repositories(state);
+                self.stage(state);
+                PendingSaves::global().flush();
                 true
             }
 
@@ -0,0 +1,228 @@
+//! Snooze Middleware
+//!
+//! Owns the snoozed PRs and keeps them in sync with the state.
+//!
+//! - Loads the snooze file on bootstrap
+//! - Snoozes and wakes the selected PRs (or the PR under the cursor)
+//! - Wakes PRs whose deadline passed on every refresh tick
+//! - Wakes PRs that got a new commit or update when their list is loaded
+//!
+//! In demo mode snoozes are kept in memory only.
+
+use crate::actions::{Action, BootstrapAction, Event, PullRequestAction, StatusBarAction};
+use crate::dispatcher::Dispatcher;
+use crate::domain_models::{Pr, Repository, SnoozeDuration};
+use crate::middleware::Middleware;
+use crate::state::AppState;
+use crate::utils::autosave::PendingSaves;
+use crate::utils::format::format_count;
+use crate::utils::io_worker::IoWorker;
+use chrono::Utc;
+use gh_pr_config::{SnoozeList, SnoozedPr};
+
+/// Middleware for snoozed PRs
+pub struct SnoozeMiddleware {
+    list: SnoozeList,
+    /// Whether snoozes are read from and written to the snooze file
+    persist: bool,
+    /// Writes the snooze file off the middleware thread
+    io: IoWorker,
+}
+
+impl SnoozeMiddleware {
+    pub fn new() -> Self {
+        Self {
+            list: SnoozeList::default(),
+            persist: true,
+            io: IoWorker::spawn("snooze-io"),
+        }
+    }
+
+    /// Snoozes that are never written to disk (demo mode)
+    pub fn in_memory() -> Self {
+        Self {
+            list: SnoozeList::default(),
+            persist: false,
+            io: IoWorker::spawn("snooze-io"),
+        }
+    }
+
+    /// Write the snooze file and publish the snoozes to the state
+    fn store(&self, dispatcher: &Dispatcher) {
+        if self.persist {
+            let list = self.list.clone();
+            let dispatcher = dispatcher.clone();
+            PendingSaves::global().stage("snoozed", move || {
+                if let Err(e) = list.save() {
+                    log::error!("Failed to save snoozed PRs: {:#}", e);
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::error(
+                        format!("Failed to save snoozed PRs: {:#}", e),
+                        "Snooze",
+                    )));
+                }
+            });
+            self.io.run(|| {
+                PendingSaves::global().flush();
+            });
+        }
+        self.publish(dispatcher);
+    }
+
+    fn publish(&self, dispatcher: &Dispatcher) {
+        let snoozed = self
+            .list
+            .snoozed
+            .iter()
+            .map(|entry| (entry.pr.clone(), entry.clone()))
+            .collect();
+        dispatcher.dispatch(Action::PullRequest(PullRequestAction::SnoozesUpdated(
+            snoozed,
+        )));
+    }
+
+    /// The selected PRs of the current repository, or the PR under the cursor
+    fn target_prs(state: &AppState) -> Option<(&Repository, Vec<&Pr>)> {
+        let repo_idx = state.main_view.selected_repository;
+        let repo = state.main_view.repositories.get(repo_idx)?;
+        let data = state.main_view.repo_data.get(&repo_idx)?;
+        let prs: Vec<&Pr> = if data.selected_pr_numbers.is_empty() {
+            data.prs.get(data.selected_pr).into_iter().collect()
+        } else {
+            data.prs
+                .iter()
+                .filter(|pr| data.selected_pr_numbers.contains(&pr.number))
+                .collect()
+        };
+        Some((repo, prs))
+    }
+
+    fn snooze(&mut self, duration: SnoozeDuration, state: &AppState, dispatcher: &Dispatcher) {
+        let Some((repo, prs)) = Self::target_prs(state) else {
+            return;
+        };
+        if prs.is_empty() {
+            return;
+        }
+
+        let now = Utc::now();
+        for pr in &prs {
+            self.list.snooze(SnoozedPr {
+                pr: repo.watched_pr(pr.number),
+                head_sha: pr.head_sha.clone(),
+                updated_at: pr.updated_at,
+                until: duration.deadline(now),
+            });
+        }
+        self.store(dispatcher);
+
+        let message = match prs.as_slice() {
+            [pr] => format!(
+                "Snoozed {} {}",
+                repo.watched_pr(pr.number),
+                duration.label()
+            ),
+            prs => format!(
+                "Snoozed {} {}",
+                format_count(prs.len(), "PR", "PRs"),
+                duration.label()
+            ),
+        };
+        dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(message, "Snooze")));
+        // Hidden PRs must not stay part of a bulk operation
+        dispatcher.dispatch(Action::PullRequest(PullRequestAction::DeselectAll));
+    }
+
+    fn unsnooze(&mut self, state: &AppState, dispatcher: &Dispatcher) {
+        let Some((repo, prs)) = Self::target_prs(state) else {
+            return;
+        };
+        let woken: Vec<SnoozedPr> = prs
+            .iter()
+            .filter_map(|pr| self.list.wake(&repo.watched_pr(pr.number)))
+            .collect();
+        if !woken.is_empty() {
+            self.store(dispatcher);
+            Self::report_woken(&woken, "unsnoozed", dispatcher);
+        }
+    }
+
+    /// Wake snoozed PRs of a loaded list that changed since they were snoozed
+    fn wake_updated(&mut self, repo: &Repository, prs: &[Pr], dispatcher: &Dispatcher) {
+        let woken: Vec<SnoozedPr> = prs
+            .iter()
+            .filter_map(|pr| {
+                self.list
+                    .wake_updated(&repo.watched_pr(pr.number), &pr.head_sha, pr.updated_at)
+            })
+            .collect();
+        if !woken.is_empty() {
+            self.store(dispatcher);
+            Self::report_woken(&woken, "updated", dispatcher);
+        }
+    }
+
+    fn wake_expired(&mut self, dispatcher: &Dispatcher) {
+        let woken = self.list.wake_expired(Utc::now());
+        if !woken.is_empty() {
+            self.store(dispatcher);
+            Self::report_woken(&woken, "snooze ended", dispatcher);
+        }
+    }
+
+    fn report_woken(woken: &[SnoozedPr], reason: &str, dispatcher: &Dispatcher) {
+        let message = match woken {
+            [entry] => format!("{} woke up ({})", entry.pr, reason),
+            woken => format!("{} snoozed PRs woke up ({})", woken.len(), reason),
+        };
+        dispatcher.dispatch(Action::StatusBar(StatusBarAction::info(message, "Snooze")));
+    }
+}
+
+impl Default for SnoozeMiddleware {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Middleware<Action, AppState> for SnoozeMiddleware {
+    fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        match action {
+            Action::Bootstrap(BootstrapAction::Start) if self.persist => {
+                let (list, notice) = SnoozeList::load_with_recovery();
+                if let Some(notice) = notice {
+                    dispatcher.dispatch(Action::StatusBar(StatusBarAction::warning(
+                        notice.message(),
+                        "Snooze",
+                    )));
+                }
+                self.list = list;
+                self.wake_expired(dispatcher);
+                self.publish(dispatcher);
+                true // Pass through
+            }
+
+            Action::PullRequest(PullRequestAction::Snooze { duration }) => {
+                self.snooze(*duration, state, dispatcher);
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::Unsnooze) => {
+                self.unsnooze(state, dispatcher);
+                false // Consume action
+            }
+
+            Action::PullRequest(PullRequestAction::Loaded { repo, prs, .. })
+            | Action::PullRequest(PullRequestAction::Prefetched { repo, prs }) => {
+                self.wake_updated(repo, prs, dispatcher);
+                true // Let reducer store the PRs
+            }
+
+            Action::Event(Event::RefreshDue) => {
+                self.wake_expired(dispatcher);
+                true // Pass through
+            }
+
+            _ => true,
+        }
+    }
+}
@@ -0,0 +1,777 @@
+//! Test doubles for the GitHub middleware
+//!
+//! `MockGitHubClient` answers from canned responses and records its calls,
+//! optionally after a delay,
+//! `MockClientFactory` hands it out for every repository. `settle` drives a
+//! current-thread runtime until all spawned tasks finished, so the actions
+//! dispatched by a handler can be asserted deterministically, `run_middleware`
+//! does all of that for one action.
+
+use super::client_factory::ClientFactory;
+use super::github_context::GitHubContext;
+use super::Middleware;
+use crate::actions::{Action, StatusBarAction};
+use crate::dispatcher::Dispatcher;
+use crate::domain_models::Repository;
+use crate::state::{AppState, StatusKind};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use gh_client::types::{
+    BlameRange, CheckConclusion, CheckRun, CheckRunStatus, CheckStatus, CiStatus, CommentCounts,
+    CommentLines, Comparison, Label, MaturityState, MergeMethod, MergeResult, MergeableState,
+    PrReviewers, PrState, PrStateFilter, PullRequest, RepositorySettings, ReviewComment,
+    ReviewDecision, ReviewEvent, SearchResults, WorkflowRun,
+};
+use gh_client::GitHubClient;
+use std::collections::HashMap;
+use std::sync::mpsc::channel;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+/// GitHub client answering from canned responses
+///
+/// Methods without a canned response fail with "not mocked", methods listed
+/// in `errors` fail with the given message. Methods listed in `delays` take
+/// that long to answer, the call is recorded right away.
+#[derive(Debug, Default)]
+pub struct MockGitHubClient {
+    pub pull_requests: Vec<PullRequest>,
+    pub ci_status: Option<CiStatus>,
+    pub review_decision: Option<ReviewDecision>,
+    pub comment_counts: Option<CommentCounts>,
+    pub reviewers: Option<Vec<PrReviewers>>,
+    pub blame: Option<Vec<BlameRange>>,
+    pub comparison: Option<Comparison>,
+    /// Unified diff of every PR
+    pub diff: Option<String>,
+    /// Content per file path
+    pub files: HashMap<String, String>,
+    pub collaborators: Option<Vec<String>>,
+    pub branches: Option<Vec<String>>,
+    pub merge_result: Option<MergeResult>,
+    pub branch_head_sha: Option<String>,
+    pub latest_tag: Option<String>,
+    /// Commit date per tag, branch or SHA
+    pub ref_dates: HashMap<String, DateTime<Utc>>,
+    pub repository_settings: Option<RepositorySettings>,
+    /// Required checks of every branch (None = protection unreadable)
+    pub required_checks: Option<Vec<String>>,
+    /// Workflow runs of every commit
+    pub workflow_runs: Vec<WorkflowRun>,
+    /// Results of every PR search, on a single page
+    pub search_results: Vec<PullRequest>,
+    /// Count of searches by a qualifier their query contains (e.g. "merged:")
+    pub search_counts: Vec<(&'static str, u64)>,
+    /// Error message per method name
+    pub errors: HashMap<&'static str, String>,
+    /// Error message per PR number, for calls on one PR
+    pub pr_errors: HashMap<u64, String>,
+    /// Time to answer per method name
+    pub delays: HashMap<&'static str, Duration>,
+    /// Calls made, as `method owner/repo args`
+    pub calls: Mutex<Vec<String>>,
+}
+
+impl MockGitHubClient {
+    /// Let a method fail with the given message
+    pub fn failing(mut self, method: &'static str, message: &str) -> Self {
+        self.errors.insert(method, message.to_string());
+        self
+    }
+
+    /// Calls made so far
+    pub fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    /// Record a call, failing if the method is set up to fail
+    async fn call(&self, method: &'static str, detail: String) -> anyhow::Result<()> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("{} {}", method, detail));
+        if let Some(delay) = self.delays.get(method) {
+            tokio::time::sleep(*delay).await;
+        }
+        match self.errors.get(method) {
+            Some(message) => Err(anyhow::anyhow!("{}", message)),
+            None => Ok(()),
+        }
+    }
+
+    fn canned<T: Clone>(method: &str, value: &Option<T>) -> anyhow::Result<T> {
+        value
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("{} not mocked", method))
+    }
+}
+
+#[async_trait]
+impl GitHubClient for MockGitHubClient {
+    async fn fetch_pull_requests(
+        &self,
+        owner: &str,
+        repo: &str,
+        _base_branch: Option<&str>,
+        _state: PrStateFilter,
+    ) -> anyhow::Result<Vec<PullRequest>> {
+        self.call("fetch_pull_requests", format!("{}/{}", owner, repo))
+            .await?;
+        Ok(self.pull_requests.clone())
+    }
+
+    async fn search_pull_requests_in_repo(
+        &self,
+        owner: &str,
+        repo: &str,
+        query: &str,
+        _page: u32,
+    ) -> anyhow::Result<SearchResults> {
+        self.call(
+            "search_pull_requests_in_repo",
+            format!("{}/{} {}", owner, repo, query),
+        )
+        .await?;
+        Ok(SearchResults {
+            total_count: self.search_results.len() as u64,
+            items: self.search_results.clone(),
+            incomplete: false,
+        })
+    }
+
+    async fn count_search(&self, query: &str) -> anyhow::Result<u64> {
+        self.call("count_search", query.to_string()).await?;
+        self.search_counts
+            .iter()
+            .find(|(qualifier, _)| query.contains(qualifier))
+            .map(|(_, count)| *count)
+            .ok_or_else(|| anyhow::anyhow!("count_search not mocked for {}", query))
+    }
+
+    async fn fetch_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<PullRequest> {
+        self.call(
+            "fetch_pull_request",
+            format!("{}/{}#{}", owner, repo, pr_number),
+        )
+        .await?;
+        self.pull_requests
+            .iter()
+            .find(|pr| pr.number == pr_number)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("PR #{} not mocked", pr_number))
+    }
+
+    async fn fetch_check_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        commit_sha: &str,
+    ) -> anyhow::Result<Vec<CheckRun>> {
+        self.call(
+            "fetch_check_runs",
+            format!("{}/{} {}", owner, repo, commit_sha),
+        )
+        .await?;
+        Ok(Vec::new())
+    }
+
+    async fn fetch_commit_status(
+        &self,
+        owner: &str,
+        repo: &str,
+        commit_sha: &str,
+    ) -> anyhow::Result<CheckStatus> {
+        self.call(
+            "fetch_commit_status",
+            format!("{}/{} {}", owner, repo, commit_sha),
+        )
+        .await?;
+        anyhow::bail!("fetch_commit_status not mocked")
+    }
+
+    async fn merge_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        _merge_method: MergeMethod,
+        _commit_title: Option<&str>,
+        _commit_message: Option<&str>,
+    ) -> anyhow::Result<MergeResult> {
+        self.call(
+            "merge_pull_request",
+            format!("{}/{}#{}", owner, repo, pr_number),
+        )
+        .await?;
+        Self::canned("merge_pull_request", &self.merge_result)
+    }
+
+    async fn update_pull_request_branch(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<()> {
+        self.call(
+            "update_pull_request_branch",
+            format!("{}/{}#{}", owner, repo, pr_number),
+        )
+        .await
+    }
+
+    async fn create_review(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        event: ReviewEvent,
+        body: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.call(
+            "create_review",
+            format!(
+                "{}/{}#{} {:?} {}",
+                owner,
+                repo,
+                pr_number,
+                event,
+                body.unwrap_or("-")
+            ),
+        )
+        .await
+    }
+
+    async fn close_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<()> {
+        self.call(
+            "close_pull_request",
+            format!("{}/{}#{}", owner, repo, pr_number),
+        )
+        .await
+    }
+
+    async fn fetch_repository_settings(
+        &self,
+        owner: &str,
+        repo: &str,
+    ) -> anyhow::Result<RepositorySettings> {
+        self.call("fetch_repository_settings", format!("{}/{}", owner, repo))
+            .await?;
+        Self::canned("fetch_repository_settings", &self.repository_settings)
+    }
+
+    async fn fetch_required_checks(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> anyhow::Result<Option<Vec<String>>> {
+        self.call(
+            "fetch_required_checks",
+            format!("{}/{} {}", owner, repo, branch),
+        )
+        .await?;
+        Ok(self.required_checks.clone())
+    }
+
+    async fn delete_branch(&self, owner: &str, repo: &str, branch: &str) -> anyhow::Result<()> {
+        self.call("delete_branch", format!("{}/{} {}", owner, repo, branch))
+            .await
+    }
+
+    async fn fetch_repo_labels(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<Label>> {
+        self.call("fetch_repo_labels", format!("{}/{}", owner, repo))
+            .await?;
+        Ok(Vec::new())
+    }
+
+    async fn add_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        labels: &[String],
+    ) -> anyhow::Result<()> {
+        self.call(
+            "add_labels",
+            format!("{}/{}#{} {}", owner, repo, pr_number, labels.join(",")),
+        )
+        .await
+    }
+
+    async fn remove_label(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        label: &str,
+    ) -> anyhow::Result<()> {
+        self.call(
+            "remove_label",
+            format!("{}/{}#{} {}", owner, repo, pr_number, label),
+        )
+        .await
+    }
+
+    async fn fetch_collaborators(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<String>> {
+        self.call("fetch_collaborators", format!("{}/{}", owner, repo))
+            .await?;
+        Self::canned("fetch_collaborators", &self.collaborators)
+    }
+
+    async fn fetch_branches(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<String>> {
+        self.call("fetch_branches", format!("{}/{}", owner, repo))
+            .await?;
+        Self::canned("fetch_branches", &self.branches)
+    }
+
+    async fn request_reviewers(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        reviewers: &[String],
+        team_reviewers: &[String],
+    ) -> anyhow::Result<()> {
+        self.call(
+            "request_reviewers",
+            format!(
+                "{}/{}#{} {} teams {}",
+                owner,
+                repo,
+                pr_number,
+                reviewers.join(","),
+                team_reviewers.join(",")
+            ),
+        )
+        .await
+    }
+
+    async fn rerun_failed_jobs(&self, owner: &str, repo: &str, run_id: u64) -> anyhow::Result<()> {
+        self.call(
+            "rerun_failed_jobs",
+            format!("{}/{} {}", owner, repo, run_id),
+        )
+        .await
+    }
+
+    async fn fetch_workflow_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        head_sha: &str,
+    ) -> anyhow::Result<Vec<WorkflowRun>> {
+        self.call(
+            "fetch_workflow_runs",
+            format!("{}/{} {}", owner, repo, head_sha),
+        )
+        .await?;
+        Ok(self.workflow_runs.clone())
+    }
+
+    async fn fetch_recent_workflow_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        _limit: usize,
+    ) -> anyhow::Result<Vec<WorkflowRun>> {
+        self.call(
+            "fetch_recent_workflow_runs",
+            format!("{}/{} {}", owner, repo, branch),
+        )
+        .await?;
+        Ok(Vec::new())
+    }
+
+    async fn fetch_branch_head_sha(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> anyhow::Result<Option<String>> {
+        self.call(
+            "fetch_branch_head_sha",
+            format!("{}/{} {}", owner, repo, branch),
+        )
+        .await?;
+        Ok(self.branch_head_sha.clone())
+    }
+
+    async fn fetch_compare(
+        &self,
+        owner: &str,
+        repo: &str,
+        base: &str,
+        head: &str,
+    ) -> anyhow::Result<Comparison> {
+        self.call(
+            "fetch_compare",
+            format!("{}/{} {}...{}", owner, repo, base, head),
+        )
+        .await?;
+        Self::canned("fetch_compare", &self.comparison)
+    }
+
+    async fn fetch_latest_tag(&self, owner: &str, repo: &str) -> anyhow::Result<Option<String>> {
+        self.call("fetch_latest_tag", format!("{}/{}", owner, repo))
+            .await?;
+        Ok(self.latest_tag.clone())
+    }
+
+    async fn fetch_ref_date(
+        &self,
+        owner: &str,
+        repo: &str,
+        git_ref: &str,
+    ) -> anyhow::Result<Option<DateTime<Utc>>> {
+        self.call("fetch_ref_date", format!("{}/{} {}", owner, repo, git_ref))
+            .await?;
+        Ok(self.ref_dates.get(git_ref).copied())
+    }
+
+    async fn fetch_pull_request_diff(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<String> {
+        self.call(
+            "fetch_pull_request_diff",
+            format!("{}/{}#{}", owner, repo, pr_number),
+        )
+        .await?;
+        Self::canned("fetch_pull_request_diff", &self.diff)
+    }
+
+    async fn fetch_ci_status(
+        &self,
+        owner: &str,
+        repo: &str,
+        head_sha: &str,
+    ) -> anyhow::Result<CiStatus> {
+        self.call(
+            "fetch_ci_status",
+            format!("{}/{} {}", owner, repo, head_sha),
+        )
+        .await?;
+        Self::canned("fetch_ci_status", &self.ci_status)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn create_review_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        _commit_id: &str,
+        path: &str,
+        lines: &CommentLines,
+        _body: &str,
+    ) -> anyhow::Result<u64> {
+        self.call(
+            "create_review_comment",
+            format!("{}/{}#{} {}:{}", owner, repo, pr_number, path, lines.line),
+        )
+        .await?;
+        Ok(1)
+    }
+
+    async fn delete_review_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        comment_id: u64,
+    ) -> anyhow::Result<()> {
+        self.call(
+            "delete_review_comment",
+            format!("{}/{} {}", owner, repo, comment_id),
+        )
+        .await
+    }
+
+    async fn create_file_review_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        _commit_id: &str,
+        path: &str,
+        _body: &str,
+    ) -> anyhow::Result<u64> {
+        self.call(
+            "create_file_review_comment",
+            format!("{}/{}#{} {}", owner, repo, pr_number, path),
+        )
+        .await?;
+        Ok(1)
+    }
+
+    async fn create_issue_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        body: &str,
+    ) -> anyhow::Result<u64> {
+        self.call(
+            "create_issue_comment",
+            format!("{}/{}#{} {}", owner, repo, pr_number, body),
+        )
+        .await?;
+        if let Some(message) = self.pr_errors.get(&pr_number) {
+            anyhow::bail!("{}", message);
+        }
+        Ok(1)
+    }
+
+    async fn delete_issue_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        comment_id: u64,
+    ) -> anyhow::Result<()> {
+        self.call(
+            "delete_issue_comment",
+            format!("{}/{} {}", owner, repo, comment_id),
+        )
+        .await
+    }
+
+    async fn fetch_review_comments(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<Vec<ReviewComment>> {
+        self.call(
+            "fetch_review_comments",
+            format!("{}/{}#{}", owner, repo, pr_number),
+        )
+        .await?;
+        Ok(Vec::new())
+    }
+
+    async fn fetch_review_decision(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<ReviewDecision> {
+        self.call(
+            "fetch_review_decision",
+            format!("{}/{}#{}", owner, repo, pr_number),
+        )
+        .await?;
+        Self::canned("fetch_review_decision", &self.review_decision)
+    }
+
+    async fn fetch_comment_counts(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> anyhow::Result<CommentCounts> {
+        self.call(
+            "fetch_comment_counts",
+            format!("{}/{}#{}", owner, repo, pr_number),
+        )
+        .await?;
+        Self::canned("fetch_comment_counts", &self.comment_counts)
+    }
+
+    async fn fetch_reviewers(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_numbers: &[u64],
+    ) -> anyhow::Result<Vec<PrReviewers>> {
+        self.call(
+            "fetch_reviewers",
+            format!("{}/{} {:?}", owner, repo, pr_numbers),
+        )
+        .await?;
+        Self::canned("fetch_reviewers", &self.reviewers)
+    }
+
+    async fn fetch_blame(
+        &self,
+        owner: &str,
+        repo: &str,
+        commit_sha: &str,
+        path: &str,
+    ) -> anyhow::Result<Vec<BlameRange>> {
+        self.call(
+            "fetch_blame",
+            format!("{}/{} {}:{}", owner, repo, commit_sha, path),
+        )
+        .await?;
+        Self::canned("fetch_blame", &self.blame)
+    }
+
+    async fn fetch_file_content(
+        &self,
+        owner: &str,
+        repo: &str,
+        path: &str,
+        git_ref: Option<&str>,
+    ) -> anyhow::Result<String> {
+        self.call(
+            "fetch_file_content",
+            format!("{}/{} {}@{}", owner, repo, path, git_ref.unwrap_or("HEAD")),
+        )
+        .await?;
+        Self::canned("fetch_file_content", &self.files.get(path).cloned())
+    }
+}
+
+/// Open pull request into `main`, as returned by the API
+pub fn pull_request(number: u64, head_sha: &str) -> PullRequest {
+    PullRequest {
+        number,
+        title: format!("PR {}", number),
+        body: None,
+        author: "bob".to_string(),
+        comments: 0,
+        head_sha: head_sha.to_string(),
+        base_branch: "main".to_string(),
+        head_branch: format!("feature-{}", number),
+        mergeable: Some(true),
+        mergeable_state: Some(MergeableState::Clean),
+        mergeable_state_raw: None,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+        html_url: format!("https://github.com/acme/api/pull/{}", number),
+        additions: 10,
+        deletions: 2,
+        changed_files: Some(1),
+        maturity: MaturityState::Ready,
+        review_decision: ReviewDecision::Pending,
+        state: PrState::Open,
+        labels: Vec::new(),
+        head_repo_owner: Some("acme".to_string()),
+        head_repo_name: Some("api".to_string()),
+        is_from_fork: false,
+        maintainer_can_modify: false,
+    }
+}
+
+/// Factory handing out the same mock client for every repository
+pub struct MockClientFactory {
+    pub client: Arc<MockGitHubClient>,
+    /// Every client is anonymous (read-only mode)
+    pub anonymous: bool,
+}
+
+impl MockClientFactory {
+    pub fn new(client: MockGitHubClient) -> Self {
+        Self {
+            client: Arc::new(client),
+            anonymous: false,
+        }
+    }
+}
+
+#[async_trait]
+impl ClientFactory for MockClientFactory {
+    async fn client(
+        &self,
+        _repo: &Repository,
+        _force_refresh: bool,
+    ) -> anyhow::Result<Arc<dyn GitHubClient>> {
+        Ok(self.client.clone())
+    }
+
+    async fn is_anonymous(&self, _repo: &Repository) -> bool {
+        self.anonymous
+    }
+
+    async fn login(&self, repo: &Repository) -> anyhow::Result<String> {
+        if self.anonymous {
+            anyhow::bail!("No token configured for {}", repo.account_key());
+        }
+        Ok("bob".to_string())
+    }
+}
+
+/// Current-thread runtime, spawned tasks only run while it is driven
+pub fn test_runtime() -> Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to create test runtime")
+}
+
+/// Drive the runtime until every spawned task finished
+pub fn settle(runtime: &Runtime) {
+    runtime.block_on(async {
+        while tokio::runtime::Handle::current()
+            .metrics()
+            .num_alive_tasks()
+            > 0
+        {
+            tokio::task::yield_now().await;
+        }
+    });
+}
+
+/// Status bar messages among the actions, in dispatch order
+pub fn status_messages(actions: &[Action]) -> Vec<(StatusKind, String)> {
+    actions
+        .iter()
+        .filter_map(|action| match action {
+            Action::StatusBar(StatusBarAction::Push { kind, message, .. }) => {
+                Some((*kind, message.clone()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Repository `acme/api` tracking `main`
+pub fn repo() -> Repository {
+    Repository::new("acme", "api", "main")
+}
+
+/// State with [`repo`] as the only, selected repository
+pub fn state() -> AppState {
+    let mut state = AppState::default();
+    state.main_view.repositories.push(repo());
+    state
+}
+
+/// CI status of a single successful check
+pub fn green() -> CiStatus {
+    CiStatus::from_checks([(CheckRunStatus::Completed, Some(CheckConclusion::Success))])
+}
+
+/// Handle `action` with the middleware built by `new` on a [`GitHubContext`]
+/// handing out `client`, and return every action dispatched until all
+/// spawned operations finished
+pub fn run_middleware<M: Middleware<Action, AppState>>(
+    new: impl FnOnce(GitHubContext) -> M,
+    state: &AppState,
+    client: MockGitHubClient,
+    action: Action,
+) -> (Vec<Action>, Arc<MockGitHubClient>) {
+    let runtime = test_runtime();
+    let factory = Arc::new(MockClientFactory::new(client));
+    let mut middleware = new(GitHubContext::with_clients(
+        Arc::clone(&factory) as Arc<dyn ClientFactory>,
+        runtime.handle().clone(),
+    ));
+    let (tx, rx) = channel();
+    middleware.handle(&action, state, &Dispatcher::new(tx));
+    settle(&runtime);
+    (rx.try_iter().collect(), Arc::clone(&factory.client))
+}
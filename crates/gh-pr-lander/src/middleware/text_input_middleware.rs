@@ -25,7 +25,7 @@ impl Default for TextInputMiddleware {
     }
 }
 
-impl Middleware for TextInputMiddleware {
+impl Middleware<Action, AppState> for TextInputMiddleware {
     fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
         if let Action::TextInput(input) = action {
             if let Some(view) = state.view_stack.last() {
@@ -0,0 +1,137 @@
+//! Actions View Reducer
+//!
+//! Handles state updates for the workflow run history view.
+
+use crate::actions::ActionsViewAction;
+use crate::state::ActionsViewState;
+
+/// Reduce Actions view state based on ActionsViewAction
+pub fn reduce_actions_view(
+    mut state: ActionsViewState,
+    action: &ActionsViewAction,
+) -> ActionsViewState {
+    match action {
+        ActionsViewAction::LoadStart { repo, branch } => {
+            // Keep the cursor when refreshing the same branch
+            let same_branch = state.repo.as_ref() == Some(repo) && state.branch == *branch;
+            if !same_branch {
+                state.runs.clear();
+                state.selected = 0;
+            }
+            state.repo = Some(repo.clone());
+            state.branch = branch.clone();
+            state.loading = true;
+            state.error = None;
+        }
+
+        ActionsViewAction::Loaded(runs) => {
+            state.runs = runs.clone();
+            state.selected = state.selected.min(runs.len().saturating_sub(1));
+            state.loading = false;
+        }
+
+        ActionsViewAction::LoadError(error) => {
+            state.loading = false;
+            state.error = Some(error.clone());
+        }
+
+        ActionsViewAction::NavigateNext => {
+            if state.selected + 1 < state.runs.len() {
+                state.selected += 1;
+            }
+        }
+
+        ActionsViewAction::NavigatePrevious => {
+            state.selected = state.selected.saturating_sub(1);
+        }
+
+        ActionsViewAction::NavigateToTop => {
+            state.selected = 0;
+        }
+
+        ActionsViewAction::NavigateToBottom => {
+            state.selected = state.runs.len().saturating_sub(1);
+        }
+
+        // Open pushes the view (app reducer), OpenSelected is handled by middleware
+        ActionsViewAction::Open | ActionsViewAction::OpenSelected => {}
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::Repository;
+    use chrono::Utc;
+    use gh_client::{WorkflowRun, WorkflowRunStatus};
+
+    fn run(id: u64) -> WorkflowRun {
+        WorkflowRun {
+            id,
+            name: "CI".to_string(),
+            status: WorkflowRunStatus::Completed,
+            conclusion: None,
+            head_sha: "abc".to_string(),
+            head_branch: Some("main".to_string()),
+            event: "push".to_string(),
+            html_url: String::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            run_attempt: 1,
+        }
+    }
+
+    fn loaded() -> ActionsViewState {
+        let state = reduce_actions_view(
+            ActionsViewState::default(),
+            &ActionsViewAction::LoadStart {
+                repo: Repository::new("acme", "api", "main"),
+                branch: "main".to_string(),
+            },
+        );
+        assert!(state.loading);
+        reduce_actions_view(
+            state,
+            &ActionsViewAction::Loaded(vec![run(3), run(2), run(1)]),
+        )
+    }
+
+    #[test]
+    fn test_navigation_stays_in_range() {
+        let mut state = loaded();
+        assert!(!state.loading);
+        state = reduce_actions_view(state, &ActionsViewAction::NavigatePrevious);
+        assert_eq!(state.selected, 0);
+        state = reduce_actions_view(state, &ActionsViewAction::NavigateToBottom);
+        state = reduce_actions_view(state, &ActionsViewAction::NavigateNext);
+        assert_eq!(state.selected_run().map(|r| r.id), Some(1));
+    }
+
+    #[test]
+    fn test_refresh_keeps_cursor_and_new_branch_resets() {
+        let state = reduce_actions_view(loaded(), &ActionsViewAction::NavigateNext);
+        let repo = Repository::new("acme", "api", "main");
+
+        let refreshed = reduce_actions_view(
+            state.clone(),
+            &ActionsViewAction::LoadStart {
+                repo: repo.clone(),
+                branch: "main".to_string(),
+            },
+        );
+        assert_eq!(refreshed.selected, 1);
+        assert_eq!(refreshed.runs.len(), 3);
+
+        let other = reduce_actions_view(
+            state,
+            &ActionsViewAction::LoadStart {
+                repo,
+                branch: "release".to_string(),
+            },
+        );
+        assert_eq!(other.selected, 0);
+        assert!(other.runs.is_empty());
+    }
+}
@@ -6,23 +6,43 @@
 //! - No ViewId matching - views own their action translation
 
 use crate::actions::{
-    Action, BootstrapAction, CommandPaletteAction, GlobalAction, KeyBindingsAction,
-    RepositoryAction,
+    Action, ActionsViewAction, AuditLogAction, BootstrapAction, BuildLogAction,
+    CacheInspectorAction, CommandPaletteAction, ConfirmationPopupAction, ConflictHelperAction,
+    DebugConsoleAction, DependencyUpdatesAction, DiffViewerAction, GlobalAction, KeyBindingsAction,
+    PerfStatsAction, PullRequestAction, ReleaseNotesAction, RepositoryAction, WatchListAction,
 };
 use crate::reducers::{
-    build_log_reducer, command_palette_reducer, confirmation_popup_reducer, debug_console_reducer,
-    diff_viewer_reducer, key_bindings_reducer, pull_request_reducer, repository_reducer,
-    session_reducer, splash_reducer, status_bar_reducer,
+    actions_view_reducer, audit_log_reducer, auth_reducer, branch_picker_reducer,
+    bug_report_reducer, build_log_reducer, cache_inspector_reducer, command_palette_reducer,
+    confirmation_popup_reducer, conflict_helper_reducer, debug_console_reducer,
+    dependency_updates_reducer, diff_viewer_reducer, duplicate_updates_reducer,
+    filter_builder_reducer, key_bindings_reducer, label_picker_reducer, onboarding_reducer,
+    perf_stats_reducer, pr_search_reducer, pull_request_reducer, release_notes_reducer,
+    repository_reducer, reviewer_picker_reducer, session_reducer, splash_reducer,
+    status_bar_reducer, watch_list_reducer,
+};
+use crate::state::{dependency_groups, AppState, PrSort, StageStatus, TerminalSize};
+use crate::views::{
+    ActionsView, AuditLogView, CacheInspectorView, CommandPaletteView, ConflictHelperView,
+    DependencyUpdatesView, DiffViewerView, PerfStatsView, ReleaseNotesView, ViewId, WatchListView,
 };
-use crate::state::AppState;
-use crate::views::DiffViewerView;
 
 /// Reducer - pure function that produces new state from current state + action
 ///
 /// This is the root reducer that orchestrates all sub-reducers.
 /// It routes actions by their tag (screen-specific variants) or translates
 /// generic actions via the active view.
-pub fn reduce(mut state: AppState, action: &Action) -> AppState {
+pub fn reduce(state: AppState, action: &Action) -> AppState {
+    // The cursor moves through many reducers, so its changes are noticed here
+    let selection = state.main_view.session_selection();
+    let mut state = reduce_action(state, action);
+    if state.main_view.session_selection() != selection {
+        state.main_view.mark_session_dirty();
+    }
+    state
+}
+
+fn reduce_action(mut state: AppState, action: &Action) -> AppState {
     match action {
         // =======================================================================
         // EVENTS - Re-routed to middleware in main loop, should not reach here
@@ -37,7 +57,8 @@ pub fn reduce(mut state: AppState, action: &Action) -> AppState {
         // =======================================================================
         // GLOBAL ACTIONS - Application-wide behavior
         // =======================================================================
-        Action::Global(GlobalAction::Quit) => {
+        // RequestQuit only gets here without the middleware asking first
+        Action::Global(GlobalAction::Quit | GlobalAction::RequestQuit) => {
             state.running = false;
             state
         }
@@ -46,9 +67,21 @@ pub fn reduce(mut state: AppState, action: &Action) -> AppState {
             if state.view_stack.len() > 1 {
                 let popped = state.view_stack.pop();
                 log::debug!("Closed view: {:?}", popped.map(|v| v.view_id()));
-            } else {
+            } else if state.app_config.ui.quit_on_close {
                 log::debug!("Closing last view - quitting application");
                 state.running = false;
+            } else {
+                log::debug!("Close on the root view ignored (ui.quit_on_close = false)");
+            }
+            state
+        }
+
+        Action::Global(GlobalAction::PopView) => {
+            if state.view_stack.len() > 1 {
+                let popped = state.view_stack.pop();
+                log::debug!("Popped view: {:?}", popped.map(|v| v.view_id()));
+            } else {
+                log::debug!("PopView on the root view ignored");
             }
             state
         }
@@ -81,18 +114,37 @@ pub fn reduce(mut state: AppState, action: &Action) -> AppState {
             state
         }
 
+        Action::Global(GlobalAction::Resized { width, height }) => {
+            log::debug!("Terminal resized to {}x{}", width, height);
+            state.terminal_size = TerminalSize::new(*width, *height);
+            apply_terminal_size(state)
+        }
+
         Action::Global(GlobalAction::Tick) => {
             // Tick is used for animations - delegate to splash reducer
-            state.splash =
-                splash_reducer::reduce_splash(state.splash, &crate::actions::SplashAction::Tick);
+            if !state.app_config.ui.reduced_motion {
+                state.splash = splash_reducer::reduce_splash(
+                    state.splash,
+                    &crate::actions::SplashAction::Tick,
+                );
+            }
             state
         }
 
-        Action::Global(GlobalAction::KeyPressed(_)) => {
+        Action::Global(
+            GlobalAction::KeyPressed(_) | GlobalAction::KeyRepeated { .. } | GlobalAction::Paste(_),
+        ) => {
             // Handled by keyboard middleware, not by reducer
             state
         }
 
+        Action::Global(GlobalAction::Repeat { action, count }) => {
+            for _ in 0..*count {
+                state = reduce(state, action);
+            }
+            state
+        }
+
         // =======================================================================
         // GENERIC ACTIONS - Translate via active view and recurse
         // =======================================================================
@@ -136,34 +188,44 @@ pub fn reduce(mut state: AppState, action: &Action) -> AppState {
         // SCREEN-SPECIFIC ACTIONS - Route by tag to type-safe reducers
         // =======================================================================
         Action::PullRequest(sub) => {
+            if matches!(
+                sub,
+                PullRequestAction::RepositoryNext | PullRequestAction::RepositoryPrevious
+            ) {
+                state = cancel_confirmation(state);
+            }
             // TODO: here we should have a dedicated pull request state in the future
             state.main_view = pull_request_reducer::reduce_pull_request(state.main_view, sub);
             state
         }
 
         Action::CommandPalette(sub) => {
-            // Handle Close and Execute here for view stack management
+            // Handle Open, Close and Execute here for view stack management
+            if matches!(sub, CommandPaletteAction::Open(_))
+                && state.active_view().view_id() != ViewId::CommandPalette
+            {
+                state.view_stack.push(Box::new(CommandPaletteView::new()));
+            }
             if matches!(
                 sub,
                 CommandPaletteAction::Close | CommandPaletteAction::Execute
-            ) && state.view_stack.len() > 1
-            {
-                let popped = state.view_stack.pop();
-                log::debug!("Closed view: {:?}", popped.map(|v| v.view_id()));
+            ) {
+                state.pop_view(ViewId::CommandPalette);
             }
             state.command_palette = command_palette_reducer::reduce_command_palette(
                 state.command_palette,
                 sub,
                 &state.keymap,
+                &state.main_view.saved_filters,
+                state.main_view.profile.as_ref(),
             );
             state
         }
 
         Action::KeyBindings(sub) => {
             // Handle Close here for view stack management
-            if matches!(sub, KeyBindingsAction::Close) && state.view_stack.len() > 1 {
-                let popped = state.view_stack.pop();
-                log::debug!("Closed view: {:?}", popped.map(|v| v.view_id()));
+            if matches!(sub, KeyBindingsAction::Close) {
+                state.pop_view(ViewId::KeyBindings);
             }
             state.key_bindings_panel =
                 key_bindings_reducer::reduce_key_bindings(state.key_bindings_panel, sub);
@@ -194,11 +256,42 @@ pub fn reduce(mut state: AppState, action: &Action) -> AppState {
                     state.splash.bootstrapping = false;
                 }
                 BootstrapAction::ConfigLoaded(config) => {
-                    state.app_config = config.clone();
+                    state.app_config = (**config).clone();
+                    state.main_view.tab_cycling = config.ui.tab_cycling;
+                    state.main_view.pr_sizes = config.ui.pr_size;
+                    state.main_view.default_sort = if config.ui.sort_by_attention {
+                        PrSort::Attention
+                    } else {
+                        PrSort::Newest
+                    };
+                    state.build_log.collapse_successful = config.ui.collapse_successful_jobs;
+                    state.theme = active_theme(&state);
                     log::info!("App config loaded into state");
                 }
+                BootstrapAction::StageCompleted(stage) => {
+                    state.splash.set_stage_status(*stage, StageStatus::Done);
+                }
+                BootstrapAction::StageWarning(stage, warning) => {
+                    state
+                        .splash
+                        .set_stage_status(*stage, StageStatus::Warning(warning.clone()));
+                }
+                BootstrapAction::StageFailed(stage, error) => {
+                    state
+                        .splash
+                        .set_stage_status(*stage, StageStatus::Failed(error.clone()));
+                }
+                BootstrapAction::Retry => {
+                    // Failed stages run again, completed stages are kept
+                    for (_, status) in state.splash.stages.iter_mut() {
+                        if matches!(status, StageStatus::Failed(_)) {
+                            *status = StageStatus::Pending;
+                        }
+                    }
+                }
                 BootstrapAction::LoadRecentRepositories
-                | BootstrapAction::LoadRecentRepositoriesDone => {
+                | BootstrapAction::LoadRecentRepositoriesDone
+                | BootstrapAction::RetryStage(_) => {
                     // Handled by middleware
                 }
             }
@@ -211,6 +304,129 @@ pub fn reduce(mut state: AppState, action: &Action) -> AppState {
             state
         }
 
+        // Onboarding wizard actions - delegate to dedicated reducer
+        Action::Onboarding(sub) => onboarding_reducer::reduce_onboarding(state, sub),
+
+        // Stored token actions - delegate to dedicated reducer
+        Action::Auth(sub) => auth_reducer::reduce_auth(state, sub),
+        Action::BugReport(sub) => bug_report_reducer::reduce_bug_report(state, sub),
+        Action::LabelPicker(sub) => label_picker_reducer::reduce_label_picker(state, sub),
+        Action::ReviewerPicker(sub) => reviewer_picker_reducer::reduce_reviewer_picker(state, sub),
+        Action::BranchPicker(sub) => branch_picker_reducer::reduce_branch_picker(state, sub),
+        Action::FilterBuilder(sub) => filter_builder_reducer::reduce_filter_builder(state, sub),
+        Action::DuplicateUpdates(sub) => {
+            duplicate_updates_reducer::reduce_duplicate_updates(state, sub)
+        }
+
+        // PR search actions - delegate to dedicated reducer
+        Action::PrSearch(sub) => pr_search_reducer::reduce_pr_search(state, sub),
+
+        // Watch list actions - open the view here, delegate the rest
+        Action::WatchList(sub) => {
+            if matches!(sub, WatchListAction::Open) {
+                state.view_stack.push(Box::new(WatchListView::new()));
+            }
+            state.main_view.watch_list =
+                watch_list_reducer::reduce_watch_list(state.main_view.watch_list, sub);
+            if matches!(sub, WatchListAction::Remove(_)) {
+                state.main_view.mark_session_dirty();
+            }
+            state
+        }
+
+        // Actions view - open the view here, delegate the rest
+        Action::ActionsView(sub) => {
+            if matches!(sub, ActionsViewAction::Open) {
+                state.view_stack.push(Box::new(ActionsView::new()));
+            }
+            state.actions_view = actions_view_reducer::reduce_actions_view(state.actions_view, sub);
+            state
+        }
+
+        // Dependency updates view - open the view here, delegate the cursor
+        Action::DependencyUpdates(sub) => {
+            if matches!(sub, DependencyUpdatesAction::Open) {
+                state
+                    .view_stack
+                    .push(Box::new(DependencyUpdatesView::new()));
+            }
+            let entry_count = dependency_groups(&state.main_view)
+                .iter()
+                .map(|group| group.entries.len())
+                .sum();
+            state.dependency_updates = dependency_updates_reducer::reduce_dependency_updates(
+                state.dependency_updates,
+                sub,
+                entry_count,
+            );
+            state
+        }
+
+        // Performance stats popup - open the view here, delegate the snapshot
+        Action::PerfStats(sub) => {
+            if matches!(sub, PerfStatsAction::Open) {
+                state.view_stack.push(Box::new(PerfStatsView::new()));
+            }
+            state.perf_stats = perf_stats_reducer::reduce_perf_stats(state.perf_stats, sub);
+            state
+        }
+
+        // Cache inspector popup - open the view here, delegate the listing
+        Action::CacheInspector(sub) => {
+            if matches!(sub, CacheInspectorAction::Open)
+                && state.active_view().view_id() != ViewId::CacheInspector
+            {
+                state.view_stack.push(Box::new(CacheInspectorView::new()));
+            }
+            state.cache_inspector =
+                cache_inspector_reducer::reduce_cache_inspector(state.cache_inspector, sub);
+            state
+        }
+
+        // Audit log panel - focus the PR under the cursor, delegate the listing
+        Action::AuditLog(sub) => {
+            if matches!(sub, AuditLogAction::Open) {
+                let repo_idx = state.main_view.selected_repository;
+                let repo = state.main_view.repositories.get(repo_idx).cloned();
+                let pr_number = state
+                    .main_view
+                    .repo_data
+                    .get(&repo_idx)
+                    .and_then(|data| data.prs.get(data.selected_pr))
+                    .map(|pr| pr.number);
+                state.audit_log.focus(repo, pr_number);
+                if state.active_view().view_id() != ViewId::AuditLog {
+                    state.view_stack.push(Box::new(AuditLogView::new()));
+                }
+            }
+            state.audit_log = audit_log_reducer::reduce_audit_log(state.audit_log, sub);
+            state
+        }
+
+        // Conflict helper popup - open the view here, delegate the comparison
+        Action::ConflictHelper(sub) => {
+            if matches!(sub, ConflictHelperAction::Open { .. })
+                && state.active_view().view_id() != ViewId::ConflictHelper
+            {
+                state.view_stack.push(Box::new(ConflictHelperView::new()));
+            }
+            state.conflict_helper =
+                conflict_helper_reducer::reduce_conflict_helper(state.conflict_helper, sub);
+            state
+        }
+
+        // Release notes popup - open the view here, delegate the notes
+        Action::ReleaseNotes(sub) => {
+            if matches!(sub, ReleaseNotesAction::Generate { .. })
+                && state.active_view().view_id() != ViewId::ReleaseNotes
+            {
+                state.view_stack.push(Box::new(ReleaseNotesView::new()));
+            }
+            state.release_notes =
+                release_notes_reducer::reduce_release_notes(state.release_notes, sub);
+            state
+        }
+
         // MergeBot actions - currently handled by middlewares (no state changes in reducer)
         Action::MergeBot(_) => state,
 
@@ -222,7 +438,11 @@ pub fn reduce(mut state: AppState, action: &Action) -> AppState {
 
         // Build log actions
         Action::BuildLog(sub) => {
+            let show_successful = state.build_log.show_successful;
             state.build_log = build_log_reducer::reduce_build_log(state.build_log, sub);
+            if state.build_log.show_successful != show_successful {
+                state.main_view.mark_session_dirty();
+            }
             state
         }
 
@@ -233,8 +453,6 @@ pub fn reduce(mut state: AppState, action: &Action) -> AppState {
 
         // Diff viewer actions
         Action::DiffViewer(sub) => {
-            use crate::actions::DiffViewerAction;
-
             // Handle Open specially to push view onto stack
             if matches!(sub, DiffViewerAction::Open) {
                 log::debug!("Opening diff viewer");
@@ -242,6 +460,15 @@ pub fn reduce(mut state: AppState, action: &Action) -> AppState {
             }
 
             state.diff_viewer = diff_viewer_reducer::reduce_diff_viewer(state.diff_viewer, sub);
+
+            // A loaded diff starts with a fresh viewport
+            if matches!(sub, DiffViewerAction::Loaded { .. }) {
+                let (width, height) = state.terminal_size.diff_viewport();
+                state.diff_viewer = diff_viewer_reducer::reduce_diff_viewer(
+                    state.diff_viewer,
+                    &DiffViewerAction::SetViewport { width, height },
+                );
+            }
             state
         }
 
@@ -252,14 +479,17 @@ pub fn reduce(mut state: AppState, action: &Action) -> AppState {
                 RepositoryAction::FormConfirm => state.add_repo_form.is_valid(),
                 _ => false,
             };
-            if should_close && state.view_stack.len() > 1 {
-                state.view_stack.pop();
+            if should_close {
+                state.pop_view(ViewId::AddRepository);
             }
 
             // Delegate to repository reducer for both main view and form state
             state.main_view = repository_reducer::reduce_repository(state.main_view, sub);
             state.add_repo_form =
                 repository_reducer::reduce_add_repo_form(state.add_repo_form, sub);
+            if let RepositoryAction::ProfileActivated(_) = sub {
+                state.theme = active_theme(&state);
+            }
             state
         }
 
@@ -267,3 +497,213 @@ pub fn reduce(mut state: AppState, action: &Action) -> AppState {
         Action::None => state,
     }
 }
+
+/// Theme of the active workspace profile, else the configured one
+fn active_theme(state: &AppState) -> gh_pr_lander_theme::Theme {
+    let name = state
+        .main_view
+        .profile
+        .as_ref()
+        .and_then(|profile| profile.theme.as_deref())
+        .unwrap_or(&state.app_config.ui.theme);
+    gh_pr_lander_theme::Theme::by_name(name).unwrap_or_else(|| {
+        log::warn!(
+            "Unknown theme {:?}, expected one of {:?}",
+            name,
+            gh_pr_lander_theme::THEME_NAMES
+        );
+        gh_pr_lander_theme::Theme::default()
+    })
+}
+
+/// Cancel an open confirmation popup
+///
+/// Its PR numbers belong to the selected repository, so it must not survive
+/// a change of that repository.
+fn cancel_confirmation(state: AppState) -> AppState {
+    if state.confirmation_popup.is_none() {
+        return state;
+    }
+    log::debug!("Repository changed, cancelling confirmation popup");
+    confirmation_popup_reducer::reduce_confirmation_popup(state, &ConfirmationPopupAction::Cancel)
+}
+
+/// Derive every viewport dependent state from `state.terminal_size`
+fn apply_terminal_size(mut state: AppState) -> AppState {
+    let size = state.terminal_size;
+    state.debug_console = debug_console_reducer::reduce_debug_console(
+        state.debug_console,
+        &DebugConsoleAction::SetVisibleHeight(size.debug_console_visible_lines()),
+    );
+    let (width, height) = size.diff_viewport();
+    state.diff_viewer = diff_viewer_reducer::reduce_diff_viewer(
+        state.diff_viewer,
+        &DiffViewerAction::SetViewport { width, height },
+    );
+    state.build_log = build_log_reducer::reduce_build_log(
+        state.build_log,
+        &BuildLogAction::SetViewportHeight(size.build_log_viewport_height()),
+    );
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resize_updates_viewports() {
+        let state = reduce(
+            AppState::default(),
+            &Action::Global(GlobalAction::Resized {
+                width: 100,
+                height: 30,
+            }),
+        );
+        assert_eq!(state.terminal_size, TerminalSize::new(100, 30));
+        assert_eq!(state.debug_console.visible_height, 19);
+        assert_eq!(state.build_log.viewport_height, 24);
+
+        let state = reduce(
+            state,
+            &Action::Global(GlobalAction::Resized {
+                width: 100,
+                height: 12,
+            }),
+        );
+        assert_eq!(state.debug_console.visible_height, 6);
+        assert_eq!(state.build_log.viewport_height, 6);
+    }
+
+    #[test]
+    fn test_loaded_diff_uses_terminal_size() {
+        let state = reduce(
+            AppState::default(),
+            &Action::Global(GlobalAction::Resized {
+                width: 90,
+                height: 25,
+            }),
+        );
+        let diff = gh_diff_viewer::parse_unified_diff(
+            "diff --git a/a b/a\n--- a/a\n+++ b/a\n@@ -1 +1 @@\n-x\n+y\n",
+            "base",
+            "head",
+        )
+        .unwrap();
+        let state = reduce(
+            state,
+            &Action::DiffViewer(DiffViewerAction::Loaded {
+                diff,
+                repo: crate::domain_models::Repository::default(),
+                pr_number: 1,
+                pr_title: "t".to_string(),
+                head_sha: "abc".to_string(),
+                comments: Vec::new(),
+            }),
+        );
+        let inner = state.diff_viewer.inner.as_ref().unwrap();
+        assert_eq!(inner.viewport_height, 22);
+    }
+
+    #[test]
+    fn test_only_changes_of_saved_data_mark_the_session_dirty() {
+        use crate::reducers::test_harness::{dispatch_all, state_with_repos};
+
+        let mut state = state_with_repos(2, &[1, 2]);
+        let dirty = |state: &mut AppState, action: PullRequestAction| {
+            let revision = state.main_view.session_revision;
+            dispatch_all(state, [Action::PullRequest(action)]);
+            state.main_view.session_revision != revision
+        };
+
+        assert!(dirty(&mut state, PullRequestAction::NavigateNext));
+        assert!(dirty(&mut state, PullRequestAction::RepositoryNext));
+        assert!(dirty(&mut state, PullRequestAction::MarkSeen));
+        assert!(!dirty(&mut state, PullRequestAction::MarkSeen));
+        assert!(dirty(&mut state, PullRequestAction::ToggleWatch));
+        assert!(!dirty(&mut state, PullRequestAction::SelectAll));
+        assert!(!dirty(&mut state, PullRequestAction::TogglePeek));
+    }
+
+    #[test]
+    fn test_switching_repository_cancels_confirmation() {
+        use crate::reducers::test_harness::{dispatch_all, state_with_repos};
+        use crate::state::ConfirmationIntent;
+
+        let mut state = state_with_repos(2, &[1]);
+        dispatch_all(
+            &mut state,
+            [
+                Action::ConfirmationPopup(ConfirmationPopupAction::Show {
+                    intent: ConfirmationIntent::Approve {
+                        pr_numbers: vec![1],
+                    },
+                    default_message: String::new(),
+                    repo_context: "org/repo-0".to_string(),
+                    warnings: vec![],
+                }),
+                Action::PullRequest(PullRequestAction::RepositoryNext),
+            ],
+        );
+
+        assert!(state.confirmation_popup.is_none());
+        assert_eq!(state.view_stack.len(), 1);
+        assert_eq!(state.main_view.selected_repository, 1);
+    }
+
+    #[test]
+    fn test_closing_a_covered_view_keeps_the_views_above() {
+        use crate::reducers::test_harness::{dispatch_all, state_with_repos};
+        use crate::views::KeyBindingsView;
+
+        let mut state = state_with_repos(1, &[1]);
+        dispatch_all(
+            &mut state,
+            [
+                Action::Global(GlobalAction::PushView(Box::new(KeyBindingsView::new()))),
+                Action::ConfirmationPopup(ConfirmationPopupAction::Cancel),
+                Action::CommandPalette(CommandPaletteAction::Close),
+            ],
+        );
+        assert_eq!(state.active_view().view_id(), ViewId::KeyBindings);
+
+        dispatch_all(
+            &mut state,
+            [
+                Action::Global(GlobalAction::PopView),
+                Action::Global(GlobalAction::PopView),
+            ],
+        );
+        assert_eq!(state.view_stack.len(), 1);
+        assert!(state.running);
+    }
+
+    #[test]
+    fn test_profile_theme_outlives_config_reload() {
+        use crate::state::ActiveProfile;
+        use gh_pr_lander_theme::Theme;
+
+        let mut state = reduce(
+            AppState::default(),
+            &Action::Repository(RepositoryAction::ProfileActivated(ActiveProfile {
+                name: "work".to_string(),
+                names: vec!["work".to_string()],
+                default_filter: None,
+                theme: Some("high-contrast".to_string()),
+            })),
+        );
+        assert_eq!(
+            state.theme.tab_active_bg,
+            Theme::high_contrast().tab_active_bg
+        );
+
+        state = reduce(
+            state,
+            &Action::Bootstrap(BootstrapAction::ConfigLoaded(Box::default())),
+        );
+        assert_eq!(
+            state.theme.tab_active_bg,
+            Theme::high_contrast().tab_active_bg
+        );
+    }
+}
@@ -0,0 +1,22 @@
+//! Audit Log Reducer
+//!
+//! Keeps the read audit entries, the scope and the cursor of the audit log
+//! panel.
+
+use crate::actions::AuditLogAction;
+use crate::state::AuditLogState;
+
+/// Reduce audit log panel state
+pub fn reduce_audit_log(mut state: AuditLogState, action: &AuditLogAction) -> AuditLogState {
+    match action {
+        AuditLogAction::Loaded(entries) => state.set_entries(entries.clone()),
+        AuditLogAction::NavigateNext => state.select_next(),
+        AuditLogAction::NavigatePrevious => state.select_previous(),
+        AuditLogAction::NavigateToTop => state.selected = 0,
+        AuditLogAction::NavigateToBottom => state.select_last(),
+        AuditLogAction::ToggleScope => state.toggle_scope(),
+        // The app reducer focuses the PR, the middleware reads the file
+        AuditLogAction::Open | AuditLogAction::Refresh => {}
+    }
+    state
+}
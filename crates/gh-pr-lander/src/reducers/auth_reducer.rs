@@ -0,0 +1,91 @@
+//! Auth Reducer
+//!
+//! Handles the token input popup state, including view stack management.
+
+use crate::actions::AuthAction;
+use crate::state::{AppState, BootstrapStage, StageStatus, TokenInputState};
+use crate::views::{TokenInputView, ViewId};
+use gh_client::DEFAULT_HOST;
+
+/// Reduce token input state based on actions.
+pub fn reduce_auth(mut state: AppState, action: &AuthAction) -> AppState {
+    match action {
+        AuthAction::OpenTokenInput => {
+            let repo = state
+                .main_view
+                .repositories
+                .get(state.main_view.selected_repository);
+            let host = repo
+                .map(|repo| repo.effective_host().to_string())
+                .unwrap_or_else(|| DEFAULT_HOST.to_string());
+            let profile = repo.and_then(|repo| repo.profile.clone());
+            state.token_input = Some(TokenInputState::new(host).with_profile(profile));
+            state.view_stack.push(Box::new(TokenInputView::new()));
+        }
+
+        AuthAction::InputChar(c) => {
+            if let Some(ref mut input) = state.token_input {
+                if !input.validating && !c.is_whitespace() {
+                    input.input.push(*c);
+                    input.error = None;
+                }
+            }
+        }
+
+        AuthAction::InputBackspace => {
+            if let Some(ref mut input) = state.token_input {
+                if !input.validating {
+                    input.input.pop();
+                }
+            }
+        }
+
+        AuthAction::InputClear => {
+            if let Some(ref mut input) = state.token_input {
+                if !input.validating {
+                    input.input.clear();
+                }
+            }
+        }
+
+        AuthAction::SubmitToken => {
+            if let Some(ref mut input) = state.token_input {
+                if !input.input.is_empty() {
+                    input.validating = true;
+                    input.error = None;
+                }
+            }
+        }
+
+        AuthAction::TokenRejected(error) => {
+            if let Some(ref mut input) = state.token_input {
+                input.validating = false;
+                input.error = Some(error.clone());
+            }
+        }
+
+        AuthAction::TokenStored { account, .. } => {
+            // A token for the default account ends read-only mode
+            if account == DEFAULT_HOST && state.splash.read_only() {
+                state
+                    .splash
+                    .set_stage_status(BootstrapStage::ResolveToken, StageStatus::Done);
+            }
+            close_token_input(&mut state);
+        }
+
+        AuthAction::CloseTokenInput => {
+            close_token_input(&mut state);
+        }
+
+        AuthAction::DeleteStoredToken | AuthAction::ListStoredTokens => {}
+    }
+
+    state
+}
+
+/// Clear the token input state and pop its view
+fn close_token_input(state: &mut AppState) {
+    state.token_input = None;
+    state.pop_view(ViewId::TokenInput);
+}
@@ -0,0 +1,105 @@
+//! Branch Picker Reducer
+//!
+//! Handles the branch picker popup, including view stack management.
+
+use crate::actions::BranchPickerAction;
+use crate::state::{AppState, BranchPickerState};
+use crate::views::{BranchPickerView, ViewId};
+
+/// Reduce branch picker popup state based on actions.
+pub fn reduce_branch_picker(mut state: AppState, action: &BranchPickerAction) -> AppState {
+    match action {
+        BranchPickerAction::Show(repo) => {
+            if state.branch_picker.is_none() {
+                state.branch_picker = Some(BranchPickerState::new(repo.clone()));
+                state.view_stack.push(Box::new(BranchPickerView::new()));
+            }
+        }
+
+        BranchPickerAction::Loaded(branches) => {
+            if let Some(ref mut picker) = state.branch_picker {
+                picker.set_branches(branches.clone());
+            }
+        }
+
+        BranchPickerAction::LoadFailed(error) => {
+            if let Some(ref mut picker) = state.branch_picker {
+                picker.loading = false;
+                picker.error = Some(error.clone());
+            }
+        }
+
+        BranchPickerAction::InputChar(c) => {
+            if let Some(ref mut picker) = state.branch_picker {
+                picker.push_query(*c);
+            }
+        }
+
+        BranchPickerAction::InputBackspace => {
+            if let Some(ref mut picker) = state.branch_picker {
+                picker.pop_query();
+            }
+        }
+
+        BranchPickerAction::InputClear => {
+            if let Some(ref mut picker) = state.branch_picker {
+                picker.clear_query();
+            }
+        }
+
+        BranchPickerAction::NavigateNext => {
+            if let Some(ref mut picker) = state.branch_picker {
+                picker.select_next();
+            }
+        }
+
+        BranchPickerAction::NavigatePrevious => {
+            if let Some(ref mut picker) = state.branch_picker {
+                picker.select_previous();
+            }
+        }
+
+        // The middleware switched the branch before
+        BranchPickerAction::Select | BranchPickerAction::Close => {
+            state.branch_picker = None;
+            state.pop_view(ViewId::BranchPicker);
+        }
+
+        BranchPickerAction::Open => {}
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::Repository;
+
+    fn shown() -> AppState {
+        reduce_branch_picker(
+            AppState::default(),
+            &BranchPickerAction::Show(Repository::new("acme", "api", "main")),
+        )
+    }
+
+    #[test]
+    fn test_show_pushes_popup_while_loading() {
+        let state = shown();
+        assert_eq!(state.active_view().view_id(), ViewId::BranchPicker);
+        assert!(state.branch_picker.as_ref().unwrap().loading);
+    }
+
+    #[test]
+    fn test_select_closes_popup() {
+        let state = reduce_branch_picker(
+            shown(),
+            &BranchPickerAction::Loaded(vec!["main".to_string(), "release/2.x".to_string()]),
+        );
+        assert_eq!(state.branch_picker.as_ref().unwrap().branches.len(), 2);
+
+        let state = reduce_branch_picker(state, &BranchPickerAction::Select);
+        assert!(state.branch_picker.is_none());
+        assert_eq!(state.active_view().view_id(), ViewId::Splash);
+    }
+}
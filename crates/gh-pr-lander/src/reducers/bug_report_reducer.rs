@@ -0,0 +1,126 @@
+//! Bug Report Reducer
+//!
+//! Handles the bug report path popup, including view stack management.
+
+use crate::actions::BugReportAction;
+use crate::state::{AppState, BugReportState};
+use crate::views::{BugReportView, ViewId};
+
+/// Reduce bug report popup state based on actions.
+pub fn reduce_bug_report(mut state: AppState, action: &BugReportAction) -> AppState {
+    match action {
+        BugReportAction::Show { default_path } => {
+            if state.bug_report.is_none() {
+                state.bug_report = Some(BugReportState::new(default_path.clone()));
+                state.view_stack.push(Box::new(BugReportView::new()));
+            }
+        }
+
+        BugReportAction::InputChar(c) => {
+            if let Some(ref mut popup) = state.bug_report {
+                if !popup.writing {
+                    popup.path.push(*c);
+                    popup.error = None;
+                }
+            }
+        }
+
+        BugReportAction::InputBackspace => {
+            if let Some(ref mut popup) = state.bug_report {
+                if !popup.writing {
+                    popup.path.pop();
+                }
+            }
+        }
+
+        BugReportAction::InputClear => {
+            if let Some(ref mut popup) = state.bug_report {
+                if !popup.writing {
+                    popup.path.clear();
+                }
+            }
+        }
+
+        BugReportAction::Submit => {
+            if let Some(ref mut popup) = state.bug_report {
+                if !popup.path.trim().is_empty() {
+                    popup.writing = true;
+                    popup.error = None;
+                }
+            }
+        }
+
+        BugReportAction::Failed(error) => {
+            if let Some(ref mut popup) = state.bug_report {
+                popup.writing = false;
+                popup.error = Some(error.clone());
+            }
+        }
+
+        BugReportAction::Written(_) | BugReportAction::Close => {
+            close_bug_report(&mut state);
+        }
+
+        BugReportAction::Open => {}
+    }
+
+    state
+}
+
+/// Clear the bug report state and pop its view
+fn close_bug_report(state: &mut AppState) {
+    state.bug_report = None;
+    state.pop_view(ViewId::BugReport);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn shown() -> AppState {
+        reduce_bug_report(
+            AppState::default(),
+            &BugReportAction::Show {
+                default_path: "report.zip".to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_show_pushes_popup_once() {
+        let state = shown();
+        assert_eq!(state.active_view().view_id(), ViewId::BugReport);
+        let state = reduce_bug_report(
+            state,
+            &BugReportAction::Show {
+                default_path: "other.zip".to_string(),
+            },
+        );
+        assert_eq!(state.view_stack.len(), 2);
+        assert_eq!(state.bug_report.unwrap().path, "report.zip");
+    }
+
+    #[test]
+    fn test_failed_keeps_popup_open() {
+        let state = reduce_bug_report(shown(), &BugReportAction::Submit);
+        assert!(state.bug_report.as_ref().unwrap().writing);
+        let state = reduce_bug_report(state, &BugReportAction::InputChar('x'));
+        assert_eq!(state.bug_report.as_ref().unwrap().path, "report.zip");
+
+        let state = reduce_bug_report(state, &BugReportAction::Failed("denied".to_string()));
+        let popup = state.bug_report.as_ref().unwrap();
+        assert!(!popup.writing);
+        assert_eq!(popup.error.as_deref(), Some("denied"));
+    }
+
+    #[test]
+    fn test_written_closes_popup() {
+        let state = reduce_bug_report(
+            shown(),
+            &BugReportAction::Written(PathBuf::from("report.zip")),
+        );
+        assert!(state.bug_report.is_none());
+        assert_eq!(state.active_view().view_id(), ViewId::Splash);
+    }
+}
@@ -3,22 +3,71 @@
 //! Handles state updates for the build log panel.
 
 use crate::actions::BuildLogAction;
-use crate::state::{BuildLogLoadingState, BuildLogState};
+use crate::state::{BuildLogJobStatus, BuildLogLoadingState, BuildLogState, SUCCEEDED_GROUP};
+use std::sync::Arc;
 
 /// Reduce build log state based on BuildLogAction
 pub fn reduce_build_log(mut state: BuildLogState, action: &BuildLogAction) -> BuildLogState {
     match action {
         BuildLogAction::LoadStart => {
-            state.loading_state = BuildLogLoadingState::Loading;
+            state.loading_state = BuildLogLoadingState::Loading {
+                loaded: 0,
+                total: None,
+            };
+        }
+
+        BuildLogAction::RunsFound { context, total } => {
+            state.set_workflows(Vec::new(), &[]);
+            state.context = Some(context.clone());
+            state.loading_state = if *total == 0 {
+                BuildLogLoadingState::Loaded
+            } else {
+                BuildLogLoadingState::Loading {
+                    loaded: 0,
+                    total: Some(*total),
+                }
+            };
+            state.job_metadata.clear();
+            state.expanded_nodes.clear();
+            state.cursor_path = vec![0];
+            state.scroll_offset = 0;
+        }
+
+        BuildLogAction::WorkflowLoaded {
+            run,
+            jobs,
+            parsed_run,
+        } => {
+            for meta in jobs {
+                let key = format!("{}:{}", meta.workflow_name, meta.name);
+                state.job_metadata.insert(key, meta.clone());
+            }
+
+            // Failed workflows first, otherwise in the order they arrive
+            let index = if run.has_failures {
+                state.workflows.iter().filter(|w| w.has_failures).count()
+            } else {
+                state.workflows.len()
+            };
+            state.insert_workflow(index, Arc::clone(run), parsed_run.as_ref());
+            auto_expand_workflow_errors(&mut state, index);
+
+            if let BuildLogLoadingState::Loading { loaded, total } = &mut state.loading_state {
+                *loaded += 1;
+                if total.is_some_and(|total| *loaded >= total) {
+                    state.loading_state = BuildLogLoadingState::Loaded;
+                }
+            }
         }
 
         BuildLogAction::Loaded {
             workflows,
             job_metadata,
-            pr_context,
+            context,
+            parsed_runs,
         } => {
-            state.workflows = workflows.clone();
-            state.pr_context = pr_context.clone();
+            state.set_workflows(workflows.clone(), parsed_runs);
+            state.context = Some(context.clone());
             state.loading_state = BuildLogLoadingState::Loaded;
 
             // Build job metadata map
@@ -120,34 +169,68 @@ pub fn reduce_build_log(mut state: BuildLogState, action: &BuildLogAction) -> Bu
             state.show_timestamps = !state.show_timestamps;
         }
 
+        BuildLogAction::ToggleShowSuccessful => {
+            state.show_successful = Some(!state.shows_successful());
+            keep_cursor_on_visible_node(&mut state);
+        }
+
+        BuildLogAction::SetShowSuccessful(show) => {
+            state.show_successful = Some(*show);
+            keep_cursor_on_visible_node(&mut state);
+        }
+
         BuildLogAction::SetViewportHeight(height) => {
             state.viewport_height = *height;
+            // Keep the cursor on screen when the viewport shrinks
+            let visible = state.flatten_visible_nodes();
+            if let Some(idx) = visible.iter().position(|path| path == &state.cursor_path) {
+                ensure_cursor_visible(&mut state, idx);
+            }
         }
 
         // Open is handled by middleware, not reducer
-        BuildLogAction::Open => {}
+        BuildLogAction::Open | BuildLogAction::OpenRun { .. } | BuildLogAction::Export => {}
     }
 
     state
 }
 
-/// Auto-expand workflows and nodes with errors
+/// Move a cursor hidden by folding or listing jobs to its workflow
+fn keep_cursor_on_visible_node(state: &mut BuildLogState) {
+    let visible = state.flatten_visible_nodes();
+    if !visible.contains(&state.cursor_path) {
+        state.cursor_path = vec![state.cursor_path.first().copied().unwrap_or(0)];
+    }
+    if let Some(idx) = visible.iter().position(|path| path == &state.cursor_path) {
+        ensure_cursor_visible(state, idx);
+    }
+}
+
+/// Auto-expand workflows, failed and running jobs, and nodes with errors
 fn auto_expand_errors(state: &mut BuildLogState) {
-    for (w_idx, workflow) in state.workflows.iter().enumerate() {
-        // Always expand workflows (top level)
-        state.expanded_nodes.insert(w_idx.to_string());
+    for w_idx in 0..state.workflows.len() {
+        auto_expand_workflow_errors(state, w_idx);
+    }
+}
 
-        // Auto-expand jobs and steps with errors
-        for (j_idx, job) in workflow.jobs.iter().enumerate() {
-            if job.error_count > 0 {
-                state.expanded_nodes.insert(format!("{}:{}", w_idx, j_idx));
+/// Auto-expand the workflow at `w_idx` down to its errors
+fn auto_expand_workflow_errors(state: &mut BuildLogState, w_idx: usize) {
+    let workflow = Arc::clone(&state.workflows[w_idx]);
 
-                for (s_idx, step) in job.steps.iter().enumerate() {
-                    if step.error_count > 0 {
-                        state
-                            .expanded_nodes
-                            .insert(format!("{}:{}:{}", w_idx, j_idx, s_idx));
-                    }
+    // Always expand workflows (top level)
+    state.expanded_nodes.insert(w_idx.to_string());
+
+    // Auto-expand failed and running jobs, and steps with errors
+    for (j_idx, job) in workflow.jobs.iter().enumerate() {
+        let status = state.job_status(&workflow, job);
+        if job.error_count > 0 || status.is_failed() || status == BuildLogJobStatus::InProgress {
+            state.expanded_nodes.insert(format!("{}:{}", w_idx, j_idx));
+
+            for (s_idx, step) in job.steps.iter().enumerate() {
+                if step.error_count > 0 {
+                    state
+                        .expanded_nodes
+                        .insert(format!("{}:{}:{}", w_idx, j_idx, s_idx));
                 }
             }
         }
@@ -158,6 +241,9 @@ fn auto_expand_errors(state: &mut BuildLogState) {
 fn expand_all(state: &mut BuildLogState) {
     for (w_idx, workflow) in state.workflows.iter().enumerate() {
         state.expanded_nodes.insert(w_idx.to_string());
+        state
+            .expanded_nodes
+            .insert(format!("{}:{}", w_idx, SUCCEEDED_GROUP));
 
         for (j_idx, job) in workflow.jobs.iter().enumerate() {
             state.expanded_nodes.insert(format!("{}:{}", w_idx, j_idx));
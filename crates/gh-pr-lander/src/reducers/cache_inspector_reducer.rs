@@ -0,0 +1,39 @@
+//! Cache Inspector Reducer
+//!
+//! Keeps the listed cache entries, the cursor, filter and sort of the cache
+//! inspector popup.
+
+use crate::actions::CacheInspectorAction;
+use crate::state::{CacheInput, CacheInspectorState};
+
+/// Reduce cache inspector popup state
+pub fn reduce_cache_inspector(
+    mut state: CacheInspectorState,
+    action: &CacheInspectorAction,
+) -> CacheInspectorState {
+    match action {
+        CacheInspectorAction::Open => {
+            state.selected = 0;
+            state.input = None;
+        }
+        CacheInspectorAction::Loaded(entries) => state.set_entries(entries.clone()),
+        CacheInspectorAction::NavigateNext => state.select_next(),
+        CacheInspectorAction::NavigatePrevious => state.select_previous(),
+        CacheInspectorAction::NavigateToTop => state.selected = 0,
+        CacheInspectorAction::NavigateToBottom => state.select_last(),
+        CacheInspectorAction::CycleSort => state.cycle_sort(),
+        CacheInspectorAction::EditFilter => state.input = Some(CacheInput::Filter),
+        CacheInspectorAction::EditPattern => {
+            state.input = Some(CacheInput::Pattern(String::new()));
+        }
+        CacheInspectorAction::InputChar(c) => state.push_input(*c),
+        CacheInspectorAction::InputBackspace => state.pop_input(),
+        CacheInspectorAction::InputClear => state.clear_input(),
+        // The middleware invalidated the pattern already
+        CacheInspectorAction::InputConfirm => state.input = None,
+        CacheInspectorAction::InputCancel => state.cancel_input(),
+        // Handled by middleware (cache access)
+        CacheInspectorAction::Refresh | CacheInspectorAction::DeleteSelected => {}
+    }
+    state
+}
@@ -5,9 +5,14 @@
 //! if the command palette is active - that's the middleware's job.
 
 use crate::actions::CommandPaletteAction;
-use crate::commands::{filter_commands, get_palette_commands_with_hints};
+use crate::commands::{
+    filter_commands, get_palette_commands_with_hints, get_profile_commands,
+    get_saved_filter_commands,
+};
 use crate::keybindings::Keymap;
-use crate::state::CommandPaletteState;
+use crate::state::{ActiveProfile, CommandPaletteState};
+use gh_pr_config::FilterSpec;
+use std::collections::BTreeMap;
 
 /// Reducer for command palette state.
 ///
@@ -17,8 +22,15 @@ pub fn reduce_command_palette(
     mut state: CommandPaletteState,
     action: &CommandPaletteAction,
     keymap: &Keymap,
+    saved_filters: &BTreeMap<String, FilterSpec>,
+    profile: Option<&ActiveProfile>,
 ) -> CommandPaletteState {
     match action {
+        CommandPaletteAction::Open(query) => {
+            state.query = query.clone();
+            state.selected_index = 0;
+        }
+
         CommandPaletteAction::Char(c) => {
             state.query.push(*c);
             state.selected_index = 0;
@@ -47,7 +59,9 @@ pub fn reduce_command_palette(
         }
 
         CommandPaletteAction::NavigateNext => {
-            let all_commands = get_palette_commands_with_hints(keymap);
+            let mut all_commands = get_palette_commands_with_hints(keymap);
+            all_commands.extend(get_saved_filter_commands(saved_filters));
+            all_commands.extend(get_profile_commands(profile));
             let filtered = filter_commands(&all_commands, &state.query);
             if !filtered.is_empty() {
                 state.selected_index = (state.selected_index + 1).min(filtered.len() - 1);
@@ -5,7 +5,7 @@
 
 use crate::actions::ConfirmationPopupAction;
 use crate::state::{AppState, ConfirmationPopupState};
-use crate::views::ConfirmationPopupView;
+use crate::views::{ConfirmationPopupView, ViewId};
 
 /// Reduce confirmation popup state based on actions.
 ///
@@ -19,13 +19,17 @@ pub fn reduce_confirmation_popup(
             intent,
             default_message,
             repo_context,
+            warnings,
         } => {
             // Create popup state and push view
-            state.confirmation_popup = Some(ConfirmationPopupState::new(
-                intent.clone(),
-                default_message.clone(),
-                repo_context.clone(),
-            ));
+            state.confirmation_popup = Some(
+                ConfirmationPopupState::new(
+                    intent.clone(),
+                    default_message.clone(),
+                    repo_context.clone(),
+                )
+                .with_warnings(warnings.clone()),
+            );
             state
                 .view_stack
                 .push(Box::new(ConfirmationPopupView::new()));
@@ -35,9 +39,7 @@ pub fn reduce_confirmation_popup(
         ConfirmationPopupAction::Cancel => {
             // Clear state and pop view
             state.confirmation_popup = None;
-            if state.view_stack.len() > 1 {
-                state.view_stack.pop();
-            }
+            state.pop_view(ViewId::ConfirmationPopup);
             log::debug!("Cancelled confirmation popup");
         }
 
@@ -48,15 +50,18 @@ pub fn reduce_confirmation_popup(
         ConfirmationPopupAction::Confirmed => {
             // Clear state and pop view after middleware dispatched PR action
             state.confirmation_popup = None;
-            if state.view_stack.len() > 1 {
-                state.view_stack.pop();
-            }
+            state.pop_view(ViewId::ConfirmationPopup);
             log::debug!("Confirmation popup closed after confirm");
         }
 
         ConfirmationPopupAction::Char(c) => {
             if let Some(ref mut popup) = state.confirmation_popup {
-                popup.input_value.push(*c);
+                if popup.intent.takes_message() {
+                    popup.input_value.push(*c);
+                } else if *c == ' ' {
+                    // Without a message, Space toggles the option (if any)
+                    popup.intent.toggle_delete_branch();
+                }
             }
         }
 
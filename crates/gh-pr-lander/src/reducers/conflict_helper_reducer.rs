@@ -0,0 +1,89 @@
+//! Conflict Helper Reducer
+//!
+//! Tracks the PR of the conflict helper popup and the comparison of its
+//! branches. Results of a PR no longer shown are dropped.
+
+use crate::actions::ConflictHelperAction;
+use crate::domain_models::WatchedPr;
+use crate::state::ConflictHelperState;
+
+/// Reduce conflict helper popup state
+pub fn reduce_conflict_helper(
+    mut state: ConflictHelperState,
+    action: &ConflictHelperAction,
+) -> ConflictHelperState {
+    match action {
+        ConflictHelperAction::Open { repo, pr } => {
+            state = ConflictHelperState {
+                target: Some((repo.clone(), (**pr).clone())),
+                summary: None,
+                error: None,
+            };
+        }
+        ConflictHelperAction::Loaded { pr, summary } => {
+            if is_target(&state, pr) {
+                state.summary = Some((**summary).clone());
+            }
+        }
+        ConflictHelperAction::LoadError { pr, error } => {
+            if is_target(&state, pr) {
+                state.error = Some(error.clone());
+            }
+        }
+        ConflictHelperAction::CopyCommands
+        | ConflictHelperAction::OpenConflictsPage
+        | ConflictHelperAction::RecreateDependabot => {}
+    }
+    state
+}
+
+/// Whether the popup shows the given PR
+fn is_target(state: &ConflictHelperState, pr: &WatchedPr) -> bool {
+    state
+        .target
+        .as_ref()
+        .is_some_and(|(repo, target)| repo.watched_pr(target.number) == *pr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::{ConflictSummary, Pr, Repository};
+
+    #[test]
+    fn test_results_of_other_prs_are_dropped() {
+        let repo = Repository::new("acme", "api", "main");
+        let open = ConflictHelperAction::Open {
+            repo: repo.clone(),
+            pr: Box::new(Pr::new(7, "Fix", "alice", "abc")),
+        };
+        let state = reduce_conflict_helper(ConflictHelperState::default(), &open);
+        assert!(state.target.is_some());
+
+        let state = reduce_conflict_helper(
+            state,
+            &ConflictHelperAction::Loaded {
+                pr: repo.watched_pr(8),
+                summary: Box::default(),
+            },
+        );
+        assert!(state.summary.is_none());
+
+        let summary = ConflictSummary {
+            behind_by: 3,
+            ..Default::default()
+        };
+        let state = reduce_conflict_helper(
+            state,
+            &ConflictHelperAction::Loaded {
+                pr: repo.watched_pr(7),
+                summary: Box::new(summary.clone()),
+            },
+        );
+        assert_eq!(state.summary, Some(summary));
+
+        // Reopening starts over
+        let state = reduce_conflict_helper(state, &open);
+        assert!(state.summary.is_none() && state.error.is_none());
+    }
+}
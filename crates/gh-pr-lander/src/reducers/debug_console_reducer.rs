@@ -51,6 +51,10 @@ pub fn reduce_debug_console(
             let new_max = state.lines.len().saturating_sub(state.visible_height);
             state.scroll_offset = state.scroll_offset.min(new_max);
         }
+        // Handled by the middleware
+        DebugConsoleAction::ShowApiUsage
+        | DebugConsoleAction::EditLogLevel
+        | DebugConsoleAction::SetLogLevel(_) => {}
     }
     state
 }
@@ -0,0 +1,39 @@
+//! Dependency Updates Reducer
+//!
+//! Handles the cursor of the dependency updates view.
+
+use crate::actions::DependencyUpdatesAction;
+use crate::state::DependencyUpdatesState;
+
+/// Reduce dependency updates view state
+///
+/// `entry_count` is the number of PRs listed in all groups.
+pub fn reduce_dependency_updates(
+    mut state: DependencyUpdatesState,
+    action: &DependencyUpdatesAction,
+    entry_count: usize,
+) -> DependencyUpdatesState {
+    match action {
+        DependencyUpdatesAction::Open | DependencyUpdatesAction::NavigateToTop => {
+            state.selected = 0;
+        }
+
+        DependencyUpdatesAction::NavigateNext => {
+            if state.selected + 1 < entry_count {
+                state.selected += 1;
+            }
+        }
+
+        DependencyUpdatesAction::NavigatePrevious => {
+            state.selected = state.selected.saturating_sub(1);
+        }
+
+        DependencyUpdatesAction::NavigateToBottom => {
+            state.selected = entry_count.saturating_sub(1);
+        }
+
+        // Handled by middleware (browser)
+        DependencyUpdatesAction::OpenSelected => {}
+    }
+    state
+}
@@ -30,33 +30,52 @@ pub fn reduce_diff_viewer(
 
         DiffViewerAction::Loaded {
             diff,
+            repo,
             pr_number,
             pr_title,
             head_sha,
             comments,
         } => {
-            state.load(diff.clone(), *pr_number, pr_title.clone(), head_sha.clone());
+            state.load(
+                diff.clone(),
+                repo.clone(),
+                *pr_number,
+                pr_title.clone(),
+                head_sha.clone(),
+            );
 
             // Add existing comments from GitHub as pending comments
             if let Some(ref mut inner) = state.inner {
                 for comment in comments {
-                    // Only add comments with valid line numbers
-                    if let Some(line) = comment.line {
-                        let side = match comment.side.as_deref() {
-                            Some("LEFT") => DiffSide::Left,
-                            _ => DiffSide::Right, // Default to RIGHT
-                        };
-                        let mut pending = PendingComment::new(
-                            &comment.path,
-                            CommentPosition::single(side, line),
-                            &comment.body,
-                        );
-                        pending.github_id = Some(comment.github_id);
-                        inner.pending_comments.push(pending);
+                    // A restored review state already has the comments seen before
+                    if inner
+                        .pending_comments
+                        .iter()
+                        .any(|c| c.github_id == Some(comment.github_id))
+                    {
+                        continue;
                     }
+                    // Only add file comments and comments with valid line numbers
+                    let position = if comment.is_file_level {
+                        CommentPosition::File(comment.path.clone())
+                    } else if let Some(line) = comment.line {
+                        let side = diff_side(comment.side.as_deref());
+                        match comment.start_line {
+                            Some(start_line) => CommentPosition::span(
+                                (diff_side(comment.start_side.as_deref()), start_line),
+                                (side, line),
+                            ),
+                            None => CommentPosition::single(side, line),
+                        }
+                    } else {
+                        continue;
+                    };
+                    let mut pending = PendingComment::new(&comment.path, position, &comment.body);
+                    pending.github_id = Some(comment.github_id);
+                    inner.pending_comments.push(pending);
                 }
                 log::debug!(
-                    "Loaded {} existing comments from GitHub",
+                    "Loaded {} comments, including existing ones from GitHub",
                     inner.pending_comments.len()
                 );
             }
@@ -81,14 +100,24 @@ pub fn reduce_diff_viewer(
         }
 
         DiffViewerAction::NavigateLeft => {
-            // In diff viewer context, left goes to file tree
-            forward_action(&mut state, DiffAction::FocusFileTree);
+            // In the file tree, left collapses; in the diff, it goes to the file tree
+            let action = tree_or(
+                &state,
+                DiffAction::CollapseTreeNode,
+                DiffAction::FocusFileTree,
+            );
+            forward_action(&mut state, action);
             state
         }
 
         DiffViewerAction::NavigateRight => {
-            // In diff viewer context, right goes to diff content
-            forward_action(&mut state, DiffAction::FocusDiffContent);
+            // In the file tree, right expands (or opens a file)
+            let action = tree_or(
+                &state,
+                DiffAction::ExpandTreeNode,
+                DiffAction::FocusDiffContent,
+            );
+            forward_action(&mut state, action);
             state
         }
 
@@ -191,8 +220,22 @@ pub fn reduce_diff_viewer(
                     match c {
                         'j' => forward_action(&mut state, DiffAction::CursorDown),
                         'k' => forward_action(&mut state, DiffAction::CursorUp),
-                        'h' => forward_action(&mut state, DiffAction::FocusFileTree),
-                        'l' => forward_action(&mut state, DiffAction::FocusDiffContent),
+                        'h' => {
+                            let action = tree_or(
+                                &state,
+                                DiffAction::CollapseTreeNode,
+                                DiffAction::FocusFileTree,
+                            );
+                            forward_action(&mut state, action)
+                        }
+                        'l' => {
+                            let action = tree_or(
+                                &state,
+                                DiffAction::ExpandTreeNode,
+                                DiffAction::FocusDiffContent,
+                            );
+                            forward_action(&mut state, action)
+                        }
                         'g' => forward_action(&mut state, DiffAction::CursorFirst),
                         'G' => forward_action(&mut state, DiffAction::CursorLast),
                         'n' => forward_action(&mut state, DiffAction::NextHunk),
@@ -201,6 +244,7 @@ pub fn reduce_diff_viewer(
                         'c' => forward_action(&mut state, DiffAction::StartComment),
                         'R' => forward_action(&mut state, DiffAction::ShowReviewPopup),
                         'v' => forward_action(&mut state, DiffAction::EnterVisualMode),
+                        'b' => forward_action(&mut state, DiffAction::ToggleBlame),
                         _ => {} // Ignore unknown keys
                     }
                 }
@@ -208,6 +252,17 @@ pub fn reduce_diff_viewer(
             state
         }
 
+        DiffViewerAction::Paste(text) => {
+            if state
+                .inner
+                .as_ref()
+                .is_some_and(|inner| inner.is_editing_comment())
+            {
+                forward_action(&mut state, DiffAction::CommentInsertText(text.clone()));
+            }
+            state
+        }
+
         DiffViewerAction::Backspace => {
             if let Some(ref inner) = state.inner {
                 if inner.is_editing_comment() {
@@ -240,6 +295,16 @@ pub fn reduce_diff_viewer(
             state
         }
 
+        DiffViewerAction::CommentOnFile => {
+            forward_action(&mut state, DiffAction::CommentOnFile);
+            state
+        }
+
+        DiffViewerAction::CommentOnPr => {
+            forward_action(&mut state, DiffAction::CommentOnPr);
+            state
+        }
+
         DiffViewerAction::CancelComment => {
             forward_action(&mut state, DiffAction::CancelComment);
             state
@@ -298,24 +363,23 @@ pub fn reduce_diff_viewer(
 
         DiffViewerAction::CommentPosted {
             path,
-            line,
-            side,
+            position,
             github_id,
         } => {
             // Update the pending comment with its GitHub ID
             if let Some(ref mut inner) = state.inner {
-                // Find the comment by path/line/side and update github_id
+                // Find the comment by path/position (still without ID) and update github_id
                 for comment in &mut inner.pending_comments {
                     if comment.path == *path
-                        && comment.position.line == *line
-                        && comment.position.side.as_github_str() == side
+                        && comment.position == *position
+                        && comment.github_id.is_none()
                     {
                         comment.github_id = Some(*github_id);
                         log::debug!(
-                            "Updated pending comment with github_id {} at {}:{}",
+                            "Updated pending comment with github_id {} at {} ({})",
                             github_id,
                             path,
-                            line
+                            position.describe()
                         );
                         break;
                     }
@@ -329,19 +393,60 @@ pub fn reduce_diff_viewer(
             state
         }
 
-        DiffViewerAction::CommentDeleted { path, line, side } => {
+        DiffViewerAction::CommentDeleted { path, position } => {
             // Remove the pending comment from local state
             if let Some(ref mut inner) = state.inner {
-                // Find and remove the comment by path/line/side
-                let idx = inner.pending_comments.iter().position(|c| {
-                    c.path == *path
-                        && c.position.line == *line
-                        && c.position.side.as_github_str() == side
-                });
+                // Find and remove the comment by path/position
+                let idx = inner
+                    .pending_comments
+                    .iter()
+                    .position(|c| c.path == *path && c.position == *position);
                 if let Some(idx) = idx {
                     inner.pending_comments.remove(idx);
-                    log::debug!("Removed pending comment at {}:{}", path, line);
+                    log::debug!(
+                        "Removed pending comment at {} ({})",
+                        path,
+                        position.describe()
+                    );
+                }
+            }
+            state
+        }
+
+        // === Blame ===
+        DiffViewerAction::ToggleBlame => {
+            forward_action(&mut state, DiffAction::ToggleBlame);
+            state
+        }
+
+        DiffViewerAction::FetchBlame => {
+            // The GitHub middleware fetches the request, mark it as taken
+            if let Some(ref mut inner) = state.inner {
+                inner.request_blame();
+            }
+            state
+        }
+
+        DiffViewerAction::BlameLoaded {
+            path,
+            base_sha,
+            entries,
+        } => {
+            if let Some(ref mut inner) = state.inner {
+                if inner.diff.base_sha.is_empty() {
+                    inner.diff.base_sha = base_sha.clone();
                 }
+                inner.insert_blame(path, entries.clone());
+            }
+            state
+        }
+
+        DiffViewerAction::BlameFailed { path, .. } => {
+            // Hide blame rather than asking again on every key, showing it
+            // again retries
+            if let Some(ref mut inner) = state.inner {
+                inner.blame_failed(path);
+                inner.show_blame = false;
             }
             state
         }
@@ -366,6 +471,14 @@ pub fn reduce_diff_viewer(
     }
 }
 
+/// Pick `tree_action` while the file tree has focus, `other` otherwise.
+fn tree_or(state: &DiffViewerState, tree_action: DiffAction, other: DiffAction) -> DiffAction {
+    match state.inner {
+        Some(ref inner) if inner.nav.file_tree_focused => tree_action,
+        _ => other,
+    }
+}
+
 /// Forward a DiffAction to the inner state if it exists.
 fn forward_action(state: &mut DiffViewerState, action: DiffAction) {
     if let Some(ref mut inner) = state.inner {
@@ -374,3 +487,11 @@ fn forward_action(state: &mut DiffViewerState, action: DiffAction) {
         let _events = inner.handle_action(action);
     }
 }
+
+/// Diff side of a GitHub comment ("LEFT" or "RIGHT", RIGHT when missing).
+fn diff_side(side: Option<&str>) -> DiffSide {
+    match side {
+        Some("LEFT") => DiffSide::Left,
+        _ => DiffSide::Right,
+    }
+}
@@ -0,0 +1,118 @@
+//! Duplicate Updates Reducer
+//!
+//! Handles the popup listing the PRs opening the same bot update in several
+//! repositories, including view stack management.
+
+use crate::actions::DuplicateUpdatesAction;
+use crate::state::{AppState, DuplicateUpdatesState};
+use crate::views::{DuplicateUpdatesView, ViewId};
+
+/// Reduce duplicate updates popup state based on actions.
+pub fn reduce_duplicate_updates(mut state: AppState, action: &DuplicateUpdatesAction) -> AppState {
+    match action {
+        DuplicateUpdatesAction::Open => {
+            let main_view = &state.main_view;
+            let update = main_view
+                .repo_data
+                .get(&main_view.selected_repository)
+                .and_then(|data| data.prs.get(data.selected_pr))
+                .and_then(|pr| pr.dependency_update.as_ref());
+            // The middleware rejects PRs not opened in other repositories
+            if let Some(update) = update {
+                let key = update.update_key();
+                let siblings = main_view.bot_updates.siblings(&key).to_vec();
+                let summary = format!("{} → {}", update.name, update.to_version);
+                state.duplicate_updates = Some(DuplicateUpdatesState::new(summary, siblings));
+                if state.active_view().view_id() != ViewId::DuplicateUpdates {
+                    state.view_stack.push(Box::new(DuplicateUpdatesView::new()));
+                }
+            }
+        }
+
+        DuplicateUpdatesAction::NavigateNext => {
+            if let Some(ref mut popup) = state.duplicate_updates {
+                popup.select_next();
+            }
+        }
+
+        DuplicateUpdatesAction::NavigatePrevious => {
+            if let Some(ref mut popup) = state.duplicate_updates {
+                popup.select_previous();
+            }
+        }
+
+        DuplicateUpdatesAction::Toggle => {
+            if let Some(ref mut popup) = state.duplicate_updates {
+                popup.toggle();
+            }
+        }
+
+        DuplicateUpdatesAction::PickAll => {
+            if let Some(ref mut popup) = state.duplicate_updates {
+                popup.pick_all();
+            }
+        }
+
+        DuplicateUpdatesAction::PickNone => {
+            if let Some(ref mut popup) = state.duplicate_updates {
+                popup.pick_none();
+            }
+        }
+
+        // The middleware dispatches the merges before it gets here
+        DuplicateUpdatesAction::Merge | DuplicateUpdatesAction::Close => {
+            state.duplicate_updates = None;
+            state.pop_view(ViewId::DuplicateUpdates);
+        }
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::{Pr, Repository};
+    use crate::state::RepositoryData;
+
+    fn bump(number: usize) -> Pr {
+        Pr::new(
+            number,
+            "Bump serde from 1.0.1 to 1.0.2",
+            "dependabot[bot]",
+            "sha",
+        )
+        .with_branches("dependabot/cargo/serde-1.0.2", "main")
+        .with_detected_dependency_update()
+    }
+
+    #[test]
+    fn test_open_lists_siblings_and_merge_closes() {
+        let mut state = AppState::default();
+        let api = Repository::new("acme", "api", "main");
+        let web = Repository::new("acme", "web", "main");
+        state.main_view.repositories = vec![api.clone(), web.clone()];
+        for (idx, (repo, number)) in [(&api, 1), (&web, 7)].into_iter().enumerate() {
+            let prs = vec![bump(number)];
+            state.main_view.bot_updates.update_repo(repo, &prs);
+            state.main_view.repo_data.insert(
+                idx,
+                RepositoryData {
+                    prs,
+                    ..Default::default()
+                },
+            );
+        }
+
+        let state = reduce_duplicate_updates(state, &DuplicateUpdatesAction::Open);
+        assert_eq!(state.active_view().view_id(), ViewId::DuplicateUpdates);
+        let popup = state.duplicate_updates.as_ref().unwrap();
+        assert_eq!(popup.summary, "serde → 1.0.2");
+        assert_eq!(popup.siblings, vec![(api, 1), (web, 7)]);
+        assert_eq!(popup.picked.len(), 2);
+
+        let state = reduce_duplicate_updates(state, &DuplicateUpdatesAction::Merge);
+        assert!(state.duplicate_updates.is_none());
+        assert_ne!(state.active_view().view_id(), ViewId::DuplicateUpdates);
+    }
+}
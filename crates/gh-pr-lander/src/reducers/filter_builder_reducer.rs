@@ -0,0 +1,145 @@
+//! Filter Builder Reducer
+//!
+//! Handles the filter builder popup, including view stack management.
+
+use crate::actions::FilterBuilderAction;
+use crate::state::{AppState, FilterBuilderState};
+use crate::views::{FilterBuilderView, ViewId};
+
+/// Reduce filter builder popup state based on actions.
+pub fn reduce_filter_builder(mut state: AppState, action: &FilterBuilderAction) -> AppState {
+    match action {
+        FilterBuilderAction::Open => {
+            let repo_idx = state.main_view.selected_repository;
+            if state.filter_builder.is_none() {
+                if let Some(repo_data) = state.main_view.repo_data.get(&repo_idx) {
+                    state.filter_builder =
+                        Some(FilterBuilderState::from_filter(&repo_data.current_filter));
+                    state.view_stack.push(Box::new(FilterBuilderView::new()));
+                }
+            }
+        }
+
+        FilterBuilderAction::NavigateNext => {
+            if let Some(ref mut builder) = state.filter_builder {
+                builder.select_next();
+            }
+        }
+
+        FilterBuilderAction::NavigatePrevious => {
+            if let Some(ref mut builder) = state.filter_builder {
+                builder.select_previous();
+            }
+        }
+
+        FilterBuilderAction::CycleNext => {
+            if let Some(ref mut builder) = state.filter_builder {
+                builder.cycle(true);
+            }
+        }
+
+        FilterBuilderAction::CyclePrevious => {
+            if let Some(ref mut builder) = state.filter_builder {
+                builder.cycle(false);
+            }
+        }
+
+        FilterBuilderAction::Toggle => {
+            if let Some(ref mut builder) = state.filter_builder {
+                if builder.selected_row().choices().is_some() {
+                    builder.cycle(true);
+                } else {
+                    builder.push_char(' ');
+                }
+            }
+        }
+
+        FilterBuilderAction::InputChar(c) => {
+            if let Some(ref mut builder) = state.filter_builder {
+                builder.push_char(*c);
+            }
+        }
+
+        FilterBuilderAction::InputBackspace => {
+            if let Some(ref mut builder) = state.filter_builder {
+                builder.pop_char();
+            }
+        }
+
+        FilterBuilderAction::InputClear => {
+            if let Some(ref mut builder) = state.filter_builder {
+                builder.clear_row();
+            }
+        }
+
+        // The middleware applies (and saves) the filter before it gets here
+        FilterBuilderAction::Apply | FilterBuilderAction::Close => {
+            state.filter_builder = None;
+            state.pop_view(ViewId::FilterBuilder);
+        }
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::Repository;
+    use crate::state::{FilterRow, PrFilter, RepositoryData};
+    use gh_pr_config::FilterCi;
+
+    fn opened(filter: PrFilter) -> AppState {
+        let mut state = AppState::default();
+        state.main_view.repositories = vec![Repository::new("acme", "api", "main")];
+        state.main_view.repo_data.insert(
+            0,
+            RepositoryData {
+                current_filter: filter,
+                ..Default::default()
+            },
+        );
+        reduce_filter_builder(state, &FilterBuilderAction::Open)
+    }
+
+    #[test]
+    fn test_open_prefills_current_filter() {
+        let state = opened(PrFilter::parse("ci:failed label:bug").unwrap());
+        assert_eq!(state.active_view().view_id(), ViewId::FilterBuilder);
+        let spec = state.filter_builder.as_ref().unwrap().spec();
+        assert_eq!(spec.ci, Some(FilterCi::Failed));
+        assert_eq!(spec.labels_include, vec!["bug"]);
+    }
+
+    #[test]
+    fn test_space_cycles_choices_and_types_into_text_rows() {
+        let mut state = opened(PrFilter::All);
+        while state.filter_builder.as_ref().unwrap().selected_row() != FilterRow::Ci {
+            state = reduce_filter_builder(state, &FilterBuilderAction::NavigateNext);
+        }
+        state = reduce_filter_builder(state, &FilterBuilderAction::Toggle);
+        state = reduce_filter_builder(state, &FilterBuilderAction::Toggle);
+        assert_eq!(
+            state.filter_builder.as_ref().unwrap().spec().ci,
+            Some(FilterCi::Pending)
+        );
+
+        // Labels may contain spaces
+        state = reduce_filter_builder(state, &FilterBuilderAction::NavigatePrevious);
+        for action in [
+            FilterBuilderAction::InputChar('n'),
+            FilterBuilderAction::Toggle,
+            FilterBuilderAction::InputChar('m'),
+        ] {
+            state = reduce_filter_builder(state, &action);
+        }
+        assert_eq!(
+            state.filter_builder.as_ref().unwrap().spec().labels_exclude,
+            vec!["n m"]
+        );
+
+        let state = reduce_filter_builder(state, &FilterBuilderAction::Close);
+        assert!(state.filter_builder.is_none());
+        assert_ne!(state.active_view().view_id(), ViewId::FilterBuilder);
+    }
+}
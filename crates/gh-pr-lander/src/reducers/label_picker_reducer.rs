@@ -0,0 +1,152 @@
+//! Label Picker Reducer
+//!
+//! Handles the label picker popup, including view stack management.
+
+use crate::actions::LabelPickerAction;
+use crate::state::{AppState, LabelPickerState};
+use crate::views::{LabelPickerView, ViewId};
+
+/// Reduce label picker popup state based on actions.
+pub fn reduce_label_picker(mut state: AppState, action: &LabelPickerAction) -> AppState {
+    match action {
+        LabelPickerAction::Show { repo, prs } => {
+            if state.label_picker.is_none() {
+                state.label_picker = Some(LabelPickerState::new(repo.clone(), prs.clone()));
+                state.view_stack.push(Box::new(LabelPickerView::new()));
+            }
+        }
+
+        LabelPickerAction::Loaded(labels) => {
+            if let Some(ref mut picker) = state.label_picker {
+                picker.set_labels(labels.clone());
+            }
+        }
+
+        LabelPickerAction::LoadFailed(error) => {
+            if let Some(ref mut picker) = state.label_picker {
+                picker.loading = false;
+                picker.error = Some(error.clone());
+            }
+        }
+
+        LabelPickerAction::InputChar(c) => {
+            if let Some(ref mut picker) = state.label_picker {
+                picker.push_query(*c);
+            }
+        }
+
+        LabelPickerAction::InputBackspace => {
+            if let Some(ref mut picker) = state.label_picker {
+                picker.pop_query();
+            }
+        }
+
+        LabelPickerAction::InputClear => {
+            if let Some(ref mut picker) = state.label_picker {
+                picker.clear_query();
+            }
+        }
+
+        LabelPickerAction::NavigateNext => {
+            if let Some(ref mut picker) = state.label_picker {
+                picker.select_next();
+            }
+        }
+
+        LabelPickerAction::NavigatePrevious => {
+            if let Some(ref mut picker) = state.label_picker {
+                picker.select_previous();
+            }
+        }
+
+        LabelPickerAction::Toggle => {
+            if let Some(ref mut picker) = state.label_picker {
+                if !picker.applying {
+                    picker.toggle_selected();
+                }
+            }
+        }
+
+        LabelPickerAction::Apply => {
+            if let Some(ref mut picker) = state.label_picker {
+                picker.applying = true;
+            }
+        }
+
+        LabelPickerAction::Applied | LabelPickerAction::Close => {
+            close_label_picker(&mut state);
+        }
+
+        LabelPickerAction::Open => {}
+    }
+
+    state
+}
+
+/// Clear the label picker state and pop its view
+fn close_label_picker(state: &mut AppState) {
+    state.label_picker = None;
+    state.pop_view(ViewId::LabelPicker);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::Repository;
+    use gh_client::Label;
+
+    fn shown() -> AppState {
+        reduce_label_picker(
+            AppState::default(),
+            &LabelPickerAction::Show {
+                repo: Repository::new("acme", "api", "main"),
+                prs: vec![(7, vec!["bug".to_string()])],
+            },
+        )
+    }
+
+    fn label(name: &str) -> Label {
+        Label {
+            name: name.to_string(),
+            color: "ededed".to_string(),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_show_pushes_popup_while_loading() {
+        let state = shown();
+        assert_eq!(state.active_view().view_id(), ViewId::LabelPicker);
+        assert!(state.label_picker.as_ref().unwrap().loading);
+
+        let state = reduce_label_picker(
+            state,
+            &LabelPickerAction::Loaded(vec![label("bug"), label("wip")]),
+        );
+        let picker = state.label_picker.as_ref().unwrap();
+        assert!(!picker.loading);
+        assert_eq!(picker.choices.len(), 2);
+    }
+
+    #[test]
+    fn test_toggle_and_filter() {
+        let state = reduce_label_picker(
+            shown(),
+            &LabelPickerAction::Loaded(vec![label("bug"), label("wip")]),
+        );
+        let state = reduce_label_picker(state, &LabelPickerAction::InputChar('w'));
+        let state = reduce_label_picker(state, &LabelPickerAction::Toggle);
+        let changes = state.label_picker.as_ref().unwrap().changes();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].1.add, vec!["wip".to_string()]);
+    }
+
+    #[test]
+    fn test_applied_closes_popup() {
+        let state = reduce_label_picker(shown(), &LabelPickerAction::Apply);
+        assert!(state.label_picker.as_ref().unwrap().applying);
+        let state = reduce_label_picker(state, &LabelPickerAction::Applied);
+        assert!(state.label_picker.is_none());
+        assert_eq!(state.active_view().view_id(), ViewId::Splash);
+    }
+}
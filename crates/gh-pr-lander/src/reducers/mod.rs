@@ -1,12 +1,31 @@
+pub mod actions_view_reducer;
 pub mod app_reducer;
+pub mod audit_log_reducer;
+pub mod auth_reducer;
+pub mod branch_picker_reducer;
+pub mod bug_report_reducer;
 pub mod build_log_reducer;
+pub mod cache_inspector_reducer;
 pub mod command_palette_reducer;
 pub mod confirmation_popup_reducer;
+pub mod conflict_helper_reducer;
 pub mod debug_console_reducer;
+pub mod dependency_updates_reducer;
 pub mod diff_viewer_reducer;
+pub mod duplicate_updates_reducer;
+pub mod filter_builder_reducer;
 pub mod key_bindings_reducer;
+pub mod label_picker_reducer;
+pub mod onboarding_reducer;
+pub mod perf_stats_reducer;
+pub mod pr_search_reducer;
 pub mod pull_request_reducer;
+pub mod release_notes_reducer;
 pub mod repository_reducer;
+pub mod reviewer_picker_reducer;
 pub mod session_reducer;
 pub mod splash_reducer;
 pub mod status_bar_reducer;
+#[cfg(test)]
+pub mod test_harness;
+pub mod watch_list_reducer;
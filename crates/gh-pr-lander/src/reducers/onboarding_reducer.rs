@@ -0,0 +1,63 @@
+//! Onboarding Reducer
+//!
+//! Handles the onboarding wizard state machine, including view stack management.
+
+use crate::actions::OnboardingAction;
+use crate::state::{AppState, BootstrapStage, OnboardingState, OnboardingStep, StageStatus};
+use crate::views::{AddRepositoryView, OnboardingView, ViewId};
+
+/// Reduce onboarding state based on actions.
+///
+/// Handles all Onboarding actions including view stack management.
+pub fn reduce_onboarding(mut state: AppState, action: &OnboardingAction) -> AppState {
+    match action {
+        OnboardingAction::Show => {
+            let token_available = state.splash.stages.iter().any(|(stage, status)| {
+                *stage == BootstrapStage::ResolveToken && *status == StageStatus::Done
+            });
+            state.onboarding = Some(OnboardingState::new(token_available));
+            state.view_stack.push(Box::new(OnboardingView::new()));
+            log::debug!("Showing onboarding wizard");
+        }
+
+        OnboardingAction::RepositoryDetected(repo) => {
+            if let Some(ref mut onboarding) = state.onboarding {
+                onboarding.detected_repo = repo.clone();
+            }
+        }
+
+        OnboardingAction::ConfigWritten(message) => {
+            if let Some(ref mut onboarding) = state.onboarding {
+                onboarding.config_message = Some(message.clone());
+            }
+        }
+
+        OnboardingAction::Next => {
+            if let Some(ref mut onboarding) = state.onboarding {
+                // The repository step is finished by the middleware
+                if onboarding.step == OnboardingStep::Token {
+                    onboarding.step = OnboardingStep::Repository;
+                }
+            }
+        }
+
+        OnboardingAction::AddDetectedRepository | OnboardingAction::Skip => {
+            close_wizard(&mut state);
+        }
+
+        OnboardingAction::AddRepositoryManually => {
+            close_wizard(&mut state);
+            state.add_repo_form.reset();
+            state.view_stack.push(Box::new(AddRepositoryView::new()));
+        }
+    }
+
+    state
+}
+
+/// Clear the wizard state and pop its view
+fn close_wizard(state: &mut AppState) {
+    state.onboarding = None;
+    state.pop_view(ViewId::Onboarding);
+    log::debug!("Onboarding wizard closed");
+}
@@ -0,0 +1,17 @@
+//! Performance Stats Reducer
+//!
+//! Keeps the last snapshot of the API metrics for the performance stats popup.
+
+use crate::actions::PerfStatsAction;
+use crate::state::PerfStatsState;
+
+/// Reduce performance stats popup state
+pub fn reduce_perf_stats(mut state: PerfStatsState, action: &PerfStatsAction) -> PerfStatsState {
+    match action {
+        PerfStatsAction::Loaded(snapshot) => {
+            state.snapshot = (**snapshot).clone();
+        }
+        PerfStatsAction::Open | PerfStatsAction::Refresh => {}
+    }
+    state
+}
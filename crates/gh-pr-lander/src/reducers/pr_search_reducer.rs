@@ -0,0 +1,162 @@
+//! PR Search Reducer
+//!
+//! Handles the search input popup and the search mode of the PR table.
+
+use crate::actions::PrSearchAction;
+use crate::domain_models::{LoadingState, Repository};
+use crate::state::{AppState, PrSearchInputState, PrSearchState, RepositoryData};
+use crate::views::{PrSearchView, ViewId};
+
+/// Data of the currently selected repository
+fn current_repo_data(state: &mut AppState) -> Option<&mut RepositoryData> {
+    let idx = state.main_view.selected_repository;
+    state.main_view.repo_data.get_mut(&idx)
+}
+
+/// Data of the given repository, if it is still in the list
+fn repo_data_for<'a>(state: &'a mut AppState, repo: &Repository) -> Option<&'a mut RepositoryData> {
+    let idx = state
+        .main_view
+        .repositories
+        .iter()
+        .position(|r| r.id() == repo.id())?;
+    state.main_view.repo_data.get_mut(&idx)
+}
+
+/// Reduce PR search state based on actions.
+pub fn reduce_pr_search(mut state: AppState, action: &PrSearchAction) -> AppState {
+    match action {
+        PrSearchAction::OpenInput => {
+            let query = current_repo_data(&mut state)
+                .and_then(|data| data.search.as_ref())
+                .map(|search| search.query.clone())
+                .unwrap_or_default();
+            state.pr_search_input = Some(PrSearchInputState::new(query));
+            state.view_stack.push(Box::new(PrSearchView::new()));
+        }
+
+        PrSearchAction::InputChar(c) => {
+            if let Some(ref mut input) = state.pr_search_input {
+                input.query.push(*c);
+            }
+        }
+
+        PrSearchAction::InputBackspace => {
+            if let Some(ref mut input) = state.pr_search_input {
+                input.query.pop();
+            }
+        }
+
+        PrSearchAction::InputClear => {
+            if let Some(ref mut input) = state.pr_search_input {
+                input.query.clear();
+            }
+        }
+
+        PrSearchAction::SubmitInput | PrSearchAction::CloseInput => {
+            close_search_input(&mut state);
+        }
+
+        PrSearchAction::Search { query } => {
+            let idx = state.main_view.selected_repository;
+            if idx < state.main_view.repositories.len() {
+                let data = state.main_view.repo_data.entry(idx).or_default();
+                // A new query keeps the originally loaded list aside
+                let (saved_prs, saved_selected) = match data.search.take() {
+                    Some(search) => (search.saved_prs, search.saved_selected),
+                    None => (std::mem::take(&mut data.prs), data.selected_pr),
+                };
+                data.prs.clear();
+                data.selected_pr = 0;
+                data.selected_pr_numbers.clear();
+                data.search = Some(PrSearchState::new(query.clone(), saved_prs, saved_selected));
+            }
+        }
+
+        PrSearchAction::ResultsLoaded {
+            repo,
+            query,
+            page,
+            total_count,
+            incomplete,
+            prs,
+        } => {
+            if let Some(data) = repo_data_for(&mut state, repo) {
+                let Some(ref mut search) = data.search else {
+                    return state;
+                };
+                // Ignore pages of a superseded query
+                if search.query != *query {
+                    return state;
+                }
+                search.loading = false;
+                search.error = None;
+                search.page = *page;
+                search.total_count = *total_count;
+                search.incomplete = *incomplete;
+                if *page <= 1 {
+                    data.prs = prs.clone();
+                    data.selected_pr = 0;
+                } else {
+                    let known: std::collections::HashSet<usize> =
+                        data.prs.iter().map(|pr| pr.number).collect();
+                    data.prs
+                        .extend(prs.iter().filter(|pr| !known.contains(&pr.number)).cloned());
+                }
+                data.loading_state = LoadingState::Loaded;
+            }
+        }
+
+        PrSearchAction::Failed { repo, error } => {
+            if let Some(search) = repo_data_for(&mut state, repo).and_then(|d| d.search.as_mut()) {
+                search.loading = false;
+                search.error = Some(error.clone());
+            }
+        }
+
+        PrSearchAction::LoadMore => {
+            if let Some(search) = current_repo_data(&mut state).and_then(|d| d.search.as_mut()) {
+                if !search.loading && search.has_more() {
+                    search.loading = true;
+                }
+            }
+        }
+
+        PrSearchAction::HydrateCursor => {
+            if let Some(data) = current_repo_data(&mut state) {
+                let number = data.prs.get(data.selected_pr).map(|pr| pr.number);
+                if let (Some(search), Some(number)) = (data.search.as_mut(), number) {
+                    search.hydrated.insert(number);
+                }
+            }
+        }
+
+        PrSearchAction::ResultHydrated { repo, pr } => {
+            if let Some(data) = repo_data_for(&mut state, repo) {
+                if data.search.is_some() {
+                    if let Some(existing) = data.prs.iter_mut().find(|p| p.number == pr.number) {
+                        *existing = (**pr).clone();
+                    }
+                }
+            }
+        }
+
+        PrSearchAction::Exit => {
+            if let Some(data) = current_repo_data(&mut state) {
+                if let Some(search) = data.search.take() {
+                    data.prs = search.saved_prs;
+                    data.selected_pr = search.saved_selected.min(data.prs.len().saturating_sub(1));
+                    data.selected_pr_numbers.clear();
+                }
+            }
+        }
+    }
+
+    state
+}
+
+/// Clear the search input state and pop its view
+fn close_search_input(state: &mut AppState) {
+    state.pr_search_input = None;
+    state.pop_view(ViewId::PrSearch);
+}
@@ -3,29 +3,116 @@
 //! Handles state updates for Pull Request data using tagged PullRequestAction.
 
 use crate::actions::PullRequestAction;
-use crate::domain_models::{LoadingState, Repository};
-use crate::state::MainViewState;
+use crate::domain_models::{
+    normalize_author, LoadingState, OperationType, Repository, ReviewDecision,
+};
+use crate::state::{MainViewState, PrFilter, PrPeekState, PrSort};
+use crate::utils::cycling::{next_index, previous_index};
 
 /// Find repository index by Repository
 fn find_repo_idx(state: &MainViewState, repo: &Repository) -> Option<usize> {
-    state
-        .repositories
+    state.repositories.iter().position(|r| r.id() == repo.id())
+}
+
+/// Find the tab a PR list was loaded for, if it still shows the same branch
+///
+/// Lists of a branch the tab switched away from arrive late and are dropped.
+fn find_list_repo_idx(state: &MainViewState, repo: &Repository) -> Option<usize> {
+    find_repo_idx(state, repo).filter(|&idx| state.repositories[idx].branch == repo.branch)
+}
+
+/// Next shown PR index after `current`, wrapping around
+fn next_visible(visible: &[usize], current: usize) -> Option<usize> {
+    visible
         .iter()
-        .position(|r| r.org == repo.org && r.repo == repo.repo)
+        .find(|&&idx| idx > current)
+        .or(visible.first())
+        .copied()
+}
+
+/// Apply `filter` to a repository, keeping cursor and selection on shown PRs
+fn set_filter(state: &mut MainViewState, repo_idx: usize, filter: PrFilter) {
+    if let Some(repo_data) = state.repo_data.get_mut(&repo_idx) {
+        repo_data.current_filter = filter;
+        repo_data.selected_pr = 0; // Reset selection when filter changes
+    }
+    retain_visible_selection(state, repo_idx);
+    ensure_cursor_visible(state, repo_idx);
+}
+
+/// Re-sort a repository whose PRs changed, if the order depends on them
+///
+/// Newest first is the order PRs are loaded in, so only the attention and
+/// size sorts need to be applied again.
+fn keep_sorted(state: &mut MainViewState, repo_idx: usize) {
+    if state.sort_of(repo_idx) != PrSort::Newest {
+        state.apply_sort(repo_idx);
+    }
+}
+
+/// Move the cursor off a hidden (snoozed or filtered out) PR onto the next shown one
+fn ensure_cursor_visible(state: &mut MainViewState, repo_idx: usize) {
+    let visible = state.visible_pr_indices(repo_idx);
+    if let Some(repo_data) = state.repo_data.get_mut(&repo_idx) {
+        if !visible.is_empty() && !visible.contains(&repo_data.selected_pr) {
+            if let Some(next) = next_visible(&visible, repo_data.selected_pr) {
+                repo_data.selected_pr = next;
+            }
+        }
+    }
+}
+
+/// Drop hidden (filtered out) PRs from the bulk selection
+fn retain_visible_selection(state: &mut MainViewState, repo_idx: usize) {
+    let visible = state.visible_pr_indices(repo_idx);
+    if let Some(repo_data) = state.repo_data.get_mut(&repo_idx) {
+        let shown: std::collections::HashSet<usize> = visible
+            .iter()
+            .map(|&idx| repo_data.prs[idx].number)
+            .collect();
+        repo_data
+            .selected_pr_numbers
+            .retain(|number| shown.contains(number));
+    }
+}
+
+/// Mark `operation` as running on a PR
+fn start_operation(
+    state: &mut MainViewState,
+    repo: &Repository,
+    pr_number: usize,
+    operation: OperationType,
+) {
+    state
+        .in_flight
+        .insert((repo.watched_pr(pr_number), operation));
 }
 
 /// Reduce PR-related state based on actions (new tagged action version)
 ///
 /// Accepts only PullRequestAction, making it type-safe and focused.
 pub fn reduce_pull_request(mut state: MainViewState, action: &PullRequestAction) -> MainViewState {
+    // Navigating closes the peek popup, moving the cursor in the same press
+    if matches!(
+        action,
+        PullRequestAction::NavigateNext
+            | PullRequestAction::NavigatePrevious
+            | PullRequestAction::NavigateToTop
+            | PullRequestAction::NavigateToBottom
+            | PullRequestAction::RepositoryNext
+            | PullRequestAction::RepositoryPrevious
+    ) {
+        state.peek = None;
+    }
+
     match action {
         PullRequestAction::LoadStart { repo } => {
             // Find repo index
-            let Some(repo_idx) = find_repo_idx(&state, repo) else {
-                log::warn!(
-                    "LoadStart: Repository {}/{} not found in state",
-                    repo.org,
-                    repo.repo
+            let Some(repo_idx) = find_list_repo_idx(&state, repo) else {
+                log::debug!(
+                    "LoadStart: No tab shows {} @ {}",
+                    repo.display_name(),
+                    repo.branch
                 );
                 return state;
             };
@@ -39,24 +126,58 @@ pub fn reduce_pull_request(mut state: MainViewState, action: &PullRequestAction)
             );
         }
 
-        PullRequestAction::Loaded { repo, prs } => {
+        PullRequestAction::Loaded {
+            repo,
+            prs,
+            from_cache,
+        } => {
             // Find repo index
-            let Some(repo_idx) = find_repo_idx(&state, repo) else {
-                log::warn!(
-                    "Loaded: Repository {}/{} not found in state",
-                    repo.org,
-                    repo.repo
+            let Some(repo_idx) = find_list_repo_idx(&state, repo) else {
+                log::debug!(
+                    "Loaded: No tab shows {} @ {}",
+                    repo.display_name(),
+                    repo.branch
                 );
                 return state;
             };
             // Update repository data with loaded PRs
             let repo_data = state.repo_data.entry(repo_idx).or_default();
-            repo_data.prs = prs.clone();
+            // The fresh list keeps the cursor the user moved on the cached one
+            let cursor_pr = repo_data
+                .from_cache
+                .and_then(|_| repo_data.prs.get(repo_data.selected_pr))
+                .map(|pr| pr.number);
             repo_data.loading_state = LoadingState::Loaded;
-            repo_data.last_updated = Some(chrono::Local::now());
-            repo_data.selected_pr = 0;
+            repo_data.last_updated = Some(from_cache.unwrap_or_else(chrono::Local::now));
+            repo_data.prefetched = false;
+            repo_data.from_cache = *from_cache;
+            if from_cache.is_none() {
+                repo_data.record_load_success();
+            }
+            state.bot_updates.update_repo(repo, prs);
+            let repo_data = state.repo_data.entry(repo_idx).or_default();
+            // While searching, the loaded list is kept aside for when search ends
+            if let Some(ref mut search) = repo_data.search {
+                search.saved_prs = prs.clone();
+                search.saved_selected = 0;
+                return state;
+            }
+            // Stats of unchanged PRs stay until they are fetched again
+            let previous = std::mem::take(&mut repo_data.prs);
+            repo_data.prs = prs.clone();
+            for pr in &mut repo_data.prs {
+                if let Some(old) = previous.iter().find(|old| old.number == pr.number) {
+                    pr.keep_stats_of(old);
+                }
+            }
+            repo_data.selected_pr = cursor_pr
+                .and_then(|number| prs.iter().position(|pr| pr.number == number))
+                .unwrap_or(0);
             // Clear selection when PRs are reloaded
             repo_data.selected_pr_numbers.clear();
+            keep_sorted(&mut state, repo_idx);
+            ensure_cursor_visible(&mut state, repo_idx);
+            state.prune_last_seen(repo);
             log::info!(
                 "Loaded {} PRs for repository {}/{}",
                 prs.len(),
@@ -65,19 +186,70 @@ pub fn reduce_pull_request(mut state: MainViewState, action: &PullRequestAction)
             );
         }
 
-        PullRequestAction::LoadError { repo, error } => {
+        PullRequestAction::Prefetched { repo, prs } => {
+            let Some(repo_idx) = find_list_repo_idx(&state, repo) else {
+                return state;
+            };
+            let Some(repo_data) = state.repo_data.get_mut(&repo_idx) else {
+                return state;
+            };
+            // A load or search started meanwhile wins over the prefetch
+            if repo_data.loading_state != LoadingState::Loaded || repo_data.search.is_some() {
+                return state;
+            }
+
+            // Keep loaded details (CI, stats) of PRs that did not change
+            let cursor_pr = repo_data.prs.get(repo_data.selected_pr).map(|pr| pr.number);
+            let merged: Vec<_> = prs
+                .iter()
+                .map(|pr| {
+                    repo_data
+                        .prs
+                        .iter()
+                        .find(|old| old.number == pr.number && old.updated_at == pr.updated_at)
+                        .cloned()
+                        .unwrap_or_else(|| pr.clone())
+                })
+                .collect();
+            repo_data.selected_pr = cursor_pr
+                .and_then(|number| merged.iter().position(|pr| pr.number == number))
+                .unwrap_or(0);
+            repo_data
+                .selected_pr_numbers
+                .retain(|number| merged.iter().any(|pr| pr.number == *number));
+            repo_data.prs = merged;
+            repo_data.last_updated = Some(chrono::Local::now());
+            repo_data.prefetched = true;
+            state.bot_updates.update_repo(repo, prs);
+            keep_sorted(&mut state, repo_idx);
+            ensure_cursor_visible(&mut state, repo_idx);
+        }
+
+        PullRequestAction::LoadError {
+            repo,
+            error,
+            status,
+        } => {
             // Find repo index
-            let Some(repo_idx) = find_repo_idx(&state, repo) else {
-                log::warn!(
-                    "LoadError: Repository {}/{} not found in state",
-                    repo.org,
-                    repo.repo
+            let Some(repo_idx) = find_list_repo_idx(&state, repo) else {
+                log::debug!(
+                    "LoadError: No tab shows {} @ {}",
+                    repo.display_name(),
+                    repo.branch
                 );
                 return state;
             };
             // Set error state for the repository
             let repo_data = state.repo_data.entry(repo_idx).or_default();
             repo_data.loading_state = LoadingState::Error(error.clone());
+            let was_inaccessible = repo_data.inaccessible.is_some();
+            if repo_data.record_load_error(error, *status) && !was_inaccessible {
+                log::warn!(
+                    "Repository {}/{} is inaccessible, retrying less often",
+                    repo.org,
+                    repo.repo
+                );
+            }
             log::error!(
                 "Failed to load PRs for repository {}/{}: {}",
                 repo.org,
@@ -87,40 +259,46 @@ pub fn reduce_pull_request(mut state: MainViewState, action: &PullRequestAction)
         }
 
         // Navigation actions (translated from NavigationAction)
+        // Navigation skips PRs hidden from the table (snoozed or filtered out)
         PullRequestAction::NavigateNext => {
             let repo_idx = state.selected_repository;
+            let visible = state.visible_pr_indices(repo_idx);
             if let Some(repo_data) = state.repo_data.get_mut(&repo_idx) {
-                if !repo_data.prs.is_empty() {
-                    repo_data.selected_pr = (repo_data.selected_pr + 1) % repo_data.prs.len();
+                if let Some(next) = next_visible(&visible, repo_data.selected_pr) {
+                    repo_data.selected_pr = next;
                 }
             }
         }
 
         PullRequestAction::NavigatePrevious => {
             let repo_idx = state.selected_repository;
+            let visible = state.visible_pr_indices(repo_idx);
             if let Some(repo_data) = state.repo_data.get_mut(&repo_idx) {
-                if !repo_data.prs.is_empty() {
-                    repo_data.selected_pr = if repo_data.selected_pr == 0 {
-                        repo_data.prs.len() - 1
-                    } else {
-                        repo_data.selected_pr - 1
-                    };
+                let previous = visible
+                    .iter()
+                    .rev()
+                    .find(|&&idx| idx < repo_data.selected_pr)
+                    .or(visible.last());
+                if let Some(&previous) = previous {
+                    repo_data.selected_pr = previous;
                 }
             }
         }
 
         PullRequestAction::NavigateToTop => {
             let repo_idx = state.selected_repository;
+            let visible = state.visible_pr_indices(repo_idx);
             if let Some(repo_data) = state.repo_data.get_mut(&repo_idx) {
-                repo_data.selected_pr = 0;
+                repo_data.selected_pr = visible.first().copied().unwrap_or(0);
             }
         }
 
         PullRequestAction::NavigateToBottom => {
             let repo_idx = state.selected_repository;
+            let visible = state.visible_pr_indices(repo_idx);
             if let Some(repo_data) = state.repo_data.get_mut(&repo_idx) {
-                if !repo_data.prs.is_empty() {
-                    repo_data.selected_pr = repo_data.prs.len() - 1;
+                if let Some(&last) = visible.last() {
+                    repo_data.selected_pr = last;
                 }
             }
         }
@@ -128,20 +306,19 @@ pub fn reduce_pull_request(mut state: MainViewState, action: &PullRequestAction)
         // Repository switching
         PullRequestAction::RepositoryNext => {
             let num_repos = state.repositories.len();
-            if num_repos > 0 {
-                state.selected_repository = (state.selected_repository + 1) % num_repos;
+            if let Some(next) = next_index(state.selected_repository, num_repos, state.tab_cycling)
+            {
+                state.selected_repository = next;
                 log::debug!("Switched to repository {}", state.selected_repository);
             }
         }
 
         PullRequestAction::RepositoryPrevious => {
             let num_repos = state.repositories.len();
-            if num_repos > 0 {
-                state.selected_repository = if state.selected_repository == 0 {
-                    num_repos - 1
-                } else {
-                    state.selected_repository - 1
-                };
+            if let Some(previous) =
+                previous_index(state.selected_repository, num_repos, state.tab_cycling)
+            {
+                state.selected_repository = previous;
                 log::debug!("Switched to repository {}", state.selected_repository);
             }
         }
@@ -149,6 +326,7 @@ pub fn reduce_pull_request(mut state: MainViewState, action: &PullRequestAction)
         // Selection actions
         PullRequestAction::ToggleSelection => {
             let repo_idx = state.selected_repository;
+            let visible = state.visible_pr_indices(repo_idx);
             if let Some(repo_data) = state.repo_data.get_mut(&repo_idx) {
                 if let Some(pr) = repo_data.prs.get(repo_data.selected_pr) {
                     let pr_number = pr.number;
@@ -159,15 +337,22 @@ pub fn reduce_pull_request(mut state: MainViewState, action: &PullRequestAction)
                         repo_data.selected_pr_numbers.insert(pr_number);
                         log::debug!("Selected PR #{}", pr_number);
                     }
-                    repo_data.selected_pr = (repo_data.selected_pr + 1) % repo_data.prs.len();
+                    if let Some(next) = next_visible(&visible, repo_data.selected_pr) {
+                        repo_data.selected_pr = next;
+                    }
                 }
             }
         }
 
         PullRequestAction::SelectAll => {
             let repo_idx = state.selected_repository;
+            // Snoozed PRs are only included while they are shown
+            let visible = state.visible_pr_indices(repo_idx);
             if let Some(repo_data) = state.repo_data.get_mut(&repo_idx) {
-                repo_data.selected_pr_numbers = repo_data.prs.iter().map(|pr| pr.number).collect();
+                repo_data.selected_pr_numbers = visible
+                    .iter()
+                    .map(|&idx| repo_data.prs[idx].number)
+                    .collect();
                 log::debug!("Selected all {} PRs", repo_data.selected_pr_numbers.len());
             }
         }
@@ -184,35 +369,240 @@ pub fn reduce_pull_request(mut state: MainViewState, action: &PullRequestAction)
         // Filter actions
         PullRequestAction::SetFilter(filter) => {
             let repo_idx = state.selected_repository;
+            set_filter(&mut state, repo_idx, filter.clone());
+        }
+
+        PullRequestAction::SetRepoFilter { repo, filter } => {
+            if let Some(repo_idx) = state.repositories.iter().position(|r| r == repo) {
+                set_filter(&mut state, repo_idx, filter.clone());
+            }
+        }
+
+        PullRequestAction::ApplySavedFilter(name) => {
+            let repo_idx = state.selected_repository;
+            if let Some(spec) = state.saved_filters.get(name).cloned() {
+                let filter = PrFilter::Spec {
+                    name: Some(name.clone()),
+                    spec: Box::new(spec),
+                };
+                set_filter(&mut state, repo_idx, filter);
+            }
+        }
+
+        PullRequestAction::SavedFiltersUpdated(filters) => {
+            state.saved_filters = filters.clone();
+            // Repositories filtered by a saved filter follow its new criteria
+            for repo_data in state.repo_data.values_mut() {
+                if let PrFilter::Spec {
+                    name: Some(name),
+                    spec,
+                } = &mut repo_data.current_filter
+                {
+                    if let Some(saved) = filters.get(name) {
+                        **spec = saved.clone();
+                    }
+                }
+            }
+            for repo_idx in 0..state.repositories.len() {
+                ensure_cursor_visible(&mut state, repo_idx);
+            }
+        }
+
+        PullRequestAction::ToggleWatch => {
+            let repo_idx = state.selected_repository;
+            let watched = state.repositories.get(repo_idx).and_then(|repo| {
+                let data = state.repo_data.get(&repo_idx)?;
+                let pr = data.prs.get(data.selected_pr)?;
+                Some(repo.watched_pr(pr.number))
+            });
+            if let Some(watched) = watched {
+                state.watch_list.toggle(watched);
+                state.mark_session_dirty();
+            }
+        }
+
+        PullRequestAction::CycleSort => {
+            let repo_idx = state.selected_repository;
+            let sort = state.sort_of(repo_idx).next();
             if let Some(repo_data) = state.repo_data.get_mut(&repo_idx) {
-                repo_data.current_filter = filter.clone();
-                repo_data.selected_pr = 0; // Reset selection when filter changes
+                repo_data.sort = Some(sort);
+                state.apply_sort(repo_idx);
+            }
+        }
+
+        PullRequestAction::ReviewersLoaded { repo, reviewers } => {
+            let Some(repo_idx) = find_repo_idx(&state, repo) else {
+                return state;
+            };
+            let Some(repo_data) = state.repo_data.get_mut(&repo_idx) else {
+                return state;
+            };
+            for pr in repo_data.prs.iter_mut() {
+                let Some(fetched) = reviewers.iter().find(|r| r.number == pr.number as u64) else {
+                    continue;
+                };
+                // Repositories without review rules have no decision
+                if fetched.review_decision != ReviewDecision::Unknown {
+                    pr.review_decision = fetched.review_decision;
+                }
+                pr.reviewers = Some(fetched.clone());
+            }
+            keep_sorted(&mut state, repo_idx);
+        }
+
+        PullRequestAction::CycleStateFilter => {
+            let repo_idx = state.selected_repository;
+            if repo_idx < state.repositories.len() {
+                let repo_data = state.repo_data.entry(repo_idx).or_default();
+                repo_data.pr_state = repo_data.next_pr_state();
+                repo_data.selected_pr = 0;
+                repo_data.selected_pr_numbers.clear();
+            }
+        }
+
+        PullRequestAction::TogglePeek => {
+            let repo_idx = state.selected_repository;
+            state.peek = match state.peek {
+                Some(_) => None,
+                None => state
+                    .repo_data
+                    .get(&repo_idx)
+                    .and_then(|data| data.prs.get(data.selected_pr))
+                    .map(|pr| PrPeekState {
+                        repo_idx,
+                        pr_number: pr.number,
+                        error: None,
+                    }),
+            };
+        }
+
+        PullRequestAction::ClosePeek => {
+            state.peek = None;
+        }
+
+        PullRequestAction::PeekBodyLoaded {
+            pr,
+            updated_at,
+            body,
+        } => {
+            state
+                .peek_bodies
+                .insert(pr.clone(), (*updated_at, body.clone()));
+        }
+
+        PullRequestAction::PeekBodyFailed { pr, error } => {
+            let peeked = state
+                .peeked_pr()
+                .map(|(repo, peeked)| repo.watched_pr(peeked.number));
+            if peeked.as_ref() == Some(pr) {
+                if let Some(ref mut peek) = state.peek {
+                    peek.error = Some(error.clone());
+                }
+            }
+        }
+
+        PullRequestAction::NotesUpdated(notes) => {
+            state.notes = notes.clone();
+        }
+
+        PullRequestAction::SnoozesUpdated(snoozed) => {
+            state.snoozed = snoozed.clone();
+            for repo_idx in 0..state.repositories.len() {
+                ensure_cursor_visible(&mut state, repo_idx);
+            }
+        }
+
+        PullRequestAction::ToggleShowSnoozed => {
+            state.show_snoozed = !state.show_snoozed;
+            let repo_idx = state.selected_repository;
+            ensure_cursor_visible(&mut state, repo_idx);
+        }
+
+        PullRequestAction::MarkSeen => {
+            let repo_idx = state.selected_repository;
+            let seen = state
+                .repositories
+                .get(repo_idx)
+                .zip(state.repo_data.get(&repo_idx))
+                .and_then(|(repo, data)| {
+                    let pr = data.prs.get(data.selected_pr)?;
+                    Some((repo.watched_pr(pr.number), pr.updated_at))
+                });
+            if let Some((pr, updated_at)) = seen {
+                state.mark_seen(pr, updated_at);
+            }
+        }
+
+        PullRequestAction::MarkAllSeen => {
+            let repo_idx = state.selected_repository;
+            let seen: Vec<_> = state
+                .repositories
+                .get(repo_idx)
+                .zip(state.repo_data.get(&repo_idx))
+                .map(|(repo, data)| {
+                    data.prs
+                        .iter()
+                        .map(|pr| (repo.watched_pr(pr.number), pr.updated_at))
+                        .collect()
+                })
+                .unwrap_or_default();
+            for (pr, updated_at) in seen {
+                state.mark_seen(pr, updated_at);
+            }
+        }
+
+        PullRequestAction::LastSeenLoaded(last_seen) => {
+            state.last_seen = last_seen.clone();
+        }
+
+        PullRequestAction::ToggleAuthorFilter => {
+            let repo_idx = state.selected_repository;
+            let filter = state.repo_data.get(&repo_idx).and_then(|repo_data| {
+                match repo_data.current_filter {
+                    PrFilter::Author(_) => Some(PrFilter::All),
+                    _ => repo_data
+                        .prs
+                        .get(repo_data.selected_pr)
+                        .map(|pr| PrFilter::Author(normalize_author(&pr.author))),
+                }
+            });
+            if let Some(filter) = filter {
+                set_filter(&mut state, repo_idx, filter);
             }
         }
 
         PullRequestAction::ClearFilter => {
             let repo_idx = state.selected_repository;
             if let Some(repo_data) = state.repo_data.get_mut(&repo_idx) {
-                repo_data.current_filter = crate::state::PrFilter::All;
+                repo_data.current_filter = PrFilter::All;
                 repo_data.selected_pr = 0;
             }
+            ensure_cursor_visible(&mut state, repo_idx);
         }
 
         // Operations that are handled by middleware (these just get dispatched)
         // The actual state changes happen via success/error callbacks
-        PullRequestAction::OpenInBrowser
+        PullRequestAction::OpenInBrowser(_)
         | PullRequestAction::OpenInIDE
         | PullRequestAction::OpenBuildLogs
         | PullRequestAction::OpenRelatedIssue { .. }
         | PullRequestAction::Refresh
         | PullRequestAction::CycleFilter
         | PullRequestAction::MergeRequest
+        | PullRequestAction::MergeBotUpdatesRequest
         | PullRequestAction::RebaseRequest
+        | PullRequestAction::RebaseStackRequest
+        | PullRequestAction::InspectConflicts
         | PullRequestAction::ApproveRequest
         | PullRequestAction::CommentRequest
         | PullRequestAction::RequestChangesRequest
         | PullRequestAction::CloseRequest
-        | PullRequestAction::RerunFailedJobs => {
+        | PullRequestAction::RerunFailedJobs
+        | PullRequestAction::EditNote
+        | PullRequestAction::SaveNote { .. }
+        | PullRequestAction::Snooze { .. }
+        | PullRequestAction::Unsnooze
+        | PullRequestAction::RunPolicyRequest => {
             // These are request actions - handled by middleware
         }
 
@@ -220,19 +610,46 @@ pub fn reduce_pull_request(mut state: MainViewState, action: &PullRequestAction)
         PullRequestAction::ApproveWithMessage { .. }
         | PullRequestAction::CommentOnPr { .. }
         | PullRequestAction::RequestChanges { .. }
-        | PullRequestAction::ClosePrWithMessage { .. } => {
+        | PullRequestAction::ClosePrWithMessage { .. }
+        | PullRequestAction::MergePrs { .. }
+        | PullRequestAction::MergeRepoPrs { .. }
+        | PullRequestAction::RunPolicy { .. } => {
             // These are confirmation actions - handled by middleware
         }
 
-        // Operation start actions (could set loading state if needed)
-        PullRequestAction::MergeStart { .. }
-        | PullRequestAction::RebaseStart { .. }
-        | PullRequestAction::ApproveStart { .. }
-        | PullRequestAction::CommentStart { .. }
-        | PullRequestAction::RequestChangesStart { .. }
-        | PullRequestAction::CloseStart { .. }
-        | PullRequestAction::RerunStart { .. } => {
-            // These could set operation-in-progress state if needed
+        // Operation start actions mark the PR until the operation finished
+        PullRequestAction::MergeStart { repo, pr_number } => {
+            start_operation(&mut state, repo, *pr_number, OperationType::Merge);
+        }
+        PullRequestAction::RebaseStart { repo, pr_number } => {
+            start_operation(&mut state, repo, *pr_number, OperationType::Rebase);
+        }
+        PullRequestAction::ApproveStart { repo, pr_number } => {
+            start_operation(&mut state, repo, *pr_number, OperationType::Approve);
+        }
+        PullRequestAction::CommentStart { repo, pr_number } => {
+            start_operation(&mut state, repo, *pr_number, OperationType::Comment);
+        }
+        PullRequestAction::RequestChangesStart { repo, pr_number } => {
+            start_operation(&mut state, repo, *pr_number, OperationType::RequestChanges);
+        }
+        PullRequestAction::CloseStart { repo, pr_number } => {
+            start_operation(&mut state, repo, *pr_number, OperationType::Close);
+        }
+        PullRequestAction::RerunStart {
+            repo, pr_number, ..
+        } => {
+            start_operation(&mut state, repo, *pr_number as usize, OperationType::Rerun);
+        }
+
+        PullRequestAction::OperationFinished {
+            repo,
+            pr_number,
+            operation,
+        } => {
+            state
+                .in_flight
+                .remove(&(repo.watched_pr(*pr_number), *operation));
         }
 
         // CI/Build status actions
@@ -240,10 +657,39 @@ pub fn reduce_pull_request(mut state: MainViewState, action: &PullRequestAction)
             // Handled by middleware - triggers async CI status fetch
         }
 
+        PullRequestAction::BranchStatusUpdated { repo, ci } => {
+            if let Some(repo_data) =
+                find_repo_idx(&state, repo).and_then(|repo_idx| state.repo_data.get_mut(&repo_idx))
+            {
+                repo_data.branch_ci = *ci;
+            }
+        }
+
+        // Only reached when the middleware fetches it
+        PullRequestAction::FetchPulse => {
+            let repo_idx = state.selected_repository;
+            if repo_idx < state.repositories.len() {
+                state.repo_data.entry(repo_idx).or_default().pulse_requested =
+                    Some(chrono::Local::now());
+            }
+        }
+
+        // Counted for the whole repository, whichever branch a tab tracks
+        PullRequestAction::PulseUpdated { repo, pulse } => {
+            let id = repo.id();
+            for repo_idx in 0..state.repositories.len() {
+                if state.repositories[repo_idx].id() == id {
+                    state.repo_data.entry(repo_idx).or_default().pulse = Some(*pulse);
+                }
+            }
+        }
+
         PullRequestAction::BuildStatusUpdated {
             repo,
             pr_number,
             status,
+            budget,
+            checks,
         } => {
             // Find repo index
             let Some(repo_idx) = find_repo_idx(&state, repo) else {
@@ -269,6 +715,8 @@ pub fn reduce_pull_request(mut state: MainViewState, action: &PullRequestAction)
                         status
                     );
                     pr.mergeable = *status;
+                    pr.ci_budget = *budget;
+                    pr.ci_checks = checks.clone();
                 } else {
                     log::warn!(
                         "Reducer: PR #{} not found in repo_data for {}/{}",
@@ -285,6 +733,8 @@ pub fn reduce_pull_request(mut state: MainViewState, action: &PullRequestAction)
                     pr_number
                 );
             }
+            // Failing CI on the user's PRs counts for the attention sort
+            keep_sorted(&mut state, repo_idx);
         }
 
         PullRequestAction::StatsUpdated {
@@ -292,6 +742,7 @@ pub fn reduce_pull_request(mut state: MainViewState, action: &PullRequestAction)
             pr_number,
             additions,
             deletions,
+            changed_files,
         } => {
             // Find repo index
             let Some(repo_idx) = find_repo_idx(&state, repo) else {
@@ -303,7 +754,7 @@ pub fn reduce_pull_request(mut state: MainViewState, action: &PullRequestAction)
                 );
                 return state;
             };
-            // Update the PR's additions/deletions
+            // Update the PR's additions/deletions/changed files
             if let Some(repo_data) = state.repo_data.get_mut(&repo_idx) {
                 if let Some(pr) = repo_data
                     .prs
@@ -318,6 +769,7 @@ pub fn reduce_pull_request(mut state: MainViewState, action: &PullRequestAction)
                     );
                     pr.additions = *additions;
                     pr.deletions = *deletions;
+                    pr.changed_files = Some(*changed_files);
                 } else {
                     log::warn!(
                         "Reducer: PR #{} not found in repo_data for {}/{}",
@@ -334,6 +786,55 @@ pub fn reduce_pull_request(mut state: MainViewState, action: &PullRequestAction)
                     pr_number
                 );
             }
+            if state.sort_of(repo_idx) == PrSort::Size {
+                state.apply_sort(repo_idx);
+            }
+        }
+
+        PullRequestAction::LabelsUpdated {
+            repo,
+            pr_number,
+            labels,
+        } => {
+            let Some(repo_idx) = find_repo_idx(&state, repo) else {
+                log::warn!(
+                    "Reducer: Repository {}/{} not found when updating PR #{} labels",
+                    repo.org,
+                    repo.repo,
+                    pr_number
+                );
+                return state;
+            };
+            if let Some(pr) = state.repo_data.get_mut(&repo_idx).and_then(|data| {
+                data.prs
+                    .iter_mut()
+                    .find(|p| p.number == *pr_number as usize)
+            }) {
+                pr.labels = labels.clone();
+            }
+        }
+
+        PullRequestAction::CommentCountsUpdated {
+            repo,
+            pr_number,
+            counts,
+        } => {
+            let Some(repo_idx) = find_repo_idx(&state, repo) else {
+                log::warn!(
+                    "Reducer: Repository {}/{} not found when updating PR #{} comment counts",
+                    repo.org,
+                    repo.repo,
+                    pr_number
+                );
+                return state;
+            };
+            if let Some(pr) = state.repo_data.get_mut(&repo_idx).and_then(|data| {
+                data.prs
+                    .iter_mut()
+                    .find(|p| p.number == *pr_number as usize)
+            }) {
+                pr.comment_counts = Some(*counts);
+            }
         }
 
         PullRequestAction::ReviewDecisionUpdated {
@@ -386,3 +887,466 @@ pub fn reduce_pull_request(mut state: MainViewState, action: &PullRequestAction)
 
     state
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::Pr;
+    use crate::state::RepositoryData;
+    use gh_pr_config::SnoozedPr;
+    use std::collections::HashSet;
+
+    fn state_with_snoozed(snoozed: &[usize]) -> MainViewState {
+        let repo = Repository::new("org", "repo", "main");
+        let mut state = MainViewState {
+            repositories: vec![repo.clone()],
+            ..Default::default()
+        };
+        state.repo_data.insert(
+            0,
+            RepositoryData {
+                prs: (1..=4).map(|n| Pr::new(n, "PR", "alice", "abc")).collect(),
+                ..Default::default()
+            },
+        );
+        state.snoozed = snoozed
+            .iter()
+            .map(|&number| {
+                let pr = repo.watched_pr(number);
+                let entry = SnoozedPr {
+                    pr: pr.clone(),
+                    head_sha: "abc".to_string(),
+                    updated_at: chrono::Utc::now(),
+                    until: None,
+                };
+                (pr, entry)
+            })
+            .collect();
+        state
+    }
+
+    fn cursor(state: &MainViewState) -> usize {
+        state.repo_data[&0].selected_pr
+    }
+
+    #[test]
+    fn test_navigation_skips_snoozed_prs() {
+        let state = state_with_snoozed(&[2, 4]);
+        let state = reduce_pull_request(state, &PullRequestAction::NavigateNext);
+        assert_eq!(cursor(&state), 2);
+        let state = reduce_pull_request(state, &PullRequestAction::NavigateNext);
+        assert_eq!(cursor(&state), 0);
+        let state = reduce_pull_request(state, &PullRequestAction::NavigateToBottom);
+        assert_eq!(cursor(&state), 2);
+
+        // Revealed snoozed PRs can be navigated to again
+        let state = reduce_pull_request(state, &PullRequestAction::ToggleShowSnoozed);
+        let state = reduce_pull_request(state, &PullRequestAction::NavigateNext);
+        assert_eq!(cursor(&state), 3);
+    }
+
+    #[test]
+    fn test_snoozing_moves_cursor_and_select_all_skips_snoozed() {
+        let mut snoozed = state_with_snoozed(&[1]).snoozed;
+        let state = reduce_pull_request(
+            state_with_snoozed(&[]),
+            &PullRequestAction::SnoozesUpdated(std::mem::take(&mut snoozed)),
+        );
+        assert_eq!(cursor(&state), 1);
+
+        let state = reduce_pull_request(state, &PullRequestAction::SelectAll);
+        let mut selected: Vec<_> = state.repo_data[&0]
+            .selected_pr_numbers
+            .iter()
+            .copied()
+            .collect();
+        selected.sort();
+        assert_eq!(selected, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_toggle_author_filter() {
+        let mut state = state_with_snoozed(&[]);
+        let data = state.repo_data.get_mut(&0).unwrap();
+        data.prs[1].author = "dependabot[bot]".to_string();
+        data.prs[3].author = "dependabot-preview[bot]".to_string();
+        data.selected_pr = 1;
+
+        let state = reduce_pull_request(state, &PullRequestAction::ToggleAuthorFilter);
+        assert_eq!(
+            state.repo_data[&0].current_filter,
+            PrFilter::Author("dependabot".to_string())
+        );
+        assert_eq!(state.visible_pr_indices(0), vec![1, 3]);
+        assert_eq!(cursor(&state), 1);
+
+        // The same key clears it again
+        let state = reduce_pull_request(state, &PullRequestAction::ToggleAuthorFilter);
+        assert_eq!(state.repo_data[&0].current_filter, PrFilter::All);
+    }
+
+    #[test]
+    fn test_saved_filter_follows_updates() {
+        let mut state = state_with_snoozed(&[]);
+        state.repo_data.get_mut(&0).unwrap().prs[2].labels = vec!["security".to_string()];
+        let saved = |label: &str| {
+            let spec = gh_pr_config::FilterSpec {
+                labels_include: vec![label.to_string()],
+                ..Default::default()
+            };
+            [("security-bumps".to_string(), spec)].into_iter().collect()
+        };
+
+        let state = reduce_pull_request(
+            state,
+            &PullRequestAction::SavedFiltersUpdated(saved("security")),
+        );
+        let state = reduce_pull_request(
+            state,
+            &PullRequestAction::ApplySavedFilter("security-bumps".to_string()),
+        );
+        assert_eq!(state.repo_data[&0].current_filter.label(), "security-bumps");
+        assert_eq!(state.visible_pr_indices(0), vec![2]);
+        assert_eq!(cursor(&state), 2);
+
+        // Saving the filter again changes the shown PRs
+        let state = reduce_pull_request(
+            state,
+            &PullRequestAction::SavedFiltersUpdated(saved("docs")),
+        );
+        assert!(state.visible_pr_indices(0).is_empty());
+
+        // Unknown names leave the filter alone
+        let state = reduce_pull_request(
+            state,
+            &PullRequestAction::ApplySavedFilter("missing".to_string()),
+        );
+        assert_eq!(state.repo_data[&0].current_filter.label(), "security-bumps");
+    }
+
+    #[test]
+    fn test_repeated_access_errors_mark_repository_inaccessible() {
+        let repo = Repository::new("org", "repo", "main");
+        let load_error = |status| PullRequestAction::LoadError {
+            repo: repo.clone(),
+            error: "Not Found".to_string(),
+            status,
+        };
+
+        // A single 404 may be a hiccup, a second one is persistent
+        let state = reduce_pull_request(state_with_snoozed(&[]), &load_error(Some(404)));
+        assert!(state.repo_data[&0].inaccessible.is_none());
+        let state = reduce_pull_request(state, &load_error(Some(404)));
+        let inaccessible = state.repo_data[&0].inaccessible.as_ref().unwrap();
+        assert_eq!(inaccessible.error, "Not Found");
+
+        // Other errors (e.g. offline) keep the marker but restart the count
+        let state = reduce_pull_request(state, &load_error(None));
+        assert!(state.repo_data[&0].inaccessible.is_some());
+        assert_eq!(state.repo_data[&0].access_failures, 0);
+
+        // A successful load recovers the repository
+        let state = reduce_pull_request(
+            state,
+            &PullRequestAction::Loaded {
+                repo: repo.clone(),
+                prs: Vec::new(),
+                from_cache: None,
+            },
+        );
+        assert!(state.repo_data[&0].inaccessible.is_none());
+    }
+
+    #[test]
+    fn test_fresh_load_replaces_cached_prs() {
+        let repo = Repository::new("org", "repo", "main");
+        let prs = |numbers: &[usize]| -> Vec<Pr> {
+            numbers
+                .iter()
+                .map(|&n| Pr::new(n, "PR", "alice", "abc"))
+                .collect()
+        };
+        let cached_at = chrono::Local::now() - chrono::TimeDelta::minutes(52);
+
+        let mut state = reduce_pull_request(
+            state_with_snoozed(&[]),
+            &PullRequestAction::Loaded {
+                repo: repo.clone(),
+                prs: prs(&[4, 3, 2]),
+                from_cache: Some(cached_at),
+            },
+        );
+        let data = &state.repo_data[&0];
+        assert_eq!(data.loading_state, LoadingState::Loaded);
+        assert_eq!(data.from_cache, Some(cached_at));
+        assert_eq!(data.last_updated, Some(cached_at));
+
+        // The cursor stays on the PR it was moved to while refreshing
+        state.repo_data.get_mut(&0).unwrap().selected_pr = 1;
+        let state = reduce_pull_request(
+            state,
+            &PullRequestAction::Loaded {
+                repo: repo.clone(),
+                prs: prs(&[5, 4, 3, 2]),
+                from_cache: None,
+            },
+        );
+        let data = &state.repo_data[&0];
+        assert_eq!(data.from_cache, None);
+        assert!(data.last_updated.unwrap() > cached_at);
+        assert_eq!(data.prs[data.selected_pr].number, 3);
+    }
+
+    #[test]
+    fn test_stats_survive_reload_and_keep_size_sort() {
+        let repo = Repository::new("org", "repo", "main");
+        let mut state = state_with_snoozed(&[]);
+        state.repo_data.get_mut(&0).unwrap().sort = Some(PrSort::Size);
+        let stats = |number, additions, changed_files| PullRequestAction::StatsUpdated {
+            repo: repo.clone(),
+            pr_number: number,
+            additions,
+            deletions: 0,
+            changed_files,
+        };
+        let state = reduce_pull_request(state, &stats(1, 300, 4));
+        let state = reduce_pull_request(state, &stats(3, 5, 1));
+        let numbers = |state: &MainViewState| -> Vec<usize> {
+            state.repo_data[&0].prs.iter().map(|pr| pr.number).collect()
+        };
+        // Smallest first, PRs without stats last
+        assert_eq!(numbers(&state), vec![3, 1, 4, 2]);
+
+        // A reload lists the PRs without stats, the fetched ones stay
+        let state = reduce_pull_request(
+            state,
+            &PullRequestAction::Loaded {
+                repo: repo.clone(),
+                prs: vec![
+                    Pr::new(1, "PR", "alice", "new-head"),
+                    Pr::new(3, "PR", "alice", "abc"),
+                ],
+                from_cache: None,
+            },
+        );
+        let data = &state.repo_data[&0];
+        let pr = |number| data.prs.iter().find(|pr| pr.number == number).unwrap();
+        assert_eq!(pr(3).changed_lines(), Some(5));
+        // A new head commit needs its stats fetched again
+        assert_eq!(pr(1).changed_lines(), None);
+        assert_eq!(numbers(&state), vec![3, 1]);
+    }
+
+    #[test]
+    fn test_last_seen_marks_and_prunes() {
+        let repo = Repository::new("org", "repo", "main");
+        let state = reduce_pull_request(state_with_snoozed(&[]), &PullRequestAction::MarkSeen);
+        assert_eq!(state.last_seen.len(), 1);
+        assert!(state.last_seen.contains_key(&repo.watched_pr(1)));
+
+        let mut state = reduce_pull_request(state, &PullRequestAction::MarkAllSeen);
+        assert_eq!(state.last_seen.len(), 4);
+        assert!(state.unseen_prs(0).is_empty());
+
+        // An update on GitHub marks the PR until it is seen again
+        let data = state.repo_data.get_mut(&0).unwrap();
+        data.prs[1].updated_at += chrono::TimeDelta::hours(1);
+        assert_eq!(state.unseen_prs(0), HashSet::from([2]));
+        let mut state = reduce_pull_request(state, &PullRequestAction::MarkSeen);
+        assert_eq!(state.unseen_prs(0), HashSet::from([2]));
+        state.repo_data.get_mut(&0).unwrap().selected_pr = 1;
+        let mut state = reduce_pull_request(state, &PullRequestAction::MarkSeen);
+        assert!(state.unseen_prs(0).is_empty());
+
+        // PRs no longer open are forgotten, once every tab of the repository loaded
+        let loaded = |state: MainViewState, numbers: &[usize]| {
+            reduce_pull_request(
+                state,
+                &PullRequestAction::Loaded {
+                    repo: Repository::new("org", "repo", "main"),
+                    prs: numbers
+                        .iter()
+                        .map(|&n| Pr::new(n, "PR", "alice", "abc"))
+                        .collect(),
+                    from_cache: None,
+                },
+            )
+        };
+        state
+            .repositories
+            .push(Repository::new("org", "repo", "release"));
+        let mut state = loaded(state, &[1, 2]);
+        assert_eq!(state.last_seen.len(), 4);
+
+        state.repositories.pop();
+        let state = loaded(state, &[1, 2]);
+        let mut kept: Vec<u64> = state.last_seen.keys().map(|pr| pr.number).collect();
+        kept.sort_unstable();
+        assert_eq!(kept, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_navigation_closes_peek_and_moves_cursor() {
+        let state = reduce_pull_request(state_with_snoozed(&[]), &PullRequestAction::TogglePeek);
+        assert_eq!(
+            state.peek,
+            Some(PrPeekState {
+                repo_idx: 0,
+                pr_number: 1,
+                error: None,
+            })
+        );
+
+        let state = reduce_pull_request(state, &PullRequestAction::NavigateNext);
+        assert_eq!(state.peek, None);
+        assert_eq!(cursor(&state), 1);
+
+        let state = reduce_pull_request(state, &PullRequestAction::TogglePeek);
+        let state = reduce_pull_request(state, &PullRequestAction::TogglePeek);
+        assert_eq!(state.peek, None);
+    }
+
+    #[test]
+    fn test_peek_body_is_kept_until_pr_updates() {
+        let repo = Repository::new("org", "repo", "main");
+        let state = reduce_pull_request(state_with_snoozed(&[]), &PullRequestAction::TogglePeek);
+        let updated_at = state.repo_data[&0].prs[0].updated_at;
+        let (_, pr) = state.peeked_pr().unwrap();
+        assert_eq!(state.peek_body(&repo, pr), None);
+
+        let mut state = reduce_pull_request(
+            state,
+            &PullRequestAction::PeekBodyLoaded {
+                pr: repo.watched_pr(1),
+                updated_at,
+                body: "Fixes the parser".to_string(),
+            },
+        );
+        let (_, pr) = state.peeked_pr().unwrap();
+        assert_eq!(state.peek_body(&repo, pr), Some("Fixes the parser"));
+
+        state.repo_data.get_mut(&0).unwrap().prs[0].updated_at += chrono::TimeDelta::minutes(1);
+        let (_, pr) = state.peeked_pr().unwrap();
+        assert_eq!(state.peek_body(&repo, pr), None);
+
+        let state = reduce_pull_request(
+            state,
+            &PullRequestAction::PeekBodyFailed {
+                pr: repo.watched_pr(1),
+                error: "timeout".to_string(),
+            },
+        );
+        assert_eq!(state.peek.unwrap().error.as_deref(), Some("timeout"));
+    }
+
+    #[test]
+    fn test_operations_are_in_flight_until_finished() {
+        let repo = Repository::new("org", "repo", "main");
+        let state = reduce_pull_request(
+            state_with_snoozed(&[]),
+            &PullRequestAction::MergeStart {
+                repo: repo.clone(),
+                pr_number: 2,
+            },
+        );
+        assert!(state
+            .in_flight
+            .contains(&(repo.watched_pr(2), OperationType::Merge)));
+
+        let state = reduce_pull_request(
+            state,
+            &PullRequestAction::OperationFinished {
+                repo: repo.clone(),
+                pr_number: 2,
+                operation: OperationType::Merge,
+            },
+        );
+        assert!(state.in_flight.is_empty());
+    }
+
+    mod edge_cases {
+        use crate::actions::{Action, PullRequestAction};
+        use crate::reducers::test_harness::{
+            dispatch_all, loaded, pr, repo, selected_data, state_with_repos,
+        };
+        use gh_pr_config::TabCycling;
+
+        fn pr_action(action: PullRequestAction) -> Action {
+            Action::PullRequest(action)
+        }
+
+        #[test]
+        fn test_refresh_to_empty_list_while_pr_selected() {
+            let mut state = state_with_repos(1, &[1, 2, 3]);
+            dispatch_all(
+                &mut state,
+                [
+                    pr_action(PullRequestAction::NavigateToBottom),
+                    pr_action(PullRequestAction::ToggleSelection),
+                    loaded(0, &[]),
+                ],
+            );
+            assert_eq!(selected_data(&state).selected_pr, 0);
+            assert!(selected_data(&state).selected_pr_numbers.is_empty());
+
+            dispatch_all(
+                &mut state,
+                [
+                    pr_action(PullRequestAction::NavigateNext),
+                    pr_action(PullRequestAction::NavigatePrevious),
+                    pr_action(PullRequestAction::NavigateToBottom),
+                    pr_action(PullRequestAction::ToggleSelection),
+                    pr_action(PullRequestAction::SelectAll),
+                ],
+            );
+            assert_eq!(selected_data(&state).selected_pr, 0);
+            assert!(selected_data(&state).selected_pr_numbers.is_empty());
+        }
+
+        #[test]
+        fn test_repository_cycling() {
+            let next = || pr_action(PullRequestAction::RepositoryNext);
+            let previous = || pr_action(PullRequestAction::RepositoryPrevious);
+
+            let mut state = state_with_repos(3, &[1]);
+            dispatch_all(&mut state, [previous()]);
+            assert_eq!(state.main_view.selected_repository, 2);
+            dispatch_all(&mut state, [next()]);
+            assert_eq!(state.main_view.selected_repository, 0);
+
+            state.main_view.tab_cycling = TabCycling::Stop;
+            dispatch_all(&mut state, [previous(), next(), next(), next()]);
+            assert_eq!(state.main_view.selected_repository, 2);
+
+            let mut single = state_with_repos(1, &[1]);
+            dispatch_all(&mut single, [next(), previous()]);
+            assert_eq!(single.main_view.selected_repository, 0);
+
+            let mut empty = state_with_repos(0, &[]);
+            dispatch_all(&mut empty, [next(), previous()]);
+            assert_eq!(empty.main_view.selected_repository, 0);
+        }
+
+        #[test]
+        fn test_toggle_selection_of_vanished_pr() {
+            let mut state = state_with_repos(1, &[1, 2, 3]);
+            dispatch_all(
+                &mut state,
+                [
+                    pr_action(PullRequestAction::NavigateToBottom),
+                    pr_action(PullRequestAction::Prefetched {
+                        repo: repo(0),
+                        prs: vec![pr(1), pr(2)],
+                    }),
+                ],
+            );
+            // The cursor PR is gone, so the cursor falls back to the first PR
+            assert_eq!(selected_data(&state).selected_pr, 0);
+
+            // A stale cursor past the end toggles nothing
+            state.main_view.repo_data.get_mut(&0).unwrap().selected_pr = 5;
+            dispatch_all(&mut state, [pr_action(PullRequestAction::ToggleSelection)]);
+            assert!(selected_data(&state).selected_pr_numbers.is_empty());
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! Release Notes Reducer
+//!
+//! Tracks the release notes shown in the popup and their scroll position.
+//! Results of an earlier request (another repository or start) are dropped.
+
+use crate::actions::ReleaseNotesAction;
+use crate::domain_models::{Repository, SinceRef};
+use crate::state::ReleaseNotesState;
+
+/// Reduce release notes popup state
+pub fn reduce_release_notes(
+    mut state: ReleaseNotesState,
+    action: &ReleaseNotesAction,
+) -> ReleaseNotesState {
+    let last_line = state.line_count().saturating_sub(1);
+    match action {
+        ReleaseNotesAction::Generate { repo, since } => {
+            state = ReleaseNotesState {
+                target: Some((repo.clone(), since.clone())),
+                ..ReleaseNotesState::default()
+            };
+        }
+        ReleaseNotesAction::Loaded {
+            repo,
+            since,
+            markdown,
+            pr_count,
+        } => {
+            if is_target(&state, repo, since) {
+                state.markdown = Some(markdown.clone());
+                state.pr_count = *pr_count;
+                state.scroll_offset = 0;
+            }
+        }
+        ReleaseNotesAction::LoadError { repo, since, error } => {
+            if is_target(&state, repo, since) {
+                state.error = Some(error.clone());
+            }
+        }
+        ReleaseNotesAction::NavigateNext => {
+            state.scroll_offset = (state.scroll_offset + 1).min(last_line);
+        }
+        ReleaseNotesAction::NavigatePrevious => {
+            state.scroll_offset = state.scroll_offset.saturating_sub(1);
+        }
+        ReleaseNotesAction::NavigateToTop => state.scroll_offset = 0,
+        ReleaseNotesAction::NavigateToBottom => state.scroll_offset = last_line,
+        ReleaseNotesAction::Prepare | ReleaseNotesAction::Copy | ReleaseNotesAction::WriteFile => {}
+    }
+    state
+}
+
+/// Whether the popup waits for the notes of `repo` since `since`
+fn is_target(state: &ReleaseNotesState, repo: &Repository, since: &SinceRef) -> bool {
+    state
+        .target
+        .as_ref()
+        .is_some_and(|(target_repo, target_since)| target_repo == repo && target_since == since)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notes_of_other_requests_are_dropped() {
+        let repo = Repository::new("acme", "api", "main");
+        let v1 = SinceRef::Tag("v1.0.0".to_string());
+        let v2 = SinceRef::Tag("v2.0.0".to_string());
+        let loaded = |since: &SinceRef| ReleaseNotesAction::Loaded {
+            repo: repo.clone(),
+            since: since.clone(),
+            markdown: "## Changes\n\n### Features\n\n- Dark mode\n".to_string(),
+            pr_count: 1,
+        };
+
+        let state = reduce_release_notes(
+            ReleaseNotesState::default(),
+            &ReleaseNotesAction::Generate {
+                repo: repo.clone(),
+                since: v2.clone(),
+            },
+        );
+        let state = reduce_release_notes(state, &loaded(&v1));
+        assert!(state.markdown.is_none());
+
+        let state = reduce_release_notes(state, &loaded(&v2));
+        assert_eq!(state.pr_count, 1);
+
+        // Scrolling stops at the last line
+        let state = reduce_release_notes(state, &ReleaseNotesAction::NavigateToBottom);
+        assert_eq!(state.scroll_offset, 4);
+        let state = reduce_release_notes(state, &ReleaseNotesAction::NavigateNext);
+        assert_eq!(state.scroll_offset, 4);
+    }
+}
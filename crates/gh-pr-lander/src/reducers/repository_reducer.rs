@@ -1,11 +1,15 @@
 //! Repository Reducer
 //!
 //! Handles all repository-related state changes including:
-//! - Repository list management (add, remove)
+//! - Repository list management (add, remove, undo removal)
+//! - Switching workspace profiles
 //! - Add repository form state
 
 use crate::actions::RepositoryAction;
-use crate::state::{AddRepoField, AddRepoFormState, MainViewState};
+use crate::domain_models::{LoadingState, Repository, WatchedPr};
+use crate::state::{
+    AddRepoField, AddRepoFormState, MainViewState, PrSort, RemovedRepository, RepositoryData,
+};
 
 /// Reduce repository list state
 pub fn reduce_repository(mut state: MainViewState, action: &RepositoryAction) -> MainViewState {
@@ -16,40 +20,66 @@ pub fn reduce_repository(mut state: MainViewState, action: &RepositoryAction) ->
         RepositoryAction::AddRepository(repo) => {
             log::info!("Adding repository: {}", repo.display_name());
             state.repositories.push(repo.clone());
+            state.mark_session_dirty();
         }
         RepositoryAction::RemoveCurrentRepository => {
-            if !state.repositories.is_empty() {
-                let idx = state.selected_repository;
-                let removed = state.repositories.remove(idx);
-                log::info!("Removed repository: {}", removed.display_name());
-
-                // Also remove associated repo_data
-                state.repo_data.remove(&idx);
-
-                // Re-index repo_data for indices above the removed one
-                let keys_to_update: Vec<usize> = state
-                    .repo_data
-                    .keys()
-                    .filter(|&&k| k > idx)
-                    .copied()
-                    .collect();
-                for old_key in keys_to_update {
-                    if let Some(data) = state.repo_data.remove(&old_key) {
-                        state.repo_data.insert(old_key - 1, data);
-                    }
+            // Confirmation popup shown by middleware
+        }
+        RepositoryAction::RemoveRepository(repo) => {
+            if let Some(idx) = state.repositories.iter().position(|r| r == repo) {
+                state = remove_repository(state, idx);
+            }
+        }
+        RepositoryAction::UndoRemoveRepository => {
+            if let Some(removed) = state.removed_repository.take() {
+                if !state.repositories.contains(&removed.repo) {
+                    state = insert_repository(state, removed.index, removed.repo);
                 }
-
-                // Adjust selected index if necessary
-                if state.selected_repository >= state.repositories.len()
-                    && !state.repositories.is_empty()
-                {
-                    state.selected_repository = state.repositories.len() - 1;
+            }
+        }
+        RepositoryAction::LoadRepositoryData(repo)
+        | RepositoryAction::PreloadRepositoryData(repo) => {
+            // Fetched by middleware, a tab never loaded before shows it is loading
+            if let Some(idx) = state.repositories.iter().position(|r| r == repo) {
+                let data = state.repo_data.entry(idx).or_default();
+                if data.loading_state == LoadingState::Idle {
+                    data.loading_state = LoadingState::Loading;
                 }
             }
         }
-        RepositoryAction::LoadRepositoryData(_) => {
+        RepositoryAction::RefreshRepositoryData(_) => {
             // Side effect handled by middleware
         }
+        RepositoryAction::SwitchBranch { repo, branch } => {
+            if let Some(idx) = state.repositories.iter().position(|r| r == repo) {
+                state = switch_branch(state, idx, branch);
+            }
+        }
+        RepositoryAction::AccountResolved { account, login } => {
+            state.account_logins.insert(account.clone(), login.clone());
+            // The attention sort depends on who the user is
+            for repo_idx in 0..state.repositories.len() {
+                if state.repositories[repo_idx].account_key() == *account
+                    && state.sort_of(repo_idx) == PrSort::Attention
+                {
+                    state.apply_sort(repo_idx);
+                }
+            }
+        }
+        RepositoryAction::HostCapabilitiesResolved { host, capabilities } => {
+            state.host_capabilities.insert(host.clone(), *capabilities);
+        }
+        RepositoryAction::SwitchProfile(_) => {
+            // Profile loaded by middleware
+        }
+        RepositoryAction::ProfileActivated(profile) => {
+            let switched = state.profile.as_ref().map(|p| &p.name) != Some(&profile.name);
+            if switched && state.profile.is_some() {
+                state = drop_repositories(state);
+            }
+            state.profile = Some(profile.clone());
+            state.mark_session_dirty();
+        }
         // Form actions don't affect MainViewState
         RepositoryAction::FormNextField
         | RepositoryAction::FormPrevField
@@ -62,6 +92,127 @@ pub fn reduce_repository(mut state: MainViewState, action: &RepositoryAction) ->
     state
 }
 
+/// Remove the repository at `idx` with everything loaded for it
+///
+/// The PR data of the repositories after it moves down one index. Seen
+/// markers and descriptions of its PRs are dropped unless another tab tracks
+/// the same repository (e.g. a different branch); local notes and snoozes
+/// are kept.
+fn remove_repository(mut state: MainViewState, idx: usize) -> MainViewState {
+    let removed = state.repositories.remove(idx);
+    log::info!("Removed repository: {}", removed.display_name());
+
+    state.repo_data.remove(&idx);
+    state.bot_updates.remove_repo(&removed);
+    state.repo_data = std::mem::take(&mut state.repo_data)
+        .into_iter()
+        .map(|(k, data)| (if k > idx { k - 1 } else { k }, data))
+        .collect();
+
+    let repos = &state.repositories;
+    let orphaned = |pr: &WatchedPr| {
+        removed.contains_watched(pr) && !repos.iter().any(|repo| repo.contains_watched(pr))
+    };
+    state.last_seen.retain(|pr, _| !orphaned(pr));
+    state.peek_bodies.retain(|pr, _| !orphaned(pr));
+    state.peek = state.peek.take().and_then(|mut peek| match peek.repo_idx {
+        i if i == idx => None,
+        i if i > idx => {
+            peek.repo_idx -= 1;
+            Some(peek)
+        }
+        _ => Some(peek),
+    });
+
+    // Keep the same tab selected, or its neighbor within the list (0 once empty)
+    if state.selected_repository > idx {
+        state.selected_repository -= 1;
+    }
+    state.selected_repository = state
+        .selected_repository
+        .min(state.repositories.len().saturating_sub(1));
+
+    state.removed_repository = Some(RemovedRepository {
+        repo: removed,
+        index: idx,
+        removed_at: chrono::Local::now(),
+    });
+    state.mark_session_dirty();
+    state
+}
+
+/// Drop every tab with what was loaded and running for it
+///
+/// Operations still running finish, but their results no longer find their
+/// tab. Notes, snoozes and seen markers are kept.
+fn drop_repositories(mut state: MainViewState) -> MainViewState {
+    log::info!("Dropping {} repositories", state.repositories.len());
+    state.repositories.clear();
+    state.repo_data.clear();
+    state.selected_repository = 0;
+    state.in_flight.clear();
+    state.peek = None;
+    state.removed_repository = None;
+    state.bot_updates = Default::default();
+    state
+}
+
+/// Point the tab at `idx` to another base branch
+///
+/// The PRs of the old branch are dropped, the filter, sort, state shown and
+/// the repository-wide pulse are kept for the new one. The configured
+/// branch is what gets saved.
+fn switch_branch(mut state: MainViewState, idx: usize, branch: &str) -> MainViewState {
+    let old = state.repositories[idx].clone();
+    let repo = old.clone().with_base_branch(branch);
+    log::info!(
+        "Switched {} from base branch {} to {}",
+        repo.display_name(),
+        old.branch,
+        repo.branch
+    );
+    state.repositories[idx] = repo;
+
+    if let Some(data) = state.repo_data.remove(&idx) {
+        state.repo_data.insert(
+            idx,
+            RepositoryData {
+                current_filter: data.current_filter,
+                sort: data.sort,
+                pr_state: data.pr_state,
+                pulse: data.pulse,
+                pulse_requested: data.pulse_requested,
+                ..Default::default()
+            },
+        );
+    }
+    state.bot_updates.remove_repo(&old);
+    if state.peek.as_ref().is_some_and(|peek| peek.repo_idx == idx) {
+        state.peek = None;
+    }
+    state.mark_session_dirty();
+    state
+}
+
+/// Insert a repository at `idx` (or last) and select it
+fn insert_repository(mut state: MainViewState, idx: usize, repo: Repository) -> MainViewState {
+    let idx = idx.min(state.repositories.len());
+    log::info!("Restored repository: {}", repo.display_name());
+    state.repositories.insert(idx, repo);
+    state.repo_data = std::mem::take(&mut state.repo_data)
+        .into_iter()
+        .map(|(k, data)| (if k >= idx { k + 1 } else { k }, data))
+        .collect();
+    if let Some(ref mut peek) = state.peek {
+        if peek.repo_idx >= idx {
+            peek.repo_idx += 1;
+        }
+    }
+    state.selected_repository = idx;
+    state.mark_session_dirty();
+    state
+}
+
 /// Reduce add repository form state
 pub fn reduce_add_repo_form(
     mut state: AddRepoFormState,
@@ -149,8 +300,301 @@ pub fn reduce_add_repo_form(
         RepositoryAction::OpenRepositoryInBrowser
         | RepositoryAction::AddRepository(_)
         | RepositoryAction::RemoveCurrentRepository
-        | RepositoryAction::LoadRepositoryData(_) => {}
+        | RepositoryAction::RemoveRepository(_)
+        | RepositoryAction::UndoRemoveRepository
+        | RepositoryAction::LoadRepositoryData(_)
+        | RepositoryAction::PreloadRepositoryData(_)
+        | RepositoryAction::RefreshRepositoryData(_)
+        | RepositoryAction::SwitchBranch { .. }
+        | RepositoryAction::AccountResolved { .. }
+        | RepositoryAction::HostCapabilitiesResolved { .. }
+        | RepositoryAction::SwitchProfile(_)
+        | RepositoryAction::ProfileActivated(_) => {}
     }
 
     state
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::actions::{Action, PullRequestAction, RepositoryAction};
+    use crate::domain_models::LoadingState;
+    use crate::reducers::test_harness::{
+        dispatch_all, loaded, pr, repo, selected_data, state_with_repos,
+    };
+    use crate::state::{ActiveProfile, PrPeekState, PrSort};
+
+    fn remove(idx: usize) -> Action {
+        Action::Repository(RepositoryAction::RemoveRepository(repo(idx)))
+    }
+
+    fn undo() -> Action {
+        Action::Repository(RepositoryAction::UndoRemoveRepository)
+    }
+
+    fn activate(name: &str) -> Action {
+        Action::Repository(RepositoryAction::ProfileActivated(ActiveProfile {
+            name: name.to_string(),
+            names: vec!["work".to_string(), "oss".to_string()],
+            default_filter: None,
+            theme: None,
+        }))
+    }
+
+    #[test]
+    fn test_switching_profile_drops_the_tabs() {
+        let mut state = state_with_repos(3, &[1]);
+        dispatch_all(&mut state, [activate("work"), loaded(1, &[7, 8])]);
+        state.main_view.selected_repository = 2;
+        let seen = repo(1).watched_pr(7);
+        state
+            .main_view
+            .last_seen
+            .insert(seen.clone(), chrono::Utc::now());
+
+        // Activating the same profile again (bootstrap retry) keeps the tabs
+        dispatch_all(&mut state, [activate("work")]);
+        assert_eq!(state.main_view.repositories.len(), 3);
+        assert_eq!(state.main_view.repo_data[&1].prs.len(), 2);
+
+        dispatch_all(&mut state, [activate("oss")]);
+        assert!(state.main_view.repositories.is_empty());
+        assert!(state.main_view.repo_data.is_empty());
+        assert_eq!(state.main_view.selected_repository, 0);
+        assert_eq!(state.main_view.profile.as_ref().unwrap().name, "oss");
+        assert!(state.main_view.last_seen.contains_key(&seen));
+    }
+
+    #[test]
+    fn test_remove_first_repository() {
+        let mut state = state_with_repos(3, &[1]);
+        dispatch_all(&mut state, [loaded(1, &[7, 8])]);
+        state.main_view.selected_repository = 1;
+        let seen = repo(0).watched_pr(1);
+        state
+            .main_view
+            .last_seen
+            .insert(seen.clone(), chrono::Utc::now());
+        state.main_view.peek = Some(PrPeekState {
+            repo_idx: 2,
+            pr_number: 1,
+            error: None,
+        });
+
+        dispatch_all(&mut state, [remove(0)]);
+
+        // The selected tab stays selected and keeps its data
+        assert_eq!(state.main_view.repositories.len(), 2);
+        assert_eq!(state.main_view.selected_repository, 0);
+        assert_eq!(state.main_view.repositories[0].repo, "repo-1");
+        assert_eq!(selected_data(&state).prs.len(), 2);
+        assert!(!state.main_view.last_seen.contains_key(&seen));
+        assert_eq!(state.main_view.peek.as_ref().unwrap().repo_idx, 1);
+        let removed = state.main_view.removed_repository.as_ref().unwrap();
+        assert_eq!((removed.repo.clone(), removed.index), (repo(0), 0));
+    }
+
+    #[test]
+    fn test_remove_middle_repository_selects_neighbor() {
+        let mut state = state_with_repos(3, &[1]);
+        state.main_view.selected_repository = 1;
+        dispatch_all(&mut state, [loaded(2, &[7, 8])]);
+
+        dispatch_all(&mut state, [remove(1)]);
+
+        assert_eq!(state.main_view.selected_repository, 1);
+        assert_eq!(state.main_view.repositories[1].repo, "repo-2");
+        assert_eq!(selected_data(&state).prs.len(), 2);
+    }
+
+    #[test]
+    fn test_remove_last_repository_in_list() {
+        let mut state = state_with_repos(3, &[1, 2]);
+        state.main_view.selected_repository = 2;
+
+        dispatch_all(&mut state, [remove(2)]);
+
+        assert_eq!(state.main_view.repositories.len(), 2);
+        assert_eq!(state.main_view.selected_repository, 1);
+        assert_eq!(state.main_view.repo_data.len(), 2);
+        assert_eq!(selected_data(&state).prs.len(), 2);
+    }
+
+    #[test]
+    fn test_remove_with_stale_selection() {
+        let mut state = state_with_repos(2, &[1]);
+        state.main_view.selected_repository = 5;
+
+        dispatch_all(&mut state, [remove(1)]);
+
+        assert_eq!(state.main_view.repositories.len(), 1);
+        assert_eq!(state.main_view.selected_repository, 0);
+    }
+
+    #[test]
+    fn test_remove_only_repository() {
+        let mut state = state_with_repos(1, &[1]);
+
+        dispatch_all(&mut state, [remove(0), remove(0)]);
+
+        assert!(state.main_view.repositories.is_empty());
+        assert!(state.main_view.repo_data.is_empty());
+        assert_eq!(state.main_view.selected_repository, 0);
+    }
+
+    #[test]
+    fn test_remove_current_only_asks() {
+        let mut state = state_with_repos(2, &[1]);
+
+        dispatch_all(
+            &mut state,
+            [Action::Repository(
+                RepositoryAction::RemoveCurrentRepository,
+            )],
+        );
+
+        assert_eq!(state.main_view.repositories.len(), 2);
+    }
+
+    #[test]
+    fn test_undo_restores_position_and_settings() {
+        let mut state = state_with_repos(3, &[1]);
+        let profiled = repo(1).with_profile(Some("work".to_string()));
+        state.main_view.repositories[1] = profiled.clone();
+        state.main_view.selected_repository = 1;
+
+        dispatch_all(
+            &mut state,
+            [Action::Repository(RepositoryAction::RemoveRepository(
+                profiled.clone(),
+            ))],
+        );
+        state.main_view.selected_repository = 0;
+        dispatch_all(&mut state, [undo()]);
+
+        assert_eq!(state.main_view.repositories[1], profiled);
+        assert_eq!(state.main_view.repositories[2].repo, "repo-2");
+        assert_eq!(state.main_view.selected_repository, 1);
+        // The restored tab is empty until reloaded, the others keep their data
+        assert!(!state.main_view.repo_data.contains_key(&1));
+        assert_eq!(state.main_view.repo_data[&2].prs.len(), 1);
+        assert!(state.main_view.removed_repository.is_none());
+
+        // Nothing left to undo
+        dispatch_all(&mut state, [undo()]);
+        assert_eq!(state.main_view.repositories.len(), 3);
+    }
+
+    #[test]
+    fn test_switch_branch_keeps_tab_settings() {
+        let mut state = state_with_repos(2, &[1, 2]);
+        state.main_view.repo_data.get_mut(&1).unwrap().sort = Some(PrSort::Attention);
+        state.main_view.peek = Some(PrPeekState {
+            repo_idx: 1,
+            pr_number: 1,
+            error: None,
+        });
+
+        dispatch_all(
+            &mut state,
+            [Action::Repository(RepositoryAction::SwitchBranch {
+                repo: repo(1),
+                branch: "release/2.x".to_string(),
+            })],
+        );
+
+        let switched = &state.main_view.repositories[1];
+        assert_eq!(switched.branch, "release/2.x");
+        assert_eq!(switched.saved_branch(), repo(1).branch);
+        // The old branch's PRs are gone, the sort stays
+        let data = &state.main_view.repo_data[&1];
+        assert!(data.prs.is_empty());
+        assert_eq!(data.sort, Some(PrSort::Attention));
+        assert!(state.main_view.peek.is_none());
+        assert_eq!(state.main_view.repo_data[&0].prs.len(), 2);
+    }
+
+    #[test]
+    fn test_load_of_the_old_branch_after_a_switch_is_dropped() {
+        let mut state = state_with_repos(2, &[1, 2]);
+        let switched = repo(1).with_base_branch("release/2.x");
+        dispatch_all(
+            &mut state,
+            [
+                Action::Repository(RepositoryAction::SwitchBranch {
+                    repo: repo(1),
+                    branch: "release/2.x".to_string(),
+                }),
+                // Requested before the switch, arriving after it
+                loaded(1, &[1, 2, 3]),
+            ],
+        );
+
+        // Still the same tab, the PRs of main are not shown for release/2.x
+        assert_eq!(state.main_view.repositories[1], repo(1));
+        assert!(state.main_view.repo_data[&1].prs.is_empty());
+
+        dispatch_all(
+            &mut state,
+            [Action::PullRequest(PullRequestAction::Loaded {
+                repo: switched,
+                prs: vec![pr(7)],
+                from_cache: None,
+            })],
+        );
+        assert_eq!(state.main_view.repo_data[&1].prs.len(), 1);
+        assert_eq!(state.main_view.repo_data[&1].prs[0].number, 7);
+    }
+
+    #[test]
+    fn test_requested_load_marks_only_new_tabs_loading() {
+        let mut state = state_with_repos(1, &[1]);
+        dispatch_all(
+            &mut state,
+            [
+                Action::Repository(RepositoryAction::AddRepository(repo(1))),
+                Action::Repository(RepositoryAction::AddRepository(repo(2))),
+                Action::Repository(RepositoryAction::PreloadRepositoryData(repo(1))),
+                Action::Repository(RepositoryAction::LoadRepositoryData(repo(0))),
+            ],
+        );
+
+        let loading_state = |idx| {
+            state
+                .main_view
+                .repo_data
+                .get(&idx)
+                .map(|data| data.loading_state.clone())
+        };
+        // A loaded tab keeps showing its PRs while it is fetched again
+        assert_eq!(loading_state(0), Some(LoadingState::Loaded));
+        assert_eq!(loading_state(1), Some(LoadingState::Loading));
+        // Not requested yet, loads once opened
+        assert_eq!(loading_state(2), None);
+    }
+
+    #[test]
+    fn test_host_capabilities_default_to_supported() {
+        let mut state = state_with_repos(1, &[1]);
+        let feature = gh_client::Feature::MergeQueue;
+        assert!(state.main_view.host_supports(&repo(0), feature));
+
+        let capabilities = gh_client::HostCapabilities {
+            supports_merge_queue: false,
+            ..Default::default()
+        };
+        dispatch_all(
+            &mut state,
+            [Action::Repository(
+                RepositoryAction::HostCapabilitiesResolved {
+                    host: repo(0).effective_host().to_string(),
+                    capabilities,
+                },
+            )],
+        );
+        assert!(!state.main_view.host_supports(&repo(0), feature));
+        assert!(state
+            .main_view
+            .host_supports(&repo(0), gh_client::Feature::AutoMerge));
+    }
+}
@@ -0,0 +1,140 @@
+//! Reviewer Picker Reducer
+//!
+//! Handles the reviewer picker popup, including view stack management.
+
+use crate::actions::ReviewerPickerAction;
+use crate::state::{AppState, ReviewerPickerState};
+use crate::views::{ReviewerPickerView, ViewId};
+
+/// Reduce reviewer picker popup state based on actions.
+pub fn reduce_reviewer_picker(mut state: AppState, action: &ReviewerPickerAction) -> AppState {
+    match action {
+        ReviewerPickerAction::Show {
+            repo,
+            pr_number,
+            author,
+        } => {
+            if state.reviewer_picker.is_none() {
+                state.reviewer_picker = Some(ReviewerPickerState::new(
+                    repo.clone(),
+                    *pr_number,
+                    author.clone(),
+                ));
+                state.view_stack.push(Box::new(ReviewerPickerView::new()));
+            }
+        }
+
+        ReviewerPickerAction::Loaded {
+            suggested,
+            collaborators,
+        } => {
+            if let Some(ref mut picker) = state.reviewer_picker {
+                picker.set_candidates(suggested.clone(), collaborators.clone());
+            }
+        }
+
+        ReviewerPickerAction::LoadFailed(error) => {
+            if let Some(ref mut picker) = state.reviewer_picker {
+                picker.loading = false;
+                picker.error = Some(error.clone());
+            }
+        }
+
+        ReviewerPickerAction::InputChar(c) => {
+            if let Some(ref mut picker) = state.reviewer_picker {
+                picker.push_query(*c);
+            }
+        }
+
+        ReviewerPickerAction::InputBackspace => {
+            if let Some(ref mut picker) = state.reviewer_picker {
+                picker.pop_query();
+            }
+        }
+
+        ReviewerPickerAction::InputClear => {
+            if let Some(ref mut picker) = state.reviewer_picker {
+                picker.clear_query();
+            }
+        }
+
+        ReviewerPickerAction::NavigateNext => {
+            if let Some(ref mut picker) = state.reviewer_picker {
+                picker.select_next();
+            }
+        }
+
+        ReviewerPickerAction::NavigatePrevious => {
+            if let Some(ref mut picker) = state.reviewer_picker {
+                picker.select_previous();
+            }
+        }
+
+        ReviewerPickerAction::Toggle => {
+            if let Some(ref mut picker) = state.reviewer_picker {
+                if !picker.applying {
+                    picker.toggle_selected();
+                }
+            }
+        }
+
+        ReviewerPickerAction::Apply => {
+            if let Some(ref mut picker) = state.reviewer_picker {
+                picker.applying = true;
+            }
+        }
+
+        ReviewerPickerAction::Applied | ReviewerPickerAction::Close => {
+            state.reviewer_picker = None;
+            state.pop_view(ViewId::ReviewerPicker);
+        }
+
+        ReviewerPickerAction::Open => {}
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::{Owner, Repository};
+
+    fn shown() -> AppState {
+        reduce_reviewer_picker(
+            AppState::default(),
+            &ReviewerPickerAction::Show {
+                repo: Repository::new("acme", "api", "main"),
+                pr_number: 7,
+                author: "bob".to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_show_pushes_popup_while_loading() {
+        let state = shown();
+        assert_eq!(state.active_view().view_id(), ViewId::ReviewerPicker);
+        assert!(state.reviewer_picker.as_ref().unwrap().loading);
+
+        let state = reduce_reviewer_picker(
+            state,
+            &ReviewerPickerAction::Loaded {
+                suggested: vec![(Owner::Team("acme/core".to_string()), 2)],
+                collaborators: vec!["alice".to_string(), "bob".to_string()],
+            },
+        );
+        let picker = state.reviewer_picker.as_ref().unwrap();
+        assert!(!picker.loading);
+        assert_eq!(picker.choices.len(), 2);
+    }
+
+    #[test]
+    fn test_applied_closes_popup() {
+        let state = reduce_reviewer_picker(shown(), &ReviewerPickerAction::Apply);
+        assert!(state.reviewer_picker.as_ref().unwrap().applying);
+        let state = reduce_reviewer_picker(state, &ReviewerPickerAction::Applied);
+        assert!(state.reviewer_picker.is_none());
+        assert_eq!(state.active_view().view_id(), ViewId::Splash);
+    }
+}
@@ -5,7 +5,6 @@
 
 use crate::actions::SessionAction;
 use crate::state::MainViewState;
-use gh_pr_config::DEFAULT_HOST;
 
 /// Reduce session actions
 pub fn reduce_session(mut state: MainViewState, action: &SessionAction) -> MainViewState {
@@ -13,36 +12,25 @@ pub fn reduce_session(mut state: MainViewState, action: &SessionAction) -> MainV
         SessionAction::Loaded {
             selected_repo,
             selected_pr_no,
+            profile,
         } => {
             // Store session selection to restore after repositories load
             state.pending_session_repo = selected_repo.clone();
             state.pending_session_pr_no = *selected_pr_no;
+            state.pending_session_profile = profile.clone();
             log::info!(
-                "Session loaded: repo={:?}, pr_no={:?}",
+                "Session loaded: repo={:?}, pr_no={:?}, profile={:?}",
                 selected_repo,
-                selected_pr_no
+                selected_pr_no,
+                profile
             );
         }
 
         SessionAction::RestoreSelection => {
             // Apply pending session selection if repositories match
-            if let Some((org, name, branch, host)) = &state.pending_session_repo {
+            if let Some((org, name, ..)) = &state.pending_session_repo {
                 for (idx, repo) in state.repositories.iter().enumerate() {
-                    // Match all fields including host
-                    // Treat None and Some(DEFAULT_HOST) as equivalent
-                    let host_matches = match (&repo.host, host) {
-                        (None, None) => true,
-                        (None, Some(h)) if h == DEFAULT_HOST => true,
-                        (Some(a), None) if a == DEFAULT_HOST => true,
-                        (Some(a), Some(b)) => a == b,
-                        _ => false,
-                    };
-
-                    if repo.org == *org
-                        && repo.repo == *name
-                        && repo.branch == *branch
-                        && host_matches
-                    {
+                    if state.is_session_repo(repo) {
                         log::info!(
                             "Session: Restoring repository selection to index {} ({}/{})",
                             idx,
@@ -83,3 +71,46 @@ pub fn reduce_session(mut state: MainViewState, action: &SessionAction) -> MainV
     }
     state
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::actions::{Action, SessionAction};
+    use crate::reducers::test_harness::{dispatch_all, selected_data, state_with_repos};
+
+    fn restore(name: &str, pr_no: usize) -> [Action; 2] {
+        [
+            Action::Session(SessionAction::Loaded {
+                selected_repo: Some((
+                    "org".to_string(),
+                    name.to_string(),
+                    "main".to_string(),
+                    None,
+                )),
+                selected_pr_no: Some(pr_no),
+                profile: None,
+            }),
+            Action::Session(SessionAction::RestoreSelection),
+        ]
+    }
+
+    #[test]
+    fn test_restore_pr_missing_from_loaded_list() {
+        let mut state = state_with_repos(2, &[1, 2]);
+
+        dispatch_all(&mut state, restore("repo-1", 99));
+
+        assert_eq!(state.main_view.selected_repository, 1);
+        assert_eq!(selected_data(&state).selected_pr, 0);
+        assert!(state.main_view.pending_session_repo.is_none());
+    }
+
+    #[test]
+    fn test_restore_removed_repository() {
+        let mut state = state_with_repos(2, &[1, 2]);
+
+        dispatch_all(&mut state, restore("repo-7", 2));
+
+        assert_eq!(state.main_view.selected_repository, 0);
+        assert!(state.main_view.pending_session_pr_no.is_none());
+    }
+}
@@ -7,7 +7,8 @@ use crate::state::SplashState;
 pub fn reduce_splash(mut state: SplashState, action: &SplashAction) -> SplashState {
     match action {
         SplashAction::Tick => {
-            if state.bootstrapping {
+            // Freeze the animation while a failed stage waits for retry
+            if state.bootstrapping && !state.has_failed() {
                 // Advance animation frame (16 frames total for 5x5 snake)
                 state.animation_frame += 1;
             }
@@ -3,7 +3,8 @@
 //! Handles status bar state updates.
 
 use crate::actions::StatusBarAction;
-use crate::state::{StatusBarState, StatusMessage};
+use crate::state::{ActiveOperation, StatusBarState, StatusKind, StatusMessage};
+use std::time::Instant;
 
 /// Reduce status bar state
 pub fn reduce_status_bar(mut state: StatusBarState, action: &StatusBarAction) -> StatusBarState {
@@ -18,6 +19,66 @@ pub fn reduce_status_bar(mut state: StatusBarState, action: &StatusBarAction) ->
         StatusBarAction::Clear => {
             state.clear();
         }
+        StatusBarAction::OperationStarted { id, label, source } => {
+            // Keep the start in the history, the bar itself shows the operation
+            state.push(StatusMessage::new(
+                StatusKind::Running,
+                format!("{}...", label),
+                source.clone(),
+            ));
+            state.operations.push(ActiveOperation {
+                id: *id,
+                label: label.clone(),
+                source_action: source.clone(),
+                started_at: Instant::now(),
+            });
+        }
+        StatusBarAction::OperationFinished { id } => {
+            state.operations.retain(|op| op.id != *id);
+        }
+        StatusBarAction::ApiUsage(usage) => {
+            state.api_usage = Some(*usage);
+        }
+        StatusBarAction::Incident(incident) => {
+            state.incident = incident.clone();
+        }
+        StatusBarAction::WorkerUnresponsive(unresponsive) => {
+            state.worker_unresponsive = *unresponsive;
+        }
     }
     state
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::OperationId;
+
+    #[test]
+    fn test_operations_are_tracked_until_finished() {
+        let merge = OperationId::next();
+        let rebase = OperationId::next();
+        let state = reduce_status_bar(
+            StatusBarState::default(),
+            &StatusBarAction::started(merge, "Merging PR #1", "Merge"),
+        );
+        let state = reduce_status_bar(
+            state,
+            &StatusBarAction::started(rebase, "Updating branch for PR #2", "Rebase"),
+        );
+        assert_eq!(state.operations.len(), 2);
+        assert_eq!(
+            state.latest().unwrap().message,
+            "Updating branch for PR #2..."
+        );
+
+        let state = reduce_status_bar(state, &StatusBarAction::finished(merge));
+        assert_eq!(state.operations.len(), 1);
+        assert_eq!(state.oldest_operation().unwrap().id, rebase);
+
+        // Finishing twice is harmless
+        let state = reduce_status_bar(state, &StatusBarAction::finished(merge));
+        let state = reduce_status_bar(state, &StatusBarAction::finished(rebase));
+        assert!(state.operations.is_empty());
+    }
+}
@@ -0,0 +1,62 @@
+//! Reducer test harness
+//!
+//! `AppState` fixtures and a `dispatch_all` helper running actions through the
+//! root reducer, for tests of edge cases spanning several sub-reducers.
+
+use crate::actions::{Action, GlobalAction, PullRequestAction, RepositoryAction};
+use crate::domain_models::{Pr, Repository};
+use crate::reducers::app_reducer::reduce;
+use crate::state::{AppState, RepositoryData};
+use crate::views::PullRequestView;
+
+/// Repository `org/repo-<idx>`
+pub fn repo(idx: usize) -> Repository {
+    Repository::new("org", format!("repo-{}", idx), "main")
+}
+
+/// Open PR with the given number
+pub fn pr(number: usize) -> Pr {
+    Pr::new(number, format!("PR {}", number), "alice", "abc")
+}
+
+/// Loaded action replacing the PRs of `repo(idx)` with the given numbers
+pub fn loaded(idx: usize, numbers: &[usize]) -> Action {
+    Action::PullRequest(PullRequestAction::Loaded {
+        repo: repo(idx),
+        prs: numbers.iter().map(|&number| pr(number)).collect(),
+        from_cache: None,
+    })
+}
+
+/// PR view with `repos` repositories, each loaded with the PRs `numbers`
+pub fn state_with_repos(repos: usize, numbers: &[usize]) -> AppState {
+    let mut state = AppState::default();
+    dispatch_all(
+        &mut state,
+        [Action::Global(GlobalAction::ReplaceView(Box::new(
+            PullRequestView::new(),
+        )))],
+    );
+    for idx in 0..repos {
+        dispatch_all(
+            &mut state,
+            [
+                Action::Repository(RepositoryAction::AddRepository(repo(idx))),
+                loaded(idx, numbers),
+            ],
+        );
+    }
+    state
+}
+
+/// Run `actions` through the root reducer, in order
+pub fn dispatch_all(state: &mut AppState, actions: impl IntoIterator<Item = Action>) {
+    for action in actions {
+        *state = reduce(std::mem::take(state), &action);
+    }
+}
+
+/// Data of the selected repository
+pub fn selected_data(state: &AppState) -> &RepositoryData {
+    &state.main_view.repo_data[&state.main_view.selected_repository]
+}
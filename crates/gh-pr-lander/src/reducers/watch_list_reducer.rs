@@ -0,0 +1,65 @@
+//! Watch List Reducer
+//!
+//! Handles state updates for PRs watched across repositories.
+
+use crate::actions::WatchListAction;
+use crate::state::{WatchEntry, WatchListState};
+
+/// Reducer for watch list state
+///
+/// Accepts only WatchListAction, making it type-safe and focused.
+pub fn reduce_watch_list(mut state: WatchListState, action: &WatchListAction) -> WatchListState {
+    match action {
+        WatchListAction::Loaded(watched) => {
+            state.entries = watched
+                .iter()
+                .map(|pr| WatchEntry {
+                    pr: pr.clone(),
+                    status: None,
+                })
+                .collect();
+            state.selected = 0;
+            log::info!("Watch list restored with {} PRs", state.entries.len());
+        }
+
+        WatchListAction::StatusFetched { pr, status } => {
+            if let Some(entry) = state.entries.iter_mut().find(|entry| entry.pr == *pr) {
+                entry.status = Some(status.clone());
+            }
+        }
+
+        WatchListAction::Remove(pr) => {
+            state.remove(pr);
+        }
+
+        WatchListAction::NavigateNext => {
+            if !state.entries.is_empty() {
+                state.selected = (state.selected + 1) % state.entries.len();
+            }
+        }
+
+        WatchListAction::NavigatePrevious => {
+            if !state.entries.is_empty() {
+                state.selected = state
+                    .selected
+                    .checked_sub(1)
+                    .unwrap_or(state.entries.len() - 1);
+            }
+        }
+
+        WatchListAction::NavigateToTop => {
+            state.selected = 0;
+        }
+
+        WatchListAction::NavigateToBottom => {
+            state.selected = state.entries.len().saturating_sub(1);
+        }
+
+        // Handled by middleware (API calls, browser) or the app reducer (view stack)
+        WatchListAction::Check
+        | WatchListAction::Open
+        | WatchListAction::OpenSelected
+        | WatchListAction::RemoveSelected => {}
+    }
+    state
+}
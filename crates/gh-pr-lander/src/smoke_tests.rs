@@ -0,0 +1,660 @@
+//! End-to-end smoke tests
+//!
+//! Drive the real store, reducers and middleware chain with scripted key
+//! presses, GitHub being a [`MockGitHubClient`]. API calls run on a
+//! current-thread runtime and the middleware is stepped one action at a
+//! time, so each script processes the same actions in the same order.
+//!
+//! Middleware reading or writing local files (session, config, notes, ...)
+//! is left out, the recent repositories are given to the repository
+//! middleware.
+
+use crate::actions::{Action, BootstrapAction, Event, GlobalAction};
+use crate::background::ActionProcessor;
+use crate::dispatcher::Dispatcher;
+use crate::domain_models::LoadingState;
+use crate::middleware::test_clients::{
+    green, pull_request, settle, test_runtime, MockClientFactory, MockGitHubClient,
+};
+use crate::middleware::{
+    bootstrap_middleware::BootstrapMiddleware, ci_middleware::CiMiddleware,
+    command_palette_middleware::CommandPaletteMiddleware,
+    confirmation_popup_middleware::ConfirmationPopupMiddleware,
+    diff_viewer_middleware::DiffViewerMiddleware, github_context::GitHubContext,
+    github_middleware::GitHubMiddleware, keyboard_middleware::KeyboardMiddleware,
+    logging_middleware, navigation_middleware::NavigationMiddleware,
+    pr_load_middleware::PrLoadMiddleware, pr_mutation_middleware::PrMutationMiddleware,
+    pull_request_middleware::PullRequestMiddleware, quit_middleware::QuitMiddleware,
+    repository_middleware::RepositoryMiddleware, text_input_middleware::TextInputMiddleware,
+    Middleware, MiddlewareChain,
+};
+use crate::state::{AppState, BootstrapStage, ConfirmationIntent, StageStatus, StatusKind};
+use crate::store::Store;
+use crate::views::ViewId;
+use gh_client::types::{MergeResult, RepositorySettings};
+use gh_pr_config::{
+    AppConfig, RecentRepository, RepoSettings, WorkspaceProfile, WorkspaceProfiles,
+};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+/// Actions one step may process before the script is considered looping
+const MAX_ACTIONS_PER_STEP: usize = 10_000;
+
+/// The app wired like `main`, on a mock GitHub client
+struct App {
+    runtime: Runtime,
+    client: Arc<MockGitHubClient>,
+    processor: ActionProcessor,
+    store: Store,
+    action_tx: Sender<Action>,
+    action_rx: Receiver<Action>,
+    result_rx: Receiver<Action>,
+}
+
+impl App {
+    /// App tracking the `acme` repositories `repos`
+    ///
+    /// The client of github.com is set up before bootstrap (with a token
+    /// unless `anonymous`), so no real token is ever resolved.
+    fn new(client: MockGitHubClient, repos: &[&str], anonymous: bool) -> Self {
+        let recent = repos
+            .iter()
+            .map(|name| RecentRepository::new("acme", *name, "main"))
+            .collect();
+        Self::with_profiles(
+            client,
+            WorkspaceProfiles::from_repositories(recent),
+            anonymous,
+        )
+    }
+
+    /// App tracking the repositories of workspace profiles, the first one active
+    fn with_profiles(
+        client: MockGitHubClient,
+        profiles: WorkspaceProfiles,
+        anonymous: bool,
+    ) -> Self {
+        let runtime = test_runtime();
+        let factory = Arc::new(MockClientFactory {
+            client: Arc::new(client),
+            anonymous,
+        });
+        let github = GitHubContext::with_clients(factory.clone(), runtime.handle().clone());
+        // The client's connection worker would keep the test runtime from
+        // settling, it is never used to reach GitHub
+        test_runtime()
+            .block_on(async {
+                let mut manager = github.client_manager.lock().await;
+                if anonymous {
+                    manager.init_anonymous_client(None, None)
+                } else {
+                    manager.init_client_with_token(None, None, "test-token".to_string())
+                }
+            })
+            .expect("Failed to create the GitHub client");
+
+        let middleware: MiddlewareChain = vec![
+            Box::new(logging_middleware()),
+            Box::new(BootstrapMiddleware::new()),
+            Box::new(GitHubMiddleware::new(github.clone())),
+            Box::new(PrLoadMiddleware::new(github.clone())),
+            Box::new(PrMutationMiddleware::new(github.clone())),
+            Box::new(CiMiddleware::new(github)),
+            Box::new(KeyboardMiddleware::new()),
+            Box::new(NavigationMiddleware::new()),
+            Box::new(TextInputMiddleware::new()),
+            Box::new(DiffViewerMiddleware::new()),
+            Box::new(CommandPaletteMiddleware::new()),
+            Box::new(ConfirmationPopupMiddleware::new()),
+            Box::new(QuitMiddleware::new()),
+            Box::new(RepositoryMiddleware::with_profiles(profiles)),
+            Box::new(PullRequestMiddleware::new()),
+        ];
+
+        let (action_tx, action_rx) = channel();
+        let (result_tx, result_rx) = channel();
+        Self {
+            runtime,
+            client: Arc::clone(&factory.client),
+            processor: ActionProcessor::new(middleware, action_tx.clone(), result_tx),
+            store: Store::new(AppState::default()),
+            action_tx,
+            action_rx,
+            result_rx,
+        }
+    }
+
+    /// App after bootstrap, like on a 120x40 terminal
+    fn started(client: MockGitHubClient, repos: &[&str], anonymous: bool) -> Self {
+        Self::new(client, repos, anonymous).start()
+    }
+
+    /// App reading `config` instead of the defaults
+    fn with_config(mut self, config: AppConfig) -> Self {
+        self.store = Store::new(AppState {
+            app_config: config,
+            ..Default::default()
+        });
+        self
+    }
+
+    /// Bootstrap, like on a 120x40 terminal
+    fn start(mut self) -> Self {
+        self.dispatch(Action::Global(GlobalAction::Resized {
+            width: 120,
+            height: 40,
+        }));
+        self.dispatch(Action::Bootstrap(BootstrapAction::Start));
+        self
+    }
+
+    fn state(&self) -> &AppState {
+        self.store.state()
+    }
+
+    /// Send `action` like the main loop does, then process until idle
+    fn dispatch(&mut self, action: Action) {
+        self.action_tx.send(action).unwrap();
+        self.run_until_idle();
+    }
+
+    /// Send `action` and process the queued actions, leaving the API calls
+    /// they spawned running (e.g. waiting on a slow client)
+    fn send(&mut self, action: Action) {
+        self.action_tx.send(action).unwrap();
+        while let Ok(action) = self.action_rx.try_recv() {
+            let state = self.store.state().clone();
+            assert!(self.processor.process(action, &state));
+            self.reduce_results();
+        }
+    }
+
+    /// Press the keys one after another, each processed until idle
+    fn press(&mut self, keys: &[KeyCode]) {
+        for code in keys {
+            self.dispatch(key(*code));
+        }
+    }
+
+    /// Type the characters of `keys` (e.g. a key sequence like "pm")
+    fn type_keys(&mut self, keys: &str) {
+        let codes: Vec<KeyCode> = keys.chars().map(KeyCode::Char).collect();
+        self.press(&codes);
+    }
+
+    /// Process actions and the API calls they spawn until none are left
+    ///
+    /// The background worker and the main loop in one: each action runs
+    /// through the middleware, what it forwards is reduced before the next
+    /// action, so middleware always sees the latest state.
+    fn run_until_idle(&mut self) {
+        let mut queue = VecDeque::new();
+        let mut processed = 0;
+        loop {
+            queue.extend(self.action_rx.try_iter());
+            if queue.is_empty() {
+                settle(&self.runtime);
+                queue.extend(self.action_rx.try_iter());
+                if queue.is_empty() {
+                    return;
+                }
+            }
+            while let Some(action) = queue.pop_front() {
+                let state = self.store.state().clone();
+                assert!(self.processor.process(action, &state));
+                self.reduce_results();
+                processed += 1;
+                assert!(
+                    processed < MAX_ACTIONS_PER_STEP,
+                    "actions keep coming, top view: {:?}",
+                    self.store.state().view_stack.last().map(|v| v.view_id())
+                );
+            }
+        }
+    }
+
+    /// Apply the forwarded actions, events re-enter the middleware
+    fn reduce_results(&mut self) {
+        while let Ok(action) = self.result_rx.try_recv() {
+            match action {
+                Action::Event(event) => self.action_tx.send(Action::Event(event)).unwrap(),
+                action => self.store.dispatch(action),
+            }
+        }
+    }
+
+    fn top_view(&self) -> Option<ViewId> {
+        self.state().view_stack.last().map(|view| view.view_id())
+    }
+
+    /// Numbers of the PRs shown for the repository at `idx`
+    fn pr_numbers(&self, idx: usize) -> Vec<usize> {
+        self.state().main_view.repo_data[&idx]
+            .prs
+            .iter()
+            .map(|pr| pr.number)
+            .collect()
+    }
+
+    /// Status bar messages of a kind, oldest first
+    fn messages(&self, kind: StatusKind) -> Vec<String> {
+        self.state()
+            .status_bar
+            .messages
+            .iter()
+            .filter(|message| message.kind == kind)
+            .map(|message| message.message.clone())
+            .collect()
+    }
+
+    /// API calls of a method made so far
+    fn calls(&self, method: &str) -> Vec<String> {
+        self.client
+            .calls()
+            .into_iter()
+            .filter(|call| call.split(' ').next() == Some(method))
+            .collect()
+    }
+}
+
+/// Key press of `code` without modifiers
+fn key(code: KeyCode) -> Action {
+    Action::Global(GlobalAction::KeyPressed(KeyEvent::new(
+        code,
+        KeyModifiers::NONE,
+    )))
+}
+
+/// GitHub answering with three open PRs for every repository
+fn github() -> MockGitHubClient {
+    MockGitHubClient {
+        pull_requests: vec![
+            pull_request(1, "sha1"),
+            pull_request(2, "sha2"),
+            pull_request(3, "sha3"),
+        ],
+        ci_status: Some(green()),
+        repository_settings: Some(RepositorySettings {
+            default_branch: "main".to_string(),
+            delete_branch_on_merge: Some(false),
+        }),
+        merge_result: Some(MergeResult {
+            merged: true,
+            sha: Some("merged".to_string()),
+            message: "Pull Request successfully merged".to_string(),
+            rejection: None,
+        }),
+        diff: Some(
+            "diff --git a/src/lib.rs b/src/lib.rs\n\
+             index 1111111..2222222 100644\n\
+             --- a/src/lib.rs\n\
+             +++ b/src/lib.rs\n\
+             @@ -1,2 +1,2 @@\n \
+             fn main() {}\n\
+             -const A: u8 = 1;\n\
+             +const A: u8 = 2;\n"
+                .to_string(),
+        ),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_bootstrap_loads_the_recent_repositories() {
+    let app = App::started(github(), &["api"], false);
+
+    let splash = &app.state().splash;
+    assert!(!splash.has_failed() && !splash.read_only());
+    for stage in [
+        BootstrapStage::ResolveToken,
+        BootstrapStage::InitClient,
+        BootstrapStage::LoadRecentRepos,
+        BootstrapStage::LoadFirstRepo,
+    ] {
+        assert!(
+            splash.stages.contains(&(stage, StageStatus::Done)),
+            "{:?}: {:?}",
+            stage,
+            splash.stages
+        );
+    }
+    assert_eq!(app.top_view(), Some(ViewId::PullRequestView));
+
+    let main_view = &app.state().main_view;
+    assert_eq!(main_view.repositories.len(), 1);
+    assert_eq!(main_view.repo_data[&0].loading_state, LoadingState::Loaded);
+    assert_eq!(app.pr_numbers(0), vec![1, 2, 3]);
+    assert_eq!(
+        main_view.account_logins.get("github.com"),
+        Some(&"bob".to_string())
+    );
+    assert_eq!(
+        app.calls("fetch_pull_requests"),
+        ["fetch_pull_requests acme/api"]
+    );
+    assert!(app.messages(StatusKind::Error).is_empty());
+}
+
+#[test]
+fn test_bootstrap_without_token_is_read_only() {
+    let mut app = App::started(github(), &["api"], true);
+
+    // Public PRs still load, without a token nothing can be changed
+    assert!(app.state().splash.read_only());
+    assert!(!app.state().splash.has_failed());
+    assert_eq!(app.top_view(), Some(ViewId::PullRequestView));
+    assert_eq!(app.pr_numbers(0), vec![1, 2, 3]);
+    assert!(app.state().main_view.account_logins.is_empty());
+
+    app.type_keys("pm");
+    assert!(app.state().confirmation_popup.is_none());
+    assert!(app
+        .messages(StatusKind::Warning)
+        .iter()
+        .any(|message| message.starts_with("read-only")));
+    assert!(app.calls("fetch_repository_settings").is_empty());
+    assert!(app.calls("merge_pull_request").is_empty());
+}
+
+/// Fails the first client initialization and counts bootstrap starts
+struct FlakyClientInit {
+    failed: bool,
+    starts: Arc<AtomicUsize>,
+}
+
+impl Middleware<Action, AppState> for FlakyClientInit {
+    fn handle(&mut self, action: &Action, _state: &AppState, dispatcher: &Dispatcher) -> bool {
+        match action {
+            Action::Bootstrap(BootstrapAction::Start) => {
+                self.starts.fetch_add(1, Ordering::SeqCst);
+                true
+            }
+            Action::Bootstrap(BootstrapAction::StageCompleted(BootstrapStage::InitClient))
+                if !self.failed =>
+            {
+                self.failed = true;
+                dispatcher.dispatch(Action::Bootstrap(BootstrapAction::StageFailed(
+                    BootstrapStage::InitClient,
+                    "connection refused".to_string(),
+                )));
+                false
+            }
+            Action::Event(Event::ClientReady) => self.failed,
+            _ => true,
+        }
+    }
+}
+
+#[test]
+fn test_retrying_bootstrap_runs_only_the_failed_stage() {
+    let starts = Arc::new(AtomicUsize::new(0));
+    let mut app = App::new(github(), &["api"], false);
+    app.processor.insert_middleware(
+        1,
+        Box::new(FlakyClientInit {
+            failed: false,
+            starts: Arc::clone(&starts),
+        }),
+    );
+    let mut app = app.start();
+    assert!(app.state().splash.has_failed());
+    assert_eq!(app.top_view(), Some(ViewId::Splash));
+
+    app.type_keys("r");
+
+    // Start (loading config and session) is not run again
+    assert_eq!(starts.load(Ordering::SeqCst), 1);
+    let splash = &app.state().splash;
+    assert!(!splash.has_failed());
+    assert!(splash
+        .stages
+        .contains(&(BootstrapStage::InitClient, StageStatus::Done)));
+    assert_eq!(app.top_view(), Some(ViewId::PullRequestView));
+    assert_eq!(app.state().main_view.repositories.len(), 1);
+    assert_eq!(app.pr_numbers(0), vec![1, 2, 3]);
+}
+
+/// Panics on every key press, like a middleware with a bug would
+struct PanicOnKeys;
+
+impl Middleware<Action, AppState> for PanicOnKeys {
+    fn handle(&mut self, action: &Action, _state: &AppState, _dispatcher: &Dispatcher) -> bool {
+        if matches!(action, Action::Global(GlobalAction::KeyPressed(_))) {
+            panic!("key handling bug");
+        }
+        true
+    }
+}
+
+#[test]
+fn test_panicking_middleware_leaves_the_app_working() {
+    let mut app = App::started(github(), &["api", "web"], false);
+    app.processor.insert_middleware(1, Box::new(PanicOnKeys));
+
+    app.press(&[KeyCode::Tab]);
+    assert_eq!(app.state().main_view.selected_repository, 1);
+    assert_eq!(
+        app.messages(StatusKind::Error),
+        ["PanicOnKeys failed: key handling bug"]
+    );
+
+    // Disabled after repeated panics, the keys keep working
+    app.press(&[KeyCode::Tab, KeyCode::Tab, KeyCode::Tab]);
+    assert_eq!(app.state().main_view.selected_repository, 0);
+    assert_eq!(app.messages(StatusKind::Error).len(), 3);
+    assert_eq!(
+        app.messages(StatusKind::Warning),
+        ["PanicOnKeys disabled after 3 failures, restart to enable it again"]
+    );
+}
+
+#[test]
+fn test_switching_between_two_repositories() {
+    let mut app = App::started(github(), &["api", "web"], false);
+
+    let main_view = &app.state().main_view;
+    assert_eq!(main_view.repositories.len(), 2);
+    assert_eq!(main_view.selected_repository, 0);
+    assert_eq!(app.pr_numbers(1), vec![1, 2, 3]);
+    let mut loaded = app.calls("fetch_pull_requests");
+    loaded.sort();
+    assert_eq!(
+        loaded,
+        [
+            "fetch_pull_requests acme/api",
+            "fetch_pull_requests acme/web"
+        ]
+    );
+
+    app.press(&[KeyCode::Tab]);
+    assert_eq!(app.state().main_view.selected_repository, 1);
+    app.press(&[KeyCode::Tab]);
+    assert_eq!(app.state().main_view.selected_repository, 0);
+    // Both were loaded at startup, switching does not load them again
+    assert_eq!(app.calls("fetch_pull_requests").len(), 2);
+}
+
+#[test]
+fn test_startup_load_leaves_later_tabs_until_opened() {
+    let config = AppConfig {
+        startup_load: Some(2),
+        ..Default::default()
+    };
+    let mut app = App::new(github(), &["api", "web", "docs"], false)
+        .with_config(config)
+        .start();
+
+    let mut loaded = app.calls("fetch_pull_requests");
+    loaded.sort();
+    assert_eq!(
+        loaded,
+        [
+            "fetch_pull_requests acme/api",
+            "fetch_pull_requests acme/web"
+        ]
+    );
+    assert!(!app.state().main_view.repo_data.contains_key(&2));
+    assert!(app.messages(StatusKind::Success).iter().any(|message| {
+        message.starts_with("2/2 repositories loaded in ")
+            && message.ends_with(", 1 more load when opened")
+    }));
+
+    app.press(&[KeyCode::Tab, KeyCode::Tab]);
+    assert_eq!(app.state().main_view.selected_repository, 2);
+    assert_eq!(app.pr_numbers(2), vec![1, 2, 3]);
+    // Opened again, loaded tabs are not fetched again
+    app.press(&[KeyCode::Tab]);
+    assert_eq!(app.calls("fetch_pull_requests").len(), 3);
+}
+
+#[test]
+fn test_switching_profiles_replaces_the_tabs() {
+    let profile = |name: &str, repos: &[&str]| WorkspaceProfile {
+        repositories: repos
+            .iter()
+            .map(|repo| RecentRepository::new("acme", *repo, "main"))
+            .collect(),
+        ..WorkspaceProfile::new(name)
+    };
+    let profiles = WorkspaceProfiles {
+        profiles: vec![profile("work", &["api", "web"]), profile("oss", &["docs"])],
+    };
+    let mut app = App::with_profiles(github(), profiles, false).start();
+    assert_eq!(app.state().main_view.repositories.len(), 2);
+
+    // "Switch profile…" lists the other profiles in the command palette
+    app.type_keys("rp");
+    assert_eq!(app.top_view(), Some(ViewId::CommandPalette));
+    app.press(&[KeyCode::Enter]);
+
+    let main_view = &app.state().main_view;
+    assert_eq!(main_view.profile.as_ref().unwrap().name, "oss");
+    assert_eq!(main_view.repositories.len(), 1);
+    assert_eq!(main_view.repositories[0].repo, "docs");
+    assert_eq!(main_view.selected_repository, 0);
+    assert_eq!(app.pr_numbers(0), vec![1, 2, 3]);
+    assert_eq!(app.top_view(), Some(ViewId::PullRequestView));
+    assert!(app
+        .messages(StatusKind::Info)
+        .contains(&"Switched to profile oss".to_string()));
+    assert!(app.messages(StatusKind::Error).is_empty());
+}
+
+#[test]
+fn test_merging_selected_prs() {
+    let mut app = App::started(github(), &["api"], false);
+
+    // Selecting moves the cursor on to the next PR
+    app.press(&[KeyCode::Char(' '), KeyCode::Char(' ')]);
+    let selected = &app.state().main_view.repo_data[&0].selected_pr_numbers;
+    assert_eq!(selected.len(), 2);
+    assert!(selected.contains(&1) && selected.contains(&2));
+
+    app.type_keys("pm");
+    let popup = app
+        .state()
+        .confirmation_popup
+        .as_ref()
+        .expect("confirmation");
+    let ConfirmationIntent::Merge {
+        pr_numbers,
+        delete_branch,
+    } = &popup.intent
+    else {
+        panic!("merge confirmation expected, got {:?}", popup.intent);
+    };
+    let mut pr_numbers = pr_numbers.clone();
+    pr_numbers.sort();
+    assert_eq!(pr_numbers, vec![1, 2]);
+    assert!(!delete_branch);
+    assert!(app.calls("merge_pull_request").is_empty());
+
+    app.press(&[KeyCode::Enter]);
+    assert!(app.state().confirmation_popup.is_none());
+    let mut merged = app.calls("merge_pull_request");
+    merged.sort();
+    assert_eq!(
+        merged,
+        [
+            "merge_pull_request acme/api#1",
+            "merge_pull_request acme/api#2"
+        ]
+    );
+    let successes = app.messages(StatusKind::Success);
+    assert!(successes.contains(&"PR #1 merged".to_string()));
+    assert!(successes.contains(&"PR #2 merged".to_string()));
+    assert!(app.calls("delete_branch").is_empty());
+    // The list was reloaded after the merges
+    assert!(app.calls("fetch_pull_requests").len() > 1);
+}
+
+#[test]
+fn test_merging_again_while_a_slow_merge_runs() {
+    let mut client = github();
+    client
+        .delays
+        .insert("merge_pull_request", Duration::from_millis(200));
+    // Branch deletion is configured, so the confirmation needs no API call
+    let mut config = AppConfig::default();
+    config.repos.insert(
+        "acme/api".to_string(),
+        RepoSettings {
+            delete_branch_after_merge: Some(false),
+            ..Default::default()
+        },
+    );
+    let mut app = App::new(client, &["api"], false)
+        .with_config(config)
+        .start();
+
+    for _ in 0..2 {
+        // Merge the PR under the cursor and confirm, without waiting for GitHub
+        for code in [KeyCode::Char('p'), KeyCode::Char('m'), KeyCode::Enter] {
+            app.send(key(code));
+        }
+        // Long enough for the merge to reach GitHub, not for it to answer
+        app.runtime
+            .block_on(async { tokio::time::sleep(Duration::from_millis(20)).await });
+    }
+    app.run_until_idle();
+
+    assert_eq!(
+        app.calls("merge_pull_request"),
+        ["merge_pull_request acme/api#1"]
+    );
+    assert!(app
+        .messages(StatusKind::Warning)
+        .contains(&"merge already in progress for #1".to_string()));
+    assert!(app
+        .messages(StatusKind::Success)
+        .contains(&"PR #1 merged".to_string()));
+}
+
+#[test]
+fn test_opening_and_closing_the_diff_viewer() {
+    let mut app = App::started(github(), &["api"], false);
+
+    app.press(&[KeyCode::Char('j')]);
+    app.type_keys("dd");
+    assert_eq!(app.top_view(), Some(ViewId::DiffViewer));
+    let diff_viewer = &app.state().diff_viewer;
+    assert_eq!(diff_viewer.pr_number, Some(2));
+    assert!(diff_viewer.inner.is_some());
+    assert_eq!(
+        app.calls("fetch_pull_request_diff"),
+        ["fetch_pull_request_diff acme/api#2"]
+    );
+    assert_eq!(
+        app.calls("fetch_review_comments"),
+        ["fetch_review_comments acme/api#2"]
+    );
+
+    app.press(&[KeyCode::Esc]);
+    assert_eq!(app.top_view(), Some(ViewId::PullRequestView));
+}
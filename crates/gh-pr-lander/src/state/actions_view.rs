@@ -0,0 +1,46 @@
+//! Actions View State
+//!
+//! Recent workflow runs of a repository branch, independent of any PR.
+
+use super::BuildLogRunContext;
+use crate::domain_models::Repository;
+use gh_client::WorkflowRun;
+
+/// Number of workflow runs listed in the Actions view
+pub const ACTIONS_VIEW_RUN_LIMIT: usize = 30;
+
+/// State of the Actions (workflow run history) view
+#[derive(Debug, Clone, Default)]
+pub struct ActionsViewState {
+    /// Repository the runs belong to
+    pub repo: Option<Repository>,
+    /// Branch the runs are listed for
+    pub branch: String,
+    /// Workflow runs, newest first
+    pub runs: Vec<WorkflowRun>,
+    /// Cursor position in `runs`
+    pub selected: usize,
+    /// Runs are being fetched
+    pub loading: bool,
+    /// Fetching the runs failed
+    pub error: Option<String>,
+}
+
+impl ActionsViewState {
+    /// Workflow run under the cursor
+    pub fn selected_run(&self) -> Option<&WorkflowRun> {
+        self.runs.get(self.selected)
+    }
+
+    /// Build log context of the workflow run under the cursor
+    pub fn selected_run_context(&self) -> Option<BuildLogRunContext> {
+        self.selected_run().map(|run| BuildLogRunContext {
+            run_id: run.id,
+            workflow_name: run.name.clone(),
+            branch: run.head_branch.clone().unwrap_or_default(),
+            event: run.event.clone(),
+            waiting: run.status == gh_client::WorkflowRunStatus::Waiting,
+            attempt: run.run_attempt,
+        })
+    }
+}
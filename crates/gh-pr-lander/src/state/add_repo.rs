@@ -1,7 +1,7 @@
 //! Add Repository Form State
 
 use crate::domain_models::Repository;
-use gh_pr_config::DEFAULT_HOST;
+use gh_pr_config::{RepoId, DEFAULT_HOST};
 
 /// Form field for the add repository dialog
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -121,72 +121,13 @@ impl AddRepoFormState {
 /// - `https://github.example.com/org/repo`
 /// - `git@github.com:org/repo.git`
 /// - `git@github.example.com:org/repo.git`
-fn parse_github_url(url: &str) -> Option<(Option<String>, String, String)> {
-    let url = url.trim();
-
-    // Try HTTPS format: https://host/org/repo[.git]
-    if let Some(rest) = url
-        .strip_prefix("https://")
-        .or_else(|| url.strip_prefix("http://"))
-    {
-        // Split host from path
-        if let Some((host, path)) = rest.split_once('/') {
-            if let Some((org, repo)) = parse_org_repo_path(path) {
-                let host = if host == DEFAULT_HOST {
-                    None
-                } else {
-                    Some(host.to_string())
-                };
-                return Some((host, org, repo));
-            }
-        }
-    }
-
-    // Try SSH format: git@host:org/repo[.git]
-    if let Some(rest) = url.strip_prefix("git@") {
-        if let Some((host, path)) = rest.split_once(':') {
-            if let Some((org, repo)) = parse_org_repo_path(path) {
-                let host = if host == DEFAULT_HOST {
-                    None
-                } else {
-                    Some(host.to_string())
-                };
-                return Some((host, org, repo));
-            }
-        }
-    }
-
-    // Try short format: host/org/repo (e.g., github.com/org/repo or ghe.example.com/org/repo)
-    let parts: Vec<&str> = url.split('/').collect();
-    if parts.len() >= 3 && parts[0].contains('.') {
-        let host = parts[0];
-        if let Some((org, repo)) = parse_org_repo_path(&parts[1..].join("/")) {
-            let host = if host == DEFAULT_HOST {
-                None
-            } else {
-                Some(host.to_string())
-            };
-            return Some((host, org, repo));
-        }
-    }
-
-    None
-}
-
-/// Parse "org/repo[.git]" into (org, repo)
-fn parse_org_repo_path(path: &str) -> Option<(String, String)> {
-    // Remove trailing .git if present
-    let path = path.strip_suffix(".git").unwrap_or(path);
-
-    // Split by '/'
-    let parts: Vec<&str> = path.split('/').collect();
-
-    if parts.len() >= 2 && !parts[0].is_empty() && !parts[1].is_empty() {
-        // Take only the first two parts (org/repo), ignore anything after
-        Some((parts[0].to_string(), parts[1].to_string()))
-    } else {
-        None
-    }
+pub fn parse_github_url(url: &str) -> Option<(Option<String>, String, String)> {
+    let id = RepoId::parse_url(url)?;
+    Some((
+        id.host().map(String::from),
+        id.org().to_string(),
+        id.repo().to_string(),
+    ))
 }
 
 #[cfg(test)]
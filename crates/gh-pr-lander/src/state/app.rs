@@ -1,17 +1,23 @@
 //! Application State
 
 use crate::keymap::{default_keymap, Keymap};
-use crate::views::{SplashView, View};
+use crate::views::{SplashView, View, ViewId};
 
 use super::{
-    AddRepoFormState, BuildLogState, CommandPaletteState, ConfirmationPopupState,
-    DebugConsoleState, DiffViewerState, KeyBindingsPanelState, MainViewState, MergeBotState,
-    SplashState, StatusBarState,
+    ActionsViewState, AddRepoFormState, AuditLogState, BranchPickerState, BugReportState,
+    BuildLogState, CacheInspectorState, CommandPaletteState, ConfirmationPopupState,
+    ConflictHelperState, DebugConsoleState, DependencyUpdatesState, DiffViewerState,
+    DuplicateUpdatesState, FilterBuilderState, KeyBindingsPanelState, LabelPickerState,
+    MainViewState, MergeBotState, OnboardingState, PerfStatsState, PrSearchInputState,
+    ReleaseNotesState, ReviewerPickerState, SplashState, StatusBarState, TerminalSize,
+    TokenInputState,
 };
 
 /// Application state
 pub struct AppState {
     pub running: bool,
+    /// Last known terminal size, viewports are derived from it
+    pub terminal_size: TerminalSize,
     /// Stack of views - bottom view is the base, top views are floating overlays
     /// Views are rendered bottom-up, so the last view in the stack renders on top
     pub view_stack: Vec<Box<dyn View>>,
@@ -25,8 +31,33 @@ pub struct AppState {
     pub status_bar: StatusBarState,
     pub build_log: BuildLogState,
     pub diff_viewer: DiffViewerState,
+    pub actions_view: ActionsViewState,
+    pub dependency_updates: DependencyUpdatesState,
+    pub perf_stats: PerfStatsState,
+    pub cache_inspector: CacheInspectorState,
+    pub conflict_helper: ConflictHelperState,
+    pub audit_log: AuditLogState,
+    pub release_notes: ReleaseNotesState,
     /// Confirmation popup state (present only when popup is shown)
     pub confirmation_popup: Option<ConfirmationPopupState>,
+    /// Onboarding wizard state (present only while the wizard is shown)
+    pub onboarding: Option<OnboardingState>,
+    /// Token input popup state (present only while the popup is shown)
+    pub token_input: Option<TokenInputState>,
+    /// PR search input popup state (present only while the popup is shown)
+    pub pr_search_input: Option<PrSearchInputState>,
+    /// Bug report popup state (present only while the popup is shown)
+    pub bug_report: Option<BugReportState>,
+    /// Label picker popup state (present only while the popup is shown)
+    pub label_picker: Option<LabelPickerState>,
+    /// Reviewer picker popup state (present only while the popup is shown)
+    pub reviewer_picker: Option<ReviewerPickerState>,
+    /// Branch picker popup state (present only while the popup is shown)
+    pub branch_picker: Option<BranchPickerState>,
+    /// Filter builder popup state (present only while the popup is shown)
+    pub filter_builder: Option<FilterBuilderState>,
+    /// Duplicate bot updates popup state (present only while the popup is shown)
+    pub duplicate_updates: Option<DuplicateUpdatesState>,
     pub theme: gh_pr_lander_theme::Theme,
     /// The keymap containing all keybindings
     pub keymap: Keymap,
@@ -42,6 +73,20 @@ impl AppState {
             .expect("View stack should never be empty")
             .as_ref()
     }
+
+    /// Pop the top-most view if it is `id` and not the root view
+    ///
+    /// Views close themselves through this, so closing never removes another
+    /// view that was pushed on top of them in the meantime.
+    pub fn pop_view(&mut self, id: ViewId) -> bool {
+        let is_top = self.view_stack.last().is_some_and(|v| v.view_id() == id);
+        if !is_top || self.view_stack.len() < 2 {
+            return false;
+        }
+        self.view_stack.pop();
+        log::debug!("Closed view: {:?}", id);
+        true
+    }
 }
 
 impl std::fmt::Debug for AppState {
@@ -56,10 +101,26 @@ impl std::fmt::Debug for AppState {
             .field("add_repo_form", &self.add_repo_form)
             .field("merge_bot", &self.merge_bot)
             .field("key_bindings_panel", &self.key_bindings_panel)
+            .field("terminal_size", &self.terminal_size)
             .field("status_bar", &self.status_bar)
             .field("build_log", &self.build_log)
             .field("diff_viewer", &self.diff_viewer)
+            .field("actions_view", &self.actions_view)
+            .field("dependency_updates", &self.dependency_updates)
+            .field("cache_inspector", &self.cache_inspector.entries.len())
+            .field("conflict_helper", &self.conflict_helper)
+            .field("audit_log", &self.audit_log.entries.len())
+            .field("release_notes", &self.release_notes.target)
             .field("confirmation_popup", &self.confirmation_popup)
+            .field("onboarding", &self.onboarding)
+            .field("token_input", &self.token_input.as_ref().map(|t| &t.host))
+            .field("pr_search_input", &self.pr_search_input)
+            .field("bug_report", &self.bug_report)
+            .field("label_picker", &self.label_picker)
+            .field("reviewer_picker", &self.reviewer_picker)
+            .field("branch_picker", &self.branch_picker)
+            .field("filter_builder", &self.filter_builder)
+            .field("duplicate_updates", &self.duplicate_updates)
             .field("theme", &"<theme>")
             .field("app_config", &self.app_config)
             .finish()
@@ -78,10 +139,27 @@ impl Clone for AppState {
             add_repo_form: self.add_repo_form.clone(),
             merge_bot: self.merge_bot.clone(),
             key_bindings_panel: self.key_bindings_panel.clone(),
+            terminal_size: self.terminal_size,
             status_bar: self.status_bar.clone(),
             build_log: self.build_log.clone(),
             diff_viewer: self.diff_viewer.clone(),
+            actions_view: self.actions_view.clone(),
+            dependency_updates: self.dependency_updates.clone(),
+            perf_stats: self.perf_stats.clone(),
+            cache_inspector: self.cache_inspector.clone(),
+            conflict_helper: self.conflict_helper.clone(),
+            audit_log: self.audit_log.clone(),
+            release_notes: self.release_notes.clone(),
             confirmation_popup: self.confirmation_popup.clone(),
+            onboarding: self.onboarding.clone(),
+            token_input: self.token_input.clone(),
+            pr_search_input: self.pr_search_input.clone(),
+            bug_report: self.bug_report.clone(),
+            label_picker: self.label_picker.clone(),
+            reviewer_picker: self.reviewer_picker.clone(),
+            branch_picker: self.branch_picker.clone(),
+            filter_builder: self.filter_builder.clone(),
+            duplicate_updates: self.duplicate_updates.clone(),
             theme: self.theme.clone(),
             keymap: self.keymap.clone(),
             app_config: self.app_config.clone(),
@@ -101,10 +179,27 @@ impl Default for AppState {
             add_repo_form: AddRepoFormState::default(),
             merge_bot: MergeBotState::default(),
             key_bindings_panel: KeyBindingsPanelState::default(),
+            terminal_size: TerminalSize::default(),
             status_bar: StatusBarState::default(),
             build_log: BuildLogState::default(),
             diff_viewer: DiffViewerState::default(),
+            actions_view: ActionsViewState::default(),
+            dependency_updates: DependencyUpdatesState::default(),
+            perf_stats: PerfStatsState::default(),
+            cache_inspector: CacheInspectorState::default(),
+            conflict_helper: ConflictHelperState::default(),
+            audit_log: AuditLogState::default(),
+            release_notes: ReleaseNotesState::default(),
             confirmation_popup: None,
+            onboarding: None,
+            token_input: None,
+            pr_search_input: None,
+            bug_report: None,
+            label_picker: None,
+            reviewer_picker: None,
+            branch_picker: None,
+            filter_builder: None,
+            duplicate_updates: None,
             theme: gh_pr_lander_theme::Theme::default(),
             keymap: default_keymap(),
             app_config: gh_pr_config::AppConfig::default(),
@@ -0,0 +1,157 @@
+//! Audit Log State
+//!
+//! Entries of the audit log as read by the middleware, shown for the repository
+//! or the PR that was focused when the panel was opened.
+
+use crate::audit::AuditEntry;
+use crate::domain_models::Repository;
+
+/// Which entries the audit log panel lists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AuditScope {
+    /// Operations on the focused PR
+    #[default]
+    Pr,
+    /// Operations on any PR of the repository
+    Repository,
+}
+
+/// State of the audit log panel
+#[derive(Debug, Clone, Default)]
+pub struct AuditLogState {
+    /// Entries as last read by the middleware, oldest first
+    pub entries: Vec<AuditEntry>,
+    /// Repository focused when the panel was opened
+    pub repo: Option<Repository>,
+    /// PR focused when the panel was opened
+    pub pr_number: Option<usize>,
+    pub scope: AuditScope,
+    /// Cursor position within the visible entries
+    pub selected: usize,
+}
+
+impl AuditLogState {
+    /// Show the entries of `repo`, narrowed to `pr_number` if a PR is focused
+    pub fn focus(&mut self, repo: Option<Repository>, pr_number: Option<usize>) {
+        self.scope = if pr_number.is_some() {
+            AuditScope::Pr
+        } else {
+            AuditScope::Repository
+        };
+        self.repo = repo;
+        self.pr_number = pr_number;
+        self.selected = 0;
+    }
+
+    /// Replace the entries, keeping the cursor in range
+    pub fn set_entries(&mut self, entries: Vec<AuditEntry>) {
+        self.entries = entries;
+        self.clamp_selection();
+    }
+
+    /// Entries in scope, newest first
+    pub fn visible(&self) -> Vec<&AuditEntry> {
+        let Some(repo) = &self.repo else {
+            return Vec::new();
+        };
+        self.entries
+            .iter()
+            .rev()
+            .filter(|entry| entry.is_for(repo))
+            .filter(|entry| self.scope == AuditScope::Repository || entry.pr == self.pr_number)
+            .collect()
+    }
+
+    /// Switch between the focused PR and its repository
+    pub fn toggle_scope(&mut self) {
+        self.scope = match self.scope {
+            AuditScope::Pr => AuditScope::Repository,
+            AuditScope::Repository if self.pr_number.is_some() => AuditScope::Pr,
+            AuditScope::Repository => AuditScope::Repository,
+        };
+        self.selected = 0;
+    }
+
+    pub fn select_next(&mut self) {
+        let count = self.visible().len();
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        let count = self.visible().len();
+        if count > 0 {
+            self.selected = (self.selected + count - 1) % count;
+        }
+    }
+
+    pub fn select_last(&mut self) {
+        self.selected = self.visible().len().saturating_sub(1);
+    }
+
+    fn clamp_selection(&mut self) {
+        let count = self.visible().len();
+        self.selected = self.selected.min(count.saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::{AuditOperation, AuditOutcome};
+
+    fn entry(repo: &str, pr: usize, operation: AuditOperation) -> AuditEntry {
+        AuditEntry {
+            ts: String::new(),
+            login: None,
+            operation,
+            repo: repo.to_string(),
+            host: None,
+            pr: Some(pr),
+            params: Default::default(),
+            outcome: AuditOutcome::Success,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_scoped_to_pr_or_repository_newest_first() {
+        let mut state = AuditLogState::default();
+        state.focus(Some(Repository::new("acme", "api", "main")), Some(7));
+        state.set_entries(vec![
+            entry("acme/api", 7, AuditOperation::Approve),
+            entry("acme/web", 7, AuditOperation::Merge),
+            entry("acme/api", 8, AuditOperation::Comment),
+            entry("acme/api", 7, AuditOperation::Merge),
+        ]);
+
+        let operations = |state: &AuditLogState| {
+            state
+                .visible()
+                .iter()
+                .map(|e| e.operation)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            operations(&state),
+            vec![AuditOperation::Merge, AuditOperation::Approve]
+        );
+
+        state.toggle_scope();
+        assert_eq!(state.scope, AuditScope::Repository);
+        assert_eq!(
+            operations(&state),
+            vec![
+                AuditOperation::Merge,
+                AuditOperation::Comment,
+                AuditOperation::Approve
+            ]
+        );
+
+        // Without a focused PR the repository stays the scope
+        state.focus(Some(Repository::new("acme", "api", "main")), None);
+        state.toggle_scope();
+        assert_eq!(state.scope, AuditScope::Repository);
+    }
+}
@@ -0,0 +1,150 @@
+//! Branch Picker State
+//!
+//! Popup to pick the base branch whose PRs the current repository tab shows.
+
+use super::label_picker::fuzzy_match;
+use crate::domain_models::Repository;
+
+/// State of the branch picker popup
+#[derive(Debug, Clone)]
+pub struct BranchPickerState {
+    /// Repository of the current tab
+    pub repo: Repository,
+    /// Branch names of the repository (empty while loading)
+    pub branches: Vec<String>,
+    /// Filter typed by the user
+    pub query: String,
+    /// Cursor position within the filtered branches
+    pub selected: usize,
+    /// Branches are being fetched
+    pub loading: bool,
+    /// Fetching the branches failed
+    pub error: Option<String>,
+}
+
+impl BranchPickerState {
+    pub fn new(repo: Repository) -> Self {
+        Self {
+            repo,
+            branches: Vec::new(),
+            query: String::new(),
+            selected: 0,
+            loading: true,
+            error: None,
+        }
+    }
+
+    /// Set the branches, the cursor starts on the one shown now
+    pub fn set_branches(&mut self, branches: Vec<String>) {
+        self.branches = branches;
+        self.loading = false;
+        self.error = None;
+        self.selected = self
+            .filtered()
+            .iter()
+            .position(|branch| *branch == self.repo.branch)
+            .unwrap_or(0);
+    }
+
+    /// Branches matching the filter, in display order
+    pub fn filtered(&self) -> Vec<&str> {
+        self.branches
+            .iter()
+            .filter(|branch| fuzzy_match(&self.query, branch))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Branch to switch to: the one under the cursor, or the typed name
+    /// when no fetched branch matches (e.g. beyond the first thousand)
+    pub fn choice(&self) -> Option<String> {
+        match self.filtered().get(self.selected) {
+            Some(branch) => Some(branch.to_string()),
+            None => Some(self.query.trim().to_string()).filter(|query| !query.is_empty()),
+        }
+    }
+
+    pub fn push_query(&mut self, c: char) {
+        self.query.push(c);
+        self.clamp_selection();
+    }
+
+    pub fn pop_query(&mut self) {
+        self.query.pop();
+        self.clamp_selection();
+    }
+
+    pub fn clear_query(&mut self) {
+        self.query.clear();
+        self.clamp_selection();
+    }
+
+    pub fn select_next(&mut self) {
+        let count = self.filtered().len();
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        let count = self.filtered().len();
+        if count > 0 {
+            self.selected = (self.selected + count - 1) % count;
+        }
+    }
+
+    fn clamp_selection(&mut self) {
+        let count = self.filtered().len();
+        self.selected = self.selected.min(count.saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn picker() -> BranchPickerState {
+        let mut state = BranchPickerState::new(Repository::new("acme", "api", "release/2.x"));
+        state.set_branches(vec![
+            "main".to_string(),
+            "release/1.x".to_string(),
+            "release/2.x".to_string(),
+        ]);
+        state
+    }
+
+    #[test]
+    fn test_cursor_starts_on_current_branch() {
+        let state = picker();
+        assert!(!state.loading);
+        assert_eq!(state.choice().as_deref(), Some("release/2.x"));
+    }
+
+    #[test]
+    fn test_filter_keeps_selection_in_range() {
+        let mut state = picker();
+        for c in "r1".chars() {
+            state.push_query(c);
+        }
+        assert_eq!(state.filtered(), vec!["release/1.x"]);
+        assert_eq!(state.choice().as_deref(), Some("release/1.x"));
+
+        state.clear_query();
+        state.select_next();
+        assert_eq!(state.choice().as_deref(), Some("release/1.x"));
+    }
+
+    #[test]
+    fn test_unknown_branch_is_taken_as_typed() {
+        let mut state = picker();
+        for c in "hotfix".chars() {
+            state.push_query(c);
+        }
+        assert!(state.filtered().is_empty());
+        assert_eq!(state.choice().as_deref(), Some("hotfix"));
+
+        state.clear_query();
+        state.branches.clear();
+        assert_eq!(state.choice(), None);
+    }
+}
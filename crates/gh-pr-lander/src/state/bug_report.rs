@@ -0,0 +1,24 @@
+//! Bug Report State
+//!
+//! Path input popup for writing a bug report bundle.
+
+/// State for the bug report popup
+#[derive(Debug, Clone, Default)]
+pub struct BugReportState {
+    /// Target path of the zip file
+    pub path: String,
+    /// The bundle is being written
+    pub writing: bool,
+    /// Last write error
+    pub error: Option<String>,
+}
+
+impl BugReportState {
+    /// Create a new popup with the given default path
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            ..Self::default()
+        }
+    }
+}
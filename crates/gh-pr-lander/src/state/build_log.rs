@@ -2,9 +2,21 @@
 //!
 //! State for the build log panel that displays CI workflow results.
 
-use std::collections::{HashMap, HashSet};
+use crate::domain_models::Repository;
+use gh_actions_log_parser::{JobNode, WorkflowNode};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
 use std::time::Duration;
 
+/// Maximum number of parsed workflow runs kept in memory
+pub const PARSED_RUNS_LIMIT: usize = 8;
+
+/// Maximum approximate memory of the parsed workflow runs kept in memory
+pub const PARSED_RUNS_MAX_BYTES: usize = 64 * 1024 * 1024;
+
+/// Path segment of a workflow's "N succeeded" node, in place of a job index
+pub const SUCCEEDED_GROUP: usize = usize::MAX;
+
 /// Job execution status for build logs
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum BuildLogJobStatus {
@@ -33,6 +45,11 @@ impl BuildLogJobStatus {
     pub fn is_failed(&self) -> bool {
         matches!(self, Self::Failure)
     }
+
+    /// Finished without anything to look at (folded into the succeeded node)
+    pub fn is_successful(&self) -> bool {
+        matches!(self, Self::Success | Self::Skipped)
+    }
 }
 
 /// Metadata for a build job (from GitHub API)
@@ -54,12 +71,78 @@ pub struct BuildLogPrContext {
     pub author: String,
 }
 
+/// Workflow run context for build logs opened outside of a PR
+#[derive(Debug, Clone, Default)]
+pub struct BuildLogRunContext {
+    pub run_id: u64,
+    pub workflow_name: String,
+    pub branch: String,
+    pub event: String,
+    /// Whether the run waits for a deployment to be approved (it has no logs yet)
+    pub waiting: bool,
+    /// Attempt of the run, counting up with every re-run
+    pub attempt: u64,
+}
+
+/// What the build logs were opened for
+#[derive(Debug, Clone)]
+pub enum BuildLogContext {
+    /// All workflow runs of a PR's head commit
+    Pr(BuildLogPrContext),
+    /// A single workflow run (e.g. from the Actions view)
+    Run(BuildLogRunContext),
+}
+
+/// Parsed logs of a finished workflow run attempt, kept for reopening
+///
+/// Only finished attempts are kept, as their logs won't change anymore.
+#[derive(Debug, Clone)]
+pub struct ParsedRun {
+    pub repo: Repository,
+    pub run_id: u64,
+    pub attempt: u64,
+    /// Shared with `BuildLogState::workflows` while displayed
+    pub workflow: Arc<WorkflowNode>,
+    pub job_metadata: Vec<BuildLogJobMetadata>,
+    /// Approximate memory of the parsed logs
+    pub bytes: usize,
+}
+
+impl ParsedRun {
+    pub fn new(
+        repo: Repository,
+        run_id: u64,
+        attempt: u64,
+        workflow: Arc<WorkflowNode>,
+        job_metadata: Vec<BuildLogJobMetadata>,
+    ) -> Self {
+        let bytes = workflow.approx_bytes();
+        Self {
+            repo,
+            run_id,
+            attempt,
+            workflow,
+            job_metadata,
+            bytes,
+        }
+    }
+
+    /// Whether these are the logs of the given run attempt
+    pub fn is_attempt(&self, repo: &Repository, run_id: u64, attempt: u64) -> bool {
+        self.run_id == run_id && self.attempt == attempt && &self.repo == repo
+    }
+}
+
 /// Loading state for build logs
 #[derive(Debug, Clone, Default)]
 pub enum BuildLogLoadingState {
     #[default]
     Idle,
-    Loading,
+    /// Workflow runs are loading, `total` is None until the runs are known
+    Loading {
+        loaded: usize,
+        total: Option<usize>,
+    },
     Loaded,
     Error(String),
 }
@@ -68,7 +151,16 @@ pub enum BuildLogLoadingState {
 #[derive(Debug, Clone)]
 pub struct BuildLogState {
     /// Tree data from parser
-    pub workflows: Vec<gh_actions_log_parser::WorkflowNode>,
+    pub workflows: Vec<Arc<WorkflowNode>>,
+
+    /// Parsed runs of earlier loads, most recently used first
+    ///
+    /// Bounded by [`PARSED_RUNS_LIMIT`] and [`PARSED_RUNS_MAX_BYTES`], runs
+    /// that are displayed are never evicted.
+    pub parsed_runs: VecDeque<ParsedRun>,
+
+    /// Approximate memory of displayed workflows that are not in `parsed_runs`
+    pub unfinished_bytes: usize,
 
     /// Job metadata from GitHub API (key: "workflow:job")
     pub job_metadata: HashMap<String, BuildLogJobMetadata>,
@@ -88,11 +180,17 @@ pub struct BuildLogState {
     /// Show timestamps toggle
     pub show_timestamps: bool,
 
+    /// Fold successful jobs into a "N succeeded" node (`ui.collapse_successful_jobs`)
+    pub collapse_successful: bool,
+
+    /// Choice of listing successful jobs made this session (None = as configured)
+    pub show_successful: Option<bool>,
+
     /// Viewport height (set during rendering)
     pub viewport_height: usize,
 
-    /// PR context for header
-    pub pr_context: BuildLogPrContext,
+    /// PR or workflow run the logs belong to (for the header)
+    pub context: Option<BuildLogContext>,
 
     /// Loading state
     pub loading_state: BuildLogLoadingState,
@@ -102,20 +200,110 @@ impl Default for BuildLogState {
     fn default() -> Self {
         Self {
             workflows: Vec::new(),
+            parsed_runs: VecDeque::new(),
+            unfinished_bytes: 0,
             job_metadata: HashMap::new(),
             expanded_nodes: HashSet::new(),
             cursor_path: vec![0],
             scroll_offset: 0,
             horizontal_scroll: 0,
             show_timestamps: false,
+            collapse_successful: true,
+            show_successful: None,
             viewport_height: 20,
-            pr_context: BuildLogPrContext::default(),
+            context: None,
             loading_state: BuildLogLoadingState::Idle,
         }
     }
 }
 
 impl BuildLogState {
+    /// Display freshly loaded workflows and remember their finished runs
+    ///
+    /// Evicts the least recently used runs beyond the limits, except the
+    /// displayed ones.
+    pub fn set_workflows(&mut self, workflows: Vec<Arc<WorkflowNode>>, runs: &[ParsedRun]) {
+        for run in runs.iter().rev() {
+            self.parsed_runs
+                .retain(|parsed| !parsed.is_attempt(&run.repo, run.run_id, run.attempt));
+            self.parsed_runs.push_front(run.clone());
+        }
+        self.workflows = workflows;
+        self.unfinished_bytes = self
+            .workflows
+            .iter()
+            .filter(|workflow| !self.is_parsed_run(workflow))
+            .map(|workflow| workflow.approx_bytes())
+            .sum();
+
+        let mut bytes: usize = self.parsed_runs.iter().map(|run| run.bytes).sum();
+        let mut idx = self.parsed_runs.len();
+        while idx > 0
+            && (self.parsed_runs.len() > PARSED_RUNS_LIMIT || bytes > PARSED_RUNS_MAX_BYTES)
+        {
+            idx -= 1;
+            let displayed = self
+                .workflows
+                .iter()
+                .any(|workflow| Arc::ptr_eq(workflow, &self.parsed_runs[idx].workflow));
+            if !displayed {
+                if let Some(run) = self.parsed_runs.remove(idx) {
+                    log::debug!(
+                        "Evicting parsed logs of run {} ({} bytes)",
+                        run.run_id,
+                        run.bytes
+                    );
+                    bytes -= run.bytes;
+                }
+            }
+        }
+    }
+
+    /// Display a workflow at `index`, shifting the paths of those after it
+    pub fn insert_workflow(
+        &mut self,
+        index: usize,
+        workflow: Arc<WorkflowNode>,
+        run: Option<&ParsedRun>,
+    ) {
+        let mut workflows = self.workflows.clone();
+        workflows.insert(index, workflow);
+        self.set_workflows(workflows, run.map(std::slice::from_ref).unwrap_or_default());
+
+        let shift = |key: &str| -> String {
+            let mut parts: Vec<&str> = key.split(':').collect();
+            let shifted = match parts[0].parse::<usize>() {
+                Ok(w_idx) if w_idx >= index => (w_idx + 1).to_string(),
+                _ => return key.to_string(),
+            };
+            parts[0] = &shifted;
+            parts.join(":")
+        };
+        self.expanded_nodes = self.expanded_nodes.iter().map(|key| shift(key)).collect();
+        // The cursor stays on its node, unless the tree was empty
+        if self.workflows.len() > 1 {
+            if let Some(w_idx) = self
+                .cursor_path
+                .first_mut()
+                .filter(|w_idx| **w_idx >= index)
+            {
+                *w_idx += 1;
+            }
+        }
+    }
+
+    /// Whether the workflow is one of the remembered parsed runs
+    fn is_parsed_run(&self, workflow: &Arc<WorkflowNode>) -> bool {
+        self.parsed_runs
+            .iter()
+            .any(|run| Arc::ptr_eq(&run.workflow, workflow))
+    }
+
+    /// Approximate memory of all parsed logs held in state
+    pub fn log_memory_bytes(&self) -> usize {
+        self.unfinished_bytes + self.parsed_runs.iter().map(|run| run.bytes).sum::<usize>()
+    }
+
     /// Convert path to string key for expanded_nodes
     pub fn path_to_key(path: &[usize]) -> String {
         path.iter()
@@ -139,9 +327,48 @@ impl BuildLogState {
         }
     }
 
+    /// Whether successful jobs are listed instead of folded
+    pub fn shows_successful(&self) -> bool {
+        self.show_successful.unwrap_or(!self.collapse_successful)
+    }
+
+    /// Status of a job from its metadata (or inferred from its error count)
+    pub fn job_status(&self, workflow: &WorkflowNode, job: &JobNode) -> BuildLogJobStatus {
+        self.job_metadata
+            .get(&format!("{}:{}", workflow.name, job.name))
+            .map(|meta| meta.status)
+            .unwrap_or(if job.error_count > 0 {
+                BuildLogJobStatus::Failure
+            } else {
+                BuildLogJobStatus::Success
+            })
+    }
+
+    /// Job indices of a workflow, split into listed and folded (succeeded) jobs
+    ///
+    /// When folding, failed jobs come first, then the other unsuccessful ones.
+    /// Nothing is folded if every job succeeded or none did.
+    pub fn grouped_jobs(&self, workflow: &WorkflowNode) -> (Vec<usize>, Vec<usize>) {
+        let all: Vec<usize> = (0..workflow.jobs.len()).collect();
+        if self.shows_successful() {
+            return (all, Vec::new());
+        }
+
+        let status = |j_idx: &usize| self.job_status(workflow, &workflow.jobs[*j_idx]);
+        let (succeeded, mut listed): (Vec<usize>, Vec<usize>) = all
+            .into_iter()
+            .partition(|j_idx| status(j_idx).is_successful());
+        if listed.is_empty() {
+            return (succeeded, Vec::new());
+        }
+        listed.sort_by_key(|j_idx| !status(j_idx).is_failed());
+        (listed, succeeded)
+    }
+
     /// Flatten tree to list of visible node paths
     ///
     /// Returns paths as vectors: \[workflow\], \[workflow, job\], \[workflow, job, step\], etc.
+    /// Folded successful jobs sit below a \[workflow, [`SUCCEEDED_GROUP`]\] node.
     pub fn flatten_visible_nodes(&self) -> Vec<Vec<usize>> {
         let mut result = Vec::new();
 
@@ -153,31 +380,53 @@ impl BuildLogState {
                 continue;
             }
 
-            for (j_idx, job) in workflow.jobs.iter().enumerate() {
-                // Job node
-                result.push(vec![w_idx, j_idx]);
+            let (listed, succeeded) = self.grouped_jobs(workflow);
+            for j_idx in listed {
+                self.push_visible_job(&mut result, w_idx, j_idx, &workflow.jobs[j_idx]);
+            }
 
-                if !self.is_expanded(&[w_idx, j_idx]) {
-                    continue;
+            if succeeded.is_empty() {
+                continue;
+            }
+            result.push(vec![w_idx, SUCCEEDED_GROUP]);
+            if self.is_expanded(&[w_idx, SUCCEEDED_GROUP]) {
+                for j_idx in succeeded {
+                    self.push_visible_job(&mut result, w_idx, j_idx, &workflow.jobs[j_idx]);
                 }
+            }
+        }
 
-                for (s_idx, step) in job.steps.iter().enumerate() {
-                    // Step node
-                    result.push(vec![w_idx, j_idx, s_idx]);
+        result
+    }
 
-                    if !self.is_expanded(&[w_idx, j_idx, s_idx]) {
-                        continue;
-                    }
+    /// Append the visible nodes of a job (job, steps and log lines)
+    fn push_visible_job(
+        &self,
+        result: &mut Vec<Vec<usize>>,
+        w_idx: usize,
+        j_idx: usize,
+        job: &JobNode,
+    ) {
+        // Job node
+        result.push(vec![w_idx, j_idx]);
+
+        if !self.is_expanded(&[w_idx, j_idx]) {
+            return;
+        }
 
-                    // Log lines
-                    for l_idx in 0..step.lines.len() {
-                        result.push(vec![w_idx, j_idx, s_idx, l_idx]);
-                    }
-                }
+        for (s_idx, step) in job.steps.iter().enumerate() {
+            // Step node
+            result.push(vec![w_idx, j_idx, s_idx]);
+
+            if !self.is_expanded(&[w_idx, j_idx, s_idx]) {
+                continue;
             }
-        }
 
-        result
+            // Log lines
+            for l_idx in 0..step.lines.len() {
+                result.push(vec![w_idx, j_idx, s_idx, l_idx]);
+            }
+        }
     }
 
     /// Get visible nodes within viewport
@@ -200,4 +449,96 @@ impl BuildLogState {
     pub fn total_visible_nodes(&self) -> usize {
         self.flatten_visible_nodes().len()
     }
+
+    /// Metadata of the job at the cursor (or containing the cursor's step or line)
+    pub fn focused_job(&self) -> Option<&BuildLogJobMetadata> {
+        let [w_idx, j_idx, ..] = self.cursor_path[..] else {
+            return None;
+        };
+        let workflow = self.workflows.get(w_idx)?;
+        let job = workflow.jobs.get(j_idx)?;
+        self.job_metadata
+            .get(&format!("{}:{}", workflow.name, job.name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(run_id: u64, bytes: usize) -> ParsedRun {
+        let workflow = Arc::new(WorkflowNode {
+            name: format!("CI {}", run_id),
+            jobs: Vec::new(),
+            total_errors: 0,
+            has_failures: false,
+        });
+        let mut run = ParsedRun::new(
+            Repository::new("acme", "api", "main"),
+            run_id,
+            1,
+            workflow,
+            Vec::new(),
+        );
+        run.bytes = bytes;
+        run
+    }
+
+    fn display(state: &mut BuildLogState, runs: &[ParsedRun]) {
+        let workflows = runs.iter().map(|run| Arc::clone(&run.workflow)).collect();
+        state.set_workflows(workflows, runs);
+    }
+
+    #[test]
+    fn test_parsed_runs_are_bounded_most_recent_first() {
+        let mut state = BuildLogState::default();
+        for run_id in 0..PARSED_RUNS_LIMIT as u64 + 3 {
+            display(&mut state, &[run(run_id, 1024)]);
+        }
+
+        assert_eq!(state.parsed_runs.len(), PARSED_RUNS_LIMIT);
+        assert_eq!(state.parsed_runs[0].run_id, PARSED_RUNS_LIMIT as u64 + 2);
+        assert!(state.parsed_runs.iter().all(|run| run.run_id >= 3));
+        assert_eq!(state.log_memory_bytes(), PARSED_RUNS_LIMIT * 1024);
+
+        // Reopening moves a run to the front without duplicating it
+        let reopened = state.parsed_runs[3].clone();
+        display(&mut state, &[reopened]);
+        assert_eq!(state.parsed_runs.len(), PARSED_RUNS_LIMIT);
+        assert_eq!(state.parsed_runs[0].run_id, PARSED_RUNS_LIMIT as u64 - 1);
+    }
+
+    #[test]
+    fn test_displayed_runs_are_never_evicted() {
+        let mut state = BuildLogState::default();
+        display(&mut state, &[run(1, 1024), run(2, 1024)]);
+        display(
+            &mut state,
+            &[run(3, PARSED_RUNS_MAX_BYTES), run(4, PARSED_RUNS_MAX_BYTES)],
+        );
+
+        let ids: Vec<u64> = state.parsed_runs.iter().map(|run| run.run_id).collect();
+        assert_eq!(ids, vec![3, 4]);
+        assert_eq!(state.workflows.len(), 2);
+        assert_eq!(state.unfinished_bytes, 0);
+    }
+
+    #[test]
+    fn test_inserted_workflows_keep_cursor_and_expansion() {
+        let mut state = BuildLogState::default();
+        let (first, second) = (run(1, 1024), run(2, 1024));
+        state.insert_workflow(0, Arc::clone(&first.workflow), Some(&first));
+        state.expanded_nodes.insert("0".to_string());
+        state.expanded_nodes.insert("0:1".to_string());
+        state.cursor_path = vec![0, 1];
+
+        state.insert_workflow(0, Arc::clone(&second.workflow), None);
+
+        assert_eq!(state.workflows[1].name, "CI 1");
+        assert_eq!(state.cursor_path, vec![1, 1]);
+        assert!(state.is_expanded(&[1]) && state.is_expanded(&[1, 1]));
+        assert!(!state.is_expanded(&[0]));
+        assert_eq!(state.parsed_runs.len(), 1);
+        assert_eq!(state.unfinished_bytes, second.workflow.approx_bytes());
+    }
 }
@@ -0,0 +1,262 @@
+//! Cache Inspector State
+//!
+//! Metadata of the cached GitHub API responses, listed for debugging stale
+//! data. Bodies stay in the cache, only keys, sizes and ages are kept here.
+
+use gh_client::CacheEntryInfo;
+
+/// Order of the listed entries
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CacheSort {
+    /// Largest first
+    #[default]
+    Size,
+    /// Oldest first
+    Age,
+}
+
+impl CacheSort {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Size => Self::Age,
+            Self::Age => Self::Size,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Size => "size",
+            Self::Age => "age",
+        }
+    }
+}
+
+/// Text being typed in the cache inspector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheInput {
+    /// Editing the filter, which applies while typing
+    Filter,
+    /// Pattern of keys to invalidate on confirm
+    Pattern(String),
+}
+
+/// State of the cache inspector popup
+#[derive(Debug, Clone, Default)]
+pub struct CacheInspectorState {
+    /// Entries as last listed by the middleware
+    pub entries: Vec<CacheEntryInfo>,
+    /// Case-insensitive substring of the key or category
+    pub filter: String,
+    pub sort: CacheSort,
+    /// Cursor position within the visible entries
+    pub selected: usize,
+    /// Text being typed (None while navigating)
+    pub input: Option<CacheInput>,
+}
+
+impl CacheInspectorState {
+    /// Replace the entries, keeping the cursor in range
+    pub fn set_entries(&mut self, entries: Vec<CacheEntryInfo>) {
+        self.entries = entries;
+        self.clamp_selection();
+    }
+
+    /// Entries matching the filter, in display order
+    pub fn visible(&self) -> Vec<&CacheEntryInfo> {
+        let filter = self.filter.to_lowercase();
+        let mut visible: Vec<&CacheEntryInfo> = self
+            .entries
+            .iter()
+            .filter(|entry| {
+                entry.key.to_lowercase().contains(&filter) || entry.category.contains(&filter)
+            })
+            .collect();
+        match self.sort {
+            CacheSort::Size => visible.sort_by(|a, b| b.size.cmp(&a.size).then(a.key.cmp(&b.key))),
+            CacheSort::Age => {
+                visible.sort_by(|a, b| b.age_seconds.cmp(&a.age_seconds).then(a.key.cmp(&b.key)))
+            }
+        }
+        visible
+    }
+
+    /// The entry under the cursor
+    pub fn selected_entry(&self) -> Option<&CacheEntryInfo> {
+        self.visible().get(self.selected).copied()
+    }
+
+    /// The pattern being typed, if any
+    pub fn pattern(&self) -> Option<&str> {
+        match self.input {
+            Some(CacheInput::Pattern(ref pattern)) => Some(pattern),
+            _ => None,
+        }
+    }
+
+    pub fn push_input(&mut self, c: char) {
+        match self.input {
+            Some(CacheInput::Filter) => {
+                self.filter.push(c);
+                self.clamp_selection();
+            }
+            Some(CacheInput::Pattern(ref mut pattern)) => pattern.push(c),
+            None => {}
+        }
+    }
+
+    pub fn pop_input(&mut self) {
+        match self.input {
+            Some(CacheInput::Filter) => {
+                self.filter.pop();
+                self.clamp_selection();
+            }
+            Some(CacheInput::Pattern(ref mut pattern)) => {
+                pattern.pop();
+            }
+            None => {}
+        }
+    }
+
+    pub fn clear_input(&mut self) {
+        match self.input {
+            Some(CacheInput::Filter) => {
+                self.filter.clear();
+                self.clamp_selection();
+            }
+            Some(CacheInput::Pattern(ref mut pattern)) => pattern.clear(),
+            None => {}
+        }
+    }
+
+    /// Stop typing, a cancelled filter is cleared
+    pub fn cancel_input(&mut self) {
+        if self.input.take() == Some(CacheInput::Filter) {
+            self.filter.clear();
+            self.clamp_selection();
+        }
+    }
+
+    pub fn cycle_sort(&mut self) {
+        self.sort = self.sort.next();
+        self.selected = 0;
+    }
+
+    pub fn select_next(&mut self) {
+        let count = self.visible().len();
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        let count = self.visible().len();
+        if count > 0 {
+            self.selected = (self.selected + count - 1) % count;
+        }
+    }
+
+    pub fn select_last(&mut self) {
+        self.selected = self.visible().len().saturating_sub(1);
+    }
+
+    fn clamp_selection(&mut self) {
+        let count = self.visible().len();
+        self.selected = self.selected.min(count.saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, category: &str, size: usize, age_seconds: u64) -> CacheEntryInfo {
+        CacheEntryInfo {
+            key: key.to_string(),
+            category: category.to_string(),
+            size,
+            age_seconds,
+            fresh: age_seconds < 1200,
+        }
+    }
+
+    fn inspector() -> CacheInspectorState {
+        let mut state = CacheInspectorState::default();
+        state.set_entries(vec![
+            entry("GET:/repos/acme/api/pulls?state=open", "pulls", 4000, 60),
+            entry(
+                "GET:/repos/acme/api/pulls/12/comments",
+                "pulls/comments",
+                300,
+                3000,
+            ),
+            entry("GET:/repos/acme/web/labels", "labels", 900, 600),
+        ]);
+        state
+    }
+
+    fn keys(state: &CacheInspectorState) -> Vec<&str> {
+        state
+            .visible()
+            .into_iter()
+            .map(|entry| entry.key.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_sorted_by_size_or_age() {
+        let mut state = inspector();
+        assert_eq!(
+            keys(&state),
+            vec![
+                "GET:/repos/acme/api/pulls?state=open",
+                "GET:/repos/acme/web/labels",
+                "GET:/repos/acme/api/pulls/12/comments",
+            ]
+        );
+
+        state.cycle_sort();
+        assert_eq!(state.sort, CacheSort::Age);
+        assert_eq!(
+            state.selected_entry().unwrap().key,
+            "GET:/repos/acme/api/pulls/12/comments"
+        );
+    }
+
+    #[test]
+    fn test_filter_by_key_or_category_while_typing() {
+        let mut state = inspector();
+        state.select_last();
+        state.input = Some(CacheInput::Filter);
+        for c in "ACME/API".chars() {
+            state.push_input(c);
+        }
+        assert_eq!(keys(&state).len(), 2);
+        assert_eq!(state.selected, 1);
+
+        state.clear_input();
+        for c in "comments".chars() {
+            state.push_input(c);
+        }
+        assert_eq!(keys(&state), vec!["GET:/repos/acme/api/pulls/12/comments"]);
+        assert_eq!(state.selected, 0);
+
+        // Cancelling drops the filter
+        state.cancel_input();
+        assert_eq!(state.input, None);
+        assert_eq!(keys(&state).len(), 3);
+    }
+
+    #[test]
+    fn test_pattern_does_not_filter() {
+        let mut state = inspector();
+        state.input = Some(CacheInput::Pattern(String::new()));
+        for c in "/web/".chars() {
+            state.push_input(c);
+        }
+        assert_eq!(state.pattern(), Some("/web/"));
+        assert_eq!(keys(&state).len(), 3);
+
+        state.cancel_input();
+        assert_eq!(state.pattern(), None);
+    }
+}
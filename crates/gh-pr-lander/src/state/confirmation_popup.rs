@@ -3,57 +3,181 @@
 //! State for a reusable confirmation popup with text input.
 //! Used for PR actions that require user confirmation and optional message editing.
 
+use crate::domain_models::{PolicyStep, Pr, Repository};
+use crate::utils::template::PrPlaceholders;
+
 /// The intent of the confirmation - determines what action to execute on confirm
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ConfirmationIntent {
+    /// Merge the specified PRs, optionally deleting their head branches
+    Merge {
+        pr_numbers: Vec<u64>,
+        delete_branch: bool,
+    },
     /// Approve the specified PRs with a message
     Approve { pr_numbers: Vec<u64> },
     /// Post a comment on the specified PRs
     Comment { pr_numbers: Vec<u64> },
+    /// Post a comment again on the PRs it failed on, `failures` tell why
+    RetryComment {
+        pr_numbers: Vec<u64>,
+        failures: Vec<String>,
+    },
     /// Request changes on the specified PRs
     RequestChanges { pr_numbers: Vec<u64> },
     /// Close the specified PRs with a message
     Close { pr_numbers: Vec<u64> },
+    /// Edit the local note of a PR (never sent to GitHub)
+    EditNote { pr_number: u64 },
+    /// Merge bot PRs, `updates` lists what each of them updates
+    MergeBotUpdates {
+        pr_numbers: Vec<u64>,
+        updates: Vec<String>,
+    },
+    /// Run the policy steps, `report` is the dry-run result
+    RunPolicy {
+        repo: Box<Repository>,
+        pr_numbers: Vec<u64>,
+        steps: Vec<PolicyStep>,
+        report: Vec<String>,
+    },
+    /// Change the log level filter at runtime
+    SetLogLevel,
+    /// Remove a repository from the list, `details` tell what goes with it
+    RemoveRepository {
+        repo: Box<Repository>,
+        details: Vec<String>,
+    },
+    /// Quit with unfinished work, `summary` tells what ("2 merges in progress")
+    Quit {
+        summary: String,
+        details: Vec<String>,
+        /// Operations are in flight, so quitting once they finished is offered
+        can_wait: bool,
+    },
+    /// Draft release notes of the repository, the message is the tag or date they start at
+    ReleaseNotes { repo: Box<Repository> },
 }
 
 impl ConfirmationIntent {
     /// Get the PR numbers for this intent
     pub fn pr_numbers(&self) -> &[u64] {
         match self {
-            Self::Approve { pr_numbers }
+            Self::Merge { pr_numbers, .. }
+            | Self::Approve { pr_numbers }
             | Self::Comment { pr_numbers }
+            | Self::RetryComment { pr_numbers, .. }
             | Self::RequestChanges { pr_numbers }
-            | Self::Close { pr_numbers } => pr_numbers,
+            | Self::Close { pr_numbers }
+            | Self::MergeBotUpdates { pr_numbers, .. }
+            | Self::RunPolicy { pr_numbers, .. } => pr_numbers,
+            Self::EditNote { pr_number } => std::slice::from_ref(pr_number),
+            Self::SetLogLevel
+            | Self::RemoveRepository { .. }
+            | Self::Quit { .. }
+            | Self::ReleaseNotes { .. } => &[],
         }
     }
 
     /// Get a human-readable action verb for this intent
     pub fn action_verb(&self) -> &'static str {
         match self {
+            Self::Merge { .. } => "Merging",
             Self::Approve { .. } => "Approving",
             Self::Comment { .. } => "Commenting on",
+            Self::RetryComment { .. } => "Failed on",
             Self::RequestChanges { .. } => "Requesting changes on",
             Self::Close { .. } => "Closing",
+            Self::EditNote { .. } => "Note for",
+            Self::MergeBotUpdates { .. } => "Merging",
+            Self::RunPolicy { .. } => "Applying policy to",
+            Self::SetLogLevel => "Setting",
+            Self::RemoveRepository { .. } => "Removing",
+            Self::Quit { .. } => "Unfinished",
+            Self::ReleaseNotes { .. } => "Drafting",
         }
     }
 
     /// Get the title for the popup
     pub fn popup_title(&self) -> &'static str {
         match self {
+            Self::Merge { .. } => "Merge Pull Request",
             Self::Approve { .. } => "Approve Pull Request",
             Self::Comment { .. } => "Comment on Pull Request",
+            Self::RetryComment { .. } => "Comment Failed",
             Self::RequestChanges { .. } => "Request Changes",
             Self::Close { .. } => "Close Pull Request",
+            Self::EditNote { .. } => "Local Note",
+            Self::MergeBotUpdates { .. } => "Merge Dependency Updates",
+            Self::RunPolicy { .. } => "Run Policy (Dry Run)",
+            Self::SetLogLevel => "Set Log Level",
+            Self::RemoveRepository { .. } => "Remove Repository",
+            Self::Quit { .. } => "Quit",
+            Self::ReleaseNotes { .. } => "Generate Release Notes",
         }
     }
 
     /// Get the instruction text
     pub fn instructions(&self) -> &'static str {
         match self {
+            Self::Merge { .. } => "Merged with the default merge method:",
             Self::Approve { .. } => "Enter your approval message:",
             Self::Comment { .. } => "Enter your comment:",
+            Self::RetryComment { .. } => "Confirm to post the comment again on these PRs:",
             Self::RequestChanges { .. } => "Enter your change request message:",
             Self::Close { .. } => "Enter a closing comment (optional):",
+            Self::EditNote { .. } => "Only stored on this machine (empty removes the note):",
+            Self::MergeBotUpdates { .. } => "Patch/minor bot PRs with green CI, merged one by one:",
+            Self::RunPolicy { .. } => "Nothing has happened yet, confirm to run these steps:",
+            Self::SetLogLevel => "Default and per-module levels (e.g. info,gh_client=debug):",
+            Self::RemoveRepository { .. } => "Undo shortly after with \"Undo repository removal\"",
+            Self::Quit { can_wait: true, .. } => "Quit anyway? [q]uit / [w]ait / [c]ancel",
+            Self::Quit { .. } => "Quit anyway? [q]uit / [c]ancel",
+            Self::ReleaseNotes { .. } => "PRs merged since the tag or date (YYYY-MM-DD):",
+        }
+    }
+
+    /// Lines listing exactly what the action affects
+    pub fn details(&self) -> &[String] {
+        match self {
+            Self::RetryComment { failures, .. } => failures,
+            Self::MergeBotUpdates { updates, .. } => updates,
+            Self::RunPolicy { report, .. } => report,
+            Self::RemoveRepository { details, .. } => details,
+            Self::Quit { details, .. } => details,
+            _ => &[],
+        }
+    }
+
+    /// Whether the popup asks for a message
+    pub fn takes_message(&self) -> bool {
+        !matches!(
+            self,
+            Self::Merge { .. }
+                | Self::MergeBotUpdates { .. }
+                | Self::RunPolicy { .. }
+                | Self::RemoveRepository { .. }
+                | Self::Quit { .. }
+        )
+    }
+
+    /// Whether the message may use PR placeholders, filled in per PR
+    pub fn is_templated(&self) -> bool {
+        matches!(self, Self::Comment { .. } | Self::RetryComment { .. })
+    }
+
+    /// The "delete branch after merge" option, if this intent offers it
+    pub fn delete_branch(&self) -> Option<bool> {
+        match self {
+            Self::Merge { delete_branch, .. } => Some(*delete_branch),
+            _ => None,
+        }
+    }
+
+    /// Flip the "delete branch after merge" option (no-op without the option)
+    pub fn toggle_delete_branch(&mut self) {
+        if let Self::Merge { delete_branch, .. } = self {
+            *delete_branch = !*delete_branch;
         }
     }
 }
@@ -67,6 +191,8 @@ pub struct ConfirmationPopupState {
     pub input_value: String,
     /// Repository context (owner/repo) for display
     pub repo_context: String,
+    /// Things to double check before confirming (e.g. failing CI)
+    pub warnings: Vec<String>,
 }
 
 impl ConfirmationPopupState {
@@ -76,11 +202,24 @@ impl ConfirmationPopupState {
             intent,
             input_value: default_message,
             repo_context,
+            warnings: Vec::new(),
         }
     }
 
+    /// Show `warnings` prominently above the message input
+    pub fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
     /// Format the target info string (e.g., "PR #123" or "PR #123, #321, #453")
     pub fn target_info(&self) -> String {
+        match &self.intent {
+            ConfirmationIntent::RemoveRepository { repo, .. }
+            | ConfirmationIntent::ReleaseNotes { repo } => return repo.full_display_name(),
+            ConfirmationIntent::Quit { summary, .. } => return summary.clone(),
+            _ => {}
+        }
         let pr_numbers = self.intent.pr_numbers();
         if pr_numbers.is_empty() {
             return String::new();
@@ -113,12 +252,35 @@ impl ConfirmationPopupState {
     pub fn requires_input(&self) -> bool {
         match self.intent {
             // Comment requires a message
-            ConfirmationIntent::Comment { .. } => true,
+            ConfirmationIntent::Comment { .. } | ConfirmationIntent::RetryComment { .. } => true,
             // Request changes requires a message
             ConfirmationIntent::RequestChanges { .. } => true,
+            // The log filter needs at least a level
+            ConfirmationIntent::SetLogLevel => true,
+            // Notes start at a tag or date
+            ConfirmationIntent::ReleaseNotes { .. } => true,
             // Approve and close can have empty messages
             ConfirmationIntent::Approve { .. } | ConfirmationIntent::Close { .. } => false,
+            // An empty note removes it
+            ConfirmationIntent::EditNote { .. } => false,
+            // Nothing to type, the listed PRs are merged
+            ConfirmationIntent::Merge { .. }
+            | ConfirmationIntent::MergeBotUpdates { .. }
+            | ConfirmationIntent::RunPolicy { .. } => false,
+            // Nothing to type, the repository is removed
+            ConfirmationIntent::RemoveRepository { .. } => false,
+            // Keys choose, there is no message
+            ConfirmationIntent::Quit { .. } => false,
+        }
+    }
+
+    /// The message as posted on `pr`, if placeholders change it
+    pub fn preview(&self, pr: &Pr) -> Option<String> {
+        if !self.intent.is_templated() {
+            return None;
         }
+        let rendered = PrPlaceholders::of(pr).render(&self.input_value);
+        (rendered != self.input_value).then(|| format!("#{}: {}", pr.number, rendered))
     }
 
     /// Check if the form is valid for submission
@@ -189,6 +351,24 @@ mod tests {
         assert!(!state_close.requires_input()); // Close doesn't require message
     }
 
+    #[test]
+    fn test_merge_delete_branch_option() {
+        let mut intent = ConfirmationIntent::Merge {
+            pr_numbers: vec![1],
+            delete_branch: true,
+        };
+        assert!(!intent.takes_message());
+        assert_eq!(intent.delete_branch(), Some(true));
+        intent.toggle_delete_branch();
+        assert_eq!(intent.delete_branch(), Some(false));
+
+        let mut approve = ConfirmationIntent::Approve {
+            pr_numbers: vec![1],
+        };
+        approve.toggle_delete_branch();
+        assert_eq!(approve.delete_branch(), None);
+    }
+
     #[test]
     fn test_is_valid() {
         let comment = ConfirmationIntent::Comment {
@@ -203,4 +383,30 @@ mod tests {
         assert!(!state_empty.is_valid()); // Empty comment is invalid
         assert!(state_with_msg.is_valid()); // Non-empty comment is valid
     }
+
+    #[test]
+    fn test_preview_fills_in_placeholders() {
+        let comment = ConfirmationIntent::Comment {
+            pr_numbers: vec![7, 8],
+        };
+        let mut state = ConfirmationPopupState::new(
+            comment,
+            "@dependabot rebase".to_string(),
+            "owner/repo".to_string(),
+        );
+        let pr = Pr::new(7, "Bump serde", "dependabot[bot]", "sha");
+        assert_eq!(state.preview(&pr), None);
+
+        state.input_value = "Freeze: #{number} by @{author} waits".to_string();
+        assert_eq!(
+            state.preview(&pr).as_deref(),
+            Some("#7: Freeze: #7 by @dependabot[bot] waits")
+        );
+
+        let close = ConfirmationIntent::Close {
+            pr_numbers: vec![7],
+        };
+        let state = ConfirmationPopupState::new(close, "{number}".to_string(), String::new());
+        assert_eq!(state.preview(&pr), None);
+    }
 }
@@ -0,0 +1,14 @@
+//! Conflict Helper State
+
+use crate::domain_models::{ConflictSummary, Pr, Repository};
+
+/// State of the conflict helper popup
+#[derive(Debug, Clone, Default)]
+pub struct ConflictHelperState {
+    /// The PR whose branch could not be updated
+    pub target: Option<(Repository, Pr)>,
+    /// Likely conflicting files, `None` while comparing
+    pub summary: Option<ConflictSummary>,
+    /// Error of the last comparison
+    pub error: Option<String>,
+}
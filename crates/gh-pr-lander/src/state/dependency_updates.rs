@@ -0,0 +1,143 @@
+//! Dependency Updates State
+//!
+//! Bot PRs of all loaded repositories grouped by the dependency they update,
+//! so the same bump across several repositories shows up as one group.
+
+use super::MainViewState;
+use crate::domain_models::{DependencyUpdate, MergeableStatus, PrState, Repository};
+
+/// A bot PR within a dependency group
+#[derive(Debug, Clone, PartialEq)]
+pub struct DependencyUpdateEntry {
+    pub repo: Repository,
+    pub pr_number: usize,
+    pub update: DependencyUpdate,
+    pub mergeable: MergeableStatus,
+    pub html_url: String,
+}
+
+/// Open bot PRs updating the same dependency
+#[derive(Debug, Clone, PartialEq)]
+pub struct DependencyGroup {
+    /// Dependency name as written by the first PR of the group
+    pub name: String,
+    pub entries: Vec<DependencyUpdateEntry>,
+}
+
+/// State of the dependency updates view
+#[derive(Debug, Clone, Default)]
+pub struct DependencyUpdatesState {
+    /// Cursor position among the entries of all groups
+    pub selected: usize,
+}
+
+impl DependencyUpdatesState {
+    /// Entry under the cursor
+    pub fn selected_entry<'a>(
+        &self,
+        groups: &'a [DependencyGroup],
+    ) -> Option<&'a DependencyUpdateEntry> {
+        groups
+            .iter()
+            .flat_map(|group| &group.entries)
+            .nth(self.selected)
+    }
+}
+
+/// Group the open bot PRs of all loaded repositories by dependency
+///
+/// Groups spanning the most repositories come first, ties are sorted by name.
+pub fn dependency_groups(main_view: &MainViewState) -> Vec<DependencyGroup> {
+    let mut groups: Vec<(String, DependencyGroup)> = Vec::new();
+
+    for (repo_idx, repo) in main_view.repositories.iter().enumerate() {
+        let Some(data) = main_view.repo_data.get(&repo_idx) else {
+            continue;
+        };
+        for pr in data.prs.iter().filter(|pr| pr.state == PrState::Open) {
+            let Some(ref update) = pr.dependency_update else {
+                continue;
+            };
+            let entry = DependencyUpdateEntry {
+                repo: repo.clone(),
+                pr_number: pr.number,
+                update: update.clone(),
+                mergeable: pr.mergeable,
+                html_url: pr.html_url.clone(),
+            };
+            let key = update.group_key();
+            match groups.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, group)) => group.entries.push(entry),
+                None => groups.push((
+                    key,
+                    DependencyGroup {
+                        name: update.name.clone(),
+                        entries: vec![entry],
+                    },
+                )),
+            }
+        }
+    }
+
+    let mut groups: Vec<DependencyGroup> = groups.into_iter().map(|(_, group)| group).collect();
+    groups.sort_by(|a, b| {
+        b.entries
+            .len()
+            .cmp(&a.entries.len())
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::Pr;
+    use crate::state::RepositoryData;
+
+    fn bump(number: usize, name: &str, to: &str) -> Pr {
+        Pr::new(
+            number,
+            format!("Bump {} from 1.0.0 to {}", name, to),
+            "dependabot[bot]",
+            "sha",
+        )
+        .with_branches(format!("dependabot/cargo/{}-{}", name, to), "main")
+        .with_detected_dependency_update()
+    }
+
+    #[test]
+    fn test_bumps_of_a_dependency_cluster_across_repositories() {
+        let mut main_view = MainViewState::default();
+        for (idx, name) in ["api", "web", "cli"].iter().enumerate() {
+            main_view
+                .repositories
+                .push(Repository::new("acme", *name, "main"));
+            let mut prs = vec![bump(1, "serde", "1.0.1"), Pr::new(2, "Fix", "alice", "sha")];
+            if idx == 0 {
+                prs.push(bump(3, "tokio", "2.0.0"));
+            }
+            main_view.repo_data.insert(
+                idx,
+                RepositoryData {
+                    prs,
+                    ..Default::default()
+                },
+            );
+        }
+
+        let groups = dependency_groups(&main_view);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].name, "serde");
+        let repos: Vec<&str> = groups[0]
+            .entries
+            .iter()
+            .map(|entry| entry.repo.repo.as_str())
+            .collect();
+        assert_eq!(repos, ["api", "web", "cli"]);
+        assert_eq!(groups[1].name, "tokio");
+
+        let state = DependencyUpdatesState { selected: 3 };
+        assert_eq!(state.selected_entry(&groups).unwrap().update.name, "tokio");
+    }
+}
@@ -3,7 +3,24 @@
 //! Wrapper state for the diff viewer panel in gh-pr-lander.
 //! This delegates to gh_diff_viewer's DiffViewerState for the actual diff logic.
 
+use crate::domain_models::Repository;
+use gh_diff_viewer::model::PendingComment;
 use gh_diff_viewer::{DiffHighlighter, DiffViewerState as InnerState, PullRequestDiff};
+use std::collections::VecDeque;
+
+/// How many PRs keep their review state while other PRs are viewed
+const SAVED_REVIEWS_LIMIT: usize = 5;
+
+/// Review state (cursor, scroll, collapsed directories, comments) of a PR
+/// the diff viewer showed before
+#[derive(Debug, Clone)]
+pub struct SavedReview {
+    pub repo: Repository,
+    pub pr_number: u64,
+    /// Head SHA the review state belongs to
+    pub head_sha: String,
+    pub inner: InnerState,
+}
 
 /// Loading state for the diff viewer
 #[derive(Debug, Clone, Default)]
@@ -29,10 +46,13 @@ pub struct DiffViewerState {
     /// Loading state
     pub loading: DiffViewerLoadingState,
     /// PR context
+    pub repo: Option<Repository>,
     pub pr_number: Option<u64>,
     pub pr_title: Option<String>,
     /// Head SHA for API calls (comments)
     pub head_sha: Option<String>,
+    /// Review states of the last PRs viewed before this one, oldest first
+    pub saved: VecDeque<SavedReview>,
 }
 
 impl Default for DiffViewerState {
@@ -41,9 +61,11 @@ impl Default for DiffViewerState {
             inner: None,
             highlighter: DiffHighlighter::new(),
             loading: DiffViewerLoadingState::default(),
+            repo: None,
             pr_number: None,
             pr_title: None,
             head_sha: None,
+            saved: VecDeque::new(),
         }
     }
 }
@@ -54,9 +76,11 @@ impl Clone for DiffViewerState {
             inner: self.inner.clone(),
             highlighter: DiffHighlighter::new(), // Highlighter is not Clone, create new
             loading: self.loading.clone(),
+            repo: self.repo.clone(),
             pr_number: self.pr_number,
             pr_title: self.pr_title.clone(),
             head_sha: self.head_sha.clone(),
+            saved: self.saved.clone(),
         }
     }
 }
@@ -68,20 +92,110 @@ impl DiffViewerState {
     }
 
     /// Load a diff into the viewer
+    ///
+    /// Reloading a PR at the same head SHA restores its review state. If the
+    /// PR was updated, only the collapsed directories are kept, and comments
+    /// not posted yet become outdated drafts at their old positions.
     pub fn load(
         &mut self,
         diff: PullRequestDiff,
+        repo: Repository,
         pr_number: u64,
         pr_title: String,
         head_sha: String,
     ) {
-        self.inner = Some(InnerState::new(diff));
+        self.save_review();
+        let previous = self
+            .saved
+            .iter()
+            .position(|saved| saved.repo == repo && saved.pr_number == pr_number)
+            .and_then(|idx| self.saved.remove(idx));
+
+        let inner = match previous {
+            Some(saved) if saved.head_sha == head_sha => {
+                log::debug!("Restoring review state of PR #{}", pr_number);
+                saved.inner
+            }
+            Some(saved) => {
+                let mut inner = InnerState::new(diff);
+                inner.restore_collapsed_dirs(&saved.inner.collapsed_dirs());
+                inner.pending_comments = outdated_drafts(&saved.inner);
+                inner
+            }
+            None => InnerState::new(diff),
+        };
+        self.inner = Some(inner);
         self.loading = DiffViewerLoadingState::Loaded;
+        self.repo = Some(repo);
         self.pr_number = Some(pr_number);
         self.pr_title = Some(pr_title);
         self.head_sha = Some(head_sha);
     }
 
+    /// Review state kept for a PR whose head moved away from `head_sha`
+    ///
+    /// Returns the number of comments that would be kept as outdated drafts,
+    /// `None` if there is nothing to reset.
+    pub fn outdated_review(
+        &self,
+        repo: &Repository,
+        pr_number: u64,
+        head_sha: &str,
+    ) -> Option<usize> {
+        let current = match (&self.inner, &self.repo, &self.head_sha) {
+            (Some(inner), Some(repo), Some(sha)) if self.pr_number == Some(pr_number) => {
+                Some((repo, sha.as_str(), inner))
+            }
+            _ => None,
+        };
+        let saved = self
+            .saved
+            .iter()
+            .filter(|saved| saved.pr_number == pr_number)
+            .map(|saved| (&saved.repo, saved.head_sha.as_str(), &saved.inner));
+
+        current
+            .into_iter()
+            .chain(saved)
+            .find(|(saved_repo, _, _)| *saved_repo == repo)
+            .filter(|(_, sha, _)| *sha != head_sha)
+            .map(|(_, _, inner)| inner.unsent_comments().len())
+    }
+
+    /// Review comments not posted yet, of the shown PR and the saved ones
+    ///
+    /// Includes the comment being written. They only live in memory.
+    pub fn unsent_comment_count(&self) -> usize {
+        self.inner
+            .iter()
+            .chain(self.saved.iter().map(|saved| &saved.inner))
+            .map(|inner| inner.unsent_comments().len())
+            .sum()
+    }
+
+    /// Keep the review state of the shown PR for when it is opened again
+    fn save_review(&mut self) {
+        let (Some(inner), Some(repo), Some(pr_number), Some(head_sha)) = (
+            self.inner.take(),
+            self.repo.clone(),
+            self.pr_number,
+            self.head_sha.clone(),
+        ) else {
+            return;
+        };
+        self.saved
+            .retain(|saved| !(saved.repo == repo && saved.pr_number == pr_number));
+        self.saved.push_back(SavedReview {
+            repo,
+            pr_number,
+            head_sha,
+            inner,
+        });
+        while self.saved.len() > SAVED_REVIEWS_LIMIT {
+            self.saved.pop_front();
+        }
+    }
+
     /// Set loading state
     pub fn set_loading(&mut self) {
         self.loading = DiffViewerLoadingState::Loading;
@@ -90,7 +204,7 @@ impl DiffViewerState {
     /// Set error state
     pub fn set_error(&mut self, error: String) {
         self.loading = DiffViewerLoadingState::Error(error);
-        self.inner = None;
+        self.save_review();
     }
 
     /// Check if a diff is loaded
@@ -115,8 +229,107 @@ impl DiffViewerState {
     pub fn reset(&mut self) {
         self.inner = None;
         self.loading = DiffViewerLoadingState::Idle;
+        self.repo = None;
         self.pr_number = None;
         self.pr_title = None;
         self.head_sha = None;
     }
 }
+
+/// Comments of an older revision not posted yet, marked as outdated
+fn outdated_drafts(inner: &InnerState) -> Vec<PendingComment> {
+    inner
+        .unsent_comments()
+        .into_iter()
+        .map(|mut comment| {
+            comment.outdated = true;
+            comment
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gh_diff_viewer::DiffAction;
+
+    const DIFF: &str = "diff --git a/a b/a\n--- a/a\n+++ b/a\n@@ -1,2 +1,2 @@\n x\n-y\n+z\n";
+
+    fn diff() -> PullRequestDiff {
+        gh_diff_viewer::parse_unified_diff(DIFF, "base", "head").unwrap()
+    }
+
+    fn repo() -> Repository {
+        Repository::new("acme", "api", "main")
+    }
+
+    fn load(state: &mut DiffViewerState, pr_number: u64, head_sha: &str) {
+        state.load(
+            diff(),
+            repo(),
+            pr_number,
+            "t".to_string(),
+            head_sha.to_string(),
+        );
+    }
+
+    /// Write an (unsent) comment on a diff row of the open PR
+    fn comment(state: &mut DiffViewerState, row: usize, body: &str) {
+        let inner = state.inner.as_mut().unwrap();
+        inner.nav.file_tree_focused = false;
+        inner.nav.cursor_line = row;
+        inner.handle_action(DiffAction::StartComment);
+        for c in body.chars() {
+            inner.handle_action(DiffAction::CommentInsertChar(c));
+        }
+        inner.handle_action(DiffAction::CommitComment);
+    }
+
+    #[test]
+    fn test_review_state_survives_viewing_other_prs() {
+        let mut state = DiffViewerState::new();
+        load(&mut state, 1, "aaa");
+        comment(&mut state, 2, "nit");
+        assert_eq!(state.outdated_review(&repo(), 1, "aaa"), None);
+
+        load(&mut state, 2, "bbb");
+        assert!(state.inner.as_ref().unwrap().pending_comments.is_empty());
+        assert_eq!(state.unsent_comment_count(), 1);
+
+        load(&mut state, 1, "aaa");
+        let inner = state.inner.as_ref().unwrap();
+        assert_eq!(inner.pending_comments[0].body, "nit");
+        assert!(!inner.pending_comments[0].outdated);
+        assert_eq!(inner.nav.cursor_line, 2);
+        assert_eq!(state.saved.len(), 1);
+    }
+
+    #[test]
+    fn test_updated_pr_keeps_unsent_comments_as_outdated_drafts() {
+        let mut state = DiffViewerState::new();
+        load(&mut state, 1, "aaa");
+        comment(&mut state, 2, "posted");
+        state.inner.as_mut().unwrap().pending_comments[0].github_id = Some(7);
+        comment(&mut state, 1, "draft");
+        load(&mut state, 2, "bbb");
+
+        assert_eq!(state.outdated_review(&repo(), 1, "aaa"), None);
+        assert_eq!(state.outdated_review(&repo(), 1, "ccc"), Some(1));
+
+        load(&mut state, 1, "ccc");
+        let inner = state.inner.as_ref().unwrap();
+        assert_eq!(inner.pending_comments.len(), 1);
+        assert_eq!(inner.pending_comments[0].body, "draft");
+        assert!(inner.pending_comments[0].outdated);
+    }
+
+    #[test]
+    fn test_saved_reviews_are_bounded() {
+        let mut state = DiffViewerState::new();
+        for pr_number in 0..=SAVED_REVIEWS_LIMIT as u64 + 2 {
+            load(&mut state, pr_number, "sha");
+        }
+        assert_eq!(state.saved.len(), SAVED_REVIEWS_LIMIT);
+        assert_eq!(state.saved[0].pr_number, 2);
+    }
+}
@@ -0,0 +1,218 @@
+//! Duplicate Updates State
+//!
+//! The same bot update (e.g. "Bump serde from 1.0.200 to 1.0.201") opened in
+//! several tracked repositories. The index is kept up to date per repository
+//! as its PRs load, so the PR table can mark such PRs without regrouping all
+//! repositories on every frame.
+
+use crate::domain_models::{Pr, PrState, Repository, UpdateKey};
+use std::collections::{HashMap, HashSet};
+
+/// Open bot PRs of all loaded repositories by update
+#[derive(Debug, Clone, Default)]
+pub struct DuplicateUpdateIndex {
+    /// PRs opening each update, in the order their repositories loaded
+    by_key: HashMap<UpdateKey, Vec<(Repository, usize)>>,
+    /// Updates each repository contributed, replaced when it loads again
+    by_repo: HashMap<Repository, Vec<UpdateKey>>,
+}
+
+impl DuplicateUpdateIndex {
+    /// Replace the updates of `repo` with those of its open bot PRs
+    pub fn update_repo(&mut self, repo: &Repository, prs: &[Pr]) {
+        self.remove_repo(repo);
+        let mut keys = Vec::new();
+        for pr in prs.iter().filter(|pr| pr.state == PrState::Open) {
+            let Some(update) = &pr.dependency_update else {
+                continue;
+            };
+            let key = update.update_key();
+            self.by_key
+                .entry(key.clone())
+                .or_default()
+                .push((repo.clone(), pr.number));
+            keys.push(key);
+        }
+        if !keys.is_empty() {
+            self.by_repo.insert(repo.clone(), keys);
+        }
+    }
+
+    /// Forget the updates of a repository no longer tracked
+    pub fn remove_repo(&mut self, repo: &Repository) {
+        let Some(keys) = self.by_repo.remove(repo) else {
+            return;
+        };
+        for key in keys {
+            if let Some(prs) = self.by_key.get_mut(&key) {
+                prs.retain(|(other, _)| other != repo);
+                if prs.is_empty() {
+                    self.by_key.remove(&key);
+                }
+            }
+        }
+    }
+
+    /// PRs opening the update `key`, in all repositories
+    pub fn siblings(&self, key: &UpdateKey) -> &[(Repository, usize)] {
+        self.by_key.get(key).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Number of distinct repositories with a PR opening the update `key`
+    ///
+    /// A repository tracked on two branches counts once.
+    pub fn repo_count(&self, key: &UpdateKey) -> usize {
+        let mut seen = Vec::new();
+        for (repo, _) in self.siblings(key) {
+            let id = repo.id();
+            if !seen.contains(&id) {
+                seen.push(id);
+            }
+        }
+        seen.len()
+    }
+
+    /// Update key of the PR if it is opened in more than one repository
+    pub fn duplicated_key(&self, pr: &Pr) -> Option<UpdateKey> {
+        let key = pr.dependency_update.as_ref()?.update_key();
+        (self.repo_count(&key) > 1).then_some(key)
+    }
+}
+
+/// State of the popup listing the PRs opening the same update
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateUpdatesState {
+    /// Update shown ("serde → 1.0.201")
+    pub summary: String,
+    /// The PRs opening it, across repositories
+    pub siblings: Vec<(Repository, usize)>,
+    /// Indices of the siblings picked for merging
+    pub picked: HashSet<usize>,
+    /// Cursor position among the siblings
+    pub selected: usize,
+}
+
+impl DuplicateUpdatesState {
+    /// Popup for the update `key`, every sibling picked
+    pub fn new(summary: String, siblings: Vec<(Repository, usize)>) -> Self {
+        Self {
+            summary,
+            picked: (0..siblings.len()).collect(),
+            siblings,
+            selected: 0,
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.siblings.is_empty() {
+            self.selected = (self.selected + 1) % self.siblings.len();
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if !self.siblings.is_empty() {
+            self.selected = (self.selected + self.siblings.len() - 1) % self.siblings.len();
+        }
+    }
+
+    /// Pick or unpick the sibling under the cursor
+    pub fn toggle(&mut self) {
+        if !self.picked.remove(&self.selected) && self.selected < self.siblings.len() {
+            self.picked.insert(self.selected);
+        }
+    }
+
+    pub fn pick_all(&mut self) {
+        self.picked = (0..self.siblings.len()).collect();
+    }
+
+    pub fn pick_none(&mut self) {
+        self.picked.clear();
+    }
+
+    /// Picked PR numbers grouped by repository, in list order
+    pub fn picked_by_repo(&self) -> Vec<(Repository, Vec<u64>)> {
+        let mut groups: Vec<(Repository, Vec<u64>)> = Vec::new();
+        for (idx, (repo, pr_number)) in self.siblings.iter().enumerate() {
+            if !self.picked.contains(&idx) {
+                continue;
+            }
+            match groups.iter_mut().find(|(other, _)| other == repo) {
+                Some((_, numbers)) => numbers.push(*pr_number as u64),
+                None => groups.push((repo.clone(), vec![*pr_number as u64])),
+            }
+        }
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bump(number: usize, to: &str) -> Pr {
+        Pr::new(
+            number,
+            format!("Bump serde from 1.0.200 to {}", to),
+            "dependabot[bot]",
+            "sha",
+        )
+        .with_branches(format!("dependabot/cargo/serde-{}", to), "main")
+        .with_detected_dependency_update()
+    }
+
+    fn repo(name: &str) -> Repository {
+        Repository::new("acme", name, "main")
+    }
+
+    #[test]
+    fn test_index_rebuilds_per_repository() {
+        let mut index = DuplicateUpdateIndex::default();
+        index.update_repo(&repo("api"), &[bump(1, "1.0.201")]);
+        let key = bump(1, "1.0.201").dependency_update.unwrap().update_key();
+        assert_eq!(index.repo_count(&key), 1);
+        assert_eq!(index.duplicated_key(&bump(1, "1.0.201")), None);
+
+        index.update_repo(&repo("web"), &[bump(7, "1.0.201"), bump(8, "1.0.202")]);
+        index.update_repo(&repo("cli"), &[bump(3, "1.0.201")]);
+        assert_eq!(index.repo_count(&key), 3);
+        assert_eq!(index.duplicated_key(&bump(1, "1.0.201")), Some(key.clone()));
+
+        // Reloading a repository replaces its PRs, merged ones drop out
+        let mut merged = bump(7, "1.0.201");
+        merged.state = PrState::Merged;
+        index.update_repo(&repo("web"), &[merged, bump(8, "1.0.202")]);
+        assert_eq!(index.siblings(&key), &[(repo("api"), 1), (repo("cli"), 3)]);
+
+        // The same repository on another branch is not another repository
+        index.update_repo(
+            &Repository::new("acme", "api", "release"),
+            &[bump(9, "1.0.201")],
+        );
+        assert_eq!(index.repo_count(&key), 2);
+
+        index.remove_repo(&repo("cli"));
+        index.remove_repo(&Repository::new("acme", "api", "release"));
+        assert_eq!(index.siblings(&key), &[(repo("api"), 1)]);
+    }
+
+    #[test]
+    fn test_popup_picks_siblings_by_repository() {
+        let mut state = DuplicateUpdatesState::new(
+            "serde → 1.0.201".to_string(),
+            vec![(repo("api"), 1), (repo("web"), 7), (repo("api"), 4)],
+        );
+        assert_eq!(
+            state.picked_by_repo(),
+            vec![(repo("api"), vec![1, 4]), (repo("web"), vec![7])]
+        );
+
+        state.select_next();
+        state.toggle();
+        assert_eq!(state.picked_by_repo(), vec![(repo("api"), vec![1, 4])]);
+        state.pick_none();
+        assert!(state.picked_by_repo().is_empty());
+        state.pick_all();
+        assert_eq!(state.picked.len(), 3);
+    }
+}
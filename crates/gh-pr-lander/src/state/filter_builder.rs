@@ -0,0 +1,308 @@
+//! Filter Builder State
+//!
+//! Popup assembling a `FilterSpec` row by row. A row counts as a criterion
+//! as soon as it has a value, clearing it drops the criterion again.
+
+use crate::state::PrFilter;
+use gh_pr_config::{FilterCi, FilterSpec};
+
+/// A row of the filter builder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterRow {
+    AuthorIs,
+    AuthorContains,
+    LabelsInclude,
+    LabelsExclude,
+    Ci,
+    Draft,
+    UpdateType,
+    BaseBranch,
+    OlderThan,
+    NewerThan,
+    MinSize,
+    MaxSize,
+    /// Name to save the filter under (not a criterion)
+    SaveAs,
+}
+
+impl FilterRow {
+    /// All rows, in display order
+    pub const ALL: [FilterRow; 13] = [
+        FilterRow::AuthorIs,
+        FilterRow::AuthorContains,
+        FilterRow::LabelsInclude,
+        FilterRow::LabelsExclude,
+        FilterRow::Ci,
+        FilterRow::Draft,
+        FilterRow::UpdateType,
+        FilterRow::BaseBranch,
+        FilterRow::OlderThan,
+        FilterRow::NewerThan,
+        FilterRow::MinSize,
+        FilterRow::MaxSize,
+        FilterRow::SaveAs,
+    ];
+
+    /// Row title
+    pub fn label(self) -> &'static str {
+        match self {
+            FilterRow::AuthorIs => "Author is",
+            FilterRow::AuthorContains => "Author contains",
+            FilterRow::LabelsInclude => "Labels include",
+            FilterRow::LabelsExclude => "Labels exclude",
+            FilterRow::Ci => "CI",
+            FilterRow::Draft => "Draft",
+            FilterRow::UpdateType => "Update type",
+            FilterRow::BaseBranch => "Base branch",
+            FilterRow::OlderThan => "Older than (days)",
+            FilterRow::NewerThan => "Newer than (days)",
+            FilterRow::MinSize => "Size at least",
+            FilterRow::MaxSize => "Size at most",
+            FilterRow::SaveAs => "Save as",
+        }
+    }
+
+    /// Values of a choice row (the empty value = any), None for text rows
+    pub fn choices(self) -> Option<&'static [&'static str]> {
+        match self {
+            FilterRow::Ci => Some(&["", "success", "pending", "failed"]),
+            FilterRow::Draft => Some(&["", "draft", "ready"]),
+            FilterRow::UpdateType => Some(&["", "patch", "minor", "major"]),
+            FilterRow::MinSize | FilterRow::MaxSize => Some(&["", "XS", "S", "M", "L", "XL"]),
+            _ => None,
+        }
+    }
+
+    /// Whether the row only takes digits
+    fn is_numeric(self) -> bool {
+        matches!(self, FilterRow::OlderThan | FilterRow::NewerThan)
+    }
+}
+
+/// State of the filter builder popup
+#[derive(Debug, Clone, Default)]
+pub struct FilterBuilderState {
+    /// Value per row of `FilterRow::ALL` (empty = not set)
+    pub values: [String; FilterRow::ALL.len()],
+    /// Cursor position (index into `FilterRow::ALL`)
+    pub selected: usize,
+}
+
+impl FilterBuilderState {
+    /// Builder prefilled with a repository's current filter
+    pub fn from_filter(filter: &PrFilter) -> Self {
+        let mut state = Self::default();
+        match filter {
+            PrFilter::Spec { name, spec } => {
+                state.set_spec(spec);
+                state.set(FilterRow::SaveAs, name.clone().unwrap_or_default());
+            }
+            PrFilter::Author(author) => state.set(FilterRow::AuthorIs, author.clone()),
+            _ => {}
+        }
+        state
+    }
+
+    fn set_spec(&mut self, spec: &FilterSpec) {
+        let days = |days: Option<u32>| days.map(|d| d.to_string()).unwrap_or_default();
+        self.set(FilterRow::AuthorIs, spec.author.clone().unwrap_or_default());
+        self.set(
+            FilterRow::AuthorContains,
+            spec.author_contains.clone().unwrap_or_default(),
+        );
+        self.set(FilterRow::LabelsInclude, spec.labels_include.join(", "));
+        self.set(FilterRow::LabelsExclude, spec.labels_exclude.join(", "));
+        self.set(
+            FilterRow::Ci,
+            spec.ci
+                .map(|ci| ci.as_str())
+                .unwrap_or_default()
+                .to_string(),
+        );
+        let draft = match spec.draft {
+            Some(true) => "draft",
+            Some(false) => "ready",
+            None => "",
+        };
+        self.set(FilterRow::Draft, draft.to_string());
+        self.set(
+            FilterRow::UpdateType,
+            spec.update_type.clone().unwrap_or_default(),
+        );
+        self.set(
+            FilterRow::BaseBranch,
+            spec.base_branch.clone().unwrap_or_default(),
+        );
+        self.set(FilterRow::OlderThan, days(spec.older_than_days));
+        self.set(FilterRow::NewerThan, days(spec.newer_than_days));
+        self.set(
+            FilterRow::MinSize,
+            spec.min_size.clone().unwrap_or_default(),
+        );
+        self.set(
+            FilterRow::MaxSize,
+            spec.max_size.clone().unwrap_or_default(),
+        );
+    }
+
+    /// Value of a row
+    pub fn value(&self, row: FilterRow) -> &str {
+        &self.values[row as usize]
+    }
+
+    fn set(&mut self, row: FilterRow, value: String) {
+        self.values[row as usize] = value;
+    }
+
+    /// Row under the cursor
+    pub fn selected_row(&self) -> FilterRow {
+        FilterRow::ALL[self.selected]
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % FilterRow::ALL.len();
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = (self.selected + FilterRow::ALL.len() - 1) % FilterRow::ALL.len();
+    }
+
+    /// Step through the values of the choice row under the cursor
+    pub fn cycle(&mut self, forward: bool) {
+        let row = self.selected_row();
+        let Some(choices) = row.choices() else {
+            return;
+        };
+        let current = choices
+            .iter()
+            .position(|choice| *choice == self.value(row))
+            .unwrap_or(0);
+        let next = if forward {
+            (current + 1) % choices.len()
+        } else {
+            (current + choices.len() - 1) % choices.len()
+        };
+        self.set(row, choices[next].to_string());
+    }
+
+    /// Type a character into the text row under the cursor
+    pub fn push_char(&mut self, c: char) {
+        let row = self.selected_row();
+        if row.choices().is_some() || (row.is_numeric() && !c.is_ascii_digit()) {
+            return;
+        }
+        self.values[self.selected].push(c);
+    }
+
+    pub fn pop_char(&mut self) {
+        let row = self.selected_row();
+        if row.choices().is_some() {
+            self.set(row, String::new());
+        } else {
+            self.values[self.selected].pop();
+        }
+    }
+
+    pub fn clear_row(&mut self) {
+        self.values[self.selected].clear();
+    }
+
+    /// Name to save the filter under
+    pub fn name(&self) -> Option<&str> {
+        Some(self.value(FilterRow::SaveAs).trim()).filter(|name| !name.is_empty())
+    }
+
+    /// The filter assembled from the rows
+    pub fn spec(&self) -> FilterSpec {
+        let text = |row| Some(self.value(row).trim().to_string()).filter(|v| !v.is_empty());
+        let list = |row| {
+            self.value(row)
+                .split(',')
+                .map(str::trim)
+                .filter(|label| !label.is_empty())
+                .map(str::to_string)
+                .collect()
+        };
+        let days = |row| self.value(row).parse().ok();
+        FilterSpec {
+            author: text(FilterRow::AuthorIs),
+            author_contains: text(FilterRow::AuthorContains),
+            labels_include: list(FilterRow::LabelsInclude),
+            labels_exclude: list(FilterRow::LabelsExclude),
+            ci: FilterCi::parse(self.value(FilterRow::Ci)),
+            draft: match self.value(FilterRow::Draft) {
+                "draft" => Some(true),
+                "ready" => Some(false),
+                _ => None,
+            },
+            update_type: text(FilterRow::UpdateType),
+            base_branch: text(FilterRow::BaseBranch),
+            older_than_days: days(FilterRow::OlderThan),
+            newer_than_days: days(FilterRow::NewerThan),
+            min_size: text(FilterRow::MinSize),
+            max_size: text(FilterRow::MaxSize),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn select(state: &mut FilterBuilderState, row: FilterRow) {
+        while state.selected_row() != row {
+            state.select_next();
+        }
+    }
+
+    fn type_text(state: &mut FilterBuilderState, text: &str) {
+        text.chars().for_each(|c| state.push_char(c));
+    }
+
+    #[test]
+    fn test_rows_assemble_spec() {
+        let mut state = FilterBuilderState::default();
+        assert!(state.spec().is_empty());
+
+        select(&mut state, FilterRow::LabelsInclude);
+        type_text(&mut state, "security, dependencies,");
+        select(&mut state, FilterRow::Ci);
+        state.cycle(true);
+        select(&mut state, FilterRow::Draft);
+        state.cycle(false);
+        select(&mut state, FilterRow::OlderThan);
+        type_text(&mut state, "1x4");
+
+        let spec = state.spec();
+        assert_eq!(spec.labels_include, vec!["security", "dependencies"]);
+        assert_eq!(spec.ci, Some(FilterCi::Success));
+        assert_eq!(spec.draft, Some(false));
+        assert_eq!(spec.older_than_days, Some(14));
+        assert_eq!(state.name(), None);
+    }
+
+    #[test]
+    fn test_prefill_round_trips_saved_filter() {
+        let spec = FilterSpec {
+            author_contains: Some("bot".to_string()),
+            labels_exclude: vec!["wip".to_string(), "do not merge".to_string()],
+            update_type: Some("major".to_string()),
+            draft: Some(true),
+            newer_than_days: Some(3),
+            base_branch: Some("release/*".to_string()),
+            max_size: Some("M".to_string()),
+            ..Default::default()
+        };
+        let mut state = FilterBuilderState::from_filter(&PrFilter::Spec {
+            name: Some("majors".to_string()),
+            spec: Box::new(spec.clone()),
+        });
+        assert_eq!(state.spec(), spec);
+        assert_eq!(state.name(), Some("majors"));
+
+        // Backspace on a choice row resets it to "any"
+        select(&mut state, FilterRow::UpdateType);
+        state.pop_char();
+        assert_eq!(state.spec().update_type, None);
+    }
+}
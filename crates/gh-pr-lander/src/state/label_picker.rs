@@ -0,0 +1,318 @@
+//! Label Picker State
+//!
+//! Multi-select popup to add and remove labels on the selected PRs.
+
+use crate::domain_models::Repository;
+use gh_client::Label;
+
+/// Checkbox state of a label across the target PRs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelCheck {
+    /// None of the PRs has the label
+    Unchecked,
+    /// All PRs have the label
+    Checked,
+    /// Only some of the PRs have the label
+    Indeterminate,
+}
+
+/// A repository label with its initial and edited checkbox state
+#[derive(Debug, Clone)]
+pub struct LabelChoice {
+    pub label: Label,
+    /// State when the picker was opened
+    pub initial: LabelCheck,
+    /// State after the user's toggles
+    pub current: LabelCheck,
+}
+
+impl LabelChoice {
+    fn new(label: Label, prs: &[(usize, Vec<String>)]) -> Self {
+        let count = prs
+            .iter()
+            .filter(|(_, labels)| labels.contains(&label.name))
+            .count();
+        let initial = match count {
+            0 => LabelCheck::Unchecked,
+            n if n == prs.len() => LabelCheck::Checked,
+            _ => LabelCheck::Indeterminate,
+        };
+        Self {
+            label,
+            initial,
+            current: initial,
+        }
+    }
+
+    /// Cycle checked / unchecked, and back to "leave as is" for mixed labels
+    fn toggle(&mut self) {
+        self.current = match self.current {
+            LabelCheck::Indeterminate => LabelCheck::Checked,
+            LabelCheck::Checked => LabelCheck::Unchecked,
+            LabelCheck::Unchecked if self.initial == LabelCheck::Indeterminate => {
+                LabelCheck::Indeterminate
+            }
+            LabelCheck::Unchecked => LabelCheck::Checked,
+        };
+    }
+}
+
+/// Labels to add to and remove from one PR
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LabelChanges {
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
+}
+
+impl LabelChanges {
+    pub fn is_empty(&self) -> bool {
+        self.add.is_empty() && self.remove.is_empty()
+    }
+}
+
+/// State of the label picker popup
+#[derive(Debug, Clone)]
+pub struct LabelPickerState {
+    /// Repository of the target PRs
+    pub repo: Repository,
+    /// Target PRs with their current labels
+    pub prs: Vec<(usize, Vec<String>)>,
+    /// Repository labels (empty while loading)
+    pub choices: Vec<LabelChoice>,
+    /// Filter typed by the user
+    pub query: String,
+    /// Cursor position within the filtered labels
+    pub selected: usize,
+    /// Repository labels are being fetched
+    pub loading: bool,
+    /// Changes are being applied
+    pub applying: bool,
+    /// Fetching the labels failed
+    pub error: Option<String>,
+}
+
+impl LabelPickerState {
+    pub fn new(repo: Repository, prs: Vec<(usize, Vec<String>)>) -> Self {
+        Self {
+            repo,
+            prs,
+            choices: Vec::new(),
+            query: String::new(),
+            selected: 0,
+            loading: true,
+            applying: false,
+            error: None,
+        }
+    }
+
+    /// Set the repository labels, checked per the target PRs' labels
+    pub fn set_labels(&mut self, labels: Vec<Label>) {
+        self.choices = labels
+            .into_iter()
+            .map(|label| LabelChoice::new(label, &self.prs))
+            .collect();
+        self.loading = false;
+        self.error = None;
+        self.clamp_selection();
+    }
+
+    /// Indices into `choices` matching the filter, in display order
+    pub fn filtered(&self) -> Vec<usize> {
+        self.choices
+            .iter()
+            .enumerate()
+            .filter(|(_, choice)| fuzzy_match(&self.query, &choice.label.name))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    pub fn push_query(&mut self, c: char) {
+        self.query.push(c);
+        self.clamp_selection();
+    }
+
+    pub fn pop_query(&mut self) {
+        self.query.pop();
+        self.clamp_selection();
+    }
+
+    pub fn clear_query(&mut self) {
+        self.query.clear();
+        self.clamp_selection();
+    }
+
+    pub fn select_next(&mut self) {
+        let count = self.filtered().len();
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        let count = self.filtered().len();
+        if count > 0 {
+            self.selected = (self.selected + count - 1) % count;
+        }
+    }
+
+    /// Toggle the label under the cursor
+    pub fn toggle_selected(&mut self) {
+        if let Some(&idx) = self.filtered().get(self.selected) {
+            self.choices[idx].toggle();
+        }
+    }
+
+    /// Labels to add and remove per target PR, PRs without changes left out
+    pub fn changes(&self) -> Vec<(usize, LabelChanges)> {
+        self.prs
+            .iter()
+            .map(|(number, labels)| {
+                let mut changes = LabelChanges::default();
+                for choice in self.choices.iter().filter(|c| c.current != c.initial) {
+                    let has_label = labels.contains(&choice.label.name);
+                    match choice.current {
+                        LabelCheck::Checked if !has_label => {
+                            changes.add.push(choice.label.name.clone())
+                        }
+                        LabelCheck::Unchecked if has_label => {
+                            changes.remove.push(choice.label.name.clone())
+                        }
+                        _ => {}
+                    }
+                }
+                (*number, changes)
+            })
+            .filter(|(_, changes)| !changes.is_empty())
+            .collect()
+    }
+
+    fn clamp_selection(&mut self) {
+        let count = self.filtered().len();
+        self.selected = self.selected.min(count.saturating_sub(1));
+    }
+}
+
+/// Case-insensitive subsequence match ("skcl" matches "skip-changelog")
+pub fn fuzzy_match(query: &str, text: &str) -> bool {
+    let mut text = text.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|q| text.any(|t| t == q))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(name: &str) -> Label {
+        Label {
+            name: name.to_string(),
+            color: "ededed".to_string(),
+            description: None,
+        }
+    }
+
+    fn picker() -> LabelPickerState {
+        let repo = Repository::new("acme", "api", "main");
+        let mut state = LabelPickerState::new(
+            repo,
+            vec![
+                (1, vec!["bug".to_string(), "dependencies".to_string()]),
+                (2, vec!["dependencies".to_string()]),
+            ],
+        );
+        state.set_labels(vec![
+            label("bug"),
+            label("dependencies"),
+            label("skip-changelog"),
+        ]);
+        state
+    }
+
+    #[test]
+    fn test_initial_checks_follow_pr_labels() {
+        let state = picker();
+        let checks: Vec<_> = state.choices.iter().map(|c| c.initial).collect();
+        assert_eq!(
+            checks,
+            vec![
+                LabelCheck::Indeterminate,
+                LabelCheck::Checked,
+                LabelCheck::Unchecked
+            ]
+        );
+        assert!(state.changes().is_empty());
+    }
+
+    #[test]
+    fn test_toggle_cycles_through_indeterminate() {
+        let mut state = picker();
+        state.toggle_selected();
+        assert_eq!(state.choices[0].current, LabelCheck::Checked);
+        state.toggle_selected();
+        assert_eq!(state.choices[0].current, LabelCheck::Unchecked);
+        state.toggle_selected();
+        assert_eq!(state.choices[0].current, LabelCheck::Indeterminate);
+
+        // Plain labels just flip
+        state.select_next();
+        state.toggle_selected();
+        assert_eq!(state.choices[1].current, LabelCheck::Unchecked);
+        state.toggle_selected();
+        assert_eq!(state.choices[1].current, LabelCheck::Checked);
+    }
+
+    #[test]
+    fn test_changes_per_pr() {
+        let mut state = picker();
+        // Add "bug" to all, remove "dependencies", add "skip-changelog"
+        state.toggle_selected();
+        state.select_next();
+        state.toggle_selected();
+        state.select_next();
+        state.toggle_selected();
+
+        let changes = state.changes();
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    1,
+                    LabelChanges {
+                        add: vec!["skip-changelog".to_string()],
+                        remove: vec!["dependencies".to_string()],
+                    }
+                ),
+                (
+                    2,
+                    LabelChanges {
+                        add: vec!["bug".to_string(), "skip-changelog".to_string()],
+                        remove: vec!["dependencies".to_string()],
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filter_keeps_selection_in_range() {
+        let mut state = picker();
+        state.select_next();
+        state.select_next();
+        for c in "skcl".chars() {
+            state.push_query(c);
+        }
+        assert_eq!(state.filtered(), vec![2]);
+        assert_eq!(state.selected, 0);
+        state.toggle_selected();
+        assert_eq!(state.choices[2].current, LabelCheck::Checked);
+    }
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert!(fuzzy_match("", "bug"));
+        assert!(fuzzy_match("SkCh", "skip-changelog"));
+        assert!(!fuzzy_match("gub", "bug"));
+    }
+}
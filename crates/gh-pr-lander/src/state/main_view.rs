@@ -1,6 +1,11 @@
 //! Main View State
 
-use crate::domain_models::Repository;
+use crate::domain_models::{
+    attention_order, filter_spec, normalize_author, LoadingState, MaturityState, MergeableStatus,
+    Pr, PrState, PrStateFilter, Repository, UpdateType,
+};
+use gh_pr_config::{FilterSpec, PrSizeThresholds};
+use std::borrow::Cow;
 
 /// Main view state
 #[derive(Debug, Clone, Default)]
@@ -15,6 +20,104 @@ pub struct MainViewState {
     pub pending_session_repo: Option<(String, String, String, Option<String>)>,
     /// Pending PR number from session (not index)
     pub pending_session_pr_no: Option<usize>,
+    /// Workspace profile active when the session was saved
+    pub pending_session_profile: Option<String>,
+
+    /// Workspace profile the repositories were loaded from (None in demo mode)
+    pub profile: Option<ActiveProfile>,
+
+    /// Authenticated login per account (`host` or `profile@host`)
+    pub account_logins: std::collections::HashMap<String, String>,
+
+    /// GraphQL features supported per host (e.g. "github.com")
+    pub host_capabilities: std::collections::HashMap<String, gh_client::HostCapabilities>,
+
+    /// PRs watched across repositories
+    pub watch_list: super::WatchListState,
+
+    /// Local notes per PR (never sent to GitHub)
+    pub notes: std::collections::HashMap<crate::domain_models::WatchedPr, String>,
+
+    /// Snoozed PRs, hidden from the PR table until they wake up
+    pub snoozed:
+        std::collections::HashMap<crate::domain_models::WatchedPr, gh_pr_config::SnoozedPr>,
+    /// Whether snoozed PRs are shown (dimmed) instead of hidden
+    pub show_snoozed: bool,
+
+    /// Filters saved by name (`filters.toml`)
+    pub saved_filters: std::collections::BTreeMap<String, FilterSpec>,
+
+    /// Tab cycling past the ends of the repository list (from `[ui]` config)
+    pub tab_cycling: gh_pr_config::TabCycling,
+
+    /// Changed lines bounding the PR size buckets (from `[ui]` config)
+    pub pr_sizes: gh_pr_config::PrSizeThresholds,
+
+    /// Sort of repositories not sorted otherwise (`ui.sort_by_attention`)
+    pub default_sort: PrSort,
+
+    /// Mutating operations running on PRs (shown as a marker on their rows)
+    pub in_flight: std::collections::HashSet<(
+        crate::domain_models::WatchedPr,
+        crate::domain_models::OperationType,
+    )>,
+
+    /// PR description popup over the PR table (closed by navigating)
+    pub peek: Option<PrPeekState>,
+    /// Descriptions fetched for the peek popup, with the `updated_at` of the
+    /// PR they are current for
+    pub peek_bodies: std::collections::HashMap<
+        crate::domain_models::WatchedPr,
+        (chrono::DateTime<chrono::Utc>, String),
+    >,
+
+    /// GitHub's `updated_at` of each PR when it was last seen in the table
+    pub last_seen:
+        std::collections::HashMap<crate::domain_models::WatchedPr, chrono::DateTime<chrono::Utc>>,
+
+    /// Repository removed last, until it is added back by undo
+    pub removed_repository: Option<RemovedRepository>,
+
+    /// Open bot PRs by update, to spot the same update in several repositories
+    pub bot_updates: super::DuplicateUpdateIndex,
+
+    /// Bumped by reducers whenever data saved in the session changes, so
+    /// autosave can skip saving while nothing changed
+    pub session_revision: u64,
+}
+
+/// The workspace profile whose repositories are shown
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveProfile {
+    pub name: String,
+    /// Names of all profiles, including the active one
+    pub names: Vec<String>,
+    /// Saved filter of repositories without a configured `default_filter`
+    pub default_filter: Option<String>,
+    /// Theme of the profile (None = `ui.theme`)
+    pub theme: Option<String>,
+}
+
+/// A repository removed from the list, kept to undo the removal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemovedRepository {
+    pub repo: Repository,
+    /// Position it had in `repositories`
+    pub index: usize,
+    pub removed_at: chrono::DateTime<chrono::Local>,
+}
+
+/// How long a removed repository can be added back by undo
+pub const UNDO_REMOVE_WINDOW: chrono::TimeDelta = chrono::TimeDelta::minutes(1);
+
+/// The PR shown in the peek popup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrPeekState {
+    /// Repository of the PR (index into `repositories`)
+    pub repo_idx: usize,
+    pub pr_number: usize,
+    /// Why fetching the description failed
+    pub error: Option<String>,
 }
 
 /// Data for a single repository (PRs, loading state, etc.)
@@ -32,6 +135,441 @@ pub struct RepositoryData {
     pub last_updated: Option<chrono::DateTime<chrono::Local>>,
     /// Current filter for displaying PRs
     pub current_filter: PrFilter,
+    /// Sort picked for this repository (None = `default_sort`)
+    pub sort: Option<PrSort>,
+    /// Which PRs are fetched (open, closed, merged or all)
+    pub pr_state: PrStateFilter,
+    /// Active server-side search (results replace `prs` while set)
+    pub search: Option<super::PrSearchState>,
+    /// Whether `prs` were last refreshed by the idle prefetcher
+    pub prefetched: bool,
+    /// When `prs` were cached, while they are shown from the API cache
+    /// until the fresh load lands
+    pub from_cache: Option<chrono::DateTime<chrono::Local>>,
+    /// CI state of the head of the tracked branch (`None` until known)
+    pub branch_ci: Option<gh_client::CiState>,
+    /// Consecutive PR loads rejected with an access error (403/404)
+    pub access_failures: u32,
+    /// Set once the token keeps being refused access to the repository
+    pub inaccessible: Option<InaccessibleRepo>,
+    /// PRs opened and merged in the last week (`None` until fetched, or
+    /// when the search failed)
+    pub pulse: Option<RepoPulse>,
+    /// When the pulse was last requested
+    pub pulse_requested: Option<chrono::DateTime<chrono::Local>>,
+}
+
+/// How long the pulse of a repository is kept before it is fetched again
+pub const PULSE_TTL: chrono::TimeDelta = chrono::TimeDelta::hours(6);
+
+/// Days counted as "this week" by the pulse
+pub const PULSE_DAYS: i64 = 7;
+
+/// Activity of a repository over the last [`PULSE_DAYS`] days
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RepoPulse {
+    pub opened: u64,
+    pub merged: u64,
+}
+
+impl RepoPulse {
+    /// Spelled out, e.g. "12 PRs opened, 9 merged in the last 7 days"
+    pub fn describe(&self) -> String {
+        format!(
+            "{} PRs opened, {} merged in the last {} days",
+            self.opened, self.merged, PULSE_DAYS
+        )
+    }
+}
+
+/// Consecutive access errors after which a repository counts as inaccessible
+pub const INACCESSIBLE_AFTER_FAILURES: u32 = 2;
+
+/// A repository the token can (no longer) access, e.g. it went private
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InaccessibleRepo {
+    /// Error of the last attempt
+    pub error: String,
+    /// When loading its PRs was last attempted
+    pub last_attempt: chrono::DateTime<chrono::Local>,
+}
+
+/// Whether a failed PR load means the token has no access to the repository
+///
+/// GitHub answers 404 for repositories that are private to the token and
+/// 403 when access was revoked. Rate limits are 403s too, but pass.
+pub fn is_access_error(status: Option<u16>, error: &str) -> bool {
+    match status {
+        Some(404 | 451) => true,
+        Some(403) => !error.to_lowercase().contains("rate limit"),
+        _ => false,
+    }
+}
+
+impl MainViewState {
+    /// Local note of a PR of a repository
+    pub fn note_for(&self, repo: &Repository, pr_number: usize) -> Option<&str> {
+        self.notes
+            .get(&repo.watched_pr(pr_number))
+            .map(String::as_str)
+    }
+
+    /// Whether a PR of a repository is snoozed
+    pub fn is_snoozed(&self, repo: &Repository, pr_number: usize) -> bool {
+        self.snoozed.contains_key(&repo.watched_pr(pr_number))
+    }
+
+    /// Authenticated login of the account a repository is accessed with
+    pub fn login_for(&self, repo: &Repository) -> Option<&str> {
+        self.account_logins
+            .get(&repo.account_key())
+            .map(String::as_str)
+    }
+
+    /// Whether the host of a repository supports a GraphQL feature
+    ///
+    /// Hosts not probed yet are assumed to support everything.
+    pub fn host_supports(&self, repo: &Repository, feature: gh_client::Feature) -> bool {
+        self.host_capabilities
+            .get(repo.effective_host())
+            .is_none_or(|capabilities| capabilities.supports(feature))
+    }
+
+    /// Whether `repo` is the repository selected when the last session ended
+    pub fn is_session_repo(&self, repo: &Repository) -> bool {
+        self.pending_session_repo
+            .as_ref()
+            .is_some_and(|(org, name, branch, host)| {
+                repo.id() == gh_pr_config::RepoId::new(host.as_deref(), org, name)
+                    && repo.branch == *branch
+            })
+    }
+
+    /// Loaded PR of the selected repository by number
+    pub fn find_pr(&self, pr_number: usize) -> Option<&Pr> {
+        self.repo_data
+            .get(&self.selected_repository)?
+            .prs
+            .iter()
+            .find(|pr| pr.number == pr_number)
+    }
+
+    /// Loaded data of a tracked repository
+    pub fn repo_data_of(&self, repo: &Repository) -> Option<&RepositoryData> {
+        let idx = self.repositories.iter().position(|r| r == repo)?;
+        self.repo_data.get(&idx)
+    }
+
+    /// Merge confirmation warnings for PRs not merging into their repository's
+    /// configured branch ("#12: merging into release/1.4!")
+    pub fn base_branch_warnings(&self, targets: &[(Repository, usize)]) -> Vec<String> {
+        targets
+            .iter()
+            .filter_map(|(repo, pr_number)| {
+                let pr = self
+                    .repo_data_of(repo)?
+                    .prs
+                    .iter()
+                    .find(|pr| pr.number == *pr_number)?;
+                let base = pr.unusual_base(repo)?;
+                Some(format!("#{}: merging into {}!", pr_number, base))
+            })
+            .collect()
+    }
+
+    /// Repository and PR of the peek popup, while it is open over their table
+    pub fn peeked_pr(&self) -> Option<(&Repository, &Pr)> {
+        let peek = self.peek.as_ref()?;
+        if peek.repo_idx != self.selected_repository {
+            return None;
+        }
+        let repo = self.repositories.get(peek.repo_idx)?;
+        let pr = self
+            .repo_data
+            .get(&peek.repo_idx)?
+            .prs
+            .iter()
+            .find(|pr| pr.number == peek.pr_number)?;
+        Some((repo, pr))
+    }
+
+    /// Description of a PR for the peek popup (`None` until it is fetched)
+    ///
+    /// Not every fetch path includes descriptions, so an empty one is looked
+    /// up in the fetched ones, which stay current until the PR is updated.
+    pub fn peek_body<'a>(&'a self, repo: &Repository, pr: &'a Pr) -> Option<&'a str> {
+        if !pr.body.is_empty() {
+            return Some(&pr.body);
+        }
+        self.peek_bodies
+            .get(&repo.watched_pr(pr.number))
+            .filter(|(updated_at, _)| *updated_at == pr.updated_at)
+            .map(|(_, body)| body.as_str())
+    }
+
+    /// Patch and minor bot updates of a repository that are ready to merge
+    ///
+    /// Ready means open, not a draft, passing required checks and no
+    /// conflicts. Snoozed PRs are left out unless they are revealed.
+    pub fn mergeable_bot_updates(&self, repo_idx: usize) -> Vec<&Pr> {
+        let (Some(repo), Some(data)) = (
+            self.repositories.get(repo_idx),
+            self.repo_data.get(&repo_idx),
+        ) else {
+            return Vec::new();
+        };
+        data.prs
+            .iter()
+            .filter(|pr| {
+                pr.dependency_update.as_ref().is_some_and(|update| {
+                    matches!(
+                        update.update_type,
+                        Some(UpdateType::Patch | UpdateType::Minor)
+                    )
+                })
+            })
+            .filter(|pr| {
+                pr.state == PrState::Open
+                    && pr.maturity == MaturityState::Ready
+                    && pr.mergeable.passes_required_checks()
+            })
+            .filter(|pr| self.show_snoozed || !self.is_snoozed(repo, pr.number))
+            .collect()
+    }
+
+    /// Numbers of a repository's PRs updated on GitHub since they were last seen
+    ///
+    /// PRs never seen have nothing to compare with and are left out, and so
+    /// are snoozed PRs unless they are revealed.
+    pub fn unseen_prs(&self, repo_idx: usize) -> std::collections::HashSet<usize> {
+        let (Some(repo), Some(data)) = (
+            self.repositories.get(repo_idx),
+            self.repo_data.get(&repo_idx),
+        ) else {
+            return std::collections::HashSet::new();
+        };
+        data.prs
+            .iter()
+            .filter(|pr| {
+                self.last_seen
+                    .get(&repo.watched_pr(pr.number))
+                    .is_some_and(|seen| pr.updated_at > *seen)
+            })
+            .filter(|pr| self.show_snoozed || !self.is_snoozed(repo, pr.number))
+            .map(|pr| pr.number)
+            .collect()
+    }
+
+    /// Remember a PR as seen in the version last updated at `updated_at`
+    pub fn mark_seen(
+        &mut self,
+        pr: crate::domain_models::WatchedPr,
+        updated_at: chrono::DateTime<chrono::Utc>,
+    ) {
+        if self
+            .last_seen
+            .get(&pr)
+            .is_some_and(|seen| *seen >= updated_at)
+        {
+            return;
+        }
+        self.last_seen.insert(pr, updated_at);
+        self.mark_session_dirty();
+    }
+
+    /// Forget the seen PRs of a repository that are no longer open
+    ///
+    /// The same repository can be tracked on several branches, so this waits
+    /// until all of its tabs list their open PRs.
+    pub fn prune_last_seen(&mut self, repo: &Repository) {
+        let mut open = std::collections::HashSet::new();
+        for (idx, tab) in self.repositories.iter().enumerate() {
+            if !tab.contains_watched(&repo.watched_pr(0)) {
+                continue;
+            }
+            match self.repo_data.get(&idx) {
+                Some(data)
+                    if data.loading_state == LoadingState::Loaded
+                        && data.pr_state == PrStateFilter::Open
+                        && data.search.is_none() =>
+                {
+                    open.extend(data.prs.iter().map(|pr| pr.number as u64));
+                }
+                _ => return,
+            }
+        }
+        let before = self.last_seen.len();
+        self.last_seen
+            .retain(|seen, _| !repo.contains_watched(seen) || open.contains(&seen.number));
+        if self.last_seen.len() != before {
+            self.mark_session_dirty();
+        }
+    }
+
+    /// Note a change of data saved in the session
+    pub fn mark_session_dirty(&mut self) {
+        self.session_revision = self.session_revision.wrapping_add(1);
+    }
+
+    /// Selected repository and number of the PR under its cursor, as saved
+    /// in the session
+    pub fn session_selection(&self) -> (usize, Option<usize>) {
+        let pr_number = self
+            .repo_data
+            .get(&self.selected_repository)
+            .and_then(|data| data.prs.get(data.selected_pr))
+            .map(|pr| pr.number);
+        (self.selected_repository, pr_number)
+    }
+
+    /// Sort of a repository's PRs
+    pub fn sort_of(&self, repo_idx: usize) -> PrSort {
+        self.repo_data
+            .get(&repo_idx)
+            .and_then(|data| data.sort)
+            .unwrap_or(self.default_sort)
+    }
+
+    /// Order a repository's PRs by its sort, keeping the cursor on its PR
+    pub fn apply_sort(&mut self, repo_idx: usize) {
+        let sort = self.sort_of(repo_idx);
+        let login = self
+            .repositories
+            .get(repo_idx)
+            .and_then(|repo| self.login_for(repo))
+            .map(str::to_string);
+        let Some(data) = self.repo_data.get_mut(&repo_idx) else {
+            return;
+        };
+        let cursor_pr = data.prs.get(data.selected_pr).map(|pr| pr.number);
+        match sort {
+            PrSort::Newest => data.prs.sort_by(|a, b| {
+                b.created_at
+                    .cmp(&a.created_at)
+                    .then_with(|| b.number.cmp(&a.number))
+            }),
+            // Without a login nothing needs attention, so PRs go by age
+            PrSort::Attention => {
+                let login = login.as_deref().unwrap_or_default();
+                data.prs.sort_by(|a, b| attention_order(a, b, login));
+            }
+            // Smallest first, PRs whose stats are not fetched yet last
+            PrSort::Size => data.prs.sort_by(|a, b| {
+                let lines = |pr: &Pr| pr.changed_lines().unwrap_or(usize::MAX);
+                lines(a)
+                    .cmp(&lines(b))
+                    .then_with(|| b.number.cmp(&a.number))
+            }),
+        }
+        if let Some(position) =
+            cursor_pr.and_then(|number| data.prs.iter().position(|pr| pr.number == number))
+        {
+            data.selected_pr = position;
+        }
+    }
+
+    /// Indices of the PRs shown in the table of a repository
+    ///
+    /// PRs not matching the repository's filter are left out, and so are
+    /// snoozed PRs unless they are revealed.
+    pub fn visible_pr_indices(&self, repo_idx: usize) -> Vec<usize> {
+        let (Some(repo), Some(data)) = (
+            self.repositories.get(repo_idx),
+            self.repo_data.get(&repo_idx),
+        ) else {
+            return Vec::new();
+        };
+        let login = self.login_for(repo);
+        data.prs
+            .iter()
+            .enumerate()
+            .filter(|(_, pr)| data.current_filter.matches(pr, login, &self.pr_sizes))
+            .filter(|(_, pr)| self.show_snoozed || !self.is_snoozed(repo, pr.number))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+}
+
+impl RepositoryData {
+    /// Record a failed PR load, returns whether the repository is inaccessible now
+    ///
+    /// Other errors (e.g. offline) restart the count but keep an existing
+    /// marker, so the retry interval still applies.
+    pub fn record_load_error(&mut self, error: &str, status: Option<u16>) -> bool {
+        let now = chrono::Local::now();
+        if !is_access_error(status, error) {
+            self.access_failures = 0;
+            if let Some(ref mut inaccessible) = self.inaccessible {
+                inaccessible.last_attempt = now;
+            }
+            return self.inaccessible.is_some();
+        }
+        self.access_failures += 1;
+        if self.access_failures >= INACCESSIBLE_AFTER_FAILURES || self.inaccessible.is_some() {
+            self.inaccessible = Some(InaccessibleRepo {
+                error: error.to_string(),
+                last_attempt: now,
+            });
+        }
+        self.inaccessible.is_some()
+    }
+
+    /// Forget earlier access errors after a successful load
+    pub fn record_load_success(&mut self) {
+        self.access_failures = 0;
+        self.inaccessible = None;
+    }
+
+    /// Whether the pulse should be fetched (again) when the tab is shown
+    ///
+    /// Once a day and after [`PULSE_TTL`], failed fetches alike.
+    pub fn pulse_due(&self, now: chrono::DateTime<chrono::Local>) -> bool {
+        self.pulse_requested.is_none_or(|requested| {
+            requested.date_naive() != now.date_naive() || now - requested >= PULSE_TTL
+        })
+    }
+
+    /// Next PR state in the selector cycle (Open → Closed → Merged → All)
+    pub fn next_pr_state(&self) -> PrStateFilter {
+        match self.pr_state {
+            PrStateFilter::Open => PrStateFilter::Closed,
+            PrStateFilter::Closed => PrStateFilter::Merged,
+            PrStateFilter::Merged => PrStateFilter::All,
+            PrStateFilter::All => PrStateFilter::Open,
+        }
+    }
+}
+
+/// Order of the PRs in the table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PrSort {
+    /// Newest first, as GitHub lists them
+    #[default]
+    Newest,
+    /// What needs the current user first (see `domain_models::attention`)
+    Attention,
+    /// Fewest changed lines first
+    Size,
+}
+
+impl PrSort {
+    /// Get the display label for this sort
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Newest => "newest",
+            Self::Attention => "attention",
+            Self::Size => "size",
+        }
+    }
+
+    /// Cycle to the next sort
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Newest => Self::Attention,
+            Self::Attention => Self::Size,
+            Self::Size => Self::Newest,
+        }
+    }
 }
 
 /// PR filter for displaying only matching PRs
@@ -50,18 +588,58 @@ pub enum PrFilter {
     MyPRs,
     /// Custom text filter (matches title or author)
     Custom(String),
+    /// Structured filter, saved under `name` or typed as a query (`update:major`)
+    Spec {
+        name: Option<String>,
+        spec: Box<FilterSpec>,
+    },
+    /// Show only PRs by an author (normalized, see `normalize_author`)
+    Author(String),
 }
 
 impl PrFilter {
     /// Get the display label for this filter
-    pub fn label(&self) -> &str {
+    pub fn label(&self) -> Cow<'_, str> {
+        match self {
+            Self::All => "All".into(),
+            Self::ReadyToMerge => "Ready to Merge".into(),
+            Self::NeedsRebase => "Needs Rebase".into(),
+            Self::BuildFailed => "Build Failed".into(),
+            Self::MyPRs => "My PRs".into(),
+            Self::Custom(_) => "Custom".into(),
+            Self::Spec {
+                name: Some(name), ..
+            } => name.into(),
+            Self::Spec { name: None, spec } => filter_spec::to_query(spec).into(),
+            Self::Author(author) => author.into(),
+        }
+    }
+
+    /// Parse a filter typed into the search input (`update:major ci:success`)
+    ///
+    /// Returns `None` for anything else, which is a GitHub search query.
+    pub fn parse(query: &str) -> Option<Self> {
+        filter_spec::parse(query).map(|spec| Self::Spec {
+            name: None,
+            spec: Box::new(spec),
+        })
+    }
+
+    /// Whether a PR is shown with this filter (`login` of the current user,
+    /// `sizes` bounding the size buckets)
+    pub fn matches(&self, pr: &Pr, login: Option<&str>, sizes: &PrSizeThresholds) -> bool {
         match self {
-            Self::All => "All",
-            Self::ReadyToMerge => "Ready to Merge",
-            Self::NeedsRebase => "Needs Rebase",
-            Self::BuildFailed => "Build Failed",
-            Self::MyPRs => "My PRs",
-            Self::Custom(_) => "Custom",
+            Self::All => true,
+            Self::ReadyToMerge => pr.mergeable.passes_required_checks(),
+            Self::NeedsRebase => pr.needs_rebase,
+            Self::BuildFailed => pr.mergeable == MergeableStatus::BuildFailed,
+            Self::MyPRs => login.is_some_and(|login| pr.author == login),
+            Self::Custom(text) => {
+                let text = text.to_lowercase();
+                pr.title.to_lowercase().contains(&text) || pr.author.to_lowercase().contains(&text)
+            }
+            Self::Spec { spec, .. } => filter_spec::matches(spec, pr, chrono::Utc::now(), sizes),
+            Self::Author(author) => normalize_author(&pr.author) == *author,
         }
     }
 
@@ -73,7 +651,42 @@ impl PrFilter {
             Self::NeedsRebase => Self::BuildFailed,
             Self::BuildFailed => Self::MyPRs,
             Self::MyPRs => Self::All,
-            Self::Custom(_) => Self::All,
+            Self::Custom(_) | Self::Spec { .. } | Self::Author(_) => Self::All,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_access_error() {
+        assert!(is_access_error(Some(404), "Not Found"));
+        assert!(is_access_error(
+            Some(403),
+            "Resource not accessible by integration"
+        ));
+        assert!(!is_access_error(
+            Some(403),
+            "API rate limit exceeded for user"
+        ));
+        assert!(!is_access_error(Some(500), "Server Error"));
+        assert!(!is_access_error(None, "connection refused"));
+    }
+
+    #[test]
+    fn test_pulse_due() {
+        let now = chrono::Local::now();
+        let mut data = RepositoryData::default();
+        assert!(data.pulse_due(now));
+
+        data.pulse_requested = Some(now - chrono::TimeDelta::minutes(5));
+        assert_eq!(
+            data.pulse_due(now),
+            now.date_naive() != (now - chrono::TimeDelta::minutes(5)).date_naive()
+        );
+        data.pulse_requested = Some(now - PULSE_TTL);
+        assert!(data.pulse_due(now));
+    }
+}
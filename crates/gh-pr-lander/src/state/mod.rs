@@ -2,30 +2,75 @@
 //!
 //! Contains all state types used by the application, organized by feature.
 
+mod actions_view;
 mod add_repo;
 mod app;
+mod audit_log;
+mod branch_picker;
+mod bug_report;
 mod build_log;
+mod cache_inspector;
 mod command_palette;
 mod confirmation_popup;
+mod conflict_helper;
 mod debug_console;
+mod dependency_updates;
 mod diff_viewer;
+mod duplicate_updates;
+mod filter_builder;
 mod key_bindings;
+mod label_picker;
 mod main_view;
 mod merge_bot;
+mod onboarding;
+mod perf_stats;
+mod pr_search;
+mod release_notes;
+mod reviewer_picker;
 mod splash;
 mod status_bar;
+mod terminal_size;
+mod token_input;
+mod unfinished_work;
+mod watch_list;
 
-pub use add_repo::{AddRepoField, AddRepoFormState};
+pub use actions_view::{ActionsViewState, ACTIONS_VIEW_RUN_LIMIT};
+pub use add_repo::{parse_github_url, AddRepoField, AddRepoFormState};
 pub use app::AppState;
+pub use audit_log::{AuditLogState, AuditScope};
+pub use branch_picker::BranchPickerState;
+pub use bug_report::BugReportState;
 pub use build_log::{
-    BuildLogJobMetadata, BuildLogJobStatus, BuildLogLoadingState, BuildLogPrContext, BuildLogState,
+    BuildLogContext, BuildLogJobMetadata, BuildLogJobStatus, BuildLogLoadingState,
+    BuildLogPrContext, BuildLogRunContext, BuildLogState, ParsedRun, PARSED_RUNS_LIMIT,
+    PARSED_RUNS_MAX_BYTES, SUCCEEDED_GROUP,
 };
+pub use cache_inspector::{CacheInput, CacheInspectorState};
 pub use command_palette::CommandPaletteState;
 pub use confirmation_popup::{ConfirmationIntent, ConfirmationPopupState};
+pub use conflict_helper::ConflictHelperState;
 pub use debug_console::DebugConsoleState;
+pub use dependency_updates::{dependency_groups, DependencyUpdateEntry, DependencyUpdatesState};
 pub use diff_viewer::DiffViewerState;
+pub use duplicate_updates::{DuplicateUpdateIndex, DuplicateUpdatesState};
+pub use filter_builder::{FilterBuilderState, FilterRow};
 pub use key_bindings::KeyBindingsPanelState;
-pub use main_view::{MainViewState, PrFilter, RepositoryData};
+pub use label_picker::{LabelChanges, LabelCheck, LabelPickerState};
+pub use main_view::{
+    ActiveProfile, InaccessibleRepo, MainViewState, PrFilter, PrPeekState, PrSort,
+    RemovedRepository, RepoPulse, RepositoryData, PULSE_DAYS, UNDO_REMOVE_WINDOW,
+};
 pub use merge_bot::MergeBotState;
-pub use splash::SplashState;
-pub use status_bar::{StatusBarState, StatusKind, StatusMessage};
+pub use onboarding::{OnboardingState, OnboardingStep};
+pub use perf_stats::PerfStatsState;
+pub use pr_search::{PrSearchInputState, PrSearchState};
+pub use release_notes::ReleaseNotesState;
+pub use reviewer_picker::ReviewerPickerState;
+pub use splash::{BootstrapStage, SplashState, StageStatus};
+pub use status_bar::{
+    ActiveOperation, ApiUsage, OperationId, StatusBarState, StatusKind, StatusMessage,
+};
+pub use terminal_size::{TerminalSize, MIN_HEIGHT, MIN_WIDTH};
+pub use token_input::TokenInputState;
+pub use unfinished_work::UnfinishedWork;
+pub use watch_list::{WatchEntry, WatchListState, WatchStatus};
@@ -0,0 +1,38 @@
+//! Onboarding Wizard State
+//!
+//! First-run flow shown when bootstrap finds no repositories and no local session.
+
+use crate::domain_models::Repository;
+
+/// Step of the onboarding wizard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnboardingStep {
+    /// Check that a GitHub token is available
+    #[default]
+    Token,
+    /// Add a first repository (detected from git remote, or via the form)
+    Repository,
+}
+
+/// State for the onboarding wizard
+#[derive(Debug, Clone, Default)]
+pub struct OnboardingState {
+    /// Current wizard step
+    pub step: OnboardingStep,
+    /// Whether a GitHub token was resolved during bootstrap
+    pub token_available: bool,
+    /// Repository detected from the current directory's git remote
+    pub detected_repo: Option<Repository>,
+    /// Result of writing the default config file (path written or reason)
+    pub config_message: Option<String>,
+}
+
+impl OnboardingState {
+    /// Create a new wizard state
+    pub fn new(token_available: bool) -> Self {
+        Self {
+            token_available,
+            ..Self::default()
+        }
+    }
+}
@@ -0,0 +1,10 @@
+//! Performance Stats State
+
+use gh_client::ApiMetricsSnapshot;
+
+/// State of the performance stats popup
+#[derive(Debug, Clone, Default)]
+pub struct PerfStatsState {
+    /// Last snapshot of the API metrics taken by the middleware
+    pub snapshot: ApiMetricsSnapshot,
+}
@@ -0,0 +1,84 @@
+//! PR Search State
+//!
+//! Search input popup and the per-repository search mode of the PR table.
+
+use crate::domain_models::Pr;
+
+/// State for the search input popup
+#[derive(Debug, Clone, Default)]
+pub struct PrSearchInputState {
+    /// Entered query (GitHub search syntax)
+    pub query: String,
+}
+
+impl PrSearchInputState {
+    /// Create a search input prefilled with the given query
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+        }
+    }
+}
+
+/// Active search of a repository
+///
+/// While a search is active the repository's PR list shows the results, so
+/// all row actions work on them; the loaded list is kept aside and restored
+/// when leaving search mode.
+#[derive(Debug, Clone, Default)]
+pub struct PrSearchState {
+    /// Query of the shown results
+    pub query: String,
+    /// Last loaded page (1-based, 0 = none yet)
+    pub page: u32,
+    /// Total number of matches
+    pub total_count: u64,
+    /// A page is being fetched
+    pub loading: bool,
+    /// GitHub reported incomplete results
+    pub incomplete: bool,
+    /// Last error (e.g. search rate limit)
+    pub error: Option<String>,
+    /// PR numbers whose full details are fetched or being fetched
+    pub hydrated: std::collections::HashSet<usize>,
+    /// The loaded PR list, restored when leaving search mode
+    pub saved_prs: Vec<Pr>,
+    /// Cursor in the loaded PR list
+    pub saved_selected: usize,
+}
+
+impl PrSearchState {
+    /// Start a search, keeping the loaded list aside
+    pub fn new(query: impl Into<String>, saved_prs: Vec<Pr>, saved_selected: usize) -> Self {
+        Self {
+            query: query.into(),
+            loading: true,
+            saved_prs,
+            saved_selected,
+            ..Self::default()
+        }
+    }
+
+    /// Whether more result pages can be fetched
+    pub fn has_more(&self) -> bool {
+        (self.page as u64) * (gh_client::SEARCH_PAGE_SIZE as u64) < self.total_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_more() {
+        let mut search = PrSearchState::new("is:merged", Vec::new(), 0);
+        assert!(!search.has_more());
+
+        search.page = 1;
+        search.total_count = 31;
+        assert!(search.has_more());
+
+        search.page = 2;
+        assert!(!search.has_more());
+    }
+}
@@ -0,0 +1,27 @@
+//! Release Notes State
+
+use crate::domain_models::{Repository, SinceRef};
+
+/// State of the release notes popup
+#[derive(Debug, Clone, Default)]
+pub struct ReleaseNotesState {
+    /// Repository and start of the notes shown
+    pub target: Option<(Repository, SinceRef)>,
+    /// Drafted markdown, `None` while fetching the merged PRs
+    pub markdown: Option<String>,
+    /// Number of merged PRs listed
+    pub pr_count: usize,
+    /// Error of the last fetch
+    pub error: Option<String>,
+    /// First line shown
+    pub scroll_offset: usize,
+}
+
+impl ReleaseNotesState {
+    /// Number of lines of the drafted markdown
+    pub fn line_count(&self) -> usize {
+        self.markdown
+            .as_ref()
+            .map_or(0, |markdown| markdown.lines().count())
+    }
+}
@@ -0,0 +1,218 @@
+//! Reviewer Picker State
+//!
+//! Multi-select popup to request reviews on a PR. Owners of the changed
+//! files per CODEOWNERS are listed first as suggestions, the remaining
+//! collaborators below them.
+
+use super::label_picker::fuzzy_match;
+use crate::domain_models::{Owner, Repository};
+
+/// A user or team that can be requested
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewerChoice {
+    pub owner: Owner,
+    /// Changed files owned per CODEOWNERS (None for other collaborators)
+    pub owned_files: Option<usize>,
+    pub checked: bool,
+}
+
+/// State of the reviewer picker popup
+#[derive(Debug, Clone)]
+pub struct ReviewerPickerState {
+    /// Repository of the PR
+    pub repo: Repository,
+    pub pr_number: usize,
+    /// Author of the PR, left out of the choices
+    pub author: String,
+    /// Suggested owners first, then the other collaborators
+    pub choices: Vec<ReviewerChoice>,
+    /// Filter typed by the user
+    pub query: String,
+    /// Cursor position within the filtered choices
+    pub selected: usize,
+    /// Collaborators are being fetched
+    pub loading: bool,
+    /// The request is being sent
+    pub applying: bool,
+    /// Fetching the collaborators failed
+    pub error: Option<String>,
+}
+
+impl ReviewerPickerState {
+    pub fn new(repo: Repository, pr_number: usize, author: String) -> Self {
+        Self {
+            repo,
+            pr_number,
+            author,
+            choices: Vec::new(),
+            query: String::new(),
+            selected: 0,
+            loading: true,
+            applying: false,
+            error: None,
+        }
+    }
+
+    /// Set the suggested owners and the collaborators not suggested already
+    pub fn set_candidates(&mut self, suggested: Vec<(Owner, usize)>, collaborators: Vec<String>) {
+        let author = Owner::User(self.author.clone());
+        let mut choices: Vec<ReviewerChoice> = suggested
+            .into_iter()
+            .filter(|(owner, _)| *owner != author)
+            .map(|(owner, count)| ReviewerChoice {
+                owner,
+                owned_files: Some(count),
+                checked: false,
+            })
+            .collect();
+        for login in collaborators {
+            let owner = Owner::User(login);
+            if owner != author && !choices.iter().any(|choice| choice.owner == owner) {
+                choices.push(ReviewerChoice {
+                    owner,
+                    owned_files: None,
+                    checked: false,
+                });
+            }
+        }
+        self.choices = choices;
+        self.loading = false;
+        self.error = None;
+        self.clamp_selection();
+    }
+
+    /// Indices into `choices` matching the filter, in display order
+    pub fn filtered(&self) -> Vec<usize> {
+        self.choices
+            .iter()
+            .enumerate()
+            .filter(|(_, choice)| fuzzy_match(&self.query, &choice.owner.to_string()))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    pub fn push_query(&mut self, c: char) {
+        self.query.push(c);
+        self.clamp_selection();
+    }
+
+    pub fn pop_query(&mut self) {
+        self.query.pop();
+        self.clamp_selection();
+    }
+
+    pub fn clear_query(&mut self) {
+        self.query.clear();
+        self.clamp_selection();
+    }
+
+    pub fn select_next(&mut self) {
+        let count = self.filtered().len();
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        let count = self.filtered().len();
+        if count > 0 {
+            self.selected = (self.selected + count - 1) % count;
+        }
+    }
+
+    /// Toggle the reviewer under the cursor
+    pub fn toggle_selected(&mut self) {
+        if let Some(&idx) = self.filtered().get(self.selected) {
+            self.choices[idx].checked = !self.choices[idx].checked;
+        }
+    }
+
+    /// Checked user logins and team slugs, as requested from GitHub
+    pub fn requested(&self) -> (Vec<String>, Vec<String>) {
+        let mut reviewers = Vec::new();
+        let mut team_reviewers = Vec::new();
+        for choice in self.choices.iter().filter(|choice| choice.checked) {
+            match choice.owner {
+                Owner::User(ref login) => reviewers.push(login.clone()),
+                Owner::Team(_) => {
+                    team_reviewers.extend(choice.owner.team_slug().map(str::to_string))
+                }
+                // Never suggested, GitHub only takes logins and teams
+                Owner::Email(_) => {}
+            }
+        }
+        (reviewers, team_reviewers)
+    }
+
+    fn clamp_selection(&mut self) {
+        let count = self.filtered().len();
+        self.selected = self.selected.min(count.saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn picker() -> ReviewerPickerState {
+        let mut state =
+            ReviewerPickerState::new(Repository::new("acme", "api", "main"), 7, "bob".to_string());
+        state.set_candidates(
+            vec![
+                (Owner::User("alice".to_string()), 3),
+                (Owner::Team("acme/core".to_string()), 2),
+                (Owner::User("bob".to_string()), 1),
+            ],
+            vec!["alice".to_string(), "bob".to_string(), "carol".to_string()],
+        );
+        state
+    }
+
+    #[test]
+    fn test_suggestions_first_without_author_or_duplicates() {
+        let state = picker();
+        let choices: Vec<(String, Option<usize>)> = state
+            .choices
+            .iter()
+            .map(|choice| (choice.owner.to_string(), choice.owned_files))
+            .collect();
+        assert_eq!(
+            choices,
+            vec![
+                ("@alice".to_string(), Some(3)),
+                ("@acme/core".to_string(), Some(2)),
+                ("@carol".to_string(), None),
+            ]
+        );
+        assert!(!state.loading);
+    }
+
+    #[test]
+    fn test_requested_users_and_team_slugs() {
+        let mut state = picker();
+        state.toggle_selected();
+        state.select_next();
+        state.toggle_selected();
+        assert_eq!(
+            state.requested(),
+            (vec!["alice".to_string()], vec!["core".to_string()])
+        );
+
+        state.toggle_selected();
+        assert_eq!(state.requested(), (vec!["alice".to_string()], vec![]));
+    }
+
+    #[test]
+    fn test_filter_keeps_selection_in_range() {
+        let mut state = picker();
+        state.select_next();
+        state.select_next();
+        for c in "crl".chars() {
+            state.push_query(c);
+        }
+        assert_eq!(state.filtered(), vec![2]);
+        assert_eq!(state.selected, 0);
+        state.toggle_selected();
+        assert_eq!(state.requested().0, vec!["carol".to_string()]);
+    }
+}
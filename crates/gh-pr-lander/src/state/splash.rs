@@ -5,6 +5,56 @@ use std::time::Instant;
 /// Minimum time the splash screen should be visible (in seconds)
 pub const MIN_SPLASH_DURATION_SECS: f64 = 2.0;
 
+/// Explicit stages of the bootstrap sequence, in the order they are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BootstrapStage {
+    /// Load the application configuration file
+    LoadConfig,
+    /// Resolve a GitHub token (env vars, gh CLI)
+    ResolveToken,
+    /// Create the GitHub API client
+    InitClient,
+    /// Load the list of recent repositories
+    LoadRecentRepos,
+    /// Load pull requests of the first repository
+    LoadFirstRepo,
+}
+
+impl BootstrapStage {
+    /// All stages in display order
+    pub const ALL: [BootstrapStage; 5] = [
+        BootstrapStage::LoadConfig,
+        BootstrapStage::ResolveToken,
+        BootstrapStage::InitClient,
+        BootstrapStage::LoadRecentRepos,
+        BootstrapStage::LoadFirstRepo,
+    ];
+
+    /// Human-readable label for the splash checklist
+    pub fn label(&self) -> &'static str {
+        match self {
+            BootstrapStage::LoadConfig => "Load configuration",
+            BootstrapStage::ResolveToken => "Resolve GitHub token",
+            BootstrapStage::InitClient => "Initialize GitHub client",
+            BootstrapStage::LoadRecentRepos => "Load recent repositories",
+            BootstrapStage::LoadFirstRepo => "Load pull requests",
+        }
+    }
+}
+
+/// Progress of a single bootstrap stage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StageStatus {
+    /// Not finished yet
+    Pending,
+    /// Finished successfully
+    Done,
+    /// Finished, but with a limitation the user should know about
+    Warning(String),
+    /// Failed with an actionable error message
+    Failed(String),
+}
+
 /// Splash screen state
 #[derive(Debug, Clone)]
 pub struct SplashState {
@@ -14,6 +64,8 @@ pub struct SplashState {
     pub started_at: Option<Instant>,
     /// Whether bootstrap has finished loading (but we might still show splash)
     pub loading_complete: bool,
+    /// Status of each bootstrap stage, in display order
+    pub stages: Vec<(BootstrapStage, StageStatus)>,
 }
 
 impl Default for SplashState {
@@ -23,11 +75,20 @@ impl Default for SplashState {
             animation_frame: 0,
             started_at: Some(Instant::now()),
             loading_complete: false,
+            stages: Self::initial_stages(),
         }
     }
 }
 
 impl SplashState {
+    /// All stages in pending state
+    pub fn initial_stages() -> Vec<(BootstrapStage, StageStatus)> {
+        BootstrapStage::ALL
+            .iter()
+            .map(|stage| (*stage, StageStatus::Pending))
+            .collect()
+    }
+
     /// Check if the minimum splash duration has elapsed
     pub fn min_duration_elapsed(&self) -> bool {
         self.started_at
@@ -39,4 +100,117 @@ impl SplashState {
     pub fn can_dismiss(&self) -> bool {
         self.loading_complete && self.min_duration_elapsed()
     }
+
+    /// Update the status of a stage
+    pub fn set_stage_status(&mut self, stage: BootstrapStage, status: StageStatus) {
+        if let Some(entry) = self.stages.iter_mut().find(|(s, _)| *s == stage) {
+            entry.1 = status;
+        }
+    }
+
+    /// The first failed stage and its error, if any
+    pub fn failure(&self) -> Option<(BootstrapStage, &str)> {
+        self.stages.iter().find_map(|(stage, status)| match status {
+            StageStatus::Failed(error) => Some((*stage, error.as_str())),
+            _ => None,
+        })
+    }
+
+    /// Whether any bootstrap stage has failed
+    pub fn has_failed(&self) -> bool {
+        self.failure().is_some()
+    }
+
+    /// Whether the GitHub client was created, so API operations can run
+    pub fn client_ready(&self) -> bool {
+        self.stages.iter().any(|(stage, status)| {
+            *stage == BootstrapStage::InitClient && *status == StageStatus::Done
+        })
+    }
+
+    /// Whether no token was found, so only public data can be read
+    pub fn read_only(&self) -> bool {
+        self.stages.iter().any(|(stage, status)| {
+            *stage == BootstrapStage::ResolveToken && matches!(status, StageStatus::Warning(_))
+        })
+    }
+
+    /// The first stage finished with a warning, and the warning
+    pub fn warning(&self) -> Option<(BootstrapStage, &str)> {
+        self.stages.iter().find_map(|(stage, status)| match status {
+            StageStatus::Warning(warning) => Some((*stage, warning.as_str())),
+            _ => None,
+        })
+    }
+
+    /// The stage currently in progress (first pending stage), unless bootstrap failed
+    pub fn current_stage(&self) -> Option<BootstrapStage> {
+        if self.has_failed() {
+            return None;
+        }
+        self.stages
+            .iter()
+            .find(|(_, status)| *status == StageStatus::Pending)
+            .map(|(stage, _)| *stage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_stages_pending() {
+        let state = SplashState::default();
+        assert_eq!(state.stages.len(), BootstrapStage::ALL.len());
+        assert!(state
+            .stages
+            .iter()
+            .all(|(_, status)| *status == StageStatus::Pending));
+        assert_eq!(state.current_stage(), Some(BootstrapStage::LoadConfig));
+    }
+
+    #[test]
+    fn test_current_stage_advances() {
+        let mut state = SplashState::default();
+        state.set_stage_status(BootstrapStage::LoadConfig, StageStatus::Done);
+        assert_eq!(state.current_stage(), Some(BootstrapStage::ResolveToken));
+    }
+
+    #[test]
+    fn test_failure_reported() {
+        let mut state = SplashState::default();
+        state.set_stage_status(BootstrapStage::LoadConfig, StageStatus::Done);
+        state.set_stage_status(
+            BootstrapStage::ResolveToken,
+            StageStatus::Failed("no token".to_string()),
+        );
+
+        assert!(state.has_failed());
+        assert_eq!(
+            state.failure(),
+            Some((BootstrapStage::ResolveToken, "no token"))
+        );
+        assert_eq!(state.current_stage(), None);
+    }
+
+    #[test]
+    fn test_missing_token_is_read_only_not_failed() {
+        let mut state = SplashState::default();
+        state.set_stage_status(BootstrapStage::LoadConfig, StageStatus::Done);
+        state.set_stage_status(
+            BootstrapStage::ResolveToken,
+            StageStatus::Warning("read-only".to_string()),
+        );
+        state.set_stage_status(BootstrapStage::InitClient, StageStatus::Done);
+
+        assert!(!state.has_failed());
+        assert!(state.read_only());
+        assert!(state.client_ready());
+        assert_eq!(
+            state.warning(),
+            Some((BootstrapStage::ResolveToken, "read-only"))
+        );
+        assert_eq!(state.current_stage(), Some(BootstrapStage::LoadRecentRepos));
+    }
 }
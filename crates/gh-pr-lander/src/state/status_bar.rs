@@ -2,6 +2,8 @@
 
 use chrono::{DateTime, Local};
 use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
 /// Kind of status message (determines icon and color)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -60,13 +62,68 @@ impl StatusMessage {
     }
 }
 
-/// Status bar state - history of messages
+/// Identifier of a running operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OperationId(u64);
+
+impl OperationId {
+    /// Allocate a new, process-wide unique id
+    pub fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(1);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// A long-running operation (merge, rebase, ...) shown until it finishes
+#[derive(Debug, Clone)]
+pub struct ActiveOperation {
+    pub id: OperationId,
+    /// What is being done (e.g. "Merging PR #123")
+    pub label: String,
+    /// The action that started the operation (for context)
+    pub source_action: String,
+    /// When the operation started (elapsed time is derived at render time)
+    pub started_at: Instant,
+}
+
+impl ActiveOperation {
+    /// Time since the operation started, as seen at `now`
+    pub fn elapsed_at(&self, now: Instant) -> Duration {
+        now.saturating_duration_since(self.started_at)
+    }
+}
+
+/// GitHub API requests issued in the last minute
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApiUsage {
+    pub requests_last_minute: u64,
+    /// Configured limit (None = unlimited)
+    pub requests_per_minute: Option<u32>,
+}
+
+impl ApiUsage {
+    /// Whether at least 80% of the configured limit is used
+    pub fn is_high(&self) -> bool {
+        self.requests_per_minute
+            .is_some_and(|limit| self.requests_last_minute * 5 >= u64::from(limit) * 4)
+    }
+}
+
+/// Status bar state - history of messages and running operations
 #[derive(Debug, Clone)]
 pub struct StatusBarState {
     /// Message history (newest at back) - VecDeque for O(1) pop_front
     pub messages: VecDeque<StatusMessage>,
     /// Maximum messages to keep (prevent unbounded growth)
     pub max_history: usize,
+    /// Operations in progress, oldest first
+    pub operations: Vec<ActiveOperation>,
+    /// Request rate, refreshed periodically (None until the first refresh)
+    pub api_usage: Option<ApiUsage>,
+    /// Active GitHub incident reported by the status page, shown as a banner
+    pub incident: Option<String>,
+    /// The background worker left input unanswered for long, shown as a banner
+    pub worker_unresponsive: bool,
 }
 
 impl Default for StatusBarState {
@@ -74,6 +131,10 @@ impl Default for StatusBarState {
         Self {
             messages: VecDeque::new(),
             max_history: 100,
+            operations: Vec::new(),
+            api_usage: None,
+            incident: None,
+            worker_unresponsive: false,
         }
     }
 }
@@ -97,4 +158,9 @@ impl StatusBarState {
     pub fn clear(&mut self) {
         self.messages.clear();
     }
+
+    /// The operation running the longest (if any)
+    pub fn oldest_operation(&self) -> Option<&ActiveOperation> {
+        self.operations.first()
+    }
 }
@@ -0,0 +1,106 @@
+//! Terminal Size State
+//!
+//! The last known terminal size, updated on resize. Viewport dependent state
+//! (diff viewer, build log, debug console) is derived from it in the reducer,
+//! so all views agree on the same layout.
+
+/// Smallest terminal the views are laid out for
+pub const MIN_WIDTH: u16 = 40;
+pub const MIN_HEIGHT: u16 = 10;
+
+/// Smallest terminal the full layout (tabs, bordered table, status bar and
+/// footer) is laid out for; below it the compact layout is used
+pub const COMPACT_WIDTH: u16 = 80;
+pub const COMPACT_HEIGHT: u16 = 20;
+
+/// Terminal size in cells
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TerminalSize {
+    pub width: u16,
+    pub height: u16,
+}
+
+impl TerminalSize {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self { width, height }
+    }
+
+    /// Below the minimum size only the "terminal too small" screen is shown
+    pub fn is_too_small(&self) -> bool {
+        self.width < MIN_WIDTH || self.height < MIN_HEIGHT
+    }
+
+    /// Between the minimum and the full layout size only the PR table is
+    /// shown, without borders, tabs, status bar and footer
+    pub fn is_compact(&self) -> bool {
+        !self.is_too_small() && (self.width < COMPACT_WIDTH || self.height < COMPACT_HEIGHT)
+    }
+
+    /// Height of the debug console panel, including borders (70% of the screen)
+    pub fn debug_console_height(&self) -> u16 {
+        (u32::from(self.height) * 70 / 100) as u16
+    }
+
+    /// Visible log lines of the debug console (panel height minus borders)
+    pub fn debug_console_visible_lines(&self) -> usize {
+        self.debug_console_height().saturating_sub(2) as usize
+    }
+
+    /// Diff viewer viewport (full height minus status bar and borders)
+    pub fn diff_viewport(&self) -> (u16, u16) {
+        (self.width, self.height.saturating_sub(3))
+    }
+
+    /// Visible rows of the build log tree
+    /// (minus status bar, PR header and tree borders)
+    pub fn build_log_viewport_height(&self) -> usize {
+        self.height.saturating_sub(1 + 3 + 2) as usize
+    }
+}
+
+impl From<ratatui::layout::Rect> for TerminalSize {
+    fn from(area: ratatui::layout::Rect) -> Self {
+        Self::new(area.width, area.height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derived_viewports() {
+        let size = TerminalSize::new(120, 40);
+        assert!(!size.is_too_small());
+        assert_eq!(size.debug_console_height(), 28);
+        assert_eq!(size.debug_console_visible_lines(), 26);
+        assert_eq!(size.diff_viewport(), (120, 37));
+        assert_eq!(size.build_log_viewport_height(), 34);
+    }
+
+    #[test]
+    fn test_tiny_sizes_saturate() {
+        let size = TerminalSize::new(0, 0);
+        assert!(size.is_too_small());
+        assert_eq!(size.debug_console_visible_lines(), 0);
+        assert_eq!(size.diff_viewport(), (0, 0));
+        assert_eq!(size.build_log_viewport_height(), 0);
+        assert!(TerminalSize::new(39, 30).is_too_small());
+        assert!(TerminalSize::new(80, 9).is_too_small());
+        assert!(!TerminalSize::new(40, 10).is_too_small());
+        assert_eq!(
+            TerminalSize::new(u16::MAX, u16::MAX).debug_console_height(),
+            45874
+        );
+    }
+
+    #[test]
+    fn test_compact_layout_range() {
+        assert!(!TerminalSize::new(30, 8).is_compact());
+        assert!(TerminalSize::new(40, 10).is_compact());
+        assert!(TerminalSize::new(70, 15).is_compact());
+        assert!(TerminalSize::new(200, 19).is_compact());
+        assert!(TerminalSize::new(79, 50).is_compact());
+        assert!(!TerminalSize::new(80, 20).is_compact());
+    }
+}
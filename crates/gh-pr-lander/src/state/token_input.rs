@@ -0,0 +1,65 @@
+//! Token Input State
+//!
+//! Masked input popup for storing a GitHub token.
+
+/// State for the token input popup
+#[derive(Debug, Clone, Default)]
+pub struct TokenInputState {
+    /// Host the token is for
+    pub host: String,
+    /// Account profile the token is for (None = the host's default account)
+    pub profile: Option<String>,
+    /// Entered token (never rendered in clear text)
+    pub input: String,
+    /// Validation in progress
+    pub validating: bool,
+    /// Last validation error
+    pub error: Option<String>,
+}
+
+impl TokenInputState {
+    /// Create a new token input for the given host
+    pub fn new(host: impl Into<String>) -> Self {
+        Self {
+            host: host.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Store the token for the given account profile
+    pub fn with_profile(mut self, profile: Option<String>) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Key of the account the token is for (`host` or `profile@host`)
+    pub fn account(&self) -> String {
+        gh_client::account_key(Some(&self.host), self.profile.as_deref())
+    }
+
+    /// The entered token masked for display
+    pub fn masked(&self) -> String {
+        "•".repeat(self.input.chars().count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_masked_hides_token() {
+        let mut state = TokenInputState::new("github.com");
+        state.input = "ghp_secret".to_string();
+        assert_eq!(state.masked(), "••••••••••");
+        assert!(!state.masked().contains("secret"));
+    }
+
+    #[test]
+    fn test_account_includes_profile() {
+        let state = TokenInputState::new("github.com");
+        assert_eq!(state.account(), "github.com");
+        let state = state.with_profile(Some("work".to_string()));
+        assert_eq!(state.account(), "work@github.com");
+    }
+}
@@ -0,0 +1,132 @@
+//! Unfinished Work
+//!
+//! What quitting right now would leave behind: PR operations still in flight
+//! and review comments not posted yet. Quitting asks first when there is any.
+
+use crate::domain_models::OperationType;
+use crate::state::{AppState, ConfirmationIntent};
+
+/// PR operations in flight and unsent review comments
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnfinishedWork {
+    /// Operations in flight and how many of each, sorted by name
+    pub operations: Vec<(OperationType, usize)>,
+    /// Review comments (and drafts) of the diff viewer not posted yet
+    pub unsent_comments: usize,
+}
+
+impl UnfinishedWork {
+    pub fn of(state: &AppState) -> Self {
+        let mut operations: Vec<(OperationType, usize)> = Vec::new();
+        for (_, operation) in &state.main_view.in_flight {
+            match operations.iter_mut().find(|(other, _)| other == operation) {
+                Some((_, count)) => *count += 1,
+                None => operations.push((*operation, 1)),
+            }
+        }
+        operations.sort_by_key(|(operation, _)| operation.noun());
+        Self {
+            operations,
+            unsent_comments: state.diff_viewer.unsent_comment_count(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty() && self.unsent_comments == 0
+    }
+
+    /// The operations in flight ("2 merges, 1 approval")
+    pub fn operations_summary(&self) -> String {
+        self.operations
+            .iter()
+            .map(|(operation, count)| counted(*count, operation.noun()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Everything unfinished ("2 merges in progress, 3 unsent review comments")
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.operations.is_empty() {
+            parts.push(format!("{} in progress", self.operations_summary()));
+        }
+        if self.unsent_comments > 0 {
+            parts.push(counted(self.unsent_comments, "unsent review comment"));
+        }
+        parts.join(", ")
+    }
+
+    /// The confirmation asking whether to quit anyway
+    ///
+    /// Waiting is only offered for operations: unsent comments never finish
+    /// on their own, and nothing sent to GitHub can be called back.
+    pub fn quit_intent(&self) -> ConfirmationIntent {
+        let can_wait = !self.operations.is_empty();
+        let mut details = Vec::new();
+        if can_wait {
+            details.push("Quitting won't cancel what was sent, only hide the outcome".into());
+            details.push("Waiting keeps the app open until they all finished".into());
+        }
+        if self.unsent_comments > 0 {
+            details.push("Unsent review comments are lost, waiting or not".into());
+        }
+        ConfirmationIntent::Quit {
+            summary: self.summary(),
+            details,
+            can_wait,
+        }
+    }
+}
+
+/// "1 merge", "2 merges"
+fn counted(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::Repository;
+
+    #[test]
+    fn test_summary_counts_operations_by_type() {
+        let repo = Repository::new("acme", "api", "main");
+        let mut state = AppState::default();
+        assert!(UnfinishedWork::of(&state).is_empty());
+
+        for (pr_number, operation) in [
+            (1, OperationType::Merge),
+            (2, OperationType::Merge),
+            (2, OperationType::Approve),
+        ] {
+            state
+                .main_view
+                .in_flight
+                .insert((repo.watched_pr(pr_number), operation));
+        }
+        let work = UnfinishedWork::of(&state);
+        assert_eq!(work.summary(), "1 approval, 2 merges in progress");
+        assert!(matches!(
+            work.quit_intent(),
+            ConfirmationIntent::Quit { can_wait: true, .. }
+        ));
+
+        let comments_only = UnfinishedWork {
+            operations: Vec::new(),
+            unsent_comments: 3,
+        };
+        assert_eq!(comments_only.summary(), "3 unsent review comments");
+        let ConfirmationIntent::Quit {
+            can_wait, details, ..
+        } = comments_only.quit_intent()
+        else {
+            panic!("quit intent");
+        };
+        assert!(!can_wait);
+        assert_eq!(details, ["Unsent review comments are lost, waiting or not"]);
+    }
+}
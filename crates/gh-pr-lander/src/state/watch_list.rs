@@ -0,0 +1,209 @@
+//! Watch List State
+//!
+//! Pull requests watched across repositories, with their last known status.
+//! The status is refreshed in the background so transitions can be reported
+//! while another repository is shown.
+
+use crate::domain_models::{PrState, ReviewDecision, WatchedPr};
+use gh_client::CiState;
+
+/// Last known status of a watched PR
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchStatus {
+    pub title: String,
+    pub state: PrState,
+    /// CI state of the head commit (None if not fetched, e.g. merged PRs)
+    pub ci: Option<CiState>,
+    pub review: ReviewDecision,
+}
+
+impl WatchStatus {
+    /// Whether the PR is merged or closed and no longer worth watching
+    pub fn is_done(&self) -> bool {
+        self.state != PrState::Open
+    }
+
+    /// Notable changes since the previous status
+    pub fn transitions(&self, previous: &WatchStatus) -> Vec<WatchTransition> {
+        let mut transitions = Vec::new();
+
+        if self.state != previous.state {
+            match self.state {
+                PrState::Merged => transitions.push(WatchTransition::Merged),
+                PrState::Closed => transitions.push(WatchTransition::Closed),
+                PrState::Open => {}
+            }
+        }
+
+        if previous.ci.is_some_and(|ci| ci.is_in_flight()) {
+            match self.ci {
+                Some(CiState::Success) => transitions.push(WatchTransition::CiPassed),
+                Some(CiState::Failure) => transitions.push(WatchTransition::CiFailed),
+                _ => {}
+            }
+        }
+
+        if self.review != previous.review {
+            match self.review {
+                ReviewDecision::Approved => transitions.push(WatchTransition::Approved),
+                ReviewDecision::ChangesRequested => {
+                    transitions.push(WatchTransition::ChangesRequested)
+                }
+                _ => {}
+            }
+        }
+
+        transitions
+    }
+}
+
+/// A notable status change of a watched PR
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchTransition {
+    Merged,
+    Closed,
+    CiPassed,
+    CiFailed,
+    Approved,
+    ChangesRequested,
+}
+
+impl WatchTransition {
+    /// Short description for notifications
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Merged => "was merged",
+            Self::Closed => "was closed",
+            Self::CiPassed => "CI passed",
+            Self::CiFailed => "CI failed",
+            Self::Approved => "was approved",
+            Self::ChangesRequested => "has changes requested",
+        }
+    }
+
+    /// Whether the change needs attention (shown as a warning)
+    pub fn is_bad(&self) -> bool {
+        matches!(self, Self::Closed | Self::CiFailed | Self::ChangesRequested)
+    }
+}
+
+/// A watched PR with its last known status
+#[derive(Debug, Clone)]
+pub struct WatchEntry {
+    pub pr: WatchedPr,
+    /// None until the first background check finished
+    pub status: Option<WatchStatus>,
+}
+
+/// State of the watch list
+#[derive(Debug, Clone, Default)]
+pub struct WatchListState {
+    pub entries: Vec<WatchEntry>,
+    /// Cursor in the watch list view
+    pub selected: usize,
+}
+
+impl WatchListState {
+    /// Whether the PR is watched
+    pub fn contains(&self, pr: &WatchedPr) -> bool {
+        self.entries.iter().any(|entry| entry.pr == *pr)
+    }
+
+    /// Get the entry of a watched PR
+    pub fn get(&self, pr: &WatchedPr) -> Option<&WatchEntry> {
+        self.entries.iter().find(|entry| entry.pr == *pr)
+    }
+
+    /// Watched PRs in list order
+    pub fn watched(&self) -> Vec<WatchedPr> {
+        self.entries.iter().map(|entry| entry.pr.clone()).collect()
+    }
+
+    /// The entry under the cursor
+    pub fn selected_entry(&self) -> Option<&WatchEntry> {
+        self.entries.get(self.selected)
+    }
+
+    /// Watch the PR, or stop watching it if already watched
+    pub fn toggle(&mut self, pr: WatchedPr) {
+        if self.contains(&pr) {
+            self.remove(&pr);
+        } else {
+            self.entries.push(WatchEntry { pr, status: None });
+        }
+    }
+
+    /// Stop watching the PR
+    pub fn remove(&mut self, pr: &WatchedPr) {
+        self.entries.retain(|entry| entry.pr != *pr);
+        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(state: PrState, ci: Option<CiState>, review: ReviewDecision) -> WatchStatus {
+        WatchStatus {
+            title: "Bump serde".to_string(),
+            state,
+            ci,
+            review,
+        }
+    }
+
+    #[test]
+    fn test_transitions() {
+        let pending = status(
+            PrState::Open,
+            Some(CiState::Pending),
+            ReviewDecision::Pending,
+        );
+
+        let passed = status(
+            PrState::Open,
+            Some(CiState::Success),
+            ReviewDecision::Approved,
+        );
+        assert_eq!(
+            passed.transitions(&pending),
+            vec![WatchTransition::CiPassed, WatchTransition::Approved]
+        );
+
+        let merged = status(PrState::Merged, None, ReviewDecision::Approved);
+        assert_eq!(merged.transitions(&passed), vec![WatchTransition::Merged]);
+        assert!(merged.is_done());
+
+        // A CI result without a previous pending state is not a transition
+        assert!(passed.transitions(&passed).is_empty());
+
+        // Queued and approval-gated runs count as pending too
+        for ci in [CiState::Queued, CiState::WaitingApproval] {
+            let waiting = status(PrState::Open, Some(ci), ReviewDecision::Approved);
+            assert_eq!(
+                passed.transitions(&waiting),
+                vec![WatchTransition::CiPassed]
+            );
+        }
+    }
+
+    #[test]
+    fn test_toggle_and_remove() {
+        let mut list = WatchListState::default();
+        let a = WatchedPr::new(None, "org", "repo", 1);
+        let b = WatchedPr::new(None, "org", "repo", 2);
+
+        list.toggle(a.clone());
+        list.toggle(b.clone());
+        assert_eq!(list.watched(), vec![a.clone(), b.clone()]);
+
+        list.selected = 1;
+        list.toggle(b.clone());
+        assert!(!list.contains(&b));
+        assert_eq!(list.selected, 0);
+
+        list.remove(&a);
+        assert!(list.entries.is_empty());
+    }
+}
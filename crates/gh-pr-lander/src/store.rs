@@ -6,32 +6,16 @@ use crate::state::AppState;
 ///
 /// In the new architecture, middleware runs on a background thread.
 /// The store only handles reducer logic on the main thread.
-pub struct Store {
-    state: AppState,
-}
+pub type Store = gh_redux::Store<AppReducer>;
 
-impl Store {
-    pub fn new(initial_state: AppState) -> Self {
-        Self {
-            state: initial_state,
-        }
-    }
+/// The root reducer, see [`reduce`]
+pub struct AppReducer;
 
-    /// Get the current state
-    pub fn state(&self) -> &AppState {
-        &self.state
-    }
-
-    /// Get mutable state reference
-    #[allow(dead_code)]
-    pub fn state_mut(&mut self) -> &mut AppState {
-        &mut self.state
-    }
+impl gh_redux::Reducer for AppReducer {
+    type Action = Action;
+    type State = AppState;
 
-    /// Process an action through reducer ONLY (no middleware)
-    ///
-    /// Middleware runs on background thread, so this is just reducer logic.
-    pub fn dispatch(&mut self, action: Action) {
-        self.state = reduce(self.state.clone(), &action);
+    fn reduce(state: AppState, action: &Action) -> AppState {
+        reduce(state, action)
     }
 }
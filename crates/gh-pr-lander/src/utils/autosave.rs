@@ -0,0 +1,241 @@
+//! Crash-safe saving of local data
+//!
+//! Middleware stages the newest version of a file in [`PendingSaves`] and
+//! lets its [`IoWorker`](super::io_worker::IoWorker) flush them. A staged
+//! save replaces an older one of the same file that was not written yet, and
+//! one writer at a time runs them, so saves of the same file never
+//! interleave. When the app panics or is killed by a hangup or terminate
+//! signal, whatever is still staged is written within [`CRASH_FLUSH_TIMEOUT`].
+//!
+//! Panics inside [`catching_panics`] are caught and survived by the app, they
+//! are only logged: no flush, and nothing printed over the terminal UI.
+
+use std::cell::Cell;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::{Mutex, OnceLock, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+/// Longest a panic or signal waits for pending saves
+pub const CRASH_FLUSH_TIMEOUT: Duration = Duration::from_millis(500);
+
+type SaveJob = Box<dyn FnOnce() + Send>;
+
+thread_local! {
+    /// Whether panics on this thread are caught, see [`catching_panics`]
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Set by the panic hook for a caught panic, see [`take_caught_panic`]
+static CAUGHT_PANIC: AtomicBool = AtomicBool::new(false);
+
+/// Run `f`, whose panics the caller catches with `catch_unwind`
+///
+/// The panic hook of [`flush_on_crash`] only logs them and leaves the staged
+/// saves alone, the app keeps running.
+pub fn catching_panics<R>(f: impl FnOnce() -> R) -> R {
+    struct Restore(bool);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CATCHING.with(|catching| catching.set(self.0));
+        }
+    }
+
+    let _restore = Restore(CATCHING.with(|catching| catching.replace(true)));
+    f()
+}
+
+/// Whether a caught panic happened since the last call
+///
+/// The terminal should be redrawn from scratch: a panic message from outside
+/// the hook (e.g. a library writing to stderr) may have garbled it.
+pub fn take_caught_panic() -> bool {
+    CAUGHT_PANIC.swap(false, Ordering::Relaxed)
+}
+
+/// Saves not written yet, newest per file
+#[derive(Default)]
+pub struct PendingSaves {
+    pending: Mutex<Vec<(&'static str, SaveJob)>>,
+    /// Held while saves run, so they never interleave
+    writer: Mutex<()>,
+}
+
+impl PendingSaves {
+    /// The saves of the whole app, flushed on crashes
+    pub fn global() -> &'static Self {
+        static SAVES: OnceLock<PendingSaves> = OnceLock::new();
+        SAVES.get_or_init(Self::default)
+    }
+
+    /// Stage `save` of the file `name`, replacing a staged save of it
+    ///
+    /// The job reports its own errors, it runs on the next flush.
+    pub fn stage(&self, name: &'static str, save: impl FnOnce() + Send + 'static) {
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+        pending.retain(|(staged, _)| *staged != name);
+        pending.push((name, Box::new(save)));
+    }
+
+    /// Run the staged saves, returns how many ran
+    pub fn flush(&self) -> usize {
+        let _writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        let saves =
+            std::mem::take(&mut *self.pending.lock().unwrap_or_else(PoisonError::into_inner));
+        let count = saves.len();
+        for (_, save) in saves {
+            save();
+        }
+        count
+    }
+
+    /// [`flush`](Self::flush) on another thread, waiting at most `timeout`
+    ///
+    /// Returns false if the saves did not finish in time, e.g. because a
+    /// hanging disk blocks the writer.
+    pub fn flush_within(&'static self, timeout: Duration) -> bool {
+        let (done_tx, done) = mpsc::channel();
+        let spawned = thread::Builder::new()
+            .name("crash-flush".to_string())
+            .spawn(move || done_tx.send(self.flush()).ok());
+        spawned.is_ok() && done.recv_timeout(timeout).is_ok()
+    }
+}
+
+/// Write pending saves when the app panics or is told to hang up or terminate
+///
+/// `on_signal` runs after the flush of a signal (e.g. to restore the
+/// terminal), then the process exits.
+pub fn flush_on_crash(on_signal: fn()) {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if flush_if_crashing(PendingSaves::global(), info) {
+            previous(info);
+        }
+    }));
+
+    #[cfg(unix)]
+    if let Err(e) = thread::Builder::new()
+        .name("signals".to_string())
+        .spawn(move || wait_for_signal(on_signal))
+    {
+        log::warn!("Failed to listen for signals: {}", e);
+    }
+}
+
+/// Flush `saves` for a panic that crashes the app, returns false for a
+/// caught one, which is only logged
+fn flush_if_crashing(saves: &'static PendingSaves, panic: &dyn Display) -> bool {
+    if CATCHING.with(Cell::get) {
+        log::error!("Caught panic: {}", panic);
+        CAUGHT_PANIC.store(true, Ordering::Relaxed);
+        return false;
+    }
+    if !saves.flush_within(CRASH_FLUSH_TIMEOUT) {
+        log::error!("Pending saves did not finish before the panic");
+    }
+    true
+}
+
+/// Flush and exit on SIGHUP (closed terminal, SSH drop) or SIGTERM
+#[cfg(unix)]
+fn wait_for_signal(on_signal: fn()) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            log::warn!("Failed to listen for signals: {}", e);
+            return;
+        }
+    };
+    let exit_code = runtime.block_on(async {
+        let (mut hangup, mut terminate) = match (
+            signal(SignalKind::hangup()),
+            signal(SignalKind::terminate()),
+        ) {
+            (Ok(hangup), Ok(terminate)) => (hangup, terminate),
+            (Err(e), _) | (_, Err(e)) => {
+                log::warn!("Failed to listen for signals: {}", e);
+                return None;
+            }
+        };
+        // Exit codes of a shell for a process killed by the signal
+        tokio::select! {
+            _ = hangup.recv() => Some(128 + 1),
+            _ = terminate.recv() => Some(128 + 15),
+        }
+    });
+    let Some(exit_code) = exit_code else {
+        return;
+    };
+
+    log::info!("Received signal, saving before exit");
+    if !PendingSaves::global().flush_within(CRASH_FLUSH_TIMEOUT) {
+        log::error!("Pending saves did not finish before exit");
+    }
+    on_signal();
+    std::process::exit(exit_code);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn leaked() -> &'static PendingSaves {
+        Box::leak(Box::default())
+    }
+
+    #[test]
+    fn test_staged_saves_of_a_file_collapse_into_the_newest() {
+        let saves = leaked();
+        let written = Arc::new(Mutex::new(Vec::new()));
+        for (name, version) in [("session", 1), ("notes", 1), ("session", 2), ("session", 3)] {
+            let written = Arc::clone(&written);
+            saves.stage(name, move || written.lock().unwrap().push((name, version)));
+        }
+
+        assert_eq!(saves.flush(), 2);
+        assert_eq!(*written.lock().unwrap(), vec![("notes", 1), ("session", 3)]);
+        // Nothing is written twice
+        assert_eq!(saves.flush(), 0);
+    }
+
+    #[test]
+    fn test_flush_within_gives_up_on_a_hanging_save() {
+        let saves = leaked();
+        let (release_tx, release) = mpsc::channel::<()>();
+        saves.stage("session", move || {
+            release.recv().ok();
+        });
+
+        assert!(!saves.flush_within(Duration::from_millis(50)));
+        release_tx.send(()).unwrap();
+
+        saves.stage("notes", || {});
+        assert!(saves.flush_within(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_caught_panics_do_not_flush() {
+        let saves = leaked();
+        let written = Arc::new(Mutex::new(0));
+        let counter = Arc::clone(&written);
+        saves.stage("session", move || *counter.lock().unwrap() += 1);
+
+        assert!(!catching_panics(|| flush_if_crashing(saves, &"caught")));
+        assert_eq!(*written.lock().unwrap(), 0);
+        assert!(take_caught_panic());
+        assert!(!take_caught_panic());
+
+        // Outside of catching_panics a panic crashes the app
+        assert!(flush_if_crashing(saves, &"crash"));
+        assert_eq!(*written.lock().unwrap(), 1);
+    }
+}
@@ -1,26 +1,203 @@
 //! Browser utilities
 //!
-//! Functions for opening URLs in the system's default browser.
+//! Every URL the app opens goes through [`UrlOpener`], which follows the
+//! `browser_command` setting: the system's default browser, a configured
+//! command, the clipboard or the status bar.
 
-/// Open a URL in the system's default browser
+use crate::actions::{Action, StatusBarAction};
+use crate::dispatcher::Dispatcher;
+use crate::utils::clipboard::copy_to_clipboard;
+use gh_pr_config::{AppConfig, BrowserCommand};
+use std::process::Stdio;
+use tokio::runtime::Handle;
+
+/// How a URL is handed over
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpenPlan {
+    /// Run the program with the arguments (the URL being the last one)
+    Spawn { program: String, args: Vec<String> },
+    /// Copy the URL to the clipboard
+    Clipboard,
+    /// Show the URL in the status bar
+    Print,
+}
+
+/// Opens URLs as configured by `browser_command`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UrlOpener {
+    command: BrowserCommand,
+}
+
+impl UrlOpener {
+    pub fn new(command: BrowserCommand) -> Self {
+        Self { command }
+    }
+
+    /// Opener of the configured `browser_command`
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self::new(config.browser_command.clone())
+    }
+
+    /// How `url` would be opened
+    pub fn plan(&self, url: &str) -> OpenPlan {
+        let mut command = match &self.command {
+            BrowserCommand::System => system_command(),
+            BrowserCommand::Command(args) => args.clone(),
+            BrowserCommand::Clipboard => return OpenPlan::Clipboard,
+            BrowserCommand::Print => return OpenPlan::Print,
+        };
+        command.push(url.to_string());
+        let program = command.remove(0);
+        OpenPlan::Spawn {
+            program,
+            args: command,
+        }
+    }
+
+    /// Open `url`
+    ///
+    /// Returns the message to show when the URL was copied or printed rather
+    /// than opened, or an error naming the command that was attempted.
+    pub async fn open(&self, url: String) -> Result<Option<String>, String> {
+        match self.plan(&url) {
+            OpenPlan::Spawn { program, args } => {
+                log::debug!("Opening {} with {}", url, program);
+                let attempted = command_line(&program, &args[..args.len() - 1]);
+                let status = tokio::process::Command::new(&program)
+                    .args(&args)
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .status()
+                    .await
+                    .map_err(|e| format!("Failed to open URL with `{}`: {}", attempted, e))?;
+                if status.success() {
+                    Ok(None)
+                } else {
+                    Err(format!(
+                        "Failed to open URL with `{}`: exited with {}",
+                        attempted, status
+                    ))
+                }
+            }
+            // The URL stays readable in the message when copying fails
+            OpenPlan::Clipboard => match copy_to_clipboard(url.clone()).await {
+                Ok(()) => Ok(Some(format!("Copied {} to the clipboard", url))),
+                Err(e) => Err(format!("Failed to copy {} to the clipboard: {}", url, e)),
+            },
+            OpenPlan::Print => {
+                log::info!("URL: {}", url);
+                Ok(Some(format!("URL: {}", url)))
+            }
+        }
+    }
+
+    /// Open `url` on `runtime`, reporting the outcome in the status bar
+    pub fn spawn(&self, runtime: &Handle, url: String, dispatcher: &Dispatcher) {
+        let opener = self.clone();
+        let dispatcher = dispatcher.clone();
+        runtime.spawn(async move {
+            let action = match opener.open(url).await {
+                Ok(None) => return,
+                Ok(Some(message)) => StatusBarAction::info(message, "Browser"),
+                Err(e) => {
+                    log::error!("{}", e);
+                    StatusBarAction::error(e, "Browser")
+                }
+            };
+            dispatcher.dispatch(Action::StatusBar(action));
+        });
+    }
+}
+
+/// Program and arguments opening a URL in the system's default browser
 ///
-/// Uses platform-specific commands:
 /// - macOS: `open`
 /// - Linux: `xdg-open`
-/// - Windows: `cmd /C start`
-pub async fn open_url(url: String) {
-    #[cfg(target_os = "macos")]
-    let result = tokio::process::Command::new("open").arg(&url).spawn();
+/// - Windows: `cmd /C start ""` (the empty title keeps `start` from taking
+///   a quoted URL for the window title)
+fn system_command() -> Vec<String> {
+    let command: &[&str] = if cfg!(target_os = "macos") {
+        &["open"]
+    } else if cfg!(target_os = "windows") {
+        &["cmd", "/C", "start", ""]
+    } else {
+        &["xdg-open"]
+    };
+    command.iter().map(|arg| arg.to_string()).collect()
+}
+
+/// Command as shown in error messages ("firefox -P work")
+fn command_line(program: &str, args: &[String]) -> String {
+    std::iter::once(program)
+        .chain(args.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://github.com/acme/api/pull/7";
+
+    fn opener(command: BrowserCommand) -> UrlOpener {
+        UrlOpener::new(command)
+    }
+
+    fn open(opener: &UrlOpener) -> Result<Option<String>, String> {
+        tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(opener.open(URL.to_string()))
+    }
+
+    #[test]
+    fn test_plan_per_config() {
+        let firefox = opener(BrowserCommand::Command(vec![
+            "firefox".into(),
+            "-P".into(),
+            "work".into(),
+        ]));
+        assert_eq!(
+            firefox.plan(URL),
+            OpenPlan::Spawn {
+                program: "firefox".to_string(),
+                args: vec!["-P".into(), "work".into(), URL.into()],
+            }
+        );
 
-    #[cfg(target_os = "linux")]
-    let result = tokio::process::Command::new("xdg-open").arg(&url).spawn();
+        let OpenPlan::Spawn { program, args } = opener(BrowserCommand::System).plan(URL) else {
+            panic!("the system browser is spawned");
+        };
+        assert_eq!(program, system_command()[0]);
+        assert_eq!(args.last().map(String::as_str), Some(URL));
 
-    #[cfg(target_os = "windows")]
-    let result = tokio::process::Command::new("cmd")
-        .args(["/C", "start", &url])
-        .spawn();
+        assert_eq!(
+            opener(BrowserCommand::Clipboard).plan(URL),
+            OpenPlan::Clipboard
+        );
+        assert_eq!(opener(BrowserCommand::Print).plan(URL), OpenPlan::Print);
+    }
+
+    #[test]
+    fn test_print_shows_the_url() {
+        assert_eq!(
+            open(&opener(BrowserCommand::Print)),
+            Ok(Some(format!("URL: {}", URL)))
+        );
+    }
 
-    if let Err(e) = result {
-        log::error!("Failed to open URL in browser: {}", e);
+    #[test]
+    fn test_failure_names_the_command() {
+        let missing = opener(BrowserCommand::Command(vec![
+            "no-such-browser-for-tests".into(),
+            "--new-tab".into(),
+        ]));
+        let error = open(&missing).unwrap_err();
+        assert!(
+            error.starts_with("Failed to open URL with `no-such-browser-for-tests --new-tab`: "),
+            "{}",
+            error
+        );
     }
 }
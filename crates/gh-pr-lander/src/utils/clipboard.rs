@@ -0,0 +1,55 @@
+//! Clipboard utilities
+//!
+//! Copies text to the system clipboard through the platform's command line
+//! tool, so no clipboard crate is needed.
+
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// Copy text to the system clipboard
+///
+/// Uses platform-specific commands:
+/// - macOS: `pbcopy`
+/// - Linux: `wl-copy` on Wayland, `xclip -selection clipboard` otherwise
+/// - Windows: `clip`
+pub async fn copy_to_clipboard(text: String) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("pbcopy");
+
+    #[cfg(target_os = "linux")]
+    let mut command = if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        Command::new("wl-copy")
+    } else {
+        let mut command = Command::new("xclip");
+        command.args(["-selection", "clipboard"]);
+        command
+    };
+
+    #[cfg(target_os = "windows")]
+    let mut command = Command::new("clip");
+
+    let program = command.as_std().get_program().to_string_lossy().to_string();
+    let mut child = command
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map_err(|e| format!("{} not available: {}", program, e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write to {}: {}", program, e))?;
+    }
+
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| format!("{} failed: {}", program, e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} exited with {}", program, status))
+    }
+}
@@ -0,0 +1,81 @@
+//! Index cycling
+//!
+//! Next/previous index in a list of tabs, shared by the reducer (which moves
+//! the selection) and middleware (which prefetches for the upcoming tab).
+
+use gh_pr_config::TabCycling;
+
+/// Index after `current` in a list of `len` items
+///
+/// `None` when the list is empty or when `Stop` is at the last item.
+pub fn next_index(current: usize, len: usize, cycling: TabCycling) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    let current = current.min(len - 1);
+    match cycling {
+        TabCycling::Wrap => Some((current + 1) % len),
+        TabCycling::Stop => (current + 1 < len).then_some(current + 1),
+    }
+}
+
+/// Index before `current` in a list of `len` items
+///
+/// `None` when the list is empty or when `Stop` is at the first item.
+pub fn previous_index(current: usize, len: usize, cycling: TabCycling) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    let current = current.min(len - 1);
+    match (current, cycling) {
+        (0, TabCycling::Wrap) => Some(len - 1),
+        (0, TabCycling::Stop) => None,
+        _ => Some(current - 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_list() {
+        for cycling in [TabCycling::Wrap, TabCycling::Stop] {
+            assert_eq!(next_index(0, 0, cycling), None);
+            assert_eq!(previous_index(0, 0, cycling), None);
+        }
+    }
+
+    #[test]
+    fn test_single_item() {
+        assert_eq!(next_index(0, 1, TabCycling::Wrap), Some(0));
+        assert_eq!(previous_index(0, 1, TabCycling::Wrap), Some(0));
+        assert_eq!(next_index(0, 1, TabCycling::Stop), None);
+        assert_eq!(previous_index(0, 1, TabCycling::Stop), None);
+    }
+
+    #[test]
+    fn test_two_items() {
+        assert_eq!(next_index(0, 2, TabCycling::Wrap), Some(1));
+        assert_eq!(next_index(1, 2, TabCycling::Wrap), Some(0));
+        assert_eq!(previous_index(0, 2, TabCycling::Wrap), Some(1));
+        assert_eq!(next_index(1, 2, TabCycling::Stop), None);
+        assert_eq!(previous_index(1, 2, TabCycling::Stop), Some(0));
+    }
+
+    #[test]
+    fn test_many_items_visit_every_index() {
+        let mut idx = 0;
+        let mut visited = vec![];
+        for _ in 0..5 {
+            idx = next_index(idx, 5, TabCycling::Wrap).unwrap();
+            visited.push(idx);
+        }
+        assert_eq!(visited, vec![1, 2, 3, 4, 0]);
+        assert_eq!(next_index(3, 5, TabCycling::Stop), Some(4));
+        assert_eq!(previous_index(3, 5, TabCycling::Stop), Some(2));
+        // A stale index past the end counts as the last item
+        assert_eq!(next_index(9, 5, TabCycling::Wrap), Some(0));
+        assert_eq!(previous_index(9, 5, TabCycling::Stop), Some(3));
+    }
+}
@@ -0,0 +1,222 @@
+//! Human-facing formatting
+//!
+//! Timestamps, durations and counts shown in the UI or written to exports go
+//! through this module, so the `[ui] timestamps` style, `timezone` and
+//! `date_format` settings apply everywhere and counts read "1 PR", "2 PRs".
+
+use chrono::{DateTime, Local, TimeDelta, Utc};
+use gh_pr_config::{TimestampStyle, UiConfig};
+
+/// Format of clock times of recent events (status messages, last refresh)
+const CLOCK_FORMAT: &str = "%H:%M:%S";
+
+/// Format `dt` in the configured style, relative times measured at `now`
+pub fn format_timestamp(dt: DateTime<Utc>, now: DateTime<Utc>, config: &UiConfig) -> String {
+    match config.timestamps {
+        TimestampStyle::Relative => format_relative(dt, now),
+        TimestampStyle::Absolute => format_date(dt, config),
+        TimestampStyle::Both => {
+            format!("{} ({})", format_date(dt, config), format_relative(dt, now))
+        }
+    }
+}
+
+/// Absolute date and time in the configured `date_format` and time zone
+pub fn format_date(dt: DateTime<Utc>, config: &UiConfig) -> String {
+    format_in_timezone(dt, config, &config.date_format)
+}
+
+/// Clock time of a recent event ("12:00:00") in the configured time zone
+pub fn format_clock(dt: DateTime<Utc>, config: &UiConfig) -> String {
+    format_in_timezone(dt, config, CLOCK_FORMAT)
+}
+
+/// Format the time from `dt` to `now` as "just now", "5m ago", "3h ago" or "2d ago"
+///
+/// Timestamps in the future (clock skew) read "in 5m", or "just now" when
+/// they are less than a minute ahead.
+pub fn format_relative(dt: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let secs = (now - dt).num_seconds();
+    if secs.unsigned_abs() < 60 {
+        return "just now".to_string();
+    }
+    let span = format_span(secs.unsigned_abs());
+    if secs < 0 {
+        format!("in {}", span)
+    } else {
+        format!("{} ago", span)
+    }
+}
+
+/// Format a run time as "45s", "3m 12s" or "1h 5m" (negative times count as 0s)
+pub fn format_duration(duration: TimeDelta) -> String {
+    let secs = duration.num_seconds().max(0);
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m {}s", secs / 60, secs % 60),
+        _ => format!("{}h {}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// "1 PR", "0 PRs", "3 dependencies"
+pub fn format_count(count: usize, singular: &str, plural: &str) -> String {
+    if count == 1 {
+        format!("1 {}", singular)
+    } else {
+        format!("{} {}", count, plural)
+    }
+}
+
+/// Largest whole unit of a number of seconds ("5m", "3h", "2d")
+pub fn format_span(secs: u64) -> String {
+    match secs {
+        0..=3599 => format!("{}m", secs / 60),
+        3600..=86399 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86400),
+    }
+}
+
+/// Format `dt` in the configured time zone, the system one if none is set
+fn format_in_timezone(dt: DateTime<Utc>, config: &UiConfig, format: &str) -> String {
+    match config.timezone_offset() {
+        Some(offset) => dt.with_timezone(&offset).format(format).to_string(),
+        None => dt.with_timezone(&Local).format(format).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, TimeZone};
+
+    fn ui(timestamps: TimestampStyle, timezone: &str) -> UiConfig {
+        UiConfig {
+            timestamps,
+            timezone: Some(timezone.to_string()),
+            ..UiConfig::default()
+        }
+    }
+
+    fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn test_relative() {
+        let now = utc(2025, 1, 15, 12, 0);
+        assert_eq!(format_relative(now, now), "just now");
+        assert_eq!(
+            format_relative(now - TimeDelta::seconds(59), now),
+            "just now"
+        );
+        assert_eq!(format_relative(now - TimeDelta::minutes(5), now), "5m ago");
+        assert_eq!(format_relative(now - TimeDelta::hours(3), now), "3h ago");
+        assert_eq!(format_relative(now - TimeDelta::days(2), now), "2d ago");
+    }
+
+    #[test]
+    fn test_relative_future_timestamps() {
+        let now = utc(2025, 1, 15, 12, 0);
+        assert_eq!(
+            format_relative(now + TimeDelta::seconds(30), now),
+            "just now"
+        );
+        assert_eq!(format_relative(now + TimeDelta::minutes(5), now), "in 5m");
+        assert_eq!(format_relative(now + TimeDelta::days(400), now), "in 400d");
+        assert!(format_relative(DateTime::<Utc>::MAX_UTC, now).starts_with("in "));
+        assert!(format_relative(DateTime::<Utc>::MIN_UTC, now).ends_with("d ago"));
+    }
+
+    #[test]
+    fn test_relative_across_dst_change() {
+        // Europe/Berlin switched from +01:00 to +02:00 at 02:00 on 2025-03-30:
+        // 01:30 to 03:30 on the wall clock is one hour
+        let before = FixedOffset::east_opt(3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 3, 30, 1, 30, 0)
+            .unwrap();
+        let after = FixedOffset::east_opt(2 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 3, 30, 3, 30, 0)
+            .unwrap();
+        assert_eq!(format_relative(before.to_utc(), after.to_utc()), "1h ago");
+
+        // And back on 2025-10-26: 02:30 (+02:00) to 02:30 (+01:00) is one hour too
+        let before = FixedOffset::east_opt(2 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 10, 26, 2, 30, 0)
+            .unwrap();
+        let after = FixedOffset::east_opt(3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 10, 26, 2, 30, 0)
+            .unwrap();
+        assert_eq!(format_relative(before.to_utc(), after.to_utc()), "1h ago");
+    }
+
+    #[test]
+    fn test_styles_and_timezone() {
+        let now = utc(2025, 3, 30, 1, 30);
+        let dt = utc(2025, 3, 30, 0, 30);
+
+        assert_eq!(
+            format_timestamp(dt, now, &ui(TimestampStyle::Relative, "UTC")),
+            "1h ago"
+        );
+        assert_eq!(
+            format_timestamp(dt, now, &ui(TimestampStyle::Absolute, "UTC")),
+            "2025-03-30 00:30"
+        );
+        assert_eq!(
+            format_timestamp(dt, now, &ui(TimestampStyle::Both, "+02:00")),
+            "2025-03-30 02:30 (1h ago)"
+        );
+        // Negative offsets move the date back
+        assert_eq!(
+            format_timestamp(dt, now, &ui(TimestampStyle::Absolute, "-05:00")),
+            "2025-03-29 19:30"
+        );
+        assert_eq!(
+            format_clock(dt, &ui(TimestampStyle::Relative, "+05:30")),
+            "06:00:00"
+        );
+    }
+
+    #[test]
+    fn test_custom_date_format() {
+        let config = UiConfig {
+            date_format: "%d.%m.%Y".to_string(),
+            ..ui(TimestampStyle::Absolute, "UTC")
+        };
+        let dt = utc(2025, 3, 30, 0, 30);
+        assert_eq!(format_date(dt, &config), "30.03.2025");
+        assert_eq!(format_timestamp(dt, dt, &config), "30.03.2025");
+    }
+
+    #[test]
+    fn test_format_count() {
+        assert_eq!(format_count(0, "PR", "PRs"), "0 PRs");
+        assert_eq!(format_count(1, "PR", "PRs"), "1 PR");
+        assert_eq!(format_count(2, "PR", "PRs"), "2 PRs");
+        assert_eq!(
+            format_count(1, "dependency", "dependencies"),
+            "1 dependency"
+        );
+    }
+
+    #[test]
+    fn test_format_span() {
+        assert_eq!(format_span(0), "0m");
+        assert_eq!(format_span(59), "0m");
+        assert_eq!(format_span(7200), "2h");
+        assert_eq!(format_span(3 * 86400), "3d");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(TimeDelta::zero()), "0s");
+        assert_eq!(format_duration(TimeDelta::seconds(45)), "45s");
+        assert_eq!(format_duration(TimeDelta::seconds(192)), "3m 12s");
+        assert_eq!(format_duration(TimeDelta::seconds(3900)), "1h 5m");
+        assert_eq!(format_duration(TimeDelta::seconds(-5)), "0s");
+    }
+}
@@ -0,0 +1,81 @@
+//! Blocking file and process work off the middleware thread
+//!
+//! All middleware runs on one background thread, so a handler writing a file
+//! or waiting for `git` delays every action queued behind it. Middleware
+//! without a tokio runtime hands such work to an [`IoWorker`] instead.
+//! Jobs run one after the other in the order they were queued, so two saves
+//! of the same file never race.
+
+use std::sync::mpsc::{self, Sender};
+use std::thread::{self, JoinHandle};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// A thread running blocking jobs in order
+pub struct IoWorker {
+    jobs: Option<Sender<Job>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl IoWorker {
+    /// Start a worker thread called `name`
+    pub fn spawn(name: &str) -> Self {
+        let (jobs, rx) = mpsc::channel::<Job>();
+        match thread::Builder::new()
+            .name(name.to_string())
+            .spawn(move || rx.into_iter().for_each(|job| job()))
+        {
+            Ok(thread) => Self {
+                jobs: Some(jobs),
+                thread: Some(thread),
+            },
+            Err(e) => {
+                log::warn!("Failed to start {}, running its jobs inline: {}", name, e);
+                Self {
+                    jobs: None,
+                    thread: None,
+                }
+            }
+        }
+    }
+
+    /// Queue `job`, returns at once (runs it right away without a thread)
+    pub fn run(&self, job: impl FnOnce() + Send + 'static) {
+        match &self.jobs {
+            Some(jobs) => {
+                if let Err(mpsc::SendError(job)) = jobs.send(Box::new(job)) {
+                    job();
+                }
+            }
+            None => job(),
+        }
+    }
+}
+
+impl Drop for IoWorker {
+    /// Finish the queued jobs, so saves before quitting land on disk
+    fn drop(&mut self) {
+        self.jobs.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_jobs_run_in_order_before_drop_returns() {
+        let done = Arc::new(Mutex::new(Vec::new()));
+        let worker = IoWorker::spawn("io-worker-test");
+        for job in 0..10 {
+            let done = Arc::clone(&done);
+            worker.run(move || done.lock().unwrap().push(job));
+        }
+        drop(worker);
+        assert_eq!(*done.lock().unwrap(), (0..10).collect::<Vec<_>>());
+    }
+}
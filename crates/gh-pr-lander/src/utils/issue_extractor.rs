@@ -112,7 +112,7 @@ impl IssueTracker {
 /// - `org/*` matches any repo in org
 /// - `org/repo` matches exact repo
 /// - `*` matches anything
-fn glob_match(pattern: &str, text: &str) -> bool {
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
     let parts: Vec<&str> = pattern.split('*').collect();
 
     if parts.len() == 1 {
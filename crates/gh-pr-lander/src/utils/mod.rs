@@ -2,5 +2,12 @@
 //!
 //! Common utilities used across the application.
 
+pub mod autosave;
 pub mod browser;
+pub mod clipboard;
+pub mod cycling;
+pub mod format;
+pub mod io_worker;
 pub mod issue_extractor;
+pub mod paste;
+pub mod template;
@@ -0,0 +1,60 @@
+//! Pasted text
+//!
+//! Terminals with bracketed paste deliver a paste as one piece of text
+//! instead of key presses. Before it is inserted it is normalized for the
+//! input it goes into: single-line fields get one line, multi-line editors
+//! keep the lines but never the terminal's control characters.
+
+/// Text flattened to one line for a single-line field
+///
+/// Line breaks and tabs become single spaces and the ends are trimmed, so a
+/// URL copied with its line break pastes as just the URL.
+pub fn single_line(text: &str) -> String {
+    text.split(['\r', '\n', '\t'])
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect()
+}
+
+/// Text for a multi-line editor, with `\n` line breaks
+///
+/// Windows (`\r\n`) and old Mac (`\r`) line breaks are converted, tabs kept
+/// and other control characters dropped.
+pub fn multi_line(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .chars()
+        .filter(|c| !c.is_control() || *c == '\n' || *c == '\t')
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_line_flattens_lines() {
+        assert_eq!(
+            single_line("https://github.com/acme/api/pull/7\n"),
+            "https://github.com/acme/api/pull/7"
+        );
+        assert_eq!(
+            single_line("  first line\r\nsecond\tline\n\nthird  "),
+            "first line second line third"
+        );
+        assert_eq!(single_line("bell\u{7} and escape\u{1b}"), "bell and escape");
+        assert_eq!(single_line("\n\n"), "");
+    }
+
+    #[test]
+    fn test_multi_line_keeps_lines() {
+        assert_eq!(
+            multi_line("first\r\nsecond\rthird\n\tindented\u{1b}\n"),
+            "first\nsecond\nthird\n\tindented\n"
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! Message templates
+//!
+//! Messages posted to GitHub may hold placeholders like `{author}`, filled
+//! in per PR right before posting. Unknown placeholders are kept as typed,
+//! so braces in code snippets or JSON survive.
+
+use crate::domain_models::Pr;
+
+/// Placeholders a PR message may use
+pub const PR_PLACEHOLDERS: [&str; 4] = ["number", "title", "author", "branch"];
+
+/// Fill in every `{name}` of `template` that `value` knows
+pub fn render(template: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let filled = after
+            .find('}')
+            .and_then(|end| Some((value(&after[..end])?, &after[end + 1..])));
+        match filled {
+            Some((filled, remaining)) => {
+                rendered.push_str(&filled);
+                rest = remaining;
+            }
+            None => {
+                rendered.push('{');
+                rest = after;
+            }
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Values of the [`PR_PLACEHOLDERS`], taken from one PR
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrPlaceholders {
+    pub number: usize,
+    pub title: String,
+    pub author: String,
+    pub branch: String,
+}
+
+impl PrPlaceholders {
+    pub fn of(pr: &Pr) -> Self {
+        Self {
+            number: pr.number,
+            title: pr.title.clone(),
+            author: pr.author.clone(),
+            branch: pr.head_branch.clone(),
+        }
+    }
+
+    /// The message as posted on this PR
+    ///
+    /// Unknown values (of PRs not loaded) keep their placeholder.
+    pub fn render(&self, template: &str) -> String {
+        render(template, |name| {
+            match name {
+                "number" => Some(self.number.to_string()),
+                "title" => Some(self.title.clone()),
+                "author" => Some(self.author.clone()),
+                "branch" => Some(self.branch.clone()),
+                _ => None,
+            }
+            .filter(|value| !value.is_empty())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders() -> PrPlaceholders {
+        PrPlaceholders {
+            number: 4312,
+            title: "Bump serde".to_string(),
+            author: "dependabot[bot]".to_string(),
+            branch: "dependabot/cargo/serde-1.0.200".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_pr_placeholders_are_filled_in() {
+        assert_eq!(
+            placeholders().render("@{author} please rebase #{number} ({title}) on {branch}"),
+            "@dependabot[bot] please rebase #4312 (Bump serde) on dependabot/cargo/serde-1.0.200"
+        );
+        assert_eq!(placeholders().render("{number}{number}"), "43124312");
+    }
+
+    #[test]
+    fn test_unknown_placeholders_and_braces_are_kept() {
+        assert_eq!(
+            placeholders().render("{\"pr\": {number}} {unknown} {open"),
+            "{\"pr\": 4312} {unknown} {open"
+        );
+        assert_eq!(placeholders().render("no placeholders"), "no placeholders");
+        assert_eq!(placeholders().render("}{"), "}{");
+    }
+
+    #[test]
+    fn test_values_of_unloaded_prs_keep_their_placeholder() {
+        let unloaded = PrPlaceholders {
+            number: 4312,
+            ..PrPlaceholders::default()
+        };
+        assert_eq!(
+            unloaded.render("@{author} please rebase #{number} on {branch}"),
+            "@{author} please rebase #4312 on {branch}"
+        );
+    }
+}
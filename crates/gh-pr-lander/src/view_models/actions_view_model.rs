@@ -0,0 +1,204 @@
+//! View model for the Actions view
+//!
+//! Pre-computes the workflow run rows (status glyph, duration, relative
+//! time) for the workflow run history of a branch.
+
+use crate::state::ActionsViewState;
+use crate::utils::format::{format_duration, format_timestamp};
+use chrono::{DateTime, Utc};
+use gh_client::{WorkflowRun, WorkflowRunConclusion, WorkflowRunStatus};
+use gh_pr_config::UiConfig;
+use ratatui::style::Color;
+
+/// View model for the Actions view
+#[derive(Debug, Clone)]
+pub struct ActionsViewModel {
+    /// Panel title (e.g., "Workflow runs: acme/api @ main")
+    pub title: String,
+    /// Workflow run rows, newest first
+    pub rows: Vec<WorkflowRunRow>,
+    /// Loading, error or empty message shown instead of the rows
+    pub message: Option<String>,
+    /// Cursor position in `rows`
+    pub selected: usize,
+}
+
+/// A single workflow run row
+#[derive(Debug, Clone)]
+pub struct WorkflowRunRow {
+    pub glyph: &'static str,
+    pub glyph_color: Color,
+    pub workflow: String,
+    pub branch: String,
+    pub trigger: String,
+    /// Run time (e.g. "3m 12s"), running runs count up to now
+    pub duration: String,
+    /// When the run started (e.g. "5m ago"), in the configured timestamp style
+    pub age: String,
+}
+
+impl ActionsViewModel {
+    /// Build view model from Actions view state, with times relative to `now`
+    pub fn from_state(
+        state: &ActionsViewState,
+        theme: &gh_pr_lander_theme::Theme,
+        ui: &UiConfig,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let title = match state.repo {
+            Some(ref repo) => format!(
+                "Workflow runs: {}/{} @ {}",
+                repo.org, repo.repo, state.branch
+            ),
+            None => "Workflow runs".to_string(),
+        };
+
+        let rows: Vec<WorkflowRunRow> = state
+            .runs
+            .iter()
+            .map(|run| {
+                let (glyph, glyph_color) = run_glyph(run, theme);
+                let finished_at = if run.status == WorkflowRunStatus::Completed {
+                    run.updated_at
+                } else {
+                    now
+                };
+                WorkflowRunRow {
+                    glyph,
+                    glyph_color,
+                    workflow: run.name.clone(),
+                    branch: run.head_branch.clone().unwrap_or_else(|| "-".to_string()),
+                    trigger: run.event.clone(),
+                    duration: format_duration(finished_at - run.created_at),
+                    age: format_timestamp(run.created_at, now, ui),
+                }
+            })
+            .collect();
+
+        let message = if let Some(ref error) = state.error {
+            Some(format!("Failed to load workflow runs: {}", error))
+        } else if state.loading && rows.is_empty() {
+            Some("Loading workflow runs...".to_string())
+        } else if rows.is_empty() {
+            Some(format!("No workflow runs on {}", state.branch))
+        } else {
+            None
+        };
+
+        Self {
+            title,
+            rows,
+            message,
+            selected: state.selected,
+        }
+    }
+}
+
+/// Status glyph of a workflow run and its color
+fn run_glyph(run: &WorkflowRun, theme: &gh_pr_lander_theme::Theme) -> (&'static str, Color) {
+    match (run.status, run.conclusion) {
+        (WorkflowRunStatus::Completed, Some(WorkflowRunConclusion::Success)) => {
+            ("✓", theme.status_success)
+        }
+        (
+            WorkflowRunStatus::Completed,
+            Some(WorkflowRunConclusion::Failure | WorkflowRunConclusion::TimedOut),
+        ) => ("✗", theme.status_error),
+        (WorkflowRunStatus::Completed, Some(WorkflowRunConclusion::ActionRequired)) => {
+            ("!", theme.status_warning)
+        }
+        (WorkflowRunStatus::Completed, _) => ("○", theme.text_muted),
+        (WorkflowRunStatus::InProgress, _) => ("●", theme.status_checking),
+        (WorkflowRunStatus::Waiting, _) => ("⏸", theme.status_warning),
+        (WorkflowRunStatus::Queued | WorkflowRunStatus::Pending, _) => ("◌", theme.status_info),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::Repository;
+    use chrono::TimeDelta;
+
+    fn run(
+        status: WorkflowRunStatus,
+        conclusion: Option<WorkflowRunConclusion>,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    ) -> WorkflowRun {
+        WorkflowRun {
+            id: 1,
+            name: "CI".to_string(),
+            status,
+            conclusion,
+            head_sha: "abc".to_string(),
+            head_branch: Some("main".to_string()),
+            event: "push".to_string(),
+            html_url: String::new(),
+            created_at,
+            updated_at,
+            run_attempt: 1,
+        }
+    }
+
+    #[test]
+    fn test_rows_show_status_duration_and_age() {
+        let now = Utc::now();
+        let started = now - TimeDelta::minutes(10);
+        let state = ActionsViewState {
+            repo: Some(Repository::new("acme", "api", "main")),
+            branch: "main".to_string(),
+            runs: vec![
+                run(
+                    WorkflowRunStatus::InProgress,
+                    None,
+                    now - TimeDelta::seconds(30),
+                    now,
+                ),
+                run(
+                    WorkflowRunStatus::Completed,
+                    Some(WorkflowRunConclusion::Failure),
+                    started,
+                    started + TimeDelta::seconds(192),
+                ),
+            ],
+            ..Default::default()
+        };
+
+        let vm = ActionsViewModel::from_state(
+            &state,
+            &gh_pr_lander_theme::Theme::default(),
+            &UiConfig::default(),
+            now,
+        );
+        assert_eq!(vm.title, "Workflow runs: acme/api @ main");
+        assert!(vm.message.is_none());
+        assert_eq!(vm.rows[0].glyph, "●");
+        assert_eq!(vm.rows[0].duration, "30s");
+        assert_eq!(vm.rows[0].age, "just now");
+        assert_eq!(vm.rows[1].glyph, "✗");
+        assert_eq!(vm.rows[1].duration, "3m 12s");
+        assert_eq!(vm.rows[1].age, "10m ago");
+        assert_eq!(vm.rows[1].trigger, "push");
+    }
+
+    #[test]
+    fn test_empty_and_error_messages() {
+        let theme = gh_pr_lander_theme::Theme::default();
+        let mut state = ActionsViewState {
+            branch: "main".to_string(),
+            loading: true,
+            ..Default::default()
+        };
+        let vm = ActionsViewModel::from_state(&state, &theme, &UiConfig::default(), Utc::now());
+        assert_eq!(vm.message.as_deref(), Some("Loading workflow runs..."));
+
+        state.loading = false;
+        state.error = Some("404".to_string());
+        let vm = ActionsViewModel::from_state(&state, &theme, &UiConfig::default(), Utc::now());
+        assert_eq!(
+            vm.message.as_deref(),
+            Some("Failed to load workflow runs: 404")
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! View model for the audit log panel
+//!
+//! Pre-computes one row per audit entry in scope (time, PR, operation,
+//! outcome and parameters), newest first, plus the title and summary line.
+
+use crate::audit::{AuditEntry, AuditOutcome};
+use crate::state::{AuditLogState, AuditScope};
+use crate::utils::format::format_count;
+use chrono::DateTime;
+
+/// View model for the audit log panel
+#[derive(Debug, Clone)]
+pub struct AuditLogViewModel {
+    /// Panel title naming the scope
+    pub title: String,
+    /// Count and position line shown above the table
+    pub summary: String,
+    /// Entries in scope, newest first
+    pub rows: Vec<AuditLogRow>,
+    /// Message shown instead of the table (nothing recorded)
+    pub message: Option<String>,
+}
+
+/// A single audit entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditLogRow {
+    pub time: String,
+    pub pr: String,
+    pub operation: String,
+    pub login: String,
+    /// Parameters as "key=value", then the error of a failure
+    pub details: String,
+    pub is_failure: bool,
+    pub is_selected: bool,
+}
+
+impl AuditLogRow {
+    fn from_entry(entry: &AuditEntry, is_selected: bool) -> Self {
+        let mut details: Vec<String> = entry
+            .params
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect();
+        if let Some(error) = &entry.error {
+            details.push(format!("error: {}", error));
+        }
+        Self {
+            time: format_time(&entry.ts),
+            pr: entry.pr.map(|pr| format!("#{}", pr)).unwrap_or_default(),
+            operation: entry.operation.label().to_string(),
+            login: entry.login.clone().unwrap_or_default(),
+            details: details.join(", "),
+            is_failure: entry.outcome == AuditOutcome::Failure,
+            is_selected,
+        }
+    }
+}
+
+impl AuditLogViewModel {
+    /// Build view model from the audit log state
+    pub fn from_state(state: &AuditLogState) -> Self {
+        let rows: Vec<AuditLogRow> = state
+            .visible()
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| AuditLogRow::from_entry(entry, i == state.selected))
+            .collect();
+
+        let target = match (&state.repo, state.scope, state.pr_number) {
+            (None, ..) => "no repository".to_string(),
+            (Some(repo), AuditScope::Pr, Some(pr)) => format!("{} #{}", repo.display_name(), pr),
+            (Some(repo), ..) => repo.display_name(),
+        };
+        let title = format!("Audit log of {}", target);
+
+        let mut summary = format_count(rows.len(), "operation", "operations");
+        if !rows.is_empty() {
+            summary = format!("{} of {}", state.selected + 1, summary);
+        }
+
+        let message = rows
+            .is_empty()
+            .then(|| format!("No operations recorded for {}", target));
+
+        Self {
+            title,
+            summary,
+            rows,
+            message,
+        }
+    }
+}
+
+/// Format an entry timestamp as "2026-10-16 14:02:11", in the zone it was recorded
+fn format_time(ts: &str) -> String {
+    DateTime::parse_from_rfc3339(ts)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|_| ts.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::AuditOperation;
+    use crate::domain_models::Repository;
+
+    #[test]
+    fn test_rows_and_title() {
+        let mut state = AuditLogState::default();
+        state.focus(Some(Repository::new("acme", "api", "main")), Some(123));
+        let vm = AuditLogViewModel::from_state(&state);
+        assert_eq!(vm.title, "Audit log of acme/api #123");
+        assert_eq!(
+            vm.message.as_deref(),
+            Some("No operations recorded for acme/api #123")
+        );
+
+        state.set_entries(vec![AuditEntry {
+            ts: "2026-10-16T14:02:11.402+02:00".to_string(),
+            login: Some("alice".to_string()),
+            operation: AuditOperation::RequestChanges,
+            repo: "acme/api".to_string(),
+            host: None,
+            pr: Some(123),
+            params: [("message".to_string(), "Please rebase".to_string())].into(),
+            outcome: AuditOutcome::Failure,
+            error: Some("Not Found".to_string()),
+        }]);
+        let vm = AuditLogViewModel::from_state(&state);
+        assert_eq!(vm.summary, "1 of 1 operation");
+        assert_eq!(
+            vm.rows,
+            vec![AuditLogRow {
+                time: "2026-10-16 14:02:11".to_string(),
+                pr: "#123".to_string(),
+                operation: "request changes".to_string(),
+                login: "alice".to_string(),
+                details: "message=Please rebase, error: Not Found".to_string(),
+                is_failure: true,
+                is_selected: true,
+            }]
+        );
+        assert!(vm.message.is_none());
+    }
+}
@@ -0,0 +1,108 @@
+//! View model for the branch picker
+//!
+//! Pre-computes the branch rows and messages of the branch picker,
+//! separating data preparation from rendering logic.
+
+use crate::state::BranchPickerState;
+
+/// View model for the branch picker popup
+#[derive(Debug, Clone)]
+pub struct BranchPickerViewModel {
+    /// Popup title (e.g., "Base branch: acme/api")
+    pub title: String,
+    /// Current filter text
+    pub query: String,
+    /// Rows matching the filter
+    pub rows: Vec<BranchRow>,
+    /// Loading, error or empty message shown instead of the rows
+    pub message: Option<String>,
+}
+
+/// A single branch row
+#[derive(Debug, Clone)]
+pub struct BranchRow {
+    /// Is this row under the cursor?
+    pub is_selected: bool,
+    /// Branch name
+    pub name: String,
+    /// Marker of the branch shown now, or the configured one
+    pub note: &'static str,
+}
+
+impl BranchPickerViewModel {
+    /// Build view model from branch picker state
+    pub fn from_state(state: &BranchPickerState) -> Self {
+        let repo = &state.repo;
+        let rows: Vec<BranchRow> = state
+            .filtered()
+            .into_iter()
+            .enumerate()
+            .map(|(row, branch)| BranchRow {
+                is_selected: row == state.selected,
+                name: branch.to_string(),
+                note: if branch == repo.branch {
+                    "current"
+                } else if branch == repo.saved_branch() {
+                    "configured"
+                } else {
+                    ""
+                },
+            })
+            .collect();
+
+        let message = if state.loading {
+            Some("Loading branches...".to_string())
+        } else if let Some(ref error) = state.error {
+            Some(format!("Failed to load branches: {}", error))
+        } else if rows.is_empty() && !state.query.trim().is_empty() {
+            Some(format!(
+                "Enter shows PRs targeting '{}'",
+                state.query.trim()
+            ))
+        } else if rows.is_empty() {
+            Some("No branches".to_string())
+        } else {
+            None
+        };
+
+        Self {
+            title: format!("Base branch: {}", repo.display_name()),
+            query: state.query.clone(),
+            rows,
+            message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::Repository;
+
+    #[test]
+    fn test_rows_mark_current_and_configured_branch() {
+        let repo = Repository::new("acme", "api", "main").with_base_branch("release/2.x");
+        let mut state = BranchPickerState::new(repo);
+        state.set_branches(vec![
+            "main".to_string(),
+            "release/1.x".to_string(),
+            "release/2.x".to_string(),
+        ]);
+
+        let vm = BranchPickerViewModel::from_state(&state);
+        assert_eq!(vm.title, "Base branch: acme/api");
+        let notes: Vec<_> = vm.rows.iter().map(|row| row.note).collect();
+        assert_eq!(notes, vec!["configured", "", "current"]);
+        assert!(vm.rows[2].is_selected);
+        assert!(vm.message.is_none());
+
+        for c in "hotfix".chars() {
+            state.push_query(c);
+        }
+        let vm = BranchPickerViewModel::from_state(&state);
+        assert_eq!(
+            vm.message.as_deref(),
+            Some("Enter shows PRs targeting 'hotfix'")
+        );
+    }
+}
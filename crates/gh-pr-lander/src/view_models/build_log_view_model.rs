@@ -2,9 +2,16 @@
 //!
 //! Pre-computes presentation data for the build log panel.
 
-use crate::state::{BuildLogJobMetadata, BuildLogJobStatus, BuildLogLoadingState, BuildLogState};
+use crate::state::{
+    BuildLogContext, BuildLogJobMetadata, BuildLogJobStatus, BuildLogLoadingState, BuildLogState,
+    SUCCEEDED_GROUP,
+};
+use crate::utils::format::{format_count, format_duration};
 use ratatui::style::Color;
 
+/// Label of error log lines in themes with text labels
+const ERROR_LABEL: &str = "[ERROR] ";
+
 /// View model for rendering the build log panel
 #[derive(Debug, Clone)]
 pub struct BuildLogViewModel {
@@ -19,9 +26,11 @@ pub struct BuildLogViewModel {
     #[allow(dead_code)]
     pub viewport_height: usize,
 
-    /// Loading state
+    /// Loading state, true until the first workflow arrives
     pub is_loading: bool,
     pub error_message: Option<String>,
+    /// "2 of 6 workflows loaded" while workflows are loading
+    pub progress: Option<String>,
 }
 
 /// PR header view model for build log
@@ -72,6 +81,8 @@ pub enum BuildLogRowStyle {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BuildLogNodeType {
     Workflow,
+    /// Folded successful jobs of a workflow
+    SucceededGroup,
     Job,
     Step,
     LogLine,
@@ -80,16 +91,35 @@ pub enum BuildLogNodeType {
 impl BuildLogViewModel {
     /// Transform BuildLogState into display-ready view model
     pub fn from_state(state: &BuildLogState, theme: &gh_pr_lander_theme::Theme) -> Self {
-        let is_loading = matches!(state.loading_state, BuildLogLoadingState::Loading);
+        let (is_loading, progress) = match state.loading_state {
+            BuildLogLoadingState::Loading { loaded, total } => (
+                state.workflows.is_empty(),
+                total.map(|total| format!("{} of {} workflows loaded", loaded, total)),
+            ),
+            _ => (false, None),
+        };
         let error_message = match &state.loading_state {
             BuildLogLoadingState::Error(e) => Some(e.clone()),
             _ => None,
         };
 
+        let (number_text, title, author_text) = match &state.context {
+            Some(BuildLogContext::Pr(pr)) => (
+                format!("#{}", pr.number),
+                pr.title.clone(),
+                format!("by {}", pr.author),
+            ),
+            Some(BuildLogContext::Run(run)) => (
+                run.workflow_name.clone(),
+                format!("on {}", run.branch),
+                format!("run {} triggered by {}", run.run_id, run.event),
+            ),
+            None => (String::new(), String::new(), String::new()),
+        };
         let pr_header = BuildLogPrHeaderViewModel {
-            number_text: format!("#{}", state.pr_context.number),
-            title: state.pr_context.title.clone(),
-            author_text: format!("by {}", state.pr_context.author),
+            number_text,
+            title,
+            author_text,
             number_color: theme.status_info,
             title_color: theme.text_primary,
             author_color: theme.text_muted,
@@ -99,7 +129,7 @@ impl BuildLogViewModel {
         let mut rows = Vec::new();
 
         for path in visible_paths.iter() {
-            let row = Self::build_row_view_model(state, path);
+            let row = Self::build_row_view_model(state, path, theme.text_labels);
             rows.push(row);
         }
 
@@ -110,10 +140,15 @@ impl BuildLogViewModel {
             viewport_height: state.viewport_height,
             is_loading,
             error_message,
+            progress,
         }
     }
 
-    fn build_row_view_model(state: &BuildLogState, path: &[usize]) -> BuildLogTreeRowViewModel {
+    fn build_row_view_model(
+        state: &BuildLogState,
+        path: &[usize],
+        text_labels: bool,
+    ) -> BuildLogTreeRowViewModel {
         let indent_level = path.len().saturating_sub(1);
 
         // Tree structure alignment:
@@ -143,7 +178,10 @@ impl BuildLogViewModel {
                 };
 
                 let error_info = if workflow.total_errors > 0 {
-                    format!(" ({} errors)", workflow.total_errors)
+                    format!(
+                        " ({})",
+                        format_count(workflow.total_errors, "error", "errors")
+                    )
                 } else {
                     String::new()
                 };
@@ -164,6 +202,31 @@ impl BuildLogViewModel {
                 }
             }
 
+            2 if path[1] == SUCCEEDED_GROUP => {
+                // Folded successful jobs (level 2) - same branch as the jobs
+                let workflow = &state.workflows[path[0]];
+                let (_, succeeded) = state.grouped_jobs(workflow);
+                let icon = if state.is_expanded(path) {
+                    "▼"
+                } else {
+                    "▶"
+                };
+
+                BuildLogTreeRowViewModel {
+                    text: format!(
+                        "├─ {} {} {} succeeded",
+                        icon,
+                        BuildLogJobStatus::Success.icon(),
+                        succeeded.len()
+                    ),
+                    indent_level,
+                    is_cursor: path == state.cursor_path,
+                    style: BuildLogRowStyle::Success,
+                    path: path.to_vec(),
+                    node_type: BuildLogNodeType::SucceededGroup,
+                }
+            }
+
             2 => {
                 // Job node (level 2) - tree branch from workflow
                 let workflow = &state.workflows[path[0]];
@@ -179,23 +242,12 @@ impl BuildLogViewModel {
                 };
 
                 // Get actual job status from metadata (or infer from error count)
-                let key = format!("{}:{}", workflow.name, job.name);
-                let status = state
-                    .job_metadata
-                    .get(&key)
-                    .map(|m| m.status)
-                    .unwrap_or_else(|| {
-                        if job.error_count > 0 {
-                            BuildLogJobStatus::Failure
-                        } else {
-                            BuildLogJobStatus::Success
-                        }
-                    });
+                let status = state.job_status(workflow, job);
 
                 let status_icon = status.icon();
 
                 let error_info = if job.error_count > 0 {
-                    format!(" ({} errors)", job.error_count)
+                    format!(" ({})", format_count(job.error_count, "error", "errors"))
                 } else {
                     String::new()
                 };
@@ -240,7 +292,7 @@ impl BuildLogViewModel {
                 };
 
                 let error_info = if step.error_count > 0 {
-                    format!(" ({} errors)", step.error_count)
+                    format!(" ({})", format_count(step.error_count, "error", "errors"))
                 } else {
                     String::new()
                 };
@@ -291,17 +343,17 @@ impl BuildLogViewModel {
                     String::new()
                 };
 
-                // Apply horizontal scroll to content
-                let content = if state.horizontal_scroll > 0 {
-                    line.display_content
-                        .chars()
-                        .skip(state.horizontal_scroll)
-                        .collect::<String>()
+                // Apply horizontal scroll to content, by cells and whole graphemes
+                let content = gh_text_width::skip(&line.display_content, state.horizontal_scroll);
+
+                // Error lines get a label in themes that don't rely on color alone
+                let severity = if is_error && text_labels {
+                    ERROR_LABEL
                 } else {
-                    line.display_content.clone()
+                    ""
                 };
 
-                let text = format!("{}{}{}", prefix, timestamp_part, content);
+                let text = format!("{}{}{}{}", prefix, timestamp_part, severity, content);
 
                 let style = if is_error {
                     BuildLogRowStyle::Error
@@ -340,12 +392,9 @@ impl BuildLogViewModel {
 
         if let Some(meta) = metadata.get(&key) {
             if let Some(duration) = meta.duration {
-                let secs = duration.as_secs();
-                return if secs >= 60 {
-                    format!(" ({}m {}s)", secs / 60, secs % 60)
-                } else {
-                    format!(" ({}s)", secs)
-                };
+                let duration =
+                    chrono::TimeDelta::from_std(duration).unwrap_or(chrono::TimeDelta::MAX);
+                return format!(" ({})", format_duration(duration));
             }
         }
 
@@ -375,3 +424,212 @@ impl BuildLogViewModel {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::BuildLogAction;
+    use crate::demo::fixtures::workflow_logs_zip;
+    use crate::reducers::build_log_reducer::reduce_build_log;
+    use crate::state::BuildLogPrContext;
+    use gh_actions_log_parser::{job_log_to_tree, parse_workflow_logs, WorkflowNode};
+    use std::sync::Arc;
+
+    fn loaded_state() -> BuildLogState {
+        log_state("##[group]Run tests\n##[error]boom\n##[endgroup]\n")
+    }
+
+    /// Build log of one job running `log`
+    fn log_state(log: &str) -> BuildLogState {
+        let zip = workflow_logs_zip(&[("test".to_string(), log.to_string())]);
+        let job = job_log_to_tree(parse_workflow_logs(&zip).unwrap().jobs.remove(0));
+        let workflow = WorkflowNode {
+            name: "CI".to_string(),
+            has_failures: true,
+            total_errors: job.error_count,
+            jobs: vec![job],
+        };
+        reduce_build_log(
+            BuildLogState::default(),
+            &BuildLogAction::Loaded {
+                workflows: vec![Arc::new(workflow)],
+                job_metadata: Vec::new(),
+                context: BuildLogContext::Pr(BuildLogPrContext::default()),
+                parsed_runs: Vec::new(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_high_contrast_labels_error_lines() {
+        let state = loaded_state();
+
+        let vm = BuildLogViewModel::from_state(&state, &gh_pr_lander_theme::Theme::high_contrast());
+        let line = vm.rows.last().unwrap();
+        assert_eq!(line.node_type, BuildLogNodeType::LogLine);
+        assert!(line.text.ends_with("[ERROR] boom"), "{}", line.text);
+
+        let vm = BuildLogViewModel::from_state(&state, &gh_pr_lander_theme::Theme::default());
+        assert!(vm.rows.last().unwrap().text.ends_with("│  │  boom"));
+    }
+
+    #[test]
+    fn test_horizontal_scroll_keeps_wide_characters_whole() {
+        let mut state =
+            log_state("##[group]Run tests\n##[error]漢字 build 🚀 done\n##[endgroup]\n");
+        let theme = gh_pr_lander_theme::Theme::default();
+
+        state.horizontal_scroll = 2;
+        let vm = BuildLogViewModel::from_state(&state, &theme);
+        let text = &vm.rows.last().unwrap().text;
+        assert!(text.ends_with("│  │  字 build 🚀 done"), "{}", text);
+
+        // Half of 漢 scrolled out of view leaves a blank cell
+        state.horizontal_scroll = 1;
+        let vm = BuildLogViewModel::from_state(&state, &theme);
+        let text = &vm.rows.last().unwrap().text;
+        assert!(text.ends_with("│  │   字 build 🚀 done"), "{}", text);
+    }
+
+    /// Build log of one workflow with a job per status, named after its index
+    fn jobs_state(statuses: &[BuildLogJobStatus]) -> BuildLogState {
+        let jobs = statuses
+            .iter()
+            .enumerate()
+            .map(|(idx, _)| gh_actions_log_parser::JobNode {
+                name: format!("job{}", idx),
+                steps: Vec::new(),
+                error_count: 0,
+            })
+            .collect();
+        let job_metadata = statuses
+            .iter()
+            .enumerate()
+            .map(|(idx, status)| BuildLogJobMetadata {
+                name: format!("job{}", idx),
+                workflow_name: "CI".to_string(),
+                status: *status,
+                error_count: 0,
+                duration: None,
+                html_url: String::new(),
+            })
+            .collect();
+        let workflow = WorkflowNode {
+            name: "CI".to_string(),
+            has_failures: statuses.iter().any(|status| status.is_failed()),
+            total_errors: 0,
+            jobs,
+        };
+        reduce_build_log(
+            BuildLogState::default(),
+            &BuildLogAction::Loaded {
+                workflows: vec![Arc::new(workflow)],
+                job_metadata,
+                context: BuildLogContext::Pr(BuildLogPrContext::default()),
+                parsed_runs: Vec::new(),
+            },
+        )
+    }
+
+    /// Job names and the succeeded node's label, below the workflow row
+    fn job_rows(state: &BuildLogState) -> Vec<String> {
+        let vm = BuildLogViewModel::from_state(state, &gh_pr_lander_theme::Theme::default());
+        vm.rows
+            .iter()
+            .skip(1)
+            .map(|row| match row.node_type {
+                BuildLogNodeType::Job => state.workflows[0].jobs[row.path[1]].name.clone(),
+                _ => row.text.rsplit(" ✅ ").next().unwrap().to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_successful_jobs_are_folded_below_failed_and_running_ones() {
+        use BuildLogJobStatus::*;
+        let mut state = jobs_state(&[Success, InProgress, Skipped, Failure]);
+
+        assert_eq!(job_rows(&state), vec!["job3", "job1", "2 succeeded"]);
+
+        // Expanding the group lists the successful jobs below it
+        state.toggle_expanded(&[0, SUCCEEDED_GROUP]);
+        assert_eq!(
+            job_rows(&state),
+            vec!["job3", "job1", "2 succeeded", "job0", "job2"]
+        );
+
+        // Listing successful jobs restores the original order
+        state.toggle_expanded(&[0, SUCCEEDED_GROUP]);
+        state.cursor_path = vec![0, SUCCEEDED_GROUP];
+        let state = reduce_build_log(state, &BuildLogAction::ToggleShowSuccessful);
+        assert_eq!(job_rows(&state), vec!["job0", "job1", "job2", "job3"]);
+        assert_eq!(state.cursor_path, vec![0]);
+    }
+
+    #[test]
+    fn test_all_green_and_all_red_runs_are_not_folded() {
+        use BuildLogJobStatus::*;
+        let state = jobs_state(&[Success, Success, Skipped]);
+        assert_eq!(job_rows(&state), vec!["job0", "job1", "job2"]);
+
+        let state = jobs_state(&[Failure, Cancelled, Failure]);
+        assert_eq!(job_rows(&state), vec!["job0", "job2", "job1"]);
+        assert!(
+            BuildLogViewModel::from_state(&state, &gh_pr_lander_theme::Theme::default())
+                .rows
+                .iter()
+                .all(|row| row.node_type != BuildLogNodeType::SucceededGroup)
+        );
+    }
+
+    #[test]
+    fn test_configured_to_list_successful_jobs() {
+        use BuildLogJobStatus::*;
+        let mut state = jobs_state(&[Success, Failure]);
+        state.collapse_successful = false;
+        assert_eq!(job_rows(&state), vec!["job0", "job1"]);
+
+        // A choice made this session wins over the configuration
+        let state = reduce_build_log(state, &BuildLogAction::SetShowSuccessful(false));
+        assert_eq!(job_rows(&state), vec!["job1", "1 succeeded"]);
+    }
+
+    #[test]
+    fn test_workflows_fill_in_failed_first_with_progress() {
+        let theme = gh_pr_lander_theme::Theme::default();
+        let workflow = |name: &str, has_failures: bool| BuildLogAction::WorkflowLoaded {
+            run: Arc::new(WorkflowNode {
+                name: name.to_string(),
+                jobs: Vec::new(),
+                has_failures,
+                total_errors: 0,
+            }),
+            jobs: Vec::new(),
+            parsed_run: None,
+        };
+
+        let mut state = reduce_build_log(BuildLogState::default(), &BuildLogAction::LoadStart);
+        state = reduce_build_log(
+            state,
+            &BuildLogAction::RunsFound {
+                context: BuildLogContext::Pr(BuildLogPrContext::default()),
+                total: 3,
+            },
+        );
+        let vm = BuildLogViewModel::from_state(&state, &theme);
+        assert!(vm.is_loading);
+        assert_eq!(vm.progress.as_deref(), Some("0 of 3 workflows loaded"));
+
+        state = reduce_build_log(state, &workflow("Lint", false));
+        state = reduce_build_log(state, &workflow("Test ⚠ failed to download logs", true));
+        let vm = BuildLogViewModel::from_state(&state, &theme);
+        assert!(!vm.is_loading);
+        assert_eq!(vm.progress.as_deref(), Some("2 of 3 workflows loaded"));
+        let names: Vec<&str> = state.workflows.iter().map(|w| w.name.as_str()).collect();
+        assert_eq!(names, vec!["Test ⚠ failed to download logs", "Lint"]);
+
+        state = reduce_build_log(state, &workflow("Docs", false));
+        assert!(matches!(state.loading_state, BuildLogLoadingState::Loaded));
+        assert_eq!(BuildLogViewModel::from_state(&state, &theme).progress, None);
+    }
+}
@@ -0,0 +1,190 @@
+//! View model for the cache inspector popup
+//!
+//! Pre-computes one row per cached GitHub API response (key, category, size
+//! and age) in the order of the state's sort, plus the summary and the line
+//! of the filter or pattern being typed.
+
+use crate::state::{CacheInput, CacheInspectorState};
+use crate::utils::format::{format_count, format_span};
+use gh_client::CacheEntryInfo;
+
+/// View model for the cache inspector popup
+#[derive(Debug, Clone)]
+pub struct CacheInspectorViewModel {
+    /// Panel title with the entry count and total size
+    pub title: String,
+    /// Sort, filter and position line shown above the table
+    pub summary: String,
+    /// Visible entries in display order
+    pub rows: Vec<CacheInspectorRow>,
+    /// Filter or pattern being typed (None while navigating)
+    pub input: Option<String>,
+    /// Message shown instead of the table (empty cache or no match)
+    pub message: Option<String>,
+}
+
+/// A single cached response
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheInspectorRow {
+    pub key: String,
+    pub category: String,
+    pub size: String,
+    pub age: String,
+    /// Still within the cache TTL
+    pub is_fresh: bool,
+    pub is_selected: bool,
+}
+
+impl CacheInspectorRow {
+    fn from_entry(entry: &CacheEntryInfo, is_selected: bool) -> Self {
+        Self {
+            key: entry.key.clone(),
+            category: entry.category.clone(),
+            size: format_size(entry.size),
+            age: format_age(entry.age_seconds),
+            is_fresh: entry.fresh,
+            is_selected,
+        }
+    }
+}
+
+impl CacheInspectorViewModel {
+    /// Build view model from the cache inspector state
+    pub fn from_state(state: &CacheInspectorState) -> Self {
+        let visible = state.visible();
+        let rows: Vec<CacheInspectorRow> = visible
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| CacheInspectorRow::from_entry(entry, i == state.selected))
+            .collect();
+
+        let total: usize = state.entries.iter().map(|entry| entry.size).sum();
+        let title = format!(
+            "Cache inspector ({}, {})",
+            format_count(state.entries.len(), "entry", "entries"),
+            format_size(total)
+        );
+
+        let mut summary = format!("Sorted by {}", state.sort.label());
+        if !state.filter.is_empty() {
+            summary.push_str(&format!(" • filter \"{}\"", state.filter));
+        }
+        if !rows.is_empty() {
+            summary.push_str(&format!(
+                " • {} of {}",
+                state.selected + 1,
+                format_count(rows.len(), "entry", "entries")
+            ));
+        }
+
+        let input = match state.input {
+            Some(CacheInput::Filter) => Some(format!("Filter: {}", state.filter)),
+            Some(CacheInput::Pattern(ref pattern)) => {
+                Some(format!("Invalidate keys containing: {}", pattern))
+            }
+            None => None,
+        };
+
+        let message = if state.entries.is_empty() {
+            Some("Nothing cached".to_string())
+        } else if rows.is_empty() {
+            Some("No cached response matches the filter".to_string())
+        } else {
+            None
+        };
+
+        Self {
+            title,
+            summary,
+            rows,
+            input,
+            message,
+        }
+    }
+}
+
+/// Format a byte count as "512 B", "3.2 KB" or "1.4 MB"
+fn format_size(bytes: usize) -> String {
+    match bytes {
+        0..=1023 => format!("{} B", bytes),
+        1024..=1_048_575 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0)),
+    }
+}
+
+/// Format an age as "45s" below a minute, else its largest whole unit
+fn format_age(secs: u64) -> String {
+    if secs < 60 {
+        format!("{}s", secs)
+    } else {
+        format_span(secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, size: usize, age_seconds: u64) -> CacheEntryInfo {
+        CacheEntryInfo {
+            key: key.to_string(),
+            category: "pulls".to_string(),
+            size,
+            age_seconds,
+            fresh: age_seconds < 1200,
+        }
+    }
+
+    #[test]
+    fn test_rows_and_title() {
+        let mut state = CacheInspectorState::default();
+        state.set_entries(vec![
+            entry("GET:/repos/acme/api/pulls", 3 * 1024 * 1024 / 2, 30),
+            entry("GET:/repos/acme/web/pulls", 512, 7200),
+        ]);
+        state.select_next();
+
+        let vm = CacheInspectorViewModel::from_state(&state);
+        assert_eq!(vm.title, "Cache inspector (2 entries, 1.5 MB)");
+        assert_eq!(vm.summary, "Sorted by size • 2 of 2 entries");
+        assert_eq!(vm.rows[0].size, "1.5 MB");
+        assert_eq!(vm.rows[0].age, "30s");
+        assert!(vm.rows[0].is_fresh);
+        assert_eq!(vm.rows[1].size, "512 B");
+        assert_eq!(vm.rows[1].age, "2h");
+        assert!(!vm.rows[1].is_fresh);
+        assert!(vm.rows[1].is_selected);
+        assert!(vm.input.is_none());
+        assert!(vm.message.is_none());
+    }
+
+    #[test]
+    fn test_filter_and_pattern_lines() {
+        let mut state = CacheInspectorState::default();
+        let vm = CacheInspectorViewModel::from_state(&state);
+        assert_eq!(vm.message.as_deref(), Some("Nothing cached"));
+
+        state.set_entries(vec![entry("GET:/repos/acme/api/pulls", 2048, 90)]);
+        state.input = Some(CacheInput::Filter);
+        for c in "web".chars() {
+            state.push_input(c);
+        }
+        let vm = CacheInspectorViewModel::from_state(&state);
+        assert_eq!(vm.input.as_deref(), Some("Filter: web"));
+        assert_eq!(vm.summary, "Sorted by size • filter \"web\"");
+        assert_eq!(
+            vm.message.as_deref(),
+            Some("No cached response matches the filter")
+        );
+
+        state.cancel_input();
+        state.input = Some(CacheInput::Pattern("/acme/".to_string()));
+        let vm = CacheInspectorViewModel::from_state(&state);
+        assert_eq!(
+            vm.input.as_deref(),
+            Some("Invalidate keys containing: /acme/")
+        );
+        assert_eq!(vm.rows[0].size, "2.0 KB");
+        assert_eq!(vm.rows[0].age, "1m");
+    }
+}
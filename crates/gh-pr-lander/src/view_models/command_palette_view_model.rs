@@ -4,7 +4,10 @@
 //! data preparation from rendering logic.
 
 use crate::command_id::CommandId;
-use crate::commands::{filter_commands, get_issue_commands, get_palette_commands_with_hints};
+use crate::commands::{
+    filter_commands, get_issue_commands, get_palette_commands_with_hints, get_profile_commands,
+    get_saved_filter_commands,
+};
 use crate::state::AppState;
 use crate::utils::issue_extractor::RepoContext;
 use ratatui::style::Color;
@@ -74,6 +77,8 @@ impl CommandPaletteViewModel {
 
         // Get static commands
         let mut all_commands = get_palette_commands_with_hints(&state.keymap);
+        all_commands.extend(get_saved_filter_commands(&state.main_view.saved_filters));
+        all_commands.extend(get_profile_commands(state.main_view.profile.as_ref()));
 
         // Add dynamic issue commands based on selected PRs and repo context
         let pr_texts = Self::get_selected_pr_texts(state);
@@ -158,8 +163,16 @@ impl CommandPaletteViewModel {
         // Get selected command details
         let selected_command = filtered_commands
             .get(state.command_palette.selected_index)
-            .map(|cmd| SelectedCommandDetails {
-                description: cmd.description().to_string(),
+            .map(|cmd| {
+                let description = cmd.description().to_string();
+                // Repository commands also tell how busy the selected repository is
+                let description = match Self::selected_repo_pulse(state) {
+                    Some(pulse) if cmd.category() == "Repository" => {
+                        format!("{} · {}", description, pulse)
+                    }
+                    _ => description,
+                };
+                SelectedCommandDetails { description }
             });
 
         // Build footer hints from keymap
@@ -190,6 +203,17 @@ impl CommandPaletteViewModel {
         }
     }
 
+    /// Weekly activity of the selected repository, once fetched
+    fn selected_repo_pulse(state: &AppState) -> Option<String> {
+        let main_view = &state.main_view;
+        let repo = main_view.repositories.get(main_view.selected_repository)?;
+        let pulse = main_view
+            .repo_data
+            .get(&main_view.selected_repository)?
+            .pulse?;
+        Some(format!("{}: {}", repo.display_name(), pulse.describe()))
+    }
+
     /// Get PR texts from currently selected/active PRs for issue extraction
     fn get_selected_pr_texts(state: &AppState) -> Vec<String> {
         let repo_idx = state.main_view.selected_repository;
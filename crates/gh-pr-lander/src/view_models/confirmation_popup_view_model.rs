@@ -3,7 +3,9 @@
 //! Pre-computes all display data for the confirmation popup view,
 //! separating data preparation from rendering logic.
 
+use crate::domain_models::Pr;
 use crate::state::ConfirmationPopupState;
+use crate::utils::template::PR_PLACEHOLDERS;
 use ratatui::style::Color;
 
 /// View model for the confirmation popup
@@ -13,12 +15,21 @@ pub struct ConfirmationPopupViewModel {
     pub title: String,
     /// Target info line (e.g., "Approving: PR #123" or "Approving: 3 PRs")
     pub target_line: String,
+    /// What exactly is affected (e.g., "#123  serde 1.0.1 → 1.0.2 (patch)"),
+    /// for comments the placeholders and the message as posted on the first PR
+    pub details: Vec<String>,
+    /// Warnings to double check (e.g., "#123: CI is failing")
+    pub warnings: Vec<String>,
     /// Instructions text (e.g., "Enter your approval message:")
     pub instructions: String,
     /// Input label (e.g., "Message:")
     pub input_label: String,
     /// Current input value
     pub input_value: String,
+    /// Whether the message input is shown
+    pub show_input: bool,
+    /// Option checkbox shown instead of the input (e.g., "[x] Delete branch after merge")
+    pub option: Option<String>,
     /// Whether input is empty (for placeholder styling)
     #[allow(dead_code)]
     pub input_is_empty: bool,
@@ -56,16 +67,47 @@ pub struct ConfirmationPopupColors {
     #[allow(dead_code)]
     pub hint_fg: Color,
     pub error_fg: Color,
+    pub warning_fg: Color,
 }
 
 impl ConfirmationPopupViewModel {
     /// Build view model from confirmation popup state
-    pub fn from_state(state: &ConfirmationPopupState, theme: &gh_pr_lander_theme::Theme) -> Self {
+    ///
+    /// `first_pr` is the first target PR, if loaded, for the message preview.
+    pub fn from_state(
+        state: &ConfirmationPopupState,
+        first_pr: Option<&Pr>,
+        theme: &gh_pr_lander_theme::Theme,
+    ) -> Self {
         let title = state.title().to_string();
         let target_line = format!("{}: {}", state.action_verb(), state.target_info());
+        let mut details: Vec<String> = state
+            .intent
+            .details()
+            .iter()
+            .map(|line| format!("  {}", line))
+            .collect();
+        if state.intent.is_templated() {
+            let placeholders: Vec<String> = PR_PLACEHOLDERS
+                .iter()
+                .map(|name| format!("{{{}}}", name))
+                .collect();
+            details.push(format!("  Placeholders: {}", placeholders.join(" ")));
+            if let Some(preview) = first_pr.and_then(|pr| state.preview(pr)) {
+                details.push(format!("  Preview {}", preview));
+            }
+        }
+        let warnings = state.warnings.iter().map(|w| format!("⚠ {}", w)).collect();
         let instructions = state.instructions().to_string();
         let input_label = "Message:".to_string();
         let input_value = state.input_value.clone();
+        let show_input = state.intent.takes_message();
+        let option = state.intent.delete_branch().map(|delete| {
+            format!(
+                "[{}] Delete branch after merge (Space toggles)",
+                if delete { "x" } else { " " }
+            )
+        });
         let input_is_empty = input_value.is_empty();
         let is_valid = state.is_valid();
 
@@ -90,14 +132,19 @@ impl ConfirmationPopupViewModel {
             border_fg: theme.accent_primary,
             hint_fg: theme.text_muted,
             error_fg: theme.status_error,
+            warning_fg: theme.status_warning,
         };
 
         Self {
             title,
             target_line,
+            details,
+            warnings,
             instructions,
             input_label,
             input_value,
+            show_input,
+            option,
             input_is_empty,
             is_valid,
             validation_hint,
@@ -0,0 +1,206 @@
+//! View model for the conflict helper popup
+//!
+//! Pre-computes one row per likely conflicting file plus the lines telling
+//! how the list was derived, so the popup never looks like git's own
+//! conflict report.
+
+use crate::domain_models::{author, ConflictCandidate, ConflictKind};
+use crate::state::ConflictHelperState;
+
+/// View model for the conflict helper popup
+#[derive(Debug, Clone)]
+pub struct ConflictHelperViewModel {
+    /// Panel title
+    pub title: String,
+    /// PR number and branches
+    pub header: String,
+    /// How many files overlap, shown below the header
+    pub summary: String,
+    /// Reminder that the list is a guess, shown below the rows
+    pub note: String,
+    /// Candidate rows, likeliest conflict first
+    pub rows: Vec<ConflictRow>,
+    /// Message shown instead of the table (loading, error or no overlap)
+    pub message: Option<String>,
+    /// Message is an error
+    pub is_error: bool,
+    /// The PR is dependabot's, which can recreate it
+    pub is_dependabot: bool,
+}
+
+/// A single candidate file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConflictRow {
+    pub path: String,
+    pub kind: String,
+    /// Lines changed by the PR
+    pub pr_churn: String,
+    /// Lines changed on the base branch
+    pub base_churn: String,
+    /// Conflicts for sure (modified/deleted)
+    pub is_certain: bool,
+}
+
+impl ConflictRow {
+    fn from_candidate(candidate: &ConflictCandidate) -> Self {
+        let kind = if candidate.is_lockfile() {
+            format!("{} (regenerate)", candidate.kind.label())
+        } else {
+            candidate.kind.label().to_string()
+        };
+        Self {
+            path: candidate.path.clone(),
+            kind,
+            pr_churn: format!("±{}", candidate.pr_churn),
+            base_churn: format!("±{}", candidate.base_churn),
+            is_certain: candidate.kind == ConflictKind::ModifiedDeleted,
+        }
+    }
+}
+
+impl ConflictHelperViewModel {
+    /// Build view model from the conflict helper state
+    pub fn from_state(state: &ConflictHelperState) -> Self {
+        let mut vm = Self {
+            title: "Likely conflicts (approximation)".to_string(),
+            header: String::new(),
+            summary: String::new(),
+            note: "Files changed on both sides since the merge base. \
+                   Only git knows the actual conflicts."
+                .to_string(),
+            rows: Vec::new(),
+            message: None,
+            is_error: false,
+            is_dependabot: false,
+        };
+
+        let Some((_, ref pr)) = state.target else {
+            vm.message = Some("No pull request".to_string());
+            return vm;
+        };
+        vm.header = format!(
+            "#{} {} ← {}: {}",
+            pr.number, pr.base_branch, pr.head_branch, pr.title
+        );
+        vm.is_dependabot = author::is_dependabot(&pr.author);
+
+        if let Some(ref error) = state.error {
+            vm.message = Some(format!("Failed to compare the branches: {}", error));
+            vm.is_error = true;
+            return vm;
+        }
+        let Some(ref summary) = state.summary else {
+            vm.message = Some("Comparing branches...".to_string());
+            return vm;
+        };
+
+        vm.summary = format!(
+            "{} of {} changed files also changed on {} ({} commits behind)",
+            summary.candidates.len(),
+            summary.pr_files,
+            pr.base_branch,
+            summary.behind_by
+        );
+        if summary.truncated {
+            vm.summary
+                .push_str(" • GitHub lists 300 files per side, some may be missing");
+        }
+        vm.rows = summary
+            .candidates
+            .iter()
+            .map(ConflictRow::from_candidate)
+            .collect();
+        if vm.rows.is_empty() {
+            vm.message =
+                Some("No file changed on both sides, the conflict may be in a rename".to_string());
+        }
+        vm
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::{ConflictSummary, Pr, Repository};
+
+    fn state(author: &str, summary: Option<ConflictSummary>) -> ConflictHelperState {
+        let pr = Pr::new(7, "Bump serde", author, "abc").with_branches("deps/serde", "main");
+        ConflictHelperState {
+            target: Some((Repository::new("acme", "api", "main"), pr)),
+            summary,
+            error: None,
+        }
+    }
+
+    fn candidate(path: &str, kind: ConflictKind) -> ConflictCandidate {
+        ConflictCandidate {
+            path: path.to_string(),
+            kind,
+            pr_churn: 4,
+            base_churn: 10,
+        }
+    }
+
+    #[test]
+    fn test_rows_and_summary() {
+        let summary = ConflictSummary {
+            candidates: vec![
+                candidate("src/gone.rs", ConflictKind::ModifiedDeleted),
+                candidate("Cargo.lock", ConflictKind::BothModified),
+            ],
+            pr_files: 5,
+            base_files: 20,
+            behind_by: 3,
+            truncated: false,
+        };
+        let vm = ConflictHelperViewModel::from_state(&state("alice", Some(summary)));
+
+        assert!(vm.title.contains("approximation"));
+        assert_eq!(vm.header, "#7 main ← deps/serde: Bump serde");
+        assert_eq!(
+            vm.summary,
+            "2 of 5 changed files also changed on main (3 commits behind)"
+        );
+        assert!(vm.message.is_none());
+        assert!(!vm.is_dependabot);
+        assert_eq!(
+            vm.rows[0],
+            ConflictRow {
+                path: "src/gone.rs".to_string(),
+                kind: "modified/deleted".to_string(),
+                pr_churn: "±4".to_string(),
+                base_churn: "±10".to_string(),
+                is_certain: true,
+            }
+        );
+        assert_eq!(vm.rows[1].kind, "changed on both sides (regenerate)");
+        assert!(!vm.rows[1].is_certain);
+    }
+
+    #[test]
+    fn test_messages() {
+        let vm = ConflictHelperViewModel::from_state(&state("dependabot[bot]", None));
+        assert_eq!(vm.message.as_deref(), Some("Comparing branches..."));
+        assert!(vm.is_dependabot);
+
+        let summary = ConflictSummary {
+            truncated: true,
+            ..Default::default()
+        };
+        let vm = ConflictHelperViewModel::from_state(&state("alice", Some(summary)));
+        assert!(vm
+            .message
+            .unwrap()
+            .starts_with("No file changed on both sides"));
+        assert!(vm.summary.ends_with("some may be missing"));
+
+        let mut failed = state("alice", None);
+        failed.error = Some("Not Found".to_string());
+        let vm = ConflictHelperViewModel::from_state(&failed);
+        assert!(vm.is_error);
+        assert_eq!(
+            vm.message.as_deref(),
+            Some("Failed to compare the branches: Not Found")
+        );
+    }
+}
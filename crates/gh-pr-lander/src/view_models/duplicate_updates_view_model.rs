@@ -0,0 +1,120 @@
+//! View model for the duplicate updates popup
+//!
+//! Pre-computes one row per PR opening the same bot update (repository, PR,
+//! title and CI state), looked up live so CI changes show while it is open.
+
+use crate::state::{DuplicateUpdatesState, MainViewState};
+use crate::utils::format::format_count;
+
+/// View model for the duplicate updates popup
+#[derive(Debug, Clone)]
+pub struct DuplicateUpdatesViewModel {
+    /// Popup title naming the update
+    pub title: String,
+    /// Picked count line shown above the table
+    pub summary: String,
+    /// One row per PR opening the update
+    pub rows: Vec<DuplicateUpdateRow>,
+}
+
+/// A single PR opening the update
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateUpdateRow {
+    /// "[x]" when picked for merging, "[ ]" otherwise
+    pub checkbox: &'static str,
+    pub repo: String,
+    pub pr: String,
+    pub title: String,
+    /// CI state ("✅ Ready"), empty once the PR left the loaded list
+    pub ci: String,
+    pub is_selected: bool,
+}
+
+impl DuplicateUpdatesViewModel {
+    /// Build view model from the popup state and the loaded PRs
+    pub fn from_state(popup: &DuplicateUpdatesState, main_view: &MainViewState) -> Self {
+        let rows = popup
+            .siblings
+            .iter()
+            .enumerate()
+            .map(|(idx, (repo, number))| {
+                let pr = main_view
+                    .repo_data_of(repo)
+                    .and_then(|data| data.prs.iter().find(|pr| pr.number == *number));
+                DuplicateUpdateRow {
+                    checkbox: if popup.picked.contains(&idx) {
+                        "[x]"
+                    } else {
+                        "[ ]"
+                    },
+                    repo: repo.display_name(),
+                    pr: format!("#{}", number),
+                    title: pr.map(|pr| pr.title.clone()).unwrap_or_default(),
+                    ci: pr
+                        .map(|pr| format!("{} {}", pr.mergeable.icon(), pr.mergeable.label()))
+                        .unwrap_or_default(),
+                    is_selected: idx == popup.selected,
+                }
+            })
+            .collect();
+
+        let repos = popup.picked_by_repo().len();
+        let summary = format!(
+            "{} in {} picked for merging",
+            format_count(popup.picked.len(), "PR", "PRs"),
+            format_count(repos, "repository", "repositories")
+        );
+
+        Self {
+            title: format!("Update {}", popup.summary),
+            summary,
+            rows,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::{MergeableStatus, Pr, Repository};
+    use crate::state::RepositoryData;
+
+    #[test]
+    fn test_rows_show_ci_of_loaded_prs() {
+        let api = Repository::new("acme", "api", "main");
+        let web = Repository::new("acme", "web", "main");
+        let mut main_view = MainViewState {
+            repositories: vec![api.clone(), web.clone()],
+            ..Default::default()
+        };
+        let mut pr = Pr::new(
+            1,
+            "Bump serde from 1.0.1 to 1.0.2",
+            "dependabot[bot]",
+            "sha",
+        );
+        pr.mergeable = MergeableStatus::BuildFailed;
+        main_view.repo_data.insert(
+            0,
+            RepositoryData {
+                prs: vec![pr],
+                ..Default::default()
+            },
+        );
+
+        let mut popup =
+            DuplicateUpdatesState::new("serde → 1.0.2".to_string(), vec![(api, 1), (web, 7)]);
+        popup.select_next();
+        popup.toggle();
+        let vm = DuplicateUpdatesViewModel::from_state(&popup, &main_view);
+        assert_eq!(vm.title, "Update serde → 1.0.2");
+        assert_eq!(vm.summary, "1 PR in 1 repository picked for merging");
+        assert_eq!(vm.rows[0].checkbox, "[x]");
+        assert_eq!(vm.rows[0].ci, "🚨 Build Failed");
+        // Not loaded (anymore): no title nor CI
+        assert_eq!(vm.rows[1].checkbox, "[ ]");
+        assert_eq!(vm.rows[1].pr, "#7");
+        assert_eq!(vm.rows[1].ci, "");
+        assert!(vm.rows[1].is_selected);
+    }
+}
@@ -0,0 +1,126 @@
+//! View model for the filter builder
+//!
+//! Pre-computes the criteria rows and the live count of matching PRs,
+//! separating data preparation from rendering logic.
+
+use crate::domain_models::filter_spec;
+use crate::state::{FilterBuilderState, FilterRow, RepositoryData};
+use chrono::{DateTime, Utc};
+use gh_pr_config::PrSizeThresholds;
+
+/// View model for the filter builder popup
+#[derive(Debug, Clone)]
+pub struct FilterBuilderViewModel {
+    /// Criteria rows followed by the "Save as" row
+    pub rows: Vec<FilterBuilderRow>,
+    /// How many loaded PRs match (e.g. "3 of 12 PRs match")
+    pub count: String,
+    /// The filter as inline query (e.g. "ci:failed label:bug")
+    pub query: String,
+}
+
+/// A single row of the filter builder
+#[derive(Debug, Clone)]
+pub struct FilterBuilderRow {
+    /// Is this row under the cursor?
+    pub is_selected: bool,
+    /// Checkbox of criteria ("[x]" when set), empty for "Save as"
+    pub checkbox: &'static str,
+    /// Row title
+    pub label: &'static str,
+    /// Value, "any" for unset choice rows
+    pub value: String,
+    /// Row takes typed text (shows a cursor when selected)
+    pub is_text: bool,
+}
+
+impl FilterBuilderViewModel {
+    /// Build view model from the builder and the current repository's PRs
+    /// (`sizes` bound the size buckets)
+    pub fn from_state(
+        state: &FilterBuilderState,
+        repo_data: Option<&RepositoryData>,
+        now: DateTime<Utc>,
+        sizes: &PrSizeThresholds,
+    ) -> Self {
+        let rows = FilterRow::ALL
+            .iter()
+            .enumerate()
+            .map(|(idx, &row)| {
+                let value = state.value(row);
+                let is_text = row.choices().is_none();
+                FilterBuilderRow {
+                    is_selected: idx == state.selected,
+                    checkbox: match (row, value.is_empty()) {
+                        (FilterRow::SaveAs, _) => "",
+                        (_, true) => "[ ]",
+                        (_, false) => "[x]",
+                    },
+                    label: row.label(),
+                    value: if value.is_empty() && !is_text {
+                        "any".to_string()
+                    } else {
+                        value.to_string()
+                    },
+                    is_text,
+                }
+            })
+            .collect();
+
+        let spec = state.spec();
+        let prs = repo_data
+            .map(|data| data.prs.as_slice())
+            .unwrap_or_default();
+        let matching = prs
+            .iter()
+            .filter(|pr| filter_spec::matches(&spec, pr, now, sizes))
+            .count();
+
+        Self {
+            rows,
+            count: format!("{} of {} PRs match", matching, prs.len()),
+            query: filter_spec::to_query(&spec),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::{MergeableStatus, Pr};
+
+    #[test]
+    fn test_live_count_follows_criteria() {
+        let mut data = RepositoryData {
+            prs: (1..=3).map(|n| Pr::new(n, "PR", "alice", "sha")).collect(),
+            ..Default::default()
+        };
+        data.prs[1].mergeable = MergeableStatus::BuildFailed;
+
+        let mut state = FilterBuilderState::default();
+        let vm = FilterBuilderViewModel::from_state(
+            &state,
+            Some(&data),
+            Utc::now(),
+            &PrSizeThresholds::default(),
+        );
+        assert_eq!(vm.count, "3 of 3 PRs match");
+        assert_eq!(vm.rows[0].checkbox, "[ ]");
+        assert_eq!(vm.rows[4].value, "any");
+        assert!(vm.rows[0].is_selected);
+
+        while state.selected_row() != FilterRow::Ci {
+            state.select_next();
+        }
+        state.cycle(false);
+        let vm = FilterBuilderViewModel::from_state(
+            &state,
+            Some(&data),
+            Utc::now(),
+            &PrSizeThresholds::default(),
+        );
+        assert_eq!(vm.count, "1 of 3 PRs match");
+        assert_eq!(vm.rows[4].checkbox, "[x]");
+        assert_eq!(vm.query, "ci:failed");
+    }
+}
@@ -0,0 +1,272 @@
+//! Footer View Model
+//!
+//! Lays out the active view's available actions as key hints for the footer
+//! (e.g., `Space Deselect • p -> m Merge 4 • p -> a Approve 4`).
+
+use crate::actions::AvailableAction;
+use crate::keybindings::Keymap;
+use crate::state::AppState;
+use ratatui::style::{Color, Style};
+
+/// Separator between footer items
+pub const SEPARATOR: &str = " • ";
+
+/// Terminal height from which the footer may wrap onto a second line
+pub const EXPANDED_MIN_HEIGHT: u16 = 24;
+
+/// A single key hint in the footer
+#[derive(Debug, Clone, PartialEq)]
+pub struct FooterItem {
+    /// Key hint resolved from the keymap (e.g., "p -> m")
+    pub key: String,
+    /// Label, with the count if any (e.g., "Merge 4")
+    pub label: String,
+    /// Style of the key hint
+    pub key_style: Style,
+    /// Style of the label
+    pub label_style: Style,
+}
+
+impl FooterItem {
+    /// Width of the item in cells
+    pub fn width(&self) -> usize {
+        gh_text_width::width(&self.key) + 1 + gh_text_width::width(&self.label)
+    }
+}
+
+/// View model for rendering the footer
+#[derive(Debug, Clone)]
+pub struct FooterViewModel {
+    /// Items per footer line, in priority order
+    pub lines: Vec<Vec<FooterItem>>,
+    /// Style of the separators
+    pub separator_style: Style,
+    /// Background color for the footer
+    pub bg_color: Color,
+}
+
+impl FooterViewModel {
+    /// Build the footer for the active view, fitting an `width` x `height` terminal
+    pub fn from_state(state: &AppState, width: u16, height: u16) -> Self {
+        let theme = &state.theme;
+        let actions = state
+            .view_stack
+            .last()
+            .map(|view| view.available_actions(state))
+            .unwrap_or_default();
+
+        let items = actions
+            .iter()
+            .filter_map(|action| Self::item(action, &state.keymap, theme))
+            .collect();
+        let max_lines = if height >= EXPANDED_MIN_HEIGHT { 2 } else { 1 };
+
+        Self {
+            // 1 cell padding on each side
+            lines: layout(items, (width as usize).saturating_sub(2), max_lines),
+            separator_style: theme.muted(),
+            bg_color: theme.bg_primary,
+        }
+    }
+
+    /// Number of terminal rows the footer takes
+    pub fn height(&self) -> u16 {
+        self.lines.len() as u16
+    }
+
+    /// Footer item of an action, `None` if no key triggers it
+    fn item(
+        action: &AvailableAction,
+        keymap: &Keymap,
+        theme: &gh_pr_lander_theme::Theme,
+    ) -> Option<FooterItem> {
+        let key = keymap.compact_hint_for_command(action.command)?;
+        let label = match action.count {
+            Some(count) => format!("{} {}", action.label, count),
+            None => action.label.to_string(),
+        };
+        let (key_style, label_style) = if action.enabled {
+            (theme.key_hint(), theme.key_description())
+        } else {
+            (theme.muted(), theme.muted())
+        };
+        Some(FooterItem {
+            key,
+            label,
+            key_style,
+            label_style,
+        })
+    }
+}
+
+/// Distribute items over at most `max_lines` lines of `width` cells
+///
+/// Items are placed in priority order; an item that does not fit the current
+/// line moves on to the next one, and items fitting no line are dropped.
+fn layout(items: Vec<FooterItem>, width: usize, max_lines: usize) -> Vec<Vec<FooterItem>> {
+    let mut lines = Vec::new();
+    let mut remaining = items;
+
+    while !remaining.is_empty() && lines.len() < max_lines {
+        let mut line: Vec<FooterItem> = Vec::new();
+        let mut used = 0;
+        let mut overflow = Vec::new();
+
+        for item in remaining {
+            let needed = if line.is_empty() {
+                item.width()
+            } else {
+                gh_text_width::width(SEPARATOR) + item.width()
+            };
+            if used + needed <= width {
+                used += needed;
+                line.push(item);
+            } else {
+                overflow.push(item);
+            }
+        }
+
+        if line.is_empty() {
+            break;
+        }
+        lines.push(line);
+        remaining = overflow;
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{Action, PullRequestAction};
+    use crate::domain_models::MaturityState;
+    use crate::reducers::test_harness::{dispatch_all, state_with_repos};
+    use crate::state::{BootstrapStage, StageStatus};
+
+    fn text(vm: &FooterViewModel) -> Vec<String> {
+        vm.lines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|item| format!("{} {}", item.key, item.label))
+                    .collect::<Vec<_>>()
+                    .join(SEPARATOR)
+            })
+            .collect()
+    }
+
+    fn item(key: &str, label: &str) -> FooterItem {
+        FooterItem {
+            key: key.to_string(),
+            label: label.to_string(),
+            key_style: Style::default(),
+            label_style: Style::default(),
+        }
+    }
+
+    fn online(state: &mut crate::state::AppState) {
+        state
+            .splash
+            .set_stage_status(BootstrapStage::InitClient, StageStatus::Done);
+    }
+
+    #[test]
+    fn test_layout_keeps_priority_order_and_skips_wide_items() {
+        let items = vec![item("a", "One"), item("b", "Very Long"), item("c", "Two")];
+
+        // "a One • c Two" fits 13 cells, "b Very Long" moves on
+        let lines = layout(items.clone(), 13, 1);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(
+            lines[0].iter().map(|i| i.key.as_str()).collect::<Vec<_>>(),
+            vec!["a", "c"]
+        );
+
+        let lines = layout(items.clone(), 13, 2);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1][0].key, "b");
+
+        assert!(layout(items, 3, 2).is_empty());
+    }
+
+    #[test]
+    fn test_layout_measures_wide_labels_in_cells() {
+        let items = vec![item("a", "マージ"), item("b", "One")];
+
+        // "a マージ" takes 8 cells, so "b One" no longer fits in 12
+        assert_eq!(items[0].width(), 8);
+        let lines = layout(items.clone(), 12, 1);
+        assert_eq!(lines[0].len(), 1);
+
+        assert_eq!(layout(items, 16, 1)[0].len(), 2);
+    }
+
+    #[test]
+    fn test_pr_view_counts_follow_selection() {
+        let mut state = state_with_repos(1, &[1, 2, 3, 4, 5]);
+        online(&mut state);
+
+        let vm = FooterViewModel::from_state(&state, 200, 40);
+        assert_eq!(vm.height(), 1);
+        assert!(text(&vm)[0].starts_with("Space Select • p -> m Merge • p -> a Approve"));
+
+        dispatch_all(
+            &mut state,
+            [
+                Action::PullRequest(PullRequestAction::SelectAll),
+                Action::PullRequest(PullRequestAction::ToggleSelection),
+            ],
+        );
+        let vm = FooterViewModel::from_state(&state, 200, 40);
+        // Toggling moved the cursor on to a PR that is still selected
+        assert!(text(&vm)[0]
+            .starts_with("Space Deselect • p -> m Merge 4 • p -> a Approve 4 • p -> c Comment 4"));
+    }
+
+    #[test]
+    fn test_unavailable_actions_are_greyed_out() {
+        let mut state = state_with_repos(1, &[1]);
+        let muted = state.theme.muted();
+        let merge = |vm: &FooterViewModel| {
+            vm.lines[0]
+                .iter()
+                .find(|item| item.label == "Merge")
+                .cloned()
+                .unwrap()
+        };
+
+        // No GitHub client yet
+        let vm = FooterViewModel::from_state(&state, 200, 40);
+        assert_eq!(merge(&vm).label_style, muted);
+
+        online(&mut state);
+        let vm = FooterViewModel::from_state(&state, 200, 40);
+        assert_ne!(merge(&vm).label_style, muted);
+
+        // Read-only mode (no token) cannot merge
+        state.splash.set_stage_status(
+            BootstrapStage::ResolveToken,
+            StageStatus::Warning("read-only".to_string()),
+        );
+        let vm = FooterViewModel::from_state(&state, 200, 40);
+        assert_eq!(merge(&vm).label_style, muted);
+        state
+            .splash
+            .set_stage_status(BootstrapStage::ResolveToken, StageStatus::Done);
+
+        // Drafts cannot be merged
+        state.main_view.repo_data.get_mut(&0).unwrap().prs[0].maturity = MaturityState::Draft;
+        let vm = FooterViewModel::from_state(&state, 200, 40);
+        assert_eq!(merge(&vm).label_style, muted);
+    }
+
+    #[test]
+    fn test_footer_wraps_only_on_tall_terminals() {
+        let state = state_with_repos(1, &[1]);
+
+        assert_eq!(FooterViewModel::from_state(&state, 60, 40).height(), 2);
+        assert_eq!(FooterViewModel::from_state(&state, 60, 20).height(), 1);
+        assert_eq!(FooterViewModel::from_state(&state, 300, 40).height(), 1);
+    }
+}
@@ -3,6 +3,7 @@
 //! Pre-computes presentation data for the key bindings help panel.
 
 use crate::command_id::CommandId;
+use crate::command_registry::CommandRegistry;
 use crate::keybindings::Keymap;
 use crate::state::AppState;
 use std::collections::BTreeMap;
@@ -100,27 +101,19 @@ impl KeyBindingsPanelViewModel {
         // Use BTreeMap for consistent ordering
         let mut by_category: BTreeMap<&'static str, Vec<BindingRow>> = BTreeMap::new();
 
+        let registry = CommandRegistry::global();
         for binding in keymap.bindings() {
-            // Filter out commands that shouldn't be shown
-            if !binding.command.show_in_palette()
-                && !matches!(
-                    binding.command,
-                    CommandId::NavigateNext
-                        | CommandId::NavigatePrevious
-                        | CommandId::NavigateToTop
-                        | CommandId::NavigateToBottom
-                )
-            {
+            let spec = registry.get(binding.command);
+            if !spec.in_help() {
                 continue;
             }
 
-            let category = binding.command.category();
             let row = BindingRow {
                 keys: binding.hint.clone(),
-                description: binding.command.description().to_string(),
+                description: spec.description.to_string(),
             };
 
-            by_category.entry(category).or_default().push(row);
+            by_category.entry(spec.category).or_default().push(row);
         }
 
         let category_order = CommandId::category_order();
@@ -0,0 +1,151 @@
+//! View model for the label picker
+//!
+//! Pre-computes the checkbox rows and status line of the label picker,
+//! separating data preparation from rendering logic.
+
+use crate::state::{LabelCheck, LabelPickerState};
+use ratatui::style::Color;
+
+/// View model for the label picker popup
+#[derive(Debug, Clone)]
+pub struct LabelPickerViewModel {
+    /// Popup title (e.g., "Labels: PR #12, #14")
+    pub title: String,
+    /// Current filter text
+    pub query: String,
+    /// Rows matching the filter
+    pub rows: Vec<LabelRow>,
+    /// Loading, error or empty message shown instead of the rows
+    pub message: Option<String>,
+    /// Summary of pending changes (e.g., "+1 -2 labels")
+    pub pending: String,
+}
+
+/// A single label row
+#[derive(Debug, Clone)]
+pub struct LabelRow {
+    /// Is this row under the cursor?
+    pub is_selected: bool,
+    /// Checkbox ("[x]", "[ ]" or "[-]")
+    pub checkbox: &'static str,
+    /// Label name
+    pub name: String,
+    /// Label description, if any
+    pub description: String,
+    /// Checkbox differs from the PRs' current labels
+    pub is_changed: bool,
+    /// Label color from GitHub
+    pub swatch: Color,
+}
+
+impl LabelPickerViewModel {
+    /// Build view model from label picker state
+    pub fn from_state(state: &LabelPickerState) -> Self {
+        let numbers: Vec<String> = state
+            .prs
+            .iter()
+            .map(|(number, _)| format!("#{}", number))
+            .collect();
+        let title = format!("Labels: PR {}", numbers.join(", "));
+
+        let rows: Vec<LabelRow> = state
+            .filtered()
+            .into_iter()
+            .enumerate()
+            .map(|(row, idx)| {
+                let choice = &state.choices[idx];
+                LabelRow {
+                    is_selected: row == state.selected,
+                    checkbox: match choice.current {
+                        LabelCheck::Checked => "[x]",
+                        LabelCheck::Unchecked => "[ ]",
+                        LabelCheck::Indeterminate => "[-]",
+                    },
+                    name: choice.label.name.clone(),
+                    description: choice.label.description.clone().unwrap_or_default(),
+                    is_changed: choice.current != choice.initial,
+                    swatch: parse_hex_color(&choice.label.color).unwrap_or(Color::Reset),
+                }
+            })
+            .collect();
+
+        let message = if state.applying {
+            Some("Applying label changes...".to_string())
+        } else if state.loading {
+            Some("Loading labels...".to_string())
+        } else if let Some(ref error) = state.error {
+            Some(format!("Failed to load labels: {}", error))
+        } else if rows.is_empty() {
+            Some("No matching labels".to_string())
+        } else {
+            None
+        };
+
+        let changed = state
+            .choices
+            .iter()
+            .filter(|c| c.current != c.initial)
+            .count();
+        let pending = match changed {
+            0 => String::new(),
+            1 => "1 label changed".to_string(),
+            n => format!("{} labels changed", n),
+        };
+
+        Self {
+            title,
+            query: state.query.clone(),
+            rows,
+            message,
+            pending,
+        }
+    }
+}
+
+/// Parse a GitHub label color ("d73a4a") into an RGB color
+fn parse_hex_color(hex: &str) -> Option<Color> {
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |range| u8::from_str_radix(hex.get(range)?, 16).ok();
+    Some(Color::Rgb(channel(0..2)?, channel(2..4)?, channel(4..6)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::Repository;
+    use gh_client::Label;
+
+    #[test]
+    fn test_rows_show_checkboxes() {
+        let mut state = LabelPickerState::new(
+            Repository::new("acme", "api", "main"),
+            vec![(1, vec!["bug".to_string()]), (2, vec![])],
+        );
+        state.set_labels(vec![
+            Label {
+                name: "bug".to_string(),
+                color: "d73a4a".to_string(),
+                description: Some("Something isn't working".to_string()),
+            },
+            Label {
+                name: "wip".to_string(),
+                color: "zz".to_string(),
+                description: None,
+            },
+        ]);
+        state.select_next();
+        state.toggle_selected();
+
+        let vm = LabelPickerViewModel::from_state(&state);
+        assert_eq!(vm.title, "Labels: PR #1, #2");
+        assert_eq!(vm.rows[0].checkbox, "[-]");
+        assert_eq!(vm.rows[0].swatch, Color::Rgb(0xd7, 0x3a, 0x4a));
+        assert_eq!(vm.rows[1].checkbox, "[x]");
+        assert!(vm.rows[1].is_selected && vm.rows[1].is_changed);
+        assert_eq!(vm.rows[1].swatch, Color::Reset);
+        assert_eq!(vm.pending, "1 label changed");
+        assert!(vm.message.is_none());
+    }
+}
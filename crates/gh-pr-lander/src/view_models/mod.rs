@@ -1,21 +1,47 @@
+pub mod actions_view_model;
+pub mod audit_log_view_model;
+pub mod branch_picker_view_model;
 pub mod build_log_view_model;
+pub mod cache_inspector_view_model;
 pub mod command_palette_view_model;
 pub mod confirmation_popup_view_model;
+pub mod conflict_helper_view_model;
 pub mod debug_console_view_model;
+pub mod duplicate_updates_view_model;
+pub mod filter_builder_view_model;
+pub mod footer_view_model;
 pub mod key_bindings_view_model;
+pub mod label_picker_view_model;
+pub mod perf_stats_view_model;
+pub mod pr_peek_view_model;
 pub mod pull_request_view_model;
+pub mod release_notes_view_model;
 pub mod repository_tabs_view_model;
+pub mod reviewer_picker_view_model;
 pub mod status_bar;
 
+pub use actions_view_model::ActionsViewModel;
+pub use audit_log_view_model::AuditLogViewModel;
+pub use branch_picker_view_model::BranchPickerViewModel;
 pub use build_log_view_model::{BuildLogRowStyle, BuildLogViewModel};
+pub use cache_inspector_view_model::CacheInspectorViewModel;
 pub use command_palette_view_model::CommandPaletteViewModel;
 pub use confirmation_popup_view_model::ConfirmationPopupViewModel;
+pub use conflict_helper_view_model::ConflictHelperViewModel;
+pub use duplicate_updates_view_model::DuplicateUpdatesViewModel;
+pub use filter_builder_view_model::FilterBuilderViewModel;
+pub use footer_view_model::FooterViewModel;
 pub use key_bindings_view_model::KeyBindingsPanelViewModel;
+pub use label_picker_view_model::LabelPickerViewModel;
+pub use perf_stats_view_model::PerfStatsViewModel;
+pub use pr_peek_view_model::PrPeekViewModel;
 #[allow(unused_imports)]
 pub use pull_request_view_model::PrTableViewModel;
+pub use release_notes_view_model::ReleaseNotesViewModel;
 pub use repository_tabs_view_model::{
     determine_main_content, EmptyStateViewModel, MainContentViewModel, RepositoryTabsViewModel,
 };
 #[allow(unused_imports)]
 pub use repository_tabs_view_model::{TabHintViewModel, TabViewModel};
+pub use reviewer_picker_view_model::ReviewerPickerViewModel;
 pub use status_bar::StatusBarViewModel;
@@ -0,0 +1,226 @@
+//! View model for the performance stats popup
+//!
+//! Pre-computes one row per GitHub API endpoint (calls, errors and latency
+//! percentiles) plus the totals since startup, and the memory held by
+//! parsed build logs.
+
+use crate::state::{BuildLogState, PerfStatsState, PARSED_RUNS_LIMIT, PARSED_RUNS_MAX_BYTES};
+use crate::utils::format::format_span;
+use gh_client::{format_latency, ApiMetricsSnapshot, EndpointStats};
+
+/// View model for the performance stats popup
+#[derive(Debug, Clone)]
+pub struct PerfStatsViewModel {
+    /// Panel title
+    pub title: String,
+    /// Uptime and cache line shown above the table
+    pub summary: String,
+    /// Memory of the parsed build logs, shown below the summary
+    pub build_logs: String,
+    /// Endpoint rows, most called first
+    pub rows: Vec<PerfStatsRow>,
+    /// Totals over all endpoints
+    pub totals: PerfStatsRow,
+    /// Message shown instead of the table (disabled or no calls yet)
+    pub message: Option<String>,
+}
+
+/// A single endpoint row
+#[derive(Debug, Clone, PartialEq)]
+pub struct PerfStatsRow {
+    pub endpoint: String,
+    pub calls: String,
+    pub errors: String,
+    pub mean: String,
+    pub p50: String,
+    pub p95: String,
+    pub max: String,
+    /// At least one call failed
+    pub has_errors: bool,
+}
+
+impl PerfStatsRow {
+    fn from_stats(stats: &EndpointStats) -> Self {
+        Self {
+            endpoint: stats.endpoint.to_string(),
+            calls: stats.calls.to_string(),
+            errors: stats.errors.to_string(),
+            mean: format_latency(stats.mean),
+            p50: format_latency(stats.p50),
+            p95: format_latency(stats.p95),
+            max: format_latency(stats.max),
+            has_errors: stats.errors > 0,
+        }
+    }
+
+    /// Percentiles are per endpoint, the totals only sum up calls and time
+    fn totals(snapshot: &ApiMetricsSnapshot) -> Self {
+        let calls = snapshot.total_calls();
+        let errors = snapshot.total_errors();
+        let max = snapshot.endpoints.iter().map(|e| e.max).max();
+        Self {
+            endpoint: "Total".to_string(),
+            calls: calls.to_string(),
+            errors: errors.to_string(),
+            mean: if calls > 0 {
+                format_latency(snapshot.total_time() / calls as u32)
+            } else {
+                "-".to_string()
+            },
+            p50: "-".to_string(),
+            p95: "-".to_string(),
+            max: max.map(format_latency).unwrap_or_else(|| "-".to_string()),
+            has_errors: errors > 0,
+        }
+    }
+}
+
+impl PerfStatsViewModel {
+    /// Build view model from the last metrics snapshot and the build log state
+    pub fn from_state(state: &PerfStatsState, build_log: &BuildLogState) -> Self {
+        let snapshot = &state.snapshot;
+
+        let mut endpoints: Vec<&EndpointStats> = snapshot.endpoints.iter().collect();
+        endpoints.sort_by(|a, b| b.calls.cmp(&a.calls).then(a.endpoint.cmp(b.endpoint)));
+        let rows = endpoints
+            .into_iter()
+            .map(PerfStatsRow::from_stats)
+            .collect();
+
+        let mut summary = format!(
+            "Since startup: {}, {} waiting on GitHub",
+            format_span(snapshot.uptime.as_secs()),
+            format_latency(snapshot.total_time())
+        );
+        if let Some(ref cache) = snapshot.cache {
+            match cache.hit_ratio() {
+                Some(ratio) => summary.push_str(&format!(
+                    " • cache hit ratio {:.0}% ({} of {} lookups, {} entries)",
+                    ratio * 100.0,
+                    cache.hits,
+                    cache.hits + cache.misses,
+                    cache.total_entries
+                )),
+                None => summary.push_str(" • no cache lookups yet"),
+            }
+        }
+
+        let build_logs = format!(
+            "Build logs in memory: {} finished runs, {} (keeps {} runs / {})",
+            build_log.parsed_runs.len(),
+            format_megabytes(build_log.log_memory_bytes()),
+            PARSED_RUNS_LIMIT,
+            format_megabytes(PARSED_RUNS_MAX_BYTES)
+        );
+
+        let message = if !snapshot.enabled {
+            Some("API metrics are disabled ([metrics] enabled = false)".to_string())
+        } else if snapshot.endpoints.is_empty() {
+            Some("No GitHub API calls yet".to_string())
+        } else {
+            None
+        };
+
+        Self {
+            title: "Performance stats".to_string(),
+            summary,
+            build_logs,
+            rows,
+            totals: PerfStatsRow::totals(snapshot),
+            message,
+        }
+    }
+}
+
+/// Format a byte count in megabytes
+fn format_megabytes(bytes: usize) -> String {
+    format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gh_client::{ApiMetrics, CacheStats};
+    use std::time::Duration;
+
+    #[test]
+    fn test_rows_sorted_by_calls_with_totals() {
+        let metrics = ApiMetrics::new();
+        metrics.record("fetch_ci_status", Duration::from_millis(100), true);
+        metrics.record("fetch_pull_requests", Duration::from_millis(200), true);
+        metrics.record("fetch_pull_requests", Duration::from_millis(400), false);
+        let mut snapshot = metrics.snapshot();
+        snapshot.cache = Some(CacheStats {
+            total_entries: 12,
+            fresh_entries: 10,
+            stale_entries: 2,
+            ttl_seconds: 1200,
+            hits: 3,
+            misses: 1,
+        });
+
+        let vm =
+            PerfStatsViewModel::from_state(&PerfStatsState { snapshot }, &BuildLogState::default());
+        assert!(vm.message.is_none());
+        assert_eq!(
+            vm.rows
+                .iter()
+                .map(|row| row.endpoint.as_str())
+                .collect::<Vec<_>>(),
+            vec!["fetch_pull_requests", "fetch_ci_status"]
+        );
+        assert_eq!(vm.rows[0].mean, "300ms");
+        assert!(vm.rows[0].has_errors);
+        assert!(!vm.rows[1].has_errors);
+
+        assert_eq!(vm.totals.calls, "3");
+        assert_eq!(vm.totals.errors, "1");
+        assert_eq!(vm.totals.mean, "233ms");
+        assert_eq!(vm.totals.max, "400ms");
+        assert!(vm
+            .summary
+            .ends_with("cache hit ratio 75% (3 of 4 lookups, 12 entries)"));
+    }
+
+    #[test]
+    fn test_message_when_disabled_or_empty() {
+        let metrics = ApiMetrics::new();
+        let vm = PerfStatsViewModel::from_state(
+            &PerfStatsState {
+                snapshot: metrics.snapshot(),
+            },
+            &BuildLogState::default(),
+        );
+        assert_eq!(vm.message.as_deref(), Some("No GitHub API calls yet"));
+        assert_eq!(vm.totals.mean, "-");
+
+        metrics.set_enabled(false);
+        let vm = PerfStatsViewModel::from_state(
+            &PerfStatsState {
+                snapshot: metrics.snapshot(),
+            },
+            &BuildLogState::default(),
+        );
+        assert!(vm.message.unwrap().contains("disabled"));
+    }
+
+    #[test]
+    fn test_build_log_memory() {
+        let metrics = ApiMetrics::new();
+        let build_log = BuildLogState {
+            unfinished_bytes: 3 * 1024 * 1024 / 2,
+            ..Default::default()
+        };
+
+        let vm = PerfStatsViewModel::from_state(
+            &PerfStatsState {
+                snapshot: metrics.snapshot(),
+            },
+            &build_log,
+        );
+        assert_eq!(
+            vm.build_logs,
+            "Build logs in memory: 0 finished runs, 1.5 MB (keeps 8 runs / 64.0 MB)"
+        );
+    }
+}
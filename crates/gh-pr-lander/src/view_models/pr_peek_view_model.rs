@@ -0,0 +1,356 @@
+//! View model for the PR peek popup
+//!
+//! Pre-computes the description and details of the peeked PR, separating
+//! data preparation from rendering logic.
+
+use crate::domain_models::{normalize_author, CiChecks, MergeableStatus, Pr, PrState};
+use crate::state::AppState;
+use crate::utils::format::format_timestamp;
+use chrono::{DateTime, Utc};
+use gh_pr_config::UiConfig;
+
+/// View model for the peek popup
+#[derive(Debug, Clone)]
+pub struct PrPeekViewModel {
+    /// Popup title (e.g., "#12 Fix the parser")
+    pub title: String,
+    /// Author login without bot decorations
+    pub author: String,
+    /// Target and source branch (e.g., "main ← fix-parser")
+    pub branches: String,
+    /// The target is not the repository's configured branch
+    pub unusual_base: bool,
+    /// Comma separated labels, empty if there are none
+    pub labels: String,
+    /// When the PR was opened and last updated
+    pub times: String,
+    /// Changed lines and files with the size bucket, "…" until fetched
+    pub changes: String,
+    /// CI and merge state with its icon (e.g., "✅ Ready")
+    pub ci: String,
+    /// Explanation of the CI state, if its label needs one
+    pub ci_hint: Option<String>,
+    /// Progress of running checks against their usual duration
+    pub ci_eta: Option<String>,
+    /// Checks run longer than they usually take
+    pub ci_overdue: bool,
+    /// Checks under a Required and an Optional heading, empty without checks
+    pub ci_checks: Vec<CiCheckGroup>,
+    /// Why all checks are listed as required, if branch protection is unknown
+    pub ci_checks_note: Option<&'static str>,
+    /// Description text, or a loading/error/empty message
+    pub body: String,
+    /// `body` is a message rather than the description
+    pub body_is_placeholder: bool,
+}
+
+/// Checks under one heading of the peek popup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CiCheckGroup {
+    /// Required (or optional) by branch protection
+    pub required: bool,
+    /// Names of the failed checks
+    pub failed: Vec<String>,
+    /// Names of the checks still running or queued
+    pub pending: Vec<String>,
+    /// Number of passed checks, listed as a count only
+    pub passed: usize,
+}
+
+impl CiCheckGroup {
+    /// Groups of the checks that exist, required ones first
+    fn of(ci: &CiChecks) -> Vec<Self> {
+        [true, false]
+            .into_iter()
+            .filter_map(|required| {
+                let mut group = Self {
+                    required,
+                    failed: Vec::new(),
+                    pending: Vec::new(),
+                    passed: 0,
+                };
+                for check in ci.checks.iter().filter(|check| check.required == required) {
+                    if check.is_failed() {
+                        group.failed.push(check.name.clone());
+                    } else if check.is_pending() {
+                        group.pending.push(check.name.clone());
+                    } else {
+                        group.passed += 1;
+                    }
+                }
+                let empty =
+                    group.failed.is_empty() && group.pending.is_empty() && group.passed == 0;
+                (!empty).then_some(group)
+            })
+            .collect()
+    }
+
+    pub fn heading(&self) -> &'static str {
+        if self.required {
+            "Required"
+        } else {
+            "Optional"
+        }
+    }
+}
+
+impl PrPeekViewModel {
+    /// Build view model for the peeked PR, `None` if nothing is peeked
+    pub fn from_state(state: &AppState) -> Option<Self> {
+        let main_view = &state.main_view;
+        let (repo, pr) = main_view.peeked_pr()?;
+        let error = main_view.peek.as_ref()?.error.as_deref();
+        let mut vm = Self::from_pr(
+            pr,
+            main_view.peek_body(repo, pr),
+            error,
+            &state.app_config.ui,
+            Utc::now(),
+        );
+        vm.unusual_base = pr.unusual_base(repo).is_some();
+        Some(vm)
+    }
+
+    /// Build view model from a PR and its description (`None` while fetching)
+    pub fn from_pr(
+        pr: &Pr,
+        body: Option<&str>,
+        error: Option<&str>,
+        ui: &UiConfig,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let ci = match pr.state {
+            PrState::Open => format!("{} {}", pr.mergeable.icon(), pr.mergeable.label()),
+            PrState::Merged => "✓ Merged".to_string(),
+            PrState::Closed => "✗ Closed".to_string(),
+        };
+        let ci_hint = match pr.state {
+            PrState::Open => pr.mergeable.hint().map(str::to_string).or_else(|| {
+                pr.mergeable_state_raw
+                    .as_ref()
+                    .map(|raw| format!("unknown state: '{}'", raw))
+            }),
+            _ => None,
+        };
+        let ci_eta = pr
+            .ci_budget
+            .filter(|_| pr.state == PrState::Open && pr.mergeable == MergeableStatus::Checking)
+            .map(|budget| budget.eta(now));
+        let (ci_checks, ci_checks_note) = match pr.state {
+            PrState::Open => (
+                CiCheckGroup::of(&pr.ci_checks),
+                (!pr.ci_checks.required_known && !pr.ci_checks.checks.is_empty())
+                    .then_some("required checks unknown, all count as required"),
+            ),
+            _ => (Vec::new(), None),
+        };
+
+        let changes = match (pr.changed_files, pr.size(&ui.pr_size)) {
+            (Some(files), Some(size)) => format!(
+                "+{} −{} in {} {} ({})",
+                pr.additions,
+                pr.deletions,
+                files,
+                if files == 1 { "file" } else { "files" },
+                size.label()
+            ),
+            _ => "…".to_string(),
+        };
+
+        let (body, body_is_placeholder) = match (body.map(str::trim), error) {
+            (Some(""), _) => ("No description provided.".to_string(), true),
+            (Some(body), _) => (body.to_string(), false),
+            (None, Some(error)) => (format!("Failed to load the description: {}", error), true),
+            (None, None) => ("Loading description…".to_string(), true),
+        };
+
+        Self {
+            title: format!("#{} {}", pr.number, pr.title),
+            author: normalize_author(&pr.author),
+            branches: format!("{} ← {}", pr.base_branch, pr.head_branch),
+            unusual_base: false,
+            labels: pr.labels.join(", "),
+            times: format!(
+                "opened {} · updated {}",
+                format_timestamp(pr.created_at, now, ui),
+                format_timestamp(pr.updated_at, now, ui)
+            ),
+            changes,
+            ci,
+            ci_hint,
+            ci_eta: ci_eta.map(|eta| eta.describe()),
+            ci_overdue: ci_eta.is_some_and(|eta| eta.is_overdue()),
+            ci_checks,
+            ci_checks_note,
+            body,
+            body_is_placeholder,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::CiBudget;
+    use gh_client::types::{CheckConclusion, CheckRunStatus};
+    use gh_client::CheckResult;
+
+    fn pr() -> Pr {
+        let now = Utc::now();
+        let mut pr = Pr::new(12, "Fix the parser", "renovate[bot]", "abc");
+        pr.base_branch = "main".to_string();
+        pr.head_branch = "fix-parser".to_string();
+        pr.labels = vec!["bug".to_string(), "parser".to_string()];
+        pr.mergeable = MergeableStatus::Queued;
+        pr.created_at = now - chrono::TimeDelta::days(3);
+        pr.updated_at = now - chrono::TimeDelta::hours(2);
+        pr
+    }
+
+    #[test]
+    fn test_peek_shows_pr_details() {
+        let pr = pr();
+        let vm = PrPeekViewModel::from_pr(
+            &pr,
+            Some("Closes #3\n"),
+            None,
+            &UiConfig::default(),
+            Utc::now(),
+        );
+
+        assert_eq!(vm.title, "#12 Fix the parser");
+        assert_eq!(vm.author, "renovate");
+        assert_eq!(vm.branches, "main ← fix-parser");
+        assert_eq!(vm.labels, "bug, parser");
+        assert_eq!(vm.times, "opened 3d ago · updated 2h ago");
+        assert_eq!(vm.changes, "…");
+        assert_eq!(vm.ci, "🕒 Queued");
+        assert_eq!(vm.ci_hint.as_deref(), MergeableStatus::Queued.hint());
+        assert_eq!(vm.ci_eta, None);
+        assert!(vm.ci_checks.is_empty());
+        assert_eq!(vm.ci_checks_note, None);
+        assert_eq!(vm.body, "Closes #3");
+        assert!(!vm.body_is_placeholder);
+    }
+
+    #[test]
+    fn test_peek_shows_changes_with_size() {
+        let pr = pr().with_stats(240, 10, 7);
+        let vm = PrPeekViewModel::from_pr(&pr, None, None, &UiConfig::default(), Utc::now());
+        assert_eq!(vm.changes, "+240 −10 in 7 files (M)");
+
+        let pr = pr.with_stats(3, 0, 1);
+        let vm = PrPeekViewModel::from_pr(&pr, None, None, &UiConfig::default(), Utc::now());
+        assert_eq!(vm.changes, "+3 −0 in 1 file (XS)");
+    }
+
+    #[test]
+    fn test_peek_shows_progress_of_running_checks() {
+        let now = Utc::now();
+        let mut pr = pr();
+        pr.mergeable = MergeableStatus::Checking;
+        pr.ci_budget = Some(CiBudget {
+            started_at: now - chrono::TimeDelta::minutes(20),
+            usual_seconds: Some(14 * 60),
+        });
+        let vm = PrPeekViewModel::from_pr(&pr, None, None, &UiConfig::default(), now);
+
+        assert_eq!(
+            vm.ci_eta.as_deref(),
+            Some("running 20m, overdue (usually 14m)")
+        );
+        assert!(vm.ci_overdue);
+    }
+
+    #[test]
+    fn test_peek_shows_unknown_mergeable_state() {
+        let mut pr = pr();
+        pr.mergeable = MergeableStatus::Unknown;
+        pr.mergeable_state_raw = Some("behind_policy".to_string());
+        let vm = PrPeekViewModel::from_pr(&pr, None, None, &UiConfig::default(), Utc::now());
+
+        assert_eq!(vm.ci, "❓ Unknown");
+        assert_eq!(
+            vm.ci_hint.as_deref(),
+            Some("unknown state: 'behind_policy'")
+        );
+    }
+
+    fn check(name: &str, conclusion: Option<CheckConclusion>, required: bool) -> CheckResult {
+        CheckResult {
+            id: 1,
+            name: name.to_string(),
+            status: if conclusion.is_some() {
+                CheckRunStatus::Completed
+            } else {
+                CheckRunStatus::InProgress
+            },
+            conclusion,
+            started_at: None,
+            completed_at: None,
+            required,
+        }
+    }
+
+    #[test]
+    fn test_peek_groups_required_and_optional_checks() {
+        let mut pr = pr();
+        pr.mergeable = MergeableStatus::OptionalFailed;
+        pr.ci_checks = CiChecks {
+            checks: vec![
+                check("test", Some(CheckConclusion::Success), true),
+                check("build", Some(CheckConclusion::Success), true),
+                check("lint", Some(CheckConclusion::Failure), false),
+                check("docs", None, false),
+            ],
+            required_known: true,
+        };
+        let vm = PrPeekViewModel::from_pr(&pr, None, None, &UiConfig::default(), Utc::now());
+
+        assert_eq!(vm.ci, "🟡 optional ✗");
+        assert_eq!(
+            vm.ci_checks,
+            vec![
+                CiCheckGroup {
+                    required: true,
+                    failed: vec![],
+                    pending: vec![],
+                    passed: 2,
+                },
+                CiCheckGroup {
+                    required: false,
+                    failed: vec!["lint".to_string()],
+                    pending: vec!["docs".to_string()],
+                    passed: 0,
+                },
+            ]
+        );
+        assert_eq!(vm.ci_checks_note, None);
+
+        // Without branch protection every check is listed as required
+        pr.ci_checks.required_known = false;
+        for check in &mut pr.ci_checks.checks {
+            check.required = true;
+        }
+        let vm = PrPeekViewModel::from_pr(&pr, None, None, &UiConfig::default(), Utc::now());
+        assert_eq!(vm.ci_checks.len(), 1);
+        assert_eq!(vm.ci_checks[0].heading(), "Required");
+        assert_eq!(vm.ci_checks[0].failed, vec!["lint"]);
+        assert_eq!(vm.ci_checks[0].passed, 2);
+        assert!(vm.ci_checks_note.is_some());
+    }
+
+    #[test]
+    fn test_peek_body_placeholders() {
+        let pr = pr();
+        let ui = UiConfig::default();
+        let body = |body, error| PrPeekViewModel::from_pr(&pr, body, error, &ui, Utc::now()).body;
+
+        assert_eq!(body(None, None), "Loading description…");
+        assert_eq!(
+            body(None, Some("404")),
+            "Failed to load the description: 404"
+        );
+        assert_eq!(body(Some("  "), None), "No description provided.");
+    }
+}
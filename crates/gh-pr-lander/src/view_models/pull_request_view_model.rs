@@ -4,11 +4,41 @@
 //! Pre-computes all display text, colors, and styles in the view model.
 
 use crate::domain_models::{
-    LoadingState, MaturityState, MergeableStatus, Pr, Repository, ReviewDecision,
+    normalize_author, Attention, CommentCounts, LoadingState, MaturityState, MergeableStatus,
+    OperationType, Pr, PrSize, PrStacks, PrState, PrStateFilter, Repository, ReviewDecision,
+    UpdateType, WatchedPr,
 };
-use crate::state::RepositoryData;
+use crate::state::{
+    DuplicateUpdateIndex, PrFilter, PrSearchState, PrSort, RepoPulse, RepositoryData,
+};
+use crate::utils::format::{format_clock, format_timestamp};
+use crate::view_models::repository_tabs_view_model::branch_status_glyph;
+use chrono::{DateTime, Utc};
+use gh_pr_config::{PrSizeThresholds, SnoozedPr, UiConfig};
 use gh_pr_lander_theme::Theme;
 use ratatui::style::Color;
+use std::collections::{HashMap, HashSet};
+
+/// Marker of PRs with a local note
+const NOTE_MARKER: &str = "📝";
+
+/// Author cell of rows repeating the previous row's author
+const REPEATED_AUTHOR: &str = "〃";
+
+/// Marker of revealed snoozed PRs
+const SNOOZE_MARKER: &str = "💤";
+
+/// Marker of PRs with a running operation (merge, comment, ...)
+const IN_FLIGHT_MARKER: &str = "⋯";
+
+/// Marker of PRs updated since they were last seen
+const UNSEEN_MARKER: &str = "●";
+
+/// Marker of bot updates opened in several repositories, before the count
+const DUPLICATE_MARKER: &str = "×";
+
+/// Delta and size cells of PRs whose stats are not fetched yet
+const PENDING_STATS: &str = "…";
 
 /// View model for the entire PR table
 #[derive(Debug, Clone)]
@@ -17,8 +47,14 @@ pub struct PrTableViewModel {
     pub header: PrTableHeaderViewModel,
     /// Pre-computed rows ready to display
     pub rows: Vec<PrRowViewModel>,
-    /// Current cursor position (for keyboard navigation)
-    pub selected_index: usize,
+    /// Current cursor position among the rows (None if the cursor PR is hidden)
+    pub selected_index: Option<usize>,
+    /// Local note of the PR under the cursor
+    pub note: Option<String>,
+    /// Explanation of the cursor PR's status, for statuses that need one
+    pub status_hint: Option<&'static str>,
+    /// Rows carry why they need attention (attention sort active)
+    pub attention_column: bool,
 }
 
 /// View model for table header
@@ -35,13 +71,27 @@ pub struct PrTableHeaderViewModel {
 /// View model for a single PR row
 #[derive(Debug, Clone)]
 pub struct PrRowViewModel {
+    /// Number of the PR shown in this row
+    pub number: usize,
     /// Pre-formatted cell texts
     pub pr_number: String, // "#123"
-    pub title: String,         // "Fix: broken tests"
-    pub author: String,        // "sassman"
-    pub maturity_text: String, // "Draft" or ""
-    pub review_text: String,   // "✓", "!", "○", "?"
-    pub status_text: String,   // "✓ Ready"
+    pub title: String,           // "Fix: broken tests"
+    pub author: String,          // "sassman"
+    pub maturity_text: String,   // "Draft" or ""
+    pub review_text: String,     // "✓", "!", "○", "?"
+    pub comments_text: String,   // "3💬", blank without comments
+    pub unresolved_text: String, // "2⚠", blank without unresolved threads
+    pub status_text: String,     // "✓ Ready"
+    /// Update type badge of bot PRs ("major") with its color
+    pub update_badge: Option<(String, Color)>,
+    /// Head of fork PRs as "user:branch"
+    pub fork_head: Option<String>,
+    /// Base branch, if it is not the repository's configured branch
+    pub base_branch: Option<String>,
+    /// Title is bold (PR updated since it was last seen)
+    pub bold_title: bool,
+    /// Why the PR needs the user ("review requested"), with the attention sort
+    pub attention: Option<&'static str>,
 
     /// Pre-computed styles
     pub bg_color: Color, // Background (alternating, selected, etc.)
@@ -51,45 +101,356 @@ pub struct PrRowViewModel {
     pub status_color: Color,   // Status-specific color
     pub additions: usize,      // Raw additions count (for coloring)
     pub deletions: usize,      // Raw deletions count (for coloring)
+    /// Additions and deletions are not fetched yet (the delta shows "…")
+    pub stats_pending: bool,
+    /// Size bucket badge ("M", "…" until the stats are fetched) with its color
+    pub size_badge: (&'static str, Color),
 }
 
 impl PrTableViewModel {
     /// Transform state into display-ready view model
-    pub fn from_repo_data(repo_data: &RepositoryData, repo: &Repository, theme: &Theme) -> Self {
+    pub fn from_repo_data(
+        repo_data: &RepositoryData,
+        repo: &Repository,
+        theme: &Theme,
+        ui: &UiConfig,
+    ) -> Self {
         // Build header
-        let header = Self::build_header(repo_data, repo, theme);
+        let header = Self::build_header(repo_data, repo, theme, ui);
 
         // Build rows
+        let stacks = PrStacks::compute(&repo_data.prs);
+        let now = Utc::now();
         let rows = repo_data
             .prs
             .iter()
             .enumerate()
             .map(|(index, pr)| {
                 let is_multi_selected = repo_data.selected_pr_numbers.contains(&pr.number);
-                Self::build_row(pr, index, repo_data.selected_pr, is_multi_selected, theme)
+                let mut row = Self::build_row(
+                    pr,
+                    index,
+                    repo_data.selected_pr,
+                    is_multi_selected,
+                    theme,
+                    now,
+                    &ui.pr_size,
+                );
+                // Stacked PRs name their parent PR rather than its branch
+                if let Some(parent) = stacks.parent(pr.number) {
+                    let indent = "  ".repeat(stacks.depth(pr.number).saturating_sub(1));
+                    row.title = format!("{}↳ stacked on #{}  {}", indent, parent, row.title);
+                } else {
+                    row.base_branch = pr.unusual_base(repo).map(str::to_string);
+                }
+                row
             })
             .collect();
 
         Self {
             header,
             rows,
-            selected_index: repo_data.selected_pr,
+            selected_index: Some(repo_data.selected_pr),
+            note: None,
+            status_hint: repo_data
+                .prs
+                .get(repo_data.selected_pr)
+                .and_then(|pr| pr.mergeable.hint()),
+            attention_column: false,
+        }
+    }
+
+    /// Mark the rows of PRs with a local note, and show the note under the cursor
+    pub fn with_notes(mut self, notes: &HashMap<WatchedPr, String>, repo: &Repository) -> Self {
+        for (index, row) in self.rows.iter_mut().enumerate() {
+            let Some(note) = notes.get(&repo.watched_pr(row.number)) else {
+                continue;
+            };
+            row.title = format!("{} {}", NOTE_MARKER, row.title);
+            if Some(index) == self.selected_index {
+                self.note = Some(note.clone());
+            }
+        }
+        self
+    }
+
+    /// Mark the rows of PRs with a running operation, naming the operation
+    pub fn with_in_flight(
+        mut self,
+        in_flight: &HashSet<(WatchedPr, OperationType)>,
+        repo: &Repository,
+    ) -> Self {
+        for row in &mut self.rows {
+            let pr = repo.watched_pr(row.number);
+            let mut labels: Vec<&str> = in_flight
+                .iter()
+                .filter(|(running, _)| *running == pr)
+                .map(|(_, operation)| operation.label())
+                .collect();
+            if labels.is_empty() {
+                continue;
+            }
+            labels.sort_unstable();
+            row.title = format!("{} {} {}", IN_FLIGHT_MARKER, labels.join(", "), row.title);
+        }
+        self
+    }
+
+    /// Mark the rows of bot updates opened in other repositories too ("×3 repos")
+    pub fn with_duplicates(
+        mut self,
+        index: &DuplicateUpdateIndex,
+        repo_data: &RepositoryData,
+    ) -> Self {
+        for row in &mut self.rows {
+            let Some(key) = repo_data
+                .prs
+                .iter()
+                .find(|pr| pr.number == row.number)
+                .and_then(|pr| index.duplicated_key(pr))
+            else {
+                continue;
+            };
+            row.title = format!(
+                "{}{} repos {}",
+                DUPLICATE_MARKER,
+                index.repo_count(&key),
+                row.title
+            );
         }
+        self
+    }
+
+    /// Mark the rows of PRs updated since they were last seen, in bold if wanted
+    pub fn with_unseen(mut self, unseen: &HashSet<usize>, bold: bool) -> Self {
+        for row in &mut self.rows {
+            if unseen.contains(&row.number) {
+                row.title = format!("{} {}", UNSEEN_MARKER, row.title);
+                row.bold_title = bold;
+            }
+        }
+        self
+    }
+
+    /// Hide the PRs not matching the repository's filter and name it in the header
+    pub fn with_filter(
+        mut self,
+        repo_data: &RepositoryData,
+        login: Option<&str>,
+        sizes: &PrSizeThresholds,
+    ) -> Self {
+        let filter = &repo_data.current_filter;
+        if *filter == PrFilter::All {
+            return self;
+        }
+        let shown: HashSet<usize> = repo_data
+            .prs
+            .iter()
+            .filter(|pr| filter.matches(pr, login, sizes))
+            .map(|pr| pr.number)
+            .collect();
+        self.retain_rows(|row| shown.contains(&row.number));
+        let label = match filter {
+            PrFilter::Author(author) => format!("author {} (a/F to clear)", author),
+            other => format!("{} (F to clear)", other.label()),
+        };
+        self.header.title = format!("{}· filter: {} ", self.header.title, label);
+        self
+    }
+
+    /// Name a sort other than newest first in the header, and with the
+    /// attention sort tag the rows with why they need `login`
+    ///
+    /// The order itself comes from the state.
+    pub fn with_sort(
+        mut self,
+        sort: PrSort,
+        repo_data: &RepositoryData,
+        login: Option<&str>,
+    ) -> Self {
+        if sort == PrSort::Newest {
+            return self;
+        }
+        self.attention_column = sort == PrSort::Attention;
+        if let Some(login) = login.filter(|_| self.attention_column) {
+            for row in &mut self.rows {
+                row.attention = repo_data
+                    .prs
+                    .iter()
+                    .find(|pr| pr.number == row.number)
+                    .and_then(|pr| Attention::of(pr, login))
+                    .map(|attention| attention.tag());
+            }
+        }
+        self.header.title = format!(
+            "{}· sort: {} (S to cycle) ",
+            self.header.title,
+            sort.label()
+        );
+        self
+    }
+
+    /// Replace the author of rows following a row by the same author with "〃"
+    ///
+    /// Applied to the rows left after filtering, so long runs of bot PRs do
+    /// not repeat the bot's name. The cursor row always names its author.
+    pub fn with_repeated_authors_collapsed(mut self) -> Self {
+        let mut previous: Option<String> = None;
+        for (index, row) in self.rows.iter_mut().enumerate() {
+            let author = std::mem::take(&mut row.author);
+            row.author = if previous.as_deref() == Some(author.as_str())
+                && Some(index) != self.selected_index
+            {
+                REPEATED_AUTHOR.to_string()
+            } else {
+                author.clone()
+            };
+            previous = Some(author);
+        }
+        self
+    }
+
+    /// Hide snoozed PRs (or dim them when revealed) and count them in the header
+    ///
+    /// Only the rows are filtered, the PRs in the state stay untouched.
+    pub fn with_snoozed(
+        mut self,
+        snoozed: &HashMap<WatchedPr, SnoozedPr>,
+        show_snoozed: bool,
+        repo: &Repository,
+        theme: &Theme,
+    ) -> Self {
+        let muted = theme.muted().fg.unwrap_or(Color::Gray);
+        let is_snoozed = |row: &PrRowViewModel| snoozed.contains_key(&repo.watched_pr(row.number));
+        let count = self.rows.iter().filter(|row| is_snoozed(row)).count();
+
+        if show_snoozed {
+            for (index, row) in self.rows.iter_mut().enumerate() {
+                if is_snoozed(row) {
+                    row.title = format!("{} {}", SNOOZE_MARKER, row.title);
+                    if Some(index) != self.selected_index {
+                        row.fg_color = muted;
+                    }
+                }
+            }
+        } else {
+            self.retain_rows(|row| !is_snoozed(row));
+        }
+
+        if count > 0 {
+            let verb = if show_snoozed { "shown" } else { "hidden" };
+            self.header.title = format!("{}· {} snoozed ({}) ", self.header.title, count, verb);
+        }
+        self
+    }
+
+    /// Keep the rows matching `keep`, following the cursor (None once it is hidden)
+    fn retain_rows(&mut self, keep: impl Fn(&PrRowViewModel) -> bool) {
+        let cursor = self.selected_index;
+        let mut selected_index = None;
+        let mut rows = Vec::with_capacity(self.rows.len());
+        for (index, row) in std::mem::take(&mut self.rows).into_iter().enumerate() {
+            if !keep(&row) {
+                continue;
+            }
+            if Some(index) == cursor {
+                selected_index = Some(rows.len());
+            }
+            rows.push(row);
+        }
+        self.rows = rows;
+        self.selected_index = selected_index;
     }
 
     fn build_header(
         repo_data: &RepositoryData,
         repo: &Repository,
         theme: &Theme,
+        ui: &UiConfig,
     ) -> PrTableHeaderViewModel {
-        let title = format!("  {}/{}@{} ", repo.org, repo.repo, repo.branch);
+        if let Some(ref search) = repo_data.search {
+            return Self::build_search_header(search, repo_data.prs.len(), repo);
+        }
+
+        let branch = match branch_status_glyph(repo_data.branch_ci) {
+            Some(glyph) => format!("{} {}", repo.branch, glyph),
+            None => repo.branch.clone(),
+        };
+        let pulse = repo_data
+            .pulse
+            .map(|pulse| format!("{} ", Self::pulse_text(pulse, theme.text_labels)))
+            .unwrap_or_default();
+        let title = match repo_data.pr_state {
+            PrStateFilter::Open => {
+                format!("  {}/{}@{} {}", repo.org, repo.repo, repo.branch, pulse)
+            }
+            other => format!(
+                "  {}/{}@{} [{}] {}",
+                repo.org,
+                repo.repo,
+                branch,
+                other.as_str(),
+                pulse
+            ),
+        };
 
         let (status_text, status_color) = Self::format_loading_state(
             &repo_data.loading_state,
             repo_data.last_updated.as_ref(),
+            repo_data.prefetched,
+            repo_data.from_cache.as_ref(),
             theme,
+            ui,
+        );
+
+        PrTableHeaderViewModel {
+            title,
+            status_text,
+            status_color,
+        }
+    }
+
+    /// Weekly activity next to the repository name ("↑12 ↓9 this week")
+    fn pulse_text(pulse: RepoPulse, text_labels: bool) -> String {
+        if text_labels {
+            format!("{} opened {} merged this week", pulse.opened, pulse.merged)
+        } else {
+            format!("↑{} ↓{} this week", pulse.opened, pulse.merged)
+        }
+    }
+
+    /// Header of the search results, labeled so the mode is obvious
+    fn build_search_header(
+        search: &PrSearchState,
+        shown: usize,
+        repo: &Repository,
+    ) -> PrTableHeaderViewModel {
+        let title = format!(
+            " search results — esc to return: \"{}\" in {}/{} ",
+            search.query, repo.org, repo.repo
         );
 
+        let (status_text, status_color) = if search.loading && shown == 0 {
+            ("Searching...".to_string(), Color::Yellow)
+        } else if let Some(ref error) = search.error {
+            (format!("Error: {}", error), Color::Red)
+        } else {
+            let more = if search.loading {
+                " loading more..."
+            } else {
+                ""
+            };
+            let incomplete = if search.incomplete {
+                " (incomplete)"
+            } else {
+                ""
+            };
+            (
+                format!("{} of {}{}{}", shown, search.total_count, incomplete, more),
+                Color::Green,
+            )
+        };
+
         PrTableHeaderViewModel {
             title,
             status_text,
@@ -103,6 +464,8 @@ impl PrTableViewModel {
         cursor_index: usize,
         is_multi_selected: bool,
         theme: &Theme,
+        now: DateTime<Utc>,
+        sizes: &PrSizeThresholds,
     ) -> PrRowViewModel {
         let is_cursor = index == cursor_index;
 
@@ -110,19 +473,49 @@ impl PrTableViewModel {
         let selection_indicator = if is_multi_selected { "●" } else { " " };
         let pr_number = format!("{} #{}", selection_indicator, pr.number);
         let title = pr.title.clone();
-        let author = pr.author.clone();
+        let author = normalize_author(&pr.author);
 
         // Format maturity (Draft/Ready)
-        let maturity_text = Self::maturity_status_text(pr.maturity).to_string();
+        let maturity_text = Self::maturity_status_text(pr.maturity, theme.text_labels).to_string();
         let maturity_color = Self::maturity_status_color(pr.maturity, theme);
 
         // Format review status
-        let review_text = Self::review_status_icon(pr.review_decision).to_string();
+        let review_text = if theme.text_labels {
+            Self::review_status_label(pr.review_decision).to_string()
+        } else {
+            Self::review_status_icon(pr.review_decision).to_string()
+        };
         let review_color = Self::review_status_color(pr.review_decision, theme);
 
-        // Format status with icon and label
-        let status_text = format!("{} {}", pr.mergeable.icon(), pr.mergeable.label());
-        let status_color = Self::mergeable_status_color(pr.mergeable, theme);
+        // Comments stay blank until counted (and when there are none)
+        let (comments_text, unresolved_text) = Self::comment_texts(pr.comment_counts);
+
+        // Format status with icon and label (closed/merged PRs show their final state)
+        let muted = theme.muted().fg.unwrap_or(Color::Gray);
+        let (status_text, status_color) = match pr.state {
+            // Running checks show their progress instead of the label, e.g. "⏳ 6/14m"
+            PrState::Open => match pr
+                .ci_budget
+                .filter(|_| pr.mergeable == MergeableStatus::Checking)
+            {
+                Some(budget) => {
+                    let eta = budget.eta(now);
+                    let color = if eta.is_overdue() {
+                        Color::LightRed
+                    } else {
+                        Self::mergeable_status_color(pr.mergeable, theme)
+                    };
+                    (format!("{} {}", pr.mergeable.icon(), eta.short()), color)
+                }
+                None => (
+                    format!("{} {}", pr.mergeable.icon(), pr.mergeable.label()),
+                    Self::mergeable_status_color(pr.mergeable, theme),
+                ),
+            },
+            PrState::Merged => ("✓ Merged".to_string(), muted),
+            PrState::Closed => ("✗ Closed".to_string(), muted),
+        };
+        let is_inactive = pr.state != PrState::Open;
 
         // Compute colors - multi-selected rows get highlighted differently
         let (fg_color, bg_color) = if is_cursor {
@@ -140,10 +533,21 @@ impl PrTableViewModel {
             } else {
                 Color::Rgb(30, 30, 40) // Subtle alternate row color
             };
-            (theme.text().fg.unwrap_or(Color::White), bg)
+            let fg = if is_inactive {
+                muted
+            } else {
+                theme.text().fg.unwrap_or(Color::White)
+            };
+            (fg, bg)
         };
 
+        let update_badge = pr
+            .dependency_update
+            .as_ref()
+            .map(|update| Self::update_badge(update.update_type, theme));
+
         PrRowViewModel {
+            number: pr.number,
             pr_number,
             title,
             author,
@@ -151,21 +555,59 @@ impl PrTableViewModel {
             maturity_color,
             review_text,
             review_color,
+            comments_text,
+            unresolved_text,
             status_text,
+            update_badge,
+            fork_head: pr.is_from_fork.then(|| pr.head_label()),
+            base_branch: None,
+            bold_title: false,
+            attention: None,
             bg_color,
             fg_color,
             status_color,
             additions: pr.additions,
             deletions: pr.deletions,
+            stats_pending: pr.changed_files.is_none(),
+            size_badge: Self::size_badge(pr.size(sizes), theme),
         }
     }
 
+    /// Comment count and unresolved thread count cells (blank for zero)
+    fn comment_texts(counts: Option<CommentCounts>) -> (String, String) {
+        let counts = counts.unwrap_or_default();
+        let text = |count: u32, icon: &str| {
+            if count == 0 {
+                String::new()
+            } else {
+                format!("{}{}", count, icon)
+            }
+        };
+        (
+            text(counts.total(), "💬"),
+            text(counts.unresolved_threads, "⚠"),
+        )
+    }
+
     /// Format loading state for display
     fn format_loading_state(
         state: &LoadingState,
         last_updated: Option<&chrono::DateTime<chrono::Local>>,
+        prefetched: bool,
+        from_cache: Option<&chrono::DateTime<chrono::Local>>,
         theme: &Theme,
+        ui: &UiConfig,
     ) -> (String, Color) {
+        // PRs shown from the cache until the fresh load lands
+        if let (LoadingState::Loaded, Some(cached_at)) = (state, from_cache) {
+            return (
+                format!(
+                    "cached {} — refreshing…",
+                    format_timestamp(cached_at.to_utc(), chrono::Utc::now(), ui)
+                ),
+                theme.muted().fg.unwrap_or(Color::Gray),
+            );
+        }
         match state {
             LoadingState::Idle => (
                 "Idle [Ctrl+r to refresh]".to_string(),
@@ -175,8 +617,9 @@ impl PrTableViewModel {
             LoadingState::Loaded => {
                 let status_text = if let Some(timestamp) = last_updated {
                     format!(
-                        "Updated {} [Ctrl+r to refresh]",
-                        timestamp.format("%H:%M:%S")
+                        "{} {} [Ctrl+r to refresh]",
+                        if prefetched { "Prefetched" } else { "Updated" },
+                        format_clock(timestamp.to_utc(), ui)
                     )
                 } else {
                     "Loaded [Ctrl+r to refresh]".to_string()
@@ -202,9 +645,12 @@ impl PrTableViewModel {
         match status {
             MergeableStatus::Unknown => theme.muted().fg.unwrap_or(Color::Gray),
             MergeableStatus::Checking => Color::Yellow,
+            MergeableStatus::Queued => Color::Yellow,
+            MergeableStatus::WaitingApproval => Color::Magenta,
             MergeableStatus::Ready => Color::Green,
             MergeableStatus::NeedsRebase => Color::Yellow,
             MergeableStatus::BuildFailed => Color::Red,
+            MergeableStatus::OptionalFailed => Color::Yellow,
             MergeableStatus::Conflicted => Color::Red,
             MergeableStatus::Blocked => Color::Red,
             MergeableStatus::Rebasing => Color::Cyan,
@@ -212,10 +658,34 @@ impl PrTableViewModel {
         }
     }
 
+    /// Badge of a dependency update, major updates stand out in red
+    fn size_badge(size: Option<PrSize>, theme: &Theme) -> (&'static str, Color) {
+        match size {
+            Some(size @ (PrSize::Xs | PrSize::S)) => (size.label(), Color::Green),
+            Some(PrSize::M) => (PrSize::M.label(), Color::Yellow),
+            Some(PrSize::L) => (PrSize::L.label(), Color::LightRed),
+            Some(PrSize::Xl) => (PrSize::Xl.label(), Color::Red),
+            None => (PENDING_STATS, theme.muted().fg.unwrap_or(Color::Gray)),
+        }
+    }
+
+    fn update_badge(update_type: Option<UpdateType>, theme: &Theme) -> (String, Color) {
+        match update_type {
+            Some(UpdateType::Major) => ("major".to_string(), Color::Red),
+            Some(UpdateType::Minor) => ("minor".to_string(), Color::Yellow),
+            Some(UpdateType::Patch) => ("patch".to_string(), Color::Green),
+            None => (
+                "update".to_string(),
+                theme.muted().fg.unwrap_or(Color::Gray),
+            ),
+        }
+    }
+
     // --- Presentation helpers for MaturityState ---
 
-    fn maturity_status_text(maturity: MaturityState) -> &'static str {
+    fn maturity_status_text(maturity: MaturityState, text_labels: bool) -> &'static str {
         match maturity {
+            MaturityState::Draft if text_labels => "Draft",
             MaturityState::Draft => "🏗️",
             MaturityState::Ready => "",
         }
@@ -239,6 +709,16 @@ impl PrTableViewModel {
         }
     }
 
+    /// Icon plus a short label, for themes that never rely on color alone
+    fn review_status_label(decision: ReviewDecision) -> &'static str {
+        match decision {
+            ReviewDecision::Unknown => "? n/a",
+            ReviewDecision::Pending => "○ req",
+            ReviewDecision::Approved => "✓ ok",
+            ReviewDecision::ChangesRequested => "! chg",
+        }
+    }
+
     fn review_status_color(decision: ReviewDecision, _theme: &Theme) -> Color {
         match decision {
             ReviewDecision::Unknown => Color::Gray,
@@ -248,3 +728,446 @@ impl PrTableViewModel {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo_data() -> RepositoryData {
+        let mut draft = Pr::new(1, "Draft PR", "alice", "abc");
+        draft.maturity = MaturityState::Draft;
+        draft.review_decision = ReviewDecision::Approved;
+        let mut blocked = Pr::new(2, "Blocked PR", "bob", "def");
+        blocked.review_decision = ReviewDecision::ChangesRequested;
+        blocked.mergeable = MergeableStatus::Blocked;
+        RepositoryData {
+            prs: vec![draft, blocked],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_high_contrast_rows_carry_text_labels() {
+        let repo = Repository::new("org", "repo", "main");
+        let vm = PrTableViewModel::from_repo_data(
+            &repo_data(),
+            &repo,
+            &Theme::high_contrast(),
+            &UiConfig::default(),
+        );
+
+        assert_eq!(vm.rows[0].maturity_text, "Draft");
+        assert_eq!(vm.rows[0].review_text, "✓ ok");
+        assert_eq!(vm.rows[1].review_text, "! chg");
+        assert!(vm.rows[1].status_text.ends_with("Blocked"));
+    }
+
+    #[test]
+    fn test_default_theme_rows_use_icons() {
+        let repo = Repository::new("org", "repo", "main");
+        let vm = PrTableViewModel::from_repo_data(
+            &repo_data(),
+            &repo,
+            &Theme::default(),
+            &UiConfig::default(),
+        );
+
+        assert_eq!(vm.rows[0].maturity_text, "🏗️");
+        assert_eq!(vm.rows[0].review_text, "✓");
+    }
+
+    #[test]
+    fn test_comment_counts_are_blank_for_zero() {
+        let repo = Repository::new("org", "repo", "main");
+        let mut data = repo_data();
+        data.prs[0].comment_counts = Some(CommentCounts {
+            unresolved_threads: 2,
+            review_comments: 2,
+            issue_comments: 1,
+        });
+        data.prs[1].comment_counts = Some(CommentCounts {
+            issue_comments: 4,
+            ..Default::default()
+        });
+        data.prs.push(Pr::new(3, "Not counted yet", "carol", "ghi"));
+
+        let vm =
+            PrTableViewModel::from_repo_data(&data, &repo, &Theme::default(), &UiConfig::default());
+        let cells: Vec<(&str, &str)> = vm
+            .rows
+            .iter()
+            .map(|row| (row.comments_text.as_str(), row.unresolved_text.as_str()))
+            .collect();
+        assert_eq!(cells, vec![("3💬", "2⚠"), ("4💬", ""), ("", "")]);
+    }
+
+    #[test]
+    fn test_repeated_authors_are_collapsed_and_filterable() {
+        let repo = Repository::new("org", "repo", "main");
+        let data = RepositoryData {
+            prs: vec![
+                Pr::new(1, "Bump a", "dependabot[bot]", "a"),
+                Pr::new(2, "Bump b", "dependabot-preview[bot]", "b"),
+                Pr::new(3, "Bump c", "app/dependabot", "c"),
+                Pr::new(4, "Fix", "alice", "d"),
+            ],
+            selected_pr: 2,
+            ..Default::default()
+        };
+
+        let vm =
+            PrTableViewModel::from_repo_data(&data, &repo, &Theme::default(), &UiConfig::default())
+                .with_repeated_authors_collapsed();
+        let authors: Vec<&str> = vm.rows.iter().map(|row| row.author.as_str()).collect();
+        // The cursor row keeps its author
+        assert_eq!(authors, vec!["dependabot", "〃", "dependabot", "alice"]);
+
+        let data = RepositoryData {
+            current_filter: PrFilter::Author("dependabot".to_string()),
+            ..data
+        };
+        let vm =
+            PrTableViewModel::from_repo_data(&data, &repo, &Theme::default(), &UiConfig::default())
+                .with_filter(&data, None, &PrSizeThresholds::default());
+        assert_eq!(vm.rows.len(), 3);
+        assert!(vm.header.title.contains("filter: author dependabot"));
+    }
+
+    #[test]
+    fn test_notes_mark_rows_and_show_under_cursor() {
+        let repo = Repository::new("org", "repo", "main");
+        let mut data = repo_data();
+        let notes = HashMap::from([(repo.watched_pr(2), "waiting on upstream".to_string())]);
+
+        let vm =
+            PrTableViewModel::from_repo_data(&data, &repo, &Theme::default(), &UiConfig::default())
+                .with_notes(&notes, &repo);
+        assert_eq!(vm.rows[0].title, "Draft PR");
+        assert_eq!(vm.rows[1].title, "📝 Blocked PR");
+        assert_eq!(vm.note, None);
+
+        data.selected_pr = 1;
+        let vm =
+            PrTableViewModel::from_repo_data(&data, &repo, &Theme::default(), &UiConfig::default())
+                .with_notes(&notes, &repo);
+        assert_eq!(vm.note.as_deref(), Some("waiting on upstream"));
+    }
+
+    #[test]
+    fn test_unseen_rows_are_marked_and_bold() {
+        let repo = Repository::new("org", "repo", "main");
+        let data = repo_data();
+        let vm =
+            PrTableViewModel::from_repo_data(&data, &repo, &Theme::default(), &UiConfig::default())
+                .with_notes(
+                    &HashMap::from([(repo.watched_pr(2), "n".to_string())]),
+                    &repo,
+                )
+                .with_unseen(&HashSet::from([2]), true);
+        assert_eq!(vm.rows[0].title, "Draft PR");
+        assert!(!vm.rows[0].bold_title);
+        assert_eq!(vm.rows[1].title, "● 📝 Blocked PR");
+        assert!(vm.rows[1].bold_title);
+
+        let vm =
+            PrTableViewModel::from_repo_data(&data, &repo, &Theme::default(), &UiConfig::default())
+                .with_unseen(&HashSet::from([2]), false);
+        assert_eq!(vm.rows[1].title, "● Blocked PR");
+        assert!(!vm.rows[1].bold_title);
+    }
+
+    #[test]
+    fn test_attention_sort_tags_rows() {
+        let repo = Repository::new("org", "repo", "main");
+        let mut data = repo_data();
+        data.prs[1].reviewers = Some(crate::domain_models::PrReviewers {
+            number: 2,
+            requested: vec!["alice".to_string()],
+            ..Default::default()
+        });
+        let vm = |sort| {
+            PrTableViewModel::from_repo_data(&data, &repo, &Theme::default(), &UiConfig::default())
+                .with_sort(sort, &data, Some("alice"))
+        };
+
+        let newest = vm(PrSort::Newest);
+        assert!(!newest.attention_column);
+        assert_eq!(newest.rows[1].attention, None);
+
+        let attention = vm(PrSort::Attention);
+        assert!(attention.attention_column);
+        assert_eq!(attention.rows[0].attention, None);
+        assert_eq!(attention.rows[1].attention, Some("review requested"));
+        assert!(attention.header.title.contains("sort: attention"));
+
+        let size = vm(PrSort::Size);
+        assert!(!size.attention_column);
+        assert_eq!(size.rows[1].attention, None);
+        assert!(size.header.title.contains("sort: size"));
+    }
+
+    #[test]
+    fn test_size_badge_until_stats_are_fetched() {
+        let repo = Repository::new("org", "repo", "main");
+        let mut data = repo_data();
+        data.prs[0] = data.prs[0].clone().with_stats(40, 11, 3);
+        let ui = UiConfig::default();
+
+        let vm = PrTableViewModel::from_repo_data(&data, &repo, &Theme::default(), &ui);
+        assert_eq!(vm.rows[0].size_badge.0, "M");
+        assert!(!vm.rows[0].stats_pending);
+        assert_eq!(vm.rows[1].size_badge.0, "…");
+        assert!(vm.rows[1].stats_pending);
+
+        let ui = UiConfig {
+            pr_size: PrSizeThresholds {
+                s: 60,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let vm = PrTableViewModel::from_repo_data(&data, &repo, &Theme::default(), &ui);
+        assert_eq!(vm.rows[0].size_badge.0, "S");
+    }
+
+    #[test]
+    fn test_status_hint_of_cursor_pr() {
+        let repo = Repository::new("org", "repo", "main");
+        let mut data = repo_data();
+        data.prs[1].mergeable = MergeableStatus::WaitingApproval;
+
+        let vm =
+            PrTableViewModel::from_repo_data(&data, &repo, &Theme::default(), &UiConfig::default());
+        assert_eq!(vm.status_hint, None);
+
+        data.selected_pr = 1;
+        let vm =
+            PrTableViewModel::from_repo_data(&data, &repo, &Theme::default(), &UiConfig::default());
+        assert_eq!(vm.status_hint, MergeableStatus::WaitingApproval.hint());
+        assert_eq!(vm.rows[1].status_text, "✋ Awaiting Approval");
+    }
+
+    #[test]
+    fn test_running_checks_show_their_progress() {
+        let repo = Repository::new("org", "repo", "main");
+        let mut data = repo_data();
+        for pr in &mut data.prs {
+            pr.mergeable = MergeableStatus::Checking;
+            pr.ci_budget = Some(crate::domain_models::CiBudget {
+                started_at: Utc::now() - chrono::TimeDelta::minutes(6),
+                usual_seconds: Some(14 * 60),
+            });
+        }
+        data.prs[1].ci_budget.as_mut().unwrap().usual_seconds = Some(4 * 60);
+
+        let vm =
+            PrTableViewModel::from_repo_data(&data, &repo, &Theme::default(), &UiConfig::default());
+        assert_eq!(vm.rows[0].status_text, "⏳ 6/14m");
+        assert_eq!(vm.rows[0].status_color, Color::Yellow);
+        // Overdue checks stand out instead of counting down below zero
+        assert_eq!(vm.rows[1].status_text, "⏳ 6/4m");
+        assert_eq!(vm.rows[1].status_color, Color::LightRed);
+    }
+
+    #[test]
+    fn test_rows_mark_running_operations() {
+        let repo = Repository::new("org", "repo", "main");
+        let in_flight = HashSet::from([(repo.watched_pr(2), OperationType::Merge)]);
+        let vm = PrTableViewModel::from_repo_data(
+            &repo_data(),
+            &repo,
+            &Theme::default(),
+            &UiConfig::default(),
+        )
+        .with_in_flight(&in_flight, &repo);
+
+        assert_eq!(vm.rows[0].title, "Draft PR");
+        assert_eq!(vm.rows[1].title, "⋯ Merging Blocked PR");
+    }
+
+    #[test]
+    fn test_rows_mark_updates_opened_in_other_repos() {
+        let repo = Repository::new("org", "repo", "main");
+        let bump = |number| {
+            Pr::new(
+                number,
+                "Bump serde from 1.0.1 to 1.0.2",
+                "dependabot[bot]",
+                "ghi",
+            )
+            .with_branches("dependabot/cargo/serde-1.0.2", "main")
+            .with_detected_dependency_update()
+        };
+        let mut data = repo_data();
+        data.prs.push(bump(3));
+        let mut index = DuplicateUpdateIndex::default();
+        index.update_repo(&repo, &data.prs);
+        let render = |index: &DuplicateUpdateIndex| {
+            PrTableViewModel::from_repo_data(&data, &repo, &Theme::default(), &UiConfig::default())
+                .with_duplicates(index, &data)
+        };
+
+        // Only in this repository: no marker
+        assert_eq!(
+            render(&index).rows[2].title,
+            "Bump serde from 1.0.1 to 1.0.2"
+        );
+
+        index.update_repo(&Repository::new("org", "web", "main"), &[bump(8)]);
+        index.update_repo(&Repository::new("org", "cli", "main"), &[bump(5)]);
+        let vm = render(&index);
+        assert_eq!(vm.rows[0].title, "Draft PR");
+        assert_eq!(vm.rows[2].title, "×3 repos Bump serde from 1.0.1 to 1.0.2");
+    }
+
+    #[test]
+    fn test_header_marks_cached_prs() {
+        let repo = Repository::new("org", "repo", "main");
+        let mut data = repo_data();
+        data.loading_state = LoadingState::Loaded;
+        data.from_cache = Some(chrono::Local::now() - chrono::TimeDelta::minutes(52));
+
+        let vm =
+            PrTableViewModel::from_repo_data(&data, &repo, &Theme::default(), &UiConfig::default());
+        assert_eq!(vm.header.status_text, "cached 52m ago — refreshing…");
+
+        data.from_cache = None;
+        let vm =
+            PrTableViewModel::from_repo_data(&data, &repo, &Theme::default(), &UiConfig::default());
+        assert!(!vm.header.status_text.contains("cached"));
+    }
+
+    #[test]
+    fn test_header_shows_the_weekly_pulse() {
+        let repo = Repository::new("org", "repo", "main");
+        let mut data = repo_data();
+        let vm =
+            PrTableViewModel::from_repo_data(&data, &repo, &Theme::default(), &UiConfig::default());
+        assert!(!vm.header.title.contains("this week"));
+
+        data.pulse = Some(RepoPulse {
+            opened: 12,
+            merged: 9,
+        });
+        let vm =
+            PrTableViewModel::from_repo_data(&data, &repo, &Theme::default(), &UiConfig::default());
+        assert!(vm.header.title.ends_with("org/repo@main ↑12 ↓9 this week "));
+        let vm = PrTableViewModel::from_repo_data(
+            &data,
+            &repo,
+            &Theme::high_contrast(),
+            &UiConfig::default(),
+        );
+        assert!(vm
+            .header
+            .title
+            .ends_with("@main 12 opened 9 merged this week "));
+    }
+
+    #[test]
+    fn test_snoozed_rows_are_hidden_or_dimmed() {
+        let repo = Repository::new("org", "repo", "main");
+        let mut data = repo_data();
+        data.selected_pr = 1;
+        let snoozed = HashMap::from([(
+            repo.watched_pr(1),
+            SnoozedPr {
+                pr: repo.watched_pr(1),
+                head_sha: "abc".to_string(),
+                updated_at: chrono::Utc::now(),
+                until: None,
+            },
+        )]);
+        let theme = Theme::default();
+
+        let vm = PrTableViewModel::from_repo_data(&data, &repo, &theme, &UiConfig::default())
+            .with_snoozed(&snoozed, false, &repo, &theme);
+        assert_eq!(vm.rows.len(), 1);
+        assert_eq!(vm.rows[0].title, "Blocked PR");
+        assert_eq!(vm.selected_index, Some(0));
+        assert!(vm.header.title.ends_with("· 1 snoozed (hidden) "));
+        // The state keeps every PR for bulk operations
+        assert_eq!(data.prs.len(), 2);
+
+        let vm = PrTableViewModel::from_repo_data(&data, &repo, &theme, &UiConfig::default())
+            .with_snoozed(&snoozed, true, &repo, &theme);
+        assert_eq!(vm.rows.len(), 2);
+        assert_eq!(vm.rows[0].title, "💤 Draft PR");
+        assert_eq!(vm.selected_index, Some(1));
+    }
+
+    #[test]
+    fn test_update_filter_keeps_matching_bot_prs_with_badge() {
+        let repo = Repository::new("org", "repo", "main");
+        let mut data = repo_data();
+        let mut bump = Pr::new(
+            3,
+            "Bump serde from 1.0.1 to 2.0.0",
+            "dependabot[bot]",
+            "ghi",
+        )
+        .with_branches("dependabot/cargo/serde-2.0.0", "main")
+        .with_detected_dependency_update();
+        bump.mergeable = MergeableStatus::Ready;
+        data.prs.push(bump);
+        data.selected_pr = 2;
+        let theme = Theme::default();
+
+        let vm = PrTableViewModel::from_repo_data(&data, &repo, &theme, &UiConfig::default());
+        assert_eq!(vm.rows[0].update_badge, None);
+        assert_eq!(
+            vm.rows[2].update_badge,
+            Some(("major".to_string(), Color::Red))
+        );
+
+        data.current_filter = PrFilter::parse("update:major").unwrap();
+        let vm = PrTableViewModel::from_repo_data(&data, &repo, &theme, &UiConfig::default())
+            .with_filter(&data, None, &PrSizeThresholds::default());
+        assert_eq!(vm.rows.len(), 1);
+        assert_eq!(vm.rows[0].number, 3);
+        assert_eq!(vm.selected_index, Some(0));
+        assert!(vm.header.title.contains("filter: update:major"));
+    }
+
+    #[test]
+    fn test_fork_prs_show_user_and_branch() {
+        let repo = Repository::new("org", "repo", "main");
+        let mut data = repo_data();
+        let fork = Pr::new(3, "Fix typo", "alice", "ghi")
+            .with_branches("main", "main")
+            .with_fork("alice", "repo", false);
+        assert_eq!(
+            fork.update_branch_blocker(),
+            Some("its fork does not allow edits from maintainers")
+        );
+        data.prs.push(fork);
+
+        let vm =
+            PrTableViewModel::from_repo_data(&data, &repo, &Theme::default(), &UiConfig::default());
+        assert_eq!(vm.rows[0].fork_head, None);
+        assert_eq!(vm.rows[2].fork_head.as_deref(), Some("alice:main"));
+    }
+
+    #[test]
+    fn test_rows_name_unusual_base_branches() {
+        let repo = Repository::new("org", "repo", "main");
+        let mut data = repo_data();
+        data.prs[0].base_branch = "main".to_string();
+        data.prs.push(
+            Pr::new(3, "Bump serde", "dependabot[bot]", "ghi").with_branches("bump", "release/1.4"),
+        );
+        // Stacked on #3, named by the stack marker instead
+        data.prs
+            .push(Pr::new(4, "Follow-up", "alice", "jkl").with_branches("follow-up", "bump"));
+
+        let vm =
+            PrTableViewModel::from_repo_data(&data, &repo, &Theme::default(), &UiConfig::default());
+        let base = |row: usize| vm.rows[row].base_branch.as_deref();
+        assert_eq!(base(0), None);
+        // The base of PRs loaded without branches is unknown
+        assert_eq!(base(1), None);
+        assert_eq!(base(2), Some("release/1.4"));
+        assert_eq!(base(3), None);
+        assert!(vm.rows[3].title.contains("stacked on #3"));
+    }
+}
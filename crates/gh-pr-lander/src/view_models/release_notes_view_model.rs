@@ -0,0 +1,108 @@
+//! View model for the release notes popup
+//!
+//! Pre-computes the title and the markdown lines shown from the scroll
+//! position, or the message shown instead while fetching or after a failure.
+
+use crate::state::ReleaseNotesState;
+
+/// View model for the release notes popup
+#[derive(Debug, Clone)]
+pub struct ReleaseNotesViewModel {
+    /// Panel title
+    pub title: String,
+    /// Markdown lines from the scroll position on
+    pub lines: Vec<String>,
+    /// Scroll position of all lines ("12-30 of 42")
+    pub position: String,
+    /// Message shown instead of the notes (fetching or error)
+    pub message: Option<String>,
+    /// Message is an error
+    pub is_error: bool,
+}
+
+impl ReleaseNotesViewModel {
+    /// Build view model from the release notes state, for `height` lines
+    pub fn from_state(state: &ReleaseNotesState, height: usize) -> Self {
+        let mut vm = Self {
+            title: "Release notes".to_string(),
+            lines: Vec::new(),
+            position: String::new(),
+            message: None,
+            is_error: false,
+        };
+        let Some((repo, since)) = &state.target else {
+            vm.message = Some("No repository".to_string());
+            return vm;
+        };
+        vm.title = format!(
+            "Release notes of {} since {}",
+            repo.full_display_name(),
+            since
+        );
+
+        if let Some(error) = &state.error {
+            vm.message = Some(error.clone());
+            vm.is_error = true;
+            return vm;
+        }
+        let Some(markdown) = &state.markdown else {
+            vm.message = Some(format!(
+                "Fetching the PRs merged into {} since {}...",
+                repo.branch, since
+            ));
+            return vm;
+        };
+
+        let total = state.line_count();
+        // The last page stays full when scrolled to the last line
+        let first = state.scroll_offset.min(total.saturating_sub(height));
+        vm.lines = markdown
+            .lines()
+            .skip(first)
+            .take(height)
+            .map(str::to_string)
+            .collect();
+        vm.position = format!(
+            "{} PRs, lines {}-{} of {}",
+            state.pr_count,
+            (first + 1).min(total),
+            first + vm.lines.len(),
+            total
+        );
+        vm
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::{Repository, SinceRef};
+
+    #[test]
+    fn test_last_page_stays_full() {
+        let markdown: String = (1..=10).map(|line| format!("line {}\n", line)).collect();
+        let state = ReleaseNotesState {
+            target: Some((
+                Repository::new("acme", "api", "main"),
+                SinceRef::Tag("v1.0.0".to_string()),
+            )),
+            markdown: Some(markdown),
+            pr_count: 8,
+            error: None,
+            scroll_offset: 9,
+        };
+        let vm = ReleaseNotesViewModel::from_state(&state, 4);
+        assert_eq!(vm.lines, ["line 7", "line 8", "line 9", "line 10"]);
+        assert_eq!(vm.position, "8 PRs, lines 7-10 of 10");
+
+        let loading = ReleaseNotesState {
+            markdown: None,
+            ..state
+        };
+        let vm = ReleaseNotesViewModel::from_state(&loading, 4);
+        assert_eq!(
+            vm.message.as_deref(),
+            Some("Fetching the PRs merged into main since v1.0.0...")
+        );
+    }
+}
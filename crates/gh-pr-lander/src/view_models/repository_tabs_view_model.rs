@@ -3,13 +3,43 @@
 //! Pre-computes all presentation data for the repository tab bar,
 //! separating business logic from view rendering.
 
-use crate::domain_models::LoadingState;
-use crate::state::AppState;
+use crate::domain_models::{LoadingState, Repository};
+use crate::keymap::CommandId;
+use crate::state::{AppState, InaccessibleRepo};
+use crate::utils::format::format_clock;
+use gh_client::CiState;
 use ratatui::style::{Color, Modifier, Style};
 
 /// Hourglass icon for loading state
 const HOURGLASS_ICON: &str = "⏳";
 
+/// Marker of tabs not loaded yet, they load once opened
+const NOT_LOADED_MARKER: &str = "○";
+
+/// Marker of tabs whose PRs failed to load
+const LOAD_ERROR_MARKER: &str = "⚠";
+
+/// Marker of the selected tab in themes with text labels
+const SELECTED_TAB_MARKER: &str = "▸";
+
+/// Marker of repositories the token cannot access
+const INACCESSIBLE_MARKER: &str = "🔒";
+
+/// Badge of repositories with PRs updated since they were last seen
+const UNSEEN_BADGE: &str = "●";
+
+/// Glyph of the CI state of a repository's tracked branch, if known
+pub fn branch_status_glyph(ci: Option<CiState>) -> Option<&'static str> {
+    match ci? {
+        CiState::Success => Some("✓"),
+        CiState::Failure => Some("✗"),
+        CiState::Pending => Some("●"),
+        CiState::Queued => Some("◌"),
+        CiState::WaitingApproval => Some("⏸"),
+        CiState::Unknown => None,
+    }
+}
+
 /// View model for the entire repository tab bar
 #[derive(Debug, Clone)]
 pub struct RepositoryTabsViewModel {
@@ -20,8 +50,12 @@ pub struct RepositoryTabsViewModel {
     pub selected_index: usize,
     /// Help hint shown at the far left (e.g., "?" for key bindings)
     pub help_hint: TabHintViewModel,
+    /// Name of the active workspace profile, when there are several
+    pub profile: Option<TabHintViewModel>,
     /// Hint text shown at the end (e.g., "p → a" for add repo)
     pub hint: TabHintViewModel,
+    /// Read-only badge (no token configured), right-aligned
+    pub read_only: Option<TabHintViewModel>,
     /// Background color for the entire tab line
     pub line_bg: Color,
 }
@@ -76,23 +110,73 @@ impl RepositoryTabsViewModel {
             .enumerate()
             .map(|(idx, repo)| {
                 let is_selected = idx == selected_index;
-                let is_loading = Self::is_repo_loading(state, idx);
-
-                // Build title: "org/repo@branch"
-                let title = repo.repo.to_string();
+                let loading_state = state
+                    .main_view
+                    .repo_data
+                    .get(&idx)
+                    .map(|data| &data.loading_state);
+                let is_inaccessible = state
+                    .main_view
+                    .repo_data
+                    .get(&idx)
+                    .is_some_and(|data| data.inaccessible.is_some());
+
+                // Build title: "repo", plus the active login on the selected tab
+                let login = state.main_view.account_logins.get(&repo.account_key());
+                let title = match login {
+                    Some(login) if is_selected => format!("{} @{}", repo.repo, login),
+                    _ => repo.repo.to_string(),
+                };
 
-                // Add loading icon if needed
-                let display_text = if is_loading {
-                    format!("{} {}", HOURGLASS_ICON, title)
+                // Count the PRs updated since they were last seen
+                let unseen = state.main_view.unseen_prs(idx).len();
+                let title = if unseen > 0 {
+                    format!("{} {}{}", title, UNSEEN_BADGE, unseen)
                 } else {
                     title
                 };
 
+                // Show whether the tracked branch itself is red or green
+                let branch_ci = state
+                    .main_view
+                    .repo_data
+                    .get(&idx)
+                    .and_then(|data| branch_status_glyph(data.branch_ci));
+                let title = match branch_ci {
+                    Some(glyph) => format!("{} {}", title, glyph),
+                    None => title,
+                };
+
+                // Mark tabs not showing PRs with why
+                let marker = match loading_state {
+                    _ if is_inaccessible => Some(INACCESSIBLE_MARKER),
+                    None | Some(LoadingState::Idle) => Some(NOT_LOADED_MARKER),
+                    Some(LoadingState::Loading) => Some(HOURGLASS_ICON),
+                    Some(LoadingState::Error(_)) => Some(LOAD_ERROR_MARKER),
+                    Some(LoadingState::Loaded) => None,
+                };
+                let display_text = match marker {
+                    Some(marker) => format!("{} {}", marker, title),
+                    None => title,
+                };
+
+                // Mark the selected tab by glyph, not only by its colors
+                let display_text = if is_selected && theme.text_labels {
+                    format!("{} {}", SELECTED_TAB_MARKER, display_text)
+                } else {
+                    display_text
+                };
+
                 let (style, tab_bg) = if is_selected {
                     (style_tab_active, theme.tab_active_bg)
                 } else {
                     (style_tab_inactive, theme.tab_line_bg)
                 };
+                let style = if is_inaccessible {
+                    style.add_modifier(Modifier::DIM)
+                } else {
+                    style
+                };
 
                 (display_text, style, tab_bg)
             })
@@ -106,7 +190,7 @@ impl RepositoryTabsViewModel {
                 let style_separator = Style::default().fg(*tab_bg).bg(theme.tab_line_bg);
 
                 // Width: left_sep(1) + padding(2) + text + padding(2) + right_sep(1)
-                let width = display_text.chars().count() as u16 + 6;
+                let width = gh_text_width::width(display_text) as u16 + 6;
 
                 TabViewModel {
                     display_text: display_text.clone(),
@@ -131,9 +215,27 @@ impl RepositoryTabsViewModel {
             style: Style::default()
                 .fg(theme.tab_hint_fg)
                 .add_modifier(Modifier::DIM),
-            width: label.len() as _,
+            width: gh_text_width::width(&label) as _,
         };
 
+        // Profile name next to the help hint, only worth the space with several profiles
+        let profile = state
+            .main_view
+            .profile
+            .as_ref()
+            .filter(|profile| profile.names.len() > 1)
+            .map(|profile| {
+                let text = format!(" {} ", profile.name);
+                TabHintViewModel {
+                    width: gh_text_width::width(&text) as _,
+                    text,
+                    style: Style::default()
+                        .fg(theme.accent_primary)
+                        .bg(theme.tab_line_bg)
+                        .add_modifier(Modifier::BOLD),
+                }
+            });
+
         // Build add repo hint (right side)
         let label = format!(
             " {} ",
@@ -145,28 +247,32 @@ impl RepositoryTabsViewModel {
         let hint = TabHintViewModel {
             text: label.clone(),
             style: help_hint.style,
-            width: label.len() as _,
+            width: gh_text_width::width(&label) as _,
         };
 
+        let read_only = state.splash.read_only().then(|| {
+            let text = " read-only: no token ".to_string();
+            TabHintViewModel {
+                width: gh_text_width::width(&text) as _,
+                text,
+                style: Style::default()
+                    .fg(theme.status_warning)
+                    .bg(theme.tab_line_bg)
+                    .add_modifier(Modifier::BOLD),
+            }
+        });
+
         Self {
             tabs,
             selected_index,
             help_hint,
+            profile,
             hint,
+            read_only,
             line_bg: theme.tab_line_bg,
         }
     }
 
-    /// Check if a repository is in a loading state
-    fn is_repo_loading(state: &AppState, repo_idx: usize) -> bool {
-        state.main_view.repo_data.get(&repo_idx).is_none_or(|data| {
-            matches!(
-                data.loading_state,
-                LoadingState::Idle | LoadingState::Loading
-            )
-        })
-    }
-
     /// Get the style for active (selected) tabs
     #[allow(dead_code)]
     pub fn active_tab_style(state: &AppState) -> Style {
@@ -190,7 +296,7 @@ impl RepositoryTabsViewModel {
 pub enum MainContentViewModel {
     /// Show empty state with a message
     Empty(EmptyStateViewModel),
-    /// Show the PR table
+    /// Show the PR table (the selected repository and its data exist)
     PrTable,
 }
 
@@ -240,6 +346,37 @@ impl EmptyStateViewModel {
         }
     }
 
+    /// Create view model for a repository the token cannot access
+    pub fn inaccessible(
+        state: &AppState,
+        repo: &Repository,
+        inaccessible: &InaccessibleRepo,
+    ) -> Self {
+        let theme = &state.theme;
+        // Commands without a key binding are named for the command palette
+        let key = |command: CommandId| match state.keymap.compact_hint_for_command(command) {
+            Some(key) => format!("'{}'", key),
+            None => format!("\"{}\" in the command palette", command.title()),
+        };
+        Self {
+            message: format!(
+                "{} {}/{} is not accessible with your token: {} (last tried {}). \
+                 Retry now with {}, check your access in the browser with {} \
+                 or remove it with {}.",
+                INACCESSIBLE_MARKER,
+                repo.org,
+                repo.repo,
+                inaccessible.error,
+                format_clock(inaccessible.last_attempt.to_utc(), &state.app_config.ui),
+                key(CommandId::PrRefresh),
+                key(CommandId::RepositoryOpenInBrowser),
+                key(CommandId::RepositoryRemove),
+            ),
+            border_color: theme.accent_primary,
+            text_style: theme.muted(),
+        }
+    }
+
     /// Create view model for error state
     pub fn error(state: &AppState, error_msg: &str) -> Self {
         let theme = &state.theme;
@@ -260,20 +397,29 @@ pub fn determine_main_content(state: &AppState) -> MainContentViewModel {
         return MainContentViewModel::Empty(EmptyStateViewModel::no_repos(state));
     }
 
-    // Get repository data
-    let repo_data = state.main_view.repo_data.get(&repo_idx);
+    // Selected repository and its data, both missing while the data has not
+    // loaded yet or right after the repository was removed
+    let (Some(repo), Some(repo_data)) = (
+        state.main_view.repositories.get(repo_idx),
+        state.main_view.repo_data.get(&repo_idx),
+    ) else {
+        return MainContentViewModel::Empty(EmptyStateViewModel::loading(state));
+    };
 
     // Check loading state
-    match repo_data.map(|rd| &rd.loading_state) {
-        None | Some(LoadingState::Idle) | Some(LoadingState::Loading) => {
+    match &repo_data.loading_state {
+        LoadingState::Idle | LoadingState::Loading => {
             MainContentViewModel::Empty(EmptyStateViewModel::loading(state))
         }
-        Some(LoadingState::Error(err)) => {
-            MainContentViewModel::Empty(EmptyStateViewModel::error(state, err))
-        }
-        Some(LoadingState::Loaded) => {
+        LoadingState::Error(err) => match repo_data.inaccessible {
+            Some(ref inaccessible) => MainContentViewModel::Empty(
+                EmptyStateViewModel::inaccessible(state, repo, inaccessible),
+            ),
+            None => MainContentViewModel::Empty(EmptyStateViewModel::error(state, err)),
+        },
+        LoadingState::Loaded => {
             // Check if there are any PRs
-            if repo_data.is_some_and(|rd| rd.prs.is_empty()) {
+            if repo_data.prs.is_empty() {
                 MainContentViewModel::Empty(EmptyStateViewModel::no_prs(state))
             } else {
                 MainContentViewModel::PrTable
@@ -281,3 +427,209 @@ pub fn determine_main_content(state: &AppState) -> MainContentViewModel {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::Repository;
+
+    fn state_with_tabs(theme: gh_pr_lander_theme::Theme) -> AppState {
+        let mut state = AppState {
+            theme,
+            ..Default::default()
+        };
+        state.main_view.repositories = vec![
+            Repository::new("org", "first", "main"),
+            Repository::new("org", "second", "main"),
+        ];
+        state
+    }
+
+    #[test]
+    fn test_high_contrast_marks_selected_tab() {
+        let vm = RepositoryTabsViewModel::from_state(&state_with_tabs(
+            gh_pr_lander_theme::Theme::high_contrast(),
+        ));
+        assert!(vm.tabs[0].display_text.starts_with("▸ "));
+        assert!(!vm.tabs[1].display_text.contains('▸'));
+
+        let vm = RepositoryTabsViewModel::from_state(&state_with_tabs(
+            gh_pr_lander_theme::Theme::default(),
+        ));
+        assert!(!vm.tabs[0].display_text.contains('▸'));
+    }
+
+    #[test]
+    fn test_tab_shows_branch_ci_state() {
+        let mut state = state_with_tabs(gh_pr_lander_theme::Theme::default());
+        for (idx, ci) in [(0, CiState::Failure), (1, CiState::Unknown)] {
+            state.main_view.repo_data.insert(
+                idx,
+                crate::state::RepositoryData {
+                    loading_state: LoadingState::Loaded,
+                    branch_ci: Some(ci),
+                    ..Default::default()
+                },
+            );
+        }
+
+        let vm = RepositoryTabsViewModel::from_state(&state);
+        assert_eq!(vm.tabs[0].display_text, "first ✗");
+        assert_eq!(vm.tabs[1].display_text, "second");
+    }
+
+    #[test]
+    fn test_tab_counts_unseen_prs() {
+        let mut state = state_with_tabs(gh_pr_lander_theme::Theme::default());
+        let repo = state.main_view.repositories[0].clone();
+        let prs: Vec<_> = (1..=3)
+            .map(|n| crate::domain_models::Pr::new(n, "PR", "alice", "abc"))
+            .collect();
+        // Seen before its last update, seen as is, never seen
+        let earlier = prs[0].updated_at - chrono::TimeDelta::hours(1);
+        state.main_view.last_seen = [
+            (repo.watched_pr(1), earlier),
+            (repo.watched_pr(2), prs[1].updated_at),
+        ]
+        .into();
+        state.main_view.repo_data.insert(
+            0,
+            crate::state::RepositoryData {
+                loading_state: LoadingState::Loaded,
+                prs,
+                ..Default::default()
+            },
+        );
+
+        let vm = RepositoryTabsViewModel::from_state(&state);
+        assert_eq!(vm.tabs[0].display_text, "first ●1");
+        // Not loaded yet, nothing to count
+        assert_eq!(vm.tabs[1].display_text, "○ second");
+
+        // Hidden snoozed PRs are not counted
+        state.main_view.snoozed.insert(
+            repo.watched_pr(1),
+            gh_pr_config::SnoozedPr {
+                pr: repo.watched_pr(1),
+                head_sha: "abc".to_string(),
+                updated_at: earlier,
+                until: None,
+            },
+        );
+        let vm = RepositoryTabsViewModel::from_state(&state);
+        assert_eq!(vm.tabs[0].display_text, "first");
+    }
+
+    #[test]
+    fn test_tab_marks_its_loading_state() {
+        let mut state = state_with_tabs(gh_pr_lander_theme::Theme::default());
+        state
+            .main_view
+            .repositories
+            .push(Repository::new("org", "third", "main"));
+        for (idx, loading_state) in [
+            (0, LoadingState::Loading),
+            (1, LoadingState::Error("timed out".to_string())),
+        ] {
+            state.main_view.repo_data.insert(
+                idx,
+                crate::state::RepositoryData {
+                    loading_state,
+                    ..Default::default()
+                },
+            );
+        }
+
+        let vm = RepositoryTabsViewModel::from_state(&state);
+        assert_eq!(vm.tabs[0].display_text, "⏳ first");
+        assert_eq!(vm.tabs[1].display_text, "⚠ second");
+        assert_eq!(vm.tabs[2].display_text, "○ third");
+    }
+
+    #[test]
+    fn test_inaccessible_repository_is_locked_and_dimmed() {
+        let mut state = state_with_tabs(gh_pr_lander_theme::Theme::default());
+        state.main_view.repo_data.insert(
+            1,
+            crate::state::RepositoryData {
+                loading_state: LoadingState::Error("Not Found".to_string()),
+                inaccessible: Some(InaccessibleRepo {
+                    error: "Not Found".to_string(),
+                    last_attempt: chrono::Local::now(),
+                }),
+                ..Default::default()
+            },
+        );
+
+        let vm = RepositoryTabsViewModel::from_state(&state);
+        assert_eq!(vm.tabs[1].display_text, "🔒 second");
+        assert!(vm.tabs[1].style.add_modifier.contains(Modifier::DIM));
+
+        state.main_view.selected_repository = 1;
+        match determine_main_content(&state) {
+            MainContentViewModel::Empty(vm) => {
+                assert!(vm.message.starts_with("🔒 org/second is not accessible"));
+                assert!(vm.message.contains("Retry now with 'Ctrl+R'"));
+            }
+            MainContentViewModel::PrTable => panic!("expected the inaccessible state"),
+        }
+    }
+
+    #[test]
+    fn test_read_only_badge() {
+        let mut state = state_with_tabs(gh_pr_lander_theme::Theme::default());
+        assert!(RepositoryTabsViewModel::from_state(&state)
+            .read_only
+            .is_none());
+
+        state.splash.set_stage_status(
+            crate::state::BootstrapStage::ResolveToken,
+            crate::state::StageStatus::Warning("no token".to_string()),
+        );
+        let vm = RepositoryTabsViewModel::from_state(&state);
+        assert_eq!(vm.read_only.unwrap().text.trim(), "read-only: no token");
+    }
+
+    #[test]
+    fn test_profile_shown_with_several_profiles() {
+        let mut state = state_with_tabs(gh_pr_lander_theme::Theme::default());
+        let mut profile = crate::state::ActiveProfile {
+            name: "work".to_string(),
+            names: vec!["work".to_string()],
+            default_filter: None,
+            theme: None,
+        };
+        state.main_view.profile = Some(profile.clone());
+        assert!(RepositoryTabsViewModel::from_state(&state)
+            .profile
+            .is_none());
+
+        profile.names.push("oss".to_string());
+        state.main_view.profile = Some(profile);
+        let vm = RepositoryTabsViewModel::from_state(&state);
+        assert_eq!(vm.profile.unwrap().text, " work ");
+    }
+
+    #[test]
+    fn test_missing_repo_data_shows_loading() {
+        let mut state = state_with_tabs(gh_pr_lander_theme::Theme::default());
+        state.main_view.selected_repository = 1;
+        let loading = |state: &AppState| match determine_main_content(state) {
+            MainContentViewModel::Empty(vm) => vm.message == "Loading pull requests...",
+            MainContentViewModel::PrTable => false,
+        };
+        assert!(loading(&state));
+
+        // Loaded data left behind at an index past the repository list
+        state.main_view.selected_repository = 2;
+        state.main_view.repo_data.insert(
+            2,
+            crate::state::RepositoryData {
+                loading_state: LoadingState::Loaded,
+                prs: vec![crate::domain_models::Pr::new(1, "PR", "alice", "abc")],
+                ..Default::default()
+            },
+        );
+        assert!(loading(&state));
+    }
+}
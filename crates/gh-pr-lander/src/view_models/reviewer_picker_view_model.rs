@@ -0,0 +1,153 @@
+//! View model for the reviewer picker
+//!
+//! Pre-computes the checkbox rows of the reviewer picker, grouped into the
+//! owners suggested by CODEOWNERS and the other collaborators.
+
+use crate::state::ReviewerPickerState;
+use crate::utils::format::format_count;
+
+/// View model for the reviewer picker popup
+#[derive(Debug, Clone)]
+pub struct ReviewerPickerViewModel {
+    /// Popup title (e.g., "Request reviews: PR #12")
+    pub title: String,
+    /// Current filter text
+    pub query: String,
+    /// Rows matching the filter
+    pub rows: Vec<ReviewerRow>,
+    /// Loading, error or empty message shown instead of the rows
+    pub message: Option<String>,
+    /// Number of checked reviewers (e.g., "2 reviewers to request")
+    pub pending: String,
+}
+
+/// A single reviewer row
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewerRow {
+    /// Section title shown above the first row of a section
+    pub header: Option<&'static str>,
+    /// Is this row under the cursor?
+    pub is_selected: bool,
+    /// Checkbox ("[x]" or "[ ]")
+    pub checkbox: &'static str,
+    /// "@login" or "@org/team"
+    pub name: String,
+    /// Changed files owned (e.g., "owns 3 files"), empty for collaborators
+    pub ownership: String,
+}
+
+impl ReviewerPickerViewModel {
+    /// Build view model from reviewer picker state
+    pub fn from_state(state: &ReviewerPickerState) -> Self {
+        let any_suggested = state.choices.iter().any(|c| c.owned_files.is_some());
+        let mut previous_suggested = None;
+        let rows: Vec<ReviewerRow> = state
+            .filtered()
+            .into_iter()
+            .enumerate()
+            .map(|(row, idx)| {
+                let choice = &state.choices[idx];
+                let suggested = choice.owned_files.is_some();
+                let header = if previous_suggested == Some(suggested) {
+                    None
+                } else if suggested {
+                    Some("Suggested by CODEOWNERS")
+                } else if any_suggested {
+                    Some("Collaborators")
+                } else {
+                    None
+                };
+                previous_suggested = Some(suggested);
+                ReviewerRow {
+                    header,
+                    is_selected: row == state.selected,
+                    checkbox: if choice.checked { "[x]" } else { "[ ]" },
+                    name: choice.owner.to_string(),
+                    ownership: choice
+                        .owned_files
+                        .map(|count| format!("owns {}", format_count(count, "file", "files")))
+                        .unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        let message = if state.applying {
+            Some("Requesting reviews...".to_string())
+        } else if state.loading {
+            Some("Loading collaborators...".to_string())
+        } else if let Some(ref error) = state.error {
+            Some(format!("Failed to load collaborators: {}", error))
+        } else if rows.is_empty() {
+            Some("No matching reviewers".to_string())
+        } else {
+            None
+        };
+
+        let checked = state.choices.iter().filter(|c| c.checked).count();
+        let pending = match checked {
+            0 => String::new(),
+            n => format!("{} to request", format_count(n, "reviewer", "reviewers")),
+        };
+
+        Self {
+            title: format!("Request reviews: PR #{}", state.pr_number),
+            query: state.query.clone(),
+            rows,
+            message,
+            pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_models::{Owner, Repository};
+
+    fn picker(suggested: Vec<(Owner, usize)>) -> ReviewerPickerState {
+        let mut state = ReviewerPickerState::new(
+            Repository::new("acme", "api", "main"),
+            12,
+            "bob".to_string(),
+        );
+        state.set_candidates(suggested, vec!["alice".to_string(), "carol".to_string()]);
+        state
+    }
+
+    #[test]
+    fn test_suggested_section_above_collaborators() {
+        let mut state = picker(vec![
+            (Owner::User("alice".to_string()), 3),
+            (Owner::Team("acme/core".to_string()), 1),
+        ]);
+        state.toggle_selected();
+
+        let vm = ReviewerPickerViewModel::from_state(&state);
+        assert_eq!(vm.title, "Request reviews: PR #12");
+        let rows: Vec<(Option<&str>, &str, &str)> = vm
+            .rows
+            .iter()
+            .map(|row| (row.header, row.name.as_str(), row.ownership.as_str()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (Some("Suggested by CODEOWNERS"), "@alice", "owns 3 files"),
+                (None, "@acme/core", "owns 1 file"),
+                (Some("Collaborators"), "@carol", ""),
+            ]
+        );
+        assert_eq!(vm.rows[0].checkbox, "[x]");
+        assert!(vm.rows[0].is_selected);
+        assert_eq!(vm.pending, "1 reviewer to request");
+        assert!(vm.message.is_none());
+    }
+
+    #[test]
+    fn test_without_codeowners_only_collaborators() {
+        let vm = ReviewerPickerViewModel::from_state(&picker(Vec::new()));
+        assert!(vm.rows.iter().all(|row| row.header.is_none()));
+        assert_eq!(vm.rows.len(), 2);
+        assert_eq!(vm.pending, "");
+    }
+}
@@ -1,68 +1,661 @@
 //! Status Bar View Model
 //!
-//! Pre-computes presentation data for the status bar.
+//! Lays out the status bar for a given width. Besides the message the bar has
+//! optional segments (an incident banner, timestamp, running operations,
+//! request rate, context and a key hint); when space runs out the least important ones are dropped
+//! first, then the message is truncated with an ellipsis. A fresh error that
+//! does not fit takes a second line until it is a few seconds old.
 
-use crate::state::{AppState, StatusKind};
+use crate::command_id::CommandId;
+use crate::state::{AppState, StatusKind, StatusMessage};
+use crate::utils::format::{format_clock, format_count, format_duration};
+use chrono::{DateTime, Local, TimeDelta};
+use gh_text_width::truncate;
 use ratatui::style::{Color, Modifier, Style};
+use std::time::{Duration, Instant};
+
+/// Separator between the segments on the right
+pub const SEPARATOR: &str = " • ";
+
+/// Cells of the emoji and the space after it
+const EMOJI_WIDTH: usize = 3;
+
+/// The message keeps at least this many cells before segments are dropped
+const MIN_MESSAGE_WIDTH: usize = 20;
+
+/// How long a truncated error stays expanded onto a second line
+pub const ERROR_EXPAND_SECS: i64 = 5;
+
+/// Spinner frames of a running operation
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Static progress symbol with reduced motion
+const STATIC_PROGRESS: &str = "…";
+
+/// Optional parts of the status bar, most important first
+///
+/// Segments are kept in this order while they fit, so the last ones are the
+/// first to go on narrow terminals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentKind {
+    /// Banner of an active GitHub incident or an unresponsive worker
+    Incident,
+    /// Spinner and elapsed time of running operations
+    Operation,
+    /// Key opening the full message, only while it is truncated
+    KeyHint,
+    /// Action the message came from (e.g. "[Merge]")
+    Context,
+    /// GitHub API requests in the last minute
+    RateLimit,
+    /// Time of the message
+    Timestamp,
+}
+
+/// A segment of the status bar
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusSegment {
+    pub kind: SegmentKind,
+    pub text: String,
+    pub style: Style,
+}
+
+impl StatusSegment {
+    fn new(kind: SegmentKind, text: String, style: Style) -> Self {
+        Self { kind, text, style }
+    }
+
+    /// Width of the segment in cells
+    pub fn width(&self) -> usize {
+        gh_text_width::width(&self.text)
+    }
+}
 
 /// View model for rendering the status bar
 #[derive(Debug, Clone)]
 pub struct StatusBarViewModel {
-    /// Whether there's a message to show
-    pub has_message: bool,
+    /// Timestamp segment left of the message, if it fits
+    pub timestamp: Option<StatusSegment>,
     /// Emoji/icon for the status
     pub emoji: &'static str,
-    /// The message text
+    /// The message, truncated to the space left by the segments
     pub message: String,
-    /// Timestamp formatted for display (e.g., "14:32:05")
-    pub timestamp: String,
-    /// Source action for context
-    pub source: String,
+    /// Rest of an expanded error message, shown on a second line
+    pub continuation: Option<String>,
+    /// Segments right of the message, in display order
+    pub segments: Vec<StatusSegment>,
     /// Foreground style (color based on kind)
     pub message_style: Style,
+    /// Style of the separators
+    pub separator_style: Style,
     /// Background color for the bar
     pub bg_color: Color,
-    /// Style for metadata (timestamp, source)
-    pub metadata_style: Style,
+}
+
+/// Content of the bar before it is fitted to a width
+#[derive(Clone)]
+struct Content {
+    emoji: &'static str,
+    message: String,
+    message_style: Style,
+    /// Candidate segments, in priority order
+    segments: Vec<StatusSegment>,
+    /// An error recent enough to take a second line
+    expandable: bool,
 }
 
 impl StatusBarViewModel {
-    pub fn from_state(state: &AppState) -> Self {
+    /// Lay out the status bar for a terminal `width` cells wide
+    pub fn from_state(state: &AppState, width: u16) -> Self {
+        Self::from_state_at(state, width, Instant::now(), Local::now())
+    }
+
+    /// Build the view model with elapsed times measured at `now` and message
+    /// ages measured at `clock`
+    pub fn from_state_at(
+        state: &AppState,
+        width: u16,
+        now: Instant,
+        clock: DateTime<Local>,
+    ) -> Self {
+        let content = Content::from_state(state, now, clock);
         let theme = &state.theme;
+        let hint = state
+            .keymap
+            .compact_hint_for_command(CommandId::StatusBarShowMessage)
+            .map(|key| {
+                StatusSegment::new(
+                    SegmentKind::KeyHint,
+                    format!("{} Details", key),
+                    theme.key_hint(),
+                )
+            });
+
+        // The key hint only earns its place when the message is cut
+        let layout = Layout::fit(&content, width as usize);
+        let Some(hint) = hint.filter(|_| layout.truncated()) else {
+            return layout.into_view_model(state);
+        };
+        let mut hinted = content.clone();
+        let after_operation = hinted
+            .segments
+            .iter()
+            .take_while(|segment| {
+                matches!(segment.kind, SegmentKind::Incident | SegmentKind::Operation)
+            })
+            .count();
+        hinted.segments.insert(after_operation, hint);
+        Layout::fit(&hinted, width as usize).into_view_model(state)
+    }
+
+    /// Number of terminal rows the status bar takes
+    pub fn height(&self) -> u16 {
+        if self.continuation.is_some() {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Segment of the given kind, if it survived the layout
+    #[cfg(test)]
+    pub fn segment(&self, kind: SegmentKind) -> Option<&StatusSegment> {
+        self.timestamp
+            .iter()
+            .chain(&self.segments)
+            .find(|segment| segment.kind == kind)
+    }
+}
+
+impl Content {
+    fn from_state(state: &AppState, now: Instant, clock: DateTime<Local>) -> Self {
+        let theme = &state.theme;
+        let metadata = Style::default().fg(theme.text_muted);
+        let latest = state.status_bar.latest();
+        let mut segments = Vec::new();
+
+        if state.status_bar.worker_unresponsive {
+            segments.push(StatusSegment::new(
+                SegmentKind::Incident,
+                "Background worker unresponsive".to_string(),
+                Style::default()
+                    .fg(theme.status_error)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        if let Some(incident) = &state.status_bar.incident {
+            segments.push(StatusSegment::new(
+                SegmentKind::Incident,
+                format!("GitHub incident: {}", incident),
+                Style::default()
+                    .fg(theme.status_error)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        // Running operations take the bar, unless a problem was reported since
+        let problem =
+            latest.filter(|msg| matches!(msg.kind, StatusKind::Error | StatusKind::Warning));
+        let mut content = match (state.status_bar.oldest_operation(), problem) {
+            (Some(_), Some(msg)) => {
+                segments.push(StatusSegment::new(
+                    SegmentKind::Operation,
+                    format!("{} {}", spinner(state, now), operations_summary(state, now)),
+                    Style::default().fg(theme.status_warning),
+                ));
+                Self::for_message(state, msg, clock)
+            }
+            (Some(op), None) => {
+                let stuck = op.elapsed_at(now) >= stuck_threshold(state);
+                Self {
+                    emoji: if stuck {
+                        "⚠️"
+                    } else {
+                        StatusKind::Running.emoji()
+                    },
+                    message: operations_summary(state, now),
+                    message_style: Style::default()
+                        .fg(theme.status_warning)
+                        .add_modifier(Modifier::BOLD),
+                    segments: Vec::new(),
+                    expandable: false,
+                }
+            }
+            (None, _) => match latest {
+                Some(msg) => Self::for_message(state, msg, clock),
+                // Welcome message when no status messages
+                None => Self {
+                    emoji: "👋",
+                    message: "Welcome to GitHub PR Lander".to_string(),
+                    message_style: Style::default()
+                        .fg(theme.text_muted)
+                        .add_modifier(Modifier::ITALIC),
+                    segments: Vec::new(),
+                    expandable: false,
+                },
+            },
+        };
+
+        // The operation's own source is its context
+        let context = match (state.status_bar.oldest_operation(), problem) {
+            (Some(op), None) => Some(op.source_action.as_str()),
+            _ => latest.map(|msg| msg.source_action.as_str()),
+        };
+        if let Some(context) = context.filter(|c| !c.is_empty()) {
+            segments.push(StatusSegment::new(
+                SegmentKind::Context,
+                format!("[{}]", context),
+                metadata,
+            ));
+        }
 
-        if let Some(msg) = state.status_bar.latest() {
-            let fg_color = match msg.kind {
-                StatusKind::Running => theme.status_warning,
-                StatusKind::Success => theme.status_success,
-                StatusKind::Error => theme.status_error,
-                StatusKind::Warning => theme.status_warning,
-                StatusKind::Info => theme.status_info,
+        if let Some(usage) = state.status_bar.api_usage {
+            let text = match usage.requests_per_minute {
+                Some(limit) => format!("{}/{} req/min", usage.requests_last_minute, limit),
+                None => format!("{} req/min", usage.requests_last_minute),
+            };
+            let style = if usage.is_high() {
+                Style::default().fg(theme.status_warning)
+            } else {
+                metadata
             };
+            segments.push(StatusSegment::new(SegmentKind::RateLimit, text, style));
+        }
+
+        let timestamp = match (state.status_bar.oldest_operation(), problem) {
+            (Some(_), None) => None,
+            _ => latest,
+        };
+        if let Some(msg) = timestamp {
+            segments.push(StatusSegment::new(
+                SegmentKind::Timestamp,
+                format!(
+                    "[{}]",
+                    format_clock(msg.timestamp.to_utc(), &state.app_config.ui)
+                ),
+                metadata,
+            ));
+        }
+
+        content.segments = segments;
+        content
+    }
+
+    fn for_message(state: &AppState, msg: &StatusMessage, clock: DateTime<Local>) -> Self {
+        let theme = &state.theme;
+        let fg_color = match msg.kind {
+            StatusKind::Running => theme.status_warning,
+            StatusKind::Success => theme.status_success,
+            StatusKind::Error => theme.status_error,
+            StatusKind::Warning => theme.status_warning,
+            StatusKind::Info => theme.status_info,
+        };
 
-            Self {
-                has_message: true,
-                emoji: msg.kind.emoji(),
-                message: msg.message.clone(),
-                timestamp: msg.timestamp.format("%H:%M:%S").to_string(),
-                source: msg.source_action.clone(),
-                message_style: Style::default().fg(fg_color).add_modifier(Modifier::BOLD),
-                bg_color: theme.bg_primary,
-                metadata_style: Style::default().fg(theme.text_muted),
+        Self {
+            emoji: msg.kind.emoji(),
+            message: msg.message.clone(),
+            message_style: Style::default().fg(fg_color).add_modifier(Modifier::BOLD),
+            segments: Vec::new(),
+            expandable: msg.kind == StatusKind::Error
+                && (clock - msg.timestamp).num_seconds() < ERROR_EXPAND_SECS,
+        }
+    }
+}
+
+/// Segments that fit next to the message, and the width left for it
+struct Layout<'a> {
+    content: &'a Content,
+    timestamp: Option<StatusSegment>,
+    segments: Vec<StatusSegment>,
+    message_width: usize,
+    width: usize,
+}
+
+impl<'a> Layout<'a> {
+    /// Keep segments in priority order while the message keeps its minimum width
+    fn fit(content: &'a Content, width: usize) -> Self {
+        let message_len = gh_text_width::width(&content.message);
+        let min_message = message_len.min(MIN_MESSAGE_WIDTH);
+
+        let mut kept: Vec<StatusSegment> = Vec::new();
+        for segment in &content.segments {
+            kept.push(segment.clone());
+            if message_width(&kept, width) < min_message {
+                kept.pop();
+                break;
             }
+        }
+
+        let message_width = message_width(&kept, width);
+        let (timestamp, mut segments): (Vec<_>, Vec<_>) = kept
+            .into_iter()
+            .partition(|segment| segment.kind == SegmentKind::Timestamp);
+        // Display order: incident, operation, request rate, context, key hint
+        segments.sort_by_key(|segment| match segment.kind {
+            SegmentKind::Incident => 0,
+            SegmentKind::Operation => 1,
+            SegmentKind::RateLimit => 2,
+            SegmentKind::Context => 3,
+            SegmentKind::KeyHint => 4,
+            SegmentKind::Timestamp => 5,
+        });
+
+        Self {
+            content,
+            timestamp: timestamp.into_iter().next(),
+            segments,
+            message_width,
+            width,
+        }
+    }
+
+    /// Whether the message does not fit its space
+    fn truncated(&self) -> bool {
+        gh_text_width::width(&self.content.message) > self.message_width
+    }
+
+    fn into_view_model(self, state: &AppState) -> StatusBarViewModel {
+        let content = self.content;
+        let (message, continuation) = if content.expandable && self.truncated() {
+            // 1 cell padding on each side of the second line
+            let (first, rest) = split_at_word(&content.message, self.message_width);
+            let rest = truncate(rest.trim_start(), self.width.saturating_sub(2));
+            (first.trim_end().to_string(), Some(rest.into_owned()))
         } else {
-            // Welcome message when no status messages
-            Self {
-                has_message: true,
-                emoji: "👋",
-                message: "Welcome to GitHub PR Lander".to_string(),
-                timestamp: String::new(),
-                source: String::new(),
-                message_style: Style::default()
-                    .fg(theme.text_muted)
-                    .add_modifier(Modifier::ITALIC),
-                bg_color: theme.bg_primary,
-                metadata_style: Style::default().fg(theme.text_muted),
+            let message = truncate(&content.message, self.message_width);
+            (message.into_owned(), None)
+        };
+
+        StatusBarViewModel {
+            timestamp: self.timestamp,
+            emoji: content.emoji,
+            message,
+            continuation,
+            segments: self.segments,
+            message_style: content.message_style,
+            separator_style: state.theme.muted(),
+            bg_color: state.theme.bg_primary,
+        }
+    }
+}
+
+/// Cells left for the message next to `segments` in a `width` cells bar
+///
+/// The bar has 1 cell padding on each side and 2 cells between the message
+/// and the segments on the right; the timestamp is followed by a space.
+fn message_width(segments: &[StatusSegment], width: usize) -> usize {
+    let mut used = 1 + EMOJI_WIDTH + 2;
+    let mut right = 0;
+    for segment in segments {
+        if segment.kind == SegmentKind::Timestamp {
+            used += segment.width() + 1;
+        } else {
+            if right > 0 {
+                right += gh_text_width::width(SEPARATOR);
             }
+            right += segment.width();
         }
     }
+    if right > 0 {
+        used += right + 1;
+    }
+    width.saturating_sub(used)
+}
+
+/// Split `text` after at most `width` cells, at the last space if there is one
+fn split_at_word(text: &str, width: usize) -> (&str, &str) {
+    let end = gh_text_width::clip(text, width).len();
+    let split = text[..end].rfind(' ').filter(|&idx| idx > 0).unwrap_or(end);
+    text.split_at(split)
+}
+
+/// Spinner frame of the running operations (static with reduced motion)
+fn spinner(state: &AppState, now: Instant) -> &'static str {
+    if state.app_config.ui.reduced_motion {
+        return STATIC_PROGRESS;
+    }
+    let elapsed = state
+        .status_bar
+        .oldest_operation()
+        .map(|op| op.elapsed_at(now))
+        .unwrap_or_default();
+    SPINNER_FRAMES[(elapsed.as_millis() / 100) as usize % SPINNER_FRAMES.len()]
+}
+
+/// Duration after which an operation is marked as possibly stuck
+fn stuck_threshold(state: &AppState) -> Duration {
+    Duration::from_secs(state.app_config.ui.stuck_operation_secs)
+}
+
+/// "3 ops ▸ Merging PR #123 (12s)" for the oldest running operation
+fn operations_summary(state: &AppState, now: Instant) -> String {
+    let operations = &state.status_bar.operations;
+    let Some(op) = operations.first() else {
+        return String::new();
+    };
+    let elapsed = op.elapsed_at(now);
+
+    let mut summary = String::new();
+    if operations.len() > 1 {
+        summary.push_str(&format!(
+            "{} ▸ ",
+            format_count(operations.len(), "op", "ops")
+        ));
+    }
+    let elapsed_text = format_duration(TimeDelta::from_std(elapsed).unwrap_or(TimeDelta::MAX));
+    summary.push_str(&format!("{} ({})", op.label, elapsed_text));
+    if elapsed >= stuck_threshold(state) {
+        summary.push_str(" - may be stuck");
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::StatusBarAction;
+    use crate::reducers::status_bar_reducer::reduce_status_bar;
+    use crate::state::{ApiUsage, OperationId};
+
+    fn start(state: &mut AppState, label: &str) -> OperationId {
+        let id = OperationId::next();
+        state.status_bar = reduce_status_bar(
+            state.status_bar.clone(),
+            &StatusBarAction::started(id, label, "Test"),
+        );
+        id
+    }
+
+    /// An old error with a long message, the request rate and a context
+    fn busy_state() -> AppState {
+        let mut state = AppState::default();
+        state.status_bar = reduce_status_bar(
+            state.status_bar.clone(),
+            &StatusBarAction::error(
+                "Merge failed: Required status check \"ci / build\" is expected but has not been reported yet",
+                "Merge",
+            ),
+        );
+        state.status_bar.api_usage = Some(ApiUsage {
+            requests_last_minute: 42,
+            requests_per_minute: Some(60),
+        });
+        state
+    }
+
+    fn kinds(vm: &StatusBarViewModel) -> Vec<SegmentKind> {
+        vm.timestamp
+            .iter()
+            .chain(&vm.segments)
+            .map(|s| s.kind)
+            .collect()
+    }
+
+    fn later(state: &AppState) -> DateTime<Local> {
+        state.status_bar.latest().unwrap().timestamp + chrono::Duration::minutes(1)
+    }
+
+    #[test]
+    fn test_oldest_operation_with_elapsed_time() {
+        let mut state = AppState::default();
+        start(&mut state, "Merging PR #123");
+        start(&mut state, "Approving PR #7");
+        let now = state.status_bar.operations[0].started_at + Duration::from_secs(12);
+
+        let vm = StatusBarViewModel::from_state_at(&state, 80, now, Local::now());
+        assert_eq!(vm.message, "2 ops ▸ Merging PR #123 (12s)");
+        assert_eq!(vm.emoji, StatusKind::Running.emoji());
+    }
+
+    #[test]
+    fn test_stuck_operation_is_marked() {
+        let mut state = AppState::default();
+        start(&mut state, "Merging PR #123");
+        let now = state.status_bar.operations[0].started_at + Duration::from_secs(65);
+
+        let vm = StatusBarViewModel::from_state_at(&state, 80, now, Local::now());
+        assert_eq!(vm.message, "Merging PR #123 (1m 5s) - may be stuck");
+        assert_eq!(vm.emoji, "⚠️");
+    }
+
+    #[test]
+    fn test_error_is_shown_next_to_operations() {
+        let mut state = AppState::default();
+        start(&mut state, "Merging PR #123");
+        state.status_bar = reduce_status_bar(
+            state.status_bar.clone(),
+            &StatusBarAction::error("Approve failed", "Approve"),
+        );
+        let now = state.status_bar.operations[0].started_at;
+
+        let vm = StatusBarViewModel::from_state_at(&state, 80, now, Local::now());
+        assert_eq!(vm.message, "Approve failed");
+        let operation = vm.segment(SegmentKind::Operation).unwrap();
+        assert!(operation.text.ends_with(" Merging PR #123 (0s)"));
+    }
+
+    #[test]
+    fn test_segments_at_180_columns() {
+        let state = busy_state();
+        let vm = StatusBarViewModel::from_state_at(&state, 180, Instant::now(), later(&state));
+
+        assert!(!vm.message.ends_with('…'));
+        assert_eq!(
+            kinds(&vm),
+            vec![
+                SegmentKind::Timestamp,
+                SegmentKind::RateLimit,
+                SegmentKind::Context
+            ]
+        );
+        assert_eq!(vm.segments[0].text, "42/60 req/min");
+        assert_eq!(vm.segments[1].text, "[Merge]");
+        assert_eq!(vm.height(), 1);
+    }
+
+    #[test]
+    fn test_segments_at_100_columns() {
+        let state = busy_state();
+        let vm = StatusBarViewModel::from_state_at(&state, 100, Instant::now(), later(&state));
+
+        // The truncated message points to the full one
+        assert!(vm.message.ends_with('…'));
+        assert_eq!(
+            kinds(&vm),
+            vec![
+                SegmentKind::Timestamp,
+                SegmentKind::RateLimit,
+                SegmentKind::Context,
+                SegmentKind::KeyHint
+            ]
+        );
+        assert_eq!(vm.segments[2].text, "! Details");
+    }
+
+    #[test]
+    fn test_segments_at_60_columns() {
+        let state = busy_state();
+        let vm = StatusBarViewModel::from_state_at(&state, 60, Instant::now(), later(&state));
+
+        // The least important segments go first
+        assert!(gh_text_width::width(&vm.message) >= MIN_MESSAGE_WIDTH);
+        assert_eq!(kinds(&vm), vec![SegmentKind::Context, SegmentKind::KeyHint]);
+    }
+
+    #[test]
+    fn test_wide_message_is_truncated_by_cells() {
+        let mut state = AppState::default();
+        state.status_bar = reduce_status_bar(
+            state.status_bar.clone(),
+            &StatusBarAction::info("マージしました 🚀🔥 ".repeat(6), "Merge"),
+        );
+        let content = Content::from_state(&state, Instant::now(), later(&state));
+        let layout = Layout::fit(&content, 60);
+        let message_width = layout.message_width;
+        let vm = layout.into_view_model(&state);
+
+        // A wide grapheme that does not fit leaves a cell at most
+        assert!(vm.message.ends_with('…'));
+        assert!(gh_text_width::width(&vm.message) <= message_width);
+        assert!(gh_text_width::width(&vm.message) >= message_width - 1);
+    }
+
+    #[test]
+    fn test_fresh_error_expands_to_second_line() {
+        let state = busy_state();
+        let fresh = state.status_bar.latest().unwrap().timestamp;
+
+        let vm = StatusBarViewModel::from_state_at(&state, 100, Instant::now(), fresh);
+        assert_eq!(vm.height(), 2);
+        let continuation = vm.continuation.as_deref().unwrap();
+        assert_eq!(
+            format!("{} {}", vm.message, continuation),
+            state.status_bar.latest().unwrap().message
+        );
+    }
+
+    #[test]
+    fn test_high_request_rate_is_highlighted() {
+        let mut state = busy_state();
+        state.status_bar.api_usage = Some(ApiUsage {
+            requests_last_minute: 55,
+            requests_per_minute: Some(60),
+        });
+        let vm = StatusBarViewModel::from_state_at(&state, 180, Instant::now(), later(&state));
+
+        let rate = vm.segment(SegmentKind::RateLimit).unwrap();
+        assert_eq!(rate.style.fg, Some(state.theme.status_warning));
+    }
+
+    #[test]
+    fn test_incident_banner_is_kept_first() {
+        let mut state = busy_state();
+        state.status_bar = reduce_status_bar(
+            state.status_bar.clone(),
+            &StatusBarAction::Incident(Some("degraded performance: API Requests".to_string())),
+        );
+        let vm = StatusBarViewModel::from_state_at(&state, 100, Instant::now(), later(&state));
+
+        assert_eq!(vm.segments[0].kind, SegmentKind::Incident);
+        assert_eq!(
+            vm.segments[0].text,
+            "GitHub incident: degraded performance: API Requests"
+        );
+        assert_eq!(kinds(&vm).last(), Some(&SegmentKind::KeyHint));
+    }
+
+    #[test]
+    fn test_unresponsive_worker_banner() {
+        let mut state = busy_state();
+        let vm = StatusBarViewModel::from_state_at(&state, 100, Instant::now(), later(&state));
+        assert!(vm.segment(SegmentKind::Incident).is_none());
+
+        state.status_bar = reduce_status_bar(
+            state.status_bar.clone(),
+            &StatusBarAction::WorkerUnresponsive(true),
+        );
+        let vm = StatusBarViewModel::from_state_at(&state, 100, Instant::now(), later(&state));
+        assert_eq!(vm.segments[0].kind, SegmentKind::Incident);
+        assert_eq!(vm.segments[0].text, "Background worker unresponsive");
+    }
 }
@@ -0,0 +1,180 @@
+//! Actions View
+//!
+//! Recent workflow runs of the current repository's branch, independent of
+//! any PR. Enter opens the build logs of the run under the cursor.
+
+use crate::actions::{Action, ActionsViewAction, AvailableAction, ContextAction, NavigationAction};
+use crate::capabilities::PanelCapabilities;
+use crate::command_id::CommandId;
+use crate::state::AppState;
+use crate::view_models::{ActionsViewModel, StatusBarViewModel};
+use crate::views::status_bar::StatusBarWidget;
+use crate::views::{View, ViewId};
+use ratatui::{prelude::*, widgets::*};
+
+/// Actions view - workflow run history of a branch
+#[derive(Debug, Clone)]
+pub struct ActionsView;
+
+impl ActionsView {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ActionsView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for ActionsView {
+    fn view_id(&self) -> ViewId {
+        ViewId::Actions
+    }
+
+    fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
+        let status_vm = StatusBarViewModel::from_state(state, area.width);
+
+        // Split area to preserve status bar at bottom
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(status_vm.height())])
+            .split(area);
+
+        let vm = ActionsViewModel::from_state(
+            &state.actions_view,
+            &state.theme,
+            &state.app_config.ui,
+            chrono::Utc::now(),
+        );
+        render_runs(f, &vm, &state.theme, chunks[0]);
+
+        f.render_widget(StatusBarWidget(&status_vm), chunks[1]);
+    }
+
+    fn capabilities(&self, _state: &AppState) -> PanelCapabilities {
+        PanelCapabilities::SCROLL_VERTICAL
+            | PanelCapabilities::VIM_NAVIGATION_BINDINGS
+            | PanelCapabilities::ITEM_NAVIGATION
+    }
+
+    fn clone_box(&self) -> Box<dyn View> {
+        Box::new(self.clone())
+    }
+
+    fn translate_navigation(&self, nav: NavigationAction) -> Option<Action> {
+        let action = match nav {
+            NavigationAction::Next => ActionsViewAction::NavigateNext,
+            NavigationAction::Previous => ActionsViewAction::NavigatePrevious,
+            NavigationAction::ToTop => ActionsViewAction::NavigateToTop,
+            NavigationAction::ToBottom => ActionsViewAction::NavigateToBottom,
+            NavigationAction::Left | NavigationAction::Right => return None,
+        };
+        Some(Action::ActionsView(action))
+    }
+
+    fn translate_context_action(&self, action: ContextAction, _state: &AppState) -> Option<Action> {
+        match action {
+            ContextAction::Confirm => Some(Action::ActionsView(ActionsViewAction::OpenSelected)),
+            _ => None,
+        }
+    }
+
+    fn accepts_action(&self, action: &Action) -> bool {
+        matches!(
+            action,
+            Action::ActionsView(_)
+                | Action::BuildLog(_)
+                | Action::ViewContext(_)
+                | Action::Navigate(_)
+                | Action::Global(_)
+        )
+    }
+
+    fn available_actions(&self, _state: &AppState) -> Vec<AvailableAction> {
+        vec![
+            AvailableAction::primary(CommandId::Confirm, "Build Logs"),
+            AvailableAction::navigation(CommandId::NavigateNext, "Down"),
+            AvailableAction::navigation(CommandId::GlobalClose, "Close"),
+        ]
+    }
+}
+
+/// Render the workflow run table
+fn render_runs(
+    f: &mut Frame,
+    vm: &ActionsViewModel,
+    theme: &gh_pr_lander_theme::Theme,
+    area: Rect,
+) {
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", vm.title))
+        .border_style(Style::default().fg(theme.accent_primary))
+        .style(Style::default().bg(theme.bg_panel));
+
+    if let Some(ref message) = vm.message {
+        let paragraph = Paragraph::new(message.clone())
+            .block(block)
+            .style(Style::default().fg(theme.text_muted).bg(theme.bg_panel))
+            .alignment(Alignment::Center);
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let header = Row::new(vec![
+        "", "Workflow", "Branch", "Trigger", "Duration", "Started",
+    ])
+    .style(
+        Style::default()
+            .fg(theme.table_header_fg)
+            .bg(theme.table_header_bg)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let rows: Vec<Row> = vm
+        .rows
+        .iter()
+        .map(|row| {
+            Row::new(vec![
+                Cell::from(row.glyph).style(Style::default().fg(row.glyph_color)),
+                Cell::from(row.workflow.clone()),
+                Cell::from(row.branch.clone()),
+                Cell::from(row.trigger.clone()),
+                Cell::from(row.duration.clone()),
+                Cell::from(row.age.clone()),
+            ])
+            .style(Style::default().fg(theme.table_row_fg))
+        })
+        .collect();
+
+    // Absolute timestamps are wider than relative ones
+    let started_width = vm
+        .rows
+        .iter()
+        .map(|row| gh_text_width::width(&row.age) as u16)
+        .max()
+        .unwrap_or(0)
+        .max(9);
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(2),
+            Constraint::Min(20),
+            Constraint::Length(20),
+            Constraint::Length(20),
+            Constraint::Length(9),
+            Constraint::Length(started_width),
+        ],
+    )
+    .header(header)
+    .block(block)
+    .row_highlight_style(theme.table_selected());
+
+    let mut table_state = TableState::default().with_selected(Some(vm.selected));
+    f.render_stateful_widget(table, area, &mut table_state);
+}
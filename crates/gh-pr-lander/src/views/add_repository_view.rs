@@ -6,7 +6,7 @@
 use crate::actions::{Action, ContextAction, NavigationAction, RepositoryAction, TextInputAction};
 use crate::capabilities::PanelCapabilities;
 use crate::state::{AddRepoField, AddRepoFormState, AppState};
-use crate::views::View;
+use crate::views::{centered_area, percent, View};
 use gh_pr_lander_theme::Theme;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
@@ -100,17 +100,9 @@ fn render(form: &AddRepoFormState, theme: &Theme, area: Rect, f: &mut Frame) {
     f.render_widget(overlay, area);
 
     // Calculate centered area (65% width, fixed height)
-    let popup_width = (area.width * 65 / 100).clamp(55, 80);
+    let popup_width = percent(area.width, 65).clamp(55, 80);
     let popup_height = 17; // Fixed height for the form (includes host field)
-    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
-    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
-
-    let popup_area = Rect {
-        x: area.x + popup_x,
-        y: area.y + popup_y,
-        width: popup_width,
-        height: popup_height,
-    };
+    let popup_area = centered_area(area, popup_width, popup_height);
 
     // Clear the popup area (removes the dim effect for the popup itself)
     f.render_widget(Clear, popup_area);
@@ -0,0 +1,177 @@
+//! Audit Log View
+//!
+//! Floating panel listing the operations the app ran on GitHub for the PR
+//! focused when it was opened, or for its whole repository. Enter reads the
+//! log again.
+
+use crate::actions::{Action, AuditLogAction, AvailableAction, ContextAction, NavigationAction};
+use crate::capabilities::PanelCapabilities;
+use crate::command_id::CommandId;
+use crate::state::AppState;
+use crate::view_models::audit_log_view_model::AuditLogRow;
+use crate::view_models::AuditLogViewModel;
+use crate::views::{centered_area, View, ViewId};
+use ratatui::{prelude::*, widgets::*};
+
+/// Audit log view - table of recorded operations
+#[derive(Debug, Clone)]
+pub struct AuditLogView;
+
+impl AuditLogView {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for AuditLogView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for AuditLogView {
+    fn view_id(&self) -> ViewId {
+        ViewId::AuditLog
+    }
+
+    fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
+        render(state, area, f);
+    }
+
+    fn capabilities(&self, _state: &AppState) -> PanelCapabilities {
+        PanelCapabilities::SCROLL_VERTICAL | PanelCapabilities::VIM_NAVIGATION_BINDINGS
+    }
+
+    fn clone_box(&self) -> Box<dyn View> {
+        Box::new(self.clone())
+    }
+
+    fn translate_navigation(&self, nav: NavigationAction) -> Option<Action> {
+        let action = match nav {
+            NavigationAction::Next => AuditLogAction::NavigateNext,
+            NavigationAction::Previous => AuditLogAction::NavigatePrevious,
+            NavigationAction::ToTop => AuditLogAction::NavigateToTop,
+            NavigationAction::ToBottom => AuditLogAction::NavigateToBottom,
+            NavigationAction::Left | NavigationAction::Right => return None,
+        };
+        Some(Action::AuditLog(action))
+    }
+
+    fn translate_context_action(&self, action: ContextAction, _state: &AppState) -> Option<Action> {
+        match action {
+            ContextAction::Confirm => Some(Action::AuditLog(AuditLogAction::Refresh)),
+            _ => None,
+        }
+    }
+
+    fn accepts_action(&self, action: &Action) -> bool {
+        matches!(
+            action,
+            Action::AuditLog(_) | Action::ViewContext(_) | Action::Navigate(_) | Action::Global(_)
+        )
+    }
+
+    fn available_actions(&self, _state: &AppState) -> Vec<AvailableAction> {
+        vec![
+            AvailableAction::primary(CommandId::Confirm, "Refresh"),
+            AvailableAction::selection(CommandId::AuditLogToggleScope, "PR/Repo"),
+            AvailableAction::navigation(CommandId::GlobalClose, "Close"),
+        ]
+    }
+}
+
+/// Render the audit log panel
+fn render(state: &AppState, area: Rect, f: &mut Frame) {
+    let theme = &state.theme;
+    let vm = AuditLogViewModel::from_state(&state.audit_log);
+
+    // Render dimmed overlay over the entire screen
+    let overlay = Block::default().style(
+        Style::default()
+            .bg(Color::Black)
+            .add_modifier(Modifier::DIM),
+    );
+    f.render_widget(overlay, area);
+
+    // Summary, header, rows and borders
+    let height = (vm.rows.len() as u16).saturating_add(4).min(area.height);
+    let panel_area = centered_area(area, area.width.min(120), height.max(8));
+    f.render_widget(Clear, panel_area);
+
+    let block = Block::default()
+        .title(format!(" {} ", vm.title))
+        .borders(Borders::ALL)
+        .border_style(theme.panel_border())
+        .title_style(theme.panel_title())
+        .title_alignment(Alignment::Center)
+        .style(theme.panel_background());
+    let inner = block.inner(panel_area);
+    f.render_widget(block, panel_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(inner);
+    f.render_widget(
+        Paragraph::new(format!(" {}", vm.summary)).style(theme.muted()),
+        chunks[0],
+    );
+
+    if let Some(ref message) = vm.message {
+        let paragraph = Paragraph::new(message.clone())
+            .style(theme.muted())
+            .alignment(Alignment::Center);
+        f.render_widget(paragraph, chunks[1]);
+        return;
+    }
+
+    // Keep the cursor row in view below the header
+    let visible = chunks[1].height.saturating_sub(1) as usize;
+    let cursor = vm.rows.iter().position(|r| r.is_selected).unwrap_or(0);
+    let offset = (cursor + 1).saturating_sub(visible);
+
+    let header =
+        Row::new(vec!["Time", "PR", "Operation", "By", "Details"]).style(theme.table_header());
+    let rows: Vec<Row> = vm
+        .rows
+        .iter()
+        .skip(offset)
+        .take(visible)
+        .map(|row| build_row(row, theme))
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(20),
+            Constraint::Length(7),
+            Constraint::Length(16),
+            Constraint::Length(14),
+            Constraint::Min(20),
+        ],
+    )
+    .header(header);
+    f.render_widget(table, chunks[1]);
+}
+
+/// Build a table row, highlighting the cursor and failed operations
+fn build_row<'a>(row: &AuditLogRow, theme: &gh_pr_lander_theme::Theme) -> Row<'a> {
+    let style = if row.is_selected {
+        theme.table_row().bg(theme.active_bg).fg(theme.active_fg)
+    } else {
+        theme.table_row()
+    };
+    let operation_style = if row.is_failure {
+        style.patch(theme.error())
+    } else {
+        style
+    };
+    Row::new(vec![
+        Cell::from(format!(" {}", row.time)),
+        Cell::from(row.pr.clone()),
+        Cell::from(row.operation.clone()).style(operation_style),
+        Cell::from(row.login.clone()),
+        Cell::from(row.details.clone()),
+    ])
+    .style(style)
+}
@@ -0,0 +1,137 @@
+//! Bug Report View
+//!
+//! Path input popup for writing a bug report bundle.
+
+use crate::actions::{Action, BugReportAction, TextInputAction};
+use crate::capabilities::PanelCapabilities;
+use crate::state::{AppState, BugReportState};
+use crate::views::{centered_area, percent, View, ViewId};
+use gh_pr_lander_theme::Theme;
+use ratatui::{
+    layout::{Alignment, Margin, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Bug report view - floating panel with the target path input
+#[derive(Debug, Clone)]
+pub struct BugReportView;
+
+impl BugReportView {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for BugReportView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for BugReportView {
+    fn view_id(&self) -> ViewId {
+        ViewId::BugReport
+    }
+
+    fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
+        if let Some(ref popup) = state.bug_report {
+            render_popup(popup, &state.theme, area, f);
+        }
+    }
+
+    fn capabilities(&self, _state: &AppState) -> PanelCapabilities {
+        PanelCapabilities::TEXT_INPUT
+    }
+
+    fn clone_box(&self) -> Box<dyn View> {
+        Box::new(self.clone())
+    }
+
+    fn translate_text_input(&self, input: TextInputAction) -> Option<Action> {
+        let action = match input {
+            TextInputAction::Char(c) => BugReportAction::InputChar(c),
+            TextInputAction::Backspace => BugReportAction::InputBackspace,
+            TextInputAction::ClearLine => BugReportAction::InputClear,
+            TextInputAction::Escape => BugReportAction::Close,
+            TextInputAction::Confirm => BugReportAction::Submit,
+        };
+        Some(Action::BugReport(action))
+    }
+
+    fn accepts_action(&self, action: &Action) -> bool {
+        matches!(
+            action,
+            Action::BugReport(_) | Action::TextInput(_) | Action::Global(_)
+        )
+    }
+}
+
+/// Render the path input as a centered floating window
+fn render_popup(state: &BugReportState, theme: &Theme, area: Rect, f: &mut Frame) {
+    // Render dimmed overlay over the entire screen to create modal effect
+    let overlay = Block::default().style(
+        Style::default()
+            .bg(ratatui::style::Color::Black)
+            .add_modifier(Modifier::DIM),
+    );
+    f.render_widget(overlay, area);
+
+    let popup_width = percent(area.width, 70).clamp(50, 90).min(area.width);
+    let popup_height = 10.min(area.height);
+    let popup_area = centered_area(area, popup_width, popup_height);
+
+    f.render_widget(Clear, popup_area);
+
+    let footer_hint = Line::from(vec![
+        Span::styled(" Enter", theme.key_hint().bold()),
+        Span::styled(" write  ", theme.muted()),
+        Span::styled("Esc", theme.key_hint().bold()),
+        Span::styled(" cancel ", theme.muted()),
+    ]);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Create bug report bundle ")
+        .title_style(theme.panel_title().add_modifier(Modifier::BOLD))
+        .title_bottom(footer_hint)
+        .title_alignment(Alignment::Center)
+        .border_style(theme.panel_border())
+        .style(theme.panel_background());
+    f.render_widget(block, popup_area);
+
+    let inner = popup_area.inner(Margin {
+        horizontal: 2,
+        vertical: 1,
+    });
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Path: ", theme.text()),
+            Span::styled(state.path.clone(), theme.text().bold()),
+            Span::styled("█", theme.key_hint()),
+        ]),
+        Line::from(""),
+    ];
+
+    if state.writing {
+        lines.push(Line::from(Span::styled(
+            "Writing bug report...",
+            theme.muted(),
+        )));
+    } else if let Some(ref error) = state.error {
+        lines.push(Line::from(Span::styled(error.clone(), theme.error())));
+    } else {
+        lines.push(Line::from(Span::styled(
+            "Contains version, state summary, config and recent log lines. Tokens are redacted.",
+            theme.muted(),
+        )));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .style(theme.panel_background());
+    f.render_widget(paragraph, inner);
+}
@@ -2,9 +2,12 @@
 //!
 //! Renders the build log panel with tree navigation.
 
-use crate::actions::{Action, AvailableAction, BuildLogAction, ContextAction, NavigationAction};
+use crate::actions::{
+    Action, AvailableAction, BuildLogAction, ContextAction, NavigationAction, PullRequestAction,
+};
 use crate::capabilities::PanelCapabilities;
 use crate::command_id::CommandId;
+use crate::domain_models::PrTab;
 use crate::state::AppState;
 use crate::view_models::{BuildLogRowStyle, BuildLogViewModel, StatusBarViewModel};
 use crate::views::status_bar::StatusBarWidget;
@@ -33,12 +36,14 @@ impl View for BuildLogView {
     }
 
     fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
+        let status_vm = StatusBarViewModel::from_state(state, area.width);
+
         // Split area to preserve status bar at bottom
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Min(0),    // Build log content
-                Constraint::Length(1), // Status bar (single row)
+                Constraint::Min(0),                     // Build log content
+                Constraint::Length(status_vm.height()), // Status bar
             ])
             .split(area);
 
@@ -47,7 +52,6 @@ impl View for BuildLogView {
         render_build_log_panel(f, &vm, &state.theme, chunks[0]);
 
         // Render status bar at bottom
-        let status_vm = StatusBarViewModel::from_state(state);
         f.render_widget(StatusBarWidget(&status_vm), chunks[1]);
     }
 
@@ -89,7 +93,12 @@ impl View for BuildLogView {
     fn accepts_action(&self, action: &Action) -> bool {
         matches!(
             action,
-            Action::BuildLog(_) | Action::ViewContext(_) | Action::Navigate(_) | Action::Global(_)
+            Action::BuildLog(_)
+                | Action::ViewContext(_)
+                | Action::Navigate(_)
+                | Action::Global(_)
+                // Opens the focused failed job
+                | Action::PullRequest(PullRequestAction::OpenInBrowser(PrTab::Checks))
         )
     }
 
@@ -97,6 +106,10 @@ impl View for BuildLogView {
         vec![
             AvailableAction::primary(CommandId::Confirm, "Toggle"),
             AvailableAction::primary(CommandId::BuildLogNextError, "Next Error"),
+            AvailableAction::primary(CommandId::BuildLogPrevError, "Prev Error"),
+            AvailableAction::primary(CommandId::BuildLogExpandAll, "Expand All"),
+            AvailableAction::primary(CommandId::BuildLogCollapseAll, "Collapse All"),
+            AvailableAction::primary(CommandId::BuildLogToggleTimestamps, "Timestamps"),
             AvailableAction::navigation(CommandId::NavigateNext, "Down"),
             AvailableAction::navigation(CommandId::GlobalClose, "Close"),
         ]
@@ -119,7 +132,11 @@ fn render_build_log_panel(
 
     // Handle loading state
     if view_model.is_loading {
-        let loading_msg = Paragraph::new("Loading build logs...")
+        let mut text = vec![Line::from("Loading build logs...")];
+        if let Some(progress) = &view_model.progress {
+            text.push(Line::from(progress.as_str()));
+        }
+        let loading_msg = Paragraph::new(text)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
@@ -281,17 +298,23 @@ fn render_build_log_tree(
         rows.push(Row::new(vec![Cell::from(row_vm.text.clone())]).style(style));
     }
 
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        // todo: the navigation hints should be dynamic based on keymap, like in other views done
+        .title(" Build Logs | j/k: navigate, Enter: toggle, n/N: next/prev error, Esc: close ")
+        .border_style(Style::default().fg(theme.accent_primary))
+        .style(Style::default().bg(theme.bg_panel));
+    if let Some(progress) = &view_model.progress {
+        block = block.title_bottom(
+            Line::styled(
+                format!(" {} ", progress),
+                Style::default().fg(theme.text_muted),
+            )
+            .right_aligned(),
+        );
+    }
     let table = Table::new(rows, vec![Constraint::Percentage(100)])
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                // todo: the navigation hints should be dynamic based on keymap, like in other views done
-                .title(
-                    " Build Logs | j/k: navigate, Enter: toggle, n/N: next/prev error, Esc: close ",
-                )
-                .border_style(Style::default().fg(theme.accent_primary))
-                .style(Style::default().bg(theme.bg_panel)),
-        )
+        .block(block)
         .style(Style::default().bg(theme.bg_panel));
 
     f.render_widget(table, area);
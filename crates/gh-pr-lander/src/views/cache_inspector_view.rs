@@ -0,0 +1,212 @@
+//! Cache Inspector View
+//!
+//! Floating panel listing the cached GitHub API responses with their size
+//! and age. Entries can be filtered, deleted one by one, or invalidated by a
+//! key pattern. Enter lists the entries again.
+
+use crate::actions::{
+    Action, AvailableAction, CacheInspectorAction, ContextAction, NavigationAction, TextInputAction,
+};
+use crate::capabilities::PanelCapabilities;
+use crate::command_id::CommandId;
+use crate::state::AppState;
+use crate::view_models::cache_inspector_view_model::CacheInspectorRow;
+use crate::view_models::CacheInspectorViewModel;
+use crate::views::{centered_area, View, ViewId};
+use ratatui::{prelude::*, widgets::*};
+
+/// Cache inspector view - table of cached API responses
+#[derive(Debug, Clone)]
+pub struct CacheInspectorView;
+
+impl CacheInspectorView {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for CacheInspectorView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for CacheInspectorView {
+    fn view_id(&self) -> ViewId {
+        ViewId::CacheInspector
+    }
+
+    fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
+        render(state, area, f);
+    }
+
+    fn capabilities(&self, state: &AppState) -> PanelCapabilities {
+        if state.cache_inspector.input.is_some() {
+            // Typing the filter or pattern, arrow keys still move the cursor
+            PanelCapabilities::TEXT_INPUT | PanelCapabilities::ITEM_NAVIGATION
+        } else {
+            PanelCapabilities::SCROLL_VERTICAL | PanelCapabilities::VIM_NAVIGATION_BINDINGS
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn View> {
+        Box::new(self.clone())
+    }
+
+    fn translate_navigation(&self, nav: NavigationAction) -> Option<Action> {
+        let action = match nav {
+            NavigationAction::Next => CacheInspectorAction::NavigateNext,
+            NavigationAction::Previous => CacheInspectorAction::NavigatePrevious,
+            NavigationAction::ToTop => CacheInspectorAction::NavigateToTop,
+            NavigationAction::ToBottom => CacheInspectorAction::NavigateToBottom,
+            NavigationAction::Left | NavigationAction::Right => return None,
+        };
+        Some(Action::CacheInspector(action))
+    }
+
+    fn translate_text_input(&self, input: TextInputAction) -> Option<Action> {
+        let action = match input {
+            TextInputAction::Char(c) => CacheInspectorAction::InputChar(c),
+            TextInputAction::Backspace => CacheInspectorAction::InputBackspace,
+            TextInputAction::ClearLine => CacheInspectorAction::InputClear,
+            TextInputAction::Escape => CacheInspectorAction::InputCancel,
+            TextInputAction::Confirm => CacheInspectorAction::InputConfirm,
+        };
+        Some(Action::CacheInspector(action))
+    }
+
+    fn translate_context_action(&self, action: ContextAction, _state: &AppState) -> Option<Action> {
+        match action {
+            ContextAction::Confirm => Some(Action::CacheInspector(CacheInspectorAction::Refresh)),
+            _ => None,
+        }
+    }
+
+    fn accepts_action(&self, action: &Action) -> bool {
+        matches!(
+            action,
+            Action::CacheInspector(_)
+                | Action::ViewContext(_)
+                | Action::Navigate(_)
+                | Action::TextInput(_)
+                | Action::Global(_)
+        )
+    }
+
+    fn available_actions(&self, _state: &AppState) -> Vec<AvailableAction> {
+        vec![
+            AvailableAction::primary(CommandId::Confirm, "Refresh"),
+            AvailableAction::primary(CommandId::CacheInspectorDelete, "Delete"),
+            AvailableAction::primary(CommandId::CacheInspectorInvalidatePattern, "Invalidate"),
+            AvailableAction::selection(CommandId::CacheInspectorFilter, "Filter"),
+            AvailableAction::selection(CommandId::CacheInspectorCycleSort, "Sort"),
+            AvailableAction::navigation(CommandId::GlobalClose, "Close"),
+        ]
+    }
+}
+
+/// Render the cache inspector panel
+fn render(state: &AppState, area: Rect, f: &mut Frame) {
+    let theme = &state.theme;
+    let vm = CacheInspectorViewModel::from_state(&state.cache_inspector);
+
+    // Render dimmed overlay over the entire screen
+    let overlay = Block::default().style(
+        Style::default()
+            .bg(Color::Black)
+            .add_modifier(Modifier::DIM),
+    );
+    f.render_widget(overlay, area);
+
+    // Summary, input, header, rows and borders
+    let height = (vm.rows.len() as u16).saturating_add(5).min(area.height);
+    let panel_area = centered_area(area, area.width.min(110), height.max(8));
+    f.render_widget(Clear, panel_area);
+
+    let block = Block::default()
+        .title(format!(" {} ", vm.title))
+        .borders(Borders::ALL)
+        .border_style(theme.panel_border())
+        .title_style(theme.panel_title())
+        .title_alignment(Alignment::Center)
+        .style(theme.panel_background());
+    let inner = block.inner(panel_area);
+    f.render_widget(block, panel_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .split(inner);
+    f.render_widget(
+        Paragraph::new(format!(" {}", vm.summary)).style(theme.muted()),
+        chunks[0],
+    );
+    if let Some(ref input) = vm.input {
+        let line = Line::from(vec![
+            Span::styled(format!(" {}", input), theme.text().bold()),
+            Span::styled("█", theme.key_hint()),
+        ]);
+        f.render_widget(Paragraph::new(line), chunks[1]);
+    }
+
+    if let Some(ref message) = vm.message {
+        let paragraph = Paragraph::new(message.clone())
+            .style(theme.muted())
+            .alignment(Alignment::Center);
+        f.render_widget(paragraph, chunks[2]);
+        return;
+    }
+
+    // Keep the cursor row in view below the header
+    let visible = chunks[2].height.saturating_sub(1) as usize;
+    let cursor = vm.rows.iter().position(|r| r.is_selected).unwrap_or(0);
+    let offset = (cursor + 1).saturating_sub(visible);
+
+    let header = Row::new(vec!["Key", "Category", "Size", "Age", ""]).style(theme.table_header());
+    let rows: Vec<Row> = vm
+        .rows
+        .iter()
+        .skip(offset)
+        .take(visible)
+        .map(|row| build_row(row, theme))
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(40),
+            Constraint::Length(18),
+            Constraint::Length(10),
+            Constraint::Length(6),
+            Constraint::Length(6),
+        ],
+    )
+    .header(header);
+    f.render_widget(table, chunks[2]);
+}
+
+/// Build a table row, highlighting the cursor and muting stale entries
+fn build_row<'a>(row: &CacheInspectorRow, theme: &gh_pr_lander_theme::Theme) -> Row<'a> {
+    let style = if row.is_selected {
+        theme.table_row().bg(theme.active_bg).fg(theme.active_fg)
+    } else {
+        theme.table_row()
+    };
+    let (freshness, freshness_style) = if row.is_fresh {
+        ("fresh", style)
+    } else {
+        ("stale", style.patch(theme.muted()))
+    };
+    Row::new(vec![
+        Cell::from(format!(" {}", row.key)),
+        Cell::from(row.category.clone()),
+        Cell::from(row.size.clone()),
+        Cell::from(row.age.clone()),
+        Cell::from(freshness).style(freshness_style),
+    ])
+    .style(style)
+}
@@ -4,7 +4,7 @@ use crate::actions::{
 use crate::capabilities::PanelCapabilities;
 use crate::state::AppState;
 use crate::view_models::CommandPaletteViewModel;
-use crate::views::View;
+use crate::views::{centered_area, percent, View};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Modifier, Stylize},
@@ -104,17 +104,9 @@ fn render(state: &AppState, area: Rect, f: &mut Frame) {
     f.render_widget(overlay, area);
 
     // Calculate centered area (70% width, 60% height)
-    let popup_width = (area.width * 70 / 100).min(100);
-    let popup_height = (area.height * 60 / 100).min(30);
-    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
-    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
-
-    let popup_area = Rect {
-        x: area.x + popup_x,
-        y: area.y + popup_y,
-        width: popup_width,
-        height: popup_height,
-    };
+    let popup_width = percent(area.width, 70).min(100);
+    let popup_height = percent(area.height, 60).min(30);
+    let popup_area = centered_area(area, popup_width, popup_height);
 
     // Clear the popup area (removes the dim effect for the popup itself)
     f.render_widget(Clear, popup_area);
@@ -9,7 +9,7 @@ use crate::actions::{
 use crate::capabilities::PanelCapabilities;
 use crate::state::AppState;
 use crate::view_models::ConfirmationPopupViewModel;
-use crate::views::{View, ViewId};
+use crate::views::{centered_area, percent, View, ViewId};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Margin, Rect},
     style::{Modifier, Style, Stylize},
@@ -41,7 +41,12 @@ impl View for ConfirmationPopupView {
 
     fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
         if let Some(ref popup_state) = state.confirmation_popup {
-            let vm = ConfirmationPopupViewModel::from_state(popup_state, &state.theme);
+            let first_pr = popup_state
+                .intent
+                .pr_numbers()
+                .first()
+                .and_then(|number| state.main_view.find_pr(*number as usize));
+            let vm = ConfirmationPopupViewModel::from_state(popup_state, first_pr, &state.theme);
             render_popup(f, &vm, &state.theme, area);
         }
     }
@@ -109,18 +114,11 @@ fn render_popup(
     );
     f.render_widget(overlay, area);
 
-    // Calculate centered area (60% width, fixed height)
-    let popup_width = (area.width * 60 / 100).clamp(50, 70);
-    let popup_height = 10; // Fixed height for the popup
-    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
-    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
-
-    let popup_area = Rect {
-        x: area.x + popup_x,
-        y: area.y + popup_y,
-        width: popup_width,
-        height: popup_height,
-    };
+    // Calculate centered area (60% width, fixed height plus one row per detail and warning)
+    let popup_width = percent(area.width, 60).clamp(50, 70);
+    let warning_rows = (vm.details.len() + vm.warnings.len()) as u16;
+    let popup_height = (10 + warning_rows).min(area.height);
+    let popup_area = centered_area(area, popup_width, popup_height);
 
     // Clear the popup area (removes the dim effect for the popup itself)
     f.render_widget(Clear, popup_area);
@@ -164,13 +162,14 @@ fn render_popup(
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1), // Target info (e.g., "Approving: PR #123")
-            Constraint::Length(1), // Spacing
-            Constraint::Length(1), // Instructions
-            Constraint::Length(1), // Spacing
-            Constraint::Length(1), // Input field
-            Constraint::Length(1), // Validation hint
-            Constraint::Min(0),    // Remaining
+            Constraint::Length(1),            // Target info (e.g., "Approving: PR #123")
+            Constraint::Length(warning_rows), // Details and warnings (e.g., "#123: CI is failing")
+            Constraint::Length(1),            // Spacing
+            Constraint::Length(1),            // Instructions
+            Constraint::Length(1),            // Spacing
+            Constraint::Length(1),            // Input field
+            Constraint::Length(1),            // Validation hint
+            Constraint::Min(0),               // Remaining
         ])
         .split(inner);
 
@@ -183,15 +182,41 @@ fn render_popup(
     ));
     f.render_widget(Paragraph::new(target_line), chunks[0]);
 
+    // Details, then warnings prominent so confirming is a deliberate choice
+    let details = vm.details.iter().map(|detail| {
+        Line::from(Span::styled(
+            detail,
+            Style::default().fg(vm.colors.input_label_fg),
+        ))
+    });
+    let warnings = vm.warnings.iter().map(|warning| {
+        Line::from(Span::styled(
+            warning,
+            Style::default()
+                .fg(vm.colors.warning_fg)
+                .add_modifier(Modifier::BOLD),
+        ))
+    });
+    let lines: Vec<Line> = details.chain(warnings).collect();
+    f.render_widget(Paragraph::new(lines), chunks[1]);
+
     // Instructions
     let instructions = Line::from(Span::styled(
         &vm.instructions,
         Style::default().fg(vm.colors.instructions_fg),
     ));
-    f.render_widget(Paragraph::new(instructions), chunks[2]);
+    f.render_widget(Paragraph::new(instructions), chunks[3]);
 
-    // Input field
-    render_input_field(f, chunks[4], vm, theme);
+    // Input field (not for confirmations without a message)
+    if vm.show_input {
+        render_input_field(f, chunks[5], vm, theme);
+    } else if let Some(ref option) = vm.option {
+        let line = Line::from(Span::styled(
+            option,
+            Style::default().fg(vm.colors.input_label_fg),
+        ));
+        f.render_widget(Paragraph::new(line), chunks[5]);
+    }
 
     // Validation hint (if present)
     if let Some(ref hint) = vm.validation_hint {
@@ -201,7 +226,7 @@ fn render_popup(
                 .fg(vm.colors.error_fg)
                 .add_modifier(Modifier::ITALIC),
         ));
-        f.render_widget(Paragraph::new(hint_line), chunks[5]);
+        f.render_widget(Paragraph::new(hint_line), chunks[6]);
     }
 }
 
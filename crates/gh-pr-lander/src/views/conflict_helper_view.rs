@@ -0,0 +1,177 @@
+//! Conflict Helper View
+//!
+//! Floating panel with the files a PR likely conflicts on after its branch
+//! could not be updated. Enter opens GitHub's conflict editor, the other
+//! keys copy local resolution commands or let dependabot recreate the PR.
+
+use crate::actions::{Action, AvailableAction, ConflictHelperAction, ContextAction};
+use crate::capabilities::PanelCapabilities;
+use crate::command_id::CommandId;
+use crate::state::AppState;
+use crate::view_models::ConflictHelperViewModel;
+use crate::views::{centered_area, View, ViewId};
+use ratatui::{prelude::*, widgets::*};
+
+/// Conflict helper view - table of likely conflicting files
+#[derive(Debug, Clone)]
+pub struct ConflictHelperView;
+
+impl ConflictHelperView {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ConflictHelperView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for ConflictHelperView {
+    fn view_id(&self) -> ViewId {
+        ViewId::ConflictHelper
+    }
+
+    fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
+        render(state, area, f);
+    }
+
+    fn capabilities(&self, _state: &AppState) -> PanelCapabilities {
+        PanelCapabilities::empty()
+    }
+
+    fn clone_box(&self) -> Box<dyn View> {
+        Box::new(self.clone())
+    }
+
+    fn translate_context_action(&self, action: ContextAction, _state: &AppState) -> Option<Action> {
+        match action {
+            ContextAction::Confirm => Some(Action::ConflictHelper(
+                ConflictHelperAction::OpenConflictsPage,
+            )),
+            _ => None,
+        }
+    }
+
+    fn accepts_action(&self, action: &Action) -> bool {
+        matches!(
+            action,
+            Action::ConflictHelper(_) | Action::ViewContext(_) | Action::Global(_)
+        )
+    }
+
+    fn available_actions(&self, state: &AppState) -> Vec<AvailableAction> {
+        let mut actions = vec![
+            AvailableAction::primary(CommandId::Confirm, "Resolve on GitHub"),
+            AvailableAction::primary(CommandId::ConflictHelperCopyCommands, "Copy commands"),
+        ];
+        if ConflictHelperViewModel::from_state(&state.conflict_helper).is_dependabot {
+            actions.push(AvailableAction::primary(
+                CommandId::ConflictHelperRecreate,
+                "Recreate",
+            ));
+        }
+        actions.push(AvailableAction::navigation(CommandId::GlobalClose, "Close"));
+        actions
+    }
+}
+
+/// Render the conflict helper panel
+fn render(state: &AppState, area: Rect, f: &mut Frame) {
+    let theme = &state.theme;
+    let vm = ConflictHelperViewModel::from_state(&state.conflict_helper);
+
+    // Render dimmed overlay over the entire screen
+    let overlay = Block::default().style(
+        Style::default()
+            .bg(Color::Black)
+            .add_modifier(Modifier::DIM),
+    );
+    f.render_widget(overlay, area);
+
+    // Header, summary, table header, rows, note and borders
+    let height = (vm.rows.len().max(1) as u16)
+        .saturating_add(6)
+        .min(area.height);
+    let panel_area = centered_area(area, area.width.min(100), height.max(8));
+    f.render_widget(Clear, panel_area);
+
+    let block = Block::default()
+        .title(format!(" {} ", vm.title))
+        .borders(Borders::ALL)
+        .border_style(theme.panel_border())
+        .title_style(theme.panel_title())
+        .title_alignment(Alignment::Center)
+        .style(theme.panel_background());
+    let inner = block.inner(panel_area);
+    f.render_widget(block, panel_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+    f.render_widget(
+        Paragraph::new(format!(" {}", vm.header)).style(theme.table_row().bold()),
+        chunks[0],
+    );
+    f.render_widget(
+        Paragraph::new(format!(" {}", vm.summary)).style(theme.muted()),
+        chunks[1],
+    );
+    f.render_widget(
+        Paragraph::new(format!(" {}", vm.note)).style(theme.warning()),
+        chunks[3],
+    );
+
+    if let Some(ref message) = vm.message {
+        let style = if vm.is_error {
+            theme.error()
+        } else {
+            theme.muted()
+        };
+        let paragraph = Paragraph::new(message.clone())
+            .style(style)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        f.render_widget(paragraph, chunks[2]);
+        return;
+    }
+
+    let header = Row::new(vec![" File", "Conflict", "PR", "Base"]).style(theme.table_header());
+    let rows: Vec<Row> = vm
+        .rows
+        .iter()
+        .map(|row| {
+            let kind_style = if row.is_certain {
+                theme.error()
+            } else {
+                theme.table_row()
+            };
+            Row::new(vec![
+                Cell::from(format!(" {}", row.path)),
+                Cell::from(row.kind.clone()).style(kind_style),
+                Cell::from(row.pr_churn.clone()),
+                Cell::from(row.base_churn.clone()),
+            ])
+            .style(theme.table_row())
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(30),
+            Constraint::Length(34),
+            Constraint::Length(8),
+            Constraint::Length(8),
+        ],
+    )
+    .header(header);
+    f.render_widget(table, chunks[2]);
+}
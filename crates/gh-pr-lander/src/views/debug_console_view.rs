@@ -5,6 +5,7 @@ use crate::capabilities::{PanelCapabilities, PanelCapabilityProvider};
 use crate::keybindings::Keymap;
 use crate::state::AppState;
 use crate::state::DebugConsoleState;
+use crate::state::TerminalSize;
 use crate::view_models::debug_console_view_model::DebugConsoleViewModel;
 use crate::views::View;
 use gh_pr_lander_theme::Theme;
@@ -87,8 +88,9 @@ fn render(state: &DebugConsoleState, theme: &Theme, keymap: &Keymap, area: Rect,
     );
     f.render_widget(overlay, area);
 
-    // Calculate console height based on percentage
-    let console_height = (area.height * 70) / 100;
+    // Console height is derived from the terminal size
+    let size = TerminalSize::from(area);
+    let console_height = size.debug_console_height();
     let console_area = Rect {
         x: area.x,
         y: area.y,
@@ -124,7 +126,7 @@ fn render(state: &DebugConsoleState, theme: &Theme, keymap: &Keymap, area: Rect,
         .title_alignment(Alignment::Center);
 
     // Calculate visible window
-    let available_height = console_height.saturating_sub(2) as usize; // -2 for borders
+    let available_height = size.debug_console_visible_lines();
 
     // Get visible lines and format them
     let visible_lines = view_model.visible_lines(available_height);
@@ -0,0 +1,203 @@
+//! Dependency Updates View
+//!
+//! Floating panel listing the open bot PRs of all loaded repositories,
+//! grouped by the dependency they update.
+
+use crate::actions::{Action, ContextAction, DependencyUpdatesAction, NavigationAction};
+use crate::capabilities::PanelCapabilities;
+use crate::domain_models::UpdateType;
+use crate::state::{dependency_groups, AppState, DependencyUpdateEntry};
+use crate::utils::format::format_count;
+use crate::views::{centered_area, percent, View, ViewId};
+use gh_pr_lander_theme::Theme;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Dependency updates view - floating panel with one group per dependency
+#[derive(Debug, Clone)]
+pub struct DependencyUpdatesView;
+
+impl DependencyUpdatesView {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for DependencyUpdatesView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for DependencyUpdatesView {
+    fn view_id(&self) -> ViewId {
+        ViewId::DependencyUpdates
+    }
+
+    fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
+        render(state, area, f);
+    }
+
+    fn capabilities(&self, _state: &AppState) -> PanelCapabilities {
+        PanelCapabilities::SCROLL_VERTICAL | PanelCapabilities::VIM_NAVIGATION_BINDINGS
+    }
+
+    fn clone_box(&self) -> Box<dyn View> {
+        Box::new(self.clone())
+    }
+
+    fn translate_navigation(&self, nav: NavigationAction) -> Option<Action> {
+        let action = match nav {
+            NavigationAction::Next => DependencyUpdatesAction::NavigateNext,
+            NavigationAction::Previous => DependencyUpdatesAction::NavigatePrevious,
+            NavigationAction::ToTop => DependencyUpdatesAction::NavigateToTop,
+            NavigationAction::ToBottom => DependencyUpdatesAction::NavigateToBottom,
+            NavigationAction::Left | NavigationAction::Right => return None,
+        };
+        Some(Action::DependencyUpdates(action))
+    }
+
+    fn translate_context_action(&self, action: ContextAction, _state: &AppState) -> Option<Action> {
+        match action {
+            ContextAction::Confirm => Some(Action::DependencyUpdates(
+                DependencyUpdatesAction::OpenSelected,
+            )),
+            _ => None,
+        }
+    }
+
+    fn accepts_action(&self, action: &Action) -> bool {
+        matches!(
+            action,
+            Action::DependencyUpdates(_)
+                | Action::ViewContext(_)
+                | Action::Navigate(_)
+                | Action::Global(_)
+        )
+    }
+}
+
+/// Render the dependency updates panel
+fn render(state: &AppState, area: Rect, f: &mut Frame) {
+    let theme = &state.theme;
+    let groups = dependency_groups(&state.main_view);
+    let selected = state.dependency_updates.selected;
+
+    // Render dimmed overlay over the entire screen
+    let overlay = Block::default().style(
+        Style::default()
+            .bg(ratatui::style::Color::Black)
+            .add_modifier(Modifier::DIM),
+    );
+    f.render_widget(overlay, area);
+
+    let mut lines: Vec<Line> = Vec::new();
+    let mut cursor_line = 0;
+    let mut entry_idx = 0;
+    for group in &groups {
+        let repos = format_count(group.entries.len(), "PR", "PRs");
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {}", group.name), theme.accent().bold()),
+            Span::styled(format!("  {}", repos), theme.muted()),
+        ]));
+        for entry in &group.entries {
+            if entry_idx == selected {
+                cursor_line = lines.len();
+            }
+            lines.push(build_row(entry, entry_idx == selected, theme));
+            entry_idx += 1;
+        }
+    }
+    let pr_count = entry_idx;
+
+    let panel_width = percent(area.width, 70).max(60).min(area.width);
+    let panel_height = (lines.len() as u16)
+        .saturating_add(4)
+        .max(6)
+        .min(percent(area.height, 80))
+        .min(area.height);
+    let panel_area = centered_area(area, panel_width, panel_height);
+
+    f.render_widget(Clear, panel_area);
+
+    let footer_hint = Line::from(vec![
+        Span::styled(" Enter", theme.key_hint().bold()),
+        Span::styled(" open  ", theme.muted()),
+        Span::styled("Esc", theme.key_hint().bold()),
+        Span::styled(" close ", theme.muted()),
+    ]);
+
+    let block = Block::default()
+        .title(format!(
+            " Dependency updates ({}, {}) ",
+            format_count(pr_count, "PR", "PRs"),
+            format_count(groups.len(), "dependency", "dependencies")
+        ))
+        .borders(Borders::ALL)
+        .border_style(theme.panel_border())
+        .title_style(theme.panel_title())
+        .title_alignment(Alignment::Center)
+        .title_bottom(footer_hint);
+
+    let inner_area = block.inner(panel_area);
+
+    if lines.is_empty() {
+        lines = vec![
+            Line::default(),
+            Line::from(Span::styled(
+                "  No open Dependabot or Renovate PRs in the loaded repositories.",
+                theme.muted(),
+            )),
+        ];
+    }
+
+    // Keep the cursor visible
+    let visible = inner_area.height as usize;
+    let skip = (cursor_line + 1).saturating_sub(visible);
+
+    let paragraph = Paragraph::new(lines.into_iter().skip(skip).collect::<Vec<_>>())
+        .block(block)
+        .style(theme.panel_background());
+    f.render_widget(paragraph, panel_area);
+}
+
+/// Build one row: repository and PR, version change, update type and status
+fn build_row<'a>(entry: &DependencyUpdateEntry, selected: bool, theme: &Theme) -> Line<'a> {
+    let update = &entry.update;
+    let (badge, badge_style) = match update.update_type {
+        Some(UpdateType::Major) => ("major", theme.error()),
+        Some(UpdateType::Minor) => ("minor", theme.warning()),
+        Some(UpdateType::Patch) => ("patch", theme.success()),
+        None => ("update", theme.muted()),
+    };
+    let versions = match update.from_version {
+        Some(ref from) => format!("{} → {}", from, update.to_version),
+        None => format!("→ {}", update.to_version),
+    };
+    let pr = format!("{}/{}#{}", entry.repo.org, entry.repo.repo, entry.pr_number);
+
+    let row = Line::from(vec![
+        Span::raw(if selected { " > " } else { "   " }),
+        Span::styled(format!("{:<32}", pr), theme.text().bold()),
+        Span::styled(format!("{:<24}", versions), theme.text()),
+        Span::styled(
+            format!("{:<8}", badge),
+            badge_style.add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("{} {}", entry.mergeable.icon(), entry.mergeable.label()),
+            theme.muted(),
+        ),
+    ]);
+
+    if selected {
+        row.style(theme.table_selected())
+    } else {
+        row
+    }
+}
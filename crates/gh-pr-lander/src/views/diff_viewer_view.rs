@@ -35,11 +35,11 @@ struct LanderThemeAdapter<'a>(&'a gh_pr_lander_theme::Theme);
 
 impl ThemeProvider for LanderThemeAdapter<'_> {
     fn addition_background(&self) -> Color {
-        Color::Rgb(20, 40, 20) // Dark green tint
+        self.0.diff_addition_bg
     }
 
     fn deletion_background(&self) -> Color {
-        Color::Rgb(40, 20, 20) // Dark red tint
+        self.0.diff_deletion_bg
     }
 
     fn context_background(&self) -> Color {
@@ -109,12 +109,14 @@ impl View for DiffViewerView {
     }
 
     fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
+        let status_vm = StatusBarViewModel::from_state(state, area.width);
+
         // Split area to preserve status bar at bottom
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Min(0),    // Diff viewer content
-                Constraint::Length(1), // Status bar
+                Constraint::Min(0),                     // Diff viewer content
+                Constraint::Length(status_vm.height()), // Status bar
             ])
             .split(area);
 
@@ -181,6 +183,7 @@ impl View for DiffViewerView {
                 vec![
                     FooterHint::new("c", "Comment"),
                     FooterHint::new("R", "Review"),
+                    FooterHint::new("b", "Blame"),
                     FooterHint::new("q", "Close"),
                     FooterHint::new("Ctrl+f", "Page Down"),
                     FooterHint::new("Ctrl+b", "Page Up"),
@@ -188,7 +191,9 @@ impl View for DiffViewerView {
             };
 
             // Create the diff viewer widget with theme and hints
-            let widget = DiffViewer::new(&mut highlighter, &theme_adapter).with_footer_hints(hints);
+            let widget = DiffViewer::new(&mut highlighter, &theme_adapter)
+                .with_footer_hints(hints)
+                .with_large_file_threshold(state.app_config.ui.large_file_changes);
 
             // We need to clone the inner state for rendering since render_with_state requires &mut
             let mut render_state = inner_state.clone();
@@ -213,7 +218,6 @@ impl View for DiffViewerView {
         }
 
         // Render status bar
-        let status_vm = StatusBarViewModel::from_state(state);
         f.render_widget(StatusBarWidget(&status_vm), chunks[1]);
     }
 
@@ -266,6 +270,13 @@ impl View for DiffViewerView {
         }
     }
 
+    fn translate_paste(&self, text: &str) -> Vec<Action> {
+        // Kept whole, the reducer only inserts it into the comment editor
+        vec![Action::DiffViewer(DiffViewerAction::Paste(
+            crate::utils::paste::multi_line(text),
+        ))]
+    }
+
     fn translate_context_action(&self, action: ContextAction, _state: &AppState) -> Option<Action> {
         match action {
             // Confirm toggles expand/collapse in file tree or confirms comment
@@ -309,11 +320,19 @@ impl View for DiffViewerView {
             }
         }
 
-        // Normal mode
+        // Normal mode: file comments need a file entry or hunk header under the cursor
+        let comment = match state.diff_viewer.inner {
+            Some(ref inner) if inner.file_comment_target().is_some() => {
+                AvailableAction::primary(CommandId::DiffViewerCommentOnFile, "Comment File")
+            }
+            _ => AvailableAction::primary(CommandId::DiffViewerAddComment, "Comment"),
+        };
         vec![
             AvailableAction::primary(CommandId::Confirm, "Toggle/Select"),
-            AvailableAction::primary(CommandId::DiffViewerAddComment, "Comment"),
+            comment,
+            AvailableAction::primary(CommandId::DiffViewerCommentOnPr, "Comment PR"),
             AvailableAction::primary(CommandId::DiffViewerShowReviewPopup, "Review"),
+            AvailableAction::primary(CommandId::DiffViewerToggleBlame, "Blame"),
             AvailableAction::navigation(CommandId::NavigateNext, "Down"),
             AvailableAction::navigation(CommandId::DiffViewerSwitchPane, "Switch Pane"),
             AvailableAction::navigation(CommandId::GlobalClose, "Close"),
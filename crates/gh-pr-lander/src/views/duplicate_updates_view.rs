@@ -0,0 +1,167 @@
+//! Duplicate Updates View
+//!
+//! Popup listing the PRs opening the same bot update in the tracked
+//! repositories, with their CI state. The picked PRs are merged together,
+//! each in its repository.
+
+use crate::actions::{Action, AvailableAction, DuplicateUpdatesAction, NavigationAction};
+use crate::capabilities::PanelCapabilities;
+use crate::command_id::CommandId;
+use crate::state::AppState;
+use crate::view_models::duplicate_updates_view_model::DuplicateUpdateRow;
+use crate::view_models::DuplicateUpdatesViewModel;
+use crate::views::{centered_area, EscapeBehavior, View, ViewId};
+use ratatui::{prelude::*, widgets::*};
+
+/// Duplicate updates view - table of PRs opening the same update
+#[derive(Debug, Clone)]
+pub struct DuplicateUpdatesView;
+
+impl DuplicateUpdatesView {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for DuplicateUpdatesView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for DuplicateUpdatesView {
+    fn view_id(&self) -> ViewId {
+        ViewId::DuplicateUpdates
+    }
+
+    fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
+        if let Some(ref popup) = state.duplicate_updates {
+            let vm = DuplicateUpdatesViewModel::from_state(popup, &state.main_view);
+            render_popup(&vm, &state.theme, area, f);
+        }
+    }
+
+    fn capabilities(&self, _state: &AppState) -> PanelCapabilities {
+        PanelCapabilities::ITEM_NAVIGATION | PanelCapabilities::VIM_NAVIGATION_BINDINGS
+    }
+
+    fn clone_box(&self) -> Box<dyn View> {
+        Box::new(self.clone())
+    }
+
+    fn escape_behavior(&self, _state: &AppState) -> EscapeBehavior {
+        EscapeBehavior::Forward(Box::new(Action::DuplicateUpdates(
+            DuplicateUpdatesAction::Close,
+        )))
+    }
+
+    fn translate_navigation(&self, nav: NavigationAction) -> Option<Action> {
+        let action = match nav {
+            NavigationAction::Next => DuplicateUpdatesAction::NavigateNext,
+            NavigationAction::Previous => DuplicateUpdatesAction::NavigatePrevious,
+            _ => return None,
+        };
+        Some(Action::DuplicateUpdates(action))
+    }
+
+    fn accepts_action(&self, action: &Action) -> bool {
+        matches!(
+            action,
+            Action::DuplicateUpdates(_) | Action::Navigate(_) | Action::Global(_)
+        )
+    }
+
+    fn available_actions(&self, _state: &AppState) -> Vec<AvailableAction> {
+        vec![
+            AvailableAction::primary(CommandId::DuplicateUpdatesMerge, "Merge"),
+            AvailableAction::selection(CommandId::DuplicateUpdatesToggle, "Pick"),
+            AvailableAction::selection(CommandId::DuplicateUpdatesPickAll, "All"),
+            AvailableAction::selection(CommandId::DuplicateUpdatesPickNone, "None"),
+            AvailableAction::navigation(CommandId::GlobalClose, "Close"),
+        ]
+    }
+}
+
+/// Render the duplicate updates popup as a centered floating window
+fn render_popup(
+    vm: &DuplicateUpdatesViewModel,
+    theme: &gh_pr_lander_theme::Theme,
+    area: Rect,
+    f: &mut Frame,
+) {
+    // Render dimmed overlay over the entire screen
+    let overlay = Block::default().style(
+        Style::default()
+            .bg(Color::Black)
+            .add_modifier(Modifier::DIM),
+    );
+    f.render_widget(overlay, area);
+
+    // Summary, header, rows and borders
+    let height = (vm.rows.len() as u16).saturating_add(4).min(area.height);
+    let popup_area = centered_area(area, area.width.min(110), height.max(6));
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(" {} ", vm.title))
+        .borders(Borders::ALL)
+        .border_style(theme.panel_border())
+        .title_style(theme.panel_title())
+        .title_alignment(Alignment::Center)
+        .style(theme.panel_background());
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(inner);
+    f.render_widget(
+        Paragraph::new(format!(" {}", vm.summary)).style(theme.muted()),
+        chunks[0],
+    );
+
+    // Keep the cursor row in view below the header
+    let visible = chunks[1].height.saturating_sub(1) as usize;
+    let cursor = vm.rows.iter().position(|r| r.is_selected).unwrap_or(0);
+    let offset = (cursor + 1).saturating_sub(visible);
+
+    let header = Row::new(vec!["", "Repository", "PR", "CI", "Title"]).style(theme.table_header());
+    let rows: Vec<Row> = vm
+        .rows
+        .iter()
+        .skip(offset)
+        .take(visible)
+        .map(|row| build_row(row, theme))
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(4),
+            Constraint::Length(28),
+            Constraint::Length(7),
+            Constraint::Length(20),
+            Constraint::Min(20),
+        ],
+    )
+    .header(header);
+    f.render_widget(table, chunks[1]);
+}
+
+/// Build a table row, highlighting the cursor
+fn build_row<'a>(row: &DuplicateUpdateRow, theme: &gh_pr_lander_theme::Theme) -> Row<'a> {
+    let style = if row.is_selected {
+        theme.table_row().bg(theme.active_bg).fg(theme.active_fg)
+    } else {
+        theme.table_row()
+    };
+    Row::new(vec![
+        Cell::from(format!(" {}", row.checkbox)),
+        Cell::from(row.repo.clone()),
+        Cell::from(row.pr.clone()),
+        Cell::from(row.ci.clone()),
+        Cell::from(row.title.clone()),
+    ])
+    .style(style)
+}
@@ -0,0 +1,219 @@
+//! Filter Builder View
+//!
+//! Popup assembling a structured PR filter, with a live count of matches.
+
+use crate::actions::{
+    Action, ContextAction, FilterBuilderAction, NavigationAction, TextInputAction,
+};
+use crate::capabilities::PanelCapabilities;
+use crate::state::AppState;
+use crate::view_models::FilterBuilderViewModel;
+use crate::views::{centered_area, percent, View, ViewId};
+use gh_pr_lander_theme::Theme;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Filter builder view - floating form of filter criteria
+#[derive(Debug, Clone)]
+pub struct FilterBuilderView;
+
+impl FilterBuilderView {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for FilterBuilderView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for FilterBuilderView {
+    fn view_id(&self) -> ViewId {
+        ViewId::FilterBuilder
+    }
+
+    fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
+        if let Some(ref builder) = state.filter_builder {
+            let repo_data = state
+                .main_view
+                .repo_data
+                .get(&state.main_view.selected_repository);
+            let vm = FilterBuilderViewModel::from_state(
+                builder,
+                repo_data,
+                chrono::Utc::now(),
+                &state.main_view.pr_sizes,
+            );
+            render_popup(&vm, &state.theme, area, f);
+        }
+    }
+
+    fn capabilities(&self, _state: &AppState) -> PanelCapabilities {
+        // Typing edits the row under the cursor, arrow keys move and cycle
+        PanelCapabilities::TEXT_INPUT | PanelCapabilities::ITEM_NAVIGATION
+    }
+
+    fn clone_box(&self) -> Box<dyn View> {
+        Box::new(self.clone())
+    }
+
+    fn translate_navigation(&self, nav: NavigationAction) -> Option<Action> {
+        let action = match nav {
+            NavigationAction::Next => FilterBuilderAction::NavigateNext,
+            NavigationAction::Previous => FilterBuilderAction::NavigatePrevious,
+            NavigationAction::Right => FilterBuilderAction::CycleNext,
+            NavigationAction::Left => FilterBuilderAction::CyclePrevious,
+            NavigationAction::ToTop | NavigationAction::ToBottom => return None,
+        };
+        Some(Action::FilterBuilder(action))
+    }
+
+    fn translate_text_input(&self, input: TextInputAction) -> Option<Action> {
+        let action = match input {
+            TextInputAction::Char(' ') => FilterBuilderAction::Toggle,
+            TextInputAction::Char(c) => FilterBuilderAction::InputChar(c),
+            TextInputAction::Backspace => FilterBuilderAction::InputBackspace,
+            TextInputAction::ClearLine => FilterBuilderAction::InputClear,
+            TextInputAction::Escape => FilterBuilderAction::Close,
+            TextInputAction::Confirm => FilterBuilderAction::Apply,
+        };
+        Some(Action::FilterBuilder(action))
+    }
+
+    fn translate_paste(&self, text: &str) -> Vec<Action> {
+        // Pasted spaces are part of the values, they don't toggle
+        crate::utils::paste::single_line(text)
+            .chars()
+            .map(|c| Action::FilterBuilder(FilterBuilderAction::InputChar(c)))
+            .collect()
+    }
+
+    fn translate_context_action(&self, action: ContextAction, _state: &AppState) -> Option<Action> {
+        match action {
+            ContextAction::Confirm => Some(Action::FilterBuilder(FilterBuilderAction::Apply)),
+            ContextAction::ToggleSelect => Some(Action::FilterBuilder(FilterBuilderAction::Toggle)),
+            _ => None,
+        }
+    }
+
+    fn accepts_action(&self, action: &Action) -> bool {
+        matches!(
+            action,
+            Action::FilterBuilder(_)
+                | Action::ViewContext(_)
+                | Action::Navigate(_)
+                | Action::TextInput(_)
+                | Action::Global(_)
+        )
+    }
+}
+
+/// Render the filter builder as a centered floating window
+fn render_popup(vm: &FilterBuilderViewModel, theme: &Theme, area: Rect, f: &mut Frame) {
+    // Render dimmed overlay over the entire screen to create modal effect
+    let overlay = Block::default().style(
+        Style::default()
+            .bg(ratatui::style::Color::Black)
+            .add_modifier(Modifier::DIM),
+    );
+    f.render_widget(overlay, area);
+
+    let popup_width = percent(area.width, 60).clamp(50, 80);
+    let popup_height = (vm.rows.len() as u16).saturating_add(5).min(area.height);
+    let popup_area = centered_area(area, popup_width, popup_height);
+
+    f.render_widget(Clear, popup_area);
+
+    let footer_hint = Line::from(vec![
+        Span::styled(" ←/→", theme.key_hint().bold()),
+        Span::styled(" choose  ", theme.muted()),
+        Span::styled("Enter", theme.key_hint().bold()),
+        Span::styled(" apply  ", theme.muted()),
+        Span::styled("Esc", theme.key_hint().bold()),
+        Span::styled(" cancel ", theme.muted()),
+    ]);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Filter PRs ")
+        .title_style(theme.panel_title().add_modifier(Modifier::BOLD))
+        .title_bottom(footer_hint)
+        .title_alignment(Alignment::Center)
+        .border_style(theme.panel_border().add_modifier(Modifier::BOLD))
+        .style(theme.panel_background());
+    f.render_widget(block, popup_area);
+
+    let inner = popup_area.inner(Margin {
+        horizontal: 2,
+        vertical: 1,
+    });
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),    // Rows
+            Constraint::Length(1), // Spacing
+            Constraint::Length(1), // Query
+            Constraint::Length(1), // Match count
+        ])
+        .split(inner);
+
+    // Keep the cursor row in view when the popup is clamped to a small screen
+    let visible = chunks[0].height as usize;
+    let cursor = vm.rows.iter().position(|r| r.is_selected).unwrap_or(0);
+    let offset = (cursor + 1).saturating_sub(visible);
+
+    let label_width = vm.rows.iter().map(|row| row.label.len()).max().unwrap_or(0);
+    let lines: Vec<Line> = vm
+        .rows
+        .iter()
+        .skip(offset)
+        .take(visible)
+        .map(|row| {
+            let mut text_style = theme.text();
+            if row.is_selected {
+                text_style = text_style.bg(theme.active_bg).fg(theme.active_fg);
+            }
+            let indicator = if row.is_selected { "> " } else { "  " };
+            let value = if row.is_text {
+                row.value.clone()
+            } else {
+                format!("< {} >", row.value)
+            };
+            let mut spans = vec![
+                Span::styled(
+                    format!(
+                        "{}{:3} {:width$}  ",
+                        indicator,
+                        row.checkbox,
+                        row.label,
+                        width = label_width
+                    ),
+                    text_style,
+                ),
+                Span::styled(value, theme.text().bold()),
+            ];
+            if row.is_selected && row.is_text {
+                spans.push(Span::styled("█", theme.key_hint()));
+            }
+            Line::from(spans)
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+
+    f.render_widget(
+        Paragraph::new(vm.query.clone()).style(theme.muted()),
+        chunks[2],
+    );
+    f.render_widget(
+        Paragraph::new(vm.count.clone()).style(theme.text().bold()),
+        chunks[3],
+    );
+}
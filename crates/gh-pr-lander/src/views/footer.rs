@@ -0,0 +1,49 @@
+//! Footer Widget
+//!
+//! Renders the key hints of the active view's available actions below
+//! everything else. Format: `key label • key label • ...`
+
+use crate::view_models::footer_view_model::SEPARATOR;
+use crate::view_models::FooterViewModel;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+/// Widget for rendering the footer
+pub struct FooterWidget<'a>(pub &'a FooterViewModel);
+
+impl Widget for FooterWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let vm = self.0;
+
+        for (row, items) in vm.lines.iter().enumerate().take(area.height as usize) {
+            let y = area.y + row as u16;
+
+            // Fill entire row with background
+            for x in area.x..area.x + area.width {
+                buf[(x, y)].set_bg(vm.bg_color).set_char(' ');
+            }
+
+            let mut spans = Vec::new();
+            for (idx, item) in items.iter().enumerate() {
+                if idx > 0 {
+                    spans.push(Span::styled(SEPARATOR, vm.separator_style));
+                }
+                spans.push(Span::styled(item.key.as_str(), item.key_style));
+                spans.push(Span::raw(" "));
+                spans.push(Span::styled(item.label.as_str(), item.label_style));
+            }
+
+            // 1 char padding
+            buf.set_line(
+                area.x + 1,
+                y,
+                &Line::from(spans),
+                area.width.saturating_sub(2),
+            );
+        }
+    }
+}
@@ -6,7 +6,7 @@ use crate::actions::{Action, ContextAction, KeyBindingsAction, NavigationAction}
 use crate::capabilities::PanelCapabilities;
 use crate::state::AppState;
 use crate::view_models::KeyBindingsPanelViewModel;
-use crate::views::View;
+use crate::views::{centered_area, View};
 use gh_pr_lander_theme::Theme;
 use ratatui::{
     layout::{Alignment, Rect},
@@ -84,15 +84,7 @@ fn render(state: &AppState, area: Rect, f: &mut Frame) {
     let panel_width = (area.width * 60) / 100;
     let panel_height = (area.height * 90) / 100;
 
-    let panel_x = area.x + (area.width - panel_width) / 2;
-    let panel_y = area.y + (area.height - panel_height) / 2;
-
-    let panel_area = Rect {
-        x: panel_x,
-        y: panel_y,
-        width: panel_width,
-        height: panel_height,
-    };
+    let panel_area = centered_area(area, panel_width, panel_height);
 
     // Render dimmed overlay over the entire screen
     let overlay = Block::default().style(
@@ -0,0 +1,209 @@
+//! Label Picker View
+//!
+//! Multi-select popup to add and remove labels on the selected PRs.
+
+use crate::actions::{Action, ContextAction, LabelPickerAction, NavigationAction, TextInputAction};
+use crate::capabilities::PanelCapabilities;
+use crate::state::AppState;
+use crate::view_models::LabelPickerViewModel;
+use crate::views::{centered_area, percent, View, ViewId};
+use gh_pr_lander_theme::Theme;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Label picker view - floating checkbox list of repository labels
+#[derive(Debug, Clone)]
+pub struct LabelPickerView;
+
+impl LabelPickerView {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for LabelPickerView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for LabelPickerView {
+    fn view_id(&self) -> ViewId {
+        ViewId::LabelPicker
+    }
+
+    fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
+        if let Some(ref picker) = state.label_picker {
+            let vm = LabelPickerViewModel::from_state(picker);
+            render_popup(&vm, &state.theme, area, f);
+        }
+    }
+
+    fn capabilities(&self, _state: &AppState) -> PanelCapabilities {
+        // Typing filters the labels, arrow keys move through them
+        PanelCapabilities::TEXT_INPUT | PanelCapabilities::ITEM_NAVIGATION
+    }
+
+    fn clone_box(&self) -> Box<dyn View> {
+        Box::new(self.clone())
+    }
+
+    fn translate_navigation(&self, nav: NavigationAction) -> Option<Action> {
+        let action = match nav {
+            NavigationAction::Next => LabelPickerAction::NavigateNext,
+            NavigationAction::Previous => LabelPickerAction::NavigatePrevious,
+            NavigationAction::Left
+            | NavigationAction::Right
+            | NavigationAction::ToTop
+            | NavigationAction::ToBottom => return None,
+        };
+        Some(Action::LabelPicker(action))
+    }
+
+    fn translate_text_input(&self, input: TextInputAction) -> Option<Action> {
+        let action = match input {
+            // Space toggles, label names don't contain leading spaces worth filtering on
+            TextInputAction::Char(' ') => LabelPickerAction::Toggle,
+            TextInputAction::Char(c) => LabelPickerAction::InputChar(c),
+            TextInputAction::Backspace => LabelPickerAction::InputBackspace,
+            TextInputAction::ClearLine => LabelPickerAction::InputClear,
+            TextInputAction::Escape => LabelPickerAction::Close,
+            TextInputAction::Confirm => LabelPickerAction::Apply,
+        };
+        Some(Action::LabelPicker(action))
+    }
+
+    fn translate_paste(&self, text: &str) -> Vec<Action> {
+        // Pasted spaces are part of the label names, they don't toggle
+        crate::utils::paste::single_line(text)
+            .chars()
+            .map(|c| Action::LabelPicker(LabelPickerAction::InputChar(c)))
+            .collect()
+    }
+
+    fn translate_context_action(&self, action: ContextAction, _state: &AppState) -> Option<Action> {
+        match action {
+            ContextAction::Confirm => Some(Action::LabelPicker(LabelPickerAction::Apply)),
+            ContextAction::ToggleSelect => Some(Action::LabelPicker(LabelPickerAction::Toggle)),
+            _ => None,
+        }
+    }
+
+    fn accepts_action(&self, action: &Action) -> bool {
+        matches!(
+            action,
+            Action::LabelPicker(_)
+                | Action::ViewContext(_)
+                | Action::Navigate(_)
+                | Action::TextInput(_)
+                | Action::Global(_)
+        )
+    }
+}
+
+/// Render the label picker as a centered floating window
+fn render_popup(vm: &LabelPickerViewModel, theme: &Theme, area: Rect, f: &mut Frame) {
+    // Render dimmed overlay over the entire screen to create modal effect
+    let overlay = Block::default().style(
+        Style::default()
+            .bg(ratatui::style::Color::Black)
+            .add_modifier(Modifier::DIM),
+    );
+    f.render_widget(overlay, area);
+
+    let popup_width = percent(area.width, 60).clamp(50, 80);
+    let popup_height = percent(area.height, 60).clamp(12, 24);
+    let popup_area = centered_area(area, popup_width, popup_height);
+
+    f.render_widget(Clear, popup_area);
+
+    let footer_hint = Line::from(vec![
+        Span::styled(" Space", theme.key_hint().bold()),
+        Span::styled(" toggle  ", theme.muted()),
+        Span::styled("Enter", theme.key_hint().bold()),
+        Span::styled(" apply  ", theme.muted()),
+        Span::styled("Esc", theme.key_hint().bold()),
+        Span::styled(" cancel ", theme.muted()),
+    ]);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", vm.title))
+        .title_style(theme.panel_title().add_modifier(Modifier::BOLD))
+        .title_bottom(footer_hint)
+        .title_alignment(Alignment::Center)
+        .border_style(theme.panel_border().add_modifier(Modifier::BOLD))
+        .style(theme.panel_background());
+    f.render_widget(block, popup_area);
+
+    let inner = popup_area.inner(Margin {
+        horizontal: 2,
+        vertical: 1,
+    });
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Filter
+            Constraint::Length(1), // Spacing
+            Constraint::Min(1),    // Labels
+            Constraint::Length(1), // Pending changes
+        ])
+        .split(inner);
+
+    let filter = Line::from(vec![
+        Span::styled("Filter: ", theme.text()),
+        Span::styled(vm.query.clone(), theme.text().bold()),
+        Span::styled("█", theme.key_hint()),
+    ]);
+    f.render_widget(Paragraph::new(filter), chunks[0]);
+
+    if let Some(ref message) = vm.message {
+        f.render_widget(
+            Paragraph::new(message.clone())
+                .style(theme.muted())
+                .alignment(Alignment::Center),
+            chunks[2],
+        );
+    } else {
+        // Keep the cursor row in view
+        let visible = chunks[2].height as usize;
+        let cursor = vm.rows.iter().position(|r| r.is_selected).unwrap_or(0);
+        let offset = (cursor + 1).saturating_sub(visible);
+
+        let lines: Vec<Line> = vm
+            .rows
+            .iter()
+            .skip(offset)
+            .take(visible)
+            .map(|row| {
+                let mut text_style = if row.is_changed {
+                    theme.text().bold()
+                } else {
+                    theme.text()
+                };
+                if row.is_selected {
+                    text_style = text_style.bg(theme.active_bg).fg(theme.active_fg);
+                }
+                let indicator = if row.is_selected { "> " } else { "  " };
+                Line::from(vec![
+                    Span::styled(format!("{}{} ", indicator, row.checkbox), text_style),
+                    Span::styled("● ", Style::default().fg(row.swatch)),
+                    Span::styled(row.name.clone(), text_style),
+                    Span::styled(format!("  {}", row.description), theme.muted()),
+                ])
+            })
+            .collect();
+        f.render_widget(Paragraph::new(lines), chunks[2]);
+    }
+
+    f.render_widget(
+        Paragraph::new(vm.pending.clone()).style(theme.muted()),
+        chunks[3],
+    );
+}
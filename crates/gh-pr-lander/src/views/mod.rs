@@ -1,31 +1,78 @@
 use crate::actions::{Action, AvailableAction, ContextAction, NavigationAction, TextInputAction};
 use crate::capabilities::PanelCapabilities;
-use crate::state::AppState;
-use ratatui::{layout::Rect, Frame};
+use crate::state::{AppState, TerminalSize, MIN_HEIGHT, MIN_WIDTH};
+use crate::view_models::FooterViewModel;
+use footer::FooterWidget;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::Line,
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
 
 // New view modules (concrete view types)
+pub mod actions_view;
 pub mod add_repository_view;
+pub mod audit_log_view;
+pub mod branch_picker_view;
+pub mod bug_report_view;
 pub mod build_log_view;
+pub mod cache_inspector_view;
 pub mod command_palette_view;
 pub mod confirmation_popup_view;
+pub mod conflict_helper_view;
 pub mod debug_console_view;
+pub mod dependency_updates_view;
 pub mod diff_viewer_view;
+pub mod duplicate_updates_view;
+pub mod filter_builder_view;
+pub mod footer;
 pub mod key_bindings_view;
+pub mod label_picker_view;
+pub mod onboarding_view;
+pub mod perf_stats_view;
+pub mod pr_search_view;
 pub mod pull_request_view;
+pub mod release_notes_view;
 pub mod repository_tabs_view;
+pub mod reviewer_picker_view;
 pub mod splash_view;
 pub mod status_bar;
+pub mod status_message_view;
+pub mod token_input_view;
+pub mod watch_list_view;
+
+#[cfg(test)]
+mod snapshot_tests;
 
 // Re-export concrete view types for convenience
+pub use actions_view::ActionsView;
 pub use add_repository_view::AddRepositoryView;
+pub use audit_log_view::AuditLogView;
+pub use branch_picker_view::BranchPickerView;
+pub use bug_report_view::BugReportView;
 pub use build_log_view::BuildLogView;
+pub use cache_inspector_view::CacheInspectorView;
 pub use command_palette_view::CommandPaletteView;
 pub use confirmation_popup_view::ConfirmationPopupView;
+pub use conflict_helper_view::ConflictHelperView;
 pub use debug_console_view::DebugConsoleView;
+pub use dependency_updates_view::DependencyUpdatesView;
 pub use diff_viewer_view::DiffViewerView;
+pub use duplicate_updates_view::DuplicateUpdatesView;
+pub use filter_builder_view::FilterBuilderView;
 pub use key_bindings_view::KeyBindingsView;
+pub use label_picker_view::LabelPickerView;
+pub use onboarding_view::OnboardingView;
+pub use perf_stats_view::PerfStatsView;
+pub use pr_search_view::PrSearchView;
 pub use pull_request_view::PullRequestView;
+pub use release_notes_view::ReleaseNotesView;
+pub use reviewer_picker_view::ReviewerPickerView;
 pub use splash_view::SplashView;
+pub use status_message_view::StatusMessageView;
+pub use token_input_view::TokenInputView;
+pub use watch_list_view::WatchListView;
 
 /// View identifier - allows comparing which view is active
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -39,6 +86,33 @@ pub enum ViewId {
     BuildLog,
     ConfirmationPopup,
     DiffViewer,
+    Onboarding,
+    TokenInput,
+    PrSearch,
+    WatchList,
+    BugReport,
+    LabelPicker,
+    ReviewerPicker,
+    FilterBuilder,
+    Actions,
+    DependencyUpdates,
+    PerfStats,
+    CacheInspector,
+    AuditLog,
+    DuplicateUpdates,
+    ConflictHelper,
+    ReleaseNotes,
+    BranchPicker,
+    StatusMessage,
+}
+
+/// What Escape does while a view is the top-most view
+#[derive(Debug, Clone)]
+pub enum EscapeBehavior {
+    /// Pop the view off the stack (`GlobalAction::PopView`)
+    Pop,
+    /// Dispatch this action instead, the view decides whether to cancel or close
+    Forward(Box<Action>),
 }
 
 /// View trait - defines the interface that all views must implement
@@ -106,6 +180,19 @@ pub trait View: std::fmt::Debug + Send + Sync {
         None // Default: view doesn't handle text input
     }
 
+    /// Translate pasted text to this view's specific actions.
+    ///
+    /// Only called while the view accepts text input. The default flattens
+    /// the text to one line and types it character by character through
+    /// [`translate_text_input`](Self::translate_text_input). Views with a
+    /// multi-line editor, or whose characters are commands, override it.
+    fn translate_paste(&self, text: &str) -> Vec<Action> {
+        crate::utils::paste::single_line(text)
+            .chars()
+            .filter_map(|c| self.translate_text_input(TextInputAction::Char(c)))
+            .collect()
+    }
+
     /// Translate a context-sensitive action to this view's specific action.
     ///
     /// Context actions are semantic actions (Confirm, ToggleSelect, etc.) that
@@ -118,7 +205,7 @@ pub trait View: std::fmt::Debug + Send + Sync {
     /// ```ignore
     /// fn translate_context_action(&self, action: ContextAction, _state: &AppState) -> Option<Action> {
     ///     match action {
-    ///         ContextAction::Confirm => Some(Action::PullRequest(PullRequestAction::OpenInBrowser)),
+    ///         ContextAction::Confirm => Some(Action::PullRequest(PullRequestAction::OpenInBrowser(PrTab::Conversation))),
     ///         ContextAction::ToggleSelect => Some(Action::PullRequest(PullRequestAction::ToggleSelection)),
     ///         _ => None,
     ///     }
@@ -152,6 +239,19 @@ pub trait View: std::fmt::Debug + Send + Sync {
         true // Default: accept all actions (backward compatible)
     }
 
+    /// What Escape does while this view is on top.
+    ///
+    /// Escape never quits the app, at most it pops this view. The default
+    /// forwards `TextInputAction::Escape` to text input views (they cancel
+    /// their edit and close themselves) and pops all other views.
+    fn escape_behavior(&self, state: &AppState) -> EscapeBehavior {
+        if self.capabilities(state).accepts_text_input() {
+            EscapeBehavior::Forward(Box::new(Action::TextInput(TextInputAction::Escape)))
+        } else {
+            EscapeBehavior::Pop
+        }
+    }
+
     /// Get the available actions for this view in the current state.
     ///
     /// Returns a list of actions that can be performed, used for rendering
@@ -184,9 +284,69 @@ impl Clone for Box<dyn View> {
 /// Rendering strategy:
 /// - Render all views in the stack from bottom to top
 /// - Views using `Clear` widget will preserve portions of underlying views
+/// - The footer with the active view's available actions takes the bottom rows
+/// - Below the minimum terminal size only a hint to enlarge it is shown
+/// - In the compact layout the views get the whole screen, without footer
 pub fn render(state: &AppState, area: Rect, f: &mut Frame) {
+    let size = TerminalSize::from(area);
+    if size.is_too_small() {
+        render_too_small(state, area, f);
+        return;
+    }
+    if size.is_compact() {
+        for view in &state.view_stack {
+            view.render(state, area, f);
+        }
+        return;
+    }
+
+    let footer = FooterViewModel::from_state(state, area.width, area.height);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(footer.height())])
+        .split(area);
+
     // Render each view bottom-up so views on top render last
     for view in &state.view_stack {
-        view.render(state, area, f);
+        view.render(state, chunks[0], f);
+    }
+
+    f.render_widget(FooterWidget(&footer), chunks[1]);
+}
+
+/// `percent` % of `len` cells, without overflowing on huge terminals
+pub fn percent(len: u16, percent: u16) -> u16 {
+    (u32::from(len) * u32::from(percent) / 100) as u16
+}
+
+/// Area of a `width` x `height` popup centered in `area`, clamped to fit
+pub fn centered_area(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
     }
 }
+
+/// Placeholder for terminals too small to lay out the views
+fn render_too_small(state: &AppState, area: Rect, f: &mut Frame) {
+    let text = vec![
+        Line::styled("Terminal too small", state.theme.error()),
+        Line::styled(
+            format!(
+                "{}x{}, need {}x{}",
+                area.width, area.height, MIN_WIDTH, MIN_HEIGHT
+            ),
+            state.theme.muted(),
+        ),
+    ];
+    let height = (text.len() as u16).min(area.height);
+    let paragraph = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .style(state.theme.panel_background());
+    f.render_widget(paragraph, centered_area(area, area.width, height));
+}
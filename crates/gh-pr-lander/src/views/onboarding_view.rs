@@ -0,0 +1,208 @@
+//! Onboarding View
+//!
+//! First-run wizard shown when no repositories are configured.
+//! Walks through token availability and adding a first repository.
+
+use crate::actions::{Action, OnboardingAction, TextInputAction};
+use crate::capabilities::PanelCapabilities;
+use crate::state::{AppState, OnboardingState, OnboardingStep};
+use crate::views::{centered_area, percent, View, ViewId};
+use gh_pr_lander_theme::Theme;
+use ratatui::{
+    layout::{Alignment, Margin, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Onboarding wizard view - floating panel guiding through the first run
+#[derive(Debug, Clone)]
+pub struct OnboardingView;
+
+impl OnboardingView {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for OnboardingView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for OnboardingView {
+    fn view_id(&self) -> ViewId {
+        ViewId::Onboarding
+    }
+
+    fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
+        if let Some(ref onboarding) = state.onboarding {
+            render_wizard(onboarding, &state.theme, area, f);
+        }
+    }
+
+    fn capabilities(&self, _state: &AppState) -> PanelCapabilities {
+        // Single-key choices (y/a/s) are routed as text input
+        PanelCapabilities::TEXT_INPUT
+    }
+
+    fn clone_box(&self) -> Box<dyn View> {
+        Box::new(self.clone())
+    }
+
+    fn translate_text_input(&self, input: TextInputAction) -> Option<Action> {
+        let action = match input {
+            TextInputAction::Confirm => OnboardingAction::Next,
+            TextInputAction::Escape | TextInputAction::Char('s') => OnboardingAction::Skip,
+            TextInputAction::Char('y') => OnboardingAction::AddDetectedRepository,
+            TextInputAction::Char('a') => OnboardingAction::AddRepositoryManually,
+            _ => return None,
+        };
+        Some(Action::Onboarding(action))
+    }
+
+    fn translate_paste(&self, _text: &str) -> Vec<Action> {
+        // Keys are the step's commands, pasted text would run them
+        Vec::new()
+    }
+
+    fn accepts_action(&self, action: &Action) -> bool {
+        matches!(
+            action,
+            Action::Onboarding(_) | Action::TextInput(_) | Action::Global(_)
+        )
+    }
+}
+
+/// Render the onboarding wizard as a centered floating window
+fn render_wizard(state: &OnboardingState, theme: &Theme, area: Rect, f: &mut Frame) {
+    // Render dimmed overlay over the entire screen to create modal effect
+    let overlay = Block::default().style(
+        Style::default()
+            .bg(ratatui::style::Color::Black)
+            .add_modifier(Modifier::DIM),
+    );
+    f.render_widget(overlay, area);
+
+    let popup_width = percent(area.width, 70).clamp(50, 80).min(area.width);
+    let popup_height = 18.min(area.height);
+    let popup_area = centered_area(area, popup_width, popup_height);
+
+    f.render_widget(Clear, popup_area);
+
+    let footer_hint = Line::from(vec![
+        Span::styled(" Enter", theme.key_hint().bold()),
+        Span::styled(" continue  ", theme.muted()),
+        Span::styled("s/Esc", theme.key_hint().bold()),
+        Span::styled(" skip ", theme.muted()),
+    ]);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Welcome to GitHub PR Lander ")
+        .title_style(theme.panel_title().add_modifier(Modifier::BOLD))
+        .title_bottom(footer_hint)
+        .title_alignment(Alignment::Center)
+        .border_style(theme.panel_border())
+        .style(theme.panel_background());
+    f.render_widget(block, popup_area);
+
+    let inner = popup_area.inner(Margin {
+        horizontal: 2,
+        vertical: 1,
+    });
+
+    let paragraph = Paragraph::new(wizard_lines(state, theme))
+        .wrap(Wrap { trim: false })
+        .style(theme.panel_background());
+    f.render_widget(paragraph, inner);
+}
+
+/// Build the wizard content for the current step
+fn wizard_lines(state: &OnboardingState, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "No repositories configured yet - let's set things up.",
+            theme.text(),
+        )),
+        Line::from(""),
+    ];
+
+    // Step 1: token
+    let token_active = state.step == OnboardingStep::Token;
+    lines.push(step_header("1. GitHub token", token_active, theme));
+    if state.token_available {
+        lines.push(Line::from(Span::styled(
+            "   ✓ A GitHub token was found.",
+            theme.success(),
+        )));
+    } else {
+        lines.push(Line::from(Span::styled(
+            "   ✗ No GitHub token found. Provide one by either:",
+            theme.error(),
+        )));
+        lines.push(Line::from(Span::styled(
+            "     - running 'gh auth login', or",
+            theme.text(),
+        )));
+        lines.push(Line::from(Span::styled(
+            "     - exporting GITHUB_TOKEN (or GITHUB_TOKEN_<HOST> for Enterprise)",
+            theme.text(),
+        )));
+    }
+    lines.push(Line::from(""));
+
+    // Step 2: repository
+    let repo_active = state.step == OnboardingStep::Repository;
+    lines.push(step_header("2. First repository", repo_active, theme));
+    if repo_active {
+        match &state.detected_repo {
+            Some(repo) => {
+                lines.push(Line::from(vec![
+                    Span::styled("   Detected from git remote: ", theme.text()),
+                    Span::styled(repo.full_display_name(), theme.accent().bold()),
+                ]));
+                lines.push(Line::from(vec![
+                    Span::styled("   y", theme.key_hint().bold()),
+                    Span::styled(" add it   ", theme.muted()),
+                    Span::styled("a", theme.key_hint().bold()),
+                    Span::styled(" add a different repository", theme.muted()),
+                ]));
+            }
+            None => {
+                lines.push(Line::from(Span::styled(
+                    "   No GitHub remote found in the current directory.",
+                    theme.muted(),
+                )));
+                lines.push(Line::from(vec![
+                    Span::styled("   Enter/a", theme.key_hint().bold()),
+                    Span::styled(" open the add repository form", theme.muted()),
+                ]));
+            }
+        }
+    } else {
+        lines.push(Line::from(Span::styled(
+            "   Press Enter to continue.",
+            theme.muted(),
+        )));
+    }
+
+    if let Some(ref message) = state.config_message {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(message.clone(), theme.muted())));
+    }
+
+    lines
+}
+
+/// Header line for a wizard step, highlighted when active
+fn step_header(title: &'static str, active: bool, theme: &Theme) -> Line<'static> {
+    let style = if active {
+        theme.section_header().bold()
+    } else {
+        theme.muted()
+    };
+    Line::from(Span::styled(title, style))
+}
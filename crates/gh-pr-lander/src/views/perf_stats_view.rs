@@ -0,0 +1,165 @@
+//! Performance Stats View
+//!
+//! Floating panel with the call counts and latencies of the GitHub API
+//! endpoints since startup and the memory of parsed build logs. Enter takes
+//! a new snapshot.
+
+use crate::actions::{Action, AvailableAction, ContextAction, PerfStatsAction};
+use crate::capabilities::PanelCapabilities;
+use crate::command_id::CommandId;
+use crate::state::AppState;
+use crate::view_models::perf_stats_view_model::PerfStatsRow;
+use crate::view_models::PerfStatsViewModel;
+use crate::views::{centered_area, View, ViewId};
+use ratatui::{prelude::*, widgets::*};
+
+/// Performance stats view - table of API endpoints
+#[derive(Debug, Clone)]
+pub struct PerfStatsView;
+
+impl PerfStatsView {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for PerfStatsView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for PerfStatsView {
+    fn view_id(&self) -> ViewId {
+        ViewId::PerfStats
+    }
+
+    fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
+        render(state, area, f);
+    }
+
+    fn capabilities(&self, _state: &AppState) -> PanelCapabilities {
+        PanelCapabilities::empty()
+    }
+
+    fn clone_box(&self) -> Box<dyn View> {
+        Box::new(self.clone())
+    }
+
+    fn translate_context_action(&self, action: ContextAction, _state: &AppState) -> Option<Action> {
+        match action {
+            ContextAction::Confirm => Some(Action::PerfStats(PerfStatsAction::Refresh)),
+            _ => None,
+        }
+    }
+
+    fn accepts_action(&self, action: &Action) -> bool {
+        matches!(
+            action,
+            Action::PerfStats(_) | Action::ViewContext(_) | Action::Global(_)
+        )
+    }
+
+    fn available_actions(&self, _state: &AppState) -> Vec<AvailableAction> {
+        vec![
+            AvailableAction::primary(CommandId::Confirm, "Refresh"),
+            AvailableAction::navigation(CommandId::GlobalClose, "Close"),
+        ]
+    }
+}
+
+/// Render the performance stats panel
+fn render(state: &AppState, area: Rect, f: &mut Frame) {
+    let theme = &state.theme;
+    let vm = PerfStatsViewModel::from_state(&state.perf_stats, &state.build_log);
+
+    // Render dimmed overlay over the entire screen
+    let overlay = Block::default().style(
+        Style::default()
+            .bg(Color::Black)
+            .add_modifier(Modifier::DIM),
+    );
+    f.render_widget(overlay, area);
+
+    // Header, separator, rows, separator, totals, two summary lines and borders
+    let height = (vm.rows.len() as u16).saturating_add(8).min(area.height);
+    let panel_area = centered_area(area, area.width.min(100), height.max(8));
+    f.render_widget(Clear, panel_area);
+
+    let block = Block::default()
+        .title(format!(" {} ", vm.title))
+        .borders(Borders::ALL)
+        .border_style(theme.panel_border())
+        .title_style(theme.panel_title())
+        .title_alignment(Alignment::Center)
+        .style(theme.panel_background());
+    let inner = block.inner(panel_area);
+    f.render_widget(block, panel_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .split(inner);
+    f.render_widget(
+        Paragraph::new(format!(" {}", vm.summary)).style(theme.muted()),
+        chunks[0],
+    );
+    f.render_widget(
+        Paragraph::new(format!(" {}", vm.build_logs)).style(theme.muted()),
+        chunks[1],
+    );
+
+    if let Some(ref message) = vm.message {
+        let paragraph = Paragraph::new(message.clone())
+            .style(theme.muted())
+            .alignment(Alignment::Center);
+        f.render_widget(paragraph, chunks[2]);
+        return;
+    }
+
+    let header = Row::new(vec![
+        "Endpoint", "Calls", "Errors", "Mean", "p50", "p95", "Max",
+    ])
+    .style(theme.table_header());
+
+    let mut rows: Vec<Row> = vm
+        .rows
+        .iter()
+        .map(|row| build_row(row, theme.table_row(), theme))
+        .collect();
+    rows.push(build_row(&vm.totals, theme.table_row().bold(), theme).top_margin(1));
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(28),
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(8),
+        ],
+    )
+    .header(header);
+    f.render_widget(table, chunks[2]);
+}
+
+/// Build a table row, highlighting the error count of failing endpoints
+fn build_row<'a>(row: &PerfStatsRow, style: Style, theme: &gh_pr_lander_theme::Theme) -> Row<'a> {
+    let errors_style = if row.has_errors { theme.error() } else { style };
+    Row::new(vec![
+        Cell::from(format!(" {}", row.endpoint)),
+        Cell::from(row.calls.clone()),
+        Cell::from(row.errors.clone()).style(errors_style),
+        Cell::from(row.mean.clone()),
+        Cell::from(row.p50.clone()),
+        Cell::from(row.p95.clone()),
+        Cell::from(row.max.clone()),
+    ])
+    .style(style)
+}
@@ -0,0 +1,127 @@
+//! PR Search View
+//!
+//! Input popup for a server-side search of the current repository's PRs.
+
+use crate::actions::{Action, PrSearchAction, TextInputAction};
+use crate::capabilities::PanelCapabilities;
+use crate::state::{AppState, PrSearchInputState};
+use crate::views::{centered_area, percent, View, ViewId};
+use gh_pr_lander_theme::Theme;
+use ratatui::{
+    layout::{Alignment, Margin, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// PR search view - floating panel with the query input
+#[derive(Debug, Clone)]
+pub struct PrSearchView;
+
+impl PrSearchView {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for PrSearchView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for PrSearchView {
+    fn view_id(&self) -> ViewId {
+        ViewId::PrSearch
+    }
+
+    fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
+        if let Some(ref input) = state.pr_search_input {
+            render_popup(input, &state.theme, area, f);
+        }
+    }
+
+    fn capabilities(&self, _state: &AppState) -> PanelCapabilities {
+        PanelCapabilities::TEXT_INPUT
+    }
+
+    fn clone_box(&self) -> Box<dyn View> {
+        Box::new(self.clone())
+    }
+
+    fn translate_text_input(&self, input: TextInputAction) -> Option<Action> {
+        let action = match input {
+            TextInputAction::Char(c) => PrSearchAction::InputChar(c),
+            TextInputAction::Backspace => PrSearchAction::InputBackspace,
+            TextInputAction::ClearLine => PrSearchAction::InputClear,
+            TextInputAction::Escape => PrSearchAction::CloseInput,
+            TextInputAction::Confirm => PrSearchAction::SubmitInput,
+        };
+        Some(Action::PrSearch(action))
+    }
+
+    fn accepts_action(&self, action: &Action) -> bool {
+        matches!(
+            action,
+            Action::PrSearch(_) | Action::TextInput(_) | Action::Global(_)
+        )
+    }
+}
+
+/// Render the search input as a centered floating window
+fn render_popup(state: &PrSearchInputState, theme: &Theme, area: Rect, f: &mut Frame) {
+    // Render dimmed overlay over the entire screen to create modal effect
+    let overlay = Block::default().style(
+        Style::default()
+            .bg(ratatui::style::Color::Black)
+            .add_modifier(Modifier::DIM),
+    );
+    f.render_widget(overlay, area);
+
+    let popup_width = percent(area.width, 60).clamp(50, 70).min(area.width);
+    let popup_height = 8.min(area.height);
+    let popup_area = centered_area(area, popup_width, popup_height);
+
+    f.render_widget(Clear, popup_area);
+
+    let footer_hint = Line::from(vec![
+        Span::styled(" Enter", theme.key_hint().bold()),
+        Span::styled(" search  ", theme.muted()),
+        Span::styled("Esc", theme.key_hint().bold()),
+        Span::styled(" cancel ", theme.muted()),
+    ]);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Search pull requests ")
+        .title_style(theme.panel_title().add_modifier(Modifier::BOLD))
+        .title_bottom(footer_hint)
+        .title_alignment(Alignment::Center)
+        .border_style(theme.panel_border())
+        .style(theme.panel_background());
+    f.render_widget(block, popup_area);
+
+    let inner = popup_area.inner(Margin {
+        horizontal: 2,
+        vertical: 1,
+    });
+
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("Query: ", theme.text()),
+            Span::styled(state.query.clone(), theme.text().bold()),
+            Span::styled("█", theme.key_hint()),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "GitHub search syntax, e.g. author:octocat is:merged label:bug",
+            theme.muted(),
+        )),
+    ];
+
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .style(theme.panel_background());
+    f.render_widget(paragraph, inner);
+}
@@ -3,24 +3,27 @@
 //! Renders the repository tabs and PR table.
 
 use crate::actions::{
-    Action, AvailableAction, BuildLogAction, ContextAction, DiffViewerAction, NavigationAction,
-    PullRequestAction,
+    Action, AvailableAction, BuildLogAction, CommandPaletteAction, ContextAction, DiffViewerAction,
+    NavigationAction, PrSearchAction, PullRequestAction, WatchListAction,
 };
 use crate::capabilities::PanelCapabilities;
 use crate::command_id::CommandId;
-use crate::state::AppState;
+use crate::domain_models::{MaturityState, Pr, PrTab};
+use crate::state::{AppState, TerminalSize};
 use crate::view_models::{
-    determine_main_content, MainContentViewModel, PrTableViewModel, RepositoryTabsViewModel,
-    StatusBarViewModel,
+    determine_main_content, EmptyStateViewModel, MainContentViewModel, PrPeekViewModel,
+    PrTableViewModel, RepositoryTabsViewModel, StatusBarViewModel,
 };
 use crate::views::repository_tabs_view::RepositoryTabsWidget;
 use crate::views::status_bar::StatusBarWidget;
-use crate::views::View;
+use crate::views::{centered_area, percent, EscapeBehavior, View};
+use gh_pr_lander_theme::Theme;
+use gh_text_width::{center, clip, pad_left, pad_right, width, ELLIPSIS};
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Margin, Rect},
+    style::{Color, Modifier, Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, Cell, Paragraph, Row, Table},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, Wrap},
     Frame,
 };
 
@@ -64,7 +67,7 @@ impl View for PullRequestView {
 
     fn translate_context_action(&self, action: ContextAction, _state: &AppState) -> Option<Action> {
         let pr_action = match action {
-            ContextAction::Confirm => PullRequestAction::OpenInBrowser,
+            ContextAction::Confirm => PullRequestAction::OpenInBrowser(PrTab::Conversation),
             ContextAction::ToggleSelect => PullRequestAction::ToggleSelection,
             ContextAction::SelectAll => PullRequestAction::SelectAll,
             ContextAction::DeselectAll => PullRequestAction::DeselectAll,
@@ -76,8 +79,11 @@ impl View for PullRequestView {
         matches!(
             action,
             Action::PullRequest(_)
+                | Action::PrSearch(_)
+                | Action::WatchList(WatchListAction::Open)
                 | Action::DiffViewer(DiffViewerAction::Open)
                 | Action::BuildLog(BuildLogAction::Open)
+                | Action::CommandPalette(CommandPaletteAction::Open(_))
                 | Action::ViewContext(_)
                 | Action::Navigate(_)
                 | Action::Global(_)
@@ -85,27 +91,112 @@ impl View for PullRequestView {
         )
     }
 
-    fn available_actions(&self, _state: &AppState) -> Vec<AvailableAction> {
+    fn escape_behavior(&self, state: &AppState) -> EscapeBehavior {
+        let main_view = &state.main_view;
+        let in_search = main_view
+            .repo_data
+            .get(&main_view.selected_repository)
+            .is_some_and(|data| data.search.is_some());
+        if main_view.peek.is_some() {
+            EscapeBehavior::Forward(Box::new(Action::PullRequest(PullRequestAction::ClosePeek)))
+        } else if in_search {
+            // Back to the loaded PR list
+            EscapeBehavior::Forward(Box::new(Action::PrSearch(PrSearchAction::Exit)))
+        } else {
+            EscapeBehavior::Pop
+        }
+    }
+
+    fn available_actions(&self, state: &AppState) -> Vec<AvailableAction> {
+        let main_view = &state.main_view;
+        let repo_data = main_view.repo_data.get(&main_view.selected_repository);
+
+        // An inaccessible repository has no PRs to act on, only ways out
+        if repo_data.is_some_and(|data| data.inaccessible.is_some()) {
+            return vec![
+                AvailableAction::primary(CommandId::PrRefresh, "Retry Now"),
+                AvailableAction::primary(CommandId::RepositoryOpenInBrowser, "Check Access"),
+                AvailableAction::primary(CommandId::RepositoryRemove, "Remove Repo"),
+                AvailableAction::navigation(CommandId::RepositoryNext, "Next Repo")
+                    .enabled_if(main_view.repositories.len() > 1),
+                AvailableAction::navigation(CommandId::CommandPaletteOpen, "Commands"),
+                AvailableAction::navigation(CommandId::KeyBindingsToggleView, "Help"),
+            ];
+        }
+
+        let cursor = repo_data.and_then(|data| data.prs.get(data.selected_pr));
+        let selected = repo_data.map_or(0, |data| data.selected_pr_numbers.len());
+        let cursor_selected = cursor
+            .zip(repo_data)
+            .is_some_and(|(pr, data)| data.selected_pr_numbers.contains(&pr.number));
+
+        // Operations apply to the selected PRs, or to the cursor PR
+        let targets: Vec<&Pr> = match repo_data {
+            Some(data) if selected > 0 => data
+                .prs
+                .iter()
+                .filter(|pr| data.selected_pr_numbers.contains(&pr.number))
+                .collect(),
+            _ => cursor.into_iter().collect(),
+        };
+        let online = state.splash.client_ready();
+        // Without a token PRs can be read, but not changed
+        let can_operate = online && !state.splash.read_only() && !targets.is_empty();
+        let any_draft = targets.iter().any(|pr| pr.maturity == MaturityState::Draft);
+        let counted = |action: AvailableAction| {
+            if selected > 0 {
+                action.with_count(selected)
+            } else {
+                action
+            }
+        };
+
         vec![
-            AvailableAction::primary(CommandId::Confirm, "Open"),
-            AvailableAction::primary(CommandId::PrMerge, "Merge"),
-            AvailableAction::primary(CommandId::PrOpenBuildLogs, "Build Logs"),
-            AvailableAction::primary(CommandId::DiffViewerOpen, "Diffs"),
-            AvailableAction::selection(CommandId::ToggleSelect, "Select"),
-            AvailableAction::navigation(CommandId::RepositoryNext, "Next Repo"),
+            AvailableAction::selection(
+                CommandId::PrToggleSelection,
+                if cursor_selected {
+                    "Deselect"
+                } else {
+                    "Select"
+                },
+            )
+            .enabled_if(cursor.is_some()),
+            counted(AvailableAction::primary(CommandId::PrMerge, "Merge"))
+                .enabled_if(can_operate && !any_draft),
+            counted(AvailableAction::primary(CommandId::PrApprove, "Approve"))
+                .enabled_if(can_operate),
+            counted(AvailableAction::primary(CommandId::PrComment, "Comment"))
+                .enabled_if(can_operate),
+            AvailableAction::primary(CommandId::PrOpenInBrowser, "Open")
+                .enabled_if(cursor.is_some()),
+            AvailableAction::primary(CommandId::DiffViewerOpen, "Diff")
+                .enabled_if(online && cursor.is_some()),
+            AvailableAction::primary(CommandId::PrOpenBuildLogs, "Build Logs")
+                .enabled_if(online && cursor.is_some()),
+            AvailableAction::navigation(CommandId::RepositoryNext, "Next Repo")
+                .enabled_if(main_view.repositories.len() > 1),
+            AvailableAction::navigation(CommandId::CommandPaletteOpen, "Commands"),
+            AvailableAction::navigation(CommandId::KeyBindingsToggleView, "Help"),
         ]
     }
 }
 
 /// Render the main view
 fn render(state: &AppState, area: Rect, f: &mut Frame) {
+    if TerminalSize::from(f.area()).is_compact() {
+        render_compact(state, area, f);
+        return;
+    }
+
+    let status_vm = StatusBarViewModel::from_state(state, area.width);
+
     // Split into repository tabs, content area, and status bar
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1), // Repository tab bar (single row)
-            Constraint::Min(0),    // Content area
-            Constraint::Length(1), // Status bar (single row)
+            Constraint::Length(1),                  // Repository tab bar (single row)
+            Constraint::Min(0),                     // Content area
+            Constraint::Length(status_vm.height()), // Status bar
         ])
         .split(area);
 
@@ -119,55 +210,160 @@ fn render(state: &AppState, area: Rect, f: &mut Frame) {
             render_empty_state(&empty_vm, chunks[1], f);
         }
         MainContentViewModel::PrTable => {
-            render_pr_table(state, chunks[1], f);
+            render_pr_table(state, chunks[1], true, f);
+            if let Some(peek_vm) = PrPeekViewModel::from_state(state) {
+                render_peek(&peek_vm, &state.theme, chunks[1], f);
+            }
         }
     }
 
     // Render status bar at the bottom
-    let status_vm = StatusBarViewModel::from_state(state);
     f.render_widget(StatusBarWidget(&status_vm), chunks[2]);
 }
 
+/// Render the compact layout for small terminals and tmux panes
+///
+/// Tabs, status bar and the table borders would leave (almost) no rows for
+/// the PRs, so only the table itself is shown.
+fn render_compact(state: &AppState, area: Rect, f: &mut Frame) {
+    match determine_main_content(state) {
+        MainContentViewModel::Empty(empty_vm) => render_empty_state(&empty_vm, area, f),
+        MainContentViewModel::PrTable => {
+            render_pr_table(state, area, false, f);
+            if let Some(peek_vm) = PrPeekViewModel::from_state(state) {
+                render_peek(&peek_vm, &state.theme, area, f);
+            }
+        }
+    }
+}
+
 /// Render the PR table for the currently selected repository
-fn render_pr_table(state: &AppState, area: Rect, f: &mut Frame) {
+///
+/// Without `bordered` the header and status hints of the block are left out.
+fn render_pr_table(state: &AppState, area: Rect, bordered: bool, f: &mut Frame) {
     let theme = &state.theme;
     let repo_idx = state.main_view.selected_repository;
 
-    let repo = &state.main_view.repositories[repo_idx];
-    let repo_data = state.main_view.repo_data.get(&repo_idx).unwrap();
+    let (Some(repo), Some(repo_data)) = (
+        state.main_view.repositories.get(repo_idx),
+        state.main_view.repo_data.get(&repo_idx),
+    ) else {
+        // Unreachable through `determine_main_content`, but never panic mid-draw
+        render_empty_state(&EmptyStateViewModel::loading(state), area, f);
+        return;
+    };
 
     // Build view model
-    let vm = PrTableViewModel::from_repo_data(repo_data, repo, theme);
+    let vm = PrTableViewModel::from_repo_data(repo_data, repo, theme, &state.app_config.ui)
+        .with_notes(&state.main_view.notes, repo)
+        .with_in_flight(&state.main_view.in_flight, repo)
+        .with_duplicates(&state.main_view.bot_updates, repo_data)
+        .with_filter(
+            repo_data,
+            state.main_view.login_for(repo),
+            &state.main_view.pr_sizes,
+        )
+        .with_sort(
+            state.main_view.sort_of(repo_idx),
+            repo_data,
+            state.main_view.login_for(repo),
+        )
+        .with_snoozed(
+            &state.main_view.snoozed,
+            state.main_view.show_snoozed,
+            repo,
+            theme,
+        )
+        .with_unseen(
+            &state.main_view.unseen_prs(repo_idx),
+            state.app_config.ui.bold_unseen,
+        )
+        .with_repeated_authors_collapsed();
 
     // Build block with header
     let status_line = Line::from(vm.header.status_text.clone())
         .style(ratatui::style::Style::default().fg(vm.header.status_color))
         .right_aligned();
 
-    let block = Block::bordered()
+    let mut block = Block::bordered()
         .border_type(ratatui::widgets::BorderType::QuadrantOutside)
         .border_style(ratatui::style::Style::default().fg(theme.accent_primary))
         .title(vm.header.title.clone())
         .title(status_line);
 
+    // Local note of the focused PR in the bottom border
+    if let Some(note) = &vm.note {
+        block = block.title_bottom(Line::from(Span::styled(
+            format!(" 📝 {} ", note),
+            theme.muted(),
+        )));
+    }
+
+    // What the focused PR's status is waiting for, right of the note
+    if let Some(hint) = vm.status_hint {
+        block = block.title_bottom(
+            Line::from(Span::styled(format!(" {} ", hint), theme.muted())).right_aligned(),
+        );
+    }
+
     // Build header row
     let header_style = theme.table_header();
 
-    // Column widths: Delta=12, Maturity=6, Review=6
-    let header_cells = [
+    // Column widths: Why=17, Delta=12, Size=4, Maturity=6, Review=6, Comments=8
+    let mut header_cells = vec![
         "  #PR".to_string(),
         "Title".to_string(),
         "Author".to_string(),
-        format!("{:^12}", "Delta"),
-        format!("{:^6}", "Draft?"),
-        format!("{:^6}", "Rev."),
+        center("Delta", 12),
+        "Size".to_string(),
+        center("Draft?", 6),
+        center("Rev.", 6),
+        "Comm.".to_string(),
         "Status".to_string(),
-    ]
-    .into_iter()
-    .map(|h| Cell::from(h).style(header_style));
+    ];
+    // Why a PR needs attention, only with the attention sort
+    if vm.attention_column {
+        header_cells.insert(1, "Why".to_string());
+    }
+    let header_cells = header_cells
+        .into_iter()
+        .map(|h| Cell::from(h).style(header_style));
 
     let header = Row::new(header_cells).style(header_style).height(1);
 
+    // Calculate PR number column width based on longest PR number
+    // Format is "● #12345" or "  #12345" - find max length across all rows
+    let pr_number_width = vm
+        .rows
+        .iter()
+        .map(|row| width(&row.pr_number))
+        .max()
+        .unwrap_or(6) // fallback to 6 if no rows
+        .max(5) as u16; // minimum width for "  #PR" header
+
+    let mut widths = vec![
+        Constraint::Length(pr_number_width), // #PR - dynamic width
+        Constraint::Percentage(35),          // Title (reduced)
+        Constraint::Percentage(10),          // Author
+        Constraint::Length(12),              // Delta (+123 -456)
+        Constraint::Length(4),               // Size (XL)
+        Constraint::Length(6),               // Maturity
+        Constraint::Length(6),               // Review
+        Constraint::Length(8),               // Comments (12💬 3⚠)
+        Constraint::Percentage(15),          // Status
+    ];
+    if vm.attention_column {
+        widths.insert(1, Constraint::Length(17)); // Why ("changes requested")
+    }
+    let title_column = if vm.attention_column { 2 } else { 1 };
+    let table_area = if bordered { block.inner(area) } else { area };
+    let title_width = column_width(
+        table_area,
+        &widths,
+        title_column,
+        vm.selected_index.is_some(),
+    );
+
     // Build rows from view model
     let rows: Vec<Row> = vm
         .rows
@@ -177,66 +373,267 @@ fn render_pr_table(state: &AppState, area: Rect, f: &mut Frame) {
 
             // Build delta cell with colored additions (green) and deletions (red)
             // Right-align additions, space, left-align deletions within 12-char column
-            let add_str = format!("+{}", row_vm.additions);
-            let del_str = format!("-{}", row_vm.deletions);
-            let delta_line = Line::from(vec![
-                Span::styled(format!("{:>5}", add_str), Style::default().fg(Color::Green)),
-                Span::raw(" "),
-                Span::styled(format!("{:<6}", del_str), Style::default().fg(Color::Red)),
-            ]);
-
-            Row::new(vec![
+            // The stats arrive after the list, until then the delta is pending
+            let delta_line = if row_vm.stats_pending {
+                Line::from(Span::styled(center(ELLIPSIS, 12), theme.muted()))
+            } else {
+                let add_str = format!("+{}", row_vm.additions);
+                let del_str = format!("-{}", row_vm.deletions);
+                Line::from(vec![
+                    Span::styled(pad_left(&add_str, 5), Style::default().fg(Color::Green)),
+                    Span::raw(" "),
+                    Span::styled(pad_right(&del_str, 6), Style::default().fg(Color::Red)),
+                ])
+            };
+
+            // Comment count, then the unresolved review threads highlighted
+            let mut comment_spans = Vec::with_capacity(3);
+            if !row_vm.comments_text.is_empty() {
+                comment_spans.push(Span::raw(row_vm.comments_text.clone()));
+            }
+            if !row_vm.unresolved_text.is_empty() {
+                if !comment_spans.is_empty() {
+                    comment_spans.push(Span::raw(" "));
+                }
+                comment_spans.push(Span::styled(
+                    row_vm.unresolved_text.clone(),
+                    theme.warning().add_modifier(Modifier::BOLD),
+                ));
+            }
+
+            // Bot PRs lead with their update type badge
+            let mut title_spans = Vec::with_capacity(4);
+            if let Some((ref badge, color)) = row_vm.update_badge {
+                title_spans.push(Span::styled(
+                    format!("[{}] ", badge),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ));
+            }
+            let title_style = if row_vm.bold_title {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            title_spans.push(Span::styled(row_vm.title.clone(), title_style));
+            // Fork PRs name the fork's branch after the title
+            if let Some(ref head) = row_vm.fork_head {
+                title_spans.push(Span::styled(format!("  ⑂ {}", head), theme.muted()));
+            }
+            // PRs merging into another branch than the repository's name it
+            if let Some(ref base) = row_vm.base_branch {
+                title_spans.push(Span::styled(format!("  → {}", base), theme.muted()));
+            }
+
+            let mut cells = vec![
                 Cell::from(row_vm.pr_number.clone()),
-                Cell::from(row_vm.title.clone()),
+                Cell::from(Line::from(fit_spans(title_spans, title_width))),
                 Cell::from(row_vm.author.clone()),
                 Cell::from(delta_line),
-                Cell::from(format!("{:^6}", row_vm.maturity_text))
+                Cell::from(center(row_vm.size_badge.0, 4)).style(
+                    Style::default()
+                        .fg(row_vm.size_badge.1)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Cell::from(center(&row_vm.maturity_text, 6))
                     .style(Style::default().fg(row_vm.maturity_color)),
-                Cell::from(format!("{:^6}", row_vm.review_text))
+                Cell::from(center(&row_vm.review_text, 6))
                     .style(Style::default().fg(row_vm.review_color)),
+                Cell::from(Line::from(comment_spans)),
                 Cell::from(row_vm.status_text.clone())
                     .style(Style::default().fg(row_vm.status_color)),
-            ])
-            .style(style)
-            .height(1)
+            ];
+            if vm.attention_column {
+                cells.insert(
+                    1,
+                    Cell::from(row_vm.attention.unwrap_or_default()).style(theme.warning()),
+                );
+            }
+            Row::new(cells).style(style).height(1)
         })
         .collect();
 
-    // Calculate PR number column width based on longest PR number
-    // Format is "● #12345" or "  #12345" - find max length across all rows
-    let pr_number_width = vm
-        .rows
-        .iter()
-        .map(|row| row.pr_number.chars().count())
-        .max()
-        .unwrap_or(6) // fallback to 6 if no rows
-        .max(5) as u16; // minimum width for "  #PR" header
-
-    let widths = [
-        Constraint::Length(pr_number_width), // #PR - dynamic width
-        Constraint::Percentage(35),          // Title (reduced)
-        Constraint::Percentage(10),          // Author
-        Constraint::Length(12),              // Delta (+123 -456)
-        Constraint::Length(6),               // Maturity
-        Constraint::Length(6),               // Review
-        Constraint::Percentage(15),          // Status
-    ];
-
-    let table = Table::new(rows, widths)
+    let mut table = Table::new(rows, widths)
         .header(header)
-        .block(block)
         .row_highlight_style(theme.table_selected())
         .highlight_symbol("> ");
+    if bordered {
+        table = table.block(block);
+    }
 
     // Create a table state for highlighting
     let mut table_state = ratatui::widgets::TableState::default();
-    table_state.select(Some(vm.selected_index));
+    table_state.select(vm.selected_index);
 
     f.render_stateful_widget(table, area, &mut table_state);
 }
 
+/// Cells the table gives column `index`
+///
+/// Mirrors the `Table` layout: the highlight symbol takes its cells in front
+/// of the columns while a row is selected, columns are one cell apart.
+fn column_width(area: Rect, widths: &[Constraint], index: usize, selected: bool) -> usize {
+    let selection = if selected { 2 } else { 0 };
+    let [_, columns] =
+        Layout::horizontal([Constraint::Length(selection), Constraint::Fill(0)]).areas(area);
+    Layout::horizontal(widths.iter().copied())
+        .flex(Flex::Start)
+        .spacing(1)
+        .split(columns)
+        .get(index)
+        .map_or(0, |column| column.width as usize)
+}
+
+/// Spans cut to `cells`, ending in an ellipsis when cut
+///
+/// The cell clips too, but mid grapheme and without showing the title goes on.
+fn fit_spans(spans: Vec<Span<'static>>, cells: usize) -> Vec<Span<'static>> {
+    if spans.iter().map(|span| width(&span.content)).sum::<usize>() <= cells {
+        return spans;
+    }
+    let mut budget = cells.saturating_sub(1);
+    let mut fitted = Vec::with_capacity(spans.len());
+    for span in spans {
+        let span_width = width(&span.content);
+        if span_width <= budget {
+            budget -= span_width;
+            fitted.push(span);
+            continue;
+        }
+        let cut = format!("{}{}", clip(&span.content, budget), ELLIPSIS);
+        fitted.push(Span::styled(cut, span.style));
+        break;
+    }
+    fitted
+}
+
 /// Render empty/loading state
-fn render_empty_state(vm: &crate::view_models::EmptyStateViewModel, area: Rect, f: &mut Frame) {
+/// Render the peek popup floating over the PR table
+///
+/// There is no overlay, the table stays readable around the popup.
+fn render_peek(vm: &PrPeekViewModel, theme: &Theme, area: Rect, f: &mut Frame) {
+    let popup_width = percent(area.width, 70).clamp(50, 100);
+    let popup_height = percent(area.height, 70).clamp(12, 30);
+    let popup_area = centered_area(area, popup_width, popup_height);
+
+    f.render_widget(Clear, popup_area);
+
+    let footer_hint = Line::from(vec![
+        Span::styled(" j/k", theme.key_hint().bold()),
+        Span::styled(" move on  ", theme.muted()),
+        Span::styled("Esc", theme.key_hint().bold()),
+        Span::styled(" close ", theme.muted()),
+    ]);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", vm.title))
+        .title_style(theme.panel_title().add_modifier(Modifier::BOLD))
+        .title_bottom(footer_hint)
+        .title_alignment(Alignment::Center)
+        .border_style(theme.panel_border().add_modifier(Modifier::BOLD))
+        .style(theme.panel_background());
+    f.render_widget(block, popup_area);
+
+    let inner = popup_area.inner(Margin {
+        horizontal: 2,
+        vertical: 1,
+    });
+
+    let field = |name: &'static str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{:<9}", name), theme.muted()),
+            Span::styled(value, theme.text()),
+        ])
+    };
+    let mut branches = field("Branches", vm.branches.clone());
+    if vm.unusual_base {
+        branches.spans[1].style = theme.warning();
+        branches.push_span(Span::styled(" (not the tracked branch)", theme.warning()));
+    }
+    let mut details = vec![
+        field("Author", vm.author.clone()),
+        branches,
+        field("Times", vm.times.clone()),
+        field("Changes", vm.changes.clone()),
+    ];
+    if !vm.labels.is_empty() {
+        details.push(field("Labels", vm.labels.clone()));
+    }
+    let mut ci = field("CI", vm.ci.clone());
+    if let Some(hint) = &vm.ci_hint {
+        ci.push_span(Span::styled(format!(" ({})", hint), theme.muted()));
+    }
+    if let Some(eta) = &vm.ci_eta {
+        let style = if vm.ci_overdue {
+            theme.warning()
+        } else {
+            theme.muted()
+        };
+        ci.push_span(Span::styled(format!(" · {}", eta), style));
+    }
+    details.push(ci);
+    for group in &vm.ci_checks {
+        details.push(Line::from(Span::styled(
+            format!("  {}", group.heading()),
+            theme.muted(),
+        )));
+        // Only required failures block the merge
+        let failed_style = if group.required {
+            theme.error()
+        } else {
+            theme.warning()
+        };
+        for name in &group.failed {
+            details.push(Line::from(Span::styled(
+                format!("    ✗ {}", name),
+                failed_style,
+            )));
+        }
+        for name in &group.pending {
+            details.push(Line::from(Span::styled(
+                format!("    ⏳ {}", name),
+                theme.text(),
+            )));
+        }
+        if group.passed > 0 {
+            details.push(Line::from(Span::styled(
+                format!("    ✓ {} passed", group.passed),
+                theme.muted(),
+            )));
+        }
+    }
+    if let Some(note) = vm.ci_checks_note {
+        details.push(Line::from(Span::styled(
+            format!("  ({})", note),
+            theme.muted(),
+        )));
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(details.len() as u16),
+            Constraint::Length(1), // Spacing
+            Constraint::Min(1),    // Description
+        ])
+        .split(inner);
+
+    f.render_widget(Paragraph::new(details), chunks[0]);
+
+    let body_style = if vm.body_is_placeholder {
+        theme.muted()
+    } else {
+        theme.text()
+    };
+    f.render_widget(
+        Paragraph::new(vm.body.clone())
+            .style(body_style)
+            .wrap(Wrap { trim: false }),
+        chunks[2],
+    );
+}
+
+fn render_empty_state(vm: &EmptyStateViewModel, area: Rect, f: &mut Frame) {
     let block = Block::bordered()
         .border_type(ratatui::widgets::BorderType::QuadrantOutside)
         .border_style(ratatui::style::Style::default().fg(vm.border_color));
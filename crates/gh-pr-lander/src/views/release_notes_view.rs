@@ -0,0 +1,156 @@
+//! Release Notes View
+//!
+//! Floating panel previewing the drafted release notes as markdown source,
+//! scrolled with the navigation keys. The notes are only copied or written
+//! to a file, never published.
+
+use crate::actions::{
+    Action, AvailableAction, ContextAction, NavigationAction, ReleaseNotesAction,
+};
+use crate::capabilities::PanelCapabilities;
+use crate::command_id::CommandId;
+use crate::state::AppState;
+use crate::view_models::ReleaseNotesViewModel;
+use crate::views::{centered_area, View, ViewId};
+use ratatui::{prelude::*, widgets::*};
+
+/// Release notes view - scrollable markdown preview
+#[derive(Debug, Clone)]
+pub struct ReleaseNotesView;
+
+impl ReleaseNotesView {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ReleaseNotesView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for ReleaseNotesView {
+    fn view_id(&self) -> ViewId {
+        ViewId::ReleaseNotes
+    }
+
+    fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
+        render(state, area, f);
+    }
+
+    fn capabilities(&self, _state: &AppState) -> PanelCapabilities {
+        PanelCapabilities::SCROLL_VERTICAL | PanelCapabilities::VIM_NAVIGATION_BINDINGS
+    }
+
+    fn clone_box(&self) -> Box<dyn View> {
+        Box::new(self.clone())
+    }
+
+    fn translate_navigation(&self, nav: NavigationAction) -> Option<Action> {
+        let action = match nav {
+            NavigationAction::Next => ReleaseNotesAction::NavigateNext,
+            NavigationAction::Previous => ReleaseNotesAction::NavigatePrevious,
+            NavigationAction::ToTop => ReleaseNotesAction::NavigateToTop,
+            NavigationAction::ToBottom => ReleaseNotesAction::NavigateToBottom,
+            NavigationAction::Left | NavigationAction::Right => return None,
+        };
+        Some(Action::ReleaseNotes(action))
+    }
+
+    fn translate_context_action(&self, action: ContextAction, _state: &AppState) -> Option<Action> {
+        match action {
+            ContextAction::Confirm => Some(Action::ReleaseNotes(ReleaseNotesAction::Copy)),
+            _ => None,
+        }
+    }
+
+    fn accepts_action(&self, action: &Action) -> bool {
+        matches!(
+            action,
+            Action::ReleaseNotes(_)
+                | Action::ViewContext(_)
+                | Action::Navigate(_)
+                | Action::Global(_)
+        )
+    }
+
+    fn available_actions(&self, _state: &AppState) -> Vec<AvailableAction> {
+        vec![
+            AvailableAction::primary(CommandId::ReleaseNotesCopy, "Copy"),
+            AvailableAction::primary(CommandId::ReleaseNotesWrite, "Write file"),
+            AvailableAction::navigation(CommandId::GlobalClose, "Close"),
+        ]
+    }
+}
+
+/// Render the release notes panel
+fn render(state: &AppState, area: Rect, f: &mut Frame) {
+    let theme = &state.theme;
+
+    // Render dimmed overlay over the entire screen
+    let overlay = Block::default().style(
+        Style::default()
+            .bg(Color::Black)
+            .add_modifier(Modifier::DIM),
+    );
+    f.render_widget(overlay, area);
+
+    let panel_area = centered_area(
+        area,
+        area.width.min(100),
+        area.height.saturating_sub(4).max(8).min(area.height),
+    );
+    f.render_widget(Clear, panel_area);
+
+    // Borders and the position line
+    let height = panel_area.height.saturating_sub(3) as usize;
+    let vm = ReleaseNotesViewModel::from_state(&state.release_notes, height);
+
+    let block = Block::default()
+        .title(format!(" {} ", vm.title))
+        .borders(Borders::ALL)
+        .border_style(theme.panel_border())
+        .title_style(theme.panel_title())
+        .title_alignment(Alignment::Center)
+        .style(theme.panel_background());
+    let inner = block.inner(panel_area);
+    f.render_widget(block, panel_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    if let Some(ref message) = vm.message {
+        let style = if vm.is_error {
+            theme.error()
+        } else {
+            theme.muted()
+        };
+        let paragraph = Paragraph::new(message.clone())
+            .style(style)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        f.render_widget(paragraph, chunks[0]);
+        return;
+    }
+
+    let lines: Vec<Line> = vm
+        .lines
+        .iter()
+        .map(|line| {
+            let style = if line.starts_with('#') {
+                theme.table_row().bold()
+            } else {
+                theme.table_row()
+            };
+            Line::styled(format!(" {}", line), style)
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+    f.render_widget(
+        Paragraph::new(format!(" {}", vm.position)).style(theme.muted()),
+        chunks[1],
+    );
+}
@@ -25,13 +25,30 @@ impl Widget for RepositoryTabsWidget<'_> {
 
         let mut x = area.x;
 
+        // Read-only badge on the far right, tabs stop before it
+        let mut end = area.x + area.width;
+        if let Some(ref badge) = vm.read_only {
+            if badge.width < area.width / 2 {
+                end -= badge.width;
+                buf.set_string(end, area.y, &badge.text, badge.style);
+            }
+        }
+
         // Render help hint on the far left
         buf.set_string(x, area.y, &vm.help_hint.text, vm.help_hint.style);
         x += vm.help_hint.width;
 
+        // Render the active profile after it
+        if let Some(ref profile) = vm.profile {
+            if x + profile.width < end {
+                buf.set_string(x, area.y, &profile.text, profile.style);
+                x += profile.width;
+            }
+        }
+
         // Render each tab with powerline separators
         for tab in &vm.tabs {
-            if x + tab.width > area.x + area.width {
+            if x + tab.width > end {
                 break; // Don't overflow
             }
 
@@ -42,7 +59,7 @@ impl Widget for RepositoryTabsWidget<'_> {
             // Tab content with padding
             let padded_text = format!("  {}  ", tab.display_text);
             buf.set_string(x, area.y, &padded_text, tab.style);
-            x += padded_text.chars().count() as u16;
+            x += gh_text_width::width(&padded_text) as u16;
 
             // Right powerline separator
             buf.set_string(x, area.y, RIGHT_SEP, tab.right_sep_style);
@@ -50,7 +67,7 @@ impl Widget for RepositoryTabsWidget<'_> {
         }
 
         // Render add repo hint at the end
-        if x + vm.hint.width <= area.x + area.width {
+        if x + vm.hint.width <= end {
             buf.set_string(x + 1, area.y, &vm.hint.text, vm.hint.style);
         }
     }
@@ -0,0 +1,214 @@
+//! Reviewer Picker View
+//!
+//! Multi-select popup to request reviews on a PR, with the owners of its
+//! changed files suggested first.
+
+use crate::actions::{
+    Action, ContextAction, NavigationAction, ReviewerPickerAction, TextInputAction,
+};
+use crate::capabilities::PanelCapabilities;
+use crate::state::AppState;
+use crate::view_models::ReviewerPickerViewModel;
+use crate::views::{centered_area, percent, View, ViewId};
+use gh_pr_lander_theme::Theme;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Reviewer picker view - floating checkbox list of users and teams
+#[derive(Debug, Clone)]
+pub struct ReviewerPickerView;
+
+impl ReviewerPickerView {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ReviewerPickerView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for ReviewerPickerView {
+    fn view_id(&self) -> ViewId {
+        ViewId::ReviewerPicker
+    }
+
+    fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
+        if let Some(ref picker) = state.reviewer_picker {
+            let vm = ReviewerPickerViewModel::from_state(picker);
+            render_popup(&vm, &state.theme, area, f);
+        }
+    }
+
+    fn capabilities(&self, _state: &AppState) -> PanelCapabilities {
+        // Typing filters the reviewers, arrow keys move through them
+        PanelCapabilities::TEXT_INPUT | PanelCapabilities::ITEM_NAVIGATION
+    }
+
+    fn clone_box(&self) -> Box<dyn View> {
+        Box::new(self.clone())
+    }
+
+    fn translate_navigation(&self, nav: NavigationAction) -> Option<Action> {
+        let action = match nav {
+            NavigationAction::Next => ReviewerPickerAction::NavigateNext,
+            NavigationAction::Previous => ReviewerPickerAction::NavigatePrevious,
+            NavigationAction::Left
+            | NavigationAction::Right
+            | NavigationAction::ToTop
+            | NavigationAction::ToBottom => return None,
+        };
+        Some(Action::ReviewerPicker(action))
+    }
+
+    fn translate_text_input(&self, input: TextInputAction) -> Option<Action> {
+        let action = match input {
+            // Space toggles, logins and team names don't contain spaces
+            TextInputAction::Char(' ') => ReviewerPickerAction::Toggle,
+            TextInputAction::Char(c) => ReviewerPickerAction::InputChar(c),
+            TextInputAction::Backspace => ReviewerPickerAction::InputBackspace,
+            TextInputAction::ClearLine => ReviewerPickerAction::InputClear,
+            TextInputAction::Escape => ReviewerPickerAction::Close,
+            TextInputAction::Confirm => ReviewerPickerAction::Apply,
+        };
+        Some(Action::ReviewerPicker(action))
+    }
+
+    fn translate_paste(&self, text: &str) -> Vec<Action> {
+        // Pasted spaces are part of the filter text, they don't toggle
+        crate::utils::paste::single_line(text)
+            .chars()
+            .map(|c| Action::ReviewerPicker(ReviewerPickerAction::InputChar(c)))
+            .collect()
+    }
+
+    fn translate_context_action(&self, action: ContextAction, _state: &AppState) -> Option<Action> {
+        match action {
+            ContextAction::Confirm => Some(Action::ReviewerPicker(ReviewerPickerAction::Apply)),
+            ContextAction::ToggleSelect => {
+                Some(Action::ReviewerPicker(ReviewerPickerAction::Toggle))
+            }
+            _ => None,
+        }
+    }
+
+    fn accepts_action(&self, action: &Action) -> bool {
+        matches!(
+            action,
+            Action::ReviewerPicker(_)
+                | Action::ViewContext(_)
+                | Action::Navigate(_)
+                | Action::TextInput(_)
+                | Action::Global(_)
+        )
+    }
+}
+
+/// Render the reviewer picker as a centered floating window
+fn render_popup(vm: &ReviewerPickerViewModel, theme: &Theme, area: Rect, f: &mut Frame) {
+    // Render dimmed overlay over the entire screen to create modal effect
+    let overlay = Block::default().style(
+        Style::default()
+            .bg(ratatui::style::Color::Black)
+            .add_modifier(Modifier::DIM),
+    );
+    f.render_widget(overlay, area);
+
+    let popup_width = percent(area.width, 60).clamp(50, 80);
+    let popup_height = percent(area.height, 60).clamp(12, 24);
+    let popup_area = centered_area(area, popup_width, popup_height);
+
+    f.render_widget(Clear, popup_area);
+
+    let footer_hint = Line::from(vec![
+        Span::styled(" Space", theme.key_hint().bold()),
+        Span::styled(" toggle  ", theme.muted()),
+        Span::styled("Enter", theme.key_hint().bold()),
+        Span::styled(" request  ", theme.muted()),
+        Span::styled("Esc", theme.key_hint().bold()),
+        Span::styled(" cancel ", theme.muted()),
+    ]);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", vm.title))
+        .title_style(theme.panel_title().add_modifier(Modifier::BOLD))
+        .title_bottom(footer_hint)
+        .title_alignment(Alignment::Center)
+        .border_style(theme.panel_border().add_modifier(Modifier::BOLD))
+        .style(theme.panel_background());
+    f.render_widget(block, popup_area);
+
+    let inner = popup_area.inner(Margin {
+        horizontal: 2,
+        vertical: 1,
+    });
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Filter
+            Constraint::Length(1), // Spacing
+            Constraint::Min(1),    // Reviewers
+            Constraint::Length(1), // Pending requests
+        ])
+        .split(inner);
+
+    let filter = Line::from(vec![
+        Span::styled("Filter: ", theme.text()),
+        Span::styled(vm.query.clone(), theme.text().bold()),
+        Span::styled("█", theme.key_hint()),
+    ]);
+    f.render_widget(Paragraph::new(filter), chunks[0]);
+
+    if let Some(ref message) = vm.message {
+        f.render_widget(
+            Paragraph::new(message.clone())
+                .style(theme.muted())
+                .alignment(Alignment::Center),
+            chunks[2],
+        );
+    } else {
+        // Section headers take a line each, keep the cursor row in view
+        let mut lines: Vec<Line> = Vec::new();
+        let mut cursor_line = 0;
+        for row in &vm.rows {
+            if let Some(header) = row.header {
+                lines.push(Line::from(Span::styled(header, theme.panel_title().bold())));
+            }
+            if row.is_selected {
+                cursor_line = lines.len();
+            }
+            let mut text_style = if row.checkbox == "[x]" {
+                theme.text().bold()
+            } else {
+                theme.text()
+            };
+            if row.is_selected {
+                text_style = text_style.bg(theme.active_bg).fg(theme.active_fg);
+            }
+            let indicator = if row.is_selected { "> " } else { "  " };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{}{} ", indicator, row.checkbox), text_style),
+                Span::styled(row.name.clone(), text_style),
+                Span::styled(format!("  {}", row.ownership), theme.muted()),
+            ]));
+        }
+        let visible = chunks[2].height as usize;
+        let offset = (cursor_line + 1).saturating_sub(visible);
+        let lines: Vec<Line> = lines.into_iter().skip(offset).take(visible).collect();
+        f.render_widget(Paragraph::new(lines), chunks[2]);
+    }
+
+    f.render_widget(
+        Paragraph::new(vm.pending.clone()).style(theme.muted()),
+        chunks[3],
+    );
+}
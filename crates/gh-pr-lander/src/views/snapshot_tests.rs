@@ -0,0 +1,462 @@
+//! Snapshot tests for the major views
+//!
+//! Each case builds an `AppState` by running fixture actions through the
+//! reducer, renders the full view stack into a `TestBackend` at a few
+//! terminal sizes and compares the text of the buffer against a golden file
+//! in `src/views/snapshots/`.
+//!
+//! Timestamps that end up on screen are pinned to `frozen_time()`, so the
+//! snapshots don't depend on the clock or the timezone.
+//!
+//! To accept changed output, run the tests with `UPDATE_SNAPSHOTS=1` and
+//! review the diff of the snapshot files.
+
+use crate::actions::{
+    Action, BuildLogAction, CommandPaletteAction, ConfirmationPopupAction, DiffViewerAction,
+    FilterBuilderAction, GlobalAction, LabelPickerAction, PullRequestAction, RepositoryAction,
+    StatusBarAction,
+};
+use crate::demo::fixtures;
+use crate::domain_models::Repository;
+use crate::state::{
+    AppState, BuildLogContext, BuildLogJobMetadata, BuildLogJobStatus, BuildLogPrContext,
+    ConfirmationIntent,
+};
+use crate::store::Store;
+use crate::views::{render, BuildLogView, CommandPaletteView, PullRequestView};
+use chrono::{DateTime, Local, TimeZone};
+use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Terminal sizes every view is rendered at
+const SIZES: &[(u16, u16)] = &[(80, 24), (140, 40)];
+
+/// Sizes around the compact layout: a small tmux pane, the smallest full
+/// layout and a large screen
+const LAYOUT_SIZES: &[(u16, u16)] = &[(60, 12), (80, 20), (200, 50)];
+
+/// Seed of the generated repositories and PRs
+const SEED: u64 = 7;
+
+const SMALL_DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,6 +1,7 @@
+ pub fn add(a: i32, b: i32) -> i32 {
+-    a + b
++    // Saturate instead of overflowing
++    a.saturating_add(b)
+ }
+
+ pub fn sub(a: i32, b: i32) -> i32 {
+     a - b
+";
+
+const SMALL_LOG: &str = "\
+2025-01-15T12:00:00.0000000Z ##[group]Run cargo build
+2025-01-15T12:00:01.0000000Z    Compiling gh-pr-lander v0.1.0
+2025-01-15T12:00:02.0000000Z     Finished dev profile
+2025-01-15T12:00:02.0000000Z ##[endgroup]
+2025-01-15T12:00:03.0000000Z ##[group]Run cargo test
+2025-01-15T12:00:04.0000000Z running 3 tests
+2025-01-15T12:00:05.0000000Z ##[error]test views::snapshot_tests::main_view failed
+2025-01-15T12:00:05.0000000Z ##[endgroup]
+";
+
+/// The instant all on-screen timestamps are pinned to
+fn frozen_time() -> DateTime<Local> {
+    Local.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap()
+}
+
+/// Store with two loaded repositories and the PR table on screen
+fn main_view_store() -> Store {
+    let mut store = Store::new(AppState::default());
+    store.dispatch(Action::Global(GlobalAction::ReplaceView(Box::new(
+        PullRequestView::new(),
+    ))));
+    for repo in fixtures::repositories(2, SEED) {
+        let prs = fixtures::pull_requests(&repo, 12, SEED);
+        store.dispatch(Action::Repository(RepositoryAction::AddRepository(
+            repo.clone(),
+        )));
+        store.dispatch(Action::PullRequest(PullRequestAction::Loaded {
+            repo,
+            prs,
+            from_cache: None,
+        }));
+    }
+    store.dispatch(Action::PullRequest(PullRequestAction::NavigateNext));
+    store.dispatch(Action::PullRequest(PullRequestAction::ToggleSelection));
+    freeze_timestamps(store.state_mut());
+    store
+}
+
+/// Replace the wall-clock timestamps set by reducers with `frozen_time()`
+fn freeze_timestamps(state: &mut AppState) {
+    for repo_data in state.main_view.repo_data.values_mut() {
+        if repo_data.last_updated.is_some() {
+            repo_data.last_updated = Some(frozen_time());
+        }
+    }
+    for message in state.status_bar.messages.iter_mut() {
+        message.timestamp = frozen_time();
+    }
+}
+
+fn build_log_store() -> Store {
+    let mut store = main_view_store();
+    let zip = fixtures::workflow_logs_zip(&[("test".to_string(), SMALL_LOG.to_string())]);
+    let parsed = gh_actions_log_parser::parse_workflow_logs(&zip).unwrap();
+    let job = gh_actions_log_parser::job_log_to_tree(parsed.jobs[0].clone());
+    let job_metadata = vec![BuildLogJobMetadata {
+        name: job.name.clone(),
+        workflow_name: "CI".to_string(),
+        status: BuildLogJobStatus::Failure,
+        error_count: job.error_count,
+        duration: Some(std::time::Duration::from_secs(65)),
+        html_url: String::new(),
+    }];
+    let workflow = gh_actions_log_parser::WorkflowNode {
+        name: "CI".to_string(),
+        has_failures: true,
+        total_errors: job.error_count,
+        jobs: vec![job],
+    };
+
+    store.dispatch(Action::Global(GlobalAction::PushView(Box::new(
+        BuildLogView::new(),
+    ))));
+    store.dispatch(Action::BuildLog(BuildLogAction::Loaded {
+        workflows: vec![Arc::new(workflow)],
+        job_metadata,
+        context: BuildLogContext::Pr(BuildLogPrContext {
+            number: 42,
+            title: "Saturate arithmetic".to_string(),
+            author: "alice".to_string(),
+        }),
+        parsed_runs: Vec::new(),
+    }));
+    store
+}
+
+fn diff_viewer_store(width: u16, height: u16) -> Store {
+    let mut store = main_view_store();
+    let diff = gh_diff_viewer::parse_unified_diff(SMALL_DIFF, "base", "head").unwrap();
+    store.dispatch(Action::DiffViewer(DiffViewerAction::Open));
+    store.dispatch(Action::DiffViewer(DiffViewerAction::Loaded {
+        diff,
+        repo: Repository::new("acme", "api-gateway", "main"),
+        pr_number: 42,
+        pr_title: "Saturate arithmetic".to_string(),
+        head_sha: "2222222".to_string(),
+        comments: Vec::new(),
+    }));
+    store.dispatch(Action::Global(GlobalAction::Resized { width, height }));
+    store
+}
+
+fn confirmation_popup_store() -> Store {
+    let mut store = main_view_store();
+    store.dispatch(Action::ConfirmationPopup(ConfirmationPopupAction::Show {
+        intent: ConfirmationIntent::Approve {
+            pr_numbers: vec![111, 110],
+        },
+        default_message: "LGTM :rocket:".to_string(),
+        repo_context: "acme/api-gateway".to_string(),
+        warnings: vec!["#110: CI is failing".to_string()],
+    }));
+    store
+}
+
+fn label_picker_store() -> Store {
+    let mut store = main_view_store();
+    store.dispatch(Action::LabelPicker(LabelPickerAction::Show {
+        repo: Repository::new("acme", "api-gateway", "main"),
+        prs: vec![
+            (111, vec!["dependencies".to_string(), "rust".to_string()]),
+            (110, vec!["dependencies".to_string()]),
+        ],
+    }));
+    let label = |name: &str, color: &str, description: Option<&str>| gh_client::Label {
+        name: name.to_string(),
+        color: color.to_string(),
+        description: description.map(str::to_string),
+    };
+    store.dispatch(Action::LabelPicker(LabelPickerAction::Loaded(vec![
+        label("bug", "d73a4a", Some("Something isn't working")),
+        label(
+            "dependencies",
+            "0366d6",
+            Some("Pull requests that update a dependency"),
+        ),
+        label("rust", "dea584", None),
+        label("skip-changelog", "ededed", None),
+    ])));
+    store.dispatch(Action::LabelPicker(LabelPickerAction::NavigatePrevious));
+    store.dispatch(Action::LabelPicker(LabelPickerAction::Toggle));
+    store
+}
+
+fn filter_builder_store() -> Store {
+    let mut store = main_view_store();
+    store.dispatch(Action::FilterBuilder(FilterBuilderAction::Open));
+    let actions = [
+        FilterBuilderAction::NavigateNext,
+        FilterBuilderAction::InputChar('b'),
+        FilterBuilderAction::InputChar('o'),
+        FilterBuilderAction::InputChar('t'),
+        FilterBuilderAction::NavigateNext,
+        FilterBuilderAction::NavigateNext,
+        FilterBuilderAction::NavigateNext,
+        FilterBuilderAction::CycleNext,
+    ];
+    for action in actions {
+        store.dispatch(Action::FilterBuilder(action));
+    }
+    store
+}
+
+fn command_palette_store() -> Store {
+    let mut store = main_view_store();
+    store.dispatch(Action::Global(GlobalAction::PushView(Box::new(
+        CommandPaletteView::new(),
+    ))));
+    for c in "merge".chars() {
+        store.dispatch(Action::CommandPalette(CommandPaletteAction::Char(c)));
+    }
+    store
+}
+
+fn status_bar_store() -> Store {
+    let mut store = main_view_store();
+    store.dispatch(Action::StatusBar(StatusBarAction::error(
+        "Merge failed: Required status check \"ci / build (ubuntu-latest, stable)\" is expected but has not been reported yet",
+        "Merge",
+    )));
+    freeze_timestamps(store.state_mut());
+    store
+}
+
+/// Render the whole view stack and return the buffer
+fn render_to_buffer(state: &AppState, width: u16, height: u16) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal
+        .draw(|frame| render(state, frame.area(), frame))
+        .unwrap();
+    terminal.backend().buffer().clone()
+}
+
+/// Text content of a buffer, one line per row, trailing spaces removed
+fn buffer_to_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut text = String::new();
+    for y in area.top()..area.bottom() {
+        // The cells covered by a wide character are left out, the text then
+        // lines up like the terminal shows it
+        let mut line = String::new();
+        let mut x = area.left();
+        while x < area.right() {
+            let symbol = buffer[(x, y)].symbol();
+            line.push_str(symbol);
+            x += gh_text_width::width(symbol).max(1) as u16;
+        }
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+/// Compare `actual` with the stored snapshot `name`, or store it when
+/// `UPDATE_SNAPSHOTS` is set
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/views/snapshots")
+        .join(format!("{}.txt", name));
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "Missing snapshot {}, run with UPDATE_SNAPSHOTS=1 to create it",
+            path.display()
+        )
+    });
+    if expected != actual {
+        panic!(
+            "Snapshot {} changed, run with UPDATE_SNAPSHOTS=1 to accept\n--- expected\n{}\n+++ actual\n{}",
+            name, expected, actual
+        );
+    }
+}
+
+/// Render `store_for(width, height)` at every size and check the snapshots
+fn check_view(name: &str, store_for: impl Fn(u16, u16) -> Store) {
+    check_view_at(name, SIZES, store_for);
+}
+
+/// Render `store_for(width, height)` at each of `sizes` and check the snapshots
+fn check_view_at(name: &str, sizes: &[(u16, u16)], store_for: impl Fn(u16, u16) -> Store) {
+    for &(width, height) in sizes {
+        let store = store_for(width, height);
+        let buffer = render_to_buffer(store.state(), width, height);
+        assert_snapshot(
+            &format!("{}_{}x{}", name, width, height),
+            &buffer_to_text(&buffer),
+        );
+    }
+}
+
+#[test]
+fn test_main_view_snapshot() {
+    check_view("main_view", |_, _| main_view_store());
+}
+
+#[test]
+fn test_main_view_high_contrast_snapshot() {
+    check_view("main_view_high_contrast", |_, _| {
+        let mut store = main_view_store();
+        store.state_mut().theme = gh_pr_lander_theme::Theme::high_contrast();
+        store
+    });
+}
+
+#[test]
+fn test_main_view_wide_title_snapshot() {
+    check_view("main_view_wide_title", |_, _| {
+        let mut store = main_view_store();
+        let repo_data = store.state_mut().main_view.repo_data.get_mut(&0).unwrap();
+        repo_data.prs[0].title =
+            "🚀🔥 リリース: launch the café checklist for 漢字 locales everywhere".to_string();
+        store
+    });
+}
+
+#[test]
+fn test_build_log_view_snapshot() {
+    check_view("build_log_view", |_, _| build_log_store());
+}
+
+#[test]
+fn test_diff_viewer_view_snapshot() {
+    check_view("diff_viewer_view", diff_viewer_store);
+}
+
+#[test]
+fn test_confirmation_popup_snapshot() {
+    check_view("confirmation_popup", |_, _| confirmation_popup_store());
+}
+
+#[test]
+fn test_label_picker_snapshot() {
+    check_view("label_picker", |_, _| label_picker_store());
+}
+
+#[test]
+fn test_filter_builder_snapshot() {
+    check_view("filter_builder", |_, _| filter_builder_store());
+}
+
+#[test]
+fn test_command_palette_snapshot() {
+    check_view("command_palette", |_, _| command_palette_store());
+}
+
+#[test]
+fn test_status_bar_snapshot() {
+    check_view("status_bar", |_, _| status_bar_store());
+}
+
+#[test]
+fn test_layout_sizes_snapshot() {
+    check_view_at("main_view", LAYOUT_SIZES, |_, _| main_view_store());
+    check_view_at("filter_builder", LAYOUT_SIZES, |_, _| {
+        filter_builder_store()
+    });
+}
+
+#[test]
+fn test_clamped_popup_keeps_cursor_in_view() {
+    let mut store = filter_builder_store();
+    for _ in 0..8 {
+        store.dispatch(Action::FilterBuilder(FilterBuilderAction::NavigateNext));
+    }
+    // Only 7 of the 13 rows fit, the cursor is on the last one
+    let text = buffer_to_text(&render_to_buffer(store.state(), 60, 12));
+    assert!(text.contains("> ") && text.contains("Save as"));
+    assert!(!text.contains("Author is"));
+}
+
+#[test]
+fn test_render_is_repeatable() {
+    // Rendering twice must give the same buffer (no clock or randomness)
+    let store = build_log_store();
+    assert_eq!(
+        render_to_buffer(store.state(), 80, 24),
+        render_to_buffer(store.state(), 80, 24)
+    );
+}
+
+#[test]
+fn test_terminal_too_small_snapshot() {
+    let store = main_view_store();
+    let buffer = render_to_buffer(store.state(), 30, 8);
+    assert_snapshot("terminal_too_small_30x8", &buffer_to_text(&buffer));
+}
+
+#[test]
+fn test_small_sizes_do_not_panic() {
+    let stores = [
+        main_view_store(),
+        build_log_store(),
+        diff_viewer_store(40, 10),
+        confirmation_popup_store(),
+        label_picker_store(),
+        filter_builder_store(),
+        command_palette_store(),
+        status_bar_store(),
+    ];
+    for store in &stores {
+        for (width, height) in [
+            (0, 0),
+            (1, 1),
+            (39, 9),
+            (40, 10),
+            (41, 11),
+            (60, 12),
+            (70, 15),
+            (79, 19),
+            (80, 20),
+            (200, 10),
+            (1000, 100),
+        ] {
+            render_to_buffer(store.state(), width, height);
+        }
+    }
+}
+
+#[test]
+fn test_selected_repository_without_data_does_not_panic() {
+    let mut store = main_view_store();
+    let state = store.state_mut();
+    // The selected repository's data is gone (RepositoryNext racing Loaded)
+    let selected = state.main_view.selected_repository;
+    let data = state.main_view.repo_data.remove(&selected).unwrap();
+    assert!(!data.prs.is_empty());
+    let text = buffer_to_text(&render_to_buffer(store.state(), 80, 24));
+    assert!(text.contains("Loading pull requests..."));
+
+    // Loaded data left at an index past the repository list (after a removal)
+    let state = store.state_mut();
+    let idx = state.main_view.repositories.len();
+    state.main_view.selected_repository = idx;
+    state.main_view.repo_data.insert(idx, data);
+    let text = buffer_to_text(&render_to_buffer(store.state(), 80, 24));
+    assert!(text.contains("Loading pull requests..."));
+}
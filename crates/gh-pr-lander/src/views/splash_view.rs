@@ -1,6 +1,6 @@
 use crate::actions::Action;
 use crate::capabilities::PanelCapabilities;
-use crate::state::AppState;
+use crate::state::{AppState, SplashState, StageStatus};
 use crate::views::View;
 use figlet_rs::FIGfont;
 use gh_pr_lander_theme::Theme;
@@ -8,11 +8,14 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::Stylize,
     text::{Line, Span},
-    widgets::{Block, Paragraph},
+    widgets::{Block, Paragraph, Wrap},
     Frame,
 };
 
 /// Splash screen view - shown during bootstrap
+///
+/// Renders the bootstrap stage checklist and, when a stage failed, its error
+/// together with the retry/set token/quit hints.
 #[derive(Debug, Clone)]
 pub struct SplashView;
 
@@ -28,7 +31,13 @@ impl View for SplashView {
     }
 
     fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
-        render_splash(&state.splash, &state.theme, area, f);
+        render_splash(
+            &state.splash,
+            &state.theme,
+            state.app_config.ui.reduced_motion,
+            area,
+            f,
+        );
     }
 
     fn capabilities(&self, _state: &AppState) -> PanelCapabilities {
@@ -47,7 +56,15 @@ impl View for SplashView {
 }
 
 /// Render the splash screen with snake loading animation
-fn render_splash(state: &crate::state::SplashState, theme: &Theme, area: Rect, f: &mut Frame) {
+///
+/// With `reduced_motion` the snake and the stage spinner are drawn static.
+fn render_splash(
+    state: &SplashState,
+    theme: &Theme,
+    reduced_motion: bool,
+    area: Rect,
+    f: &mut Frame,
+) {
     // Full screen background
     let background_block = Block::default().style(theme.panel_background());
     f.render_widget(background_block, area);
@@ -56,13 +73,14 @@ fn render_splash(state: &crate::state::SplashState, theme: &Theme, area: Rect, f
     let title_lines = generate_figlet_title(theme);
     let title_height = title_lines.len() as u16;
 
-    // Title at the top
+    // Title at the top, cut off on short screens
     let title_area = Rect {
         x: area.x,
-        y: area.y + 2,
+        y: area.y.saturating_add(2),
         width: area.width,
         height: title_height + 1,
-    };
+    }
+    .intersection(area);
 
     let title = Paragraph::new(title_lines).alignment(Alignment::Center);
     f.render_widget(title, title_area);
@@ -89,20 +107,119 @@ fn render_splash(state: &crate::state::SplashState, theme: &Theme, area: Rect, f
     let center_area = horizontal_chunks[1];
 
     // Generate snake animation pattern (5x5 grid)
-    let snake_lines = generate_snake_animation(state.animation_frame, theme);
+    let frame = if reduced_motion {
+        STATIC_SNAKE_FRAME
+    } else {
+        state.animation_frame
+    };
+    let snake_lines = generate_snake_animation(frame, theme);
 
     // Add loading text
+    let loading_text = if state.has_failed() {
+        Span::styled("Failed", theme.error().bold())
+    } else {
+        Span::styled("Loading...", theme.text().dim())
+    };
     let mut lines = snake_lines;
     lines.push(Line::from(""));
-    lines.push(
-        Line::from(Span::styled("Loading...", theme.text().dim())).alignment(Alignment::Center),
-    );
+    lines.push(Line::from(loading_text).alignment(Alignment::Center));
 
     let paragraph = Paragraph::new(lines)
         .alignment(Alignment::Center)
         .style(theme.panel_background());
 
     f.render_widget(paragraph, center_area);
+
+    // Stage checklist (and error details) below the animation
+    let checklist_y = vertical_chunks[1].y + vertical_chunks[1].height + 1;
+    let checklist_width = area.width.min(72);
+    let checklist_area = Rect {
+        x: area.x + (area.width - checklist_width) / 2,
+        y: checklist_y.min(area.y + area.height),
+        width: checklist_width,
+        height: (area.y + area.height).saturating_sub(checklist_y),
+    };
+    let checklist = Paragraph::new(generate_stage_checklist(state, theme, reduced_motion))
+        .wrap(Wrap { trim: false })
+        .style(theme.panel_background());
+    f.render_widget(checklist, checklist_area);
+}
+
+/// Spinner frames for the stage currently in progress
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Static indicator for the stage in progress with reduced motion
+const STATIC_PROGRESS: &str = "…";
+
+/// Snake frame shown with reduced motion (full-length snake)
+const STATIC_SNAKE_FRAME: usize = 4;
+
+/// Generate the bootstrap stage checklist with the error text of a failed stage
+fn generate_stage_checklist(
+    state: &SplashState,
+    theme: &Theme,
+    reduced_motion: bool,
+) -> Vec<Line<'static>> {
+    let current = state.current_stage();
+    let mut lines: Vec<Line<'static>> = state
+        .stages
+        .iter()
+        .map(|(stage, status)| {
+            let (symbol, style) = match status {
+                StageStatus::Done => ("✓".to_string(), theme.success()),
+                StageStatus::Warning(_) => ("!".to_string(), theme.warning().bold()),
+                StageStatus::Failed(_) => ("✗".to_string(), theme.error().bold()),
+                StageStatus::Pending if current == Some(*stage) => {
+                    let symbol = if reduced_motion {
+                        STATIC_PROGRESS
+                    } else {
+                        SPINNER_FRAMES[state.animation_frame % SPINNER_FRAMES.len()]
+                    };
+                    (symbol.to_string(), theme.text().cyan())
+                }
+                StageStatus::Pending => ("○".to_string(), theme.muted()),
+            };
+            let label_style = match status {
+                StageStatus::Pending if current != Some(*stage) => theme.muted(),
+                _ => theme.text(),
+            };
+            Line::from(vec![
+                Span::styled(format!("  {} ", symbol), style),
+                Span::styled(stage.label().to_string(), label_style),
+            ])
+        })
+        .collect();
+
+    if let Some((_, warning)) = state.warning().filter(|_| !state.has_failed()) {
+        lines.push(Line::from(""));
+        for warning_line in warning.lines() {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", warning_line),
+                theme.warning(),
+            )));
+        }
+    }
+
+    if let Some((_, error)) = state.failure() {
+        lines.push(Line::from(""));
+        for error_line in error.lines() {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", error_line),
+                theme.error(),
+            )));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("  r", theme.key_hint()),
+            Span::styled(" retry   ", theme.key_description()),
+            Span::styled("t", theme.key_hint()),
+            Span::styled(" set token   ", theme.key_description()),
+            Span::styled("q", theme.key_hint()),
+            Span::styled(" quit", theme.key_description()),
+        ]));
+    }
+
+    lines
 }
 
 /// Generate snake animation for the current frame
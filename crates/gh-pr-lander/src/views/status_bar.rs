@@ -1,8 +1,11 @@
 //! Status Bar Widget
 //!
 //! Renders the status bar at the bottom of the screen.
-//! Format: `[timestamp] emoji message                     [source]`
+//! Format: `[timestamp] emoji message          operation • rate • [source] • hint`
+//!
+//! An expanded error continues on a second row.
 
+use crate::view_models::status_bar::SEPARATOR;
 use crate::view_models::StatusBarViewModel;
 use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
 
@@ -17,22 +20,19 @@ impl Widget for StatusBarWidget<'_> {
             return;
         }
 
-        // Fill entire row with background
-        for x in area.x..area.x + area.width {
-            buf[(x, area.y)].set_bg(vm.bg_color).set_char(' ');
-        }
-
-        if !vm.has_message {
-            return;
+        // Fill the bar with background
+        for y in area.y..area.y + area.height.min(vm.height()) {
+            for x in area.x..area.x + area.width {
+                buf[(x, y)].set_bg(vm.bg_color).set_char(' ');
+            }
         }
 
         let mut x = area.x + 1; // 1 char padding
 
-        // Timestamp in brackets (if present)
-        if !vm.timestamp.is_empty() {
-            let ts_str = format!("[{}] ", vm.timestamp);
-            buf.set_string(x, area.y, &ts_str, vm.metadata_style);
-            x += ts_str.len() as u16;
+        // Timestamp in brackets (if it fits)
+        if let Some(timestamp) = &vm.timestamp {
+            buf.set_string(x, area.y, &timestamp.text, timestamp.style);
+            x += timestamp.width() as u16 + 1;
         }
 
         // Emoji (estimate 2 chars width for most emoji)
@@ -40,33 +40,32 @@ impl Widget for StatusBarWidget<'_> {
         buf.set_string(x, area.y, &emoji_str, vm.message_style);
         x += 3; // emoji + space (emoji typically renders as 2 cells)
 
-        // Calculate space for source on right
-        let source_width = if !vm.source.is_empty() {
-            vm.source.len() + 3 // "[source] "
-        } else {
-            0
-        };
-
-        // Message (truncate if needed)
-        let available_width = area
-            .width
-            .saturating_sub(x - area.x + source_width as u16 + 2);
+        // Message, already fitted by the view model
+        buf.set_string(x, area.y, &vm.message, vm.message_style);
 
-        if vm.message.len() > available_width as usize {
-            // Truncate with ellipsis
-            let truncate_at = available_width.saturating_sub(1) as usize;
-            let truncated: String = vm.message.chars().take(truncate_at).collect();
-            let display = format!("{}…", truncated);
-            buf.set_string(x, area.y, &display, vm.message_style);
-        } else {
-            buf.set_string(x, area.y, &vm.message, vm.message_style);
+        // Segments on the right side
+        if !vm.segments.is_empty() {
+            let separator_width = gh_text_width::width(SEPARATOR);
+            let width = vm.segments.iter().map(|s| s.width()).sum::<usize>()
+                + separator_width * (vm.segments.len() - 1);
+            let mut seg_x = (area.x + area.width)
+                .saturating_sub((width as u16).saturating_add(1))
+                .max(area.x);
+            for (idx, segment) in vm.segments.iter().enumerate() {
+                if idx > 0 {
+                    buf.set_string(seg_x, area.y, SEPARATOR, vm.separator_style);
+                    seg_x += separator_width as u16;
+                }
+                buf.set_string(seg_x, area.y, &segment.text, segment.style);
+                seg_x += segment.width() as u16;
+            }
         }
 
-        // Source on the right side
-        if !vm.source.is_empty() {
-            let source_str = format!("[{}]", vm.source);
-            let source_x = area.x + area.width - source_str.len() as u16 - 1;
-            buf.set_string(source_x, area.y, &source_str, vm.metadata_style);
+        // Rest of an expanded message
+        if let Some(continuation) = &vm.continuation {
+            if area.height > 1 {
+                buf.set_string(area.x + 1, area.y + 1, continuation, vm.message_style);
+            }
         }
     }
 }
@@ -0,0 +1,118 @@
+//! Status Message View
+//!
+//! Floating panel with the full latest status bar message, for messages the
+//! status bar had to truncate.
+
+use crate::actions::{Action, AvailableAction, ContextAction, GlobalAction};
+use crate::capabilities::PanelCapabilities;
+use crate::command_id::CommandId;
+use crate::state::AppState;
+use crate::utils::format::format_clock;
+use crate::views::{centered_area, View, ViewId};
+use ratatui::{prelude::*, widgets::*};
+
+/// Status message view - the latest message, wrapped
+#[derive(Debug, Clone)]
+pub struct StatusMessageView;
+
+impl StatusMessageView {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for StatusMessageView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for StatusMessageView {
+    fn view_id(&self) -> ViewId {
+        ViewId::StatusMessage
+    }
+
+    fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
+        render(state, area, f);
+    }
+
+    fn capabilities(&self, _state: &AppState) -> PanelCapabilities {
+        PanelCapabilities::empty()
+    }
+
+    fn clone_box(&self) -> Box<dyn View> {
+        Box::new(self.clone())
+    }
+
+    fn translate_context_action(&self, action: ContextAction, _state: &AppState) -> Option<Action> {
+        match action {
+            ContextAction::Confirm => Some(Action::Global(GlobalAction::Close)),
+            _ => None,
+        }
+    }
+
+    fn accepts_action(&self, action: &Action) -> bool {
+        matches!(action, Action::ViewContext(_) | Action::Global(_))
+    }
+
+    fn available_actions(&self, _state: &AppState) -> Vec<AvailableAction> {
+        vec![AvailableAction::navigation(CommandId::GlobalClose, "Close")]
+    }
+}
+
+/// Render the status message panel
+fn render(state: &AppState, area: Rect, f: &mut Frame) {
+    let theme = &state.theme;
+
+    // Render dimmed overlay over the entire screen
+    let overlay = Block::default().style(
+        Style::default()
+            .bg(Color::Black)
+            .add_modifier(Modifier::DIM),
+    );
+    f.render_widget(overlay, area);
+
+    let width = area.width.min(80);
+    // Borders and 1 cell padding on each side
+    let inner_width = usize::from(width.saturating_sub(4)).max(1);
+
+    let mut lines = Vec::new();
+    let mut rows = 1;
+    match state.status_bar.latest() {
+        Some(msg) => {
+            let mut header = vec![Span::styled(
+                format_clock(msg.timestamp.to_utc(), &state.app_config.ui),
+                theme.muted(),
+            )];
+            if !msg.source_action.is_empty() {
+                header.push(Span::styled(
+                    format!("  [{}]", msg.source_action),
+                    theme.muted(),
+                ));
+            }
+            lines.push(Line::from(header));
+            lines.push(Line::default());
+            let message = format!("{} {}", msg.kind.emoji(), msg.message);
+            rows = 2 + gh_text_width::width(&message).div_ceil(inner_width);
+            lines.push(Line::from(message));
+        }
+        None => lines.push(Line::styled("No status messages yet", theme.muted())),
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .style(theme.text())
+        .wrap(Wrap { trim: false });
+    // Word wrapping may take an extra row
+    let panel_area = centered_area(area, width, (rows as u16).saturating_add(5));
+    f.render_widget(Clear, panel_area);
+
+    let block = Block::default()
+        .title(" Status Message ")
+        .borders(Borders::ALL)
+        .border_style(theme.panel_border())
+        .title_style(theme.panel_title())
+        .title_alignment(Alignment::Center)
+        .padding(Padding::uniform(1))
+        .style(theme.panel_background());
+    f.render_widget(paragraph.block(block), panel_area);
+}
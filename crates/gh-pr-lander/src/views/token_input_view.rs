@@ -0,0 +1,142 @@
+//! Token Input View
+//!
+//! Masked input popup for storing a GitHub token in the keychain.
+
+use crate::actions::{Action, AuthAction, TextInputAction};
+use crate::capabilities::PanelCapabilities;
+use crate::state::{AppState, TokenInputState};
+use crate::views::{centered_area, percent, View, ViewId};
+use gh_pr_lander_theme::Theme;
+use ratatui::{
+    layout::{Alignment, Margin, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Token input view - floating panel with a masked input field
+#[derive(Debug, Clone)]
+pub struct TokenInputView;
+
+impl TokenInputView {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for TokenInputView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for TokenInputView {
+    fn view_id(&self) -> ViewId {
+        ViewId::TokenInput
+    }
+
+    fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
+        if let Some(ref input) = state.token_input {
+            render_popup(input, &state.theme, area, f);
+        }
+    }
+
+    fn capabilities(&self, _state: &AppState) -> PanelCapabilities {
+        PanelCapabilities::TEXT_INPUT
+    }
+
+    fn clone_box(&self) -> Box<dyn View> {
+        Box::new(self.clone())
+    }
+
+    fn translate_text_input(&self, input: TextInputAction) -> Option<Action> {
+        let action = match input {
+            TextInputAction::Char(c) => AuthAction::InputChar(c),
+            TextInputAction::Backspace => AuthAction::InputBackspace,
+            TextInputAction::ClearLine => AuthAction::InputClear,
+            TextInputAction::Escape => AuthAction::CloseTokenInput,
+            TextInputAction::Confirm => AuthAction::SubmitToken,
+        };
+        Some(Action::Auth(action))
+    }
+
+    fn accepts_action(&self, action: &Action) -> bool {
+        matches!(
+            action,
+            Action::Auth(_) | Action::TextInput(_) | Action::Global(_)
+        )
+    }
+}
+
+/// Render the token input as a centered floating window
+fn render_popup(state: &TokenInputState, theme: &Theme, area: Rect, f: &mut Frame) {
+    // Render dimmed overlay over the entire screen to create modal effect
+    let overlay = Block::default().style(
+        Style::default()
+            .bg(ratatui::style::Color::Black)
+            .add_modifier(Modifier::DIM),
+    );
+    f.render_widget(overlay, area);
+
+    let popup_width = percent(area.width, 60).clamp(50, 70).min(area.width);
+    let popup_height = 11.min(area.height);
+    let popup_area = centered_area(area, popup_width, popup_height);
+
+    f.render_widget(Clear, popup_area);
+
+    let footer_hint = Line::from(vec![
+        Span::styled(" Enter", theme.key_hint().bold()),
+        Span::styled(" validate & store  ", theme.muted()),
+        Span::styled("Esc", theme.key_hint().bold()),
+        Span::styled(" cancel ", theme.muted()),
+    ]);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Set GitHub token ")
+        .title_style(theme.panel_title().add_modifier(Modifier::BOLD))
+        .title_bottom(footer_hint)
+        .title_alignment(Alignment::Center)
+        .border_style(theme.panel_border())
+        .style(theme.panel_background());
+    f.render_widget(block, popup_area);
+
+    let inner = popup_area.inner(Margin {
+        horizontal: 2,
+        vertical: 1,
+    });
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Account: ", theme.muted()),
+            Span::styled(state.account(), theme.accent().bold()),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Token: ", theme.text()),
+            Span::styled(state.masked(), theme.text().bold()),
+            Span::styled("█", theme.key_hint()),
+        ]),
+        Line::from(""),
+    ];
+
+    if state.validating {
+        lines.push(Line::from(Span::styled(
+            "Validating token against /user...",
+            theme.muted(),
+        )));
+    } else if let Some(ref error) = state.error {
+        lines.push(Line::from(Span::styled(error.clone(), theme.error())));
+    } else {
+        lines.push(Line::from(Span::styled(
+            "Stored in the OS keychain, or an encrypted file if none is available.",
+            theme.muted(),
+        )));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .style(theme.panel_background());
+    f.render_widget(paragraph, inner);
+}
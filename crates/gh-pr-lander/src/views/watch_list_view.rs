@@ -0,0 +1,207 @@
+//! Watch List View
+//!
+//! Floating panel listing the watched PRs of all repositories with their
+//! last known state, CI and review status.
+
+use crate::actions::{Action, ContextAction, NavigationAction, WatchListAction};
+use crate::capabilities::PanelCapabilities;
+use crate::domain_models::{PrState, ReviewDecision};
+use crate::state::{AppState, WatchEntry};
+use crate::views::{centered_area, percent, View, ViewId};
+use gh_client::CiState;
+use gh_pr_lander_theme::Theme;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Watch list view - floating panel with one row per watched PR
+#[derive(Debug, Clone)]
+pub struct WatchListView;
+
+impl WatchListView {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for WatchListView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for WatchListView {
+    fn view_id(&self) -> ViewId {
+        ViewId::WatchList
+    }
+
+    fn render(&self, state: &AppState, area: Rect, f: &mut Frame) {
+        render(state, area, f);
+    }
+
+    fn capabilities(&self, _state: &AppState) -> PanelCapabilities {
+        PanelCapabilities::SCROLL_VERTICAL | PanelCapabilities::VIM_NAVIGATION_BINDINGS
+    }
+
+    fn clone_box(&self) -> Box<dyn View> {
+        Box::new(self.clone())
+    }
+
+    fn translate_navigation(&self, nav: NavigationAction) -> Option<Action> {
+        let action = match nav {
+            NavigationAction::Next => WatchListAction::NavigateNext,
+            NavigationAction::Previous => WatchListAction::NavigatePrevious,
+            NavigationAction::ToTop => WatchListAction::NavigateToTop,
+            NavigationAction::ToBottom => WatchListAction::NavigateToBottom,
+            NavigationAction::Left | NavigationAction::Right => return None,
+        };
+        Some(Action::WatchList(action))
+    }
+
+    fn translate_context_action(&self, action: ContextAction, _state: &AppState) -> Option<Action> {
+        let action = match action {
+            ContextAction::Confirm => WatchListAction::OpenSelected,
+            ContextAction::ToggleSelect => WatchListAction::RemoveSelected,
+            ContextAction::SelectAll | ContextAction::DeselectAll => return None,
+        };
+        Some(Action::WatchList(action))
+    }
+
+    fn accepts_action(&self, action: &Action) -> bool {
+        matches!(
+            action,
+            Action::WatchList(_) | Action::ViewContext(_) | Action::Navigate(_) | Action::Global(_)
+        )
+    }
+}
+
+/// Render the watch list panel
+fn render(state: &AppState, area: Rect, f: &mut Frame) {
+    let theme = &state.theme;
+    let watch_list = &state.main_view.watch_list;
+
+    // Render dimmed overlay over the entire screen
+    let overlay = Block::default().style(
+        Style::default()
+            .bg(ratatui::style::Color::Black)
+            .add_modifier(Modifier::DIM),
+    );
+    f.render_widget(overlay, area);
+
+    let panel_width = percent(area.width, 70).max(60).min(area.width);
+    let panel_height = (watch_list.entries.len() as u16)
+        .saturating_add(4)
+        .max(6)
+        .min(percent(area.height, 80))
+        .min(area.height);
+    let panel_area = centered_area(area, panel_width, panel_height);
+
+    f.render_widget(Clear, panel_area);
+
+    let footer_hint = Line::from(vec![
+        Span::styled(" Enter", theme.key_hint().bold()),
+        Span::styled(" open  ", theme.muted()),
+        Span::styled("Space", theme.key_hint().bold()),
+        Span::styled(" unwatch  ", theme.muted()),
+        Span::styled("Esc", theme.key_hint().bold()),
+        Span::styled(" close ", theme.muted()),
+    ]);
+
+    let block = Block::default()
+        .title(format!(" Watched PRs ({}) ", watch_list.entries.len()))
+        .borders(Borders::ALL)
+        .border_style(theme.panel_border())
+        .title_style(theme.panel_title())
+        .title_alignment(Alignment::Center)
+        .title_bottom(footer_hint);
+
+    let inner_area = block.inner(panel_area);
+
+    let lines: Vec<Line> = if watch_list.entries.is_empty() {
+        vec![
+            Line::default(),
+            Line::from(Span::styled(
+                "  No watched PRs. Press w w on a PR to watch it.",
+                theme.muted(),
+            )),
+        ]
+    } else {
+        watch_list
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| build_row(entry, idx == watch_list.selected, theme))
+            .collect()
+    };
+
+    // Keep the cursor visible
+    let visible = inner_area.height as usize;
+    let skip = (watch_list.selected + 1).saturating_sub(visible);
+
+    let paragraph = Paragraph::new(lines.into_iter().skip(skip).collect::<Vec<_>>())
+        .block(block)
+        .style(theme.panel_background());
+    f.render_widget(paragraph, panel_area);
+}
+
+/// Build one row: state, PR, CI, review and title
+fn build_row<'a>(entry: &WatchEntry, selected: bool, theme: &Theme) -> Line<'a> {
+    let (state_label, state_style, ci_label, ci_style, review_label, title) = match entry.status {
+        Some(ref status) => {
+            let (state_label, state_style) = match status.state {
+                PrState::Open => ("open", theme.success()),
+                PrState::Merged => ("merged", theme.accent()),
+                PrState::Closed => ("closed", theme.muted()),
+            };
+            let (ci_label, ci_style) = match status.ci {
+                Some(CiState::Success) => ("✓ CI", theme.success()),
+                Some(CiState::Failure) => ("✗ CI", theme.error()),
+                Some(CiState::Pending) => ("● CI", theme.warning()),
+                Some(CiState::Queued) => ("◌ CI", theme.warning()),
+                Some(CiState::WaitingApproval) => ("⏸ CI", theme.warning()),
+                Some(CiState::Unknown) | None => ("- CI", theme.muted()),
+            };
+            let review_label = match status.review {
+                ReviewDecision::Approved => "approved",
+                ReviewDecision::ChangesRequested => "changes requested",
+                ReviewDecision::Pending => "review pending",
+                ReviewDecision::Unknown => "",
+            };
+            (
+                state_label,
+                state_style,
+                ci_label,
+                ci_style,
+                review_label,
+                status.title.clone(),
+            )
+        }
+        None => (
+            "…",
+            theme.muted(),
+            "",
+            theme.muted(),
+            "",
+            "checking...".to_string(),
+        ),
+    };
+
+    let row = Line::from(vec![
+        Span::raw(if selected { " > " } else { "   " }),
+        Span::styled(format!("{:<7}", state_label), state_style),
+        Span::styled(format!("{:<32}", entry.pr.to_string()), theme.text().bold()),
+        Span::styled(format!("{:<6}", ci_label), ci_style),
+        Span::styled(format!("{:<19}", review_label), theme.muted()),
+        Span::styled(title, theme.text()),
+    ]);
+
+    if selected {
+        row.style(theme.table_selected())
+    } else {
+        row
+    }
+}
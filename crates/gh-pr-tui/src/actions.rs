@@ -66,8 +66,12 @@ pub enum Action {
     NavigateRight,    // vim: l, arrow right - right/expand in active panel
 
     // Semantic scroll actions (vim-style, capability-based)
-    ScrollToTop,    // vim: gg - scroll to top of current panel
-    ScrollToBottom, // vim: G - scroll to bottom of current panel
+    ScrollToTop,        // vim: gg - scroll to top of current panel
+    ScrollToBottom,     // vim: G - scroll to bottom of current panel
+    ScrollPageDown,     // PageDown - scroll a page down
+    ScrollPageUp,       // PageUp - scroll a page up
+    ScrollHalfPageDown, // vim: Ctrl+d - scroll half a page down
+    ScrollHalfPageUp,   // vim: Ctrl+u - scroll half a page up
 
     // Add repository popup
     ShowAddRepoPopup,
@@ -99,9 +103,11 @@ pub enum Action {
     SetReposLoading(Vec<usize>), // Set multiple repos to loading state
     TickSpinner,                 // Increment spinner animation frame
 
-    // Recurring updates
-    StartRecurringUpdates(u64), // Start recurring updates with interval in milliseconds
-    RecurringUpdateTriggered,   // Triggered when recurring update interval elapses
+    // Recurring updates (one schedule per id, starting an id again replaces it)
+    StartRecurringUpdates(crate::state::ScheduleId, u64), // Start with interval in milliseconds
+    StopRecurringUpdates(crate::state::ScheduleId),
+    RecurringUpdateTriggered(crate::state::ScheduleId), // Interval of the schedule elapsed
+    SchedulesChanged(Vec<crate::state::ScheduleInfo>),  // Active schedules, for the debug console
 
     // Background task completion notifications
     BootstrapComplete(Result<BootstrapResult, String>),
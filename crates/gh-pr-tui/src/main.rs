@@ -28,6 +28,8 @@ use crate::theme::Theme;
 
 mod actions;
 mod capabilities;
+// Not wired into the command palette yet, only its tests use it
+#[allow(dead_code)]
 mod command_palette_integration;
 mod config;
 mod panel_capabilities;
@@ -79,7 +81,7 @@ fn shutdown() -> Result<()> {
     Ok(())
 }
 
-async fn update(app: &mut App, msg: Action) {
+fn update(app: &mut App, msg: Action) {
     // When close PR popup is open, handle popup-specific actions
     let msg = if app.store.state().ui.close_pr_state.is_some() {
         match msg {
@@ -120,8 +122,8 @@ async fn update(app: &mut App, msg: Action) {
     let dispatcher = crate::middleware::Dispatcher::new(app.action_tx.clone());
 
     // Dispatch through middleware chain, then reducer
-    // Middleware can handle async operations and dispatch follow-up actions
-    app.store.dispatch_async(msg, &dispatcher).await;
+    // Middleware spawns async operations and dispatches follow-up actions
+    app.store.dispatch_through(msg, &dispatcher);
 }
 
 fn start_event_handler(
@@ -245,7 +247,7 @@ async fn run_with_log_buffer(log_buffer: log_capture::LogBuffer) -> Result<()> {
 
         match maybe_action {
             Ok(Some(action)) => {
-                update(&mut app, action).await;
+                update(&mut app, action);
             }
             Ok(None) => {
                 // Channel closed - this shouldn't happen in normal operation
@@ -427,6 +429,7 @@ impl App {
             ),
         );
 
+        #[allow(deprecated)]
         let cache_file = gh_pr_config::get_cache_file_path()
             .unwrap_or_else(|_| std::env::temp_dir().join("gh-api-cache.json"));
 
@@ -435,13 +438,16 @@ impl App {
         let mut store = Store::new(initial_state);
 
         // Add middleware in order (first added = first called)
-        // 1. Shutdown middleware - handles graceful shutdown (must be first to intercept Quit/FatalError)
+        // 0. Scheduler middleware - manages recurring updates (before shutdown, so Quit cancels them)
+        store.add_middleware(crate::middleware::SchedulerMiddleware::new());
+
+        // 1. Shutdown middleware - handles graceful shutdown (blocks Quit/FatalError for all later middleware)
         store.add_middleware(crate::middleware::ShutdownMiddleware::new(
             should_quit.clone(),
         ));
 
         // 2. Logging middleware - logs all actions for debugging
-        store.add_middleware(crate::middleware::LoggingMiddleware::new());
+        store.add_middleware(crate::middleware::logging_middleware());
 
         // 3. Splash screen middleware - manages splash screen lifecycle and spinner animation
         store.add_middleware(crate::middleware::SplashScreenMiddleware::new());
@@ -469,14 +475,6 @@ impl App {
             .unwrap_or_default()
     }
 
-    fn repo(&self) -> Option<&Repo> {
-        self.store
-            .state()
-            .repos
-            .recent_repos
-            .get(self.store.state().repos.selected_repo)
-    }
-
     /// Get mutable table state for ratatui rendering
     ///
     /// IMPORTANT: This is ONLY for ratatui's StatefulWidget API requirement.
@@ -537,7 +535,7 @@ pub async fn fetch_github_data(
     }
 
     // Sort by PR number (descending) for stable, predictable ordering
-    prs.sort_by(|a, b| b.number.cmp(&a.number));
+    prs.sort_by_key(|pr| std::cmp::Reverse(pr.number));
 
     Ok(prs)
 }
@@ -620,7 +618,7 @@ pub async fn fetch_github_data_cached(
                         prs.push(pr);
                     }
 
-                    prs.sort_by(|a, b| b.number.cmp(&a.number));
+                    prs.sort_by_key(|pr| std::cmp::Reverse(pr.number));
 
                     // If cache entry was stale (status_code 200), refresh in background
                     // but return cached data immediately for fast startup
@@ -929,6 +927,7 @@ fn handle_key_event(key: KeyEvent, ctx: &KeyEventContext) -> Action {
 }
 
 /// loading recent repositories from a local config file, that is just json file
+#[allow(deprecated)]
 fn loading_recent_repos() -> Result<Vec<Repo>> {
     let repos = if let Ok(recent_repos) = gh_pr_config::open_recent_repositories_file() {
         let reader = BufReader::new(recent_repos);
@@ -948,6 +947,7 @@ fn loading_recent_repos() -> Result<Vec<Repo>> {
 }
 
 /// Storing recent repositories to a local json config file
+#[allow(deprecated)]
 fn store_recent_repos(repos: &[Repo]) -> Result<()> {
     let file = gh_pr_config::create_recent_repositories_file()?;
     serde_json::to_writer_pretty(file, &repos)
@@ -958,15 +958,9 @@ fn store_recent_repos(repos: &[Repo]) -> Result<()> {
     Ok(())
 }
 
-fn store_persisted_state(state: &PersistedState) -> Result<()> {
-    let file = gh_pr_config::create_session_file()?;
-    serde_json::to_writer_pretty(file, state).context("Failed to write persisted state to file")?;
-
-    debug!("Stored persisted state: {:?}", state);
-
-    Ok(())
-}
-
+// gh-pr-tui keeps its session and recent repositories in the working
+// directory, the legacy files gh-pr-lander moved away from
+#[allow(deprecated)]
 fn load_persisted_state() -> Result<PersistedState> {
     let file = gh_pr_config::open_session_file()?;
     let reader = BufReader::new(file);
@@ -6,7 +6,7 @@
 //! - Multi-key sequences (e.g., "gg" for go-to-top)
 //! - Vim-style navigation patterns
 
-use super::{BoxFuture, Dispatcher, Middleware};
+use super::{Dispatcher, Middleware};
 use crate::{actions::Action, state::AppState};
 use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::time::{Duration, Instant};
@@ -166,38 +166,38 @@ impl KeyboardMiddleware {
         match key.code {
             KeyCode::Down if capabilities.supports_vim_navigation() => {
                 dispatcher.dispatch(Action::NavigateNext);
-                return false;
+                false
             }
 
             KeyCode::Up if capabilities.supports_vim_navigation() => {
                 dispatcher.dispatch(Action::NavigatePrevious);
-                return false;
+                false
             }
 
             KeyCode::Left if capabilities.supports_vim_navigation() => {
                 dispatcher.dispatch(Action::NavigateLeft);
-                return false;
+                false
             }
 
             KeyCode::Right if capabilities.supports_vim_navigation() => {
                 dispatcher.dispatch(Action::NavigateRight);
-                return false;
+                false
             }
 
             KeyCode::PageDown if capabilities.contains(PanelCapabilities::SCROLL_VERTICAL) => {
                 dispatcher.dispatch(Action::ScrollPageDown);
-                return false;
+                false
             }
 
             KeyCode::PageUp if capabilities.contains(PanelCapabilities::SCROLL_VERTICAL) => {
                 dispatcher.dispatch(Action::ScrollPageUp);
-                return false;
+                false
             }
 
             // All other keys pass through
             _ => {
                 self.clear_sequence();
-                return true;
+                true
             }
         }
     }
@@ -219,10 +219,10 @@ impl KeyboardMiddleware {
                         "Dispatching ScrollToTop (capabilities support vim vertical scroll)"
                     );
                     dispatcher.dispatch(Action::ScrollToTop);
-                    return false; // Block original key event
+                    false // Block original key event
                 } else {
                     log::debug!("Ignoring 'gg' - panel doesn't support vim vertical scrolling");
-                    return true; // Pass through
+                    true // Pass through
                 }
             }
 
@@ -233,10 +233,10 @@ impl KeyboardMiddleware {
                         "Dispatching ScrollToBottom (capabilities support vim vertical scroll)"
                     );
                     dispatcher.dispatch(Action::ScrollToBottom);
-                    return false; // Block original key event
+                    false // Block original key event
                 } else {
                     log::debug!("Ignoring 'G' - panel doesn't support vim vertical scrolling");
-                    return true; // Pass through
+                    true // Pass through
                 }
             }
         }
@@ -249,28 +249,21 @@ impl Default for KeyboardMiddleware {
     }
 }
 
-impl Middleware for KeyboardMiddleware {
-    fn handle<'a>(
-        &'a mut self,
-        action: &'a Action,
-        state: &'a AppState,
-        dispatcher: &'a Dispatcher,
-    ) -> BoxFuture<'a, bool> {
-        Box::pin(async move {
-            // Only intercept KeyPressed actions
-            if let Action::KeyPressed(key) = action {
-                let capabilities = state.ui.active_panel_capabilities;
-                log::debug!(
-                    "KeyboardMiddleware: key={:?}, capabilities={:?}",
-                    key,
-                    capabilities
-                );
-                return self.handle_key(*key, capabilities, dispatcher);
-            }
+impl Middleware<Action, AppState, Dispatcher> for KeyboardMiddleware {
+    fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        // Only intercept KeyPressed actions
+        if let Action::KeyPressed(key) = action {
+            let capabilities = state.ui.active_panel_capabilities;
+            log::debug!(
+                "KeyboardMiddleware: key={:?}, capabilities={:?}",
+                key,
+                capabilities
+            );
+            return self.handle_key(*key, capabilities, dispatcher);
+        }
 
-            // All other actions pass through
-            true
-        })
+        // All other actions pass through
+        true
     }
 }
 
@@ -286,21 +279,21 @@ mod tests {
     use super::*;
     use tokio::sync::mpsc;
 
-    #[tokio::test]
-    async fn test_keyboard_middleware_passthrough() {
+    #[test]
+    fn test_keyboard_middleware_passthrough() {
         let mut middleware = KeyboardMiddleware::new();
         let (tx, _rx) = mpsc::unbounded_channel();
         let dispatcher = Dispatcher::new(tx);
         let state = AppState::default();
 
         // Non-KeyPressed actions should pass through
-        let should_continue = middleware.handle(&Action::Quit, &state, &dispatcher).await;
+        let should_continue = middleware.handle(&Action::Quit, &state, &dispatcher);
 
         assert!(should_continue);
     }
 
-    #[tokio::test]
-    async fn test_keyboard_middleware_intercepts_keypressed() {
+    #[test]
+    fn test_keyboard_middleware_intercepts_keypressed() {
         let mut middleware = KeyboardMiddleware::new();
         let (tx, mut rx) = mpsc::unbounded_channel();
         let dispatcher = Dispatcher::new(tx);
@@ -311,9 +304,8 @@ mod tests {
 
         // Should intercept and dispatch NavigateNext (semantic action)
         // Default state has VIM_NAVIGATION_BINDINGS capability
-        let should_continue = middleware
-            .handle(&Action::KeyPressed(key_event), &state, &dispatcher)
-            .await;
+        let should_continue =
+            middleware.handle(&Action::KeyPressed(key_event), &state, &dispatcher);
 
         // Should block the original KeyPressed action
         assert!(!should_continue);
@@ -339,8 +331,8 @@ mod tests {
         assert!(middleware.last_key.is_none());
     }
 
-    #[tokio::test]
-    async fn test_capability_based_keybindings() {
+    #[test]
+    fn test_capability_based_keybindings() {
         use crate::capabilities::PanelCapabilities;
 
         let mut middleware = KeyboardMiddleware::new();
@@ -354,9 +346,8 @@ mod tests {
         let key_event = KeyEvent::from(KeyCode::Char('j'));
 
         // Should pass through (not intercept) because no capabilities
-        let should_continue = middleware
-            .handle(&Action::KeyPressed(key_event), &state, &dispatcher)
-            .await;
+        let should_continue =
+            middleware.handle(&Action::KeyPressed(key_event), &state, &dispatcher);
 
         // Should pass through
         assert!(should_continue);
@@ -366,8 +357,8 @@ mod tests {
         assert!(dispatched_action.is_err());
     }
 
-    #[tokio::test]
-    async fn test_vim_scroll_capabilities() {
+    #[test]
+    fn test_vim_scroll_capabilities() {
         use crate::capabilities::PanelCapabilities;
 
         let mut middleware = KeyboardMiddleware::new();
@@ -381,9 +372,8 @@ mod tests {
 
         // Test 'G' (go to bottom)
         let key_event = KeyEvent::from(KeyCode::Char('G'));
-        let should_continue = middleware
-            .handle(&Action::KeyPressed(key_event), &state, &dispatcher)
-            .await;
+        let should_continue =
+            middleware.handle(&Action::KeyPressed(key_event), &state, &dispatcher);
 
         // Should block the original KeyPressed action
         assert!(!should_continue);
@@ -396,8 +386,8 @@ mod tests {
 
     // End-to-End Integration Tests
 
-    #[tokio::test]
-    async fn test_e2e_pr_table_navigation() {
+    #[test]
+    fn test_e2e_pr_table_navigation() {
         use crate::reducer::reduce;
 
         let mut middleware = KeyboardMiddleware::new();
@@ -416,8 +406,11 @@ mod tests {
                 merge_state: "clean".to_string(),
                 mergeable: crate::pr::MergeableStatus::Unknown,
                 needs_rebase: false,
+                head_sha: String::new(),
                 created_at: chrono::Utc::now(),
                 updated_at: chrono::Utc::now(),
+                maturity: crate::pr::MaturityState::Ready,
+                review_decision: crate::pr::ReviewDecision::Unknown,
             },
             crate::pr::Pr {
                 number: 2,
@@ -428,17 +421,19 @@ mod tests {
                 merge_state: "clean".to_string(),
                 mergeable: crate::pr::MergeableStatus::Unknown,
                 needs_rebase: false,
+                head_sha: String::new(),
                 created_at: chrono::Utc::now(),
                 updated_at: chrono::Utc::now(),
+                maturity: crate::pr::MaturityState::Ready,
+                review_decision: crate::pr::ReviewDecision::Unknown,
             },
         ];
         state.repos.state.select(Some(0)); // Select first PR
 
         // Simulate pressing 'j' key
         let key_event = KeyEvent::from(KeyCode::Char('j'));
-        let _should_continue = middleware
-            .handle(&Action::KeyPressed(key_event), &state, &dispatcher)
-            .await;
+        let _should_continue =
+            middleware.handle(&Action::KeyPressed(key_event), &state, &dispatcher);
 
         // Middleware should dispatch NavigateNext
         let action = rx.try_recv().unwrap();
@@ -449,8 +444,8 @@ mod tests {
         assert_eq!(new_state.repos.state.selected(), Some(1));
     }
 
-    #[tokio::test]
-    async fn test_e2e_shortcuts_panel_scroll_to_top() {
+    #[test]
+    fn test_e2e_shortcuts_panel_scroll_to_top() {
         use crate::reducer::reduce;
 
         let mut middleware = KeyboardMiddleware::new();
@@ -472,15 +467,11 @@ mod tests {
         let g_key = KeyEvent::from(KeyCode::Char('g'));
 
         // First 'g' - should be recorded but not dispatch
-        let should_continue = middleware
-            .handle(&Action::KeyPressed(g_key), &state, &dispatcher)
-            .await;
+        let should_continue = middleware.handle(&Action::KeyPressed(g_key), &state, &dispatcher);
         assert!(!should_continue); // Blocks waiting for second 'g'
 
         // Second 'g' - should dispatch ScrollToTop
-        let should_continue = middleware
-            .handle(&Action::KeyPressed(g_key), &state, &dispatcher)
-            .await;
+        let should_continue = middleware.handle(&Action::KeyPressed(g_key), &state, &dispatcher);
         assert!(!should_continue);
 
         // Middleware should dispatch ScrollToTop
@@ -492,8 +483,8 @@ mod tests {
         assert_eq!(new_state.ui.shortcuts_scroll, 0);
     }
 
-    #[tokio::test]
-    async fn test_e2e_capability_blocking() {
+    #[test]
+    fn test_e2e_capability_blocking() {
         let mut middleware = KeyboardMiddleware::new();
         let (tx, mut rx) = mpsc::unbounded_channel();
         let dispatcher = Dispatcher::new(tx);
@@ -503,9 +494,8 @@ mod tests {
 
         // Simulate pressing 'G' (go to bottom) - should be ignored
         let key_event = KeyEvent::from(KeyCode::Char('G'));
-        let should_continue = middleware
-            .handle(&Action::KeyPressed(key_event), &state, &dispatcher)
-            .await;
+        let should_continue =
+            middleware.handle(&Action::KeyPressed(key_event), &state, &dispatcher);
 
         // Should pass through because PR table doesn't support vim vertical scroll
         assert!(should_continue);
@@ -515,8 +505,8 @@ mod tests {
         assert!(result.is_err()); // No action dispatched
     }
 
-    #[tokio::test]
-    async fn test_e2e_context_switching() {
+    #[test]
+    fn test_e2e_context_switching() {
         use crate::reducer::reduce;
 
         let mut middleware = KeyboardMiddleware::new();
@@ -534,16 +524,17 @@ mod tests {
             merge_state: "clean".to_string(),
             mergeable: crate::pr::MergeableStatus::Unknown,
             needs_rebase: false,
+            head_sha: String::new(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            maturity: crate::pr::MaturityState::Ready,
+            review_decision: crate::pr::ReviewDecision::Unknown,
         }];
         state.repos.state.select(Some(0));
 
         // Press 'j' in PR table - should navigate PRs
         let j_key = KeyEvent::from(KeyCode::Char('j'));
-        let _should_continue = middleware
-            .handle(&Action::KeyPressed(j_key), &state, &dispatcher)
-            .await;
+        let _should_continue = middleware.handle(&Action::KeyPressed(j_key), &state, &dispatcher);
 
         let action = rx.try_recv().unwrap();
         assert!(matches!(action, Action::NavigateNext));
@@ -552,9 +543,7 @@ mod tests {
         let (state, _) = reduce(state, &Action::ToggleShortcuts);
 
         // Press 'j' again - now should scroll shortcuts panel
-        let _should_continue = middleware
-            .handle(&Action::KeyPressed(j_key), &state, &dispatcher)
-            .await;
+        let _should_continue = middleware.handle(&Action::KeyPressed(j_key), &state, &dispatcher);
 
         let action = rx.try_recv().unwrap();
         assert!(matches!(action, Action::NavigateNext));
@@ -564,8 +553,8 @@ mod tests {
         assert_eq!(new_state.ui.shortcuts_scroll, 1); // Scrolled down in shortcuts
     }
 
-    #[tokio::test]
-    async fn test_e2e_command_palette_priority() {
+    #[test]
+    fn test_e2e_command_palette_priority() {
         use crate::reducer::reduce;
 
         let mut middleware = KeyboardMiddleware::new();
@@ -607,9 +596,7 @@ mod tests {
 
         // Press 'j' - should navigate command palette, NOT PR table
         let j_key = KeyEvent::from(KeyCode::Char('j'));
-        let _should_continue = middleware
-            .handle(&Action::KeyPressed(j_key), &state, &dispatcher)
-            .await;
+        let _should_continue = middleware.handle(&Action::KeyPressed(j_key), &state, &dispatcher);
 
         let action = rx.try_recv().unwrap();
         assert!(matches!(action, Action::NavigateNext));
@@ -6,7 +6,7 @@
 //! - Stopping tick timer when bot completes or stops
 //! - Managing bot state transitions
 
-use super::{BoxFuture, Dispatcher, Middleware};
+use super::{Dispatcher, Middleware};
 use crate::{actions::Action, state::AppState};
 use std::time::{Duration, Instant};
 
@@ -51,54 +51,47 @@ impl Default for MergeBotMiddleware {
     }
 }
 
-impl Middleware for MergeBotMiddleware {
-    fn handle<'a>(
-        &'a mut self,
-        action: &'a Action,
-        state: &'a AppState,
-        dispatcher: &'a Dispatcher,
-    ) -> BoxFuture<'a, bool> {
-        Box::pin(async move {
-            let is_running = state.merge_bot.bot.is_running();
-
-            // Detect transition from not running to running (bot started)
-            if !self.was_running && is_running {
-                log::debug!("MergeBotMiddleware: Merge bot started, beginning periodic ticks");
-                self.was_running = true;
-                // Reset tick timer so we can tick immediately
-                self.last_tick = None;
-            }
+impl Middleware<Action, AppState, Dispatcher> for MergeBotMiddleware {
+    fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        let is_running = state.merge_bot.bot.is_running();
 
-            // Detect transition from running to not running (bot stopped)
-            if self.was_running && !is_running {
-                log::debug!("MergeBotMiddleware: Merge bot stopped, ending periodic ticks");
-                self.was_running = false;
-                self.last_tick = None;
-            }
+        // Detect transition from not running to running (bot started)
+        if !self.was_running && is_running {
+            log::debug!("MergeBotMiddleware: Merge bot started, beginning periodic ticks");
+            self.was_running = true;
+            // Reset tick timer so we can tick immediately
+            self.last_tick = None;
+        }
 
-            // If bot is running and enough time has passed, dispatch MergeBotTick
-            // Check on every action to ensure smooth bot operation
-            if is_running && self.should_tick() {
-                dispatcher.dispatch(Action::MergeBotTick);
-            }
+        // Detect transition from running to not running (bot stopped)
+        if self.was_running && !is_running {
+            log::debug!("MergeBotMiddleware: Merge bot stopped, ending periodic ticks");
+            self.was_running = false;
+            self.last_tick = None;
+        }
 
-            // Actions that might affect bot state (for logging/debugging)
-            match action {
-                Action::StartMergeBot | Action::StartMergeBotWithPrData(_) => {
-                    // Bot starting - reducer will update state, we'll detect it on next action
-                }
-                Action::MergeBotTick => {
-                    // Bot tick being processed
-                }
-                Action::MergeComplete(_) => {
-                    // Bot might be completing
-                }
-                _ => {}
+        // If bot is running and enough time has passed, dispatch MergeBotTick
+        // Check on every action to ensure smooth bot operation
+        if is_running && self.should_tick() {
+            dispatcher.dispatch(Action::MergeBotTick);
+        }
+
+        // Actions that might affect bot state (for logging/debugging)
+        match action {
+            Action::StartMergeBot | Action::StartMergeBotWithPrData(_) => {
+                // Bot starting - reducer will update state, we'll detect it on next action
+            }
+            Action::MergeBotTick => {
+                // Bot tick being processed
             }
+            Action::MergeComplete(_) => {
+                // Bot might be completing
+            }
+            _ => {}
+        }
 
-            // Always continue to next middleware
-            true
-        })
+        // Always continue to next middleware
+        true
     }
 }
 
@@ -119,9 +112,7 @@ mod tests {
         assert!(!state.merge_bot.bot.is_running());
 
         // Handle action while bot is not running - should not dispatch MergeBotTick
-        middleware
-            .handle(&Action::Bootstrap, &state, &dispatcher)
-            .await;
+        middleware.handle(&Action::Bootstrap, &state, &dispatcher);
 
         let maybe_action = rx.try_recv();
         assert!(maybe_action.is_err()); // No tick dispatched
@@ -135,9 +126,7 @@ mod tests {
         tokio::time::sleep(Duration::from_millis(110)).await;
 
         // Handle action while bot is running - should dispatch MergeBotTick
-        middleware
-            .handle(&Action::Bootstrap, &state, &dispatcher)
-            .await;
+        middleware.handle(&Action::Bootstrap, &state, &dispatcher);
 
         let maybe_action = rx.try_recv();
         assert!(maybe_action.is_ok());
@@ -151,16 +140,14 @@ mod tests {
         tokio::time::sleep(Duration::from_millis(110)).await;
 
         // Handle action after bot stopped - should not dispatch MergeBotTick
-        middleware
-            .handle(&Action::Bootstrap, &state, &dispatcher)
-            .await;
+        middleware.handle(&Action::Bootstrap, &state, &dispatcher);
 
         let maybe_action = rx.try_recv();
         assert!(maybe_action.is_err()); // No more ticks
     }
 
-    #[tokio::test]
-    async fn test_tick_throttling() {
+    #[test]
+    fn test_tick_throttling() {
         let mut middleware = MergeBotMiddleware::new();
         let (tx, mut rx) = mpsc::unbounded_channel();
         let dispatcher = Dispatcher::new(tx);
@@ -172,9 +159,7 @@ mod tests {
 
         // Dispatch multiple actions quickly - should only tick once due to throttle
         for _ in 0..5 {
-            middleware
-                .handle(&Action::Bootstrap, &state, &dispatcher)
-                .await;
+            middleware.handle(&Action::Bootstrap, &state, &dispatcher);
         }
 
         // Should have only one MergeBotTick
@@ -201,9 +186,7 @@ mod tests {
 
         // Handle many actions - should never dispatch MergeBotTick
         for _ in 0..10 {
-            middleware
-                .handle(&Action::Bootstrap, &state, &dispatcher)
-                .await;
+            middleware.handle(&Action::Bootstrap, &state, &dispatcher);
         }
 
         // Should have no ticks
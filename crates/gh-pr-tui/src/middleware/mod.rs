@@ -19,32 +19,26 @@
 //! ## Example
 //!
 //! ```rust
-//! struct LoggingMiddleware;
+//! struct CountingMiddleware {
+//!     seen: usize,
+//! }
 //!
-//! impl Middleware for LoggingMiddleware {
-//!     fn handle(
-//!         &mut self,
-//!         action: &Action,
-//!         _state: &AppState,
-//!         _dispatcher: &Dispatcher,
-//!     ) -> BoxFuture<'_, bool> {
-//!         Box::pin(async move {
-//!             log::debug!("Action: {:?}", action);
-//!             true // Continue to next middleware
-//!         })
+//! impl Middleware<Action, AppState, Dispatcher> for CountingMiddleware {
+//!     fn handle(&mut self, _action: &Action, _state: &AppState, _dispatcher: &Dispatcher) -> bool {
+//!         self.seen += 1;
+//!         true // Continue to next middleware
 //!     }
 //! }
 //! ```
 
-use crate::{actions::Action, state::AppState};
-use std::future::Future;
-use std::pin::Pin;
+use crate::actions::Action;
+use crate::state::AppState;
 
 // Module declarations
 mod dispatcher;
 mod keyboard;
-mod logging;
 mod merge_bot;
+mod scheduler;
 mod shutdown;
 mod splash_screen;
 mod task;
@@ -52,68 +46,36 @@ mod task;
 // Re-exports
 pub use dispatcher::Dispatcher;
 pub use keyboard::KeyboardMiddleware;
-pub use logging::LoggingMiddleware;
 pub use merge_bot::MergeBotMiddleware;
+pub use scheduler::SchedulerMiddleware;
 pub use shutdown::ShutdownMiddleware;
 pub use splash_screen::SplashScreenMiddleware;
 pub use task::TaskMiddleware;
 
-/// BoxFuture type alias for async middleware handlers
-pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
-
-/// Middleware trait - handles actions before they reach the reducer
-///
-/// Middleware is called in order for each action. Each middleware can:
-/// - Inspect the action and current state
-/// - Dispatch new actions via the Dispatcher
-/// - Perform async operations
-/// - Block the action from continuing (return false)
-///
-/// # Example
+/// Middleware - handles actions before they reach the reducer
 ///
-/// ```rust
-/// struct MyMiddleware;
-///
-/// impl Middleware for MyMiddleware {
-///     fn handle<'a>(
-///         &'a mut self,
-///         action: &'a Action,
-///         state: &'a AppState,
-///         dispatcher: &'a Dispatcher,
-///     ) -> BoxFuture<'a, bool> {
-///         Box::pin(async move {
-///             match action {
-///                 Action::SomeAction => {
-///                     // Perform side effect
-///                     do_something().await;
-///                     // Dispatch follow-up action
-///                     dispatcher.dispatch(Action::SomeOtherAction);
-///                     // Let action continue to reducer
-///                     true
-///                 }
-///                 _ => true, // Pass through other actions
-///             }
-///         })
-///     }
-/// }
-/// ```
-pub trait Middleware: Send + Sync {
-    /// Handle an action before it reaches the reducer
-    ///
-    /// # Parameters
-    /// - `action`: The action being dispatched
-    /// - `state`: Current application state (read-only)
-    /// - `dispatcher`: Can dispatch new actions
-    ///
-    /// # Returns
-    /// - `true`: Continue to next middleware/reducer
-    /// - `false`: Block this action from continuing
-    fn handle<'a>(
-        &'a mut self,
-        action: &'a Action,
-        state: &'a AppState,
-        dispatcher: &'a Dispatcher,
-    ) -> BoxFuture<'a, bool>;
+/// The trait is shared with gh-pr-lander through `gh-redux`. Handlers run
+/// synchronously on the event loop: anything slow (API calls, sleeps) is
+/// moved into a spawned tokio task that dispatches its result back.
+pub use gh_redux::Middleware;
+
+/// The middleware chain, in the order actions pass through it
+pub type MiddlewareChain = Vec<Box<dyn Middleware<Action, AppState, Dispatcher> + Send>>;
+
+/// Logs every action at debug level, except ticks and viewport updates
+pub fn logging_middleware() -> gh_redux::LoggingMiddleware<Action> {
+    gh_redux::LoggingMiddleware::new(log::Level::Debug, |action| {
+        matches!(
+            action,
+            Action::None
+                | Action::TickSpinner
+                | Action::MergeBotTick
+                | Action::UpdateLogPanelViewport(_)
+                | Action::UpdateDebugConsoleViewport(_)
+                | Action::UpdateShortcutsMaxScroll(_)
+                | Action::ResetForceRedraw
+        )
+    })
 }
 
 #[cfg(test)]
@@ -125,30 +87,40 @@ mod tests {
         called: bool,
     }
 
-    impl Middleware for TestMiddleware {
-        fn handle<'a>(
-            &'a mut self,
-            _action: &'a Action,
-            _state: &'a AppState,
-            _dispatcher: &'a Dispatcher,
-        ) -> BoxFuture<'a, bool> {
-            Box::pin(async move {
-                self.called = true;
-                true
-            })
+    impl Middleware<Action, AppState, Dispatcher> for TestMiddleware {
+        fn handle(
+            &mut self,
+            _action: &Action,
+            _state: &AppState,
+            _dispatcher: &Dispatcher,
+        ) -> bool {
+            self.called = true;
+            true
         }
     }
 
-    #[tokio::test]
-    async fn test_middleware_trait() {
+    #[test]
+    fn test_middleware_trait() {
         let mut middleware = TestMiddleware { called: false };
         let (tx, _rx) = mpsc::unbounded_channel();
         let dispatcher = Dispatcher::new(tx);
         let state = AppState::default();
 
-        let should_continue = middleware.handle(&Action::None, &state, &dispatcher).await;
+        let should_continue = middleware.handle(&Action::None, &state, &dispatcher);
 
         assert!(should_continue);
         assert!(middleware.called);
     }
+
+    #[test]
+    fn test_logging_middleware_passes_every_action_on() {
+        let mut middleware = logging_middleware();
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let dispatcher = Dispatcher::new(tx);
+        let state = AppState::default();
+
+        assert!(!middleware.logs(&Action::TickSpinner));
+        assert!(middleware.logs(&Action::Quit));
+        assert!(middleware.handle(&Action::Quit, &state, &dispatcher));
+    }
 }
@@ -0,0 +1,208 @@
+//! SchedulerMiddleware - manages recurring updates
+//!
+//! This middleware handles:
+//! - Starting one interval task per schedule id (starting an id again replaces it)
+//! - Cancelling a schedule on StopRecurringUpdates
+//! - Cancelling all schedules on Quit and FatalError
+//! - Reporting the active schedules to the debug console
+//!
+//! Intervals skip missed ticks, so a suspended system does not fire a burst
+//! of updates when it wakes up, and ticks do not drift by the time a dispatch
+//! takes.
+
+use super::{Dispatcher, Middleware};
+use crate::{
+    actions::Action,
+    state::{AppState, ScheduleId, ScheduleInfo},
+};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tokio::time::{Instant, MissedTickBehavior};
+
+/// A running interval task
+struct Schedule {
+    interval: Duration,
+    /// Cancels the task when sent or dropped
+    _cancel: oneshot::Sender<()>,
+}
+
+/// Registry of the running schedules, keyed by purpose
+#[derive(Default)]
+pub struct Scheduler {
+    schedules: BTreeMap<ScheduleId, Schedule>,
+}
+
+impl Scheduler {
+    /// Dispatch `RecurringUpdateTriggered(id)` every `interval`
+    ///
+    /// The first tick comes after one interval. A schedule with the same id
+    /// is cancelled and replaced.
+    pub fn start(&mut self, id: ScheduleId, interval: Duration, dispatcher: &Dispatcher) {
+        let (cancel, mut cancelled) = oneshot::channel::<()>();
+        let dispatcher = dispatcher.clone();
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval_at(Instant::now() + interval, interval);
+            ticks.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            loop {
+                tokio::select! {
+                    biased;
+                    _ = &mut cancelled => break,
+                    _ = ticks.tick() => {
+                        log::debug!("Recurring {} triggered", id.label());
+                        dispatcher.dispatch(Action::RecurringUpdateTriggered(id));
+                    }
+                }
+            }
+            log::debug!("Recurring {} stopped", id.label());
+        });
+
+        let schedule = Schedule {
+            interval,
+            _cancel: cancel,
+        };
+        if self.schedules.insert(id, schedule).is_some() {
+            log::debug!("Replaced recurring {} schedule", id.label());
+        }
+    }
+
+    /// Cancel the schedule of `id`, returns false if none was running
+    pub fn stop(&mut self, id: ScheduleId) -> bool {
+        self.schedules.remove(&id).is_some()
+    }
+
+    /// Cancel all schedules
+    pub fn stop_all(&mut self) {
+        self.schedules.clear();
+    }
+
+    /// The running schedules, ordered by id
+    pub fn active(&self) -> Vec<ScheduleInfo> {
+        self.schedules
+            .iter()
+            .map(|(id, schedule)| ScheduleInfo {
+                id: *id,
+                interval: schedule.interval,
+            })
+            .collect()
+    }
+}
+
+/// Middleware for managing recurring updates
+#[derive(Default)]
+pub struct SchedulerMiddleware {
+    scheduler: Scheduler,
+}
+
+impl SchedulerMiddleware {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Middleware<Action, AppState, Dispatcher> for SchedulerMiddleware {
+    fn handle(&mut self, action: &Action, _state: &AppState, dispatcher: &Dispatcher) -> bool {
+        match action {
+            Action::StartRecurringUpdates(id, interval_ms) => {
+                log::debug!("SchedulerMiddleware: Starting recurring {}", id.label());
+                self.scheduler
+                    .start(*id, Duration::from_millis(*interval_ms), dispatcher);
+                dispatcher.dispatch(Action::SchedulesChanged(self.scheduler.active()));
+            }
+            Action::StopRecurringUpdates(id) if self.scheduler.stop(*id) => {
+                log::debug!("SchedulerMiddleware: Stopped recurring {}", id.label());
+                dispatcher.dispatch(Action::SchedulesChanged(self.scheduler.active()));
+            }
+            Action::Quit | Action::FatalError(_) => {
+                // Runs before ShutdownMiddleware, which blocks these actions
+                log::debug!("SchedulerMiddleware: Cancelling all schedules");
+                self.scheduler.stop_all();
+            }
+            _ => {}
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    // The tests run on paused time: sleeps advance the clock instantly and
+    // fire the ticks due in between in order, so tick counts are exact.
+
+    fn triggered(rx: &mut mpsc::UnboundedReceiver<Action>) -> usize {
+        let mut count = 0;
+        while let Ok(action) = rx.try_recv() {
+            if matches!(action, Action::RecurringUpdateTriggered(_)) {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_starting_an_id_again_replaces_the_schedule() {
+        let (dispatcher_tx, mut dispatcher_rx) = mpsc::unbounded_channel();
+        let dispatcher = Dispatcher::new(dispatcher_tx);
+        let mut middleware = SchedulerMiddleware::new();
+        let state = AppState::default();
+
+        let fast = Action::StartRecurringUpdates(ScheduleId::RepoRefresh, 10);
+        let slow = Action::StartRecurringUpdates(ScheduleId::RepoRefresh, 3_600_000);
+        middleware.handle(&fast, &state, &dispatcher);
+        middleware.handle(&slow, &state, &dispatcher);
+
+        assert_eq!(
+            middleware.scheduler.active(),
+            vec![ScheduleInfo {
+                id: ScheduleId::RepoRefresh,
+                interval: Duration::from_secs(3600),
+            }]
+        );
+
+        // The replaced fast schedule no longer ticks
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(triggered(&mut dispatcher_rx), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_no_dispatches_after_stop() {
+        let (dispatcher_tx, mut dispatcher_rx) = mpsc::unbounded_channel();
+        let dispatcher = Dispatcher::new(dispatcher_tx);
+        let mut middleware = SchedulerMiddleware::new();
+        let state = AppState::default();
+
+        let start = Action::StartRecurringUpdates(ScheduleId::RepoRefresh, 10);
+        middleware.handle(&start, &state, &dispatcher);
+        // Ticks at 10, 20 and 30ms, none right away
+        tokio::time::sleep(Duration::from_millis(35)).await;
+        assert_eq!(triggered(&mut dispatcher_rx), 3);
+
+        let stop = Action::StopRecurringUpdates(ScheduleId::RepoRefresh);
+        middleware.handle(&stop, &state, &dispatcher);
+        assert!(middleware.scheduler.active().is_empty());
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(triggered(&mut dispatcher_rx), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_quit_cancels_all_schedules() {
+        let (dispatcher_tx, mut dispatcher_rx) = mpsc::unbounded_channel();
+        let dispatcher = Dispatcher::new(dispatcher_tx);
+        let mut middleware = SchedulerMiddleware::new();
+        let state = AppState::default();
+
+        let start = Action::StartRecurringUpdates(ScheduleId::RepoRefresh, 10);
+        middleware.handle(&start, &state, &dispatcher);
+        // Quit passes on to ShutdownMiddleware
+        assert!(middleware.handle(&Action::Quit, &state, &dispatcher));
+        assert!(middleware.scheduler.active().is_empty());
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(triggered(&mut dispatcher_rx), 0);
+    }
+}
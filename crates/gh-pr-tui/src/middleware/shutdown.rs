@@ -6,7 +6,7 @@
 //! - Closing the action channel to signal main loop termination
 //! - Ensuring shutdown happens exactly once
 
-use super::{BoxFuture, Dispatcher, Middleware};
+use super::{Dispatcher, Middleware};
 use crate::{actions::Action, state::AppState};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -28,7 +28,7 @@ impl ShutdownMiddleware {
     }
 
     /// Perform cleanup and initiate shutdown
-    async fn perform_shutdown(&self, state: &AppState, reason: &str) {
+    fn perform_shutdown(&self, state: &AppState, reason: &str) {
         // Ensure shutdown happens exactly once
         if self
             .shutdown_initiated
@@ -50,10 +50,10 @@ impl ShutdownMiddleware {
         }
 
         // Save session state (selected repo)
-        if let Some(selected_repo) = state.repos.recent_repos.get(state.repos.selected_repo) {
-            if let Err(e) = self.store_persisted_state(selected_repo) {
-                log::error!("Failed to save session state during shutdown: {}", e);
-            }
+        if let Some(selected_repo) = state.repos.recent_repos.get(state.repos.selected_repo)
+            && let Err(e) = self.store_persisted_state(selected_repo)
+        {
+            log::error!("Failed to save session state during shutdown: {}", e);
         }
 
         log::info!("ShutdownMiddleware: Cleanup complete, setting should_quit flag");
@@ -63,6 +63,7 @@ impl ShutdownMiddleware {
     }
 
     /// Save recent repositories to file
+    #[allow(deprecated)]
     fn store_recent_repos(&self, repos: &[crate::state::Repo]) -> anyhow::Result<()> {
         let file = gh_pr_config::create_recent_repositories_file()?;
         serde_json::to_writer_pretty(file, repos)?;
@@ -71,6 +72,7 @@ impl ShutdownMiddleware {
     }
 
     /// Save session state to file
+    #[allow(deprecated)]
     fn store_persisted_state(&self, selected_repo: &crate::state::Repo) -> anyhow::Result<()> {
         use serde::{Deserialize, Serialize};
 
@@ -90,35 +92,27 @@ impl ShutdownMiddleware {
     }
 }
 
-impl Middleware for ShutdownMiddleware {
-    fn handle<'a>(
-        &'a mut self,
-        action: &'a Action,
-        state: &'a AppState,
-        _dispatcher: &'a Dispatcher,
-    ) -> BoxFuture<'a, bool> {
-        Box::pin(async move {
-            match action {
-                Action::Quit => {
-                    log::debug!("ShutdownMiddleware: Quit action received");
-                    self.perform_shutdown(state, "user quit").await;
-                    // Don't pass Quit to reducer - we're handling it here
-                    // Return false to block the action
-                    return false;
-                }
-                Action::FatalError(err) => {
-                    log::error!("ShutdownMiddleware: Fatal error - {}", err);
-                    self.perform_shutdown(state, &format!("fatal error: {}", err))
-                        .await;
-                    // Don't pass FatalError to reducer
-                    return false;
-                }
-                _ => {}
+impl Middleware<Action, AppState, Dispatcher> for ShutdownMiddleware {
+    fn handle(&mut self, action: &Action, state: &AppState, _dispatcher: &Dispatcher) -> bool {
+        match action {
+            Action::Quit => {
+                log::debug!("ShutdownMiddleware: Quit action received");
+                self.perform_shutdown(state, "user quit");
+                // Don't pass Quit to reducer - we're handling it here
+                // Return false to block the action
+                return false;
+            }
+            Action::FatalError(err) => {
+                log::error!("ShutdownMiddleware: Fatal error - {}", err);
+                self.perform_shutdown(state, &format!("fatal error: {}", err));
+                // Don't pass FatalError to reducer
+                return false;
             }
+            _ => {}
+        }
 
-            // Continue to next middleware for all other actions
-            true
-        })
+        // Continue to next middleware for all other actions
+        true
     }
 }
 
@@ -127,8 +121,8 @@ mod tests {
     use super::*;
     use tokio::sync::mpsc;
 
-    #[tokio::test]
-    async fn test_shutdown_on_quit() {
+    #[test]
+    fn test_shutdown_on_quit() {
         let should_quit = Arc::new(AtomicBool::new(false));
         let (dispatcher_tx, _dispatcher_rx) = mpsc::unbounded_channel();
         let dispatcher = Dispatcher::new(dispatcher_tx);
@@ -137,7 +131,7 @@ mod tests {
         let state = AppState::default();
 
         // Handle Quit action
-        let should_continue = middleware.handle(&Action::Quit, &state, &dispatcher).await;
+        let should_continue = middleware.handle(&Action::Quit, &state, &dispatcher);
 
         // Should block the action (return false)
         assert!(!should_continue);
@@ -146,8 +140,8 @@ mod tests {
         assert!(should_quit.load(Ordering::SeqCst));
     }
 
-    #[tokio::test]
-    async fn test_shutdown_on_fatal_error() {
+    #[test]
+    fn test_shutdown_on_fatal_error() {
         let should_quit = Arc::new(AtomicBool::new(false));
         let (dispatcher_tx, _dispatcher_rx) = mpsc::unbounded_channel();
         let dispatcher = Dispatcher::new(dispatcher_tx);
@@ -156,13 +150,11 @@ mod tests {
         let state = AppState::default();
 
         // Handle FatalError action
-        let should_continue = middleware
-            .handle(
-                &Action::FatalError("test error".to_string()),
-                &state,
-                &dispatcher,
-            )
-            .await;
+        let should_continue = middleware.handle(
+            &Action::FatalError("test error".to_string()),
+            &state,
+            &dispatcher,
+        );
 
         // Should block the action
         assert!(!should_continue);
@@ -171,8 +163,8 @@ mod tests {
         assert!(should_quit.load(Ordering::SeqCst));
     }
 
-    #[tokio::test]
-    async fn test_shutdown_happens_once() {
+    #[test]
+    fn test_shutdown_happens_once() {
         let should_quit = Arc::new(AtomicBool::new(false));
         let (dispatcher_tx, _dispatcher_rx) = mpsc::unbounded_channel();
         let dispatcher = Dispatcher::new(dispatcher_tx);
@@ -181,17 +173,17 @@ mod tests {
         let state = AppState::default();
 
         // First quit
-        middleware.handle(&Action::Quit, &state, &dispatcher).await;
+        middleware.handle(&Action::Quit, &state, &dispatcher);
         assert!(should_quit.load(Ordering::SeqCst));
 
         // Second quit - should be ignored (shutdown already initiated)
-        middleware.handle(&Action::Quit, &state, &dispatcher).await;
+        middleware.handle(&Action::Quit, &state, &dispatcher);
 
         // If we got here without panic, the duplicate shutdown was handled gracefully
     }
 
-    #[tokio::test]
-    async fn test_other_actions_pass_through() {
+    #[test]
+    fn test_other_actions_pass_through() {
         let should_quit = Arc::new(AtomicBool::new(false));
         let (dispatcher_tx, _dispatcher_rx) = mpsc::unbounded_channel();
         let dispatcher = Dispatcher::new(dispatcher_tx);
@@ -200,9 +192,7 @@ mod tests {
         let state = AppState::default();
 
         // Handle a non-shutdown action
-        let should_continue = middleware
-            .handle(&Action::Bootstrap, &state, &dispatcher)
-            .await;
+        let should_continue = middleware.handle(&Action::Bootstrap, &state, &dispatcher);
 
         // Should continue (return true)
         assert!(should_continue);
@@ -6,7 +6,7 @@
 //! - Stopping spinner animation when bootstrap completes
 //! - Updating splash screen view model
 
-use super::{BoxFuture, Dispatcher, Middleware};
+use super::{Dispatcher, Middleware};
 use crate::{
     actions::Action,
     state::{AppState, BootstrapState},
@@ -64,43 +64,36 @@ impl Default for SplashScreenMiddleware {
     }
 }
 
-impl Middleware for SplashScreenMiddleware {
-    fn handle<'a>(
-        &'a mut self,
-        action: &'a Action,
-        state: &'a AppState,
-        dispatcher: &'a Dispatcher,
-    ) -> BoxFuture<'a, bool> {
-        Box::pin(async move {
-            // Update splash_active flag based on bootstrap state
-            let should_be_active = Self::is_splash_visible(&state.infra.bootstrap_state);
-
-            // Detect transition from active to inactive (bootstrap completed)
-            if self.splash_active && !should_be_active {
-                log::debug!("SplashScreenMiddleware: Bootstrap completed, stopping spinner");
-                self.splash_active = false;
-            }
+impl Middleware<Action, AppState, Dispatcher> for SplashScreenMiddleware {
+    fn handle(&mut self, action: &Action, state: &AppState, dispatcher: &Dispatcher) -> bool {
+        // Update splash_active flag based on bootstrap state
+        let should_be_active = Self::is_splash_visible(&state.infra.bootstrap_state);
 
-            // If splash is active and enough time has passed, dispatch TickSpinner
-            // Check on every action to ensure smooth animation
-            if self.splash_active && self.should_tick() {
-                dispatcher.dispatch(Action::TickSpinner);
-            }
+        // Detect transition from active to inactive (bootstrap completed)
+        if self.splash_active && !should_be_active {
+            log::debug!("SplashScreenMiddleware: Bootstrap completed, stopping spinner");
+            self.splash_active = false;
+        }
 
-            // Update splash screen view model when bootstrap state changes
-            match action {
-                Action::Bootstrap
-                | Action::SetBootstrapState(_)
-                | Action::BootstrapComplete(_)
-                | Action::TickSpinner => {
-                    // View model is updated by reducer, we just need to let the action through
-                }
-                _ => {}
+        // If splash is active and enough time has passed, dispatch TickSpinner
+        // Check on every action to ensure smooth animation
+        if self.splash_active && self.should_tick() {
+            dispatcher.dispatch(Action::TickSpinner);
+        }
+
+        // Update splash screen view model when bootstrap state changes
+        match action {
+            Action::Bootstrap
+            | Action::SetBootstrapState(_)
+            | Action::BootstrapComplete(_)
+            | Action::TickSpinner => {
+                // View model is updated by reducer, we just need to let the action through
             }
+            _ => {}
+        }
 
-            // Always continue to next middleware
-            true
-        })
+        // Always continue to next middleware
+        true
     }
 }
 
@@ -120,9 +113,7 @@ mod tests {
         state.infra.bootstrap_state = BootstrapState::NotStarted;
 
         // Handle any action - should dispatch TickSpinner since splash is active
-        middleware
-            .handle(&Action::Bootstrap, &state, &dispatcher)
-            .await;
+        middleware.handle(&Action::Bootstrap, &state, &dispatcher);
 
         // Wait a bit for tick throttle
         tokio::time::sleep(Duration::from_millis(110)).await;
@@ -134,27 +125,25 @@ mod tests {
 
         // Transition to UIReady (bootstrap complete)
         state.infra.bootstrap_state = BootstrapState::UIReady;
-        middleware
-            .handle(
-                &Action::SetBootstrapState(BootstrapState::UIReady),
-                &state,
-                &dispatcher,
-            )
-            .await;
+        middleware.handle(
+            &Action::SetBootstrapState(BootstrapState::UIReady),
+            &state,
+            &dispatcher,
+        );
 
         // Wait for next tick interval
         tokio::time::sleep(Duration::from_millis(110)).await;
 
         // Handle another action - should NOT dispatch TickSpinner (splash inactive)
-        middleware.handle(&Action::Quit, &state, &dispatcher).await;
+        middleware.handle(&Action::Quit, &state, &dispatcher);
 
         // Should not have dispatched anything
         let maybe_action = rx.try_recv();
         assert!(maybe_action.is_err()); // Channel empty
     }
 
-    #[tokio::test]
-    async fn test_tick_throttling() {
+    #[test]
+    fn test_tick_throttling() {
         let mut middleware = SplashScreenMiddleware::new();
         let (tx, mut rx) = mpsc::unbounded_channel();
         let dispatcher = Dispatcher::new(tx);
@@ -163,9 +152,7 @@ mod tests {
 
         // Dispatch multiple actions quickly - should only tick once due to throttle
         for _ in 0..5 {
-            middleware
-                .handle(&Action::Bootstrap, &state, &dispatcher)
-                .await;
+            middleware.handle(&Action::Bootstrap, &state, &dispatcher);
         }
 
         // Should have only one TickSpinner
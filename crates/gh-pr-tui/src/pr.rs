@@ -19,7 +19,7 @@ pub struct Pr {
     pub head_sha: String,           // HEAD commit SHA for CI status checks
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
-    pub maturity: MaturityState,       // Draft vs Ready state
+    pub maturity: MaturityState,         // Draft vs Ready state
     pub review_decision: ReviewDecision, // Review status (approved, changes requested, etc.)
 }
 
@@ -83,13 +83,8 @@ pub fn reduce(mut state: AppState, action: &Action) -> (AppState, Vec<Effect>) {
     state.ui = ui_state;
     effects.extend(ui_effects);
 
-    let (repos_state, repos_effects) = repos_reducer(
-        state.repos,
-        action,
-        &state.config,
-        &state.theme,
-        &state.infra,
-    );
+    let (repos_state, repos_effects) =
+        repos_reducer(state.repos, action, &state.theme, &state.infra);
     state.repos = repos_state;
     effects.extend(repos_effects);
 
@@ -142,36 +137,30 @@ pub fn reduce(mut state: AppState, action: &Action) -> (AppState, Vec<Effect>) {
         Action::ShowAddRepoPopup => {
             push_panel(&mut state, ActivePanel::AddRepoPopup);
         }
-        Action::HideAddRepoPopup | Action::AddRepoFormSubmit => {
-            if !state.ui.show_add_repo {
-                remove_panel(&mut state, ActivePanel::AddRepoPopup);
-            }
+        Action::HideAddRepoPopup | Action::AddRepoFormSubmit if !state.ui.show_add_repo => {
+            remove_panel(&mut state, ActivePanel::AddRepoPopup);
         }
         Action::ShowClosePrPopup => {
             push_panel(&mut state, ActivePanel::ClosePrPopup);
         }
-        Action::HideClosePrPopup | Action::ClosePrFormSubmit => {
-            if state.ui.close_pr_state.is_none() {
-                remove_panel(&mut state, ActivePanel::ClosePrPopup);
-            }
+        Action::HideClosePrPopup | Action::ClosePrFormSubmit
+            if state.ui.close_pr_state.is_none() =>
+        {
+            remove_panel(&mut state, ActivePanel::ClosePrPopup);
         }
         Action::ShowCommandPalette => {
             push_panel(&mut state, ActivePanel::CommandPalette);
         }
-        Action::HideCommandPalette | Action::CommandPaletteExecute => {
-            if state.ui.command_palette.is_none() {
-                remove_panel(&mut state, ActivePanel::CommandPalette);
-            }
+        Action::HideCommandPalette | Action::CommandPaletteExecute
+            if state.ui.command_palette.is_none() =>
+        {
+            remove_panel(&mut state, ActivePanel::CommandPalette);
         }
-        Action::BuildLogsLoaded(_, _) => {
-            if state.log_panel.panel.is_some() {
-                push_panel(&mut state, ActivePanel::LogPanel);
-            }
+        Action::BuildLogsLoaded(_, _) if state.log_panel.panel.is_some() => {
+            push_panel(&mut state, ActivePanel::LogPanel);
         }
-        Action::CloseLogPanel => {
-            if state.log_panel.panel.is_none() {
-                remove_panel(&mut state, ActivePanel::LogPanel);
-            }
+        Action::CloseLogPanel if state.log_panel.panel.is_none() => {
+            remove_panel(&mut state, ActivePanel::LogPanel);
         }
         Action::ToggleDebugConsole => {
             if state.debug_console.is_open {
@@ -266,12 +255,11 @@ fn ui_reducer(
                 recompute_shortcuts_panel_view_model(&mut state, theme);
             }
         }
-        Action::CloseLogPanel => {
+        Action::CloseLogPanel
             // Close shortcuts panel first if open
-            if state.show_shortcuts {
+            if state.show_shortcuts => {
                 state.show_shortcuts = false;
             }
-        }
         Action::ShowAddRepoPopup => {
             state.show_add_repo = true;
             state.add_repo_form = AddRepoForm::default();
@@ -319,7 +307,7 @@ fn ui_reducer(
                 AddRepoField::Branch => AddRepoField::Org,
             };
         }
-        Action::AddRepoFormSubmit => {
+        Action::AddRepoFormSubmit
             // MIGRATION NOTE: AddRepository now handled by TaskMiddleware
             // Middleware will:
             // - Check if repo exists
@@ -327,11 +315,10 @@ fn ui_reducer(
             // - Dispatch RepositoryAdded, SelectRepoByIndex, ReloadRepo
 
             // Just hide the form and reset it
-            if !state.add_repo_form.org.is_empty() && !state.add_repo_form.repo.is_empty() {
+            if !state.add_repo_form.org.is_empty() && !state.add_repo_form.repo.is_empty() => {
                 state.show_add_repo = false;
                 state.add_repo_form = AddRepoForm::default();
             }
-        }
         Action::ShowClosePrPopup => {
             state.close_pr_state = Some(crate::state::ClosePrState::new());
         }
@@ -390,7 +377,7 @@ fn ui_reducer(
         Action::ShowCacheStats => {
             return (state, vec![]);
         }
-        Action::InvalidateRepoCache(repo_index) => {
+        Action::InvalidateRepoCache(_) => {
             return (state, vec![]);
         }
 
@@ -432,56 +419,50 @@ fn ui_reducer(
             }
             // Otherwise, no-op (handled by other panels)
         }
-        Action::ScrollToTop => {
+        Action::ScrollToTop
             // Shortcuts panel - scroll to top
-            if state.show_shortcuts {
+            if state.show_shortcuts => {
                 state.shortcuts_scroll = 0;
                 recompute_shortcuts_panel_view_model(&mut state, theme);
             }
             // Otherwise, no-op (handled by other panels)
-        }
-        Action::ScrollToBottom => {
+        Action::ScrollToBottom
             // Shortcuts panel - scroll to bottom
-            if state.show_shortcuts {
+            if state.show_shortcuts => {
                 state.shortcuts_scroll = state.shortcuts_max_scroll;
                 recompute_shortcuts_panel_view_model(&mut state, theme);
             }
             // Otherwise, no-op (handled by other panels)
-        }
-        Action::ScrollPageDown => {
+        Action::ScrollPageDown
             // Shortcuts panel - page down (10 lines)
-            if state.show_shortcuts {
+            if state.show_shortcuts => {
                 state.shortcuts_scroll =
                     (state.shortcuts_scroll + 10).min(state.shortcuts_max_scroll);
                 recompute_shortcuts_panel_view_model(&mut state, theme);
             }
             // Otherwise, no-op (handled by other panels)
-        }
-        Action::ScrollPageUp => {
+        Action::ScrollPageUp
             // Shortcuts panel - page up (10 lines)
-            if state.show_shortcuts {
+            if state.show_shortcuts => {
                 state.shortcuts_scroll = state.shortcuts_scroll.saturating_sub(10);
                 recompute_shortcuts_panel_view_model(&mut state, theme);
             }
             // Otherwise, no-op (handled by other panels)
-        }
-        Action::ScrollHalfPageDown => {
+        Action::ScrollHalfPageDown
             // Shortcuts panel - half page down (5 lines)
-            if state.show_shortcuts {
+            if state.show_shortcuts => {
                 state.shortcuts_scroll =
                     (state.shortcuts_scroll + 5).min(state.shortcuts_max_scroll);
                 recompute_shortcuts_panel_view_model(&mut state, theme);
             }
             // Otherwise, no-op (handled by other panels)
-        }
-        Action::ScrollHalfPageUp => {
+        Action::ScrollHalfPageUp
             // Shortcuts panel - half page up (5 lines)
-            if state.show_shortcuts {
+            if state.show_shortcuts => {
                 state.shortcuts_scroll = state.shortcuts_scroll.saturating_sub(5);
                 recompute_shortcuts_panel_view_model(&mut state, theme);
             }
             // Otherwise, no-op (handled by other panels)
-        }
 
         Action::CommandPaletteSelectNext => {
             if let Some(ref mut palette) = state.command_palette
@@ -508,7 +489,7 @@ fn ui_reducer(
         Action::CommandPaletteExecute => {
             // Execute the selected command and close palette
             if let Some(palette) = state.command_palette.take()
-                && let Some((cmd, _score)) = palette.filtered_commands.get(palette.selected_index)
+                && palette.filtered_commands.get(palette.selected_index).is_some()
             {
                 // Dispatch the selected action
                 return (state, vec![]);
@@ -682,12 +663,9 @@ fn infrastructure_reducer(
         }
         Action::BootstrapComplete(result) => {
             // Start recurring updates when bootstrap completes
-            let mut effects = vec![];
             match result {
                 Ok(_) => {
                     state.bootstrap_state = BootstrapState::UIReady;
-                    // Start recurring updates every 30 minutes (1800000 milliseconds)
-                    const THIRTY_MINUTES_MS: u64 = 30 * 60 * 1000;
                     debug!("Bootstrap completed, starting recurring updates");
                 }
                 Err(_) => {
@@ -695,7 +673,7 @@ fn infrastructure_reducer(
                         BootstrapState::Error(result.as_ref().unwrap_err().clone());
                 }
             }
-            (state, effects)
+            (state, vec![])
         }
         _ => (state, vec![]),
     }
@@ -706,11 +684,10 @@ fn infrastructure_reducer(
 fn repos_reducer(
     mut state: ReposState,
     action: &Action,
-    config: &crate::config::Config,
     theme: &crate::theme::Theme,
     infrastructure: &InfrastructureState,
 ) -> (ReposState, Vec<Effect>) {
-    let mut effects = vec![];
+    let effects = vec![];
 
     match action {
         // Bootstrap: Load repositories and session
@@ -763,25 +740,17 @@ fn repos_reducer(
                     let data = state.repo_data.entry(i).or_default();
                     data.loading_state = LoadingState::Loading;
                 }
-
-                // Collect all repos with their indices
-                let repos_with_indices: Vec<_> = result
-                    .repos
-                    .iter()
-                    .enumerate()
-                    .map(|(i, repo)| (i, repo.clone()))
-                    .collect();
             }
         }
-        Action::BootstrapComplete(Err(err)) => {}
+        Action::BootstrapComplete(Err(_)) => {}
         Action::RepoLoadingStarted(repo_index) => {
             // Mark repo as loading (request in flight)
             let data = state.repo_data.entry(*repo_index).or_default();
             data.loading_state = LoadingState::Loading;
         }
-        Action::DeleteCurrentRepo => {
+        Action::DeleteCurrentRepo
             // Delete the currently selected repository
-            if !state.recent_repos.is_empty() {
+            if !state.recent_repos.is_empty() => {
                 let selected_idx = state.selected_repo;
 
                 // Remove the repo from the list
@@ -831,7 +800,6 @@ fn repos_reducer(
                 // - Save to file
                 // - Dispatch SetTaskStatus with success/error message
             }
-        }
         Action::RepositoryAdded { repo_index, repo } => {
             // Add repository to state (dispatched from effect after file save)
             state.recent_repos.push(repo.clone());
@@ -845,8 +813,8 @@ fn repos_reducer(
                 recompute_pr_table_view_model(&mut state, theme);
             }
         }
-        Action::SelectRepoByIndex(index) => {
-            if *index < state.recent_repos.len() {
+        Action::SelectRepoByIndex(index)
+            if *index < state.recent_repos.len() => {
                 state.selected_repo = *index;
 
                 // Sync legacy fields with repo_data
@@ -859,7 +827,6 @@ fn repos_reducer(
                 // Recompute view model for new repo
                 recompute_pr_table_view_model(&mut state, theme);
             }
-        }
         Action::RepoDataLoaded(repo_index, Ok(prs)) => {
             let data = state.repo_data.entry(*repo_index).or_default();
             data.prs = prs.clone();
@@ -890,13 +857,6 @@ fn repos_reducer(
                 state.loading_state = LoadingState::Loaded;
             }
 
-            // Effect: Check merge status for loaded PRs
-            if let Some(repo) = state.recent_repos.get(*repo_index).cloned() {
-                let pr_numbers: Vec<usize> = prs.iter().map(|pr| pr.number).collect();
-
-                // Effect: Check comment counts for loaded PRs
-            }
-
             // Quick load: Check if this is the first repo loaded (selected repo)
             if infrastructure.bootstrap_state == BootstrapState::LoadingFirstRepo
                 && *repo_index == state.selected_repo
@@ -928,15 +888,6 @@ fn repos_reducer(
                         }
                     }
 
-                    // Collect repos to load with their indices (all except selected one)
-                    let repos_to_load: Vec<_> = state
-                        .recent_repos
-                        .iter()
-                        .enumerate()
-                        .filter(|(i, _)| *i != state.selected_repo)
-                        .map(|(i, repo)| (i, repo.clone()))
-                        .collect();
-
                     // Effect: Load remaining repos
                 } else {
                     // Only one repo - we're done
@@ -1011,15 +962,6 @@ fn repos_reducer(
                         }
                     }
 
-                    // Collect repos to load with their indices (all except selected one)
-                    let repos_to_load: Vec<_> = state
-                        .recent_repos
-                        .iter()
-                        .enumerate()
-                        .filter(|(i, _)| *i != state.selected_repo)
-                        .map(|(i, repo)| (i, repo.clone()))
-                        .collect();
-
                     // Effect: Load remaining repos
                 } else {
                     // Only one repo and it failed - still complete bootstrap to show UI
@@ -1043,27 +985,14 @@ fn repos_reducer(
                     )
                 });
 
-            // Effect: Dispatch bootstrap completion if all done
             if all_loaded && infrastructure.bootstrap_state == BootstrapState::LoadingRemainingRepos
             {
-                let loaded_count = state
-                    .repo_data
-                    .values()
-                    .filter(|d| matches!(d.loading_state, LoadingState::Loaded))
-                    .count();
-                let error_count = state
-                    .repo_data
-                    .values()
-                    .filter(|d| matches!(d.loading_state, LoadingState::Error(_)))
-                    .count();
+                debug!("All repositories finished loading");
             }
         }
         Action::CycleFilter => {
             state.filter = state.filter.next();
 
-            // Reload current repository with new filter (use cache, filter is client-side)
-            if let Some(repo) = state.recent_repos.get(state.selected_repo).cloned() {}
-
             // Note: View model will be recomputed when RepoDataLoaded action fires
         }
 
@@ -1072,7 +1001,6 @@ fn repos_reducer(
             return repos_reducer(
                 state,
                 &Action::NavigateToNextPr,
-                config,
                 theme,
                 infrastructure,
             );
@@ -1081,7 +1009,6 @@ fn repos_reducer(
             return repos_reducer(
                 state,
                 &Action::NavigateToPreviousPr,
-                config,
                 theme,
                 infrastructure,
             );
@@ -1091,7 +1018,6 @@ fn repos_reducer(
             return repos_reducer(
                 state,
                 &Action::SelectPreviousRepo,
-                config,
                 theme,
                 infrastructure,
             );
@@ -1101,7 +1027,6 @@ fn repos_reducer(
             return repos_reducer(
                 state,
                 &Action::SelectNextRepo,
-                config,
                 theme,
                 infrastructure,
             );
@@ -1225,7 +1150,7 @@ fn repos_reducer(
 
             // If status is BuildInProgress, start monitoring the build
             if *status == crate::pr::MergeableStatus::BuildInProgress
-                && let Some(repo) = state.recent_repos.get(*repo_index).cloned()
+                && state.recent_repos.get(*repo_index).is_some()
             {
                 // First dispatch action to update state immediately
 
@@ -1312,18 +1237,18 @@ fn repos_reducer(
             // Old: Effect::LoadSingleRepo with bypass_cache: true
             // No effects needed - middleware handles everything
         }
-        Action::StartRecurringUpdates(interval_ms) => {
-            // Effect: Start background recurring task to update all repos periodically
+        Action::StartRecurringUpdates(id, interval_ms) => {
+            // MIGRATION NOTE: Scheduling now handled by SchedulerMiddleware
             debug!(
-                "Starting recurring updates with interval: {}ms ({} minutes)",
+                "Starting recurring {} with interval: {}ms ({} minutes)",
+                id.label(),
                 interval_ms,
                 interval_ms / 60000
             );
         }
-        Action::RecurringUpdateTriggered => {
+        Action::RecurringUpdateTriggered(crate::state::ScheduleId::RepoRefresh) => {
             // Effect: Reload all repositories (triggered by recurring background task)
             debug!("Recurring update triggered, reloading all repos");
-            for (repo_index, repo) in state.recent_repos.iter().enumerate() {}
         }
         Action::Rebase => {
             // MIGRATION NOTE: PerformRebase and StartOperationMonitoring now handled by TaskMiddleware
@@ -1344,37 +1269,7 @@ fn repos_reducer(
             }
         }
         Action::RerunFailedJobs => {
-            // Effect: Rerun failed CI jobs for current or selected PRs
-            if let Some(repo) = state.recent_repos.get(state.selected_repo).cloned() {
-                // Use PR numbers for stable selection
-                let has_selection = if let Some(data) = state.repo_data.get(&state.selected_repo) {
-                    !data.selected_pr_numbers.is_empty()
-                } else {
-                    false
-                };
-
-                let pr_numbers: Vec<usize> = if !has_selection {
-                    // Rerun for current PR only
-                    state
-                        .state
-                        .selected()
-                        .and_then(|idx| state.prs.get(idx))
-                        .map(|pr| vec![pr.number])
-                        .unwrap_or_default()
-                } else if let Some(data) = state.repo_data.get(&state.selected_repo) {
-                    // Rerun for selected PRs using PR numbers (stable)
-                    state
-                        .prs
-                        .iter()
-                        .filter(|pr| data.selected_pr_numbers.contains(&PrNumber::from_pr(pr)))
-                        .map(|pr| pr.number)
-                        .collect()
-                } else {
-                    Vec::new()
-                };
-
-                if !pr_numbers.is_empty() {}
-            }
+            // MIGRATION NOTE: Rerunning failed jobs now handled by TaskMiddleware
         }
         Action::ApprovePrs => {
             // MIGRATION NOTE: ApprovePrs now handled by TaskMiddleware
@@ -1405,23 +1300,7 @@ fn repos_reducer(
             }
         }
         Action::StartMergeBot => {
-            // Effect: Start merge bot with selected PRs
-            if let Some(repo) = state.recent_repos.get(state.selected_repo).cloned() {
-                // Use PR numbers for stable selection
-                let prs_to_process: Vec<_> =
-                    if let Some(data) = state.repo_data.get(&state.selected_repo) {
-                        state
-                            .prs
-                            .iter()
-                            .filter(|pr| data.selected_pr_numbers.contains(&PrNumber::from_pr(pr)))
-                            .cloned()
-                            .collect()
-                    } else {
-                        Vec::new()
-                    };
-
-                if !prs_to_process.is_empty() {}
-            }
+            // MIGRATION NOTE: Collecting the PRs for the merge bot now handled by TaskMiddleware
         }
         Action::OpenCurrentPrInBrowser => {
             // MIGRATION NOTE: OpenInBrowser now handled by TaskMiddleware
@@ -1431,12 +1310,7 @@ fn repos_reducer(
             // No effects needed
         }
         Action::OpenBuildLogs => {
-            // Effect: Load build logs for current PR
-            if let Some(selected_idx) = state.state.selected()
-                && let Some(pr) = state.prs.get(selected_idx).cloned()
-                && let Some(repo) = state.recent_repos.get(state.selected_repo).cloned()
-            {
-            }
+            // MIGRATION NOTE: Loading build logs now handled by TaskMiddleware
         }
         Action::OpenInIDE => {
             // MIGRATION NOTE: OpenInIDE now handled by TaskMiddleware
@@ -1446,8 +1320,8 @@ fn repos_reducer(
             // - Send BackgroundTask::OpenPRInIDE
             // No effects needed
         }
-        Action::SelectNextRepo => {
-            if !state.recent_repos.is_empty() {
+        Action::SelectNextRepo
+            if !state.recent_repos.is_empty() => {
                 state.selected_repo = (state.selected_repo + 1) % state.recent_repos.len();
 
                 // Sync legacy fields with repo_data
@@ -1461,9 +1335,8 @@ fn repos_reducer(
                 recompute_pr_table_view_model(&mut state, theme);
                 recompute_repository_tabs_view_model(&mut state);
             }
-        }
-        Action::SelectPreviousRepo => {
-            if !state.recent_repos.is_empty() {
+        Action::SelectPreviousRepo
+            if !state.recent_repos.is_empty() => {
                 state.selected_repo = if state.selected_repo == 0 {
                     state.recent_repos.len() - 1
                 } else {
@@ -1481,7 +1354,6 @@ fn repos_reducer(
                 recompute_pr_table_view_model(&mut state, theme);
                 recompute_repository_tabs_view_model(&mut state);
             }
-        }
         Action::StartOperationMonitor(repo_index, pr_number, operation) => {
             // Add PR to operation monitor queue and set initial state
             if let Some(data) = state.repo_data.get_mut(repo_index) {
@@ -1590,7 +1462,7 @@ fn repos_reducer(
                         .retain(|pr| pr.pr_number != *pr_number);
                 } else {
                     // Check PR status
-                    if let Some(repo) = state.recent_repos.get(*repo_index).cloned() {
+                    if state.recent_repos.get(*repo_index).is_some() {
                         // Find the PR to check its status
                         if let Some(pr) = data.prs.iter().find(|p| p.number == *pr_number) {
                             match pr.mergeable {
@@ -1868,7 +1740,7 @@ fn merge_bot_reducer(
     action: &Action,
     repos: &ReposState,
 ) -> (MergeBotState, Vec<Effect>) {
-    let mut effects = vec![];
+    let effects = vec![];
 
     match action {
         Action::StartMergeBot => {
@@ -1879,11 +1751,9 @@ fn merge_bot_reducer(
             // Initialize merge bot with PR data (reducer responsibility)
             state.bot.start(pr_data.clone());
         }
-        Action::MergeBotTick => {
+        Action::MergeBotTick
             // Process merge bot queue if bot is running
-            if state.bot.is_running()
-                && let Some(repo) = repos.recent_repos.get(repos.selected_repo).cloned()
-            {
+            if state.bot.is_running() && repos.recent_repos.get(repos.selected_repo).is_some() => {
                 let repo_data = repos
                     .repo_data
                     .get(&repos.selected_repo)
@@ -1897,7 +1767,7 @@ fn merge_bot_reducer(
                         MergeBotAction::DispatchMerge(_indices) => {}
                         MergeBotAction::DispatchRebase(_indices) => {}
                         MergeBotAction::WaitForCI(_pr_number) => {}
-                        MergeBotAction::PollMergeStatus(pr_number, is_checking_ci) => {}
+                        MergeBotAction::PollMergeStatus(_pr_number, _is_checking_ci) => {}
                         MergeBotAction::PrSkipped(_pr_number, _reason) => {}
                         MergeBotAction::Completed => {
 
@@ -1906,27 +1776,22 @@ fn merge_bot_reducer(
                     }
                 }
             }
-        }
-        Action::MergeStatusUpdated(_repo_index, pr_number, status) => {
-            if state.bot.is_running() {
+        Action::MergeStatusUpdated(_repo_index, pr_number, status)
+            if state.bot.is_running() => {
                 state.bot.handle_status_update(*pr_number, *status);
             }
-        }
-        Action::RebaseComplete(result) => {
-            if state.bot.is_running() {
+        Action::RebaseComplete(result)
+            if state.bot.is_running() => {
                 state.bot.handle_rebase_complete(result.is_ok());
             }
-        }
-        Action::MergeComplete(result) => {
-            if state.bot.is_running() {
+        Action::MergeComplete(result)
+            if state.bot.is_running() => {
                 state.bot.handle_merge_complete(result.is_ok());
             }
-        }
-        Action::PRMergedConfirmed(_repo_index, pr_number, is_merged) => {
-            if state.bot.is_running() {
+        Action::PRMergedConfirmed(_repo_index, pr_number, is_merged)
+            if state.bot.is_running() => {
                 state.bot.handle_pr_merged_confirmed(*pr_number, *is_merged);
             }
-        }
         _ => {}
     }
 
@@ -2121,6 +1986,12 @@ fn debug_console_reducer(
             // Just recompute view model in case logs changed
             recompute_debug_console_view_model(&mut state, theme);
         }
+        Action::SchedulesChanged(schedules) => {
+            state.schedules = schedules.clone();
+            if state.is_open {
+                recompute_debug_console_view_model(&mut state, theme);
+            }
+        }
         _ => {}
     }
 
@@ -2146,6 +2017,7 @@ fn recompute_debug_console_view_model(state: &mut DebugConsoleState, theme: &cra
     state.view_model = Some(
         crate::view_models::debug_console::DebugConsoleViewModel::from_state(
             &logs,
+            &state.schedules,
             state.scroll_offset,
             state.auto_scroll,
             visible_height,
@@ -2405,8 +2277,11 @@ mod tests {
                 merge_state: "clean".to_string(),
                 mergeable: crate::pr::MergeableStatus::Unknown,
                 needs_rebase: false,
+                head_sha: String::new(),
                 created_at: chrono::Utc::now(),
                 updated_at: chrono::Utc::now(),
+                maturity: crate::pr::MaturityState::Ready,
+                review_decision: crate::pr::ReviewDecision::Unknown,
             },
             crate::pr::Pr {
                 number: 2,
@@ -2417,8 +2292,11 @@ mod tests {
                 merge_state: "clean".to_string(),
                 mergeable: crate::pr::MergeableStatus::Unknown,
                 needs_rebase: false,
+                head_sha: String::new(),
                 created_at: chrono::Utc::now(),
                 updated_at: chrono::Utc::now(),
+                maturity: crate::pr::MaturityState::Ready,
+                review_decision: crate::pr::ReviewDecision::Unknown,
             },
         ];
         state.repos.state.select(Some(0));
@@ -2426,7 +2304,6 @@ mod tests {
         let (repos_state, _effects) = repos_reducer(
             state.repos,
             &Action::NavigateNext,
-            &state.config,
             &state.theme,
             &state.infra,
         );
@@ -2456,7 +2333,6 @@ mod tests {
         let (repos_state, _effects) = repos_reducer(
             state.repos,
             &Action::NavigateLeft,
-            &state.config,
             &state.theme,
             &state.infra,
         );
@@ -2486,7 +2362,6 @@ mod tests {
         let (repos_state, _effects) = repos_reducer(
             state.repos,
             &Action::NavigateRight,
-            &state.config,
             &state.theme,
             &state.infra,
         );
@@ -2499,22 +2374,24 @@ mod tests {
 
     #[test]
     fn test_log_panel_scroll_to_top() {
-        let mut state = LogPanelState::default();
-        state.panel = Some(crate::log::LogPanel {
-            workflows: vec![],
-            job_metadata: std::collections::HashMap::new(),
-            expanded_nodes: std::collections::HashSet::new(),
-            cursor_path: vec![],
-            scroll_offset: 10,
-            horizontal_scroll: 0,
-            show_timestamps: false,
-            viewport_height: 20,
-            pr_context: crate::log::PrContext {
-                number: 1,
-                title: "Test PR".to_string(),
-                author: "test".to_string(),
-            },
-        });
+        let state = LogPanelState {
+            panel: Some(crate::log::LogPanel {
+                workflows: vec![],
+                job_metadata: std::collections::HashMap::new(),
+                expanded_nodes: std::collections::HashSet::new(),
+                cursor_path: vec![],
+                scroll_offset: 10,
+                horizontal_scroll: 0,
+                show_timestamps: false,
+                viewport_height: 20,
+                pr_context: crate::log::PrContext {
+                    number: 1,
+                    title: "Test PR".to_string(),
+                    author: "test".to_string(),
+                },
+            }),
+            ..Default::default()
+        };
 
         let (log_state, _effects) =
             log_panel_reducer(state, &Action::ScrollToTop, &crate::theme::Theme::default());
@@ -2524,22 +2401,24 @@ mod tests {
 
     #[test]
     fn test_log_panel_navigate_next_scrolls_down() {
-        let mut state = LogPanelState::default();
-        state.panel = Some(crate::log::LogPanel {
-            workflows: vec![],
-            job_metadata: std::collections::HashMap::new(),
-            expanded_nodes: std::collections::HashSet::new(),
-            cursor_path: vec![],
-            scroll_offset: 0,
-            horizontal_scroll: 0,
-            show_timestamps: false,
-            viewport_height: 20,
-            pr_context: crate::log::PrContext {
-                number: 1,
-                title: "Test PR".to_string(),
-                author: "test".to_string(),
-            },
-        });
+        let state = LogPanelState {
+            panel: Some(crate::log::LogPanel {
+                workflows: vec![],
+                job_metadata: std::collections::HashMap::new(),
+                expanded_nodes: std::collections::HashSet::new(),
+                cursor_path: vec![],
+                scroll_offset: 0,
+                horizontal_scroll: 0,
+                show_timestamps: false,
+                viewport_height: 20,
+                pr_context: crate::log::PrContext {
+                    number: 1,
+                    title: "Test PR".to_string(),
+                    author: "test".to_string(),
+                },
+            }),
+            ..Default::default()
+        };
 
         let (log_state, _effects) = log_panel_reducer(
             state,
@@ -2557,9 +2436,11 @@ mod tests {
 
     #[test]
     fn test_debug_console_scroll_to_top() {
-        let mut state = DebugConsoleState::default();
-        state.is_open = true;
-        state.scroll_offset = 10;
+        let state = DebugConsoleState {
+            is_open: true,
+            scroll_offset: 10,
+            ..Default::default()
+        };
 
         let (console_state, _effects) =
             debug_console_reducer(state, &Action::ScrollToTop, &crate::theme::Theme::default());
@@ -2570,10 +2451,12 @@ mod tests {
 
     #[test]
     fn test_debug_console_scroll_to_bottom_enables_autoscroll() {
-        let mut state = DebugConsoleState::default();
-        state.is_open = true;
-        state.scroll_offset = 0;
-        state.auto_scroll = false;
+        let state = DebugConsoleState {
+            is_open: true,
+            scroll_offset: 0,
+            auto_scroll: false,
+            ..Default::default()
+        };
 
         let (console_state, _effects) = debug_console_reducer(
             state,
@@ -2586,9 +2469,11 @@ mod tests {
 
     #[test]
     fn test_debug_console_navigate_next_scrolls_down() {
-        let mut state = DebugConsoleState::default();
-        state.is_open = true;
-        state.scroll_offset = 0;
+        let state = DebugConsoleState {
+            is_open: true,
+            scroll_offset: 0,
+            ..Default::default()
+        };
 
         let (console_state, _effects) = debug_console_reducer(
             state,
@@ -2,6 +2,7 @@ use ratatui::widgets::TableState;
 use std::{
     collections::{HashMap, HashSet},
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
 use crate::{config::Config, log::LogPanel, merge_bot::MergeBot, pr::Pr, theme::Theme};
@@ -208,10 +209,34 @@ pub struct DebugConsoleState {
     pub auto_scroll: bool,    // Follow new logs as they arrive
     pub height_percent: u16,  // Height as percentage of screen (30-70)
     pub logs: crate::log_capture::LogBuffer,
+    /// Active recurring updates, reported by the scheduler
+    pub schedules: Vec<ScheduleInfo>,
     /// View model - pre-computed presentation data
     pub view_model: Option<crate::view_models::debug_console::DebugConsoleViewModel>,
 }
 
+/// Purpose of a recurring update, at most one schedule runs per purpose
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ScheduleId {
+    /// Reload all repositories
+    RepoRefresh,
+}
+
+impl ScheduleId {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScheduleId::RepoRefresh => "repo refresh",
+        }
+    }
+}
+
+/// An active recurring update
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleInfo {
+    pub id: ScheduleId,
+    pub interval: Duration,
+}
+
 // Re-export types from main.rs that are part of state
 #[derive(Debug, Clone)]
 pub struct TaskStatus {
@@ -462,6 +487,7 @@ impl Default for DebugConsoleState {
             auto_scroll: true,
             height_percent: 50, // 50% of screen height
             logs: crate::log_capture::DebugConsoleLogger::create_buffer(),
+            schedules: Vec::new(),
             view_model: None,
         }
     }
@@ -1,6 +1,6 @@
 use crate::{
     actions::Action,
-    middleware::{Dispatcher, Middleware},
+    middleware::{Dispatcher, Middleware, MiddlewareChain},
     reducer::reduce,
     state::AppState,
 };
@@ -18,15 +18,15 @@ use crate::{
 /// The store now supports middleware for handling side effects:
 /// ```rust
 /// let mut store = Store::new(state);
-/// store.add_middleware(LoggingMiddleware);
+/// store.add_middleware(logging_middleware());
 /// store.add_middleware(TaskMiddleware::new(octocrab, cache));
 ///
-/// // Async dispatch through middleware
-/// store.dispatch_async(action, &dispatcher).await;
+/// // Dispatch through middleware
+/// store.dispatch_through(action, &dispatcher);
 /// ```
 pub struct Store {
     state: AppState,
-    middleware: Vec<Box<dyn Middleware>>,
+    middleware: MiddlewareChain,
 }
 
 impl Store {
@@ -45,10 +45,13 @@ impl Store {
     ///
     /// # Example
     /// ```rust
-    /// store.add_middleware(LoggingMiddleware);
+    /// store.add_middleware(logging_middleware());
     /// store.add_middleware(TaskMiddleware::new(octocrab, cache));
     /// ```
-    pub fn add_middleware<M: Middleware + 'static>(&mut self, middleware: M) {
+    pub fn add_middleware<M>(&mut self, middleware: M)
+    where
+        M: Middleware<Action, AppState, Dispatcher> + 'static,
+    {
         self.middleware.push(Box::new(middleware));
     }
 
@@ -85,20 +88,12 @@ impl Store {
     ///
     /// # Example
     /// ```rust
-    /// store.dispatch_async(Action::MergeSelectedPrs, &dispatcher).await;
+    /// store.dispatch_through(Action::MergeSelectedPrs, &dispatcher);
     /// ```
-    pub async fn dispatch_async(&mut self, action: Action, dispatcher: &Dispatcher) {
-        // Run action through middleware chain
-        let mut should_continue = true;
-        for middleware in &mut self.middleware {
-            if !middleware.handle(&action, &self.state, dispatcher).await {
-                should_continue = false;
-                break;
-            }
-        }
-
-        // If not blocked by middleware, apply to reducer
-        if should_continue {
+    pub fn dispatch_through(&mut self, action: Action, dispatcher: &Dispatcher) {
+        // Run action through middleware chain, the reducer only sees it if
+        // no middleware blocked it
+        if gh_redux::run_chain(&mut self.middleware, &action, &self.state, dispatcher) {
             let (new_state, _effects) = reduce(self.state.clone(), &action);
             self.state = new_state;
             // Effects are always empty now - all side effects in middleware
@@ -110,7 +105,7 @@ impl Store {
     /// This is the old synchronous dispatch method. It bypasses middleware
     /// and goes straight to the reducer.
     ///
-    /// Prefer `dispatch_async()` for new code. This method should only be used
+    /// Prefer `dispatch_through()` for new code. This method should only be used
     /// in tests or simple scenarios where middleware is not needed.
     pub fn dispatch(&mut self, action: Action) {
         // Apply reducer to get new state (effects ignored)
@@ -25,6 +25,7 @@ impl DebugConsoleViewModel {
     /// Returns only the visible logs for the current viewport
     pub fn from_state(
         logs: &[crate::log_capture::LogEntry],
+        schedules: &[crate::state::ScheduleInfo],
         scroll_offset: usize,
         auto_scroll: bool,
         visible_height: usize,
@@ -83,7 +84,26 @@ impl DebugConsoleViewModel {
 
         // Pre-format title
         let mode_text = if auto_scroll { "[AUTO]" } else { "[MANUAL]" };
-        let title = format!(" Debug Console ({}) {} ", log_count, mode_text);
+        let title = if schedules.is_empty() {
+            format!(" Debug Console ({}) {} ", log_count, mode_text)
+        } else {
+            let active: Vec<String> = schedules
+                .iter()
+                .map(|schedule| {
+                    format!(
+                        "{} every {}",
+                        schedule.id.label(),
+                        format_interval(schedule.interval)
+                    )
+                })
+                .collect();
+            format!(
+                " Debug Console ({}) {} | Schedules: {} ",
+                log_count,
+                mode_text,
+                active.join(", ")
+            )
+        };
 
         // Pre-format footer
         let footer = " `~` Close | j/k Scroll | a Auto-scroll | c Clear ".to_string();
@@ -95,3 +115,15 @@ impl DebugConsoleViewModel {
         }
     }
 }
+
+/// Format an interval in its largest whole unit (e.g., "5m", "30s", "250ms")
+fn format_interval(interval: std::time::Duration) -> String {
+    let ms = interval.as_millis();
+    if ms >= 60_000 && ms.is_multiple_of(60_000) {
+        format!("{}m", ms / 60_000)
+    } else if ms >= 1_000 && ms.is_multiple_of(1_000) {
+        format!("{}s", ms / 1_000)
+    } else {
+        format!("{}ms", ms)
+    }
+}
@@ -38,15 +38,15 @@ pub struct PrTableHeaderViewModel {
 #[derive(Debug, Clone)]
 pub struct PrRowViewModel {
     /// Pre-formatted cell texts
-    pub pr_number: String,    // "#123"
-    pub title: String,        // "Fix: broken tests"
-    pub author: String,       // "sassman"
+    pub pr_number: String, // "#123"
+    pub title: String,         // "Fix: broken tests"
+    pub author: String,        // "sassman"
     pub maturity_text: String, // "Draft" or ""
-    pub review_text: String,  // "✓", "!", "○", "?"
-    pub status_text: String,  // "✓ Ready"
+    pub review_text: String,   // "✓", "!", "○", "?"
+    pub status_text: String,   // "✓ Ready"
 
     /// Pre-computed styles
-    pub bg_color: Color,       // Background (alternating, selected, etc.)
+    pub bg_color: Color, // Background (alternating, selected, etc.)
     pub fg_color: Color,       // Text color
     pub maturity_color: Color, // Maturity-specific color
     pub review_color: Color,   // Review-specific color
@@ -54,8 +54,8 @@ pub struct PrRowViewModel {
 
     /// Metadata for interactions (not displayed)
     pub pr_number_raw: usize, // For opening PR
-    pub is_selected: bool,    // Space key selection
-    pub is_cursor: bool,      // Keyboard navigation position
+    pub is_selected: bool, // Space key selection
+    pub is_cursor: bool,   // Keyboard navigation position
     pub row_style: RowStyle,
 }
 
@@ -43,16 +43,9 @@ pub fn render_pr_table(f: &mut Frame, area: Rect, app: &mut App) {
         .fg(theme.table_header_fg)
         .bg(theme.table_header_bg);
 
-    let header_cells = [
-        "#PR",
-        "Description",
-        "Author",
-        "Draft?",
-        "Review",
-        "Status",
-    ]
-    .iter()
-    .map(|h| Cell::from(*h).style(header_style));
+    let header_cells = ["#PR", "Description", "Author", "Draft?", "Review", "Status"]
+        .iter()
+        .map(|h| Cell::from(*h).style(header_style));
 
     let header = Row::new(header_cells)
         .style(Style::default().bg(theme.table_header_bg))
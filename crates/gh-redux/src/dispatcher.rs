@@ -0,0 +1,59 @@
+//! Dispatcher for middleware action dispatch
+
+use std::sync::mpsc::Sender;
+
+/// Sends actions back into the middleware chain
+///
+/// Actions dispatched here re-enter the chain from the beginning (via the
+/// channel feeding the worker that runs it), so middleware can trigger other
+/// middleware.
+pub struct Dispatcher<A> {
+    action_tx: Sender<A>,
+}
+
+// Manual impl, a derive would require `A: Clone`
+impl<A> Clone for Dispatcher<A> {
+    fn clone(&self) -> Self {
+        Self {
+            action_tx: self.action_tx.clone(),
+        }
+    }
+}
+
+impl<A> Dispatcher<A> {
+    /// Create a dispatcher sending into the channel of the middleware worker
+    pub fn new(action_tx: Sender<A>) -> Self {
+        Self { action_tx }
+    }
+
+    /// Dispatch an action through the middleware chain
+    pub fn dispatch(&self, action: A) {
+        if self.action_tx.send(action).is_err() {
+            log::error!("Dispatcher: failed to send action, channel closed");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_dispatch_sends_to_channel() {
+        let (tx, rx) = mpsc::channel();
+        let dispatcher = Dispatcher::new(tx);
+
+        dispatcher.clone().dispatch(1);
+        dispatcher.dispatch(2);
+
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_dispatch_to_closed_channel_does_not_panic() {
+        let (tx, rx) = mpsc::channel();
+        drop(rx);
+        Dispatcher::new(tx).dispatch(1);
+    }
+}
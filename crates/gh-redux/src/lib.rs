@@ -0,0 +1,26 @@
+//! Redux plumbing shared by the gh-pr apps
+//!
+//! Generic over the action and state types of an app:
+//!
+//! ```text
+//! Action → Middleware chain → Reducer → State
+//! ```
+//!
+//! - [`Middleware`] intercepts actions before the reducer and performs side
+//!   effects; [`run_chain`] runs a chain of them and logs handlers slower
+//!   than [`SLOW_HANDLER`], [`run_chain_catching`] also survives panicking
+//!   handlers.
+//! - [`Dispatcher`] lets middleware dispatch follow-up actions that re-enter
+//!   the chain.
+//! - [`Store`] holds the state and applies a [`Reducer`].
+//! - [`LoggingMiddleware`] logs every action that is not noise.
+
+mod dispatcher;
+mod logging;
+mod middleware;
+mod store;
+
+pub use dispatcher::Dispatcher;
+pub use logging::LoggingMiddleware;
+pub use middleware::{run_chain, run_chain_catching, HandlerPanic, Middleware, SLOW_HANDLER};
+pub use store::{Reducer, Store};
@@ -0,0 +1,58 @@
+//! LoggingMiddleware - logs actions for debugging
+
+use crate::Middleware;
+use std::fmt::Debug;
+
+/// Logs every action passing through the chain, except noise
+///
+/// Never consumes actions. `is_noise` filters frequent actions (ticks,
+/// viewport updates) that would drown the log.
+pub struct LoggingMiddleware<A> {
+    level: log::Level,
+    is_noise: fn(&A) -> bool,
+}
+
+impl<A> LoggingMiddleware<A> {
+    pub fn new(level: log::Level, is_noise: fn(&A) -> bool) -> Self {
+        Self { level, is_noise }
+    }
+
+    /// Whether `action` gets logged
+    pub fn logs(&self, action: &A) -> bool {
+        !(self.is_noise)(action)
+    }
+}
+
+impl<A: Debug, S, D> Middleware<A, S, D> for LoggingMiddleware<A> {
+    fn handle(&mut self, action: &A, _state: &S, _dispatcher: &D) -> bool {
+        if self.logs(action) {
+            log::log!(self.level, "Action: {:?}", action);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Dispatcher;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_logging_never_consumes() {
+        let mut middleware = LoggingMiddleware::new(log::Level::Debug, |a: &u32| *a == 0);
+        let (tx, _rx) = mpsc::channel::<u32>();
+        let dispatcher = Dispatcher::new(tx);
+
+        assert!(!middleware.logs(&0));
+        assert!(middleware.logs(&1));
+        for action in [0, 1] {
+            assert!(Middleware::<u32, ()>::handle(
+                &mut middleware,
+                &action,
+                &(),
+                &dispatcher
+            ));
+        }
+    }
+}
@@ -0,0 +1,242 @@
+//! Middleware trait and chain
+
+use crate::Dispatcher;
+use std::any::Any;
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+/// Handlers taking longer than this are logged as slow
+///
+/// All middleware runs on one thread, so a handler blocking on file or
+/// process I/O delays every action queued behind it (e.g. refresh results).
+pub const SLOW_HANDLER: Duration = Duration::from_millis(50);
+
+/// Intercepts actions before they reach the reducer
+///
+/// Middleware may inspect the action and a read-only state snapshot,
+/// dispatch follow-up actions and perform side effects (API calls, file I/O).
+/// `D` is the dispatcher handed to it, [`Dispatcher`] unless an app wraps it.
+pub trait Middleware<A, S, D = Dispatcher<A>>: Send {
+    /// Handle an action
+    ///
+    /// Returns `true` to continue the chain, `false` to consume the action
+    fn handle(&mut self, action: &A, state: &S, dispatcher: &D) -> bool;
+
+    /// Name in slow handler warnings, the type name by default
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+}
+
+/// Run `action` through the middleware chain, in order
+///
+/// Stops at the first middleware consuming the action. Returns whether the
+/// action should be forwarded to the reducer. Handlers slower than
+/// [`SLOW_HANDLER`] are logged with the action they handled.
+pub fn run_chain<A: Debug, S, D>(
+    middleware: &mut [Box<dyn Middleware<A, S, D> + Send>],
+    action: &A,
+    state: &S,
+    dispatcher: &D,
+) -> bool {
+    middleware
+        .iter_mut()
+        .all(|mw| handle_timed(mw.as_mut(), action, state, dispatcher))
+}
+
+/// A middleware handler that panicked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandlerPanic {
+    /// Position of the middleware in the chain
+    pub index: usize,
+    /// [`Middleware::name`] of the middleware
+    pub name: &'static str,
+    /// The panic message
+    pub message: String,
+}
+
+/// Run `action` through the middleware chain like [`run_chain`], catching
+/// panicking handlers
+///
+/// A panicking middleware is skipped: the action goes on to the next one as
+/// if it was passed on. Middleware for which `skip` returns true is not run
+/// at all. Returns whether the action should be forwarded to the reducer and
+/// the caught panics.
+///
+/// The handlers run under [`AssertUnwindSafe`]: a middleware that panicked
+/// may have left its own fields half updated. Stop running it after repeated
+/// panics rather than relying on its state.
+pub fn run_chain_catching<A: Debug, S, D>(
+    middleware: &mut [Box<dyn Middleware<A, S, D> + Send>],
+    action: &A,
+    state: &S,
+    dispatcher: &D,
+    skip: impl Fn(usize) -> bool,
+) -> (bool, Vec<HandlerPanic>) {
+    let mut panics = Vec::new();
+    for (index, mw) in middleware.iter_mut().enumerate() {
+        if skip(index) {
+            continue;
+        }
+        let handled = panic::catch_unwind(AssertUnwindSafe(|| {
+            handle_timed(mw.as_mut(), action, state, dispatcher)
+        }));
+        match handled {
+            Ok(true) => {}
+            Ok(false) => return (false, panics),
+            Err(payload) => panics.push(HandlerPanic {
+                index,
+                name: mw.name(),
+                message: panic_message(payload.as_ref()),
+            }),
+        }
+    }
+    (true, panics)
+}
+
+/// Run one handler, logging it when slower than [`SLOW_HANDLER`]
+fn handle_timed<A: Debug, S, D>(
+    mw: &mut (dyn Middleware<A, S, D> + Send),
+    action: &A,
+    state: &S,
+    dispatcher: &D,
+) -> bool {
+    let started = Instant::now();
+    let forward = mw.handle(action, state, dispatcher);
+    let elapsed = started.elapsed();
+    if elapsed > SLOW_HANDLER {
+        log::warn!(
+            "Slow middleware: {} took {}ms for {:?}",
+            mw.name(),
+            elapsed.as_millis(),
+            action
+        );
+    }
+    forward
+}
+
+/// The message a panic was raised with (`panic!` payloads are strings)
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    /// Records what it saw, consumes `consume` and re-dispatches `echo`
+    struct Probe {
+        seen: std::sync::Arc<std::sync::Mutex<Vec<u32>>>,
+        consume: u32,
+        echo: u32,
+    }
+
+    impl Middleware<u32, ()> for Probe {
+        fn handle(&mut self, action: &u32, _state: &(), dispatcher: &Dispatcher<u32>) -> bool {
+            self.seen.lock().unwrap().push(*action);
+            if *action == self.echo {
+                dispatcher.dispatch(action + 100);
+            }
+            *action != self.consume
+        }
+    }
+
+    #[test]
+    fn test_chain_order_consume_and_reentry() {
+        let first = std::sync::Arc::default();
+        let second = std::sync::Arc::default();
+        let mut chain: Vec<Box<dyn Middleware<u32, ()> + Send>> = vec![
+            Box::new(Probe {
+                seen: std::sync::Arc::clone(&first),
+                consume: 1,
+                echo: 2,
+            }),
+            Box::new(Probe {
+                seen: std::sync::Arc::clone(&second),
+                consume: 0,
+                echo: 0,
+            }),
+        ];
+        let (tx, rx) = mpsc::channel();
+        let dispatcher = Dispatcher::new(tx);
+
+        // Drain the queue like the app workers do, re-entering dispatched actions
+        let mut forwarded = vec![];
+        let mut queue = vec![1, 2];
+        while let Some(action) = queue.pop() {
+            if run_chain(&mut chain, &action, &(), &dispatcher) {
+                forwarded.push(action);
+            }
+            queue.extend(rx.try_iter());
+        }
+
+        assert_eq!(forwarded, vec![2, 102]);
+        assert_eq!(*first.lock().unwrap(), vec![2, 102, 1]);
+        // The consumed action never reaches the second middleware
+        assert_eq!(*second.lock().unwrap(), vec![2, 102]);
+    }
+
+    /// Panics on `panic_on`, passes everything else on
+    struct Faulty {
+        panic_on: u32,
+    }
+
+    impl Middleware<u32, ()> for Faulty {
+        fn handle(&mut self, action: &u32, _state: &(), _dispatcher: &Dispatcher<u32>) -> bool {
+            if *action == self.panic_on {
+                panic!("faulty handler hit {}", action);
+            }
+            true
+        }
+    }
+
+    #[test]
+    fn test_panicking_handler_is_skipped() {
+        let seen = std::sync::Arc::default();
+        let mut chain: Vec<Box<dyn Middleware<u32, ()> + Send>> = vec![
+            Box::new(Faulty { panic_on: 7 }),
+            Box::new(Probe {
+                seen: std::sync::Arc::clone(&seen),
+                consume: 8,
+                echo: 0,
+            }),
+        ];
+        let (tx, _rx) = mpsc::channel();
+        let dispatcher = Dispatcher::new(tx);
+
+        let (forward, panics) = run_chain_catching(&mut chain, &7, &(), &dispatcher, |_| false);
+        assert!(forward);
+        assert_eq!(panics.len(), 1);
+        assert_eq!(panics[0].index, 0);
+        assert!(panics[0].name.ends_with("::Faulty"));
+        assert_eq!(panics[0].message, "faulty handler hit 7");
+        // The rest of the chain still saw the action
+        assert_eq!(*seen.lock().unwrap(), vec![7]);
+
+        let (forward, panics) = run_chain_catching(&mut chain, &8, &(), &dispatcher, |_| false);
+        assert!(!forward);
+        assert!(panics.is_empty());
+
+        // Skipped middleware does not run
+        let (forward, panics) = run_chain_catching(&mut chain, &7, &(), &dispatcher, |i| i == 0);
+        assert!(forward);
+        assert!(panics.is_empty());
+        assert_eq!(*seen.lock().unwrap(), vec![7, 8, 7]);
+    }
+
+    #[test]
+    fn test_name_defaults_to_the_type_name() {
+        let probe = Probe {
+            seen: std::sync::Arc::default(),
+            consume: 0,
+            echo: 0,
+        };
+        assert!(Middleware::<u32, ()>::name(&probe).ends_with("::Probe"));
+    }
+}
@@ -0,0 +1,71 @@
+//! Store holding the app state
+
+/// Pure state transition of an app
+pub trait Reducer {
+    /// Action type of the app
+    type Action;
+    /// State type of the app
+    type State: Clone;
+
+    /// Compute the next state from the current one and an action
+    fn reduce(state: Self::State, action: &Self::Action) -> Self::State;
+}
+
+/// Holds the app state and applies actions through the reducer `R`
+///
+/// Middleware runs elsewhere (on the worker thread), the store only applies
+/// actions that made it through the chain.
+pub struct Store<R: Reducer> {
+    state: R::State,
+}
+
+impl<R: Reducer> Store<R> {
+    pub fn new(initial_state: R::State) -> Self {
+        Self {
+            state: initial_state,
+        }
+    }
+
+    /// Get the current state
+    pub fn state(&self) -> &R::State {
+        &self.state
+    }
+
+    /// Get mutable state reference
+    pub fn state_mut(&mut self) -> &mut R::State {
+        &mut self.state
+    }
+
+    /// Apply an action through the reducer (no middleware)
+    pub fn dispatch(&mut self, action: R::Action) {
+        self.state = R::reduce(self.state.clone(), &action);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Counter;
+
+    impl Reducer for Counter {
+        type Action = i32;
+        type State = i32;
+
+        fn reduce(state: i32, action: &i32) -> i32 {
+            state + action
+        }
+    }
+
+    #[test]
+    fn test_dispatch_applies_reducer() {
+        let mut store = Store::<Counter>::new(1);
+        store.dispatch(2);
+        store.dispatch(-5);
+        assert_eq!(*store.state(), -2);
+
+        *store.state_mut() = 10;
+        store.dispatch(1);
+        assert_eq!(*store.state(), 11);
+    }
+}
@@ -0,0 +1,186 @@
+//! Display width of terminal text
+//!
+//! A terminal cell is not a `char`: CJK characters and most emoji take two
+//! cells, combining marks and zero width joiners none. Padding with
+//! `format!("{:<8}")` or cutting with `chars().take(8)` misaligns the columns
+//! after such text and can split an emoji sequence or strip the accent off
+//! its letter.
+//!
+//! Text is measured like ratatui renders it, per grapheme cluster with
+//! `unicode-width`, and only ever cut between grapheme clusters.
+
+use std::borrow::Cow;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Appended to truncated text, one cell wide
+pub const ELLIPSIS: &str = "…";
+
+/// Cells `text` takes in a terminal
+pub fn width(text: &str) -> usize {
+    text.graphemes(true).map(UnicodeWidthStr::width).sum()
+}
+
+/// The longest start of `text` that fits in `cells`, without an ellipsis
+pub fn clip(text: &str, cells: usize) -> &str {
+    let mut used = 0;
+    for (idx, grapheme) in text.grapheme_indices(true) {
+        used += grapheme.width();
+        if used > cells {
+            return &text[..idx];
+        }
+    }
+    text
+}
+
+/// The longest end of `text` that fits in `cells`, without an ellipsis
+pub fn clip_end(text: &str, cells: usize) -> &str {
+    let mut used = 0;
+    for (idx, grapheme) in text.grapheme_indices(true).rev() {
+        used += grapheme.width();
+        if used > cells {
+            return &text[idx + grapheme.len()..];
+        }
+    }
+    text
+}
+
+/// `text` cut to at most `cells`, ending in an ellipsis when cut
+///
+/// A wide grapheme that does not fit before the ellipsis is left out, the
+/// result is one cell short then.
+pub fn truncate(text: &str, cells: usize) -> Cow<'_, str> {
+    if width(text) <= cells {
+        return Cow::Borrowed(text);
+    }
+    if cells == 0 {
+        return Cow::Borrowed("");
+    }
+    Cow::Owned(format!("{}{}", clip(text, cells - 1), ELLIPSIS))
+}
+
+/// `text` without its first `cells` (horizontal scrolling)
+///
+/// A wide grapheme scrolled half out of view leaves a space for its other half.
+pub fn skip(text: &str, cells: usize) -> Cow<'_, str> {
+    let mut skipped = 0;
+    for (idx, grapheme) in text.grapheme_indices(true) {
+        if skipped >= cells {
+            return Cow::Borrowed(&text[idx..]);
+        }
+        skipped += grapheme.width();
+        if skipped > cells {
+            let rest = &text[idx + grapheme.len()..];
+            return Cow::Owned(format!("{}{}", " ".repeat(skipped - cells), rest));
+        }
+    }
+    Cow::Borrowed("")
+}
+
+/// `text` truncated or padded with spaces on the right to exactly `cells`
+pub fn pad_right(text: &str, cells: usize) -> String {
+    let text = truncate(text, cells);
+    let padding = cells - width(&text);
+    format!("{}{}", text, " ".repeat(padding))
+}
+
+/// `text` truncated or padded with spaces on the left to exactly `cells`
+pub fn pad_left(text: &str, cells: usize) -> String {
+    let text = truncate(text, cells);
+    let padding = cells - width(&text);
+    format!("{}{}", " ".repeat(padding), text)
+}
+
+/// `text` truncated or centered in exactly `cells`, an odd space goes right
+pub fn center(text: &str, cells: usize) -> String {
+    let text = truncate(text, cells);
+    let padding = cells - width(&text);
+    format!(
+        "{}{}{}",
+        " ".repeat(padding / 2),
+        text,
+        " ".repeat(padding - padding / 2)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Family: man, woman, girl joined by zero width joiners
+    const FAMILY: &str = "👨\u{200d}👩\u{200d}👧";
+    /// "é" as "e" and a combining acute accent
+    const E_ACUTE: &str = "e\u{301}";
+
+    #[test]
+    fn test_width() {
+        assert_eq!(width("merge"), 5);
+        assert_eq!(width("🚀🔥"), 4);
+        assert_eq!(width("漢字"), 4);
+        assert_eq!(width(FAMILY), 2);
+        assert_eq!(width(E_ACUTE), 1);
+        assert_eq!(width(&format!("caf{}", E_ACUTE)), 4);
+    }
+
+    #[test]
+    fn test_truncate_keeps_grapheme_clusters() {
+        assert_eq!(truncate("fits", 4), "fits");
+        assert_eq!(truncate("too long", 4), "too…");
+        assert_eq!(truncate("too long", 0), "");
+
+        // The ellipsis counts, a wide grapheme left over is dropped whole
+        assert_eq!(truncate("🚀🔥 Launch", 5), "🚀🔥…");
+        assert_eq!(truncate("🚀🔥 Launch", 4), "🚀…");
+        assert_eq!(truncate("漢字テスト", 6), "漢字…");
+        assert_eq!(width(&truncate("漢字テスト", 6)), 5);
+        assert_eq!(
+            truncate(&format!("{}{} family", FAMILY, FAMILY), 4),
+            format!("{}…", FAMILY)
+        );
+
+        // The accent stays on its letter
+        let cafe = format!("caf{} au lait", E_ACUTE);
+        assert_eq!(truncate(&cafe, 5), format!("caf{}…", E_ACUTE));
+    }
+
+    #[test]
+    fn test_clip_end() {
+        assert_eq!(clip_end("factory.java", 4), "java");
+        assert_eq!(clip_end("fits", 9), "fits");
+        // A wide grapheme that does not fit is left out whole
+        assert_eq!(clip_end("漢字テスト", 3), "ト");
+        assert_eq!(clip_end(&format!("x{}", FAMILY), 2), FAMILY);
+    }
+
+    #[test]
+    fn test_skip() {
+        assert_eq!(skip("scroll", 0), "scroll");
+        assert_eq!(skip("scroll", 2), "roll");
+        assert_eq!(skip("scroll", 9), "");
+        assert_eq!(skip("漢字テスト", 2), "字テスト");
+        // Half of 漢 scrolled out, its other half is blank
+        assert_eq!(skip("漢字テスト", 1), " 字テスト");
+        assert_eq!(skip(&format!("{}x", E_ACUTE), 1), "x");
+    }
+
+    #[test]
+    fn test_padding_by_cells() {
+        assert_eq!(pad_right("漢字", 6), "漢字  ");
+        assert_eq!(pad_left("🔥", 4), "  🔥");
+        assert_eq!(center("✅", 6), "  ✅  ");
+        assert_eq!(center("XL", 5), " XL  ");
+        assert_eq!(pad_right("漢字テスト", 6), "漢字… ");
+        for text in ["🚀🔥 Launch", "漢字テスト", FAMILY, E_ACUTE, "plain"] {
+            for cells in 0..8 {
+                assert_eq!(
+                    width(&pad_right(text, cells)),
+                    cells,
+                    "{:?} {}",
+                    text,
+                    cells
+                );
+                assert_eq!(width(&center(text, cells)), cells, "{:?} {}", text, cells);
+            }
+        }
+    }
+}